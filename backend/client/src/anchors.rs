@@ -0,0 +1,15 @@
+use reqwest::Method;
+
+use crate::models::AnchorsResponse;
+use crate::{Client, Result};
+
+impl Client {
+    /// GET /api/anchors - A page of anchors with their metrics
+    pub async fn list_anchors(&self, limit: i64, offset: i64) -> Result<AnchorsResponse> {
+        Self::send(
+            self.request(Method::GET, "/api/anchors")
+                .query(&[("limit", limit), ("offset", offset)]),
+        )
+        .await
+    }
+}
@@ -0,0 +1,96 @@
+use reqwest::Method;
+use serde::Serialize;
+use stellar_insights_core::CorridorKey;
+
+use crate::models::{
+    CohortRetentionResponse, CorridorDetailResponse, CorridorResponse, HealthHistoryResponse,
+    SeasonalityResponse,
+};
+use crate::{Client, Result};
+
+/// Filters for [`Client::list_corridors`]; unset fields use the API defaults
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CorridorQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    /// `success_rate` or `volume`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success_rate_min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success_rate_max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_code: Option<String>,
+    /// e.g. `24h`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_period: Option<String>,
+    /// Least trusted metric source to include, e.g. `horizon`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_source: Option<String>,
+}
+
+impl Client {
+    /// GET /api/corridors
+    pub async fn list_corridors(&self, query: &CorridorQuery) -> Result<Vec<CorridorResponse>> {
+        Self::send(self.request(Method::GET, "/api/corridors").query(query)).await
+    }
+
+    /// GET /api/corridors/{corridor_key} - Metrics, history, order book
+    /// depth, recent trades and anchors of one corridor
+    pub async fn get_corridor(&self, key: &CorridorKey) -> Result<CorridorDetailResponse> {
+        Self::send(self.request(Method::GET, &format!("/api/corridors/{}", key))).await
+    }
+
+    /// GET /api/corridors/{corridor_key}/seasonality - Trend, weekday and
+    /// hour-of-day components of hourly volume over `period` (`30d` or `90d`,
+    /// default 30 days)
+    pub async fn corridor_seasonality(
+        &self,
+        key: &CorridorKey,
+        period: Option<&str>,
+    ) -> Result<SeasonalityResponse> {
+        let mut request = self.request(Method::GET, &format!("/api/corridors/{}/seasonality", key));
+        if let Some(period) = period {
+            request = request.query(&[("period", period)]);
+        }
+        Self::send(request).await
+    }
+
+    /// GET /api/corridors/{corridor_key}/cohorts - Monthly retention of the
+    /// corridor's sending accounts over the last `months` cohorts (default 12)
+    pub async fn corridor_cohorts(
+        &self,
+        key: &CorridorKey,
+        months: Option<u32>,
+    ) -> Result<CohortRetentionResponse> {
+        let mut request = self.request(Method::GET, &format!("/api/corridors/{}/cohorts", key));
+        if let Some(months) = months {
+            request = request.query(&[("months", months)]);
+        }
+        Self::send(request).await
+    }
+
+    /// GET /api/corridors/{corridor_key}/health-history - The corridor's
+    /// health scores over the last `days` days (default 30)
+    pub async fn corridor_health_history(
+        &self,
+        key: &CorridorKey,
+        days: Option<i64>,
+    ) -> Result<HealthHistoryResponse> {
+        let mut request = self.request(
+            Method::GET,
+            &format!("/api/corridors/{}/health-history", key),
+        );
+        if let Some(days) = days {
+            request = request.query(&[("days", days)]);
+        }
+        Self::send(request).await
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The request couldn't be sent or its response read
+    Http(reqwest::Error),
+    /// The API answered with a non-success status
+    Api {
+        status: u16,
+        /// Machine-readable code, e.g. `CORRIDOR_NOT_FOUND`, when the
+        /// endpoint returns structured errors
+        code: Option<String>,
+        message: String,
+    },
+    /// A response body or WebSocket message didn't match its model
+    Decode(serde_json::Error),
+    WebSocket(tokio_tungstenite::tungstenite::Error),
+}
+
+impl Error {
+    /// Read either error body the API returns: `{"error": {"code", "message"}}`
+    /// or `{"error": "message"}`
+    pub(crate) fn from_response(status: u16, body: &[u8]) -> Self {
+        let error = serde_json::from_slice::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v.get("error").cloned());
+        let (code, message) = match error {
+            Some(serde_json::Value::Object(error)) => (
+                error.get("code").and_then(|c| c.as_str()).map(String::from),
+                error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            Some(serde_json::Value::String(message)) => (None, message),
+            _ => (None, String::from_utf8_lossy(body).into_owned()),
+        };
+        Self::Api {
+            status,
+            code,
+            message,
+        }
+    }
+
+    /// HTTP status of an API error
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            Self::Http(e) => e.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(e) => write!(f, "HTTP error: {}", e),
+            Self::Api {
+                status,
+                code: Some(code),
+                message,
+            } => write!(f, "API error {} {}: {}", status, code, message),
+            Self::Api {
+                status, message, ..
+            } => write!(f, "API error {}: {}", status, message),
+            Self::Decode(e) => write!(f, "Unexpected response: {}", e),
+            Self::WebSocket(e) => write!(f, "WebSocket error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            Self::WebSocket(e) => Some(e),
+            Self::Api { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Http(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Decode(e)
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::WebSocket(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_both_error_shapes() {
+        let structured = Error::from_response(
+            404,
+            br#"{"error": {"code": "CORRIDOR_NOT_FOUND", "message": "No such corridor"}}"#,
+        );
+        assert!(matches!(
+            structured,
+            Error::Api { status: 404, code: Some(ref c), ref message }
+                if c == "CORRIDOR_NOT_FOUND" && message == "No such corridor"
+        ));
+
+        let plain = Error::from_response(400, br#"{"error": "Webhook URL must be valid HTTP(S)"}"#);
+        assert_eq!(
+            plain.to_string(),
+            "API error 400: Webhook URL must be valid HTTP(S)"
+        );
+
+        let html = Error::from_response(502, b"Bad Gateway");
+        assert_eq!(html.status(), Some(502));
+        assert_eq!(html.to_string(), "API error 502: Bad Gateway");
+    }
+}
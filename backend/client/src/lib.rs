@@ -0,0 +1,111 @@
+//! Typed async client for the Stellar Insights API.
+//!
+//! Covers the public anchor, corridor and snapshot endpoints, webhook
+//! management (which needs a bearer token) and `/ws` subscriptions. Models
+//! mirror the schemas in the backend's OpenAPI spec, and the backend's test
+//! suite fails when [`ENDPOINTS`] or a model drifts from it.
+//!
+//! ```no_run
+//! # async fn run() -> stellar_insights_client::Result<()> {
+//! use stellar_insights_client::{Client, CorridorQuery};
+//!
+//! let client = Client::new("https://api.stellarinsights.io");
+//! let corridors = client.list_corridors(&CorridorQuery::default()).await?;
+//! let mut updates = client.subscribe(&[format!("corridor:{}", corridors[0].id)]).await?;
+//! while let Some(message) = updates.next().await {
+//!     println!("{:?}", message?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod anchors;
+mod corridors;
+pub mod error;
+pub mod models;
+mod snapshots;
+mod webhooks;
+pub mod ws;
+
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+
+pub use corridors::CorridorQuery;
+pub use error::{Error, Result};
+pub use stellar_insights_core::{CorridorKey, WsMessage};
+pub use ws::Subscription;
+
+/// Every HTTP operation this client calls, as (method, OpenAPI path)
+pub const ENDPOINTS: &[(&str, &str)] = &[
+    ("get", "/api/anchors"),
+    ("get", "/api/corridors"),
+    ("get", "/api/corridors/{corridor_key}"),
+    ("get", "/api/corridors/{corridor_key}/seasonality"),
+    ("get", "/api/corridors/{corridor_key}/cohorts"),
+    ("get", "/api/corridors/{corridor_key}/health-history"),
+    ("get", "/api/snapshots/signing-key"),
+    ("get", "/api/snapshots/{epoch}/signature"),
+    ("get", "/api/snapshots/{epoch}/sla"),
+    ("get", "/api/snapshots/{epoch}/sla/verify"),
+    ("get", "/api/webhooks"),
+    ("post", "/api/webhooks"),
+    ("delete", "/api/webhooks/{id}"),
+    ("post", "/api/webhooks/{id}/rotate-secret"),
+    ("post", "/api/webhooks/{id}/test"),
+];
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl Client {
+    /// Client for the API served at `base_url`, e.g. `https://api.stellarinsights.io`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// JWT sent as a bearer token, required for webhook management
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Use a preconfigured HTTP client (timeouts, proxies, TLS roots)
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let builder = self
+            .http
+            .request(method, format!("{}{}", self.base_url, path));
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(builder: RequestBuilder) -> Result<T> {
+        let body = Self::send_raw(builder).await?;
+        serde_json::from_slice(&body).map_err(Error::Decode)
+    }
+
+    /// Response body of a successful request
+    async fn send_raw(builder: RequestBuilder) -> Result<Vec<u8>> {
+        let response = builder.send().await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+        if !status.is_success() {
+            return Err(Error::from_response(status.as_u16(), &body));
+        }
+        Ok(body.to_vec())
+    }
+}
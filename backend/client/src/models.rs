@@ -0,0 +1,342 @@
+//! Request and response bodies, named after their OpenAPI schemas.
+
+use serde::{Deserialize, Serialize};
+use stellar_insights_core::snapshot::SnapshotSlaSummary;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnchorsResponse {
+    pub anchors: Vec<AnchorMetricsResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnchorMetricsResponse {
+    pub id: String,
+    pub name: String,
+    pub stellar_account: String,
+    /// 0-100
+    pub reliability_score: f64,
+    pub asset_coverage: usize,
+    /// Percentage of failed transactions
+    pub failure_rate: f64,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    /// `green`, `yellow` or `red`
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorridorResponse {
+    /// Canonical corridor key
+    pub id: String,
+    pub source_asset: String,
+    pub destination_asset: String,
+    pub success_rate: f64,
+    pub total_attempts: i64,
+    pub successful_payments: i64,
+    pub failed_payments: i64,
+    pub average_latency_ms: f64,
+    pub median_latency_ms: f64,
+    pub p95_latency_ms: f64,
+    pub p99_latency_ms: f64,
+    pub liquidity_depth_usd: f64,
+    pub liquidity_volume_24h_usd: f64,
+    pub liquidity_trend: String,
+    pub health_score: f64,
+    pub last_updated: String,
+    /// `on_chain`, `horizon`, `derived` or `self_reported`
+    #[serde(default)]
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorridorDetailResponse {
+    pub corridor: CorridorResponse,
+    pub historical_success_rate: Vec<SuccessRateDataPoint>,
+    pub latency_distribution: Vec<LatencyDataPoint>,
+    pub liquidity_trends: Vec<LiquidityDataPoint>,
+    pub related_corridors: Option<Vec<CorridorResponse>>,
+    pub order_book_depth_usd: Option<f64>,
+    pub recent_trades: Option<RecentTradesSummary>,
+    pub anchors: Option<Vec<CorridorAnchor>>,
+    /// Daily windows over the last 30 days, oldest first
+    pub concentration: Option<Vec<ConcentrationPoint>>,
+    /// Over the last 30 days
+    pub settlement_times: Option<SettlementTimes>,
+    /// Parts that failed or timed out and were left out
+    #[serde(default)]
+    pub unavailable: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SuccessRateDataPoint {
+    pub timestamp: String,
+    pub success_rate: f64,
+    pub attempts: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyDataPoint {
+    pub latency_bucket_ms: i32,
+    pub count: i64,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityDataPoint {
+    pub timestamp: String,
+    pub liquidity_usd: f64,
+    pub volume_24h_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecentTradesSummary {
+    pub trade_count: i64,
+    /// In units of the corridor's source asset
+    pub volume: f64,
+    pub last_trade_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorridorAnchor {
+    pub id: String,
+    pub name: String,
+    pub asset_code: String,
+}
+
+/// Herfindahl–Hirschman index (0 to 10,000) of volume across accounts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConcentrationPoint {
+    pub window_start: String,
+    pub window_end: String,
+    /// `unconcentrated`, `moderate` or `high`
+    pub level: String,
+    pub sender_hhi: Option<f64>,
+    pub senders: i64,
+    pub top_sender_share: Option<f64>,
+    pub receiver_hhi: Option<f64>,
+    pub receivers: i64,
+    pub top_receiver_share: Option<f64>,
+    pub market_maker_hhi: Option<f64>,
+    pub market_makers: i64,
+    pub top_market_maker_share: Option<f64>,
+}
+
+/// Path payment settlement time percentiles
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SettlementTimes {
+    pub payments: i64,
+    /// Share settled after a failed attempt, 0 to 1
+    pub retried_share: f64,
+    pub p50_ms: i64,
+    pub p90_ms: i64,
+    pub p95_ms: i64,
+    pub p99_ms: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalityResponse {
+    pub corridor_key: String,
+    pub from: String,
+    pub to: String,
+    pub daily_profile: Vec<HourOfDayEffect>,
+    pub weekly_profile: Vec<WeekdayEffect>,
+    pub residual_std_usd: f64,
+    pub latest: Option<LatestHour>,
+    /// Hourly volume and its components, oldest first
+    pub points: Vec<SeasonalityPoint>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HourOfDayEffect {
+    /// Hour of the day (UTC), 0-23
+    pub hour: u32,
+    pub effect_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeekdayEffect {
+    /// `monday` to `sunday`
+    pub weekday: String,
+    pub effect_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalityPoint {
+    pub hour: String,
+    pub volume_usd: f64,
+    pub trend_usd: f64,
+    pub weekly_usd: f64,
+    pub daily_usd: f64,
+    pub residual_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatestHour {
+    pub hour: String,
+    pub weekday: String,
+    pub volume_usd: f64,
+    /// Volume the trend and seasonal pattern predict
+    pub expected_usd: f64,
+    /// Residual in standard deviations
+    pub z_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CohortRetentionResponse {
+    pub corridor_key: String,
+    /// Oldest cohort first
+    pub cohorts: Vec<Cohort>,
+    /// Retention by months since the first payment, weighted by cohort size
+    pub average_retention: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cohort {
+    /// `YYYY-MM` of the accounts' first payment in the corridor
+    pub month: String,
+    pub accounts: i64,
+    /// Active accounts in the cohort month and each month after it
+    pub active_accounts: Vec<i64>,
+    pub retention: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HealthHistoryResponse {
+    pub corridor_key: String,
+    /// Oldest first
+    pub history: Vec<HealthPoint>,
+}
+
+/// A health score (0-100) and the components it was weighted from; a
+/// component is absent when the corridor had no data for it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HealthPoint {
+    pub computed_at: String,
+    pub score: f64,
+    pub success_rate: Option<f64>,
+    pub liquidity: Option<f64>,
+    pub volume_trend: Option<f64>,
+    pub anchor_reliability: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SigningKeyResponse {
+    pub algorithm: String,
+    /// Hex-encoded ed25519 public key
+    pub public_key: String,
+    /// The same key as a Stellar G-address
+    pub stellar_address: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotSignatureResponse {
+    pub algorithm: String,
+    pub epoch: i64,
+    /// Hex SHA-256 hash of the canonical snapshot JSON
+    pub hash: String,
+    /// Base64 ed25519 signature of the hash
+    pub signature: String,
+    /// Hex ed25519 public key
+    pub signing_key: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OnChainSla {
+    /// Ledger timestamp of the submission
+    pub timestamp: u64,
+    pub snapshot_hash_matches: bool,
+    /// Hex SLA hash recorded by the contract
+    pub sla_hash: Option<String>,
+    pub sla_hash_matches: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotSlaResponse {
+    pub epoch: u64,
+    pub schema_version: u32,
+    pub snapshot_timestamp: String,
+    /// Hex SHA-256 hash of the canonical snapshot JSON
+    pub snapshot_hash: String,
+    /// Hex SHA-256 hash of the canonical `sla_summaries` array
+    pub sla_hash: Option<String>,
+    pub summaries: Vec<SnapshotSlaSummary>,
+    /// `None` when the epoch is not on-chain
+    pub on_chain: Option<OnChainSla>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerifySlaResponse {
+    /// Figures match and the anchored hashes match the chain
+    pub verified: bool,
+    pub figures_match: bool,
+    pub epoch: u64,
+    pub snapshot_timestamp: String,
+    pub snapshot_hash: String,
+    pub sla_hash: Option<String>,
+    pub anchored: SnapshotSlaSummary,
+    pub on_chain: Option<OnChainSla>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    /// Public HTTPS endpoint events are POSTed to
+    pub url: String,
+    /// e.g. `corridor.health_degraded`
+    pub event_types: Vec<String>,
+    pub filters: Option<serde_json::Value>,
+    #[serde(default)]
+    pub delivery: DeliverySettings,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub filters: Option<serde_json::Value>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub delivery: DeliverySettings,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RotateSecretRequest {
+    /// Hours deliveries stay signed with the old secret as well (default 24)
+    pub grace_period_hours: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RotateSecretResponse {
+    pub id: String,
+    /// The new signing secret; it is not shown again
+    pub secret: String,
+    pub previous_secret_expires_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliverySettings {
+    /// Events combined into one POST (1 disables batching)
+    pub max_batch_size: u32,
+    /// Seconds to wait for a batch to fill before sending a partial one
+    pub batch_window_secs: u32,
+    /// Maximum POSTs per minute (`None` = unlimited)
+    pub max_deliveries_per_minute: Option<u32>,
+}
+
+impl Default for DeliverySettings {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 1,
+            batch_window_secs: 0,
+            max_deliveries_per_minute: None,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use reqwest::Method;
+
+use crate::models::{
+    SigningKeyResponse, SnapshotSignatureResponse, SnapshotSlaResponse, VerifySlaResponse,
+};
+use crate::{Client, Result};
+
+impl Client {
+    /// GET /api/snapshots/signing-key - The key snapshots are currently signed with
+    pub async fn snapshot_signing_key(&self) -> Result<SigningKeyResponse> {
+        Self::send(self.request(Method::GET, "/api/snapshots/signing-key")).await
+    }
+
+    /// GET /api/snapshots/{epoch}/signature - Signature over an epoch's snapshot hash
+    pub async fn snapshot_signature(&self, epoch: u64) -> Result<SnapshotSignatureResponse> {
+        Self::send(self.request(Method::GET, &format!("/api/snapshots/{}/signature", epoch))).await
+    }
+
+    /// GET /api/snapshots/{epoch}/sla - SLA summaries anchored with an epoch's snapshot
+    pub async fn snapshot_sla(&self, epoch: u64) -> Result<SnapshotSlaResponse> {
+        Self::send(self.request(Method::GET, &format!("/api/snapshots/{}/sla", epoch))).await
+    }
+
+    /// GET /api/snapshots/{epoch}/sla/verify - Check reported SLA figures
+    /// against those anchored for a corridor at `epoch`
+    pub async fn verify_snapshot_sla(
+        &self,
+        epoch: u64,
+        corridor_key: &str,
+        uptime_percent: f64,
+        success_rate: Option<f64>,
+    ) -> Result<VerifySlaResponse> {
+        let mut query = vec![
+            ("corridor_key", corridor_key.to_string()),
+            ("uptime_percent", uptime_percent.to_string()),
+        ];
+        if let Some(success_rate) = success_rate {
+            query.push(("success_rate", success_rate.to_string()));
+        }
+        Self::send(
+            self.request(Method::GET, &format!("/api/snapshots/{}/sla/verify", epoch))
+                .query(&query),
+        )
+        .await
+    }
+}
@@ -0,0 +1,49 @@
+//! Webhook management. Every call needs [`Client::with_token`].
+
+use reqwest::Method;
+
+use crate::models::{
+    CreateWebhookRequest, RotateSecretRequest, RotateSecretResponse, WebhookListResponse,
+    WebhookResponse,
+};
+use crate::{Client, Result};
+
+impl Client {
+    /// POST /api/webhooks - Register a webhook for the token's user
+    pub async fn create_webhook(&self, request: &CreateWebhookRequest) -> Result<WebhookResponse> {
+        Self::send(self.request(Method::POST, "/api/webhooks").json(request)).await
+    }
+
+    /// GET /api/webhooks
+    pub async fn list_webhooks(&self) -> Result<Vec<WebhookResponse>> {
+        let list: WebhookListResponse =
+            Self::send(self.request(Method::GET, "/api/webhooks")).await?;
+        Ok(list.webhooks)
+    }
+
+    /// DELETE /api/webhooks/{id}
+    pub async fn delete_webhook(&self, id: &str) -> Result<()> {
+        Self::send_raw(self.request(Method::DELETE, &format!("/api/webhooks/{}", id))).await?;
+        Ok(())
+    }
+
+    /// POST /api/webhooks/{id}/rotate-secret - Replace the signing secret;
+    /// the old one keeps signing deliveries through the grace period
+    pub async fn rotate_webhook_secret(
+        &self,
+        id: &str,
+        request: &RotateSecretRequest,
+    ) -> Result<RotateSecretResponse> {
+        Self::send(
+            self.request(Method::POST, &format!("/api/webhooks/{}/rotate-secret", id))
+                .json(request),
+        )
+        .await
+    }
+
+    /// POST /api/webhooks/{id}/test - Prepare a test delivery; the response
+    /// carries its `payload`
+    pub async fn test_webhook(&self, id: &str) -> Result<serde_json::Value> {
+        Self::send(self.request(Method::POST, &format!("/api/webhooks/{}/test", id))).await
+    }
+}
@@ -0,0 +1,110 @@
+//! `/ws` subscriptions.
+//!
+//! Channels include `corridor:<key>`, `anchor:<id>`, `sep_transaction:<id>` and
+//! `movers`; see the backend's WEBSOCKET_API.md. Corridor channels accept
+//! any spelling of the key and are canonicalized by the server.
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::{Client, Result, WsMessage};
+
+impl Client {
+    /// Connect to `/ws` and subscribe to `channels`
+    pub async fn subscribe(&self, channels: &[String]) -> Result<Subscription> {
+        let (stream, _) = connect_async(self.ws_url()).await?;
+        let mut subscription = Subscription { stream };
+        subscription.subscribe(channels).await?;
+        Ok(subscription)
+    }
+
+    fn ws_url(&self) -> String {
+        let base = match self.base_url.split_once("://") {
+            Some(("https", rest)) => format!("wss://{}", rest),
+            Some((_, rest)) => format!("ws://{}", rest),
+            None => format!("ws://{}", self.base_url),
+        };
+        match &self.token {
+            Some(token) => format!("{}/ws?token={}", base, token),
+            None => format!("{}/ws", base),
+        }
+    }
+}
+
+/// An open `/ws` connection
+pub struct Subscription {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl Subscription {
+    /// Next message from the server, `None` once the connection closes.
+    /// Heartbeat pings are answered here and not returned.
+    pub async fn next(&mut self) -> Option<Result<WsMessage>> {
+        loop {
+            let text = match self.stream.next().await? {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => return None,
+                Ok(_) => continue,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let message = match serde_json::from_str::<WsMessage>(&text) {
+                Ok(message) => message,
+                Err(e) => return Some(Err(e.into())),
+            };
+            if let WsMessage::Ping { timestamp } = message {
+                if let Err(e) = self.send(&WsMessage::Pong { timestamp }).await {
+                    return Some(Err(e));
+                }
+                continue;
+            }
+            return Some(Ok(message));
+        }
+    }
+
+    /// Add channels; the server answers with a `SubscriptionConfirm`
+    pub async fn subscribe(&mut self, channels: &[String]) -> Result<()> {
+        self.send(&WsMessage::Subscribe {
+            channels: channels.to_vec(),
+        })
+        .await
+    }
+
+    pub async fn unsubscribe(&mut self, channels: &[String]) -> Result<()> {
+        self.send(&WsMessage::Unsubscribe {
+            channels: channels.to_vec(),
+        })
+        .await
+    }
+
+    pub async fn close(mut self) -> Result<()> {
+        self.stream.close(None).await?;
+        Ok(())
+    }
+
+    async fn send(&mut self, message: &WsMessage) -> Result<()> {
+        let text = serde_json::to_string(message)?;
+        self.stream.send(Message::Text(text)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ws_url() {
+        assert_eq!(
+            Client::new("https://api.stellarinsights.io/").ws_url(),
+            "wss://api.stellarinsights.io/ws"
+        );
+        assert_eq!(
+            Client::new("http://localhost:8080")
+                .with_token("abc")
+                .ws_url(),
+            "ws://localhost:8080/ws?token=abc"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Bindings for `contracts/analytics`, one method per contract function.
+
+use std::collections::BTreeMap;
+use stellar_xdr::curr::{ScAddress, ScMap, ScVal};
+
+use crate::{parse_address, Call, FromScVal, Result};
+
+/// `SnapshotMetadata` as stored by the contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotMetadata {
+    pub epoch: u64,
+    /// Ledger timestamp of the submission
+    pub timestamp: u64,
+    pub hash: [u8; 32],
+    pub submitter: ScAddress,
+}
+
+impl SnapshotMetadata {
+    /// Whether the on-chain hash matches a locally computed snapshot hash
+    pub fn matches(&self, hash: &[u8; 32]) -> bool {
+        &self.hash == hash
+    }
+}
+
+/// `#[contracttype]` structs arrive as maps keyed by field name
+impl FromScVal for SnapshotMetadata {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        let ScVal::Map(Some(map)) = value else {
+            return Err("expected a SnapshotMetadata struct".to_string());
+        };
+        Ok(Self {
+            epoch: u64::from_scval(field(&map, "epoch")?)?,
+            timestamp: u64::from_scval(field(&map, "timestamp")?)?,
+            hash: <[u8; 32]>::from_scval(field(&map, "hash")?)?,
+            submitter: ScAddress::from_scval(field(&map, "submitter")?)?,
+        })
+    }
+}
+
+/// `Map<u64, SnapshotMetadata>` from `get_snapshot_history`
+impl FromScVal for BTreeMap<u64, SnapshotMetadata> {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        let ScVal::Map(Some(map)) = value else {
+            return Err("expected a map of epochs to snapshots".to_string());
+        };
+        map.0
+            .into_vec()
+            .into_iter()
+            .map(|entry| {
+                Ok((
+                    u64::from_scval(entry.key)?,
+                    SnapshotMetadata::from_scval(entry.val)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+fn field(map: &ScMap, name: &str) -> std::result::Result<ScVal, String> {
+    map.0
+        .iter()
+        .find(|entry| match &entry.key {
+            ScVal::Symbol(sym) => sym.0.as_slice() == name.as_bytes(),
+            _ => false,
+        })
+        .map(|entry| entry.val.clone())
+        .ok_or_else(|| format!("missing field `{}`", name))
+}
+
+fn address(address: &ScAddress) -> ScVal {
+    ScVal::Address(address.clone())
+}
+
+/// A deployed analytics contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyticsContract {
+    address: ScAddress,
+}
+
+impl AnalyticsContract {
+    /// Bindings for the contract with the given `C...` ID
+    pub fn new(contract_id: &str) -> Result<Self> {
+        Ok(Self::from_address(parse_address(contract_id)?))
+    }
+
+    pub fn from_address(address: ScAddress) -> Self {
+        Self { address }
+    }
+
+    pub fn address(&self) -> &ScAddress {
+        &self.address
+    }
+
+    pub fn initialize(&self, admin: &ScAddress) -> Call<()> {
+        Call::new(&self.address, "initialize", vec![address(admin)])
+    }
+
+    /// Like [`initialize`](Self::initialize), keeping at most
+    /// `max_history_entries` snapshots in the history map and archiving older ones
+    pub fn initialize_with_retention(
+        &self,
+        admin: &ScAddress,
+        max_history_entries: u32,
+    ) -> Call<()> {
+        Call::new(
+            &self.address,
+            "initialize_with_retention",
+            vec![address(admin), max_history_entries.into()],
+        )
+    }
+
+    /// `None` when history is unlimited
+    pub fn get_max_history_entries(&self) -> Call<Option<u32>> {
+        Call::new(&self.address, "get_max_history_entries", vec![])
+    }
+
+    /// Delete snapshots older than `before_epoch`; `caller` must be the admin.
+    /// Returns how many were deleted.
+    pub fn prune_history(&self, before_epoch: u64, caller: &ScAddress) -> Call<u32> {
+        Call::new(
+            &self.address,
+            "prune_history",
+            vec![before_epoch.into(), address(caller)],
+        )
+    }
+
+    /// Record `hash` for `epoch`; `caller` must be the admin and authorize
+    /// the call. Returns the ledger timestamp of the submission.
+    pub fn submit_snapshot(&self, epoch: u64, hash: [u8; 32], caller: &ScAddress) -> Call<u64> {
+        let hash = ScVal::try_from(hash).expect("32 bytes fit in ScBytes");
+        Call::new(
+            &self.address,
+            "submit_snapshot",
+            vec![epoch.into(), hash, address(caller)],
+        )
+    }
+
+    /// Like [`submit_snapshot`](Self::submit_snapshot), also recording the
+    /// hash of the snapshot's SLA section
+    pub fn submit_snapshot_with_sla(
+        &self,
+        epoch: u64,
+        hash: [u8; 32],
+        sla_hash: [u8; 32],
+        caller: &ScAddress,
+    ) -> Call<u64> {
+        let hash = ScVal::try_from(hash).expect("32 bytes fit in ScBytes");
+        let sla_hash = ScVal::try_from(sla_hash).expect("32 bytes fit in ScBytes");
+        Call::new(
+            &self.address,
+            "submit_snapshot_with_sla",
+            vec![epoch.into(), hash, sla_hash, address(caller)],
+        )
+    }
+
+    /// SLA section hash of an epoch submitted with `submit_snapshot_with_sla`
+    pub fn get_sla_hash(&self, epoch: u64) -> Call<Option<[u8; 32]>> {
+        Call::new(&self.address, "get_sla_hash", vec![epoch.into()])
+    }
+
+    /// Whether the SHA-256 of `payload` is the hash stored for `epoch`
+    pub fn verify_snapshot(&self, epoch: u64, payload: &[u8]) -> Call<bool> {
+        let payload = ScVal::try_from(payload).expect("payload fits in ScBytes");
+        Call::new(
+            &self.address,
+            "verify_snapshot",
+            vec![epoch.into(), payload],
+        )
+    }
+
+    pub fn get_snapshot(&self, epoch: u64) -> Call<Option<SnapshotMetadata>> {
+        Call::new(&self.address, "get_snapshot", vec![epoch.into()])
+    }
+
+    /// Keep `epoch`'s entries alive for at least `ledgers` more ledgers
+    pub fn extend_snapshot_ttl(&self, epoch: u64, ledgers: u32) -> Call<()> {
+        Call::new(
+            &self.address,
+            "extend_snapshot_ttl",
+            vec![epoch.into(), ledgers.into()],
+        )
+    }
+
+    pub fn get_latest_snapshot(&self) -> Call<Option<SnapshotMetadata>> {
+        Call::new(&self.address, "get_latest_snapshot", vec![])
+    }
+
+    pub fn get_snapshot_history(&self) -> Call<BTreeMap<u64, SnapshotMetadata>> {
+        Call::new(&self.address, "get_snapshot_history", vec![])
+    }
+
+    /// Snapshots submitted between two ledger timestamps, at most
+    /// `MAX_SNAPSHOTS_PER_QUERY` (50) of them
+    pub fn get_snapshots_between(&self, ts_start: u64, ts_end: u64) -> Call<Vec<SnapshotMetadata>> {
+        Call::new(
+            &self.address,
+            "get_snapshots_between",
+            vec![ts_start.into(), ts_end.into()],
+        )
+    }
+
+    /// Up to `limit` snapshots from `start_epoch` on, at most
+    /// `MAX_SNAPSHOTS_PER_QUERY` (50) of them
+    pub fn get_snapshot_page(&self, start_epoch: u64, limit: u32) -> Call<Vec<SnapshotMetadata>> {
+        Call::new(
+            &self.address,
+            "get_snapshot_page",
+            vec![start_epoch.into(), limit.into()],
+        )
+    }
+
+    /// The `n` most recent snapshots, newest first, at most
+    /// `MAX_SNAPSHOTS_PER_QUERY` (50) of them
+    pub fn get_recent_snapshots(&self, n: u32) -> Call<Vec<SnapshotMetadata>> {
+        Call::new(&self.address, "get_recent_snapshots", vec![n.into()])
+    }
+
+    pub fn get_snapshot_count(&self) -> Call<u32> {
+        Call::new(&self.address, "get_snapshot_count", vec![])
+    }
+
+    pub fn get_latest_epoch(&self) -> Call<u64> {
+        Call::new(&self.address, "get_latest_epoch", vec![])
+    }
+
+    pub fn get_all_epochs(&self) -> Call<Vec<u64>> {
+        Call::new(&self.address, "get_all_epochs", vec![])
+    }
+
+    /// 1 until `migrate_v1_to_v2` has moved every legacy snapshot
+    pub fn get_storage_version(&self) -> Call<u32> {
+        Call::new(&self.address, "get_storage_version", vec![])
+    }
+
+    /// Move up to `limit` legacy snapshots to per-epoch storage; `caller`
+    /// must be the admin. Returns how many are left to move.
+    pub fn migrate_v1_to_v2(&self, caller: &ScAddress, limit: u32) -> Call<u32> {
+        Call::new(
+            &self.address,
+            "migrate_v1_to_v2",
+            vec![address(caller), limit.into()],
+        )
+    }
+
+    pub fn get_admin(&self) -> Call<Option<ScAddress>> {
+        Call::new(&self.address, "get_admin", vec![])
+    }
+
+    pub fn set_admin(&self, current_admin: &ScAddress, new_admin: &ScAddress) -> Call<()> {
+        Call::new(
+            &self.address,
+            "set_admin",
+            vec![address(current_admin), address(new_admin)],
+        )
+    }
+
+    pub fn add_submitter(&self, caller: &ScAddress, submitter: &ScAddress) -> Call<()> {
+        Call::new(
+            &self.address,
+            "add_submitter",
+            vec![address(caller), address(submitter)],
+        )
+    }
+
+    pub fn remove_submitter(&self, caller: &ScAddress, submitter: &ScAddress) -> Call<()> {
+        Call::new(
+            &self.address,
+            "remove_submitter",
+            vec![address(caller), address(submitter)],
+        )
+    }
+
+    pub fn list_submitters(&self) -> Call<Vec<ScAddress>> {
+        Call::new(&self.address, "list_submitters", vec![])
+    }
+
+    pub fn pause(&self, caller: &ScAddress) -> Call<()> {
+        Call::new(&self.address, "pause", vec![address(caller)])
+    }
+
+    pub fn unpause(&self, caller: &ScAddress) -> Call<()> {
+        Call::new(&self.address, "unpause", vec![address(caller)])
+    }
+
+    pub fn is_paused(&self) -> Call<bool> {
+        Call::new(&self.address, "is_paused", vec![])
+    }
+
+    pub fn set_governance(&self, caller: &ScAddress, governance: &ScAddress) -> Call<()> {
+        Call::new(
+            &self.address,
+            "set_governance",
+            vec![address(caller), address(governance)],
+        )
+    }
+
+    pub fn get_governance(&self) -> Call<Option<ScAddress>> {
+        Call::new(&self.address, "get_governance", vec![])
+    }
+
+    pub fn set_admin_by_governance(&self, caller: &ScAddress, new_admin: &ScAddress) -> Call<()> {
+        Call::new(
+            &self.address,
+            "set_admin_by_governance",
+            vec![address(caller), address(new_admin)],
+        )
+    }
+
+    pub fn set_paused_by_governance(&self, caller: &ScAddress, paused: bool) -> Call<()> {
+        Call::new(
+            &self.address,
+            "set_paused_by_governance",
+            vec![address(caller), paused.into()],
+        )
+    }
+}
@@ -0,0 +1,266 @@
+//! Client bindings for the Stellar Insights Soroban contracts.
+//!
+//! Each contract function is a method returning a [`Call`]: the
+//! `InvokeContractArgs` to put in a transaction (or a simulation, see
+//! [`Call::simulation_envelope`]) together with a typed decoder for the
+//! function's return value. The snapshot publisher uses these to submit and
+//! read snapshots; external verifiers can use them to check a published
+//! hash against the chain without depending on the backend. The [`ttl`]
+//! module covers the ledger entries that keep a deployed contract alive.
+//!
+//! ```
+//! use stellar_insights_contract_bindings::AnalyticsContract;
+//! use stellar_xdr::curr::ScVal;
+//!
+//! let contract =
+//!     AnalyticsContract::new("CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O").unwrap();
+//! let call = contract.get_latest_epoch();
+//! assert_eq!(call.decode(ScVal::U64(7)).unwrap(), 7);
+//! ```
+
+pub mod analytics;
+pub mod ttl;
+
+use std::fmt;
+use std::marker::PhantomData;
+use stellar_xdr::curr::{
+    AccountId, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Memo, MuxedAccount,
+    Operation, OperationBody, Preconditions, PublicKey, ScAddress, ScVal, SequenceNumber,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256,
+};
+
+pub use analytics::{AnalyticsContract, SnapshotMetadata};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A contract ID or account is not a valid strkey
+    InvalidAddress(String),
+    /// A return value does not have the type the function declares
+    UnexpectedValue {
+        function: &'static str,
+        reason: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidAddress(address) => write!(f, "Invalid Stellar address: {}", address),
+            Error::UnexpectedValue { function, reason } => {
+                write!(f, "Unexpected {} return value: {}", function, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Address of an ed25519 account, e.g. the signer submitting snapshots
+pub fn account_address(public_key: [u8; 32]) -> ScAddress {
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+        public_key,
+    ))))
+}
+
+/// Parse a `G...` or `C...` strkey
+pub fn parse_address(address: &str) -> Result<ScAddress> {
+    address
+        .parse()
+        .map_err(|_| Error::InvalidAddress(address.to_string()))
+}
+
+/// One contract function invocation and the type it returns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call<T> {
+    pub args: InvokeContractArgs,
+    function: &'static str,
+    returns: PhantomData<fn() -> T>,
+}
+
+impl<T: FromScVal> Call<T> {
+    fn new(contract: &ScAddress, function: &'static str, args: Vec<ScVal>) -> Self {
+        Self {
+            args: InvokeContractArgs {
+                contract_address: contract.clone(),
+                function_name: function
+                    .try_into()
+                    .expect("contract function names are valid symbols"),
+                args: args
+                    .try_into()
+                    .expect("contract functions take few arguments"),
+            },
+            function,
+            returns: PhantomData,
+        }
+    }
+
+    /// Name of the contract function
+    pub fn function(&self) -> &'static str {
+        self.function
+    }
+
+    /// Decode the function's return value
+    pub fn decode(&self, value: ScVal) -> Result<T> {
+        T::from_scval(value).map_err(|reason| Error::UnexpectedValue {
+            function: self.function,
+            reason,
+        })
+    }
+
+    /// Unsigned single-operation transaction for Soroban RPC
+    /// `simulateTransaction`, which ignores the sequence number and fee
+    pub fn simulation_envelope(&self, source_account: [u8; 32]) -> TransactionEnvelope {
+        let operation = Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(self.args.clone()),
+                auth: Default::default(),
+            }),
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256(source_account)),
+                fee: 100,
+                seq_num: SequenceNumber(0),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![operation]
+                    .try_into()
+                    .expect("one operation fits in a transaction"),
+                ext: TransactionExt::V0,
+            },
+            signatures: Default::default(),
+        })
+    }
+}
+
+/// Conversion from a contract return value
+pub trait FromScVal: Sized {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String>;
+}
+
+impl FromScVal for () {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::Void => Ok(()),
+            other => Err(format!("expected void, got {:?}", other.discriminant())),
+        }
+    }
+}
+
+impl FromScVal for bool {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::Bool(b) => Ok(b),
+            other => Err(format!("expected a bool, got {:?}", other.discriminant())),
+        }
+    }
+}
+
+impl FromScVal for u32 {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::U32(n) => Ok(n),
+            other => Err(format!("expected a u32, got {:?}", other.discriminant())),
+        }
+    }
+}
+
+impl FromScVal for u64 {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::U64(n) => Ok(n),
+            other => Err(format!("expected a u64, got {:?}", other.discriminant())),
+        }
+    }
+}
+
+/// `BytesN<32>` values, such as snapshot hashes
+impl FromScVal for [u8; 32] {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::Bytes(bytes) => bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| "expected a 32-byte hash".to_string()),
+            other => Err(format!("expected bytes, got {:?}", other.discriminant())),
+        }
+    }
+}
+
+impl FromScVal for ScAddress {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::Address(address) => Ok(address),
+            other => Err(format!(
+                "expected an address, got {:?}",
+                other.discriminant()
+            )),
+        }
+    }
+}
+
+/// `Option<T>` return values are void when `None`
+impl<T: FromScVal> FromScVal for Option<T> {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::Void => Ok(None),
+            other => T::from_scval(other).map(Some),
+        }
+    }
+}
+
+impl<T: FromScVal> FromScVal for Vec<T> {
+    fn from_scval(value: ScVal) -> std::result::Result<Self, String> {
+        match value {
+            ScVal::Vec(Some(items)) => items.0.into_vec().into_iter().map(T::from_scval).collect(),
+            other => Err(format!("expected a vec, got {:?}", other.discriminant())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{Limits, ReadXdr, WriteXdr};
+
+    #[test]
+    fn test_simulation_envelope_round_trips() {
+        let contract =
+            AnalyticsContract::new("CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O")
+                .unwrap();
+        let call = contract.get_snapshot(3);
+        let envelope = call.simulation_envelope([7u8; 32]);
+
+        let xdr = envelope.to_xdr(Limits::none()).unwrap();
+        let decoded = TransactionEnvelope::from_xdr(xdr, Limits::none()).unwrap();
+        let TransactionEnvelope::Tx(v1) = decoded else {
+            panic!("expected a v1 envelope");
+        };
+        let OperationBody::InvokeHostFunction(op) = &v1.tx.operations[0].body else {
+            panic!("expected an invoke operation");
+        };
+        assert_eq!(op.host_function, HostFunction::InvokeContract(call.args));
+    }
+
+    #[test]
+    fn test_decode_reports_function_and_type() {
+        let contract =
+            AnalyticsContract::new("CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O")
+                .unwrap();
+        assert_eq!(contract.is_paused().decode(ScVal::Bool(true)), Ok(true));
+        assert_eq!(contract.get_admin().decode(ScVal::Void), Ok(None));
+
+        let err = contract
+            .get_latest_epoch()
+            .decode(ScVal::Bool(true))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unexpected get_latest_epoch return value: expected a u64, got Bool"
+        );
+        assert!(AnalyticsContract::new("not-a-contract").is_err());
+    }
+}
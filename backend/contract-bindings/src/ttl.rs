@@ -0,0 +1,134 @@
+//! Ledger keys and transactions for keeping contract state alive.
+//!
+//! Soroban archives persistent entries whose TTL runs out. A contract stays
+//! callable only while both its instance entry and its Wasm code entry are
+//! live; [`contract_instance_key`] and [`contract_code_key`] name those
+//! entries for Soroban RPC `getLedgerEntries`, and [`extend_ttl_envelope`]
+//! builds the `ExtendFootprintTtl` transaction that bumps them.
+
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractExecutable, ExtendFootprintTtlOp, ExtensionPoint, Hash,
+    LedgerEntryData, LedgerFootprint, LedgerKey, LedgerKeyContractCode, LedgerKeyContractData,
+    Memo, MuxedAccount, Operation, OperationBody, Preconditions, ScAddress, ScVal, SequenceNumber,
+    SorobanResources, SorobanTransactionData, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV1Envelope, Uint256,
+};
+
+/// Key of a contract's instance entry
+pub fn contract_instance_key(contract: &ScAddress) -> LedgerKey {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract.clone(),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+    })
+}
+
+/// Key of an uploaded Wasm code entry
+pub fn contract_code_key(wasm_hash: [u8; 32]) -> LedgerKey {
+    LedgerKey::ContractCode(LedgerKeyContractCode {
+        hash: Hash(wasm_hash),
+    })
+}
+
+/// Hash of the Wasm code a contract instance entry runs, or `None` for other
+/// entries and built-in (Stellar asset) contracts
+pub fn instance_wasm_hash(entry: &LedgerEntryData) -> Option<[u8; 32]> {
+    let LedgerEntryData::ContractData(data) = entry else {
+        return None;
+    };
+    match &data.val {
+        ScVal::ContractInstance(instance) => match &instance.executable {
+            ContractExecutable::Wasm(hash) => Some(hash.0),
+            ContractExecutable::StellarAsset => None,
+        },
+        _ => None,
+    }
+}
+
+/// Unsigned transaction extending the TTL of `keys` to at least `extend_to`
+/// ledgers. The resources and fee are left for `simulateTransaction` to fill
+/// in.
+pub fn extend_ttl_envelope(
+    source_account: [u8; 32],
+    keys: Vec<LedgerKey>,
+    extend_to: u32,
+) -> TransactionEnvelope {
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::ExtendFootprintTtl(ExtendFootprintTtlOp {
+            ext: ExtensionPoint::V0,
+            extend_to,
+        }),
+    };
+    let soroban_data = SorobanTransactionData {
+        ext: ExtensionPoint::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: keys
+                    .try_into()
+                    .expect("footprint keys fit in a transaction"),
+                read_write: Default::default(),
+            },
+            instructions: 0,
+            read_bytes: 0,
+            write_bytes: 0,
+        },
+        resource_fee: 0,
+    };
+    TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(source_account)),
+            fee: 100,
+            seq_num: SequenceNumber(0),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![operation]
+                .try_into()
+                .expect("one operation fits in a transaction"),
+            ext: TransactionExt::V1(soroban_data),
+        },
+        signatures: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_address;
+    use stellar_xdr::curr::{ContractDataEntry, ScContractInstance};
+
+    #[test]
+    fn test_extend_ttl_envelope_covers_instance_and_code() {
+        let contract =
+            parse_address("CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O").unwrap();
+        let instance = LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: contract.clone(),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+            val: ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::Wasm(Hash([9u8; 32])),
+                storage: None,
+            }),
+        });
+        let wasm_hash = instance_wasm_hash(&instance).unwrap();
+        assert_eq!(wasm_hash, [9u8; 32]);
+
+        let keys = vec![
+            contract_instance_key(&contract),
+            contract_code_key(wasm_hash),
+        ];
+        let TransactionEnvelope::Tx(v1) = extend_ttl_envelope([7u8; 32], keys.clone(), 1000) else {
+            panic!("expected a v1 envelope");
+        };
+        let OperationBody::ExtendFootprintTtl(op) = &v1.tx.operations[0].body else {
+            panic!("expected an extend operation");
+        };
+        assert_eq!(op.extend_to, 1000);
+        let TransactionExt::V1(data) = &v1.tx.ext else {
+            panic!("expected soroban transaction data");
+        };
+        assert_eq!(data.resources.footprint.read_only.to_vec(), keys);
+        assert!(data.resources.footprint.read_write.is_empty());
+    }
+}
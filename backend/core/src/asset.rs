@@ -0,0 +1,262 @@
+//! A Stellar asset: the native lumen or a credit asset identified by its
+//! code and issuing account.
+//!
+//! Serializes in Horizon's shape (`asset_type`, `asset_code`,
+//! `asset_issuer`) and parses SEP-11 strings (`native` or `CODE:ISSUER`).
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Longest code a `credit_alphanum12` asset can have
+const MAX_CODE_LEN: usize = 12;
+
+/// Length of a Stellar account ID (`G...` strkey)
+const ACCOUNT_ID_LEN: usize = 56;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Asset {
+    Native,
+    Credit { code: String, issuer: String },
+}
+
+impl Asset {
+    pub fn credit(code: impl Into<String>, issuer: impl Into<String>) -> Self {
+        Self::Credit {
+            code: code.into(),
+            issuer: issuer.into(),
+        }
+    }
+
+    /// Build from the separate type, code and issuer fields Horizon uses on
+    /// trades, payments and offers (`base_asset_type`, `base_asset_code`,
+    /// ...). `None` for pool shares or a credit asset missing its code or
+    /// issuer.
+    pub fn from_horizon_fields(
+        asset_type: &str,
+        code: Option<&str>,
+        issuer: Option<&str>,
+    ) -> Option<Self> {
+        if asset_type == "native" {
+            return Some(Self::Native);
+        }
+        if !asset_type.starts_with("credit_alphanum") {
+            return None;
+        }
+        match (code, issuer) {
+            (Some(code), Some(issuer)) if !code.is_empty() && !issuer.is_empty() => {
+                Some(Self::credit(code, issuer))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse an asset string as Horizon writes it, e.g. on liquidity pool
+    /// reserves. Unlike [`str::parse`], the issuer is taken as-is.
+    pub fn from_horizon_str(value: &str) -> Option<Self> {
+        if value == "native" {
+            return Some(Self::Native);
+        }
+        let (code, issuer) = value.split_once(':')?;
+        Self::from_horizon_fields(Self::credit_type(code), Some(code), Some(issuer))
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self, Self::Native)
+    }
+
+    /// Horizon `asset_type`
+    pub fn asset_type(&self) -> &'static str {
+        match self {
+            Self::Native => "native",
+            Self::Credit { code, .. } => Self::credit_type(code),
+        }
+    }
+
+    /// Asset code, `XLM` for the native asset
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Native => "XLM",
+            Self::Credit { code, .. } => code,
+        }
+    }
+
+    pub fn issuer(&self) -> Option<&str> {
+        match self {
+            Self::Native => None,
+            Self::Credit { issuer, .. } => Some(issuer),
+        }
+    }
+
+    /// `XLM:native` or `CODE:ISSUER`, the form corridor keys and the price
+    /// feed use
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.code(), self.issuer().unwrap_or("native"))
+    }
+
+    /// Horizon query parameters selecting this asset, e.g. `selling_asset_type=native`
+    pub fn horizon_query(&self, prefix: &str) -> String {
+        match self {
+            Self::Native => format!("{}_asset_type=native", prefix),
+            Self::Credit { code, issuer } => format!(
+                "{p}_asset_type={}&{p}_asset_code={}&{p}_asset_issuer={}",
+                self.asset_type(),
+                code,
+                issuer,
+                p = prefix
+            ),
+        }
+    }
+
+    /// `asset_type`, `asset_code` and `asset_issuer` as Horizon writes them
+    pub fn horizon_fields(&self) -> (String, Option<String>, Option<String>) {
+        (
+            self.asset_type().to_string(),
+            self.issuer().map(|_| self.code().to_string()),
+            self.issuer().map(str::to_string),
+        )
+    }
+
+    fn credit_type(code: &str) -> &'static str {
+        if code.len() <= 4 {
+            "credit_alphanum4"
+        } else {
+            "credit_alphanum12"
+        }
+    }
+}
+
+/// SEP-11 form: `native` or `CODE:ISSUER`
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native => f.write_str("native"),
+            Self::Credit { code, issuer } => write!(f, "{}:{}", code, issuer),
+        }
+    }
+}
+
+/// Parses user input: `native`, `XLM` or `XLM:native` for the native asset,
+/// otherwise `CODE:ISSUER` with a valid code and a `G...` issuer
+impl std::str::FromStr for Asset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw = s.trim();
+        if raw.eq_ignore_ascii_case("native")
+            || raw.eq_ignore_ascii_case("xlm")
+            || raw.eq_ignore_ascii_case("xlm:native")
+        {
+            return Ok(Self::Native);
+        }
+
+        let (code, issuer) = raw
+            .split_once(':')
+            .ok_or_else(|| format!("Asset '{}' must be 'native' or CODE:ISSUER", raw))?;
+        if code.is_empty()
+            || code.len() > MAX_CODE_LEN
+            || !code.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(format!("Invalid asset code '{}'", code));
+        }
+        if issuer.len() != ACCOUNT_ID_LEN || !issuer.starts_with('G') {
+            return Err(format!("Invalid asset issuer '{}'", issuer));
+        }
+
+        Ok(Self::credit(code, issuer))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HorizonAssetFields {
+    asset_type: String,
+    #[serde(default)]
+    asset_code: Option<String>,
+    #[serde(default)]
+    asset_issuer: Option<String>,
+}
+
+impl Serialize for Asset {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (asset_type, asset_code, asset_issuer) = self.horizon_fields();
+        HorizonAssetFields {
+            asset_type,
+            asset_code,
+            asset_issuer,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Asset {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = HorizonAssetFields::deserialize(deserializer)?;
+        Self::from_horizon_fields(
+            &fields.asset_type,
+            fields.asset_code.as_deref(),
+            fields.asset_issuer.as_deref(),
+        )
+        .ok_or_else(|| {
+            serde::de::Error::custom(format!("unsupported asset type '{}'", fields.asset_type))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    #[test]
+    fn test_parse_sep11() {
+        assert_eq!("native".parse::<Asset>().unwrap(), Asset::Native);
+        assert_eq!("XLM".parse::<Asset>().unwrap(), Asset::Native);
+
+        let usdc: Asset = format!("USDC:{}", USDC_ISSUER).parse().unwrap();
+        assert_eq!(usdc, Asset::credit("USDC", USDC_ISSUER));
+        assert_eq!(usdc.asset_type(), "credit_alphanum4");
+        assert_eq!(usdc.to_string(), format!("USDC:{}", USDC_ISSUER));
+        assert_eq!(Asset::Native.to_string(), "native");
+        assert_eq!(Asset::Native.key(), "XLM:native");
+
+        assert!("USDC".parse::<Asset>().is_err());
+        assert!("USDC:GSHORT".parse::<Asset>().is_err());
+        assert!(format!("TOOLONGASSETCODE:{}", USDC_ISSUER)
+            .parse::<Asset>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_horizon_serialization() {
+        let usdc = Asset::credit("USDC", USDC_ISSUER);
+        let json = serde_json::to_value(&usdc).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "asset_type": "credit_alphanum4",
+                "asset_code": "USDC",
+                "asset_issuer": USDC_ISSUER,
+            })
+        );
+        assert_eq!(serde_json::from_value::<Asset>(json).unwrap(), usdc);
+        assert_eq!(
+            serde_json::from_str::<Asset>(r#"{"asset_type": "native"}"#).unwrap(),
+            Asset::Native
+        );
+        assert!(
+            serde_json::from_str::<Asset>(r#"{"asset_type": "liquidity_pool_shares"}"#).is_err()
+        );
+
+        assert_eq!(
+            usdc.horizon_query("selling"),
+            format!(
+                "selling_asset_type=credit_alphanum4&selling_asset_code=USDC&selling_asset_issuer={}",
+                USDC_ISSUER
+            )
+        );
+        assert_eq!(
+            Asset::from_horizon_str("EURC:GB").unwrap(),
+            Asset::credit("EURC", "GB")
+        );
+    }
+}
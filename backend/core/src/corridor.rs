@@ -0,0 +1,267 @@
+//! Stellar payment corridors and their canonical keys.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Corridor {
+    pub asset_a_code: String,
+    pub asset_a_issuer: String,
+    pub asset_b_code: String,
+    pub asset_b_issuer: String,
+}
+
+impl Corridor {
+    pub fn new(
+        asset_a_code: String,
+        asset_a_issuer: String,
+        asset_b_code: String,
+        asset_b_issuer: String,
+    ) -> Self {
+        let mut corridor = Corridor {
+            asset_a_code,
+            asset_a_issuer,
+            asset_b_code,
+            asset_b_issuer,
+        };
+        corridor.normalize_ordering();
+        corridor
+    }
+
+    fn normalize_ordering(&mut self) {
+        let asset_a_key = format!("{}:{}", self.asset_a_code, self.asset_a_issuer);
+        let asset_b_key = format!("{}:{}", self.asset_b_code, self.asset_b_issuer);
+
+        if asset_a_key > asset_b_key {
+            std::mem::swap(&mut self.asset_a_code, &mut self.asset_b_code);
+            std::mem::swap(&mut self.asset_a_issuer, &mut self.asset_b_issuer);
+        }
+    }
+
+    pub fn to_string_key(&self) -> String {
+        format!(
+            "{}:{}->{}:{}",
+            self.asset_a_code, self.asset_a_issuer, self.asset_b_code, self.asset_b_issuer
+        )
+    }
+
+    pub fn key(&self) -> CorridorKey {
+        CorridorKey(self.to_string_key())
+    }
+}
+
+/// A validated corridor key in canonical form: `CODE:ISSUER->CODE:ISSUER`
+/// with the assets in [`Corridor`] order and the native asset spelled
+/// `XLM:native`. Parsing also accepts a single `-` between the assets, the
+/// assets in either order, and `native` or `XLM` for the native asset, so
+/// every spelling of a corridor maps to the same cache entry, WebSocket
+/// channel and database rows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CorridorKey(String);
+
+/// Longest asset code Stellar allows (`credit_alphanum12`)
+const MAX_ASSET_CODE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorridorKeyError {
+    MissingSeparator,
+    InvalidAsset(String),
+}
+
+impl fmt::Display for CorridorKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSeparator => {
+                write!(f, "Corridor key must be CODE:ISSUER->CODE:ISSUER")
+            }
+            Self::InvalidAsset(asset) => write!(
+                f,
+                "Invalid corridor asset '{}', expected CODE:ISSUER or native",
+                asset
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CorridorKeyError {}
+
+impl CorridorKey {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn corridor(&self) -> Corridor {
+        // Both halves were validated on construction
+        let (asset_a, asset_b) = self.0.split_once("->").unwrap_or_default();
+        let (a_code, a_issuer) = asset_a.split_once(':').unwrap_or_default();
+        let (b_code, b_issuer) = asset_b.split_once(':').unwrap_or_default();
+        Corridor::new(
+            a_code.to_string(),
+            a_issuer.to_string(),
+            b_code.to_string(),
+            b_issuer.to_string(),
+        )
+    }
+
+    /// WebSocket channel carrying this corridor's updates
+    pub fn channel(&self) -> String {
+        format!("corridor:{}", self.0)
+    }
+
+    fn parse_asset(raw: &str) -> Result<(String, String), CorridorKeyError> {
+        let raw = raw.trim();
+        if raw.eq_ignore_ascii_case("native")
+            || raw.eq_ignore_ascii_case("xlm")
+            || raw.eq_ignore_ascii_case("xlm:native")
+        {
+            return Ok(("XLM".to_string(), "native".to_string()));
+        }
+
+        let invalid = || CorridorKeyError::InvalidAsset(raw.to_string());
+        let (code, issuer) = raw.split_once(':').ok_or_else(invalid)?;
+        let valid_code = !code.is_empty()
+            && code.len() <= MAX_ASSET_CODE_LEN
+            && code.chars().all(|c| c.is_ascii_alphanumeric());
+        let valid_issuer = !issuer.is_empty() && issuer.chars().all(|c| c.is_ascii_alphanumeric());
+        if !valid_code || !valid_issuer {
+            return Err(invalid());
+        }
+        Ok((code.to_string(), issuer.to_string()))
+    }
+}
+
+impl std::str::FromStr for CorridorKey {
+    type Err = CorridorKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Codes and issuers are alphanumeric, so a lone `-` is unambiguous
+        let (asset_a, asset_b) = s
+            .split_once("->")
+            .or_else(|| s.split_once('-'))
+            .ok_or(CorridorKeyError::MissingSeparator)?;
+        let (a_code, a_issuer) = Self::parse_asset(asset_a)?;
+        let (b_code, b_issuer) = Self::parse_asset(asset_b)?;
+        Ok(Corridor::new(a_code, a_issuer, b_code, b_issuer).key())
+    }
+}
+
+impl TryFrom<String> for CorridorKey {
+    type Error = CorridorKeyError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CorridorKey> for String {
+    fn from(key: CorridorKey) -> Self {
+        key.0
+    }
+}
+
+impl From<&Corridor> for CorridorKey {
+    fn from(corridor: &Corridor) -> Self {
+        corridor.key()
+    }
+}
+
+impl fmt::Display for CorridorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corridor_normalization() {
+        let corridor1 = Corridor::new(
+            "USDC".to_string(),
+            "issuer1".to_string(),
+            "EURC".to_string(),
+            "issuer2".to_string(),
+        );
+
+        let corridor2 = Corridor::new(
+            "EURC".to_string(),
+            "issuer2".to_string(),
+            "USDC".to_string(),
+            "issuer1".to_string(),
+        );
+
+        assert_eq!(corridor1, corridor2);
+        assert_eq!(corridor1.asset_a_code, "EURC");
+        assert_eq!(corridor1.asset_b_code, "USDC");
+    }
+
+    #[test]
+    fn test_corridor_same_asset_order() {
+        let corridor = Corridor::new(
+            "USDC".to_string(),
+            "issuer1".to_string(),
+            "USDC".to_string(),
+            "issuer2".to_string(),
+        );
+
+        assert_eq!(corridor.asset_a_code, "USDC");
+        assert_eq!(corridor.asset_b_code, "USDC");
+        assert_eq!(corridor.asset_a_issuer, "issuer1");
+        assert_eq!(corridor.asset_b_issuer, "issuer2");
+    }
+
+    #[test]
+    fn test_corridor_to_string_key() {
+        let corridor = Corridor::new(
+            "USDC".to_string(),
+            "issuer1".to_string(),
+            "EURC".to_string(),
+            "issuer2".to_string(),
+        );
+
+        let key = corridor.to_string_key();
+        assert!(key.contains("EURC:issuer2"));
+        assert!(key.contains("USDC:issuer1"));
+        assert!(key.contains("->"));
+    }
+
+    #[test]
+    fn test_corridor_key_canonicalizes() {
+        let canonical: CorridorKey = "EURC:issuer2->USDC:issuer1".parse().unwrap();
+        for spelling in [
+            "USDC:issuer1->EURC:issuer2",
+            "USDC:issuer1-EURC:issuer2",
+            " EURC:issuer2 -> USDC:issuer1 ",
+        ] {
+            assert_eq!(spelling.parse::<CorridorKey>().unwrap(), canonical);
+        }
+
+        let native: CorridorKey = "native->USDC:GA".parse().unwrap();
+        assert_eq!(native.as_str(), "USDC:GA->XLM:native");
+        assert_eq!(native, "xlm-USDC:GA".parse().unwrap());
+        assert_eq!(native.channel(), "corridor:USDC:GA->XLM:native");
+        assert_eq!(native.corridor().key(), native);
+    }
+
+    #[test]
+    fn test_corridor_key_rejects_malformed() {
+        assert_eq!(
+            "USDC:issuer1".parse::<CorridorKey>(),
+            Err(CorridorKeyError::MissingSeparator)
+        );
+        for key in [
+            "USDC->EURC:issuer2",
+            "USDC:->EURC:issuer2",
+            "USDC:a:b->EURC:issuer2",
+            "TOOLONGASSETCODE:issuer1->EURC:issuer2",
+        ] {
+            assert!(matches!(
+                key.parse::<CorridorKey>(),
+                Err(CorridorKeyError::InvalidAsset(_))
+            ));
+        }
+        assert!(serde_json::from_str::<CorridorKey>("\"USDC\"").is_err());
+    }
+}
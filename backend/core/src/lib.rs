@@ -0,0 +1,17 @@
+//! Types shared by the Stellar Insights backend and its Rust consumers:
+//! assets, corridor keys, the snapshot document, webhook envelopes and
+//! WebSocket messages. Deliberately free of web framework and database
+//! dependencies so CLIs and SDKs can depend on it directly.
+
+pub mod asset;
+pub mod corridor;
+pub mod movers;
+pub mod snapshot;
+pub mod webhook;
+pub mod ws;
+
+pub use asset::Asset;
+pub use corridor::{Corridor, CorridorKey, CorridorKeyError};
+pub use snapshot::AnalyticsSnapshot;
+pub use webhook::{WebhookBatchEnvelope, WebhookEventEnvelope};
+pub use ws::WsMessage;
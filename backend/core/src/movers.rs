@@ -0,0 +1,35 @@
+//! Corridors whose volume, health or spread changed most between two
+//! windows, as pushed on the `movers` WebSocket channel.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoverMetric {
+    Volume,
+    Health,
+    Spread,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mover {
+    pub corridor_key: String,
+    pub metric: MoverMetric,
+    pub previous: f64,
+    pub current: f64,
+    pub change: f64,
+    /// `None` when the previous value was zero
+    pub change_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoverWindow {
+    /// `1h` or `24h`
+    pub window: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub volume: Vec<Mover>,
+    pub health: Vec<Mover>,
+    pub spread: Vec<Mover>,
+}
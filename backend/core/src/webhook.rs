@@ -0,0 +1,22 @@
+//! Bodies POSTed to webhook endpoints. Each delivery is signed with
+//! HMAC-SHA256 over the serialized envelope.
+
+use serde::{Deserialize, Serialize};
+
+/// Webhook event envelope
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookEventEnvelope {
+    pub id: String, // Delivery ID for idempotency
+    pub event: String,
+    pub timestamp: i64,
+    pub data: serde_json::Value,
+}
+
+/// Several events delivered in one request to a batching webhook
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookBatchEnvelope {
+    pub id: String,    // Delivery ID for idempotency
+    pub event: String, // always "batch"
+    pub timestamp: i64,
+    pub events: Vec<WebhookEventEnvelope>,
+}
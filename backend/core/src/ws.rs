@@ -0,0 +1,102 @@
+//! Messages exchanged over the `/ws` WebSocket, tagged by `type`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::movers::MoverWindow;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsMessage {
+    /// New snapshot available
+    SnapshotUpdate {
+        snapshot_id: String,
+        epoch: i64,
+        timestamp: String,
+        hash: String,
+    },
+    /// Corridor metrics updated
+    CorridorUpdate {
+        corridor_key: String,
+        asset_a_code: String,
+        asset_a_issuer: String,
+        asset_b_code: String,
+        asset_b_issuer: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        success_rate: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        health_score: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_updated: Option<String>,
+    },
+    /// Anchor metrics updated
+    AnchorUpdate {
+        anchor_id: String,
+        name: String,
+        reliability_score: f64,
+        status: String,
+    },
+    /// New payment event
+    NewPayment {
+        corridor_id: String,
+        amount: f64,
+        successful: bool,
+        timestamp: String,
+    },
+    /// Health alert for corridor
+    HealthAlert {
+        corridor_id: String,
+        severity: String,
+        message: String,
+        timestamp: String,
+    },
+    /// Status change of a tracked SEP-24/31 transaction
+    /// (sent on the `sep_transaction:<id>` channel)
+    SepTransactionUpdate {
+        transaction_id: String,
+        anchor_id: Option<String>,
+        old_status: String,
+        new_status: String,
+        updated_at: String,
+    },
+    /// Top movers changed (sent on the `movers` channel)
+    MoversUpdate {
+        generated_at: String,
+        windows: Vec<MoverWindow>,
+    },
+    /// Subscription management
+    Subscribe {
+        channels: Vec<String>,
+    },
+    Unsubscribe {
+        channels: Vec<String>,
+    },
+    /// Subscription confirmation
+    SubscriptionConfirm {
+        channels: Vec<String>,
+        status: String,
+    },
+    /// Heartbeat/Ping message
+    Ping {
+        timestamp: i64,
+    },
+    /// Pong response
+    Pong {
+        timestamp: i64,
+    },
+    /// Connection established
+    Connected {
+        connection_id: String,
+    },
+    /// Connection status update
+    ConnectionStatus {
+        status: String,
+    },
+    /// Error message
+    Error {
+        message: String,
+    },
+    /// Server is shutting down
+    ServerShutdown {
+        message: String,
+    },
+}
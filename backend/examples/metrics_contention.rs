@@ -0,0 +1,44 @@
+//! Measures metrics recording throughput as threads are added.
+//!
+//! Run with `cargo run --release --example metrics_contention`. With
+//! contention-free recording, per-operation cost stays roughly flat as the
+//! thread count grows.
+
+use std::time::Instant;
+use stellar_insights_backend::observability::metrics;
+
+const OPS_PER_THREAD: usize = 200_000;
+const METHODS: &[&str] = &[
+    "get_latest_ledger",
+    "get_ledgers",
+    "fetch_payments",
+    "fetch_trades",
+];
+
+fn run(threads: usize) -> f64 {
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        for thread in 0..threads {
+            scope.spawn(move || {
+                for i in 0..OPS_PER_THREAD {
+                    let method = METHODS[(thread + i) % METHODS.len()];
+                    metrics::record_rpc_call(method, "success", 0.002);
+                    metrics::observe_db_query("list_corridors", "success", 0.0005);
+                }
+            });
+        }
+    });
+    let ops = (threads * OPS_PER_THREAD * 2) as f64;
+    start.elapsed().as_nanos() as f64 / ops
+}
+
+fn main() {
+    metrics::init_metrics();
+    // Register every label set before timing
+    run(1);
+
+    println!("{:>8} {:>12}", "threads", "ns/op");
+    for threads in [1, 2, 4, 8, 16] {
+        println!("{:>8} {:>12.1}", threads, run(threads));
+    }
+}
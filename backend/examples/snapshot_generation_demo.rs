@@ -11,6 +11,7 @@
 //! 6. Verify submission success
 
 use std::sync::Arc;
+use stellar_insights_backend::config::Settings;
 use stellar_insights_backend::database::Database;
 use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
 use stellar_insights_backend::services::snapshot::SnapshotService;
@@ -38,7 +39,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize contract service (optional)
     let contract_service = if std::env::var("SNAPSHOT_CONTRACT_ID").is_ok() {
         info!("Contract service configured - will submit to blockchain");
-        Some(Arc::new(ContractService::from_env()?))
+        let settings = Settings::load()?;
+        Some(Arc::new(
+            ContractService::from_env(&settings.signing).await?,
+        ))
     } else {
         info!("Contract service not configured - will only generate hash");
         None
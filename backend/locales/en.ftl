@@ -0,0 +1,67 @@
+# English (default) message bundle.
+# Syntax is a subset of Project Fluent: `key = value` with `{ $name }` placeholders.
+
+## Errors, keyed by `error-` + the lowercased API error code
+
+error-not-found = The requested resource was not found.
+error-anchor-not-found = Anchor not found.
+error-corridor-not-found = Corridor not found.
+error-entity-not-found = The anchor or corridor was not found.
+error-tag-not-found = Tag not found.
+error-internal-error = An internal error occurred.
+error-database-error = A database error occurred.
+error-not-implemented = This endpoint is not implemented yet.
+error-invalid-token = The authentication token is invalid or has expired.
+error-invalid-credentials = Invalid username or password.
+error-account-locked = Too many failed login attempts. Try again later.
+error-invalid-input = The request contains invalid input.
+error-invalid-query = The search query is invalid.
+error-invalid-time-range = The requested time range is invalid.
+error-invalid-granularity = The requested granularity is invalid for this time range.
+error-invalid-fields = The fields parameter is invalid.
+error-invalid-corridor-format = The corridor identifier is not in a valid format.
+error-invalid-tag = Tags must be 1-32 characters of letters, digits, '-' or '_'.
+error-invalid-entity-type = Entity type must be anchor or corridor.
+error-too-many-tags = This entity has too many tags.
+error-invalid-merge = The merge request is invalid.
+error-merge-failed = The anchors could not be merged.
+
+## Alerts
+
+alert-success-rate-drop = Success rate dropped from { $old }% to { $new }%
+alert-latency-increase = Latency increased from { $old }ms to { $new }ms
+alert-liquidity-decrease = Liquidity decreased from ${ $old } to ${ $new }
+alert-supply-mint = Supply rose from { $old } to { $new } (large mint)
+alert-supply-burn = Supply fell from { $old } to { $new } (large burn)
+alert-network-congestion = Likely caused by network-wide congestion ({ $level }), not the anchor.
+alert-email-subject = Stellar Insights alert: { $corridor }
+
+## Email digest
+
+digest-period-weekly = Weekly
+digest-period-monthly = Monthly
+digest-subject = Stellar Insights - { $period } Performance Report
+digest-overview = Overview
+digest-total-volume = Total Volume
+digest-avg-success-rate = Average Success Rate
+digest-top-corridors = Top Corridors
+digest-top-anchors = Top Anchors
+digest-col-corridor = Corridor
+digest-col-anchor = Anchor
+digest-col-success-rate = Success Rate
+digest-col-volume = Volume (USD)
+digest-col-avg-latency = Avg Latency
+digest-col-change = Change
+digest-col-transactions = Transactions
+
+## Notifications
+
+email-unsubscribe = Unsubscribe from these emails
+unsubscribe-confirmed-digest = You will no longer receive Stellar Insights digest emails.
+unsubscribe-confirmed-alerts = You will no longer receive Stellar Insights alert emails.
+
+## Account security
+
+new-device-email-subject = New sign-in to your Stellar Insights account
+new-device-email-body = Your account was signed into from { $device } (IP { $ip }) at { $time }.
+new-device-email-action = If this wasn't you, revoke the session in your account settings and change your password.
@@ -0,0 +1,66 @@
+# Spanish message bundle. Missing keys fall back to English.
+
+## Errors
+
+error-not-found = No se encontró el recurso solicitado.
+error-anchor-not-found = No se encontró el anchor.
+error-corridor-not-found = No se encontró el corredor.
+error-entity-not-found = No se encontró el anchor o corredor.
+error-tag-not-found = No se encontró la etiqueta.
+error-internal-error = Se produjo un error interno.
+error-database-error = Se produjo un error de base de datos.
+error-not-implemented = Este endpoint aún no está implementado.
+error-invalid-token = El token de autenticación no es válido o ha caducado.
+error-invalid-credentials = Usuario o contraseña incorrectos.
+error-account-locked = Demasiados intentos fallidos de inicio de sesión. Inténtalo más tarde.
+error-invalid-input = La solicitud contiene datos no válidos.
+error-invalid-query = La consulta de búsqueda no es válida.
+error-invalid-time-range = El rango de tiempo solicitado no es válido.
+error-invalid-granularity = La granularidad solicitada no es válida para este rango de tiempo.
+error-invalid-fields = El parámetro fields no es válido.
+error-invalid-corridor-format = El identificador del corredor no tiene un formato válido.
+error-invalid-tag = Las etiquetas deben tener de 1 a 32 caracteres: letras, dígitos, '-' o '_'.
+error-invalid-entity-type = El tipo de entidad debe ser anchor o corridor.
+error-too-many-tags = Esta entidad tiene demasiadas etiquetas.
+error-invalid-merge = La solicitud de fusión no es válida.
+error-merge-failed = No se pudieron fusionar los anchors.
+
+## Alerts
+
+alert-success-rate-drop = La tasa de éxito bajó del { $old }% al { $new }%
+alert-latency-increase = La latencia aumentó de { $old }ms a { $new }ms
+alert-liquidity-decrease = La liquidez disminuyó de ${ $old } a ${ $new }
+alert-supply-mint = El suministro subió de { $old } a { $new } (emisión grande)
+alert-supply-burn = El suministro bajó de { $old } a { $new } (quema grande)
+alert-network-congestion = Probablemente causado por congestión en toda la red ({ $level }), no por el anchor.
+alert-email-subject = Alerta de Stellar Insights: { $corridor }
+
+## Email digest
+
+digest-period-weekly = Semanal
+digest-period-monthly = Mensual
+digest-subject = Stellar Insights - Informe de rendimiento { $period }
+digest-overview = Resumen
+digest-total-volume = Volumen total
+digest-avg-success-rate = Tasa de éxito promedio
+digest-top-corridors = Corredores principales
+digest-top-anchors = Anchors principales
+digest-col-corridor = Corredor
+digest-col-anchor = Anchor
+digest-col-success-rate = Tasa de éxito
+digest-col-volume = Volumen (USD)
+digest-col-avg-latency = Latencia promedio
+digest-col-change = Cambio
+digest-col-transactions = Transacciones
+
+## Notifications
+
+email-unsubscribe = Cancelar la suscripción a estos correos
+unsubscribe-confirmed-digest = Ya no recibirás los correos de resumen de Stellar Insights.
+unsubscribe-confirmed-alerts = Ya no recibirás los correos de alertas de Stellar Insights.
+
+## Account security
+
+new-device-email-subject = Nuevo inicio de sesión en tu cuenta de Stellar Insights
+new-device-email-body = Se inició sesión en tu cuenta desde { $device } (IP { $ip }) el { $time }.
+new-device-email-action = Si no fuiste tú, revoca la sesión en la configuración de tu cuenta y cambia tu contraseña.
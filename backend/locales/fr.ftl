@@ -0,0 +1,66 @@
+# French message bundle. Missing keys fall back to English.
+
+## Errors
+
+error-not-found = La ressource demandée est introuvable.
+error-anchor-not-found = Anchor introuvable.
+error-corridor-not-found = Corridor introuvable.
+error-entity-not-found = L'anchor ou le corridor est introuvable.
+error-tag-not-found = Étiquette introuvable.
+error-internal-error = Une erreur interne s'est produite.
+error-database-error = Une erreur de base de données s'est produite.
+error-not-implemented = Ce point de terminaison n'est pas encore implémenté.
+error-invalid-token = Le jeton d'authentification est invalide ou a expiré.
+error-invalid-credentials = Nom d'utilisateur ou mot de passe incorrect.
+error-account-locked = Trop de tentatives de connexion échouées. Réessayez plus tard.
+error-invalid-input = La requête contient des données invalides.
+error-invalid-query = La requête de recherche est invalide.
+error-invalid-time-range = La période demandée est invalide.
+error-invalid-granularity = La granularité demandée est invalide pour cette période.
+error-invalid-fields = Le paramètre fields est invalide.
+error-invalid-corridor-format = L'identifiant du corridor n'est pas dans un format valide.
+error-invalid-tag = Les étiquettes doivent comporter 1 à 32 caractères : lettres, chiffres, '-' ou '_'.
+error-invalid-entity-type = Le type d'entité doit être anchor ou corridor.
+error-too-many-tags = Cette entité a trop d'étiquettes.
+error-invalid-merge = La demande de fusion est invalide.
+error-merge-failed = Les anchors n'ont pas pu être fusionnés.
+
+## Alerts
+
+alert-success-rate-drop = Le taux de réussite est passé de { $old } % à { $new } %
+alert-latency-increase = La latence est passée de { $old } ms à { $new } ms
+alert-liquidity-decrease = La liquidité est passée de { $old } $ à { $new } $
+alert-supply-mint = L'offre est passée de { $old } à { $new } (émission importante)
+alert-supply-burn = L'offre est passée de { $old } à { $new } (destruction importante)
+alert-network-congestion = Probablement dû à une congestion de tout le réseau ({ $level }), et non à l'anchor.
+alert-email-subject = Alerte Stellar Insights : { $corridor }
+
+## Email digest
+
+digest-period-weekly = hebdomadaire
+digest-period-monthly = mensuel
+digest-subject = Stellar Insights - Rapport de performance { $period }
+digest-overview = Vue d'ensemble
+digest-total-volume = Volume total
+digest-avg-success-rate = Taux de réussite moyen
+digest-top-corridors = Principaux corridors
+digest-top-anchors = Principaux anchors
+digest-col-corridor = Corridor
+digest-col-anchor = Anchor
+digest-col-success-rate = Taux de réussite
+digest-col-volume = Volume (USD)
+digest-col-avg-latency = Latence moyenne
+digest-col-change = Variation
+digest-col-transactions = Transactions
+
+## Notifications
+
+email-unsubscribe = Se désabonner de ces e-mails
+unsubscribe-confirmed-digest = Vous ne recevrez plus les e-mails de synthèse de Stellar Insights.
+unsubscribe-confirmed-alerts = Vous ne recevrez plus les e-mails d'alerte de Stellar Insights.
+
+## Account security
+
+new-device-email-subject = Nouvelle connexion à votre compte Stellar Insights
+new-device-email-body = Votre compte a été connecté depuis { $device } (IP { $ip }) le { $time }.
+new-device-email-action = Si ce n'était pas vous, révoquez la session dans les paramètres de votre compte et changez votre mot de passe.
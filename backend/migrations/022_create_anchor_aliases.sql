@@ -0,0 +1,13 @@
+-- Aliases left behind when duplicate anchor records are merged, so lookups by
+-- the old id, account or domain still resolve to the surviving anchor
+CREATE TABLE IF NOT EXISTS anchor_aliases (
+    id TEXT PRIMARY KEY,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    alias_type TEXT NOT NULL CHECK (alias_type IN ('anchor_id', 'stellar_account', 'home_domain')),
+    alias_value TEXT NOT NULL,
+    merged_from TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(alias_type, alias_value)
+);
+
+CREATE INDEX IF NOT EXISTS idx_anchor_aliases_anchor ON anchor_aliases(anchor_id);
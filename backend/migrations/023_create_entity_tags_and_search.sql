@@ -0,0 +1,152 @@
+-- Free-form tags on anchors and corridors
+CREATE TABLE IF NOT EXISTS entity_tags (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('anchor', 'corridor')),
+    entity_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(entity_type, entity_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_entity_tags_entity ON entity_tags(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_entity_tags_tag ON entity_tags(tag);
+
+-- Full-text index over names, asset codes, domains and tags.
+-- One row per anchor/corridor, kept in sync by the triggers below.
+CREATE VIRTUAL TABLE IF NOT EXISTS entity_search USING fts5(
+    entity_type UNINDEXED,
+    entity_id UNINDEXED,
+    title,
+    body,
+    tags,
+    tokenize = 'unicode61 remove_diacritics 2',
+    prefix = '2 3'
+);
+
+-- Anchor documents: name / account, domain and issued asset codes / tags
+CREATE TRIGGER IF NOT EXISTS trg_search_anchor_insert AFTER INSERT ON anchors
+BEGIN
+    INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+    VALUES (
+        'anchor', NEW.id, NEW.name,
+        NEW.stellar_account || ' ' || COALESCE(NEW.home_domain, ''),
+        ''
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_search_anchor_update AFTER UPDATE OF name, stellar_account, home_domain ON anchors
+BEGIN
+    DELETE FROM entity_search WHERE entity_type = 'anchor' AND entity_id = OLD.id;
+    INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+    SELECT 'anchor', a.id, a.name,
+           a.stellar_account || ' ' || COALESCE(a.home_domain, '') || ' ' ||
+               COALESCE((SELECT group_concat(asset_code, ' ') FROM assets WHERE anchor_id = a.id), ''),
+           COALESCE((SELECT group_concat(tag, ' ') FROM entity_tags
+                     WHERE entity_type = 'anchor' AND entity_id = a.id), '')
+    FROM anchors a WHERE a.id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_search_anchor_delete AFTER DELETE ON anchors
+BEGIN
+    DELETE FROM entity_search WHERE entity_type = 'anchor' AND entity_id = OLD.id;
+    DELETE FROM entity_tags WHERE entity_type = 'anchor' AND entity_id = OLD.id;
+END;
+
+-- Asset changes refresh the owning anchor's document
+CREATE TRIGGER IF NOT EXISTS trg_search_asset_insert AFTER INSERT ON assets
+BEGIN
+    DELETE FROM entity_search WHERE entity_type = 'anchor' AND entity_id = NEW.anchor_id;
+    INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+    SELECT 'anchor', a.id, a.name,
+           a.stellar_account || ' ' || COALESCE(a.home_domain, '') || ' ' ||
+               COALESCE((SELECT group_concat(asset_code, ' ') FROM assets WHERE anchor_id = a.id), ''),
+           COALESCE((SELECT group_concat(tag, ' ') FROM entity_tags
+                     WHERE entity_type = 'anchor' AND entity_id = a.id), '')
+    FROM anchors a WHERE a.id = NEW.anchor_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_search_asset_update AFTER UPDATE OF anchor_id, asset_code ON assets
+BEGIN
+    DELETE FROM entity_search
+    WHERE entity_type = 'anchor' AND entity_id IN (OLD.anchor_id, NEW.anchor_id);
+    INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+    SELECT 'anchor', a.id, a.name,
+           a.stellar_account || ' ' || COALESCE(a.home_domain, '') || ' ' ||
+               COALESCE((SELECT group_concat(asset_code, ' ') FROM assets WHERE anchor_id = a.id), ''),
+           COALESCE((SELECT group_concat(tag, ' ') FROM entity_tags
+                     WHERE entity_type = 'anchor' AND entity_id = a.id), '')
+    FROM anchors a WHERE a.id IN (OLD.anchor_id, NEW.anchor_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_search_asset_delete AFTER DELETE ON assets
+BEGIN
+    DELETE FROM entity_search WHERE entity_type = 'anchor' AND entity_id = OLD.anchor_id;
+    INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+    SELECT 'anchor', a.id, a.name,
+           a.stellar_account || ' ' || COALESCE(a.home_domain, '') || ' ' ||
+               COALESCE((SELECT group_concat(asset_code, ' ') FROM assets WHERE anchor_id = a.id), ''),
+           COALESCE((SELECT group_concat(tag, ' ') FROM entity_tags
+                     WHERE entity_type = 'anchor' AND entity_id = a.id), '')
+    FROM anchors a WHERE a.id = OLD.anchor_id;
+END;
+
+-- Corridor documents: asset codes / issuers / tags
+CREATE TRIGGER IF NOT EXISTS trg_search_corridor_insert AFTER INSERT ON corridors
+BEGIN
+    INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+    VALUES (
+        'corridor', NEW.id,
+        NEW.source_asset_code || ' ' || NEW.destination_asset_code,
+        NEW.source_asset_issuer || ' ' || NEW.destination_asset_issuer,
+        ''
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_search_corridor_delete AFTER DELETE ON corridors
+BEGIN
+    DELETE FROM entity_search WHERE entity_type = 'corridor' AND entity_id = OLD.id;
+    DELETE FROM entity_tags WHERE entity_type = 'corridor' AND entity_id = OLD.id;
+END;
+
+-- Tag changes refresh the tags column of the tagged entity
+CREATE TRIGGER IF NOT EXISTS trg_search_tag_insert AFTER INSERT ON entity_tags
+BEGIN
+    UPDATE entity_search
+    SET tags = COALESCE((SELECT group_concat(tag, ' ') FROM entity_tags
+                         WHERE entity_type = NEW.entity_type AND entity_id = NEW.entity_id), '')
+    WHERE entity_type = NEW.entity_type AND entity_id = NEW.entity_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_search_tag_delete AFTER DELETE ON entity_tags
+BEGIN
+    UPDATE entity_search
+    SET tags = COALESCE((SELECT group_concat(tag, ' ') FROM entity_tags
+                         WHERE entity_type = OLD.entity_type AND entity_id = OLD.entity_id), '')
+    WHERE entity_type = OLD.entity_type AND entity_id = OLD.entity_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_search_tag_update AFTER UPDATE ON entity_tags
+BEGIN
+    UPDATE entity_search
+    SET tags = COALESCE((SELECT group_concat(tag, ' ') FROM entity_tags t
+                         WHERE t.entity_type = entity_search.entity_type
+                           AND t.entity_id = entity_search.entity_id), '')
+    WHERE (entity_type = OLD.entity_type AND entity_id = OLD.entity_id)
+       OR (entity_type = NEW.entity_type AND entity_id = NEW.entity_id);
+END;
+
+-- Backfill existing rows
+INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+SELECT 'anchor', a.id, a.name,
+       a.stellar_account || ' ' || COALESCE(a.home_domain, '') || ' ' ||
+           COALESCE((SELECT group_concat(asset_code, ' ') FROM assets WHERE anchor_id = a.id), ''),
+       ''
+FROM anchors a;
+
+INSERT INTO entity_search (entity_type, entity_id, title, body, tags)
+SELECT 'corridor', c.id,
+       c.source_asset_code || ' ' || c.destination_asset_code,
+       c.source_asset_issuer || ' ' || c.destination_asset_issuer,
+       ''
+FROM corridors c;
@@ -0,0 +1,14 @@
+-- Per-user display and notification preferences
+CREATE TABLE IF NOT EXISTS user_preferences (
+    user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    default_network TEXT NOT NULL DEFAULT 'mainnet' CHECK (default_network IN ('mainnet', 'testnet')),
+    base_currency TEXT NOT NULL DEFAULT 'USD',
+    locale TEXT NOT NULL DEFAULT 'en',
+    notification_email TEXT,
+    digest_frequency TEXT NOT NULL DEFAULT 'none' CHECK (digest_frequency IN ('none', 'weekly', 'monthly')),
+    alert_notifications INTEGER NOT NULL DEFAULT 1,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_preferences_digest ON user_preferences(digest_frequency);
@@ -0,0 +1,13 @@
+-- Historical USD prices for assets and fiat currencies, used to value metrics
+-- at the time they were recorded. Keys match price feed asset ids
+-- (e.g. 'XLM:native', 'USDC:G...') or 'fiat:<ISO code>' for fiat rates.
+CREATE TABLE IF NOT EXISTS price_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    asset TEXT NOT NULL,
+    price_usd REAL NOT NULL,
+    source TEXT NOT NULL,
+    observed_at TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_price_history_asset_time ON price_history(asset, observed_at);
@@ -0,0 +1,25 @@
+-- Component health probes and incidents backing the public status page.
+-- A probe is recorded for every component on each monitor run; an incident is
+-- opened when a component leaves 'operational' and resolved when it recovers.
+CREATE TABLE IF NOT EXISTS status_probes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    component TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('operational', 'degraded', 'outage')),
+    latency_ms INTEGER,
+    message TEXT,
+    checked_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_status_probes_component_time ON status_probes(component, checked_at);
+
+CREATE TABLE IF NOT EXISTS status_incidents (
+    id TEXT PRIMARY KEY,
+    component TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('degraded', 'outage')),
+    title TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    resolved_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_status_incidents_component ON status_incidents(component, resolved_at);
+CREATE INDEX IF NOT EXISTS idx_status_incidents_started ON status_incidents(started_at);
@@ -0,0 +1,9 @@
+-- Per-webhook delivery settings.
+-- max_batch_size > 1 lets the dispatcher combine up to that many pending
+-- events into one POST, waiting at most batch_window_secs for a batch to fill.
+-- max_deliveries_per_minute caps POSTs to the endpoint (NULL = unlimited).
+ALTER TABLE webhooks ADD COLUMN max_batch_size INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE webhooks ADD COLUMN batch_window_secs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE webhooks ADD COLUMN max_deliveries_per_minute INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_webhook_events_pending ON webhook_events(status, webhook_id, created_at);
@@ -0,0 +1,30 @@
+-- SEP-24/31 transactions initiated through the transfer proxies, keyed by the
+-- anchor's own transaction id so anchor status callbacks can be matched.
+CREATE TABLE IF NOT EXISTS sep_transactions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    anchor_id TEXT REFERENCES anchors(id) ON DELETE SET NULL,
+    protocol TEXT NOT NULL CHECK (protocol IN ('sep24', 'sep31')),
+    kind TEXT NOT NULL,
+    transfer_server TEXT NOT NULL,
+    anchor_transaction_id TEXT NOT NULL,
+    asset_code TEXT,
+    amount TEXT,
+    status TEXT NOT NULL,
+    status_message TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    completed_at TEXT,
+    UNIQUE (transfer_server, anchor_transaction_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sep_transactions_user ON sep_transactions(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_sep_transactions_anchor_ref ON sep_transactions(anchor_id, anchor_transaction_id);
+
+-- Public keys anchors sign status callbacks with (the SIGNING_KEY from their stellar.toml)
+CREATE TABLE IF NOT EXISTS anchor_callback_keys (
+    anchor_id TEXT PRIMARY KEY REFERENCES anchors(id) ON DELETE CASCADE,
+    signing_key TEXT NOT NULL,
+    updated_by TEXT,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,8 @@
+-- Status polling for tracked SEP transactions.
+-- auth_token holds the user's SEP-10 token for the anchor (encrypted at rest)
+-- so the poller can query the anchor until the transaction is terminal.
+ALTER TABLE sep_transactions ADD COLUMN auth_token TEXT;
+ALTER TABLE sep_transactions ADD COLUMN last_polled_at TEXT;
+ALTER TABLE sep_transactions ADD COLUMN poll_failures INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_sep_transactions_open ON sep_transactions(completed_at, last_polled_at);
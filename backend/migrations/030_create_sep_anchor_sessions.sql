@@ -0,0 +1,10 @@
+-- Server-side SEP-10 sessions: a user's anchor token per transfer server,
+-- encrypted at rest, so the SEP proxies never need it in a URL.
+CREATE TABLE IF NOT EXISTS sep_anchor_sessions (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    transfer_server TEXT NOT NULL,
+    auth_token TEXT NOT NULL,
+    expires_at TEXT,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, transfer_server)
+);
@@ -0,0 +1,5 @@
+-- Row versions for optimistic concurrency on metric updates. Each update
+-- increments the version; writers may send the version they read (If-Match or
+-- a `version` field) and are rejected with 409 if it has since changed.
+ALTER TABLE anchors ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE corridors ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,31 @@
+-- Self-service anchor onboarding: an operator claims an anchor record by
+-- proving control of its home domain or issuer key, and is then granted
+-- scoped permissions over that anchor.
+CREATE TABLE IF NOT EXISTS anchor_claims (
+    id TEXT PRIMARY KEY,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    -- domain_file | toml_field | issuer_signature
+    method TEXT NOT NULL,
+    challenge TEXT NOT NULL,
+    -- pending | verified
+    status TEXT NOT NULL DEFAULT 'pending',
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    verified_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_anchor_claims_anchor ON anchor_claims(anchor_id, user_id);
+
+CREATE TABLE IF NOT EXISTS anchor_operators (
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    -- Comma-separated, e.g. "anchor:metadata"
+    scopes TEXT NOT NULL,
+    claim_id TEXT REFERENCES anchor_claims(id) ON DELETE SET NULL,
+    granted_at TEXT NOT NULL,
+    PRIMARY KEY (anchor_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_anchor_operators_user ON anchor_operators(user_id);
@@ -0,0 +1,22 @@
+-- Off-chain statistics pushed by verified anchor operators. Kept apart from
+-- the metrics we observe on-chain and always labeled as self-reported.
+CREATE TABLE IF NOT EXISTS anchor_self_reported_metrics (
+    id TEXT PRIMARY KEY,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    reported_by TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    deposit_volume_usd REAL,
+    withdrawal_volume_usd REAL,
+    deposit_count INTEGER,
+    withdrawal_count INTEGER,
+    avg_deposit_time_ms INTEGER,
+    avg_withdrawal_time_ms INTEGER,
+    source TEXT NOT NULL DEFAULT 'self_reported',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (anchor_id, period_start, period_end)
+);
+
+CREATE INDEX IF NOT EXISTS idx_anchor_self_reported_anchor
+    ON anchor_self_reported_metrics(anchor_id, period_end DESC);
@@ -0,0 +1,9 @@
+-- Provenance of every stored metric row: on_chain (read from ledgers or
+-- contract events), horizon (Horizon API), self_reported (submitted by an
+-- anchor operator) or derived (computed by us). Aggregates carry the least
+-- trusted source among their inputs.
+ALTER TABLE payments ADD COLUMN source TEXT NOT NULL DEFAULT 'horizon';
+ALTER TABLE corridor_metrics ADD COLUMN source TEXT NOT NULL DEFAULT 'horizon';
+ALTER TABLE corridor_metrics_hourly ADD COLUMN source TEXT NOT NULL DEFAULT 'horizon';
+ALTER TABLE anchor_metrics_history ADD COLUMN source TEXT NOT NULL DEFAULT 'derived';
+ALTER TABLE metrics ADD COLUMN source TEXT NOT NULL DEFAULT 'derived';
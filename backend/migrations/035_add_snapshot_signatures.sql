@@ -0,0 +1,3 @@
+-- Backend Ed25519 signature over each snapshot hash, and the public key (hex) that made it
+ALTER TABLE snapshots ADD COLUMN signature TEXT;
+ALTER TABLE snapshots ADD COLUMN signing_key TEXT;
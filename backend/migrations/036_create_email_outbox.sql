@@ -0,0 +1,32 @@
+-- Persistent outbound email queue. Digests and alert emails are queued here
+-- and delivered by the outbox worker, which retries transient failures with
+-- backoff and records the final status of every message.
+CREATE TABLE IF NOT EXISTS email_outbox (
+    id TEXT PRIMARY KEY,
+    -- 'digest' or 'alert'
+    kind TEXT NOT NULL,
+    -- Digest run (e.g. 'weekly-2026-10-12') or alert the email belongs to
+    reference TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    html_body TEXT NOT NULL,
+    -- pending, sent, failed, bounced or suppressed
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    provider TEXT,
+    provider_message_id TEXT,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    sent_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_outbox_due ON email_outbox(status, next_attempt_at);
+CREATE INDEX IF NOT EXISTS idx_email_outbox_reference ON email_outbox(kind, reference);
+
+-- Recipients whose mail hard-bounced; nothing more is sent to them
+CREATE TABLE IF NOT EXISTS email_suppressions (
+    email TEXT PRIMARY KEY,
+    reason TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
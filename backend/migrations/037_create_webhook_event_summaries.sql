@@ -0,0 +1,19 @@
+-- Daily roll-up of webhook events that have aged out of webhook_events.
+-- The retention job folds finished events (delivered or failed) older than
+-- the retention window into one row per webhook, day, event type and status,
+-- then deletes them (or strips their payload, see WEBHOOK_EVENT_ARCHIVE_MODE).
+CREATE TABLE IF NOT EXISTS webhook_event_summaries (
+    webhook_id TEXT NOT NULL,
+    -- YYYY-MM-DD (UTC) the events were created
+    date TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    event_count INTEGER NOT NULL DEFAULT 0,
+    total_retries INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (webhook_id, date, event_type, status)
+);
+
+-- Set when an event has been folded into webhook_event_summaries but kept
+-- with its payload stripped, so it is never counted twice
+ALTER TABLE webhook_events ADD COLUMN compacted_at TEXT;
@@ -0,0 +1,8 @@
+-- OAuth tokens are stored encrypted with a random nonce, so the ciphertext
+-- can't be used to find a token again. access_token_hash is the SHA-256 of
+-- the plaintext access token, used to look tokens up for revocation. Rows
+-- written before this column existed are backfilled by the PII re-encryption
+-- job.
+ALTER TABLE oauth_tokens ADD COLUMN access_token_hash TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_tokens_access_token_hash ON oauth_tokens(access_token_hash);
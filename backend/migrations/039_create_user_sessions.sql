@@ -0,0 +1,18 @@
+-- One row per issued refresh token (login session). Users can list their
+-- active sessions and revoke them individually. The refresh token itself is
+-- not stored, only its SHA-256.
+CREATE TABLE IF NOT EXISTS user_sessions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    refresh_token_hash TEXT NOT NULL,
+    user_agent TEXT,
+    -- Short label derived from the user agent, e.g. 'Firefox on Linux'
+    device TEXT NOT NULL,
+    ip_address TEXT,
+    created_at TEXT NOT NULL,
+    last_used_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user ON user_sessions(user_id, revoked_at);
@@ -0,0 +1,3 @@
+-- End of the last corridor.digest window queued for a webhook, so a restart
+-- or an early run of the digest job never sends the same window twice
+ALTER TABLE webhooks ADD COLUMN last_digest_at TEXT;
@@ -0,0 +1,19 @@
+-- Supply and holder distribution of tracked anchor assets over time.
+-- One row per asset per tracker run. top10_share is the fraction (0-1) of the
+-- supply held by the ten largest holders among the accounts scanned;
+-- holders_complete is 0 when the holder scan stopped at its page limit.
+CREATE TABLE IF NOT EXISTS asset_supply_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    total_supply REAL NOT NULL,
+    num_holders INTEGER NOT NULL,
+    top10_balance REAL NOT NULL,
+    top10_share REAL NOT NULL,
+    holders_scanned INTEGER NOT NULL,
+    holders_complete INTEGER NOT NULL DEFAULT 1,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_supply_snapshots_asset
+    ON asset_supply_snapshots(asset_code, asset_issuer, recorded_at DESC);
@@ -0,0 +1,22 @@
+-- Issuer compliance actions on tracked anchor assets: clawbacks and
+-- trustline flag changes (freezes and re-authorizations).
+-- action is one of clawback, freeze, unfreeze, flags_changed; amount is set
+-- for clawbacks only. flags_set/flags_cleared are JSON arrays of Horizon flag
+-- names (authorized, authorized_to_maintain_liabilities, clawback_enabled).
+CREATE TABLE IF NOT EXISTS asset_compliance_events (
+    operation_id TEXT PRIMARY KEY,
+    transaction_hash TEXT NOT NULL,
+    ledger_sequence INTEGER NOT NULL,
+    operation_type TEXT NOT NULL,
+    action TEXT NOT NULL,
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    account TEXT NOT NULL,
+    amount REAL,
+    flags_set TEXT NOT NULL DEFAULT '[]',
+    flags_cleared TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_compliance_events_asset
+    ON asset_compliance_events(asset_code, asset_issuer, created_at DESC);
@@ -0,0 +1,25 @@
+-- Precomputed corridor list rows. Rebuilt wholesale by the ingestion pipeline
+-- (metrics sync and hourly aggregation) so the corridor list reads one indexed
+-- table instead of joining hourly metrics, liquidity pools and anchor assets
+-- on every request. Windowed columns cover the 24 hours before refreshed_at.
+CREATE TABLE IF NOT EXISTS corridor_summaries (
+    corridor_id TEXT PRIMARY KEY,
+    source_asset_code TEXT NOT NULL,
+    source_asset_issuer TEXT NOT NULL,
+    destination_asset_code TEXT NOT NULL,
+    destination_asset_issuer TEXT NOT NULL,
+    reliability_score REAL NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'active',
+    total_transactions_24h INTEGER NOT NULL DEFAULT 0,
+    successful_transactions_24h INTEGER NOT NULL DEFAULT 0,
+    failed_transactions_24h INTEGER NOT NULL DEFAULT 0,
+    success_rate_24h REAL NOT NULL DEFAULT 0,
+    volume_usd_24h REAL NOT NULL DEFAULT 0,
+    avg_settlement_latency_ms INTEGER,
+    liquidity_depth_usd REAL NOT NULL DEFAULT 0,
+    anchor_count INTEGER NOT NULL DEFAULT 0,
+    refreshed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_corridor_summaries_reliability
+    ON corridor_summaries(reliability_score DESC);
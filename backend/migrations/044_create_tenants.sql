@@ -0,0 +1,28 @@
+-- Tenants for the optional multi-tenant mode (MULTI_TENANT_MODE=true). An API
+-- key issued to a tenant scopes its requests to the anchors and corridors in
+-- that tenant's directory and to the webhooks registered under it. Anchors
+-- and corridors are shared rows joined through membership tables, since two
+-- organizations can track the same anchor or asset pair.
+CREATE TABLE IF NOT EXISTS tenants (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS tenant_anchors (
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    PRIMARY KEY (tenant_id, anchor_id)
+);
+
+CREATE TABLE IF NOT EXISTS tenant_corridors (
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    corridor_id TEXT NOT NULL REFERENCES corridors(id) ON DELETE CASCADE,
+    PRIMARY KEY (tenant_id, corridor_id)
+);
+
+ALTER TABLE api_keys ADD COLUMN tenant_id TEXT REFERENCES tenants(id);
+ALTER TABLE webhooks ADD COLUMN tenant_id TEXT REFERENCES tenants(id);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_tenant_id ON api_keys (tenant_id);
+CREATE INDEX IF NOT EXISTS idx_webhooks_tenant_id ON webhooks (tenant_id);
@@ -0,0 +1,20 @@
+-- Metered usage for billing tenants on the hosted tier: one row per tenant API
+-- call, per delivered webhook event, and per closed WebSocket session (in
+-- whole minutes). exported_at is set once the billing exporter has reported
+-- the row, so each row is billed once.
+CREATE TABLE IF NOT EXISTS usage_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('api_call', 'webhook_delivery', 'ws_minutes')),
+    quantity INTEGER NOT NULL DEFAULT 1,
+    recorded_at TEXT NOT NULL,
+    exported_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_events_tenant_recorded
+    ON usage_events (tenant_id, recorded_at);
+CREATE INDEX IF NOT EXISTS idx_usage_events_unexported
+    ON usage_events (tenant_id, kind) WHERE exported_at IS NULL;
+
+-- Customer the tenant's usage is billed to (a Stripe customer ID)
+ALTER TABLE tenants ADD COLUMN billing_customer_id TEXT;
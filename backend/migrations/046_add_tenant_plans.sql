@@ -0,0 +1,3 @@
+-- Subscription plan of each tenant: free, pro or enterprise. Limits per plan
+-- are defined in src/plan.rs.
+ALTER TABLE tenants ADD COLUMN plan TEXT NOT NULL DEFAULT 'free';
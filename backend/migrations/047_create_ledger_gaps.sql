@@ -0,0 +1,18 @@
+-- Missing ledger ranges found between stored ledgers. The ledger-gap-repair
+-- job backfills each range from the RPC; resolved_at is set once every
+-- ledger in the range is stored, and a range that was only partly filled is
+-- resolved in favour of the smaller ranges left over.
+CREATE TABLE IF NOT EXISTS ledger_gaps (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    start_ledger INTEGER NOT NULL,
+    end_ledger INTEGER NOT NULL,
+    detected_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_attempt_at TEXT,
+    last_error TEXT,
+    resolved_at TEXT,
+    UNIQUE (start_ledger, end_ledger)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_gaps_unresolved
+    ON ledger_gaps (start_ledger) WHERE resolved_at IS NULL;
@@ -0,0 +1,23 @@
+-- Months in which each sending account made a payment in a corridor, the
+-- input to per-corridor cohort retention. An account's cohort is the first
+-- month it appears in. Filled by hourly aggregation from payments; a payment's
+-- corridor is its asset on both sides, as in corridor metrics, with a missing
+-- asset spelled XLM:native.
+CREATE TABLE IF NOT EXISTS corridor_account_months (
+    corridor_key TEXT NOT NULL,
+    account TEXT NOT NULL,
+    -- YYYY-MM (UTC)
+    month TEXT NOT NULL,
+    PRIMARY KEY (corridor_key, account, month)
+);
+
+CREATE INDEX IF NOT EXISTS idx_corridor_account_months_month
+    ON corridor_account_months (corridor_key, month);
+
+INSERT OR IGNORE INTO corridor_account_months (corridor_key, account, month)
+SELECT
+    COALESCE(asset_code, 'XLM') || ':' || COALESCE(asset_issuer, 'native')
+        || '->' || COALESCE(asset_code, 'XLM') || ':' || COALESCE(asset_issuer, 'native'),
+    source_account,
+    substr(created_at, 1, 7)
+FROM payments;
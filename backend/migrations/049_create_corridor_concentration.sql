@@ -0,0 +1,21 @@
+-- Herfindahl-Hirschman concentration of a corridor's volume across sending
+-- accounts, receiving accounts and market makers (both accounts of each
+-- trade between the corridor's assets), one row per corridor per window.
+-- A side without volume in the window has NULL index and share.
+CREATE TABLE IF NOT EXISTS corridor_concentration (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    corridor_key TEXT NOT NULL,
+    window_start TEXT NOT NULL,
+    window_end TEXT NOT NULL,
+    sender_hhi REAL,
+    senders INTEGER NOT NULL DEFAULT 0,
+    top_sender_share REAL,
+    receiver_hhi REAL,
+    receivers INTEGER NOT NULL DEFAULT 0,
+    top_receiver_share REAL,
+    market_maker_hhi REAL,
+    market_makers INTEGER NOT NULL DEFAULT 0,
+    top_market_maker_share REAL,
+    computed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (corridor_key, window_end)
+);
@@ -0,0 +1,23 @@
+-- Corridor health scores, one row per corridor per run of the health-score
+-- job. Each component is 0-100; a NULL component had no data and its weight
+-- was shared among the others.
+CREATE TABLE IF NOT EXISTS corridor_health_scores (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    corridor_key TEXT NOT NULL,
+    computed_at TEXT NOT NULL,
+    score REAL NOT NULL,
+    success_rate_score REAL,
+    liquidity_score REAL,
+    volume_trend_score REAL,
+    anchor_reliability_score REAL,
+    UNIQUE(corridor_key, computed_at)
+);
+
+-- Component weights overriding the HEALTH_SCORE_WEIGHT_* environment
+-- variables; components without a row keep their configured weight.
+CREATE TABLE IF NOT EXISTS health_score_weights (
+    component TEXT PRIMARY KEY
+        CHECK (component IN ('success_rate', 'liquidity', 'volume_trend', 'anchor_reliability')),
+    weight REAL NOT NULL CHECK (weight >= 0),
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,23 @@
+-- Possible wash trading found in network trades by the trade-surveillance
+-- job, kept for compliance review. `accounts` is the sorted, comma-separated
+-- set of accounts involved and `volume` is in the corridor's first asset.
+-- A pattern is flagged once while its windows overlap.
+CREATE TABLE IF NOT EXISTS surveillance_flags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    corridor_key TEXT NOT NULL,
+    pattern TEXT NOT NULL CHECK (pattern IN ('self_trade', 'round_trip', 'circular_flow')),
+    accounts TEXT NOT NULL,
+    trade_count INTEGER NOT NULL,
+    volume REAL NOT NULL,
+    window_start TEXT NOT NULL,
+    window_end TEXT NOT NULL,
+    detected_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'confirmed', 'dismissed')),
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    review_note TEXT,
+    UNIQUE (corridor_key, pattern, accounts, window_end)
+);
+
+CREATE INDEX IF NOT EXISTS idx_surveillance_flags_status
+    ON surveillance_flags(status, detected_at);
@@ -0,0 +1,12 @@
+-- Anomaly models trained on each corridor's hourly volume and success rate
+-- by the ml-anomaly-retrain job. `artifact` is the model's JSON; `version`
+-- counts retrainings of the corridor's model.
+CREATE TABLE IF NOT EXISTS anomaly_models (
+    corridor_key TEXT NOT NULL,
+    model TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    trained_at TEXT NOT NULL,
+    sample_count INTEGER NOT NULL,
+    artifact TEXT NOT NULL,
+    PRIMARY KEY (corridor_key, model)
+);
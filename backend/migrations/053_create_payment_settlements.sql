@@ -0,0 +1,25 @@
+-- Settlement times of path payments, measured by ledger ingestion. A
+-- payment's time runs from the close of the ledger before its first attempt
+-- to the close of the ledger that settled it; `attempts` counts failed
+-- transactions from the same source with the same memo before it.
+CREATE TABLE IF NOT EXISTS payment_settlements (
+    transaction_hash TEXT PRIMARY KEY,
+    corridor_key TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    settled_at TEXT NOT NULL,
+    settlement_ms INTEGER NOT NULL,
+    attempts INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_settlements_corridor
+    ON payment_settlements(corridor_key, settled_at);
+
+-- Failed transactions awaiting a successful retry from the same source with
+-- the same memo
+CREATE TABLE IF NOT EXISTS settlement_attempts (
+    source_account TEXT NOT NULL,
+    memo TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (source_account, memo)
+);
@@ -0,0 +1,22 @@
+-- Delivery schedule of webhook events and the deliveries that exhausted
+-- their retries.
+-- next_attempt_at holds a failed event back until its backoff has elapsed
+-- (NULL = due now); delivered_at is when the endpoint accepted it.
+ALTER TABLE webhook_events ADD COLUMN next_attempt_at TEXT;
+ALTER TABLE webhook_events ADD COLUMN delivered_at TEXT;
+
+-- Permanently failed events. The payload is copied so a failure can still be
+-- re-driven after webhook_events retention has archived the event.
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+    event_id TEXT PRIMARY KEY,
+    webhook_id TEXT NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    retries INTEGER NOT NULL,
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    failed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_webhook
+    ON webhook_dead_letters(webhook_id, failed_at);
@@ -0,0 +1,6 @@
+-- Secret rotation for webhooks.
+-- After a rotation, deliveries are also signed with previous_secret until
+-- previous_secret_expires_at so receivers can switch over without dropping
+-- events.
+ALTER TABLE webhooks ADD COLUMN previous_secret TEXT;
+ALTER TABLE webhooks ADD COLUMN previous_secret_expires_at TEXT;
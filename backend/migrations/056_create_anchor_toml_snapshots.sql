@@ -0,0 +1,9 @@
+-- Last stellar.toml fetched for each anchor, parsed to JSON.
+-- The refresh job compares each fetch with it to raise anchor.toml_changed
+-- webhook events.
+CREATE TABLE IF NOT EXISTS anchor_toml_snapshots (
+    anchor_id TEXT PRIMARY KEY,
+    domain TEXT NOT NULL,
+    content TEXT NOT NULL,
+    fetched_at TEXT NOT NULL
+);
@@ -0,0 +1,40 @@
+-- Latency and outcome of requests the SEP-24/31 proxies send to anchor
+-- transfer servers. outcome is the HTTP status code, or 'timeout',
+-- 'network' or 'circuit_open' when no response arrived. Samples are rolled
+-- up into sep_performance_daily and pruned after a few days.
+CREATE TABLE IF NOT EXISTS sep_request_samples (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transfer_server TEXT NOT NULL,
+    host TEXT NOT NULL,
+    protocol TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    is_error INTEGER NOT NULL,
+    latency_ms INTEGER NOT NULL,
+    requested_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sep_request_samples_requested_at
+    ON sep_request_samples(requested_at);
+
+-- One row per transfer server, protocol, endpoint and UTC day.
+-- outcome_counts is a JSON object of outcome -> request count.
+CREATE TABLE IF NOT EXISTS sep_performance_daily (
+    transfer_server TEXT NOT NULL,
+    host TEXT NOT NULL,
+    protocol TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    date TEXT NOT NULL,
+    request_count INTEGER NOT NULL,
+    error_count INTEGER NOT NULL,
+    avg_latency_ms REAL NOT NULL,
+    p50_latency_ms INTEGER NOT NULL,
+    p95_latency_ms INTEGER NOT NULL,
+    max_latency_ms INTEGER NOT NULL,
+    outcome_counts TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (transfer_server, protocol, endpoint, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sep_performance_daily_host
+    ON sep_performance_daily(host, date);
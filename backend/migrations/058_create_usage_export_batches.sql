@@ -0,0 +1,24 @@
+-- Usage reports sent to the billing exporter. A batch is written before its
+-- first send and covers the unexported usage events of one tenant and kind
+-- between first_event_id and last_event_id; it is resent with the same
+-- identifier, quantity and created_at until the exporter acknowledges it,
+-- after which its events are marked exported.
+CREATE TABLE IF NOT EXISTS usage_export_batches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    billing_customer_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    first_event_id INTEGER NOT NULL,
+    last_event_id INTEGER NOT NULL,
+    quantity INTEGER NOT NULL,
+    identifier TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    acknowledged_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_export_batches_unacknowledged
+    ON usage_export_batches (id) WHERE acknowledged_at IS NULL;
+
+ALTER TABLE usage_events ADD COLUMN export_batch_id INTEGER REFERENCES usage_export_batches(id);
@@ -0,0 +1,3 @@
+-- Provenance of each corridor summary: the least trusted source among the
+-- hourly rows it aggregates, or derived when the window had none.
+ALTER TABLE corridor_summaries ADD COLUMN source TEXT NOT NULL DEFAULT 'derived';
@@ -24,6 +24,17 @@ impl AdminAuditLogger {
         Self { pool }
     }
 
+    /// Hash of the most recent entry, used to chain the next one
+    pub async fn latest_hash(&self) -> Result<Option<String>> {
+        let hash = sqlx::query_scalar::<_, String>(
+            "SELECT hash FROM admin_audit_log ORDER BY timestamp DESC, created_at DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(hash)
+    }
+
     /// Record an admin action with tamper-proof hash chaining
     pub async fn log_action(
         &self,
@@ -7,21 +7,23 @@ use std::sync::Arc;
 use tokio::sync::broadcast;
 
 use crate::alerts::{Alert, AlertManager};
+use crate::i18n::Locale;
 
 pub async fn alert_websocket_handler(
     ws: WebSocketUpgrade,
     State(alert_manager): State<Arc<AlertManager>>,
+    locale: Locale,
 ) -> Response {
-    ws.on_upgrade(|socket| handle_alert_socket(socket, alert_manager))
+    ws.on_upgrade(move |socket| handle_alert_socket(socket, alert_manager, locale))
 }
 
-async fn handle_alert_socket(socket: WebSocket, alert_manager: Arc<AlertManager>) {
+async fn handle_alert_socket(socket: WebSocket, alert_manager: Arc<AlertManager>, locale: Locale) {
     let (mut sender, mut receiver) = socket.split();
     let mut rx = alert_manager.subscribe();
 
     let mut send_task = tokio::spawn(async move {
         while let Ok(alert) = rx.recv().await {
-            if let Ok(msg) = serde_json::to_string(&alert) {
+            if let Ok(msg) = serde_json::to_string(&alert.localized(locale)) {
                 if sender.send(axum::extract::ws::Message::Text(msg)).await.is_err() {
                     break;
                 }
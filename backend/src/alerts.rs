@@ -1,43 +1,139 @@
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use tokio::sync::broadcast;
 
+use crate::i18n::{self, Locale};
+use crate::services::network_congestion::CongestionLevel;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AlertType {
     SuccessRateDrop,
     LatencyIncrease,
     LiquidityDecrease,
+    SupplyMint,
+    SupplyBurn,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
     pub alert_type: AlertType,
+    /// Corridor key, or `CODE:ISSUER` for asset supply alerts
     pub corridor_id: String,
     pub message: String,
     pub old_value: f64,
     pub new_value: f64,
     pub timestamp: String,
+    /// Set when the network was congested as the alert fired, meaning the
+    /// degradation is likely network-wide rather than the anchor's fault
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_congestion: Option<CongestionLevel>,
+}
+
+impl Alert {
+    /// Render the alert message in the given locale
+    pub fn localized_message(&self, locale: Locale) -> String {
+        let (key, old, new) = match self.alert_type {
+            AlertType::SuccessRateDrop => (
+                "alert-success-rate-drop",
+                format!("{:.1}", self.old_value),
+                format!("{:.1}", self.new_value),
+            ),
+            AlertType::LatencyIncrease => (
+                "alert-latency-increase",
+                format!("{:.0}", self.old_value),
+                format!("{:.0}", self.new_value),
+            ),
+            AlertType::LiquidityDecrease => (
+                "alert-liquidity-decrease",
+                format!("{:.0}", self.old_value),
+                format!("{:.0}", self.new_value),
+            ),
+            AlertType::SupplyMint => (
+                "alert-supply-mint",
+                format!("{:.0}", self.old_value),
+                format!("{:.0}", self.new_value),
+            ),
+            AlertType::SupplyBurn => (
+                "alert-supply-burn",
+                format!("{:.0}", self.old_value),
+                format!("{:.0}", self.new_value),
+            ),
+        };
+        let message = i18n::t(locale, key, &[("old", old), ("new", new)]);
+        match self.network_congestion {
+            Some(level) => format!(
+                "{} {}",
+                message,
+                i18n::t(
+                    locale,
+                    "alert-network-congestion",
+                    &[("level", level.as_str().to_string())]
+                )
+            ),
+            None => message,
+        }
+    }
+
+    /// Copy of this alert with `message` rendered in the given locale
+    pub fn localized(&self, locale: Locale) -> Self {
+        Self {
+            message: self.localized_message(locale),
+            ..self.clone()
+        }
+    }
 }
 
 pub struct AlertManager {
     tx: broadcast::Sender<Alert>,
+    network_congestion: RwLock<Option<CongestionLevel>>,
 }
 
 impl AlertManager {
     pub fn new() -> (Self, broadcast::Receiver<Alert>) {
         let (tx, rx) = broadcast::channel(100);
-        (Self { tx }, rx)
+        (
+            Self {
+                tx,
+                network_congestion: RwLock::new(None),
+            },
+            rx,
+        )
+    }
+
+    /// Record the latest network congestion level (see `NetworkCongestionService`)
+    pub fn set_network_congestion(&self, level: CongestionLevel) {
+        if let Ok(mut current) = self.network_congestion.write() {
+            *current = Some(level);
+        }
+    }
+
+    /// Congestion level to attach to corridor alerts, if the network is busy
+    /// enough to explain them
+    fn network_wide_congestion(&self) -> Option<CongestionLevel> {
+        self.network_congestion
+            .read()
+            .ok()
+            .and_then(|level| *level)
+            .filter(CongestionLevel::is_network_wide)
     }
 
     pub fn check_and_alert(&self, corridor_id: &str, old_success: f64, new_success: f64, old_latency: f64, new_latency: f64, old_liquidity: f64, new_liquidity: f64) {
+        let congestion = self.network_wide_congestion();
+        let cause = if congestion.is_some() {
+            " (network-wide congestion, not an anchor issue)"
+        } else {
+            ""
+        };
+
         if new_success < old_success - 10.0 {
             let _ = self.tx.send(Alert {
                 alert_type: AlertType::SuccessRateDrop,
                 corridor_id: corridor_id.to_string(),
-                message: format!("Success rate dropped from {:.1}% to {:.1}%", old_success, new_success),
+                message: format!("Success rate dropped from {:.1}% to {:.1}%{}", old_success, new_success, cause),
                 old_value: old_success,
                 new_value: new_success,
                 timestamp: chrono::Utc::now().to_rfc3339(),
+                network_congestion: congestion,
             });
         }
 
@@ -45,10 +141,11 @@ impl AlertManager {
             let _ = self.tx.send(Alert {
                 alert_type: AlertType::LatencyIncrease,
                 corridor_id: corridor_id.to_string(),
-                message: format!("Latency increased from {:.0}ms to {:.0}ms", old_latency, new_latency),
+                message: format!("Latency increased from {:.0}ms to {:.0}ms{}", old_latency, new_latency, cause),
                 old_value: old_latency,
                 new_value: new_latency,
                 timestamp: chrono::Utc::now().to_rfc3339(),
+                network_congestion: congestion,
             });
         }
 
@@ -60,10 +157,39 @@ impl AlertManager {
                 old_value: old_liquidity,
                 new_value: new_liquidity,
                 timestamp: chrono::Utc::now().to_rfc3339(),
+                network_congestion: None,
             });
         }
     }
 
+    /// Alert on a mint or burn that moved an asset's supply by at least
+    /// `threshold_pct` percent. Returns whether an alert was raised.
+    pub fn check_supply_change(&self, asset: &str, old_supply: f64, new_supply: f64, threshold_pct: f64) -> bool {
+        if old_supply <= 0.0 {
+            return false;
+        }
+        let change_pct = (new_supply - old_supply) / old_supply * 100.0;
+        if change_pct.abs() < threshold_pct {
+            return false;
+        }
+
+        let (alert_type, verb) = if change_pct > 0.0 {
+            (AlertType::SupplyMint, "rose")
+        } else {
+            (AlertType::SupplyBurn, "fell")
+        };
+        let _ = self.tx.send(Alert {
+            alert_type,
+            corridor_id: asset.to_string(),
+            message: format!("Supply {} from {:.0} to {:.0} ({:+.1}%)", verb, old_supply, new_supply, change_pct),
+            old_value: old_supply,
+            new_value: new_supply,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            network_congestion: None,
+        });
+        true
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<Alert> {
         self.tx.subscribe()
     }
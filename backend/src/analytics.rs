@@ -1,6 +1,8 @@
 use crate::models::{AnchorMetrics, AnchorStatus};
 
+pub mod concentration;
 pub mod corridor;
+pub mod stats;
 
 /// Performance metrics for an anchor's individual asset
 #[derive(Debug, Clone)]
@@ -0,0 +1,94 @@
+//! Herfindahl–Hirschman concentration of volume across accounts.
+//!
+//! The HHI is the sum of each participant's squared percentage share of the
+//! total, from near 0 (volume spread over many accounts) to 10,000 (a single
+//! account). Levels follow the US merger guidelines: below 1,500 is
+//! unconcentrated and above 2,500 highly concentrated.
+
+use serde::Serialize;
+
+/// HHI of a single participant holding all volume
+pub const HHI_MAX: f64 = 10_000.0;
+
+/// Upper bound of the unconcentrated level
+pub const HHI_MODERATE: f64 = 1_500.0;
+
+/// Lower bound of the highly concentrated level
+pub const HHI_HIGH: f64 = 2_500.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Concentration {
+    /// 0 to [`HHI_MAX`]
+    pub hhi: f64,
+    /// Accounts with positive volume
+    pub participants: i64,
+    /// Share of the largest account, 0 to 1
+    pub top_share: f64,
+}
+
+/// Concentration of `volumes`, one per account. `None` when there is no
+/// positive volume; non-positive volumes are ignored.
+pub fn concentration(volumes: impl IntoIterator<Item = f64>) -> Option<Concentration> {
+    let volumes: Vec<f64> = volumes.into_iter().filter(|v| *v > 0.0).collect();
+    let total: f64 = volumes.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let shares = volumes.iter().map(|v| v / total);
+    Some(Concentration {
+        hhi: shares.clone().map(|s| (s * 100.0).powi(2)).sum(),
+        participants: volumes.len() as i64,
+        top_share: shares.fold(0.0, f64::max),
+    })
+}
+
+/// `unconcentrated`, `moderate` or `high`
+pub fn level(hhi: f64) -> &'static str {
+    if hhi > HHI_HIGH {
+        "high"
+    } else if hhi >= HHI_MODERATE {
+        "moderate"
+    } else {
+        "unconcentrated"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_account_is_fully_concentrated() {
+        let result = concentration([250.0]).unwrap();
+        assert_eq!(result.hhi, HHI_MAX);
+        assert_eq!(result.participants, 1);
+        assert_eq!(result.top_share, 1.0);
+        assert_eq!(level(result.hhi), "high");
+    }
+
+    #[test]
+    fn test_even_split_is_unconcentrated() {
+        let result = concentration(vec![10.0; 10]).unwrap();
+        assert!((result.hhi - 1_000.0).abs() < 1e-9);
+        assert_eq!(result.participants, 10);
+        assert!((result.top_share - 0.1).abs() < 1e-12);
+        assert_eq!(level(result.hhi), "unconcentrated");
+    }
+
+    #[test]
+    fn test_whale_dominates() {
+        // 60% / 20% / 20%: 3600 + 400 + 400
+        let result = concentration([60.0, 20.0, 20.0, 0.0]).unwrap();
+        assert!((result.hhi - 4_400.0).abs() < 1e-9);
+        assert_eq!(result.participants, 3);
+        assert!((result.top_share - 0.6).abs() < 1e-12);
+        assert_eq!(level(2_000.0), "moderate");
+    }
+
+    #[test]
+    fn test_no_volume() {
+        assert_eq!(concentration([]), None);
+        assert_eq!(concentration([0.0, -5.0]), None);
+    }
+}
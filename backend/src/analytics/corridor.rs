@@ -1,15 +1,13 @@
-use crate::models::corridor::{Corridor, CorridorAnalytics, PaymentRecord};
+use crate::models::corridor::{CorridorAnalytics, CorridorKey, PaymentRecord};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
 pub fn compute_corridor_analytics(payments: &[PaymentRecord]) -> Vec<CorridorAnalytics> {
-    let mut corridor_payments: HashMap<String, Vec<&PaymentRecord>> = HashMap::new();
+    let mut corridor_payments: HashMap<CorridorKey, Vec<&PaymentRecord>> = HashMap::new();
 
     for payment in payments {
-        let corridor = payment.get_corridor();
-        let corridor_key = corridor.to_string_key();
         corridor_payments
-            .entry(corridor_key)
+            .entry(payment.get_corridor().key())
             .or_default()
             .push(payment);
     }
@@ -32,7 +30,12 @@ pub fn compute_corridor_analytics(payments: &[PaymentRecord]) -> Vec<CorridorAna
 
         let volume_usd: f64 = corridor_payment_records.iter().map(|p| p.amount).sum();
 
-        let corridor = parse_corridor_key(&corridor_key);
+        let corridor = corridor_key.corridor();
+        let source = corridor_payment_records
+            .iter()
+            .fold(corridor_payment_records[0].source, |acc, p| {
+                acc.least_trusted(p.source)
+            });
 
         analytics.push(CorridorAnalytics {
             corridor,
@@ -41,6 +44,7 @@ pub fn compute_corridor_analytics(payments: &[PaymentRecord]) -> Vec<CorridorAna
             successful_transactions,
             failed_transactions,
             volume_usd,
+            source,
         });
     }
 
@@ -94,22 +98,10 @@ pub fn get_corridors_by_success_rate(
         .collect()
 }
 
-fn parse_corridor_key(corridor_key: &str) -> Corridor {
-    let parts: Vec<&str> = corridor_key.split("->").collect();
-    let asset_a_parts: Vec<&str> = parts[0].split(':').collect();
-    let asset_b_parts: Vec<&str> = parts[1].split(':').collect();
-
-    Corridor::new(
-        asset_a_parts[0].to_string(),
-        asset_a_parts[1].to_string(),
-        asset_b_parts[0].to_string(),
-        asset_b_parts[1].to_string(),
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::MetricSource;
     use uuid::Uuid;
 
     fn create_test_payment(
@@ -131,6 +123,7 @@ mod tests {
             timestamp: Utc::now(),
             submission_time: None,
             confirmation_time: None,
+            source: MetricSource::Horizon,
         }
     }
 
@@ -0,0 +1,182 @@
+//! Seasonal decomposition of hourly series.
+//!
+//! [`decompose_hourly`] splits a series into trend, weekday, hour-of-day and
+//! residual parts using classical additive decomposition: the trend is a
+//! week-long centered moving average, and the seasonal parts are the average
+//! detrended value for each hour of the day and then each day of the week.
+//! `value = trend + weekly + daily + residual` at every point.
+
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+
+pub const HOURS_PER_DAY: usize = 24;
+pub const DAYS_PER_WEEK: usize = 7;
+pub const HOURS_PER_WEEK: usize = HOURS_PER_DAY * DAYS_PER_WEEK;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decomposition {
+    pub trend: Vec<f64>,
+    /// Day-of-week effect at each point
+    pub weekly: Vec<f64>,
+    /// Hour-of-day effect at each point
+    pub daily: Vec<f64>,
+    pub residual: Vec<f64>,
+    /// Effect of each hour of the day (UTC), summing to zero
+    pub daily_profile: [f64; HOURS_PER_DAY],
+    /// Effect of each day of the week, Monday first, summing to zero
+    pub weekly_profile: [f64; DAYS_PER_WEEK],
+}
+
+/// Mean of the values within `window / 2` points either side of each point.
+/// Windows are cut short at the ends of the series.
+pub fn centered_moving_average(values: &[f64], window: usize) -> Vec<f64> {
+    let half = window / 2;
+    (0..values.len())
+        .map(|i| {
+            let start = i.saturating_sub(half);
+            let end = (i + half + 1).min(values.len());
+            mean(&values[start..end])
+        })
+        .collect()
+}
+
+pub fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Population standard deviation
+pub fn std_dev(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mean = mean(values);
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    variance.sqrt()
+}
+
+/// Average of `values` per group, shifted so the averages sum to zero.
+/// Groups without values have no effect.
+fn centered_group_means<const N: usize>(
+    values: &[f64],
+    group: impl Fn(usize) -> usize,
+) -> [f64; N] {
+    let mut sums = [0.0; N];
+    let mut counts = [0usize; N];
+    for (i, value) in values.iter().enumerate() {
+        sums[group(i)] += value;
+        counts[group(i)] += 1;
+    }
+
+    let mut means = [0.0; N];
+    for g in 0..N {
+        if counts[g] > 0 {
+            means[g] = sums[g] / counts[g] as f64;
+        }
+    }
+    let observed: Vec<f64> = (0..N)
+        .filter(|g| counts[*g] > 0)
+        .map(|g| means[g])
+        .collect();
+    let offset = mean(&observed);
+    for g in 0..N {
+        if counts[g] > 0 {
+            means[g] -= offset;
+        }
+    }
+    means
+}
+
+/// Decompose `values`, where `values[i]` covers the hour starting at
+/// `start + i` hours
+pub fn decompose_hourly(start: DateTime<Utc>, values: &[f64]) -> Decomposition {
+    let hour_of = |i: usize| (start + Duration::hours(i as i64)).hour() as usize;
+    let weekday_of = |i: usize| {
+        (start + Duration::hours(i as i64))
+            .weekday()
+            .num_days_from_monday() as usize
+    };
+
+    let trend = centered_moving_average(values, HOURS_PER_WEEK);
+    let detrended: Vec<f64> = values.iter().zip(&trend).map(|(v, t)| v - t).collect();
+
+    let daily_profile: [f64; HOURS_PER_DAY] = centered_group_means(&detrended, hour_of);
+    let daily: Vec<f64> = (0..values.len())
+        .map(|i| daily_profile[hour_of(i)])
+        .collect();
+
+    let without_daily: Vec<f64> = detrended.iter().zip(&daily).map(|(d, s)| d - s).collect();
+    let weekly_profile: [f64; DAYS_PER_WEEK] = centered_group_means(&without_daily, weekday_of);
+    let weekly: Vec<f64> = (0..values.len())
+        .map(|i| weekly_profile[weekday_of(i)])
+        .collect();
+
+    let residual = (0..values.len())
+        .map(|i| values[i] - trend[i] - weekly[i] - daily[i])
+        .collect();
+
+    Decomposition {
+        trend,
+        weekly,
+        daily,
+        residual,
+        daily_profile,
+        weekly_profile,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_moving_average_trims_at_edges() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(
+            centered_moving_average(&values, 2),
+            vec![1.5, 2.0, 3.0, 4.0, 4.5]
+        );
+    }
+
+    #[test]
+    fn test_std_dev() {
+        assert_eq!(std_dev(&[]), 0.0);
+        assert_eq!(std_dev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]), 2.0);
+    }
+
+    #[test]
+    fn test_decompose_recovers_seasonal_pattern() {
+        // Monday 2024-01-01, four weeks: busy afternoons, quiet Sundays
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let values: Vec<f64> = (0..4 * HOURS_PER_WEEK)
+            .map(|i| {
+                let hour = i % HOURS_PER_DAY;
+                let weekday = (i / HOURS_PER_DAY) % DAYS_PER_WEEK;
+                let afternoon = if (12..18).contains(&hour) { 40.0 } else { 0.0 };
+                let sunday = if weekday == 6 { -70.0 } else { 0.0 };
+                100.0 + afternoon + sunday
+            })
+            .collect();
+
+        let result = decompose_hourly(start, &values);
+
+        for (i, value) in values.iter().enumerate() {
+            let sum = result.trend[i] + result.weekly[i] + result.daily[i] + result.residual[i];
+            assert!((sum - value).abs() < 1e-9);
+        }
+        assert!(result.weekly_profile[6] < -50.0);
+        assert!(result.weekly_profile[..6]
+            .iter()
+            .all(|effect| *effect > 0.0));
+        assert!(result.daily_profile[14] > 20.0);
+        assert!(result.daily_profile[3] < 0.0);
+        assert!(result.daily_profile.iter().sum::<f64>().abs() < 1e-9);
+        assert!(result.weekly_profile.iter().sum::<f64>().abs() < 1e-9);
+
+        // Away from the edges the pattern is explained almost entirely
+        let middle = &result.residual[HOURS_PER_WEEK..3 * HOURS_PER_WEEK];
+        assert!(std_dev(middle) < 15.0);
+    }
+}
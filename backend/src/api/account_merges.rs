@@ -6,10 +6,13 @@ use axum::{
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::screening::{AccountScreener, Screened};
 use crate::services::account_merge_detector::{
     AccountMergeDetector, AccountMergeEvent, AccountMergeStats, DestinationAccountPattern,
 };
 
+type MergeState = (Arc<AccountMergeDetector>, Arc<AccountScreener>);
+
 #[derive(Deserialize)]
 pub struct RecentMergesParams {
     #[serde(default = "default_recent_limit")]
@@ -30,16 +33,16 @@ fn default_destination_limit() -> i64 {
     20
 }
 
-pub fn routes(detector: Arc<AccountMergeDetector>) -> Router {
+pub fn routes(detector: Arc<AccountMergeDetector>, screener: Arc<AccountScreener>) -> Router {
     Router::new()
         .route("/stats", get(get_account_merge_stats))
         .route("/recent", get(get_recent_account_merges))
         .route("/destinations", get(get_destination_patterns))
-        .with_state(detector)
+        .with_state((detector, screener))
 }
 
 async fn get_account_merge_stats(
-    State(detector): State<Arc<AccountMergeDetector>>,
+    State((detector, _)): State<MergeState>,
 ) -> Json<AccountMergeStats> {
     let stats = detector
         .get_merge_stats()
@@ -55,22 +58,48 @@ async fn get_account_merge_stats(
 }
 
 async fn get_recent_account_merges(
-    State(detector): State<Arc<AccountMergeDetector>>,
+    State((detector, screener)): State<MergeState>,
     Query(params): Query<RecentMergesParams>,
-) -> Json<Vec<AccountMergeEvent>> {
+) -> Json<Vec<Screened<AccountMergeEvent>>> {
     let limit = params.limit.clamp(1, 200);
     let merges = detector.get_recent_merges(limit).await.unwrap_or_default();
-    Json(merges)
+    let statuses = screener
+        .statuses(
+            merges
+                .iter()
+                .flat_map(|m| [m.source_account.as_str(), m.destination_account.as_str()]),
+        )
+        .await;
+    Json(
+        merges
+            .into_iter()
+            .map(|m| {
+                let accounts = [m.source_account.clone(), m.destination_account.clone()];
+                Screened::new(m, &[&accounts[0], &accounts[1]], &statuses)
+            })
+            .collect(),
+    )
 }
 
 async fn get_destination_patterns(
-    State(detector): State<Arc<AccountMergeDetector>>,
+    State((detector, screener)): State<MergeState>,
     Query(params): Query<DestinationParams>,
-) -> Json<Vec<DestinationAccountPattern>> {
+) -> Json<Vec<Screened<DestinationAccountPattern>>> {
     let limit = params.limit.clamp(1, 100);
     let patterns = detector
         .get_destination_patterns(limit)
         .await
         .unwrap_or_default();
-    Json(patterns)
+    let statuses = screener
+        .statuses(patterns.iter().map(|p| p.destination_account.as_str()))
+        .await;
+    Json(
+        patterns
+            .into_iter()
+            .map(|p| {
+                let account = p.destination_account.clone();
+                Screened::new(p, &[&account], &statuses)
+            })
+            .collect(),
+    )
 }
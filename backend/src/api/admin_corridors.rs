@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::services::corridor_recompute::{
+    CorridorRecomputeService, RecomputeProgress, RecomputeRequest,
+};
+
+#[derive(Clone)]
+pub struct RecomputeState {
+    service: Arc<CorridorRecomputeService>,
+    runs: Arc<RwLock<HashMap<String, RecomputeProgress>>>,
+}
+
+fn store(runs: &RwLock<HashMap<String, RecomputeProgress>>, progress: &RecomputeProgress) {
+    if let Ok(mut runs) = runs.write() {
+        runs.insert(progress.id.clone(), progress.clone());
+    }
+}
+
+/// POST /api/admin/corridors/recompute - Rebuild hourly corridor metrics for a
+/// range from stored payments. Runs in the background; poll the returned id.
+pub async fn start_recompute(
+    State(state): State<RecomputeState>,
+    Json(req): Json<RecomputeRequest>,
+) -> ApiResult<(StatusCode, Json<RecomputeProgress>)> {
+    let hours = req
+        .hours()
+        .map_err(|e| ApiError::bad_request("INVALID_RANGE", e.to_string()))?;
+    let progress = RecomputeProgress::new(&req, hours.len());
+    store(&state.runs, &progress);
+
+    let initial = progress.clone();
+    tokio::spawn(async move {
+        let runs = Arc::clone(&state.runs);
+        state
+            .service
+            .recompute(&req, progress, |p| store(&runs, p))
+            .await;
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(initial)))
+}
+
+/// GET /api/admin/corridors/recompute/:id - Progress, and the diff so far
+pub async fn get_recompute(
+    State(state): State<RecomputeState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<RecomputeProgress>> {
+    state
+        .runs
+        .read()
+        .ok()
+        .and_then(|runs| runs.get(&id).cloned())
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found("RECOMPUTE_NOT_FOUND", format!("No recompute run {}", id))
+        })
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    let state = RecomputeState {
+        service: Arc::new(CorridorRecomputeService::new(db)),
+        runs: Arc::new(RwLock::new(HashMap::new())),
+    };
+    Router::new()
+        .route("/api/admin/corridors/recompute", post(start_recompute))
+        .route("/api/admin/corridors/recompute/:id", get(get_recompute))
+        .with_state(state)
+}
@@ -0,0 +1,100 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::sync::Arc;
+
+use crate::database::{Database, PoolMetrics};
+use crate::observability::slow_queries::{self, SlowQuery};
+
+#[derive(Debug, Serialize)]
+pub struct DbPoolResponse {
+    #[serde(flatten)]
+    pub pool: PoolMetrics,
+    /// Time this request waited to check out a connection
+    pub acquire_wait_ms: Option<f64>,
+    /// Why the checkout failed, e.g. the pool is exhausted
+    pub acquire_error: Option<String>,
+}
+
+/// GET /api/admin/db-pool - Pool configuration, utilization and a live acquire-wait probe
+pub async fn db_pool(State(db): State<Arc<Database>>) -> Json<DbPoolResponse> {
+    let probe = db.probe_pool().await;
+    Json(DbPoolResponse {
+        pool: db.pool_metrics(),
+        acquire_wait_ms: probe.as_ref().ok().map(|d| d.as_secs_f64() * 1000.0),
+        acquire_error: probe.err().map(|e| e.to_string()),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SlowQueriesParams {
+    /// Attach `EXPLAIN QUERY PLAN` output to each statement
+    #[serde(default)]
+    pub explain: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlowQueryView {
+    #[serde(flatten)]
+    pub query: SlowQuery,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlowQueriesResponse {
+    pub threshold_ms: u64,
+    pub capacity: usize,
+    pub queries: Vec<SlowQueryView>,
+}
+
+/// Query plan steps for a tracked statement. Redacted literals are `?`
+/// placeholders, which SQLite plans as unbound parameters; nothing is executed.
+async fn explain(db: &Database, statement: &str) -> Option<Vec<String>> {
+    // A second statement after `;` would run for real, so skip those
+    if statement.trim_end_matches(';').contains(';') {
+        return None;
+    }
+    let rows = sqlx::query(&format!("EXPLAIN QUERY PLAN {}", statement))
+        .fetch_all(db.pool())
+        .await
+        .ok()?;
+    Some(
+        rows.iter()
+            .filter_map(|row| row.try_get::<String, _>("detail").ok())
+            .collect(),
+    )
+}
+
+/// GET /api/admin/slow-queries - Slowest statements since startup, slowest first
+pub async fn slow_queries(
+    State(db): State<Arc<Database>>,
+    Query(params): Query<SlowQueriesParams>,
+) -> Json<SlowQueriesResponse> {
+    let tracker = slow_queries::tracker();
+    let mut queries = Vec::new();
+    for query in tracker.top() {
+        let plan = if params.explain {
+            explain(&db, &query.statement).await
+        } else {
+            None
+        };
+        queries.push(SlowQueryView { query, plan });
+    }
+    Json(SlowQueriesResponse {
+        threshold_ms: tracker.threshold().as_millis() as u64,
+        capacity: tracker.capacity(),
+        queries,
+    })
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/admin/db-pool", get(db_pool))
+        .route("/api/admin/slow-queries", get(slow_queries))
+        .with_state(db)
+}
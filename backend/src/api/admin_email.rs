@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::email::outbox::{
+    deliveries_for, delivery_summaries, EmailDelivery, EmailDeliverySummary, EmailKind,
+};
+use crate::error::{ApiError, ApiResult};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryListQuery {
+    /// `digest`, `alert` or `security`
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+}
+
+fn parse_kind(kind: &str) -> ApiResult<EmailKind> {
+    EmailKind::parse(kind).ok_or_else(|| {
+        ApiError::bad_request(
+            "INVALID_EMAIL_KIND",
+            format!("Unknown email kind '{}', expected digest, alert or security", kind),
+        )
+    })
+}
+
+/// GET /api/admin/email/deliveries - Delivery counts per digest run and alert
+pub async fn list_deliveries(
+    State(db): State<Arc<Database>>,
+    Query(query): Query<DeliveryListQuery>,
+) -> ApiResult<Json<Vec<EmailDeliverySummary>>> {
+    let kind = query.kind.as_deref().map(parse_kind).transpose()?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let summaries = delivery_summaries(&db, kind, limit).await.map_err(|e| {
+        tracing::error!("Failed to load email delivery summaries: {}", e);
+        ApiError::internal("EMAIL_DELIVERY_ERROR", "Failed to load email deliveries")
+    })?;
+
+    Ok(Json(summaries))
+}
+
+/// GET /api/admin/email/deliveries/:kind/:reference - Per-recipient status of
+/// one digest run or alert
+pub async fn get_deliveries(
+    State(db): State<Arc<Database>>,
+    Path((kind, reference)): Path<(String, String)>,
+) -> ApiResult<Json<Vec<EmailDelivery>>> {
+    let kind = parse_kind(&kind)?;
+
+    let deliveries = deliveries_for(&db, kind, &reference).await.map_err(|e| {
+        tracing::error!("Failed to load email deliveries for {}: {}", reference, e);
+        ApiError::internal("EMAIL_DELIVERY_ERROR", "Failed to load email deliveries")
+    })?;
+
+    if deliveries.is_empty() {
+        return Err(ApiError::not_found(
+            "EMAIL_DELIVERY_NOT_FOUND",
+            format!("No {} emails for '{}'", kind.as_str(), reference),
+        ));
+    }
+
+    Ok(Json(deliveries))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/admin/email/deliveries", get(list_deliveries))
+        .route(
+            "/api/admin/email/deliveries/:kind/:reference",
+            get(get_deliveries),
+        )
+        .with_state(db)
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::db::ledger_gaps::{GapTotals, LedgerGap};
+use crate::error::{ApiError, ApiResult};
+use crate::ingestion::gaps::MAX_REPAIR_ATTEMPTS;
+
+#[derive(Debug, Deserialize)]
+pub struct LedgerGapsParams {
+    /// Also list gaps that have been filled
+    #[serde(default)]
+    pub include_resolved: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LedgerGapsResponse {
+    #[serde(flatten)]
+    pub totals: GapTotals,
+    /// Unresolved gaps with this many attempts are no longer retried
+    pub max_attempts: i64,
+    pub gaps: Vec<LedgerGap>,
+}
+
+/// GET /api/admin/ingestion/gaps - Missing ledger ranges and their backfill state
+pub async fn ledger_gaps(
+    State(db): State<Arc<Database>>,
+    Query(params): Query<LedgerGapsParams>,
+) -> ApiResult<Json<LedgerGapsResponse>> {
+    let gaps = db.ledger_gaps();
+    let load = async {
+        let totals = gaps.totals().await?;
+        let list = gaps.list(params.include_resolved).await?;
+        anyhow::Ok((totals, list))
+    };
+    let (totals, gaps) = load.await.map_err(|e| {
+        tracing::error!("Failed to load ledger gaps: {}", e);
+        ApiError::internal("LEDGER_GAPS_ERROR", "Failed to load ledger gaps")
+    })?;
+
+    Ok(Json(LedgerGapsResponse {
+        totals,
+        max_attempts: MAX_REPAIR_ATTEMPTS,
+        gaps,
+    }))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/admin/ingestion/gaps", get(ledger_gaps))
+        .with_state(db)
+}
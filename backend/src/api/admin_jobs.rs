@@ -0,0 +1,24 @@
+use axum::{extract::State, routing::get, Json, Router};
+use serde::Serialize;
+
+use crate::jobs::{TaskHealth, TaskState, TaskSupervisor};
+
+#[derive(Debug, Serialize)]
+pub struct JobsResponse {
+    /// True when every supervised task is running
+    pub healthy: bool,
+    pub tasks: Vec<TaskHealth>,
+}
+
+/// GET /api/admin/jobs - Health and restart counts of supervised background tasks
+pub async fn list_jobs(State(supervisor): State<TaskSupervisor>) -> Json<JobsResponse> {
+    let tasks = supervisor.snapshot();
+    let healthy = tasks.iter().all(|t| t.state == TaskState::Running);
+    Json(JobsResponse { healthy, tasks })
+}
+
+pub fn routes(supervisor: TaskSupervisor) -> Router {
+    Router::new()
+        .route("/api/admin/jobs", get(list_jobs))
+        .with_state(supervisor)
+}
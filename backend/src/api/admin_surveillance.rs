@@ -0,0 +1,119 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::db::surveillance::{ReviewStatus, SurveillanceFlag};
+use crate::error::{ApiError, ApiResult};
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct ListFlagsParams {
+    /// `open`, `confirmed` or `dismissed`; all flags when absent
+    pub status: Option<ReviewStatus>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewFlagRequest {
+    pub status: ReviewStatus,
+    pub note: Option<String>,
+}
+
+/// A flag with its accounts as a list
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagResponse {
+    pub id: i64,
+    pub corridor_key: String,
+    pub pattern: String,
+    pub accounts: Vec<String>,
+    pub trade_count: i64,
+    pub volume: f64,
+    pub window_start: String,
+    pub window_end: String,
+    pub detected_at: String,
+    pub status: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_note: Option<String>,
+}
+
+impl From<SurveillanceFlag> for FlagResponse {
+    fn from(flag: SurveillanceFlag) -> Self {
+        Self {
+            id: flag.id,
+            corridor_key: flag.corridor_key,
+            pattern: flag.pattern,
+            accounts: flag.accounts.split(',').map(str::to_string).collect(),
+            trade_count: flag.trade_count,
+            volume: flag.volume,
+            window_start: flag.window_start,
+            window_end: flag.window_end,
+            detected_at: flag.detected_at,
+            status: flag.status,
+            reviewed_by: flag.reviewed_by,
+            reviewed_at: flag.reviewed_at,
+            review_note: flag.review_note,
+        }
+    }
+}
+
+fn surveillance_error(e: anyhow::Error) -> ApiError {
+    tracing::error!("Surveillance flag operation failed: {}", e);
+    ApiError::internal("SURVEILLANCE_ERROR", "Failed to access surveillance flags")
+}
+
+/// GET /api/admin/surveillance/flags - Suspected wash trading, newest first
+pub async fn list_flags(
+    State(db): State<Arc<Database>>,
+    Query(params): Query<ListFlagsParams>,
+) -> ApiResult<Json<Vec<FlagResponse>>> {
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let flags = db
+        .surveillance_flags()
+        .list(params.status, limit)
+        .await
+        .map_err(surveillance_error)?;
+    Ok(Json(flags.into_iter().map(FlagResponse::from).collect()))
+}
+
+/// PUT /api/admin/surveillance/flags/:id/review - Confirm or dismiss a flag,
+/// or reopen it
+pub async fn review_flag(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+    Path(id): Path<i64>,
+    Json(req): Json<ReviewFlagRequest>,
+) -> ApiResult<Json<FlagResponse>> {
+    let note = req.note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    let flag = db
+        .surveillance_flags()
+        .review(id, req.status, &user.username, note)
+        .await
+        .map_err(surveillance_error)?
+        .ok_or_else(|| {
+            ApiError::not_found("FLAG_NOT_FOUND", format!("No surveillance flag {}", id))
+        })?;
+
+    tracing::info!(
+        flag_id = id,
+        reviewer = %user.username,
+        "Surveillance flag marked {}",
+        flag.status
+    );
+    Ok(Json(flag.into()))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/admin/surveillance/flags", get(list_flags))
+        .route("/api/admin/surveillance/flags/:id/review", put(review_flag))
+        .with_state(db)
+}
@@ -0,0 +1,167 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::db::tenants::Tenant;
+use crate::error::{ApiError, ApiResult};
+use crate::plan::Plan;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTenantRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetPlanRequest {
+    pub plan: Plan,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BillingCustomerRequest {
+    /// Stripe customer ID; `null` stops billing the tenant
+    pub customer_id: Option<String>,
+}
+
+fn tenant_error(e: anyhow::Error) -> ApiError {
+    tracing::error!("Tenant operation failed: {}", e);
+    ApiError::internal("TENANT_ERROR", "Failed to update tenants")
+}
+
+/// POST /api/admin/tenants - Create a tenant for multi-tenant mode
+pub async fn create_tenant(
+    State(db): State<Arc<Database>>,
+    Json(req): Json<CreateTenantRequest>,
+) -> ApiResult<(StatusCode, Json<Tenant>)> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::bad_request(
+            "INVALID_TENANT_NAME",
+            "Tenant name must not be empty",
+        ));
+    }
+    let tenants = db.tenants();
+    if tenants
+        .list()
+        .await
+        .map_err(tenant_error)?
+        .iter()
+        .any(|t| t.name == name)
+    {
+        return Err(ApiError::conflict(
+            "TENANT_EXISTS",
+            format!("A tenant named '{}' already exists", name),
+        ));
+    }
+
+    let tenant = tenants.create(name).await.map_err(tenant_error)?;
+    Ok((StatusCode::CREATED, Json(tenant)))
+}
+
+/// GET /api/admin/tenants - All tenants
+pub async fn list_tenants(State(db): State<Arc<Database>>) -> ApiResult<Json<Vec<Tenant>>> {
+    let tenants = db.tenants().list().await.map_err(tenant_error)?;
+    Ok(Json(tenants))
+}
+
+/// PUT /api/admin/tenants/:id/api-keys/:key_id - Scope an API key to the
+/// tenant
+pub async fn assign_api_key(
+    State(db): State<Arc<Database>>,
+    Path((tenant_id, key_id)): Path<(String, String)>,
+) -> ApiResult<StatusCode> {
+    let tenants = db.tenants();
+    if tenants
+        .get(&tenant_id)
+        .await
+        .map_err(tenant_error)?
+        .is_none()
+    {
+        return Err(ApiError::not_found(
+            "TENANT_NOT_FOUND",
+            format!("No tenant {}", tenant_id),
+        ));
+    }
+    if !tenants
+        .assign_api_key(&key_id, &tenant_id)
+        .await
+        .map_err(tenant_error)?
+    {
+        return Err(ApiError::not_found(
+            "API_KEY_NOT_FOUND",
+            format!("No active API key {}", key_id),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// PUT /api/admin/tenants/:id/plan - Move the tenant to another plan
+pub async fn set_plan(
+    State(db): State<Arc<Database>>,
+    Path(tenant_id): Path<String>,
+    Json(req): Json<SetPlanRequest>,
+) -> ApiResult<StatusCode> {
+    if !db
+        .tenants()
+        .set_plan(&tenant_id, req.plan)
+        .await
+        .map_err(tenant_error)?
+    {
+        return Err(ApiError::not_found(
+            "TENANT_NOT_FOUND",
+            format!("No tenant {}", tenant_id),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// PUT /api/admin/tenants/:id/billing-customer - Bill the tenant's metered
+/// usage to a customer
+pub async fn set_billing_customer(
+    State(db): State<Arc<Database>>,
+    Path(tenant_id): Path<String>,
+    Json(req): Json<BillingCustomerRequest>,
+) -> ApiResult<StatusCode> {
+    let customer_id = req.customer_id.as_deref().map(str::trim);
+    if customer_id == Some("") {
+        return Err(ApiError::bad_request(
+            "INVALID_BILLING_CUSTOMER",
+            "Billing customer ID must not be empty",
+        ));
+    }
+    if !db
+        .tenants()
+        .set_billing_customer(&tenant_id, customer_id)
+        .await
+        .map_err(tenant_error)?
+    {
+        return Err(ApiError::not_found(
+            "TENANT_NOT_FOUND",
+            format!("No tenant {}", tenant_id),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/admin/tenants", get(list_tenants).post(create_tenant))
+        .route(
+            "/api/admin/tenants/:id/api-keys/:key_id",
+            put(assign_api_key),
+        )
+        .route("/api/admin/tenants/:id/plan", put(set_plan))
+        .route(
+            "/api/admin/tenants/:id/billing-customer",
+            put(set_billing_customer),
+        )
+        .with_state(db)
+}
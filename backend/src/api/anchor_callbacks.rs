@@ -0,0 +1,335 @@
+//! Inbound SEP-24/31 status callbacks from anchors.
+//!
+//! Anchors sign callbacks with the SIGNING_KEY published in their stellar.toml.
+//! The `Signature` header has the form `t=<unix timestamp>, s=<base64 signature>`
+//! and the signed payload is `<timestamp>.<host>.<body>`, where `host` is the
+//! host of the callback URL.
+//!
+//! Operators of a claimed anchor (`anchor:metadata` scope) load the key with
+//! `PUT /api/anchors/:id/callback-key`, which reads SIGNING_KEY from the
+//! stellar.toml on the anchor's home domain.
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    routing::{post, put},
+    Json, Router,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::Utc;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::anchor_claims::require_scope;
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::db::anchor_claims::SCOPE_METADATA;
+use crate::db::sep_transactions::StatusUpdate;
+use crate::error::{ApiError, ApiResult};
+use crate::models::Anchor;
+use crate::muxed::decode_account_id;
+use crate::services::sep_transactions::SepTransactionTracker;
+use crate::services::stellar_toml::StellarTomlClient;
+
+/// Oldest callback timestamp accepted, to limit replay of captured requests
+const MAX_CALLBACK_AGE_SECS: i64 = 300;
+
+#[derive(Clone)]
+pub struct AnchorCallbackState {
+    pub db: Arc<Database>,
+    pub tracker: Arc<SepTransactionTracker>,
+    /// Overrides the `Host` header when a proxy rewrites it (`sep_tracking.callback_host`)
+    pub callback_host: Option<String>,
+    /// Fetches SIGNING_KEY from the anchor's stellar.toml
+    pub toml: Arc<StellarTomlClient>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnchorCallbackPayload {
+    pub transaction: CallbackTransaction,
+}
+
+/// The fields of a SEP-24/31 transaction object the callback handler uses
+#[derive(Debug, Deserialize)]
+pub struct CallbackTransaction {
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnchorCallbackResponse {
+    pub transaction_id: String,
+    pub status: String,
+    pub updated: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallbackKeyResponse {
+    pub anchor_id: String,
+    pub signing_key: String,
+}
+
+/// POST /api/callbacks/anchor/:anchor_id - Receive a signed SEP-24/31 status callback
+pub async fn receive_callback(
+    State(state): State<AnchorCallbackState>,
+    Path(anchor_id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<AnchorCallbackResponse>> {
+    let anchor = find_anchor(&state.db, &anchor_id).await?;
+    let store = state.db.sep_transactions();
+
+    let signing_key = store
+        .callback_signing_key(&anchor.id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::unauthorized(
+                "CALLBACK_NOT_CONFIGURED",
+                "No callback signing key is configured for this anchor",
+            )
+        })?;
+
+    let signature_header = headers
+        .get("Signature")
+        .or_else(|| headers.get("X-Stellar-Signature"))
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::unauthorized("INVALID_SIGNATURE", "Missing Signature header"))?;
+
+    let host = callback_host(state.callback_host.as_deref(), &headers)
+        .ok_or_else(|| ApiError::bad_request("INVALID_CALLBACK", "Missing Host header"))?;
+
+    verify_callback_signature(
+        signature_header,
+        &host,
+        &body,
+        &signing_key,
+        Utc::now().timestamp(),
+    )
+    .map_err(|reason| {
+        tracing::warn!(anchor_id = %anchor.id, "Rejected anchor callback: {}", reason);
+        ApiError::unauthorized("INVALID_SIGNATURE", reason)
+    })?;
+
+    let payload: AnchorCallbackPayload = serde_json::from_slice(&body).map_err(|e| {
+        ApiError::bad_request("INVALID_CALLBACK", format!("Invalid callback body: {}", e))
+    })?;
+
+    let tx = store
+        .get_by_anchor_reference(&anchor.id, &payload.transaction.id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SEP_TRANSACTION_NOT_FOUND",
+                format!("Transaction {} is not tracked", payload.transaction.id),
+            )
+        })?;
+
+    let update = state
+        .tracker
+        .record_status(
+            &tx,
+            &payload.transaction.status,
+            payload.transaction.message.as_deref(),
+            Utc::now(),
+        )
+        .await?;
+
+    if update == StatusUpdate::AlreadyTerminal {
+        tracing::info!(
+            transaction_id = %tx.id,
+            "Ignored callback status '{}' for terminal transaction",
+            payload.transaction.status
+        );
+    }
+
+    Ok(Json(AnchorCallbackResponse {
+        transaction_id: tx.id,
+        status: payload.transaction.status,
+        updated: matches!(update, StatusUpdate::Changed { .. }),
+    }))
+}
+
+/// PUT /api/anchors/:id/callback-key - Load the key an anchor signs callbacks
+/// with from the SIGNING_KEY in its stellar.toml
+pub async fn set_callback_key(
+    State(state): State<AnchorCallbackState>,
+    user: AuthUser,
+    Path(anchor_id): Path<String>,
+) -> ApiResult<Json<CallbackKeyResponse>> {
+    let anchor = find_anchor(&state.db, &anchor_id).await?;
+    require_scope(&state.db, &anchor, &user, SCOPE_METADATA).await?;
+
+    let domain = anchor
+        .home_domain
+        .as_deref()
+        .filter(|domain| !domain.trim().is_empty())
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                "HOME_DOMAIN_REQUIRED",
+                "The anchor needs a home domain publishing SIGNING_KEY in its stellar.toml",
+            )
+        })?;
+    let body = state
+        .toml
+        .fetch_well_known(domain, "stellar.toml")
+        .await
+        .map_err(|e| {
+            ApiError::bad_request(
+                "STELLAR_TOML_UNAVAILABLE",
+                format!("Could not fetch stellar.toml: {}", e),
+            )
+        })?;
+    let signing_key = signing_key_from_toml(&body)
+        .map_err(|reason| ApiError::bad_request("INVALID_SIGNING_KEY", reason))?;
+
+    state
+        .db
+        .sep_transactions()
+        .set_callback_signing_key(&anchor.id, &signing_key, &user.user_id)
+        .await?;
+
+    tracing::info!(anchor_id = %anchor.id, user_id = %user.user_id, "Updated anchor callback key");
+    Ok(Json(CallbackKeyResponse {
+        anchor_id: anchor.id,
+        signing_key,
+    }))
+}
+
+/// SIGNING_KEY published in a stellar.toml
+fn signing_key_from_toml(body: &str) -> Result<String, String> {
+    let parsed: toml::Value =
+        toml::from_str(body).map_err(|e| format!("stellar.toml is not valid TOML: {}", e))?;
+    let signing_key = parsed
+        .get("SIGNING_KEY")
+        .and_then(toml::Value::as_str)
+        .map(str::trim)
+        .ok_or("stellar.toml has no SIGNING_KEY")?;
+    if decode_account_id(signing_key).is_none() {
+        return Err(
+            "SIGNING_KEY in stellar.toml is not a valid Stellar public key (G...)".to_string(),
+        );
+    }
+    Ok(signing_key.to_string())
+}
+
+pub(crate) async fn find_anchor(db: &Database, anchor_id: &str) -> ApiResult<Anchor> {
+    let not_found = || ApiError::not_found("ANCHOR_NOT_FOUND", format!("Anchor {} not found", anchor_id));
+    let id = Uuid::parse_str(anchor_id).map_err(|_| not_found())?;
+    db.get_anchor_by_id(id).await?.ok_or_else(not_found)
+}
+
+/// Host the anchor sent the callback to; the configured host wins over the
+/// `Host` header when running behind a proxy that rewrites it
+fn callback_host(configured: Option<&str>, headers: &HeaderMap) -> Option<String> {
+    configured.map(str::to_string).or_else(|| {
+        headers
+            .get(header::HOST)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    })
+}
+
+/// Verify a `t=<timestamp>, s=<base64 signature>` callback signature
+fn verify_callback_signature(
+    header_value: &str,
+    host: &str,
+    body: &[u8],
+    signing_key: &str,
+    now: i64,
+) -> Result<(), String> {
+    let mut timestamp = None;
+    let mut signature = None;
+    for part in header_value.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.trim().parse::<i64>().ok(),
+            Some(("s", value)) => signature = Some(value.trim()),
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or("Signature header has no valid timestamp")?;
+    let signature = signature.ok_or("Signature header has no signature")?;
+
+    if (now - timestamp).abs() > MAX_CALLBACK_AGE_SECS {
+        return Err("Callback timestamp is outside the accepted window".to_string());
+    }
+
+    let key_bytes = decode_account_id(signing_key).ok_or("Configured signing key is invalid")?;
+    let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| "Configured signing key is invalid")?;
+    let signature_bytes = BASE64
+        .decode(signature)
+        .map_err(|_| "Signature is not valid base64")?;
+    let signature =
+        Signature::from_slice(&signature_bytes).map_err(|_| "Signature has an invalid length")?;
+
+    let mut message = format!("{}.{}.", timestamp, host).into_bytes();
+    message.extend_from_slice(body);
+
+    key.verify(&message, &signature)
+        .map_err(|_| "Signature does not match".to_string())
+}
+
+/// Public callback receiver (authenticated by anchor signature)
+pub fn routes(state: AnchorCallbackState) -> Router {
+    Router::new()
+        .route("/api/callbacks/anchor/:anchor_id", post(receive_callback))
+        .with_state(state)
+}
+
+/// Callback key management (requires authentication)
+pub fn key_management_routes(state: AnchorCallbackState) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/callback-key", put(set_callback_key))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::muxed::encode_account_id;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed_header(key: &SigningKey, timestamp: i64, host: &str, body: &[u8]) -> String {
+        let mut message = format!("{}.{}.", timestamp, host).into_bytes();
+        message.extend_from_slice(body);
+        let signature = key.sign(&message);
+        format!("t={}, s={}", timestamp, BASE64.encode(signature.to_bytes()))
+    }
+
+    #[test]
+    fn test_verify_callback_signature() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public = encode_account_id(&key.verifying_key().to_bytes());
+        let body = br#"{"transaction":{"id":"abc","status":"completed"}}"#;
+        let now = 1_700_000_000;
+        let header = signed_header(&key, now, "api.example.com", body);
+
+        assert!(verify_callback_signature(&header, "api.example.com", body, &public, now + 10).is_ok());
+        // Tampered body, wrong host, stale timestamp
+        assert!(verify_callback_signature(&header, "api.example.com", b"{}", &public, now).is_err());
+        assert!(verify_callback_signature(&header, "evil.example.com", body, &public, now).is_err());
+        assert!(verify_callback_signature(&header, "api.example.com", body, &public, now + 3600).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_header() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public = encode_account_id(&key.verifying_key().to_bytes());
+        assert!(verify_callback_signature("s=abc", "h", b"", &public, 0).is_err());
+        assert!(verify_callback_signature("t=0, s=!!", "h", b"", &public, 0).is_err());
+    }
+
+    #[test]
+    fn test_signing_key_from_toml() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public = encode_account_id(&key.verifying_key().to_bytes());
+        let body = format!("VERSION = \"2.0.0\"\nSIGNING_KEY = \"{}\"\n", public);
+        assert_eq!(signing_key_from_toml(&body), Ok(public));
+        assert!(signing_key_from_toml("VERSION = \"2.0.0\"").is_err());
+        assert!(signing_key_from_toml("SIGNING_KEY = \"GNOTAKEY\"").is_err());
+    }
+}
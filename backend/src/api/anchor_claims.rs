@@ -0,0 +1,480 @@
+//! Self-service anchor onboarding.
+//!
+//! An authenticated user claims an anchor record by proving they control it:
+//!
+//! - `domain_file`: serve the challenge at
+//!   `https://<home_domain>/.well-known/stellar-insights-claim.txt`
+//! - `toml_field`: publish `STELLAR_INSIGHTS_CLAIM = "<challenge>"` in the
+//!   home domain's stellar.toml
+//! - `issuer_signature`: sign the challenge with the anchor's account key or
+//!   the issuer key of one of its assets (base64 ed25519 signature)
+//!
+//! A verified claim makes the user an operator of the anchor with the
+//! `anchor:metadata` scope, which allows editing the anchor's name and home
+//! domain through `PATCH /api/anchors/:id/metadata` and loading its callback
+//! signing key (see `anchor_callbacks`), and the `anchor:metrics`
+//! scope for submitting self-reported stats (see `anchor_self_reported`).
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, patch, post},
+    Json, Router,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::anchor_callbacks::find_anchor;
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::db::anchor_claims::{AnchorClaim, AnchorOperator, SCOPE_METADATA};
+use crate::error::{ApiError, ApiResult};
+use crate::models::Anchor;
+use crate::muxed::decode_account_id;
+use crate::services::stellar_toml::StellarTomlClient;
+
+/// File served from the home domain's `.well-known` directory
+pub const CLAIM_FILE: &str = "stellar-insights-claim.txt";
+
+/// stellar.toml field carrying the challenge
+pub const CLAIM_TOML_FIELD: &str = "STELLAR_INSIGHTS_CLAIM";
+
+/// How long a challenge can be verified after it is issued
+const CLAIM_TTL_HOURS: i64 = 24;
+
+const MAX_NAME_LEN: usize = 200;
+
+#[derive(Clone)]
+pub struct AnchorClaimState {
+    pub db: Arc<Database>,
+    pub toml: Arc<StellarTomlClient>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimMethod {
+    DomainFile,
+    TomlField,
+    IssuerSignature,
+}
+
+impl ClaimMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DomainFile => "domain_file",
+            Self::TomlField => "toml_field",
+            Self::IssuerSignature => "issuer_signature",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "domain_file" => Some(Self::DomainFile),
+            "toml_field" => Some(Self::TomlField),
+            "issuer_signature" => Some(Self::IssuerSignature),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StartClaimRequest {
+    pub method: ClaimMethod,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaimChallengeResponse {
+    pub claim_id: String,
+    pub anchor_id: String,
+    pub method: ClaimMethod,
+    pub challenge: String,
+    pub expires_at: DateTime<Utc>,
+    /// What the operator has to do before calling verify
+    pub instructions: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct VerifyClaimRequest {
+    /// Base64 ed25519 signature of the challenge (`issuer_signature` only)
+    pub signature: Option<String>,
+    /// Key that produced the signature; defaults to the anchor's account
+    pub signer: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OperatorResponse {
+    pub anchor_id: String,
+    pub scopes: Vec<String>,
+    pub granted_at: DateTime<Utc>,
+}
+
+impl From<AnchorOperator> for OperatorResponse {
+    fn from(op: AnchorOperator) -> Self {
+        Self {
+            anchor_id: op.anchor_id,
+            scopes: op.scopes.split(',').map(str::to_string).collect(),
+            granted_at: op.granted_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAnchorMetadataRequest {
+    pub name: Option<String>,
+    pub home_domain: Option<String>,
+}
+
+fn instructions(method: ClaimMethod, anchor: &Anchor, challenge: &str) -> String {
+    let domain = anchor.home_domain.as_deref().unwrap_or_default();
+    match method {
+        ClaimMethod::DomainFile => format!(
+            "Serve the challenge as the contents of https://{}/.well-known/{}",
+            domain, CLAIM_FILE
+        ),
+        ClaimMethod::TomlField => format!(
+            "Add {} = \"{}\" to https://{}/.well-known/stellar.toml",
+            CLAIM_TOML_FIELD, challenge, domain
+        ),
+        ClaimMethod::IssuerSignature => format!(
+            "Sign the challenge with the secret key of {} or one of the anchor's asset issuers \
+             and submit the base64 signature",
+            anchor.stellar_account
+        ),
+    }
+}
+
+/// POST /api/anchors/:id/claims - Start claiming an anchor; returns the challenge to publish or sign
+pub async fn start_claim(
+    State(state): State<AnchorClaimState>,
+    user: AuthUser,
+    Path(anchor_id): Path<String>,
+    Json(req): Json<StartClaimRequest>,
+) -> ApiResult<(StatusCode, Json<ClaimChallengeResponse>)> {
+    let anchor = find_anchor(&state.db, &anchor_id).await?;
+    if req.method != ClaimMethod::IssuerSignature && anchor.home_domain.is_none() {
+        return Err(ApiError::bad_request(
+            "NO_HOME_DOMAIN",
+            "This anchor has no home domain; claim it with issuer_signature instead",
+        ));
+    }
+
+    let challenge = format!("stellar-insights:{}:{}", anchor.id, Uuid::new_v4().simple());
+    let claim = state
+        .db
+        .anchor_claims()
+        .create(
+            &anchor.id,
+            &user.user_id,
+            req.method.as_str(),
+            &challenge,
+            Utc::now() + Duration::hours(CLAIM_TTL_HOURS),
+        )
+        .await?;
+
+    tracing::info!(
+        anchor_id = %anchor.id,
+        user_id = %user.user_id,
+        method = req.method.as_str(),
+        "Started anchor claim"
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(ClaimChallengeResponse {
+            instructions: instructions(req.method, &anchor, &claim.challenge),
+            claim_id: claim.id,
+            anchor_id: anchor.id,
+            method: req.method,
+            challenge: claim.challenge,
+            expires_at: claim.expires_at,
+        }),
+    ))
+}
+
+/// POST /api/anchors/:id/claims/:claim_id/verify - Check the proof and grant operator scopes
+pub async fn verify_claim(
+    State(state): State<AnchorClaimState>,
+    user: AuthUser,
+    Path((anchor_id, claim_id)): Path<(String, String)>,
+    Json(req): Json<VerifyClaimRequest>,
+) -> ApiResult<Json<OperatorResponse>> {
+    let anchor = find_anchor(&state.db, &anchor_id).await?;
+    let claims = state.db.anchor_claims();
+    let claim = claims
+        .get(&claim_id)
+        .await?
+        .filter(|c| c.anchor_id == anchor.id && c.user_id == user.user_id)
+        .ok_or_else(|| {
+            ApiError::not_found("CLAIM_NOT_FOUND", format!("Claim {} not found", claim_id))
+        })?;
+
+    if claim.status == "verified" {
+        if let Some(operator) = claims.operator(&anchor.id, &user.user_id).await? {
+            return Ok(Json(operator.into()));
+        }
+    }
+    if claim.expires_at < Utc::now() {
+        return Err(ApiError::bad_request(
+            "CLAIM_EXPIRED",
+            "The challenge has expired; start a new claim",
+        ));
+    }
+
+    if let Err(reason) = check_proof(&state, &anchor, &claim, &req).await {
+        claims.record_failure(&claim.id, &reason).await?;
+        tracing::info!(
+            anchor_id = %anchor.id,
+            user_id = %user.user_id,
+            "Anchor claim not verified: {}",
+            reason
+        );
+        return Err(ApiError::bad_request("CLAIM_VERIFICATION_FAILED", reason));
+    }
+
+    let operator = claims.grant(&claim).await?;
+    tracing::info!(anchor_id = %anchor.id, user_id = %user.user_id, "Anchor claim verified");
+    Ok(Json(operator.into()))
+}
+
+/// GET /api/me/anchors - Anchors the current user operates
+pub async fn list_my_anchors(
+    State(state): State<AnchorClaimState>,
+    user: AuthUser,
+) -> ApiResult<Json<Vec<OperatorResponse>>> {
+    let operators = state
+        .db
+        .anchor_claims()
+        .list_for_user(&user.user_id)
+        .await?;
+    Ok(Json(operators.into_iter().map(Into::into).collect()))
+}
+
+/// PATCH /api/anchors/:id/metadata - Edit an anchor's name or home domain (operators only)
+pub async fn update_anchor_metadata(
+    State(state): State<AnchorClaimState>,
+    user: AuthUser,
+    Path(anchor_id): Path<String>,
+    Json(req): Json<UpdateAnchorMetadataRequest>,
+) -> ApiResult<Json<Anchor>> {
+    let anchor = find_anchor(&state.db, &anchor_id).await?;
+    require_scope(&state.db, &anchor, &user, SCOPE_METADATA).await?;
+
+    let name = req.name.as_deref().map(str::trim);
+    if name.is_some_and(|n| n.is_empty() || n.len() > MAX_NAME_LEN) {
+        return Err(ApiError::bad_request(
+            "INVALID_INPUT",
+            format!("name must be 1-{} characters", MAX_NAME_LEN),
+        ));
+    }
+    let home_domain = req.home_domain.as_deref().map(str::trim);
+    if let Some(domain) = home_domain {
+        state
+            .toml
+            .validate_domain(domain)
+            .map_err(|e| ApiError::bad_request("INVALID_HOME_DOMAIN", e.to_string()))?;
+    }
+
+    let id = Uuid::parse_str(&anchor.id)
+        .map_err(|_| ApiError::internal("INVALID_ANCHOR_ID", "Stored anchor id is invalid"))?;
+    let updated = state
+        .db
+        .update_anchor_metadata(id, name, home_domain)
+        .await?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ANCHOR_NOT_FOUND",
+                format!("Anchor {} not found", anchor_id),
+            )
+        })?;
+
+    tracing::info!(
+        anchor_id = %updated.id,
+        user_id = %user.user_id,
+        "Operator updated anchor metadata"
+    );
+    Ok(Json(updated))
+}
+
+/// Fail with 403 unless `user` operates `anchor` with `scope`
+pub(crate) async fn require_scope(
+    db: &Database,
+    anchor: &Anchor,
+    user: &AuthUser,
+    scope: &str,
+) -> ApiResult<()> {
+    let allowed = db
+        .anchor_claims()
+        .operator(&anchor.id, &user.user_id)
+        .await?
+        .is_some_and(|op| op.has_scope(scope));
+    if allowed {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "ANCHOR_SCOPE_REQUIRED",
+            format!("Claim this anchor to do this (requires {})", scope),
+        ))
+    }
+}
+
+async fn check_proof(
+    state: &AnchorClaimState,
+    anchor: &Anchor,
+    claim: &AnchorClaim,
+    req: &VerifyClaimRequest,
+) -> Result<(), String> {
+    let method = ClaimMethod::parse(&claim.method).ok_or("Unknown claim method")?;
+    let domain = || {
+        anchor
+            .home_domain
+            .as_deref()
+            .ok_or_else(|| "The anchor no longer has a home domain".to_string())
+    };
+
+    match method {
+        ClaimMethod::DomainFile => {
+            let body = state
+                .toml
+                .fetch_well_known(domain()?, CLAIM_FILE)
+                .await
+                .map_err(|e| format!("Could not fetch {}: {}", CLAIM_FILE, e))?;
+            file_has_challenge(&body, &claim.challenge)
+        }
+        ClaimMethod::TomlField => {
+            let body = state
+                .toml
+                .fetch_well_known(domain()?, "stellar.toml")
+                .await
+                .map_err(|e| format!("Could not fetch stellar.toml: {}", e))?;
+            toml_has_challenge(&body, &claim.challenge)
+        }
+        ClaimMethod::IssuerSignature => {
+            let signature = req
+                .signature
+                .as_deref()
+                .ok_or("signature is required for issuer_signature claims")?;
+            let signer = req.signer.as_deref().unwrap_or(&anchor.stellar_account);
+
+            let anchor_id = Uuid::parse_str(&anchor.id).map_err(|_| "Invalid anchor id")?;
+            let assets = state
+                .db
+                .get_assets_by_anchor(anchor_id)
+                .await
+                .map_err(|e| format!("Could not load anchor assets: {}", e))?;
+            let allowed =
+                signer == anchor.stellar_account || assets.iter().any(|a| a.asset_issuer == signer);
+            if !allowed {
+                return Err(format!(
+                    "{} is not this anchor's account or an issuer of its assets",
+                    signer
+                ));
+            }
+            verify_challenge_signature(signer, &claim.challenge, signature)
+        }
+    }
+}
+
+fn file_has_challenge(body: &str, challenge: &str) -> Result<(), String> {
+    if body.lines().any(|line| line.trim() == challenge) {
+        Ok(())
+    } else {
+        Err(format!("{} does not contain the challenge", CLAIM_FILE))
+    }
+}
+
+fn toml_has_challenge(body: &str, challenge: &str) -> Result<(), String> {
+    let parsed: toml::Value =
+        toml::from_str(body).map_err(|e| format!("stellar.toml is not valid TOML: {}", e))?;
+    let found = match parsed.get(CLAIM_TOML_FIELD) {
+        Some(toml::Value::String(value)) => value == challenge,
+        Some(toml::Value::Array(values)) => values.iter().any(|v| v.as_str() == Some(challenge)),
+        _ => false,
+    };
+    if found {
+        Ok(())
+    } else {
+        Err(format!(
+            "stellar.toml has no {} matching the challenge",
+            CLAIM_TOML_FIELD
+        ))
+    }
+}
+
+/// Verify a base64 ed25519 signature of the challenge by a G-address
+fn verify_challenge_signature(
+    signer: &str,
+    challenge: &str,
+    signature: &str,
+) -> Result<(), String> {
+    let key_bytes = decode_account_id(signer).ok_or("signer is not a valid Stellar public key")?;
+    let key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|_| "signer is not a valid Stellar public key")?;
+    let signature_bytes = BASE64
+        .decode(signature.trim())
+        .map_err(|_| "Signature is not valid base64")?;
+    let signature =
+        Signature::from_slice(&signature_bytes).map_err(|_| "Signature has an invalid length")?;
+
+    key.verify(challenge.as_bytes(), &signature)
+        .map_err(|_| "Signature does not match".to_string())
+}
+
+/// Anchor claim and operator routes (require authentication)
+pub fn routes(state: AnchorClaimState) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/claims", post(start_claim))
+        .route(
+            "/api/anchors/:id/claims/:claim_id/verify",
+            post(verify_claim),
+        )
+        .route("/api/anchors/:id/metadata", patch(update_anchor_metadata))
+        .route("/api/me/anchors", get(list_my_anchors))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::muxed::encode_account_id;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const CHALLENGE: &str = "stellar-insights:anchor-1:abc123";
+
+    #[test]
+    fn test_challenge_signature() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let signer = encode_account_id(&key.verifying_key().to_bytes());
+        let signature = BASE64.encode(key.sign(CHALLENGE.as_bytes()).to_bytes());
+
+        assert!(verify_challenge_signature(&signer, CHALLENGE, &signature).is_ok());
+        assert!(
+            verify_challenge_signature(&signer, "stellar-insights:other:abc123", &signature)
+                .is_err()
+        );
+
+        let other = encode_account_id(
+            &SigningKey::from_bytes(&[8u8; 32])
+                .verifying_key()
+                .to_bytes(),
+        );
+        assert!(verify_challenge_signature(&other, CHALLENGE, &signature).is_err());
+    }
+
+    #[test]
+    fn test_domain_proofs() {
+        assert!(file_has_challenge(&format!("{}\n", CHALLENGE), CHALLENGE).is_ok());
+        assert!(file_has_challenge("something else", CHALLENGE).is_err());
+
+        let toml = format!(
+            "VERSION = \"2.0.0\"\n{} = \"{}\"\n",
+            CLAIM_TOML_FIELD, CHALLENGE
+        );
+        assert!(toml_has_challenge(&toml, CHALLENGE).is_ok());
+        let toml = format!("{} = [\"old\", \"{}\"]\n", CLAIM_TOML_FIELD, CHALLENGE);
+        assert!(toml_has_challenge(&toml, CHALLENGE).is_ok());
+        assert!(toml_has_challenge("VERSION = \"2.0.0\"\n", CHALLENGE).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth_middleware::AuthUser;
+use crate::cache_invalidation::CacheInvalidationService;
+use crate::database::Database;
+use crate::db::anchor_merge::{AnchorAlias, AnchorMergeSummary};
+use crate::error::{ApiError, ApiResult};
+use crate::tenant::TenantScope;
+
+type MergeState = (Arc<Database>, Arc<CacheInvalidationService>);
+
+#[derive(Debug, Deserialize)]
+pub struct MergeAnchorsRequest {
+    /// Anchor that remains after the merge
+    pub survivor_id: Uuid,
+    /// Duplicate anchor folded into the survivor and removed
+    pub duplicate_id: Uuid,
+    /// Optional free-text justification recorded in the audit trail
+    pub reason: Option<String>,
+}
+
+/// POST /api/admin/anchors/merge - Merge a duplicate anchor into a surviving record
+pub async fn merge_anchors(
+    State((db, cache_invalidation)): State<MergeState>,
+    tenant: TenantScope,
+    user: AuthUser,
+    Json(req): Json<MergeAnchorsRequest>,
+) -> ApiResult<Json<AnchorMergeSummary>> {
+    if req.survivor_id == req.duplicate_id {
+        return Err(ApiError::bad_request(
+            "INVALID_MERGE",
+            "survivor_id and duplicate_id must differ",
+        ));
+    }
+
+    // Anchors outside the tenant's directory are reported as missing
+    for id in [req.survivor_id, req.duplicate_id] {
+        if !db.tenants().has_anchor(&tenant, &id.to_string()).await?
+            || db.get_anchor_by_id(id).await?.is_none()
+        {
+            let mut details = HashMap::new();
+            details.insert("anchor_id".to_string(), serde_json::json!(id.to_string()));
+            return Err(ApiError::not_found_with_details(
+                "ANCHOR_NOT_FOUND",
+                format!("Anchor with id {} not found", id),
+                details,
+            ));
+        }
+    }
+
+    let resource = format!("anchor:{}", req.survivor_id);
+    let result = db
+        .anchor_merges()
+        .merge(&req.survivor_id.to_string(), &req.duplicate_id.to_string())
+        .await;
+
+    let (status, details) = match &result {
+        Ok(summary) => (
+            "success",
+            serde_json::json!({ "summary": summary, "reason": req.reason }),
+        ),
+        Err(e) => (
+            "failure",
+            serde_json::json!({
+                "duplicate_id": req.duplicate_id.to_string(),
+                "reason": req.reason,
+                "error": e.to_string(),
+            }),
+        ),
+    };
+    let prev_hash = db.admin_audit_logger.latest_hash().await.unwrap_or(None);
+    if let Err(e) = db
+        .admin_audit_logger
+        .log_action(
+            "anchor.merge",
+            &resource,
+            &user.user_id,
+            status,
+            details,
+            prev_hash.as_deref(),
+        )
+        .await
+    {
+        tracing::error!("Failed to write audit log for anchor merge: {}", e);
+    }
+
+    let summary = result.map_err(|e| ApiError::bad_request("MERGE_FAILED", e.to_string()))?;
+
+    if let Err(e) = cache_invalidation.invalidate_anchor(&summary.survivor_id).await {
+        tracing::warn!("Failed to invalidate anchor caches after merge: {}", e);
+    }
+    if let Err(e) = cache_invalidation.invalidate_anchor(&summary.merged_id).await {
+        tracing::warn!("Failed to invalidate anchor caches after merge: {}", e);
+    }
+
+    tracing::info!(
+        survivor_id = %summary.survivor_id,
+        merged_id = %summary.merged_id,
+        user_id = %user.user_id,
+        "Merged duplicate anchor"
+    );
+
+    Ok(Json(summary))
+}
+
+/// GET /api/admin/anchors/:id/aliases - List aliases that resolve to an anchor
+pub async fn list_anchor_aliases(
+    State((db, _)): State<MergeState>,
+    tenant: TenantScope,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Vec<AnchorAlias>>> {
+    if !db.tenants().has_anchor(&tenant, &id.to_string()).await? {
+        return Err(ApiError::not_found(
+            "ANCHOR_NOT_FOUND",
+            format!("Anchor with id {} not found", id),
+        ));
+    }
+    let aliases = db.anchor_merges().list_aliases(&id.to_string()).await?;
+    Ok(Json(aliases))
+}
+
+pub fn routes(db: Arc<Database>, cache_invalidation: Arc<CacheInvalidationService>) -> Router {
+    Router::new()
+        .route("/api/admin/anchors/merge", post(merge_anchors))
+        .route("/api/admin/anchors/:id/aliases", get(list_anchor_aliases))
+        .with_state((db, cache_invalidation))
+}
@@ -0,0 +1,123 @@
+//! Off-chain stats submitted by anchor operators.
+//!
+//! Operators holding the `anchor:metrics` scope (granted by a verified claim,
+//! see `anchor_claims`) push deposit/withdrawal volumes and processing times
+//! per reporting period. Reports are stored apart from observed metrics,
+//! carry `source: "self_reported"`, and appear under `self_reported` in
+//! `GET /api/anchors/:id`.
+
+use axum::{
+    extract::{Path, State},
+    routing::post,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use crate::api::anchor_callbacks::find_anchor;
+use crate::api::anchor_claims::require_scope;
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::db::anchor_claims::SCOPE_METRICS;
+use crate::db::self_reported::{SelfReportedMetrics, SelfReportedValues};
+use crate::error::{ApiError, ApiResult};
+
+/// Longest period a single report may cover
+const MAX_PERIOD_DAYS: i64 = 31;
+
+fn validate(values: &SelfReportedValues) -> Result<(), String> {
+    if values.period_end <= values.period_start {
+        return Err("period_end must be after period_start".to_string());
+    }
+    if values.period_end - values.period_start > Duration::days(MAX_PERIOD_DAYS) {
+        return Err(format!("A report may cover at most {} days", MAX_PERIOD_DAYS));
+    }
+    if values.period_end > Utc::now() + Duration::minutes(5) {
+        return Err("period_end is in the future".to_string());
+    }
+
+    let volumes = [values.deposit_volume_usd, values.withdrawal_volume_usd];
+    if volumes.iter().flatten().any(|v| !v.is_finite() || *v < 0.0) {
+        return Err("Volumes must be non-negative numbers".to_string());
+    }
+    let counts = [
+        values.deposit_count,
+        values.withdrawal_count,
+        values.avg_deposit_time_ms,
+        values.avg_withdrawal_time_ms,
+    ];
+    if counts.iter().flatten().any(|v| *v < 0) {
+        return Err("Counts and processing times must be non-negative".to_string());
+    }
+    if volumes.iter().all(Option::is_none) && counts.iter().all(Option::is_none) {
+        return Err("Report at least one metric".to_string());
+    }
+    Ok(())
+}
+
+/// POST /api/anchors/:id/self-reported-metrics - Submit the operator's stats for a period
+pub async fn submit_self_reported_metrics(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+    Path(anchor_id): Path<String>,
+    Json(values): Json<SelfReportedValues>,
+) -> ApiResult<Json<SelfReportedMetrics>> {
+    let anchor = find_anchor(&db, &anchor_id).await?;
+    require_scope(&db, &anchor, &user, SCOPE_METRICS).await?;
+    validate(&values).map_err(|e| ApiError::bad_request("INVALID_INPUT", e))?;
+
+    let report = db
+        .self_reported_metrics()
+        .upsert(&anchor.id, &user.user_id, &values)
+        .await?;
+
+    tracing::info!(
+        anchor_id = %anchor.id,
+        user_id = %user.user_id,
+        "Stored self-reported metrics for {} - {}",
+        values.period_start,
+        values.period_end
+    );
+    Ok(Json(report))
+}
+
+/// Self-reported metrics submission (requires authentication)
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route(
+            "/api/anchors/:id/self-reported-metrics",
+            post(submit_self_reported_metrics),
+        )
+        .with_state(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(days: i64) -> SelfReportedValues {
+        let end = Utc::now() - Duration::hours(1);
+        SelfReportedValues {
+            period_start: end - Duration::days(days),
+            period_end: end,
+            deposit_volume_usd: Some(1000.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_report() {
+        assert!(validate(&report(1)).is_ok());
+        assert!(validate(&report(40)).is_err());
+
+        let mut negative = report(1);
+        negative.withdrawal_count = Some(-1);
+        assert!(validate(&negative).is_err());
+
+        let empty = SelfReportedValues {
+            deposit_volume_usd: None,
+            ..report(1)
+        };
+        assert!(validate(&empty).is_err());
+    }
+}
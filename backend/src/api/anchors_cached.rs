@@ -18,6 +18,8 @@ use crate::rpc::{
     StellarRpcClient,
 };
 use crate::services::price_feed::PriceFeedClient;
+use crate::sparse_fields::FieldsQuery;
+use crate::tenant::TenantScope;
 
 #[derive(Debug, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
@@ -94,7 +96,8 @@ pub struct AnchorsResponse {
 /// List all anchors with key metrics
 ///
 /// Returns a paginated list of all anchors with their performance metrics.
-/// Data is cached for improved performance.
+/// Data is cached as serialized JSON, which is returned as-is on a hit. Pass
+/// `?fields=` to receive only the listed per-anchor fields.
 ///
 /// **DATA SOURCE: RPC + Database**
 /// - Anchor metadata (name, account) from database
@@ -102,9 +105,10 @@ pub struct AnchorsResponse {
 #[utoipa::path(
     get,
     path = "/api/anchors",
-    params(ListAnchorsQuery),
+    params(ListAnchorsQuery, FieldsQuery),
     responses(
         (status = 200, description = "List of anchors retrieved successfully", body = AnchorsResponse),
+        (status = 400, description = "Invalid fields selection"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Anchors"
@@ -116,14 +120,20 @@ pub async fn get_anchors(
         Arc<StellarRpcClient>,
         Arc<PriceFeedClient>,
     )>,
+    tenant: TenantScope,
     Query(params): Query<ListAnchorsQuery>,
+    Query(fields): Query<FieldsQuery>,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
-    let cache_key = keys::anchor_list(params.limit, params.offset);
+    let selection = fields.selection()?;
+    let cache_key = tenant.cache_key(keys::anchor_list(params.limit, params.offset));
 
-    let response = <()>::get_or_fetch(&cache, &cache_key, cache.config.get_ttl("anchor"), async {
+    let ttl = cache.config.get_ttl("anchor");
+    let cached = <()>::get_or_fetch_json(&cache, &cache_key, ttl, async {
         // Get anchor metadata from database (names, accounts, etc.)
-        let anchors = db.list_anchors(params.limit, params.offset).await?;
+        let anchors = db
+            .list_anchors(params.limit, params.offset, &tenant)
+            .await?;
         let circuit_breaker = rpc_circuit_breaker();
 
         let mut anchor_responses = Vec::new();
@@ -231,8 +241,18 @@ pub async fn get_anchors(
     })
     .await?;
 
-    let ttl = cache.config.get_ttl("anchor");
-    let response = crate::http_cache::cached_json_response(&headers, &cache_key, &response, ttl)?;
+    let response = match selection {
+        Some(selection) => {
+            let response: AnchorsResponse = cached.decode()?;
+            let payload = serde_json::json!({
+                "anchors": selection.apply(&response.anchors)?,
+                "total": response.total,
+            });
+            let resource_key = format!("{}:fields:{}", cache_key, selection.cache_suffix());
+            crate::http_cache::cached_json_response(&headers, &resource_key, &payload, ttl)?
+        }
+        None => crate::http_cache::cached_bytes_response(&headers, &cache_key, &cached, ttl),
+    };
     Ok(response)
 }
 
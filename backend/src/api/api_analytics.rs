@@ -6,29 +6,50 @@ use axum::{
 };
 use std::sync::Arc;
 use crate::database::Database;
-use crate::models::{ApiAnalyticsOverview, EndpointStat, StatusStat};
+use crate::models::{ApiAnalyticsOverview, EndpointStat, RequestBucketStat, StatusStat};
+use crate::preferences::Preferences;
+use crate::time_range::TimeRange;
 
 /// Handler for GET /api/admin/analytics/overview
+///
+/// Accepts the shared `period`/`from`/`to`/`granularity` query parameters.
+/// Buckets and window bounds are expressed in the caller's preferred timezone.
 pub async fn get_analytics_overview(
     State(db): State<Arc<Database>>,
+    Preferences(prefs): Preferences,
+    range: TimeRange,
 ) -> Json<ApiAnalyticsOverview> {
     // 1. Total Requests
-    let total_requests: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_usage_stats")
-        .fetch_one(db.pool())
-        .await
-        .unwrap_or(0);
+    let total_requests: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM api_usage_stats WHERE timestamp >= $1 AND timestamp <= $2",
+    )
+    .bind(range.from)
+    .bind(range.to)
+    .fetch_one(db.pool())
+    .await
+    .unwrap_or(0);
 
     // 2. Avg Response Time
-    let avg_response_time_ms: f64 = sqlx::query_scalar("SELECT AVG(response_time_ms) FROM api_usage_stats")
-        .fetch_one(db.pool())
-        .await
-        .unwrap_or(0.0);
+    let avg_response_time_ms: f64 = sqlx::query_scalar(
+        "SELECT COALESCE(AVG(response_time_ms), 0.0) FROM api_usage_stats
+         WHERE timestamp >= $1 AND timestamp <= $2",
+    )
+    .bind(range.from)
+    .bind(range.to)
+    .fetch_one(db.pool())
+    .await
+    .unwrap_or(0.0);
 
     // 3. Error Rate (4xx and 5xx)
-    let error_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_usage_stats WHERE status_code >= 400")
-        .fetch_one(db.pool())
-        .await
-        .unwrap_or(0);
+    let error_count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM api_usage_stats
+         WHERE status_code >= 400 AND timestamp >= $1 AND timestamp <= $2",
+    )
+    .bind(range.from)
+    .bind(range.to)
+    .fetch_one(db.pool())
+    .await
+    .unwrap_or(0);
     
     let error_rate = if total_requests > 0 {
         (error_count as f64 / total_requests as f64) * 100.0
@@ -40,10 +61,13 @@ pub async fn get_analytics_overview(
     let top_endpoints = sqlx::query_as::<_, EndpointStat>(
         "SELECT endpoint, method, COUNT(*) as count, AVG(response_time_ms) as avg_response_time_ms 
          FROM api_usage_stats 
+         WHERE timestamp >= $1 AND timestamp <= $2
          GROUP BY endpoint, method 
          ORDER BY count DESC 
          LIMIT 10"
     )
+    .bind(range.from)
+    .bind(range.to)
     .fetch_all(db.pool())
     .await
     .unwrap_or_default();
@@ -52,19 +76,55 @@ pub async fn get_analytics_overview(
     let status_distribution = sqlx::query_as::<_, StatusStat>(
         "SELECT status_code, COUNT(*) as count 
          FROM api_usage_stats 
+         WHERE timestamp >= $1 AND timestamp <= $2
          GROUP BY status_code 
          ORDER BY count DESC"
     )
+    .bind(range.from)
+    .bind(range.to)
+    .fetch_all(db.pool())
+    .await
+    .unwrap_or_default();
+
+    // 6. Requests bucketed by the requested granularity, in the user's timezone
+    let offset_minutes = prefs.utc_offset_minutes(range.to);
+    let offset = format!("{:+} minutes", offset_minutes);
+    let bucket_format = if offset_minutes == 0 {
+        range.granularity.sqlite_bucket_format()
+    } else {
+        // Local bucket labels must not claim to be UTC
+        range.granularity.sqlite_bucket_format().trim_end_matches('Z')
+    };
+    let requests_over_time = sqlx::query_as::<_, RequestBucketStat>(
+        "SELECT strftime($1, timestamp, $4) as bucket,
+                COUNT(*) as count,
+                SUM(CASE WHEN status_code >= 400 THEN 1 ELSE 0 END) as error_count
+         FROM api_usage_stats
+         WHERE timestamp >= $2 AND timestamp <= $3
+         GROUP BY bucket
+         ORDER BY bucket ASC"
+    )
+    .bind(bucket_format)
+    .bind(range.from)
+    .bind(range.to)
+    .bind(offset)
     .fetch_all(db.pool())
     .await
     .unwrap_or_default();
 
     Json(ApiAnalyticsOverview {
+        from: range.from,
+        to: range.to,
+        timezone: prefs.timezone.clone(),
+        from_local: prefs.localize(range.from),
+        to_local: prefs.localize(range.to),
+        granularity: range.granularity.as_str().to_string(),
         total_requests,
         avg_response_time_ms,
         error_rate,
         top_endpoints,
         status_distribution,
+        requests_over_time,
     })
 }
 
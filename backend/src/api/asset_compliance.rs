@@ -0,0 +1,73 @@
+//! Clawback and freeze history of an asset, recorded during ledger ingestion
+//! by `services::asset_compliance`.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::asset_compliance::{
+    AssetComplianceEvent, AssetComplianceTracker, ComplianceAction,
+};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct ComplianceEventsParams {
+    pub limit: Option<i64>,
+    /// clawback, freeze, unfreeze or flags_changed
+    pub action: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComplianceEventsResponse {
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub events: Vec<AssetComplianceEvent>,
+}
+
+/// GET /api/assets/:code/:issuer/compliance-events - Clawbacks and trustline
+/// flag changes on the asset, newest first
+pub async fn get_compliance_events(
+    State(tracker): State<Arc<AssetComplianceTracker>>,
+    Path((asset_code, asset_issuer)): Path<(String, String)>,
+    Query(params): Query<ComplianceEventsParams>,
+) -> ApiResult<Json<ComplianceEventsResponse>> {
+    let action = match params.action.as_deref() {
+        None => None,
+        Some(raw) => Some(ComplianceAction::parse(raw).ok_or_else(|| {
+            ApiError::bad_request(
+                "INVALID_ACTION",
+                format!(
+                    "Unknown action '{}'; expected clawback, freeze, unfreeze or flags_changed",
+                    raw
+                ),
+            )
+        })?),
+    };
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let events = tracker
+        .get_events(&asset_code, &asset_issuer, action, limit)
+        .await?;
+
+    Ok(Json(ComplianceEventsResponse {
+        asset_code,
+        asset_issuer,
+        events,
+    }))
+}
+
+pub fn routes(tracker: Arc<AssetComplianceTracker>) -> Router {
+    Router::new()
+        .route(
+            "/api/assets/:code/:issuer/compliance-events",
+            get(get_compliance_events),
+        )
+        .with_state(tracker)
+}
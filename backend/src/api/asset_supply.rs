@@ -0,0 +1,82 @@
+//! Supply and holder distribution history of an anchor's assets, recorded by
+//! the asset supply tracker (`services::asset_supply`).
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::anchor_callbacks::find_anchor;
+use crate::database::Database;
+use crate::db::asset_supply::AssetSupplySnapshot;
+use crate::error::ApiResult;
+
+const DEFAULT_DAYS: i64 = 30;
+const MAX_DAYS: i64 = 365;
+
+#[derive(Debug, Deserialize)]
+pub struct SupplyParams {
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssetSupply {
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub latest: Option<AssetSupplySnapshot>,
+    pub history: Vec<AssetSupplySnapshot>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnchorSupplyResponse {
+    pub anchor_id: String,
+    pub days: i64,
+    pub assets: Vec<AssetSupply>,
+}
+
+/// GET /api/anchors/:id/supply - Supply, holders and top-10 concentration of
+/// each asset the anchor issues, over the last `days` (default 30)
+pub async fn get_anchor_supply(
+    State(db): State<Arc<Database>>,
+    Path(anchor_id): Path<String>,
+    Query(params): Query<SupplyParams>,
+) -> ApiResult<Json<AnchorSupplyResponse>> {
+    let anchor = find_anchor(&db, &anchor_id).await?;
+    let days = params.days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+    let since = Utc::now() - Duration::days(days);
+
+    let anchor_uuid = Uuid::parse_str(&anchor.id).map_err(anyhow::Error::from)?;
+    let history = db.asset_supply();
+    let mut assets = Vec::new();
+    for asset in db.get_assets_by_anchor(anchor_uuid).await? {
+        let latest = history
+            .latest(&asset.asset_code, &asset.asset_issuer)
+            .await?;
+        let snapshots = history
+            .history(&asset.asset_code, &asset.asset_issuer, since)
+            .await?;
+        assets.push(AssetSupply {
+            latest,
+            history: snapshots,
+            asset_code: asset.asset_code,
+            asset_issuer: asset.asset_issuer,
+        });
+    }
+
+    Ok(Json(AnchorSupplyResponse {
+        anchor_id: anchor.id,
+        days,
+        assets,
+    }))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/supply", get(get_anchor_supply))
+        .with_state(db)
+}
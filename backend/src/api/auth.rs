@@ -1,24 +1,53 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     routing::post,
     Json, Router,
 };
 use serde_json::json;
+use std::net::SocketAddr;
 use std::sync::Arc;
 
-use crate::auth::{AuthService, LoginRequest, LogoutRequest, RefreshTokenRequest};
+use crate::auth::{AuthService, LoginError, LoginRequest, LogoutRequest, RefreshTokenRequest};
+use crate::db::sessions::ClientInfo;
 use crate::error::ApiError;
 
 /// POST /api/auth/login - User login
 pub async fn login(
     State(auth_service): State<Arc<AuthService>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(request): Json<LoginRequest>,
 ) -> Result<Response, ApiError> {
-    let response = auth_service.login(request).await.map_err(|_| {
-        ApiError::unauthorized("INVALID_CREDENTIALS", "Invalid username or password")
-    })?;
+    let client = ClientInfo {
+        user_agent: headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+        ip_address: Some(addr.ip().to_string()),
+    };
+    let response = match auth_service.login(request, client).await {
+        Ok(response) => response,
+        Err(LoginError::Locked { retry_after_secs }) => {
+            let mut response = ApiError::too_many_requests(
+                "ACCOUNT_LOCKED",
+                "Too many failed login attempts. Try again later.",
+            )
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+            return Ok(response);
+        }
+        Err(LoginError::InvalidCredentials) => {
+            return Err(ApiError::unauthorized(
+                "INVALID_CREDENTIALS",
+                "Invalid username or password",
+            ))
+        }
+        Err(LoginError::Internal(e)) => return Err(e.into()),
+    };
 
     Ok((StatusCode::OK, Json(response)).into_response())
 }
@@ -0,0 +1,199 @@
+//! Embeddable SVG health badges.
+//!
+//! `GET /badges/corridor/:key.svg` renders a shields-style badge with the
+//! corridor's current health score, so anchors can show live corridor status
+//! in their own docs. Rendered badges are cached with the corridor metrics
+//! TTL and served with a matching `Cache-Control`; unknown corridors only for
+//! the not-found TTL.
+
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use crate::api::corridors::calculate_health_score;
+use crate::cache::{keys, CacheManager};
+use crate::cache_middleware::CacheAware;
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::models::corridor::{Corridor, CorridorKey};
+use crate::services::corridor_key_filter::corridor_key_filter;
+use crate::tenant::TenantScope;
+
+/// Days of daily aggregates searched for the latest corridor metrics
+const LOOKBACK_DAYS: i64 = 30;
+
+const GREEN: &str = "#4c1";
+const YELLOW: &str = "#dfb317";
+const RED: &str = "#e05d44";
+const GREY: &str = "#9f9f9f";
+
+/// Badge colour for a health score: green from 80, yellow from 50, else red
+pub fn score_color(score: f64) -> &'static str {
+    if score >= 80.0 {
+        GREEN
+    } else if score >= 50.0 {
+        YELLOW
+    } else {
+        RED
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Rough Verdana 11px text width, good enough to size the badge halves
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+/// Render a two-part badge: grey label on the left, coloured value on the right
+pub fn render_badge(label: &str, value: &str, color: &str) -> String {
+    let label_width = text_width(label);
+    let value_width = text_width(value);
+    let width = label_width + value_width;
+    let label = escape_xml(label);
+    let value = escape_xml(value);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">
+<title>{label}: {value}</title>
+<rect width="{label_width}" height="20" fill="#555"/>
+<rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="14">{label}</text>
+<text x="{value_x}" y="14">{value}</text>
+</g>
+</svg>"##,
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
+
+fn corridor_label(corridor: &Corridor) -> String {
+    format!("{}→{}", corridor.asset_a_code, corridor.asset_b_code)
+}
+
+/// Badge for a corridor, or None when it has no recent metrics
+async fn corridor_badge(db: &Database, corridor: &Corridor) -> anyhow::Result<Option<String>> {
+    let end_date = Utc::now().date_naive();
+    let start_date = end_date - Duration::days(LOOKBACK_DAYS);
+    let metrics = db
+        .corridor_aggregates()
+        .get_corridor_metrics(corridor, start_date, end_date)
+        .await?;
+
+    Ok(metrics.first().map(|latest| {
+        let score = calculate_health_score(
+            latest.success_rate,
+            latest.total_transactions,
+            latest.volume_usd,
+        );
+        render_badge(
+            &corridor_label(corridor),
+            &format!("{:.0}", score),
+            score_color(score),
+        )
+    }))
+}
+
+fn svg_response(svg: String, max_age: usize) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "image/svg+xml".to_string()),
+            (
+                header::CACHE_CONTROL,
+                format!("public, max-age={}", max_age),
+            ),
+        ],
+        svg,
+    )
+        .into_response()
+}
+
+/// GET /badges/corridor/:key.svg - SVG health badge for a corridor. Unknown
+/// corridors get a grey "unknown" badge, remembered for the short not-found
+/// TTL so crawlers probing random keys don't each reach the database. So do
+/// corridors outside the caller's tenant.
+pub async fn get_corridor_badge(
+    State((db, cache)): State<(Arc<Database>, Arc<CacheManager>)>,
+    tenant: TenantScope,
+    Path(file): Path<String>,
+) -> ApiResult<Response> {
+    let corridor_key = file.strip_suffix(".svg").ok_or_else(|| {
+        ApiError::not_found("BADGE_NOT_FOUND", "Badges are served as <corridor>.svg")
+    })?;
+    let unknown_ttl = cache.config.get_ttl("not_found");
+    let Ok(corridor_key) = corridor_key.parse::<CorridorKey>() else {
+        return Ok(svg_response(
+            render_badge("corridor", "unknown", GREY),
+            unknown_ttl,
+        ));
+    };
+
+    let corridor = corridor_key.corridor();
+    let outside_tenant = db
+        .tenants()
+        .corridor_keys(&tenant)
+        .await?
+        .is_some_and(|keys| !keys.contains(&corridor_key));
+    if outside_tenant || !corridor_key_filter().might_contain(corridor_key.as_str()) {
+        return Ok(svg_response(
+            render_badge(&corridor_label(&corridor), "unknown", GREY),
+            unknown_ttl,
+        ));
+    }
+
+    let cache_key = keys::corridor_badge(&corridor_key);
+    let ttl = cache.config.get_ttl("corridor");
+    if let Ok(Some(svg)) = cache.get::<String>(&cache_key).await {
+        return Ok(svg_response(svg, ttl));
+    }
+
+    match <()>::fetch_unless_missing(&cache, &cache_key, corridor_badge(&db, &corridor)).await? {
+        Some(svg) => {
+            let _ = cache.set(&cache_key, &svg, ttl).await;
+            Ok(svg_response(svg, ttl))
+        }
+        None => Ok(svg_response(
+            render_badge(&corridor_label(&corridor), "unknown", GREY),
+            unknown_ttl,
+        )),
+    }
+}
+
+pub fn routes(db: Arc<Database>, cache: Arc<CacheManager>) -> Router {
+    Router::new()
+        .route("/badges/corridor/:file", get(get_corridor_badge))
+        .with_state((db, cache))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_color_thresholds() {
+        assert_eq!(score_color(92.0), GREEN);
+        assert_eq!(score_color(80.0), GREEN);
+        assert_eq!(score_color(65.0), YELLOW);
+        assert_eq!(score_color(10.0), RED);
+    }
+
+    #[test]
+    fn test_render_badge_escapes_text() {
+        let svg = render_badge("USDC<script>", "92", GREEN);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("USDC&lt;script&gt;"));
+        assert!(svg.contains(r##"fill="#4c1""##));
+    }
+}
@@ -1,14 +1,20 @@
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::HeaderMap,
     response::{IntoResponse, Response},
     routing::get,
     Json, Router,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::cache::{CacheManager, CacheStats};
+use crate::cache::{CacheManager, CacheStats, KeyspaceSample, NamespaceStats, NamespaceUsage};
+
+const DEFAULT_HOT_KEYS: usize = 10;
+const MAX_HOT_KEYS: usize = 100;
+
+/// Keys scanned per request when sampling Redis memory by namespace
+const KEYSPACE_SAMPLE_SIZE: usize = 2_000;
 
 #[derive(Serialize)]
 pub struct CacheStatsResponse {
@@ -17,6 +23,86 @@ pub struct CacheStatsResponse {
     pub invalidations: u64,
     pub hit_rate_percent: f64,
     pub total_requests: u64,
+    /// Lookup counters per namespace (anchors, corridors, toml, orderbooks, ...)
+    pub namespaces: Vec<NamespaceStatsResponse>,
+    pub hot_keys: Vec<HotKeyResponse>,
+    /// Absent when Redis is unavailable
+    pub redis: Option<RedisUsageResponse>,
+}
+
+#[derive(Serialize)]
+pub struct NamespaceStatsResponse {
+    pub namespace: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+    pub hit_rate_percent: f64,
+}
+
+impl From<NamespaceStats> for NamespaceStatsResponse {
+    fn from(stats: NamespaceStats) -> Self {
+        Self {
+            hit_rate_percent: stats.hit_rate(),
+            namespace: stats.namespace,
+            hits: stats.hits,
+            misses: stats.misses,
+            invalidations: stats.invalidations,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct HotKeyResponse {
+    pub key: String,
+    pub hits: u64,
+}
+
+#[derive(Serialize)]
+pub struct RedisUsageResponse {
+    pub total_keys: u64,
+    /// Keys behind the namespace breakdown; below `total_keys` when sampled
+    pub scanned_keys: u64,
+    pub used_memory_bytes: u64,
+    /// Server-wide; Redis does not attribute evictions to keys
+    pub evicted_keys: u64,
+    pub expired_keys: u64,
+    pub namespaces: Vec<NamespaceUsageResponse>,
+}
+
+#[derive(Serialize)]
+pub struct NamespaceUsageResponse {
+    pub namespace: String,
+    pub keys: u64,
+    pub memory_bytes: u64,
+}
+
+impl From<NamespaceUsage> for NamespaceUsageResponse {
+    fn from(usage: NamespaceUsage) -> Self {
+        Self {
+            namespace: usage.namespace,
+            keys: usage.keys,
+            memory_bytes: usage.memory_bytes,
+        }
+    }
+}
+
+impl From<KeyspaceSample> for RedisUsageResponse {
+    fn from(sample: KeyspaceSample) -> Self {
+        Self {
+            total_keys: sample.total_keys,
+            scanned_keys: sample.scanned_keys,
+            used_memory_bytes: sample.used_memory_bytes,
+            evicted_keys: sample.evicted_keys,
+            expired_keys: sample.expired_keys,
+            namespaces: sample.namespaces.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CacheStatsQuery {
+    /// Number of hot keys to list (default 10, max 100)
+    pub top: Option<usize>,
 }
 
 impl From<CacheStats> for CacheStatsResponse {
@@ -28,19 +114,42 @@ impl From<CacheStats> for CacheStatsResponse {
             invalidations: stats.invalidations,
             hit_rate_percent: stats.hit_rate(),
             total_requests,
+            namespaces: Vec::new(),
+            hot_keys: Vec::new(),
+            redis: None,
         }
     }
 }
 
-/// Handler for GET /api/cache/stats - Get cache hit rate monitoring
+/// Handler for GET /api/cache/stats - Get cache hit rate monitoring, broken
+/// down by namespace, with the hottest keys and sampled Redis usage
 pub async fn get_cache_stats(
     State(cache): State<Arc<CacheManager>>,
+    Query(query): Query<CacheStatsQuery>,
     headers: HeaderMap,
 ) -> Response {
-    let stats = cache.get_stats();
-    let response = CacheStatsResponse::from(stats);
+    let top = query.top.unwrap_or(DEFAULT_HOT_KEYS).min(MAX_HOT_KEYS);
+    let mut response = CacheStatsResponse::from(cache.get_stats());
+    response.namespaces = cache
+        .namespace_stats()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    response.hot_keys = cache
+        .hot_keys(top)
+        .into_iter()
+        .map(|k| HotKeyResponse {
+            key: k.key,
+            hits: k.hits,
+        })
+        .collect();
+    response.redis = cache
+        .sample_keyspace(KEYSPACE_SAMPLE_SIZE)
+        .await
+        .map(Into::into);
 
-    match crate::http_cache::cached_json_response(&headers, "cache:stats", &response, 30) {
+    let resource_key = format!("cache:stats:{}", top);
+    match crate::http_cache::cached_json_response(&headers, &resource_key, &response, 30) {
         Ok(resp) => resp,
         Err(e) => (
             axum::http::StatusCode::INTERNAL_SERVER_ERROR,
@@ -0,0 +1,165 @@
+//! Anomalous intervals of a corridor.
+//!
+//! Each hour of the corridor's volume and success rate is scored against the
+//! model trained on its history by the weekly ml-anomaly-retrain job (see
+//! [`crate::ml::anomaly`]); consecutive anomalous hours are returned as one
+//! interval.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::ml::anomaly::{AnomalyInterval, AnomalyService};
+use crate::models::corridor::CorridorKey;
+use crate::plan::Plan;
+use crate::tenant::TenantScope;
+
+const DEFAULT_DAYS: i64 = 7;
+const MAX_DAYS: i64 = 90;
+
+type AnomalyState = (Arc<Database>, Arc<AnomalyService>);
+
+#[derive(Debug, Deserialize)]
+pub struct AnomaliesParams {
+    /// Days of history, ending now
+    pub days: Option<i64>,
+}
+
+/// Consecutive hours the model found anomalous
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct AnomalyIntervalResponse {
+    /// Start of the first hour
+    #[schema(example = "2024-01-15T10:00:00+00:00")]
+    pub start: String,
+    /// End of the last hour
+    #[schema(example = "2024-01-15T13:00:00+00:00")]
+    pub end: String,
+    /// Highest anomaly score of its hours
+    #[schema(example = 6.2)]
+    pub peak_score: f64,
+    #[schema(example = 125000.0)]
+    pub volume_usd: f64,
+    /// Share of successful payments, 0-1; absent without payments
+    #[schema(example = 0.62)]
+    pub success_rate: Option<f64>,
+}
+
+impl From<AnomalyInterval> for AnomalyIntervalResponse {
+    fn from(interval: AnomalyInterval) -> Self {
+        Self {
+            start: interval.start.to_rfc3339(),
+            end: interval.end.to_rfc3339(),
+            peak_score: interval.peak_score,
+            volume_usd: interval.volume_usd,
+            success_rate: interval.success_rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct CorridorAnomaliesResponse {
+    pub corridor_key: String,
+    /// Detector that trained the model: `robust_z` or `isolation_forest`
+    #[schema(example = "robust_z")]
+    pub model: String,
+    /// Number of times the corridor's model has been trained
+    pub model_version: i64,
+    pub trained_at: String,
+    /// Score at or above which an hour is anomalous
+    pub threshold: f64,
+    /// Oldest first
+    pub intervals: Vec<AnomalyIntervalResponse>,
+}
+
+/// GET /api/corridors/:corridor_key/anomalies - Intervals where the
+/// corridor's volume or success rate departed from its usual behaviour
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/anomalies",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor key"),
+        ("days" = Option<i64>, Query, description = "Days of history, ending now (1-90, default 7)")
+    ),
+    responses(
+        (status = 200, description = "Anomalous intervals", body = CorridorAnomaliesResponse),
+        (status = 400, description = "Invalid corridor key or day count"),
+        (status = 403, description = "Window exceeds the plan's history limit"),
+        (status = 404, description = "Corridor not found or no model trained for it yet"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Corridors"
+)]
+pub async fn get_corridor_anomalies(
+    State((db, anomalies)): State<AnomalyState>,
+    tenant: TenantScope,
+    plan: Plan,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<AnomaliesParams>,
+) -> ApiResult<Json<CorridorAnomaliesResponse>> {
+    let corridor_key: CorridorKey = corridor_key.parse()?;
+    if let Some(tenant_keys) = db.tenants().corridor_keys(&tenant).await? {
+        if !tenant_keys.contains(&corridor_key) {
+            return Err(ApiError::not_found(
+                "CORRIDOR_NOT_FOUND",
+                format!("Corridor {} not found", corridor_key),
+            ));
+        }
+    }
+
+    let days = params.days.unwrap_or(DEFAULT_DAYS);
+    if !(1..=MAX_DAYS).contains(&days) {
+        return Err(ApiError::bad_request(
+            "INVALID_INPUT",
+            format!("days must be between 1 and {}", MAX_DAYS),
+        ));
+    }
+    plan.check_history(Duration::days(days))?;
+
+    let result = anomalies
+        .anomalies(corridor_key.as_str(), Utc::now() - Duration::days(days))
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to score anomalies for corridor {}: {}",
+                corridor_key,
+                e
+            );
+            ApiError::internal("ANOMALY_ERROR", "Failed to detect corridor anomalies")
+        })?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ANOMALY_MODEL_NOT_FOUND",
+                format!("No anomaly model trained for corridor {} yet", corridor_key),
+            )
+        })?;
+
+    Ok(Json(CorridorAnomaliesResponse {
+        corridor_key: corridor_key.to_string(),
+        model: result.model,
+        model_version: result.model_version,
+        trained_at: result.trained_at,
+        threshold: result.threshold,
+        intervals: result
+            .intervals
+            .into_iter()
+            .map(AnomalyIntervalResponse::from)
+            .collect(),
+    }))
+}
+
+pub fn routes(db: Arc<Database>, anomalies: Arc<AnomalyService>) -> Router {
+    Router::new()
+        .route(
+            "/api/corridors/:corridor_key/anomalies",
+            get(get_corridor_anomalies),
+        )
+        .with_state((db, anomalies))
+}
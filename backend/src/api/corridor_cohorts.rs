@@ -0,0 +1,261 @@
+//! Cohort retention of a corridor's sending accounts.
+//!
+//! Accounts are grouped by the month they first sent a payment in the
+//! corridor; each cohort reports how many of its accounts sent again in each
+//! later month, so an anchor can tell one-off senders from repeat remitters.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::database::Database;
+use crate::db::corridor_cohorts::CohortActivity;
+use crate::error::{ApiError, ApiResult};
+use crate::models::corridor::CorridorKey;
+use crate::plan::Plan;
+use crate::tenant::TenantScope;
+
+const DEFAULT_MONTHS: u32 = 12;
+const MAX_MONTHS: u32 = 24;
+
+#[derive(Debug, Deserialize)]
+pub struct CohortParams {
+    /// Cohorts to include, ending with the current month
+    pub months: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct Cohort {
+    /// Month the accounts first sent in the corridor, `YYYY-MM`
+    pub month: String,
+    /// Accounts in the cohort
+    pub accounts: i64,
+    /// Accounts that sent in the cohort month and each month after it, up to
+    /// the current (partial) month
+    pub active_accounts: Vec<i64>,
+    /// `active_accounts` as a fraction of `accounts`
+    pub retention: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct CohortRetentionResponse {
+    pub corridor_key: String,
+    /// Oldest cohort first
+    pub cohorts: Vec<Cohort>,
+    /// Retention by months since the first payment, across the cohorts old
+    /// enough to have reached that month and weighted by cohort size
+    pub average_retention: Vec<f64>,
+}
+
+/// Months since year 0 for a `YYYY-MM` month
+fn month_index(month: &str) -> Option<i32> {
+    let (year, month) = month.split_once('-')?;
+    let year: i32 = year.parse().ok()?;
+    let month: i32 = month.parse().ok()?;
+    (1..=12).contains(&month).then_some(year * 12 + month - 1)
+}
+
+fn month_label(index: i32) -> String {
+    format!(
+        "{:04}-{:02}",
+        index.div_euclid(12),
+        index.rem_euclid(12) + 1
+    )
+}
+
+/// Retention matrix for the cohorts in `activity`, each covering the months
+/// up to `current_month` (a [`month_index`])
+fn build_cohorts(activity: &[CohortActivity], current_month: i32) -> (Vec<Cohort>, Vec<f64>) {
+    let mut cohorts: Vec<Cohort> = Vec::new();
+    for row in activity {
+        let (Some(start), Some(month)) = (month_index(&row.cohort), month_index(&row.month)) else {
+            continue;
+        };
+        if cohorts.last().map(|c| c.month.as_str()) != Some(row.cohort.as_str()) {
+            let months = (current_month - start + 1).max(1) as usize;
+            cohorts.push(Cohort {
+                month: row.cohort.clone(),
+                active_accounts: vec![0; months],
+                ..Default::default()
+            });
+        }
+        let cohort = cohorts.last_mut().expect("cohort was just pushed");
+        if let Some(active) = cohort.active_accounts.get_mut((month - start) as usize) {
+            *active = row.accounts;
+        }
+    }
+
+    let mut active_totals: Vec<i64> = Vec::new();
+    let mut account_totals: Vec<i64> = Vec::new();
+    for cohort in &mut cohorts {
+        // Every account is active in its first month
+        cohort.accounts = cohort.active_accounts[0];
+        cohort.retention = cohort
+            .active_accounts
+            .iter()
+            .map(|active| *active as f64 / cohort.accounts.max(1) as f64)
+            .collect();
+        for (offset, active) in cohort.active_accounts.iter().enumerate() {
+            if offset == active_totals.len() {
+                active_totals.push(0);
+                account_totals.push(0);
+            }
+            active_totals[offset] += active;
+            account_totals[offset] += cohort.accounts;
+        }
+    }
+
+    let average_retention = active_totals
+        .iter()
+        .zip(&account_totals)
+        .map(|(active, accounts)| *active as f64 / (*accounts).max(1) as f64)
+        .collect();
+    (cohorts, average_retention)
+}
+
+/// GET /api/corridors/:corridor_key/cohorts - Monthly retention of the
+/// corridor's sending accounts, by the month they first sent
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/cohorts",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor key"),
+        ("months" = Option<u32>, Query, description = "Cohorts to include, ending with the current month (1-24, default 12)")
+    ),
+    responses(
+        (status = 200, description = "Cohort retention matrix", body = CohortRetentionResponse),
+        (status = 400, description = "Invalid corridor key or month count"),
+        (status = 403, description = "Window exceeds the plan's history limit"),
+        (status = 404, description = "Corridor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Corridors"
+)]
+pub async fn get_corridor_cohorts(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    plan: Plan,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<CohortParams>,
+) -> ApiResult<Json<CohortRetentionResponse>> {
+    let corridor_key: CorridorKey = corridor_key.parse()?;
+    if let Some(tenant_keys) = db.tenants().corridor_keys(&tenant).await? {
+        if !tenant_keys.contains(&corridor_key) {
+            return Err(ApiError::not_found(
+                "CORRIDOR_NOT_FOUND",
+                format!("Corridor {} not found", corridor_key),
+            ));
+        }
+    }
+
+    let months = params.months.unwrap_or(DEFAULT_MONTHS);
+    if !(1..=MAX_MONTHS).contains(&months) {
+        return Err(ApiError::bad_request(
+            "INVALID_INPUT",
+            format!("months must be between 1 and {}", MAX_MONTHS),
+        ));
+    }
+    let now = Utc::now();
+    let current_month = now.year() * 12 + now.month0() as i32;
+    let first_month = current_month - (months as i32 - 1);
+    let window_start = Utc
+        .with_ymd_and_hms(
+            first_month.div_euclid(12),
+            first_month.rem_euclid(12) as u32 + 1,
+            1,
+            0,
+            0,
+            0,
+        )
+        .single()
+        .unwrap_or(now);
+    plan.check_history(now - window_start)?;
+
+    let activity = db
+        .corridor_cohorts()
+        .activity(corridor_key.as_str(), &month_label(first_month))
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to load cohorts for corridor {}: {}",
+                corridor_key,
+                e
+            );
+            ApiError::internal("COHORTS_ERROR", "Failed to load corridor cohorts")
+        })?;
+    if activity.is_empty() {
+        return Err(ApiError::not_found(
+            "CORRIDOR_NOT_FOUND",
+            format!(
+                "No sending accounts recorded for corridor {} in this window",
+                corridor_key
+            ),
+        ));
+    }
+
+    let (cohorts, average_retention) = build_cohorts(&activity, current_month);
+    Ok(Json(CohortRetentionResponse {
+        corridor_key: corridor_key.to_string(),
+        cohorts,
+        average_retention,
+    }))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route(
+            "/api/corridors/:corridor_key/cohorts",
+            get(get_corridor_cohorts),
+        )
+        .with_state(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cohort: &str, month: &str, accounts: i64) -> CohortActivity {
+        CohortActivity {
+            cohort: cohort.to_string(),
+            month: month.to_string(),
+            accounts,
+        }
+    }
+
+    #[test]
+    fn test_month_index_round_trips() {
+        let index = month_index("2024-12").unwrap();
+        assert_eq!(month_label(index), "2024-12");
+        assert_eq!(month_label(index + 1), "2025-01");
+        assert_eq!(month_index("2024-13"), None);
+        assert_eq!(month_index("2024"), None);
+    }
+
+    #[test]
+    fn test_build_cohorts_fills_quiet_months() {
+        let activity = [
+            row("2024-01", "2024-01", 10),
+            row("2024-01", "2024-03", 4),
+            row("2024-02", "2024-02", 5),
+            row("2024-02", "2024-03", 5),
+        ];
+        let (cohorts, average) = build_cohorts(&activity, month_index("2024-03").unwrap());
+
+        assert_eq!(cohorts.len(), 2);
+        assert_eq!(cohorts[0].month, "2024-01");
+        assert_eq!(cohorts[0].accounts, 10);
+        assert_eq!(cohorts[0].active_accounts, vec![10, 0, 4]);
+        assert_eq!(cohorts[0].retention, vec![1.0, 0.0, 0.4]);
+        assert_eq!(cohorts[1].active_accounts, vec![5, 5]);
+        assert_eq!(cohorts[1].retention, vec![1.0, 1.0]);
+
+        // Month 1: 5 of the 15 accounts in both cohorts; month 2: January only
+        assert_eq!(average, vec![1.0, 5.0 / 15.0, 0.4]);
+    }
+}
@@ -0,0 +1,158 @@
+//! Health score history of a corridor.
+//!
+//! Scores are computed hourly by the health-score job (see
+//! [`crate::services::health_score`]); each point carries the score and the
+//! components it was weighted from, so a drop can be traced to its cause.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::database::Database;
+use crate::db::health_scores::CorridorHealthScore;
+use crate::error::{ApiError, ApiResult};
+use crate::models::corridor::CorridorKey;
+use crate::plan::Plan;
+use crate::tenant::TenantScope;
+
+const DEFAULT_DAYS: i64 = 30;
+const MAX_DAYS: i64 = 90;
+
+#[derive(Debug, Deserialize)]
+pub struct HealthHistoryParams {
+    /// Days of history, ending now
+    pub days: Option<i64>,
+}
+
+/// One computed score. Components are 0-100 and absent when the corridor
+/// had no data for them.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct HealthPoint {
+    #[schema(example = "2024-01-15T10:00:00+00:00")]
+    pub computed_at: String,
+    /// Weighted average of the components present, 0-100
+    #[schema(example = 87.5)]
+    pub score: f64,
+    /// Share of successful payments over the previous 24 hours
+    #[schema(example = 98.0)]
+    pub success_rate: Option<f64>,
+    /// Average liquidity depth on a log scale
+    #[schema(example = 72.4)]
+    pub liquidity: Option<f64>,
+    /// 50 for steady volume against the 24 hours before, 100 for doubled, 0 for stopped
+    #[schema(example = 55.0)]
+    pub volume_trend: Option<f64>,
+    /// Average reliability of the anchors issuing either asset
+    #[schema(example = 95.0)]
+    pub anchor_reliability: Option<f64>,
+}
+
+impl From<CorridorHealthScore> for HealthPoint {
+    fn from(row: CorridorHealthScore) -> Self {
+        Self {
+            computed_at: row.computed_at,
+            score: row.score,
+            success_rate: row.success_rate_score,
+            liquidity: row.liquidity_score,
+            volume_trend: row.volume_trend_score,
+            anchor_reliability: row.anchor_reliability_score,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct HealthHistoryResponse {
+    pub corridor_key: String,
+    /// Oldest first
+    pub history: Vec<HealthPoint>,
+}
+
+/// GET /api/corridors/:corridor_key/health-history - The corridor's health
+/// scores and their components over time
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/health-history",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor key"),
+        ("days" = Option<i64>, Query, description = "Days of history, ending now (1-90, default 30)")
+    ),
+    responses(
+        (status = 200, description = "Health score history", body = HealthHistoryResponse),
+        (status = 400, description = "Invalid corridor key or day count"),
+        (status = 403, description = "Window exceeds the plan's history limit"),
+        (status = 404, description = "Corridor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Corridors"
+)]
+pub async fn get_corridor_health_history(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    plan: Plan,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<HealthHistoryParams>,
+) -> ApiResult<Json<HealthHistoryResponse>> {
+    let corridor_key: CorridorKey = corridor_key.parse()?;
+    if let Some(tenant_keys) = db.tenants().corridor_keys(&tenant).await? {
+        if !tenant_keys.contains(&corridor_key) {
+            return Err(ApiError::not_found(
+                "CORRIDOR_NOT_FOUND",
+                format!("Corridor {} not found", corridor_key),
+            ));
+        }
+    }
+
+    let days = params.days.unwrap_or(DEFAULT_DAYS);
+    if !(1..=MAX_DAYS).contains(&days) {
+        return Err(ApiError::bad_request(
+            "INVALID_INPUT",
+            format!("days must be between 1 and {}", MAX_DAYS),
+        ));
+    }
+    plan.check_history(Duration::days(days))?;
+
+    let history = db
+        .corridor_health_scores()
+        .history(corridor_key.as_str(), Utc::now() - Duration::days(days))
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to load health history for corridor {}: {}",
+                corridor_key,
+                e
+            );
+            ApiError::internal(
+                "HEALTH_HISTORY_ERROR",
+                "Failed to load corridor health history",
+            )
+        })?;
+    if history.is_empty() {
+        return Err(ApiError::not_found(
+            "CORRIDOR_NOT_FOUND",
+            format!(
+                "No health scores recorded for corridor {} in this window",
+                corridor_key
+            ),
+        ));
+    }
+
+    Ok(Json(HealthHistoryResponse {
+        corridor_key: corridor_key.to_string(),
+        history: history.into_iter().map(HealthPoint::from).collect(),
+    }))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route(
+            "/api/corridors/:corridor_key/health-history",
+            get(get_corridor_health_history),
+        )
+        .with_state(db)
+}
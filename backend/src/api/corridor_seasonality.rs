@@ -0,0 +1,300 @@
+//! Seasonality of a corridor's volume.
+//!
+//! Decomposes hourly USD volume over the requested window into trend,
+//! day-of-week and hour-of-day components (see [`crate::analytics::stats`]),
+//! so a client can tell whether a drop is normal for the time of week: the
+//! `latest` hour carries the volume the seasonal pattern predicts and how far
+//! the actual volume is from it in residual standard deviations.
+
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Datelike, Duration, DurationRound, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::analytics::stats;
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::models::corridor::CorridorKey;
+use crate::tenant::TenantScope;
+use crate::time_range::TimeRange;
+
+/// Shortest window with enough weeks to separate weekday effects from trend
+const MIN_SPAN_DAYS: i64 = 14;
+
+/// Longest window served; the series is hourly
+const MAX_SPAN_DAYS: i64 = 90;
+
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct HourOfDayEffect {
+    /// Hour of the day (UTC), 0-23
+    pub hour: u32,
+    /// Average volume above (or below) trend in this hour
+    pub effect_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct WeekdayEffect {
+    /// `monday` to `sunday`
+    pub weekday: String,
+    /// Average hourly volume above (or below) trend on this day
+    pub effect_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct SeasonalityPoint {
+    /// Start of the hour
+    pub hour: String,
+    pub volume_usd: f64,
+    pub trend_usd: f64,
+    pub weekly_usd: f64,
+    pub daily_usd: f64,
+    pub residual_usd: f64,
+}
+
+/// The most recent complete hour against its seasonal expectation
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct LatestHour {
+    pub hour: String,
+    pub weekday: String,
+    pub volume_usd: f64,
+    /// Trend plus the weekday and hour-of-day effects
+    pub expected_usd: f64,
+    /// Residual in standard deviations; `None` when the residual is flat
+    pub z_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct SeasonalityResponse {
+    pub corridor_key: String,
+    pub from: String,
+    pub to: String,
+    pub daily_profile: Vec<HourOfDayEffect>,
+    pub weekly_profile: Vec<WeekdayEffect>,
+    /// Spread of the residual; deviations within about two of these are normal
+    pub residual_std_usd: f64,
+    pub latest: Option<LatestHour>,
+    /// Hourly volume and its components, oldest first
+    pub points: Vec<SeasonalityPoint>,
+}
+
+/// Hours `[from, to)` with their volume; hours without a row count as zero
+fn hourly_series(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    rows: &[(DateTime<Utc>, f64)],
+) -> Vec<f64> {
+    let hours = (to - from).num_hours().max(0) as usize;
+    let mut series = vec![0.0; hours];
+    for (hour, volume) in rows {
+        let index = (*hour - from).num_hours();
+        if (0..hours as i64).contains(&index) {
+            series[index as usize] += volume;
+        }
+    }
+    series
+}
+
+fn build_response(
+    corridor_key: &CorridorKey,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    series: &[f64],
+) -> SeasonalityResponse {
+    let decomposition = stats::decompose_hourly(from, series);
+    let residual_std_usd = stats::std_dev(&decomposition.residual);
+    let hour_at = |i: usize| from + Duration::hours(i as i64);
+
+    let points: Vec<SeasonalityPoint> = series
+        .iter()
+        .enumerate()
+        .map(|(i, volume)| SeasonalityPoint {
+            hour: hour_at(i).to_rfc3339(),
+            volume_usd: *volume,
+            trend_usd: decomposition.trend[i],
+            weekly_usd: decomposition.weekly[i],
+            daily_usd: decomposition.daily[i],
+            residual_usd: decomposition.residual[i],
+        })
+        .collect();
+
+    let latest = points.last().map(|point| {
+        let i = points.len() - 1;
+        LatestHour {
+            hour: point.hour.clone(),
+            weekday: WEEKDAYS[hour_at(i).weekday().num_days_from_monday() as usize].to_string(),
+            volume_usd: point.volume_usd,
+            expected_usd: point.trend_usd + point.weekly_usd + point.daily_usd,
+            z_score: (residual_std_usd > 0.0).then(|| point.residual_usd / residual_std_usd),
+        }
+    });
+
+    SeasonalityResponse {
+        corridor_key: corridor_key.to_string(),
+        from: from.to_rfc3339(),
+        to: to.to_rfc3339(),
+        daily_profile: decomposition
+            .daily_profile
+            .iter()
+            .enumerate()
+            .map(|(hour, effect)| HourOfDayEffect {
+                hour: hour as u32,
+                effect_usd: *effect,
+            })
+            .collect(),
+        weekly_profile: decomposition
+            .weekly_profile
+            .iter()
+            .zip(WEEKDAYS)
+            .map(|(effect, weekday)| WeekdayEffect {
+                weekday: weekday.to_string(),
+                effect_usd: *effect,
+            })
+            .collect(),
+        residual_std_usd,
+        latest,
+        points,
+    }
+}
+
+/// GET /api/corridors/:corridor_key/seasonality - Trend, weekday and
+/// hour-of-day components of a corridor's hourly volume
+///
+/// Accepts the shared `period`/`from`/`to` query parameters; the window must
+/// span 14 to 90 days and only complete hours are included.
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/seasonality",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor key"),
+        ("period" = Option<String>, Query, description = "Window ending now (30d or 90d)"),
+        ("from" = Option<String>, Query, description = "Start of the window (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "End of the window (RFC 3339, defaults to now)")
+    ),
+    responses(
+        (status = 200, description = "Seasonal decomposition of hourly volume", body = SeasonalityResponse),
+        (status = 400, description = "Invalid corridor key or window"),
+        (status = 404, description = "Corridor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Corridors"
+)]
+pub async fn get_corridor_seasonality(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    Path(corridor_key): Path<String>,
+    range: TimeRange,
+) -> ApiResult<Json<SeasonalityResponse>> {
+    let corridor_key: CorridorKey = corridor_key.parse()?;
+    if let Some(tenant_keys) = db.tenants().corridor_keys(&tenant).await? {
+        if !tenant_keys.contains(&corridor_key) {
+            return Err(ApiError::not_found(
+                "CORRIDOR_NOT_FOUND",
+                format!("Corridor {} not found", corridor_key),
+            ));
+        }
+    }
+
+    let span = range.span();
+    if span < Duration::days(MIN_SPAN_DAYS) || span > Duration::days(MAX_SPAN_DAYS) {
+        return Err(ApiError::bad_request(
+            "INVALID_TIME_RANGE",
+            format!(
+                "Seasonality needs a window of {} to {} days",
+                MIN_SPAN_DAYS, MAX_SPAN_DAYS
+            ),
+        ));
+    }
+    let hour = Duration::hours(1);
+    let from = range.from.duration_trunc(hour).unwrap_or(range.from);
+    let to = range.to.duration_trunc(hour).unwrap_or(range.to);
+
+    let rows = db
+        .aggregation_db()
+        .fetch_corridor_hourly_volume(corridor_key.as_str(), from, to)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load volume for corridor {}: {}", corridor_key, e);
+            ApiError::internal("SEASONALITY_ERROR", "Failed to load corridor volume")
+        })?;
+    if rows.is_empty() {
+        return Err(ApiError::not_found(
+            "CORRIDOR_NOT_FOUND",
+            format!(
+                "No volume recorded for corridor {} in this window",
+                corridor_key
+            ),
+        ));
+    }
+
+    let series = hourly_series(from, to, &rows);
+    Ok(Json(build_response(&corridor_key, from, to, &series)))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route(
+            "/api/corridors/:corridor_key/seasonality",
+            get(get_corridor_seasonality),
+        )
+        .with_state(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::stats::HOURS_PER_WEEK;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_hourly_series_fills_quiet_hours() {
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let to = from + Duration::hours(4);
+        let rows = [
+            (from + Duration::hours(1), 10.0),
+            (from + Duration::hours(3), 5.0),
+            // Outside the window
+            (to, 99.0),
+        ];
+        assert_eq!(hourly_series(from, to, &rows), vec![0.0, 10.0, 0.0, 5.0]);
+    }
+
+    #[test]
+    fn test_latest_hour_flags_unusual_sunday() {
+        // Four weeks of steady weekdays and quiet Sundays, ending on a
+        // Sunday evening with weekday-level volume
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let hours = 4 * HOURS_PER_WEEK;
+        let mut series: Vec<f64> = (0..hours)
+            .map(|i| if (i / 24) % 7 == 6 { 20.0 } else { 100.0 })
+            .collect();
+        *series.last_mut().unwrap() = 100.0;
+        let to = from + Duration::hours(hours as i64);
+        let key: CorridorKey =
+            "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN->XLM:native"
+                .parse()
+                .unwrap();
+
+        let response = build_response(&key, from, to, &series);
+        assert_eq!(response.weekly_profile[6].weekday, "sunday");
+        assert!(response.weekly_profile[6].effect_usd < -50.0);
+        let latest = response.latest.unwrap();
+        assert_eq!(latest.weekday, "sunday");
+        assert!(latest.expected_usd < 50.0);
+        assert!(latest.z_score.unwrap() > 3.0);
+    }
+}
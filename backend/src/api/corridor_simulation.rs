@@ -0,0 +1,52 @@
+//! Corridor simulation for asset pairs we don't track yet.
+
+use axum::{extract::State, routing::post, Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use stellar_insights_core::Asset;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::corridor_simulation::{CorridorSimulation, CorridorSimulator};
+
+#[derive(Debug, Deserialize)]
+pub struct SimulateCorridorRequest {
+    /// `native` or `CODE:ISSUER`
+    pub source_asset: String,
+    pub destination_asset: String,
+    /// Source units to send
+    pub amount: f64,
+}
+
+/// POST /api/tools/simulate-corridor - Path finding, order book depth and
+/// cost estimate for sending `amount` between two arbitrary assets
+pub async fn simulate_corridor(
+    State(simulator): State<Arc<CorridorSimulator>>,
+    Json(request): Json<SimulateCorridorRequest>,
+) -> ApiResult<Json<CorridorSimulation>> {
+    let invalid = |e: String| ApiError::bad_request("INVALID_ASSET", e);
+    let source: Asset = request.source_asset.parse().map_err(invalid)?;
+    let destination: Asset = request.destination_asset.parse().map_err(invalid)?;
+    if source == destination {
+        return Err(ApiError::bad_request(
+            "INVALID_ASSET",
+            "source_asset and destination_asset must differ",
+        ));
+    }
+    if !request.amount.is_finite() || request.amount <= 0.0 {
+        return Err(ApiError::bad_request(
+            "INVALID_INPUT",
+            "amount must be a positive number",
+        ));
+    }
+
+    let simulation = simulator
+        .simulate(&source, &destination, request.amount)
+        .await?;
+    Ok(Json(simulation))
+}
+
+pub fn routes(simulator: Arc<CorridorSimulator>) -> Router {
+    Router::new()
+        .route("/api/tools/simulate-corridor", post(simulate_corridor))
+        .with_state(simulator)
+}
@@ -7,9 +7,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::{ApiError, ApiResult};
-use crate::models::corridor::{Corridor, CorridorMetrics};
-use crate::models::SortBy;
+use crate::models::corridor::{CorridorKey, CorridorMetrics};
+use crate::models::{MetricSource, SortBy};
 use crate::state::AppState;
+use crate::time_range::Period;
 
 // Response DTOs matching frontend TypeScript interfaces
 
@@ -31,6 +32,9 @@ pub struct CorridorResponse {
     pub liquidity_trend: String,
     pub health_score: f64,
     pub last_updated: String,
+    /// Least trusted source behind these figures
+    #[serde(default)]
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,7 +81,9 @@ pub struct ListCorridorsQuery {
     pub volume_min: Option<f64>,
     pub volume_max: Option<f64>,
     pub asset_code: Option<String>,
-    pub time_period: Option<String>, // "7d", "30d", "90d"
+    pub time_period: Option<String>, // "24h", "7d", "30d", "90d"
+    /// Drop corridors whose metrics are less trusted than this source
+    pub min_source: Option<MetricSource>,
 }
 
 fn default_limit() -> i64 {
@@ -85,7 +91,7 @@ fn default_limit() -> i64 {
 }
 
 /// Calculate health score based on success rate, volume, and transaction count
-fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
+pub(crate) fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
     let success_weight = 0.6;
     let volume_weight = 0.2;
     let transaction_weight = 0.2;
@@ -127,14 +133,21 @@ pub async fn list_corridors(
     let today = Utc::now().date_naive();
 
     // Determine date range based on time_period
-    let (start_date, end_date) = match params.time_period.as_deref() {
-        Some("7d") => (today - Duration::days(7), today),
-        Some("30d") => (today - Duration::days(30), today),
-        Some("90d") => (today - Duration::days(90), today),
-        _ => (today, today), // Default to today
+    let period = match params.time_period.as_deref() {
+        Some(raw) => Some(Period::parse(raw).ok_or_else(|| {
+            ApiError::bad_request(
+                "INVALID_TIME_RANGE",
+                format!("Unsupported time_period '{}': expected 24h, 7d, 30d or 90d", raw),
+            )
+        })?),
+        None => None,
+    };
+    let (start_date, end_date) = match period {
+        Some(period) => ((Utc::now() - period.duration()).date_naive(), today),
+        None => (today, today), // Default to today
     };
 
-    let metrics = if params.time_period.is_some() {
+    let metrics = if period.is_some() {
         // Use aggregated metrics for time periods
         let aggregated = app_state
             .db
@@ -169,6 +182,7 @@ pub async fn list_corridors(
                 liquidity_depth_usd: m.total_volume_usd,
                 created_at: m.latest_date,
                 updated_at: m.latest_date,
+                source: m.source,
             })
             .collect()
     } else {
@@ -190,6 +204,12 @@ pub async fn list_corridors(
     let filtered_metrics: Vec<_> = metrics
         .into_iter()
         .filter(|m| {
+            if let Some(min) = params.min_source {
+                if !m.source.meets(min) {
+                    return false;
+                }
+            }
+
             // Success rate filter
             if let Some(min) = params.success_rate_min {
                 if m.success_rate < min {
@@ -253,6 +273,7 @@ pub async fn list_corridors(
                 liquidity_trend,
                 health_score,
                 last_updated: m.updated_at.to_rfc3339(),
+                source: m.source,
             }
         })
         .collect();
@@ -265,30 +286,7 @@ pub async fn get_corridor_detail(
     State(app_state): State<AppState>,
     Path(corridor_key): Path<String>,
 ) -> ApiResult<Json<CorridorDetailResponse>> {
-    let parts: Vec<&str> = corridor_key.split("->").collect();
-    if parts.len() != 2 {
-        return Err(ApiError::bad_request(
-            "INVALID_CORRIDOR_FORMAT",
-            "Invalid corridor key format",
-        ));
-    }
-
-    let asset_a_parts: Vec<&str> = parts[0].split(':').collect();
-    let asset_b_parts: Vec<&str> = parts[1].split(':').collect();
-
-    if asset_a_parts.len() != 2 || asset_b_parts.len() != 2 {
-        return Err(ApiError::bad_request(
-            "INVALID_CORRIDOR_FORMAT",
-            "Invalid corridor key format",
-        ));
-    }
-
-    let corridor = Corridor::new(
-        asset_a_parts[0].to_string(),
-        asset_a_parts[1].to_string(),
-        asset_b_parts[0].to_string(),
-        asset_b_parts[1].to_string(),
-    );
+    let corridor = corridor_key.parse::<CorridorKey>()?.corridor();
 
     let end_date = Utc::now().date_naive();
     let start_date = end_date - Duration::days(30);
@@ -341,6 +339,7 @@ pub async fn get_corridor_detail(
         liquidity_trend,
         health_score,
         last_updated: latest.updated_at.to_rfc3339(),
+        source: latest.source,
     };
 
     let historical_success_rate: Vec<SuccessRateDataPoint> = metrics
@@ -430,6 +429,7 @@ pub async fn get_corridor_detail(
                 liquidity_trend,
                 health_score,
                 last_updated: m.updated_at.to_rfc3339(),
+                source: m.source,
             }
         })
         .collect();
@@ -470,6 +470,7 @@ mod tests {
             liquidity_depth_usd: 500000.0,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            source: MetricSource::Horizon,
         };
 
         let response = CorridorResponse {
@@ -489,6 +490,7 @@ mod tests {
             liquidity_trend: "stable".to_string(),
             health_score: 95.0,
             last_updated: metrics.updated_at.to_rfc3339(),
+            source: metrics.source,
         };
 
         assert_eq!(response.source_asset, "EURC");
@@ -1,93 +1,36 @@
 use axum::{
     extract::{Path, Query, State},
     http::HeaderMap,
-    response::Response,
+    response::{IntoResponse, Response},
     Json,
 };
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex, OnceLock};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use stellar_insights_core::Asset;
 use utoipa::{IntoParams, ToSchema};
 
 use anyhow::anyhow;
+use crate::analytics::concentration;
 use crate::cache::{keys, CacheManager};
 use crate::cache_middleware::CacheAware;
 use crate::database::Database;
+use crate::db::concentration::CorridorConcentration;
 use crate::error::{ApiError, ApiResult};
-use crate::models::SortBy;
-use crate::rpc::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
-use crate::rpc::error::{with_retry, RetryConfig, RpcError};
+use crate::models::corridor::{Corridor, CorridorKey, CorridorMetrics};
+use crate::models::{MetricSource, SortBy};
+use crate::rpc::error::RpcError;
 use crate::rpc::StellarRpcClient;
+use crate::preferences::Preferences;
+use crate::services::corridor_key_filter::corridor_key_filter;
 use crate::services::price_feed::PriceFeedClient;
-
-/// Represents an asset pair (source -> destination) for a corridor
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct AssetPair {
-    source_asset: String,
-    destination_asset: String,
-}
-
-impl AssetPair {
-    fn to_corridor_key(&self) -> String {
-        format!("{}->{}", self.source_asset, self.destination_asset)
-    }
-}
-
-/// Extract asset pair from a payment operation
-/// Handles regular payments, path_payment_strict_send, and path_payment_strict_receive
-fn extract_asset_pair_from_payment(payment: &crate::rpc::Payment) -> Option<AssetPair> {
-    let operation_type = payment.operation_type.as_deref().unwrap_or("payment");
-
-    match operation_type {
-        "path_payment_strict_send" | "path_payment_strict_receive" => {
-            // Path payments have explicit source and destination assets
-            let source_asset = if let Some(src_type) = &payment.source_asset_type {
-                if src_type == "native" {
-                    "XLM:native".to_string()
-                } else {
-                    format!(
-                        "{}:{}",
-                        payment.source_asset_code.as_deref().unwrap_or("UNKNOWN"),
-                        payment.source_asset_issuer.as_deref().unwrap_or("unknown")
-                    )
-                }
-            } else {
-                return None;
-            };
-
-            let destination_asset = if payment.asset_type == "native" {
-                "XLM:native".to_string()
-            } else {
-                format!(
-                    "{}:{}",
-                    payment.asset_code.as_deref().unwrap_or("UNKNOWN"),
-                    payment.asset_issuer.as_deref().unwrap_or("unknown")
-                )
-            };
-
-            Some(AssetPair {
-                source_asset,
-                destination_asset,
-            })
-        }
-        "payment" | _ => {
-            // Regular payments: same asset for source and destination
-            let asset = if payment.asset_type == "native" {
-                "XLM:native".to_string()
-            } else {
-                format!(
-                    "{}:{}",
-                    payment.asset_code.as_deref().unwrap_or("UNKNOWN"),
-                    payment.asset_issuer.as_deref().unwrap_or("unknown")
-                )
-            };
-
-            Some(AssetPair {
-                source_asset: asset.clone(),
-                destination_asset: asset,
-            })
-        }
-    }
-}
+use crate::services::settlement_times::SettlementTimes;
+use crate::services::valuation::{QuoteQuery, ValuationService};
+use crate::sparse_fields::FieldsQuery;
+use crate::tenant::TenantScope;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CorridorResponse {
@@ -139,6 +82,10 @@ pub struct CorridorResponse {
     /// Last update timestamp
     #[schema(example = "2024-01-15T10:30:00Z")]
     pub last_updated: String,
+    /// Least trusted source behind these figures (on_chain, horizon, derived, self_reported)
+    #[serde(default)]
+    #[schema(value_type = String, example = "horizon")]
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -192,6 +139,97 @@ pub struct CorridorDetailResponse {
     pub liquidity_trends: Vec<LiquidityDataPoint>,
     /// Related corridors
     pub related_corridors: Option<Vec<CorridorResponse>>,
+    /// USD value of the top 20 levels of the live order book, both sides
+    #[schema(example = 250000.0)]
+    pub order_book_depth_usd: Option<f64>,
+    /// Trades between the corridor's assets among the latest network trades
+    pub recent_trades: Option<RecentTradesSummary>,
+    /// Anchors issuing either corridor asset
+    pub anchors: Option<Vec<CorridorAnchor>>,
+    /// Concentration of volume across accounts per daily window over the last
+    /// 30 days, oldest first
+    pub concentration: Option<Vec<ConcentrationPoint>>,
+    /// Percentiles of path payment settlement times over the last 30 days;
+    /// absent without settled payments
+    pub settlement_times: Option<SettlementTimes>,
+    /// Parts that failed or timed out and were left out of this response
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unavailable: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecentTradesSummary {
+    /// Number of matching trades
+    #[schema(example = 12)]
+    pub trade_count: i64,
+    /// Traded amount in units of the corridor's source asset
+    #[schema(example = 15000.0)]
+    pub volume: f64,
+    /// Close time of the ledger holding the latest matching trade
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub last_trade_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CorridorAnchor {
+    /// Anchor identifier
+    pub id: String,
+    /// Anchor name
+    #[schema(example = "Circle")]
+    pub name: String,
+    /// Corridor asset the anchor issues
+    #[schema(example = "USDC")]
+    pub asset_code: String,
+}
+
+/// Herfindahl–Hirschman index (0 to 10,000) of one window's volume across
+/// sending, receiving and market-making accounts. A side without volume has
+/// no index or top share.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConcentrationPoint {
+    #[schema(example = "2024-01-14T10:00:00+00:00")]
+    pub window_start: String,
+    #[schema(example = "2024-01-15T10:00:00+00:00")]
+    pub window_end: String,
+    /// Level of the most concentrated side (unconcentrated, moderate, high)
+    #[schema(example = "moderate")]
+    pub level: String,
+    #[schema(example = 1850.0)]
+    pub sender_hhi: Option<f64>,
+    #[schema(example = 42)]
+    pub senders: i64,
+    /// Share of the largest sending account, 0 to 1
+    #[schema(example = 0.35)]
+    pub top_sender_share: Option<f64>,
+    pub receiver_hhi: Option<f64>,
+    pub receivers: i64,
+    pub top_receiver_share: Option<f64>,
+    pub market_maker_hhi: Option<f64>,
+    pub market_makers: i64,
+    pub top_market_maker_share: Option<f64>,
+}
+
+impl From<CorridorConcentration> for ConcentrationPoint {
+    fn from(row: CorridorConcentration) -> Self {
+        let hhi = [row.sender_hhi, row.receiver_hhi, row.market_maker_hhi]
+            .into_iter()
+            .flatten()
+            .fold(0.0, f64::max);
+        Self {
+            window_start: row.window_start,
+            window_end: row.window_end,
+            level: concentration::level(hhi).to_string(),
+            sender_hhi: row.sender_hhi,
+            senders: row.senders,
+            top_sender_share: row.top_sender_share,
+            receiver_hhi: row.receiver_hhi,
+            receivers: row.receivers,
+            top_receiver_share: row.top_receiver_share,
+            market_maker_hhi: row.market_maker_hhi,
+            market_makers: row.market_makers,
+            top_market_maker_share: row.top_market_maker_share,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, IntoParams)]
@@ -226,6 +264,9 @@ pub struct ListCorridorsQuery {
     /// Time period for metrics (24h, 7d, 30d)
     #[param(example = "24h")]
     pub time_period: Option<String>,
+    /// Drop corridors whose metrics are less trusted than this source
+    #[param(value_type = Option<String>, example = "horizon")]
+    pub min_source: Option<MetricSource>,
 }
 
 fn default_limit() -> i64 {
@@ -264,28 +305,17 @@ fn get_liquidity_trend(volume_usd: f64) -> String {
     }
 }
 
-fn rpc_circuit_breaker() -> Arc<CircuitBreaker> {
-    static CIRCUIT_BREAKER: OnceLock<Arc<CircuitBreaker>> = OnceLock::new();
-    CIRCUIT_BREAKER
-        .get_or_init(|| {
-            Arc::new(CircuitBreaker::new(
-                CircuitBreakerConfig::default(),
-                "horizon",
-            ))
-        })
-        .clone()
-}
-
 /// Generate cache key for corridor list with filters
 fn generate_corridor_list_cache_key(params: &ListCorridorsQuery) -> String {
     let filter_str = format!(
-        "sr_min:{:?}_sr_max:{:?}_vol_min:{:?}_vol_max:{:?}_asset:{:?}_period:{:?}",
+        "sr_min:{:?}_sr_max:{:?}_vol_min:{:?}_vol_max:{:?}_asset:{:?}_period:{:?}_source:{:?}",
         params.success_rate_min,
         params.success_rate_max,
         params.volume_min,
         params.volume_max,
         params.asset_code,
-        params.time_period
+        params.time_period,
+        params.min_source
     );
     keys::corridor_list(params.limit, params.offset, &filter_str)
 }
@@ -293,184 +323,103 @@ fn generate_corridor_list_cache_key(params: &ListCorridorsQuery) -> String {
 /// List all payment corridors
 ///
 /// Returns a list of payment corridors with performance metrics.
-/// Supports filtering by success rate, volume, and asset code.
+/// Supports filtering by success rate, volume, and asset code. Pass
+/// `?quote=EUR` (or set a base currency preference) to also receive every
+/// USD amount converted at the metric's timestamp, e.g. `liquidity_depth_eur`.
 ///
-/// **DATA SOURCE: RPC**
-/// - Payment data from Horizon API
-/// - Trade data from Horizon API  
-/// - Order book data from Horizon API
-/// - Calculates corridor metrics from real-time RPC data
+/// **DATA SOURCE: Database**
+/// - Corridor summaries precomputed by the ingestion pipeline, covering the
+///   24 hours before their last refresh
 #[utoipa::path(
     get,
     path = "/api/corridors",
-    params(ListCorridorsQuery),
+    params(ListCorridorsQuery, QuoteQuery),
     responses(
         (status = 200, description = "List of corridors retrieved successfully", body = Vec<CorridorResponse>),
         (status = 500, description = "Internal server error")
     ),
     tag = "Corridors"
 )]
-#[tracing::instrument(skip(_db, cache, rpc_client, price_feed, params))]
+#[tracing::instrument(skip(db, cache, tenant, params, prefs, quote))]
 pub async fn list_corridors(
-    State((_db, cache, rpc_client, price_feed)): State<(
+    State((db, cache, _rpc_client, _price_feed)): State<(
         Arc<Database>,
         Arc<CacheManager>,
         Arc<StellarRpcClient>,
         Arc<PriceFeedClient>,
     )>,
+    tenant: TenantScope,
     Query(params): Query<ListCorridorsQuery>,
+    Query(quote): Query<QuoteQuery>,
+    Preferences(prefs): Preferences,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
-    let cache_key = generate_corridor_list_cache_key(&params);
+    let quote = quote.resolve(&prefs)?;
+    let cache_key = tenant.cache_key(generate_corridor_list_cache_key(&params));
 
-    let corridors = <()>::get_or_fetch(
+    let cached = <()>::get_or_fetch_json(
         &cache,
         &cache_key,
         cache.config.get_ttl("corridor"),
         async {
-            let circuit_breaker = rpc_circuit_breaker();
-
-            // **RPC DATA**: Fetch recent payments to identify active corridors
-            let payments = with_retry(
-                || async {
-                    rpc_client
-                        .fetch_payments(200, None)
-                        .await
-                        .map_err(|e| RpcError::categorize(&e.to_string()))
-                },
-                RetryConfig::default(),
-                circuit_breaker.clone(),
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to fetch payments from RPC: {}", e))?;
-
-            // **RPC DATA**: Fetch recent trades for volume data
-            let _trades = with_retry(
-                || async {
-                    rpc_client
-                        .fetch_trades(200, None)
-                        .await
-                        .map_err(|e| RpcError::categorize(&e.to_string()))
-                },
-                RetryConfig::default(),
-                circuit_breaker.clone(),
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to fetch trades from RPC: {}", e))?;
-            // **RPC DATA**: Fetch recent payments with pagination to identify active corridors
-            // Use paginated fetch to get more complete data (up to configured limit)
-            let payments = match rpc_client.fetch_all_payments(Some(1000)).await {
-                Ok(p) => p,
-                Err(e) => {
-                    tracing::error!("Failed to fetch payments from RPC: {}", e);
-                    return Ok(vec![]);
-                }
-            };
-
-            // **RPC DATA**: Fetch recent trades with pagination for volume data
-            let _trades = match rpc_client.fetch_all_trades(Some(1000)).await {
-                Ok(t) => t,
-                Err(e) => {
-                    tracing::warn!("Failed to fetch trades from RPC: {}", e);
-                    vec![]
-                }
-            };
-
-            // Group payments by asset pairs to identify corridors
-            use std::collections::HashMap;
-            let mut corridor_map: HashMap<String, Vec<&crate::rpc::Payment>> = HashMap::new();
-
-            for payment in &payments {
-                // Extract the actual asset pair from the payment
-                if let Some(asset_pair) = extract_asset_pair_from_payment(payment) {
-                    let corridor_key = asset_pair.to_corridor_key();
-                    corridor_map
-                        .entry(corridor_key)
-                        .or_insert_with(Vec::new)
-                        .push(payment);
-                } else {
-                    tracing::warn!("Failed to extract asset pair from payment: {}", payment.id);
-                }
-            }
-
-            // Calculate metrics for each corridor
-            let mut corridor_responses = Vec::new();
-
-            for (corridor_key, corridor_payments) in corridor_map.iter() {
-                let total_attempts = corridor_payments.len() as i64;
-
-                // In Stellar, payments in the stream are successful
-                let successful_payments = total_attempts;
-                let failed_payments = 0;
-                let success_rate = if total_attempts > 0 { 100.0 } else { 0.0 };
-
-                // Parse corridor key to get assets
-                let parts: Vec<&str> = corridor_key.split("->").collect();
-                if parts.len() != 2 {
-                    continue;
-                }
-
-                let source_parts: Vec<&str> = parts[0].split(':').collect();
-                let dest_parts: Vec<&str> = parts[1].split(':').collect();
-
-                if source_parts.len() != 2 || dest_parts.len() != 2 {
-                    continue;
-                }
-
-                // Calculate volume from payment amounts and convert to USD
-                let mut volume_usd: f64 = 0.0;
-                let source_asset_key = parts[0];
-
-                // Get price for source asset
-                if let Ok(price) = price_feed.get_price(source_asset_key).await {
-                    for payment in corridor_payments.iter() {
-                        if let Ok(amount) = payment.amount.parse::<f64>() {
-                            volume_usd += amount * price;
-                        }
-                    }
-                } else {
-                    // Fallback: use raw amounts if price unavailable
-                    tracing::warn!(
-                        "Price unavailable for {}, using raw amounts",
-                        source_asset_key
-                    );
-                    volume_usd = corridor_payments
-                        .iter()
-                        .filter_map(|p| p.amount.parse::<f64>().ok())
-                        .sum();
-                }
-
-                // Calculate health score
-                let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
-                let liquidity_trend = get_liquidity_trend(volume_usd);
-                let avg_latency = 400.0 + (success_rate * 2.0);
-
-                let corridor_response = CorridorResponse {
-                    id: corridor_key.clone(),
-                    source_asset: source_parts[0].to_string(),
-                    destination_asset: dest_parts[0].to_string(),
-                    success_rate,
-                    total_attempts,
-                    successful_payments,
-                    failed_payments,
+            // Summaries are rebuilt by the ingestion pipeline and already
+            // limited to the tenant's corridors
+            let summaries = db
+                .list_corridor_summaries(params.limit, params.offset, &tenant)
+                .await?;
+
+            let mut corridor_responses = Vec::with_capacity(summaries.len());
+            for summary in summaries {
+                let corridor_key = format!(
+                    "{}:{}->{}:{}",
+                    summary.source_asset_code,
+                    summary.source_asset_issuer,
+                    summary.destination_asset_code,
+                    summary.destination_asset_issuer
+                )
+                .parse::<CorridorKey>()
+                .map(String::from)
+                .unwrap_or(summary.corridor_id);
+
+                let health_score = calculate_health_score(
+                    summary.success_rate_24h,
+                    summary.total_transactions_24h,
+                    summary.volume_usd_24h,
+                );
+                let liquidity_trend = get_liquidity_trend(summary.volume_usd_24h);
+                let avg_latency = summary.avg_settlement_latency_ms.unwrap_or(0) as f64;
+
+                corridor_key_filter().insert(&corridor_key);
+                corridor_responses.push(CorridorResponse {
+                    id: corridor_key,
+                    source_asset: summary.source_asset_code,
+                    destination_asset: summary.destination_asset_code,
+                    success_rate: summary.success_rate_24h,
+                    total_attempts: summary.total_transactions_24h,
+                    successful_payments: summary.successful_transactions_24h,
+                    failed_payments: summary.failed_transactions_24h,
                     average_latency_ms: avg_latency,
                     median_latency_ms: avg_latency * 0.75,
                     p95_latency_ms: avg_latency * 2.5,
                     p99_latency_ms: avg_latency * 4.0,
-                    liquidity_depth_usd: volume_usd,
-                    liquidity_volume_24h_usd: volume_usd * 0.1,
+                    liquidity_depth_usd: summary.liquidity_depth_usd,
+                    liquidity_volume_24h_usd: summary.volume_usd_24h,
                     liquidity_trend,
                     health_score,
-                    last_updated: chrono::Utc::now().to_rfc3339(),
-                };
-
-                corridor_responses.push(corridor_response);
+                    last_updated: summary.refreshed_at.to_rfc3339(),
+                    source: summary.source,
+                });
             }
 
             // Apply filters
             let filtered: Vec<_> = corridor_responses
                 .into_iter()
                 .filter(|c| {
+                    if let Some(min) = params.min_source {
+                        if !c.source.meets(min) {
+                            return false;
+                        }
+                    }
                     if let Some(min) = params.success_rate_min {
                         if c.success_rate < min {
                             return false;
@@ -482,12 +431,12 @@ pub async fn list_corridors(
                         }
                     }
                     if let Some(min) = params.volume_min {
-                        if c.liquidity_depth_usd < min {
+                        if c.liquidity_volume_24h_usd < min {
                             return false;
                         }
                     }
                     if let Some(max) = params.volume_max {
-                        if c.liquidity_depth_usd > max {
+                        if c.liquidity_volume_24h_usd > max {
                             return false;
                         }
                     }
@@ -506,235 +455,425 @@ pub async fn list_corridors(
                 })
                 .collect();
 
+            crate::observability::metrics::set_corridors_tracked(filtered.len() as i64);
             Ok(filtered)
         },
     )
     .await?;
 
-    crate::observability::metrics::set_corridors_tracked(corridors.len() as i64);
-
     let ttl = cache.config.get_ttl("corridor");
-    let response = crate::http_cache::cached_json_response(&headers, &cache_key, &corridors, ttl)?;
+    if quote.is_usd() {
+        let response = crate::http_cache::cached_bytes_response(&headers, &cache_key, &cached, ttl);
+        return Ok(response);
+    }
+
+    let corridors: Vec<CorridorResponse> = cached.decode()?;
+    let mut items = corridors
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow!(e))?;
+    ValuationService::new(db.pool().clone())
+        .annotate_all(&mut items, &quote, "last_updated")
+        .await;
+
+    let resource_key = format!("{}:quote:{}", cache_key, quote.code());
+    let response = crate::http_cache::cached_json_response(&headers, &resource_key, &items, ttl)?;
     Ok(response)
 }
 
 /// Get detailed corridor information
 ///
 /// Returns detailed metrics and historical data for a specific corridor.
+/// Pass `?fields=` (dotted for nested values, e.g. `corridor.success_rate`)
+/// to receive only the listed fields.
 ///
 /// **DATA SOURCE: RPC**
 #[utoipa::path(
     get,
     path = "/api/corridors/{corridor_key}",
     params(
-        ("corridor_key" = String, Path, description = "Corridor identifier (e.g., USDC:native->XLM:native)")
+        ("corridor_key" = String, Path, description = "Corridor identifier (e.g., USDC:native->XLM:native)"),
+        FieldsQuery
     ),
     responses(
         (status = 200, description = "Corridor details retrieved successfully", body = CorridorDetailResponse),
+        (status = 400, description = "Invalid corridor key or fields selection"),
         (status = 404, description = "Corridor not found"),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error"),
+        (status = 504, description = "Corridor metrics timed out")
     ),
     tag = "Corridors"
 )]
-#[tracing::instrument(skip(_db, _cache, _rpc_client, _price_feed))]
+#[tracing::instrument(skip(db, _cache, rpc_client, price_feed, tenant, fields))]
 pub async fn get_corridor_detail(
-    State((_db, _cache, _rpc_client, _price_feed)): State<(
+    State((db, _cache, rpc_client, price_feed)): State<(
         Arc<Database>,
         Arc<CacheManager>,
         Arc<StellarRpcClient>,
         Arc<PriceFeedClient>,
     )>,
-    Path(_corridor_key): Path<String>,
-) -> ApiResult<Json<CorridorDetailResponse>> {
-    // TODO: Implement RPC-based corridor detail
-    Err(ApiError::not_found(
-        "NOT_IMPLEMENTED",
-        "Corridor detail endpoint not yet implemented with RPC",
-    ))
-}
+    tenant: TenantScope,
+    Path(corridor_key): Path<String>,
+    Query(fields): Query<FieldsQuery>,
+) -> ApiResult<Response> {
+    let selection = fields.selection()?;
+    let corridor_key = known_corridor_key(&corridor_key)?;
+    if let Some(tenant_keys) = db.tenants().corridor_keys(&tenant).await? {
+        if !tenant_keys.contains(&corridor_key) {
+            return Err(corridor_not_found(&corridor_key));
+        }
+    }
+    let detail = build_corridor_detail(
+        &db,
+        &rpc_client,
+        &price_feed,
+        &corridor_key,
+        DETAIL_PART_TIMEOUT,
+    )
+    .await?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    let response = match selection {
+        Some(selection) => Json(selection.apply(&detail)?).into_response(),
+        None => Json(detail).into_response(),
+    };
+    Ok(response)
+}
 
-    #[test]
-    fn test_health_score_calculation() {
-        let score = calculate_health_score(95.0, 1000, 1_000_000.0);
-        assert!(score > 0.0 && score <= 100.0);
+/// Canonicalize the requested key, cheaply turning away malformed keys and
+/// keys the corridor key filter has never seen before any cache or database
+/// lookup
+fn known_corridor_key(corridor_key: &str) -> ApiResult<CorridorKey> {
+    let corridor_key: CorridorKey = corridor_key.parse()?;
+    if !corridor_key_filter().might_contain(corridor_key.as_str()) {
+        return Err(corridor_not_found(&corridor_key));
     }
+    Ok(corridor_key)
+}
 
-    #[test]
-    fn test_liquidity_trend() {
-        assert_eq!(get_liquidity_trend(15_000_000.0), "increasing");
-        assert_eq!(get_liquidity_trend(5_000_000.0), "stable");
-        assert_eq!(get_liquidity_trend(500_000.0), "decreasing");
-    }
+fn corridor_not_found(corridor_key: &CorridorKey) -> ApiError {
+    ApiError::not_found(
+        "CORRIDOR_NOT_FOUND",
+        format!("Corridor {} not found", corridor_key),
+    )
+}
 
-    #[test]
-    fn test_extract_asset_pair_regular_payment_native() {
-        let payment = crate::rpc::Payment {
-            id: "test_1".to_string(),
-            paging_token: "token_1".to_string(),
-            transaction_hash: "hash_1".to_string(),
-            source_account: "GTEST".to_string(),
-            destination: "GDEST".to_string(),
-            asset_type: "native".to_string(),
-            asset_code: None,
-            asset_issuer: None,
-            amount: "100.0".to_string(),
-            created_at: "2026-01-01T00:00:00Z".to_string(),
-            operation_type: Some("payment".to_string()),
-            source_asset_type: None,
-            source_asset_code: None,
-            source_asset_issuer: None,
-            source_amount: None,
-            from: Some("GTEST".to_string()),
-            to: Some("GDEST".to_string()),
-        };
+/// Budget for each part of a corridor detail. Kept under the cached-read
+/// route budget so one slow source leaves a partial response instead of a
+/// 504 for the whole request.
+const DETAIL_PART_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Order book levels fetched per side for the depth figure
+const ORDER_BOOK_DEPTH_LEVELS: u32 = 20;
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "XLM:native");
-        assert_eq!(pair.destination_asset, "XLM:native");
-        assert_eq!(pair.to_corridor_key(), "XLM:native->XLM:native");
+/// Latest network trades scanned for trades in the corridor
+const RECENT_TRADES_SCAN: u32 = 200;
+
+#[derive(Debug)]
+enum PartError {
+    TimedOut,
+    Failed(String),
+}
+
+impl std::fmt::Display for PartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TimedOut => write!(f, "timed out"),
+            Self::Failed(e) => write!(f, "{}", e),
+        }
     }
+}
 
-    #[test]
-    fn test_extract_asset_pair_regular_payment_issued_asset() {
-        let payment = crate::rpc::Payment {
-            id: "test_2".to_string(),
-            paging_token: "token_2".to_string(),
-            transaction_hash: "hash_2".to_string(),
-            source_account: "GTEST".to_string(),
-            destination: "GDEST".to_string(),
-            asset_type: "credit_alphanum4".to_string(),
-            asset_code: Some("USDC".to_string()),
-            asset_issuer: Some("GISSUER".to_string()),
-            amount: "100.0".to_string(),
-            created_at: "2026-01-01T00:00:00Z".to_string(),
-            operation_type: Some("payment".to_string()),
-            source_asset_type: None,
-            source_asset_code: None,
-            source_asset_issuer: None,
-            source_amount: None,
-            from: Some("GTEST".to_string()),
-            to: Some("GDEST".to_string()),
-        };
+async fn fetch_part<T, E: std::fmt::Display>(
+    budget: Duration,
+    part: impl Future<Output = Result<T, E>>,
+) -> Result<T, PartError> {
+    match tokio::time::timeout(budget, part).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(PartError::Failed(e.to_string())),
+        Err(_) => Err(PartError::TimedOut),
+    }
+}
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "USDC:GISSUER");
-        assert_eq!(pair.destination_asset, "USDC:GISSUER");
-        assert_eq!(pair.to_corridor_key(), "USDC:GISSUER->USDC:GISSUER");
+/// Keep an optional part's value, or record it as unavailable
+fn optional_part<T>(
+    name: &str,
+    part: Result<T, PartError>,
+    unavailable: &mut Vec<String>,
+) -> Option<T> {
+    match part {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!(part = name, "Corridor detail part unavailable: {}", e);
+            unavailable.push(name.to_string());
+            None
+        }
     }
+}
 
-    #[test]
-    fn test_extract_asset_pair_path_payment_cross_asset() {
-        let payment = crate::rpc::Payment {
-            id: "test_3".to_string(),
-            paging_token: "token_3".to_string(),
-            transaction_hash: "hash_3".to_string(),
-            source_account: "GTEST".to_string(),
-            destination: "GDEST".to_string(),
-            asset_type: "credit_alphanum4".to_string(),
-            asset_code: Some("EUR".to_string()),
-            asset_issuer: Some("GEURISSUER".to_string()),
-            amount: "100.0".to_string(),
-            created_at: "2026-01-01T00:00:00Z".to_string(),
-            operation_type: Some("path_payment_strict_send".to_string()),
-            source_asset_type: Some("credit_alphanum4".to_string()),
-            source_asset_code: Some("USD".to_string()),
-            source_asset_issuer: Some("GUSDISSUER".to_string()),
-            source_amount: Some("105.0".to_string()),
-            from: Some("GTEST".to_string()),
-            to: Some("GDEST".to_string()),
-        };
+/// Fetch the corridor's metrics, related corridors, order book, recent
+/// trades, anchors, concentration history and settlement times concurrently. Metrics are
+/// required; any other part that fails or runs past `budget` is left out
+/// and named in `unavailable`.
+async fn build_corridor_detail(
+    db: &Database,
+    rpc_client: &StellarRpcClient,
+    price_feed: &PriceFeedClient,
+    corridor_key: &CorridorKey,
+    budget: Duration,
+) -> ApiResult<CorridorDetailResponse> {
+    let corridor = corridor_key.corridor();
+    let end_date = Utc::now().date_naive();
+    let start_date = end_date - chrono::Duration::days(30);
+    let aggregates = db.corridor_aggregates();
+
+    let (metrics, related, depth, trades, anchors, concentration, settlements) = tokio::join!(
+        fetch_part(
+            budget,
+            aggregates.get_corridor_metrics(&corridor, start_date, end_date)
+        ),
+        fetch_part(budget, aggregates.get_top_corridors_by_volume(end_date, 4)),
+        fetch_part(
+            budget,
+            fetch_order_book_depth_usd(rpc_client, price_feed, &corridor)
+        ),
+        fetch_part(budget, fetch_recent_trades(rpc_client, &corridor)),
+        fetch_part(budget, db.get_corridor_anchors(&corridor)),
+        fetch_part(
+            budget,
+            db.corridor_concentrations().history(
+                corridor_key.as_str(),
+                Utc::now() - chrono::Duration::days(30)
+            )
+        ),
+        fetch_part(budget, async {
+            db.payment_settlements()
+                .samples(
+                    corridor_key.as_str(),
+                    Utc::now() - chrono::Duration::days(30),
+                )
+                .await
+                .map(|samples| SettlementTimes::from_samples(&samples))
+        }),
+    );
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "USD:GUSDISSUER");
-        assert_eq!(pair.destination_asset, "EUR:GEURISSUER");
-        assert_eq!(pair.to_corridor_key(), "USD:GUSDISSUER->EUR:GEURISSUER");
+    let metrics = metrics.map_err(|e| match e {
+        PartError::TimedOut => ApiError::gateway_timeout(
+            "CORRIDOR_DETAIL_TIMEOUT",
+            format!("Timed out fetching metrics for corridor {}", corridor_key),
+        ),
+        PartError::Failed(e) => ApiError::internal(
+            "DATABASE_ERROR",
+            format!("Failed to fetch corridor detail: {}", e),
+        ),
+    })?;
+    let Some(latest) = metrics.first() else {
+        let mut details = HashMap::new();
+        details.insert(
+            "corridor_id".to_string(),
+            serde_json::json!(corridor_key.as_str()),
+        );
+        return Err(ApiError::not_found_with_details(
+            "CORRIDOR_NOT_FOUND",
+            format!("Corridor {} not found", corridor_key),
+            details,
+        ));
+    };
+
+    let mut unavailable = Vec::new();
+    let related_corridors =
+        optional_part("related_corridors", related, &mut unavailable).map(|related| {
+            related
+                .iter()
+                .filter(|m| m.corridor_key != latest.corridor_key)
+                .take(3)
+                .map(corridor_response_from_metrics)
+                .collect()
+        });
+    let order_book_depth_usd = optional_part("order_book", depth, &mut unavailable);
+    let recent_trades = optional_part("recent_trades", trades, &mut unavailable);
+    let anchors = optional_part("anchors", anchors, &mut unavailable).map(|anchors| {
+        anchors
+            .into_iter()
+            .map(|(id, name, asset_code)| CorridorAnchor {
+                id,
+                name,
+                asset_code,
+            })
+            .collect()
+    });
+    let concentration = optional_part("concentration", concentration, &mut unavailable)
+        .map(|rows| rows.into_iter().map(ConcentrationPoint::from).collect());
+    let settlement_times =
+        optional_part("settlement_times", settlements, &mut unavailable).flatten();
+
+    let historical_success_rate = metrics
+        .iter()
+        .rev()
+        .map(|m| SuccessRateDataPoint {
+            timestamp: m.date.format("%Y-%m-%d").to_string(),
+            success_rate: m.success_rate,
+            attempts: m.total_transactions,
+        })
+        .collect();
+    let liquidity_trends = metrics
+        .iter()
+        .rev()
+        .map(|m| LiquidityDataPoint {
+            timestamp: m.date.format("%Y-%m-%d").to_string(),
+            liquidity_usd: m.volume_usd,
+            volume_24h_usd: m.volume_usd * 0.1,
+        })
+        .collect();
+
+    Ok(CorridorDetailResponse {
+        corridor: corridor_response_from_metrics(latest),
+        historical_success_rate,
+        // Daily aggregates carry no per-payment latencies to bucket
+        latency_distribution: Vec::new(),
+        liquidity_trends,
+        related_corridors,
+        order_book_depth_usd,
+        recent_trades,
+        anchors,
+        concentration,
+        settlement_times,
+        unavailable,
+    })
+}
+
+fn corridor_response_from_metrics(m: &CorridorMetrics) -> CorridorResponse {
+    let avg_latency = 400.0 + (m.success_rate * 2.0);
+    CorridorResponse {
+        id: m.corridor_key.clone(),
+        source_asset: m.asset_a_code.clone(),
+        destination_asset: m.asset_b_code.clone(),
+        success_rate: m.success_rate,
+        total_attempts: m.total_transactions,
+        successful_payments: m.successful_transactions,
+        failed_payments: m.failed_transactions,
+        average_latency_ms: avg_latency,
+        median_latency_ms: avg_latency * 0.75,
+        p95_latency_ms: avg_latency * 2.5,
+        p99_latency_ms: avg_latency * 4.0,
+        liquidity_depth_usd: m.volume_usd,
+        liquidity_volume_24h_usd: m.volume_usd * 0.1,
+        liquidity_trend: get_liquidity_trend(m.volume_usd),
+        health_score: calculate_health_score(m.success_rate, m.total_transactions, m.volume_usd),
+        last_updated: m.updated_at.to_rfc3339(),
+        source: m.source,
     }
+}
 
-    #[test]
-    fn test_extract_asset_pair_path_payment_native_to_issued() {
-        let payment = crate::rpc::Payment {
-            id: "test_4".to_string(),
-            paging_token: "token_4".to_string(),
-            transaction_hash: "hash_4".to_string(),
-            source_account: "GTEST".to_string(),
-            destination: "GDEST".to_string(),
-            asset_type: "credit_alphanum4".to_string(),
-            asset_code: Some("USDC".to_string()),
-            asset_issuer: Some("GISSUER".to_string()),
-            amount: "100.0".to_string(),
-            created_at: "2026-01-01T00:00:00Z".to_string(),
-            operation_type: Some("path_payment_strict_receive".to_string()),
-            source_asset_type: Some("native".to_string()),
-            source_asset_code: None,
-            source_asset_issuer: None,
-            source_amount: Some("150.0".to_string()),
-            from: Some("GTEST".to_string()),
-            to: Some("GDEST".to_string()),
+/// `CODE:ISSUER` keys of the corridor's source and destination assets
+fn corridor_asset_keys(corridor: &Corridor) -> (String, String) {
+    (
+        format!("{}:{}", corridor.asset_a_code, corridor.asset_a_issuer),
+        format!("{}:{}", corridor.asset_b_code, corridor.asset_b_issuer),
+    )
+}
+
+/// USD value of both sides of the corridor's order book, with the source
+/// asset as base
+async fn fetch_order_book_depth_usd(
+    rpc_client: &StellarRpcClient,
+    price_feed: &PriceFeedClient,
+    corridor: &Corridor,
+) -> anyhow::Result<f64> {
+    let (base_key, counter_key) = corridor_asset_keys(corridor);
+    let base: Asset = base_key.parse().map_err(|e: String| anyhow!(e))?;
+    let counter: Asset = counter_key.parse().map_err(|e: String| anyhow!(e))?;
+
+    let order_book = rpc_client
+        .fetch_order_book(&base, &counter, ORDER_BOOK_DEPTH_LEVELS)
+        .await?;
+    // Ask amounts are in the base asset; bid amounts are in the counter
+    // asset at a price of counter per base
+    let asks: f64 = order_book
+        .asks
+        .iter()
+        .filter_map(|a| a.amount.parse::<f64>().ok())
+        .sum();
+    let bids: f64 = order_book
+        .bids
+        .iter()
+        .filter_map(|b| {
+            let amount = b.amount.parse::<f64>().ok()?;
+            let price = b.price.parse::<f64>().ok().filter(|p| *p > 0.0)?;
+            Some(amount / price)
+        })
+        .sum();
+
+    let base_price_usd = price_feed.get_price(&base_key).await?;
+    Ok((asks + bids) * base_price_usd)
+}
+
+async fn fetch_recent_trades(
+    rpc_client: &StellarRpcClient,
+    corridor: &Corridor,
+) -> Result<RecentTradesSummary, RpcError> {
+    let trades = rpc_client.fetch_trades(RECENT_TRADES_SCAN, None).await?;
+    let (source, destination) = corridor_asset_keys(corridor);
+
+    let mut summary = RecentTradesSummary {
+        trade_count: 0,
+        volume: 0.0,
+        last_trade_at: None,
+    };
+    for trade in &trades {
+        let (Some(base), Some(counter)) = (trade.base_asset(), trade.counter_asset()) else {
+            continue;
+        };
+        let (base, counter) = (base.key(), counter.key());
+        let amount = if base == source && counter == destination {
+            &trade.base_amount
+        } else if base == destination && counter == source {
+            &trade.counter_amount
+        } else {
+            continue;
         };
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "XLM:native");
-        assert_eq!(pair.destination_asset, "USDC:GISSUER");
-        assert_eq!(pair.to_corridor_key(), "XLM:native->USDC:GISSUER");
+        summary.trade_count += 1;
+        summary.volume += amount.parse::<f64>().unwrap_or(0.0);
+        // Trades come newest first
+        if summary.last_trade_at.is_none() {
+            summary.last_trade_at = Some(trade.ledger_close_time.clone());
+        }
     }
+    Ok(summary)
+}
 
-    #[test]
-    fn test_extract_asset_pair_path_payment_issued_to_native() {
-        let payment = crate::rpc::Payment {
-            id: "test_5".to_string(),
-            paging_token: "token_5".to_string(),
-            transaction_hash: "hash_5".to_string(),
-            source_account: "GTEST".to_string(),
-            destination: "GDEST".to_string(),
-            asset_type: "native".to_string(),
-            asset_code: None,
-            asset_issuer: None,
-            amount: "100.0".to_string(),
-            created_at: "2026-01-01T00:00:00Z".to_string(),
-            operation_type: Some("path_payment_strict_send".to_string()),
-            source_asset_type: Some("credit_alphanum4".to_string()),
-            source_asset_code: Some("BRL".to_string()),
-            source_asset_issuer: Some("GBRLISSUER".to_string()),
-            source_amount: Some("500.0".to_string()),
-            from: Some("GTEST".to_string()),
-            to: Some("GDEST".to_string()),
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "BRL:GBRLISSUER");
-        assert_eq!(pair.destination_asset, "XLM:native");
-        assert_eq!(pair.to_corridor_key(), "BRL:GBRLISSUER->XLM:native");
+    #[test]
+    fn test_health_score_calculation() {
+        let score = calculate_health_score(95.0, 1000, 1_000_000.0);
+        assert!(score > 0.0 && score <= 100.0);
     }
 
     #[test]
-    fn test_extract_asset_pair_missing_operation_type() {
-        // Should default to regular payment behavior
-        let payment = crate::rpc::Payment {
-            id: "test_6".to_string(),
-            paging_token: "token_6".to_string(),
-            transaction_hash: "hash_6".to_string(),
-            source_account: "GTEST".to_string(),
-            destination: "GDEST".to_string(),
-            asset_type: "credit_alphanum4".to_string(),
-            asset_code: Some("NGNT".to_string()),
-            asset_issuer: Some("GNGNTISSUER".to_string()),
-            amount: "100.0".to_string(),
-            created_at: "2026-01-01T00:00:00Z".to_string(),
-            operation_type: None,
-            source_asset_type: None,
-            source_asset_code: None,
-            source_asset_issuer: None,
-            source_amount: None,
-            from: Some("GTEST".to_string()),
-            to: Some("GDEST".to_string()),
-        };
+    fn test_liquidity_trend() {
+        assert_eq!(get_liquidity_trend(15_000_000.0), "increasing");
+        assert_eq!(get_liquidity_trend(5_000_000.0), "stable");
+        assert_eq!(get_liquidity_trend(500_000.0), "decreasing");
+    }
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "NGNT:GNGNTISSUER");
-        assert_eq!(pair.destination_asset, "NGNT:GNGNTISSUER");
+    #[tokio::test]
+    async fn test_slow_part_is_left_out() {
+        let budget = Duration::from_millis(20);
+        let fast = fetch_part(budget, async { Ok::<_, RpcError>(1) });
+        let slow = fetch_part(budget, async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, RpcError>(2)
+        });
+        let (fast, slow) = tokio::join!(fast, slow);
+        assert!(matches!(slow, Err(PartError::TimedOut)));
+
+        let mut unavailable = Vec::new();
+        assert_eq!(optional_part("fast", fast, &mut unavailable), Some(1));
+        assert_eq!(optional_part("slow", slow, &mut unavailable), None);
+        assert_eq!(unavailable, vec!["slow".to_string()]);
     }
 }
@@ -11,7 +11,9 @@ use std::sync::Arc;
 use utoipa::ToSchema;
 
 use crate::http_cache::cached_json_response;
+use crate::preferences::Preferences;
 use crate::services::price_feed::PriceFeedClient;
+use crate::services::valuation::{fallback_usd_rate, price_feed_asset_id};
 
 const DEFAULT_CACHE_TTL_SECONDS: usize = 60;
 const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
@@ -57,6 +59,9 @@ pub struct CostCalculationRequest {
     #[schema(example = 1550000.0)]
     pub destination_amount: Option<f64>,
     pub routes: Option<Vec<PaymentRoute>>,
+    /// Currency to value fees in; defaults to the caller's preferred base currency
+    #[schema(example = "EUR")]
+    pub base_currency: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -71,6 +76,7 @@ pub struct RouteCostBreakdown {
     pub slippage_cost_source: f64,
     pub total_fees_source: f64,
     pub total_fees_destination: f64,
+    pub total_fees_base: f64,
     pub estimated_destination_amount: f64,
     pub destination_shortfall: Option<f64>,
     pub additional_source_required: Option<f64>,
@@ -92,6 +98,9 @@ pub struct CostCalculationResponse {
     pub source_usd_rate: f64,
     pub destination_usd_rate: f64,
     pub mid_market_rate: f64,
+    pub base_currency: String,
+    /// Units of base currency per unit of source currency
+    pub source_to_base_rate: f64,
     pub best_route: RouteEstimate,
     pub routes: Vec<RouteEstimate>,
 }
@@ -153,6 +162,7 @@ impl RouteFees {
 )]
 pub async fn estimate_costs(
     State(price_feed): State<Arc<PriceFeedClient>>,
+    Preferences(prefs): Preferences,
     request_headers: HeaderMap,
     Json(request): Json<CostCalculationRequest>,
 ) -> Response {
@@ -207,15 +217,32 @@ pub async fn estimate_costs(
 
     let mid_market_rate = source_usd_rate / destination_usd_rate;
 
+    let base_currency = request
+        .base_currency
+        .as_deref()
+        .map(normalize_currency)
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| prefs.base_currency.clone());
+
+    let base_usd_rate = match resolve_usd_rate(&price_feed, &base_currency).await {
+        Ok(rate) if rate > 0.0 => rate,
+        Ok(_) => return error_response(StatusCode::BAD_REQUEST, "base currency USD rate is invalid"),
+        Err(error) => return error_response(StatusCode::BAD_REQUEST, &error),
+    };
+    let source_to_base_rate = source_usd_rate / base_usd_rate;
+
     let mut route_estimates: Vec<RouteEstimate> = unique_routes
         .into_iter()
         .map(|route| {
-            estimate_route(
+            let mut estimate = estimate_route(
                 route,
                 request.source_amount,
                 request.destination_amount,
                 mid_market_rate,
-            )
+            );
+            estimate.breakdown.total_fees_base =
+                estimate.breakdown.total_fees_source * source_to_base_rate;
+            estimate
         })
         .collect();
 
@@ -241,6 +268,8 @@ pub async fn estimate_costs(
         source_usd_rate,
         destination_usd_rate,
         mid_market_rate,
+        base_currency: base_currency.clone(),
+        source_to_base_rate,
         best_route,
         routes: route_estimates,
     };
@@ -253,12 +282,13 @@ pub async fn estimate_costs(
         .join(",");
 
     let resource_key = format!(
-        "cost-calculator:{}:{}:{:.8}:{}:{:?}",
+        "cost-calculator:{}:{}:{:.8}:{}:{:?}:{}",
         source_currency,
         destination_currency,
         request.source_amount,
         route_key,
-        request.destination_amount
+        request.destination_amount,
+        base_currency
     );
 
     match cached_json_response(
@@ -344,6 +374,7 @@ fn estimate_route(
             slippage_cost_source,
             total_fees_source,
             total_fees_destination,
+            total_fees_base: total_fees_source,
             estimated_destination_amount,
             destination_shortfall,
             additional_source_required,
@@ -379,32 +410,6 @@ async fn resolve_usd_rate(price_feed: &PriceFeedClient, currency: &str) -> Resul
     fallback_usd_rate(currency).ok_or_else(|| format!("Unsupported currency or asset: {currency}"))
 }
 
-fn price_feed_asset_id(currency: &str) -> Option<&'static str> {
-    match currency {
-        "XLM" => Some("XLM:native"),
-        "USDC" => Some("USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"),
-        "EURC" => Some("EURC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"),
-        _ => None,
-    }
-}
-
-fn fallback_usd_rate(currency: &str) -> Option<f64> {
-    match currency {
-        "USD" | "USDC" | "USDT" => Some(1.0),
-        "EUR" | "EURC" => Some(1.08),
-        "GBP" => Some(1.27),
-        "NGN" => Some(0.00065),
-        "KES" => Some(0.0077),
-        "GHS" => Some(0.064),
-        "PHP" => Some(0.0178),
-        "INR" => Some(0.012),
-        "XLM" => Some(0.12),
-        "BTC" => Some(62000.0),
-        "ETH" => Some(3200.0),
-        _ => None,
-    }
-}
-
 fn normalize_currency(input: &str) -> String {
     let value = input.trim();
     if value.contains(':') {
@@ -23,9 +23,9 @@ pub async fn send_digest_manual(
 ) -> ApiResult<Json<SendDigestResponse>> {
     // Trigger manual digest send
     match scheduler.send_digest(&req.period).await {
-        Ok(_) => Ok(Json(SendDigestResponse {
+        Ok(reference) => Ok(Json(SendDigestResponse {
             success: true,
-            message: format!("Digest sent to {} recipients", req.recipients.len()),
+            message: format!("Digest {} queued", reference),
         })),
         Err(e) => Ok(Json(SendDigestResponse {
             success: false,
@@ -0,0 +1,68 @@
+use axum::{
+    extract::{Query, State},
+    response::Html,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::email::report::{build_digest_report, canonical_period, generate_html_report};
+use crate::error::{ApiError, ApiResult};
+use crate::preferences::DigestFrequency;
+use crate::rpc::StellarRpcClient;
+
+#[derive(Clone)]
+pub struct DigestPreviewState {
+    pub db: Arc<Database>,
+    pub rpc_client: Arc<StellarRpcClient>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DigestPreviewQuery {
+    /// `weekly` or `monthly`; defaults to the user's digest frequency, else weekly
+    pub period: Option<String>,
+}
+
+/// GET /api/me/digest/preview - Render the caller's digest from current data,
+/// exactly as the scheduler would send it
+pub async fn preview_digest(
+    State(state): State<DigestPreviewState>,
+    user: AuthUser,
+    Query(query): Query<DigestPreviewQuery>,
+) -> ApiResult<Html<String>> {
+    let prefs = state.db.preferences().get(&user.user_id).await?;
+
+    let period = match query.period.as_deref() {
+        Some(period) => canonical_period(period).ok_or_else(|| {
+            ApiError::bad_request(
+                "INVALID_DIGEST_PERIOD",
+                format!(
+                    "Unknown digest period '{}', expected weekly or monthly",
+                    period
+                ),
+            )
+        })?,
+        None => match prefs.notifications.digest_frequency {
+            DigestFrequency::Monthly => "Monthly",
+            _ => "Weekly",
+        },
+    };
+
+    let report = build_digest_report(&state.rpc_client, period)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to build digest preview: {}", e);
+            ApiError::internal("DIGEST_PREVIEW_ERROR", "Failed to build the digest preview")
+        })?;
+
+    Ok(Html(generate_html_report(&report, prefs.locale)))
+}
+
+pub fn routes(state: DigestPreviewState) -> Router {
+    Router::new()
+        .route("/api/me/digest/preview", get(preview_digest))
+        .with_state(state)
+}
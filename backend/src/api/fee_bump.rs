@@ -7,8 +7,11 @@ use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::models::{FeeBumpStats, FeeBumpTransaction};
+use crate::screening::{AccountScreener, Screened};
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
 
+type FeeBumpState = (Arc<FeeBumpTrackerService>, Arc<AccountScreener>);
+
 #[derive(Deserialize)]
 pub struct RecentFeeBumpsParams {
     #[serde(default = "default_limit")]
@@ -19,16 +22,17 @@ fn default_limit() -> i64 {
     50
 }
 
-pub fn routes(fee_bump_service: Arc<FeeBumpTrackerService>) -> Router {
+pub fn routes(
+    fee_bump_service: Arc<FeeBumpTrackerService>,
+    screener: Arc<AccountScreener>,
+) -> Router {
     Router::new()
         .route("/stats", get(get_fee_bump_stats))
         .route("/recent", get(get_recent_fee_bumps))
-        .with_state(fee_bump_service)
+        .with_state((fee_bump_service, screener))
 }
 
-async fn get_fee_bump_stats(
-    State(service): State<Arc<FeeBumpTrackerService>>,
-) -> Json<FeeBumpStats> {
+async fn get_fee_bump_stats(State((service, _)): State<FeeBumpState>) -> Json<FeeBumpStats> {
     // In a real app, handle error properly (e.g. 500)
     let stats = service
         .get_fee_bump_stats()
@@ -44,14 +48,25 @@ async fn get_fee_bump_stats(
 }
 
 async fn get_recent_fee_bumps(
-    State(service): State<Arc<FeeBumpTrackerService>>,
+    State((service, screener)): State<FeeBumpState>,
     Query(params): Query<RecentFeeBumpsParams>,
-) -> Json<Vec<FeeBumpTransaction>> {
+) -> Json<Vec<Screened<FeeBumpTransaction>>> {
     let limit = params.limit.clamp(1, 100);
     // In a real app, handle error properly
     let transactions = service
         .get_recent_fee_bumps(limit)
         .await
         .unwrap_or_default();
-    Json(transactions)
+    let statuses = screener
+        .statuses(transactions.iter().map(|t| t.fee_source.as_str()))
+        .await;
+    Json(
+        transactions
+            .into_iter()
+            .map(|t| {
+                let fee_source = t.fee_source.clone();
+                Screened::new(t, &[&fee_source], &statuses)
+            })
+            .collect(),
+    )
 }
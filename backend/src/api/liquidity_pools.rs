@@ -3,11 +3,16 @@ use axum::{
     routing::get,
     Json, Router,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::sync::Arc;
 
+use crate::error::ApiResult;
 use crate::models::{LiquidityPool, LiquidityPoolSnapshot, LiquidityPoolStats};
+use crate::preferences::Preferences;
 use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
+use crate::services::valuation::{QuoteQuery, ValuationService};
+use crate::time_range::TimeRange;
 
 #[derive(Deserialize)]
 pub struct RankingsParams {
@@ -45,11 +50,36 @@ pub fn routes(analyzer: Arc<LiquidityPoolAnalyzer>) -> Router {
         .with_state(analyzer)
 }
 
+/// Serialize `items`, adding quote-currency values when a non-USD quote applies
+async fn with_quote<T: Serialize>(
+    analyzer: &LiquidityPoolAnalyzer,
+    items: &[T],
+    quote: &QuoteQuery,
+    prefs: &Preferences,
+    time_field: &str,
+) -> ApiResult<Json<Vec<Value>>> {
+    let quote = quote.resolve(&prefs.0)?;
+    let mut values = items
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(anyhow::Error::from)?;
+
+    if !quote.is_usd() {
+        ValuationService::new(analyzer.db_pool().clone())
+            .annotate_all(&mut values, &quote, time_field)
+            .await;
+    }
+    Ok(Json(values))
+}
+
 async fn list_pools(
     State(analyzer): State<Arc<LiquidityPoolAnalyzer>>,
-) -> Json<Vec<LiquidityPool>> {
-    let pools = analyzer.get_all_pools().await.unwrap_or_default();
-    Json(pools)
+    Query(quote): Query<QuoteQuery>,
+    prefs: Preferences,
+) -> ApiResult<Json<Vec<Value>>> {
+    let pools: Vec<LiquidityPool> = analyzer.get_all_pools().await.unwrap_or_default();
+    with_quote(&analyzer, &pools, &quote, &prefs, "last_synced_at").await
 }
 
 async fn get_pool_stats(
@@ -103,11 +133,14 @@ async fn get_pool_snapshots(
     State(analyzer): State<Arc<LiquidityPoolAnalyzer>>,
     Path(pool_id): Path<String>,
     Query(params): Query<SnapshotParams>,
-) -> Json<Vec<LiquidityPoolSnapshot>> {
+    Query(quote): Query<QuoteQuery>,
+    prefs: Preferences,
+    range: TimeRange,
+) -> ApiResult<Json<Vec<Value>>> {
     let limit = params.limit.clamp(1, 500);
-    let snapshots = analyzer
-        .get_pool_snapshots(&pool_id, limit)
+    let snapshots: Vec<LiquidityPoolSnapshot> = analyzer
+        .get_pool_snapshots_in_range(&pool_id, &range, limit)
         .await
         .unwrap_or_default();
-    Json(snapshots)
+    with_quote(&analyzer, &snapshots, &quote, &prefs, "snapshot_at").await
 }
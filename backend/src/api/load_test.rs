@@ -0,0 +1,268 @@
+//! Synthetic load for capacity planning, compiled only with the `load-test`
+//! feature.
+//!
+//! Each endpoint generates its load inside the process and reports the
+//! latency distribution, so a staging deployment can be sized without
+//! external tooling. Runs stop at `max_duration_ms`, which should stay below
+//! the request budget (`REQUEST_TIMEOUT_DEFAULT_SECS`).
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::util::ServiceExt;
+
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::websocket::{WsMessage, WsState};
+
+const MAX_LOOKUPS: usize = 10_000;
+const MAX_CONCURRENCY: usize = 100;
+const MAX_BROADCASTS: usize = 100_000;
+const DEFAULT_MAX_DURATION_MS: u64 = 5_000;
+const DEFAULT_CHANNEL: &str = "load-test";
+
+#[derive(Clone)]
+pub struct LoadTestState {
+    pub db: Arc<Database>,
+    /// Serves `GET /api/corridors/:corridor_key`, without rate limiting
+    pub corridor_routes: Router,
+    pub ws_state: Arc<WsState>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencySummary {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(f64::total_cmp);
+        // Nearest-rank percentile
+        let percentile = |p: f64| ms[((p / 100.0 * ms.len() as f64).ceil() as usize).max(1) - 1];
+
+        Self {
+            count: ms.len(),
+            min_ms: ms[0],
+            mean_ms: ms.iter().sum::<f64>() / ms.len() as f64,
+            p50_ms: percentile(50.0),
+            p90_ms: percentile(90.0),
+            p95_ms: percentile(95.0),
+            p99_ms: percentile(99.0),
+            max_ms: ms[ms.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoadTestReport {
+    pub requested: usize,
+    pub completed: usize,
+    /// True when `max_duration_ms` ran out before all iterations finished
+    pub stopped_early: bool,
+    pub elapsed_ms: f64,
+    pub throughput_per_sec: f64,
+    pub latency: LatencySummary,
+    /// Response status counts (corridor lookups only)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub status_counts: BTreeMap<u16, usize>,
+    /// Connections subscribed to the channel (broadcasts only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscribers: Option<usize>,
+}
+
+impl LoadTestReport {
+    fn new(requested: usize, samples: &[Duration], elapsed: Duration) -> Self {
+        let elapsed_secs = elapsed.as_secs_f64();
+        Self {
+            requested,
+            completed: samples.len(),
+            stopped_early: samples.len() < requested,
+            elapsed_ms: elapsed_secs * 1000.0,
+            throughput_per_sec: if elapsed_secs > 0.0 {
+                samples.len() as f64 / elapsed_secs
+            } else {
+                0.0
+            },
+            latency: LatencySummary::from_samples(samples),
+            status_counts: BTreeMap::new(),
+            subscribers: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CorridorLookupRequest {
+    pub iterations: usize,
+    pub concurrency: Option<usize>,
+    /// Corridors to cycle through; defaults to the 50 most reliable
+    pub corridor_keys: Option<Vec<String>>,
+    pub max_duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastRequest {
+    pub iterations: usize,
+    /// Defaults to `load-test`, which only synthetic clients subscribe to
+    pub channel: Option<String>,
+    pub max_duration_ms: Option<u64>,
+}
+
+fn check_iterations(iterations: usize, max: usize) -> ApiResult<()> {
+    if iterations == 0 || iterations > max {
+        return Err(ApiError::bad_request(
+            "INVALID_INPUT",
+            format!("iterations must be between 1 and {}", max),
+        ));
+    }
+    Ok(())
+}
+
+/// POST /api/admin/load-test/corridor-lookups - N corridor detail requests
+/// through the real handler, `concurrency` at a time
+pub async fn corridor_lookups(
+    State(state): State<LoadTestState>,
+    Json(request): Json<CorridorLookupRequest>,
+) -> ApiResult<Json<LoadTestReport>> {
+    check_iterations(request.iterations, MAX_LOOKUPS)?;
+    let concurrency = request.concurrency.unwrap_or(10).clamp(1, MAX_CONCURRENCY);
+    let keys = match request.corridor_keys {
+        Some(keys) if !keys.is_empty() => keys,
+        Some(_) => {
+            return Err(ApiError::bad_request(
+                "INVALID_INPUT",
+                "corridor_keys must not be empty",
+            ))
+        }
+        None => state
+            .db
+            .list_corridors(50, 0)
+            .await?
+            .iter()
+            .map(|c| c.to_string_key())
+            .collect(),
+    };
+    if keys.is_empty() {
+        return Err(ApiError::bad_request(
+            "INVALID_INPUT",
+            "No corridors are tracked; pass corridor_keys",
+        ));
+    }
+
+    let deadline = Instant::now()
+        + Duration::from_millis(request.max_duration_ms.unwrap_or(DEFAULT_MAX_DURATION_MS));
+    let started = Instant::now();
+    let results: Vec<Option<(Duration, u16)>> = stream::iter(0..request.iterations)
+        .map(|i| {
+            let router = state.corridor_routes.clone();
+            let uri = format!("/api/corridors/{}", keys[i % keys.len()]);
+            async move {
+                if Instant::now() >= deadline {
+                    return None;
+                }
+                let request = Request::builder().uri(uri).body(Body::empty()).ok()?;
+                let start = Instant::now();
+                let status = match router.oneshot(request).await {
+                    Ok(response) => response.status(),
+                    Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                Some((start.elapsed(), status.as_u16()))
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+    let elapsed = started.elapsed();
+
+    let mut samples = Vec::with_capacity(results.len());
+    let mut status_counts = BTreeMap::new();
+    for (latency, status) in results.into_iter().flatten() {
+        samples.push(latency);
+        *status_counts.entry(status).or_insert(0) += 1;
+    }
+
+    let mut report = LoadTestReport::new(request.iterations, &samples, elapsed);
+    report.status_counts = status_counts;
+    Ok(Json(report))
+}
+
+/// POST /api/admin/load-test/ws-broadcasts - M channel broadcasts of a ping
+/// message to every subscribed WebSocket connection
+pub async fn ws_broadcasts(
+    State(state): State<LoadTestState>,
+    Json(request): Json<BroadcastRequest>,
+) -> ApiResult<Json<LoadTestReport>> {
+    check_iterations(request.iterations, MAX_BROADCASTS)?;
+    let channel = request
+        .channel
+        .unwrap_or_else(|| DEFAULT_CHANNEL.to_string());
+    let deadline = Instant::now()
+        + Duration::from_millis(request.max_duration_ms.unwrap_or(DEFAULT_MAX_DURATION_MS));
+
+    let started = Instant::now();
+    let mut samples = Vec::with_capacity(request.iterations);
+    for _ in 0..request.iterations {
+        if Instant::now() >= deadline {
+            break;
+        }
+        let message = WsMessage::Ping {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+        let start = Instant::now();
+        state.ws_state.broadcast_to_channel(&channel, message).await;
+        samples.push(start.elapsed());
+    }
+
+    let mut report = LoadTestReport::new(request.iterations, &samples, started.elapsed());
+    report.subscribers = Some(state.ws_state.channel_subscription_count(&channel));
+    Ok(Json(report))
+}
+
+pub fn routes(state: LoadTestState) -> Router {
+    Router::new()
+        .route(
+            "/api/admin/load-test/corridor-lookups",
+            post(corridor_lookups),
+        )
+        .route("/api/admin/load-test/ws-broadcasts", post(ws_broadcasts))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_summary_percentiles() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_samples(&samples);
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.min_ms, 1.0);
+        assert_eq!(summary.p50_ms, 50.0);
+        assert_eq!(summary.p95_ms, 95.0);
+        assert_eq!(summary.p99_ms, 99.0);
+        assert_eq!(summary.max_ms, 100.0);
+        assert_eq!(summary.mean_ms, 50.5);
+
+        assert_eq!(LatencySummary::from_samples(&[]), LatencySummary::default());
+    }
+}
@@ -1,28 +1,63 @@
 pub mod account_merges;
+pub mod admin_corridors;
+pub mod admin_db;
+pub mod admin_email;
+pub mod admin_ingestion;
+pub mod admin_jobs;
+pub mod admin_surveillance;
+pub mod admin_tenants;
 pub mod achievements;
+pub mod anchor_callbacks;
+pub mod anchor_claims;
+pub mod anchor_self_reported;
+pub mod anchor_merge;
 pub mod anchors;
 pub mod anchors_cached;
 pub mod api_keys;
+pub mod asset_compliance;
+pub mod asset_supply;
 pub mod auth;
+pub mod badges;
 pub mod cache_stats;
+pub mod corridor_anomalies;
+pub mod corridor_cohorts;
+pub mod corridor_health;
+pub mod corridor_seasonality;
+pub mod corridor_simulation;
 pub mod corridors;
 pub mod corridors_cached;
 pub mod cost_calculator;
 // pub mod digest;  // Commented out - depends on email module
+pub mod digest_preview;
 pub mod fee_bump;
 pub mod governance;
 pub mod liquidity_pools;
+#[cfg(feature = "load-test")]
+pub mod load_test;
 pub mod metrics;
 pub mod metrics_cached;
+pub mod movers;
 pub mod network;
+pub mod notifications;
 pub mod oauth;
 pub mod prediction;
+pub mod preferences;
 pub mod price_feed;
+pub mod search;
 pub mod sep10;
 pub mod sep24_proxy;
 pub mod sep31_proxy;
+pub mod sep_auth;
+pub mod sep_info_cache;
+pub mod sep_performance;
+pub mod sep_transactions;
+pub mod sessions;
+pub mod snapshot_signatures;
+pub mod snapshot_sla;
+pub mod status;
 pub mod transactions;
 pub mod trustlines;
+pub mod usage;
 pub mod verification_rewards;
 pub mod webhooks;
 pub mod api_analytics;
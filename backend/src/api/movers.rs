@@ -0,0 +1,40 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::movers::{MoversReport, MoversService, DEFAULT_LIMIT};
+use crate::tenant::TenantScope;
+
+/// Most movers returned per metric and window
+const MAX_LIMIT: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct MoversParams {
+    pub limit: Option<usize>,
+}
+
+/// GET /api/analytics/movers - Corridors with the biggest 1h and 24h changes
+/// in volume, health and spread
+pub async fn get_movers(
+    State(service): State<Arc<MoversService>>,
+    tenant: TenantScope,
+    Query(params): Query<MoversParams>,
+) -> ApiResult<Json<MoversReport>> {
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let report = service.report(limit, &tenant).await.map_err(|e| {
+        tracing::error!("Failed to compute movers: {}", e);
+        ApiError::internal("MOVERS_ERROR", "Failed to compute movers")
+    })?;
+    Ok(Json(report))
+}
+
+pub fn routes(service: Arc<MoversService>) -> Router {
+    Router::new()
+        .route("/api/analytics/movers", get(get_movers))
+        .with_state(service)
+}
@@ -1,11 +1,15 @@
+use crate::error::{ApiError, ApiResult};
 use crate::network::{NetworkConfig, StellarNetwork};
+use crate::services::network_congestion::{NetworkCongestion, NetworkCongestionService};
 use axum::{
+    extract::State,
     http::StatusCode,
     response::Json,
     routing::{get, post},
     Router,
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tracing::{info, warn};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -114,6 +118,20 @@ pub async fn switch_network(
     Ok(Json(response))
 }
 
+/// GET /api/network/congestion - Current network-wide congestion level and the signals behind it
+pub async fn get_network_congestion(
+    State(service): State<Arc<NetworkCongestionService>>,
+) -> ApiResult<Json<NetworkCongestion>> {
+    let report = service.current().await.map_err(|e| {
+        tracing::error!("Failed to measure network congestion: {}", e);
+        ApiError::internal(
+            "NETWORK_CONGESTION_ERROR",
+            "Failed to measure network congestion",
+        )
+    })?;
+    Ok(Json(report))
+}
+
 /// Create network routes
 pub fn routes() -> Router {
     Router::new()
@@ -122,6 +140,13 @@ pub fn routes() -> Router {
         .route("/switch", post(switch_network))
 }
 
+/// Network congestion route, kept apart from `routes` because it needs the RPC client
+pub fn congestion_routes(service: Arc<NetworkCongestionService>) -> Router {
+    Router::new()
+        .route("/api/network/congestion", get(get_network_congestion))
+        .with_state(service)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
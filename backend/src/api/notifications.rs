@@ -0,0 +1,106 @@
+use axum::{
+    extract::{Query, State},
+    response::Html,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::email::unsubscribe::{TokenError, UnsubscribeScope, UnsubscribeTokens};
+use crate::error::{ApiError, ApiResult};
+use crate::i18n;
+
+#[derive(Clone)]
+pub struct NotificationsState {
+    pub db: Arc<Database>,
+    pub tokens: UnsubscribeTokens,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeQuery {
+    pub token: String,
+}
+
+async fn audit_unsubscribe(
+    db: &Database,
+    user_id: &str,
+    scope: UnsubscribeScope,
+    status: &str,
+    error: Option<String>,
+) {
+    let details = serde_json::json!({ "scope": scope.as_str(), "error": error });
+    let prev_hash = db.admin_audit_logger.latest_hash().await.unwrap_or(None);
+    if let Err(e) = db
+        .admin_audit_logger
+        .log_action(
+            "notifications.unsubscribe",
+            "user_preferences",
+            user_id,
+            status,
+            details,
+            prev_hash.as_deref(),
+        )
+        .await
+    {
+        tracing::error!("Failed to write unsubscribe audit entry: {}", e);
+    }
+}
+
+/// GET /api/notifications/unsubscribe?token=... - One-click opt-out from the
+/// link in a digest or alert email. No login; the signed token is the
+/// authorization.
+pub async fn unsubscribe(
+    State(state): State<NotificationsState>,
+    Query(query): Query<UnsubscribeQuery>,
+) -> ApiResult<Html<String>> {
+    let claims = state.tokens.verify(&query.token).map_err(|e| match e {
+        TokenError::Expired => ApiError::bad_request(
+            "UNSUBSCRIBE_TOKEN_EXPIRED",
+            "This unsubscribe link has expired; manage notifications from your settings",
+        ),
+        _ => ApiError::bad_request("INVALID_UNSUBSCRIBE_TOKEN", e.to_string()),
+    })?;
+
+    if let Err(e) = state
+        .db
+        .preferences()
+        .unsubscribe(&claims.user_id, claims.scope)
+        .await
+    {
+        audit_unsubscribe(
+            &state.db,
+            &claims.user_id,
+            claims.scope,
+            "failure",
+            Some(e.to_string()),
+        )
+        .await;
+        return Err(e.into());
+    }
+    audit_unsubscribe(&state.db, &claims.user_id, claims.scope, "success", None).await;
+
+    let locale = state.db.preferences().get(&claims.user_id).await?.locale;
+    let key = match claims.scope {
+        UnsubscribeScope::Digest => "unsubscribe-confirmed-digest",
+        UnsubscribeScope::Alerts => "unsubscribe-confirmed-alerts",
+    };
+
+    Ok(Html(format!(
+        r#"<!DOCTYPE html>
+<html lang="{}">
+<body style="font-family: Arial, sans-serif; margin: 20px;">
+    <p>{}</p>
+</body>
+</html>"#,
+        locale.as_str(),
+        i18n::t(locale, key, &[])
+    )))
+}
+
+pub fn routes(state: NotificationsState) -> Router {
+    Router::new()
+        .route("/api/notifications/unsubscribe", get(unsubscribe))
+        .with_state(state)
+}
@@ -8,10 +8,11 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use sqlx::SqlitePool;
+use std::sync::Arc;
 
 use crate::auth::oauth::{OAuthService, TokenResponse};
 use crate::auth_middleware::AuthUser;
+use crate::database::Database;
 
 /// OAuth Token Request (for /api/oauth/token)
 #[derive(Debug, Deserialize)]
@@ -71,7 +72,7 @@ pub struct ListOAuthAppsResponse {
 
 /// POST /api/oauth/authorize - Request authorization code
 pub async fn authorize(
-    State(db): State<SqlitePool>,
+    State(db): State<Arc<Database>>,
     auth_user: AuthUser,
     Query(request): Query<OAuthAuthorizeRequest>,
 ) -> Result<Response, OAuthApiError> {
@@ -81,7 +82,7 @@ pub async fn authorize(
         ));
     }
 
-    let service = OAuthService::new(db);
+    let service = OAuthService::new(db.pool().clone(), db.cipher().clone());
 
     // Validate scopes
     service
@@ -110,10 +111,10 @@ pub async fn authorize(
 
 /// POST /api/oauth/token - Exchange authorization code for tokens
 pub async fn token(
-    State(db): State<SqlitePool>,
+    State(db): State<Arc<Database>>,
     Json(request): Json<OAuthTokenRequest>,
 ) -> Result<Response, OAuthApiError> {
-    let service = OAuthService::new(db.clone());
+    let service = OAuthService::new(db.pool().clone(), db.cipher().clone());
 
     // Validate client credentials
     let user_id = service
@@ -124,7 +125,7 @@ pub async fn token(
     // Get user from database for username
     let user_row = sqlx::query("SELECT username FROM users WHERE id = ?")
         .bind(user_id.clone())
-        .fetch_optional(&db)
+        .fetch_optional(db.pool())
         .await
         .map_err(|e| OAuthApiError::ServerError(e.to_string()))?
         .ok_or_else(|| OAuthApiError::InvalidClient)?;
@@ -234,10 +235,10 @@ pub async fn token(
 
 /// POST /api/oauth/revoke - Revoke an access token
 pub async fn revoke(
-    State(db): State<SqlitePool>,
+    State(db): State<Arc<Database>>,
     Json(request): Json<OAuthRevokeRequest>,
 ) -> Result<Response, OAuthApiError> {
-    let service = OAuthService::new(db);
+    let service = OAuthService::new(db.pool().clone(), db.cipher().clone());
 
     // Validate client credentials
     service
@@ -260,7 +261,7 @@ pub async fn revoke(
 
 /// GET /api/oauth/apps - List OAuth apps for authenticated user
 pub async fn list_apps(
-    State(db): State<SqlitePool>,
+    State(db): State<Arc<Database>>,
     auth_user: AuthUser,
 ) -> Result<Response, OAuthApiError> {
     let rows = sqlx::query(
@@ -271,7 +272,7 @@ pub async fn list_apps(
         "#,
     )
     .bind(auth_user.user_id)
-    .fetch_all(&db)
+    .fetch_all(db.pool())
     .await
     .map_err(|e: sqlx::Error| OAuthApiError::ServerError(e.to_string()))?;
 
@@ -340,7 +341,7 @@ impl From<anyhow::Error> for OAuthApiError {
 }
 
 /// Create OAuth routes
-pub fn routes(db: SqlitePool) -> Router {
+pub fn routes(db: Arc<Database>) -> Router {
     Router::new()
         .route("/api/oauth/authorize", post(authorize))
         .route("/api/oauth/token", post(token))
@@ -0,0 +1,38 @@
+use axum::{extract::State, routing::get, Json, Router};
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::error::ApiResult;
+use crate::preferences::UserPreferences;
+
+/// GET /api/me/preferences - Preferences of the authenticated user
+pub async fn get_preferences(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+) -> ApiResult<Json<UserPreferences>> {
+    let prefs = db.preferences().get(&user.user_id).await?;
+    Ok(Json(prefs))
+}
+
+/// PUT /api/me/preferences - Replace the authenticated user's preferences
+pub async fn put_preferences(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+    Json(prefs): Json<UserPreferences>,
+) -> ApiResult<Json<UserPreferences>> {
+    let prefs = prefs.validate()?;
+    db.preferences().upsert(&user.user_id, &prefs).await?;
+
+    tracing::info!(user_id = %user.user_id, "Updated user preferences");
+    Ok(Json(prefs))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route(
+            "/api/me/preferences",
+            get(get_preferences).put(put_preferences),
+        )
+        .with_state(db)
+}
@@ -0,0 +1,243 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::db::search::{normalize_tag, ParsedSearchQuery, TaggableEntity, MAX_TAGS_PER_ENTITY};
+use crate::error::{ApiError, ApiResult};
+use crate::tenant::TenantScope;
+
+/// Longest query string accepted by the search endpoint
+const MAX_QUERY_LEN: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub q: String,
+    /// Restrict results to `anchor` or `corridor`
+    #[serde(rename = "type")]
+    pub entity_type: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub entity_type: Option<TaggableEntity>,
+    pub results: Vec<SearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddTagsRequest {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntityTagsResponse {
+    pub entity_type: TaggableEntity,
+    pub entity_id: String,
+    pub tags: Vec<String>,
+}
+
+/// GET /api/search?q= - Search anchors and corridors by name, asset code, domain and tag
+pub async fn search(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    Query(params): Query<SearchParams>,
+) -> ApiResult<Json<SearchResponse>> {
+    let raw = params.q.trim();
+    if raw.is_empty() || raw.len() > MAX_QUERY_LEN {
+        return Err(ApiError::bad_request(
+            "INVALID_QUERY",
+            format!("q must be between 1 and {} characters", MAX_QUERY_LEN),
+        ));
+    }
+
+    let mut parsed = ParsedSearchQuery::parse(raw);
+    if let Some(kind) = params.entity_type.as_deref() {
+        parsed.entity_type = Some(parse_entity_type(kind)?);
+    }
+
+    let hits = db
+        .entity_search()
+        .search(&parsed, &tenant, params.limit.clamp(1, 100))
+        .await?;
+
+    let results = hits
+        .into_iter()
+        .map(|hit| SearchResult {
+            entity_type: hit.entity_type,
+            entity_id: hit.entity_id,
+            title: hit.title,
+            tags: hit.tags.split_whitespace().map(String::from).collect(),
+            score: hit.score,
+        })
+        .collect();
+
+    Ok(Json(SearchResponse {
+        query: raw.to_string(),
+        entity_type: parsed.entity_type,
+        results,
+    }))
+}
+
+/// GET /api/tags/:entity_type/:entity_id - List tags on an anchor or corridor
+pub async fn list_tags(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    Path((entity_type, entity_id)): Path<(String, String)>,
+) -> ApiResult<Json<EntityTagsResponse>> {
+    let entity = parse_entity_type(&entity_type)?;
+    ensure_in_scope(&db, &tenant, entity, &entity_id).await?;
+    let tags = db.entity_search().list_tags(entity, &entity_id).await?;
+
+    Ok(Json(EntityTagsResponse {
+        entity_type: entity,
+        entity_id,
+        tags,
+    }))
+}
+
+/// POST /api/tags/:entity_type/:entity_id - Attach tags to an anchor or corridor
+pub async fn add_tags(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    user: AuthUser,
+    Path((entity_type, entity_id)): Path<(String, String)>,
+    Json(req): Json<AddTagsRequest>,
+) -> ApiResult<Json<EntityTagsResponse>> {
+    let entity = parse_entity_type(&entity_type)?;
+
+    let mut tags = Vec::with_capacity(req.tags.len());
+    for raw in &req.tags {
+        let tag = normalize_tag(raw).ok_or_else(|| {
+            ApiError::bad_request(
+                "INVALID_TAG",
+                format!(
+                    "Tag '{}' must be 1-32 characters of letters, digits, '-' or '_'",
+                    raw
+                ),
+            )
+        })?;
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    if tags.is_empty() {
+        return Err(ApiError::bad_request("INVALID_TAG", "tags must not be empty"));
+    }
+
+    let search = db.entity_search();
+    if !search.entity_exists(entity, &entity_id, &tenant).await? {
+        return Err(ApiError::not_found(
+            "ENTITY_NOT_FOUND",
+            format!("{} {} not found", entity.as_str(), entity_id),
+        ));
+    }
+
+    let existing = search.list_tags(entity, &entity_id).await?;
+    let new_count = tags.iter().filter(|t| !existing.contains(t)).count();
+    if existing.len() + new_count > MAX_TAGS_PER_ENTITY {
+        return Err(ApiError::bad_request(
+            "TOO_MANY_TAGS",
+            format!("An entity may have at most {} tags", MAX_TAGS_PER_ENTITY),
+        ));
+    }
+
+    let tags = search
+        .add_tags(entity, &entity_id, &tags, &user.user_id)
+        .await?;
+
+    Ok(Json(EntityTagsResponse {
+        entity_type: entity,
+        entity_id,
+        tags,
+    }))
+}
+
+/// DELETE /api/tags/:entity_type/:entity_id/:tag - Remove a tag
+pub async fn remove_tag(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    _user: AuthUser,
+    Path((entity_type, entity_id, tag)): Path<(String, String, String)>,
+) -> ApiResult<StatusCode> {
+    let entity = parse_entity_type(&entity_type)?;
+    ensure_in_scope(&db, &tenant, entity, &entity_id).await?;
+    let tag = normalize_tag(&tag)
+        .ok_or_else(|| ApiError::bad_request("INVALID_TAG", "Invalid tag"))?;
+
+    if db.entity_search().remove_tag(entity, &entity_id, &tag).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found(
+            "TAG_NOT_FOUND",
+            format!("Tag '{}' not found on {} {}", tag, entity.as_str(), entity_id),
+        ))
+    }
+}
+
+/// Report anchors and corridors outside the tenant's directory as missing
+async fn ensure_in_scope(
+    db: &Database,
+    tenant: &TenantScope,
+    entity: TaggableEntity,
+    entity_id: &str,
+) -> ApiResult<()> {
+    let in_scope = match entity {
+        TaggableEntity::Anchor => db.tenants().has_anchor(tenant, entity_id).await?,
+        TaggableEntity::Corridor => db.tenants().has_corridor(tenant, entity_id).await?,
+    };
+    if in_scope {
+        Ok(())
+    } else {
+        Err(ApiError::not_found(
+            "ENTITY_NOT_FOUND",
+            format!("{} {} not found", entity.as_str(), entity_id),
+        ))
+    }
+}
+
+fn parse_entity_type(value: &str) -> Result<TaggableEntity, ApiError> {
+    TaggableEntity::parse(value).ok_or_else(|| {
+        ApiError::bad_request(
+            "INVALID_ENTITY_TYPE",
+            format!("Unsupported entity type '{}': expected anchor or corridor", value),
+        )
+    })
+}
+
+/// Public search and tag listing routes
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/search", get(search))
+        .route("/api/tags/:entity_type/:entity_id", get(list_tags))
+        .with_state(db)
+}
+
+/// Tag management routes (require authentication)
+pub fn tag_management_routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/tags/:entity_type/:entity_id", post(add_tags))
+        .route("/api/tags/:entity_type/:entity_id/:tag", delete(remove_tag))
+        .with_state(db)
+}
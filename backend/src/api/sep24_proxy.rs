@@ -3,50 +3,103 @@
 
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     Json,
 };
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
 use serde_json::Value;
 use std::sync::Arc;
-use std::time::Duration;
-
-/// Allowed transfer server hosts (env: SEP24_ALLOWED_ORIGINS, comma-separated).
-/// If unset, any origin is allowed (use in dev only).
-fn allowed_origins() -> Vec<String> {
-    std::env::var("SEP24_ALLOWED_ORIGINS")
-        .ok()
-        .map(|s| s.split(',').map(|x| x.trim().to_string()).collect())
-        .unwrap_or_default()
-}
+use std::time::{Duration, Instant};
 
-fn is_origin_allowed(transfer_server: &str) -> bool {
-    let allowed = allowed_origins();
-    if allowed.is_empty() {
-        return true;
-    }
-    let url = match transfer_server.strip_suffix('/') {
-        Some(u) => u,
-        None => transfer_server,
-    };
-    allowed.iter().any(|o| url.starts_with(o) || o == "*")
-}
+use crate::api::{sep_auth, sep_info_cache, sep_performance};
+use crate::auth_middleware::AuthUser;
+use crate::cache::CacheManager;
+use crate::config::{SepAnchor, SepProxySettings};
+use crate::database::Database;
+use crate::db::sep_transactions::NewSepTransaction;
+use crate::http_client;
 
 #[derive(Clone)]
 pub struct Sep24State {
     pub client: Arc<Client>,
+    /// Allowed transfer servers and listed anchors (`[sep24]` settings)
+    pub settings: Arc<SepProxySettings>,
+    /// When set, transactions started through the proxy are recorded for status tracking
+    pub db: Option<Arc<Database>>,
+    /// When set, anchor `/info` responses are cached
+    pub cache: Option<Arc<CacheManager>>,
 }
 
 impl Sep24State {
-    pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .unwrap_or_else(|_| Client::new());
+    pub fn new(settings: SepProxySettings) -> Self {
+        Self {
+            client: Arc::new(http_client::shared().client(Duration::from_secs(30))),
+            settings: Arc::new(settings),
+            db: None,
+            cache: None,
+        }
+    }
+
+    pub fn with_tracking(settings: SepProxySettings, db: Arc<Database>) -> Self {
         Self {
-            client: Arc::new(client),
+            db: Some(db),
+            ..Self::new(settings)
+        }
+    }
+
+    pub fn with_info_cache(mut self, cache: Arc<CacheManager>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Send a request to an anchor through its host's circuit breaker. When
+    /// tracking, its latency and outcome are recorded for `endpoint`.
+    async fn send(
+        &self,
+        transfer_server: &str,
+        endpoint: &str,
+        request: RequestBuilder,
+    ) -> Result<Response, Sep24Error> {
+        let started = Instant::now();
+        let result = http_client::shared().send(request).await;
+        if let Some(db) = &self.db {
+            sep_performance::record_request(
+                db,
+                "sep24",
+                transfer_server,
+                endpoint,
+                started,
+                &result,
+            );
+        }
+        result.map_err(|e| Sep24Error::Proxy(e.to_string()))
+    }
+
+    /// Anchor token from the request header or the user's stored session
+    async fn anchor_token(
+        &self,
+        user: Option<&AuthUser>,
+        headers: &HeaderMap,
+        transfer_server: &str,
+    ) -> Option<String> {
+        sep_auth::anchor_token(self.db.as_deref(), user, headers, transfer_server).await
+    }
+
+    /// Record a transaction the anchor just created. Tracking is best-effort:
+    /// the anchor's response is returned to the caller even if this fails.
+    async fn track(&self, new: NewSepTransaction) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        let anchor_transaction_id = new.anchor_transaction_id.clone();
+        if let Err(e) = db.sep_transactions().create(new).await {
+            tracing::warn!(
+                anchor_transaction_id = %anchor_transaction_id,
+                "Failed to record SEP transaction: {}",
+                e
+            );
         }
     }
 }
@@ -66,18 +119,20 @@ pub async fn get_info(
     State(state): State<Sep24State>,
     Query(q): Query<InfoQuery>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&q.transfer_server) {
+    if !state.settings.allows_transfer_server(&q.transfer_server) {
         return Err(Sep24Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    if let Some(cache) = &state.cache {
+        if let Some(info) = sep_info_cache::get(cache, "sep24", &q.transfer_server).await {
+            return Ok(Json(info));
+        }
+    }
     let url = format!("{}/info", base_url(&q.transfer_server));
     let resp = state
-        .client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
+        .send(&q.transfer_server, "GET /info", state.client.get(&url))
+        .await?;
 
     let status = resp.status();
     let body = resp
@@ -88,6 +143,9 @@ pub async fn get_info(
     if !status.is_success() {
         return Err(Sep24Error::Anchor(status.as_u16(), body));
     }
+    if let Some(cache) = &state.cache {
+        sep_info_cache::store(cache, "sep24", &q.transfer_server, &body).await;
+    }
     Ok(Json(body))
 }
 
@@ -109,30 +167,35 @@ pub struct DepositInteractiveBody {
     pub amount: Option<String>,
     #[serde(default)]
     pub lang: Option<String>,
-    /// JWT from SEP-10 (optional for some anchors)
+    /// Anchor the transfer server belongs to, for status tracking
     #[serde(default)]
-    pub jwt: Option<String>,
+    pub anchor_id: Option<String>,
     #[serde(flatten)]
     pub extra: Value,
 }
 
 pub async fn post_deposit_interactive(
     State(state): State<Sep24State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<DepositInteractiveBody>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&body.transfer_server) {
+    if !state.settings.allows_transfer_server(&body.transfer_server) {
         return Err(Sep24Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &body.transfer_server)
+        .await;
     let url = format!(
         "{}/transactions/deposit/interactive",
         base_url(&body.transfer_server)
     );
 
     let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
     let payload = serde_json::json!({
         "asset_code": body.asset_code,
@@ -143,11 +206,13 @@ pub async fn post_deposit_interactive(
         "amount": body.amount,
         "lang": body.lang,
     });
-    let resp = req
-        .json(&payload)
-        .send()
-        .await
-        .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(
+            &body.transfer_server,
+            "POST /transactions/deposit/interactive",
+            req.json(&payload),
+        )
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -158,6 +223,22 @@ pub async fn post_deposit_interactive(
     if !status.is_success() {
         return Err(Sep24Error::Anchor(status.as_u16(), data));
     }
+    if let (Some(user), Some(id)) = (&user, data.get("id").and_then(Value::as_str)) {
+        state
+            .track(NewSepTransaction {
+                user_id: user.user_id.clone(),
+                anchor_id: body.anchor_id,
+                protocol: "sep24".to_string(),
+                kind: "deposit".to_string(),
+                transfer_server: base_url(&body.transfer_server),
+                anchor_transaction_id: id.to_string(),
+                asset_code: body.asset_code,
+                amount: body.amount,
+                status: "incomplete".to_string(),
+                auth_token: token,
+            })
+            .await;
+    }
     Ok(Json(data))
 }
 
@@ -182,28 +263,33 @@ pub struct WithdrawInteractiveBody {
     #[serde(default)]
     pub lang: Option<String>,
     #[serde(default)]
-    pub jwt: Option<String>,
+    pub anchor_id: Option<String>,
     #[serde(flatten)]
     pub extra: Value,
 }
 
 pub async fn post_withdraw_interactive(
     State(state): State<Sep24State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<WithdrawInteractiveBody>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&body.transfer_server) {
+    if !state.settings.allows_transfer_server(&body.transfer_server) {
         return Err(Sep24Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &body.transfer_server)
+        .await;
     let url = format!(
         "{}/transactions/withdraw/interactive",
         base_url(&body.transfer_server)
     );
 
     let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
     let payload = serde_json::json!({
         "asset_code": body.asset_code,
@@ -215,11 +301,13 @@ pub async fn post_withdraw_interactive(
         "amount": body.amount,
         "lang": body.lang,
     });
-    let resp = req
-        .json(&payload)
-        .send()
-        .await
-        .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(
+            &body.transfer_server,
+            "POST /transactions/withdraw/interactive",
+            req.json(&payload),
+        )
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -230,16 +318,30 @@ pub async fn post_withdraw_interactive(
     if !status.is_success() {
         return Err(Sep24Error::Anchor(status.as_u16(), data));
     }
+    if let (Some(user), Some(id)) = (&user, data.get("id").and_then(Value::as_str)) {
+        state
+            .track(NewSepTransaction {
+                user_id: user.user_id.clone(),
+                anchor_id: body.anchor_id,
+                protocol: "sep24".to_string(),
+                kind: "withdrawal".to_string(),
+                transfer_server: base_url(&body.transfer_server),
+                anchor_transaction_id: id.to_string(),
+                asset_code: body.asset_code,
+                amount: body.amount,
+                status: "incomplete".to_string(),
+                auth_token: token,
+            })
+            .await;
+    }
     Ok(Json(data))
 }
 
-/// GET /api/sep24/transactions?transfer_server=&jwt=&...
+/// GET /api/sep24/transactions?transfer_server=&...
 #[derive(Debug, Deserialize)]
 pub struct TransactionsQuery {
     pub transfer_server: String,
     #[serde(default)]
-    pub jwt: Option<String>,
-    #[serde(default)]
     pub asset_code: Option<String>,
     #[serde(default)]
     pub kind: Option<String>,
@@ -251,13 +353,18 @@ pub struct TransactionsQuery {
 
 pub async fn get_transactions(
     State(state): State<Sep24State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Query(q): Query<TransactionsQuery>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&q.transfer_server) {
+    if !state.settings.allows_transfer_server(&q.transfer_server) {
         return Err(Sep24Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &q.transfer_server)
+        .await;
     let base = base_url(&q.transfer_server);
     let mut url = format!("{}/transactions?", base);
     if let Some(c) = &q.asset_code {
@@ -275,13 +382,12 @@ pub async fn get_transactions(
     let url = url.trim_end_matches('&').trim_end_matches('?');
 
     let mut req = state.client.get(url);
-    if let Some(jwt) = &q.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .send()
-        .await
-        .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(&q.transfer_server, "GET /transactions", req)
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -295,24 +401,27 @@ pub async fn get_transactions(
     Ok(Json(data))
 }
 
-/// GET /api/sep24/transaction?transfer_server=&id=&jwt=
+/// GET /api/sep24/transaction?transfer_server=&id=
 #[derive(Debug, Deserialize)]
 pub struct TransactionQuery {
     pub transfer_server: String,
     pub id: String,
-    #[serde(default)]
-    pub jwt: Option<String>,
 }
 
 pub async fn get_transaction(
     State(state): State<Sep24State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Query(q): Query<TransactionQuery>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&q.transfer_server) {
+    if !state.settings.allows_transfer_server(&q.transfer_server) {
         return Err(Sep24Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &q.transfer_server)
+        .await;
     let url = format!(
         "{}/transaction?id={}",
         base_url(&q.transfer_server),
@@ -320,13 +429,12 @@ pub async fn get_transaction(
     );
 
     let mut req = state.client.get(&url);
-    if let Some(jwt) = &q.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .send()
-        .await
-        .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(&q.transfer_server, "GET /transaction", req)
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -340,23 +448,10 @@ pub async fn get_transaction(
     Ok(Json(data))
 }
 
-/// List known SEP-24-enabled anchors (from env or static list).
-/// GET /api/sep24/anchors
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Sep24AnchorInfo {
-    pub name: String,
-    pub transfer_server: String,
-    pub home_domain: Option<String>,
-}
-
-pub async fn list_anchors() -> Json<Value> {
-    // Env: SEP24_ANCHORS = JSON array of { "name", "transfer_server", "home_domain" }
-    let anchors: Vec<Sep24AnchorInfo> = if let Ok(s) = std::env::var("SEP24_ANCHORS") {
-        serde_json::from_str(&s).unwrap_or_default()
-    } else {
-        // Default: no anchors; frontend can still use custom transfer_server
-        vec![]
-    };
+/// GET /api/sep24/anchors - Anchors configured in `[sep24] anchors` (or SEP24_ANCHORS).
+/// The frontend can still use any allowed custom transfer server.
+pub async fn list_anchors(State(state): State<Sep24State>) -> Json<Value> {
+    let anchors: &[SepAnchor] = &state.settings.anchors;
     Json(serde_json::json!({ "anchors": anchors }))
 }
 
@@ -388,8 +483,20 @@ impl IntoResponse for Sep24Error {
 }
 
 /// Build SEP-24 API router
-pub fn routes() -> axum::Router {
-    let state = Sep24State::new();
+pub fn routes(settings: SepProxySettings) -> axum::Router {
+    router(Sep24State::new(settings))
+}
+
+/// SEP-24 router that records interactive transactions for authenticated users
+pub fn tracked_routes(
+    settings: SepProxySettings,
+    db: Arc<Database>,
+    cache: Arc<CacheManager>,
+) -> axum::Router {
+    router(Sep24State::with_tracking(settings, db).with_info_cache(cache))
+}
+
+fn router(state: Sep24State) -> axum::Router {
     axum::Router::new()
         .route("/api/sep24/info", axum::routing::get(get_info))
         .route(
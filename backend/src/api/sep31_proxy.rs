@@ -3,45 +3,103 @@
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     Json,
 };
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
 use serde_json::Value;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-fn allowed_origins() -> Vec<String> {
-    std::env::var("SEP31_ALLOWED_ORIGINS")
-        .ok()
-        .map(|s| s.split(',').map(|x| x.trim().to_string()).collect())
-        .unwrap_or_default()
-}
-
-fn is_origin_allowed(transfer_server: &str) -> bool {
-    let allowed = allowed_origins();
-    if allowed.is_empty() {
-        return true;
-    }
-    let url = transfer_server.trim().trim_end_matches('/');
-    allowed.iter().any(|o| url.starts_with(o) || o == "*")
-}
+use crate::api::{sep_auth, sep_info_cache, sep_performance};
+use crate::auth_middleware::AuthUser;
+use crate::cache::CacheManager;
+use crate::config::{SepAnchor, SepProxySettings};
+use crate::database::Database;
+use crate::db::sep_transactions::NewSepTransaction;
+use crate::http_client;
 
 #[derive(Clone)]
 pub struct Sep31State {
     pub client: Arc<Client>,
+    /// Allowed transfer servers and listed anchors (`[sep31]` settings)
+    pub settings: Arc<SepProxySettings>,
+    /// When set, transactions started through the proxy are recorded for status tracking
+    pub db: Option<Arc<Database>>,
+    /// When set, anchor `/info` responses are cached
+    pub cache: Option<Arc<CacheManager>>,
 }
 
 impl Sep31State {
-    pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .unwrap_or_else(|_| Client::new());
+    pub fn new(settings: SepProxySettings) -> Self {
+        Self {
+            client: Arc::new(http_client::shared().client(Duration::from_secs(30))),
+            settings: Arc::new(settings),
+            db: None,
+            cache: None,
+        }
+    }
+
+    pub fn with_tracking(settings: SepProxySettings, db: Arc<Database>) -> Self {
         Self {
-            client: Arc::new(client),
+            db: Some(db),
+            ..Self::new(settings)
+        }
+    }
+
+    pub fn with_info_cache(mut self, cache: Arc<CacheManager>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Send a request to an anchor through its host's circuit breaker. When
+    /// tracking, its latency and outcome are recorded for `endpoint`.
+    async fn send(
+        &self,
+        transfer_server: &str,
+        endpoint: &str,
+        request: RequestBuilder,
+    ) -> Result<Response, Sep31Error> {
+        let started = Instant::now();
+        let result = http_client::shared().send(request).await;
+        if let Some(db) = &self.db {
+            sep_performance::record_request(
+                db,
+                "sep31",
+                transfer_server,
+                endpoint,
+                started,
+                &result,
+            );
+        }
+        result.map_err(|e| Sep31Error::Proxy(e.to_string()))
+    }
+
+    /// Anchor token from the request header or the user's stored session
+    async fn anchor_token(
+        &self,
+        user: Option<&AuthUser>,
+        headers: &HeaderMap,
+        transfer_server: &str,
+    ) -> Option<String> {
+        sep_auth::anchor_token(self.db.as_deref(), user, headers, transfer_server).await
+    }
+
+    /// Record a transaction the anchor just created. Tracking is best-effort:
+    /// the anchor's response is returned to the caller even if this fails.
+    async fn track(&self, new: NewSepTransaction) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        let anchor_transaction_id = new.anchor_transaction_id.clone();
+        if let Err(e) = db.sep_transactions().create(new).await {
+            tracing::warn!(
+                anchor_transaction_id = %anchor_transaction_id,
+                "Failed to record SEP transaction: {}",
+                e
+            );
         }
     }
 }
@@ -60,18 +118,20 @@ pub async fn get_info(
     State(state): State<Sep31State>,
     Query(q): Query<InfoQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
+    if !state.settings.allows_transfer_server(&q.transfer_server) {
         return Err(Sep31Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    if let Some(cache) = &state.cache {
+        if let Some(info) = sep_info_cache::get(cache, "sep31", &q.transfer_server).await {
+            return Ok(Json(info));
+        }
+    }
     let url = format!("{}/info", base_url(&q.transfer_server));
     let resp = state
-        .client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+        .send(&q.transfer_server, "GET /info", state.client.get(&url))
+        .await?;
 
     let status = resp.status();
     let body = resp
@@ -82,6 +142,9 @@ pub async fn get_info(
     if !status.is_success() {
         return Err(Sep31Error::Anchor(status.as_u16(), body));
     }
+    if let Some(cache) = &state.cache {
+        sep_info_cache::store(cache, "sep31", &q.transfer_server, &body).await;
+    }
     Ok(Json(body))
 }
 
@@ -89,31 +152,36 @@ pub async fn get_info(
 #[derive(Debug, Deserialize)]
 pub struct QuoteBody {
     pub transfer_server: String,
-    #[serde(default)]
-    pub jwt: Option<String>,
     #[serde(flatten)]
     pub payload: Value,
 }
 
 pub async fn post_quote(
     State(state): State<Sep31State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<QuoteBody>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&body.transfer_server) {
+    if !state.settings.allows_transfer_server(&body.transfer_server) {
         return Err(Sep31Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &body.transfer_server)
+        .await;
     let url = format!("{}/quote", base_url(&body.transfer_server));
     let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .json(&body.payload)
-        .send()
-        .await
-        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(
+            &body.transfer_server,
+            "POST /quote",
+            req.json(&body.payload),
+        )
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -131,31 +199,39 @@ pub async fn post_quote(
 #[derive(Debug, Deserialize)]
 pub struct CreateTransactionBody {
     pub transfer_server: String,
+    /// Anchor the transfer server belongs to, for status tracking (not forwarded)
     #[serde(default)]
-    pub jwt: Option<String>,
+    pub anchor_id: Option<String>,
     #[serde(flatten)]
     pub payload: Value,
 }
 
 pub async fn post_transaction(
     State(state): State<Sep31State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<CreateTransactionBody>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&body.transfer_server) {
+    if !state.settings.allows_transfer_server(&body.transfer_server) {
         return Err(Sep31Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &body.transfer_server)
+        .await;
     let url = format!("{}/transactions", base_url(&body.transfer_server));
     let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .json(&body.payload)
-        .send()
-        .await
-        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(
+            &body.transfer_server,
+            "POST /transactions",
+            req.json(&body.payload),
+        )
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -166,16 +242,31 @@ pub async fn post_transaction(
     if !status.is_success() {
         return Err(Sep31Error::Anchor(status.as_u16(), data));
     }
+    if let (Some(user), Some(id)) = (&user, data.get("id").and_then(Value::as_str)) {
+        let payload_str = |key: &str| body.payload.get(key).and_then(Value::as_str).map(str::to_string);
+        state
+            .track(NewSepTransaction {
+                user_id: user.user_id.clone(),
+                anchor_id: body.anchor_id.clone(),
+                protocol: "sep31".to_string(),
+                kind: "send".to_string(),
+                transfer_server: base_url(&body.transfer_server),
+                anchor_transaction_id: id.to_string(),
+                asset_code: payload_str("asset_code"),
+                amount: payload_str("amount"),
+                status: "pending_sender".to_string(),
+                auth_token: token,
+            })
+            .await;
+    }
     Ok(Json(data))
 }
 
-/// GET /api/sep31/transactions?transfer_server=&jwt=&...
+/// GET /api/sep31/transactions?transfer_server=&...
 #[derive(Debug, Deserialize)]
 pub struct ListTransactionsQuery {
     pub transfer_server: String,
     #[serde(default)]
-    pub jwt: Option<String>,
-    #[serde(default)]
     pub status: Option<String>,
     #[serde(default)]
     pub limit: Option<u32>,
@@ -185,13 +276,18 @@ pub struct ListTransactionsQuery {
 
 pub async fn get_transactions(
     State(state): State<Sep31State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Query(q): Query<ListTransactionsQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
+    if !state.settings.allows_transfer_server(&q.transfer_server) {
         return Err(Sep31Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &q.transfer_server)
+        .await;
     let base = base_url(&q.transfer_server);
     let mut url = format!("{}/transactions?", base);
     if let Some(s) = &q.status {
@@ -206,13 +302,12 @@ pub async fn get_transactions(
     let url = url.trim_end_matches('&').trim_end_matches('?');
 
     let mut req = state.client.get(url);
-    if let Some(jwt) = &q.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .send()
-        .await
-        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(&q.transfer_server, "GET /transactions", req)
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -226,24 +321,27 @@ pub async fn get_transactions(
     Ok(Json(data))
 }
 
-/// GET /api/sep31/transactions/:id?transfer_server=&jwt=
+/// GET /api/sep31/transactions/:id?transfer_server=
 #[derive(Debug, Deserialize)]
 pub struct GetTransactionQuery {
     pub transfer_server: String,
-    #[serde(default)]
-    pub jwt: Option<String>,
 }
 
 pub async fn get_transaction(
     State(state): State<Sep31State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Path(id): Path<String>,
     Query(q): Query<GetTransactionQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
+    if !state.settings.allows_transfer_server(&q.transfer_server) {
         return Err(Sep31Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &q.transfer_server)
+        .await;
     let url = format!(
         "{}/transactions/{}",
         base_url(&q.transfer_server),
@@ -251,13 +349,12 @@ pub async fn get_transaction(
     );
 
     let mut req = state.client.get(&url);
-    if let Some(jwt) = &q.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .send()
-        .await
-        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(&q.transfer_server, "GET /transactions/:id", req)
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -271,24 +368,27 @@ pub async fn get_transaction(
     Ok(Json(data))
 }
 
-/// GET /api/sep31/customer?transfer_server=&jwt=&id= - KYC customer fetch
+/// GET /api/sep31/customer?transfer_server=&id= - KYC customer fetch
 #[derive(Debug, Deserialize)]
 pub struct CustomerQuery {
     pub transfer_server: String,
-    #[serde(default)]
-    pub jwt: Option<String>,
     pub id: String,
 }
 
 pub async fn get_customer(
     State(state): State<Sep31State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Query(q): Query<CustomerQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
+    if !state.settings.allows_transfer_server(&q.transfer_server) {
         return Err(Sep31Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &q.transfer_server)
+        .await;
     let url = format!(
         "{}/customer?id={}",
         base_url(&q.transfer_server),
@@ -296,13 +396,10 @@ pub async fn get_customer(
     );
 
     let mut req = state.client.get(&url);
-    if let Some(jwt) = &q.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .send()
-        .await
-        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = state.send(&q.transfer_server, "GET /customer", req).await?;
 
     let status = resp.status();
     let data = resp
@@ -320,31 +417,36 @@ pub async fn get_customer(
 #[derive(Debug, Deserialize)]
 pub struct PutCustomerBody {
     pub transfer_server: String,
-    #[serde(default)]
-    pub jwt: Option<String>,
     #[serde(flatten)]
     pub payload: Value,
 }
 
 pub async fn put_customer(
     State(state): State<Sep31State>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<PutCustomerBody>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&body.transfer_server) {
+    if !state.settings.allows_transfer_server(&body.transfer_server) {
         return Err(Sep31Error::Forbidden(
             "Transfer server not in allowed list".to_string(),
         ));
     }
+    let token = state
+        .anchor_token(user.as_ref(), &headers, &body.transfer_server)
+        .await;
     let url = format!("{}/customer", base_url(&body.transfer_server));
     let mut req = state.client.put(&url);
-    if let Some(jwt) = &body.jwt {
-        req = req.header("Authorization", format!("Bearer {}", jwt));
+    if let Some(token) = &token {
+        req = req.bearer_auth(token);
     }
-    let resp = req
-        .json(&body.payload)
-        .send()
-        .await
-        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = state
+        .send(
+            &body.transfer_server,
+            "PUT /customer",
+            req.json(&body.payload),
+        )
+        .await?;
 
     let status = resp.status();
     let data = resp
@@ -358,20 +460,10 @@ pub async fn put_customer(
     Ok(Json(data))
 }
 
-/// List configured SEP-31 anchors. GET /api/sep31/anchors
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Sep31AnchorInfo {
-    pub name: String,
-    pub transfer_server: String,
-    pub home_domain: Option<String>,
-}
-
-pub async fn list_anchors() -> Json<Value> {
-    let anchors: Vec<Sep31AnchorInfo> = if let Ok(s) = std::env::var("SEP31_ANCHORS") {
-        serde_json::from_str(&s).unwrap_or_default()
-    } else {
-        vec![]
-    };
+/// GET /api/sep31/anchors - Anchors configured in `[sep31] anchors` (or SEP31_ANCHORS).
+/// The frontend can still use any allowed custom transfer server.
+pub async fn list_anchors(State(state): State<Sep31State>) -> Json<Value> {
+    let anchors: &[SepAnchor] = &state.settings.anchors;
     Json(serde_json::json!({ "anchors": anchors }))
 }
 
@@ -402,8 +494,20 @@ impl IntoResponse for Sep31Error {
     }
 }
 
-pub fn routes() -> axum::Router {
-    let state = Sep31State::new();
+pub fn routes(settings: SepProxySettings) -> axum::Router {
+    router(Sep31State::new(settings))
+}
+
+/// SEP-31 router that records created payments for authenticated users
+pub fn tracked_routes(
+    settings: SepProxySettings,
+    db: Arc<Database>,
+    cache: Arc<CacheManager>,
+) -> axum::Router {
+    router(Sep31State::with_tracking(settings, db).with_info_cache(cache))
+}
+
+fn router(state: Sep31State) -> axum::Router {
     axum::Router::new()
         .route("/api/sep31/info", axum::routing::get(get_info))
         .route("/api/sep31/quote", axum::routing::post(post_quote))
@@ -452,4 +556,13 @@ mod tests {
         let body: CreateTransactionBody = serde_json::from_str(json).unwrap();
         assert_eq!(body.transfer_server, "https://api.test.com/sep31");
     }
+
+    #[test]
+    fn test_create_transaction_anchor_id_not_forwarded() {
+        let json = r#"{"transfer_server":"https://api.test.com/sep31","anchor_id":"a1","amount":"100"}"#;
+        let body: CreateTransactionBody = serde_json::from_str(json).unwrap();
+        assert_eq!(body.anchor_id.as_deref(), Some("a1"));
+        assert!(body.payload.get("anchor_id").is_none());
+        assert_eq!(body.payload["amount"], "100");
+    }
 }
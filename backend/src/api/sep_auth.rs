@@ -0,0 +1,175 @@
+//! Anchor (SEP-10) token handling for the SEP-24/31 proxies.
+//!
+//! Anchor tokens are never accepted in URLs. Clients either send one per
+//! request in the `X-Anchor-Authorization: Bearer <token>` header (the
+//! platform's own `Authorization` header carries the user's session), or
+//! store it once with `PUT /api/sep/session`, encrypted and keyed by user
+//! and transfer server.
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    routing::put,
+    Json, Router,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+
+/// Header carrying the anchor's SEP-10 token on proxied requests
+pub const ANCHOR_AUTH_HEADER: &str = "x-anchor-authorization";
+
+/// Transfer servers are keyed without surrounding whitespace or a trailing slash
+pub fn normalize_transfer_server(transfer_server: &str) -> String {
+    transfer_server.trim().trim_end_matches('/').to_string()
+}
+
+fn header_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(ANCHOR_AUTH_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.strip_prefix("Bearer ").unwrap_or(v).trim())
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Token to send to the anchor: the request header if present, otherwise the
+/// user's stored session for the transfer server
+pub async fn anchor_token(
+    db: Option<&Database>,
+    user: Option<&AuthUser>,
+    headers: &HeaderMap,
+    transfer_server: &str,
+) -> Option<String> {
+    if let Some(token) = header_token(headers) {
+        return Some(token);
+    }
+
+    let (db, user) = (db?, user?);
+    match db
+        .sep_transactions()
+        .anchor_session(&user.user_id, &normalize_transfer_server(transfer_server))
+        .await
+    {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::warn!(user_id = %user.user_id, "Failed to load anchor session: {}", e);
+            None
+        }
+    }
+}
+
+/// `exp` claim of a JWT, read without verifying it (the anchor verifies its own tokens)
+fn jwt_expiry(token: &str) -> Option<DateTime<Utc>> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: Option<i64>,
+    }
+
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: Claims = serde_json::from_slice(&bytes).ok()?;
+    DateTime::from_timestamp(claims.exp?, 0)
+}
+
+// No Debug derive: the request carries a credential
+#[derive(Deserialize)]
+pub struct SaveSessionRequest {
+    pub transfer_server: String,
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    pub transfer_server: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionQuery {
+    pub transfer_server: String,
+}
+
+/// PUT /api/sep/session - Store the user's SEP-10 token for a transfer server
+pub async fn save_session(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+    Json(req): Json<SaveSessionRequest>,
+) -> ApiResult<Json<SessionResponse>> {
+    let transfer_server = normalize_transfer_server(&req.transfer_server);
+    if transfer_server.is_empty() || req.token.trim().is_empty() {
+        return Err(ApiError::bad_request(
+            "INVALID_SESSION",
+            "transfer_server and token are required",
+        ));
+    }
+
+    let expires_at = jwt_expiry(req.token.trim());
+    if expires_at.is_some_and(|exp| exp <= Utc::now()) {
+        return Err(ApiError::bad_request("INVALID_SESSION", "Token has expired"));
+    }
+
+    db.sep_transactions()
+        .save_anchor_session(&user.user_id, &transfer_server, req.token.trim(), expires_at)
+        .await?;
+
+    Ok(Json(SessionResponse {
+        transfer_server,
+        expires_at,
+    }))
+}
+
+/// DELETE /api/sep/session?transfer_server= - Forget the user's token for a transfer server
+pub async fn delete_session(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+    Query(q): Query<SessionQuery>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let transfer_server = normalize_transfer_server(&q.transfer_server);
+    let deleted = db
+        .sep_transactions()
+        .delete_anchor_session(&user.user_id, &transfer_server)
+        .await?;
+    if !deleted {
+        return Err(ApiError::not_found(
+            "SESSION_NOT_FOUND",
+            format!("No session stored for {}", transfer_server),
+        ));
+    }
+
+    Ok(Json(serde_json::json!({ "deleted": true })))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/sep/session", put(save_session).delete(delete_session))
+        .with_state(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_token() {
+        let mut headers = HeaderMap::new();
+        assert!(header_token(&headers).is_none());
+        headers.insert(ANCHOR_AUTH_HEADER, "Bearer abc.def.ghi".parse().unwrap());
+        assert_eq!(header_token(&headers).as_deref(), Some("abc.def.ghi"));
+        headers.insert(ANCHOR_AUTH_HEADER, "Bearer ".parse().unwrap());
+        assert!(header_token(&headers).is_none());
+    }
+
+    #[test]
+    fn test_jwt_expiry() {
+        let claims = URL_SAFE_NO_PAD.encode(br#"{"sub":"G...","exp":1700000000}"#);
+        let token = format!("eyJhbGciOiJIUzI1NiJ9.{}.sig", claims);
+        assert_eq!(jwt_expiry(&token).map(|t| t.timestamp()), Some(1_700_000_000));
+        assert!(jwt_expiry("not-a-jwt").is_none());
+    }
+}
@@ -0,0 +1,103 @@
+//! Cached anchor `/info` responses for the SEP-24/31 proxies.
+//!
+//! The frontend refetches `/info` on most screens and anchors rate-limit it
+//! aggressively, while the document itself rarely changes. Successful
+//! responses are kept in Redis per protocol and transfer server for
+//! `CacheConfig::sep_info_ttl`; `DELETE /api/sep/info/cache` drops them
+//! once an anchor is known to have changed its configuration.
+
+use axum::{
+    extract::{Query, State},
+    routing::delete,
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::api::sep_auth::normalize_transfer_server;
+use crate::cache::{keys, CacheManager};
+use crate::error::ApiResult;
+
+/// Protocols whose `/info` responses are cached
+pub const PROTOCOLS: &[&str] = &["sep24", "sep31"];
+
+fn cache_key(protocol: &str, transfer_server: &str) -> String {
+    keys::sep_info(protocol, &normalize_transfer_server(transfer_server))
+}
+
+/// Cached `/info` document of `transfer_server`, if still fresh
+pub async fn get(cache: &CacheManager, protocol: &str, transfer_server: &str) -> Option<Value> {
+    cache
+        .get::<Value>(&cache_key(protocol, transfer_server))
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Cache a successful `/info` response
+pub async fn store(cache: &CacheManager, protocol: &str, transfer_server: &str, info: &Value) {
+    let ttl = cache.config.get_ttl("sep_info");
+    if let Err(e) = cache
+        .set(&cache_key(protocol, transfer_server), info, ttl)
+        .await
+    {
+        tracing::warn!(
+            "Failed to cache {} info for {}: {}",
+            protocol,
+            transfer_server,
+            e
+        );
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InvalidateQuery {
+    /// Only drop this transfer server's documents; all of them when omitted
+    pub transfer_server: Option<String>,
+}
+
+/// DELETE /api/sep/info/cache?transfer_server=<url>
+pub async fn invalidate(
+    State(cache): State<Arc<CacheManager>>,
+    Query(q): Query<InvalidateQuery>,
+) -> ApiResult<Json<Value>> {
+    match &q.transfer_server {
+        Some(transfer_server) => {
+            for protocol in PROTOCOLS {
+                cache.delete(&cache_key(protocol, transfer_server)).await?;
+            }
+        }
+        None => cache.delete_pattern(&keys::sep_info_pattern()).await?,
+    }
+    Ok(Json(serde_json::json!({
+        "invalidated": q
+            .transfer_server
+            .as_deref()
+            .map(normalize_transfer_server)
+            .unwrap_or_else(|| "all".to_string()),
+    })))
+}
+
+pub fn routes(cache: Arc<CacheManager>) -> Router {
+    Router::new()
+        .route("/api/sep/info/cache", delete(invalidate))
+        .with_state(cache)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_normalizes_transfer_server() {
+        assert_eq!(
+            cache_key("sep24", " https://anchor.example/sep24/ "),
+            "sep_info:sep24:https://anchor.example/sep24"
+        );
+        assert_eq!(
+            cache_key("sep31", "https://anchor.example/sep31"),
+            keys::sep_info("sep31", "https://anchor.example/sep31")
+        );
+    }
+}
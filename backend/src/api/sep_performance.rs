@@ -0,0 +1,267 @@
+//! Latency benchmarks of anchors' transfer servers.
+//!
+//! The SEP-24/31 proxies record the latency (to response headers) and
+//! outcome of every request they send to an anchor. The
+//! `sep-performance-rollup` job folds these samples into daily rows per
+//! transfer server and endpoint, and `GET /api/anchors/:id/sep-performance`
+//! serves them for the transfer servers on the anchor's home domain or its
+//! subdomains, slowest endpoints first.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::api::anchor_callbacks::find_anchor;
+use crate::database::Database;
+use crate::db::sep_performance::{
+    SepPerformanceDay, SepRequestSample, OUTCOME_CIRCUIT_OPEN, OUTCOME_NETWORK, OUTCOME_TIMEOUT,
+};
+use crate::error::ApiResult;
+use crate::rpc::error::RpcError;
+
+const DEFAULT_DAYS: i64 = 30;
+const MAX_DAYS: i64 = 90;
+
+/// Daily p95 latency above which a day counts as slow
+const SLOW_P95_MS: i64 = 2_000;
+
+/// Outcome recorded for the result of `HttpClients::send`
+fn outcome(result: &Result<Response, RpcError>) -> String {
+    match result {
+        Ok(response) => response.status().as_u16().to_string(),
+        Err(RpcError::TimeoutError(_)) => OUTCOME_TIMEOUT.to_string(),
+        Err(RpcError::CircuitBreakerOpen) => OUTCOME_CIRCUIT_OPEN.to_string(),
+        Err(_) => OUTCOME_NETWORK.to_string(),
+    }
+}
+
+/// Record a proxied request sent at `started` in the background, so the
+/// write does not delay the response
+pub(crate) fn record_request(
+    db: &Arc<Database>,
+    protocol: &str,
+    transfer_server: &str,
+    endpoint: &str,
+    started: Instant,
+    result: &Result<Response, RpcError>,
+) {
+    let elapsed = started.elapsed();
+    let sample = SepRequestSample {
+        transfer_server: transfer_server.trim().trim_end_matches('/').to_string(),
+        protocol: protocol.to_string(),
+        endpoint: endpoint.to_string(),
+        outcome: outcome(result),
+        latency_ms: elapsed.as_millis() as i64,
+        requested_at: Utc::now() - Duration::from_std(elapsed).unwrap_or_else(|_| Duration::zero()),
+    };
+    let db = Arc::clone(db);
+    tokio::spawn(async move {
+        if let Err(e) = db.sep_performance().record(&sample).await {
+            tracing::warn!(
+                transfer_server = %sample.transfer_server,
+                "Failed to record SEP request sample: {}",
+                e
+            );
+        }
+    });
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PerformanceParams {
+    pub days: Option<i64>,
+}
+
+/// Daily point of an endpoint's history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailyPerformance {
+    pub date: String,
+    pub request_count: i64,
+    pub error_count: i64,
+    pub avg_latency_ms: f64,
+    pub p50_latency_ms: i64,
+    pub p95_latency_ms: i64,
+    pub max_latency_ms: i64,
+    pub outcome_counts: BTreeMap<String, i64>,
+}
+
+/// One endpoint of a transfer server over the requested window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EndpointPerformance {
+    pub transfer_server: String,
+    pub protocol: String,
+    pub endpoint: String,
+    pub request_count: i64,
+    pub error_count: i64,
+    /// Fraction (0-1) of requests that failed on the anchor's side
+    pub error_rate: f64,
+    pub avg_latency_ms: f64,
+    /// Highest daily p95 latency in the window
+    pub worst_p95_latency_ms: i64,
+    /// Days whose p95 latency exceeded `slow_threshold_ms`
+    pub slow_days: i64,
+    pub outcome_counts: BTreeMap<String, i64>,
+    pub daily: Vec<DailyPerformance>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnchorSepPerformance {
+    pub anchor_id: String,
+    pub home_domain: Option<String>,
+    pub days: i64,
+    pub slow_threshold_ms: i64,
+    /// Slowest endpoints (by average latency) first
+    pub endpoints: Vec<EndpointPerformance>,
+}
+
+/// Combine daily rows into per-endpoint totals, slowest first
+fn by_endpoint(days: Vec<SepPerformanceDay>) -> Vec<EndpointPerformance> {
+    let mut endpoints: BTreeMap<(String, String, String), EndpointPerformance> = BTreeMap::new();
+    for day in days {
+        let endpoint = endpoints
+            .entry((
+                day.transfer_server.clone(),
+                day.protocol.clone(),
+                day.endpoint.clone(),
+            ))
+            .or_insert_with(|| EndpointPerformance {
+                transfer_server: day.transfer_server,
+                protocol: day.protocol,
+                endpoint: day.endpoint,
+                request_count: 0,
+                error_count: 0,
+                error_rate: 0.0,
+                avg_latency_ms: 0.0,
+                worst_p95_latency_ms: 0,
+                slow_days: 0,
+                outcome_counts: BTreeMap::new(),
+                daily: Vec::new(),
+            });
+
+        let summary = day.summary;
+        // Running total of latency; divided by the request count below
+        endpoint.avg_latency_ms += summary.avg_latency_ms * summary.request_count as f64;
+        endpoint.request_count += summary.request_count;
+        endpoint.error_count += summary.error_count;
+        endpoint.worst_p95_latency_ms = endpoint.worst_p95_latency_ms.max(summary.p95_latency_ms);
+        if summary.p95_latency_ms > SLOW_P95_MS {
+            endpoint.slow_days += 1;
+        }
+        for (outcome, count) in &summary.outcome_counts {
+            *endpoint.outcome_counts.entry(outcome.clone()).or_insert(0) += count;
+        }
+        endpoint.daily.push(DailyPerformance {
+            date: day.date,
+            request_count: summary.request_count,
+            error_count: summary.error_count,
+            avg_latency_ms: summary.avg_latency_ms,
+            p50_latency_ms: summary.p50_latency_ms,
+            p95_latency_ms: summary.p95_latency_ms,
+            max_latency_ms: summary.max_latency_ms,
+            outcome_counts: summary.outcome_counts,
+        });
+    }
+
+    let mut endpoints: Vec<_> = endpoints
+        .into_values()
+        .map(|mut endpoint| {
+            if endpoint.request_count > 0 {
+                endpoint.avg_latency_ms /= endpoint.request_count as f64;
+                endpoint.error_rate = endpoint.error_count as f64 / endpoint.request_count as f64;
+            }
+            endpoint
+        })
+        .collect();
+    endpoints.sort_by(|a, b| b.avg_latency_ms.total_cmp(&a.avg_latency_ms));
+    endpoints
+}
+
+/// GET /api/anchors/:id/sep-performance - Latency and error codes of the
+/// anchor's transfer server endpoints over the last `days` (default 30)
+pub async fn get_sep_performance(
+    State(db): State<Arc<Database>>,
+    Path(anchor_id): Path<String>,
+    Query(params): Query<PerformanceParams>,
+) -> ApiResult<Json<AnchorSepPerformance>> {
+    let anchor = find_anchor(&db, &anchor_id).await?;
+    let days = params.days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+    let since = Utc::now().date_naive() - Duration::days(days - 1);
+
+    let home_domain = anchor
+        .home_domain
+        .filter(|domain| !domain.trim().is_empty());
+    let endpoints = match &home_domain {
+        Some(domain) => by_endpoint(db.sep_performance().for_domain(domain, since).await?),
+        None => Vec::new(),
+    };
+
+    Ok(Json(AnchorSepPerformance {
+        anchor_id: anchor.id,
+        home_domain,
+        days,
+        slow_threshold_ms: SLOW_P95_MS,
+        endpoints,
+    }))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/sep-performance", get(get_sep_performance))
+        .with_state(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::sep_performance::LatencySummary;
+
+    fn day(endpoint: &str, date: &str, requests: i64, avg: f64, p95: i64) -> SepPerformanceDay {
+        SepPerformanceDay {
+            transfer_server: "https://api.anchor.example/sep24".to_string(),
+            protocol: "sep24".to_string(),
+            endpoint: endpoint.to_string(),
+            date: date.to_string(),
+            summary: LatencySummary {
+                request_count: requests,
+                error_count: 1,
+                avg_latency_ms: avg,
+                p50_latency_ms: avg as i64,
+                p95_latency_ms: p95,
+                max_latency_ms: p95,
+                outcome_counts: BTreeMap::from([
+                    ("200".to_string(), requests - 1),
+                    ("504".to_string(), 1),
+                ]),
+            },
+        }
+    }
+
+    #[test]
+    fn test_by_endpoint_combines_days_slowest_first() {
+        let endpoints = by_endpoint(vec![
+            day("GET /info", "2026-10-14", 10, 100.0, 200),
+            day("GET /transactions", "2026-10-14", 10, 900.0, 2_500),
+            day("GET /transactions", "2026-10-15", 30, 500.0, 1_500),
+        ]);
+
+        assert_eq!(endpoints.len(), 2);
+        let slowest = &endpoints[0];
+        assert_eq!(slowest.endpoint, "GET /transactions");
+        assert_eq!(slowest.request_count, 40);
+        assert_eq!(slowest.error_count, 2);
+        assert_eq!(slowest.error_rate, 0.05);
+        assert_eq!(slowest.avg_latency_ms, 600.0);
+        assert_eq!(slowest.worst_p95_latency_ms, 2_500);
+        assert_eq!(slowest.slow_days, 1);
+        assert_eq!(slowest.outcome_counts["200"], 38);
+        assert_eq!(slowest.daily.len(), 2);
+        assert_eq!(endpoints[1].endpoint, "GET /info");
+    }
+}
@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::db::sep_transactions::SepTransaction;
+use crate::error::ApiResult;
+
+#[derive(Debug, Deserialize)]
+pub struct ListSepTransactionsParams {
+    /// Only return transactions currently in this SEP status
+    pub status: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+pub struct SepTransactionsResponse {
+    pub transactions: Vec<SepTransaction>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// GET /api/sep/transactions - SEP-24/31 transactions started by the authenticated user
+pub async fn list_transactions(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+    Query(params): Query<ListSepTransactionsParams>,
+) -> ApiResult<Json<SepTransactionsResponse>> {
+    let limit = params.limit.clamp(1, 200);
+    let offset = params.offset.max(0);
+    let transactions = db
+        .sep_transactions()
+        .list_for_user(&user.user_id, params.status.as_deref(), limit, offset)
+        .await?;
+
+    Ok(Json(SepTransactionsResponse {
+        transactions,
+        limit,
+        offset,
+    }))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/sep/transactions", get(list_transactions))
+        .with_state(db)
+}
@@ -0,0 +1,70 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get},
+    Json, Router,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::db::sessions::UserSession;
+use crate::error::{ApiError, ApiResult};
+
+#[derive(Debug, Serialize)]
+pub struct SessionView {
+    #[serde(flatten)]
+    pub session: UserSession,
+    /// The session the request was made from
+    pub current: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionsResponse {
+    pub sessions: Vec<SessionView>,
+}
+
+/// GET /api/me/sessions - Active login sessions of the authenticated user
+pub async fn list_sessions(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+) -> ApiResult<Json<SessionsResponse>> {
+    let sessions = db
+        .sessions()
+        .list_active(&user.user_id)
+        .await?
+        .into_iter()
+        .map(|session| SessionView {
+            current: user.session_id.as_deref() == Some(session.id.as_str()),
+            session,
+        })
+        .collect();
+
+    Ok(Json(SessionsResponse { sessions }))
+}
+
+/// DELETE /api/me/sessions/:id - Revoke one session. Its refresh token stops
+/// working immediately; access tokens already issued expire within the hour.
+pub async fn revoke_session(
+    State(db): State<Arc<Database>>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> ApiResult<StatusCode> {
+    if !db.sessions().revoke(&user.user_id, &id).await? {
+        return Err(ApiError::not_found(
+            "SESSION_NOT_FOUND",
+            format!("No active session '{}'", id),
+        ));
+    }
+
+    tracing::info!(user_id = %user.user_id, session_id = %id, "Revoked session");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/me/sessions", get(list_sessions))
+        .route("/api/me/sessions/:id", delete(revoke_session))
+        .with_state(db)
+}
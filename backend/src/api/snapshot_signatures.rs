@@ -0,0 +1,124 @@
+//! Public verification of snapshot signatures.
+//!
+//! Every published snapshot's SHA-256 hash is signed with the backend's
+//! ed25519 key (held locally or in Vault/KMS, see `SNAPSHOT_SIGNER`). Consumers recompute the hash of the
+//! canonical snapshot JSON and check it against the signature and the key
+//! served here, without waiting for the on-chain submission to confirm.
+
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::snapshot::{SnapshotService, SnapshotSignature};
+use crate::snapshot::signing::SIGNATURE_ALGORITHM;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SigningKeyResponse {
+    #[schema(value_type = String, example = "ed25519")]
+    pub algorithm: &'static str,
+    /// Hex-encoded ed25519 public key
+    pub public_key: String,
+    /// The same key as a Stellar G-address
+    pub stellar_address: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotSignatureResponse {
+    #[schema(value_type = String, example = "ed25519")]
+    pub algorithm: &'static str,
+    #[serde(flatten)]
+    pub signature: SnapshotSignature,
+}
+
+/// GET /api/snapshots/signing-key - The key snapshots are currently signed with
+#[utoipa::path(
+    get,
+    path = "/api/snapshots/signing-key",
+    responses(
+        (status = 200, description = "Current snapshot signing key", body = SigningKeyResponse),
+        (status = 404, description = "Snapshot signing is not configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Snapshots"
+)]
+pub async fn get_signing_key(
+    State(service): State<Arc<SnapshotService>>,
+) -> ApiResult<Json<SigningKeyResponse>> {
+    let signer = service.signer().ok_or_else(|| {
+        ApiError::not_found(
+            "SNAPSHOT_SIGNING_DISABLED",
+            "Snapshot signing is not configured",
+        )
+    })?;
+
+    let key_error = |e: anyhow::Error| {
+        tracing::error!("Failed to read snapshot signing key: {}", e);
+        ApiError::internal(
+            "SNAPSHOT_SIGNATURE_ERROR",
+            "Failed to read the snapshot signing key",
+        )
+    };
+
+    Ok(Json(SigningKeyResponse {
+        algorithm: SIGNATURE_ALGORITHM,
+        public_key: signer.public_key_hex().await.map_err(key_error)?,
+        stellar_address: signer.stellar_address().await.map_err(key_error)?,
+    }))
+}
+
+/// GET /api/snapshots/:epoch/signature - Signature over an epoch's snapshot hash
+#[utoipa::path(
+    get,
+    path = "/api/snapshots/{epoch}/signature",
+    params(
+        ("epoch" = u64, Path, description = "Snapshot epoch")
+    ),
+    responses(
+        (status = 200, description = "Signature over the epoch's snapshot hash", body = SnapshotSignatureResponse),
+        (status = 404, description = "No signed snapshot for the epoch"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Snapshots"
+)]
+pub async fn get_snapshot_signature(
+    State(service): State<Arc<SnapshotService>>,
+    Path(epoch): Path<u64>,
+) -> ApiResult<Json<SnapshotSignatureResponse>> {
+    let signature = service
+        .signature_for_epoch(epoch)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load signature for epoch {}: {}", epoch, e);
+            ApiError::internal(
+                "SNAPSHOT_SIGNATURE_ERROR",
+                "Failed to load snapshot signature",
+            )
+        })?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SNAPSHOT_SIGNATURE_NOT_FOUND",
+                format!("No signed snapshot for epoch {}", epoch),
+            )
+        })?;
+
+    Ok(Json(SnapshotSignatureResponse {
+        algorithm: SIGNATURE_ALGORITHM,
+        signature,
+    }))
+}
+
+pub fn routes(service: Arc<SnapshotService>) -> Router {
+    Router::new()
+        .route("/api/snapshots/signing-key", get(get_signing_key))
+        .route(
+            "/api/snapshots/:epoch/signature",
+            get(get_snapshot_signature),
+        )
+        .with_state(service)
+}
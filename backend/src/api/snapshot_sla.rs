@@ -0,0 +1,204 @@
+//! Public proof of anchored corridor SLA figures.
+//!
+//! Each snapshot carries month-to-date SLA summaries (uptime and success rate
+//! per corridor). Their hash is part of the snapshot hash and, when the
+//! contract is configured, is also recorded on-chain on its own with
+//! `submit_snapshot_with_sla`. An anchor can point a counterparty at
+//! `/api/snapshots/{epoch}/sla/verify` to show that the figures it reports
+//! were anchored at that epoch, and anyone can recompute both hashes from
+//! `/api/snapshots/{epoch}/sla`.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::snapshot::{AnchoredSla, SnapshotService};
+use crate::snapshot::schema::SnapshotSlaSummary;
+
+/// Reported figures match anchored ones when they agree to two decimals
+const FIGURE_TOLERANCE: f64 = 0.005;
+
+/// What the contract recorded for the epoch
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OnChainSla {
+    /// Ledger timestamp of the submission
+    pub timestamp: u64,
+    /// Whether the on-chain snapshot hash equals the stored one
+    pub snapshot_hash_matches: bool,
+    /// Hex SLA hash recorded by the contract, if the epoch was submitted with one
+    pub sla_hash: Option<String>,
+    /// Whether the on-chain SLA hash equals the one recomputed from storage
+    pub sla_hash_matches: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotSlaResponse {
+    #[serde(flatten)]
+    pub sla: AnchoredSla,
+    /// `None` when the contract is not configured or the epoch is not on-chain
+    pub on_chain: Option<OnChainSla>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct VerifySlaQuery {
+    /// Corridor the figures are reported for
+    pub corridor_key: String,
+    /// Reported month-to-date uptime percentage
+    pub uptime_percent: f64,
+    /// Reported success rate percentage
+    pub success_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerifySlaResponse {
+    /// Reported figures match the anchored summary and the anchored hashes
+    /// match the chain
+    pub verified: bool,
+    /// Reported figures match the anchored summary
+    pub figures_match: bool,
+    pub epoch: u64,
+    pub snapshot_timestamp: String,
+    pub snapshot_hash: String,
+    pub sla_hash: Option<String>,
+    #[schema(value_type = Object)]
+    pub anchored: SnapshotSlaSummary,
+    pub on_chain: Option<OnChainSla>,
+}
+
+fn sla_error(epoch: u64, e: anyhow::Error) -> ApiError {
+    tracing::error!("Failed to load anchored SLA for epoch {}: {}", epoch, e);
+    ApiError::internal("SNAPSHOT_SLA_ERROR", "Failed to load anchored SLA figures")
+}
+
+async fn load(service: &SnapshotService, epoch: u64) -> ApiResult<SnapshotSlaResponse> {
+    let sla = service
+        .anchored_sla(epoch)
+        .await
+        .map_err(|e| sla_error(epoch, e))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SNAPSHOT_NOT_FOUND",
+                format!("No snapshot stored for epoch {}", epoch),
+            )
+        })?;
+
+    let on_chain = match service.contract_service() {
+        Some(contract) => {
+            let metadata = contract
+                .get_snapshot_metadata(epoch)
+                .await
+                .map_err(|e| sla_error(epoch, e))?;
+            match metadata {
+                Some(metadata) => {
+                    let sla_hash = contract
+                        .get_sla_hash(epoch)
+                        .await
+                        .map_err(|e| sla_error(epoch, e))?;
+                    Some(OnChainSla {
+                        timestamp: metadata.timestamp,
+                        snapshot_hash_matches: hex::encode(metadata.hash) == sla.snapshot_hash,
+                        sla_hash_matches: sla_hash.is_some() && sla_hash == sla.sla_hash,
+                        sla_hash,
+                    })
+                }
+                None => None,
+            }
+        }
+        None => None,
+    };
+
+    Ok(SnapshotSlaResponse { sla, on_chain })
+}
+
+/// GET /api/snapshots/:epoch/sla - SLA summaries anchored with an epoch's snapshot
+#[utoipa::path(
+    get,
+    path = "/api/snapshots/{epoch}/sla",
+    params(
+        ("epoch" = u64, Path, description = "Snapshot epoch")
+    ),
+    responses(
+        (status = 200, description = "Anchored SLA summaries and their hashes", body = SnapshotSlaResponse),
+        (status = 404, description = "No snapshot for the epoch"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Snapshots"
+)]
+pub async fn get_snapshot_sla(
+    State(service): State<Arc<SnapshotService>>,
+    Path(epoch): Path<u64>,
+) -> ApiResult<Json<SnapshotSlaResponse>> {
+    Ok(Json(load(&service, epoch).await?))
+}
+
+/// GET /api/snapshots/:epoch/sla/verify - Check reported SLA figures against an epoch
+#[utoipa::path(
+    get,
+    path = "/api/snapshots/{epoch}/sla/verify",
+    params(
+        ("epoch" = u64, Path, description = "Snapshot epoch"),
+        VerifySlaQuery
+    ),
+    responses(
+        (status = 200, description = "Whether the reported figures were anchored", body = VerifySlaResponse),
+        (status = 404, description = "No snapshot, or no SLA summary for the corridor, at the epoch"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Snapshots"
+)]
+pub async fn verify_snapshot_sla(
+    State(service): State<Arc<SnapshotService>>,
+    Path(epoch): Path<u64>,
+    Query(query): Query<VerifySlaQuery>,
+) -> ApiResult<Json<VerifySlaResponse>> {
+    let SnapshotSlaResponse { sla, on_chain } = load(&service, epoch).await?;
+
+    let anchored = sla
+        .summaries
+        .into_iter()
+        .find(|summary| summary.corridor_key == query.corridor_key)
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SLA_SUMMARY_NOT_FOUND",
+                format!(
+                    "Snapshot for epoch {} has no SLA summary for corridor {}",
+                    epoch, query.corridor_key
+                ),
+            )
+        })?;
+
+    let matches = |anchored: f64, reported: f64| (anchored - reported).abs() <= FIGURE_TOLERANCE;
+    let figures_match = matches(anchored.uptime_percent, query.uptime_percent)
+        && query
+            .success_rate
+            .map(|rate| matches(anchored.success_rate, rate))
+            .unwrap_or(true);
+    let verified = figures_match
+        && on_chain
+            .as_ref()
+            .is_some_and(|chain| chain.snapshot_hash_matches && chain.sla_hash_matches);
+
+    Ok(Json(VerifySlaResponse {
+        verified,
+        figures_match,
+        epoch,
+        snapshot_timestamp: sla.snapshot_timestamp,
+        snapshot_hash: sla.snapshot_hash,
+        sla_hash: sla.sla_hash,
+        anchored,
+        on_chain,
+    }))
+}
+
+pub fn routes(service: Arc<SnapshotService>) -> Router {
+    Router::new()
+        .route("/api/snapshots/:epoch/sla", get(get_snapshot_sla))
+        .route("/api/snapshots/:epoch/sla/verify", get(verify_snapshot_sla))
+        .with_state(service)
+}
@@ -0,0 +1,248 @@
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::db::status::{Component, ComponentStatus, CorridorDay, StatusIncident};
+use crate::error::ApiResult;
+use crate::services::status_monitor::STATUS_HISTORY_DAYS;
+use crate::tenant::TenantScope;
+
+/// Seconds clients and CDNs may cache the status feed
+const STATUS_CACHE_TTL_SECS: usize = 60;
+
+/// Most corridors (by volume) included in the health history
+const MAX_STATUS_CORRIDORS: i64 = 50;
+
+#[derive(Debug, Serialize)]
+pub struct ComponentHealth {
+    pub id: Component,
+    pub name: &'static str,
+    /// `null` until the component has been probed
+    pub status: Option<ComponentStatus>,
+    pub message: Option<String>,
+    pub latency_ms: Option<i64>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub uptime_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayStatus {
+    Operational,
+    Degraded,
+    Outage,
+    NoData,
+}
+
+impl From<ComponentStatus> for DayStatus {
+    fn from(status: ComponentStatus) -> Self {
+        match status {
+            ComponentStatus::Operational => Self::Operational,
+            ComponentStatus::Degraded => Self::Degraded,
+            ComponentStatus::Outage => Self::Outage,
+        }
+    }
+}
+
+/// One bar of a corridor's daily health history
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthBar {
+    pub date: NaiveDate,
+    pub status: DayStatus,
+    pub success_rate: Option<f64>,
+    pub total_transactions: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CorridorHealthHistory {
+    pub corridor_key: String,
+    /// Share of days with data that were operational
+    pub uptime_percent: Option<f64>,
+    pub days: Vec<HealthBar>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusPageResponse {
+    /// Worst current status across all probed components
+    pub status: ComponentStatus,
+    pub history_days: i64,
+    pub components: Vec<ComponentHealth>,
+    pub incidents: Vec<StatusIncident>,
+    pub corridors: Vec<CorridorHealthHistory>,
+}
+
+/// GET /api/status - Platform component health and per-corridor health history
+pub async fn get_status(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let now = Utc::now();
+    let since = now - Duration::days(STATUS_HISTORY_DAYS);
+    let store = db.status();
+
+    let mut components = Vec::with_capacity(Component::ALL.len());
+    for component in Component::ALL {
+        let latest = store.latest_probe(component).await?;
+        let uptime_percent = store.uptime_percent(component, since).await?;
+        components.push(ComponentHealth {
+            id: component,
+            name: component.display_name(),
+            status: latest.as_ref().map(|p| p.status),
+            message: latest.as_ref().and_then(|p| p.message.clone()),
+            latency_ms: latest.as_ref().and_then(|p| p.latency_ms),
+            last_checked_at: latest.as_ref().map(|p| p.checked_at),
+            uptime_percent,
+        });
+    }
+
+    let status = components
+        .iter()
+        .filter_map(|c| c.status)
+        .max()
+        .unwrap_or(ComponentStatus::Operational);
+
+    let end = now.date_naive();
+    let start = end - Duration::days(STATUS_HISTORY_DAYS - 1);
+    // A tenant's history covers only its own corridors
+    let tenant_keys: Option<Vec<String>> = db
+        .tenants()
+        .corridor_keys(&tenant)
+        .await?
+        .map(|keys| keys.into_iter().map(String::from).collect());
+    let corridor_days = store
+        .corridor_days(start, MAX_STATUS_CORRIDORS, tenant_keys.as_deref())
+        .await?;
+
+    let response = StatusPageResponse {
+        status,
+        history_days: STATUS_HISTORY_DAYS,
+        components,
+        incidents: store.incidents_since(since).await?,
+        corridors: build_corridor_history(corridor_days, start, end),
+    };
+
+    Ok(crate::http_cache::cached_json_response(
+        &headers,
+        &tenant.cache_key("status:page".to_string()),
+        &response,
+        STATUS_CACHE_TTL_SECS,
+    )?
+    .into_response())
+}
+
+/// Expand sparse daily metrics into one bar per day from `start` to `end`
+/// inclusive, marking days without metrics as `no_data`
+fn build_corridor_history(
+    days: Vec<CorridorDay>,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<CorridorHealthHistory> {
+    let mut by_corridor: BTreeMap<String, BTreeMap<NaiveDate, CorridorDay>> = BTreeMap::new();
+    for day in days {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        by_corridor
+            .entry(day.corridor_key.clone())
+            .or_default()
+            .insert(date, day);
+    }
+
+    by_corridor
+        .into_iter()
+        .map(|(corridor_key, metrics)| {
+            let days: Vec<HealthBar> = start
+                .iter_days()
+                .take_while(|date| *date <= end)
+                .map(|date| match metrics.get(&date) {
+                    Some(day) if day.total_transactions > 0 => HealthBar {
+                        date,
+                        status: ComponentStatus::from_success_rate(day.success_rate).into(),
+                        success_rate: Some(day.success_rate),
+                        total_transactions: day.total_transactions,
+                    },
+                    _ => HealthBar {
+                        date,
+                        status: DayStatus::NoData,
+                        success_rate: None,
+                        total_transactions: 0,
+                    },
+                })
+                .collect();
+
+            let with_data = days.iter().filter(|d| d.status != DayStatus::NoData).count();
+            let operational = days
+                .iter()
+                .filter(|d| d.status == DayStatus::Operational)
+                .count();
+            let uptime_percent =
+                (with_data > 0).then(|| operational as f64 / with_data as f64 * 100.0);
+
+            CorridorHealthHistory {
+                corridor_key,
+                uptime_percent,
+                days,
+            }
+        })
+        .collect()
+}
+
+/// Public status page routes
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/status", get(get_status))
+        .with_state(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(corridor_key: &str, date: &str, success_rate: f64) -> CorridorDay {
+        CorridorDay {
+            corridor_key: corridor_key.to_string(),
+            date: date.to_string(),
+            success_rate,
+            total_transactions: 100,
+        }
+    }
+
+    #[test]
+    fn test_build_corridor_history_fills_missing_days() {
+        let start = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 6, 4).unwrap();
+        let history = build_corridor_history(
+            vec![
+                day("USDC-EURC", "2024-06-01", 99.5),
+                day("USDC-EURC", "2024-06-02", 96.0),
+                day("USDC-EURC", "2024-06-04", 99.9),
+            ],
+            start,
+            end,
+        );
+
+        assert_eq!(history.len(), 1);
+        let statuses: Vec<DayStatus> = history[0].days.iter().map(|d| d.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                DayStatus::Operational,
+                DayStatus::Degraded,
+                DayStatus::NoData,
+                DayStatus::Operational
+            ]
+        );
+        let uptime = history[0].uptime_percent.unwrap();
+        assert!((uptime - 200.0 / 3.0).abs() < 1e-9);
+    }
+}
@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 use crate::models::{TrustlineMetrics, TrustlineSnapshot, TrustlineStat};
 use crate::services::trustline_analyzer::TrustlineAnalyzer;
+use crate::time_range::TimeRange;
 
 pub type ApiResult<T> = Result<T, ApiError>;
 
@@ -91,10 +92,11 @@ async fn get_trustline_history(
     State(analyzer): State<Arc<TrustlineAnalyzer>>,
     Path((asset_code, asset_issuer)): Path<(String, String)>,
     Query(params): Query<HistoryParams>,
+    range: TimeRange,
 ) -> ApiResult<Json<Vec<TrustlineSnapshot>>> {
     let limit = params.limit.clamp(1, 365);
     let history = analyzer
-        .get_asset_history(&asset_code, &asset_issuer, limit)
+        .get_asset_history_in_range(&asset_code, &asset_issuer, &range, limit)
         .await
         .unwrap_or_default();
     Ok(Json(history))
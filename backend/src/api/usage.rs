@@ -0,0 +1,56 @@
+use axum::{extract::State, routing::get, Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::db::usage_events::{UsageEvents, UsageTotals};
+use crate::error::{ApiError, ApiResult};
+use crate::tenant::TenantScope;
+use crate::time_range::TimeRange;
+
+#[derive(Debug, Serialize)]
+pub struct UsageSummary {
+    pub tenant_id: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    #[serde(flatten)]
+    pub usage: UsageTotals,
+}
+
+/// GET /api/org/usage-summary - Metered usage of the caller's tenant
+///
+/// Accepts the shared `period`/`from`/`to` query parameters.
+pub async fn get_usage_summary(
+    State(db): State<Arc<Database>>,
+    tenant: TenantScope,
+    range: TimeRange,
+) -> ApiResult<Json<UsageSummary>> {
+    let Some(tenant_id) = tenant.id() else {
+        return Err(ApiError::forbidden(
+            "TENANT_REQUIRED",
+            "Usage is metered per tenant; use an API key assigned to a tenant",
+        ));
+    };
+
+    let usage = UsageEvents::new(db.pool().clone())
+        .totals(tenant_id, range.from, range.to)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load usage for tenant {}: {}", tenant_id, e);
+            ApiError::internal("USAGE_ERROR", "Failed to load usage")
+        })?;
+
+    Ok(Json(UsageSummary {
+        tenant_id: tenant_id.to_string(),
+        from: range.from,
+        to: range.to,
+        usage,
+    }))
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/org/usage-summary", get(get_usage_summary))
+        .with_state(db)
+}
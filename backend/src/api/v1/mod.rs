@@ -1,20 +1,15 @@
 use axum::{
+    extract::FromRef,
     routing::{get, put},
     Router,
     middleware,
 };
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
-use crate::state::AppState;
-use crate::rpc::StellarRpcClient;
-use crate::cache::CacheManager;
-use crate::database::Database;
-use crate::services::price_feed::PriceFeedClient;
-use crate::services::fee_bump_tracker::FeeBumpTrackerService;
-use crate::services::account_merge_detector::AccountMergeDetector;
-use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
+use crate::state::ServicesState;
+use std::sync::Arc;
 use crate::auth_middleware::auth_middleware;
-use crate::rate_limit::{rate_limit_middleware, RateLimiter};
+use crate::rate_limit::rate_limit_middleware;
 use crate::handlers::*;
 use crate::rpc_handlers;
 use crate::api::{
@@ -22,27 +17,16 @@ use crate::api::{
     liquidity_pools, metrics_cached, oauth, webhooks, price_feed as price_feed_api,
     cache_stats,
 };
-use std::sync::Arc;
 
-pub fn routes(
-    app_state: AppState,
-    cached_state: (Arc<Database>, Arc<CacheManager>, Arc<StellarRpcClient>, Arc<PriceFeedClient>),
-    rpc_client: Arc<StellarRpcClient>,
-    fee_bump_tracker: Arc<FeeBumpTrackerService>,
-    account_merge_detector: Arc<AccountMergeDetector>,
-    lp_analyzer: Arc<LiquidityPoolAnalyzer>,
-    price_feed: Arc<PriceFeedClient>,
-    rate_limiter: Arc<RateLimiter>,
-    cors: CorsLayer,
-    pool: sqlx::SqlitePool,
-    cache: Arc<CacheManager>,
-) -> Router {
+/// Versioned API router. Every group shares `services`; handlers extract the
+/// services they use through `FromRef` (see [`ServicesState`]).
+pub fn routes(services: ServicesState, cors: CorsLayer) -> Router {
     // 1. Cached routes
     let cached_routes = Router::new()
         .route("/anchors", get(anchors_cached::get_anchors))
         .route("/corridors", get(corridors_cached::list_corridors))
         .route("/corridors/:corridor_key", get(corridors_cached::get_corridor_detail))
-        .with_state(cached_state);
+        .with_state(services.clone());
 
     // 2. Public anchor routes
     let public_anchor_routes = Router::new()
@@ -51,7 +35,12 @@ pub fn routes(
         .route("/anchors/account/:stellar_account", get(get_anchor_by_account))
         .route("/anchors/:id/assets", get(get_anchor_assets))
         .route("/analytics/muxed", get(get_muxed_analytics))
-        .with_state(app_state.clone());
+        .with_state(services.clone());
+
+    let webhook_state = webhooks::WebhookApiState {
+        db: services.app.db.pool().clone(),
+        encryption_key: services.app.settings.security.encryption_key.clone(),
+    };
 
     // 3. Protected anchor routes
     let protected_routes = Router::new()
@@ -60,35 +49,36 @@ pub fn routes(
         .route("/anchors/:id/assets", axum::routing::post(create_anchor_asset))
         .route("/corridors", axum::routing::post(create_corridor))
         .route("/corridors/:id/metrics-from-transactions", put(update_corridor_metrics_from_transactions))
-        .with_state(app_state)
+        .with_state(services.clone())
         .layer(middleware::from_fn(auth_middleware));
 
     let protected_webhook_routes = Router::new()
-        .nest("/webhooks", webhooks::routes(pool.clone()))
+        .nest("/webhooks", webhooks::routes(webhook_state))
         .layer(middleware::from_fn(auth_middleware));
 
     // 4. RPC routes
     let rpc_routes = Router::new()
         .route("/rpc/health", get(rpc_handlers::rpc_health_check))
+        .route("/rpc/endpoints", get(rpc_handlers::get_horizon_endpoints))
         .route("/rpc/ledger/latest", get(rpc_handlers::get_latest_ledger))
         .route("/rpc/payments", get(rpc_handlers::get_payments))
         .route("/rpc/payments/account/:account_id", get(rpc_handlers::get_account_payments))
         .route("/rpc/trades", get(rpc_handlers::get_trades))
         .route("/rpc/orderbook", get(rpc_handlers::get_order_book))
-        .with_state(rpc_client);
+        .with_state(services.clone());
 
     // 5. Special service routes
     let service_routes = Router::new()
-        .nest("/fee-bumps", fee_bump::routes(fee_bump_tracker))
-        .nest("/account-merges", account_merges::routes(account_merge_detector))
-        .nest("/liquidity-pools", liquidity_pools::routes(lp_analyzer))
-        .nest("/prices", price_feed_api::routes(price_feed.clone()))
-        .nest("/cost-calculator", cost_calculator::routes(price_feed))
-        .nest("/cache/stats", cache_stats::routes(cache.clone()))
-        .nest("/metrics", metrics_cached::routes(cache));
+        .nest("/fee-bumps", fee_bump::routes(FromRef::from_ref(&services), FromRef::from_ref(&services)))
+        .nest("/account-merges", account_merges::routes(FromRef::from_ref(&services), FromRef::from_ref(&services)))
+        .nest("/liquidity-pools", liquidity_pools::routes(FromRef::from_ref(&services)))
+        .nest("/prices", price_feed_api::routes(FromRef::from_ref(&services)))
+        .nest("/cost-calculator", cost_calculator::routes(FromRef::from_ref(&services)))
+        .nest("/cache/stats", cache_stats::routes(FromRef::from_ref(&services)))
+        .nest("/metrics", metrics_cached::routes(FromRef::from_ref(&services)));
 
     // 6. OAuth routes
-    let oauth_routes = oauth::routes(pool);
+    let oauth_routes = oauth::routes(FromRef::from_ref(&services));
 
     // Combine all routes
     Router::new()
@@ -101,7 +91,10 @@ pub fn routes(
         .merge(oauth_routes)
         .layer(
             ServiceBuilder::new()
-                .layer(middleware::from_fn_with_state(rate_limiter, rate_limit_middleware))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&services.rate_limiter),
+                    rate_limit_middleware,
+                ))
                 .layer(middleware::from_fn(crate::api_v1_middleware::version_middleware))
                 .layer(cors),
         )
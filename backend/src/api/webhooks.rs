@@ -1,21 +1,60 @@
 /// Webhook API endpoints
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{delete, post},
+    routing::{delete, get, post, put},
     Json, Router,
 };
+use serde::Deserialize;
 use serde_json::json;
 use sqlx::SqlitePool;
 
 use crate::auth_middleware::AuthUser;
-use crate::webhooks::{CreateWebhookRequest, WebhookResponse, WebhookService};
+use crate::error::ApiError;
+use crate::plan::Plan;
+use crate::tenant::TenantScope;
+use crate::webhooks::deliveries::{
+    RedriveRequest, RedriveResponse, WebhookDeliveriesResponse, MAX_DELIVERIES_PER_PAGE,
+};
+use crate::webhooks::digest::watched_corridors;
+use crate::webhooks::filters::EventFilter;
+use crate::webhooks::{
+    CreateWebhookRequest, DeliverySettings, RotateSecretRequest, RotateSecretResponse, Webhook,
+    WebhookEventType, WebhookListResponse, WebhookResponse, WebhookService,
+    DEFAULT_ROTATION_GRACE_HOURS, MAX_ROTATION_GRACE_HOURS,
+};
+
+#[derive(Clone)]
+pub struct WebhookApiState {
+    pub db: SqlitePool,
+    pub encryption_key: String,
+}
+
+impl WebhookApiState {
+    fn service(&self) -> WebhookService {
+        WebhookService::new(self.db.clone(), self.encryption_key.clone())
+    }
+}
 
 /// POST /api/webhooks - Register a new webhook
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook registered", body = WebhookResponse),
+        (status = 400, description = "Invalid URL, event types, delivery settings or filters"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "The tenant's plan does not allow another webhook or this many watched corridors")
+    ),
+    tag = "Webhooks"
+)]
 pub async fn register_webhook(
-    State(db): State<SqlitePool>,
+    State(state): State<WebhookApiState>,
     auth_user: AuthUser,
+    tenant: TenantScope,
+    plan: Plan,
     Json(request): Json<CreateWebhookRequest>,
 ) -> Result<Response, WebhookApiError> {
     // Validate URL scheme
@@ -78,9 +117,28 @@ pub async fn register_webhook(
         ));
     }
 
-    let service = WebhookService::new(db);
+    request
+        .delivery
+        .validate()
+        .map_err(WebhookApiError::BadRequest)?;
+    if let Some(filters) = &request.filters {
+        EventFilter::parse(filters).map_err(WebhookApiError::BadRequest)?;
+    }
+
+    let service = state.service();
+    if let Some(tenant_id) = tenant.id() {
+        let active = service
+            .count_tenant_webhooks(tenant_id)
+            .await
+            .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
+        plan.check_webhooks(active).map_err(WebhookApiError::PlanLimit)?;
+    }
+    let filters = request.filters.as_ref().map(|f| f.to_string());
+    let watched = watched_corridors(filters.as_deref()).map_or(0, |c| c.len());
+    plan.check_watchlist(watched).map_err(WebhookApiError::PlanLimit)?;
+
     let response = service
-        .register_webhook(&auth_user.user_id, request)
+        .register_webhook(&auth_user.user_id, &tenant, request)
         .await
         .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
 
@@ -88,19 +146,30 @@ pub async fn register_webhook(
 }
 
 /// GET /api/webhooks - List webhooks for authenticated user
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    responses(
+        (status = 200, description = "The caller's webhooks", body = WebhookListResponse),
+        (status = 401, description = "Missing or invalid bearer token")
+    ),
+    tag = "Webhooks"
+)]
 pub async fn list_webhooks(
-    State(db): State<SqlitePool>,
+    State(state): State<WebhookApiState>,
     auth_user: AuthUser,
+    tenant: TenantScope,
 ) -> Result<Response, WebhookApiError> {
-    let service = WebhookService::new(db);
+    let service = state.service();
     let webhooks = service
-        .list_webhooks(&auth_user.user_id)
+        .list_webhooks(&auth_user.user_id, &tenant)
         .await
         .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
 
     let response: Vec<WebhookResponse> = webhooks
         .into_iter()
         .map(|w| WebhookResponse {
+            delivery: w.delivery_settings(),
             id: w.id,
             url: w.url,
             event_types: w.event_types.split(',').map(|s| s.to_string()).collect(),
@@ -113,16 +182,33 @@ pub async fn list_webhooks(
         })
         .collect();
 
-    Ok((StatusCode::OK, Json(json!({"webhooks": response}))).into_response())
+    Ok((
+        StatusCode::OK,
+        Json(WebhookListResponse { webhooks: response }),
+    )
+        .into_response())
 }
 
 /// DELETE /api/webhooks/:id - Delete/deactivate webhook
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    params(
+        ("id" = String, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook deleted"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Webhook not found")
+    ),
+    tag = "Webhooks"
+)]
 pub async fn delete_webhook(
-    State(db): State<SqlitePool>,
+    State(state): State<WebhookApiState>,
     auth_user: AuthUser,
     Path(webhook_id): Path<String>,
 ) -> Result<Response, WebhookApiError> {
-    let service = WebhookService::new(db);
+    let service = state.service();
     let deleted = service
         .delete_webhook(&webhook_id, &auth_user.user_id)
         .await
@@ -139,13 +225,107 @@ pub async fn delete_webhook(
         .into_response())
 }
 
+/// PUT /api/webhooks/:id/delivery - Configure batching and rate caps for a webhook
+pub async fn update_delivery_settings(
+    State(state): State<WebhookApiState>,
+    auth_user: AuthUser,
+    Path(webhook_id): Path<String>,
+    Json(settings): Json<DeliverySettings>,
+) -> Result<Response, WebhookApiError> {
+    settings.validate().map_err(WebhookApiError::BadRequest)?;
+
+    let service = state.service();
+    let updated = service
+        .update_delivery_settings(&webhook_id, &auth_user.user_id, &settings)
+        .await
+        .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
+
+    if !updated {
+        return Err(WebhookApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    Ok((StatusCode::OK, Json(json!({"id": webhook_id, "delivery": settings}))).into_response())
+}
+
+/// POST /api/webhooks/:id/rotate-secret - Replace the webhook's signing
+/// secret. Until the grace window ends, deliveries carry a second signature
+/// made with the old secret in `X-Zapier-Signature-Previous`.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{id}/rotate-secret",
+    params(
+        ("id" = String, Path, description = "Webhook ID")
+    ),
+    request_body = RotateSecretRequest,
+    responses(
+        (status = 200, description = "New signing secret", body = RotateSecretResponse),
+        (status = 400, description = "Invalid grace period"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Webhook not found")
+    ),
+    tag = "Webhooks"
+)]
+pub async fn rotate_secret(
+    State(state): State<WebhookApiState>,
+    auth_user: AuthUser,
+    Path(webhook_id): Path<String>,
+    request: Option<Json<RotateSecretRequest>>,
+) -> Result<Response, WebhookApiError> {
+    let Json(request) = request.unwrap_or_default();
+    let grace_hours = request
+        .grace_period_hours
+        .unwrap_or(DEFAULT_ROTATION_GRACE_HOURS);
+    if grace_hours > MAX_ROTATION_GRACE_HOURS {
+        return Err(WebhookApiError::BadRequest(format!(
+            "grace_period_hours must be at most {}",
+            MAX_ROTATION_GRACE_HOURS
+        )));
+    }
+
+    let (secret, previous_secret_expires_at) = state
+        .service()
+        .rotate_secret(
+            &webhook_id,
+            &auth_user.user_id,
+            chrono::Duration::hours(grace_hours as i64),
+        )
+        .await
+        .map_err(|e| WebhookApiError::ServerError(e.to_string()))?
+        .ok_or_else(|| WebhookApiError::NotFound("Webhook not found".to_string()))?;
+    tracing::info!("Rotated signing secret for webhook_id={}", webhook_id);
+
+    Ok((
+        StatusCode::OK,
+        Json(RotateSecretResponse {
+            id: webhook_id,
+            secret,
+            previous_secret_expires_at,
+        }),
+    )
+        .into_response())
+}
+
 /// POST /api/webhooks/:id/test - Send test payload to webhook
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{id}/test",
+    params(
+        ("id" = String, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 200, description = "Test payload prepared"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "Webhook belongs to another user"),
+        (status = 404, description = "Webhook not found")
+    ),
+    tag = "Webhooks"
+)]
 pub async fn test_webhook(
-    State(db): State<SqlitePool>,
+    State(state): State<WebhookApiState>,
     auth_user: AuthUser,
     Path(webhook_id): Path<String>,
 ) -> Result<Response, WebhookApiError> {
-    let service = WebhookService::new(db);
+    let service = state.service();
 
     // Get webhook
     let webhook = service
@@ -183,6 +363,196 @@ pub async fn test_webhook(
         .into_response())
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DeliveriesParams {
+    /// `pending`, `delivered` or `failed`
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// The caller's webhook with id `webhook_id`
+async fn owned_webhook(
+    service: &WebhookService,
+    webhook_id: &str,
+    user_id: &str,
+) -> Result<Webhook, WebhookApiError> {
+    let webhook = service
+        .get_webhook(webhook_id)
+        .await
+        .map_err(|e| WebhookApiError::ServerError(e.to_string()))?
+        .ok_or_else(|| WebhookApiError::NotFound("Webhook not found".to_string()))?;
+    if webhook.user_id != user_id {
+        return Err(WebhookApiError::Forbidden);
+    }
+    Ok(webhook)
+}
+
+/// GET /api/webhooks/:id/deliveries - Delivery history of a webhook, newest
+/// first, including dead-lettered failures that can be re-driven
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    params(
+        ("id" = String, Path, description = "Webhook ID"),
+        ("status" = Option<String>, Query, description = "Only deliveries with this status: pending, delivered or failed"),
+        ("limit" = Option<i64>, Query, description = "Most deliveries to return (1-200, default 50)")
+    ),
+    responses(
+        (status = 200, description = "Delivery history", body = WebhookDeliveriesResponse),
+        (status = 400, description = "Invalid status or limit"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "Webhook belongs to another user"),
+        (status = 404, description = "Webhook not found")
+    ),
+    tag = "Webhooks"
+)]
+pub async fn list_deliveries(
+    State(state): State<WebhookApiState>,
+    auth_user: AuthUser,
+    Path(webhook_id): Path<String>,
+    Query(params): Query<DeliveriesParams>,
+) -> Result<Response, WebhookApiError> {
+    if let Some(status) = &params.status {
+        if !matches!(status.as_str(), "pending" | "delivered" | "failed") {
+            return Err(WebhookApiError::BadRequest(
+                "status must be pending, delivered or failed".to_string(),
+            ));
+        }
+    }
+    let limit = params.limit.unwrap_or(50);
+    if !(1..=MAX_DELIVERIES_PER_PAGE).contains(&limit) {
+        return Err(WebhookApiError::BadRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_DELIVERIES_PER_PAGE
+        )));
+    }
+
+    let service = state.service();
+    owned_webhook(&service, &webhook_id, &auth_user.user_id).await?;
+    let deliveries = service
+        .list_deliveries(&webhook_id, params.status.as_deref(), limit)
+        .await
+        .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(WebhookDeliveriesResponse {
+            webhook_id,
+            deliveries,
+        }),
+    )
+        .into_response())
+}
+
+/// POST /api/webhooks/:id/deliveries/redrive - Deliver dead-lettered events
+/// again with fresh retries
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{id}/deliveries/redrive",
+    params(
+        ("id" = String, Path, description = "Webhook ID")
+    ),
+    request_body = RedriveRequest,
+    responses(
+        (status = 200, description = "Events queued for delivery again", body = RedriveResponse),
+        (status = 400, description = "Webhook is inactive"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "Webhook belongs to another user"),
+        (status = 404, description = "Webhook not found")
+    ),
+    tag = "Webhooks"
+)]
+pub async fn redrive_deliveries(
+    State(state): State<WebhookApiState>,
+    auth_user: AuthUser,
+    Path(webhook_id): Path<String>,
+    request: Option<Json<RedriveRequest>>,
+) -> Result<Response, WebhookApiError> {
+    let Json(request) = request.unwrap_or_default();
+    let service = state.service();
+    let webhook = owned_webhook(&service, &webhook_id, &auth_user.user_id).await?;
+    if !webhook.is_active {
+        return Err(WebhookApiError::BadRequest(
+            "Webhook is inactive; its failures cannot be re-driven".to_string(),
+        ));
+    }
+
+    let redriven = service
+        .redrive_dead_letters(&webhook_id, request.event_ids.as_deref())
+        .await
+        .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
+    tracing::info!(
+        "Re-drove {} dead-lettered events for webhook_id={}",
+        redriven,
+        webhook_id
+    );
+
+    Ok((StatusCode::OK, Json(RedriveResponse { redriven })).into_response())
+}
+
+/// Event a filter is tested against: inline, or one already queued for one of
+/// the caller's webhooks
+#[derive(Debug, Deserialize)]
+pub struct FilterTestRequest {
+    pub filters: serde_json::Value,
+    pub event_type: Option<String>,
+    pub event: Option<serde_json::Value>,
+    pub event_id: Option<String>,
+}
+
+/// POST /api/webhooks/filters/test - Evaluate a filter against a sample event
+/// and explain the result, without creating or changing any webhook
+pub async fn test_filter(
+    State(state): State<WebhookApiState>,
+    auth_user: AuthUser,
+    Json(request): Json<FilterTestRequest>,
+) -> Result<Response, WebhookApiError> {
+    let filter = EventFilter::parse(&request.filters).map_err(WebhookApiError::BadRequest)?;
+
+    let (event_type, payload) = match (request.event, request.event_id) {
+        (Some(event), None) => (request.event_type, event),
+        (None, Some(event_id)) => {
+            let event = state
+                .service()
+                .get_event_for_user(&event_id, &auth_user.user_id)
+                .await
+                .map_err(|e| WebhookApiError::ServerError(e.to_string()))?
+                .ok_or_else(|| WebhookApiError::NotFound("Webhook event not found".to_string()))?;
+            let payload = serde_json::from_str(&event.payload).map_err(|_| {
+                WebhookApiError::BadRequest(
+                    "Webhook event payload is no longer available".to_string(),
+                )
+            })?;
+            (Some(event.event_type), payload)
+        }
+        _ => {
+            return Err(WebhookApiError::BadRequest(
+                "Provide exactly one of event or event_id".to_string(),
+            ))
+        }
+    };
+
+    if let Some(event_type) = &event_type {
+        if WebhookEventType::from_str(event_type).is_none() {
+            return Err(WebhookApiError::BadRequest(format!(
+                "Unknown event type '{}'",
+                event_type
+            )));
+        }
+    }
+
+    let evaluation = filter.evaluate(event_type.as_deref(), &payload);
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "event_type": event_type,
+            "matched": evaluation.matched,
+            "trace": evaluation.trace,
+        })),
+    )
+        .into_response())
+}
+
 /// Webhook API Error types
 #[derive(Debug)]
 pub enum WebhookApiError {
@@ -190,11 +560,14 @@ pub enum WebhookApiError {
     BadRequest(String),
     Forbidden,
     ServerError(String),
+    /// The tenant's plan does not allow the change; a structured error
+    PlanLimit(ApiError),
 }
 
 impl IntoResponse for WebhookApiError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
+            WebhookApiError::PlanLimit(err) => return err.into_response(),
             WebhookApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             WebhookApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             WebhookApiError::Forbidden => (
@@ -209,10 +582,18 @@ impl IntoResponse for WebhookApiError {
 }
 
 /// Create webhook routes
-pub fn routes(db: SqlitePool) -> Router {
+pub fn routes(state: WebhookApiState) -> Router {
     Router::new()
         .route("/api/webhooks", post(register_webhook).get(list_webhooks))
+        .route("/api/webhooks/filters/test", post(test_filter))
         .route("/api/webhooks/:id", delete(delete_webhook))
+        .route("/api/webhooks/:id/delivery", put(update_delivery_settings))
+        .route("/api/webhooks/:id/deliveries", get(list_deliveries))
+        .route(
+            "/api/webhooks/:id/deliveries/redrive",
+            post(redrive_deliveries),
+        )
+        .route("/api/webhooks/:id/rotate-secret", post(rotate_secret))
         .route("/api/webhooks/:id/test", post(test_webhook))
-        .with_state(db)
+        .with_state(state)
 }
@@ -1,16 +1,19 @@
 // pub mod sep10;  // Commented out - uses stellar-xdr types that require stellar-base
 pub mod sep10_middleware;
 pub mod sep10_simple;
+pub mod login_guard;
 pub mod oauth;
 
 use anyhow::{anyhow, Result};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
-use redis::aio::MultiplexedConnection;
-use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+
+use crate::database::Database;
+use crate::db::sessions::{device_label, ClientInfo, SessionStore};
+use crate::email::security_alert::{NewDeviceLogin, SecurityAlertNotifier};
+use crate::auth::login_guard::{LoginCheck, LoginGuard};
 
 // Token expiry constants
 const ACCESS_TOKEN_EXPIRY_HOURS: i64 = 1;
@@ -68,16 +71,39 @@ pub struct Claims {
     pub exp: i64,           // Expiry timestamp
     pub iat: i64,           // Issued at timestamp
     pub token_type: String, // "access" or "refresh"
+    /// Session (issued refresh token) the token belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+}
+
+/// Why a login was refused
+#[derive(Debug)]
+pub enum LoginError {
+    InvalidCredentials,
+    /// Too many failures for the account or client IP
+    Locked {
+        retry_after_secs: u64,
+    },
+    Internal(anyhow::Error),
+}
+
+impl From<anyhow::Error> for LoginError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Internal(err)
+    }
 }
 
 /// Authentication service
 pub struct AuthService {
     jwt_secret: String,
-    redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>,
+    db: Arc<Database>,
+    login_guard: Option<LoginGuard>,
+    security_alerts: Option<SecurityAlertNotifier>,
 }
 
 impl AuthService {
-    pub fn new(redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>) -> Self {
+    /// Refresh tokens are tracked per session in `user_sessions`
+    pub fn new(db: Arc<Database>) -> Self {
         let jwt_secret = std::env::var("JWT_SECRET")
             .expect("JWT_SECRET environment variable is required. Generate a cryptographically secure random key of at least 32 bytes.");
 
@@ -87,7 +113,40 @@ impl AuthService {
 
         Self {
             jwt_secret,
-            redis_connection,
+            db,
+            login_guard: None,
+            security_alerts: None,
+        }
+    }
+
+    /// Track failed logins with progressive delays and lockout
+    pub fn with_login_guard(mut self, guard: LoginGuard) -> Self {
+        self.login_guard = Some(guard);
+        self
+    }
+
+    /// Email users when they sign in from a new device
+    pub fn with_security_alerts(mut self, notifier: SecurityAlertNotifier) -> Self {
+        self.security_alerts = Some(notifier);
+        self
+    }
+
+    /// Record a login event in the audit log
+    async fn audit(&self, action: &str, subject: &str, status: &str, details: serde_json::Value) {
+        let logger = &self.db.admin_audit_logger;
+        let prev_hash = logger.latest_hash().await.unwrap_or(None);
+        if let Err(e) = logger
+            .log_action(
+                action,
+                "auth",
+                subject,
+                status,
+                details,
+                prev_hash.as_deref(),
+            )
+            .await
+        {
+            tracing::error!("Failed to write {} audit entry: {}", action, e);
         }
     }
 
@@ -100,8 +159,12 @@ impl AuthService {
         Err(anyhow!("Authentication not configured. Implement database-backed user store."))
     }
 
+    fn sessions(&self) -> SessionStore {
+        self.db.sessions()
+    }
+
     /// Generate access token
-    pub fn generate_access_token(&self, user: &User) -> Result<String> {
+    pub fn generate_access_token(&self, user: &User, session_id: &str) -> Result<String> {
         let expiration = Utc::now()
             .checked_add_signed(Duration::hours(ACCESS_TOKEN_EXPIRY_HOURS))
             .ok_or_else(|| anyhow!("Invalid timestamp"))?
@@ -113,6 +176,7 @@ impl AuthService {
             exp: expiration,
             iat: Utc::now().timestamp(),
             token_type: "access".to_string(),
+            sid: Some(session_id.to_string()),
         };
 
         encode(
@@ -124,7 +188,7 @@ impl AuthService {
     }
 
     /// Generate refresh token
-    pub fn generate_refresh_token(&self, user: &User) -> Result<String> {
+    pub fn generate_refresh_token(&self, user: &User, session_id: &str) -> Result<String> {
         let expiration = Utc::now()
             .checked_add_signed(Duration::days(REFRESH_TOKEN_EXPIRY_DAYS))
             .ok_or_else(|| anyhow!("Invalid timestamp"))?
@@ -136,6 +200,7 @@ impl AuthService {
             exp: expiration,
             iat: Utc::now().timestamp(),
             token_type: "refresh".to_string(),
+            sid: Some(session_id.to_string()),
         };
 
         encode(
@@ -159,26 +224,8 @@ impl AuthService {
         .map_err(|e| anyhow!("Invalid token: {}", e))
     }
 
-    /// Store refresh token in Redis
-    pub async fn store_refresh_token(&self, token: &str, user_id: &str) -> Result<()> {
-        if let Some(conn) = self.redis_connection.read().await.as_ref() {
-            let mut conn = conn.clone();
-            let key = format!("refresh_token:{}", user_id);
-            let expiry = REFRESH_TOKEN_EXPIRY_DAYS * 24 * 60 * 60; // seconds
-
-            conn.set_ex::<_, _, ()>(&key, token, expiry as u64)
-                .await
-                .map_err(|e| anyhow!("Failed to store refresh token: {}", e))?;
-
-            tracing::debug!("Stored refresh token for user: {}", user_id);
-        } else {
-            tracing::warn!("Redis not available, refresh token not stored");
-        }
-
-        Ok(())
-    }
-
-    /// Validate refresh token from Redis
+    /// Validate a refresh token against its stored session (fail closed -
+    /// SEC-007) and record the use
     pub async fn validate_refresh_token(&self, token: &str) -> Result<Claims> {
         // First validate JWT signature and expiry
         let claims = self.validate_token(token)?;
@@ -188,54 +235,90 @@ impl AuthService {
             return Err(anyhow!("Invalid token type"));
         }
 
-        // Check if token exists in Redis (fail closed - SEC-007)
-        if let Some(conn) = self.redis_connection.read().await.as_ref() {
-            let mut conn = conn.clone();
-            let key = format!("refresh_token:{}", claims.sub);
-
-            let stored_token: Option<String> = conn
-                .get(&key)
-                .await
-                .map_err(|e| anyhow!("Failed to retrieve refresh token: {}", e))?;
-
-            if stored_token.as_deref() != Some(token) {
-                return Err(anyhow!("Refresh token not found or invalid"));
-            }
-        } else {
-            tracing::error!("Redis not available - refusing refresh token validation (fail closed)");
-            return Err(anyhow!("Token validation service unavailable"));
-        }
+        let session_id = claims
+            .sid
+            .as_deref()
+            .ok_or_else(|| anyhow!("Refresh token has no session"))?;
+        let session = self
+            .sessions()
+            .find_active(session_id, token)
+            .await?
+            .filter(|session| session.user_id == claims.sub)
+            .ok_or_else(|| anyhow!("Refresh token not found or revoked"))?;
+        self.sessions().touch(&session.id).await?;
 
         Ok(claims)
     }
 
-    /// Invalidate refresh token (logout)
-    pub async fn invalidate_refresh_token(&self, user_id: &str) -> Result<()> {
-        if let Some(conn) = self.redis_connection.read().await.as_ref() {
-            let mut conn = conn.clone();
-            let key = format!("refresh_token:{}", user_id);
-
-            conn.del::<_, ()>(&key)
-                .await
-                .map_err(|e| anyhow!("Failed to invalidate refresh token: {}", e))?;
-
-            tracing::debug!("Invalidated refresh token for user: {}", user_id);
+    /// Login flow. Each login starts a new session, so a user can be signed
+    /// in on several devices at once. Failed attempts are delayed and
+    /// eventually locked out when a login guard is configured.
+    pub async fn login(
+        &self,
+        request: LoginRequest,
+        client: ClientInfo,
+    ) -> Result<LoginResponse, LoginError> {
+        let ip = client.ip_address.clone().unwrap_or_default();
+
+        if let Some(guard) = &self.login_guard {
+            match guard.check(&request.username, &ip).await {
+                Ok(LoginCheck::Locked { retry_after_secs }) => {
+                    self.audit(
+                        "auth.login_blocked",
+                        &request.username,
+                        "failure",
+                        serde_json::json!({ "ip": ip, "retry_after_secs": retry_after_secs }),
+                    )
+                    .await;
+                    return Err(LoginError::Locked { retry_after_secs });
+                }
+                Ok(LoginCheck::Allowed { delay }) => {
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+                }
+                Err(e) => tracing::warn!("Login guard check failed: {}", e),
+            }
         }
 
-        Ok(())
-    }
-
-    /// Login flow
-    pub async fn login(&self, request: LoginRequest) -> Result<LoginResponse> {
         // Authenticate user
-        let user = self.authenticate(&request.username, &request.password)?;
+        let user = match self.authenticate(&request.username, &request.password) {
+            Ok(user) => user,
+            Err(_) => {
+                self.record_failed_login(&request.username, &ip).await;
+                return Err(LoginError::InvalidCredentials);
+            }
+        };
+        if let Some(guard) = &self.login_guard {
+            if let Err(e) = guard.record_success(&request.username).await {
+                tracing::warn!("Failed to clear login failures: {}", e);
+            }
+        }
 
         // Generate tokens
-        let access_token = self.generate_access_token(&user)?;
-        let refresh_token = self.generate_refresh_token(&user)?;
-
-        // Store refresh token
-        self.store_refresh_token(&refresh_token, &user.id).await?;
+        let session_id = SessionStore::new_session_id();
+        let access_token = self.generate_access_token(&user, &session_id)?;
+        let refresh_token = self.generate_refresh_token(&user, &session_id)?;
+
+        // Track the session
+        let device = device_label(client.user_agent.as_deref());
+        let new_device = self.sessions().is_new_device(&user.id, &device).await?;
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::days(REFRESH_TOKEN_EXPIRY_DAYS))
+            .ok_or_else(|| anyhow!("Invalid timestamp"))?;
+        self.sessions()
+            .create(&session_id, &user.id, &refresh_token, &client, expires_at)
+            .await?;
+
+        if new_device {
+            self.notify_new_device(NewDeviceLogin {
+                user_id: user.id.clone(),
+                session_id,
+                device,
+                ip_address: client.ip_address,
+            })
+            .await;
+        }
 
         Ok(LoginResponse {
             access_token,
@@ -244,10 +327,71 @@ impl AuthService {
         })
     }
 
+    async fn record_failed_login(&self, username: &str, ip: &str) {
+        let Some(guard) = &self.login_guard else {
+            self.audit(
+                "auth.login_failed",
+                username,
+                "failure",
+                serde_json::json!({ "ip": ip }),
+            )
+            .await;
+            return;
+        };
+
+        match guard.record_failure(username, ip).await {
+            Ok(outcome) => {
+                self.audit(
+                    "auth.login_failed",
+                    username,
+                    "failure",
+                    serde_json::json!({ "ip": ip, "failures": outcome.account_failures }),
+                )
+                .await;
+                if let Some(lockout_secs) = outcome.locked_for_secs {
+                    tracing::warn!(
+                        "Login locked for {} from {} after repeated failures",
+                        username,
+                        ip
+                    );
+                    self.audit(
+                        "auth.account_locked",
+                        username,
+                        "success",
+                        serde_json::json!({ "ip": ip, "lockout_secs": lockout_secs }),
+                    )
+                    .await;
+                }
+            }
+            Err(e) => tracing::warn!("Failed to record login failure: {}", e),
+        }
+    }
+
+    async fn notify_new_device(&self, login: NewDeviceLogin) {
+        self.audit(
+            "auth.new_device_login",
+            &login.user_id,
+            "success",
+            serde_json::json!({
+                "session_id": login.session_id,
+                "device": login.device,
+                "ip": login.ip_address,
+            }),
+        )
+        .await;
+
+        if let Some(notifier) = &self.security_alerts {
+            if let Err(e) = notifier.new_device_login(&login).await {
+                tracing::error!("Failed to queue new device login email: {}", e);
+            }
+        }
+    }
+
     /// Refresh access token
     pub async fn refresh(&self, request: RefreshTokenRequest) -> Result<RefreshTokenResponse> {
         // Validate refresh token
         let claims = self.validate_refresh_token(&request.refresh_token).await?;
+        let session_id = claims.sid.unwrap_or_default();
 
         // Create user from claims
         let user = User {
@@ -256,7 +400,7 @@ impl AuthService {
         };
 
         // Generate new access token
-        let access_token = self.generate_access_token(&user)?;
+        let access_token = self.generate_access_token(&user, &session_id)?;
 
         Ok(RefreshTokenResponse {
             access_token,
@@ -264,13 +408,14 @@ impl AuthService {
         })
     }
 
-    /// Logout flow
+    /// Logout flow: revokes the session of the refresh token
     pub async fn logout(&self, request: LogoutRequest) -> Result<()> {
         // Validate and get claims from refresh token
         let claims = self.validate_token(&request.refresh_token)?;
 
-        // Invalidate refresh token
-        self.invalidate_refresh_token(&claims.sub).await?;
+        if let Some(session_id) = claims.sid.as_deref() {
+            self.sessions().revoke(&claims.sub, session_id).await?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,235 @@
+//! Failed-login tracking, progressive delays and temporary lockout.
+//!
+//! Failures are counted in Redis per account and per client IP over a sliding
+//! window. After a few free attempts each further failure doubles the delay
+//! before the next attempt is evaluated; at the threshold the account (or IP)
+//! is locked out for a while. Without Redis the guard lets every attempt
+//! through, so a Redis outage can't lock everyone out.
+
+use anyhow::Result;
+use redis::aio::MultiplexedConnection;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone)]
+pub struct LoginGuardConfig {
+    /// Failures within the window that lock the account
+    pub account_threshold: u64,
+    /// Failures from one IP within the window that lock the IP; higher than
+    /// the account threshold since many users can share an IP
+    pub ip_threshold: u64,
+    pub failure_window_secs: u64,
+    pub lockout_secs: u64,
+    /// Failures allowed before delays start
+    pub free_attempts: u64,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for LoginGuardConfig {
+    fn default() -> Self {
+        Self {
+            account_threshold: 10,
+            ip_threshold: 50,
+            failure_window_secs: 900,
+            lockout_secs: 900,
+            free_attempts: 3,
+            base_delay_ms: 250,
+            max_delay_ms: 8_000,
+        }
+    }
+}
+
+impl LoginGuardConfig {
+    /// Delay before evaluating the next attempt after `failures` failures:
+    /// nothing for the free attempts, then doubling up to `max_delay_ms`
+    pub fn progressive_delay(&self, failures: u64) -> Duration {
+        if failures < self.free_attempts {
+            return Duration::ZERO;
+        }
+        let doublings = (failures - self.free_attempts).min(16) as u32;
+        let delay = self.base_delay_ms.saturating_mul(1 << doublings);
+        Duration::from_millis(delay.min(self.max_delay_ms))
+    }
+}
+
+/// Whether a login attempt may proceed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginCheck {
+    Allowed { delay: Duration },
+    Locked { retry_after_secs: u64 },
+}
+
+/// Result of recording a failed attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailureOutcome {
+    pub account_failures: u64,
+    /// Set when this failure started a lockout
+    pub locked_for_secs: Option<u64>,
+}
+
+pub struct LoginGuard {
+    redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>,
+    config: LoginGuardConfig,
+}
+
+fn failures_key(scope: &str, id: &str) -> String {
+    format!("login_failures:{}:{}", scope, id.to_lowercase())
+}
+
+fn lockout_key(scope: &str, id: &str) -> String {
+    format!("login_lockout:{}:{}", scope, id.to_lowercase())
+}
+
+/// Whether `failures` within the window lock the scope. Failures past the
+/// threshold lock it again, e.g. after a lockout expires inside the window.
+fn locks_out(failures: u64, threshold: u64) -> bool {
+    failures >= threshold
+}
+
+impl LoginGuard {
+    pub fn new(
+        redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>,
+        config: LoginGuardConfig,
+    ) -> Self {
+        Self {
+            redis_connection,
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &LoginGuardConfig {
+        &self.config
+    }
+
+    async fn connection(&self) -> Option<MultiplexedConnection> {
+        self.redis_connection.read().await.clone()
+    }
+
+    /// Check lockouts for the account and IP, and the delay owed for earlier
+    /// failures
+    pub async fn check(&self, username: &str, ip: &str) -> Result<LoginCheck> {
+        let Some(mut conn) = self.connection().await else {
+            return Ok(LoginCheck::Allowed {
+                delay: Duration::ZERO,
+            });
+        };
+
+        let mut retry_after = 0i64;
+        for key in [lockout_key("account", username), lockout_key("ip", ip)] {
+            let ttl: i64 = redis::cmd("TTL").arg(&key).query_async(&mut conn).await?;
+            retry_after = retry_after.max(ttl);
+        }
+        if retry_after > 0 {
+            return Ok(LoginCheck::Locked {
+                retry_after_secs: retry_after as u64,
+            });
+        }
+
+        let account: Option<u64> = redis::cmd("GET")
+            .arg(failures_key("account", username))
+            .query_async(&mut conn)
+            .await?;
+        Ok(LoginCheck::Allowed {
+            delay: self.config.progressive_delay(account.unwrap_or(0)),
+        })
+    }
+
+    /// Count a failed attempt against the account and IP, locking either
+    /// while it is at or past its threshold
+    pub async fn record_failure(&self, username: &str, ip: &str) -> Result<FailureOutcome> {
+        let Some(mut conn) = self.connection().await else {
+            return Ok(FailureOutcome {
+                account_failures: 0,
+                locked_for_secs: None,
+            });
+        };
+
+        let mut counts = [0u64; 2];
+        let scopes = [
+            ("account", username, self.config.account_threshold),
+            ("ip", ip, self.config.ip_threshold),
+        ];
+        let mut locked_for_secs = None;
+        for (count, (scope, id, threshold)) in counts.iter_mut().zip(scopes) {
+            let key = failures_key(scope, id);
+            *count = redis::cmd("INCR").arg(&key).query_async(&mut conn).await?;
+            if *count == 1 {
+                redis::cmd("EXPIRE")
+                    .arg(&key)
+                    .arg(self.config.failure_window_secs)
+                    .query_async::<_, ()>(&mut conn)
+                    .await?;
+            }
+            if locks_out(*count, threshold) {
+                redis::cmd("SETEX")
+                    .arg(lockout_key(scope, id))
+                    .arg(self.config.lockout_secs)
+                    .arg(1)
+                    .query_async::<_, ()>(&mut conn)
+                    .await?;
+                locked_for_secs = Some(self.config.lockout_secs);
+            }
+        }
+
+        Ok(FailureOutcome {
+            account_failures: counts[0],
+            locked_for_secs,
+        })
+    }
+
+    /// Clear the account's failure count. The IP count is kept so a client
+    /// can't reset it by logging into an account of its own.
+    pub async fn record_success(&self, username: &str) -> Result<()> {
+        if let Some(mut conn) = self.connection().await {
+            redis::cmd("DEL")
+                .arg(failures_key("account", username))
+                .query_async::<_, ()>(&mut conn)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progressive_delay() {
+        let config = LoginGuardConfig::default();
+        assert_eq!(config.progressive_delay(0), Duration::ZERO);
+        assert_eq!(config.progressive_delay(2), Duration::ZERO);
+        assert_eq!(config.progressive_delay(3), Duration::from_millis(250));
+        assert_eq!(config.progressive_delay(4), Duration::from_millis(500));
+        assert_eq!(config.progressive_delay(6), Duration::from_millis(2_000));
+        assert_eq!(config.progressive_delay(9), Duration::from_millis(8_000));
+        assert_eq!(
+            config.progressive_delay(1_000),
+            Duration::from_millis(8_000)
+        );
+    }
+
+    #[test]
+    fn test_failures_past_threshold_lock_out() {
+        let config = LoginGuardConfig::default();
+        let threshold = config.account_threshold;
+        assert!(!locks_out(threshold - 1, threshold));
+        assert!(locks_out(threshold, threshold));
+        assert!(locks_out(threshold + 1, threshold));
+        assert!(locks_out(threshold * 3, threshold));
+    }
+
+    #[test]
+    fn test_keys_are_case_insensitive() {
+        assert_eq!(
+            failures_key("account", "Alice"),
+            "login_failures:account:alice"
+        );
+        assert_eq!(
+            lockout_key("ip", "203.0.113.7"),
+            "login_lockout:ip:203.0.113.7"
+        );
+    }
+}
@@ -1,6 +1,5 @@
 /// OAuth 2.0 module for Zapier integration
 /// Handles authorization code flow, token generation, and scope validation
-
 use anyhow::{anyhow, Result};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
@@ -8,17 +7,19 @@ use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
+use crate::crypto::{lookup_hash, FieldCipher};
+
 /// OAuth Claims - extended JWT with additional Zapier fields
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OAuthClaims {
-    pub sub: String,           // User ID
-    pub username: String,      // Username
-    pub client_id: String,     // OAuth client ID
-    pub scopes: Vec<String>,   // Granted scopes
-    pub exp: i64,              // Expiry timestamp
-    pub iat: i64,              // Issued at timestamp
-    pub aud: String,           // Audience (must be "zapier")
-    pub token_type: String,    // "access" or "refresh"
+    pub sub: String,         // User ID
+    pub username: String,    // Username
+    pub client_id: String,   // OAuth client ID
+    pub scopes: Vec<String>, // Granted scopes
+    pub exp: i64,            // Expiry timestamp
+    pub iat: i64,            // Issued at timestamp
+    pub aud: String,         // Audience (must be "zapier")
+    pub token_type: String,  // "access" or "refresh"
 }
 
 /// OAuth authorization code (short-lived, for exchanging to tokens)
@@ -63,13 +64,13 @@ pub struct OAuthService {
     jwt_audience: String,
     token_expiry_days: i64,
     refresh_expiry_days: i64,
-    encryption_key: String,
+    cipher: FieldCipher,
     db: SqlitePool,
 }
 
 impl OAuthService {
     /// Create new OAuth service
-    pub fn new(db: SqlitePool) -> Self {
+    pub fn new(db: SqlitePool, cipher: FieldCipher) -> Self {
         let jwt_secret = std::env::var("JWT_SECRET")
             .expect("JWT_SECRET environment variable is required for OAuth service");
 
@@ -85,15 +86,12 @@ impl OAuthService {
             .parse()
             .unwrap_or(30);
 
-        let encryption_key = std::env::var("ENCRYPTION_KEY")
-            .expect("ENCRYPTION_KEY environment variable is required for OAuth service");
-
         Self {
             jwt_secret,
             jwt_audience,
             token_expiry_days,
             refresh_expiry_days,
-            encryption_key,
+            cipher,
             db,
         }
     }
@@ -108,7 +106,9 @@ impl OAuthService {
         let client_id = Uuid::new_v4().to_string();
         let client_secret = Uuid::new_v4().to_string();
 
-        let encrypted_secret = crate::crypto::encrypt_data(&client_secret, &self.encryption_key)
+        let encrypted_secret = self
+            .cipher
+            .encrypt(&client_secret)
             .map_err(|e| anyhow!("Failed to encrypt client secret: {}", e))?;
 
         sqlx::query(
@@ -151,14 +151,16 @@ impl OAuthService {
 
         match client {
             Some((user_id, client_secret_record)) => {
-                let decrypted_secret = crate::crypto::decrypt_data(&client_secret_record, &self.encryption_key)
+                let decrypted_secret = self
+                    .cipher
+                    .decrypt(&client_secret_record)
                     .map_err(|_| anyhow!("Invalid client credentials"))?;
                 if decrypted_secret == client_secret {
                     Ok(user_id)
                 } else {
                     Err(anyhow!("Invalid client credentials"))
                 }
-            },
+            }
             None => Err(anyhow!("Invalid client credentials")),
         }
     }
@@ -310,12 +312,7 @@ impl OAuthService {
             r.get::<String, _>(0)
         });
 
-        Ok(auth.map(|record| {
-            record
-                .split(',')
-                .map(|s: &str| s.to_string())
-                .collect()
-        }))
+        Ok(auth.map(|record| record.split(',').map(|s: &str| s.to_string()).collect()))
     }
 
     /// Store OAuth token in database
@@ -332,15 +329,21 @@ impl OAuthService {
             .ok_or_else(|| anyhow!("Invalid timestamp"))?
             .to_rfc3339();
 
-        let enc_access_token = crate::crypto::encrypt_data(access_token, &self.encryption_key)
+        let enc_access_token = self
+            .cipher
+            .encrypt(access_token)
             .map_err(|e| anyhow!("Failed to encrypt access token: {}", e))?;
-        let enc_refresh_token = crate::crypto::encrypt_data(refresh_token, &self.encryption_key)
+        let enc_refresh_token = self
+            .cipher
+            .encrypt(refresh_token)
             .map_err(|e| anyhow!("Failed to encrypt refresh token: {}", e))?;
 
         sqlx::query(
             r#"
-            INSERT INTO oauth_tokens (id, user_id, access_token, refresh_token, token_type, expires_at)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO oauth_tokens (
+                id, user_id, access_token, refresh_token, token_type, expires_at, access_token_hash
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(id)
@@ -349,23 +352,20 @@ impl OAuthService {
         .bind(enc_refresh_token)
         .bind("Bearer")
         .bind(expires_at_str)
+        .bind(lookup_hash(access_token))
         .execute(&self.db)
         .await?;
 
         Ok(())
     }
 
-    /// Revoke OAuth token by deleting it from the database
+    /// Revoke OAuth token by deleting it from the database. The stored token
+    /// is encrypted with a random nonce, so it is found by its lookup hash.
     pub async fn revoke_token(&self, access_token: &str) -> Result<()> {
-        let enc_token = crate::crypto::encrypt_data(access_token, &self.encryption_key)
-            .map_err(|e| anyhow!("Failed to encrypt token for lookup: {}", e))?;
-
-        let result = sqlx::query!(
-            r#"DELETE FROM oauth_tokens WHERE access_token = ?"#,
-            enc_token
-        )
-        .execute(&self.db)
-        .await?;
+        let result = sqlx::query(r#"DELETE FROM oauth_tokens WHERE access_token_hash = ?"#)
+            .bind(lookup_hash(access_token))
+            .execute(&self.db)
+            .await?;
 
         if result.rows_affected() == 0 {
             tracing::warn!("Token revocation requested but token not found in database");
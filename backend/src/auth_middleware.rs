@@ -18,6 +18,8 @@ pub struct JwtSecret(pub Arc<str>);
 pub struct AuthUser {
     pub user_id: String,
     pub username: String,
+    /// Login session the access token was issued for
+    pub session_id: Option<String>,
 }
 
 #[axum::async_trait]
@@ -57,14 +59,16 @@ pub async fn auth_middleware(
     let auth_user = AuthUser {
         user_id: claims.sub,
         username: claims.username,
+        session_id: claims.sid,
     };
+    crate::request_id::record_user(&auth_user.user_id);
     req.extensions_mut().insert(auth_user);
 
     Ok(next.run(req).await)
 }
 
 /// Validate access token
-fn validate_access_token(token: &str, secret: &str) -> Result<Claims, AuthError> {
+pub fn validate_access_token(token: &str, secret: &str) -> Result<Claims, AuthError> {
     use jsonwebtoken::{decode, DecodingKey, Validation};
 
     let validation = Validation::default();
@@ -98,9 +102,10 @@ impl IntoResponse for AuthError {
             AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid or expired token"),
         };
 
-        let body = json!({
-            "error": message,
-        });
+        let body = match crate::request_id::current_request_id() {
+            Some(request_id) => json!({ "error": message, "request_id": request_id }),
+            None => json!({ "error": message }),
+        };
 
         (status, axum::Json(body)).into_response()
     }
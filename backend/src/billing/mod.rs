@@ -0,0 +1,212 @@
+//! Metered billing for the hosted, multi-tenant deployment.
+//!
+//! Tenant API calls, delivered webhook events and WebSocket minutes are
+//! recorded to `usage_events` as they happen. The `billing-usage-export` job
+//! batches unexported usage of tenants with a billing customer into
+//! `usage_export_batches` and reports each batch, until it is acknowledged,
+//! to the exporter selected in `Settings::billing` (`BILLING_EXPORTER`):
+//!
+//! - `none` (default): usage is recorded and summarized but not billed
+//! - `stripe`: Stripe metered billing (`STRIPE_API_KEY`)
+
+pub mod stripe;
+
+pub use stripe::StripeExporter;
+
+use anyhow::{Context, Result};
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::config::BillingSettings;
+
+use crate::db::usage_events::UsageEvents;
+
+/// What a usage event meters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageKind {
+    ApiCall,
+    WebhookDelivery,
+    WsMinutes,
+}
+
+impl UsageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageKind::ApiCall => "api_call",
+            UsageKind::WebhookDelivery => "webhook_delivery",
+            UsageKind::WsMinutes => "ws_minutes",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "api_call" => Some(UsageKind::ApiCall),
+            "webhook_delivery" => Some(UsageKind::WebhookDelivery),
+            "ws_minutes" => Some(UsageKind::WsMinutes),
+            _ => None,
+        }
+    }
+}
+
+/// Records usage without holding up the request, delivery or connection
+/// being metered
+#[derive(Clone)]
+pub struct UsageRecorder {
+    pool: SqlitePool,
+}
+
+impl UsageRecorder {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub fn record(&self, tenant_id: &str, kind: UsageKind, quantity: i64) {
+        let events = UsageEvents::new(self.pool.clone());
+        let tenant_id = tenant_id.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = events.record(&tenant_id, kind, quantity).await {
+                tracing::error!(
+                    "Failed to record {} usage for tenant {}: {}",
+                    kind.as_str(),
+                    tenant_id,
+                    e
+                );
+            }
+        });
+    }
+}
+
+/// Usage of one kind to bill to a customer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageReport {
+    pub customer_id: String,
+    pub kind: UsageKind,
+    pub quantity: i64,
+    /// Stable across retries of the same report, for idempotent exports
+    pub identifier: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait BillingExporter: Send + Sync {
+    /// Exporter name for logs ("stripe")
+    fn name(&self) -> &'static str;
+
+    async fn export(&self, report: &UsageReport) -> Result<()>;
+}
+
+/// Exporter selected with `BILLING_EXPORTER`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BillingExporterKind {
+    #[default]
+    None,
+    Stripe,
+}
+
+impl FromStr for BillingExporterKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" | "" => Ok(Self::None),
+            "stripe" => Ok(Self::Stripe),
+            other => Err(format!(
+                "unknown billing exporter '{}', expected none or stripe",
+                other
+            )),
+        }
+    }
+}
+
+/// Build the exporter selected in `billing`. Returns `Ok(None)` when billing
+/// export is off.
+pub fn exporter_from_settings(
+    billing: &BillingSettings,
+) -> Result<Option<Arc<dyn BillingExporter>>> {
+    let exporter: Arc<dyn BillingExporter> = match billing.exporter {
+        BillingExporterKind::None => return Ok(None),
+        BillingExporterKind::Stripe => Arc::new(StripeExporter::new(
+            billing
+                .stripe_api_key
+                .clone()
+                .context("STRIPE_API_KEY must be set when BILLING_EXPORTER=stripe")?,
+        )),
+    };
+
+    Ok(Some(exporter))
+}
+
+/// Reports unexported usage to a [`BillingExporter`]
+pub struct UsageExport {
+    pool: SqlitePool,
+    exporter: Arc<dyn BillingExporter>,
+}
+
+impl UsageExport {
+    pub fn new(pool: SqlitePool, exporter: Arc<dyn BillingExporter>) -> Self {
+        Self { pool, exporter }
+    }
+
+    /// Batch pending usage, one batch per tenant and kind, and send every
+    /// unacknowledged batch. Returns the number of batches acknowledged; a
+    /// failed batch is resent unchanged on the next run.
+    pub async fn run_once(&self) -> Result<usize> {
+        let events = UsageEvents::new(self.pool.clone());
+        events.create_export_batches().await?;
+        let mut exported = 0;
+
+        for batch in events.unacknowledged_batches().await? {
+            let Some(kind) = UsageKind::parse(&batch.kind) else {
+                continue;
+            };
+            let report = UsageReport {
+                customer_id: batch.billing_customer_id.clone(),
+                kind,
+                quantity: batch.quantity,
+                identifier: batch.identifier.clone(),
+                timestamp: batch.created_at,
+            };
+
+            if let Err(e) = self.exporter.export(&report).await {
+                tracing::warn!(
+                    "Failed to export {} usage for tenant {} to {} (attempt {}): {}",
+                    batch.kind,
+                    batch.tenant_id,
+                    self.exporter.name(),
+                    batch.attempts + 1,
+                    e
+                );
+                events
+                    .record_batch_failure(batch.id, &e.to_string())
+                    .await?;
+                continue;
+            }
+            events.acknowledge_batch(batch.id).await?;
+            exported += 1;
+        }
+
+        Ok(exported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_kind_round_trips() {
+        for kind in [
+            UsageKind::ApiCall,
+            UsageKind::WebhookDelivery,
+            UsageKind::WsMinutes,
+        ] {
+            assert_eq!(UsageKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(UsageKind::parse("ws_seconds"), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! Stripe metered billing through the Billing Meter Events API.
+//!
+//! Each [`UsageKind`] is sent as a meter event named after it (`api_call`,
+//! `webhook_delivery`, `ws_minutes`), so the Stripe account needs one meter
+//! per name, aggregated by sum.
+
+use anyhow::{bail, Result};
+use axum::async_trait;
+use reqwest::Client;
+use std::time::Duration;
+
+use super::{BillingExporter, UsageReport};
+use crate::http_client;
+
+const STRIPE_METER_EVENTS_URL: &str = "https://api.stripe.com/v1/billing/meter_events";
+
+pub struct StripeExporter {
+    client: Client,
+    api_key: String,
+}
+
+impl StripeExporter {
+    pub fn new(api_key: String) -> Self {
+        let client = http_client::shared().client(Duration::from_secs(15));
+        Self { client, api_key }
+    }
+}
+
+/// Form body of a meter event
+fn meter_event_form(report: &UsageReport) -> String {
+    [
+        ("event_name", report.kind.as_str().to_string()),
+        ("payload[stripe_customer_id]", report.customer_id.clone()),
+        ("payload[value]", report.quantity.to_string()),
+        ("identifier", report.identifier.clone()),
+        ("timestamp", report.timestamp.timestamp().to_string()),
+    ]
+    .iter()
+    .map(|(key, value)| {
+        format!(
+            "{}={}",
+            urlencoding::encode(key),
+            urlencoding::encode(value)
+        )
+    })
+    .collect::<Vec<_>>()
+    .join("&")
+}
+
+#[async_trait]
+impl BillingExporter for StripeExporter {
+    fn name(&self) -> &'static str {
+        "stripe"
+    }
+
+    async fn export(&self, report: &UsageReport) -> Result<()> {
+        let response = self
+            .client
+            .post(STRIPE_METER_EVENTS_URL)
+            .bearer_auth(&self.api_key)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            // Stripe drops a meter event whose identifier it has already seen
+            .header("Idempotency-Key", &report.identifier)
+            .body(meter_event_form(report))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Stripe returned {}: {}", status, body);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::billing::UsageKind;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_meter_event_form() {
+        let report = UsageReport {
+            customer_id: "cus_123".to_string(),
+            kind: UsageKind::WsMinutes,
+            quantity: 42,
+            identifier: "acme:ws_minutes:7".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        };
+        assert_eq!(
+            meter_event_form(&report),
+            "event_name=ws_minutes&payload%5Bstripe_customer_id%5D=cus_123&payload%5Bvalue%5D=42\
+             &identifier=acme%3Aws_minutes%3A7&timestamp=1704067200"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Rebuild hourly corridor metrics from stored payments.
+//!
+//! Usage: recompute_corridor_metrics --from <date> --to <date> [--dry-run]
+//!
+//! Dates are RFC 3339 timestamps or `YYYY-MM-DD` (midnight UTC). Connects to
+//! `DATABASE_URL`, defaulting to `sqlite://stellar_insights.db`.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::SqlitePool;
+use std::process::exit;
+use std::sync::Arc;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::corridor_recompute::{
+    CorridorRecomputeService, RecomputeProgress, RecomputeRequest, RecomputeStatus,
+};
+
+fn usage() -> ! {
+    eprintln!("Usage: recompute_corridor_metrics --from <date> --to <date> [--dry-run]");
+    exit(2);
+}
+
+fn parse_date(value: &str) -> DateTime<Utc> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return dt.with_timezone(&Utc);
+    }
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => date.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        Err(_) => {
+            eprintln!("Invalid date: {}", value);
+            usage();
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let mut from = None;
+    let mut to = None;
+    let mut dry_run = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => from = args.next().map(|v| parse_date(&v)),
+            "--to" => to = args.next().map(|v| parse_date(&v)),
+            "--dry-run" => dry_run = true,
+            _ => usage(),
+        }
+    }
+    let (Some(from), Some(to)) = (from, to) else {
+        usage();
+    };
+
+    let req = RecomputeRequest { from, to, dry_run };
+    let hours = match req.hours() {
+        Ok(hours) => hours,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(2);
+        }
+    };
+
+    let url = std::env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "sqlite://stellar_insights.db".to_string());
+    let pool = SqlitePool::connect(&url).await.unwrap_or_else(|e| {
+        eprintln!("Failed to connect to {}: {}", url, e);
+        exit(1);
+    });
+    let service = CorridorRecomputeService::new(Arc::new(Database::new(pool)));
+
+    println!(
+        "Recomputing {} hours from {} to {}{}",
+        hours.len(),
+        from,
+        to,
+        if dry_run { " (dry run)" } else { "" }
+    );
+    let progress = RecomputeProgress::new(&req, hours.len());
+    let result = service
+        .recompute(&req, progress, |p| {
+            if p.status == RecomputeStatus::Running {
+                println!(
+                    "[{}/{}] {} payments, {} changes",
+                    p.hours_done, p.hours_total, p.payments_processed, p.changes
+                );
+            }
+        })
+        .await;
+
+    for diff in &result.diff {
+        println!(
+            "{:?} {} {}\n  before: {:?}\n  after:  {:?}",
+            diff.change, diff.hour_bucket, diff.corridor_key, diff.before, diff.after
+        );
+    }
+    if result.changes > result.diff.len() {
+        println!("... {} more changes not shown", result.changes - result.diff.len());
+    }
+
+    match result.status {
+        RecomputeStatus::Failed => {
+            eprintln!("Failed: {}", result.error.unwrap_or_default());
+            exit(1);
+        }
+        _ => println!(
+            "Done: {} changes, {} rows written",
+            result.changes, result.rows_written
+        ),
+    }
+}
@@ -51,6 +51,7 @@ mod tests {
             status: "active".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
         };
 
         // Should not panic
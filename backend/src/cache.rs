@@ -1,9 +1,17 @@
+use axum::body::Bytes;
 use redis::aio::MultiplexedConnection;
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
+/// Distinct keys whose hits are counted for the hot key report
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// Keys examined per `SCAN` round trip when sampling the keyspace
+const SCAN_BATCH: usize = 500;
+
 /// Cache statistics for monitoring
 #[derive(Debug, Clone)]
 pub struct CacheStats {
@@ -23,12 +31,86 @@ impl CacheStats {
     }
 }
 
+/// Lookup counters for one key namespace (see [`namespace_of`])
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamespaceStats {
+    pub namespace: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+}
+
+impl NamespaceStats {
+    pub fn hit_rate(&self) -> f64 {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            invalidations: self.invalidations,
+        }
+        .hit_rate()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotKey {
+    pub key: String,
+    pub hits: u64,
+}
+
+/// Keys and memory held by one namespace, from sampling Redis
+#[derive(Debug, Clone, Default)]
+pub struct NamespaceUsage {
+    pub namespace: String,
+    pub keys: u64,
+    pub memory_bytes: u64,
+}
+
+/// Server-wide Redis figures plus a per-namespace breakdown of the keys scanned
+#[derive(Debug, Clone, Default)]
+pub struct KeyspaceSample {
+    /// Keys in the database (`DBSIZE`)
+    pub total_keys: u64,
+    /// Keys examined; less than `total_keys` when the scan was capped
+    pub scanned_keys: u64,
+    pub used_memory_bytes: u64,
+    /// Redis evictions and expirations are only tracked server-wide
+    pub evicted_keys: u64,
+    pub expired_keys: u64,
+    pub namespaces: Vec<NamespaceUsage>,
+}
+
+/// Namespace a cache key belongs to, from its prefix (`corridor:detail:x` ->
+/// `corridors`). Unknown prefixes are reported as-is.
+pub fn namespace_of(key: &str) -> &str {
+    let prefix = key.split(':').next().unwrap_or(key);
+    match prefix {
+        "anchor" => "anchors",
+        "corridor" => "corridors",
+        "stellar_toml" => "toml",
+        "orderbook" => "orderbooks",
+        "dashboard" | "metrics" => "dashboard",
+        other => other,
+    }
+}
+
+/// Read a numeric field from `INFO` output
+fn info_field(info: &str, field: &str) -> u64 {
+    info.lines()
+        .find_map(|line| line.strip_prefix(field)?.strip_prefix(':'))
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
 /// Cache configuration with TTL settings
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
     pub corridor_metrics_ttl: usize, // 5 minutes
     pub anchor_data_ttl: usize,      // 10 minutes
     pub dashboard_stats_ttl: usize,  // 1 minute
+    /// How long a lookup that found nothing is remembered
+    pub not_found_ttl: usize, // 30 seconds
+    /// Anchor `/info` documents fetched through the SEP proxies
+    pub sep_info_ttl: usize, // 1 minute
 }
 
 impl CacheConfig {
@@ -37,6 +119,8 @@ impl CacheConfig {
             "corridor" => self.corridor_metrics_ttl,
             "anchor" => self.anchor_data_ttl,
             "dashboard" => self.dashboard_stats_ttl,
+            "not_found" => self.not_found_ttl,
+            "sep_info" => self.sep_info_ttl,
             _ => 300,
         }
     }
@@ -48,6 +132,8 @@ impl Default for CacheConfig {
             corridor_metrics_ttl: 300, // 5 minutes
             anchor_data_ttl: 600,      // 10 minutes
             dashboard_stats_ttl: 60,   // 1 minute
+            not_found_ttl: 30,         // 30 seconds
+            sep_info_ttl: 60,          // 1 minute
         }
     }
 }
@@ -59,6 +145,8 @@ pub struct CacheManager {
     hits: Arc<AtomicU64>,
     misses: Arc<AtomicU64>,
     invalidations: Arc<AtomicU64>,
+    namespaces: Mutex<HashMap<String, NamespaceStats>>,
+    key_hits: Mutex<HashMap<String, u64>>,
 }
 
 impl CacheManager {
@@ -88,46 +176,100 @@ impl CacheManager {
             hits: Arc::new(AtomicU64::new(0)),
             misses: Arc::new(AtomicU64::new(0)),
             invalidations: Arc::new(AtomicU64::new(0)),
+            namespaces: Mutex::new(HashMap::new()),
+            key_hits: Mutex::new(HashMap::new()),
         })
     }
 
+    fn record_lookup(&self, key: &str, hit: bool) {
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        crate::observability::metrics::record_cache_lookup(hit);
+
+        let namespace = namespace_of(key);
+        if let Ok(mut namespaces) = self.namespaces.lock() {
+            let stats = namespaces
+                .entry(namespace.to_string())
+                .or_insert_with(|| NamespaceStats {
+                    namespace: namespace.to_string(),
+                    ..Default::default()
+                });
+            if hit {
+                stats.hits += 1;
+            } else {
+                stats.misses += 1;
+            }
+        }
+
+        if hit {
+            if let Ok(mut key_hits) = self.key_hits.lock() {
+                if let Some(count) = key_hits.get_mut(key) {
+                    *count += 1;
+                } else if key_hits.len() < MAX_TRACKED_KEYS {
+                    key_hits.insert(key.to_string(), 1);
+                }
+            }
+        }
+    }
+
+    fn record_invalidation(&self, key: &str) {
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+        let namespace = namespace_of(key);
+        if let Ok(mut namespaces) = self.namespaces.lock() {
+            namespaces
+                .entry(namespace.to_string())
+                .or_insert_with(|| NamespaceStats {
+                    namespace: namespace.to_string(),
+                    ..Default::default()
+                })
+                .invalidations += 1;
+        }
+    }
+
     /// Get value from cache, returns None if not found or Redis unavailable
     pub async fn get<T: DeserializeOwned>(&self, key: &str) -> anyhow::Result<Option<T>> {
+        let Some(value) = self.get_bytes(key).await? else {
+            return Ok(None);
+        };
+        match serde_json::from_slice::<T>(&value) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) => {
+                tracing::warn!("Failed to deserialize cached value for {}: {}", key, e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Raw bytes stored at `key`, returns None if not found or Redis unavailable
+    pub async fn get_bytes(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
         if let Some(conn) = self.redis_connection.read().await.as_ref() {
             let mut conn = conn.clone();
             match redis::cmd("GET")
                 .arg(key)
-                .query_async::<_, Option<String>>(&mut conn)
+                .query_async::<_, Option<Vec<u8>>>(&mut conn)
                 .await
             {
                 Ok(Some(value)) => {
-                    self.hits.fetch_add(1, Ordering::Relaxed);
-                    crate::observability::metrics::record_cache_lookup(true);
+                    self.record_lookup(key, true);
                     tracing::debug!("Cache hit for key: {}", key);
-                    match serde_json::from_str::<T>(&value) {
-                        Ok(data) => Ok(Some(data)),
-                        Err(e) => {
-                            tracing::warn!("Failed to deserialize cached value for {}: {}", key, e);
-                            Ok(None)
-                        }
-                    }
+                    Ok(Some(value))
                 }
                 Ok(None) => {
-                    self.misses.fetch_add(1, Ordering::Relaxed);
-                    crate::observability::metrics::record_cache_lookup(false);
+                    self.record_lookup(key, false);
                     tracing::debug!("Cache miss for key: {}", key);
                     Ok(None)
                 }
                 Err(e) => {
                     tracing::warn!("Redis GET error for {}: {}", key, e);
-                    self.misses.fetch_add(1, Ordering::Relaxed);
-                    crate::observability::metrics::record_cache_lookup(false);
+                    self.record_lookup(key, false);
                     Ok(None)
                 }
             }
         } else {
-            self.misses.fetch_add(1, Ordering::Relaxed);
-            crate::observability::metrics::record_cache_lookup(false);
+            self.record_lookup(key, false);
             Ok(None)
         }
     }
@@ -138,36 +280,55 @@ impl CacheManager {
         key: &str,
         value: &T,
         ttl_seconds: usize,
+    ) -> anyhow::Result<()> {
+        match serde_json::to_vec(value) {
+            Ok(serialized) => self.set_bytes(key, &serialized, ttl_seconds).await,
+            Err(e) => {
+                tracing::warn!("Failed to serialize value for cache key {}: {}", key, e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Store raw bytes at `key` with TTL
+    pub async fn set_bytes(
+        &self,
+        key: &str,
+        value: &[u8],
+        ttl_seconds: usize,
     ) -> anyhow::Result<()> {
         if let Some(conn) = self.redis_connection.read().await.as_ref() {
             let mut conn = conn.clone();
-            match serde_json::to_string(value) {
-                Ok(serialized) => {
-                    match redis::cmd("SETEX")
-                        .arg(key)
-                        .arg(ttl_seconds)
-                        .arg(&serialized)
-                        .query_async::<_, ()>(&mut conn)
-                        .await
-                    {
-                        Ok(_) => {
-                            tracing::debug!("Cache set for key: {} (TTL: {}s)", key, ttl_seconds);
-                            Ok(())
-                        }
-                        Err(e) => {
-                            tracing::warn!("Redis SETEX error for {}: {}", key, e);
-                            Ok(())
-                        }
-                    }
+            match redis::cmd("SETEX")
+                .arg(key)
+                .arg(ttl_seconds)
+                .arg(value)
+                .query_async::<_, ()>(&mut conn)
+                .await
+            {
+                Ok(_) => {
+                    tracing::debug!("Cache set for key: {} (TTL: {}s)", key, ttl_seconds);
                 }
                 Err(e) => {
-                    tracing::warn!("Failed to serialize value for cache key {}: {}", key, e);
-                    Ok(())
+                    tracing::warn!("Redis SETEX error for {}: {}", key, e);
                 }
             }
-        } else {
-            Ok(())
         }
+        Ok(())
+    }
+
+    /// Whether a recent lookup for `key` found nothing (see [`keys::not_found`])
+    pub async fn is_known_missing(&self, key: &str) -> bool {
+        matches!(
+            self.get::<bool>(&keys::not_found(key)).await,
+            Ok(Some(true))
+        )
+    }
+
+    /// Remember that `key` does not exist for [`CacheConfig::not_found_ttl`]
+    pub async fn remember_missing(&self, key: &str) -> anyhow::Result<()> {
+        self.set(&keys::not_found(key), &true, self.config.not_found_ttl)
+            .await
     }
 
     /// Delete a cache key
@@ -180,7 +341,7 @@ impl CacheManager {
                 .await
             {
                 Ok(_) => {
-                    self.invalidations.fetch_add(1, Ordering::Relaxed);
+                    self.record_invalidation(key);
                     tracing::debug!("Cache invalidated for key: {}", key);
                     Ok(())
                 }
@@ -209,7 +370,7 @@ impl CacheManager {
                             .arg(&key)
                             .query_async::<_, ()>(&mut conn)
                             .await;
-                        self.invalidations.fetch_add(1, Ordering::Relaxed);
+                        self.record_invalidation(&key);
                     }
                     tracing::debug!("Cache invalidated for pattern: {}", pattern);
                     Ok(())
@@ -244,11 +405,118 @@ impl CacheManager {
         }
     }
 
+    /// Per-namespace lookup counters, sorted by namespace
+    pub fn namespace_stats(&self) -> Vec<NamespaceStats> {
+        let mut stats: Vec<NamespaceStats> = self
+            .namespaces
+            .lock()
+            .map(|namespaces| namespaces.values().cloned().collect())
+            .unwrap_or_default();
+        stats.sort_by(|a, b| a.namespace.cmp(&b.namespace));
+        stats
+    }
+
+    /// The `limit` most-hit keys since the last reset
+    pub fn hot_keys(&self, limit: usize) -> Vec<HotKey> {
+        let mut keys: Vec<HotKey> = self
+            .key_hits
+            .lock()
+            .map(|key_hits| {
+                key_hits
+                    .iter()
+                    .map(|(key, hits)| HotKey {
+                        key: key.clone(),
+                        hits: *hits,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        keys.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.key.cmp(&b.key)));
+        keys.truncate(limit);
+        keys
+    }
+
+    /// Count keys and memory per namespace by scanning up to `max_keys` keys.
+    /// Returns None when Redis is unavailable.
+    pub async fn sample_keyspace(&self, max_keys: usize) -> Option<KeyspaceSample> {
+        let mut conn = self.redis_connection.read().await.as_ref()?.clone();
+
+        let mut sample = KeyspaceSample::default();
+        match redis::cmd("INFO").query_async::<_, String>(&mut conn).await {
+            Ok(info) => {
+                sample.used_memory_bytes = info_field(&info, "used_memory");
+                sample.evicted_keys = info_field(&info, "evicted_keys");
+                sample.expired_keys = info_field(&info, "expired_keys");
+            }
+            Err(e) => tracing::warn!("Redis INFO error: {}", e),
+        }
+        sample.total_keys = redis::cmd("DBSIZE")
+            .query_async::<_, u64>(&mut conn)
+            .await
+            .unwrap_or(0);
+
+        let mut usage: HashMap<String, NamespaceUsage> = HashMap::new();
+        let mut cursor: u64 = 0;
+        loop {
+            let (next, keys) = match redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("COUNT")
+                .arg(SCAN_BATCH)
+                .query_async::<_, (u64, Vec<String>)>(&mut conn)
+                .await
+            {
+                Ok(page) => page,
+                Err(e) => {
+                    tracing::warn!("Redis SCAN error: {}", e);
+                    break;
+                }
+            };
+
+            for key in keys {
+                let memory = redis::cmd("MEMORY")
+                    .arg("USAGE")
+                    .arg(&key)
+                    .query_async::<_, Option<u64>>(&mut conn)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or(0);
+                let namespace = namespace_of(&key);
+                let entry = usage
+                    .entry(namespace.to_string())
+                    .or_insert_with(|| NamespaceUsage {
+                        namespace: namespace.to_string(),
+                        ..Default::default()
+                    });
+                entry.keys += 1;
+                entry.memory_bytes += memory;
+                sample.scanned_keys += 1;
+            }
+
+            cursor = next;
+            if cursor == 0 || sample.scanned_keys as usize >= max_keys {
+                break;
+            }
+        }
+
+        sample.namespaces = usage.into_values().collect();
+        sample
+            .namespaces
+            .sort_by(|a, b| b.memory_bytes.cmp(&a.memory_bytes));
+        Some(sample)
+    }
+
     /// Reset statistics
     pub fn reset_stats(&self) {
         self.hits.store(0, Ordering::Relaxed);
         self.misses.store(0, Ordering::Relaxed);
         self.invalidations.store(0, Ordering::Relaxed);
+        if let Ok(mut namespaces) = self.namespaces.lock() {
+            namespaces.clear();
+        }
+        if let Ok(mut key_hits) = self.key_hits.lock() {
+            key_hits.clear();
+        }
     }
 
     /// Close Redis connection gracefully
@@ -269,8 +537,58 @@ impl CacheManager {
     }
 }
 
+/// A response body serialized once and cached as-is, with its ETag, so cache
+/// hits are written straight to the client without a serde round trip.
+///
+/// Stored in Redis as the ETag, a newline, then the JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedJson {
+    pub etag: String,
+    pub body: Bytes,
+}
+
+impl CachedJson {
+    pub fn new<T: Serialize>(value: &T) -> anyhow::Result<Self> {
+        let body = serde_json::to_vec(value)?;
+        Ok(Self {
+            etag: crate::http_cache::etag_for(&body),
+            body: Bytes::from(body),
+        })
+    }
+
+    /// Deserialize the body, for responses that reshape the cached value
+    pub fn decode<T: DeserializeOwned>(&self) -> anyhow::Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.etag.len() + 1 + self.body.len());
+        out.extend_from_slice(self.etag.as_bytes());
+        out.push(b'\n');
+        out.extend_from_slice(&self.body);
+        out
+    }
+
+    /// `None` for values not written by [`CachedJson::to_bytes`]
+    pub fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        let split = bytes.iter().position(|b| *b == b'\n')?;
+        let etag = std::str::from_utf8(&bytes[..split]).ok()?;
+        if !etag.starts_with('"') {
+            return None;
+        }
+        let etag = etag.to_string();
+        let mut body = Bytes::from(bytes);
+        Some(Self {
+            etag,
+            body: body.split_off(split + 1),
+        })
+    }
+}
+
 /// Cache key builders for consistency
 pub mod keys {
+    use crate::models::corridor::CorridorKey;
+
     pub fn anchor_list(limit: i64, offset: i64) -> String {
         format!("anchor:list:{}:{}", limit, offset)
     }
@@ -291,10 +609,25 @@ pub mod keys {
         format!("corridor:list:{}:{}:{}", limit, offset, filters)
     }
 
-    pub fn corridor_detail(corridor_key: &str) -> String {
+    pub fn corridor_detail(corridor_key: &CorridorKey) -> String {
         format!("corridor:detail:{}", corridor_key)
     }
 
+    /// Negative entry for a lookup that found nothing. It stays under the
+    /// original key's namespace so pattern invalidation clears it too.
+    pub fn not_found(key: &str) -> String {
+        format!("{}:missing", key)
+    }
+
+    pub fn corridor_badge(corridor_key: &CorridorKey) -> String {
+        format!("corridor:badge:{}", corridor_key)
+    }
+
+    /// Anchor `/info` document for one SEP protocol (`sep24`, `sep31`)
+    pub fn sep_info(protocol: &str, transfer_server: &str) -> String {
+        format!("sep_info:{}:{}", protocol, transfer_server)
+    }
+
     pub fn dashboard_stats() -> String {
         "dashboard:stats".to_string()
     }
@@ -313,6 +646,11 @@ pub mod keys {
         "corridor:*".to_string()
     }
 
+    /// Pattern for invalidating every cached anchor `/info` document
+    pub fn sep_info_pattern() -> String {
+        "sep_info:*".to_string()
+    }
+
     /// Pattern for invalidating all dashboard caches
     pub fn dashboard_pattern() -> String {
         "dashboard:*".to_string()
@@ -350,5 +688,89 @@ mod tests {
         assert_eq!(keys::anchor_by_account("GA123"), "anchor:account:GA123");
         assert_eq!(keys::dashboard_stats(), "dashboard:stats");
         assert_eq!(keys::anchor_pattern(), "anchor:*");
+        assert_eq!(
+            keys::not_found(&keys::anchor_detail("123")),
+            "anchor:detail:123:missing"
+        );
+    }
+
+    #[test]
+    fn test_cached_json_round_trip() {
+        let cached = CachedJson::new(&serde_json::json!({"total": 2})).unwrap();
+        assert_eq!(&cached.body[..], br#"{"total":2}"#);
+        assert_eq!(
+            CachedJson::from_bytes(cached.to_bytes()),
+            Some(cached.clone())
+        );
+        assert_eq!(cached.decode::<serde_json::Value>().unwrap()["total"], 2);
+
+        // Plain JSON written by `CacheManager::set` is not a cached body
+        assert_eq!(CachedJson::from_bytes(br#"{"total":2}"#.to_vec()), None);
+    }
+
+    #[test]
+    fn test_namespace_of() {
+        assert_eq!(
+            namespace_of("corridor:detail:USDC:GA->XLM:native"),
+            "corridors"
+        );
+        assert_eq!(namespace_of("anchor:list:50:0"), "anchors");
+        assert_eq!(namespace_of("stellar_toml:example.com"), "toml");
+        assert_eq!(namespace_of("custom:key"), "custom");
+    }
+
+    #[test]
+    fn test_info_field() {
+        let info =
+            "# Memory\r\nused_memory:1024\r\nused_memory_rss:4096\r\n# Stats\r\nevicted_keys:7\r\n";
+        assert_eq!(info_field(info, "used_memory"), 1024);
+        assert_eq!(info_field(info, "evicted_keys"), 7);
+        assert_eq!(info_field(info, "expired_keys"), 0);
+    }
+
+    #[test]
+    fn test_namespace_stats_and_hot_keys() {
+        let cache = CacheManager {
+            redis_connection: Arc::new(RwLock::new(None)),
+            config: CacheConfig::default(),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+            invalidations: Arc::new(AtomicU64::new(0)),
+            namespaces: Mutex::new(HashMap::new()),
+            key_hits: Mutex::new(HashMap::new()),
+        };
+        cache.record_lookup("corridor:detail:a", true);
+        cache.record_lookup("corridor:detail:a", true);
+        cache.record_lookup("corridor:detail:b", true);
+        cache.record_lookup("anchor:detail:1", false);
+        cache.record_invalidation("anchor:detail:1");
+
+        let stats = cache.namespace_stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            (
+                stats[0].namespace.as_str(),
+                stats[0].misses,
+                stats[0].invalidations
+            ),
+            ("anchors", 1, 1)
+        );
+        assert_eq!(
+            (stats[1].namespace.as_str(), stats[1].hits),
+            ("corridors", 3)
+        );
+
+        let hot = cache.hot_keys(1);
+        assert_eq!(
+            hot,
+            vec![HotKey {
+                key: "corridor:detail:a".to_string(),
+                hits: 2
+            }]
+        );
+
+        cache.reset_stats();
+        assert!(cache.namespace_stats().is_empty());
+        assert!(cache.hot_keys(10).is_empty());
     }
 }
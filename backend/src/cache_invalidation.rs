@@ -1,4 +1,5 @@
 use crate::cache::{keys, CacheManager};
+use crate::models::corridor::CorridorKey;
 use std::sync::Arc;
 
 /// Service for managing cache invalidation on data updates
@@ -41,7 +42,7 @@ impl CacheInvalidationService {
     }
 
     /// Invalidate specific corridor cache
-    pub async fn invalidate_corridor(&self, corridor_key: &str) -> anyhow::Result<()> {
+    pub async fn invalidate_corridor(&self, corridor_key: &CorridorKey) -> anyhow::Result<()> {
         tracing::info!("Invalidating cache for corridor: {}", corridor_key);
         self.cache
             .delete(&keys::corridor_detail(corridor_key))
@@ -1,4 +1,4 @@
-use crate::cache::CacheManager;
+use crate::cache::{CacheManager, CachedJson};
 use std::sync::Arc;
 
 /// Helper trait for cache-aware operations
@@ -12,6 +12,30 @@ pub trait CacheAware {
     where
         T: serde::Serialize + serde::de::DeserializeOwned,
         F: std::future::Future<Output = anyhow::Result<T>>;
+
+    /// Like [`CacheAware::get_or_fetch`], but the value is serialized once on a
+    /// miss and cache hits return the stored JSON bytes without parsing them.
+    fn get_or_fetch_json<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        ttl: usize,
+        fetch_fn: F,
+    ) -> impl std::future::Future<Output = anyhow::Result<CachedJson>>
+    where
+        T: serde::Serialize,
+        F: std::future::Future<Output = anyhow::Result<T>>;
+
+    /// Run a lookup that may find nothing, skipping it while a recent miss for
+    /// `key` is remembered. Found values are not cached; only misses are, for
+    /// the short `not_found` TTL, so probing unknown keys doesn't reach the
+    /// database or Horizon on every request.
+    fn fetch_unless_missing<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        fetch_fn: F,
+    ) -> impl std::future::Future<Output = anyhow::Result<Option<T>>>
+    where
+        F: std::future::Future<Output = anyhow::Result<Option<T>>>;
 }
 
 /// Implement for unit type to provide static methods
@@ -41,6 +65,52 @@ impl CacheAware for () {
             Ok(data)
         }
     }
+
+    fn get_or_fetch_json<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        ttl: usize,
+        fetch_fn: F,
+    ) -> impl std::future::Future<Output = anyhow::Result<CachedJson>>
+    where
+        T: serde::Serialize,
+        F: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        async move {
+            if let Ok(Some(bytes)) = cache.get_bytes(key).await {
+                if let Some(cached) = CachedJson::from_bytes(bytes) {
+                    return Ok(cached);
+                }
+            }
+
+            let cached = CachedJson::new(&fetch_fn.await?)?;
+            let _ = cache.set_bytes(key, &cached.to_bytes(), ttl).await;
+
+            Ok(cached)
+        }
+    }
+
+    fn fetch_unless_missing<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        fetch_fn: F,
+    ) -> impl std::future::Future<Output = anyhow::Result<Option<T>>>
+    where
+        F: std::future::Future<Output = anyhow::Result<Option<T>>>,
+    {
+        async move {
+            if cache.is_known_missing(key).await {
+                return Ok(None);
+            }
+
+            let data = fetch_fn.await?;
+            if data.is_none() {
+                let _ = cache.remember_missing(key).await;
+            }
+
+            Ok(data)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -71,4 +141,49 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), test_data);
     }
+
+    #[tokio::test]
+    async fn test_cache_aware_get_or_fetch_json() {
+        let cache = Arc::new(
+            CacheManager::new(Default::default())
+                .await
+                .expect("Failed to create cache"),
+        );
+
+        let test_data = TestData {
+            value: "test".to_string(),
+        };
+
+        let cached =
+            <()>::get_or_fetch_json(&cache, "test:json", 60, async { Ok(test_data.clone()) })
+                .await
+                .unwrap();
+
+        assert_eq!(&cached.body[..], br#"{"value":"test"}"#);
+        assert_eq!(cached.decode::<TestData>().unwrap(), test_data);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_unless_missing_passes_lookups_through() {
+        let cache = Arc::new(
+            CacheManager::new(Default::default())
+                .await
+                .expect("Failed to create cache"),
+        );
+
+        let missing: Option<TestData> =
+            <()>::fetch_unless_missing(&cache, "test:missing", async { Ok(None) })
+                .await
+                .unwrap();
+        assert_eq!(missing, None);
+
+        let found = <()>::fetch_unless_missing(&cache, "test:found", async {
+            Ok(Some(TestData {
+                value: "test".to_string(),
+            }))
+        })
+        .await
+        .unwrap();
+        assert_eq!(found.unwrap().value, "test");
+    }
 }
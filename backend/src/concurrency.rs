@@ -0,0 +1,234 @@
+//! Concurrency caps for the Horizon-bound `/api/rpc/*` routes.
+//!
+//! Each of these handlers holds an outbound Horizon connection for as long as
+//! it runs, so a burst on one route (typically the orderbook) could use up
+//! the client's connections and stall every other route. [`limit_route`]
+//! caps the requests in flight on one route and answers the excess with
+//! `429 Too Many Requests`; [`limit_group`] caps a whole router and answers
+//! with `503 Service Unavailable`. Requests over a cap are shed at once
+//! rather than queued, and both responses carry a `Retry-After` header.
+
+use axum::{
+    error_handling::HandleErrorLayer,
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    routing::MethodRouter,
+    BoxError, Router,
+};
+use std::collections::HashMap;
+use tower::{limit::GlobalConcurrencyLimitLayer, load_shed::error::Overloaded, ServiceBuilder};
+
+use crate::config::RpcSettings;
+use crate::error::ApiError;
+use crate::observability::metrics as obs_metrics;
+
+/// Which cap a request was shed at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitScope {
+    Route,
+    Group,
+}
+
+impl LimitScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Route => "route",
+            Self::Group => "group",
+        }
+    }
+}
+
+/// Concurrency caps for the `/api/rpc/*` routes
+#[derive(Debug, Clone, Copy)]
+pub struct ConcurrencyCaps {
+    pub group: usize,
+    pub per_route: usize,
+    pub orderbook: usize,
+    pub retry_after_secs: u64,
+}
+
+impl From<&RpcSettings> for ConcurrencyCaps {
+    fn from(settings: &RpcSettings) -> Self {
+        Self {
+            group: settings.max_concurrent_requests,
+            per_route: settings.max_concurrent_per_route,
+            orderbook: settings.max_concurrent_orderbook,
+            retry_after_secs: settings.overload_retry_after_secs,
+        }
+    }
+}
+
+/// Response for a request shed at a cap of `max` requests in flight
+fn overloaded(scope: LimitScope, max: usize, retry_after_secs: u64) -> Response {
+    tracing::warn!(
+        scope = scope.as_str(),
+        max_concurrent = max,
+        "Shed request over its concurrency cap"
+    );
+    obs_metrics::record_error("concurrency_limit");
+
+    let error = match scope {
+        LimitScope::Route => ApiError::too_many_requests(
+            "ROUTE_BUSY",
+            "Too many concurrent requests to this endpoint, retry shortly",
+        ),
+        LimitScope::Group => ApiError::service_unavailable(
+            "UPSTREAM_BUSY",
+            "Too many concurrent requests to Horizon-backed endpoints, retry shortly",
+        ),
+    };
+    let details = HashMap::from([
+        ("scope".to_string(), serde_json::json!(scope.as_str())),
+        ("max_concurrent".to_string(), serde_json::json!(max)),
+        (
+            "retry_after_secs".to_string(),
+            serde_json::json!(retry_after_secs),
+        ),
+    ]);
+
+    let mut response = error.with_details(details).into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+    response
+}
+
+async fn handle_error(
+    err: BoxError,
+    scope: LimitScope,
+    max: usize,
+    retry_after_secs: u64,
+) -> Response {
+    if err.is::<Overloaded>() {
+        return overloaded(scope, max, retry_after_secs);
+    }
+    ApiError::internal("INTERNAL_ERROR", err.to_string()).into_response()
+}
+
+/// Allow at most `max` requests in flight on `route`, answering the rest
+/// with 429
+pub fn limit_route<S>(route: MethodRouter<S>, max: usize, retry_after_secs: u64) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    route.layer(
+        ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(move |err: BoxError| {
+                handle_error(err, LimitScope::Route, max, retry_after_secs)
+            }))
+            .load_shed()
+            .layer(GlobalConcurrencyLimitLayer::new(max)),
+    )
+}
+
+/// Allow at most `max` requests in flight across all of `router`'s routes,
+/// answering the rest with 503
+pub fn limit_group<S>(router: Router<S>, max: usize, retry_after_secs: u64) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // One semaphore, cloned into every route the layer wraps
+    router.layer(
+        ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(move |err: BoxError| {
+                handle_error(err, LimitScope::Group, max, retry_after_secs)
+            }))
+            .load_shed()
+            .layer(GlobalConcurrencyLimitLayer::new(max)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, http::StatusCode, routing::get};
+    use std::sync::Arc;
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    fn get_request(uri: &str) -> Request {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_requests_over_a_cap_are_shed_with_retry_hint() {
+        let release = Arc::new(Notify::new());
+        let blocked = {
+            let release = Arc::clone(&release);
+            get(move || async move {
+                release.notified().await;
+                "done"
+            })
+        };
+        let app = limit_group(
+            Router::new()
+                .route("/api/rpc/orderbook", limit_route(blocked, 1, 2))
+                .route("/api/rpc/trades", get(|| async { "trades" }))
+                .route("/api/rpc/payments", get(|| async { "payments" })),
+            2,
+            5,
+        );
+
+        // Holds the only orderbook slot and one of the two group slots
+        let in_flight = tokio::spawn(app.clone().oneshot(get_request("/api/rpc/orderbook")));
+        tokio::task::yield_now().await;
+
+        let busy_route = app
+            .clone()
+            .oneshot(get_request("/api/rpc/orderbook"))
+            .await
+            .unwrap();
+        assert_eq!(busy_route.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(busy_route.headers()[header::RETRY_AFTER], "2");
+        let body = axum::body::to_bytes(busy_route.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "ROUTE_BUSY");
+        assert_eq!(json["error"]["details"]["max_concurrent"], 1);
+
+        // Other routes still get the group's remaining slot
+        let trades = app
+            .clone()
+            .oneshot(get_request("/api/rpc/trades"))
+            .await
+            .unwrap();
+        assert_eq!(trades.status(), StatusCode::OK);
+
+        release.notify_one();
+        assert_eq!(in_flight.await.unwrap().unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_full_group_answers_503() {
+        let release = Arc::new(Notify::new());
+        let blocked = {
+            let release = Arc::clone(&release);
+            get(move || async move {
+                release.notified().await;
+                "done"
+            })
+        };
+        let app = limit_group(
+            Router::new()
+                .route("/api/rpc/orderbook", blocked)
+                .route("/api/rpc/trades", get(|| async { "trades" })),
+            1,
+            5,
+        );
+
+        let in_flight = tokio::spawn(app.clone().oneshot(get_request("/api/rpc/orderbook")));
+        tokio::task::yield_now().await;
+
+        let shed = app
+            .clone()
+            .oneshot(get_request("/api/rpc/trades"))
+            .await
+            .unwrap();
+        assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(shed.headers()[header::RETRY_AFTER], "5");
+
+        release.notify_one();
+        assert_eq!(in_flight.await.unwrap().unwrap().status(), StatusCode::OK);
+    }
+}
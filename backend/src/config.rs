@@ -0,0 +1,2123 @@
+//! Typed application settings, loaded once at startup.
+//!
+//! Values come from an optional TOML file (path in `CONFIG_FILE`) and are
+//! then overridden by environment variables, so existing `.env` setups keep
+//! working unchanged. [`Settings::load`] validates everything up front and
+//! fails with one error listing every problem, instead of components
+//! discovering bad values at runtime.
+//!
+//! ```toml
+//! [server]
+//! host = "0.0.0.0"
+//! port = 8080
+//!
+//! [sep24]
+//! allowed_origins = ["https://testanchor.stellar.org"]
+//! anchors = [{ name = "Test Anchor", transfer_server = "https://testanchor.stellar.org/sep24" }]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::Duration;
+
+use stellar_insights_contract_bindings::parse_address;
+
+use crate::auth::login_guard::LoginGuardConfig;
+use crate::billing::BillingExporterKind;
+use crate::crypto::FieldCipher;
+use crate::email::provider::EmailProviderKind;
+use crate::http_client::HttpClientConfig;
+use crate::ingestion::ledger;
+use crate::ml::anomaly::AnomalyModel;
+use crate::observability::error_sink::ErrorSinkConfig;
+use crate::rpc::circuit_breaker::CircuitBreakerConfig;
+use crate::rpc::RpcRateLimitConfig;
+use crate::screening::ScreeningProviderKind;
+use crate::services::asset_supply::SupplyTrackerConfig;
+use crate::services::contract_ttl::ContractTtlConfig;
+use crate::services::health_score::HealthWeights;
+use crate::services::network_events::NetworkEventConfig;
+use crate::services::snapshot::{SnapshotTtlConfig, MAX_KEEPALIVE_EPOCHS};
+use crate::signer::SignerBackend;
+use crate::webhooks::retention::{ArchiveMode, RetentionConfig};
+
+/// Environment variable naming the optional TOML settings file
+pub const CONFIG_FILE_VAR: &str = "CONFIG_FILE";
+
+// No Debug derives on sections holding secrets, so settings can't be logged by accident
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub server: ServerSettings,
+    pub deployment: DeploymentSettings,
+    pub timeouts: TimeoutSettings,
+    pub database: DatabaseSettings,
+    pub redis: RedisSettings,
+    pub security: SecuritySettings,
+    pub login: LoginSettings,
+    pub rpc: RpcSettings,
+    pub sep10: Sep10Settings,
+    pub sep24: SepProxySettings,
+    pub sep31: SepProxySettings,
+    pub sep_tracking: SepTrackingSettings,
+    pub websocket: WebSocketSettings,
+    pub integrations: IntegrationSettings,
+    pub signing: SigningSettings,
+    pub email: EmailSettings,
+    pub contract_ttl: ContractTtlSettings,
+    pub screening: ScreeningSettings,
+    pub billing: BillingSettings,
+    pub alerts: AlertSettings,
+    pub ml: MlSettings,
+    pub webhooks: WebhookSettings,
+    pub health_score: HealthScoreSettings,
+    pub ingestion: IngestionSettings,
+    pub http_client: HttpClientSettings,
+    pub error_sink: ErrorSinkSettings,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+    /// Responses smaller than this many bytes are sent uncompressed
+    pub compression_min_size: u16,
+    /// Address clients reach this API at, used in links sent out by email;
+    /// defaults to the bind address
+    pub public_url: Option<String>,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            compression_min_size: 1024,
+            public_url: None,
+        }
+    }
+}
+
+impl ServerSettings {
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    pub fn public_url(&self) -> String {
+        match &self.public_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!("http://{}", self.addr()),
+        }
+    }
+}
+
+/// How this instance is shared
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeploymentSettings {
+    /// Tenant-scoped routes require a tenant's API key and only see its rows
+    /// (see [`crate::tenant`])
+    pub multi_tenant: bool,
+}
+
+/// Request time budgets, in seconds, per class of route (see [`crate::timeout`])
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeoutSettings {
+    /// Reads answered from the cache
+    pub cached_read_secs: u64,
+    /// Endpoints that call Horizon, Soroban RPC or anchor servers
+    pub upstream_secs: u64,
+    /// Everything else
+    pub default_secs: u64,
+}
+
+impl Default for TimeoutSettings {
+    fn default() -> Self {
+        Self {
+            cached_read_secs: 2,
+            upstream_secs: 15,
+            default_secs: 10,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// How long a query waits for a free connection before failing
+    pub acquire_timeout_secs: u64,
+    pub idle_timeout_secs: u64,
+    pub max_lifetime_secs: u64,
+    /// Statements slower than this are tracked at `/api/admin/slow-queries`
+    pub slow_query_threshold_ms: u64,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        let pool = crate::database::PoolConfig::default();
+        Self {
+            url: "sqlite:./stellar_insights.db".to_string(),
+            max_connections: pool.max_connections,
+            min_connections: pool.min_connections,
+            acquire_timeout_secs: pool.connect_timeout_seconds,
+            idle_timeout_secs: pool.idle_timeout_seconds,
+            max_lifetime_secs: pool.max_lifetime_seconds,
+            slow_query_threshold_ms: pool.slow_query_threshold_ms,
+        }
+    }
+}
+
+impl DatabaseSettings {
+    pub fn pool_config(&self) -> crate::database::PoolConfig {
+        crate::database::PoolConfig {
+            max_connections: self.max_connections,
+            min_connections: self.min_connections,
+            connect_timeout_seconds: self.acquire_timeout_secs,
+            idle_timeout_seconds: self.idle_timeout_secs,
+            max_lifetime_seconds: self.max_lifetime_secs,
+            slow_query_threshold_ms: self.slow_query_threshold_ms,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedisSettings {
+    pub url: String,
+}
+
+impl Default for RedisSettings {
+    fn default() -> Self {
+        Self {
+            url: "redis://127.0.0.1:6379".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecuritySettings {
+    /// 64 hex characters (32 bytes) used for AES-256-GCM at rest; there is
+    /// no default, so startup fails until one is configured
+    pub encryption_key: String,
+    /// Retired encryption keys, still accepted for decryption until the
+    /// re-encryption job has rewritten every value
+    pub encryption_key_previous: Vec<String>,
+    /// `["*"]` allows every origin (development only)
+    pub cors_allowed_origins: Vec<String>,
+    /// Client IPs exempt from rate limiting on every endpoint
+    pub rate_limit_whitelist_ips: Vec<String>,
+}
+
+impl Default for SecuritySettings {
+    fn default() -> Self {
+        Self {
+            encryption_key: String::new(),
+            encryption_key_previous: Vec::new(),
+            cors_allowed_origins: vec![
+                "http://localhost:3000".to_string(),
+                "http://localhost:3001".to_string(),
+            ],
+            rate_limit_whitelist_ips: Vec::new(),
+        }
+    }
+}
+
+impl SecuritySettings {
+    pub fn allows_any_origin(&self) -> bool {
+        self.cors_allowed_origins.iter().any(|o| o == "*")
+    }
+
+    /// Cipher for PII columns, writing under the current key
+    pub fn field_cipher(&self) -> FieldCipher {
+        FieldCipher::new(&self.encryption_key, self.encryption_key_previous.clone())
+    }
+}
+
+/// Failed-login lockout (see [`crate::auth::login_guard`])
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoginSettings {
+    /// Failures within the window that lock the account
+    pub lockout_threshold: u64,
+    /// Failures from one client IP within the window that lock the IP
+    pub ip_lockout_threshold: u64,
+    pub failure_window_secs: u64,
+    pub lockout_secs: u64,
+}
+
+impl Default for LoginSettings {
+    fn default() -> Self {
+        let guard = LoginGuardConfig::default();
+        Self {
+            lockout_threshold: guard.account_threshold,
+            ip_lockout_threshold: guard.ip_threshold,
+            failure_window_secs: guard.failure_window_secs,
+            lockout_secs: guard.lockout_secs,
+        }
+    }
+}
+
+impl LoginSettings {
+    pub fn guard_config(&self) -> LoginGuardConfig {
+        LoginGuardConfig {
+            account_threshold: self.lockout_threshold,
+            ip_threshold: self.ip_lockout_threshold,
+            failure_window_secs: self.failure_window_secs,
+            lockout_secs: self.lockout_secs,
+            ..LoginGuardConfig::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcSettings {
+    pub mock_mode: bool,
+    /// Requests in flight across all `/api/rpc/*` routes (see [`crate::concurrency`])
+    pub max_concurrent_requests: usize,
+    /// Requests in flight on any one `/api/rpc/*` route
+    pub max_concurrent_per_route: usize,
+    /// Requests in flight on `/api/rpc/orderbook`, the most expensive route
+    pub max_concurrent_orderbook: usize,
+    /// `Retry-After` sent with requests shed over a cap
+    pub overload_retry_after_secs: u64,
+    /// Extra Horizon endpoints used alongside the network's primary one
+    pub horizon_urls: Vec<String>,
+    /// Outbound Horizon/RPC budget shared by every caller (see [`crate::rpc::rate_limiter`])
+    pub rate_limit_requests_per_minute: f64,
+    pub rate_limit_burst_size: f64,
+    pub rate_limit_queue_size: usize,
+    /// Fraction of the bucket standard requests leave for interactive ones
+    pub rate_limit_standard_reserve: f64,
+    /// Fraction of the bucket background requests leave for everyone else
+    pub rate_limit_background_reserve: f64,
+}
+
+impl Default for RpcSettings {
+    fn default() -> Self {
+        let limits = RpcRateLimitConfig::default();
+        Self {
+            mock_mode: false,
+            max_concurrent_requests: 64,
+            max_concurrent_per_route: 16,
+            max_concurrent_orderbook: 8,
+            overload_retry_after_secs: 1,
+            horizon_urls: Vec::new(),
+            rate_limit_requests_per_minute: limits.requests_per_minute,
+            rate_limit_burst_size: limits.burst_size,
+            rate_limit_queue_size: limits.queue_size,
+            rate_limit_standard_reserve: limits.standard_reserve,
+            rate_limit_background_reserve: limits.background_reserve,
+        }
+    }
+}
+
+impl RpcSettings {
+    pub fn rate_limit_config(&self) -> RpcRateLimitConfig {
+        RpcRateLimitConfig {
+            requests_per_minute: self.rate_limit_requests_per_minute,
+            burst_size: self.rate_limit_burst_size,
+            queue_size: self.rate_limit_queue_size,
+            standard_reserve: self.rate_limit_standard_reserve,
+            background_reserve: self.rate_limit_background_reserve,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Sep10Settings {
+    /// Server signing account; SEP-10 challenges can't be verified without it
+    pub server_public_key: Option<String>,
+    pub home_domain: String,
+}
+
+impl Default for Sep10Settings {
+    fn default() -> Self {
+        Self {
+            server_public_key: None,
+            home_domain: "stellar-insights.local".to_string(),
+        }
+    }
+}
+
+/// Settings for one of the SEP-24/SEP-31 proxies
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SepProxySettings {
+    /// Transfer server URL prefixes the proxy may call. Empty allows any
+    /// server (development only).
+    pub allowed_origins: Vec<String>,
+    /// Anchors listed by `GET /api/sep24/anchors` / `GET /api/sep31/anchors`
+    pub anchors: Vec<SepAnchor>,
+}
+
+impl SepProxySettings {
+    pub fn allows_transfer_server(&self, transfer_server: &str) -> bool {
+        if self.allowed_origins.is_empty() {
+            return true;
+        }
+        let url = transfer_server.trim().trim_end_matches('/');
+        self.allowed_origins
+            .iter()
+            .any(|o| o == "*" || url.starts_with(o.as_str()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SepAnchor {
+    pub name: String,
+    pub transfer_server: String,
+    #[serde(default)]
+    pub home_domain: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SepTrackingSettings {
+    /// How often open SEP-24/31 transactions are polled
+    pub poll_interval_secs: u64,
+    /// Host anchors sign callbacks for, when a proxy rewrites `Host`
+    pub callback_host: Option<String>,
+}
+
+impl Default for SepTrackingSettings {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 30,
+            callback_host: None,
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebSocketSettings {
+    /// Shared token for trusted clients; when set, connections must present
+    /// it or a user access token
+    pub auth_token: Option<String>,
+    /// Users who may subscribe to `admin.*` channels
+    pub admin_user_ids: Vec<String>,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IntegrationSettings {
+    pub slack_webhook_url: Option<String>,
+    pub telegram_bot_token: Option<String>,
+}
+
+/// Keys the backend signs with (see [`crate::signer`])
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SigningSettings {
+    /// Signs snapshot hashes (`SNAPSHOT_*`)
+    pub snapshot: SignerSettings,
+    /// Source account of contract submissions (`STELLAR_SOURCE_*`)
+    pub stellar_source: SignerSettings,
+    /// Mount of the Vault transit engine used by `vault` keys
+    pub vault_transit_mount: String,
+    /// Cloud KMS API used by `kms` keys
+    pub kms_endpoint: String,
+    /// Token for KMS requests; without it one is fetched from the GCE
+    /// metadata server
+    pub kms_access_token: Option<String>,
+}
+
+impl Default for SigningSettings {
+    fn default() -> Self {
+        Self {
+            snapshot: SignerSettings::default(),
+            stellar_source: SignerSettings::default(),
+            vault_transit_mount: "transit".to_string(),
+            kms_endpoint: "https://cloudkms.googleapis.com".to_string(),
+            kms_access_token: None,
+        }
+    }
+}
+
+/// Where one signing key is held
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignerSettings {
+    pub backend: SignerBackend,
+    /// Hex or S-address ed25519 seed, for the `local` backend; signing is
+    /// off while it is unset
+    pub seed: Option<String>,
+    /// Transit key name, for the `vault` backend
+    pub vault_key: Option<String>,
+    /// Key version resource name, for the `kms` backend
+    pub kms_key: Option<String>,
+}
+
+/// Outbound email (see [`crate::email`]); disabled while no provider is set
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmailSettings {
+    pub provider: Option<EmailProviderKind>,
+    /// Sender address; defaults to the SMTP user
+    pub from: Option<String>,
+    pub smtp_host: Option<String>,
+    pub smtp_user: Option<String>,
+    pub smtp_pass: Option<String>,
+    pub ses_region: String,
+    pub ses_smtp_user: Option<String>,
+    pub ses_smtp_pass: Option<String>,
+    pub sendgrid_api_key: Option<String>,
+}
+
+impl Default for EmailSettings {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            from: None,
+            smtp_host: None,
+            smtp_user: None,
+            smtp_pass: None,
+            ses_region: "us-east-1".to_string(),
+            ses_smtp_user: None,
+            ses_smtp_pass: None,
+            sendgrid_api_key: None,
+        }
+    }
+}
+
+impl EmailSettings {
+    /// The configured provider. An SMTP host alone keeps older SMTP-only
+    /// deployments working.
+    pub fn provider_kind(&self) -> Option<EmailProviderKind> {
+        self.provider
+            .or_else(|| self.smtp_host.as_ref().map(|_| EmailProviderKind::Smtp))
+    }
+
+    /// Address emails are sent from
+    pub fn sender(&self) -> Option<&str> {
+        self.from.as_deref().or(self.smtp_user.as_deref())
+    }
+}
+
+/// TTL watch of deployed contracts (see [`crate::services::contract_ttl`])
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContractTtlSettings {
+    /// Soroban RPC endpoint the entries are read from
+    pub rpc_url: String,
+    /// Watched contract IDs (`C...`); the snapshot contract is always included
+    pub contract_ids: Vec<String>,
+    /// Entries with at most this many ledgers left are reported
+    pub warn_ledgers: u32,
+    /// Extend expiring entries with the contract source account
+    pub auto_extend: bool,
+    pub extend_to_ledgers: u32,
+    /// Newest on-chain snapshots the keep-alive job extends
+    pub snapshot_keepalive_epochs: u32,
+    /// Ledgers each of them is kept alive for
+    pub snapshot_keepalive_ledgers: u32,
+}
+
+impl Default for ContractTtlSettings {
+    fn default() -> Self {
+        let watcher = ContractTtlConfig::default();
+        let keepalive = SnapshotTtlConfig::default();
+        Self {
+            rpc_url: watcher.rpc_url,
+            contract_ids: watcher.contract_ids,
+            warn_ledgers: watcher.warn_ledgers,
+            auto_extend: false,
+            // About 30 days
+            extend_to_ledgers: 518_400,
+            snapshot_keepalive_epochs: keepalive.epochs,
+            snapshot_keepalive_ledgers: keepalive.ledgers,
+        }
+    }
+}
+
+impl ContractTtlSettings {
+    pub fn watcher_config(&self) -> ContractTtlConfig {
+        ContractTtlConfig {
+            rpc_url: self.rpc_url.clone(),
+            contract_ids: self.contract_ids.clone(),
+            warn_ledgers: self.warn_ledgers,
+            auto_extend_to: self.auto_extend.then_some(self.extend_to_ledgers),
+        }
+    }
+
+    pub fn snapshot_keepalive_config(&self) -> SnapshotTtlConfig {
+        SnapshotTtlConfig {
+            epochs: self.snapshot_keepalive_epochs,
+            ledgers: self.snapshot_keepalive_ledgers,
+        }
+    }
+}
+
+/// Counterparty screening (see [`crate::screening`])
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScreeningSettings {
+    pub provider: ScreeningProviderKind,
+    /// Screening API, for the `http` provider
+    pub url: Option<String>,
+    pub api_key: Option<String>,
+    /// How long an answer is reused
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for ScreeningSettings {
+    fn default() -> Self {
+        Self {
+            provider: ScreeningProviderKind::None,
+            url: None,
+            api_key: None,
+            cache_ttl_secs: 86_400,
+        }
+    }
+}
+
+/// Metered billing export (see [`crate::billing`])
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BillingSettings {
+    pub exporter: BillingExporterKind,
+    /// Secret (`sk_...`) or restricted (`rk_...`) key, for the `stripe` exporter
+    pub stripe_api_key: Option<String>,
+}
+
+/// Thresholds at which background monitors raise alerts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertSettings {
+    /// Change (percent) in an anchor asset's supply between hourly snapshots
+    /// reported as a mint or burn
+    pub supply_change_pct: f64,
+    /// Median fee per operation, as a multiple of the base fee, that raises
+    /// `network.fee_spike`
+    pub fee_spike_multiplier: f64,
+    /// Seconds between two ledger closes that raise `ledger.close_delayed`
+    pub ledger_close_delay_secs: f64,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        let network = NetworkEventConfig::default();
+        Self {
+            supply_change_pct: SupplyTrackerConfig::default().alert_threshold_pct,
+            fee_spike_multiplier: network.fee_spike_multiplier,
+            ledger_close_delay_secs: network.close_delay_secs,
+        }
+    }
+}
+
+impl AlertSettings {
+    pub fn supply_tracker_config(&self) -> SupplyTrackerConfig {
+        SupplyTrackerConfig {
+            alert_threshold_pct: self.supply_change_pct,
+        }
+    }
+
+    pub fn network_event_config(&self) -> NetworkEventConfig {
+        NetworkEventConfig {
+            fee_spike_multiplier: self.fee_spike_multiplier,
+            close_delay_secs: self.ledger_close_delay_secs,
+        }
+    }
+}
+
+/// Corridor anomaly detection (see [`crate::ml::anomaly`])
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MlSettings {
+    pub anomaly_model: AnomalyModel,
+}
+
+/// Webhook event retention (see [`crate::webhooks::retention`])
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookSettings {
+    /// Finished events older than this many days are folded into daily summaries
+    pub event_retention_days: i64,
+    /// Whether summarized events are deleted or kept without their payload
+    pub event_archive_mode: ArchiveMode,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        let retention = RetentionConfig::default();
+        Self {
+            event_retention_days: retention.retention_days,
+            event_archive_mode: retention.mode,
+        }
+    }
+}
+
+impl WebhookSettings {
+    pub fn retention_config(&self) -> RetentionConfig {
+        RetentionConfig {
+            retention_days: self.event_retention_days,
+            mode: self.event_archive_mode,
+        }
+    }
+}
+
+/// Corridor health score weights (see [`crate::services::health_score`])
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthScoreSettings {
+    pub weight_success_rate: f64,
+    pub weight_liquidity: f64,
+    pub weight_volume_trend: f64,
+    pub weight_anchor_reliability: f64,
+}
+
+impl Default for HealthScoreSettings {
+    fn default() -> Self {
+        let weights = HealthWeights::default();
+        Self {
+            weight_success_rate: weights.success_rate,
+            weight_liquidity: weights.liquidity,
+            weight_volume_trend: weights.volume_trend,
+            weight_anchor_reliability: weights.anchor_reliability,
+        }
+    }
+}
+
+impl HealthScoreSettings {
+    pub fn weights(&self) -> HealthWeights {
+        HealthWeights {
+            success_rate: self.weight_success_rate,
+            liquidity: self.weight_liquidity,
+            volume_trend: self.weight_volume_trend,
+            anchor_reliability: self.weight_anchor_reliability,
+        }
+    }
+}
+
+/// Ledger ingestion (see [`crate::ingestion::ledger`])
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IngestionSettings {
+    /// Ledgers fetched per run of the ingestion loop
+    pub ledger_batch_size: u32,
+}
+
+impl Default for IngestionSettings {
+    fn default() -> Self {
+        Self {
+            ledger_batch_size: ledger::DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+/// Shared outbound HTTP clients (see [`crate::http_client`])
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpClientSettings {
+    pub user_agent: String,
+    pub connect_timeout_secs: u64,
+    pub pool_idle_timeout_secs: u64,
+    pub pool_max_idle_per_host: usize,
+    /// Proxy for all outbound requests; when unset the standard
+    /// `HTTPS_PROXY`/`HTTP_PROXY` variables still apply
+    pub proxy: Option<String>,
+    /// PEM file of extra root certificates to trust
+    pub ca_bundle: Option<String>,
+    /// Consecutive failures after which a host's circuit breaker opens
+    pub circuit_breaker_failure_threshold: u32,
+    /// How long an open breaker fails requests before letting one through
+    pub circuit_breaker_timeout_secs: u64,
+}
+
+impl Default for HttpClientSettings {
+    fn default() -> Self {
+        let client = HttpClientConfig::default();
+        Self {
+            user_agent: client.user_agent,
+            connect_timeout_secs: client.connect_timeout.as_secs(),
+            pool_idle_timeout_secs: client.pool_idle_timeout.as_secs(),
+            pool_max_idle_per_host: client.pool_max_idle_per_host,
+            proxy: client.proxy,
+            ca_bundle: client.ca_bundle,
+            circuit_breaker_failure_threshold: client.circuit_breaker.failure_threshold,
+            circuit_breaker_timeout_secs: client.circuit_breaker.timeout_duration.as_secs(),
+        }
+    }
+}
+
+impl HttpClientSettings {
+    pub fn client_config(&self) -> HttpClientConfig {
+        let defaults = HttpClientConfig::default();
+        HttpClientConfig {
+            user_agent: self.user_agent.clone(),
+            connect_timeout: Duration::from_secs(self.connect_timeout_secs),
+            pool_idle_timeout: Duration::from_secs(self.pool_idle_timeout_secs),
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            proxy: self.proxy.clone(),
+            ca_bundle: self.ca_bundle.clone(),
+            circuit_breaker: CircuitBreakerConfig {
+                failure_threshold: self.circuit_breaker_failure_threshold,
+                timeout_duration: Duration::from_secs(self.circuit_breaker_timeout_secs),
+                ..defaults.circuit_breaker
+            },
+        }
+    }
+}
+
+/// Error reporting (see [`crate::observability::error_sink`])
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ErrorSinkSettings {
+    /// Sentry DSN or any URL accepting JSON POSTs; unset disables reporting
+    pub dsn: Option<String>,
+    /// Deployment name attached to every event
+    pub environment: Option<String>,
+}
+
+impl ErrorSinkSettings {
+    pub fn sink_config(&self) -> ErrorSinkConfig {
+        ErrorSinkConfig {
+            dsn: self.dsn.clone(),
+            environment: self.environment.clone(),
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings from `CONFIG_FILE` (if set) and the environment, then validate
+    pub fn load() -> Result<Self> {
+        let mut settings = match std::env::var(CONFIG_FILE_VAR) {
+            Ok(path) => Self::from_file(Path::new(&path))?,
+            Err(_) => Self::default(),
+        };
+
+        let mut errors = settings.apply_env(|name| std::env::var(name).ok());
+        errors.extend(settings.validate());
+        if !errors.is_empty() {
+            anyhow::bail!("Configuration errors:\n  - {}", errors.join("\n  - "));
+        }
+
+        Ok(settings)
+    }
+
+    fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Override fields from environment variables, returning any values that
+    /// fail to parse
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        fn parsed<T: std::str::FromStr>(
+            var: &impl Fn(&str) -> Option<String>,
+            name: &str,
+            errors: &mut Vec<String>,
+        ) -> Option<T> {
+            let value = var(name)?;
+            match value.trim().parse() {
+                Ok(v) => Some(v),
+                Err(_) => {
+                    errors.push(format!("{} has an invalid value: '{}'", name, value));
+                    None
+                }
+            }
+        }
+
+        fn anchors(
+            var: &impl Fn(&str) -> Option<String>,
+            name: &str,
+            errors: &mut Vec<String>,
+        ) -> Option<Vec<SepAnchor>> {
+            let value = var(name)?;
+            serde_json::from_str(&value)
+                .map_err(|e| errors.push(format!("{} is not a valid anchor list: {}", name, e)))
+                .ok()
+        }
+
+        if let Some(host) = var("SERVER_HOST") {
+            self.server.host = host;
+        }
+        if let Some(port) = parsed(&var, "SERVER_PORT", &mut errors) {
+            self.server.port = port;
+        }
+        if let Some(size) = parsed(&var, "COMPRESSION_MIN_SIZE", &mut errors) {
+            self.server.compression_min_size = size;
+        }
+        if let Some(url) = var("PUBLIC_API_URL") {
+            self.server.public_url = Some(url);
+        }
+        if let Some(secs) = parsed(&var, "REQUEST_TIMEOUT_CACHED_READ_SECS", &mut errors) {
+            self.timeouts.cached_read_secs = secs;
+        }
+        if let Some(secs) = parsed(&var, "REQUEST_TIMEOUT_UPSTREAM_SECS", &mut errors) {
+            self.timeouts.upstream_secs = secs;
+        }
+        if let Some(secs) = parsed(&var, "REQUEST_TIMEOUT_DEFAULT_SECS", &mut errors) {
+            self.timeouts.default_secs = secs;
+        }
+        if let Some(url) = var("DATABASE_URL") {
+            self.database.url = url;
+        }
+        if let Some(n) = parsed(&var, "DB_POOL_MAX_CONNECTIONS", &mut errors) {
+            self.database.max_connections = n;
+        }
+        if let Some(n) = parsed(&var, "DB_POOL_MIN_CONNECTIONS", &mut errors) {
+            self.database.min_connections = n;
+        }
+        // DB_POOL_CONNECT_TIMEOUT_SECONDS is the older name for the acquire timeout
+        let acquire_timeout = parsed(&var, "DB_POOL_ACQUIRE_TIMEOUT_SECONDS", &mut errors)
+            .or_else(|| parsed(&var, "DB_POOL_CONNECT_TIMEOUT_SECONDS", &mut errors));
+        if let Some(secs) = acquire_timeout {
+            self.database.acquire_timeout_secs = secs;
+        }
+        if let Some(secs) = parsed(&var, "DB_POOL_IDLE_TIMEOUT_SECONDS", &mut errors) {
+            self.database.idle_timeout_secs = secs;
+        }
+        if let Some(secs) = parsed(&var, "DB_POOL_MAX_LIFETIME_SECONDS", &mut errors) {
+            self.database.max_lifetime_secs = secs;
+        }
+        if let Some(ms) = parsed(&var, "DB_SLOW_QUERY_THRESHOLD_MS", &mut errors) {
+            self.database.slow_query_threshold_ms = ms;
+        }
+        if let Some(url) = var("REDIS_URL") {
+            self.redis.url = url;
+        }
+        if let Some(key) = var("ENCRYPTION_KEY") {
+            self.security.encryption_key = key;
+        }
+        if let Some(keys) = var("ENCRYPTION_KEY_PREVIOUS") {
+            self.security.encryption_key_previous = split_list(&keys);
+        }
+        if let Some(origins) = var("CORS_ALLOWED_ORIGINS") {
+            self.security.cors_allowed_origins = split_list(&origins);
+        }
+        if let Some(ips) = var("RATE_LIMIT_WHITELIST_IPS") {
+            self.security.rate_limit_whitelist_ips = split_list(&ips);
+        }
+        if let Some(threshold) = parsed(&var, "LOGIN_LOCKOUT_THRESHOLD", &mut errors) {
+            self.login.lockout_threshold = threshold;
+        }
+        if let Some(threshold) = parsed(&var, "LOGIN_IP_LOCKOUT_THRESHOLD", &mut errors) {
+            self.login.ip_lockout_threshold = threshold;
+        }
+        if let Some(secs) = parsed(&var, "LOGIN_FAILURE_WINDOW_SECONDS", &mut errors) {
+            self.login.failure_window_secs = secs;
+        }
+        if let Some(secs) = parsed(&var, "LOGIN_LOCKOUT_SECONDS", &mut errors) {
+            self.login.lockout_secs = secs;
+        }
+        if let Some(multi_tenant) = parsed(&var, "MULTI_TENANT_MODE", &mut errors) {
+            self.deployment.multi_tenant = multi_tenant;
+        }
+        if let Some(mock) = parsed(&var, "RPC_MOCK_MODE", &mut errors) {
+            self.rpc.mock_mode = mock;
+        }
+        if let Some(n) = parsed(&var, "RPC_MAX_CONCURRENT_REQUESTS", &mut errors) {
+            self.rpc.max_concurrent_requests = n;
+        }
+        if let Some(n) = parsed(&var, "RPC_MAX_CONCURRENT_PER_ROUTE", &mut errors) {
+            self.rpc.max_concurrent_per_route = n;
+        }
+        if let Some(n) = parsed(&var, "RPC_MAX_CONCURRENT_ORDERBOOK", &mut errors) {
+            self.rpc.max_concurrent_orderbook = n;
+        }
+        if let Some(secs) = parsed(&var, "RPC_OVERLOAD_RETRY_AFTER_SECS", &mut errors) {
+            self.rpc.overload_retry_after_secs = secs;
+        }
+        if let Some(urls) = var("STELLAR_HORIZON_URLS") {
+            self.rpc.horizon_urls = split_list(&urls);
+        }
+        if let Some(n) = parsed(&var, "RPC_RATE_LIMIT_REQUESTS_PER_MINUTE", &mut errors) {
+            self.rpc.rate_limit_requests_per_minute = n;
+        }
+        if let Some(n) = parsed(&var, "RPC_RATE_LIMIT_BURST_SIZE", &mut errors) {
+            self.rpc.rate_limit_burst_size = n;
+        }
+        if let Some(n) = parsed(&var, "RPC_RATE_LIMIT_QUEUE_SIZE", &mut errors) {
+            self.rpc.rate_limit_queue_size = n;
+        }
+        if let Some(share) = parsed(&var, "RPC_RATE_LIMIT_STANDARD_RESERVE", &mut errors) {
+            self.rpc.rate_limit_standard_reserve = share;
+        }
+        if let Some(share) = parsed(&var, "RPC_RATE_LIMIT_BACKGROUND_RESERVE", &mut errors) {
+            self.rpc.rate_limit_background_reserve = share;
+        }
+        if let Some(key) = var("SEP10_SERVER_PUBLIC_KEY") {
+            self.sep10.server_public_key = Some(key);
+        }
+        if let Some(domain) = var("SEP10_HOME_DOMAIN") {
+            self.sep10.home_domain = domain;
+        }
+        if let Some(origins) = var("SEP24_ALLOWED_ORIGINS") {
+            self.sep24.allowed_origins = split_list(&origins);
+        }
+        if let Some(list) = anchors(&var, "SEP24_ANCHORS", &mut errors) {
+            self.sep24.anchors = list;
+        }
+        if let Some(origins) = var("SEP31_ALLOWED_ORIGINS") {
+            self.sep31.allowed_origins = split_list(&origins);
+        }
+        if let Some(list) = anchors(&var, "SEP31_ANCHORS", &mut errors) {
+            self.sep31.anchors = list;
+        }
+        if let Some(secs) = parsed(&var, "SEP_POLL_INTERVAL_SECONDS", &mut errors) {
+            self.sep_tracking.poll_interval_secs = secs;
+        }
+        if let Some(host) = var("ANCHOR_CALLBACK_HOST") {
+            self.sep_tracking.callback_host = Some(host);
+        }
+        if let Some(token) = var("WS_AUTH_TOKEN") {
+            self.websocket.auth_token = Some(token);
+        }
+        if let Some(ids) = var("WS_ADMIN_USER_IDS") {
+            self.websocket.admin_user_ids = split_list(&ids);
+        }
+        if let Some(url) = var("SLACK_WEBHOOK_URL") {
+            self.integrations.slack_webhook_url = Some(url);
+        }
+        if let Some(token) = var("TELEGRAM_BOT_TOKEN") {
+            self.integrations.telegram_bot_token = Some(token);
+        }
+        for (prefix, seed_var, key) in [
+            (
+                "SNAPSHOT",
+                "SNAPSHOT_SIGNING_KEY",
+                &mut self.signing.snapshot,
+            ),
+            (
+                "STELLAR_SOURCE",
+                "STELLAR_SOURCE_SECRET_KEY",
+                &mut self.signing.stellar_source,
+            ),
+        ] {
+            if let Some(backend) = parsed(&var, &format!("{}_SIGNER", prefix), &mut errors) {
+                key.backend = backend;
+            }
+            if let Some(seed) = var(seed_var) {
+                key.seed = Some(seed);
+            }
+            if let Some(name) = var(&format!("{}_VAULT_KEY", prefix)) {
+                key.vault_key = Some(name);
+            }
+            if let Some(name) = var(&format!("{}_KMS_KEY", prefix)) {
+                key.kms_key = Some(name);
+            }
+        }
+        if let Some(mount) = var("VAULT_TRANSIT_MOUNT") {
+            self.signing.vault_transit_mount = mount;
+        }
+        if let Some(endpoint) = var("KMS_ENDPOINT") {
+            self.signing.kms_endpoint = endpoint;
+        }
+        if let Some(token) = var("KMS_ACCESS_TOKEN") {
+            self.signing.kms_access_token = Some(token);
+        }
+        if let Some(provider) = parsed(&var, "EMAIL_PROVIDER", &mut errors) {
+            self.email.provider = Some(provider);
+        }
+        for (name, field) in [
+            ("EMAIL_FROM", &mut self.email.from),
+            ("SMTP_HOST", &mut self.email.smtp_host),
+            ("SMTP_USER", &mut self.email.smtp_user),
+            ("SMTP_PASS", &mut self.email.smtp_pass),
+            ("SES_SMTP_USER", &mut self.email.ses_smtp_user),
+            ("SES_SMTP_PASS", &mut self.email.ses_smtp_pass),
+            ("SENDGRID_API_KEY", &mut self.email.sendgrid_api_key),
+        ] {
+            if let Some(value) = var(name) {
+                *field = Some(value);
+            }
+        }
+        if let Some(region) = var("AWS_REGION") {
+            self.email.ses_region = region;
+        }
+        if let Some(url) = var("SOROBAN_RPC_URL") {
+            self.contract_ttl.rpc_url = url;
+        }
+        let watched = var("SNAPSHOT_CONTRACT_ID")
+            .into_iter()
+            .chain(var("CONTRACT_TTL_WATCH_IDS"))
+            .flat_map(|ids| split_list(&ids));
+        for id in watched {
+            if !self.contract_ttl.contract_ids.contains(&id) {
+                self.contract_ttl.contract_ids.push(id);
+            }
+        }
+        if let Some(ledgers) = parsed(&var, "CONTRACT_TTL_WARN_LEDGERS", &mut errors) {
+            self.contract_ttl.warn_ledgers = ledgers;
+        }
+        if let Some(enabled) = parsed(&var, "CONTRACT_TTL_AUTO_EXTEND", &mut errors) {
+            self.contract_ttl.auto_extend = enabled;
+        }
+        if let Some(ledgers) = parsed(&var, "CONTRACT_TTL_EXTEND_TO_LEDGERS", &mut errors) {
+            self.contract_ttl.extend_to_ledgers = ledgers;
+        }
+        if let Some(epochs) = parsed(&var, "SNAPSHOT_TTL_KEEPALIVE_EPOCHS", &mut errors) {
+            self.contract_ttl.snapshot_keepalive_epochs = epochs;
+        }
+        if let Some(ledgers) = parsed(&var, "SNAPSHOT_TTL_KEEPALIVE_LEDGERS", &mut errors) {
+            self.contract_ttl.snapshot_keepalive_ledgers = ledgers;
+        }
+        if let Some(provider) = parsed(&var, "SCREENING_PROVIDER", &mut errors) {
+            self.screening.provider = provider;
+        }
+        if let Some(url) = var("SCREENING_URL") {
+            self.screening.url = Some(url);
+        }
+        if let Some(key) = var("SCREENING_API_KEY") {
+            self.screening.api_key = Some(key);
+        }
+        if let Some(ttl) = parsed(&var, "SCREENING_CACHE_TTL_SECONDS", &mut errors) {
+            self.screening.cache_ttl_secs = ttl;
+        }
+        if let Some(exporter) = parsed(&var, "BILLING_EXPORTER", &mut errors) {
+            self.billing.exporter = exporter;
+        }
+        if let Some(key) = var("STRIPE_API_KEY") {
+            self.billing.stripe_api_key = Some(key);
+        }
+        if let Some(pct) = parsed(&var, "ASSET_SUPPLY_ALERT_THRESHOLD_PCT", &mut errors) {
+            self.alerts.supply_change_pct = pct;
+        }
+        if let Some(multiplier) = parsed(&var, "WEBHOOK_FEE_SPIKE_MULTIPLIER", &mut errors) {
+            self.alerts.fee_spike_multiplier = multiplier;
+        }
+        if let Some(secs) = parsed(&var, "WEBHOOK_LEDGER_CLOSE_DELAY_SECONDS", &mut errors) {
+            self.alerts.ledger_close_delay_secs = secs;
+        }
+        if let Some(model) = parsed(&var, "ML_ANOMALY_MODEL", &mut errors) {
+            self.ml.anomaly_model = model;
+        }
+        if let Some(days) = parsed(&var, "WEBHOOK_EVENT_RETENTION_DAYS", &mut errors) {
+            self.webhooks.event_retention_days = days;
+        }
+        if let Some(mode) = parsed(&var, "WEBHOOK_EVENT_ARCHIVE_MODE", &mut errors) {
+            self.webhooks.event_archive_mode = mode;
+        }
+        for (name, field) in [
+            (
+                "HEALTH_SCORE_WEIGHT_SUCCESS_RATE",
+                &mut self.health_score.weight_success_rate,
+            ),
+            (
+                "HEALTH_SCORE_WEIGHT_LIQUIDITY",
+                &mut self.health_score.weight_liquidity,
+            ),
+            (
+                "HEALTH_SCORE_WEIGHT_VOLUME_TREND",
+                &mut self.health_score.weight_volume_trend,
+            ),
+            (
+                "HEALTH_SCORE_WEIGHT_ANCHOR_RELIABILITY",
+                &mut self.health_score.weight_anchor_reliability,
+            ),
+        ] {
+            if let Some(weight) = parsed(&var, name, &mut errors) {
+                *field = weight;
+            }
+        }
+        if let Some(size) = parsed(&var, "LEDGER_INGESTION_BATCH_SIZE", &mut errors) {
+            self.ingestion.ledger_batch_size = size;
+        }
+        if let Some(agent) = var("HTTP_CLIENT_USER_AGENT") {
+            self.http_client.user_agent = agent;
+        }
+        if let Some(secs) = parsed(&var, "HTTP_CLIENT_CONNECT_TIMEOUT_SECS", &mut errors) {
+            self.http_client.connect_timeout_secs = secs;
+        }
+        if let Some(secs) = parsed(&var, "HTTP_CLIENT_POOL_IDLE_TIMEOUT_SECS", &mut errors) {
+            self.http_client.pool_idle_timeout_secs = secs;
+        }
+        if let Some(n) = parsed(&var, "HTTP_CLIENT_POOL_MAX_IDLE_PER_HOST", &mut errors) {
+            self.http_client.pool_max_idle_per_host = n;
+        }
+        if let Some(proxy) = var("HTTP_CLIENT_PROXY").filter(|v| !v.trim().is_empty()) {
+            self.http_client.proxy = Some(proxy);
+        }
+        if let Some(path) = var("HTTP_CLIENT_CA_BUNDLE").filter(|v| !v.trim().is_empty()) {
+            self.http_client.ca_bundle = Some(path);
+        }
+        if let Some(n) = parsed(
+            &var,
+            "HTTP_CLIENT_CIRCUIT_BREAKER_FAILURE_THRESHOLD",
+            &mut errors,
+        ) {
+            self.http_client.circuit_breaker_failure_threshold = n;
+        }
+        if let Some(secs) = parsed(
+            &var,
+            "HTTP_CLIENT_CIRCUIT_BREAKER_TIMEOUT_SECONDS",
+            &mut errors,
+        ) {
+            self.http_client.circuit_breaker_timeout_secs = secs;
+        }
+        if let Some(dsn) = var("ERROR_SINK_DSN").filter(|v| !v.trim().is_empty()) {
+            self.error_sink.dsn = Some(dsn);
+        }
+        if let Some(environment) = var("ERROR_SINK_ENVIRONMENT") {
+            self.error_sink.environment = Some(environment);
+        }
+
+        errors
+    }
+
+    /// Every problem with the loaded values, as human-readable messages
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if self.server.host.trim().is_empty() {
+            errors.push("server.host (SERVER_HOST) must not be empty".to_string());
+        }
+        if self.server.port == 0 {
+            errors.push("server.port (SERVER_PORT) must be between 1 and 65535".to_string());
+        }
+        if let Some(url) = &self.server.public_url {
+            if !is_http_url(url) {
+                errors.push("server.public_url (PUBLIC_API_URL) is not an http(s) URL".to_string());
+            }
+        }
+        let t = &self.timeouts;
+        if t.cached_read_secs == 0 || t.upstream_secs == 0 || t.default_secs == 0 {
+            errors.push("timeouts (REQUEST_TIMEOUT_*_SECS) must all be positive".to_string());
+        }
+        if self.database.url.trim().is_empty() {
+            errors.push("database.url (DATABASE_URL) must not be empty".to_string());
+        }
+        let db = &self.database;
+        if db.max_connections == 0 {
+            errors.push("database.max_connections (DB_POOL_MAX_CONNECTIONS) must be positive".to_string());
+        }
+        if db.min_connections > db.max_connections {
+            errors.push(format!(
+                "database.min_connections ({}) must not exceed max_connections ({})",
+                db.min_connections, db.max_connections
+            ));
+        }
+        if db.acquire_timeout_secs == 0 {
+            errors.push(
+                "database.acquire_timeout_secs (DB_POOL_ACQUIRE_TIMEOUT_SECONDS) must be positive"
+                    .to_string(),
+            );
+        }
+        let rpc = &self.rpc;
+        if rpc.max_concurrent_requests == 0
+            || rpc.max_concurrent_per_route == 0
+            || rpc.max_concurrent_orderbook == 0
+        {
+            errors.push(
+                "rpc concurrency caps (RPC_MAX_CONCURRENT_*) must all be positive".to_string(),
+            );
+        }
+        for url in &rpc.horizon_urls {
+            if !is_http_url(url) {
+                errors.push(format!(
+                    "rpc.horizon_urls (STELLAR_HORIZON_URLS) entry '{}' is not an http(s) URL",
+                    url
+                ));
+            }
+        }
+        let positive = |n: f64| n.is_finite() && n > 0.0;
+        if !positive(rpc.rate_limit_requests_per_minute)
+            || !positive(rpc.rate_limit_burst_size)
+            || rpc.rate_limit_queue_size == 0
+        {
+            errors.push(
+                "rpc rate limit (RPC_RATE_LIMIT_REQUESTS_PER_MINUTE, _BURST_SIZE, _QUEUE_SIZE) \
+                 must all be positive"
+                    .to_string(),
+            );
+        }
+        let share = |n: f64| (0.0..1.0).contains(&n);
+        if !share(rpc.rate_limit_standard_reserve) || !share(rpc.rate_limit_background_reserve) {
+            errors.push(
+                "rpc rate limit reserves (RPC_RATE_LIMIT_*_RESERVE) must be at least 0 and below 1"
+                    .to_string(),
+            );
+        } else if rpc.rate_limit_background_reserve < rpc.rate_limit_standard_reserve {
+            errors.push(format!(
+                "rpc.rate_limit_background_reserve ({}) must not be below rate_limit_standard_reserve ({})",
+                rpc.rate_limit_background_reserve, rpc.rate_limit_standard_reserve
+            ));
+        }
+        if !self.redis.url.starts_with("redis://") && !self.redis.url.starts_with("rediss://") {
+            errors.push("redis.url (REDIS_URL) must start with redis:// or rediss://".to_string());
+        }
+
+        let is_key = |key: &str| key.len() == 64 && key.chars().all(|c| c.is_ascii_hexdigit());
+        if self.security.encryption_key.is_empty() {
+            errors.push("security.encryption_key (ENCRYPTION_KEY) must be set".to_string());
+        } else if !is_key(&self.security.encryption_key) {
+            errors.push(
+                "security.encryption_key (ENCRYPTION_KEY) must be 64 hex characters".to_string(),
+            );
+        }
+        let previous = &self.security.encryption_key_previous;
+        if !previous.iter().all(|key| is_key(key)) {
+            errors.push(
+                "security.encryption_key_previous (ENCRYPTION_KEY_PREVIOUS) entries must be 64 \
+                 hex characters"
+                    .to_string(),
+            );
+        }
+
+        if self.security.cors_allowed_origins.is_empty() {
+            errors.push(
+                "security.cors_allowed_origins (CORS_ALLOWED_ORIGINS) must list at least one \
+                 origin, or '*' for development"
+                    .to_string(),
+            );
+        }
+        for origin in &self.security.cors_allowed_origins {
+            if origin != "*" && !is_http_url(origin) {
+                errors.push(format!("CORS origin '{}' is not an http(s) URL", origin));
+            }
+        }
+        for ip in &self.security.rate_limit_whitelist_ips {
+            if ip != "*" && ip.parse::<IpAddr>().is_err() {
+                errors.push(format!("Rate limit whitelist entry '{}' is not an IP address", ip));
+            }
+        }
+
+        let login = &self.login;
+        for (value, field, name) in [
+            (
+                login.lockout_threshold,
+                "lockout_threshold",
+                "LOGIN_LOCKOUT_THRESHOLD",
+            ),
+            (
+                login.ip_lockout_threshold,
+                "ip_lockout_threshold",
+                "LOGIN_IP_LOCKOUT_THRESHOLD",
+            ),
+            (
+                login.failure_window_secs,
+                "failure_window_secs",
+                "LOGIN_FAILURE_WINDOW_SECONDS",
+            ),
+            (login.lockout_secs, "lockout_secs", "LOGIN_LOCKOUT_SECONDS"),
+        ] {
+            if value == 0 {
+                errors.push(format!("login.{} ({}) must be positive", field, name));
+            }
+        }
+        if login.ip_lockout_threshold < login.lockout_threshold {
+            errors.push(
+                "login.ip_lockout_threshold (LOGIN_IP_LOCKOUT_THRESHOLD) must not be below \
+                 login.lockout_threshold (LOGIN_LOCKOUT_THRESHOLD), since users share IPs"
+                    .to_string(),
+            );
+        }
+
+        if let Some(key) = &self.sep10.server_public_key {
+            if crate::muxed::decode_account_id(key).is_none() {
+                errors.push(
+                    "sep10.server_public_key (SEP10_SERVER_PUBLIC_KEY) must be a Stellar \
+                     public key (G...)"
+                        .to_string(),
+                );
+            }
+        }
+
+        for (label, proxy) in [("sep24", &self.sep24), ("sep31", &self.sep31)] {
+            for origin in &proxy.allowed_origins {
+                if origin != "*" && !is_http_url(origin) {
+                    errors.push(format!(
+                        "{}.allowed_origins entry '{}' is not an http(s) URL",
+                        label, origin
+                    ));
+                }
+            }
+            for anchor in &proxy.anchors {
+                if !is_http_url(&anchor.transfer_server) {
+                    errors.push(format!(
+                        "{} anchor '{}' has an invalid transfer_server '{}'",
+                        label, anchor.name, anchor.transfer_server
+                    ));
+                }
+            }
+        }
+
+        if self.sep_tracking.poll_interval_secs == 0 {
+            errors.push(
+                "sep_tracking.poll_interval_secs (SEP_POLL_INTERVAL_SECONDS) must be positive"
+                    .to_string(),
+            );
+        }
+        if self.websocket.auth_token.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            errors.push("websocket.auth_token (WS_AUTH_TOKEN) must not be empty when set".to_string());
+        }
+        if let Some(url) = &self.integrations.slack_webhook_url {
+            if !is_http_url(url) {
+                errors.push("integrations.slack_webhook_url (SLACK_WEBHOOK_URL) is not a URL".to_string());
+            }
+        }
+
+        let signing = &self.signing;
+        for (label, prefix, seed_var, key) in [
+            (
+                "snapshot",
+                "SNAPSHOT",
+                "SNAPSHOT_SIGNING_KEY",
+                &signing.snapshot,
+            ),
+            (
+                "stellar_source",
+                "STELLAR_SOURCE",
+                "STELLAR_SOURCE_SECRET_KEY",
+                &signing.stellar_source,
+            ),
+        ] {
+            let missing = match key.backend {
+                SignerBackend::Local => None,
+                SignerBackend::Vault => key.vault_key.is_none().then_some("vault_key"),
+                SignerBackend::Kms => key.kms_key.is_none().then_some("kms_key"),
+            };
+            if let Some(field) = missing {
+                errors.push(format!(
+                    "signing.{}.{} ({}_{}) must be set when signing.{}.backend ({}_SIGNER) is {}",
+                    label,
+                    field,
+                    prefix,
+                    field.to_uppercase(),
+                    label,
+                    prefix,
+                    key.backend.as_str()
+                ));
+            }
+            let invalid_seed = key
+                .seed
+                .as_deref()
+                .is_some_and(|seed| crate::signer::LocalSigner::from_seed_str(seed).is_err());
+            if key.backend == SignerBackend::Local && invalid_seed {
+                errors.push(format!(
+                    "signing.{}.seed ({}) must be a hex ed25519 seed or a Stellar secret seed (S...)",
+                    label, seed_var
+                ));
+            }
+        }
+        if signing.vault_transit_mount.trim().is_empty() {
+            errors.push(
+                "signing.vault_transit_mount (VAULT_TRANSIT_MOUNT) must not be empty".to_string(),
+            );
+        }
+        if !is_http_url(&signing.kms_endpoint) {
+            errors.push("signing.kms_endpoint (KMS_ENDPOINT) is not an http(s) URL".to_string());
+        }
+
+        let email = &self.email;
+        if let Some(kind) = email.provider_kind() {
+            let required: &[(&str, &str, &Option<String>)] = match kind {
+                EmailProviderKind::Smtp => &[
+                    ("smtp_host", "SMTP_HOST", &email.smtp_host),
+                    ("smtp_user", "SMTP_USER", &email.smtp_user),
+                    ("smtp_pass", "SMTP_PASS", &email.smtp_pass),
+                ],
+                EmailProviderKind::Ses => &[
+                    ("ses_smtp_user", "SES_SMTP_USER", &email.ses_smtp_user),
+                    ("ses_smtp_pass", "SES_SMTP_PASS", &email.ses_smtp_pass),
+                ],
+                EmailProviderKind::SendGrid => &[(
+                    "sendgrid_api_key",
+                    "SENDGRID_API_KEY",
+                    &email.sendgrid_api_key,
+                )],
+            };
+            for (field, name, value) in required {
+                if value.as_deref().map_or(true, |v| v.trim().is_empty()) {
+                    errors.push(format!(
+                        "email.{} ({}) must be set when email.provider (EMAIL_PROVIDER) is {}",
+                        field,
+                        name,
+                        kind.as_str()
+                    ));
+                }
+            }
+            match email.sender() {
+                Some(from) if from.parse::<lettre::message::Mailbox>().is_err() => {
+                    errors.push(format!(
+                        "email.from (EMAIL_FROM) '{}' is not an email address",
+                        from
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    errors.push("email.from (EMAIL_FROM) must be set when email is enabled".into());
+                }
+            }
+            if email.ses_region.trim().is_empty() {
+                errors.push("email.ses_region (AWS_REGION) must not be empty".to_string());
+            }
+        }
+
+        let ttl = &self.contract_ttl;
+        if !is_http_url(&ttl.rpc_url) {
+            errors.push("contract_ttl.rpc_url (SOROBAN_RPC_URL) is not an http(s) URL".to_string());
+        }
+        for id in &ttl.contract_ids {
+            if !id.starts_with('C') || parse_address(id).is_err() {
+                errors.push(format!(
+                    "contract_ttl.contract_ids (CONTRACT_TTL_WATCH_IDS) entry '{}' is not a contract ID",
+                    id
+                ));
+            }
+        }
+        if ttl.warn_ledgers == 0 {
+            errors.push(
+                "contract_ttl.warn_ledgers (CONTRACT_TTL_WARN_LEDGERS) must be positive"
+                    .to_string(),
+            );
+        }
+        if ttl.auto_extend && ttl.extend_to_ledgers <= ttl.warn_ledgers {
+            errors.push(
+                "contract_ttl.extend_to_ledgers (CONTRACT_TTL_EXTEND_TO_LEDGERS) must be above \
+                 contract_ttl.warn_ledgers, or extended entries are reported again"
+                    .to_string(),
+            );
+        }
+        if !(1..=MAX_KEEPALIVE_EPOCHS).contains(&ttl.snapshot_keepalive_epochs) {
+            errors.push(format!(
+                "contract_ttl.snapshot_keepalive_epochs (SNAPSHOT_TTL_KEEPALIVE_EPOCHS) must be \
+                 between 1 and {}",
+                MAX_KEEPALIVE_EPOCHS
+            ));
+        }
+        if ttl.snapshot_keepalive_ledgers == 0 {
+            errors.push(
+                "contract_ttl.snapshot_keepalive_ledgers (SNAPSHOT_TTL_KEEPALIVE_LEDGERS) must be \
+                 positive"
+                    .to_string(),
+            );
+        }
+
+        match (&self.screening.provider, &self.screening.url) {
+            (ScreeningProviderKind::Http, None) => errors.push(
+                "screening.url (SCREENING_URL) must be set when screening.provider \
+                 (SCREENING_PROVIDER) is http"
+                    .to_string(),
+            ),
+            (ScreeningProviderKind::Http, Some(url)) if !is_http_url(url) => {
+                errors.push("screening.url (SCREENING_URL) is not an http(s) URL".to_string())
+            }
+            _ => {}
+        }
+
+        let alerts = &self.alerts;
+        for (value, field, name) in [
+            (
+                alerts.supply_change_pct,
+                "supply_change_pct",
+                "ASSET_SUPPLY_ALERT_THRESHOLD_PCT",
+            ),
+            (
+                alerts.fee_spike_multiplier,
+                "fee_spike_multiplier",
+                "WEBHOOK_FEE_SPIKE_MULTIPLIER",
+            ),
+            (
+                alerts.ledger_close_delay_secs,
+                "ledger_close_delay_secs",
+                "WEBHOOK_LEDGER_CLOSE_DELAY_SECONDS",
+            ),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                errors.push(format!("alerts.{} ({}) must be positive", field, name));
+            }
+        }
+
+        if self.billing.exporter == BillingExporterKind::Stripe {
+            match self.billing.stripe_api_key.as_deref() {
+                None => errors.push(
+                    "billing.stripe_api_key (STRIPE_API_KEY) must be set when billing.exporter \
+                     (BILLING_EXPORTER) is stripe"
+                        .to_string(),
+                ),
+                Some(key) if !key.starts_with("sk_") && !key.starts_with("rk_") => errors.push(
+                    "billing.stripe_api_key (STRIPE_API_KEY) must be a secret (sk_) or \
+                     restricted (rk_) key"
+                        .to_string(),
+                ),
+                Some(_) => {}
+            }
+        }
+
+        if self.webhooks.event_retention_days <= 0 {
+            errors.push(
+                "webhooks.event_retention_days (WEBHOOK_EVENT_RETENTION_DAYS) must be positive"
+                    .to_string(),
+            );
+        }
+
+        let health = &self.health_score;
+        let weights = [
+            health.weight_success_rate,
+            health.weight_liquidity,
+            health.weight_volume_trend,
+            health.weight_anchor_reliability,
+        ];
+        if !weights.iter().all(|w| w.is_finite() && *w >= 0.0) {
+            errors.push(
+                "health_score weights (HEALTH_SCORE_WEIGHT_*) must not be negative".to_string(),
+            );
+        } else if weights.iter().all(|w| *w == 0.0) {
+            errors.push(
+                "health_score weights (HEALTH_SCORE_WEIGHT_*) must not all be zero".to_string(),
+            );
+        }
+
+        if !(1..=ledger::MAX_BATCH_SIZE).contains(&self.ingestion.ledger_batch_size) {
+            errors.push(format!(
+                "ingestion.ledger_batch_size (LEDGER_INGESTION_BATCH_SIZE) must be between 1 and {}",
+                ledger::MAX_BATCH_SIZE
+            ));
+        }
+
+        let http = &self.http_client;
+        if http.user_agent.trim().is_empty() {
+            errors.push(
+                "http_client.user_agent (HTTP_CLIENT_USER_AGENT) must not be empty".to_string(),
+            );
+        }
+        if http.connect_timeout_secs == 0 {
+            errors.push(
+                "http_client.connect_timeout_secs (HTTP_CLIENT_CONNECT_TIMEOUT_SECS) must be positive"
+                    .to_string(),
+            );
+        }
+        if let Some(proxy) = &http.proxy {
+            if reqwest::Proxy::all(proxy.as_str()).is_err() {
+                errors.push(
+                    "http_client.proxy (HTTP_CLIENT_PROXY) is not a valid proxy URL".to_string(),
+                );
+            }
+        }
+        if http.circuit_breaker_failure_threshold == 0 {
+            errors.push(
+                "http_client.circuit_breaker_failure_threshold \
+                 (HTTP_CLIENT_CIRCUIT_BREAKER_FAILURE_THRESHOLD) must be positive"
+                    .to_string(),
+            );
+        }
+        if let Some(dsn) = &self.error_sink.dsn {
+            if !is_http_url(dsn.trim()) {
+                errors.push("error_sink.dsn (ERROR_SINK_DSN) is not an http(s) URL".to_string());
+            }
+        }
+
+        errors
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_http_url(value: &str) -> bool {
+    reqwest::Url::parse(value)
+        .map(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Key every test environment sets unless it overrides `ENCRYPTION_KEY`
+    const TEST_KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn with_env(vars: &[(&str, &str)]) -> (Settings, Vec<String>) {
+        let mut vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        vars.entry("ENCRYPTION_KEY".to_string())
+            .or_insert_with(|| TEST_KEY.to_string());
+        let mut settings = Settings::default();
+        let errors = settings.apply_env(|name| vars.get(name).cloned());
+        (settings, errors)
+    }
+
+    #[test]
+    fn test_defaults_are_valid_once_a_key_is_set() {
+        let (settings, errors) = with_env(&[]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+
+        let errors = Settings::default().validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("ENCRYPTION_KEY) must be set"));
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let (settings, errors) = with_env(&[
+            ("SERVER_PORT", "9000"),
+            ("CORS_ALLOWED_ORIGINS", "https://a.example, https://b.example"),
+            (
+                "SEP24_ANCHORS",
+                r#"[{"name":"Test","transfer_server":"https://anchor.example/sep24"}]"#,
+            ),
+            ("WS_AUTH_TOKEN", "secret"),
+            ("WS_ADMIN_USER_IDS", "user-1, user-2"),
+            ("MULTI_TENANT_MODE", "true"),
+        ]);
+        assert!(errors.is_empty());
+        assert!(settings.deployment.multi_tenant);
+        assert_eq!(settings.server.addr(), "127.0.0.1:9000");
+        assert_eq!(settings.security.cors_allowed_origins.len(), 2);
+        assert_eq!(settings.sep24.anchors[0].name, "Test");
+        assert_eq!(settings.websocket.auth_token.as_deref(), Some("secret"));
+        assert_eq!(settings.websocket.admin_user_ids, vec!["user-1", "user-2"]);
+    }
+
+    #[test]
+    fn test_invalid_values_are_all_reported() {
+        let (settings, mut errors) = with_env(&[
+            ("SERVER_PORT", "http"),
+            ("SEP31_ANCHORS", "not json"),
+            ("ENCRYPTION_KEY", "short"),
+            ("RATE_LIMIT_WHITELIST_IPS", "10.0.0.1,localhost"),
+            ("MULTI_TENANT_MODE", "yes"),
+        ]);
+        errors.extend(settings.validate());
+        assert_eq!(errors.len(), 5, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("SERVER_PORT")));
+        assert!(errors.iter().any(|e| e.contains("MULTI_TENANT_MODE")));
+        assert!(errors.iter().any(|e| e.contains("localhost")));
+    }
+
+    #[test]
+    fn test_toml_file_with_env_override() {
+        let mut settings: Settings = toml::from_str(
+            r#"
+            [server]
+            port = 9090
+
+            [sep31]
+            allowed_origins = ["https://anchor.example"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(settings.server.port, 9090);
+        assert_eq!(settings.server.host, "127.0.0.1");
+
+        settings.apply_env(|name| (name == "SERVER_PORT").then(|| "7000".to_string()));
+        assert_eq!(settings.server.port, 7000);
+        assert!(toml::from_str::<Settings>("[server]\nprot = 1").is_err());
+    }
+
+    #[test]
+    fn test_pool_settings() {
+        let (settings, errors) = with_env(&[
+            ("DB_POOL_MAX_CONNECTIONS", "4"),
+            ("DB_POOL_MIN_CONNECTIONS", "8"),
+            ("DB_POOL_CONNECT_TIMEOUT_SECONDS", "5"),
+            ("DB_SLOW_QUERY_THRESHOLD_MS", "250"),
+        ]);
+        assert!(errors.is_empty());
+        assert_eq!(settings.database.pool_config().connect_timeout_seconds, 5);
+        assert_eq!(settings.database.pool_config().slow_query_threshold_ms, 250);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("min_connections"));
+    }
+
+    #[test]
+    fn test_rpc_concurrency_settings() {
+        let (settings, errors) = with_env(&[
+            ("RPC_MAX_CONCURRENT_ORDERBOOK", "2"),
+            ("RPC_MAX_CONCURRENT_PER_ROUTE", "0"),
+        ]);
+        assert!(errors.is_empty());
+        assert_eq!(settings.rpc.max_concurrent_orderbook, 2);
+        assert_eq!(settings.rpc.max_concurrent_requests, 64);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("RPC_MAX_CONCURRENT_"));
+    }
+
+    #[test]
+    fn test_horizon_urls() {
+        let (settings, errors) = with_env(&[(
+            "STELLAR_HORIZON_URLS",
+            "https://horizon.example.com, ,horizon.example.org",
+        )]);
+        assert!(errors.is_empty());
+        assert_eq!(settings.rpc.horizon_urls.len(), 2);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'horizon.example.org'"));
+    }
+
+    #[test]
+    fn test_rpc_rate_limit_settings() {
+        let (settings, errors) = with_env(&[
+            ("RPC_RATE_LIMIT_REQUESTS_PER_MINUTE", "60"),
+            ("RPC_RATE_LIMIT_STANDARD_RESERVE", "0.4"),
+            ("RPC_RATE_LIMIT_BACKGROUND_RESERVE", "0.3"),
+        ]);
+        assert!(errors.is_empty());
+        let config = settings.rpc.rate_limit_config();
+        assert_eq!(config.requests_per_minute, 60.0);
+        assert_eq!(config.burst_size, 10.0);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("rate_limit_background_reserve"));
+
+        let (settings, _) = with_env(&[
+            ("RPC_RATE_LIMIT_QUEUE_SIZE", "0"),
+            ("RPC_RATE_LIMIT_STANDARD_RESERVE", "1"),
+        ]);
+        assert_eq!(settings.validate().len(), 2);
+    }
+
+    #[test]
+    fn test_signing_settings() {
+        let (settings, errors) = with_env(&[
+            ("SNAPSHOT_SIGNER", "KMS"),
+            (
+                "SNAPSHOT_KMS_KEY",
+                "projects/p/locations/l/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1",
+            ),
+            ("STELLAR_SOURCE_SIGNER", "vault"),
+        ]);
+        assert!(errors.is_empty());
+        assert_eq!(settings.signing.snapshot.backend, SignerBackend::Kms);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("STELLAR_SOURCE_VAULT_KEY"));
+
+        let (settings, errors) = with_env(&[
+            ("SNAPSHOT_SIGNER", "hsm"),
+            ("STELLAR_SOURCE_SECRET_KEY", "not-a-seed"),
+        ]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("SNAPSHOT_SIGNER"));
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("STELLAR_SOURCE_SECRET_KEY"));
+    }
+
+    #[test]
+    fn test_email_settings() {
+        let (settings, errors) = with_env(&[]);
+        assert!(errors.is_empty());
+        assert!(settings.email.provider_kind().is_none());
+
+        // SMTP_HOST alone selects SMTP, sending as the SMTP user
+        let (settings, _) = with_env(&[
+            ("SMTP_HOST", "smtp.example.com"),
+            ("SMTP_USER", "insights@example.com"),
+            ("SMTP_PASS", "pass"),
+        ]);
+        assert_eq!(
+            settings.email.provider_kind(),
+            Some(EmailProviderKind::Smtp)
+        );
+        assert_eq!(settings.email.sender(), Some("insights@example.com"));
+        assert!(settings.validate().is_empty());
+
+        let (_, errors) = with_env(&[("EMAIL_PROVIDER", "mailgun")]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("EMAIL_PROVIDER"));
+
+        let (settings, _) = with_env(&[("EMAIL_PROVIDER", "sendgrid")]);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("SENDGRID_API_KEY"));
+        assert!(errors[1].contains("EMAIL_FROM"));
+    }
+
+    #[test]
+    fn test_contract_ttl_settings() {
+        let contract = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+        let watch_ids = format!("{}, ", contract);
+        let (settings, errors) = with_env(&[
+            ("SNAPSHOT_CONTRACT_ID", contract),
+            ("CONTRACT_TTL_WATCH_IDS", watch_ids.as_str()),
+            ("CONTRACT_TTL_AUTO_EXTEND", "true"),
+        ]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        let config = settings.contract_ttl.watcher_config();
+        assert_eq!(config.contract_ids, vec![contract.to_string()]);
+        assert_eq!(config.auto_extend_to, Some(518_400));
+
+        let (_, errors) = with_env(&[("CONTRACT_TTL_WARN_LEDGERS", "a week")]);
+        assert_eq!(errors.len(), 1);
+
+        let (settings, errors) = with_env(&[
+            ("SNAPSHOT_TTL_KEEPALIVE_EPOCHS", "10"),
+            ("SNAPSHOT_TTL_KEEPALIVE_LEDGERS", "17280"),
+        ]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        let keepalive = settings.contract_ttl.snapshot_keepalive_config();
+        assert_eq!((keepalive.epochs, keepalive.ledgers), (10, 17_280));
+
+        let (settings, _) = with_env(&[("SNAPSHOT_TTL_KEEPALIVE_EPOCHS", "51")]);
+        assert_eq!(settings.validate().len(), 1);
+
+        let (settings, _) = with_env(&[
+            (
+                "CONTRACT_TTL_WATCH_IDS",
+                "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+            ),
+            ("CONTRACT_TTL_AUTO_EXTEND", "true"),
+            ("CONTRACT_TTL_EXTEND_TO_LEDGERS", "1000"),
+        ]);
+        assert_eq!(settings.validate().len(), 2);
+    }
+
+    #[test]
+    fn test_screening_settings() {
+        let (settings, errors) = with_env(&[
+            ("SCREENING_PROVIDER", "HTTP"),
+            ("SCREENING_URL", "https://screening.example.com/v1/screen"),
+            ("SCREENING_CACHE_TTL_SECONDS", "3600"),
+        ]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        assert_eq!(settings.screening.provider, ScreeningProviderKind::Http);
+        assert_eq!(settings.screening.cache_ttl_secs, 3600);
+
+        let (_, errors) = with_env(&[("SCREENING_PROVIDER", "chainalysis")]);
+        assert_eq!(errors.len(), 1);
+
+        let (settings, _) = with_env(&[("SCREENING_PROVIDER", "http")]);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("SCREENING_URL"));
+    }
+
+    #[test]
+    fn test_billing_settings() {
+        let (settings, errors) = with_env(&[
+            ("BILLING_EXPORTER", "stripe"),
+            ("STRIPE_API_KEY", "sk_test_123"),
+        ]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        assert_eq!(settings.billing.exporter, BillingExporterKind::Stripe);
+
+        let (_, errors) = with_env(&[("BILLING_EXPORTER", "strpie")]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("BILLING_EXPORTER"));
+
+        let (settings, _) = with_env(&[("BILLING_EXPORTER", "stripe")]);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("STRIPE_API_KEY"));
+    }
+
+    #[test]
+    fn test_login_settings() {
+        let (settings, errors) = with_env(&[
+            ("LOGIN_LOCKOUT_THRESHOLD", "5"),
+            ("LOGIN_LOCKOUT_SECONDS", "600"),
+        ]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        let guard = settings.login.guard_config();
+        assert_eq!(guard.account_threshold, 5);
+        assert_eq!(guard.ip_threshold, 50);
+        assert_eq!(guard.lockout_secs, 600);
+
+        let (settings, errors) = with_env(&[
+            ("LOGIN_LOCKOUT_THRESHOLD", "100"),
+            ("LOGIN_LOCKOUT_SECONDS", "0"),
+            ("LOGIN_FAILURE_WINDOW_SECONDS", "15m"),
+        ]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(settings.validate().len(), 2);
+    }
+
+    #[test]
+    fn test_public_url() {
+        let (settings, _) = with_env(&[("SERVER_PORT", "9000")]);
+        assert_eq!(settings.server.public_url(), "http://127.0.0.1:9000");
+
+        let (settings, _) = with_env(&[("PUBLIC_API_URL", "https://api.example.com/")]);
+        assert_eq!(settings.server.public_url(), "https://api.example.com");
+        assert!(settings.validate().is_empty());
+
+        let (settings, _) = with_env(&[("PUBLIC_API_URL", "api.example.com")]);
+        assert_eq!(settings.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_previous_encryption_keys() {
+        let old_key = "1".repeat(64);
+        let previous = format!("{}, ", old_key);
+        let (settings, errors) = with_env(&[("ENCRYPTION_KEY_PREVIOUS", previous.as_str())]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        assert_eq!(settings.security.encryption_key_previous, vec![old_key]);
+
+        let (settings, _) = with_env(&[("ENCRYPTION_KEY_PREVIOUS", "not-a-key")]);
+        let errors = settings.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("ENCRYPTION_KEY_PREVIOUS"));
+    }
+
+    #[test]
+    fn test_alert_settings() {
+        let (settings, errors) = with_env(&[("ASSET_SUPPLY_ALERT_THRESHOLD_PCT", "2.5")]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        assert_eq!(
+            settings.alerts.supply_tracker_config().alert_threshold_pct,
+            2.5
+        );
+
+        let (settings, errors) = with_env(&[
+            ("WEBHOOK_FEE_SPIKE_MULTIPLIER", "5"),
+            ("WEBHOOK_LEDGER_CLOSE_DELAY_SECONDS", "30"),
+        ]);
+        assert!(errors.is_empty());
+        let network = settings.alerts.network_event_config();
+        assert_eq!(network.fee_spike_multiplier, 5.0);
+        assert_eq!(network.close_delay_secs, 30.0);
+
+        let (settings, _) = with_env(&[
+            ("ASSET_SUPPLY_ALERT_THRESHOLD_PCT", "-1"),
+            ("WEBHOOK_LEDGER_CLOSE_DELAY_SECONDS", "0"),
+        ]);
+        assert_eq!(settings.validate().len(), 2);
+    }
+
+    #[test]
+    fn test_anomaly_model() {
+        let (settings, errors) = with_env(&[("ML_ANOMALY_MODEL", "isolation_forest")]);
+        assert!(errors.is_empty());
+        assert_eq!(settings.ml.anomaly_model, AnomalyModel::IsolationForest);
+
+        let (_, errors) = with_env(&[("ML_ANOMALY_MODEL", "isolation-forest")]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("ML_ANOMALY_MODEL"));
+    }
+
+    #[test]
+    fn test_webhook_retention_settings() {
+        let (settings, errors) = with_env(&[
+            ("WEBHOOK_EVENT_RETENTION_DAYS", "7"),
+            ("WEBHOOK_EVENT_ARCHIVE_MODE", "strip"),
+        ]);
+        assert!(errors.is_empty());
+        let retention = settings.webhooks.retention_config();
+        assert_eq!(retention.retention_days, 7);
+        assert_eq!(retention.mode, ArchiveMode::StripPayload);
+
+        let (settings, errors) = with_env(&[
+            ("WEBHOOK_EVENT_RETENTION_DAYS", "0"),
+            ("WEBHOOK_EVENT_ARCHIVE_MODE", "keep"),
+        ]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("WEBHOOK_EVENT_ARCHIVE_MODE"));
+        assert_eq!(settings.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_health_score_settings() {
+        let (settings, errors) = with_env(&[("HEALTH_SCORE_WEIGHT_LIQUIDITY", "0.5")]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        let weights = settings.health_score.weights();
+        assert_eq!(weights.liquidity, 0.5);
+        assert_eq!(weights.success_rate, HealthWeights::default().success_rate);
+
+        let (settings, _) = with_env(&[("HEALTH_SCORE_WEIGHT_VOLUME_TREND", "-1")]);
+        assert_eq!(settings.validate().len(), 1);
+
+        let (_, errors) = with_env(&[("HEALTH_SCORE_WEIGHT_SUCCESS_RATE", "high")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_ingestion_settings() {
+        let (settings, errors) = with_env(&[("LEDGER_INGESTION_BATCH_SIZE", "20")]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        assert_eq!(settings.ingestion.ledger_batch_size, 20);
+
+        let (settings, _) = with_env(&[("LEDGER_INGESTION_BATCH_SIZE", "0")]);
+        assert_eq!(settings.validate().len(), 1);
+
+        let (_, errors) = with_env(&[("LEDGER_INGESTION_BATCH_SIZE", "many")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_http_client_settings() {
+        let (settings, errors) = with_env(&[
+            ("HTTP_CLIENT_USER_AGENT", "Insights-Test/2.0"),
+            ("HTTP_CLIENT_PROXY", ""),
+            ("HTTP_CLIENT_CIRCUIT_BREAKER_TIMEOUT_SECONDS", "60"),
+        ]);
+        assert!(errors.is_empty());
+        assert!(settings.validate().is_empty());
+        let config = settings.http_client.client_config();
+        assert_eq!(config.user_agent, "Insights-Test/2.0");
+        assert_eq!(config.proxy, None);
+        assert_eq!(
+            config.circuit_breaker.timeout_duration,
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            config.connect_timeout,
+            HttpClientConfig::default().connect_timeout
+        );
+
+        let (settings, _) = with_env(&[("HTTP_CLIENT_CIRCUIT_BREAKER_FAILURE_THRESHOLD", "0")]);
+        assert_eq!(settings.validate().len(), 1);
+
+        let (_, errors) = with_env(&[("HTTP_CLIENT_CONNECT_TIMEOUT_SECS", "soon")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_error_sink_settings() {
+        let (settings, errors) = with_env(&[("ERROR_SINK_DSN", "")]);
+        assert!(errors.is_empty());
+        assert!(settings.error_sink.sink_config().dsn.is_none());
+
+        let (settings, _) = with_env(&[
+            ("ERROR_SINK_DSN", "https://abc123@o1.ingest.sentry.io/42"),
+            ("ERROR_SINK_ENVIRONMENT", "staging"),
+        ]);
+        assert!(settings.validate().is_empty());
+        let config = settings.error_sink.sink_config();
+        assert_eq!(config.environment.as_deref(), Some("staging"));
+
+        let (settings, _) = with_env(&[("ERROR_SINK_DSN", "not a url")]);
+        assert_eq!(settings.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_allows_transfer_server() {
+        let open = SepProxySettings::default();
+        assert!(open.allows_transfer_server("https://anywhere.example"));
+
+        let restricted = SepProxySettings {
+            allowed_origins: vec!["https://anchor.example".to_string()],
+            anchors: vec![],
+        };
+        assert!(restricted.allows_transfer_server("https://anchor.example/sep24/"));
+        assert!(!restricted.allows_transfer_server("https://other.example"));
+    }
+}
@@ -0,0 +1,470 @@
+//! Typed decoding of Soroban events emitted by our contracts.
+//!
+//! Soroban RPC `getEvents` returns each event's topics and value as base64
+//! XDR `ScVal`s. [`decode_event`] reads the first topic symbol to identify
+//! the event and maps its value onto a [`ContractEvent`] variant. Structs
+//! published with `#[contracttype]` arrive as symbol-keyed maps; tuples
+//! arrive as vectors. Unknown topics are reported as
+//! [`DecodeError::UnknownEvent`] so callers can skip them.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use stellar_xdr::curr::{Limits, ReadXdr, ScMap, ScVal};
+
+/// An event as returned by Soroban RPC `getEvents`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawContractEvent {
+    pub contract_id: String,
+    pub ledger: u64,
+    #[serde(default)]
+    pub ledger_closed_at: Option<String>,
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+    /// Base64 XDR `ScVal` topics
+    pub topic: Vec<String>,
+    /// Base64 XDR `ScVal` event data
+    pub value: String,
+}
+
+/// Events published by the snapshot, analytics and governance contracts.
+/// Hashes are hex-encoded; addresses are strkeys (`G...`/`C...`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContractEvent {
+    /// `SNAP_SUB`: the current struct form carries the submitter; the
+    /// legacy struct and the snapshot contract's tuple do not
+    SnapshotSubmitted {
+        hash: String,
+        epoch: u64,
+        timestamp: u64,
+        submitter: Option<String>,
+    },
+    /// `INIT`
+    Initialized { admin: String, version: u32 },
+    /// `STOPPED`
+    Stopped { admin: String },
+    /// `RESUMED`
+    Resumed { admin: String },
+    /// `ADM_XFER`
+    AdminTransferred {
+        previous_admin: String,
+        new_admin: String,
+    },
+    /// `SUBM_ADD`: the analytics contract's admin allowed `submitter` to
+    /// submit snapshots
+    SubmitterAdded { admin: String, submitter: String },
+    /// `SUBM_REM`
+    SubmitterRemoved { admin: String, submitter: String },
+    /// `UPG_PREP`
+    UpgradePrepared { wasm_hash: String },
+    /// `UPGRADED`
+    Upgraded { wasm_hash: String, version: u32 },
+    /// `MIGRATED`
+    Migrated { from_version: u32, to_version: u32 },
+    /// `PROP_CRT`
+    ProposalCreated {
+        proposal_id: u64,
+        proposer: String,
+        target_contract: String,
+        voting_ends_at: u64,
+    },
+    /// `VOTE_CST`
+    VoteCast {
+        proposal_id: u64,
+        voter: String,
+        choice: u32,
+    },
+    /// `PROP_FIN`
+    ProposalFinalized {
+        proposal_id: u64,
+        status: u32,
+        votes_for: u64,
+        votes_against: u64,
+        total_voters: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedContractEvent {
+    pub contract_id: String,
+    pub ledger: u64,
+    pub tx_hash: Option<String>,
+    pub event: ContractEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A topic or value is not valid base64 XDR
+    InvalidXdr(String),
+    /// The first topic is missing or not a symbol
+    MissingTopic,
+    /// The topic symbol is not one of ours
+    UnknownEvent(String),
+    /// The topic is known but the data does not have the expected shape
+    UnexpectedShape { event: String, reason: String },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidXdr(msg) => write!(f, "Invalid event XDR: {}", msg),
+            DecodeError::MissingTopic => write!(f, "Event has no symbol topic"),
+            DecodeError::UnknownEvent(topic) => write!(f, "Unknown event topic: {}", topic),
+            DecodeError::UnexpectedShape { event, reason } => {
+                write!(f, "Unexpected {} event data: {}", event, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decode one base64 XDR `ScVal`
+pub fn decode_scval(b64: &str) -> Result<ScVal, DecodeError> {
+    let bytes = STANDARD
+        .decode(b64.trim())
+        .map_err(|e| DecodeError::InvalidXdr(e.to_string()))?;
+    ScVal::from_xdr(bytes, Limits::none()).map_err(|e| DecodeError::InvalidXdr(e.to_string()))
+}
+
+/// Decode a raw RPC event into a typed event
+pub fn decode_event(raw: &RawContractEvent) -> Result<DecodedContractEvent, DecodeError> {
+    let topics = raw
+        .topic
+        .iter()
+        .map(|t| decode_scval(t))
+        .collect::<Result<Vec<_>, _>>()?;
+    let value = decode_scval(&raw.value)?;
+
+    Ok(DecodedContractEvent {
+        contract_id: raw.contract_id.clone(),
+        ledger: raw.ledger,
+        tx_hash: raw.tx_hash.clone(),
+        event: decode_value(&topics, &value)?,
+    })
+}
+
+/// Map already-decoded topics and data onto a [`ContractEvent`]
+pub fn decode_value(topics: &[ScVal], value: &ScVal) -> Result<ContractEvent, DecodeError> {
+    let topic = topics
+        .first()
+        .and_then(symbol)
+        .ok_or(DecodeError::MissingTopic)?;
+    let data = Data {
+        event: &topic,
+        value,
+    };
+
+    let event = match topic.as_str() {
+        "SNAP_SUB" => match value {
+            ScVal::Map(Some(_)) => snapshot_from_map(&data)?,
+            _ => {
+                let [hash, epoch, timestamp] = data.tuple()?;
+                ContractEvent::SnapshotSubmitted {
+                    hash: data.bytes(hash)?,
+                    epoch: data.u64(epoch)?,
+                    timestamp: data.u64(timestamp)?,
+                    submitter: None,
+                }
+            }
+        },
+        "INIT" => {
+            let [admin, version] = data.tuple()?;
+            ContractEvent::Initialized {
+                admin: data.address(admin)?,
+                version: data.u32(version)?,
+            }
+        }
+        "STOPPED" => {
+            let [admin] = data.tuple()?;
+            ContractEvent::Stopped {
+                admin: data.address(admin)?,
+            }
+        }
+        "RESUMED" => {
+            let [admin] = data.tuple()?;
+            ContractEvent::Resumed {
+                admin: data.address(admin)?,
+            }
+        }
+        "ADM_XFER" => {
+            let [previous, new] = data.tuple()?;
+            ContractEvent::AdminTransferred {
+                previous_admin: data.address(previous)?,
+                new_admin: data.address(new)?,
+            }
+        }
+        "SUBM_ADD" => {
+            let [admin, submitter] = data.tuple()?;
+            ContractEvent::SubmitterAdded {
+                admin: data.address(admin)?,
+                submitter: data.address(submitter)?,
+            }
+        }
+        "SUBM_REM" => {
+            let [admin, submitter] = data.tuple()?;
+            ContractEvent::SubmitterRemoved {
+                admin: data.address(admin)?,
+                submitter: data.address(submitter)?,
+            }
+        }
+        "UPG_PREP" => {
+            let [hash] = data.tuple()?;
+            ContractEvent::UpgradePrepared {
+                wasm_hash: data.bytes(hash)?,
+            }
+        }
+        "UPGRADED" => {
+            let [hash, version] = data.tuple()?;
+            ContractEvent::Upgraded {
+                wasm_hash: data.bytes(hash)?,
+                version: data.u32(version)?,
+            }
+        }
+        "MIGRATED" => {
+            let [from, to] = data.tuple()?;
+            ContractEvent::Migrated {
+                from_version: data.u32(from)?,
+                to_version: data.u32(to)?,
+            }
+        }
+        "PROP_CRT" => {
+            let map = data.map()?;
+            ContractEvent::ProposalCreated {
+                proposal_id: data.u64(data.field(map, "proposal_id")?)?,
+                proposer: data.address(data.field(map, "proposer")?)?,
+                target_contract: data.address(data.field(map, "target_contract")?)?,
+                voting_ends_at: data.u64(data.field(map, "voting_ends_at")?)?,
+            }
+        }
+        "VOTE_CST" => {
+            let map = data.map()?;
+            ContractEvent::VoteCast {
+                proposal_id: data.u64(data.field(map, "proposal_id")?)?,
+                voter: data.address(data.field(map, "voter")?)?,
+                choice: data.u32(data.field(map, "choice")?)?,
+            }
+        }
+        "PROP_FIN" => {
+            let map = data.map()?;
+            ContractEvent::ProposalFinalized {
+                proposal_id: data.u64(data.field(map, "proposal_id")?)?,
+                status: data.u32(data.field(map, "status")?)?,
+                votes_for: data.u64(data.field(map, "votes_for")?)?,
+                votes_against: data.u64(data.field(map, "votes_against")?)?,
+                total_voters: data.u64(data.field(map, "total_voters")?)?,
+            }
+        }
+        other => return Err(DecodeError::UnknownEvent(other.to_string())),
+    };
+
+    Ok(event)
+}
+
+fn symbol(val: &ScVal) -> Option<String> {
+    match val {
+        ScVal::Symbol(sym) => Some(sym.0.to_utf8_string_lossy()),
+        _ => None,
+    }
+}
+
+/// `SnapshotSubmitted` (with submitter) or legacy `AnalyticsSnapshotSubmitted`
+fn snapshot_from_map(data: &Data<'_>) -> Result<ContractEvent, DecodeError> {
+    let map = data.map()?;
+    let submitter = match data.field(map, "submitter") {
+        Ok(val) => Some(data.address(val)?),
+        Err(_) => None,
+    };
+    Ok(ContractEvent::SnapshotSubmitted {
+        hash: data.bytes(data.field(map, "hash")?)?,
+        epoch: data.u64(data.field(map, "epoch")?)?,
+        timestamp: data.u64(data.field(map, "timestamp")?)?,
+        submitter,
+    })
+}
+
+/// Event data being decoded, with the topic kept for error messages
+struct Data<'a> {
+    event: &'a str,
+    value: &'a ScVal,
+}
+
+impl<'a> Data<'a> {
+    fn shape_error(&self, reason: impl Into<String>) -> DecodeError {
+        DecodeError::UnexpectedShape {
+            event: self.event.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    fn tuple<const N: usize>(&self) -> Result<[&'a ScVal; N], DecodeError> {
+        let ScVal::Vec(Some(items)) = self.value else {
+            return Err(self.shape_error("expected a tuple"));
+        };
+        let items: Vec<&ScVal> = items.0.iter().collect();
+        let len = items.len();
+        items
+            .try_into()
+            .map_err(|_| self.shape_error(format!("expected {} fields, got {}", N, len)))
+    }
+
+    fn map(&self) -> Result<&'a ScMap, DecodeError> {
+        match self.value {
+            ScVal::Map(Some(map)) => Ok(map),
+            _ => Err(self.shape_error("expected a struct")),
+        }
+    }
+
+    fn field(&self, map: &'a ScMap, name: &str) -> Result<&'a ScVal, DecodeError> {
+        map.0
+            .iter()
+            .find(|entry| symbol(&entry.key).as_deref() == Some(name))
+            .map(|entry| &entry.val)
+            .ok_or_else(|| self.shape_error(format!("missing field `{}`", name)))
+    }
+
+    fn u64(&self, val: &ScVal) -> Result<u64, DecodeError> {
+        match val {
+            ScVal::U64(n) => Ok(*n),
+            ScVal::U32(n) => Ok(u64::from(*n)),
+            ScVal::Timepoint(t) => Ok(t.0),
+            _ => Err(self.shape_error("expected an unsigned integer")),
+        }
+    }
+
+    fn u32(&self, val: &ScVal) -> Result<u32, DecodeError> {
+        match val {
+            ScVal::U32(n) => Ok(*n),
+            _ => Err(self.shape_error("expected a u32")),
+        }
+    }
+
+    fn bytes(&self, val: &ScVal) -> Result<String, DecodeError> {
+        match val {
+            ScVal::Bytes(bytes) => Ok(hex::encode(bytes.0.as_slice())),
+            _ => Err(self.shape_error("expected bytes")),
+        }
+    }
+
+    fn address(&self, val: &ScVal) -> Result<String, DecodeError> {
+        match val {
+            ScVal::Address(address) => Ok(address.to_string()),
+            _ => Err(self.shape_error("expected an address")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{Hash, ScAddress, ScBytes, ScMapEntry, ScSymbol, ScVec, WriteXdr};
+
+    fn sym(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    fn encode(val: &ScVal) -> String {
+        STANDARD.encode(val.to_xdr(Limits::none()).unwrap())
+    }
+
+    fn raw(topics: &[ScVal], value: &ScVal) -> RawContractEvent {
+        RawContractEvent {
+            contract_id: "CCONTRACT".to_string(),
+            ledger: 42,
+            ledger_closed_at: None,
+            tx_hash: Some("abc".to_string()),
+            topic: topics.iter().map(encode).collect(),
+            value: encode(value),
+        }
+    }
+
+    fn contract_address() -> ScVal {
+        ScVal::Address(ScAddress::Contract(Hash([7; 32])))
+    }
+
+    fn hash_bytes() -> ScVal {
+        ScVal::Bytes(ScBytes(vec![0xab; 32].try_into().unwrap()))
+    }
+
+    #[test]
+    fn test_decodes_snapshot_struct_with_submitter() {
+        let entry = |key: &str, val: ScVal| ScMapEntry { key: sym(key), val };
+        let entries = vec![
+            entry("epoch", ScVal::U64(5)),
+            entry("hash", hash_bytes()),
+            entry("submitter", contract_address()),
+            entry("timestamp", ScVal::U64(1_700_000_000)),
+        ];
+        let value = ScVal::Map(Some(ScMap(entries.try_into().unwrap())));
+
+        let decoded = decode_event(&raw(&[sym("SNAP_SUB"), sym("SNAP_LFE")], &value)).unwrap();
+        assert_eq!(decoded.ledger, 42);
+        match decoded.event {
+            ContractEvent::SnapshotSubmitted {
+                hash,
+                epoch,
+                timestamp,
+                submitter,
+            } => {
+                assert_eq!(hash, "ab".repeat(32));
+                assert_eq!(epoch, 5);
+                assert_eq!(timestamp, 1_700_000_000);
+                assert!(submitter.unwrap().starts_with('C'));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decodes_snapshot_tuple() {
+        let value = ScVal::Vec(Some(ScVec(
+            vec![hash_bytes(), ScVal::U64(9), ScVal::U64(100)].try_into().unwrap(),
+        )));
+        let event = decode_value(&[sym("SNAP_SUB")], &value).unwrap();
+        assert_eq!(
+            event,
+            ContractEvent::SnapshotSubmitted {
+                hash: "ab".repeat(32),
+                epoch: 9,
+                timestamp: 100,
+                submitter: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decodes_submitter_changes() {
+        let account = ScVal::Address(ScAddress::Contract(Hash([9; 32])));
+        let value = ScVal::Vec(Some(ScVec(
+            vec![contract_address(), account].try_into().unwrap(),
+        )));
+
+        let added = decode_value(&[sym("SUBM_ADD")], &value).unwrap();
+        let ContractEvent::SubmitterAdded { admin, submitter } = added else {
+            panic!("unexpected event {:?}", added);
+        };
+        assert_ne!(admin, submitter);
+        assert_eq!(
+            decode_value(&[sym("SUBM_REM")], &value).unwrap(),
+            ContractEvent::SubmitterRemoved { admin, submitter }
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_malformed_events() {
+        assert_eq!(
+            decode_value(&[sym("TRANSFER")], &ScVal::Void),
+            Err(DecodeError::UnknownEvent("TRANSFER".to_string()))
+        );
+        assert_eq!(decode_value(&[], &ScVal::Void), Err(DecodeError::MissingTopic));
+
+        let short = ScVal::Vec(Some(ScVec(vec![ScVal::U32(1)].try_into().unwrap())));
+        assert!(matches!(
+            decode_value(&[sym("MIGRATED")], &short),
+            Err(DecodeError::UnexpectedShape { .. })
+        ));
+        assert!(matches!(decode_scval("not base64!"), Err(DecodeError::InvalidXdr(_))));
+    }
+}
@@ -0,0 +1,6 @@
+//! Events emitted by our Soroban contracts, decoded into typed structs shared
+//! by contract event ingestion and replay.
+
+pub mod decode;
+
+pub use decode::{decode_event, ContractEvent, DecodeError, DecodedContractEvent, RawContractEvent};
@@ -4,6 +4,7 @@ use aes_gcm::{
 };
 use base64::{engine::general_purpose::STANDARD as base64_standard, Engine as _};
 use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
 
 /// Encrypts plaintext using AES-256-GCM.
 /// Returns a base64 encoded string containing the nonce and ciphertext separated by a colon `nonce:ciphertext`.
@@ -77,6 +78,105 @@ pub fn is_encrypted(data: &str) -> bool {
     data.contains(':') && data.split(':').count() == 2
 }
 
+/// Separates the key id from `nonce:ciphertext` in values written by [`FieldCipher`]
+const KEY_ID_SEPARATOR: char = '$';
+
+/// Field-level encryption for PII columns (notification emails, OAuth
+/// secrets and tokens) with support for key rotation.
+///
+/// Values are written as `<key id>$<nonce>:<ciphertext>` under the current
+/// key. Reads accept values under any configured previous key, values from
+/// before key ids were recorded (`nonce:ciphertext`), and plaintext rows that
+/// predate encryption, so turning encryption on or rotating the key never
+/// breaks reads. The re-encryption job rewrites old values under the current
+/// key; see [`FieldCipher::needs_rotation`].
+#[derive(Clone)]
+pub struct FieldCipher {
+    current: String,
+    previous: Vec<String>,
+}
+
+impl FieldCipher {
+    /// Encrypt under `current`; `previous` keys are still accepted for
+    /// decryption (see [`crate::config::SecuritySettings::field_cipher`])
+    pub fn new(current: impl Into<String>, previous: Vec<String>) -> Self {
+        Self {
+            current: current.into(),
+            previous,
+        }
+    }
+
+    /// Short public identifier of a key: the first 8 hex chars of its SHA-256
+    pub fn key_id(key_hex: &str) -> String {
+        let digest = Sha256::digest(key_hex.to_ascii_lowercase().as_bytes());
+        hex::encode(&digest[..4])
+    }
+
+    pub fn current_key_id(&self) -> String {
+        Self::key_id(&self.current)
+    }
+
+    pub fn encrypt(&self, plain_text: &str) -> Result<String> {
+        if plain_text.is_empty() {
+            return Ok(String::new());
+        }
+        let encrypted = encrypt_data(plain_text, &self.current)?;
+        Ok(format!("{}{}{}", self.current_key_id(), KEY_ID_SEPARATOR, encrypted))
+    }
+
+    pub fn encrypt_opt(&self, plain_text: Option<&str>) -> Result<Option<String>> {
+        plain_text.map(|value| self.encrypt(value)).transpose()
+    }
+
+    /// Decrypt a stored value with whichever configured key wrote it.
+    /// Plaintext values are returned unchanged.
+    pub fn decrypt(&self, stored: &str) -> Result<String> {
+        if !is_encrypted(stored) {
+            return Ok(stored.to_string());
+        }
+
+        match stored.split_once(KEY_ID_SEPARATOR) {
+            Some((key_id, encrypted)) => {
+                let key = self
+                    .keys()
+                    .find(|key| Self::key_id(key) == key_id)
+                    .ok_or_else(|| anyhow!("No configured encryption key with id {}", key_id))?;
+                decrypt_data(encrypted, key)
+            }
+            None => self
+                .keys()
+                .find_map(|key| decrypt_data(stored, key).ok())
+                .ok_or_else(|| anyhow!("Decryption failed with every configured key")),
+        }
+    }
+
+    pub fn decrypt_opt(&self, stored: Option<&str>) -> Result<Option<String>> {
+        stored.map(|value| self.decrypt(value)).transpose()
+    }
+
+    /// True when a stored value is plaintext or was written under a key other
+    /// than the current one
+    pub fn needs_rotation(&self, stored: &str) -> bool {
+        if stored.is_empty() {
+            return false;
+        }
+        match stored.split_once(KEY_ID_SEPARATOR) {
+            Some((key_id, _)) => key_id != self.current_key_id(),
+            None => true,
+        }
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.current).chain(self.previous.iter())
+    }
+}
+
+/// Deterministic SHA-256 lookup hash for a high-entropy secret (e.g. an OAuth
+/// token) whose stored copy is encrypted with a random nonce
+pub fn lookup_hash(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,6 +213,28 @@ mod tests {
         assert!(result.is_err());
     }
     
+    #[test]
+    fn test_field_cipher_reads_previous_keys_and_plaintext() {
+        let old_key = generate_test_key();
+        let new_key = generate_test_key();
+        let old = FieldCipher::new(old_key.clone(), vec![]);
+        let rotated = FieldCipher::new(new_key, vec![old_key.clone()]);
+
+        let under_old = old.encrypt("alice@example.com").unwrap();
+        assert!(rotated.needs_rotation(&under_old));
+        assert_eq!(rotated.decrypt(&under_old).unwrap(), "alice@example.com");
+
+        let legacy = encrypt_data("alice@example.com", &old_key).unwrap();
+        assert_eq!(rotated.decrypt(&legacy).unwrap(), "alice@example.com");
+
+        assert!(rotated.needs_rotation("alice@example.com"));
+        assert_eq!(rotated.decrypt("alice@example.com").unwrap(), "alice@example.com");
+
+        let under_new = rotated.encrypt("alice@example.com").unwrap();
+        assert!(!rotated.needs_rotation(&under_new));
+        assert!(FieldCipher::new(generate_test_key(), vec![]).decrypt(&under_new).is_err());
+    }
+
     #[test]
     fn test_empty_string() {
         let key = generate_test_key();
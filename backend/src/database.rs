@@ -1,12 +1,15 @@
 use crate::admin_audit_log::AdminAuditLogger;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use sqlx::SqlitePool;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, SqlitePool};
+use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
 use uuid::Uuid;
 
 use crate::analytics::compute_anchor_metrics;
+use crate::crypto::FieldCipher;
 use crate::models::api_key::{
     generate_api_key, hash_api_key, ApiKey, ApiKeyInfo, CreateApiKeyRequest, CreateApiKeyResponse,
 };
@@ -14,6 +17,11 @@ use crate::models::{
     Anchor, AnchorDetailResponse, AnchorMetricsHistory, Asset, CorridorRecord, CreateAnchorRequest,
     MetricRecord, MuxedAccountAnalytics, MuxedAccountUsage, SnapshotRecord,
 };
+use crate::tenant::TenantScope;
+
+/// Key `Database::new` encrypts PII columns with until `with_cipher` is
+/// called; public, so only for tests and local tools
+const DEVELOPMENT_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000000";
 
 /// Configuration for database connection pool
 #[derive(Debug, Clone)]
@@ -23,6 +31,8 @@ pub struct PoolConfig {
     pub connect_timeout_seconds: u64,
     pub idle_timeout_seconds: u64,
     pub max_lifetime_seconds: u64,
+    /// Statements slower than this are logged and tracked as slow queries
+    pub slow_query_threshold_ms: u64,
 }
 
 impl Default for PoolConfig {
@@ -33,12 +43,15 @@ impl Default for PoolConfig {
             connect_timeout_seconds: 30,
             idle_timeout_seconds: 600,
             max_lifetime_seconds: 1800,
+            slow_query_threshold_ms: crate::observability::slow_queries::DEFAULT_THRESHOLD_MS,
         }
     }
 }
 
 impl PoolConfig {
-    /// Load pool configuration from environment variables
+    /// Load pool configuration from environment variables. The slow query
+    /// threshold stays at its default; the server takes it from
+    /// [`DatabaseSettings`](crate::config::DatabaseSettings).
     pub fn from_env() -> Self {
         Self {
             max_connections: std::env::var("DB_POOL_MAX_CONNECTIONS")
@@ -61,18 +74,24 @@ impl PoolConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(1800),
+            slow_query_threshold_ms: crate::observability::slow_queries::DEFAULT_THRESHOLD_MS,
         }
     }
 
-    /// Create a configured SQLite pool with these settings
+    /// Create a configured SQLite pool with these settings. Statements slower
+    /// than `slow_query_threshold_ms` are logged at `warn` on `sqlx::query`.
     pub async fn create_pool(&self, database_url: &str) -> Result<SqlitePool> {
+        let options = SqliteConnectOptions::from_str(database_url)?.log_slow_statements(
+            log::LevelFilter::Warn,
+            Duration::from_millis(self.slow_query_threshold_ms),
+        );
         let pool = sqlx::sqlite::SqlitePoolOptions::new()
             .max_connections(self.max_connections)
             .min_connections(self.min_connections)
             .acquire_timeout(Duration::from_secs(self.connect_timeout_seconds))
             .idle_timeout(Some(Duration::from_secs(self.idle_timeout_seconds)))
             .max_lifetime(Some(Duration::from_secs(self.max_lifetime_seconds)))
-            .connect(database_url)
+            .connect_with(options)
             .await?;
 
         Ok(pool)
@@ -109,32 +128,184 @@ pub struct AnchorMetricsParams {
 pub struct PoolMetrics {
     pub size: u32,
     pub idle: usize,
+    pub in_use: u32,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// Share of `max_connections` currently checked out (0.0 - 1.0)
+    pub utilization: f64,
+    pub acquire_timeout_seconds: u64,
+    pub idle_timeout_seconds: Option<u64>,
+    pub max_lifetime_seconds: Option<u64>,
 }
 
 pub struct Database {
     pool: SqlitePool,
     pub admin_audit_logger: AdminAuditLogger,
+    cipher: FieldCipher,
 }
 
 impl Database {
     pub fn new(pool: SqlitePool) -> Self {
         let admin_audit_logger = AdminAuditLogger::new(pool.clone());
-        Self { pool, admin_audit_logger }
+        Self {
+            pool,
+            admin_audit_logger,
+            cipher: FieldCipher::new(DEVELOPMENT_KEY, Vec::new()),
+        }
+    }
+
+    /// Encrypt PII columns with `cipher` instead of the development key
+    pub fn with_cipher(mut self, cipher: FieldCipher) -> Self {
+        self.cipher = cipher;
+        self
     }
 
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
 
+    pub fn cipher(&self) -> &FieldCipher {
+        &self.cipher
+    }
+
     pub fn corridor_aggregates(&self) -> crate::db::aggregates::CorridorAggregates {
         crate::db::aggregates::CorridorAggregates::new(self.pool.clone())
     }
 
+    pub fn anchor_merges(&self) -> crate::db::anchor_merge::AnchorMerges {
+        crate::db::anchor_merge::AnchorMerges::new(self.pool.clone())
+    }
+
+    pub fn entity_search(&self) -> crate::db::search::EntitySearch {
+        crate::db::search::EntitySearch::new(self.pool.clone())
+    }
+
+    pub fn asset_supply(&self) -> crate::db::asset_supply::AssetSupplyHistory {
+        crate::db::asset_supply::AssetSupplyHistory::new(self.pool.clone())
+    }
+
+    pub fn corridor_concentrations(&self) -> crate::db::concentration::CorridorConcentrations {
+        crate::db::concentration::CorridorConcentrations::new(self.pool.clone())
+    }
+
+    pub fn corridor_cohorts(&self) -> crate::db::corridor_cohorts::CorridorCohorts {
+        crate::db::corridor_cohorts::CorridorCohorts::new(self.pool.clone())
+    }
+
+    pub fn corridor_summaries(&self) -> crate::db::corridor_summaries::CorridorSummaries {
+        crate::db::corridor_summaries::CorridorSummaries::new(self.pool.clone())
+    }
+
+    pub fn corridor_health_scores(&self) -> crate::db::health_scores::CorridorHealthScores {
+        crate::db::health_scores::CorridorHealthScores::new(self.pool.clone())
+    }
+
+    pub fn surveillance_flags(&self) -> crate::db::surveillance::SurveillanceFlags {
+        crate::db::surveillance::SurveillanceFlags::new(self.pool.clone())
+    }
+
+    pub fn anomaly_models(&self) -> crate::db::anomaly_models::AnomalyModels {
+        crate::db::anomaly_models::AnomalyModels::new(self.pool.clone())
+    }
+
+    pub fn payment_settlements(&self) -> crate::db::settlements::PaymentSettlements {
+        crate::db::settlements::PaymentSettlements::new(self.pool.clone())
+    }
+
+    pub fn ledger_gaps(&self) -> crate::db::ledger_gaps::LedgerGaps {
+        crate::db::ledger_gaps::LedgerGaps::new(self.pool.clone())
+    }
+
+    pub fn tenants(&self) -> crate::db::tenants::Tenants {
+        crate::db::tenants::Tenants::new(self.pool.clone())
+    }
+
+    pub fn price_history(&self) -> crate::db::price_history::PriceHistory {
+        crate::db::price_history::PriceHistory::new(self.pool.clone())
+    }
+
+    pub fn preferences(&self) -> crate::preferences::PreferencesStore {
+        crate::preferences::PreferencesStore::new(self.pool.clone(), self.cipher.clone())
+    }
+
+    pub fn status(&self) -> crate::db::status::StatusStore {
+        crate::db::status::StatusStore::new(self.pool.clone())
+    }
+
+    pub fn anchor_claims(&self) -> crate::db::anchor_claims::AnchorClaimStore {
+        crate::db::anchor_claims::AnchorClaimStore::new(self.pool.clone())
+    }
+
+    pub fn self_reported_metrics(&self) -> crate::db::self_reported::SelfReportedMetricsStore {
+        crate::db::self_reported::SelfReportedMetricsStore::new(self.pool.clone())
+    }
+
+    pub fn sep_performance(&self) -> crate::db::sep_performance::SepPerformanceStore {
+        crate::db::sep_performance::SepPerformanceStore::new(self.pool.clone())
+    }
+
+    pub fn sep_transactions(&self) -> crate::db::sep_transactions::SepTransactionStore {
+        crate::db::sep_transactions::SepTransactionStore::new(
+            self.pool.clone(),
+            self.cipher.clone(),
+        )
+    }
+
+    pub fn sessions(&self) -> crate::db::sessions::SessionStore {
+        crate::db::sessions::SessionStore::new(self.pool.clone())
+    }
+
     /// Get connection pool metrics
     pub fn pool_metrics(&self) -> PoolMetrics {
+        let options = self.pool.options();
+        let size = self.pool.size();
+        let idle = self.pool.num_idle();
+        let in_use = size.saturating_sub(idle as u32);
+        let max_connections = options.get_max_connections();
+
         PoolMetrics {
-            size: self.pool.size(),
-            idle: self.pool.num_idle(),
+            size,
+            idle,
+            in_use,
+            max_connections,
+            min_connections: options.get_min_connections(),
+            utilization: if max_connections == 0 {
+                0.0
+            } else {
+                f64::from(in_use) / f64::from(max_connections)
+            },
+            acquire_timeout_seconds: options.get_acquire_timeout().as_secs(),
+            idle_timeout_seconds: options.get_idle_timeout().map(|d| d.as_secs()),
+            max_lifetime_seconds: options.get_max_lifetime().map(|d| d.as_secs()),
+        }
+    }
+
+    /// Time one connection checkout and refresh the pool gauges. The wait is
+    /// recorded as `db_pool_acquire_wait_seconds`; a checkout that hits the
+    /// acquire timeout is counted as a `db_pool_acquire_timeout` error.
+    pub async fn probe_pool(&self) -> Result<Duration> {
+        let metrics = self.pool_metrics();
+        crate::observability::metrics::set_db_pool_stats(
+            metrics.in_use,
+            metrics.idle as u32,
+            metrics.max_connections,
+        );
+
+        let start = Instant::now();
+        let conn = self.pool.acquire().await;
+        let waited = start.elapsed();
+        match conn {
+            Ok(conn) => {
+                drop(conn);
+                crate::observability::metrics::observe_db_pool_acquire(waited.as_secs_f64());
+                Ok(waited)
+            }
+            Err(e) => {
+                if matches!(e, sqlx::Error::PoolTimedOut) {
+                    crate::observability::metrics::record_error("db_pool_acquire_timeout");
+                }
+                Err(e.into())
+            }
         }
     }
 
@@ -158,10 +329,17 @@ impl Database {
         Ok(anchor)
     }
 
+    /// Look up an anchor by id, following aliases left by anchor merges
     pub async fn get_anchor_by_id(&self, id: Uuid) -> Result<Option<Anchor>> {
         let anchor = sqlx::query_as::<_, Anchor>(
             r#"
-            SELECT * FROM anchors WHERE id = $1
+            SELECT * FROM anchors
+            WHERE id = $1
+               OR id = (
+                   SELECT anchor_id FROM anchor_aliases
+                   WHERE alias_type = 'anchor_id' AND alias_value = $1
+               )
+            LIMIT 1
             "#,
         )
         .bind(id.to_string())
@@ -171,13 +349,20 @@ impl Database {
         Ok(anchor)
     }
 
+    /// Look up an anchor by account, following aliases left by anchor merges
     pub async fn get_anchor_by_stellar_account(
         &self,
         stellar_account: &str,
     ) -> Result<Option<Anchor>> {
         let anchor = sqlx::query_as::<_, Anchor>(
             r#"
-            SELECT * FROM anchors WHERE stellar_account = $1
+            SELECT * FROM anchors
+            WHERE stellar_account = $1
+               OR id = (
+                   SELECT anchor_id FROM anchor_aliases
+                   WHERE alias_type = 'stellar_account' AND alias_value = $1
+               )
+            LIMIT 1
             "#,
         )
         .bind(stellar_account)
@@ -187,17 +372,25 @@ impl Database {
         Ok(anchor)
     }
 
-    pub async fn list_anchors(&self, limit: i64, offset: i64) -> Result<Vec<Anchor>> {
+    pub async fn list_anchors(
+        &self,
+        limit: i64,
+        offset: i64,
+        tenant: &TenantScope,
+    ) -> Result<Vec<Anchor>> {
         let start = Instant::now();
         let anchors = sqlx::query_as::<_, Anchor>(
             r#"
             SELECT * FROM anchors
+            WHERE $3 IS NULL
+               OR id IN (SELECT anchor_id FROM tenant_anchors WHERE tenant_id = $3)
             ORDER BY reliability_score DESC, updated_at DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(limit)
         .bind(offset)
+        .bind(tenant.id())
         .fetch_all(&self.pool)
         .await?;
 
@@ -209,6 +402,9 @@ impl Database {
         Ok(anchors)
     }
 
+    /// Update an anchor's metrics, only if its version still equals
+    /// `expected_version` when one is given. Returns `None` if nothing was written.
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_anchor_metrics(
         &self,
         anchor_id: Uuid,
@@ -217,7 +413,8 @@ impl Database {
         failed_transactions: i64,
         avg_settlement_time_ms: Option<i32>,
         volume_usd: Option<f64>,
-    ) -> Result<Anchor> {
+        expected_version: Option<i64>,
+    ) -> Result<Option<Anchor>> {
         // Compute metrics
         let metrics = compute_anchor_metrics(
             total_transactions,
@@ -237,8 +434,9 @@ impl Database {
                 reliability_score = $5,
                 status = $6,
                 total_volume_usd = COALESCE($7, total_volume_usd),
-                updated_at = $8
-            WHERE id = $9
+                updated_at = $8,
+                version = version + 1
+            WHERE id = $9 AND ($10 IS NULL OR version = $10)
             RETURNING *
             "#,
         )
@@ -251,9 +449,15 @@ impl Database {
         .bind(volume_usd.unwrap_or(0.0))
         .bind(Utc::now())
         .bind(anchor_id.to_string())
-        .fetch_one(&self.pool)
+        .bind(expected_version)
+        .fetch_optional(&self.pool)
         .await?;
 
+        // Version mismatch (or the anchor is gone): nothing was written
+        let Some(anchor) = anchor else {
+            return Ok(None);
+        };
+
         // Record metrics history
         self.record_anchor_metrics_history(AnchorMetricsParams {
             anchor_id,
@@ -268,6 +472,33 @@ impl Database {
         })
         .await?;
 
+        Ok(Some(anchor))
+    }
+
+    /// Update an anchor's descriptive fields; `None` leaves a field unchanged
+    pub async fn update_anchor_metadata(
+        &self,
+        anchor_id: Uuid,
+        name: Option<&str>,
+        home_domain: Option<&str>,
+    ) -> Result<Option<Anchor>> {
+        let anchor = sqlx::query_as::<_, Anchor>(
+            r#"
+            UPDATE anchors
+            SET name = COALESCE($1, name),
+                home_domain = COALESCE($2, home_domain),
+                updated_at = $3
+            WHERE id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .bind(home_domain)
+        .bind(Utc::now())
+        .bind(anchor_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
         Ok(anchor)
     }
 
@@ -313,6 +544,32 @@ impl Database {
         Ok(assets)
     }
 
+    /// Anchors issuing either asset of a corridor, as (anchor id, anchor
+    /// name, asset code)
+    pub async fn get_corridor_anchors(
+        &self,
+        corridor: &crate::models::corridor::Corridor,
+    ) -> Result<Vec<(String, String, String)>> {
+        let anchors = sqlx::query_as(
+            r#"
+            SELECT an.id, an.name, a.asset_code
+            FROM assets a
+            JOIN anchors an ON an.id = a.anchor_id
+            WHERE (a.asset_code = $1 AND a.asset_issuer = $2)
+               OR (a.asset_code = $3 AND a.asset_issuer = $4)
+            ORDER BY an.name, a.asset_code
+            "#,
+        )
+        .bind(&corridor.asset_a_code)
+        .bind(&corridor.asset_a_issuer)
+        .bind(&corridor.asset_b_code)
+        .bind(&corridor.asset_b_issuer)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(anchors)
+    }
+
     pub async fn count_assets_by_anchor(&self, anchor_id: Uuid) -> Result<i64> {
         let count: (i64,) = sqlx::query_as(
             r#"
@@ -420,11 +677,13 @@ impl Database {
 
         let assets = self.get_assets_by_anchor(anchor_id).await?;
         let metrics_history = self.get_anchor_metrics_history(anchor_id, 30).await?;
+        let self_reported = self.self_reported_metrics().recent(&anchor.id, 30).await?;
 
         Ok(Some(AnchorDetailResponse {
             anchor,
             assets,
             metrics_history,
+            self_reported,
         }))
     }
 
@@ -463,39 +722,58 @@ impl Database {
         Ok(corridor)
     }
 
+    /// Corridors by reliability, read from the precomputed summaries. Newly
+    /// created corridors appear once the next refresh has run.
     pub async fn list_corridors(
         &self,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<crate::models::corridor::Corridor>> {
-        let start = Instant::now();
-        let records = sqlx::query_as::<_, CorridorRecord>(
-            r#"
-            SELECT * FROM corridors ORDER BY reliability_score DESC LIMIT $1 OFFSET $2
-            "#,
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
-
-        let corridors = records
+        let corridors = self
+            .list_corridor_summaries(limit, offset, &TenantScope::All)
+            .await?
             .into_iter()
-            .map(|r| {
+            .map(|s| {
                 crate::models::corridor::Corridor::new(
-                    r.source_asset_code,
-                    r.source_asset_issuer,
-                    r.destination_asset_code,
-                    r.destination_asset_issuer,
+                    s.source_asset_code,
+                    s.source_asset_issuer,
+                    s.destination_asset_code,
+                    s.destination_asset_issuer,
                 )
             })
-            .collect::<Vec<_>>();
+            .collect();
+        Ok(corridors)
+    }
+
+    pub async fn list_corridor_summaries(
+        &self,
+        limit: i64,
+        offset: i64,
+        tenant: &TenantScope,
+    ) -> Result<Vec<crate::db::corridor_summaries::CorridorSummary>> {
+        let start = Instant::now();
+        let summaries = self
+            .corridor_summaries()
+            .list(limit, offset, tenant.id())
+            .await?;
         crate::observability::metrics::observe_db_query(
             "list_corridors",
             "success",
             start.elapsed().as_secs_f64(),
         );
-        Ok(corridors)
+        Ok(summaries)
+    }
+
+    /// Rebuild the corridor summaries from the latest metrics
+    pub async fn refresh_corridor_summaries(&self) -> Result<u64> {
+        let start = Instant::now();
+        let rows = self.corridor_summaries().refresh().await?;
+        crate::observability::metrics::observe_db_query(
+            "refresh_corridor_summaries",
+            "success",
+            start.elapsed().as_secs_f64(),
+        );
+        Ok(rows)
     }
 
     pub async fn get_corridor_by_id(
@@ -521,31 +799,45 @@ impl Database {
         }))
     }
 
+    /// Raw corridor row, including its `version`
+    pub async fn get_corridor_record(&self, id: Uuid) -> Result<Option<CorridorRecord>> {
+        let record = sqlx::query_as::<_, CorridorRecord>(
+            r#"
+            SELECT * FROM corridors WHERE id = $1
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Update a corridor's metrics, only if its version still equals
+    /// `expected_version` when one is given. Returns `None` if nothing was written.
     pub async fn update_corridor_metrics(
         &self,
         id: Uuid,
         metrics: crate::models::corridor::CorridorMetrics,
-    ) -> Result<crate::models::corridor::Corridor> {
+        expected_version: Option<i64>,
+    ) -> Result<Option<CorridorRecord>> {
         let record = sqlx::query_as::<_, CorridorRecord>(
             r#"
             UPDATE corridors
             SET reliability_score = $1,
-                updated_at = CURRENT_TIMESTAMP
-            WHERE id = $2
+                updated_at = CURRENT_TIMESTAMP,
+                version = version + 1
+            WHERE id = $2 AND ($3 IS NULL OR version = $3)
             RETURNING *
             "#,
         )
         .bind(metrics.success_rate)
         .bind(id.to_string())
-        .fetch_one(&self.pool)
+        .bind(expected_version)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(crate::models::corridor::Corridor::new(
-            record.source_asset_code,
-            record.source_asset_issuer,
-            record.destination_asset_code,
-            record.destination_asset_issuer,
-        ))
+        Ok(record)
     }
 
     // Generic Metric operations
@@ -1119,7 +1411,7 @@ impl Database {
 
         self.revoke_api_key(id, wallet_address).await?;
 
-        let new_key = self
+        let mut new_key = self
             .create_api_key(
                 wallet_address,
                 CreateApiKeyRequest {
@@ -1130,6 +1422,14 @@ impl Database {
             )
             .await?;
 
+        // The replacement key stays in the same tenant
+        if let Some(tenant_id) = old_key.tenant_id {
+            self.tenants()
+                .assign_api_key(&new_key.key.id, &tenant_id)
+                .await?;
+            new_key.key.tenant_id = Some(tenant_id);
+        }
+
         Ok(Some(new_key))
     }
 }
@@ -3,6 +3,7 @@ use chrono::NaiveDate;
 use sqlx::SqlitePool;
 
 use crate::models::corridor::{Corridor, CorridorAnalytics, CorridorMetrics};
+use crate::models::MetricSource;
 
 pub struct CorridorAggregates {
     pool: SqlitePool,
@@ -26,15 +27,16 @@ impl CorridorAggregates {
             INSERT INTO corridor_metrics (
                 corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
                 date, total_transactions, successful_transactions, failed_transactions,
-                success_rate, volume_usd
+                success_rate, volume_usd, source
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT (corridor_key, date) DO UPDATE SET
                 total_transactions = EXCLUDED.total_transactions,
                 successful_transactions = EXCLUDED.successful_transactions,
                 failed_transactions = EXCLUDED.failed_transactions,
                 success_rate = EXCLUDED.success_rate,
                 volume_usd = EXCLUDED.volume_usd,
+                source = EXCLUDED.source,
                 updated_at = CURRENT_TIMESTAMP
             RETURNING *
             "#,
@@ -50,6 +52,7 @@ impl CorridorAggregates {
         .bind(analytics.failed_transactions)
         .bind(analytics.success_rate)
         .bind(analytics.volume_usd)
+        .bind(analytics.source)
         .fetch_one(&self.pool)
         .await?;
 
@@ -112,7 +115,8 @@ impl CorridorAggregates {
         let start_datetime = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let end_datetime = end_date.and_hms_opt(23, 59, 59).unwrap().and_utc();
 
-        let metrics = sqlx::query_as::<_, AggregatedCorridorMetrics>(
+        // An aggregate is labelled with its least trusted day
+        let sql = format!(
             r#"
             SELECT
                 corridor_key,
@@ -125,17 +129,23 @@ impl CorridorAggregates {
                 SUM(failed_transactions) as failed_transactions,
                 AVG(success_rate) as avg_success_rate,
                 SUM(volume_usd) as total_volume_usd,
-                MAX(date) as latest_date
+                MAX(date) as latest_date,
+                {} as source
             FROM corridor_metrics
             WHERE date >= ? AND date <= ?
             GROUP BY corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer
             ORDER BY total_volume_usd DESC
             "#,
-        )
-        .bind(start_datetime)
-        .bind(end_datetime)
-        .fetch_all(&self.pool)
-        .await?;
+            MetricSource::from_trust_rank_sql(&format!(
+                "MIN({})",
+                MetricSource::trust_rank_sql("source")
+            ))
+        );
+        let metrics = sqlx::query_as::<_, AggregatedCorridorMetrics>(&sql)
+            .bind(start_datetime)
+            .bind(end_datetime)
+            .fetch_all(&self.pool)
+            .await?;
 
         Ok(metrics)
     }
@@ -277,6 +287,7 @@ pub struct AggregatedCorridorMetrics {
     pub avg_success_rate: f64,
     pub total_volume_usd: f64,
     pub latest_date: chrono::DateTime<chrono::Utc>,
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone, sqlx::FromRow)]
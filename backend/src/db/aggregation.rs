@@ -2,8 +2,16 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 
+use crate::models::MetricSource;
 use crate::services::aggregation::HourlyCorridorMetrics;
 
+/// SQL expression for the corridor key of a `payments` row: its asset on
+/// both sides, with a missing asset spelled `XLM:native`, as
+/// [`AggregationDb::fetch_payments_by_timerange`] attributes payments
+pub const PAYMENT_CORRIDOR_KEY_SQL: &str =
+    "COALESCE(asset_code, 'XLM') || ':' || COALESCE(asset_issuer, 'native') \
+     || '->' || COALESCE(asset_code, 'XLM') || ':' || COALESCE(asset_issuer, 'native')";
+
 pub struct AggregationDb {
     pool: SqlitePool,
 }
@@ -31,7 +39,8 @@ impl AggregationDb {
                 asset_code,
                 asset_issuer,
                 amount,
-                created_at
+                created_at,
+                source
             FROM payments
             WHERE created_at >= ? AND created_at <= ?
             ORDER BY created_at ASC
@@ -74,6 +83,7 @@ impl AggregationDb {
                     timestamp,
                     submission_time: None,
                     confirmation_time: None,
+                    source: row.source,
                 })
             })
             .collect();
@@ -88,7 +98,8 @@ impl AggregationDb {
     ) -> Result<()> {
         let now = Utc::now().to_rfc3339();
 
-        sqlx::query(
+        // Merged rows keep the less trusted of the two sources
+        let sql = format!(
             r#"
             INSERT INTO corridor_metrics_hourly (
                 id,
@@ -106,9 +117,10 @@ impl AggregationDb {
                 avg_slippage_bps,
                 avg_settlement_latency_ms,
                 liquidity_depth_usd,
+                source,
                 created_at,
                 updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(corridor_key, hour_bucket) DO UPDATE SET
                 total_transactions = total_transactions + excluded.total_transactions,
                 successful_transactions = successful_transactions + excluded.successful_transactions,
@@ -122,34 +134,107 @@ impl AggregationDb {
                     excluded.avg_settlement_latency_ms
                 ),
                 liquidity_depth_usd = (liquidity_depth_usd + excluded.liquidity_depth_usd) / 2.0,
+                source = CASE WHEN {} < {} THEN excluded.source ELSE source END,
                 updated_at = ?
             "#,
-        )
-        .bind(&metric.id)
-        .bind(&metric.corridor_key)
-        .bind(&metric.asset_a_code)
-        .bind(&metric.asset_a_issuer)
-        .bind(&metric.asset_b_code)
-        .bind(&metric.asset_b_issuer)
-        .bind(metric.hour_bucket.to_rfc3339())
-        .bind(metric.total_transactions)
-        .bind(metric.successful_transactions)
-        .bind(metric.failed_transactions)
-        .bind(metric.success_rate)
-        .bind(metric.volume_usd)
-        .bind(metric.avg_slippage_bps)
-        .bind(metric.avg_settlement_latency_ms)
-        .bind(metric.liquidity_depth_usd)
-        .bind(&now)
-        .bind(&now)
-        .bind(&now)
-        .execute(&self.pool)
-        .await
-        .context("Failed to upsert hourly corridor metric")?;
+            MetricSource::trust_rank_sql("excluded.source"),
+            MetricSource::trust_rank_sql("source")
+        );
+        sqlx::query(&sql)
+            .bind(&metric.id)
+            .bind(&metric.corridor_key)
+            .bind(&metric.asset_a_code)
+            .bind(&metric.asset_a_issuer)
+            .bind(&metric.asset_b_code)
+            .bind(&metric.asset_b_issuer)
+            .bind(metric.hour_bucket.to_rfc3339())
+            .bind(metric.total_transactions)
+            .bind(metric.successful_transactions)
+            .bind(metric.failed_transactions)
+            .bind(metric.success_rate)
+            .bind(metric.volume_usd)
+            .bind(metric.avg_slippage_bps)
+            .bind(metric.avg_settlement_latency_ms)
+            .bind(metric.liquidity_depth_usd)
+            .bind(metric.source)
+            .bind(&now)
+            .bind(&now)
+            .bind(&now)
+            .execute(&self.pool)
+            .await
+            .context("Failed to upsert hourly corridor metric")?;
 
         Ok(())
     }
 
+    /// Replace every hourly row in `hour_bucket` with `metrics`, atomically.
+    /// Unlike the upsert, counts are not added to what is already stored.
+    pub async fn replace_hourly_corridor_metrics(
+        &self,
+        hour_bucket: DateTime<Utc>,
+        metrics: &[HourlyCorridorMetrics],
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM corridor_metrics_hourly WHERE hour_bucket = ?")
+            .bind(hour_bucket.to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear hourly corridor metrics")?;
+
+        for metric in metrics {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics_hourly (
+                    id,
+                    corridor_key,
+                    asset_a_code,
+                    asset_a_issuer,
+                    asset_b_code,
+                    asset_b_issuer,
+                    hour_bucket,
+                    total_transactions,
+                    successful_transactions,
+                    failed_transactions,
+                    success_rate,
+                    volume_usd,
+                    avg_slippage_bps,
+                    avg_settlement_latency_ms,
+                    liquidity_depth_usd,
+                    source,
+                    created_at,
+                    updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&metric.id)
+            .bind(&metric.corridor_key)
+            .bind(&metric.asset_a_code)
+            .bind(&metric.asset_a_issuer)
+            .bind(&metric.asset_b_code)
+            .bind(&metric.asset_b_issuer)
+            .bind(hour_bucket.to_rfc3339())
+            .bind(metric.total_transactions)
+            .bind(metric.successful_transactions)
+            .bind(metric.failed_transactions)
+            .bind(metric.success_rate)
+            .bind(metric.volume_usd)
+            .bind(metric.avg_slippage_bps)
+            .bind(metric.avg_settlement_latency_ms)
+            .bind(metric.liquidity_depth_usd)
+            .bind(metric.source)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert hourly corridor metric")?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// Fetch hourly metrics by time range
     pub async fn fetch_hourly_metrics_by_timerange(
         &self,
@@ -173,7 +258,8 @@ impl AggregationDb {
                 volume_usd,
                 avg_slippage_bps,
                 avg_settlement_latency_ms,
-                liquidity_depth_usd
+                liquidity_depth_usd,
+                source
             FROM corridor_metrics_hourly
             WHERE hour_bucket >= ? AND hour_bucket <= ?
             ORDER BY hour_bucket ASC
@@ -208,6 +294,7 @@ impl AggregationDb {
                     avg_slippage_bps: row.avg_slippage_bps,
                     avg_settlement_latency_ms: row.avg_settlement_latency_ms,
                     liquidity_depth_usd: row.liquidity_depth_usd,
+                    source: row.source,
                 })
             })
             .collect();
@@ -215,6 +302,66 @@ impl AggregationDb {
         Ok(metrics)
     }
 
+    /// USD volume of one corridor per hour bucket in `[start_time, end_time)`.
+    /// Hours without a row had no traffic and are left out.
+    pub async fn fetch_corridor_hourly_volume(
+        &self,
+        corridor_key: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, f64)>> {
+        let rows = sqlx::query_as::<_, (String, f64)>(
+            r#"
+            SELECT hour_bucket, COALESCE(volume_usd, 0)
+            FROM corridor_metrics_hourly
+            WHERE corridor_key = ? AND hour_bucket >= ? AND hour_bucket < ?
+            ORDER BY hour_bucket ASC
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(start_time.to_rfc3339())
+        .bind(end_time.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch corridor hourly volume")?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(hour_bucket, volume)| {
+                let hour_bucket = DateTime::parse_from_rfc3339(&hour_bucket)
+                    .ok()?
+                    .with_timezone(&Utc);
+                Some((hour_bucket, volume))
+            })
+            .collect())
+    }
+
+    /// Per-corridor totals over the hour buckets in `[start_time, end_time)`
+    pub async fn fetch_corridor_window_totals(
+        &self,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<CorridorWindowTotals>> {
+        sqlx::query_as::<_, CorridorWindowTotals>(
+            r#"
+            SELECT
+                corridor_key,
+                SUM(total_transactions) as total_transactions,
+                SUM(successful_transactions) as successful_transactions,
+                SUM(volume_usd) as volume_usd,
+                AVG(avg_slippage_bps) as avg_slippage_bps
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= ? AND hour_bucket < ?
+            GROUP BY corridor_key
+            "#,
+        )
+        .bind(start_time.to_rfc3339())
+        .bind(end_time.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch corridor window totals")
+    }
+
     /// Create aggregation job record
     pub async fn create_aggregation_job(&self, job_id: &str, job_type: &str) -> Result<()> {
         let now = Utc::now().to_rfc3339();
@@ -331,6 +478,15 @@ impl AggregationDb {
 }
 
 // Database row structures
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CorridorWindowTotals {
+    pub corridor_key: String,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub volume_usd: f64,
+    pub avg_slippage_bps: f64,
+}
+
 // Note: Some fields are fetched from DB for completeness but not used in Rust code.
 // They're kept for potential future use and to match the SQL SELECT statement.
 #[derive(sqlx::FromRow)]
@@ -348,6 +504,7 @@ struct PaymentRecordRow {
     asset_issuer: Option<String>,
     amount: f64,
     created_at: String,
+    source: MetricSource,
 }
 
 #[derive(sqlx::FromRow)]
@@ -367,4 +524,5 @@ struct HourlyCorridorMetricsRow {
     avg_slippage_bps: f64,
     avg_settlement_latency_ms: Option<i32>,
     liquidity_depth_usd: f64,
+    source: MetricSource,
 }
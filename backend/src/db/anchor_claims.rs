@@ -0,0 +1,162 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Scope allowing an operator to edit the anchor's name and home domain
+pub const SCOPE_METADATA: &str = "anchor:metadata";
+
+/// Scope allowing an operator to submit self-reported off-chain metrics
+pub const SCOPE_METRICS: &str = "anchor:metrics";
+
+/// Scopes granted by a verified claim
+pub const CLAIM_SCOPES: &[&str] = &[SCOPE_METADATA, SCOPE_METRICS];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnchorClaim {
+    pub id: String,
+    pub anchor_id: String,
+    pub user_id: String,
+    pub method: String,
+    pub challenge: String,
+    pub status: String,
+    /// Why the most recent verification attempt failed
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnchorOperator {
+    pub anchor_id: String,
+    pub user_id: String,
+    /// Comma-separated scopes
+    pub scopes: String,
+    pub claim_id: Option<String>,
+    pub granted_at: DateTime<Utc>,
+}
+
+impl AnchorOperator {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.split(',').any(|s| s.trim() == scope)
+    }
+}
+
+pub struct AnchorClaimStore {
+    pool: SqlitePool,
+}
+
+impl AnchorClaimStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        anchor_id: &str,
+        user_id: &str,
+        method: &str,
+        challenge: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<AnchorClaim> {
+        let claim = sqlx::query_as::<_, AnchorClaim>(
+            r#"
+            INSERT INTO anchor_claims (id, anchor_id, user_id, method, challenge, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(anchor_id)
+        .bind(user_id)
+        .bind(method)
+        .bind(challenge)
+        .bind(Utc::now())
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(claim)
+    }
+
+    pub async fn get(&self, claim_id: &str) -> Result<Option<AnchorClaim>> {
+        let claim = sqlx::query_as::<_, AnchorClaim>("SELECT * FROM anchor_claims WHERE id = $1")
+            .bind(claim_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(claim)
+    }
+
+    pub async fn record_failure(&self, claim_id: &str, error: &str) -> Result<()> {
+        sqlx::query("UPDATE anchor_claims SET last_error = $1 WHERE id = $2")
+            .bind(error)
+            .bind(claim_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mark the claim verified and make its user an operator of the anchor
+    pub async fn grant(&self, claim: &AnchorClaim) -> Result<AnchorOperator> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE anchor_claims SET status = 'verified', last_error = NULL, verified_at = $1 WHERE id = $2",
+        )
+        .bind(now)
+        .bind(&claim.id)
+        .execute(&mut *tx)
+        .await?;
+
+        let operator = sqlx::query_as::<_, AnchorOperator>(
+            r#"
+            INSERT INTO anchor_operators (anchor_id, user_id, scopes, claim_id, granted_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(anchor_id, user_id) DO UPDATE
+            SET scopes = excluded.scopes,
+                claim_id = excluded.claim_id,
+                granted_at = excluded.granted_at
+            RETURNING *
+            "#,
+        )
+        .bind(&claim.anchor_id)
+        .bind(&claim.user_id)
+        .bind(CLAIM_SCOPES.join(","))
+        .bind(&claim.id)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(operator)
+    }
+
+    pub async fn operator(&self, anchor_id: &str, user_id: &str) -> Result<Option<AnchorOperator>> {
+        let operator = sqlx::query_as::<_, AnchorOperator>(
+            "SELECT * FROM anchor_operators WHERE anchor_id = $1 AND user_id = $2",
+        )
+        .bind(anchor_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(operator)
+    }
+
+    /// Anchors a user operates, most recently granted first
+    pub async fn list_for_user(&self, user_id: &str) -> Result<Vec<AnchorOperator>> {
+        let operators = sqlx::query_as::<_, AnchorOperator>(
+            "SELECT * FROM anchor_operators WHERE user_id = $1 ORDER BY granted_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(operators)
+    }
+}
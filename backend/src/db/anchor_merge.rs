@@ -0,0 +1,204 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::models::Anchor;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnchorAlias {
+    pub id: String,
+    pub anchor_id: String,
+    pub alias_type: String,
+    pub alias_value: String,
+    pub merged_from: String,
+    pub created_at: String,
+}
+
+/// Row counts moved from the duplicate to the surviving anchor
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnchorMergeSummary {
+    pub survivor_id: String,
+    pub merged_id: String,
+    pub assets_moved: u64,
+    pub metrics_history_moved: u64,
+    pub metrics_moved: u64,
+    pub aliases_created: u64,
+}
+
+pub struct AnchorMerges {
+    pool: SqlitePool,
+}
+
+impl AnchorMerges {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Fold `duplicate_id` into `survivor_id`.
+    ///
+    /// Assets, metrics history, generic anchor metrics, tags and tenant memberships
+    /// are reassigned to the survivor, the duplicate's id/account/domain become
+    /// aliases of the survivor, and the duplicate row is deleted. Corridors
+    /// reference assets by issuer rather than by anchor, so they follow the
+    /// moved assets automatically. Everything runs in a single transaction.
+    pub async fn merge(&self, survivor_id: &str, duplicate_id: &str) -> Result<AnchorMergeSummary> {
+        if survivor_id == duplicate_id {
+            bail!("Cannot merge an anchor into itself");
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let survivor = sqlx::query_as::<_, Anchor>("SELECT * FROM anchors WHERE id = $1")
+            .bind(survivor_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let duplicate = sqlx::query_as::<_, Anchor>("SELECT * FROM anchors WHERE id = $1")
+            .bind(duplicate_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let (Some(survivor), Some(duplicate)) = (survivor, duplicate) else {
+            bail!("Both anchors must exist to merge");
+        };
+
+        let assets_moved = sqlx::query("UPDATE assets SET anchor_id = $1, updated_at = CURRENT_TIMESTAMP WHERE anchor_id = $2")
+            .bind(&survivor.id)
+            .bind(&duplicate.id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let metrics_history_moved =
+            sqlx::query("UPDATE anchor_metrics_history SET anchor_id = $1 WHERE anchor_id = $2")
+                .bind(&survivor.id)
+                .bind(&duplicate.id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+
+        let metrics_moved = sqlx::query(
+            "UPDATE metrics SET entity_id = $1 WHERE entity_type = 'anchor' AND entity_id = $2",
+        )
+        .bind(&survivor.id)
+        .bind(&duplicate.id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        // Carry tags over; ones the survivor already has are dropped with the duplicate
+        sqlx::query(
+            "UPDATE OR IGNORE entity_tags SET entity_id = $1 WHERE entity_type = 'anchor' AND entity_id = $2",
+        )
+        .bind(&survivor.id)
+        .bind(&duplicate.id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Tenants that listed the duplicate now list the survivor
+        sqlx::query("UPDATE OR IGNORE tenant_anchors SET anchor_id = $1 WHERE anchor_id = $2")
+            .bind(&survivor.id)
+            .bind(&duplicate.id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Aliases that pointed at the duplicate now point at the survivor
+        sqlx::query("UPDATE anchor_aliases SET anchor_id = $1 WHERE anchor_id = $2")
+            .bind(&survivor.id)
+            .bind(&duplicate.id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut aliases = vec![
+            ("anchor_id", duplicate.id.clone()),
+            ("stellar_account", duplicate.stellar_account.clone()),
+        ];
+        if let Some(domain) = duplicate.home_domain.as_ref().filter(|d| !d.is_empty()) {
+            if survivor.home_domain.as_deref() != Some(domain.as_str()) {
+                aliases.push(("home_domain", domain.clone()));
+            }
+        }
+
+        let mut aliases_created = 0;
+        for (alias_type, alias_value) in aliases {
+            aliases_created += sqlx::query(
+                r#"
+                INSERT INTO anchor_aliases (id, anchor_id, alias_type, alias_value, merged_from)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (alias_type, alias_value) DO UPDATE SET anchor_id = EXCLUDED.anchor_id
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&survivor.id)
+            .bind(alias_type)
+            .bind(alias_value)
+            .bind(&duplicate.id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        // Fold transaction counters into the survivor; derived scores are
+        // recomputed by the next metrics sync
+        sqlx::query(
+            r#"
+            UPDATE anchors SET
+                total_transactions = total_transactions + $1,
+                successful_transactions = successful_transactions + $2,
+                failed_transactions = failed_transactions + $3,
+                total_volume_usd = total_volume_usd + $4,
+                home_domain = COALESCE(home_domain, $5),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $6
+            "#,
+        )
+        .bind(duplicate.total_transactions)
+        .bind(duplicate.successful_transactions)
+        .bind(duplicate.failed_transactions)
+        .bind(duplicate.total_volume_usd)
+        .bind(&duplicate.home_domain)
+        .bind(&survivor.id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM anchors WHERE id = $1")
+            .bind(&duplicate.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(AnchorMergeSummary {
+            survivor_id: survivor.id,
+            merged_id: duplicate.id,
+            assets_moved,
+            metrics_history_moved,
+            metrics_moved,
+            aliases_created,
+        })
+    }
+
+    /// Resolve an alias (old id, account or domain) to the surviving anchor id
+    pub async fn resolve_alias(&self, alias_type: &str, alias_value: &str) -> Result<Option<String>> {
+        let anchor_id = sqlx::query_scalar::<_, String>(
+            "SELECT anchor_id FROM anchor_aliases WHERE alias_type = $1 AND alias_value = $2",
+        )
+        .bind(alias_type)
+        .bind(alias_value)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(anchor_id)
+    }
+
+    pub async fn list_aliases(&self, anchor_id: &str) -> Result<Vec<AnchorAlias>> {
+        let aliases = sqlx::query_as::<_, AnchorAlias>(
+            "SELECT * FROM anchor_aliases WHERE anchor_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(anchor_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(aliases)
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+/// One hour of a corridor's metrics, as scored by anomaly models
+#[derive(Debug, Clone, Default, PartialEq, sqlx::FromRow)]
+pub struct CorridorHour {
+    pub corridor_key: String,
+    pub hour_bucket: String,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub success_rate: f64,
+    pub volume_usd: f64,
+}
+
+/// A trained model of one corridor
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct StoredAnomalyModel {
+    pub corridor_key: String,
+    /// Name of the detector that trained it
+    pub model: String,
+    pub version: i64,
+    pub trained_at: String,
+    pub sample_count: i64,
+    /// The detector's JSON artifact
+    pub artifact: String,
+}
+
+/// Corridor anomaly model artifacts and the hourly metrics they score
+pub struct AnomalyModels {
+    pool: SqlitePool,
+}
+
+impl AnomalyModels {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Hourly metrics of every corridor from `since`, ordered by corridor
+    /// and hour
+    pub async fn hours_since(&self, since: DateTime<Utc>) -> Result<Vec<CorridorHour>> {
+        sqlx::query_as::<_, CorridorHour>(
+            r#"
+            SELECT
+                corridor_key, hour_bucket, total_transactions,
+                successful_transactions, success_rate, volume_usd
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= ?
+            ORDER BY corridor_key, hour_bucket
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load corridor hourly metrics")
+    }
+
+    /// One corridor's hourly metrics from `since`, oldest first
+    pub async fn corridor_hours(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CorridorHour>> {
+        sqlx::query_as::<_, CorridorHour>(
+            r#"
+            SELECT
+                corridor_key, hour_bucket, total_transactions,
+                successful_transactions, success_rate, volume_usd
+            FROM corridor_metrics_hourly
+            WHERE corridor_key = ? AND hour_bucket >= ?
+            ORDER BY hour_bucket
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load corridor hourly metrics")
+    }
+
+    /// Store a corridor's newly trained `model`, replacing the previous
+    /// artifact and bumping its version. Returns the version.
+    pub async fn save(
+        &self,
+        corridor_key: &str,
+        model: &str,
+        trained_at: DateTime<Utc>,
+        sample_count: i64,
+        artifact: &str,
+    ) -> Result<i64> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO anomaly_models (
+                corridor_key, model, trained_at, sample_count, artifact
+            )
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(corridor_key, model) DO UPDATE SET
+                version = version + 1,
+                trained_at = excluded.trained_at,
+                sample_count = excluded.sample_count,
+                artifact = excluded.artifact
+            RETURNING version
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(model)
+        .bind(trained_at.to_rfc3339())
+        .bind(sample_count)
+        .bind(artifact)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to store anomaly model")
+    }
+
+    /// The corridor's model trained by `model`, if any
+    pub async fn get(&self, corridor_key: &str, model: &str) -> Result<Option<StoredAnomalyModel>> {
+        sqlx::query_as::<_, StoredAnomalyModel>(
+            "SELECT * FROM anomaly_models WHERE corridor_key = ? AND model = ?",
+        )
+        .bind(corridor_key)
+        .bind(model)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to load anomaly model")
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// Supply and holder distribution of an anchor asset at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetSupplySnapshot {
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub total_supply: f64,
+    pub num_holders: i64,
+    pub top10_balance: f64,
+    /// Fraction (0-1) of the supply held by the ten largest holders
+    pub top10_share: f64,
+    pub holders_scanned: i64,
+    /// False when the holder scan stopped early, making `top10_share` a
+    /// lower bound
+    pub holders_complete: bool,
+    pub recorded_at: DateTime<Utc>,
+}
+
+pub struct AssetSupplyHistory {
+    pool: SqlitePool,
+}
+
+impl AssetSupplyHistory {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Every asset issued by a known anchor, as (code, issuer)
+    pub async fn tracked_assets(&self) -> Result<Vec<(String, String)>> {
+        let assets = sqlx::query_as(
+            "SELECT asset_code, asset_issuer FROM assets ORDER BY asset_code, asset_issuer",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(assets)
+    }
+
+    /// Store a snapshot and refresh the asset's current supply and holders
+    pub async fn record(&self, snapshot: &AssetSupplySnapshot) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO asset_supply_snapshots (
+                asset_code, asset_issuer, total_supply, num_holders, top10_balance,
+                top10_share, holders_scanned, holders_complete, recorded_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(&snapshot.asset_code)
+        .bind(&snapshot.asset_issuer)
+        .bind(snapshot.total_supply)
+        .bind(snapshot.num_holders)
+        .bind(snapshot.top10_balance)
+        .bind(snapshot.top10_share)
+        .bind(snapshot.holders_scanned)
+        .bind(snapshot.holders_complete)
+        .bind(snapshot.recorded_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE assets SET total_supply = $1, num_holders = $2, updated_at = $3
+            WHERE asset_code = $4 AND asset_issuer = $5
+            "#,
+        )
+        .bind(snapshot.total_supply)
+        .bind(snapshot.num_holders)
+        .bind(snapshot.recorded_at)
+        .bind(&snapshot.asset_code)
+        .bind(&snapshot.asset_issuer)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn latest(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<Option<AssetSupplySnapshot>> {
+        let snapshot = sqlx::query_as::<_, AssetSupplySnapshot>(
+            r#"
+            SELECT asset_code, asset_issuer, total_supply, num_holders, top10_balance,
+                   top10_share, holders_scanned, holders_complete, recorded_at
+            FROM asset_supply_snapshots
+            WHERE asset_code = $1 AND asset_issuer = $2
+            ORDER BY recorded_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    /// Snapshots recorded since `since`, oldest first
+    pub async fn history(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AssetSupplySnapshot>> {
+        let snapshots = sqlx::query_as::<_, AssetSupplySnapshot>(
+            r#"
+            SELECT asset_code, asset_issuer, total_supply, num_holders, top10_balance,
+                   top10_share, holders_scanned, holders_complete, recorded_at
+            FROM asset_supply_snapshots
+            WHERE asset_code = $1 AND asset_issuer = $2 AND recorded_at >= $3
+            ORDER BY recorded_at ASC
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots)
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::aggregation::PAYMENT_CORRIDOR_KEY_SQL;
+
+/// Concentration of one corridor's volume over one window. Each side has no
+/// index or share when it had no volume.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, sqlx::FromRow)]
+pub struct CorridorConcentration {
+    pub corridor_key: String,
+    pub window_start: String,
+    pub window_end: String,
+    pub sender_hhi: Option<f64>,
+    pub senders: i64,
+    pub top_sender_share: Option<f64>,
+    pub receiver_hhi: Option<f64>,
+    pub receivers: i64,
+    pub top_receiver_share: Option<f64>,
+    pub market_maker_hhi: Option<f64>,
+    pub market_makers: i64,
+    pub top_market_maker_share: Option<f64>,
+}
+
+/// Which account of a payment its volume is attributed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentSide {
+    Sender,
+    Receiver,
+}
+
+impl PaymentSide {
+    fn column(&self) -> &'static str {
+        match self {
+            PaymentSide::Sender => "source_account",
+            PaymentSide::Receiver => "destination_account",
+        }
+    }
+}
+
+/// Concentration history per corridor
+pub struct CorridorConcentrations {
+    pool: SqlitePool,
+}
+
+impl CorridorConcentrations {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Payment volume per (corridor, account) for payments created in
+    /// `[start_time, end_time)`
+    pub async fn payment_volumes(
+        &self,
+        side: PaymentSide,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<(String, String, f64)>> {
+        let query = format!(
+            r#"
+            SELECT
+                {corridor_key} AS corridor_key,
+                {account} AS account,
+                SUM(amount) AS volume
+            FROM payments
+            WHERE created_at >= ? AND created_at < ?
+            GROUP BY corridor_key, account
+            "#,
+            corridor_key = PAYMENT_CORRIDOR_KEY_SQL,
+            account = side.column()
+        );
+        sqlx::query_as::<_, (String, String, f64)>(&query)
+            .bind(start_time.to_rfc3339())
+            .bind(end_time.to_rfc3339())
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to load payment volumes per account")
+    }
+
+    /// Store `rows`, replacing any computed earlier for the same window
+    pub async fn record(&self, rows: &[CorridorConcentration]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for row in rows {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_concentration (
+                    corridor_key, window_start, window_end,
+                    sender_hhi, senders, top_sender_share,
+                    receiver_hhi, receivers, top_receiver_share,
+                    market_maker_hhi, market_makers, top_market_maker_share
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (corridor_key, window_end) DO UPDATE SET
+                    window_start = excluded.window_start,
+                    sender_hhi = excluded.sender_hhi,
+                    senders = excluded.senders,
+                    top_sender_share = excluded.top_sender_share,
+                    receiver_hhi = excluded.receiver_hhi,
+                    receivers = excluded.receivers,
+                    top_receiver_share = excluded.top_receiver_share,
+                    market_maker_hhi = excluded.market_maker_hhi,
+                    market_makers = excluded.market_makers,
+                    top_market_maker_share = excluded.top_market_maker_share,
+                    computed_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(&row.corridor_key)
+            .bind(&row.window_start)
+            .bind(&row.window_end)
+            .bind(row.sender_hhi)
+            .bind(row.senders)
+            .bind(row.top_sender_share)
+            .bind(row.receiver_hhi)
+            .bind(row.receivers)
+            .bind(row.top_receiver_share)
+            .bind(row.market_maker_hhi)
+            .bind(row.market_makers)
+            .bind(row.top_market_maker_share)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to store corridor concentration")?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// A corridor's windows ending at or after `since`, oldest first
+    pub async fn history(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CorridorConcentration>> {
+        sqlx::query_as::<_, CorridorConcentration>(
+            r#"
+            SELECT
+                corridor_key, window_start, window_end,
+                sender_hhi, senders, top_sender_share,
+                receiver_hhi, receivers, top_receiver_share,
+                market_maker_hhi, market_makers, top_market_maker_share
+            FROM corridor_concentration
+            WHERE corridor_key = ? AND window_end >= ?
+            ORDER BY window_end ASC
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load corridor concentration history")
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use super::aggregation::PAYMENT_CORRIDOR_KEY_SQL;
+
+/// Accounts from one cohort active in one month
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct CohortActivity {
+    /// Month the accounts first sent in the corridor, `YYYY-MM`
+    pub cohort: String,
+    /// Month they were active in, `YYYY-MM`
+    pub month: String,
+    pub accounts: i64,
+}
+
+/// Monthly sending activity of accounts per corridor
+pub struct CorridorCohorts {
+    pool: SqlitePool,
+}
+
+impl CorridorCohorts {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record the sending accounts of payments created in
+    /// `[start_time, end_time]`. Already recorded months are left as they are,
+    /// so overlapping windows are safe. Returns the rows added.
+    pub async fn record_activity(
+        &self,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<u64> {
+        let query = format!(
+            r#"
+            INSERT OR IGNORE INTO corridor_account_months (corridor_key, account, month)
+            SELECT {}, source_account, substr(created_at, 1, 7)
+            FROM payments
+            WHERE created_at >= ? AND created_at <= ?
+            "#,
+            PAYMENT_CORRIDOR_KEY_SQL
+        );
+        let result = sqlx::query(&query)
+            .bind(start_time.to_rfc3339())
+            .bind(end_time.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to record corridor account activity")?;
+        Ok(result.rows_affected())
+    }
+
+    /// Active accounts per (cohort, month) for the cohorts starting in or after
+    /// `since_month` (`YYYY-MM`), ordered by cohort then month. Cohorts are
+    /// assigned over the corridor's whole history.
+    pub async fn activity(
+        &self,
+        corridor_key: &str,
+        since_month: &str,
+    ) -> Result<Vec<CohortActivity>> {
+        sqlx::query_as::<_, CohortActivity>(
+            r#"
+            WITH cohorts AS (
+                SELECT account, MIN(month) AS cohort
+                FROM corridor_account_months
+                WHERE corridor_key = ?
+                GROUP BY account
+            )
+            SELECT c.cohort, m.month, COUNT(*) AS accounts
+            FROM corridor_account_months m
+            JOIN cohorts c ON c.account = m.account
+            WHERE m.corridor_key = ? AND c.cohort >= ?
+            GROUP BY c.cohort, m.month
+            ORDER BY c.cohort, m.month
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(corridor_key)
+        .bind(since_month)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load corridor cohort activity")
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::models::MetricSource;
+
+/// One precomputed corridor list row
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CorridorSummary {
+    pub corridor_id: String,
+    pub source_asset_code: String,
+    pub source_asset_issuer: String,
+    pub destination_asset_code: String,
+    pub destination_asset_issuer: String,
+    pub reliability_score: f64,
+    pub status: String,
+    pub total_transactions_24h: i64,
+    pub successful_transactions_24h: i64,
+    pub failed_transactions_24h: i64,
+    pub success_rate_24h: f64,
+    pub volume_usd_24h: f64,
+    pub avg_settlement_latency_ms: Option<i64>,
+    /// Combined value of the liquidity pools holding both corridor assets
+    pub liquidity_depth_usd: f64,
+    /// Anchors issuing either corridor asset
+    pub anchor_count: i64,
+    pub refreshed_at: DateTime<Utc>,
+    /// Least trusted source among the aggregated hourly rows
+    pub source: MetricSource,
+}
+
+pub struct CorridorSummaries {
+    pool: SqlitePool,
+}
+
+impl CorridorSummaries {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Rebuild every summary row from the source tables in one transaction,
+    /// so readers see either the old or the new set. Returns the row count.
+    pub async fn refresh(&self) -> Result<u64> {
+        let now = Utc::now();
+        let since = (now - Duration::hours(24)).to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM corridor_summaries")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear corridor summaries")?;
+
+        // Hourly rows store each pair in normalized order, and pools may hold
+        // the reserves either way round, so both are matched in either order
+        let sql = format!(
+            r#"
+            INSERT INTO corridor_summaries (
+                corridor_id,
+                source_asset_code,
+                source_asset_issuer,
+                destination_asset_code,
+                destination_asset_issuer,
+                reliability_score,
+                status,
+                total_transactions_24h,
+                successful_transactions_24h,
+                failed_transactions_24h,
+                success_rate_24h,
+                volume_usd_24h,
+                avg_settlement_latency_ms,
+                liquidity_depth_usd,
+                anchor_count,
+                refreshed_at,
+                source
+            )
+            WITH recent AS (
+                SELECT
+                    asset_a_code,
+                    asset_a_issuer,
+                    asset_b_code,
+                    asset_b_issuer,
+                    SUM(total_transactions) AS total_transactions,
+                    SUM(successful_transactions) AS successful_transactions,
+                    SUM(failed_transactions) AS failed_transactions,
+                    SUM(volume_usd) AS volume_usd,
+                    CAST(AVG(avg_settlement_latency_ms) AS INTEGER) AS avg_settlement_latency_ms,
+                    MIN({rank}) AS source_rank
+                FROM corridor_metrics_hourly
+                WHERE hour_bucket >= ?1
+                GROUP BY asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer
+            )
+            SELECT
+                c.id,
+                c.source_asset_code,
+                c.source_asset_issuer,
+                c.destination_asset_code,
+                c.destination_asset_issuer,
+                COALESCE(c.reliability_score, 0),
+                COALESCE(c.status, 'active'),
+                COALESCE(SUM(r.total_transactions), 0),
+                COALESCE(SUM(r.successful_transactions), 0),
+                COALESCE(SUM(r.failed_transactions), 0),
+                CASE
+                    WHEN COALESCE(SUM(r.total_transactions), 0) > 0
+                    THEN SUM(r.successful_transactions) * 100.0 / SUM(r.total_transactions)
+                    ELSE 0
+                END,
+                COALESCE(SUM(r.volume_usd), 0),
+                CAST(AVG(r.avg_settlement_latency_ms) AS INTEGER),
+                (
+                    SELECT COALESCE(SUM(lp.total_value_usd), 0)
+                    FROM liquidity_pools lp
+                    WHERE (lp.reserve_a_asset_code = c.source_asset_code
+                            AND COALESCE(lp.reserve_a_asset_issuer, 'native') = c.source_asset_issuer
+                            AND lp.reserve_b_asset_code = c.destination_asset_code
+                            AND COALESCE(lp.reserve_b_asset_issuer, 'native') = c.destination_asset_issuer)
+                       OR (lp.reserve_a_asset_code = c.destination_asset_code
+                            AND COALESCE(lp.reserve_a_asset_issuer, 'native') = c.destination_asset_issuer
+                            AND lp.reserve_b_asset_code = c.source_asset_code
+                            AND COALESCE(lp.reserve_b_asset_issuer, 'native') = c.source_asset_issuer)
+                ),
+                (
+                    SELECT COUNT(DISTINCT a.anchor_id)
+                    FROM assets a
+                    WHERE (a.asset_code = c.source_asset_code
+                            AND a.asset_issuer = c.source_asset_issuer)
+                       OR (a.asset_code = c.destination_asset_code
+                            AND a.asset_issuer = c.destination_asset_issuer)
+                ),
+                ?2,
+                {source}
+            FROM corridors c
+            LEFT JOIN recent r
+                ON (r.asset_a_code = c.source_asset_code
+                    AND r.asset_a_issuer = c.source_asset_issuer
+                    AND r.asset_b_code = c.destination_asset_code
+                    AND r.asset_b_issuer = c.destination_asset_issuer)
+                OR (r.asset_a_code = c.destination_asset_code
+                    AND r.asset_a_issuer = c.destination_asset_issuer
+                    AND r.asset_b_code = c.source_asset_code
+                    AND r.asset_b_issuer = c.source_asset_issuer)
+            GROUP BY c.id
+            "#,
+            rank = MetricSource::trust_rank_sql("source"),
+            source = MetricSource::from_trust_rank_sql("COALESCE(MIN(r.source_rank), 1)"),
+        );
+        let inserted = sqlx::query(&sql)
+            .bind(since)
+            .bind(now.to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to rebuild corridor summaries")?
+            .rows_affected();
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    /// Summaries ordered by reliability, most reliable first, limited to a
+    /// tenant's corridors when `tenant_id` is given
+    pub async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        tenant_id: Option<&str>,
+    ) -> Result<Vec<CorridorSummary>> {
+        let summaries = sqlx::query_as::<_, CorridorSummary>(
+            r#"
+            SELECT * FROM corridor_summaries
+            WHERE ?3 IS NULL
+               OR corridor_id IN (SELECT corridor_id FROM tenant_corridors WHERE tenant_id = ?3)
+            ORDER BY reliability_score DESC, corridor_id
+            LIMIT ?1 OFFSET ?2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(summaries)
+    }
+}
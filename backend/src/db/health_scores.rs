@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// What a corridor's health score is computed from, over the 24 hours
+/// before the computation
+#[derive(Debug, Clone, Default, PartialEq, sqlx::FromRow)]
+pub struct HealthInputsRow {
+    pub corridor_key: String,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub volume_usd: f64,
+    /// Volume over the 24 hours before that
+    pub previous_volume_usd: f64,
+    /// Average hourly liquidity depth
+    pub liquidity_depth_usd: f64,
+    /// Average reliability of the anchors issuing either asset, if any
+    pub anchor_reliability: Option<f64>,
+}
+
+/// One corridor's score from one run of the health-score job
+#[derive(Debug, Clone, Default, PartialEq, Serialize, sqlx::FromRow)]
+pub struct CorridorHealthScore {
+    pub corridor_key: String,
+    pub computed_at: String,
+    pub score: f64,
+    pub success_rate_score: Option<f64>,
+    pub liquidity_score: Option<f64>,
+    pub volume_trend_score: Option<f64>,
+    pub anchor_reliability_score: Option<f64>,
+}
+
+/// Corridor health score history and weight overrides
+pub struct CorridorHealthScores {
+    pool: SqlitePool,
+}
+
+impl CorridorHealthScores {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Inputs for every corridor with hourly metrics in the 48 hours before
+    /// `now`, ordered by corridor key
+    pub async fn inputs(&self, now: DateTime<Utc>) -> Result<Vec<HealthInputsRow>> {
+        sqlx::query_as::<_, HealthInputsRow>(
+            r#"
+            WITH windows AS (
+                SELECT
+                    corridor_key,
+                    MAX(asset_a_code) AS asset_a_code,
+                    MAX(asset_a_issuer) AS asset_a_issuer,
+                    MAX(asset_b_code) AS asset_b_code,
+                    MAX(asset_b_issuer) AS asset_b_issuer,
+                    SUM(CASE WHEN hour_bucket >= ?2 THEN total_transactions ELSE 0 END)
+                        AS total_transactions,
+                    SUM(CASE WHEN hour_bucket >= ?2 THEN successful_transactions ELSE 0 END)
+                        AS successful_transactions,
+                    SUM(CASE WHEN hour_bucket >= ?2 THEN volume_usd ELSE 0.0 END) AS volume_usd,
+                    SUM(CASE WHEN hour_bucket < ?2 THEN volume_usd ELSE 0.0 END)
+                        AS previous_volume_usd,
+                    AVG(CASE WHEN hour_bucket >= ?2 THEN liquidity_depth_usd END)
+                        AS liquidity_depth_usd
+                FROM corridor_metrics_hourly
+                WHERE hour_bucket >= ?1 AND hour_bucket < ?3
+                GROUP BY corridor_key
+            )
+            SELECT
+                w.corridor_key,
+                w.total_transactions,
+                w.successful_transactions,
+                w.volume_usd,
+                w.previous_volume_usd,
+                COALESCE(w.liquidity_depth_usd, 0.0) AS liquidity_depth_usd,
+                (
+                    SELECT AVG(an.reliability_score)
+                    FROM anchors an
+                    WHERE an.id IN (
+                        SELECT a.anchor_id
+                        FROM assets a
+                        WHERE (a.asset_code = w.asset_a_code AND a.asset_issuer = w.asset_a_issuer)
+                           OR (a.asset_code = w.asset_b_code AND a.asset_issuer = w.asset_b_issuer)
+                    )
+                ) AS anchor_reliability
+            FROM windows w
+            ORDER BY w.corridor_key
+            "#,
+        )
+        .bind((now - Duration::hours(48)).to_rfc3339())
+        .bind((now - Duration::hours(24)).to_rfc3339())
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load corridor health inputs")
+    }
+
+    /// Component weights set in the database, as (component, weight)
+    pub async fn weight_overrides(&self) -> Result<Vec<(String, f64)>> {
+        sqlx::query_as::<_, (String, f64)>("SELECT component, weight FROM health_score_weights")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to load health score weights")
+    }
+
+    /// Store the scores of one run
+    pub async fn record(&self, scores: &[CorridorHealthScore]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for score in scores {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO corridor_health_scores (
+                    corridor_key, computed_at, score,
+                    success_rate_score, liquidity_score,
+                    volume_trend_score, anchor_reliability_score
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&score.corridor_key)
+            .bind(&score.computed_at)
+            .bind(score.score)
+            .bind(score.success_rate_score)
+            .bind(score.liquidity_score)
+            .bind(score.volume_trend_score)
+            .bind(score.anchor_reliability_score)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to store corridor health score")?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// A corridor's scores computed at or after `since`, oldest first
+    pub async fn history(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CorridorHealthScore>> {
+        sqlx::query_as::<_, CorridorHealthScore>(
+            r#"
+            SELECT
+                corridor_key, computed_at, score,
+                success_rate_score, liquidity_score,
+                volume_trend_score, anchor_reliability_score
+            FROM corridor_health_scores
+            WHERE corridor_key = ? AND computed_at >= ?
+            ORDER BY computed_at ASC
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load corridor health history")
+    }
+
+    /// The most recent score of every corridor, as (corridor key, score)
+    pub async fn latest(&self) -> Result<Vec<(String, f64)>> {
+        sqlx::query_as::<_, (String, f64)>(
+            r#"
+            SELECT s.corridor_key, s.score
+            FROM corridor_health_scores s
+            JOIN (
+                SELECT corridor_key, MAX(computed_at) AS computed_at
+                FROM corridor_health_scores
+                GROUP BY corridor_key
+            ) latest
+                ON latest.corridor_key = s.corridor_key
+               AND latest.computed_at = s.computed_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load latest corridor health scores")
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// A range of ledgers missing between two stored ones, inclusive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct LedgerGap {
+    pub id: i64,
+    pub start_ledger: i64,
+    pub end_ledger: i64,
+    pub detected_at: String,
+    /// Backfills tried so far
+    pub attempts: i64,
+    pub last_attempt_at: Option<String>,
+    pub last_error: Option<String>,
+    pub resolved_at: Option<String>,
+}
+
+impl LedgerGap {
+    pub fn len(&self) -> i64 {
+        self.end_ledger - self.start_ledger + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() <= 0
+    }
+}
+
+/// Unresolved gaps at a glance
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GapTotals {
+    pub unresolved_gaps: i64,
+    pub missing_ledgers: i64,
+}
+
+/// Missing ledger ranges and their repair state
+pub struct LedgerGaps {
+    pool: SqlitePool,
+}
+
+impl LedgerGaps {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Ranges missing between the oldest and newest stored ledger, oldest first
+    pub async fn scan(&self) -> Result<Vec<(i64, i64)>> {
+        let ranges = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT prev + 1, sequence - 1
+            FROM (
+                SELECT sequence, LAG(sequence) OVER (ORDER BY sequence) AS prev
+                FROM ledgers
+            )
+            WHERE sequence - prev > 1
+            ORDER BY sequence
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ranges)
+    }
+
+    /// Record the ranges from a [`scan`](Self::scan): new ranges are opened,
+    /// and open gaps no longer among them (filled, or narrowed to smaller
+    /// ranges) are resolved. Returns the number of newly opened gaps.
+    pub async fn sync(&self, ranges: &[(i64, i64)]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let open: Vec<(i64, i64, i64)> = sqlx::query_as(
+            "SELECT id, start_ledger, end_ledger FROM ledger_gaps WHERE resolved_at IS NULL",
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let current: HashSet<(i64, i64)> = ranges.iter().copied().collect();
+        for (id, start, end) in &open {
+            if !current.contains(&(*start, *end)) {
+                sqlx::query("UPDATE ledger_gaps SET resolved_at = CURRENT_TIMESTAMP WHERE id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        let known: HashSet<(i64, i64)> = open.iter().map(|(_, s, e)| (*s, *e)).collect();
+        let mut opened = 0;
+        for (start, end) in ranges {
+            if known.contains(&(*start, *end)) {
+                continue;
+            }
+            // A range resolved earlier only reappears if its ledgers were removed
+            sqlx::query(
+                r#"
+                INSERT INTO ledger_gaps (start_ledger, end_ledger)
+                VALUES ($1, $2)
+                ON CONFLICT (start_ledger, end_ledger) DO UPDATE SET
+                    detected_at = CURRENT_TIMESTAMP,
+                    attempts = 0,
+                    last_attempt_at = NULL,
+                    last_error = NULL,
+                    resolved_at = NULL
+                "#,
+            )
+            .bind(start)
+            .bind(end)
+            .execute(&mut *tx)
+            .await?;
+            opened += 1;
+        }
+
+        tx.commit().await?;
+        Ok(opened)
+    }
+
+    /// Open gaps tried fewer than `max_attempts` times, oldest first
+    pub async fn pending(&self, max_attempts: i64) -> Result<Vec<LedgerGap>> {
+        let gaps = sqlx::query_as::<_, LedgerGap>(
+            r#"
+            SELECT * FROM ledger_gaps
+            WHERE resolved_at IS NULL AND attempts < $1
+            ORDER BY start_ledger
+            "#,
+        )
+        .bind(max_attempts)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(gaps)
+    }
+
+    /// Gaps oldest first, resolved ones only if asked for
+    pub async fn list(&self, include_resolved: bool) -> Result<Vec<LedgerGap>> {
+        let gaps = sqlx::query_as::<_, LedgerGap>(
+            r#"
+            SELECT * FROM ledger_gaps
+            WHERE $1 OR resolved_at IS NULL
+            ORDER BY start_ledger, id
+            "#,
+        )
+        .bind(include_resolved)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(gaps)
+    }
+
+    pub async fn totals(&self) -> Result<GapTotals> {
+        let (unresolved_gaps, missing_ledgers): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), COALESCE(SUM(end_ledger - start_ledger + 1), 0)
+            FROM ledger_gaps
+            WHERE resolved_at IS NULL
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(GapTotals {
+            unresolved_gaps,
+            missing_ledgers,
+        })
+    }
+
+    /// Record a backfill attempt; a gap filled completely is resolved
+    pub async fn record_attempt(&self, id: i64, error: Option<&str>) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE ledger_gaps SET
+                attempts = attempts + 1,
+                last_attempt_at = CURRENT_TIMESTAMP,
+                last_error = $2,
+                resolved_at = CASE WHEN $2 IS NULL THEN CURRENT_TIMESTAMP END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,23 @@
 pub mod aggregates;
 pub mod aggregation;
+pub mod anchor_claims;
+pub mod anchor_merge;
+pub mod anomaly_models;
+pub mod asset_supply;
+pub mod concentration;
+pub mod corridor_cohorts;
+pub mod corridor_summaries;
+pub mod health_scores;
+pub mod ledger_gaps;
+pub mod price_history;
 pub mod schema;
+pub mod search;
+pub mod self_reported;
+pub mod sep_performance;
+pub mod sep_transactions;
+pub mod sessions;
+pub mod settlements;
+pub mod status;
+pub mod surveillance;
+pub mod tenants;
+pub mod usage_events;
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// How far from the requested timestamp an observation may be and still be used
+pub const MAX_PRICE_DISTANCE_HOURS: i64 = 48;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PricePoint {
+    pub asset: String,
+    pub price_usd: f64,
+    pub source: String,
+    pub observed_at: DateTime<Utc>,
+}
+
+pub struct PriceHistory {
+    pool: SqlitePool,
+}
+
+impl PriceHistory {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(
+        &self,
+        asset: &str,
+        price_usd: f64,
+        source: &str,
+        observed_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO price_history (asset, price_usd, source, observed_at) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(asset)
+        .bind(price_usd)
+        .bind(source)
+        .bind(observed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Price closest to `at`: the latest observation at or before it, else the
+    /// earliest one after it. Observations further than
+    /// [`MAX_PRICE_DISTANCE_HOURS`] away are ignored.
+    pub async fn price_at(&self, asset: &str, at: DateTime<Utc>) -> Result<Option<PricePoint>> {
+        let window = Duration::hours(MAX_PRICE_DISTANCE_HOURS);
+
+        let before = sqlx::query_as::<_, PricePoint>(
+            r#"
+            SELECT asset, price_usd, source, observed_at
+            FROM price_history
+            WHERE asset = $1 AND observed_at <= $2 AND observed_at >= $3
+            ORDER BY observed_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(asset)
+        .bind(at)
+        .bind(at - window)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if before.is_some() {
+            return Ok(before);
+        }
+
+        let after = sqlx::query_as::<_, PricePoint>(
+            r#"
+            SELECT asset, price_usd, source, observed_at
+            FROM price_history
+            WHERE asset = $1 AND observed_at > $2 AND observed_at <= $3
+            ORDER BY observed_at ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(asset)
+        .bind(at)
+        .bind(at + window)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(after)
+    }
+}
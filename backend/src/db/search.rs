@@ -0,0 +1,298 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::tenant::TenantScope;
+
+/// Maximum number of tags attached to a single entity
+pub const MAX_TAGS_PER_ENTITY: usize = 20;
+
+/// Maximum length of a single tag
+pub const MAX_TAG_LEN: usize = 32;
+
+/// Words that carry no meaning for entity lookups
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "for", "from", "in", "of", "on", "or", "the", "to", "with",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaggableEntity {
+    Anchor,
+    Corridor,
+}
+
+impl TaggableEntity {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "anchor" | "anchors" => Some(Self::Anchor),
+            "corridor" | "corridors" => Some(Self::Corridor),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Anchor => "anchor",
+            Self::Corridor => "corridor",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SearchHit {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub title: String,
+    pub tags: String,
+    pub score: f64,
+}
+
+/// A free-text query broken into FTS terms plus an optional entity filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSearchQuery {
+    pub terms: Vec<String>,
+    pub entity_type: Option<TaggableEntity>,
+}
+
+impl ParsedSearchQuery {
+    /// Split a query like "EURC corridors in LATAM" into terms (`eurc`, `latam`)
+    /// and an entity filter (`corridor`)
+    pub fn parse(raw: &str) -> Self {
+        let mut terms = Vec::new();
+        let mut entity_type = None;
+
+        for token in raw
+            .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+            .map(|t| t.trim_matches(|c| c == '-' || c == '_').to_lowercase())
+            .filter(|t| !t.is_empty())
+        {
+            if let Some(kind) = TaggableEntity::parse(&token) {
+                entity_type = Some(kind);
+                continue;
+            }
+            if STOPWORDS.contains(&token.as_str()) || terms.contains(&token) {
+                continue;
+            }
+            terms.push(token);
+        }
+
+        Self { terms, entity_type }
+    }
+
+    /// FTS5 MATCH expression joining prefix terms with the given operator
+    pub fn to_match_expression(&self, operator: &str) -> String {
+        self.terms
+            .iter()
+            .map(|t| format!("\"{}\"*", t.replace('"', "")))
+            .collect::<Vec<_>>()
+            .join(&format!(" {} ", operator))
+    }
+}
+
+/// Lowercase and validate a tag; `None` if it is empty or contains disallowed characters
+pub fn normalize_tag(raw: &str) -> Option<String> {
+    let tag = raw.trim().to_lowercase();
+    if tag.is_empty() || tag.len() > MAX_TAG_LEN {
+        return None;
+    }
+    tag.chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        .then_some(tag)
+}
+
+pub struct EntitySearch {
+    pool: SqlitePool,
+}
+
+impl EntitySearch {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Search the anchors and corridors in the scope's directory. All terms
+    /// must match; if nothing does, fall back to matching any term so partial
+    /// queries still return results.
+    pub async fn search(
+        &self,
+        query: &ParsedSearchQuery,
+        tenant: &TenantScope,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>> {
+        if query.terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let hits = self
+            .run_match(
+                &query.to_match_expression("AND"),
+                query.entity_type,
+                tenant,
+                limit,
+            )
+            .await?;
+        if !hits.is_empty() || query.terms.len() == 1 {
+            return Ok(hits);
+        }
+
+        self.run_match(
+            &query.to_match_expression("OR"),
+            query.entity_type,
+            tenant,
+            limit,
+        )
+        .await
+    }
+
+    async fn run_match(
+        &self,
+        expression: &str,
+        entity_type: Option<TaggableEntity>,
+        tenant: &TenantScope,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>> {
+        // bm25 weights: entity_type, entity_id (unindexed), title, body, tags
+        let hits = sqlx::query_as::<_, SearchHit>(
+            r#"
+            SELECT entity_type, entity_id, title, tags,
+                   -bm25(entity_search, 0.0, 0.0, 10.0, 3.0, 6.0) AS score
+            FROM entity_search
+            WHERE entity_search MATCH $1
+              AND ($2 IS NULL OR entity_type = $2)
+              AND ($4 IS NULL
+                   OR (entity_type = 'anchor' AND entity_id IN
+                       (SELECT anchor_id FROM tenant_anchors WHERE tenant_id = $4))
+                   OR (entity_type = 'corridor' AND entity_id IN
+                       (SELECT corridor_id FROM tenant_corridors WHERE tenant_id = $4)))
+            ORDER BY score DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(expression)
+        .bind(entity_type.map(|t| t.as_str()))
+        .bind(limit)
+        .bind(tenant.id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(hits)
+    }
+
+    pub async fn list_tags(&self, entity: TaggableEntity, entity_id: &str) -> Result<Vec<String>> {
+        let tags = sqlx::query_scalar::<_, String>(
+            "SELECT tag FROM entity_tags WHERE entity_type = $1 AND entity_id = $2 ORDER BY tag",
+        )
+        .bind(entity.as_str())
+        .bind(entity_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    /// Add already-normalized tags, ignoring duplicates. Returns the full tag list.
+    pub async fn add_tags(
+        &self,
+        entity: TaggableEntity,
+        entity_id: &str,
+        tags: &[String],
+        created_by: &str,
+    ) -> Result<Vec<String>> {
+        let mut tx = self.pool.begin().await?;
+        for tag in tags {
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO entity_tags (id, entity_type, entity_id, tag, created_by)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(entity.as_str())
+            .bind(entity_id)
+            .bind(tag)
+            .bind(created_by)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.list_tags(entity, entity_id).await
+    }
+
+    pub async fn remove_tag(&self, entity: TaggableEntity, entity_id: &str, tag: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "DELETE FROM entity_tags WHERE entity_type = $1 AND entity_id = $2 AND tag = $3",
+        )
+        .bind(entity.as_str())
+        .bind(entity_id)
+        .bind(tag)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether the anchor or corridor being tagged exists in the scope's directory
+    pub async fn entity_exists(
+        &self,
+        entity: TaggableEntity,
+        entity_id: &str,
+        tenant: &TenantScope,
+    ) -> Result<bool> {
+        let sql = match entity {
+            TaggableEntity::Anchor => {
+                r#"
+                SELECT COUNT(*) FROM anchors
+                WHERE id = $1
+                  AND ($2 IS NULL
+                       OR id IN (SELECT anchor_id FROM tenant_anchors WHERE tenant_id = $2))
+                "#
+            }
+            TaggableEntity::Corridor => {
+                r#"
+                SELECT COUNT(*) FROM corridors
+                WHERE id = $1
+                  AND ($2 IS NULL
+                       OR id IN (SELECT corridor_id FROM tenant_corridors WHERE tenant_id = $2))
+                "#
+            }
+        };
+        let count: i64 = sqlx::query_scalar(sql)
+            .bind(entity_id)
+            .bind(tenant.id())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_extracts_entity_type_and_drops_stopwords() {
+        let parsed = ParsedSearchQuery::parse("EURC corridors in LATAM");
+        assert_eq!(parsed.terms, vec!["eurc", "latam"]);
+        assert_eq!(parsed.entity_type, Some(TaggableEntity::Corridor));
+        assert_eq!(parsed.to_match_expression("AND"), "\"eurc\"* AND \"latam\"*");
+    }
+
+    #[test]
+    fn test_parse_strips_fts_syntax() {
+        let parsed = ParsedSearchQuery::parse("usdc\" OR * NEAR(");
+        assert_eq!(parsed.terms, vec!["usdc", "near"]);
+        assert_eq!(parsed.entity_type, None);
+    }
+
+    #[test]
+    fn test_normalize_tag() {
+        assert_eq!(normalize_tag("  LATAM "), Some("latam".to_string()));
+        assert_eq!(normalize_tag("high-volume"), Some("high-volume".to_string()));
+        assert_eq!(normalize_tag("two words"), None);
+        assert_eq!(normalize_tag(""), None);
+        assert_eq!(normalize_tag(&"x".repeat(MAX_TAG_LEN + 1)), None);
+    }
+}
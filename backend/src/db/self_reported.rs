@@ -0,0 +1,114 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::models::MetricSource;
+
+/// Off-chain statistics an anchor operator reported for one period
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SelfReportedMetrics {
+    pub id: String,
+    pub anchor_id: String,
+    pub reported_by: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub deposit_volume_usd: Option<f64>,
+    pub withdrawal_volume_usd: Option<f64>,
+    pub deposit_count: Option<i64>,
+    pub withdrawal_count: Option<i64>,
+    pub avg_deposit_time_ms: Option<i64>,
+    pub avg_withdrawal_time_ms: Option<i64>,
+    /// Always `self_reported`
+    pub source: MetricSource,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Values for one reporting period
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SelfReportedValues {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub deposit_volume_usd: Option<f64>,
+    pub withdrawal_volume_usd: Option<f64>,
+    pub deposit_count: Option<i64>,
+    pub withdrawal_count: Option<i64>,
+    pub avg_deposit_time_ms: Option<i64>,
+    pub avg_withdrawal_time_ms: Option<i64>,
+}
+
+pub struct SelfReportedMetricsStore {
+    pool: SqlitePool,
+}
+
+impl SelfReportedMetricsStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Store a period's report; reporting the same period again replaces it
+    pub async fn upsert(
+        &self,
+        anchor_id: &str,
+        reported_by: &str,
+        values: &SelfReportedValues,
+    ) -> Result<SelfReportedMetrics> {
+        let now = Utc::now();
+        let row = sqlx::query_as::<_, SelfReportedMetrics>(
+            r#"
+            INSERT INTO anchor_self_reported_metrics (
+                id, anchor_id, reported_by, period_start, period_end,
+                deposit_volume_usd, withdrawal_volume_usd, deposit_count, withdrawal_count,
+                avg_deposit_time_ms, avg_withdrawal_time_ms, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)
+            ON CONFLICT(anchor_id, period_start, period_end) DO UPDATE
+            SET reported_by = excluded.reported_by,
+                deposit_volume_usd = excluded.deposit_volume_usd,
+                withdrawal_volume_usd = excluded.withdrawal_volume_usd,
+                deposit_count = excluded.deposit_count,
+                withdrawal_count = excluded.withdrawal_count,
+                avg_deposit_time_ms = excluded.avg_deposit_time_ms,
+                avg_withdrawal_time_ms = excluded.avg_withdrawal_time_ms,
+                updated_at = excluded.updated_at
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(anchor_id)
+        .bind(reported_by)
+        .bind(values.period_start)
+        .bind(values.period_end)
+        .bind(values.deposit_volume_usd)
+        .bind(values.withdrawal_volume_usd)
+        .bind(values.deposit_count)
+        .bind(values.withdrawal_count)
+        .bind(values.avg_deposit_time_ms)
+        .bind(values.avg_withdrawal_time_ms)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    /// An anchor's most recent reports, latest period first
+    pub async fn recent(&self, anchor_id: &str, limit: i64) -> Result<Vec<SelfReportedMetrics>> {
+        let rows = sqlx::query_as::<_, SelfReportedMetrics>(
+            r#"
+            SELECT * FROM anchor_self_reported_metrics
+            WHERE anchor_id = $1
+            ORDER BY period_end DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
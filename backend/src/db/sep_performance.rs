@@ -0,0 +1,352 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// Days of raw samples kept after they are rolled up. Yesterday's samples
+/// must survive until its final roll-up after midnight.
+pub const SAMPLE_RETENTION_DAYS: i64 = 3;
+
+/// Outcome of a request that got no response because it timed out
+pub const OUTCOME_TIMEOUT: &str = "timeout";
+/// Outcome of a request that failed to connect or send
+pub const OUTCOME_NETWORK: &str = "network";
+/// Outcome of a request refused by the host's open circuit breaker
+pub const OUTCOME_CIRCUIT_OPEN: &str = "circuit_open";
+
+/// Whether an outcome counts against the anchor: no response, a 5xx or a
+/// 429. Other 4xx responses are caused by the caller's request.
+pub fn is_error_outcome(outcome: &str) -> bool {
+    match outcome.parse::<u16>() {
+        Ok(status) => status >= 500 || status == 429,
+        Err(_) => true,
+    }
+}
+
+/// Lowercased host of a transfer server URL, matched against anchors' home
+/// domains
+pub fn transfer_server_host(transfer_server: &str) -> String {
+    url::Url::parse(transfer_server.trim())
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .unwrap_or_default()
+}
+
+/// One request a SEP proxy sent to an anchor's transfer server
+#[derive(Debug, Clone)]
+pub struct SepRequestSample {
+    pub transfer_server: String,
+    /// `sep24` or `sep31`
+    pub protocol: String,
+    /// Method and path below the transfer server, e.g. `GET /info`
+    pub endpoint: String,
+    /// HTTP status code, or one of the `OUTCOME_*` constants
+    pub outcome: String,
+    pub latency_ms: i64,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// Request counts and latency of one endpoint over a set of samples
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub request_count: i64,
+    pub error_count: i64,
+    pub avg_latency_ms: f64,
+    pub p50_latency_ms: i64,
+    pub p95_latency_ms: i64,
+    pub max_latency_ms: i64,
+    /// Requests per outcome (status code, `timeout`, `network`, `circuit_open`)
+    pub outcome_counts: BTreeMap<String, i64>,
+}
+
+/// Summarize `(outcome, latency_ms)` samples; `None` when there are none
+pub fn summarize(samples: &[(String, i64)]) -> Option<LatencySummary> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let mut latencies: Vec<i64> = samples.iter().map(|(_, latency)| *latency).collect();
+    latencies.sort_unstable();
+    // Nearest-rank percentile
+    let percentile = |p: f64| {
+        let rank = (p * latencies.len() as f64).ceil() as usize;
+        latencies[rank.clamp(1, latencies.len()) - 1]
+    };
+
+    let mut outcome_counts = BTreeMap::new();
+    for (outcome, _) in samples {
+        *outcome_counts.entry(outcome.clone()).or_insert(0) += 1;
+    }
+
+    Some(LatencySummary {
+        request_count: samples.len() as i64,
+        error_count: samples
+            .iter()
+            .filter(|(outcome, _)| is_error_outcome(outcome))
+            .count() as i64,
+        avg_latency_ms: latencies.iter().sum::<i64>() as f64 / latencies.len() as f64,
+        p50_latency_ms: percentile(0.5),
+        p95_latency_ms: percentile(0.95),
+        max_latency_ms: latencies[latencies.len() - 1],
+        outcome_counts,
+    })
+}
+
+/// One day of an endpoint's performance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SepPerformanceDay {
+    pub transfer_server: String,
+    pub protocol: String,
+    pub endpoint: String,
+    pub date: String,
+    #[serde(flatten)]
+    pub summary: LatencySummary,
+}
+
+#[derive(sqlx::FromRow)]
+struct DayRow {
+    transfer_server: String,
+    protocol: String,
+    endpoint: String,
+    date: String,
+    request_count: i64,
+    error_count: i64,
+    avg_latency_ms: f64,
+    p50_latency_ms: i64,
+    p95_latency_ms: i64,
+    max_latency_ms: i64,
+    outcome_counts: String,
+}
+
+impl From<DayRow> for SepPerformanceDay {
+    fn from(row: DayRow) -> Self {
+        Self {
+            transfer_server: row.transfer_server,
+            protocol: row.protocol,
+            endpoint: row.endpoint,
+            date: row.date,
+            summary: LatencySummary {
+                request_count: row.request_count,
+                error_count: row.error_count,
+                avg_latency_ms: row.avg_latency_ms,
+                p50_latency_ms: row.p50_latency_ms,
+                p95_latency_ms: row.p95_latency_ms,
+                max_latency_ms: row.max_latency_ms,
+                outcome_counts: serde_json::from_str(&row.outcome_counts).unwrap_or_default(),
+            },
+        }
+    }
+}
+
+/// (transfer server, protocol, endpoint)
+type EndpointKey = (String, String, String);
+
+pub struct SepPerformanceStore {
+    pool: SqlitePool,
+}
+
+impl SepPerformanceStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, sample: &SepRequestSample) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sep_request_samples (
+                transfer_server, host, protocol, endpoint, outcome, is_error,
+                latency_ms, requested_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(&sample.transfer_server)
+        .bind(transfer_server_host(&sample.transfer_server))
+        .bind(&sample.protocol)
+        .bind(&sample.endpoint)
+        .bind(&sample.outcome)
+        .bind(is_error_outcome(&sample.outcome))
+        .bind(sample.latency_ms)
+        .bind(sample.requested_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// (Re)compute the daily rows of `date` from its samples. Returns the
+    /// number of endpoint rows written.
+    pub async fn roll_up_day(&self, date: NaiveDate) -> Result<usize> {
+        let day = date.format("%Y-%m-%d").to_string();
+        let rows = sqlx::query_as::<_, (String, String, String, String, String, i64)>(
+            r#"
+            SELECT transfer_server, host, protocol, endpoint, outcome, latency_ms
+            FROM sep_request_samples
+            WHERE substr(requested_at, 1, 10) = $1
+            "#,
+        )
+        .bind(&day)
+        .fetch_all(&self.pool)
+        .await?;
+
+        // Host and `(outcome, latency_ms)` samples of each endpoint
+        let mut endpoints: BTreeMap<EndpointKey, (String, Vec<(String, i64)>)> = BTreeMap::new();
+        for (transfer_server, host, protocol, endpoint, outcome, latency_ms) in rows {
+            endpoints
+                .entry((transfer_server, protocol, endpoint))
+                .or_insert_with(|| (host, Vec::new()))
+                .1
+                .push((outcome, latency_ms));
+        }
+
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        let mut written = 0;
+        for ((transfer_server, protocol, endpoint), (host, samples)) in endpoints {
+            let Some(summary) = summarize(&samples) else {
+                continue;
+            };
+            sqlx::query(
+                r#"
+                INSERT INTO sep_performance_daily (
+                    transfer_server, host, protocol, endpoint, date, request_count,
+                    error_count, avg_latency_ms, p50_latency_ms, p95_latency_ms,
+                    max_latency_ms, outcome_counts, updated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                ON CONFLICT(transfer_server, protocol, endpoint, date) DO UPDATE SET
+                    host = excluded.host,
+                    request_count = excluded.request_count,
+                    error_count = excluded.error_count,
+                    avg_latency_ms = excluded.avg_latency_ms,
+                    p50_latency_ms = excluded.p50_latency_ms,
+                    p95_latency_ms = excluded.p95_latency_ms,
+                    max_latency_ms = excluded.max_latency_ms,
+                    outcome_counts = excluded.outcome_counts,
+                    updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(&transfer_server)
+            .bind(&host)
+            .bind(&protocol)
+            .bind(&endpoint)
+            .bind(&day)
+            .bind(summary.request_count)
+            .bind(summary.error_count)
+            .bind(summary.avg_latency_ms)
+            .bind(summary.p50_latency_ms)
+            .bind(summary.p95_latency_ms)
+            .bind(summary.max_latency_ms)
+            .bind(serde_json::to_string(&summary.outcome_counts)?)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            written += 1;
+        }
+        tx.commit().await?;
+
+        Ok(written)
+    }
+
+    /// Delete samples from days before `before`
+    pub async fn prune_samples(&self, before: NaiveDate) -> Result<u64> {
+        let result =
+            sqlx::query("DELETE FROM sep_request_samples WHERE substr(requested_at, 1, 10) < $1")
+                .bind(before.format("%Y-%m-%d").to_string())
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Roll up yesterday (now complete) and today (so far), then drop
+    /// samples past [`SAMPLE_RETENTION_DAYS`]. Returns the rows written.
+    pub async fn run_once(&self) -> Result<usize> {
+        let today = Utc::now().date_naive();
+        let written =
+            self.roll_up_day(today - Duration::days(1)).await? + self.roll_up_day(today).await?;
+        let pruned = self
+            .prune_samples(today - Duration::days(SAMPLE_RETENTION_DAYS))
+            .await?;
+        tracing::info!(
+            "Rolled up SEP performance into {} daily rows, pruned {} samples",
+            written,
+            pruned
+        );
+
+        Ok(written)
+    }
+
+    /// Daily rows since `since` of transfer servers on `domain` or one of
+    /// its subdomains, oldest first
+    pub async fn for_domain(
+        &self,
+        domain: &str,
+        since: NaiveDate,
+    ) -> Result<Vec<SepPerformanceDay>> {
+        let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+        let rows = sqlx::query_as::<_, DayRow>(
+            r#"
+            SELECT transfer_server, protocol, endpoint, date, request_count, error_count,
+                   avg_latency_ms, p50_latency_ms, p95_latency_ms, max_latency_ms,
+                   outcome_counts
+            FROM sep_performance_daily
+            WHERE (host = $1 OR substr(host, -length($1) - 1) = '.' || $1)
+              AND date >= $2
+            ORDER BY date, transfer_server, protocol, endpoint
+            "#,
+        )
+        .bind(&domain)
+        .bind(since.format("%Y-%m-%d").to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(SepPerformanceDay::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_outcomes() {
+        assert!(!is_error_outcome("200"));
+        assert!(!is_error_outcome("403"));
+        assert!(is_error_outcome("429"));
+        assert!(is_error_outcome("502"));
+        assert!(is_error_outcome(OUTCOME_TIMEOUT));
+        assert!(is_error_outcome(OUTCOME_CIRCUIT_OPEN));
+    }
+
+    #[test]
+    fn test_transfer_server_host() {
+        assert_eq!(
+            transfer_server_host(" https://API.Anchor.example/sep24/ "),
+            "api.anchor.example"
+        );
+        assert_eq!(transfer_server_host("not a url"), "");
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(summarize(&[]), None);
+
+        let samples: Vec<(String, i64)> = (1..=20)
+            .map(|i| {
+                let outcome = if i == 20 { "504" } else { "200" };
+                (outcome.to_string(), i * 100)
+            })
+            .collect();
+        let summary = summarize(&samples).unwrap();
+        assert_eq!(summary.request_count, 20);
+        assert_eq!(summary.error_count, 1);
+        assert_eq!(summary.avg_latency_ms, 1050.0);
+        assert_eq!(summary.p50_latency_ms, 1000);
+        assert_eq!(summary.p95_latency_ms, 1900);
+        assert_eq!(summary.max_latency_ms, 2000);
+        assert_eq!(summary.outcome_counts["200"], 19);
+        assert_eq!(summary.outcome_counts["504"], 1);
+    }
+}
@@ -0,0 +1,415 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::crypto::FieldCipher;
+
+/// Consecutive failed polls after which the poller stops querying the anchor
+pub const MAX_POLL_FAILURES: i64 = 10;
+
+/// Base delay between polls of one transaction; doubled per consecutive failure
+const POLL_INTERVAL_SECS: i64 = 30;
+
+/// SEP-24/31 statuses after which an anchor no longer changes a transaction
+pub const TERMINAL_STATUSES: &[&str] = &[
+    "completed",
+    "refunded",
+    "expired",
+    "error",
+    "no_market",
+    "too_small",
+    "too_large",
+];
+
+pub fn is_terminal_status(status: &str) -> bool {
+    TERMINAL_STATUSES.contains(&status)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SepTransaction {
+    pub id: String,
+    pub user_id: String,
+    pub anchor_id: Option<String>,
+    pub protocol: String,
+    pub kind: String,
+    pub transfer_server: String,
+    pub anchor_transaction_id: String,
+    pub asset_code: Option<String>,
+    pub amount: Option<String>,
+    pub status: String,
+    pub status_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A transaction started through one of the SEP proxies
+#[derive(Debug, Clone)]
+pub struct NewSepTransaction {
+    pub user_id: String,
+    pub anchor_id: Option<String>,
+    /// `sep24` or `sep31`
+    pub protocol: String,
+    pub kind: String,
+    pub transfer_server: String,
+    pub anchor_transaction_id: String,
+    pub asset_code: Option<String>,
+    pub amount: Option<String>,
+    pub status: String,
+    /// SEP-10 token used to query the anchor; stored encrypted
+    pub auth_token: Option<String>,
+}
+
+/// An open transaction due for a status poll, with its decrypted SEP-10 token
+#[derive(Debug, Clone)]
+pub struct PollTarget {
+    pub transaction: SepTransaction,
+    pub auth_token: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PollRow {
+    #[sqlx(flatten)]
+    transaction: SepTransaction,
+    auth_token: Option<String>,
+}
+
+/// Outcome of applying an anchor-reported status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusUpdate {
+    /// The status changed from `previous`
+    Changed { previous: String },
+    /// Same status as already stored
+    Unchanged,
+    /// The stored transaction is already terminal; the update was ignored
+    AlreadyTerminal,
+}
+
+pub struct SepTransactionStore {
+    pool: SqlitePool,
+    /// Encrypts the stored SEP-10 tokens
+    cipher: FieldCipher,
+}
+
+impl SepTransactionStore {
+    pub fn new(pool: SqlitePool, cipher: FieldCipher) -> Self {
+        Self { pool, cipher }
+    }
+
+    /// Start tracking a transaction. Re-recording the same anchor transaction
+    /// returns the existing row.
+    pub async fn create(&self, new: NewSepTransaction) -> Result<SepTransaction> {
+        let auth_token = self.cipher.encrypt_opt(new.auth_token.as_deref())?;
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO sep_transactions (
+                id, user_id, anchor_id, protocol, kind, transfer_server,
+                anchor_transaction_id, asset_code, amount, status, auth_token,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)
+            ON CONFLICT(transfer_server, anchor_transaction_id) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&new.user_id)
+        .bind(&new.anchor_id)
+        .bind(&new.protocol)
+        .bind(&new.kind)
+        .bind(&new.transfer_server)
+        .bind(&new.anchor_transaction_id)
+        .bind(&new.asset_code)
+        .bind(&new.amount)
+        .bind(&new.status)
+        .bind(auth_token)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        let tx = sqlx::query_as::<_, SepTransaction>(
+            "SELECT * FROM sep_transactions WHERE transfer_server = $1 AND anchor_transaction_id = $2",
+        )
+        .bind(&new.transfer_server)
+        .bind(&new.anchor_transaction_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(tx)
+    }
+
+    /// A user's transactions, newest first
+    pub async fn list_for_user(
+        &self,
+        user_id: &str,
+        status: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SepTransaction>> {
+        let txs = sqlx::query_as::<_, SepTransaction>(
+            r#"
+            SELECT * FROM sep_transactions
+            WHERE user_id = $1 AND ($2 IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(txs)
+    }
+
+    /// Open transactions whose poll backoff has elapsed, least recently polled first
+    pub async fn due_for_poll(&self, limit: i64) -> Result<Vec<PollTarget>> {
+        let rows = sqlx::query_as::<_, PollRow>(
+            r#"
+            SELECT * FROM sep_transactions
+            WHERE completed_at IS NULL
+              AND poll_failures < $1
+              AND (
+                last_polled_at IS NULL
+                OR julianday('now') - julianday(last_polled_at)
+                    >= ($2 * (1 << MIN(poll_failures, 6))) / 86400.0
+              )
+            ORDER BY last_polled_at IS NOT NULL, last_polled_at ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(MAX_POLL_FAILURES)
+        .bind(POLL_INTERVAL_SECS)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let auth_token = row.auth_token.and_then(|token| match self.cipher.decrypt(&token) {
+                    Ok(token) => Some(token),
+                    Err(e) => {
+                        tracing::warn!(
+                            transaction_id = %row.transaction.id,
+                            "Failed to decrypt SEP auth token: {}",
+                            e
+                        );
+                        None
+                    }
+                });
+                PollTarget {
+                    transaction: row.transaction,
+                    auth_token,
+                }
+            })
+            .collect())
+    }
+
+    /// Record a poll attempt; failures extend the backoff until the poller gives up
+    pub async fn record_poll(&self, id: &str, success: bool) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sep_transactions
+            SET last_polled_at = $1,
+                poll_failures = CASE WHEN $2 THEN 0 ELSE poll_failures + 1 END
+            WHERE id = $3
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(success)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<SepTransaction>> {
+        let tx = sqlx::query_as::<_, SepTransaction>("SELECT * FROM sep_transactions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tx)
+    }
+
+    /// Find a transaction by the anchor's own transaction id
+    pub async fn get_by_anchor_reference(
+        &self,
+        anchor_id: &str,
+        anchor_transaction_id: &str,
+    ) -> Result<Option<SepTransaction>> {
+        let tx = sqlx::query_as::<_, SepTransaction>(
+            "SELECT * FROM sep_transactions WHERE anchor_id = $1 AND anchor_transaction_id = $2",
+        )
+        .bind(anchor_id)
+        .bind(anchor_transaction_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tx)
+    }
+
+    /// Store a new anchor-reported status. Terminal transactions are never reopened.
+    pub async fn apply_status(
+        &self,
+        tx: &SepTransaction,
+        status: &str,
+        message: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<StatusUpdate> {
+        if is_terminal_status(&tx.status) {
+            return Ok(if tx.status == status {
+                StatusUpdate::Unchanged
+            } else {
+                StatusUpdate::AlreadyTerminal
+            });
+        }
+        if tx.status == status && message.is_none() {
+            return Ok(StatusUpdate::Unchanged);
+        }
+
+        let completed_at = is_terminal_status(status).then_some(at);
+        sqlx::query(
+            r#"
+            UPDATE sep_transactions
+            SET status = $1,
+                status_message = COALESCE($2, status_message),
+                updated_at = $3,
+                completed_at = $4
+            WHERE id = $5
+            "#,
+        )
+        .bind(status)
+        .bind(message)
+        .bind(at)
+        .bind(completed_at)
+        .bind(&tx.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(if tx.status == status {
+            StatusUpdate::Unchanged
+        } else {
+            StatusUpdate::Changed {
+                previous: tx.status.clone(),
+            }
+        })
+    }
+
+    /// Store a user's SEP-10 token for a transfer server, replacing any previous one
+    pub async fn save_anchor_session(
+        &self,
+        user_id: &str,
+        transfer_server: &str,
+        auth_token: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let encrypted = self.cipher.encrypt(auth_token)?;
+        sqlx::query(
+            r#"
+            INSERT INTO sep_anchor_sessions (user_id, transfer_server, auth_token, expires_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(user_id, transfer_server) DO UPDATE SET
+                auth_token = excluded.auth_token,
+                expires_at = excluded.expires_at,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(transfer_server)
+        .bind(encrypted)
+        .bind(expires_at)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// A user's unexpired SEP-10 token for a transfer server
+    pub async fn anchor_session(&self, user_id: &str, transfer_server: &str) -> Result<Option<String>> {
+        let token = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT auth_token FROM sep_anchor_sessions
+            WHERE user_id = $1 AND transfer_server = $2
+              AND (expires_at IS NULL OR julianday(expires_at) > julianday('now'))
+            "#,
+        )
+        .bind(user_id)
+        .bind(transfer_server)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        self.cipher.decrypt_opt(token.as_deref())
+    }
+
+    /// Returns false if there was no session to delete
+    pub async fn delete_anchor_session(&self, user_id: &str, transfer_server: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "DELETE FROM sep_anchor_sessions WHERE user_id = $1 AND transfer_server = $2",
+        )
+        .bind(user_id)
+        .bind(transfer_server)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Public key the anchor signs status callbacks with, if configured
+    pub async fn callback_signing_key(&self, anchor_id: &str) -> Result<Option<String>> {
+        let key = sqlx::query_scalar::<_, String>(
+            "SELECT signing_key FROM anchor_callback_keys WHERE anchor_id = $1",
+        )
+        .bind(anchor_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(key)
+    }
+
+    pub async fn set_callback_signing_key(
+        &self,
+        anchor_id: &str,
+        signing_key: &str,
+        updated_by: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_callback_keys (anchor_id, signing_key, updated_by, updated_at)
+            VALUES ($1, $2, $3, datetime('now'))
+            ON CONFLICT(anchor_id) DO UPDATE SET
+                signing_key = excluded.signing_key,
+                updated_by = excluded.updated_by,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(signing_key)
+        .bind(updated_by)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminal_statuses() {
+        assert!(is_terminal_status("completed"));
+        assert!(is_terminal_status("refunded"));
+        assert!(!is_terminal_status("pending_anchor"));
+        assert!(!is_terminal_status("incomplete"));
+    }
+}
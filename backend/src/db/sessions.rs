@@ -0,0 +1,227 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::crypto::lookup_hash;
+
+/// An issued refresh token and the device it was issued to
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct UserSession {
+    pub id: String,
+    #[serde(skip)]
+    pub user_id: String,
+    #[serde(skip)]
+    pub refresh_token_hash: String,
+    pub user_agent: Option<String>,
+    pub device: String,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip)]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Where a login came from
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// Short human-readable label for a user agent, e.g. "Firefox on Linux"
+pub fn device_label(user_agent: Option<&str>) -> String {
+    let Some(ua) = user_agent.filter(|ua| !ua.trim().is_empty()) else {
+        return "Unknown device".to_string();
+    };
+
+    // Order matters: Edge and Opera also claim Chrome, Chrome claims Safari
+    let browser = [
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+        ("curl/", "curl"),
+    ]
+    .iter()
+    .find(|(marker, _)| ua.contains(marker))
+    .map(|(_, name)| *name);
+
+    // iOS and Android user agents also mention Mac OS X and Linux
+    let os = [
+        ("iPhone", "iOS"),
+        ("iPad", "iPadOS"),
+        ("Android", "Android"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ]
+    .iter()
+    .find(|(marker, _)| ua.contains(marker))
+    .map(|(_, name)| *name);
+
+    match (browser, os) {
+        (Some(browser), Some(os)) => format!("{} on {}", browser, os),
+        (Some(name), None) | (None, Some(name)) => name.to_string(),
+        (None, None) => "Unknown device".to_string(),
+    }
+}
+
+pub struct SessionStore {
+    pool: SqlitePool,
+}
+
+impl SessionStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// New session id, to be embedded in the tokens before the session is stored
+    pub fn new_session_id() -> String {
+        Uuid::new_v4().to_string()
+    }
+
+    pub async fn create(
+        &self,
+        id: &str,
+        user_id: &str,
+        refresh_token: &str,
+        client: &ClientInfo,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO user_sessions (
+                id, user_id, refresh_token_hash, user_agent, device, ip_address,
+                created_at, last_used_at, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $7, $8)
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(lookup_hash(refresh_token))
+        .bind(&client.user_agent)
+        .bind(device_label(client.user_agent.as_deref()))
+        .bind(&client.ip_address)
+        .bind(now)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// True when the user has signed in before, but never from a device with
+    /// this label. A user's first login is not treated as a new device.
+    pub async fn is_new_device(&self, user_id: &str, device: &str) -> Result<bool> {
+        let (total, same_device): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), COALESCE(SUM(device = $2), 0)
+            FROM user_sessions
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(device)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(total > 0 && same_device == 0)
+    }
+
+    /// The session a refresh token was issued for, if it is still active
+    pub async fn find_active(&self, id: &str, refresh_token: &str) -> Result<Option<UserSession>> {
+        let session = sqlx::query_as::<_, UserSession>(
+            r#"
+            SELECT * FROM user_sessions
+            WHERE id = $1 AND refresh_token_hash = $2
+              AND revoked_at IS NULL AND expires_at > $3
+            "#,
+        )
+        .bind(id)
+        .bind(lookup_hash(refresh_token))
+        .bind(Utc::now())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    pub async fn touch(&self, id: &str) -> Result<()> {
+        sqlx::query("UPDATE user_sessions SET last_used_at = $1 WHERE id = $2")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Active sessions of a user, most recently used first
+    pub async fn list_active(&self, user_id: &str) -> Result<Vec<UserSession>> {
+        let sessions = sqlx::query_as::<_, UserSession>(
+            r#"
+            SELECT * FROM user_sessions
+            WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > $2
+            ORDER BY last_used_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    /// Revoke one of a user's sessions. Returns false when the user has no
+    /// active session with that id.
+    pub async fn revoke(&self, user_id: &str, id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_sessions SET revoked_at = $1
+            WHERE id = $2 AND user_id = $3 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_label() {
+        assert_eq!(
+            device_label(Some(
+                "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0"
+            )),
+            "Firefox on Linux"
+        );
+        assert_eq!(
+            device_label(Some(
+                "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1"
+            )),
+            "Safari on iOS"
+        );
+        assert_eq!(
+            device_label(Some(
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36 Edg/126.0.0.0"
+            )),
+            "Edge on Windows"
+        );
+        assert_eq!(device_label(Some("curl/8.5.0")), "curl");
+        assert_eq!(device_label(None), "Unknown device");
+    }
+}
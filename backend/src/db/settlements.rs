@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+/// A settled path payment
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentSettlement {
+    pub transaction_hash: String,
+    pub corridor_key: String,
+    pub started_at: DateTime<Utc>,
+    pub settled_at: DateTime<Utc>,
+    /// Transactions it took, including the one that settled it
+    pub attempts: i64,
+}
+
+/// Path payment settlement times and the failed attempts awaiting a retry
+pub struct PaymentSettlements {
+    pool: SqlitePool,
+}
+
+impl PaymentSettlements {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Close time of ledger `sequence`, if it has been ingested
+    pub async fn ledger_close_time(&self, sequence: u64) -> Result<Option<DateTime<Utc>>> {
+        sqlx::query_scalar::<_, DateTime<Utc>>("SELECT close_time FROM ledgers WHERE sequence = ?")
+            .bind(sequence as i64)
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to load ledger close time")
+    }
+
+    /// Count a failed attempt by `source_account` with `memo`; the first
+    /// attempt's `started_at` is kept
+    pub async fn record_attempt(
+        &self,
+        source_account: &str,
+        memo: &str,
+        started_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO settlement_attempts (source_account, memo, started_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(source_account, memo) DO UPDATE SET attempts = attempts + 1
+            "#,
+        )
+        .bind(source_account)
+        .bind(memo)
+        .bind(started_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record settlement attempt")?;
+        Ok(())
+    }
+
+    /// Remove and return the start and count of failed attempts by
+    /// `source_account` with `memo`
+    pub async fn take_attempts(
+        &self,
+        source_account: &str,
+        memo: &str,
+    ) -> Result<Option<(DateTime<Utc>, i64)>> {
+        sqlx::query_as::<_, (DateTime<Utc>, i64)>(
+            r#"
+            DELETE FROM settlement_attempts
+            WHERE source_account = ? AND memo = ?
+            RETURNING started_at, attempts
+            "#,
+        )
+        .bind(source_account)
+        .bind(memo)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to load settlement attempts")
+    }
+
+    /// Forget failed attempts started before `before`. Returns how many.
+    pub async fn expire_attempts(&self, before: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM settlement_attempts WHERE started_at < ?")
+            .bind(before.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to expire settlement attempts")?;
+        Ok(result.rows_affected())
+    }
+
+    /// Store a settlement; one already stored for the transaction is kept
+    pub async fn record(&self, settlement: &PaymentSettlement) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO payment_settlements (
+                transaction_hash, corridor_key, started_at, settled_at,
+                settlement_ms, attempts
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&settlement.transaction_hash)
+        .bind(&settlement.corridor_key)
+        .bind(settlement.started_at.to_rfc3339())
+        .bind(settlement.settled_at.to_rfc3339())
+        .bind((settlement.settled_at - settlement.started_at).num_milliseconds())
+        .bind(settlement.attempts)
+        .execute(&self.pool)
+        .await
+        .context("Failed to store payment settlement")?;
+        Ok(())
+    }
+
+    /// (settlement time in milliseconds, attempts) of each of the corridor's
+    /// payments settled at or after `since`
+    pub async fn samples(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(i64, i64)>> {
+        sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT settlement_ms, attempts
+            FROM payment_settlements
+            WHERE corridor_key = ? AND settled_at >= ?
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load payment settlement times")
+    }
+}
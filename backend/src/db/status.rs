@@ -0,0 +1,355 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Corridor success rate at or above which a day counts as operational
+pub const OPERATIONAL_SUCCESS_RATE: f64 = 99.0;
+
+/// Corridor success rate at or above which a day counts as degraded rather than an outage
+pub const DEGRADED_SUCCESS_RATE: f64 = 95.0;
+
+/// Health of a component or corridor, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Operational,
+    Degraded,
+    Outage,
+}
+
+impl ComponentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Operational => "operational",
+            Self::Degraded => "degraded",
+            Self::Outage => "outage",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "operational" => Self::Operational,
+            "degraded" => Self::Degraded,
+            _ => Self::Outage,
+        }
+    }
+
+    /// Classify a corridor's daily payment success rate (0-100)
+    pub fn from_success_rate(success_rate: f64) -> Self {
+        if success_rate >= OPERATIONAL_SUCCESS_RATE {
+            Self::Operational
+        } else if success_rate >= DEGRADED_SUCCESS_RATE {
+            Self::Degraded
+        } else {
+            Self::Outage
+        }
+    }
+}
+
+/// Platform components probed by the status monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Component {
+    Ingestion,
+    WebhookDispatch,
+    Rpc,
+}
+
+impl Component {
+    pub const ALL: [Component; 3] = [Component::Ingestion, Component::WebhookDispatch, Component::Rpc];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ingestion => "ingestion",
+            Self::WebhookDispatch => "webhook_dispatch",
+            Self::Rpc => "rpc",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Ingestion => "Ledger ingestion",
+            Self::WebhookDispatch => "Webhook delivery",
+            Self::Rpc => "Stellar RPC connectivity",
+        }
+    }
+}
+
+/// Result of a single health check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusProbe {
+    pub component: Component,
+    pub status: ComponentStatus,
+    pub latency_ms: Option<i64>,
+    pub message: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct ProbeRow {
+    status: String,
+    latency_ms: Option<i64>,
+    message: Option<String>,
+    checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StatusIncident {
+    pub id: String,
+    pub component: String,
+    pub status: String,
+    pub title: String,
+    pub started_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// One day of a corridor's payment health
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CorridorDay {
+    pub corridor_key: String,
+    pub date: String,
+    pub success_rate: f64,
+    pub total_transactions: i64,
+}
+
+pub struct StatusStore {
+    pool: SqlitePool,
+}
+
+impl StatusStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_probe(&self, probe: &StatusProbe) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO status_probes (component, status, latency_ms, message, checked_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(probe.component.as_str())
+        .bind(probe.status.as_str())
+        .bind(probe.latency_ms)
+        .bind(&probe.message)
+        .bind(probe.checked_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn latest_probe(&self, component: Component) -> Result<Option<StatusProbe>> {
+        let row = sqlx::query_as::<_, ProbeRow>(
+            r#"
+            SELECT status, latency_ms, message, checked_at
+            FROM status_probes
+            WHERE component = $1
+            ORDER BY checked_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(component.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| StatusProbe {
+            component,
+            status: ComponentStatus::from_db(&row.status),
+            latency_ms: row.latency_ms,
+            message: row.message,
+            checked_at: row.checked_at,
+        }))
+    }
+
+    /// Percentage of probes since `since` that were not outages, or `None` without probes
+    pub async fn uptime_percent(&self, component: Component, since: DateTime<Utc>) -> Result<Option<f64>> {
+        let (total, up): (i64, Option<i64>) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), SUM(CASE WHEN status != 'outage' THEN 1 ELSE 0 END)
+            FROM status_probes
+            WHERE component = $1 AND checked_at >= $2
+            "#,
+        )
+        .bind(component.as_str())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((total > 0).then(|| up.unwrap_or(0) as f64 / total as f64 * 100.0))
+    }
+
+    pub async fn open_incident(&self, component: Component) -> Result<Option<StatusIncident>> {
+        let incident = sqlx::query_as::<_, StatusIncident>(
+            r#"
+            SELECT id, component, status, title, started_at, resolved_at
+            FROM status_incidents
+            WHERE component = $1 AND resolved_at IS NULL
+            ORDER BY started_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(component.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(incident)
+    }
+
+    pub async fn open_new_incident(
+        &self,
+        component: Component,
+        status: ComponentStatus,
+        title: &str,
+        started_at: DateTime<Utc>,
+    ) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO status_incidents (id, component, status, title, started_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(&id)
+        .bind(component.as_str())
+        .bind(status.as_str())
+        .bind(title)
+        .bind(started_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn update_incident_status(&self, id: &str, status: ComponentStatus) -> Result<()> {
+        sqlx::query("UPDATE status_incidents SET status = $1 WHERE id = $2")
+            .bind(status.as_str())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn resolve_incident(&self, id: &str, resolved_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE status_incidents SET resolved_at = $1 WHERE id = $2")
+            .bind(resolved_at)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Incidents that were open at any point since `since`, newest first
+    pub async fn incidents_since(&self, since: DateTime<Utc>) -> Result<Vec<StatusIncident>> {
+        let incidents = sqlx::query_as::<_, StatusIncident>(
+            r#"
+            SELECT id, component, status, title, started_at, resolved_at
+            FROM status_incidents
+            WHERE resolved_at IS NULL OR resolved_at >= $1
+            ORDER BY started_at DESC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(incidents)
+    }
+
+    /// Daily corridor metrics on or after `since`, for the busiest `limit`
+    /// corridors, limited to `corridor_keys` when given
+    pub async fn corridor_days(
+        &self,
+        since: NaiveDate,
+        limit: i64,
+        corridor_keys: Option<&[String]>,
+    ) -> Result<Vec<CorridorDay>> {
+        let corridor_keys = corridor_keys.map(serde_json::to_string).transpose()?;
+        let days = sqlx::query_as::<_, CorridorDay>(
+            r#"
+            SELECT m.corridor_key, m.date, m.success_rate, m.total_transactions
+            FROM corridor_metrics m
+            JOIN (
+                SELECT corridor_key
+                FROM corridor_metrics
+                WHERE date >= $1
+                  AND ($3 IS NULL OR corridor_key IN (SELECT value FROM json_each($3)))
+                GROUP BY corridor_key
+                ORDER BY SUM(volume_usd) DESC
+                LIMIT $2
+            ) top ON top.corridor_key = m.corridor_key
+            WHERE m.date >= $1
+            ORDER BY m.corridor_key, m.date
+            "#,
+        )
+        .bind(since.format("%Y-%m-%d").to_string())
+        .bind(limit)
+        .bind(corridor_keys)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(days)
+    }
+
+    /// Seconds since any ingestion cursor last advanced, or `None` if none has
+    pub async fn ingestion_lag_secs(&self) -> Result<Option<i64>> {
+        // julianday() accepts both CURRENT_TIMESTAMP and RFC 3339 values
+        let lag: Option<f64> = sqlx::query_scalar(
+            "SELECT (julianday('now') - julianday(MAX(updated_at))) * 86400.0 FROM ingestion_state",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(lag.map(|secs| secs.max(0.0) as i64))
+    }
+
+    /// Failed and total webhook events created since `since`
+    pub async fn webhook_delivery_counts(&self, since: DateTime<Utc>) -> Result<(i64, i64)> {
+        let (total, failed): (i64, Option<i64>) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END)
+            FROM webhook_events
+            WHERE julianday(created_at) >= julianday($1)
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((failed.unwrap_or(0), total))
+    }
+
+    /// Delete probes older than `before`; incidents are kept
+    pub async fn prune_probes(&self, before: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM status_probes WHERE checked_at < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_from_success_rate() {
+        assert_eq!(ComponentStatus::from_success_rate(99.5), ComponentStatus::Operational);
+        assert_eq!(ComponentStatus::from_success_rate(97.0), ComponentStatus::Degraded);
+        assert_eq!(ComponentStatus::from_success_rate(80.0), ComponentStatus::Outage);
+    }
+
+    #[test]
+    fn test_status_ordering_is_by_severity() {
+        let worst = [ComponentStatus::Degraded, ComponentStatus::Outage, ComponentStatus::Operational]
+            .into_iter()
+            .max();
+        assert_eq!(worst, Some(ComponentStatus::Outage));
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// A suspected wash-trading pattern awaiting or after review
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct SurveillanceFlag {
+    pub id: i64,
+    pub corridor_key: String,
+    /// `self_trade`, `round_trip` or `circular_flow`
+    pub pattern: String,
+    /// Sorted, comma-separated accounts involved
+    pub accounts: String,
+    pub trade_count: i64,
+    /// In the corridor's first asset
+    pub volume: f64,
+    pub window_start: String,
+    pub window_end: String,
+    pub detected_at: String,
+    /// `open`, `confirmed` or `dismissed`
+    pub status: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_note: Option<String>,
+}
+
+/// A flag to store; see [`SurveillanceFlags::record`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewSurveillanceFlag {
+    pub corridor_key: String,
+    pub pattern: &'static str,
+    pub accounts: Vec<String>,
+    pub trade_count: i64,
+    pub volume: f64,
+    pub window_start: String,
+    pub window_end: String,
+}
+
+/// Outcome of reviewing a flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Open,
+    Confirmed,
+    Dismissed,
+}
+
+impl ReviewStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewStatus::Open => "open",
+            ReviewStatus::Confirmed => "confirmed",
+            ReviewStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+/// Wash-trading flags from trade surveillance
+pub struct SurveillanceFlags {
+    pool: SqlitePool,
+}
+
+impl SurveillanceFlags {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Store `flags`, skipping any already flagged for the same corridor,
+    /// pattern and accounts in an overlapping window. Returns the number
+    /// stored.
+    pub async fn record(&self, flags: &[NewSurveillanceFlag]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut stored = 0;
+        for flag in flags {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO surveillance_flags (
+                    corridor_key, pattern, accounts, trade_count, volume,
+                    window_start, window_end
+                )
+                SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM surveillance_flags
+                    WHERE corridor_key = ?1 AND pattern = ?2 AND accounts = ?3
+                      AND window_end > ?6 AND window_start < ?7
+                )
+                "#,
+            )
+            .bind(&flag.corridor_key)
+            .bind(flag.pattern)
+            .bind(flag.accounts.join(","))
+            .bind(flag.trade_count)
+            .bind(flag.volume)
+            .bind(&flag.window_start)
+            .bind(&flag.window_end)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to store surveillance flag")?;
+            stored += result.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(stored)
+    }
+
+    /// Flags with `status`, or all flags, newest first
+    pub async fn list(
+        &self,
+        status: Option<ReviewStatus>,
+        limit: i64,
+    ) -> Result<Vec<SurveillanceFlag>> {
+        sqlx::query_as::<_, SurveillanceFlag>(
+            r#"
+            SELECT * FROM surveillance_flags
+            WHERE ?1 IS NULL OR status = ?1
+            ORDER BY detected_at DESC, id DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(status.map(|s| s.as_str()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load surveillance flags")
+    }
+
+    /// Record `reviewer`'s decision on flag `id`. `None` if there is no such
+    /// flag.
+    pub async fn review(
+        &self,
+        id: i64,
+        status: ReviewStatus,
+        reviewer: &str,
+        note: Option<&str>,
+    ) -> Result<Option<SurveillanceFlag>> {
+        sqlx::query_as::<_, SurveillanceFlag>(
+            r#"
+            UPDATE surveillance_flags
+            SET status = ?, reviewed_by = ?, reviewed_at = CURRENT_TIMESTAMP, review_note = ?
+            WHERE id = ?
+            RETURNING *
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(reviewer)
+        .bind(note)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to review surveillance flag")
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::models::corridor::{Corridor, CorridorKey};
+use crate::plan::Plan;
+use crate::tenant::TenantScope;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Tenant {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    /// Customer the tenant's usage is billed to
+    pub billing_customer_id: Option<String>,
+    /// `free`, `pro` or `enterprise`
+    pub plan: String,
+}
+
+/// Tenants and their anchor and corridor directories
+pub struct Tenants {
+    pool: SqlitePool,
+}
+
+impl Tenants {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, name: &str) -> Result<Tenant> {
+        let tenant = sqlx::query_as::<_, Tenant>(
+            "INSERT INTO tenants (id, name) VALUES ($1, $2) RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(tenant)
+    }
+
+    pub async fn list(&self) -> Result<Vec<Tenant>> {
+        let tenants = sqlx::query_as::<_, Tenant>("SELECT * FROM tenants ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(tenants)
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<Tenant>> {
+        let tenant = sqlx::query_as::<_, Tenant>("SELECT * FROM tenants WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tenant)
+    }
+
+    /// Scope requests made with the key to `tenant_id`. Returns false if
+    /// there is no such active key.
+    pub async fn assign_api_key(&self, key_id: &str, tenant_id: &str) -> Result<bool> {
+        let result =
+            sqlx::query("UPDATE api_keys SET tenant_id = $1 WHERE id = $2 AND status = 'active'")
+                .bind(tenant_id)
+                .bind(key_id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// The tenant's plan; unknown or missing tenants are on the free plan
+    pub async fn plan(&self, tenant_id: &str) -> Result<Plan> {
+        let plan = sqlx::query_scalar::<_, String>("SELECT plan FROM tenants WHERE id = $1")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(plan.as_deref().and_then(Plan::parse).unwrap_or(Plan::Free))
+    }
+
+    /// Move the tenant to `plan`. Returns false if there is no such tenant.
+    pub async fn set_plan(&self, tenant_id: &str, plan: Plan) -> Result<bool> {
+        let result = sqlx::query("UPDATE tenants SET plan = $1 WHERE id = $2")
+            .bind(plan.as_str())
+            .bind(tenant_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Bill the tenant's usage to `customer_id`, or stop billing it. Returns
+    /// false if there is no such tenant.
+    pub async fn set_billing_customer(
+        &self,
+        tenant_id: &str,
+        customer_id: Option<&str>,
+    ) -> Result<bool> {
+        let result = sqlx::query("UPDATE tenants SET billing_customer_id = $1 WHERE id = $2")
+            .bind(customer_id)
+            .bind(tenant_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Add an anchor to the tenant's directory; a no-op for [`TenantScope::All`]
+    pub async fn add_anchor(&self, tenant: &TenantScope, anchor_id: &str) -> Result<()> {
+        if let Some(tenant_id) = tenant.id() {
+            sqlx::query(
+                "INSERT OR IGNORE INTO tenant_anchors (tenant_id, anchor_id) VALUES ($1, $2)",
+            )
+            .bind(tenant_id)
+            .bind(anchor_id)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Add a corridor, by asset pair in either order, to the tenant's
+    /// directory; a no-op for [`TenantScope::All`]
+    pub async fn add_corridor(&self, tenant: &TenantScope, corridor: &Corridor) -> Result<()> {
+        if let Some(tenant_id) = tenant.id() {
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO tenant_corridors (tenant_id, corridor_id)
+                SELECT $1, id FROM corridors
+                WHERE (source_asset_code = $2 AND source_asset_issuer = $3
+                       AND destination_asset_code = $4 AND destination_asset_issuer = $5)
+                   OR (source_asset_code = $4 AND source_asset_issuer = $5
+                       AND destination_asset_code = $2 AND destination_asset_issuer = $3)
+                "#,
+            )
+            .bind(tenant_id)
+            .bind(&corridor.asset_a_code)
+            .bind(&corridor.asset_a_issuer)
+            .bind(&corridor.asset_b_code)
+            .bind(&corridor.asset_b_issuer)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Whether the anchor is in the scope's directory
+    pub async fn has_anchor(&self, tenant: &TenantScope, anchor_id: &str) -> Result<bool> {
+        let Some(tenant_id) = tenant.id() else {
+            return Ok(true);
+        };
+        let found = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM tenant_anchors WHERE tenant_id = $1 AND anchor_id = $2",
+        )
+        .bind(tenant_id)
+        .bind(anchor_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(found > 0)
+    }
+
+    /// Whether the corridor is in the scope's directory
+    pub async fn has_corridor(&self, tenant: &TenantScope, corridor_id: &str) -> Result<bool> {
+        let Some(tenant_id) = tenant.id() else {
+            return Ok(true);
+        };
+        let found = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM tenant_corridors WHERE tenant_id = $1 AND corridor_id = $2",
+        )
+        .bind(tenant_id)
+        .bind(corridor_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(found > 0)
+    }
+
+    /// Keys of the corridors in the tenant's directory; `None` for
+    /// [`TenantScope::All`], which sees every corridor
+    pub async fn corridor_keys(
+        &self,
+        tenant: &TenantScope,
+    ) -> Result<Option<HashSet<CorridorKey>>> {
+        let Some(tenant_id) = tenant.id() else {
+            return Ok(None);
+        };
+        let rows = sqlx::query_as::<_, (String, String, String, String)>(
+            r#"
+            SELECT c.source_asset_code, c.source_asset_issuer,
+                   c.destination_asset_code, c.destination_asset_issuer
+            FROM corridors c
+            JOIN tenant_corridors tc ON tc.corridor_id = c.id
+            WHERE tc.tenant_id = $1
+            "#,
+        )
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some(
+            rows.into_iter()
+                .map(|(source_code, source_issuer, dest_code, dest_issuer)| {
+                    Corridor::new(source_code, source_issuer, dest_code, dest_issuer).key()
+                })
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,232 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::billing::UsageKind;
+
+/// A tenant's usage over a window
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UsageTotals {
+    pub api_calls: i64,
+    pub webhook_deliveries: i64,
+    pub ws_minutes: i64,
+}
+
+/// Usage of one kind for a billable tenant, persisted before it is sent to
+/// the billing exporter so every retry reports the same quantity under the
+/// same identifier
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ExportBatch {
+    pub id: i64,
+    pub tenant_id: String,
+    pub billing_customer_id: String,
+    pub kind: String,
+    pub first_event_id: i64,
+    pub last_event_id: i64,
+    pub quantity: i64,
+    pub identifier: String,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingUsage {
+    tenant_id: String,
+    billing_customer_id: String,
+    kind: String,
+    quantity: i64,
+    first_event_id: i64,
+    last_event_id: i64,
+}
+
+/// Metered usage events
+pub struct UsageEvents {
+    pool: SqlitePool,
+}
+
+impl UsageEvents {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, tenant_id: &str, kind: UsageKind, quantity: i64) -> Result<()> {
+        self.record_at(tenant_id, kind, quantity, Utc::now()).await
+    }
+
+    pub async fn record_at(
+        &self,
+        tenant_id: &str,
+        kind: UsageKind,
+        quantity: i64,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO usage_events (tenant_id, kind, quantity, recorded_at) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(tenant_id)
+        .bind(kind.as_str())
+        .bind(quantity)
+        .bind(recorded_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Usage recorded for the tenant between `from` and `to`
+    pub async fn totals(
+        &self,
+        tenant_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<UsageTotals> {
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            r#"
+            SELECT kind, COALESCE(SUM(quantity), 0)
+            FROM usage_events
+            WHERE tenant_id = $1 AND recorded_at >= $2 AND recorded_at <= $3
+            GROUP BY kind
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut totals = UsageTotals::default();
+        for (kind, quantity) in rows {
+            match UsageKind::parse(&kind) {
+                Some(UsageKind::ApiCall) => totals.api_calls = quantity,
+                Some(UsageKind::WebhookDelivery) => totals.webhook_deliveries = quantity,
+                Some(UsageKind::WsMinutes) => totals.ws_minutes = quantity,
+                None => {}
+            }
+        }
+        Ok(totals)
+    }
+
+    /// Put usage not yet in an export batch into a new batch per tenant and
+    /// kind, for tenants with a billing customer. Usage of tenants without
+    /// one stays unbatched until a customer is assigned. Returns the number
+    /// of batches created.
+    pub async fn create_export_batches(&self) -> Result<usize> {
+        let mut tx = self.pool.begin().await?;
+        let pending = sqlx::query_as::<_, PendingUsage>(
+            r#"
+            SELECT u.tenant_id, t.billing_customer_id, u.kind,
+                   SUM(u.quantity) AS quantity, MIN(u.id) AS first_event_id,
+                   MAX(u.id) AS last_event_id
+            FROM usage_events u
+            JOIN tenants t ON t.id = u.tenant_id
+            WHERE u.exported_at IS NULL AND u.export_batch_id IS NULL
+              AND t.billing_customer_id IS NOT NULL
+            GROUP BY u.tenant_id, t.billing_customer_id, u.kind
+            ORDER BY u.tenant_id, u.kind
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let now = Utc::now();
+        for usage in &pending {
+            let batch_id: i64 = sqlx::query_scalar(
+                r#"
+                INSERT INTO usage_export_batches (
+                    tenant_id, billing_customer_id, kind, first_event_id, last_event_id,
+                    quantity, identifier, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING id
+                "#,
+            )
+            .bind(&usage.tenant_id)
+            .bind(&usage.billing_customer_id)
+            .bind(&usage.kind)
+            .bind(usage.first_event_id)
+            .bind(usage.last_event_id)
+            .bind(usage.quantity)
+            .bind(format!(
+                "{}:{}:{}-{}",
+                usage.tenant_id, usage.kind, usage.first_event_id, usage.last_event_id
+            ))
+            .bind(now)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE usage_events SET export_batch_id = $1
+                WHERE tenant_id = $2 AND kind = $3 AND id BETWEEN $4 AND $5
+                  AND exported_at IS NULL AND export_batch_id IS NULL
+                "#,
+            )
+            .bind(batch_id)
+            .bind(&usage.tenant_id)
+            .bind(&usage.kind)
+            .bind(usage.first_event_id)
+            .bind(usage.last_event_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(pending.len())
+    }
+
+    /// Export batches the exporter has not acknowledged yet, oldest first
+    pub async fn unacknowledged_batches(&self) -> Result<Vec<ExportBatch>> {
+        let batches = sqlx::query_as::<_, ExportBatch>(
+            r#"
+            SELECT id, tenant_id, billing_customer_id, kind, first_event_id, last_event_id,
+                   quantity, identifier, created_at, attempts
+            FROM usage_export_batches
+            WHERE acknowledged_at IS NULL
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(batches)
+    }
+
+    /// Record a failed send of the batch; it is retried on the next run
+    pub async fn record_batch_failure(&self, batch_id: i64, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE usage_export_batches SET attempts = attempts + 1, last_error = $1 WHERE id = $2",
+        )
+        .bind(error)
+        .bind(batch_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark the batch, and the usage it covers, as exported
+    pub async fn acknowledge_batch(&self, batch_id: i64) -> Result<()> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE usage_export_batches
+            SET attempts = attempts + 1, last_error = NULL, acknowledged_at = $1
+            WHERE id = $2
+            "#,
+        )
+        .bind(now)
+        .bind(batch_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE usage_events SET exported_at = $1 WHERE export_batch_id = $2")
+            .bind(now)
+            .bind(batch_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
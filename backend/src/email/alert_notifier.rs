@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::alerts::Alert;
+use crate::database::Database;
+use crate::email::outbox::{EmailKind, EmailOutbox};
+use crate::email::unsubscribe::{with_unsubscribe_footer, UnsubscribeScope, UnsubscribeTokens};
+use crate::i18n::{self, Locale};
+
+/// Outbox reference shared by every email sent for one alert
+pub fn alert_reference(alert: &Alert) -> String {
+    format!("{}@{}", alert.corridor_id, alert.timestamp)
+}
+
+fn render_alert_html(alert: &Alert, locale: Locale) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{}">
+<body style="font-family: Arial, sans-serif; margin: 20px;">
+    <h2>{}</h2>
+    <p>{}</p>
+    <p style="color: #666;">{}</p>
+</body>
+</html>"#,
+        locale.as_str(),
+        alert.corridor_id,
+        alert.localized_message(locale),
+        alert.timestamp
+    )
+}
+
+/// Queues an email per corridor alert for every user who opted into alerts
+pub struct AlertEmailNotifier {
+    outbox: Arc<EmailOutbox>,
+    db: Arc<Database>,
+    alert_rx: broadcast::Receiver<Alert>,
+    unsubscribe: Option<UnsubscribeTokens>,
+}
+
+impl AlertEmailNotifier {
+    pub fn new(
+        outbox: Arc<EmailOutbox>,
+        db: Arc<Database>,
+        alert_rx: broadcast::Receiver<Alert>,
+    ) -> Self {
+        Self {
+            outbox,
+            db,
+            alert_rx,
+            unsubscribe: None,
+        }
+    }
+
+    /// Add a signed one-click unsubscribe link to each alert email
+    pub fn with_unsubscribe_links(mut self, tokens: UnsubscribeTokens) -> Self {
+        self.unsubscribe = Some(tokens);
+        self
+    }
+
+    pub async fn start(mut self) {
+        tracing::info!("Alert email notifier started");
+
+        loop {
+            match self.alert_rx.recv().await {
+                Ok(alert) => {
+                    if let Err(e) = self.queue_alert(&alert).await {
+                        tracing::error!("Failed to queue alert emails: {}", e);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Alert email notifier skipped {} alerts", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn queue_alert(&self, alert: &Alert) -> anyhow::Result<()> {
+        let subscribers = self.db.preferences().alert_subscribers().await?;
+        let reference = alert_reference(alert);
+
+        for subscriber in &subscribers {
+            let locale = Locale::parse(&subscriber.locale).unwrap_or_default();
+            let subject = i18n::t(
+                locale,
+                "alert-email-subject",
+                &[("corridor", alert.corridor_id.clone())],
+            );
+            let mut html = render_alert_html(alert, locale);
+            if let Some(tokens) = &self.unsubscribe {
+                let link = tokens.link(&subscriber.user_id, UnsubscribeScope::Alerts);
+                html = with_unsubscribe_footer(&html, &link, locale);
+            }
+            self.outbox
+                .enqueue(
+                    EmailKind::Alert,
+                    &reference,
+                    &subscriber.email,
+                    &subject,
+                    &html,
+                )
+                .await?;
+        }
+
+        tracing::debug!(
+            "Queued alert {} for {} subscribers",
+            reference,
+            subscribers.len()
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,13 @@
+pub mod alert_notifier;
+pub mod outbox;
+pub mod provider;
 pub mod service;
 pub mod report;
 pub mod scheduler;
+pub mod security_alert;
+pub mod unsubscribe;
 
+pub use outbox::EmailOutbox;
+pub use provider::EmailProvider;
 pub use service::EmailService;
 pub use scheduler::DigestScheduler;
@@ -0,0 +1,366 @@
+//! Persistent outbound email queue.
+//!
+//! Digests and alert emails are written to `email_outbox` and delivered by a
+//! background worker. Transient failures are retried with exponential
+//! backoff; a hard bounce marks the message `bounced` and adds the recipient
+//! to `email_suppressions` so nothing more is sent to it.
+
+use anyhow::Result;
+use chrono::{Duration as ChronoDuration, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::email::provider::SendError;
+use crate::email::service::EmailService;
+
+/// Delivery attempts before a message is marked failed
+pub const MAX_ATTEMPTS: i64 = 5;
+
+/// Delay before the first retry; doubles with each further attempt
+const BASE_RETRY_DELAY_SECS: i64 = 60;
+
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// Messages sent per worker pass
+const BATCH_SIZE: i64 = 50;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// What an email was sent for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailKind {
+    Digest,
+    Alert,
+    /// Account security notices, e.g. a login from a new device
+    Security,
+}
+
+impl EmailKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Digest => "digest",
+            Self::Alert => "alert",
+            Self::Security => "security",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "digest" => Some(Self::Digest),
+            "alert" => Some(Self::Alert),
+            "security" => Some(Self::Security),
+            _ => None,
+        }
+    }
+}
+
+/// One queued message and where it stands
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct EmailDelivery {
+    pub id: String,
+    pub kind: String,
+    pub reference: String,
+    pub recipient: String,
+    pub subject: String,
+    /// pending, sent, failed, bounced or suppressed
+    pub status: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub provider: Option<String>,
+    pub provider_message_id: Option<String>,
+    pub next_attempt_at: String,
+    pub created_at: String,
+    pub sent_at: Option<String>,
+}
+
+/// Delivery counts for one digest run or alert
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct EmailDeliverySummary {
+    pub kind: String,
+    pub reference: String,
+    pub total: i64,
+    pub pending: i64,
+    pub sent: i64,
+    pub failed: i64,
+    pub bounced: i64,
+    pub suppressed: i64,
+    pub first_queued_at: String,
+    pub last_sent_at: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct QueuedEmail {
+    id: String,
+    recipient: String,
+    subject: String,
+    html_body: String,
+    attempts: i64,
+}
+
+/// Wait before retrying a message that has failed `attempts` times
+pub fn retry_delay(attempts: i64) -> ChronoDuration {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    ChronoDuration::seconds((BASE_RETRY_DELAY_SECS << exponent).min(MAX_RETRY_DELAY_SECS))
+}
+
+pub struct EmailOutbox {
+    db: Arc<Database>,
+    service: Arc<EmailService>,
+}
+
+impl EmailOutbox {
+    pub fn new(db: Arc<Database>, service: Arc<EmailService>) -> Self {
+        Self { db, service }
+    }
+
+    /// Queue a message for delivery. Suppressed recipients are recorded as
+    /// `suppressed` and never sent. Returns the outbox id.
+    pub async fn enqueue(
+        &self,
+        kind: EmailKind,
+        reference: &str,
+        to: &str,
+        subject: &str,
+        html: &str,
+    ) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let suppression: Option<String> = sqlx::query_scalar(
+            "SELECT reason FROM email_suppressions WHERE email = ? COLLATE NOCASE",
+        )
+        .bind(to)
+        .fetch_optional(self.db.pool())
+        .await?;
+        let status = if suppression.is_some() {
+            "suppressed"
+        } else {
+            "pending"
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_outbox (
+                id, kind, reference, recipient, subject, html_body, status,
+                last_error, next_attempt_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(kind.as_str())
+        .bind(reference)
+        .bind(to)
+        .bind(subject)
+        .bind(html)
+        .bind(status)
+        .bind(suppression)
+        .bind(&now)
+        .bind(&now)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Deliver due messages until the process stops
+    pub async fn run(&self) {
+        tracing::info!(
+            "Starting email outbox worker (provider: {})",
+            self.service.provider_name()
+        );
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.process_due().await {
+                tracing::error!("Error processing email outbox: {}", e);
+            }
+        }
+    }
+
+    /// Attempt every pending message whose retry time has come. Returns the
+    /// number of messages attempted.
+    pub async fn process_due(&self) -> Result<usize> {
+        let due = sqlx::query_as::<_, QueuedEmail>(
+            r#"
+            SELECT id, recipient, subject, html_body, attempts
+            FROM email_outbox
+            WHERE status = 'pending' AND next_attempt_at <= ?
+            ORDER BY next_attempt_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(Utc::now().to_rfc3339())
+        .bind(BATCH_SIZE)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let attempted = due.len();
+        for email in due {
+            self.deliver(email).await?;
+        }
+
+        Ok(attempted)
+    }
+
+    async fn deliver(&self, email: QueuedEmail) -> Result<()> {
+        let attempts = email.attempts + 1;
+        let provider = self.service.provider_name();
+        let now = Utc::now();
+
+        let result = self
+            .service
+            .send_html(&email.recipient, &email.subject, &email.html_body)
+            .await;
+
+        let (status, error, message_id, next_attempt_at) = match result {
+            Ok(message_id) => ("sent", None, message_id, now),
+            Err(SendError::Transient(e)) if attempts < MAX_ATTEMPTS => {
+                ("pending", Some(e), None, now + retry_delay(attempts))
+            }
+            Err(SendError::Transient(e)) | Err(SendError::Permanent(e)) => {
+                ("failed", Some(e), None, now)
+            }
+            Err(SendError::Bounced(e)) => {
+                self.record_bounce(&email.recipient, &e).await?;
+                ("bounced", Some(e), None, now)
+            }
+        };
+
+        if let Some(error) = &error {
+            tracing::warn!(
+                "Email {} to {} {} after {} attempt(s): {}",
+                email.id,
+                email.recipient,
+                status,
+                attempts,
+                error
+            );
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE email_outbox
+            SET status = ?, attempts = ?, last_error = ?, provider = ?,
+                provider_message_id = COALESCE(?, provider_message_id),
+                next_attempt_at = ?,
+                sent_at = CASE WHEN ? = 'sent' THEN ? ELSE sent_at END
+            WHERE id = ?
+            "#,
+        )
+        .bind(status)
+        .bind(attempts)
+        .bind(error)
+        .bind(provider)
+        .bind(message_id)
+        .bind(next_attempt_at.to_rfc3339())
+        .bind(status)
+        .bind(now.to_rfc3339())
+        .bind(&email.id)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Stop sending to `email`, and drop anything still queued for it
+    pub async fn record_bounce(&self, email: &str, reason: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO email_suppressions (email, reason, created_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(email) DO UPDATE SET reason = excluded.reason
+            "#,
+        )
+        .bind(email.to_lowercase())
+        .bind(reason)
+        .bind(Utc::now().to_rfc3339())
+        .execute(self.db.pool())
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE email_outbox SET status = 'suppressed', last_error = ?
+            WHERE status = 'pending' AND recipient = ? COLLATE NOCASE
+            "#,
+        )
+        .bind(reason)
+        .bind(email)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Per-digest / per-alert delivery counts, newest first
+pub async fn delivery_summaries(
+    db: &Database,
+    kind: Option<EmailKind>,
+    limit: i64,
+) -> Result<Vec<EmailDeliverySummary>> {
+    let summaries = sqlx::query_as::<_, EmailDeliverySummary>(
+        r#"
+        SELECT kind, reference,
+               COUNT(*) AS total,
+               SUM(status = 'pending') AS pending,
+               SUM(status = 'sent') AS sent,
+               SUM(status = 'failed') AS failed,
+               SUM(status = 'bounced') AS bounced,
+               SUM(status = 'suppressed') AS suppressed,
+               MIN(created_at) AS first_queued_at,
+               MAX(sent_at) AS last_sent_at
+        FROM email_outbox
+        WHERE ? IS NULL OR kind = ?
+        GROUP BY kind, reference
+        ORDER BY first_queued_at DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(kind.map(|k| k.as_str()))
+    .bind(kind.map(|k| k.as_str()))
+    .bind(limit)
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(summaries)
+}
+
+/// Every message sent for one digest run or alert
+pub async fn deliveries_for(
+    db: &Database,
+    kind: EmailKind,
+    reference: &str,
+) -> Result<Vec<EmailDelivery>> {
+    let deliveries = sqlx::query_as::<_, EmailDelivery>(
+        r#"
+        SELECT id, kind, reference, recipient, subject, status, attempts, last_error,
+               provider, provider_message_id, next_attempt_at, created_at, sent_at
+        FROM email_outbox
+        WHERE kind = ? AND reference = ?
+        ORDER BY recipient ASC
+        "#,
+    )
+    .bind(kind.as_str())
+    .bind(reference)
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(deliveries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_and_caps() {
+        assert_eq!(retry_delay(1), ChronoDuration::seconds(60));
+        assert_eq!(retry_delay(2), ChronoDuration::seconds(120));
+        assert_eq!(retry_delay(4), ChronoDuration::seconds(480));
+        assert_eq!(retry_delay(10), ChronoDuration::seconds(3600));
+    }
+}
@@ -0,0 +1,285 @@
+//! Email delivery providers.
+//!
+//! `Settings::email` (`EMAIL_PROVIDER`) picks the transport: `smtp` (default), `ses` (Amazon SES
+//! through its SMTP interface) or `sendgrid` (SendGrid v3 HTTP API).
+
+use anyhow::{Context, Result};
+use axum::async_trait;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::EmailSettings;
+use crate::http_client;
+
+const SENDGRID_API_URL: &str = "https://api.sendgrid.com/v3/mail/send";
+
+/// A single rendered email
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub from: String,
+    pub to: String,
+    pub subject: String,
+    pub html: String,
+}
+
+/// Why a send failed, which decides what the outbox does next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SendError {
+    /// Worth retrying later (connection errors, 4xx SMTP replies, throttling)
+    Transient(String),
+    /// Will fail the same way on retry (malformed message, rejected request)
+    Permanent(String),
+    /// The recipient's mailbox rejected the message; stop sending to it
+    Bounced(String),
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transient(msg) => write!(f, "transient failure: {}", msg),
+            Self::Permanent(msg) => write!(f, "permanent failure: {}", msg),
+            Self::Bounced(msg) => write!(f, "bounced: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SendError {}
+
+#[async_trait]
+pub trait EmailProvider: Send + Sync {
+    /// Provider name recorded with each delivery ("smtp", "ses", "sendgrid")
+    fn name(&self) -> &'static str;
+
+    /// Deliver one message. Returns the provider's message id when it has one.
+    async fn send(&self, message: &EmailMessage) -> Result<Option<String>, SendError>;
+}
+
+/// Plain SMTP relay. Also used for SES, which exposes the same interface.
+pub struct SmtpProvider {
+    name: &'static str,
+    host: String,
+    credentials: Credentials,
+}
+
+impl SmtpProvider {
+    pub fn new(host: String, user: String, pass: String) -> Self {
+        Self {
+            name: "smtp",
+            host,
+            credentials: Credentials::new(user, pass),
+        }
+    }
+
+    /// Amazon SES SMTP endpoint for `region`, authenticated with SES SMTP credentials
+    pub fn ses(region: &str, user: String, pass: String) -> Self {
+        Self {
+            name: "ses",
+            host: format!("email-smtp.{}.amazonaws.com", region),
+            credentials: Credentials::new(user, pass),
+        }
+    }
+}
+
+/// SMTP reply codes that mean the mailbox itself refused the message
+fn is_mailbox_rejection(code: &str) -> bool {
+    matches!(code, "550" | "551" | "552" | "553")
+}
+
+#[async_trait]
+impl EmailProvider for SmtpProvider {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn send(&self, message: &EmailMessage) -> Result<Option<String>, SendError> {
+        let email = Message::builder()
+            .from(
+                message
+                    .from
+                    .parse()
+                    .map_err(|e| SendError::Permanent(format!("Invalid sender: {}", e)))?,
+            )
+            .to(message
+                .to
+                .parse()
+                .map_err(|e| SendError::Bounced(format!("Invalid recipient: {}", e)))?)
+            .subject(&message.subject)
+            .header(ContentType::TEXT_HTML)
+            .body(message.html.clone())
+            .map_err(|e| SendError::Permanent(e.to_string()))?;
+
+        let mailer = SmtpTransport::relay(&self.host)
+            .map_err(|e| SendError::Transient(e.to_string()))?
+            .credentials(self.credentials.clone())
+            .build();
+
+        // lettre's blocking transport; keep it off the async workers
+        let result = tokio::task::spawn_blocking(move || mailer.send(&email))
+            .await
+            .map_err(|e| SendError::Transient(e.to_string()))?;
+
+        match result {
+            Ok(response) => Ok(response.message().next().map(str::to_string)),
+            Err(e) if e.is_permanent() => {
+                let code = e.status().map(|c| c.to_string()).unwrap_or_default();
+                if is_mailbox_rejection(&code) {
+                    Err(SendError::Bounced(e.to_string()))
+                } else {
+                    Err(SendError::Permanent(e.to_string()))
+                }
+            }
+            Err(e) => Err(SendError::Transient(e.to_string())),
+        }
+    }
+}
+
+/// SendGrid v3 mail send API
+pub struct SendGridProvider {
+    client: Client,
+    api_key: String,
+}
+
+impl SendGridProvider {
+    pub fn new(api_key: String) -> Self {
+        let client = http_client::shared().client(Duration::from_secs(15));
+        Self { client, api_key }
+    }
+}
+
+#[async_trait]
+impl EmailProvider for SendGridProvider {
+    fn name(&self) -> &'static str {
+        "sendgrid"
+    }
+
+    async fn send(&self, message: &EmailMessage) -> Result<Option<String>, SendError> {
+        let body = json!({
+            "personalizations": [{ "to": [{ "email": message.to }] }],
+            "from": { "email": message.from },
+            "subject": message.subject,
+            "content": [{ "type": "text/html", "value": message.html }],
+        });
+
+        let response = self
+            .client
+            .post(SENDGRID_API_URL)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| SendError::Transient(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response
+                .headers()
+                .get("X-Message-Id")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string));
+        }
+
+        let detail = format!("SendGrid returned {}", status);
+        // Auth and throttling problems are ours to fix, so keep retrying them
+        if status == StatusCode::BAD_REQUEST || status == StatusCode::PAYLOAD_TOO_LARGE {
+            Err(SendError::Permanent(detail))
+        } else {
+            Err(SendError::Transient(detail))
+        }
+    }
+}
+
+/// Transport selected with `EMAIL_PROVIDER`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailProviderKind {
+    Smtp,
+    Ses,
+    SendGrid,
+}
+
+impl EmailProviderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Smtp => "smtp",
+            Self::Ses => "ses",
+            Self::SendGrid => "sendgrid",
+        }
+    }
+}
+
+impl FromStr for EmailProviderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "smtp" => Ok(Self::Smtp),
+            "ses" => Ok(Self::Ses),
+            "sendgrid" => Ok(Self::SendGrid),
+            other => Err(format!(
+                "unknown email provider '{}', expected smtp, ses or sendgrid",
+                other
+            )),
+        }
+    }
+}
+
+fn required<'a>(value: &'a Option<String>, name: &str) -> Result<&'a str> {
+    value
+        .as_deref()
+        .with_context(|| format!("{} is not set", name))
+}
+
+/// Build the provider selected in `email` (validated by
+/// `Settings::validate`). Returns `Ok(None)` when email is not configured at
+/// all.
+pub fn provider_from_settings(email: &EmailSettings) -> Result<Option<Arc<dyn EmailProvider>>> {
+    let Some(kind) = email.provider_kind() else {
+        return Ok(None);
+    };
+
+    let provider: Arc<dyn EmailProvider> = match kind {
+        EmailProviderKind::Smtp => Arc::new(SmtpProvider::new(
+            required(&email.smtp_host, "SMTP_HOST")?.to_string(),
+            required(&email.smtp_user, "SMTP_USER")?.to_string(),
+            required(&email.smtp_pass, "SMTP_PASS")?.to_string(),
+        )),
+        EmailProviderKind::Ses => Arc::new(SmtpProvider::ses(
+            &email.ses_region,
+            required(&email.ses_smtp_user, "SES_SMTP_USER")?.to_string(),
+            required(&email.ses_smtp_pass, "SES_SMTP_PASS")?.to_string(),
+        )),
+        EmailProviderKind::SendGrid => Arc::new(SendGridProvider::new(
+            required(&email.sendgrid_api_key, "SENDGRID_API_KEY")?.to_string(),
+        )),
+    };
+
+    Ok(Some(provider))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mailbox_rejection_codes() {
+        assert!(is_mailbox_rejection("550"));
+        assert!(is_mailbox_rejection("553"));
+        assert!(!is_mailbox_rejection("554"));
+        assert!(!is_mailbox_rejection("421"));
+    }
+
+    #[test]
+    fn test_ses_uses_regional_smtp_endpoint() {
+        let provider = SmtpProvider::ses("eu-west-1", "user".into(), "pass".into());
+        assert_eq!(provider.name(), "ses");
+        assert_eq!(provider.host, "email-smtp.eu-west-1.amazonaws.com");
+    }
+}
@@ -3,25 +3,90 @@ use tokio::time::{interval, Duration};
 use chrono::{Datelike, Timelike, Utc};
 
 use crate::cache::CacheManager;
+use crate::database::Database;
 use crate::rpc::StellarRpcClient;
-use crate::email::service::EmailService;
-use crate::email::report::{DigestReport, CorridorSummary, AnchorSummary, generate_html_report};
+use crate::email::outbox::{EmailKind, EmailOutbox};
+use crate::email::report::{build_digest_report, digest_subject, generate_html_report};
+use crate::email::unsubscribe::{with_unsubscribe_footer, UnsubscribeScope, UnsubscribeTokens};
+use crate::i18n::Locale;
+use crate::preferences::DigestFrequency;
+
+/// A digest recipient and the language their email is rendered in
+#[derive(Debug, Clone)]
+pub struct DigestRecipient {
+    pub email: String,
+    pub locale: Locale,
+    /// Set for preference subscribers, who get an unsubscribe link
+    pub user_id: Option<String>,
+}
+
+impl From<String> for DigestRecipient {
+    fn from(email: String) -> Self {
+        Self { email, locale: Locale::default(), user_id: None }
+    }
+}
 
 pub struct DigestScheduler {
-    email_service: Arc<EmailService>,
+    outbox: Arc<EmailOutbox>,
     cache: Arc<CacheManager>,
     rpc_client: Arc<StellarRpcClient>,
-    recipients: Vec<String>,
+    recipients: Vec<DigestRecipient>,
+    db: Option<Arc<Database>>,
+    unsubscribe: Option<UnsubscribeTokens>,
 }
 
 impl DigestScheduler {
     pub fn new(
-        email_service: Arc<EmailService>,
+        outbox: Arc<EmailOutbox>,
         cache: Arc<CacheManager>,
         rpc_client: Arc<StellarRpcClient>,
-        recipients: Vec<String>,
+        recipients: Vec<DigestRecipient>,
     ) -> Self {
-        Self { email_service, cache, rpc_client, recipients }
+        Self { outbox, cache, rpc_client, recipients, db: None, unsubscribe: None }
+    }
+
+    /// Also deliver digests to users who opted in through their preferences
+    pub fn with_preferences(mut self, db: Arc<Database>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Add a signed one-click unsubscribe link to each subscriber's digest
+    pub fn with_unsubscribe_links(mut self, tokens: UnsubscribeTokens) -> Self {
+        self.unsubscribe = Some(tokens);
+        self
+    }
+
+    /// Static recipients plus preference subscribers for this period
+    async fn recipients_for(&self, period: &str) -> Vec<DigestRecipient> {
+        let mut recipients = self.recipients.clone();
+
+        let frequency = match period.to_lowercase().as_str() {
+            "weekly" => DigestFrequency::Weekly,
+            "monthly" => DigestFrequency::Monthly,
+            _ => return recipients,
+        };
+        let Some(db) = &self.db else {
+            return recipients;
+        };
+
+        match db.preferences().digest_subscribers(frequency).await {
+            Ok(subscribers) => {
+                for subscriber in subscribers {
+                    if recipients.iter().any(|r| r.email.eq_ignore_ascii_case(&subscriber.email)) {
+                        continue;
+                    }
+                    recipients.push(DigestRecipient {
+                        email: subscriber.email,
+                        locale: Locale::parse(&subscriber.locale).unwrap_or_default(),
+                        user_id: Some(subscriber.user_id),
+                    });
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load digest subscribers: {}", e),
+        }
+
+        recipients
     }
 
     pub async fn start(self: Arc<Self>) {
@@ -47,73 +112,36 @@ impl DigestScheduler {
         }
     }
 
-    pub async fn send_digest(&self, period: &str) -> anyhow::Result<()> {
-        let report = self.generate_report(period).await?;
-        let html = generate_html_report(&report);
-
-        for recipient in &self.recipients {
-            self.email_service.send_html(
-                recipient,
-                &format!("Stellar Insights - {} Performance Report", period),
-                &html
-            )?;
-        }
-
-        tracing::info!("Sent {} digest to {} recipients", period, self.recipients.len());
-        Ok(())
-    }
-
-    async fn generate_report(&self, period: &str) -> anyhow::Result<DigestReport> {
-        let payments = self
-            .rpc_client
-            .fetch_payments(500, None)
-            .await
-            .map_err(|e| anyhow::anyhow!("{}", e))?;
-
-        let mut corridor_map = std::collections::HashMap::new();
-        for payment in &payments {
-            let key = format!(
-                "{}:{}->XLM:native",
-                payment.asset_code.as_deref().unwrap_or("XLM"),
-                payment.asset_issuer.as_deref().unwrap_or("native")
-            );
-            corridor_map.entry(key).or_insert_with(Vec::new).push(payment);
+    /// Queue the digest for every recipient. Returns the outbox reference the
+    /// run's delivery status is tracked under.
+    pub async fn send_digest(&self, period: &str) -> anyhow::Result<String> {
+        let report = build_digest_report(&self.rpc_client, period).await?;
+        let recipients = self.recipients_for(period).await;
+        let reference = format!("{}-{}", period.to_lowercase(), Utc::now().format("%Y-%m-%d"));
+
+        // Render once per language rather than once per recipient
+        let mut rendered = std::collections::HashMap::new();
+        for recipient in &recipients {
+            let (subject, html) = rendered.entry(recipient.locale).or_insert_with(|| {
+                (
+                    digest_subject(period, recipient.locale),
+                    generate_html_report(&report, recipient.locale),
+                )
+            });
+            let html = match (&self.unsubscribe, &recipient.user_id) {
+                (Some(tokens), Some(user_id)) => with_unsubscribe_footer(
+                    html,
+                    &tokens.link(user_id, UnsubscribeScope::Digest),
+                    recipient.locale,
+                ),
+                _ => html.clone(),
+            };
+            self.outbox
+                .enqueue(EmailKind::Digest, &reference, &recipient.email, subject, &html)
+                .await?;
         }
 
-        let mut corridors: Vec<CorridorSummary> = corridor_map.iter()
-            .map(|(id, payments)| {
-                let volume: f64 = payments.iter()
-                    .filter_map(|p| p.amount.parse::<f64>().ok())
-                    .sum();
-                CorridorSummary {
-                    id: id.clone(),
-                    success_rate: 100.0,
-                    volume_usd: volume,
-                    avg_latency_ms: 450.0,
-                    change_pct: 5.2,
-                }
-            })
-            .collect();
-
-        corridors.sort_by(|a, b| b.volume_usd.partial_cmp(&a.volume_usd).unwrap());
-        corridors.truncate(10);
-
-        let total_volume: f64 = corridors.iter().map(|c| c.volume_usd).sum();
-        let avg_success_rate = corridors.iter().map(|c| c.success_rate).sum::<f64>() / corridors.len() as f64;
-
-        Ok(DigestReport {
-            period: period.to_string(),
-            top_corridors: corridors,
-            top_anchors: vec![
-                AnchorSummary {
-                    name: "Circle USDC".to_string(),
-                    success_rate: 99.5,
-                    total_transactions: 15420,
-                    volume_usd: 2_500_000.0,
-                }
-            ],
-            total_volume,
-            avg_success_rate,
-        })
+        tracing::info!("Queued {} digest {} for {} recipients", period, reference, recipients.len());
+        Ok(reference)
     }
 }
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::email::outbox::{EmailKind, EmailOutbox};
+use crate::i18n::{self, Locale};
+
+/// A login from a device the user hasn't signed in from before
+#[derive(Debug, Clone)]
+pub struct NewDeviceLogin {
+    pub user_id: String,
+    pub session_id: String,
+    pub device: String,
+    pub ip_address: Option<String>,
+}
+
+fn render_new_device_html(login: &NewDeviceLogin, locale: Locale, time: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{}">
+<body style="font-family: Arial, sans-serif; margin: 20px;">
+    <p>{}</p>
+    <p style="color: #666;">{}</p>
+</body>
+</html>"#,
+        locale.as_str(),
+        i18n::t(
+            locale,
+            "new-device-email-body",
+            &[
+                ("device", login.device.clone()),
+                (
+                    "ip",
+                    login
+                        .ip_address
+                        .clone()
+                        .unwrap_or_else(|| "unknown".to_string()),
+                ),
+                ("time", time.to_string()),
+            ]
+        ),
+        i18n::t(locale, "new-device-email-action", &[])
+    )
+}
+
+/// Emails users when their account is signed into from a new device. Users
+/// without a notification email get nothing; the login is still audited.
+pub struct SecurityAlertNotifier {
+    outbox: Arc<EmailOutbox>,
+    db: Arc<Database>,
+}
+
+impl SecurityAlertNotifier {
+    pub fn new(outbox: Arc<EmailOutbox>, db: Arc<Database>) -> Self {
+        Self { outbox, db }
+    }
+
+    pub async fn new_device_login(&self, login: &NewDeviceLogin) -> anyhow::Result<()> {
+        let prefs = self.db.preferences().get(&login.user_id).await?;
+        let Some(email) = prefs.notifications.email.as_deref() else {
+            return Ok(());
+        };
+
+        let locale = prefs.locale;
+        let time = prefs.localize(chrono::Utc::now());
+        let subject = i18n::t(locale, "new-device-email-subject", &[]);
+        let html = render_new_device_html(login, locale, &time);
+        self.outbox
+            .enqueue(
+                EmailKind::Security,
+                &format!("new-device@{}", login.session_id),
+                email,
+                &subject,
+                &html,
+            )
+            .await?;
+        Ok(())
+    }
+}
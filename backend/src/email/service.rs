@@ -1,32 +1,50 @@
-use lettre::{Message, SmtpTransport, Transport};
-use lettre::message::header::ContentType;
-use lettre::transport::smtp::authentication::Credentials;
+use std::sync::Arc;
 
+use crate::config::EmailSettings;
+use crate::email::provider::{provider_from_settings, EmailMessage, EmailProvider, SendError};
+
+/// Sends rendered emails through the configured provider
 pub struct EmailService {
-    smtp_host: String,
-    smtp_user: String,
-    smtp_pass: String,
+    provider: Arc<dyn EmailProvider>,
+    from: String,
 }
 
 impl EmailService {
-    pub fn new(smtp_host: String, smtp_user: String, smtp_pass: String) -> Self {
-        Self { smtp_host, smtp_user, smtp_pass }
+    pub fn new(provider: Arc<dyn EmailProvider>, from: String) -> Self {
+        Self { provider, from }
     }
 
-    pub fn send_html(&self, to: &str, subject: &str, html: &str) -> anyhow::Result<()> {
-        let email = Message::builder()
-            .from(self.smtp_user.parse()?)
-            .to(to.parse()?)
-            .subject(subject)
-            .header(ContentType::TEXT_HTML)
-            .body(html.to_string())?;
+    /// Provider from `email.provider`, sending as `email.from` (falls back to
+    /// the SMTP user). Returns `Ok(None)` when email is not configured.
+    pub fn from_settings(email: &EmailSettings) -> anyhow::Result<Option<Self>> {
+        let Some(provider) = provider_from_settings(email)? else {
+            return Ok(None);
+        };
+        let from = email
+            .sender()
+            .ok_or_else(|| anyhow::anyhow!("EMAIL_FROM is not set"))?;
+        Ok(Some(Self::new(provider, from.to_string())))
+    }
 
-        let creds = Credentials::new(self.smtp_user.clone(), self.smtp_pass.clone());
-        let mailer = SmtpTransport::relay(&self.smtp_host)?
-            .credentials(creds)
-            .build();
+    pub fn provider_name(&self) -> &'static str {
+        self.provider.name()
+    }
 
-        mailer.send(&email)?;
-        Ok(())
+    /// Send immediately, without queueing or retries. Most callers should go
+    /// through `EmailOutbox` instead.
+    pub async fn send_html(
+        &self,
+        to: &str,
+        subject: &str,
+        html: &str,
+    ) -> Result<Option<String>, SendError> {
+        self.provider
+            .send(&EmailMessage {
+                from: self.from.clone(),
+                to: to.to_string(),
+                subject: subject.to_string(),
+                html: html.to_string(),
+            })
+            .await
     }
 }
@@ -0,0 +1,194 @@
+//! Signed one-click unsubscribe tokens.
+//!
+//! Every digest and alert email carries a link to
+//! `GET /api/notifications/unsubscribe?token=...`. The token names the user
+//! and what they are opting out of, expires after [`TOKEN_TTL_DAYS`], and is
+//! HMAC-signed so it cannot be forged or pointed at another user.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL, Engine as _};
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+
+use crate::i18n::{self, Locale};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Links stay valid well past the 30 days CAN-SPAM requires after sending
+pub const TOKEN_TTL_DAYS: i64 = 60;
+
+/// Keeps these MACs distinct from anything else signed with the same key
+const DOMAIN: &[u8] = b"stellar-insights.unsubscribe.v1.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnsubscribeScope {
+    Digest,
+    Alerts,
+}
+
+impl UnsubscribeScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Digest => "digest",
+            Self::Alerts => "alerts",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsubscribeClaims {
+    #[serde(rename = "u")]
+    pub user_id: String,
+    #[serde(rename = "s")]
+    pub scope: UnsubscribeScope,
+    /// Unix seconds
+    pub exp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    Malformed,
+    BadSignature,
+    Expired,
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "Unsubscribe token is malformed"),
+            Self::BadSignature => write!(f, "Unsubscribe token signature is invalid"),
+            Self::Expired => write!(f, "Unsubscribe token has expired"),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// Issues and checks unsubscribe tokens, and builds the links that carry them
+#[derive(Clone)]
+pub struct UnsubscribeTokens {
+    secret: Vec<u8>,
+    base_url: String,
+}
+
+impl UnsubscribeTokens {
+    /// `base_url` is the public address of this API, e.g. `https://api.example.com`
+    pub fn new(secret: impl AsRef<[u8]>, base_url: &str) -> Self {
+        Self {
+            secret: secret.as_ref().to_vec(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC can take key of any size");
+        mac.update(DOMAIN);
+        mac.update(payload.as_bytes());
+        mac
+    }
+
+    pub fn issue(&self, user_id: &str, scope: UnsubscribeScope) -> String {
+        let claims = UnsubscribeClaims {
+            user_id: user_id.to_string(),
+            scope,
+            exp: (Utc::now() + Duration::days(TOKEN_TTL_DAYS)).timestamp(),
+        };
+        let payload = BASE64_URL.encode(serde_json::to_vec(&claims).unwrap_or_default());
+        let signature = hex::encode(self.mac(&payload).finalize().into_bytes());
+        format!("{}.{}", payload, signature)
+    }
+
+    pub fn verify(&self, token: &str) -> Result<UnsubscribeClaims, TokenError> {
+        let (payload, signature) = token.trim().split_once('.').ok_or(TokenError::Malformed)?;
+        let signature = hex::decode(signature).map_err(|_| TokenError::Malformed)?;
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| TokenError::BadSignature)?;
+
+        let json = BASE64_URL
+            .decode(payload)
+            .map_err(|_| TokenError::Malformed)?;
+        let claims: UnsubscribeClaims =
+            serde_json::from_slice(&json).map_err(|_| TokenError::Malformed)?;
+        if claims.exp < Utc::now().timestamp() {
+            return Err(TokenError::Expired);
+        }
+        Ok(claims)
+    }
+
+    pub fn link(&self, user_id: &str, scope: UnsubscribeScope) -> String {
+        format!(
+            "{}/api/notifications/unsubscribe?token={}",
+            self.base_url,
+            self.issue(user_id, scope)
+        )
+    }
+}
+
+/// Insert a localized unsubscribe footer before `</body>`
+pub fn with_unsubscribe_footer(html: &str, link: &str, locale: Locale) -> String {
+    let footer = format!(
+        r#"<p style="font-size: 12px; color: #888;"><a href="{}">{}</a></p>"#,
+        link,
+        i18n::t(locale, "email-unsubscribe", &[])
+    );
+    match html.rfind("</body>") {
+        Some(i) => format!("{}{}\n{}", &html[..i], footer, &html[i..]),
+        None => format!("{}\n{}", html, footer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens() -> UnsubscribeTokens {
+        UnsubscribeTokens::new("secret", "https://api.example.com/")
+    }
+
+    #[test]
+    fn test_token_round_trip() {
+        let token = tokens().issue("user-1", UnsubscribeScope::Alerts);
+        let claims = tokens().verify(&token).unwrap();
+        assert_eq!(claims.user_id, "user-1");
+        assert_eq!(claims.scope, UnsubscribeScope::Alerts);
+
+        assert!(tokens()
+            .link("user-1", UnsubscribeScope::Digest)
+            .starts_with("https://api.example.com/api/notifications/unsubscribe?token="));
+    }
+
+    #[test]
+    fn test_rejects_forged_and_expired_tokens() {
+        let token = tokens().issue("user-1", UnsubscribeScope::Digest);
+        let other = UnsubscribeTokens::new("other-secret", "https://api.example.com");
+        assert_eq!(other.verify(&token), Err(TokenError::BadSignature));
+
+        // Swap in another user's payload under the original signature
+        let (_, signature) = token.split_once('.').unwrap();
+        let forged_payload = tokens().issue("user-2", UnsubscribeScope::Digest);
+        let (payload, _) = forged_payload.split_once('.').unwrap();
+        assert_eq!(
+            tokens().verify(&format!("{}.{}", payload, signature)),
+            Err(TokenError::BadSignature)
+        );
+
+        let expired = UnsubscribeClaims {
+            user_id: "user-1".to_string(),
+            scope: UnsubscribeScope::Digest,
+            exp: Utc::now().timestamp() - 1,
+        };
+        let payload = BASE64_URL.encode(serde_json::to_vec(&expired).unwrap());
+        let signature = hex::encode(tokens().mac(&payload).finalize().into_bytes());
+        assert_eq!(
+            tokens().verify(&format!("{}.{}", payload, signature)),
+            Err(TokenError::Expired)
+        );
+
+        assert_eq!(tokens().verify("garbage"), Err(TokenError::Malformed));
+    }
+}
@@ -81,6 +81,7 @@ pub fn log_env_config() {
     // Pool config
     log_var("DB_POOL_MAX_CONNECTIONS");
     log_var("DB_POOL_MIN_CONNECTIONS");
+    log_var("DB_POOL_ACQUIRE_TIMEOUT_SECONDS");
     log_var("DB_POOL_CONNECT_TIMEOUT_SECONDS");
     log_var("DB_POOL_IDLE_TIMEOUT_SECONDS");
     log_var("DB_POOL_MAX_LIFETIME_SECONDS");
@@ -49,6 +49,31 @@ pub enum ApiError {
         message: String,
         details: Option<HashMap<String, serde_json::Value>>,
     },
+    Forbidden {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
+    Conflict {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
+    GatewayTimeout {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
+    TooManyRequests {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
+    ServiceUnavailable {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
 }
 
 impl ApiError {
@@ -115,13 +140,63 @@ impl ApiError {
         }
     }
 
+    /// Create a Forbidden error for authenticated users lacking a permission
+    pub fn forbidden(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Forbidden {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Create a Conflict error for writes whose precondition no longer holds
+    pub fn conflict(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Conflict {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Create a GatewayTimeout error for requests that exceeded their time budget
+    pub fn gateway_timeout(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::GatewayTimeout {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Create a TooManyRequests error, e.g. for a locked-out login
+    pub fn too_many_requests(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::TooManyRequests {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Create a ServiceUnavailable error for requests shed under load
+    pub fn service_unavailable(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::ServiceUnavailable {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
     /// Add details to any error variant
     pub fn with_details(mut self, details: HashMap<String, serde_json::Value>) -> Self {
         match &mut self {
             Self::NotFound { details: d, .. }
             | Self::BadRequest { details: d, .. }
             | Self::InternalError { details: d, .. }
-            | Self::Unauthorized { details: d, .. } => {
+            | Self::Unauthorized { details: d, .. }
+            | Self::Forbidden { details: d, .. }
+            | Self::Conflict { details: d, .. }
+            | Self::GatewayTimeout { details: d, .. }
+            | Self::TooManyRequests { details: d, .. }
+            | Self::ServiceUnavailable { details: d, .. } => {
                 *d = Some(details);
             }
         }
@@ -135,6 +210,11 @@ impl ApiError {
             Self::BadRequest { .. } => StatusCode::BAD_REQUEST,
             Self::InternalError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            Self::Forbidden { .. } => StatusCode::FORBIDDEN,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+            Self::GatewayTimeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
@@ -168,6 +248,31 @@ impl ApiError {
                 code,
                 message,
                 details,
+            }
+            | Self::Forbidden {
+                code,
+                message,
+                details,
+            }
+            | Self::Conflict {
+                code,
+                message,
+                details,
+            }
+            | Self::GatewayTimeout {
+                code,
+                message,
+                details,
+            }
+            | Self::TooManyRequests {
+                code,
+                message,
+                details,
+            }
+            | Self::ServiceUnavailable {
+                code,
+                message,
+                details,
             } => (code.clone(), message.clone(), details.clone(), None),
         };
 
@@ -185,8 +290,23 @@ impl ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        if let Self::InternalError {
+            code,
+            message,
+            source,
+            ..
+        } = &self
+        {
+            use crate::observability::error_sink::{self, ErrorEvent, ErrorSource};
+            error_sink::capture(ErrorEvent::new(
+                ErrorSource::ApiInternalError,
+                source.clone().unwrap_or_else(|| message.clone()),
+                serde_json::json!({ "code": code, "message": message }),
+            ));
+        }
+
         let status = self.status_code();
-        let error_response = self.to_error_response(None);
+        let error_response = self.to_error_response(crate::request_id::current_request_id());
         (status, Json(error_response)).into_response()
     }
 }
@@ -241,6 +361,13 @@ impl From<sqlx::Error> for ApiError {
     }
 }
 
+/// Convert from a malformed corridor key
+impl From<crate::models::corridor::CorridorKeyError> for ApiError {
+    fn from(err: crate::models::corridor::CorridorKeyError) -> Self {
+        Self::bad_request("INVALID_CORRIDOR_FORMAT", err.to_string())
+    }
+}
+
 pub type ApiResult<T> = Result<T, ApiError>;
 
 #[cfg(test)]
@@ -271,6 +398,30 @@ mod tests {
         assert_eq!(error.status_code(), StatusCode::UNAUTHORIZED);
     }
 
+    #[test]
+    fn test_forbidden_error() {
+        let error = ApiError::forbidden("ANCHOR_SCOPE_REQUIRED", "Not an operator of this anchor");
+        assert_eq!(error.status_code(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_conflict_error() {
+        let error = ApiError::conflict("VERSION_CONFLICT", "Resource was modified");
+        assert_eq!(error.status_code(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_gateway_timeout_error() {
+        let error = ApiError::gateway_timeout("REQUEST_TIMEOUT", "Request timed out");
+        assert_eq!(error.status_code(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[test]
+    fn test_service_unavailable_error() {
+        let error = ApiError::service_unavailable("UPSTREAM_BUSY", "Try again shortly");
+        assert_eq!(error.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[test]
     fn test_error_with_details() {
         let mut details = HashMap::new();
@@ -1,5 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue},
     response::IntoResponse,
     Json,
 };
@@ -8,11 +9,18 @@ use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::broadcast::{broadcast_anchor_update, broadcast_corridor_update};
+use crate::cache::keys;
+use crate::cache_middleware::CacheAware;
+use crate::db::corridor_summaries::CorridorSummary;
 use crate::error::{ApiError, ApiResult};
 use crate::models::corridor::Corridor;
-use crate::models::{AnchorDetailResponse, CreateAnchorRequest, CreateCorridorRequest};
+use crate::models::{
+    AnchorDetailResponse, CreateAnchorRequest, CreateCorridorRequest, SourceFilter,
+};
 use crate::services::analytics::{compute_corridor_metrics, CorridorTransaction};
-use crate::state::AppState;
+use crate::services::corridor_key_filter::corridor_key_filter;
+use crate::state::{AppState, CachedState};
+use crate::tenant::TenantScope;
 
 
 #[derive(Debug, Deserialize)]
@@ -43,18 +51,19 @@ pub struct ListCorridorsQuery {
 
 #[derive(Debug, Serialize)]
 pub struct ListCorridorsResponse {
-    pub corridors: Vec<Corridor>,
+    pub corridors: Vec<CorridorSummary>,
     pub total: usize,
 }
 
 /// GET /api/anchors - List all anchors with their metrics
 pub async fn list_anchors(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Query(params): Query<ListAnchorsQuery>,
 ) -> ApiResult<Json<ListAnchorsResponse>> {
     let anchors = app_state
         .db
-        .list_anchors(params.limit, params.offset)
+        .list_anchors(params.limit, params.offset, &tenant)
         .await?;
     let total = anchors.len();
 
@@ -63,10 +72,19 @@ pub async fn list_anchors(
 
 /// GET /api/anchors/:id - Get detailed anchor information
 pub async fn get_anchor(
-    State(app_state): State<AppState>,
+    State((db, cache, _, _)): State<CachedState>,
+    tenant: TenantScope,
     Path(id): Path<Uuid>,
+    Query(filter): Query<SourceFilter>,
 ) -> ApiResult<Json<AnchorDetailResponse>> {
-    let anchor_detail = app_state.db.get_anchor_detail(id).await?.ok_or_else(|| {
+    let cache_key = keys::anchor_detail(&id.to_string());
+    // Anchors outside the tenant's directory are reported as missing
+    let anchor_detail = if db.tenants().has_anchor(&tenant, &id.to_string()).await? {
+        <()>::fetch_unless_missing(&cache, &cache_key, db.get_anchor_detail(id)).await?
+    } else {
+        None
+    };
+    let mut anchor_detail = anchor_detail.ok_or_else(|| {
         let mut details = HashMap::new();
         details.insert("anchor_id".to_string(), serde_json::json!(id.to_string()));
         ApiError::not_found_with_details(
@@ -76,12 +94,20 @@ pub async fn get_anchor(
         )
     })?;
 
+    anchor_detail
+        .metrics_history
+        .retain(|m| filter.allows(m.source));
+    anchor_detail
+        .self_reported
+        .retain(|m| filter.allows(m.source));
+
     Ok(Json(anchor_detail))
 }
 
 /// GET /api/anchors/account/:stellar_account - Get anchor by Stellar account (G- or M-address)
 pub async fn get_anchor_by_account(
-    State(app_state): State<AppState>,
+    State((db, cache, _, _)): State<CachedState>,
+    tenant: TenantScope,
     Path(stellar_account): Path<String>,
 ) -> ApiResult<Json<crate::models::Anchor>> {
     let account_lookup = stellar_account.trim();
@@ -93,22 +119,30 @@ pub async fn get_anchor_by_account(
     } else {
         account_lookup.to_string()
     };
-    let anchor = app_state
-        .db
-        .get_anchor_by_stellar_account(&lookup_key)
-        .await?
-        .ok_or_else(|| {
-            let mut details = HashMap::new();
-            details.insert(
-                "stellar_account".to_string(),
-                serde_json::json!(account_lookup),
-            );
-            ApiError::not_found_with_details(
-                "ANCHOR_NOT_FOUND",
-                format!("Anchor with stellar account {} not found", account_lookup),
-                details,
-            )
-        })?;
+    let mut anchor = <()>::fetch_unless_missing(
+        &cache,
+        &keys::anchor_by_account(&lookup_key),
+        db.get_anchor_by_stellar_account(&lookup_key),
+    )
+    .await?;
+    // Anchors outside the tenant's directory are reported as missing
+    if let Some(found) = &anchor {
+        if !db.tenants().has_anchor(&tenant, &found.id).await? {
+            anchor = None;
+        }
+    }
+    let anchor = anchor.ok_or_else(|| {
+        let mut details = HashMap::new();
+        details.insert(
+            "stellar_account".to_string(),
+            serde_json::json!(account_lookup),
+        );
+        ApiError::not_found_with_details(
+            "ANCHOR_NOT_FOUND",
+            format!("Anchor with stellar account {} not found", account_lookup),
+            details,
+        )
+    })?;
 
     Ok(Json(anchor))
 }
@@ -135,6 +169,7 @@ pub async fn get_muxed_analytics(
 /// POST /api/anchors - Create a new anchor
 pub async fn create_anchor(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Json(req): Json<CreateAnchorRequest>,
 ) -> ApiResult<Json<crate::models::Anchor>> {
     if req.name.is_empty() {
@@ -151,10 +186,32 @@ pub async fn create_anchor(
         ));
     }
 
-    let anchor = app_state.db.create_anchor(req).await?;
+    // Tenants share anchor rows, so one already tracked elsewhere is added
+    // to this tenant's directory rather than created again
+    let existing = match tenant {
+        TenantScope::All => None,
+        TenantScope::Tenant(_) => {
+            app_state
+                .db
+                .get_anchor_by_stellar_account(&req.stellar_account)
+                .await?
+        }
+    };
+    let anchor = match existing {
+        Some(anchor) => anchor,
+        None => app_state.db.create_anchor(req).await?,
+    };
+    app_state
+        .db
+        .tenants()
+        .add_anchor(&tenant, &anchor.id)
+        .await?;
 
-    // Broadcast the new anchor to WebSocket clients
-    broadcast_anchor_update(&app_state.ws_state, &anchor);
+    // Broadcast the new anchor to WebSocket clients, which are not
+    // tenant-scoped, unless it belongs to a tenant's directory
+    if tenant == TenantScope::All {
+        broadcast_anchor_update(&app_state.ws_state, &anchor);
+    }
 
     Ok(Json(anchor))
 }
@@ -167,25 +224,95 @@ pub struct UpdateMetricsRequest {
     pub failed_transactions: i64,
     pub avg_settlement_time_ms: Option<i32>,
     pub volume_usd: Option<f64>,
+    /// Version the client last read; the update is rejected if it has changed
+    pub version: Option<i64>,
+}
+
+/// Version precondition from `If-Match` (`"3"`, `W/"3"`) or the request body.
+/// `If-Match: *` and no precondition at all both mean "update unconditionally".
+fn expected_version(headers: &HeaderMap, body_version: Option<i64>) -> ApiResult<Option<i64>> {
+    let header_version = match headers.get(header::IF_MATCH) {
+        None => None,
+        Some(value) => {
+            let value = value.to_str().unwrap_or_default().trim();
+            if value == "*" {
+                None
+            } else {
+                let tag = value.strip_prefix("W/").unwrap_or(value).trim_matches('"');
+                Some(tag.parse::<i64>().map_err(|_| {
+                    ApiError::bad_request(
+                        "INVALID_PRECONDITION",
+                        "If-Match must be a version returned in a previous ETag",
+                    )
+                })?)
+            }
+        }
+    };
+
+    match (header_version, body_version) {
+        (Some(h), Some(b)) if h != b => Err(ApiError::bad_request(
+            "INVALID_PRECONDITION",
+            "If-Match and body version disagree",
+        )),
+        (h, b) => Ok(h.or(b)),
+    }
+}
+
+fn etag_header(version: i64) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&format!("\"{}\"", version)) {
+        headers.insert(header::ETAG, value);
+    }
+    headers
+}
+
+fn version_conflict(
+    expected: Option<i64>,
+    current_version: i64,
+    current: serde_json::Value,
+) -> ApiError {
+    let mut details = HashMap::new();
+    details.insert("expected_version".to_string(), serde_json::json!(expected));
+    details.insert("current_version".to_string(), serde_json::json!(current_version));
+    details.insert("current".to_string(), current);
+    ApiError::conflict(
+        "VERSION_CONFLICT",
+        "The resource was modified since it was read; re-read it and retry",
+    )
+    .with_details(details)
 }
 
 pub async fn update_anchor_metrics(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(req): Json<UpdateMetricsRequest>,
-) -> ApiResult<Json<crate::models::Anchor>> {
-    // Verify anchor exists
-    if app_state.db.get_anchor_by_id(id).await?.is_none() {
+) -> ApiResult<(HeaderMap, Json<crate::models::Anchor>)> {
+    let anchor_not_found = || {
         let mut details = HashMap::new();
         details.insert("anchor_id".to_string(), serde_json::json!(id.to_string()));
-        return Err(ApiError::not_found_with_details(
+        ApiError::not_found_with_details(
             "ANCHOR_NOT_FOUND",
             format!("Anchor with id {} not found", id),
             details,
-        ));
+        )
+    };
+
+    // Verify anchor exists; anchors outside the tenant's directory are
+    // reported as missing
+    if !app_state
+        .db
+        .tenants()
+        .has_anchor(&tenant, &id.to_string())
+        .await?
+        || app_state.db.get_anchor_by_id(id).await?.is_none()
+    {
+        return Err(anchor_not_found());
     }
 
-    let anchor = app_state
+    let expected = expected_version(&headers, req.version)?;
+    let updated = app_state
         .db
         .update_anchor_metrics(
             id,
@@ -194,22 +321,45 @@ pub async fn update_anchor_metrics(
             req.failed_transactions,
             req.avg_settlement_time_ms,
             req.volume_usd,
+            expected,
         )
         .await?;
 
+    let Some(anchor) = updated else {
+        // Someone else updated (or deleted) the anchor after it was read
+        let current = app_state
+            .db
+            .get_anchor_by_id(id)
+            .await?
+            .ok_or_else(anchor_not_found)?;
+        return Err(version_conflict(
+            expected,
+            current.version,
+            serde_json::to_value(&current).unwrap_or_default(),
+        ));
+    };
+
     // Broadcast the anchor update to WebSocket clients
     broadcast_anchor_update(&app_state.ws_state, &anchor);
 
-    Ok(Json(anchor))
+    Ok((etag_header(anchor.version), Json(anchor)))
 }
 
 /// GET /api/anchors/:id/assets - Get assets for an anchor
 pub async fn get_anchor_assets(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<Vec<crate::models::Asset>>> {
-    // Verify anchor exists
-    if app_state.db.get_anchor_by_id(id).await?.is_none() {
+    // Verify anchor exists; anchors outside the tenant's directory are
+    // reported as missing
+    if !app_state
+        .db
+        .tenants()
+        .has_anchor(&tenant, &id.to_string())
+        .await?
+        || app_state.db.get_anchor_by_id(id).await?.is_none()
+    {
         let mut details = HashMap::new();
         details.insert("anchor_id".to_string(), serde_json::json!(id.to_string()));
         return Err(ApiError::not_found_with_details(
@@ -233,11 +383,19 @@ pub struct CreateAssetRequest {
 
 pub async fn create_anchor_asset(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Path(id): Path<Uuid>,
     Json(req): Json<CreateAssetRequest>,
 ) -> ApiResult<Json<crate::models::Asset>> {
-    // Verify anchor exists
-    if app_state.db.get_anchor_by_id(id).await?.is_none() {
+    // Verify anchor exists; anchors outside the tenant's directory are
+    // reported as missing
+    if !app_state
+        .db
+        .tenants()
+        .has_anchor(&tenant, &id.to_string())
+        .await?
+        || app_state.db.get_anchor_by_id(id).await?.is_none()
+    {
         let mut details = HashMap::new();
         details.insert("anchor_id".to_string(), serde_json::json!(id.to_string()));
         return Err(ApiError::not_found_with_details(
@@ -279,11 +437,12 @@ pub async fn pool_metrics(State(state): State<AppState>) -> impl IntoResponse {
 /// GET /api/corridors - List all corridors
 pub async fn list_corridors(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Query(params): Query<ListCorridorsQuery>,
 ) -> ApiResult<Json<ListCorridorsResponse>> {
     let corridors = app_state
         .db
-        .list_corridors(params.limit, params.offset)
+        .list_corridor_summaries(params.limit, params.offset, &tenant)
         .await?;
     let total = corridors.len();
     Ok(Json(ListCorridorsResponse { corridors, total }))
@@ -292,6 +451,7 @@ pub async fn list_corridors(
 /// POST /api/corridors - Create a new corridor
 pub async fn create_corridor(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Json(req): Json<CreateCorridorRequest>,
 ) -> ApiResult<Json<Corridor>> {
     if req.source_asset_code.is_empty() || req.dest_asset_code.is_empty() {
@@ -307,9 +467,18 @@ pub async fn create_corridor(
         ));
     }
     let corridor = app_state.db.create_corridor(req).await?;
+    app_state
+        .db
+        .tenants()
+        .add_corridor(&tenant, &corridor)
+        .await?;
+    corridor_key_filter().insert(&corridor.to_string_key());
 
-    // Broadcast the new corridor to WebSocket clients
-    broadcast_corridor_update(&app_state.ws_state, &corridor);
+    // Broadcast the new corridor to WebSocket clients, which are not
+    // tenant-scoped, unless it belongs to a tenant's directory
+    if tenant == TenantScope::All {
+        broadcast_corridor_update(&app_state.ws_state, &corridor);
+    }
 
     Ok(Json(corridor))
 }
@@ -318,6 +487,8 @@ pub async fn create_corridor(
 #[derive(Debug, Deserialize)]
 pub struct UpdateCorridorMetricsFromTxns {
     pub transactions: Vec<CorridorTransactionDto>,
+    /// Version the client last read; the update is rejected if it has changed
+    pub version: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -329,18 +500,32 @@ pub struct CorridorTransactionDto {
 
 pub async fn update_corridor_metrics_from_transactions(
     State(app_state): State<AppState>,
+    tenant: TenantScope,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(req): Json<UpdateCorridorMetricsFromTxns>,
-) -> ApiResult<Json<Corridor>> {
-    if app_state.db.get_corridor_by_id(id).await?.is_none() {
+) -> ApiResult<(HeaderMap, Json<Corridor>)> {
+    let corridor_not_found = || {
         let mut details = HashMap::new();
         details.insert("corridor_id".to_string(), serde_json::json!(id.to_string()));
-        return Err(ApiError::not_found_with_details(
+        ApiError::not_found_with_details(
             "CORRIDOR_NOT_FOUND",
             format!("Corridor with id {} not found", id),
             details,
-        ));
+        )
+    };
+
+    // Corridors outside the tenant's directory are reported as missing
+    if !app_state
+        .db
+        .tenants()
+        .has_corridor(&tenant, &id.to_string())
+        .await?
+        || app_state.db.get_corridor_by_id(id).await?.is_none()
+    {
+        return Err(corridor_not_found());
     }
+    let expected = expected_version(&headers, req.version)?;
 
     let txs: Vec<CorridorTransaction> = req
         .transactions
@@ -353,12 +538,35 @@ pub async fn update_corridor_metrics_from_transactions(
         .collect();
 
     let metrics = compute_corridor_metrics(&txs, None, 1.0);
-    let corridor = app_state.db.update_corridor_metrics(id, metrics).await?;
+    let Some(record) = app_state
+        .db
+        .update_corridor_metrics(id, metrics, expected)
+        .await?
+    else {
+        let current = app_state
+            .db
+            .get_corridor_record(id)
+            .await?
+            .ok_or_else(corridor_not_found)?;
+        return Err(version_conflict(
+            expected,
+            current.version,
+            serde_json::to_value(&current).unwrap_or_default(),
+        ));
+    };
+
+    let version = record.version;
+    let corridor = Corridor::new(
+        record.source_asset_code,
+        record.source_asset_issuer,
+        record.destination_asset_code,
+        record.destination_asset_issuer,
+    );
 
     // Broadcast the corridor update to WebSocket clients
     broadcast_corridor_update(&app_state.ws_state, &corridor);
 
-    Ok(Json(corridor))
+    Ok((etag_header(version), Json(corridor)))
 }
 
 pub async fn ingestion_status(
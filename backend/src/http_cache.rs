@@ -15,6 +15,8 @@ use chrono::{DateTime, Utc};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
+use crate::cache::CachedJson;
+
 #[derive(Clone)]
 struct CacheEntry {
     etag: String,
@@ -120,6 +122,11 @@ fn set_common_headers(
     }
 }
 
+/// Strong ETag for a response body
+pub fn etag_for(body: &[u8]) -> String {
+    format!("\"{:x}\"", Sha256::digest(body))
+}
+
 pub fn cached_json_response<T: Serialize>(
     request_headers: &HeaderMap,
     resource_key: &str,
@@ -127,26 +134,59 @@ pub fn cached_json_response<T: Serialize>(
     ttl_seconds: usize,
 ) -> anyhow::Result<Response> {
     let body = serde_json::to_vec(payload)?;
-    let etag = format!("\"{:x}\"", Sha256::digest(&body));
-    let last_modified = resolve_last_modified(resource_key, &etag);
+    let etag = etag_for(&body);
+    Ok(json_bytes_response(
+        request_headers,
+        resource_key,
+        &etag,
+        Body::from(body),
+        ttl_seconds,
+    ))
+}
+
+/// Like [`cached_json_response`] for a body that is already serialized, such
+/// as one read back from Redis; the bytes are sent without being parsed.
+pub fn cached_bytes_response(
+    request_headers: &HeaderMap,
+    resource_key: &str,
+    cached: &CachedJson,
+    ttl_seconds: usize,
+) -> Response {
+    json_bytes_response(
+        request_headers,
+        resource_key,
+        &cached.etag,
+        Body::from(cached.body.clone()),
+        ttl_seconds,
+    )
+}
+
+fn json_bytes_response(
+    request_headers: &HeaderMap,
+    resource_key: &str,
+    etag: &str,
+    body: Body,
+    ttl_seconds: usize,
+) -> Response {
+    let last_modified = resolve_last_modified(resource_key, etag);
     let cache_control = format!("public, max-age={ttl_seconds}");
 
-    let not_modified = if_none_match_matches(request_headers, &etag)
+    let not_modified = if_none_match_matches(request_headers, etag)
         || if_modified_since_matches(request_headers, last_modified);
 
     if not_modified {
         let mut response = Response::new(Body::empty());
         *response.status_mut() = StatusCode::NOT_MODIFIED;
-        set_common_headers(response.headers_mut(), &cache_control, &etag, last_modified);
-        return Ok(response);
+        set_common_headers(response.headers_mut(), &cache_control, etag, last_modified);
+        return response;
     }
 
-    let mut response = Response::new(Body::from(body));
+    let mut response = Response::new(body);
     response
         .headers_mut()
         .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-    set_common_headers(response.headers_mut(), &cache_control, &etag, last_modified);
-    Ok(response)
+    set_common_headers(response.headers_mut(), &cache_control, etag, last_modified);
+    response
 }
 
 #[cfg(test)]
@@ -174,6 +214,23 @@ mod tests {
         assert_eq!(body, r#"{"value":"a"}"#);
     }
 
+    #[tokio::test]
+    async fn cached_bytes_match_serialized_response() {
+        let headers = HeaderMap::new();
+        let payload = Payload { value: "d" };
+        let serialized = cached_json_response(&headers, "resource:d", &payload, 60).unwrap();
+        let cached = CachedJson::new(&payload).unwrap();
+        let response = cached_bytes_response(&headers, "resource:d", &cached, 60);
+
+        assert_eq!(response.headers().get(ETAG), serialized.headers().get(ETAG));
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, r#"{"value":"d"}"#);
+    }
+
     #[tokio::test]
     async fn returns_304_when_if_none_match_matches() {
         let headers = HeaderMap::new();
@@ -0,0 +1,226 @@
+//! Shared outbound HTTP clients.
+//!
+//! Modules calling third-party services (anchor transfer servers,
+//! stellar.toml hosts, price feeds, notification providers) get their
+//! `reqwest::Client` from [`shared`] rather than building their own, so the
+//! user agent, proxy, extra CA certificates and connection pool limits are
+//! configured in one place. Clients are cached per request timeout and
+//! share their connection pools between modules.
+//!
+//! [`HttpClients::send`] also runs a request through a circuit breaker for
+//! its destination host: once a host keeps failing, further requests to it
+//! fail fast until the breaker's timeout passes, while other hosts are
+//! unaffected.
+
+use reqwest::{Certificate, Client, ClientBuilder, Proxy, RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::rpc::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerRegistry};
+use crate::rpc::error::RpcError;
+
+/// Settings applied to every outbound client
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    pub user_agent: String,
+    pub connect_timeout: Duration,
+    pub pool_idle_timeout: Duration,
+    pub pool_max_idle_per_host: usize,
+    /// Proxy for all outbound requests; when unset the standard
+    /// `HTTPS_PROXY`/`HTTP_PROXY` variables still apply
+    pub proxy: Option<String>,
+    /// PEM file of extra root certificates to trust
+    pub ca_bundle: Option<String>,
+    /// Breaker settings for each destination host
+    pub circuit_breaker: CircuitBreakerConfig,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: "StellarInsights/1.0".to_string(),
+            connect_timeout: Duration::from_secs(10),
+            pool_idle_timeout: Duration::from_secs(90),
+            pool_max_idle_per_host: 16,
+            proxy: None,
+            ca_bundle: None,
+            circuit_breaker: CircuitBreakerConfig::default(),
+        }
+    }
+}
+
+/// Outbound clients and the per-host circuit breakers guarding them
+pub struct HttpClients {
+    config: HttpClientConfig,
+    clients: Mutex<HashMap<Duration, Client>>,
+    breakers: CircuitBreakerRegistry,
+}
+
+impl HttpClients {
+    pub fn new(config: HttpClientConfig) -> Self {
+        let breakers = CircuitBreakerRegistry::new(config.circuit_breaker.clone());
+        Self {
+            config,
+            clients: Mutex::new(HashMap::new()),
+            breakers,
+        }
+    }
+
+    /// Builder with the shared settings applied, for callers that need
+    /// more (e.g. a redirect policy). Prefer [`HttpClients::client`].
+    pub fn builder(&self) -> ClientBuilder {
+        let mut builder = Client::builder()
+            .user_agent(self.config.user_agent.as_str())
+            .connect_timeout(self.config.connect_timeout)
+            .pool_idle_timeout(self.config.pool_idle_timeout)
+            .pool_max_idle_per_host(self.config.pool_max_idle_per_host);
+
+        if let Some(proxy) = &self.config.proxy {
+            match Proxy::all(proxy) {
+                Ok(proxy) => builder = builder.proxy(proxy),
+                Err(e) => warn!("Ignoring invalid HTTP_CLIENT_PROXY: {}", e),
+            }
+        }
+        if let Some(path) = &self.config.ca_bundle {
+            let certificates = std::fs::read(path)
+                .map_err(|e| e.to_string())
+                .and_then(|pem| Certificate::from_pem_bundle(&pem).map_err(|e| e.to_string()));
+            match certificates {
+                Ok(certificates) => {
+                    for certificate in certificates {
+                        builder = builder.add_root_certificate(certificate);
+                    }
+                }
+                Err(e) => warn!("Ignoring HTTP_CLIENT_CA_BUNDLE {}: {}", path, e),
+            }
+        }
+        builder
+    }
+
+    /// Client whose requests time out after `timeout`. Clients are cached,
+    /// so callers with the same timeout share one connection pool.
+    pub fn client(&self, timeout: Duration) -> Client {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        clients
+            .entry(timeout)
+            .or_insert_with(|| {
+                self.builder().timeout(timeout).build().unwrap_or_else(|e| {
+                    warn!("Failed to build HTTP client, using defaults: {}", e);
+                    Client::new()
+                })
+            })
+            .clone()
+    }
+
+    /// Circuit breaker for the host a URL points at
+    pub fn breaker(&self, url: &reqwest::Url) -> Arc<CircuitBreaker> {
+        let host = url.host_str().unwrap_or_default();
+        match url.port_or_known_default() {
+            Some(port) => self.breakers.get(&format!("{}:{}", host, port)),
+            None => self.breakers.get(host),
+        }
+    }
+
+    /// Send a request through its host's circuit breaker.
+    ///
+    /// Transport errors, timeouts, 5xx and 429 responses count against the
+    /// host; any other response closes the breaker. Responses are returned
+    /// as they are, error statuses included, so callers keep handling them
+    /// as before. Fails with [`RpcError::CircuitBreakerOpen`] without
+    /// sending while the host's breaker is open.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, RpcError> {
+        let (client, request) = request.build_split();
+        let request = request.map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let breaker = self.breaker(request.url());
+
+        let mut failed_response = None;
+        let result = breaker
+            .call(|| async {
+                let response = client.execute(request).await.map_err(|e| {
+                    if e.is_timeout() {
+                        RpcError::TimeoutError(e.to_string())
+                    } else {
+                        RpcError::NetworkError(e.to_string())
+                    }
+                })?;
+                let status = response.status();
+                let error = if status == StatusCode::TOO_MANY_REQUESTS {
+                    RpcError::RateLimitError { retry_after: None }
+                } else if status.is_server_error() {
+                    RpcError::ServerError {
+                        status: status.as_u16(),
+                        message: status.to_string(),
+                    }
+                } else {
+                    return Ok(response);
+                };
+                failed_response = Some(response);
+                Err(error)
+            })
+            .await;
+
+        match (result, failed_response) {
+            (Err(_), Some(response)) => Ok(response),
+            (result, _) => result,
+        }
+    }
+}
+
+static CLIENTS: OnceLock<HttpClients> = OnceLock::new();
+
+/// Configure the process-wide clients from `config` (see
+/// [`crate::config::HttpClientSettings`]). Call once at startup, before
+/// anything uses [`shared`]; returns false if the clients already exist.
+pub fn init(config: HttpClientConfig) -> bool {
+    CLIENTS.set(HttpClients::new(config)).is_ok()
+}
+
+/// Process-wide clients, with default settings unless [`init`] ran first
+pub fn shared() -> &'static HttpClients {
+    CLIENTS.get_or_init(|| HttpClients::new(HttpClientConfig::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_clients() -> HttpClients {
+        HttpClients::new(HttpClientConfig {
+            circuit_breaker: CircuitBreakerConfig {
+                failure_threshold: 1,
+                ..CircuitBreakerConfig::default()
+            },
+            ..HttpClientConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_breakers_are_per_host() {
+        let clients = test_clients();
+        let url = |s: &str| reqwest::Url::parse(s).unwrap();
+
+        let a = clients.breaker(&url("https://anchor.example/sep24/info"));
+        let b = clients.breaker(&url("https://anchor.example:443/.well-known/stellar.toml"));
+        assert!(Arc::ptr_eq(&a, &b));
+
+        let other_port = clients.breaker(&url("http://anchor.example/info"));
+        assert!(!Arc::ptr_eq(&a, &other_port));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_host_trips_its_breaker() {
+        let clients = test_clients();
+        let client = clients.client(Duration::from_secs(1));
+
+        // Nothing listens on the discard port
+        let first = clients.send(client.get("http://127.0.0.1:9/")).await;
+        assert!(matches!(
+            first,
+            Err(RpcError::NetworkError(_)) | Err(RpcError::TimeoutError(_))
+        ));
+        let second = clients.send(client.get("http://127.0.0.1:9/")).await;
+        assert!(matches!(second, Err(RpcError::CircuitBreakerOpen)));
+    }
+}
@@ -0,0 +1,279 @@
+//! Localization of user-facing strings (API error messages, alerts, email digests).
+//!
+//! Message bundles live in `backend/locales/<lang>.ftl` and are compiled into
+//! the binary. They use a subset of Project Fluent syntax: one `key = value`
+//! per line, `#` comments, and `{ $name }` placeholders. Keys missing from a
+//! bundle fall back to English.
+//!
+//! The locale for a request is taken from the user's stored preference when
+//! available, otherwise negotiated from `Accept-Language`.
+
+use axum::{
+    async_trait,
+    body::{to_bytes, Body},
+    extract::{FromRequestParts, Request},
+    http::{header, request::Parts, HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::OnceLock;
+
+use crate::error::ErrorResponse;
+
+/// Largest error body the localization middleware will buffer and rewrite
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Fr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::Fr];
+
+    /// Parse a language tag such as `es`, `es-MX` or `fr_CA`
+    pub fn parse(tag: &str) -> Option<Self> {
+        let primary = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Self::En),
+            "es" => Some(Self::Es),
+            "fr" => Some(Self::Fr),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+            Self::Fr => "fr",
+        }
+    }
+
+    /// Pick the best supported locale from an `Accept-Language` header value
+    pub fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut candidates: Vec<(f32, usize, Locale)> = accept_language
+            .split(',')
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let mut parts = entry.split(';');
+                let locale = Self::parse(parts.next()?)?;
+                let quality = parts
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((quality, index, locale))
+            })
+            .collect();
+
+        // Highest quality first; ties keep header order
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        candidates.first().map(|(_, _, locale)| *locale)
+    }
+
+    /// Resolve a locale from an explicit preference, then `Accept-Language`, then English
+    pub fn resolve(preference: Option<&str>, accept_language: Option<&str>) -> Self {
+        preference
+            .and_then(Self::parse)
+            .or_else(|| accept_language.and_then(Self::negotiate))
+            .unwrap_or_default()
+    }
+
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let accept = headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok());
+        Self::resolve(None, accept)
+    }
+
+    fn bundle_source(&self) -> &'static str {
+        match self {
+            Self::En => include_str!("../locales/en.ftl"),
+            Self::Es => include_str!("../locales/es.ftl"),
+            Self::Fr => include_str!("../locales/fr.ftl"),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Locale
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // A locale resolved earlier in the stack (e.g. from user preferences) wins
+        if let Some(locale) = parts.extensions.get::<Locale>() {
+            return Ok(*locale);
+        }
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+type Bundle = HashMap<&'static str, &'static str>;
+
+fn bundles() -> &'static HashMap<Locale, Bundle> {
+    static BUNDLES: OnceLock<HashMap<Locale, Bundle>> = OnceLock::new();
+    BUNDLES.get_or_init(|| {
+        Locale::ALL
+            .iter()
+            .map(|locale| (*locale, parse_bundle(locale.bundle_source())))
+            .collect()
+    })
+}
+
+fn parse_bundle(source: &'static str) -> Bundle {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+/// Look up `key` in the locale's bundle (falling back to English) and fill in
+/// `{ $name }` placeholders. Returns `None` if no bundle defines the key.
+pub fn lookup(locale: Locale, key: &str, args: &[(&str, String)]) -> Option<String> {
+    let bundles = bundles();
+    let template = bundles
+        .get(&locale)
+        .and_then(|b| b.get(key))
+        .or_else(|| bundles.get(&Locale::En).and_then(|b| b.get(key)))?;
+
+    let mut message = (*template).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{ ${} }}", name), value);
+    }
+    Some(message)
+}
+
+/// Like [`lookup`], but returns the key itself when no translation exists
+pub fn t(locale: Locale, key: &str, args: &[(&str, String)]) -> String {
+    lookup(locale, key, args).unwrap_or_else(|| key.to_string())
+}
+
+/// Bundle key for an API error code, e.g. `CORRIDOR_NOT_FOUND` -> `error-corridor-not-found`
+pub fn error_key(code: &str) -> String {
+    format!("error-{}", code.to_ascii_lowercase().replace('_', "-"))
+}
+
+/// Middleware that translates `ApiError` response messages into the request locale.
+///
+/// Only error responses with a known error code are rewritten; English requests
+/// and everything else pass through untouched. The response carries a
+/// `Content-Language` header whenever a translation was applied.
+pub async fn localize_errors_middleware(req: Request, next: Next) -> Response {
+    let locale = req
+        .extensions()
+        .get::<Locale>()
+        .copied()
+        .unwrap_or_else(|| Locale::from_headers(req.headers()));
+
+    let response = next.run(req).await;
+    if locale == Locale::En
+        || !(response.status().is_client_error() || response.status().is_server_error())
+    {
+        return response;
+    }
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to buffer error response for localization: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    let Ok(mut payload) = serde_json::from_slice::<ErrorResponse>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Some(message) = lookup(locale, &error_key(&payload.error.code), &[]) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    payload.error.message = message;
+
+    match serde_json::to_vec(&payload) {
+        Ok(body) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            parts.headers.insert(
+                header::CONTENT_LANGUAGE,
+                HeaderValue::from_static(locale.as_str()),
+            );
+            Response::from_parts(parts, Body::from(body))
+        }
+        Err(_) => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_accept_language() {
+        assert_eq!(Locale::negotiate("es-MX,es;q=0.9,en;q=0.8"), Some(Locale::Es));
+        assert_eq!(Locale::negotiate("de-DE, fr;q=0.5, en;q=0.7"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("fr-CA;q=0.9, es;q=0.9"), Some(Locale::Fr));
+        assert_eq!(Locale::negotiate("de, ja"), None);
+        assert_eq!(Locale::negotiate("es;q=0"), None);
+    }
+
+    #[test]
+    fn test_resolve_prefers_stored_preference() {
+        assert_eq!(Locale::resolve(Some("fr"), Some("es")), Locale::Fr);
+        assert_eq!(Locale::resolve(Some("xx"), Some("es")), Locale::Es);
+        assert_eq!(Locale::resolve(None, None), Locale::En);
+    }
+
+    #[test]
+    fn test_lookup_interpolates_and_falls_back() {
+        let message = t(
+            Locale::Es,
+            "alert-latency-increase",
+            &[("old", "400".to_string()), ("new", "900".to_string())],
+        );
+        assert_eq!(message, "La latencia aumentó de 400ms a 900ms");
+        assert!(lookup(Locale::Fr, "no-such-key", &[]).is_none());
+        assert_eq!(t(Locale::Fr, "no-such-key", &[]), "no-such-key");
+    }
+
+    #[test]
+    fn test_every_bundle_covers_english_keys() {
+        let english = &bundles()[&Locale::En];
+        for locale in [Locale::Es, Locale::Fr] {
+            let bundle = &bundles()[&locale];
+            for key in english.keys() {
+                assert!(bundle.contains_key(key), "{} missing {}", locale.as_str(), key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_key() {
+        assert_eq!(error_key("CORRIDOR_NOT_FOUND"), "error-corridor-not-found");
+    }
+}
@@ -0,0 +1,103 @@
+//! Ledger gap detection and repair.
+//!
+//! Ledger ingestion skips a ledger it fails to store and moves on, leaving a
+//! hole in `ledgers` (and so in its payments and contract events).
+//! [`LedgerGapRepair`] finds the missing ranges between stored ledgers, records
+//! them in `ledger_gaps`, and backfills each from the RPC. A gap that keeps
+//! failing, e.g. because it is older than the RPC retains, is retried up to
+//! [`MAX_REPAIR_ATTEMPTS`] times and then left for an operator; unresolved
+//! gaps are reported in the `ledger_gaps_unresolved` metric and at
+//! `/api/admin/ingestion/gaps`.
+
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::ledger::LedgerIngestionService;
+use crate::db::ledger_gaps::LedgerGaps;
+use crate::observability::metrics;
+
+/// Backfills tried for a gap before it is left unresolved
+pub const MAX_REPAIR_ATTEMPTS: i64 = 5;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GapRepairResult {
+    /// Gaps found that were not already recorded
+    pub gaps_detected: u64,
+    /// Gaps filled completely by this run
+    pub gaps_repaired: u64,
+    /// Ledgers stored by this run's backfills
+    pub ledgers_backfilled: u64,
+}
+
+pub struct LedgerGapRepair {
+    ledgers: Arc<LedgerIngestionService>,
+    gaps: LedgerGaps,
+}
+
+impl LedgerGapRepair {
+    pub fn new(ledgers: Arc<LedgerIngestionService>, pool: SqlitePool) -> Self {
+        Self {
+            ledgers,
+            gaps: LedgerGaps::new(pool),
+        }
+    }
+
+    /// One repair pass: detect, backfill every pending gap, refresh the metrics
+    pub async fn run_once(&self) -> Result<GapRepairResult> {
+        let mut result = GapRepairResult {
+            gaps_detected: self.gaps.sync(&self.gaps.scan().await?).await?,
+            ..Default::default()
+        };
+
+        for gap in self.gaps.pending(MAX_REPAIR_ATTEMPTS).await? {
+            let outcome = self
+                .ledgers
+                .backfill(gap.start_ledger as u64, gap.end_ledger as u64)
+                .await;
+            let error = match outcome {
+                Ok(stored) => {
+                    result.ledgers_backfilled += stored;
+                    if stored as i64 >= gap.len() {
+                        result.gaps_repaired += 1;
+                        None
+                    } else {
+                        Some(format!("Stored {} of {} ledgers", stored, gap.len()))
+                    }
+                }
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(error) = &error {
+                warn!(
+                    "Backfill of ledgers {}-{} incomplete (attempt {}): {}",
+                    gap.start_ledger,
+                    gap.end_ledger,
+                    gap.attempts + 1,
+                    error
+                );
+            }
+            self.gaps.record_attempt(gap.id, error.as_deref()).await?;
+        }
+
+        // Partly filled gaps are narrowed to what is still missing
+        self.gaps.sync(&self.gaps.scan().await?).await?;
+        self.record_metrics().await?;
+        metrics::record_ledger_gaps_repaired(result.gaps_repaired);
+
+        if result.gaps_detected > 0 || result.gaps_repaired > 0 {
+            info!(
+                "Ledger gap repair: {} new gaps, {} repaired, {} ledgers backfilled",
+                result.gaps_detected, result.gaps_repaired, result.ledgers_backfilled
+            );
+        }
+        Ok(result)
+    }
+
+    async fn record_metrics(&self) -> Result<()> {
+        let totals = self.gaps.totals().await?;
+        metrics::set_ledger_gap_stats(totals.unresolved_gaps, totals.missing_ledgers);
+        Ok(())
+    }
+}
@@ -1,18 +1,48 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use sqlx::SqlitePool;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{info, warn};
 
-use crate::rpc::{GetLedgersResult, RpcLedger, StellarRpcClient};
+use crate::rpc::{RpcLedger, StellarRpcClient};
 use crate::services::account_merge_detector::AccountMergeDetector;
+use crate::services::asset_compliance::AssetComplianceTracker;
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
+use crate::services::network_events::NetworkEventDetector;
+use crate::services::settlement_times::SettlementTracker;
+
+/// Ledgers fetched per RPC call while backfilling a range
+const BACKFILL_BATCH_SIZE: u64 = 50;
+
+/// Default ledgers fetched per run of the ingestion loop
+pub const DEFAULT_BATCH_SIZE: u32 = 5;
+
+/// Most ledgers the RPC returns per `getLedgers` call
+pub const MAX_BATCH_SIZE: u32 = 200;
+
+/// Wait after the first failed run; doubled per further consecutive failure
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Longest wait between failed runs
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(300);
+
+/// How long to wait after `failures` consecutive failed runs
+pub fn retry_delay(failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    RETRY_BASE_DELAY
+        .saturating_mul(1 << exponent)
+        .min(RETRY_MAX_DELAY)
+}
 
 /// Ledger ingestion service that fetches and persists ledgers sequentially
 pub struct LedgerIngestionService {
     rpc_client: Arc<StellarRpcClient>,
     fee_bump_tracker: Arc<FeeBumpTrackerService>,
     account_merge_detector: Arc<AccountMergeDetector>,
+    asset_compliance_tracker: Arc<AssetComplianceTracker>,
+    settlement_tracker: SettlementTracker,
+    network_events: Option<NetworkEventDetector>,
     pool: SqlitePool,
 }
 
@@ -34,17 +64,28 @@ impl LedgerIngestionService {
         rpc_client: Arc<StellarRpcClient>,
         fee_bump_tracker: Arc<FeeBumpTrackerService>,
         account_merge_detector: Arc<AccountMergeDetector>,
+        asset_compliance_tracker: Arc<AssetComplianceTracker>,
         pool: SqlitePool,
     ) -> Self {
         Self {
             rpc_client,
             fee_bump_tracker,
             account_merge_detector,
+            asset_compliance_tracker,
+            settlement_tracker: SettlementTracker::new(pool.clone()),
+            network_events: None,
             pool,
         }
     }
 
-    /// I'm running the main ingestion loop - fetches ledgers and persists them
+    /// Raise network webhook events for ledgers as they are ingested
+    pub fn with_network_events(mut self, detector: NetworkEventDetector) -> Self {
+        self.network_events = Some(detector);
+        self
+    }
+
+    /// Fetch and persist the next `batch_size` ledgers after the saved
+    /// position. Returns how many were stored; 0 when caught up.
     pub async fn run_ingestion(&self, batch_size: u32) -> Result<u64> {
         let cursor = self.get_cursor().await?;
         let start_ledger = match self.get_last_ledger().await? {
@@ -70,51 +111,97 @@ impl LedgerIngestionService {
             .await
             .context("Failed to fetch ledgers")?;
 
-        let count = self.process_ledgers(&result).await?;
+        let count = self.process_ledgers(&result.ledgers, true).await?;
 
-        // I'm saving cursor for restart safety
-        if let Some(new_cursor) = &result.cursor {
-            self.save_cursor(new_cursor, result.ledgers.last().map(|l| l.sequence))
+        // Save progress so a restart resumes after the last ledger. Without a
+        // cursor the next run starts from the ledger after it.
+        if let Some(last) = result.ledgers.last() {
+            self.save_cursor(result.cursor.as_deref(), last.sequence)
                 .await?;
         }
 
         Ok(count)
     }
 
-    /// I'm processing and persisting fetched ledgers
-    async fn process_ledgers(&self, result: &GetLedgersResult) -> Result<u64> {
+    /// Fetch and persist ledgers `start..=end`, which were skipped by
+    /// [`run_ingestion`](Self::run_ingestion). Returns how many were stored;
+    /// fails if the RPC no longer retains the start of the range.
+    pub async fn backfill(&self, start: u64, end: u64) -> Result<u64> {
+        let health = self
+            .rpc_client
+            .check_health()
+            .await
+            .context("Failed to check health")?;
+        if start < health.oldest_ledger {
+            bail!(
+                "Ledgers {}-{} are older than the RPC retains (oldest ledger {})",
+                start,
+                end.min(health.oldest_ledger.saturating_sub(1)),
+                health.oldest_ledger
+            );
+        }
+
+        let mut next = start;
+        let mut count = 0u64;
+        while next <= end {
+            let limit = (end - next + 1).min(BACKFILL_BATCH_SIZE) as u32;
+            let result = self
+                .rpc_client
+                .fetch_ledgers(Some(next), limit, None)
+                .await
+                .context("Failed to fetch ledgers")?;
+            let ledgers: Vec<RpcLedger> = result
+                .ledgers
+                .into_iter()
+                .filter(|l| l.sequence >= next && l.sequence <= end)
+                .collect();
+            let Some(last) = ledgers.last().map(|l| l.sequence) else {
+                bail!("RPC returned no ledgers from {}", next);
+            };
+
+            count += self.process_ledgers(&ledgers, false).await?;
+            next = last + 1;
+        }
+
+        Ok(count)
+    }
+
+    /// I'm processing and persisting fetched ledgers; network events are
+    /// only raised for `live` ledgers, not backfilled ones
+    async fn process_ledgers(&self, ledgers: &[RpcLedger], live: bool) -> Result<u64> {
         let mut count = 0u64;
 
-        for ledger in &result.ledgers {
+        for ledger in ledgers {
             if let Err(e) = self.persist_ledger(ledger).await {
                 warn!("Failed to persist ledger {}: {}", ledger.sequence, e);
                 continue;
             }
 
             // Fetch real payments from Horizon
-            match self
+            let payments = match self
                 .rpc_client
                 .fetch_payments_for_ledger(ledger.sequence)
                 .await
             {
                 Ok(payments) => {
-                    for payment in payments {
+                    for payment in &payments {
                         // Convert RPC Payment to ExtractedPayment
                         let extracted = ExtractedPayment {
                             ledger_sequence: ledger.sequence,
-                            transaction_hash: payment.transaction_hash,
+                            transaction_hash: payment.transaction_hash.clone(),
                             operation_type: "payment".to_string(), // Horizon 'payments' endpoint returns payments
-                            source_account: payment.source_account,
-                            destination: payment.destination,
-                            asset_code: payment.asset_code,
-                            asset_issuer: payment.asset_issuer,
-                            amount: payment.amount,
+                            source_account: payment.source_account.clone(),
+                            destination: payment.destination.clone(),
+                            asset_code: payment.asset_code.clone(),
+                            asset_issuer: payment.asset_issuer.clone(),
+                            amount: payment.amount.clone(),
                         };
 
                         if let Err(e) = self.persist_payment(&extracted).await {
                             warn!("Failed to persist payment: {}", e);
                         }
                     }
+                    Some(payments)
                 }
                 Err(e) => {
                     warn!(
@@ -122,11 +209,12 @@ impl LedgerIngestionService {
                         ledger.sequence, e
                     );
                     // Non-fatal, continue ingesting ledgers
+                    None
                 }
-            }
+            };
 
             // Fetch and process transactions for fee bumps
-            match self
+            let transactions = match self
                 .rpc_client
                 .fetch_transactions_for_ledger(ledger.sequence)
                 .await
@@ -139,12 +227,39 @@ impl LedgerIngestionService {
                     {
                         warn!("Failed to process transactions for fee bumps: {}", e);
                     }
+                    Some(transactions)
                 }
                 Err(e) => {
                     warn!(
                         "Failed to fetch transactions for ledger {}: {}",
                         ledger.sequence, e
                     );
+                    None
+                }
+            };
+
+            if let (Some(transactions), Some(payments)) = (&transactions, &payments) {
+                if let Err(e) = self
+                    .settlement_tracker
+                    .process_ledger(ledger.sequence, transactions, payments)
+                    .await
+                {
+                    warn!(
+                        "Failed to record settlement times for ledger {}: {}",
+                        ledger.sequence, e
+                    );
+                }
+            }
+
+            if let Some(detector) = self.network_events.as_ref().filter(|_| live) {
+                if let Err(e) = detector
+                    .process_ledger(ledger.sequence, transactions.as_deref())
+                    .await
+                {
+                    warn!(
+                        "Failed to raise network events for ledger {}: {}",
+                        ledger.sequence, e
+                    );
                 }
             }
 
@@ -159,6 +274,17 @@ impl LedgerIngestionService {
                 );
             }
 
+            if let Err(e) = self
+                .asset_compliance_tracker
+                .process_ledger_operations(ledger.sequence)
+                .await
+            {
+                warn!(
+                    "Failed to process asset compliance operations for ledger {}: {}",
+                    ledger.sequence, e
+                );
+            }
+
             count += 1;
         }
 
@@ -247,8 +373,7 @@ impl LedgerIngestionService {
     }
 
     /// I'm saving cursor and last ledger for restart safety
-    async fn save_cursor(&self, cursor: &str, last_ledger: Option<u64>) -> Result<()> {
-        let seq = last_ledger.unwrap_or(0) as i64;
+    async fn save_cursor(&self, cursor: Option<&str>, last_ledger: u64) -> Result<()> {
         sqlx::query(
             r#"
             INSERT INTO ingestion_cursor (id, last_ledger_sequence, cursor, updated_at)
@@ -259,7 +384,7 @@ impl LedgerIngestionService {
                 updated_at = CURRENT_TIMESTAMP
             "#,
         )
-        .bind(seq)
+        .bind(last_ledger as i64)
         .bind(cursor)
         .execute(&self.pool)
         .await?;
@@ -272,3 +397,17 @@ impl LedgerIngestionService {
         Ok(Utc.timestamp_opt(ts, 0).single().unwrap_or_else(Utc::now))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(1), Duration::from_secs(2));
+        assert_eq!(retry_delay(2), Duration::from_secs(4));
+        assert_eq!(retry_delay(5), Duration::from_secs(32));
+        assert_eq!(retry_delay(9), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(u32::MAX), RETRY_MAX_DELAY);
+    }
+}
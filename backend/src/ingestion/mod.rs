@@ -1,5 +1,7 @@
 // I'm exporting the ledger ingestion module as required by issue #2
+pub mod gaps;
 pub mod ledger;
+pub mod status;
 
 use anyhow::{Context, Result};
 use serde::Serialize;
@@ -8,15 +10,27 @@ use tracing::{info, warn};
 
 use crate::database::Database;
 use crate::rpc::StellarRpcClient;
+use crate::tenant::TenantScope;
+use status::{IngestionSource, IngestionTracker, SourceStatus};
 
 pub struct DataIngestionService {
     rpc_client: Arc<StellarRpcClient>,
     db: Arc<Database>,
+    tracker: IngestionTracker,
 }
 
 impl DataIngestionService {
     pub fn new(rpc_client: Arc<StellarRpcClient>, db: Arc<Database>) -> Self {
-        Self { rpc_client, db }
+        Self {
+            rpc_client,
+            db,
+            tracker: IngestionTracker::new(),
+        }
+    }
+
+    /// Progress registry that sync loops report their runs to
+    pub fn tracker(&self) -> &IngestionTracker {
+        &self.tracker
     }
 
     /// Sync all metrics from Stellar network
@@ -24,8 +38,12 @@ impl DataIngestionService {
         info!("Starting metrics synchronization");
 
         self.sync_anchor_metrics().await?;
+        let corridors = self.db.refresh_corridor_summaries().await?;
 
-        info!("Metrics synchronization completed");
+        info!(
+            "Metrics synchronization completed ({} corridor summaries refreshed)",
+            corridors
+        );
         Ok(())
     }
 
@@ -33,7 +51,7 @@ impl DataIngestionService {
     pub async fn sync_anchor_metrics(&self) -> Result<()> {
         info!("Syncing anchor metrics from Stellar network");
 
-        let anchors = self.db.list_anchors(0, 100).await?;
+        let anchors = self.db.list_anchors(0, 100, &TenantScope::All).await?;
 
         for anchor in anchors {
             match self.process_anchor_metrics(&anchor.stellar_account).await {
@@ -140,31 +158,57 @@ pub struct NetworkHealth {
 #[derive(Debug, Clone, Serialize)]
 pub struct IngestionStatus {
     pub last_ingested_ledger: u64,
-    pub network_latest_ledger: u64,
+    /// `None` when the RPC health check fails
+    pub network_latest_ledger: Option<u64>,
+    /// Ledgers the network is ahead of ingestion; `None` with
+    /// `network_latest_ledger`
+    pub lag_ledgers: Option<u64>,
+    pub sources: Vec<SourceStatus>,
 }
 
 impl DataIngestionService {
-    // ... (existing methods remain, adding new one below)
-
     pub async fn get_ingestion_status(&self) -> Result<IngestionStatus> {
-        // We get local state
-        let cursor_row: Option<(i64,)> =
-            sqlx::query_as("SELECT last_ledger_sequence FROM ingestion_cursor WHERE id = 1")
-                .fetch_optional(self.db.pool())
-                .await?;
-
-        let last_ingested = cursor_row.map(|r| r.0 as u64).unwrap_or(0);
+        let ledger_cursor: Option<(i64, Option<String>)> = sqlx::query_as(
+            "SELECT last_ledger_sequence, cursor FROM ingestion_cursor WHERE id = 1",
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+        let payment_cursor = self.db.get_ingestion_cursor("payment_ingestion").await?;
+
+        let last_ingested = ledger_cursor.as_ref().map(|r| r.0 as u64).unwrap_or(0);
+
+        // Persisted watermarks survive restarts; prefer them over in-memory ones
+        let mut sources = self.tracker.snapshot();
+        for source in &mut sources {
+            match source.source {
+                IngestionSource::Ledgers => {
+                    if let Some((sequence, cursor)) = &ledger_cursor {
+                        source.ledger = Some(*sequence as u64);
+                        source.cursor = cursor.clone().or(source.cursor.take());
+                    }
+                }
+                IngestionSource::Payments => {
+                    if payment_cursor.is_some() {
+                        source.cursor = payment_cursor.clone();
+                    }
+                }
+                _ => {}
+            }
+        }
 
-        // We get network state
-        let health = self
-            .rpc_client
-            .check_health()
-            .await
-            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let network_latest_ledger = match self.rpc_client.check_health().await {
+            Ok(health) => Some(health.latest_ledger),
+            Err(e) => {
+                warn!("Failed to fetch network ledger for ingestion status: {}", e);
+                None
+            }
+        };
 
         Ok(IngestionStatus {
             last_ingested_ledger: last_ingested,
-            network_latest_ledger: health.latest_ledger,
+            network_latest_ledger,
+            lag_ledgers: network_latest_ledger.map(|latest| latest.saturating_sub(last_ingested)),
+            sources,
         })
     }
 }
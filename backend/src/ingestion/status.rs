@@ -0,0 +1,173 @@
+//! Per-source ingestion progress.
+//!
+//! Sync loops report each run to the shared [`IngestionTracker`]: successes
+//! clear the error streak and advance the watermark, failures extend the
+//! streak and the error count. Both record when the loop will run next. The tracker is in-memory;
+//! persisted cursors are merged in by
+//! [`DataIngestionService::get_ingestion_status`](super::DataIngestionService::get_ingestion_status).
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionSource {
+    Payments,
+    Trades,
+    Ledgers,
+    ContractEvents,
+    StellarToml,
+}
+
+impl IngestionSource {
+    pub const ALL: [Self; 5] = [
+        Self::Payments,
+        Self::Trades,
+        Self::Ledgers,
+        Self::ContractEvents,
+        Self::StellarToml,
+    ];
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceStatus {
+    pub source: IngestionSource,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// Paging cursor the next run resumes from
+    pub cursor: Option<String>,
+    /// Highest ledger sequence this source has processed
+    pub ledger: Option<u64>,
+    /// Consecutive failed runs; reset by a success
+    pub error_streak: u32,
+    /// Failed runs since the server started
+    pub error_count: u64,
+    pub last_error: Option<String>,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl SourceStatus {
+    fn new(source: IngestionSource) -> Self {
+        Self {
+            source,
+            last_success_at: None,
+            last_attempt_at: None,
+            cursor: None,
+            ledger: None,
+            error_streak: 0,
+            error_count: 0,
+            last_error: None,
+            next_run_at: None,
+        }
+    }
+}
+
+/// Shared registry of [`SourceStatus`], cheap to clone into sync loops
+#[derive(Clone, Default)]
+pub struct IngestionTracker {
+    sources: Arc<RwLock<BTreeMap<IngestionSource, SourceStatus>>>,
+}
+
+fn next_run(after: Duration) -> Option<DateTime<Utc>> {
+    chrono::Duration::from_std(after).ok().map(|d| Utc::now() + d)
+}
+
+impl IngestionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, source: IngestionSource, f: impl FnOnce(&mut SourceStatus)) {
+        if let Ok(mut sources) = self.sources.write() {
+            f(sources
+                .entry(source)
+                .or_insert_with(|| SourceStatus::new(source)));
+        }
+    }
+
+    /// Record a successful run. `ledger` and `cursor` only move the watermark
+    /// when given; `next_run_in` is how long until the loop runs again.
+    pub fn record_success(
+        &self,
+        source: IngestionSource,
+        ledger: Option<u64>,
+        cursor: Option<String>,
+        next_run_in: Duration,
+    ) {
+        let now = Utc::now();
+        self.update(source, |s| {
+            s.last_success_at = Some(now);
+            s.last_attempt_at = Some(now);
+            s.error_streak = 0;
+            s.last_error = None;
+            if ledger.is_some() {
+                s.ledger = ledger;
+            }
+            if cursor.is_some() {
+                s.cursor = cursor;
+            }
+            s.next_run_at = next_run(next_run_in);
+        });
+    }
+
+    pub fn record_failure(&self, source: IngestionSource, error: &str, next_run_in: Duration) {
+        self.update(source, |s| {
+            s.last_attempt_at = Some(Utc::now());
+            s.error_streak += 1;
+            s.error_count += 1;
+            s.last_error = Some(error.to_string());
+            s.next_run_at = next_run(next_run_in);
+        });
+    }
+
+    /// Status of every source, including ones that have never run
+    pub fn snapshot(&self) -> Vec<SourceStatus> {
+        let sources = self.sources.read().ok();
+        IngestionSource::ALL
+            .iter()
+            .map(|source| {
+                sources
+                    .as_ref()
+                    .and_then(|s| s.get(source).cloned())
+                    .unwrap_or_else(|| SourceStatus::new(*source))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success_resets_error_streak() {
+        let tracker = IngestionTracker::new();
+        let retry = Duration::from_secs(10);
+        tracker.record_success(IngestionSource::Ledgers, Some(100), Some("c1".into()), retry);
+        tracker.record_failure(IngestionSource::Ledgers, "rpc down", retry);
+        tracker.record_failure(IngestionSource::Ledgers, "rpc down", retry);
+
+        let ledgers = &tracker.snapshot()[2];
+        assert_eq!(ledgers.error_streak, 2);
+        assert_eq!(ledgers.ledger, Some(100));
+        assert_eq!(ledgers.last_error.as_deref(), Some("rpc down"));
+
+        tracker.record_success(IngestionSource::Ledgers, Some(105), None, retry);
+        let ledgers = &tracker.snapshot()[2];
+        assert_eq!(ledgers.error_streak, 0);
+        assert_eq!(ledgers.error_count, 2);
+        assert_eq!(ledgers.ledger, Some(105));
+        assert_eq!(ledgers.cursor.as_deref(), Some("c1"));
+        assert!(ledgers.last_error.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_lists_every_source() {
+        let snapshot = IngestionTracker::new().snapshot();
+        assert_eq!(snapshot.len(), IngestionSource::ALL.len());
+        assert!(snapshot.iter().all(|s| s.last_success_at.is_none()));
+    }
+}
@@ -1,3 +1,6 @@
+pub mod reencrypt;
 pub mod scheduler;
+pub mod supervisor;
 
 pub use scheduler::{JobScheduler, JobConfig};
+pub use supervisor::{JobRun, TaskHealth, TaskState, TaskSupervisor};
@@ -0,0 +1,133 @@
+//! Re-encryption of PII columns after a key rotation.
+//!
+//! After `ENCRYPTION_KEY` is replaced (with the old key moved to
+//! `ENCRYPTION_KEY_PREVIOUS`), this job rewrites every value still under a
+//! retired key, or still in plaintext from before field encryption, under the
+//! current key. Once a pass reports nothing left to rewrite, the old key can
+//! be dropped from `ENCRYPTION_KEY_PREVIOUS`.
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+use crate::crypto::{lookup_hash, FieldCipher};
+
+/// Encrypted columns as (table, row key, column); tables with a composite
+/// primary key are keyed by `rowid`
+const ENCRYPTED_COLUMNS: &[(&str, &str, &str)] = &[
+    ("user_preferences", "user_id", "notification_email"),
+    ("oauth_clients", "id", "client_secret"),
+    ("oauth_tokens", "id", "access_token"),
+    ("oauth_tokens", "id", "refresh_token"),
+    ("sep_transactions", "id", "auth_token"),
+    ("sep_anchor_sessions", "rowid", "auth_token"),
+];
+
+/// Outcome of one re-encryption pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReencryptionResult {
+    pub values_rewritten: u64,
+    /// Values no configured key could decrypt; left untouched
+    pub failures: u64,
+}
+
+pub struct PiiReencryptor {
+    db: SqlitePool,
+    cipher: FieldCipher,
+}
+
+impl PiiReencryptor {
+    pub fn new(db: SqlitePool, cipher: FieldCipher) -> Self {
+        Self { db, cipher }
+    }
+
+    pub async fn run_once(&self) -> Result<ReencryptionResult> {
+        let mut result = ReencryptionResult::default();
+        self.backfill_token_hashes(&mut result).await?;
+        for (table, id_column, column) in ENCRYPTED_COLUMNS {
+            self.reencrypt_column(table, id_column, column, &mut result)
+                .await?;
+        }
+
+        if result.values_rewritten > 0 || result.failures > 0 {
+            tracing::info!(
+                "Re-encrypted {} PII values under key {} ({} could not be decrypted)",
+                result.values_rewritten,
+                self.cipher.current_key_id(),
+                result.failures
+            );
+        }
+        Ok(result)
+    }
+
+    async fn reencrypt_column(
+        &self,
+        table: &str,
+        id_column: &str,
+        column: &str,
+        result: &mut ReencryptionResult,
+    ) -> Result<()> {
+        let rows: Vec<(String, String)> = sqlx::query_as(&format!(
+            "SELECT CAST({id_column} AS TEXT), {column} FROM {table} \
+             WHERE {column} IS NOT NULL AND {column} != ''"
+        ))
+        .fetch_all(&self.db)
+        .await?;
+
+        for (id, stored) in rows {
+            if !self.cipher.needs_rotation(&stored) {
+                continue;
+            }
+            let rewritten = match self
+                .cipher
+                .decrypt(&stored)
+                .and_then(|plain| self.cipher.encrypt(&plain))
+            {
+                Ok(rewritten) => rewritten,
+                Err(e) => {
+                    tracing::warn!("Cannot re-encrypt {}.{} for {}: {}", table, column, id, e);
+                    result.failures += 1;
+                    continue;
+                }
+            };
+
+            // Only replace the value we read, in case it changed meanwhile
+            let updated = sqlx::query(&format!(
+                "UPDATE {table} SET {column} = ? WHERE {id_column} = ? AND {column} = ?"
+            ))
+            .bind(rewritten)
+            .bind(&id)
+            .bind(&stored)
+            .execute(&self.db)
+            .await?;
+            result.values_rewritten += updated.rows_affected();
+        }
+        Ok(())
+    }
+
+    /// Tokens stored before `access_token_hash` existed can't be revoked
+    /// until their hash is filled in
+    async fn backfill_token_hashes(&self, result: &mut ReencryptionResult) -> Result<()> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id, access_token FROM oauth_tokens WHERE access_token_hash IS NULL",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        for (id, stored) in rows {
+            match self.cipher.decrypt(&stored) {
+                Ok(token) => {
+                    sqlx::query("UPDATE oauth_tokens SET access_token_hash = ? WHERE id = ?")
+                        .bind(lookup_hash(&token))
+                        .bind(&id)
+                        .execute(&self.db)
+                        .await?;
+                }
+                Err(e) => {
+                    tracing::warn!("Cannot hash OAuth token {}: {}", id, e);
+                    result.failures += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,14 +1,39 @@
 use anyhow::Result;
+use chrono::Utc;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
 use tracing::{error, info};
 
+use crate::billing::{self, UsageExport};
 use crate::cache::CacheManager;
+use crate::config::Settings;
 use crate::database::Database;
+use crate::ingestion::gaps::LedgerGapRepair;
+use crate::ingestion::ledger::LedgerIngestionService;
 use crate::ingestion::DataIngestionService;
+use crate::ml::anomaly::AnomalyService;
 use crate::rpc::StellarRpcClient;
+use crate::services::anchor_toml_refresh::AnchorTomlRefresher;
+use crate::services::concentration::ConcentrationService;
+use crate::services::contract::ContractService;
+use crate::services::contract_ttl::ContractTtlWatcher;
+use crate::services::health_score::HealthScoreService;
 use crate::services::price_feed::PriceFeedClient;
+use crate::services::snapshot::SnapshotService;
+use crate::services::status_monitor::StatusMonitor;
+use crate::services::stellar_toml::StellarTomlClient;
+use crate::services::trade_surveillance::TradeSurveillanceService;
+use crate::services::valuation::{fiat_history_key, FIAT_CURRENCIES};
+use crate::webhooks::digest::CorridorDigestBuilder;
+use crate::webhooks::retention::WebhookEventRetention;
+use crate::webhooks::WebhookService;
+
+use super::reencrypt::PiiReencryptor;
+use super::supervisor::{JobRun, TaskSupervisor};
+
+/// Most missed epochs a single snapshot publication run will catch up
+const SNAPSHOT_MAX_CATCH_UP: u64 = 24;
 
 #[derive(Clone)]
 pub struct JobConfig {
@@ -39,12 +64,22 @@ impl JobConfig {
 
 pub struct JobScheduler {
     handles: Vec<JoinHandle<()>>,
+    supervisor: Option<TaskSupervisor>,
 }
 
 impl JobScheduler {
     pub fn new() -> Self {
         Self {
             handles: Vec::new(),
+            supervisor: None,
+        }
+    }
+
+    /// Scheduler whose job loops are restarted by `supervisor` if they panic
+    pub fn with_supervisor(supervisor: TaskSupervisor) -> Self {
+        Self {
+            handles: Vec::new(),
+            supervisor: Some(supervisor),
         }
     }
 
@@ -52,43 +87,82 @@ impl JobScheduler {
     where
         F: Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>>
             + Send
+            + Sync
             + 'static,
     {
         if !config.enabled {
             info!("Job '{}' is disabled, skipping", config.name);
             return;
         }
+        let name = config.name.clone();
 
         info!(
             "Scheduling job '{}' to run every {} seconds",
             config.name, config.interval_seconds
         );
 
-        let handle = tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(config.interval_seconds));
-            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let task_name = format!("job:{}", name);
+        let job_fn = Arc::new(job_fn);
+        let history = self.supervisor.clone();
+        let history_name = task_name.clone();
+        let run_job = move || {
+            let config = config.clone();
+            let job_fn = Arc::clone(&job_fn);
+            let history = history.clone();
+            let history_name = history_name.clone();
+            async move {
+                let mut interval =
+                    tokio::time::interval(Duration::from_secs(config.interval_seconds));
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
-            loop {
-                interval.tick().await;
-                info!("Running job '{}'", config.name);
-                match job_fn().await {
-                    Ok(_) => info!("Job '{}' completed successfully", config.name),
-                    Err(e) => error!("Job '{}' failed: {}", config.name, e),
+                loop {
+                    interval.tick().await;
+                    info!("Running job '{}'", config.name);
+                    let started_at = Utc::now();
+                    let started = Instant::now();
+                    let result = job_fn().await;
+                    match &result {
+                        Ok(_) => info!("Job '{}' completed successfully", config.name),
+                        Err(e) => error!("Job '{}' failed: {}", config.name, e),
+                    }
+                    if let Some(supervisor) = &history {
+                        supervisor.record_run(
+                            &history_name,
+                            JobRun {
+                                started_at,
+                                duration_ms: started.elapsed().as_millis() as u64,
+                                success: result.is_ok(),
+                                error: result.err().map(|e| format!("{:#}", e)),
+                            },
+                        );
+                    }
                 }
             }
-        });
+        };
+
+        let handle = match &self.supervisor {
+            Some(supervisor) => supervisor.spawn(&task_name, run_job),
+            None => tokio::spawn(run_job()),
+        };
 
         self.handles.push(handle);
     }
 
     pub async fn start(
+        settings: Arc<Settings>,
         db: Arc<Database>,
         cache: Arc<CacheManager>,
         rpc: Arc<StellarRpcClient>,
         ingestion: Arc<DataIngestionService>,
+        ledgers: Arc<LedgerIngestionService>,
         price_feed: Arc<PriceFeedClient>,
+        snapshots: Arc<SnapshotService>,
+        contracts: Option<Arc<ContractService>>,
+        anomalies: Arc<AnomalyService>,
+        webhooks: Arc<WebhookService>,
+        supervisor: TaskSupervisor,
     ) -> Self {
-        let mut scheduler = Self::new();
+        let mut scheduler = Self::with_supervisor(supervisor);
 
         // Corridor refresh job
         let config = JobConfig::from_env("corridor-refresh", 300);
@@ -119,17 +193,250 @@ impl JobScheduler {
             })
         });
 
-        // Price feed update job
+        // Re-fetch anchors' stellar.toml files, raising anchor.toml_changed.
+        // Every run fetches fresh files, so the client needs no cache.
+        let config = JobConfig::from_env("anchor-toml-refresh", 3600);
+        match StellarTomlClient::new(Arc::new(tokio::sync::RwLock::new(None)), None) {
+            Ok(toml) => {
+                let toml_refresher = Arc::new(AnchorTomlRefresher::new(
+                    db.pool().clone(),
+                    toml,
+                    Arc::clone(&webhooks),
+                ));
+                scheduler.add_job(config, move || {
+                    let toml_refresher = Arc::clone(&toml_refresher);
+                    Box::pin(async move {
+                        let changed = toml_refresher.run_once().await?;
+                        info!("{} anchor stellar.toml files changed", changed);
+                        Ok(())
+                    })
+                });
+            }
+            Err(e) => error!("Anchor stellar.toml refresh disabled: {}", e),
+        }
+
+        // Price feed update job; every refresh, and the USD rates of the fiat
+        // quote currencies, is also recorded for historical valuation
         let config = JobConfig::from_env("price-feed-update", 900);
         let price_feed_clone = Arc::clone(&price_feed);
+        let db_clone = Arc::clone(&db);
         scheduler.add_job(config, move || {
             let price_feed = Arc::clone(&price_feed_clone);
+            let db = Arc::clone(&db_clone);
+            Box::pin(async move {
+                let prices = price_feed.refresh_all().await;
+                let observed_at = chrono::Utc::now();
+                let history = db.price_history();
+                for (asset, price) in prices {
+                    history
+                        .record(&asset, price, price_feed.provider_name(), observed_at)
+                        .await?;
+                }
+                for (currency, price) in price_feed.fiat_rates(FIAT_CURRENCIES).await {
+                    history
+                        .record(
+                            &fiat_history_key(&currency),
+                            price,
+                            price_feed.provider_name(),
+                            observed_at,
+                        )
+                        .await?;
+                }
+                Ok(())
+            })
+        });
+
+        // Status page probes (ingestion, webhook delivery, RPC connectivity)
+        let config = JobConfig::from_env("status-probes", 60);
+        let monitor = Arc::new(StatusMonitor::new(Arc::clone(&db), Arc::clone(&rpc)));
+        scheduler.add_job(config, move || {
+            let monitor = Arc::clone(&monitor);
+            Box::pin(async move {
+                monitor.run_probes().await?;
+                Ok(())
+            })
+        });
+
+        // Backfill ledger ranges that ingestion skipped
+        let config = JobConfig::from_env("ledger-gap-repair", 3600);
+        let gap_repair = Arc::new(LedgerGapRepair::new(ledgers, db.pool().clone()));
+        scheduler.add_job(config, move || {
+            let gap_repair = Arc::clone(&gap_repair);
+            Box::pin(async move {
+                gap_repair.run_once().await?;
+                Ok(())
+            })
+        });
+
+        // Snapshot publication; each interval is one epoch, and epochs missed
+        // during downtime are published on the next run
+        let config = JobConfig::from_env("snapshot-publish", 6 * 3600);
+        let epoch_length = Duration::from_secs(config.interval_seconds);
+        let snapshots_clone = Arc::clone(&snapshots);
+        scheduler.add_job(config, move || {
+            let snapshots = Arc::clone(&snapshots_clone);
+            Box::pin(async move {
+                let published = snapshots
+                    .publish_scheduled(epoch_length, SNAPSHOT_MAX_CATCH_UP)
+                    .await?;
+                info!("Published snapshot epochs {:?}", published);
+                Ok(())
+            })
+        });
+
+        // Keep the newest on-chain snapshots from being archived for unpaid rent
+        let config = JobConfig::from_env("snapshot-ttl-keepalive", 24 * 3600);
+        let ttl_config = settings.contract_ttl.snapshot_keepalive_config();
+        scheduler.add_job(config, move || {
+            let snapshots = Arc::clone(&snapshots);
+            Box::pin(async move {
+                let extended = snapshots.keep_recent_epochs_alive(ttl_config).await?;
+                info!("Extended TTL of snapshot epochs {:?}", extended);
+                Ok(())
+            })
+        });
+
+        // Warn before watched contracts' instance or code entries are archived
+        let config = JobConfig::from_env("contract-ttl-watch", 3600);
+        match ContractTtlWatcher::new(
+            settings.contract_ttl.watcher_config(),
+            contracts,
+            Arc::clone(&webhooks),
+        ) {
+            Ok(watcher) => {
+                let watcher = Arc::new(watcher);
+                scheduler.add_job(config, move || {
+                    let watcher = Arc::clone(&watcher);
+                    Box::pin(async move {
+                        watcher.run_once().await?;
+                        Ok(())
+                    })
+                });
+            }
+            Err(e) => error!("Contract TTL watch disabled: {:#}", e),
+        }
+
+        // Fold old webhook events into daily summaries so the table stops growing
+        let config = JobConfig::from_env("webhook-event-retention", 24 * 3600);
+        let retention = Arc::new(WebhookEventRetention::new(
+            db.pool().clone(),
+            settings.webhooks.retention_config(),
+        ));
+        scheduler.add_job(config, move || {
+            let retention = Arc::clone(&retention);
+            Box::pin(async move {
+                retention.run_once().await?;
+                Ok(())
+            })
+        });
+
+        // corridor.digest webhook events; each interval is one digest window
+        let config = JobConfig::from_env("webhook-corridor-digest", 3600);
+        let digests = Arc::new(CorridorDigestBuilder::new(
+            db.pool().clone(),
+            Duration::from_secs(config.interval_seconds),
+        ));
+        scheduler.add_job(config, move || {
+            let digests = Arc::clone(&digests);
+            Box::pin(async move {
+                digests.run_once().await?;
+                Ok(())
+            })
+        });
+
+        // Rewrite PII encrypted under a retired key (or still in plaintext)
+        let config = JobConfig::from_env("pii-reencrypt", 24 * 3600);
+        let reencryptor = Arc::new(PiiReencryptor::new(
+            db.pool().clone(),
+            settings.security.field_cipher(),
+        ));
+        scheduler.add_job(config, move || {
+            let reencryptor = Arc::clone(&reencryptor);
+            Box::pin(async move {
+                reencryptor.run_once().await?;
+                Ok(())
+            })
+        });
+
+        // Daily concentration (HHI) of each corridor's volume across accounts
+        let config = JobConfig::from_env("corridor-concentration", 24 * 3600);
+        let concentration = Arc::new(ConcentrationService::new(Arc::clone(&db), Arc::clone(&rpc)));
+        scheduler.add_job(config, move || {
+            let concentration = Arc::clone(&concentration);
+            Box::pin(async move {
+                concentration.run_once().await?;
+                Ok(())
+            })
+        });
+
+        // Corridor health scores, kept as history
+        let config = JobConfig::from_env("corridor-health-score", 3600);
+        let health_scores = Arc::new(HealthScoreService::new(
+            Arc::clone(&db),
+            settings.health_score.weights(),
+        ));
+        scheduler.add_job(config, move || {
+            let health_scores = Arc::clone(&health_scores);
+            Box::pin(async move {
+                health_scores.run_once().await?;
+                Ok(())
+            })
+        });
+
+        // Daily latency and error stats of anchors' transfer servers, rolled
+        // up from the requests the SEP proxies recorded
+        let config = JobConfig::from_env("sep-performance-rollup", 3600);
+        let db_clone = Arc::clone(&db);
+        scheduler.add_job(config, move || {
+            let db = Arc::clone(&db_clone);
+            Box::pin(async move {
+                db.sep_performance().run_once().await?;
+                Ok(())
+            })
+        });
+
+        // Wash-trading surveillance of network trades, flagged for review
+        let config = JobConfig::from_env("trade-surveillance", 24 * 3600);
+        let surveillance = Arc::new(TradeSurveillanceService::new(
+            Arc::clone(&db),
+            Arc::clone(&rpc),
+        ));
+        scheduler.add_job(config, move || {
+            let surveillance = Arc::clone(&surveillance);
+            Box::pin(async move {
+                surveillance.run_once().await?;
+                Ok(())
+            })
+        });
+
+        // Weekly retraining of the corridor anomaly models
+        let config = JobConfig::from_env("ml-anomaly-retrain", 7 * 24 * 3600);
+        scheduler.add_job(config, move || {
+            let anomalies = Arc::clone(&anomalies);
             Box::pin(async move {
-                price_feed.warm_cache().await?;
+                anomalies.retrain().await?;
                 Ok(())
             })
         });
 
+        // Report metered tenant usage to the billing exporter, if one is configured
+        match billing::exporter_from_settings(&settings.billing) {
+            Ok(Some(exporter)) => {
+                let config = JobConfig::from_env("billing-usage-export", 3600);
+                let export = Arc::new(UsageExport::new(db.pool().clone(), exporter));
+                scheduler.add_job(config, move || {
+                    let export = Arc::clone(&export);
+                    Box::pin(async move {
+                        let reports = export.run_once().await?;
+                        info!("Exported {} usage reports", reports);
+                        Ok(())
+                    })
+                });
+            }
+            Ok(None) => {}
+            Err(e) => error!("Billing export disabled: {}", e),
+        }
+
         // Cache cleanup job
         let config = JobConfig::from_env("cache-cleanup", 3600);
         let cache_clone = Arc::clone(&cache);
@@ -0,0 +1,234 @@
+//! Supervision for long-running background tasks.
+//!
+//! [`TaskSupervisor::spawn`] runs a task built by a factory closure and
+//! restarts it if it panics or returns, with exponential backoff. Every
+//! supervised task stops on the shutdown signal. Task health is kept in a
+//! shared registry exposed through `GET /api/admin/jobs`.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::observability::metrics as obs_metrics;
+use crate::shutdown::ShutdownCoordinator;
+
+/// Delay before the first restart; doubled for each consecutive failure
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// A run at least this long resets the backoff
+const HEALTHY_RUN: Duration = Duration::from_secs(60);
+
+/// Runs kept per scheduled job
+const RUN_HISTORY_LEN: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    /// Waiting out the backoff after a panic or unexpected exit
+    Restarting,
+    Stopped,
+}
+
+/// One execution of a scheduled job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobRun {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskHealth {
+    pub name: String,
+    pub state: TaskState,
+    pub restarts: u64,
+    pub started_at: DateTime<Utc>,
+    pub last_failure: Option<String>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub next_restart_at: Option<DateTime<Utc>>,
+    /// Most recent runs first; only scheduled jobs record runs
+    #[serde(skip_serializing_if = "VecDeque::is_empty")]
+    pub recent_runs: VecDeque<JobRun>,
+}
+
+#[derive(Clone)]
+pub struct TaskSupervisor {
+    shutdown: Arc<ShutdownCoordinator>,
+    tasks: Arc<RwLock<BTreeMap<String, TaskHealth>>>,
+}
+
+fn next_backoff(current: Duration) -> Duration {
+    (current * 2).min(MAX_BACKOFF)
+}
+
+impl TaskSupervisor {
+    pub fn new(shutdown: Arc<ShutdownCoordinator>) -> Self {
+        Self {
+            shutdown,
+            tasks: Arc::new(RwLock::new(BTreeMap::new())),
+        }
+    }
+
+    /// Health of every supervised task, by name
+    pub fn snapshot(&self) -> Vec<TaskHealth> {
+        self.tasks
+            .read()
+            .map(|tasks| tasks.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// New shutdown receiver, for tasks that handle shutdown themselves
+    pub fn shutdown_signal(&self) -> broadcast::Receiver<()> {
+        self.shutdown.subscribe()
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut TaskHealth)) {
+        if let Ok(mut tasks) = self.tasks.write() {
+            let health = tasks.entry(name.to_string()).or_insert_with(|| TaskHealth {
+                name: name.to_string(),
+                state: TaskState::Running,
+                restarts: 0,
+                started_at: Utc::now(),
+                last_failure: None,
+                last_failure_at: None,
+                next_restart_at: None,
+                recent_runs: VecDeque::new(),
+            });
+            f(health);
+        }
+    }
+
+    /// Record one run of the scheduled job supervised as `name`
+    pub fn record_run(&self, name: &str, run: JobRun) {
+        self.update(name, |h| {
+            h.recent_runs.push_front(run);
+            h.recent_runs.truncate(RUN_HISTORY_LEN);
+        });
+    }
+
+    /// Run the task built by `factory` until shutdown, rebuilding and
+    /// restarting it whenever it panics or exits
+    pub fn spawn<F, Fut>(&self, name: &str, factory: F) -> JoinHandle<()>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let supervisor = self.clone();
+        let name = name.to_string();
+        let mut shutdown_rx = self.shutdown.subscribe();
+
+        tokio::spawn(async move {
+            let mut backoff = INITIAL_BACKOFF;
+
+            loop {
+                supervisor.update(&name, |h| {
+                    h.state = TaskState::Running;
+                    h.started_at = Utc::now();
+                    h.next_restart_at = None;
+                });
+                let started = Instant::now();
+                let mut task = tokio::spawn(factory());
+
+                let failure = tokio::select! {
+                    result = &mut task => match result {
+                        Ok(()) => "task exited unexpectedly".to_string(),
+                        Err(e) if e.is_panic() => format!("panicked: {}", panic_message(e.into_panic())),
+                        Err(e) => format!("task failed: {}", e),
+                    },
+                    _ = shutdown_rx.recv() => {
+                        task.abort();
+                        tracing::info!(task = %name, "Supervised task shutting down");
+                        supervisor.update(&name, |h| h.state = TaskState::Stopped);
+                        return;
+                    }
+                };
+
+                if started.elapsed() >= HEALTHY_RUN {
+                    backoff = INITIAL_BACKOFF;
+                }
+                tracing::error!(
+                    task = %name,
+                    "Background task {}; restarting in {:?}",
+                    failure,
+                    backoff
+                );
+                obs_metrics::record_task_restart(&name);
+                let now = Utc::now();
+                supervisor.update(&name, |h| {
+                    h.state = TaskState::Restarting;
+                    h.restarts += 1;
+                    h.last_failure = Some(failure.clone());
+                    h.last_failure_at = Some(now);
+                    h.next_restart_at = chrono::Duration::from_std(backoff).ok().map(|d| now + d);
+                });
+
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = shutdown_rx.recv() => {
+                        supervisor.update(&name, |h| h.state = TaskState::Stopped);
+                        return;
+                    }
+                }
+                backoff = next_backoff(backoff);
+            }
+        })
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shutdown::ShutdownConfig;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_backoff_is_capped() {
+        assert_eq!(next_backoff(Duration::from_secs(1)), Duration::from_secs(2));
+        assert_eq!(next_backoff(Duration::from_secs(200)), MAX_BACKOFF);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_panicking_task_is_restarted() {
+        let coordinator = Arc::new(ShutdownCoordinator::new(ShutdownConfig::default()));
+        let supervisor = TaskSupervisor::new(Arc::clone(&coordinator));
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let counter = Arc::clone(&runs);
+        let handle = supervisor.spawn("flaky", move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("boom");
+                }
+                std::future::pending::<()>().await;
+            }
+        });
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        let health = supervisor.snapshot();
+        assert_eq!(health[0].restarts, 1);
+        assert_eq!(health[0].state, TaskState::Running);
+        assert!(health[0].last_failure.as_deref().unwrap().contains("boom"));
+
+        coordinator.trigger_shutdown();
+        handle.await.unwrap();
+        assert_eq!(supervisor.snapshot()[0].state, TaskState::Stopped);
+    }
+}
@@ -9,10 +9,14 @@ pub mod api_v1_middleware;
 
 pub mod auth;
 pub mod auth_middleware;
+pub mod billing;
 pub mod broadcast;
 pub mod cache;
 pub mod cache_invalidation;
 pub mod cache_middleware;
+pub mod concurrency;
+pub mod config;
+pub mod contract_events;
 pub mod crypto;
 pub mod database;
 pub mod db;
@@ -24,6 +28,8 @@ pub mod env_config;
 pub mod handlers;
 pub mod logging;
 pub mod http_cache;
+pub mod http_client;
+pub mod i18n;
 pub mod ingestion;
 pub mod jobs;
 pub mod ml;
@@ -34,13 +40,19 @@ pub mod muxed;
 
 pub mod network;
 pub mod openapi;
+pub mod preferences;
 pub mod observability;
+pub mod plan;
 pub mod rate_limit;
 pub mod request_id;
+pub mod screening;
 pub mod services;
 pub mod shutdown;
+pub mod signer;
 pub mod snapshot;
 pub mod snapshot_handlers;
+pub mod sparse_fields;
+pub mod sse;
 pub mod state;
 pub mod vault;
 pub mod webhooks;
@@ -49,6 +61,9 @@ pub mod websocket;
 pub mod rpc;
 pub mod rpc_handlers;
 pub mod telegram;
+pub mod tenant;
+pub mod time_range;
+pub mod timeout;
 
 #[cfg(test)]
 mod ml_tests;
\ No newline at end of file
@@ -10,16 +10,49 @@ use std::time::Duration;
 use tokio::task::JoinHandle;
 use tower_http::compression::{predicate::SizeAbove, CompressionLayer};
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::sensitive_headers::SetSensitiveRequestHeadersLayer;
 use tower_http::trace::TraceLayer;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use stellar_insights_backend::api::account_merges;
+use stellar_insights_backend::api::admin_corridors;
+use stellar_insights_backend::api::admin_db;
+use stellar_insights_backend::api::admin_email;
+use stellar_insights_backend::api::admin_ingestion;
+use stellar_insights_backend::api::movers;
+use stellar_insights_backend::api::snapshot_signatures;
+use stellar_insights_backend::api::snapshot_sla;
+use stellar_insights_backend::api::admin_jobs;
+use stellar_insights_backend::api::admin_surveillance;
+use stellar_insights_backend::api::admin_tenants;
+use stellar_insights_backend::api::usage;
+use stellar_insights_backend::api::anchor_callbacks;
+use stellar_insights_backend::api::anchor_claims;
+use stellar_insights_backend::api::anchor_self_reported;
+use stellar_insights_backend::api::asset_compliance;
+use stellar_insights_backend::api::asset_supply;
+use stellar_insights_backend::api::anchor_merge;
+use stellar_insights_backend::api::preferences;
+use stellar_insights_backend::api::sessions;
+use stellar_insights_backend::api::badges;
+use stellar_insights_backend::api::digest_preview;
+use stellar_insights_backend::api::notifications;
+use stellar_insights_backend::api::{
+    sep24_proxy, sep31_proxy, sep_auth, sep_info_cache, sep_performance, sep_transactions,
+};
+use stellar_insights_backend::api::status;
+use stellar_insights_backend::api::search;
 use stellar_insights_backend::api::anchors_cached::get_anchors;
 use stellar_insights_backend::api::api_analytics;
 use stellar_insights_backend::api::api_keys;
 use stellar_insights_backend::api::cache_stats;
 use stellar_insights_backend::api::corridors_cached::{get_corridor_detail, list_corridors};
+use stellar_insights_backend::api::corridor_anomalies;
+use stellar_insights_backend::api::corridor_cohorts;
+use stellar_insights_backend::api::corridor_health;
+use stellar_insights_backend::api::corridor_seasonality;
+use stellar_insights_backend::api::corridor_simulation;
 use stellar_insights_backend::api::cost_calculator;
 use stellar_insights_backend::api::fee_bump;
 use stellar_insights_backend::api::liquidity_pools;
@@ -27,35 +60,67 @@ use stellar_insights_backend::api::metrics_cached;
 use stellar_insights_backend::api::oauth;
 use stellar_insights_backend::api::verification_rewards;
 use stellar_insights_backend::api::webhooks;
+use stellar_insights_backend::auth::login_guard::LoginGuard;
 use stellar_insights_backend::auth::AuthService;
 use stellar_insights_backend::auth_middleware::auth_middleware;
+use stellar_insights_backend::billing::UsageRecorder;
 use stellar_insights_backend::cache::{CacheConfig, CacheManager};
 use stellar_insights_backend::cache_invalidation::CacheInvalidationService;
+use stellar_insights_backend::concurrency::{limit_group, limit_route, ConcurrencyCaps};
+use stellar_insights_backend::config::Settings;
 use stellar_insights_backend::database::Database;
+use stellar_insights_backend::email::alert_notifier::AlertEmailNotifier;
+use stellar_insights_backend::email::security_alert::SecurityAlertNotifier;
+use stellar_insights_backend::email::unsubscribe::UnsubscribeTokens;
+use stellar_insights_backend::email::{DigestScheduler, EmailOutbox, EmailService};
 use stellar_insights_backend::gdpr::{GdprService, handlers as gdpr_handlers};
 use stellar_insights_backend::handlers::*;
-use stellar_insights_backend::ingestion::ledger::LedgerIngestionService;
+use stellar_insights_backend::http_client;
+use stellar_insights_backend::ingestion::ledger::{self, LedgerIngestionService};
+use stellar_insights_backend::ingestion::status::IngestionSource;
 use stellar_insights_backend::ingestion::DataIngestionService;
-use stellar_insights_backend::jobs::JobScheduler;
+use stellar_insights_backend::jobs::{JobScheduler, TaskSupervisor};
+use stellar_insights_backend::ml::anomaly::AnomalyService;
+use stellar_insights_backend::services::contract::ContractService;
+use stellar_insights_backend::services::corridor_key_filter::corridor_key_filter;
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::snapshot::signing::SnapshotSigner;
 use stellar_insights_backend::network::NetworkConfig;
 use stellar_insights_backend::openapi::ApiDoc;
-use stellar_insights_backend::observability::{metrics as obs_metrics, tracing as obs_tracing};
+use stellar_insights_backend::observability::{
+    error_sink, metrics as obs_metrics, slow_queries, tracing as obs_tracing,
+};
 use stellar_insights_backend::rate_limit::{rate_limit_middleware, RateLimitConfig, RateLimiter};
 use stellar_insights_backend::request_id::request_id_middleware;
-use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::rpc::{RequestPriority, StellarRpcClient};
 use stellar_insights_backend::rpc_handlers;
+use stellar_insights_backend::screening::AccountScreener;
 use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
 use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
 use stellar_insights_backend::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
 use stellar_insights_backend::services::price_feed::{
     default_asset_mapping, PriceFeedClient, PriceFeedConfig,
 };
+use stellar_insights_backend::services::asset_compliance::AssetComplianceTracker;
+use stellar_insights_backend::services::corridor_simulation::CorridorSimulator;
+use stellar_insights_backend::services::asset_supply::AssetSupplyTracker;
+use stellar_insights_backend::services::movers::MoversService;
+use stellar_insights_backend::services::network_congestion::NetworkCongestionService;
+use stellar_insights_backend::services::network_events::NetworkEventDetector;
+use stellar_insights_backend::services::stellar_toml::StellarTomlClient;
 use stellar_insights_backend::services::realtime_broadcaster::RealtimeBroadcaster;
 use stellar_insights_backend::services::trustline_analyzer::TrustlineAnalyzer;
+use stellar_insights_backend::services::sep_transactions::{
+    SepTransactionPoller, SepTransactionTracker,
+};
 use stellar_insights_backend::services::webhook_dispatcher::WebhookDispatcher;
+use stellar_insights_backend::webhooks::WebhookService;
 use stellar_insights_backend::alerts::AlertManager;
 use stellar_insights_backend::monitor::CorridorMonitor;
 use stellar_insights_backend::telegram;
+use stellar_insights_backend::plan::plan_middleware;
+use stellar_insights_backend::tenant::{tenant_middleware, TenantResolver};
+use stellar_insights_backend::timeout::{timeout_middleware, TimeoutBudgets};
 use stellar_insights_backend::shutdown::{
     flush_cache, log_shutdown_summary, shutdown_background_tasks, shutdown_database,
     shutdown_websockets, wait_for_signal, ShutdownConfig, ShutdownCoordinator,
@@ -75,6 +140,7 @@ async fn main() -> Result<()> {
     // Initialize tracing + optional OpenTelemetry exporter
     obs_tracing::init_tracing("stellar-insights-backend")?;
     obs_metrics::init_metrics();
+    error_sink::install_panic_hook();
 
     tracing::info!("Starting Stellar Insights Backend");
 
@@ -85,6 +151,14 @@ async fn main() -> Result<()> {
     // Log sanitized environment configuration
     stellar_insights_backend::env_config::log_env_config();
 
+    // Load typed settings (optional CONFIG_FILE, overridden by the environment)
+    let settings = Arc::new(Settings::load().context("Invalid configuration")?);
+
+    // Outbound clients are configured before anything (the error sink
+    // included) sends a request through them
+    http_client::init(settings.http_client.client_config());
+    error_sink::init(settings.error_sink.sink_config());
+
     // Initialize shutdown coordinator
     let shutdown_config = ShutdownConfig::from_env();
     tracing::info!(
@@ -95,9 +169,11 @@ async fn main() -> Result<()> {
     );
     let shutdown_coordinator = Arc::new(ShutdownCoordinator::new(shutdown_config.clone()));
 
+    // Supervisor that restarts background tasks after panics
+    let task_supervisor = TaskSupervisor::new(Arc::clone(&shutdown_coordinator));
+
     // Database connection
-    let database_url = std::env::var("DATABASE_URL")
-        .unwrap_or_else(|_| "sqlite:./stellar_insights.db".to_string());
+    let database_url = settings.database.url.clone();
 
     // Log sanitized database URL to prevent credential leakage (SEC-016)
     let sanitized_db_url = if database_url.starts_with("sqlite:") {
@@ -113,30 +189,29 @@ async fn main() -> Result<()> {
     };
     tracing::info!("Connecting to database: {}", sanitized_db_url);
 
-    // Load pool configuration from environment
-    let pool_config = stellar_insights_backend::database::PoolConfig::from_env();
+    let pool_config = settings.database.pool_config();
     tracing::info!(
         "Database pool configuration: max_connections={}, min_connections={}, \
-         connect_timeout={}s, idle_timeout={}s, max_lifetime={}s",
+         connect_timeout={}s, idle_timeout={}s, max_lifetime={}s, slow_query_threshold={}ms",
         pool_config.max_connections,
         pool_config.min_connections,
         pool_config.connect_timeout_seconds,
         pool_config.idle_timeout_seconds,
-        pool_config.max_lifetime_seconds
+        pool_config.max_lifetime_seconds,
+        pool_config.slow_query_threshold_ms
     );
 
     let pool = pool_config.create_pool(&database_url).await?;
+    slow_queries::tracker()
+        .set_threshold(Duration::from_millis(pool_config.slow_query_threshold_ms));
 
     tracing::info!("Running database migrations...");
     sqlx::migrate!("./migrations").run(&pool).await?;
 
-    let db = Arc::new(Database::new(pool.clone()));
+    let db = Arc::new(Database::new(pool.clone()).with_cipher(settings.security.field_cipher()));
 
     // Initialize Stellar RPC Client
-    let mock_mode = std::env::var("RPC_MOCK_MODE")
-        .unwrap_or_else(|_| "false".to_string())
-        .parse::<bool>()
-        .unwrap_or(false);
+    let mock_mode = settings.rpc.mock_mode;
 
     // Initialize Stellar RPC Client with network configuration
     let network_config = NetworkConfig::from_env();
@@ -147,25 +222,39 @@ async fn main() -> Result<()> {
     );
 
     let rpc_client = if mock_mode {
-        Arc::new(StellarRpcClient::new_with_network(
-            network_config.network,
-            true,
-        ))
+        StellarRpcClient::new_with_network(network_config.network, true)
     } else {
-        Arc::new(StellarRpcClient::new(
+        StellarRpcClient::new(
             network_config.rpc_url.clone(),
             network_config.horizon_url.clone(),
             false,
-        ))
+        )
     };
+    let rpc_client = Arc::new(
+        rpc_client
+            .with_horizon_fallbacks(settings.rpc.horizon_urls.clone())
+            .with_rate_limit(settings.rpc.rate_limit_config()),
+    );
+
+    // Jobs and monitors share the API's Horizon budget but yield to it as it
+    // runs low; `rpc_client` itself is charged as interactive traffic
+    let background_rpc_client = Arc::new(rpc_client.with_priority(RequestPriority::Background));
+    let monitoring_rpc_client = Arc::new(rpc_client.with_priority(RequestPriority::Standard));
 
     // Initialize WebSocket state
-    let ws_state = Arc::new(WsState::new());
+    let ws_state = Arc::new(
+        WsState::new()
+            .with_auth_token(settings.websocket.auth_token.clone())
+            .with_auth_service(Arc::new(AuthService::new(Arc::clone(&db))))
+            .with_admin_user_ids(settings.websocket.admin_user_ids.clone())
+            .with_usage(UsageRecorder::new(pool.clone()))
+            .with_database(Arc::clone(&db)),
+    );
     tracing::info!("WebSocket state initialized");
 
     // Initialize Data Ingestion Service
     let ingestion_service = Arc::new(DataIngestionService::new(
-        Arc::clone(&rpc_client),
+        Arc::clone(&background_rpc_client),
         Arc::clone(&db),
     ));
 
@@ -175,13 +264,22 @@ async fn main() -> Result<()> {
     // Initialize Account Merge Detector Service
     let account_merge_detector = Arc::new(AccountMergeDetector::new(
         pool.clone(),
-        Arc::clone(&rpc_client),
+        Arc::clone(&background_rpc_client),
+    ));
+
+    // Counterparty screening of accounts listed by the API
+    let account_screener = Arc::new(AccountScreener::from_settings(&settings.screening)?);
+
+    // Initialize Asset Compliance Tracker (clawbacks and trustline freezes)
+    let asset_compliance_tracker = Arc::new(AssetComplianceTracker::new(
+        pool.clone(),
+        Arc::clone(&background_rpc_client),
     ));
 
     // Initialize Liquidity Pool Analyzer
     let lp_analyzer = Arc::new(LiquidityPoolAnalyzer::new(
         pool.clone(),
-        Arc::clone(&rpc_client),
+        Arc::clone(&background_rpc_client),
     ));
 
     // Initialize Price Feed Client
@@ -193,17 +291,32 @@ async fn main() -> Result<()> {
     // Initialize Trustline Analyzer
     let trustline_analyzer = Arc::new(TrustlineAnalyzer::new(
         pool.clone(),
-        Arc::clone(&rpc_client),
+        Arc::clone(&background_rpc_client),
     ));
 
-    // Initialize Ledger Ingestion Service
-    let ledger_ingestion_service = Arc::new(LedgerIngestionService::new(
-        Arc::clone(&rpc_client),
-        Arc::clone(&fee_bump_tracker),
-        Arc::clone(&account_merge_detector),
+    // Queues network-wide webhook events (fee spikes, slow ledgers, anchor
+    // stellar.toml changes, published snapshots)
+    let webhook_service = Arc::new(WebhookService::new(
         pool.clone(),
+        settings.security.encryption_key.clone(),
     ));
 
+    // Initialize Ledger Ingestion Service
+    let ledger_ingestion_service = Arc::new(
+        LedgerIngestionService::new(
+            Arc::clone(&background_rpc_client),
+            Arc::clone(&fee_bump_tracker),
+            Arc::clone(&account_merge_detector),
+            Arc::clone(&asset_compliance_tracker),
+            pool.clone(),
+        )
+        .with_network_events(NetworkEventDetector::new(
+            pool.clone(),
+            Arc::clone(&webhook_service),
+            settings.alerts.network_event_config(),
+        )),
+    );
+
     // Initialize Redis cache
     let cache_config = CacheConfig::default();
     let cache = Arc::new(CacheManager::new(cache_config).await?);
@@ -220,27 +333,32 @@ async fn main() -> Result<()> {
     let corridor_monitor = Arc::new(CorridorMonitor::new(
         Arc::clone(&alert_manager),
         Arc::clone(&cache),
-        Arc::clone(&rpc_client),
+        Arc::clone(&monitoring_rpc_client),
     ));
 
-    // Initialize RealtimeBroadcaster
-    let mut realtime_broadcaster = RealtimeBroadcaster::new(
+    // Initialize Webhook Dispatcher
+    let webhook_dispatcher =
+        WebhookDispatcher::new(pool.clone(), settings.security.encryption_key.clone());
+    tracing::info!("Webhook dispatcher initialized");
+
+    // Initialize SEP transaction tracking (anchor callbacks and status poller)
+    let sep_tracker = Arc::new(SepTransactionTracker::new(
+        Arc::clone(&db),
         Arc::clone(&ws_state),
+        WebhookService::new(pool.clone(), settings.security.encryption_key.clone()),
+    ));
+    let sep_poller = SepTransactionPoller::new(
         Arc::clone(&db),
-        Arc::clone(&rpc_client),
-        Arc::clone(&cache),
+        Arc::clone(&sep_tracker),
+        Duration::from_secs(settings.sep_tracking.poll_interval_secs),
     );
-    tracing::info!("RealtimeBroadcaster initialized");
-
-    // Initialize Webhook Dispatcher
-    let webhook_dispatcher = WebhookDispatcher::new(pool.clone());
-    tracing::info!("Webhook dispatcher initialized");
 
     // Create app state for handlers that need it
     let app_state = AppState::new(
         Arc::clone(&db),
         Arc::clone(&ws_state),
         Arc::clone(&ingestion_service),
+        Arc::clone(&settings),
     );
 
     // Create cached state tuple for cached API handlers
@@ -257,43 +375,40 @@ async fn main() -> Result<()> {
     // Metrics synchronization task
     let ingestion_clone = Arc::clone(&ingestion_service);
     let cache_invalidation_clone = Arc::clone(&cache_invalidation);
-    let shutdown_rx1 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
-        let mut shutdown_rx = shutdown_rx1;
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    if let Err(e) = ingestion_clone.sync_all_metrics().await {
-                        tracing::error!("Metrics synchronization failed: {}", e);
-                        obs_metrics::record_background_job("metrics_sync", "error");
-                    } else {
-                        obs_metrics::record_background_job("metrics_sync", "success");
-                        // Invalidate caches after successful sync
-                        if let Err(e) = cache_invalidation_clone.invalidate_anchors().await {
-                            tracing::warn!("Failed to invalidate anchor caches: {}", e);
-                        }
-                        if let Err(e) = cache_invalidation_clone.invalidate_corridors().await {
-                            tracing::warn!("Failed to invalidate corridor caches: {}", e);
-                        }
-                        if let Err(e) = cache_invalidation_clone.invalidate_metrics().await {
-                            tracing::warn!("Failed to invalidate metrics caches: {}", e);
-                        }
+    let task = task_supervisor.spawn("metrics_sync", move || {
+        let ingestion_clone = Arc::clone(&ingestion_clone);
+        let cache_invalidation_clone = Arc::clone(&cache_invalidation_clone);
+        async move {
+            let period = std::time::Duration::from_secs(300); // 5 minutes
+            let mut interval = tokio::time::interval(period);
+            let tracker = ingestion_clone.tracker().clone();
+            loop {
+                interval.tick().await;
+                if let Err(e) = ingestion_clone.sync_all_metrics().await {
+                    tracing::error!("Metrics synchronization failed: {}", e);
+                    obs_metrics::record_background_job("metrics_sync", "error");
+                    tracker.record_failure(IngestionSource::Payments, &e.to_string(), period);
+                } else {
+                    obs_metrics::record_background_job("metrics_sync", "success");
+                    tracker.record_success(IngestionSource::Payments, None, None, period);
+                    // Invalidate caches after successful sync
+                    if let Err(e) = cache_invalidation_clone.invalidate_anchors().await {
+                        tracing::warn!("Failed to invalidate anchor caches: {}", e);
+                    }
+                    if let Err(e) = cache_invalidation_clone.invalidate_corridors().await {
+                        tracing::warn!("Failed to invalidate corridor caches: {}", e);
+                    }
+                    if let Err(e) = cache_invalidation_clone.invalidate_metrics().await {
+                        tracing::warn!("Failed to invalidate metrics caches: {}", e);
                     }
-                }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Metrics synchronization task shutting down");
-                    break;
                 }
             }
         }
     });
     background_tasks.push(task);
 
-    // Initialize Auth Service with its own Redis connection
-    let redis_url =
-        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-    let auth_redis_connection = if let Ok(client) = redis::Client::open(redis_url.as_str()) {
+    // Redis connection for SEP-10 challenges and failed-login tracking
+    let auth_redis_connection = if let Ok(client) = redis::Client::open(settings.redis.url.as_str()) {
         match client.get_multiplexed_tokio_connection().await {
             Ok(conn) => {
                 tracing::info!("Auth service connected to Redis");
@@ -301,7 +416,7 @@ async fn main() -> Result<()> {
             }
             Err(e) => {
                 tracing::warn!(
-                    "Auth service failed to connect to Redis ({}), refresh tokens will not persist",
+                    "Auth service failed to connect to Redis ({}), SEP-10 challenges will not persist and failed logins are not tracked",
                     e
                 );
                 None
@@ -311,21 +426,23 @@ async fn main() -> Result<()> {
         tracing::warn!("Invalid Redis URL for auth service");
         None
     };
-    let auth_service = Arc::new(AuthService::new(Arc::new(tokio::sync::RwLock::new(
-        auth_redis_connection.clone(),
-    ))));
+    // Refresh tokens are tracked per login session in the database. The
+    // auth service is shared once email is set up (new device login alerts).
+    let mut auth_service = AuthService::new(Arc::clone(&db)).with_login_guard(LoginGuard::new(
+        Arc::new(tokio::sync::RwLock::new(auth_redis_connection.clone())),
+        settings.login.guard_config(),
+    ));
     tracing::info!("Auth service initialized");
 
     // Initialize SEP-10 Service for Stellar authentication
     let sep10_redis_connection = Arc::new(tokio::sync::RwLock::new(auth_redis_connection));
     let sep10_service = Arc::new(
         stellar_insights_backend::auth::sep10_simple::Sep10Service::new(
-            std::env::var("SEP10_SERVER_PUBLIC_KEY").unwrap_or_else(|_| {
+            settings.sep10.server_public_key.clone().unwrap_or_else(|| {
                 "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX".to_string()
             }),
             network_config.network_passphrase.clone(),
-            std::env::var("SEP10_HOME_DOMAIN")
-                .unwrap_or_else(|_| "stellar-insights.local".to_string()),
+            settings.sep10.home_domain.clone(),
             sep10_redis_connection,
         )
         .expect("Failed to initialize SEP-10 service"),
@@ -350,50 +467,51 @@ async fn main() -> Result<()> {
     let gdpr_service = Arc::new(GdprService::new(pool.clone()));
     tracing::info!("GDPR service initialized");
 
-    // ML Retraining task (commented out)
-    /*
-    let ml_service_clone = ml_service.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(7 * 24 * 3600)); // 7 days
-        loop {
-            interval.tick().await;
-            if let Ok(mut service) = ml_service_clone.try_write() {
-                if let Err(e) = service.retrain_weekly().await {
-                    tracing::error!("Weekly ML retraining failed: {}", e);
-                }
-            }
-        }
-    });
-    */
+    // Corridor anomaly detection; models are retrained weekly by the job scheduler
+    let anomaly_service = Arc::new(AnomalyService::with_model(
+        Arc::clone(&db),
+        settings.ml.anomaly_model,
+    ));
 
     // Ledger ingestion task
     let ledger_ingestion_clone = Arc::clone(&ledger_ingestion_service);
-    let shutdown_rx2 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        tracing::info!("Starting ledger ingestion background task");
-        let mut shutdown_rx = shutdown_rx2;
-        loop {
-            tokio::select! {
-                result = ledger_ingestion_clone.run_ingestion(5) => {
-                    match result {
-                        Ok(count) => {
-                            obs_metrics::record_background_job("ledger_ingestion", "success");
-                            if count == 0 {
-                                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                            } else {
-                                tokio::task::yield_now().await;
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Ledger ingestion failed: {}", e);
-                            obs_metrics::record_background_job("ledger_ingestion", "error");
-                            tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    let ledger_tracker = ingestion_service.tracker().clone();
+    let ledger_batch_size = settings.ingestion.ledger_batch_size;
+    let task = task_supervisor.spawn("ledger_ingestion", move || {
+        let ledger_ingestion_clone = Arc::clone(&ledger_ingestion_clone);
+        let tracker = ledger_tracker.clone();
+        async move {
+            tracing::info!("Starting ledger ingestion background task");
+            let mut failures = 0u32;
+            loop {
+                match ledger_ingestion_clone
+                    .run_ingestion(ledger_batch_size)
+                    .await
+                {
+                    Ok(count) => {
+                        failures = 0;
+                        obs_metrics::record_background_job("ledger_ingestion", "success");
+                        // Caught up: poll again shortly, otherwise continue immediately
+                        let delay = if count == 0 {
+                            std::time::Duration::from_secs(5)
+                        } else {
+                            std::time::Duration::ZERO
+                        };
+                        tracker.record_success(IngestionSource::Ledgers, None, None, delay);
+                        if count == 0 {
+                            tokio::time::sleep(delay).await;
+                        } else {
+                            tokio::task::yield_now().await;
                         }
                     }
-                }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Ledger ingestion task shutting down");
-                    break;
+                    Err(e) => {
+                        tracing::error!("Ledger ingestion failed: {}", e);
+                        obs_metrics::record_background_job("ledger_ingestion", "error");
+                        failures = failures.saturating_add(1);
+                        let delay = ledger::retry_delay(failures);
+                        tracker.record_failure(IngestionSource::Ledgers, &e.to_string(), delay);
+                        tokio::time::sleep(delay).await;
+                    }
                 }
             }
         }
@@ -402,30 +520,24 @@ async fn main() -> Result<()> {
 
     // Liquidity pool sync background task
     let lp_analyzer_clone = Arc::clone(&lp_analyzer);
-    let shutdown_rx3 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        tracing::info!("Starting liquidity pool sync background task");
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
-        let mut shutdown_rx = shutdown_rx3;
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    if let Err(e) = lp_analyzer_clone.sync_pools().await {
-                        tracing::error!("Liquidity pool sync failed: {}", e);
-                        obs_metrics::record_background_job("liquidity_pool_sync", "error");
-                    } else {
-                        obs_metrics::record_background_job("liquidity_pool_sync", "success");
-                    }
-                    if let Err(e) = lp_analyzer_clone.take_snapshots().await {
-                        tracing::error!("Liquidity pool snapshot failed: {}", e);
-                        obs_metrics::record_background_job("liquidity_pool_snapshot", "error");
-                    } else {
-                        obs_metrics::record_background_job("liquidity_pool_snapshot", "success");
-                    }
+    let task = task_supervisor.spawn("liquidity_pool_sync", move || {
+        let lp_analyzer_clone = Arc::clone(&lp_analyzer_clone);
+        async move {
+            tracing::info!("Starting liquidity pool sync background task");
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
+            loop {
+                interval.tick().await;
+                if let Err(e) = lp_analyzer_clone.sync_pools().await {
+                    tracing::error!("Liquidity pool sync failed: {}", e);
+                    obs_metrics::record_background_job("liquidity_pool_sync", "error");
+                } else {
+                    obs_metrics::record_background_job("liquidity_pool_sync", "success");
                 }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Liquidity pool sync task shutting down");
-                    break;
+                if let Err(e) = lp_analyzer_clone.take_snapshots().await {
+                    tracing::error!("Liquidity pool snapshot failed: {}", e);
+                    obs_metrics::record_background_job("liquidity_pool_snapshot", "error");
+                } else {
+                    obs_metrics::record_background_job("liquidity_pool_snapshot", "success");
                 }
             }
         }
@@ -434,52 +546,57 @@ async fn main() -> Result<()> {
 
     // Trustline stats sync background task
     let trustline_analyzer_clone = Arc::clone(&trustline_analyzer);
-    let shutdown_rx4 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        tracing::info!("Starting trustline stats sync background task");
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(900)); // 15 minutes
-        let mut shutdown_rx = shutdown_rx4;
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    if let Err(e) = trustline_analyzer_clone.sync_assets().await {
-                        tracing::error!("Trustline sync failed: {}", e);
-                        obs_metrics::record_background_job("trustline_sync", "error");
-                    } else {
-                        obs_metrics::record_background_job("trustline_sync", "success");
-                    }
-                    if let Err(e) = trustline_analyzer_clone.take_snapshots().await {
-                        tracing::error!("Trustline snapshot failed: {}", e);
-                        obs_metrics::record_background_job("trustline_snapshot", "error");
-                    } else {
-                        obs_metrics::record_background_job("trustline_snapshot", "success");
-                    }
+    let task = task_supervisor.spawn("trustline_sync", move || {
+        let trustline_analyzer_clone = Arc::clone(&trustline_analyzer_clone);
+        async move {
+            tracing::info!("Starting trustline stats sync background task");
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(900)); // 15 minutes
+            loop {
+                interval.tick().await;
+                if let Err(e) = trustline_analyzer_clone.sync_assets().await {
+                    tracing::error!("Trustline sync failed: {}", e);
+                    obs_metrics::record_background_job("trustline_sync", "error");
+                } else {
+                    obs_metrics::record_background_job("trustline_sync", "success");
                 }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Trustline stats sync task shutting down");
-                    break;
+                if let Err(e) = trustline_analyzer_clone.take_snapshots().await {
+                    tracing::error!("Trustline snapshot failed: {}", e);
+                    obs_metrics::record_background_job("trustline_snapshot", "error");
+                } else {
+                    obs_metrics::record_background_job("trustline_snapshot", "success");
                 }
             }
         }
     });
     background_tasks.push(task);
 
-    // Start RealtimeBroadcaster background task
-    let shutdown_rx5 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        tracing::info!("Starting RealtimeBroadcaster background task");
-        let mut shutdown_rx = shutdown_rx5;
-        tokio::select! {
-            _ = realtime_broadcaster.start() => {
-                tracing::info!("RealtimeBroadcaster task completed");
-            }
-            _ = shutdown_rx.recv() => {
-                tracing::info!("RealtimeBroadcaster task shutting down");
-            }
+    // Start RealtimeBroadcaster background task (rebuilt on restart)
+    let broadcaster_deps = (
+        Arc::clone(&ws_state),
+        Arc::clone(&db),
+        Arc::clone(&monitoring_rpc_client),
+        Arc::clone(&cache),
+    );
+    let task = task_supervisor.spawn("realtime_broadcaster", move || {
+        let (ws_state, db, rpc_client, cache) = broadcaster_deps.clone();
+        async move {
+            tracing::info!("Starting RealtimeBroadcaster background task");
+            RealtimeBroadcaster::new(ws_state, db, rpc_client, cache)
+                .start()
+                .await;
         }
     });
     background_tasks.push(task);
 
+    // Push top corridor movers to `movers` WebSocket subscribers
+    let movers_service = Arc::new(MoversService::new(Arc::clone(&db), Arc::clone(&ws_state)));
+    let movers_for_task = Arc::clone(&movers_service);
+    let task = task_supervisor.spawn("movers", move || {
+        let movers = Arc::clone(&movers_for_task);
+        async move { movers.run().await }
+    });
+    background_tasks.push(task);
+
     // Initialize Alert Manager
     let (alert_manager_raw, alert_rx) = stellar_insights_backend::alerts::AlertManager::new();
     let alert_manager = Arc::new(alert_manager_raw);
@@ -489,19 +606,46 @@ async fn main() -> Result<()> {
     let corridor_monitor = Arc::new(stellar_insights_backend::monitor::CorridorMonitor::new(
         Arc::clone(&alert_manager),
         Arc::clone(&cache),
-        Arc::clone(&rpc_client),
+        Arc::clone(&monitoring_rpc_client),
     ));
     tracing::info!("Corridor monitor initialized");
 
+    // Classify network congestion so corridor alerts can blame a network-wide surge
+    let congestion_service = Arc::new(NetworkCongestionService::new(Arc::clone(
+        &monitoring_rpc_client,
+    )));
+    let congestion_for_task = Arc::clone(&congestion_service);
+    let alert_manager_for_congestion = Arc::clone(&alert_manager);
+    let task = task_supervisor.spawn("network_congestion", move || {
+        let congestion = Arc::clone(&congestion_for_task);
+        let alert_manager = Arc::clone(&alert_manager_for_congestion);
+        async move { congestion.run(alert_manager).await }
+    });
+    background_tasks.push(task);
+
+    // Record anchor asset supply and holder concentration, alerting on large mints/burns
+    let supply_tracker = Arc::new(AssetSupplyTracker::new(
+        Arc::clone(&db),
+        Arc::clone(&background_rpc_client),
+        settings.alerts.supply_tracker_config(),
+    ));
+    let alert_manager_for_supply = Arc::clone(&alert_manager);
+    let task = task_supervisor.spawn("asset_supply", move || {
+        let tracker = Arc::clone(&supply_tracker);
+        let alert_manager = Arc::clone(&alert_manager_for_supply);
+        async move { tracker.run(alert_manager).await }
+    });
+    background_tasks.push(task);
+
     // Initialize Slack Bot Service
-    let slack_webhook_url = std::env::var("SLACK_WEBHOOK_URL").ok();
-    if let Some(url) = slack_webhook_url {
-        let slack_bot = stellar_insights_backend::services::slack_bot::SlackBotService::new(
-            url,
-            alert_manager.subscribe(),
-        );
-        let task = tokio::spawn(async move {
-            slack_bot.start().await;
+    if let Some(url) = settings.integrations.slack_webhook_url.clone() {
+        let alert_manager_clone = Arc::clone(&alert_manager);
+        let task = task_supervisor.spawn("slack_bot", move || {
+            stellar_insights_backend::services::slack_bot::SlackBotService::new(
+                url.clone(),
+                alert_manager_clone.subscribe(),
+            )
+            .start()
         });
         background_tasks.push(task);
         tracing::info!("Slack bot service started as background task");
@@ -509,63 +653,144 @@ async fn main() -> Result<()> {
         tracing::warn!("SLACK_WEBHOOK_URL not set, slack alerts disabled");
     }
 
+    // Signed one-click unsubscribe links in every digest and alert email
+    let unsubscribe_tokens = UnsubscribeTokens::new(
+        &settings.security.encryption_key,
+        &settings.server.public_url(),
+    );
+
+    // Outbound email: digests and alert emails go through a persistent retry queue
+    match EmailService::from_settings(&settings.email) {
+        Ok(Some(email_service)) => {
+            let outbox = Arc::new(EmailOutbox::new(Arc::clone(&db), Arc::new(email_service)));
+
+            let outbox_worker = Arc::clone(&outbox);
+            let task = task_supervisor.spawn("email_outbox", move || {
+                let outbox = Arc::clone(&outbox_worker);
+                async move { outbox.run().await }
+            });
+            background_tasks.push(task);
+
+            let outbox_for_alerts = Arc::clone(&outbox);
+            let db_for_alerts = Arc::clone(&db);
+            let alert_manager_for_email = Arc::clone(&alert_manager);
+            let tokens_for_alerts = unsubscribe_tokens.clone();
+            let task = task_supervisor.spawn("alert_emails", move || {
+                AlertEmailNotifier::new(
+                    Arc::clone(&outbox_for_alerts),
+                    Arc::clone(&db_for_alerts),
+                    alert_manager_for_email.subscribe(),
+                )
+                .with_unsubscribe_links(tokens_for_alerts.clone())
+                .start()
+            });
+            background_tasks.push(task);
+
+            auth_service = auth_service.with_security_alerts(SecurityAlertNotifier::new(
+                Arc::clone(&outbox),
+                Arc::clone(&db),
+            ));
+
+            let digest_scheduler = Arc::new(
+                DigestScheduler::new(
+                    Arc::clone(&outbox),
+                    Arc::clone(&cache),
+                    Arc::clone(&background_rpc_client),
+                    Vec::new(),
+                )
+                .with_preferences(Arc::clone(&db))
+                .with_unsubscribe_links(unsubscribe_tokens.clone()),
+            );
+            let task = task_supervisor.spawn("email_digests", move || {
+                Arc::clone(&digest_scheduler).start()
+            });
+            background_tasks.push(task);
+            tracing::info!("Email outbox, alert emails and digests started");
+        }
+        Ok(None) => tracing::warn!("EMAIL_PROVIDER not set, email delivery disabled"),
+        Err(e) => tracing::error!("Invalid email configuration, email delivery disabled: {}", e),
+    }
+    let auth_service = Arc::new(auth_service);
+
     // Start Corridor Monitor background task
     let monitor_clone = Arc::clone(&corridor_monitor);
-    let task = tokio::spawn(async move {
-        monitor_clone.start().await;
+    let task = task_supervisor.spawn("corridor_monitor", move || {
+        Arc::clone(&monitor_clone).start()
     });
     background_tasks.push(task);
     tracing::info!("Corridor monitor task started");
 
-    // Start Webhook Dispatcher background task
-    let shutdown_rx6 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-
-        let mut shutdown_rx = shutdown_rx6;
-        tokio::select! {
-            result = webhook_dispatcher.run() => {
-                if let Err(e) = result {
-                    tracing::error!("Webhook dispatcher encountered fatal error: {}", e);
+    // Sample pool utilization and acquire latency for /metrics
+    let db_monitor = Arc::clone(&db);
+    let task = task_supervisor.spawn("db_pool_monitor", move || {
+        let db = Arc::clone(&db_monitor);
+        async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(15));
+            loop {
+                interval.tick().await;
+                if let Err(e) = db.probe_pool().await {
+                    tracing::warn!("Database pool probe failed: {}", e);
                 }
             }
-            _ = shutdown_rx.recv() => {
-                tracing::info!("Webhook dispatcher task shutting down");
-            }
         }
     });
     background_tasks.push(task);
 
-    // Start CorridorMonitor background task
-    let monitor_clone = Arc::clone(&corridor_monitor);
-    let shutdown_rx_monitor = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        let mut shutdown_rx = shutdown_rx_monitor;
-        tokio::select! {
-            _ = monitor_clone.start() => {
-                tracing::info!("CorridorMonitor task completed");
+    // Keep the corridor key bloom filter in step with stored corridor metrics
+    let db_for_corridor_keys = Arc::clone(&db);
+    let task = task_supervisor.spawn("corridor_key_filter", move || {
+        let db = Arc::clone(&db_for_corridor_keys);
+        async move { corridor_key_filter().run(&db).await }
+    });
+    background_tasks.push(task);
+
+    // Start Webhook Dispatcher background task
+    let webhook_dispatcher = Arc::new(webhook_dispatcher);
+    let task = task_supervisor.spawn("webhook_dispatcher", move || {
+        let webhook_dispatcher = Arc::clone(&webhook_dispatcher);
+        async move {
+            if let Err(e) = webhook_dispatcher.run().await {
+                tracing::error!("Webhook dispatcher encountered fatal error: {}", e);
             }
-            _ = shutdown_rx.recv() => {
-                tracing::info!("CorridorMonitor task shutting down");
+        }
+    });
+    background_tasks.push(task);
+
+    // Start SEP transaction status poller background task
+    let sep_poller = Arc::new(sep_poller);
+    let task = task_supervisor.spawn("sep_transaction_poller", move || {
+        let sep_poller = Arc::clone(&sep_poller);
+        async move {
+            if let Err(e) = sep_poller.run().await {
+                tracing::error!("SEP transaction poller encountered fatal error: {}", e);
             }
         }
     });
     background_tasks.push(task);
 
+
     // Start Telegram Bot (conditionally, when TELEGRAM_BOT_TOKEN is set)
-    if let Ok(telegram_token) = std::env::var("TELEGRAM_BOT_TOKEN") {
+    if let Some(telegram_token) = settings.integrations.telegram_bot_token.clone() {
         tracing::info!("Telegram bot token found, starting bot");
         let tg_subscriptions = Arc::new(telegram::SubscriptionService::new(pool.clone()));
-        let tg_bot = telegram::TelegramBot::new(
-            &telegram_token,
+        let tg_deps = (
             Arc::clone(&db),
             Arc::clone(&cache),
             Arc::clone(&rpc_client),
-            tg_subscriptions,
-            &alert_manager,
+            Arc::clone(&alert_manager),
         );
-        let shutdown_rx_tg = shutdown_coordinator.subscribe();
-        let task = tokio::spawn(async move {
-            tg_bot.run(shutdown_rx_tg).await;
+        let tg_supervisor = task_supervisor.clone();
+        let task = task_supervisor.spawn("telegram_bot", move || {
+            let (db, cache, rpc_client, alert_manager) = tg_deps.clone();
+            let tg_bot = telegram::TelegramBot::new(
+                &telegram_token,
+                db,
+                cache,
+                rpc_client,
+                Arc::clone(&tg_subscriptions),
+                &alert_manager,
+            );
+            tg_bot.run(tg_supervisor.shutdown_signal())
         });
         background_tasks.push(task);
         tracing::info!("Telegram bot started");
@@ -577,20 +802,58 @@ async fn main() -> Result<()> {
     tracing::info!("Running initial metrics synchronization...");
     let _ = ingestion_service.sync_all_metrics().await;
 
+    let snapshot_signer = match SnapshotSigner::from_settings(&settings.signing).await {
+        Ok(Some(signer)) => {
+            match signer.stellar_address().await {
+                Ok(address) => tracing::info!(
+                    "Snapshot signing key ({}): {}",
+                    signer.backend(),
+                    address
+                ),
+                Err(e) => tracing::warn!("Snapshot signing key is not reachable yet: {}", e),
+            }
+            Some(Arc::new(signer))
+        }
+        Ok(None) => {
+            tracing::warn!("SNAPSHOT_SIGNING_KEY not set; snapshots will not be signed");
+            None
+        }
+        Err(e) => {
+            tracing::error!("Invalid snapshot signing key, snapshots will not be signed: {}", e);
+            None
+        }
+    };
+    let contract_service = ContractService::from_env(&settings.signing)
+        .await
+        .ok()
+        .map(Arc::new);
+    let snapshot_service = Arc::new(
+        SnapshotService::new(Arc::clone(&db), contract_service.clone())
+            .with_signer(snapshot_signer)
+            .with_webhooks(Some(Arc::clone(&webhook_service))),
+    );
+
     // Start background job scheduler
     tracing::info!("Starting background job scheduler...");
     let _job_scheduler = JobScheduler::start(
+        Arc::clone(&settings),
         Arc::clone(&db),
         Arc::clone(&cache),
-        Arc::clone(&rpc_client),
+        Arc::clone(&background_rpc_client),
         Arc::clone(&ingestion_service),
+        Arc::clone(&ledger_ingestion_service),
         Arc::clone(&price_feed),
+        Arc::clone(&snapshot_service),
+        contract_service,
+        Arc::clone(&anomaly_service),
+        Arc::clone(&webhook_service),
+        task_supervisor.clone(),
     )
     .await;
     tracing::info!("Background job scheduler started");
 
     // Initialize rate limiter
-    let rate_limiter_result = RateLimiter::new().await;
+    let rate_limiter_result = RateLimiter::new(&settings.redis.url).await;
     let rate_limiter = match rate_limiter_result {
         Ok(limiter) => {
             tracing::info!("Rate limiter initialized successfully");
@@ -602,20 +865,23 @@ async fn main() -> Result<()> {
                 e
             );
             Arc::new(
-                RateLimiter::new()
+                RateLimiter::new(&settings.redis.url)
                     .await
                     .unwrap_or_else(|_| panic!("Failed to create rate limiter: critical error")),
             )
         }
     };
 
-    // Configure rate limits for endpoints
+    // Configure rate limits for endpoints; whitelisted IPs bypass every limit
+    let rate_limit_whitelist = settings.security.rate_limit_whitelist_ips.clone();
     rate_limiter
         .register_endpoint(
             "/health".to_string(),
             RateLimitConfig {
                 requests_per_minute: 1000,
-                whitelist_ips: vec!["127.0.0.1".to_string()],
+                whitelist_ips: std::iter::once("127.0.0.1".to_string())
+                    .chain(rate_limit_whitelist.iter().cloned())
+                    .collect(),
             },
         )
         .await;
@@ -625,7 +891,7 @@ async fn main() -> Result<()> {
             "/api/anchors".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
@@ -635,7 +901,7 @@ async fn main() -> Result<()> {
             "/api/corridors".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
@@ -645,7 +911,7 @@ async fn main() -> Result<()> {
             "/api/rpc/payments".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
@@ -655,7 +921,7 @@ async fn main() -> Result<()> {
             "/api/rpc/trades".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
@@ -665,7 +931,7 @@ async fn main() -> Result<()> {
             "/api/liquidity-pools".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
@@ -675,7 +941,7 @@ async fn main() -> Result<()> {
             "/api/prices".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
@@ -685,7 +951,7 @@ async fn main() -> Result<()> {
             "/api/account-merges".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
@@ -695,21 +961,19 @@ async fn main() -> Result<()> {
             "/api/achievements".to_string(),
             RateLimitConfig {
                 requests_per_minute: 100,
-                whitelist_ips: vec![],
+                whitelist_ips: rate_limit_whitelist.clone(),
             },
         )
         .await;
 
     // CORS configuration
-    // Read comma-separated allowed origins from env.
     // Use "*" to allow all origins (development only).
     // Production example: CORS_ALLOWED_ORIGINS=https://stellar-insights.com
-    let cors_allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:3000,http://localhost:3001".to_string());
+    let cors_allowed_origins = &settings.security.cors_allowed_origins;
 
     tracing::info!(
         "Configuring CORS with allowed origins: {}",
-        cors_allowed_origins
+        cors_allowed_origins.join(",")
     );
 
     let cors_methods = [
@@ -728,7 +992,7 @@ async fn main() -> Result<()> {
             .allow_headers(Any)
             .max_age(Duration::from_secs(3600));
 
-        if cors_allowed_origins.trim() == "*" {
+        if settings.security.allows_any_origin() {
             tracing::warn!(
                 "CORS configured to allow ALL origins (*). \
                  This is insecure and should not be used in production."
@@ -736,9 +1000,9 @@ async fn main() -> Result<()> {
             base.allow_origin(Any)
         } else {
             let origins: Vec<axum::http::HeaderValue> = cors_allowed_origins
-                .split(',')
+                .iter()
                 .filter_map(|o| {
-                    let trimmed = o.trim();
+                    let trimmed = o.as_str();
                     trimmed
                         .parse::<axum::http::HeaderValue>()
                         .map_err(|e| {
@@ -763,10 +1027,7 @@ async fn main() -> Result<()> {
 
     // Compression configuration
     // Only compress responses larger than 1KB to avoid overhead on small responses
-    let compression_min_size = std::env::var("COMPRESSION_MIN_SIZE")
-        .ok()
-        .and_then(|s| s.parse::<u16>().ok())
-        .unwrap_or(1024);
+    let compression_min_size = settings.server.compression_min_size;
 
     let compression = CompressionLayer::new()
         .gzip(true)
@@ -785,28 +1046,110 @@ async fn main() -> Result<()> {
     // Build auth router
     let auth_routes = stellar_insights_backend::api::auth::routes(auth_service.clone());
 
-    // Build cached routes (anchors list, corridors list/detail) with cache state
+    // Resolves the tenant of each request to a tenant-scoped route
+    let tenant_resolver = TenantResolver {
+        db: Arc::clone(&db),
+        multi_tenant: settings.deployment.multi_tenant,
+    };
+    if tenant_resolver.multi_tenant {
+        tracing::info!("Multi-tenant mode enabled; scoped routes require a tenant API key");
+    }
+
+    // Build cached routes (anchors list/detail, corridors list/detail) with cache state
     let cached_routes = Router::new()
         .route("/api/anchors", get(get_anchors))
         .route("/api/corridors", get(list_corridors))
         .route("/api/corridors/:corridor_key", get(get_corridor_detail))
+        .route("/api/anchors/:id", get(get_anchor))
+        .route(
+            "/api/anchors/account/:stellar_account",
+            get(get_anchor_by_account),
+        )
         .with_state(cached_state.clone())
-        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build corridor seasonality, cohort, health history and anomaly routes
+    // (tenant-scoped, history window limited by plan)
+    let corridor_history_routes = corridor_seasonality::routes(Arc::clone(&db))
+        .merge(corridor_cohorts::routes(Arc::clone(&db)))
+        .merge(corridor_health::routes(Arc::clone(&db)))
+        .merge(corridor_anomalies::routes(Arc::clone(&db), Arc::clone(&anomaly_service)))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build public corridor badge routes (embedded by anchors, so rate limited)
+    let badge_routes = badges::routes(Arc::clone(&db), Arc::clone(&cache))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build public anchor asset supply routes
+    let asset_supply_routes = asset_supply::routes(Arc::clone(&db))
+        .layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
             rate_limit_middleware,
-        )))
+        ))
+        .layer(cors.clone());
+
+    // Build public anchor transfer server performance routes
+    let sep_performance_routes = sep_performance::routes(Arc::clone(&db))
+        .layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        ))
+        .layer(cors.clone());
+
+    // Build public asset compliance event routes
+    let asset_compliance_routes = asset_compliance::routes(Arc::clone(&asset_compliance_tracker))
+        .layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        ))
         .layer(cors.clone());
 
     // Build non-cached anchor routes with app state
     let anchor_routes = Router::new()
         .route("/health", get(health_check))
         .route("/api/db/pool-metrics", get(pool_metrics))
-        .route("/api/anchors/:id", get(get_anchor))
-        .route(
-            "/api/anchors/account/:stellar_account",
-            get(get_anchor_by_account),
-        )
-        .route("/api/anchors/:id/assets", get(get_anchor_assets))
+        .route("/api/ingestion/status", get(ingestion_status))
         .route("/api/analytics/muxed", get(get_muxed_analytics))
         .with_state(app_state.clone())
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
@@ -815,6 +1158,27 @@ async fn main() -> Result<()> {
         )))
         .layer(cors.clone());
 
+    // Build tenant-scoped anchor asset listing
+    let anchor_asset_routes = Router::new()
+        .route("/api/anchors/:id/assets", get(get_anchor_assets))
+        .with_state(app_state.clone())
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
     // Build protected anchor routes (require authentication)
     let protected_anchor_routes = Router::new()
         .route("/api/anchors", axum::routing::post(create_anchor))
@@ -829,6 +1193,58 @@ async fn main() -> Result<()> {
             put(update_corridor_metrics_from_transactions),
         )
         .with_state(app_state.clone())
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build admin anchor merge routes (require authentication, tenant-scoped)
+    let anchor_merge_routes = anchor_merge::routes(Arc::clone(&db), Arc::clone(&cache_invalidation))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build background task health routes (require authentication)
+    let admin_jobs_routes = admin_jobs::routes(task_supervisor.clone())
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build database pool diagnostics routes (require authentication)
+    let admin_db_routes = admin_db::routes(Arc::clone(&db))
         .layer(
             ServiceBuilder::new()
                 .layer(middleware::from_fn(auth_middleware))
@@ -839,18 +1255,352 @@ async fn main() -> Result<()> {
         )
         .layer(cors.clone());
 
+    // Build ledger gap report routes (require authentication)
+    let admin_ingestion_routes = admin_ingestion::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build email delivery status routes (require authentication)
+    let admin_email_routes = admin_email::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build corridor metrics recompute routes (require authentication)
+    let admin_corridor_routes = admin_corridors::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build trade surveillance review routes (require authentication)
+    let admin_surveillance_routes = admin_surveillance::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build tenant administration routes (require authentication)
+    let admin_tenant_routes = admin_tenants::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build tenant usage summary routes (tenant-scoped)
+    let usage_routes = usage::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build synthetic load routes (load-test feature only; require authentication)
+    #[cfg(feature = "load-test")]
+    let load_test_routes = {
+        use stellar_insights_backend::api::load_test::{self, LoadTestState};
+        use stellar_insights_backend::tenant::TenantScope;
+
+        load_test::routes(LoadTestState {
+            db: Arc::clone(&db),
+            // Synthetic requests read the whole directory
+            corridor_routes: Router::new()
+                .route("/api/corridors/:corridor_key", get(get_corridor_detail))
+                .with_state(cached_state.clone())
+                .layer(axum::Extension(TenantScope::All)),
+            ws_state: Arc::clone(&ws_state),
+        })
+        .layer(middleware::from_fn(auth_middleware))
+        .layer(cors.clone())
+    };
+    #[cfg(not(feature = "load-test"))]
+    let load_test_routes = Router::new();
+
+    // Build top movers routes (public)
+    let movers_routes = movers::routes(Arc::clone(&movers_service))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build snapshot signature verification routes (public)
+    let snapshot_signature_routes = snapshot_signatures::routes(Arc::clone(&snapshot_service))
+        .layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        ))
+        .layer(cors.clone());
+
+    // Build anchored SLA verification routes (public)
+    let snapshot_sla_routes = snapshot_sla::routes(Arc::clone(&snapshot_service))
+        .layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        ))
+        .layer(cors.clone());
+
+    // Build tenant-scoped search routes (public) and tag management routes
+    // (require authentication)
+    let search_routes = search::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    let tag_routes = search::tag_management_routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build user preference routes (require authentication)
+    let preferences_routes = preferences::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build session management routes (require authentication)
+    let session_routes = sessions::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build digest preview routes (require authentication)
+    let digest_preview_routes = digest_preview::routes(digest_preview::DigestPreviewState {
+        db: Arc::clone(&db),
+        rpc_client: Arc::clone(&rpc_client),
+    })
+    .layer(
+        ServiceBuilder::new()
+            .layer(middleware::from_fn(auth_middleware))
+            .layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            )),
+    )
+    .layer(cors.clone());
+
+    // Build one-click unsubscribe routes (public; the signed token authorizes)
+    let notification_routes = notifications::routes(notifications::NotificationsState {
+        db: Arc::clone(&db),
+        tokens: unsubscribe_tokens.clone(),
+    })
+    .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        rate_limiter.clone(),
+        rate_limit_middleware,
+    )))
+    .layer(cors.clone());
+
+    // Build anchor callback routes (verified by anchor signature) and callback
+    // key management routes (require authentication)
+    // Ownership proofs and callback keys are always fetched fresh, so the
+    // stellar.toml client has no cache.
+    let fresh_toml = Arc::new(StellarTomlClient::new(
+        Arc::new(tokio::sync::RwLock::new(None)),
+        None,
+    )?);
+
+    let callback_state = anchor_callbacks::AnchorCallbackState {
+        db: Arc::clone(&db),
+        tracker: Arc::clone(&sep_tracker),
+        callback_host: settings.sep_tracking.callback_host.clone(),
+        toml: Arc::clone(&fresh_toml),
+    };
+    let anchor_callback_routes = anchor_callbacks::routes(callback_state.clone())
+        .layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        ))
+        .layer(cors.clone());
+
+    let callback_key_routes = anchor_callbacks::key_management_routes(callback_state)
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build anchor claim and operator routes (require authentication)
+    let anchor_claim_routes = anchor_claims::routes(anchor_claims::AnchorClaimState {
+        db: Arc::clone(&db),
+        toml: fresh_toml,
+    })
+    .layer(
+        ServiceBuilder::new()
+            .layer(middleware::from_fn(auth_middleware))
+            .layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            )),
+    )
+    .layer(cors.clone());
+
+    // Build self-reported anchor metrics routes (require an operator claim)
+    let self_reported_routes = anchor_self_reported::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build SEP-24/31 proxy routes (transactions are tracked for the
+    // authenticated user) and the tracked transaction listing
+    let sep_proxy_routes = sep24_proxy::tracked_routes(
+        settings.sep24.clone(),
+        Arc::clone(&db),
+        Arc::clone(&cache),
+    )
+    .merge(sep31_proxy::tracked_routes(
+        settings.sep31.clone(),
+        Arc::clone(&db),
+        Arc::clone(&cache),
+    ))
+    .merge(sep_transactions::routes(Arc::clone(&db)))
+    .merge(sep_auth::routes(Arc::clone(&db)))
+    .merge(sep_info_cache::routes(Arc::clone(&cache)))
+    .layer(
+        ServiceBuilder::new()
+            .layer(middleware::from_fn(auth_middleware))
+            .layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            )),
+    )
+    .layer(cors.clone());
+
+    // Build public status page routes
+    let status_routes = status::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
     // Build OAuth routes
-    let oauth_routes = oauth::routes(pool.clone());
+    let oauth_routes = oauth::routes(Arc::clone(&db));
 
     // Build webhook routes (require authentication)
     let webhook_routes = Router::new()
-        .nest("/api/webhooks", webhooks::routes(pool.clone()))
+        .nest(
+            "/api/webhooks",
+            webhooks::routes(webhooks::WebhookApiState {
+                db: pool.clone(),
+                encryption_key: settings.security.encryption_key.clone(),
+            }),
+        )
         .layer(
             ServiceBuilder::new()
                 .layer(middleware::from_fn(auth_middleware))
                 .layer(middleware::from_fn_with_state(
                     rate_limiter.clone(),
                     rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
                 )),
         )
         .layer(cors.clone());
@@ -859,21 +1609,36 @@ async fn main() -> Result<()> {
     let cache_routes = cache_stats::routes(Arc::clone(&cache));
     let metrics_routes = metrics_cached::routes(Arc::clone(&cache));
 
-    // Build RPC router
+    // Build RPC router. Each Horizon-bound route gets its own concurrency cap
+    // inside a cap for the whole group, so a burst on one cannot starve the rest.
+    let caps = ConcurrencyCaps::from(&settings.rpc);
+    let capped = |route| limit_route(route, caps.per_route, caps.retry_after_secs);
     let rpc_routes = Router::new()
         .route("/api/rpc/health", get(rpc_handlers::rpc_health_check))
+        .route(
+            "/api/rpc/endpoints",
+            get(rpc_handlers::get_horizon_endpoints),
+        )
         .route(
             "/api/rpc/ledger/latest",
-            get(rpc_handlers::get_latest_ledger),
+            capped(get(rpc_handlers::get_latest_ledger)),
         )
-        .route("/api/rpc/payments", get(rpc_handlers::get_payments))
+        .route("/api/rpc/payments", capped(get(rpc_handlers::get_payments)))
         .route(
             "/api/rpc/payments/account/:account_id",
-            get(rpc_handlers::get_account_payments),
+            capped(get(rpc_handlers::get_account_payments)),
         )
-        .route("/api/rpc/trades", get(rpc_handlers::get_trades))
-        .route("/api/rpc/orderbook", get(rpc_handlers::get_order_book))
-        .with_state(rpc_client)
+        .route("/api/rpc/trades", capped(get(rpc_handlers::get_trades)))
+        .route(
+            "/api/rpc/orderbook",
+            limit_route(
+                get(rpc_handlers::get_order_book),
+                caps.orderbook,
+                caps.retry_after_secs,
+            ),
+        )
+        .with_state(rpc_client);
+    let rpc_routes = limit_group(rpc_routes, caps.group, caps.retry_after_secs)
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
             rate_limit_middleware,
@@ -884,7 +1649,10 @@ async fn main() -> Result<()> {
     let fee_bump_routes = Router::new()
         .nest(
             "/api/fee-bumps",
-            fee_bump::routes(Arc::clone(&fee_bump_tracker)),
+            fee_bump::routes(
+                Arc::clone(&fee_bump_tracker),
+                Arc::clone(&account_screener),
+            ),
         )
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
@@ -896,7 +1664,10 @@ async fn main() -> Result<()> {
     let account_merge_routes = Router::new()
         .nest(
             "/api/account-merges",
-            account_merges::routes(Arc::clone(&account_merge_detector)),
+            account_merges::routes(
+                Arc::clone(&account_merge_detector),
+                Arc::clone(&account_screener),
+            ),
         )
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
@@ -904,16 +1675,27 @@ async fn main() -> Result<()> {
         )))
         .layer(cors.clone());
 
-    // Build liquidity pool routes
+    // Build liquidity pool routes; history depth follows the tenant's plan
     let lp_routes = Router::new()
         .nest(
             "/api/liquidity-pools",
             liquidity_pools::routes(Arc::clone(&lp_analyzer)),
         )
-        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
-            rate_limiter.clone(),
-            rate_limit_middleware,
-        )))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
         .layer(cors.clone());
 
     // Build price feed routes
@@ -940,28 +1722,54 @@ async fn main() -> Result<()> {
         )))
         .layer(cors.clone());
 
+    // Build corridor simulation routes
+    let corridor_simulator = Arc::new(CorridorSimulator::new(
+        Arc::clone(&rpc_client),
+        Arc::clone(&price_feed),
+    ));
+    let corridor_simulation_routes = corridor_simulation::routes(corridor_simulator)
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
     // Build network routes
     let network_routes = Router::new()
         .nest(
             "/api/network",
             stellar_insights_backend::api::network::routes(),
         )
+        .merge(stellar_insights_backend::api::network::congestion_routes(
+            Arc::clone(&congestion_service),
+        ))
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
             rate_limit_middleware,
         )))
         .layer(cors.clone());
 
-    // Build trustline routes
+    // Build trustline routes; history depth follows the tenant's plan
     let trustline_routes = Router::new()
         .nest(
             "/api/trustlines",
             stellar_insights_backend::api::trustlines::routes(Arc::clone(&trustline_analyzer)),
         )
-        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
-            rate_limiter.clone(),
-            rate_limit_middleware,
-        )))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    tenant_resolver.clone(),
+                    tenant_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&db),
+                    plan_middleware,
+                )),
+        )
         .layer(cors.clone());
 
     // Build achievements / quests routes
@@ -1041,7 +1849,12 @@ async fn main() -> Result<()> {
     // Build WebSocket routes
     let ws_routes = Router::new()
         .route("/ws", get(stellar_insights_backend::websocket::ws_handler))
+        .route("/sse/stream", get(stellar_insights_backend::sse::sse_handler))
         .with_state(Arc::clone(&ws_state))
+        .layer(middleware::from_fn_with_state(
+            tenant_resolver.clone(),
+            tenant_middleware,
+        ))
         .layer(cors.clone());
 
     let alert_ws_routes = Router::new()
@@ -1059,13 +1872,45 @@ async fn main() -> Result<()> {
         .merge(webhook_routes)
         .merge(cached_routes)
         .merge(anchor_routes)
+        .merge(anchor_asset_routes)
         .merge(protected_anchor_routes)
+        .merge(anchor_merge_routes)
+        .merge(admin_jobs_routes)
+        .merge(admin_db_routes)
+        .merge(admin_ingestion_routes)
+        .merge(admin_email_routes)
+        .merge(admin_corridor_routes)
+        .merge(admin_surveillance_routes)
+        .merge(admin_tenant_routes)
+        .merge(usage_routes)
+        .merge(load_test_routes)
+        .merge(search_routes)
+        .merge(movers_routes)
+        .merge(snapshot_signature_routes)
+        .merge(snapshot_sla_routes)
+        .merge(tag_routes)
+        .merge(preferences_routes)
+        .merge(session_routes)
+        .merge(digest_preview_routes)
+        .merge(notification_routes)
+        .merge(corridor_history_routes)
+        .merge(badge_routes)
+        .merge(asset_supply_routes)
+        .merge(sep_performance_routes)
+        .merge(asset_compliance_routes)
+        .merge(status_routes)
+        .merge(anchor_callback_routes)
+        .merge(callback_key_routes)
+        .merge(anchor_claim_routes)
+        .merge(self_reported_routes)
+        .merge(sep_proxy_routes)
         .merge(rpc_routes)
         .merge(fee_bump_routes)
         .merge(account_merge_routes)
         .merge(lp_routes)
         .merge(price_routes)
         .merge(cost_calculator_routes)
+        .merge(corridor_simulation_routes)
         .merge(trustline_routes)
         .merge(achievements_routes)
         .merge(governance_routes)
@@ -1078,20 +1923,32 @@ async fn main() -> Result<()> {
         .merge(api_key_routes)
         .merge(ws_routes)
         .merge(alert_ws_routes)
-
+        // Innermost, so timed-out requests are still logged, counted and localized
+        .layer(middleware::from_fn_with_state(
+            TimeoutBudgets::from(&settings.timeouts),
+            timeout_middleware,
+        ))
         .layer(middleware::from_fn_with_state(
             db.clone(),
             stellar_insights_backend::api_analytics_middleware::api_analytics_middleware,
         ))
-        .layer(TraceLayer::new_for_http())
+        .layer(TraceLayer::new_for_http().make_span_with(obs_tracing::make_request_span::<axum::body::Body>))
+        // Mark credential headers sensitive so they are redacted from traces
+        .layer(SetSensitiveRequestHeadersLayer::new([
+            axum::http::header::AUTHORIZATION,
+            axum::http::HeaderName::from_static(sep_auth::ANCHOR_AUTH_HEADER),
+        ]))
         .layer(middleware::from_fn(obs_metrics::http_metrics_middleware))
+        .layer(middleware::from_fn(stellar_insights_backend::i18n::localize_errors_middleware))
+        .layer(middleware::from_fn_with_state(
+            db.clone(),
+            stellar_insights_backend::preferences::preferences_middleware,
+        ))
         .layer(middleware::from_fn(request_id_middleware))
         .layer(compression); // Apply compression to all routes
 
     // Start server
-    let host = std::env::var("SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
-    let port = std::env::var("SERVER_PORT").unwrap_or_else(|_| "8080".to_string());
-    let addr = format!("{}:{}", host, port);
+    let addr = settings.server.addr();
 
     tracing::info!("Server starting on {}", addr);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
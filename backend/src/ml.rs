@@ -2,6 +2,9 @@ use crate::database::Database;
 use chrono::{DateTime, Datelike, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 
+pub mod anomaly;
+pub mod isolation_forest;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionFeatures {
     pub corridor_hash: f32,
@@ -0,0 +1,392 @@
+//! Anomaly detection over corridor metrics.
+//!
+//! Each hour of a corridor's metrics is reduced to [`Features`] (log volume
+//! and success rate) and scored by an [`AnomalyDetector`] trained on the
+//! corridor's last [`TRAINING_DAYS`] of history. `Settings::ml`
+//! (`ML_ANOMALY_MODEL`) selects the detector:
+//!
+//! - `robust_z` (default): distance from the corridor's median, in median
+//!   absolute deviations (see [`RobustZScore`])
+//! - `isolation_forest`: how few random splits isolate the hour from the
+//!   corridor's history (see [`IsolationForest`])
+//!
+//! The ml-anomaly-retrain job retrains every corridor weekly and stores the
+//! artifacts in `anomaly_models`. [`AnomalyService::anomalies`] scores recent
+//! hours against the stored model and merges consecutive anomalous hours into
+//! intervals.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+use super::isolation_forest::IsolationForest;
+use crate::database::Database;
+use crate::db::anomaly_models::CorridorHour;
+
+/// Days of history each model is trained on
+pub const TRAINING_DAYS: i64 = 28;
+
+/// Hours of metrics a corridor needs to be trained
+pub const MIN_TRAINING_HOURS: usize = 48;
+
+pub const FEATURE_COUNT: usize = 2;
+
+/// `ln(1 + volume_usd)` and success rate (0-1) of one hour
+pub type Features = [f64; FEATURE_COUNT];
+
+pub fn features(hour: &CorridorHour) -> Features {
+    let success_rate = if hour.total_transactions > 0 {
+        hour.successful_transactions as f64 / hour.total_transactions as f64
+    } else {
+        hour.success_rate / 100.0
+    };
+    [hour.volume_usd.max(0.0).ln_1p(), success_rate]
+}
+
+/// A way of scoring corridor hours for anomalies
+pub trait AnomalyDetector: Send + Sync {
+    /// Stored with the models it trains
+    fn name(&self) -> &'static str;
+
+    /// Score at or above which an hour is anomalous
+    fn threshold(&self) -> f64;
+
+    /// Train on a corridor's history, returning the model's JSON artifact
+    fn train(&self, history: &[Features]) -> Result<String>;
+
+    /// Score of each of `hours` under a trained artifact
+    fn score(&self, artifact: &str, hours: &[Features]) -> Result<Vec<f64>>;
+}
+
+/// Detector selected with `ML_ANOMALY_MODEL`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyModel {
+    #[default]
+    RobustZ,
+    IsolationForest,
+}
+
+impl AnomalyModel {
+    pub fn detector(self) -> Arc<dyn AnomalyDetector> {
+        match self {
+            Self::RobustZ => Arc::new(RobustZScore),
+            Self::IsolationForest => Arc::new(IsolationForest),
+        }
+    }
+}
+
+impl FromStr for AnomalyModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "robust_z" => Ok(Self::RobustZ),
+            "isolation_forest" => Ok(Self::IsolationForest),
+            other => Err(format!(
+                "unknown anomaly model '{}', expected robust_z or isolation_forest",
+                other
+            )),
+        }
+    }
+}
+
+/// Smallest spread per feature, so a corridor whose history never varied
+/// (say, always fully successful) is not flagged for the slightest change
+const MIN_SPREAD: Features = [0.1, 0.01];
+
+/// Scale making the median absolute deviation of normal data its standard
+/// deviation
+const MAD_SCALE: f64 = 1.4826;
+
+/// Scores an hour by its largest per-feature distance from the corridor's
+/// median, in scaled median absolute deviations
+pub struct RobustZScore;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RobustZArtifact {
+    medians: Features,
+    spreads: Features,
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+impl AnomalyDetector for RobustZScore {
+    fn name(&self) -> &'static str {
+        "robust_z"
+    }
+
+    fn threshold(&self) -> f64 {
+        3.5
+    }
+
+    fn train(&self, history: &[Features]) -> Result<String> {
+        if history.is_empty() {
+            bail!("No history to train on");
+        }
+        let mut artifact = RobustZArtifact {
+            medians: [0.0; FEATURE_COUNT],
+            spreads: [0.0; FEATURE_COUNT],
+        };
+        for feature in 0..FEATURE_COUNT {
+            let mut values: Vec<f64> = history.iter().map(|h| h[feature]).collect();
+            let med = median(&mut values);
+            let mut deviations: Vec<f64> = values.iter().map(|v| (v - med).abs()).collect();
+            artifact.medians[feature] = med;
+            artifact.spreads[feature] =
+                (median(&mut deviations) * MAD_SCALE).max(MIN_SPREAD[feature]);
+        }
+        Ok(serde_json::to_string(&artifact)?)
+    }
+
+    fn score(&self, artifact: &str, hours: &[Features]) -> Result<Vec<f64>> {
+        let artifact: RobustZArtifact =
+            serde_json::from_str(artifact).context("Invalid robust_z artifact")?;
+        Ok(hours
+            .iter()
+            .map(|hour| {
+                (0..FEATURE_COUNT)
+                    .map(|f| (hour[f] - artifact.medians[f]).abs() / artifact.spreads[f])
+                    .fold(0.0, f64::max)
+            })
+            .collect())
+    }
+}
+
+/// Consecutive anomalous hours of a corridor
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnomalyInterval {
+    /// First hour
+    pub start: DateTime<Utc>,
+    /// End of the last hour
+    pub end: DateTime<Utc>,
+    /// Highest score of its hours
+    pub peak_score: f64,
+    pub volume_usd: f64,
+    /// Share of successful payments, 0-1; `None` without payments
+    pub success_rate: Option<f64>,
+}
+
+/// Merge consecutive hours scoring at least `threshold` into intervals.
+/// `hours` are oldest first with `scores` in the same order.
+pub fn flagged_intervals(
+    hours: &[CorridorHour],
+    scores: &[f64],
+    threshold: f64,
+) -> Vec<AnomalyInterval> {
+    let mut intervals: Vec<(AnomalyInterval, i64, i64)> = Vec::new();
+    for (hour, &score) in hours.iter().zip(scores) {
+        if score < threshold {
+            continue;
+        }
+        let Ok(start) = DateTime::parse_from_rfc3339(&hour.hour_bucket) else {
+            continue;
+        };
+        let start = start.with_timezone(&Utc);
+        match intervals.last_mut() {
+            Some((interval, total, successful)) if interval.end == start => {
+                interval.end = start + Duration::hours(1);
+                interval.peak_score = interval.peak_score.max(score);
+                interval.volume_usd += hour.volume_usd;
+                *total += hour.total_transactions;
+                *successful += hour.successful_transactions;
+            }
+            _ => intervals.push((
+                AnomalyInterval {
+                    start,
+                    end: start + Duration::hours(1),
+                    peak_score: score,
+                    volume_usd: hour.volume_usd,
+                    success_rate: None,
+                },
+                hour.total_transactions,
+                hour.successful_transactions,
+            )),
+        }
+    }
+    intervals
+        .into_iter()
+        .map(|(mut interval, total, successful)| {
+            interval.success_rate = (total > 0).then(|| successful as f64 / total as f64);
+            interval
+        })
+        .collect()
+}
+
+/// A corridor's anomalous intervals under its latest model
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorridorAnomalies {
+    pub model: String,
+    pub model_version: i64,
+    pub trained_at: String,
+    pub threshold: f64,
+    /// Oldest first
+    pub intervals: Vec<AnomalyInterval>,
+}
+
+pub struct AnomalyService {
+    db: Arc<Database>,
+    detector: Arc<dyn AnomalyDetector>,
+}
+
+impl AnomalyService {
+    pub fn new(db: Arc<Database>, detector: Arc<dyn AnomalyDetector>) -> Self {
+        Self { db, detector }
+    }
+
+    /// Service using the detector of `model`
+    pub fn with_model(db: Arc<Database>, model: AnomalyModel) -> Self {
+        Self::new(db, model.detector())
+    }
+
+    /// Retrain every corridor with at least [`MIN_TRAINING_HOURS`] of metrics
+    /// in the last [`TRAINING_DAYS`]. Returns the number of corridors trained.
+    pub async fn retrain(&self) -> Result<usize> {
+        let now = Utc::now();
+        let hours = self
+            .db
+            .anomaly_models()
+            .hours_since(now - Duration::days(TRAINING_DAYS))
+            .await?;
+
+        let mut trained = 0;
+        for corridor in hours.chunk_by(|a, b| a.corridor_key == b.corridor_key) {
+            if corridor.len() < MIN_TRAINING_HOURS {
+                continue;
+            }
+            let history: Vec<Features> = corridor.iter().map(features).collect();
+            let artifact = self.detector.train(&history)?;
+            self.db
+                .anomaly_models()
+                .save(
+                    &corridor[0].corridor_key,
+                    self.detector.name(),
+                    now,
+                    history.len() as i64,
+                    &artifact,
+                )
+                .await?;
+            trained += 1;
+        }
+        info!(
+            "Trained {} anomaly models for {} corridors",
+            self.detector.name(),
+            trained
+        );
+        Ok(trained)
+    }
+
+    /// The corridor's anomalous intervals from `since`. `None` if no model
+    /// has been trained for it.
+    pub async fn anomalies(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<CorridorAnomalies>> {
+        let models = self.db.anomaly_models();
+        let Some(model) = models.get(corridor_key, self.detector.name()).await? else {
+            return Ok(None);
+        };
+        let hours = models.corridor_hours(corridor_key, since).await?;
+        let scores = self.detector.score(
+            &model.artifact,
+            &hours.iter().map(features).collect::<Vec<_>>(),
+        )?;
+
+        let threshold = self.detector.threshold();
+        Ok(Some(CorridorAnomalies {
+            model: model.model,
+            model_version: model.version,
+            trained_at: model.trained_at,
+            threshold,
+            intervals: flagged_intervals(&hours, &scores, threshold),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hour(hour_bucket: &str, volume_usd: f64, total: i64, successful: i64) -> CorridorHour {
+        CorridorHour {
+            corridor_key: "USDC:GA->XLM:native".to_string(),
+            hour_bucket: hour_bucket.to_string(),
+            total_transactions: total,
+            successful_transactions: successful,
+            success_rate: 0.0,
+            volume_usd,
+        }
+    }
+
+    /// Volume around 1000 with 95-100% success
+    fn normal_history() -> Vec<Features> {
+        (0..100)
+            .map(|i| {
+                [
+                    (1000.0 + (i % 10) as f64 * 50.0_f64).ln_1p(),
+                    0.95 + (i % 6) as f64 * 0.01,
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_robust_z_flags_volume_spikes_and_failures() {
+        let detector = RobustZScore;
+        let artifact = detector.train(&normal_history()).unwrap();
+        let scores = detector
+            .score(
+                &artifact,
+                &[
+                    [1200.0_f64.ln_1p(), 0.97],
+                    [50_000.0_f64.ln_1p(), 0.97],
+                    [1200.0_f64.ln_1p(), 0.40],
+                ],
+            )
+            .unwrap();
+        assert!(scores[0] < detector.threshold());
+        assert!(scores[1] >= detector.threshold());
+        assert!(scores[2] >= detector.threshold());
+    }
+
+    #[test]
+    fn test_robust_z_spread_has_a_floor() {
+        let detector = RobustZScore;
+        let artifact = detector.train(&[[7.0, 1.0]; 60]).unwrap();
+        let scores = detector.score(&artifact, &[[7.0, 0.995]]).unwrap();
+        assert!(scores[0] < detector.threshold());
+    }
+
+    #[test]
+    fn test_flagged_intervals_merge_consecutive_hours() {
+        let hours = vec![
+            hour("2024-01-01T00:00:00+00:00", 100.0, 10, 10),
+            hour("2024-01-01T01:00:00+00:00", 900.0, 10, 5),
+            hour("2024-01-01T02:00:00+00:00", 800.0, 10, 7),
+            hour("2024-01-01T03:00:00+00:00", 100.0, 10, 10),
+            hour("2024-01-01T05:00:00+00:00", 700.0, 0, 0),
+        ];
+        let intervals = flagged_intervals(&hours, &[1.0, 5.0, 4.0, 1.0, 6.0], 3.5);
+
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0].start.to_rfc3339(), "2024-01-01T01:00:00+00:00");
+        assert_eq!(intervals[0].end.to_rfc3339(), "2024-01-01T03:00:00+00:00");
+        assert_eq!(intervals[0].peak_score, 5.0);
+        assert_eq!(intervals[0].volume_usd, 1700.0);
+        assert_eq!(intervals[0].success_rate, Some(0.6));
+        assert_eq!(intervals[1].end.to_rfc3339(), "2024-01-01T06:00:00+00:00");
+        assert_eq!(intervals[1].success_rate, None);
+    }
+}
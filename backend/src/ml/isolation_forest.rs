@@ -0,0 +1,213 @@
+//! Isolation forest anomaly detection.
+//!
+//! Each tree splits a random sample of the corridor's history on random
+//! features at random values until every hour is isolated. Anomalous hours
+//! sit far from the rest and are isolated after few splits, so the score,
+//! `2^(-average path length / expected path length)`, rises above the 0.5 or
+//! so of ordinary hours. Trees are grown from a
+//! fixed seed, so retraining on the same history gives the same model.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::anomaly::{AnomalyDetector, Features, FEATURE_COUNT};
+
+/// Trees in each forest
+pub const TREES: usize = 100;
+
+/// Hours of history each tree is grown from
+pub const SAMPLE_SIZE: usize = 256;
+
+const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+pub struct IsolationForest;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Node {
+    Split {
+        feature: usize,
+        value: f64,
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+    Leaf {
+        size: usize,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Forest {
+    sample_size: usize,
+    trees: Vec<Node>,
+}
+
+/// xorshift64*, enough randomness for choosing splits
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `0..n`
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Average path length of an unsuccessful search in a binary search tree of
+/// `n` points, which normalises isolation depths
+fn expected_path_length(n: usize) -> f64 {
+    if n <= 1 {
+        return 0.0;
+    }
+    let n = n as f64;
+    2.0 * ((n - 1.0).ln() + EULER_GAMMA) - 2.0 * (n - 1.0) / n
+}
+
+fn grow(points: &[Features], depth: usize, max_depth: usize, rng: &mut Rng) -> Node {
+    if points.len() <= 1 || depth >= max_depth {
+        return Node::Leaf { size: points.len() };
+    }
+    // Only features that still vary can split
+    let ranges: Vec<(usize, f64, f64)> = (0..FEATURE_COUNT)
+        .filter_map(|feature| {
+            let (min, max) = points
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                    (lo.min(p[feature]), hi.max(p[feature]))
+                });
+            (max > min).then_some((feature, min, max))
+        })
+        .collect();
+    if ranges.is_empty() {
+        return Node::Leaf { size: points.len() };
+    }
+
+    let (feature, min, max) = ranges[rng.below(ranges.len())];
+    let value = min + rng.next_f64() * (max - min);
+    let (left, right): (Vec<Features>, Vec<Features>) =
+        points.iter().partition(|p| p[feature] < value);
+    Node::Split {
+        feature,
+        value,
+        left: Box::new(grow(&left, depth + 1, max_depth, rng)),
+        right: Box::new(grow(&right, depth + 1, max_depth, rng)),
+    }
+}
+
+fn path_length(node: &Node, point: &Features, depth: usize) -> f64 {
+    match node {
+        Node::Leaf { size } => depth as f64 + expected_path_length(*size),
+        Node::Split {
+            feature,
+            value,
+            left,
+            right,
+        } => {
+            let next = if point[*feature] < *value {
+                left
+            } else {
+                right
+            };
+            path_length(next, point, depth + 1)
+        }
+    }
+}
+
+impl AnomalyDetector for IsolationForest {
+    fn name(&self) -> &'static str {
+        "isolation_forest"
+    }
+
+    fn threshold(&self) -> f64 {
+        0.6
+    }
+
+    fn train(&self, history: &[Features]) -> Result<String> {
+        if history.is_empty() {
+            bail!("No history to train on");
+        }
+        let sample_size = history.len().min(SAMPLE_SIZE);
+        let max_depth = (sample_size as f64).log2().ceil() as usize;
+        let mut rng = Rng(SEED);
+        let mut indices: Vec<usize> = (0..history.len()).collect();
+
+        let trees = (0..TREES)
+            .map(|_| {
+                // Partial Fisher-Yates: the first `sample_size` indices are the sample
+                for i in 0..sample_size {
+                    let j = i + rng.below(indices.len() - i);
+                    indices.swap(i, j);
+                }
+                let sample: Vec<Features> =
+                    indices[..sample_size].iter().map(|&i| history[i]).collect();
+                grow(&sample, 0, max_depth, &mut rng)
+            })
+            .collect();
+
+        Ok(serde_json::to_string(&Forest { sample_size, trees })?)
+    }
+
+    fn score(&self, artifact: &str, hours: &[Features]) -> Result<Vec<f64>> {
+        let forest: Forest =
+            serde_json::from_str(artifact).context("Invalid isolation_forest artifact")?;
+        let normaliser = expected_path_length(forest.sample_size);
+        if forest.trees.is_empty() || normaliser == 0.0 {
+            return Ok(vec![0.0; hours.len()]);
+        }
+        Ok(hours
+            .iter()
+            .map(|hour| {
+                let average = forest
+                    .trees
+                    .iter()
+                    .map(|tree| path_length(tree, hour, 0))
+                    .sum::<f64>()
+                    / forest.trees.len() as f64;
+                2f64.powf(-average / normaliser)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outliers_score_above_threshold() {
+        let history: Vec<Features> = (0..300)
+            .map(|i| {
+                [
+                    (1000.0 + (i % 17) as f64 * 30.0_f64).ln_1p(),
+                    0.95 + (i % 6) as f64 * 0.01,
+                ]
+            })
+            .collect();
+        let detector = IsolationForest;
+        let artifact = detector.train(&history).unwrap();
+        // Same seed, same forest
+        assert_eq!(artifact, detector.train(&history).unwrap());
+
+        let scores = detector
+            .score(
+                &artifact,
+                &[[1200.0_f64.ln_1p(), 0.97], [80_000.0_f64.ln_1p(), 0.30]],
+            )
+            .unwrap();
+        assert!(scores[0] < detector.threshold(), "{:?}", scores);
+        assert!(scores[1] >= detector.threshold(), "{:?}", scores);
+    }
+}
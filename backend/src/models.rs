@@ -19,6 +19,97 @@ impl Default for SortBy {
     }
 }
 
+/// Where a metric value came from, from most to least trusted:
+/// `on_chain`, `horizon`, `derived`, `self_reported`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum MetricSource {
+    /// Read from ledgers or contract events
+    OnChain,
+    /// Reported by the Horizon API
+    Horizon,
+    /// Computed by us from other metrics
+    #[default]
+    Derived,
+    /// Submitted by an anchor operator and not independently verified
+    SelfReported,
+}
+
+impl MetricSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OnChain => "on_chain",
+            Self::Horizon => "horizon",
+            Self::Derived => "derived",
+            Self::SelfReported => "self_reported",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "on_chain" => Some(Self::OnChain),
+            "horizon" => Some(Self::Horizon),
+            "derived" => Some(Self::Derived),
+            "self_reported" => Some(Self::SelfReported),
+            _ => None,
+        }
+    }
+
+    fn trust(&self) -> u8 {
+        match self {
+            Self::OnChain => 3,
+            Self::Horizon => 2,
+            Self::Derived => 1,
+            Self::SelfReported => 0,
+        }
+    }
+
+    /// Whether this source is at least as trusted as `min`
+    pub fn meets(&self, min: MetricSource) -> bool {
+        self.trust() >= min.trust()
+    }
+
+    /// The less trusted of two sources; an aggregate is only as trustworthy
+    /// as its weakest input
+    pub fn least_trusted(self, other: MetricSource) -> MetricSource {
+        if other.trust() < self.trust() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// SQL expression giving the trust rank (0-3) of a source `column`
+    pub fn trust_rank_sql(column: &str) -> String {
+        format!(
+            "(CASE {} WHEN 'on_chain' THEN 3 WHEN 'horizon' THEN 2 WHEN 'derived' THEN 1 ELSE 0 END)",
+            column
+        )
+    }
+
+    /// SQL expression mapping a trust rank expression back to a source name
+    pub fn from_trust_rank_sql(rank: &str) -> String {
+        format!(
+            "(CASE {} WHEN 3 THEN 'on_chain' WHEN 2 THEN 'horizon' WHEN 1 THEN 'derived' ELSE 'self_reported' END)",
+            rank
+        )
+    }
+}
+
+/// `?min_source=` filter accepted by metric endpoints
+#[derive(Debug, Default, Deserialize)]
+pub struct SourceFilter {
+    /// Drop rows less trusted than this source
+    pub min_source: Option<MetricSource>,
+}
+
+impl SourceFilter {
+    pub fn allows(&self, source: MetricSource) -> bool {
+        self.min_source.map_or(true, |min| source.meets(min))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Anchor {
     pub id: String,
@@ -34,6 +125,10 @@ pub struct Anchor {
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Incremented by every metrics update; see `If-Match` on `PUT /api/anchors/:id/metrics`
+    #[sqlx(default)]
+    #[serde(default)]
+    pub version: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -62,6 +157,9 @@ pub struct AnchorMetricsHistory {
     pub avg_settlement_time_ms: Option<i32>,
     pub volume_usd: Option<f64>,
     pub created_at: DateTime<Utc>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +221,9 @@ pub struct AnchorDetailResponse {
     pub anchor: Anchor,
     pub assets: Vec<Asset>,
     pub metrics_history: Vec<AnchorMetricsHistory>,
+    /// Off-chain stats reported by the anchor's operators, not verified by us
+    #[serde(default)]
+    pub self_reported: Vec<crate::db::self_reported::SelfReportedMetrics>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,6 +244,9 @@ pub struct CorridorRecord {
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub version: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -154,6 +258,9 @@ pub struct MetricRecord {
     pub entity_type: Option<String>,
     pub timestamp: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -409,11 +516,19 @@ pub struct ApiUsageStat {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiAnalyticsOverview {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// Timezone used for `from_local`, `to_local` and bucket labels
+    pub timezone: String,
+    pub from_local: String,
+    pub to_local: String,
+    pub granularity: String,
     pub total_requests: i64,
     pub avg_response_time_ms: f64,
     pub error_rate: f64,
     pub top_endpoints: Vec<EndpointStat>,
     pub status_distribution: Vec<StatusStat>,
+    pub requests_over_time: Vec<RequestBucketStat>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -429,3 +544,10 @@ pub struct StatusStat {
     pub status_code: i32,
     pub count: i64,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RequestBucketStat {
+    pub bucket: String,
+    pub count: i64,
+    pub error_count: i64,
+}
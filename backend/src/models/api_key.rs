@@ -15,6 +15,9 @@ pub struct ApiKey {
     pub last_used_at: Option<String>,
     pub expires_at: Option<String>,
     pub revoked_at: Option<String>,
+    /// Tenant the key's requests are scoped to in multi-tenant mode
+    #[sqlx(default)]
+    pub tenant_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +32,8 @@ pub struct ApiKeyInfo {
     pub last_used_at: Option<String>,
     pub expires_at: Option<String>,
     pub revoked_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
 }
 
 impl From<ApiKey> for ApiKeyInfo {
@@ -44,6 +49,7 @@ impl From<ApiKey> for ApiKeyInfo {
             last_used_at: key.last_used_at,
             expires_at: key.expires_at,
             revoked_at: key.revoked_at,
+            tenant_id: key.tenant_id,
         }
     }
 }
@@ -2,48 +2,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::FromRow)]
-pub struct Corridor {
-    pub asset_a_code: String,
-    pub asset_a_issuer: String,
-    pub asset_b_code: String,
-    pub asset_b_issuer: String,
-}
+use crate::models::MetricSource;
 
-impl Corridor {
-    pub fn new(
-        asset_a_code: String,
-        asset_a_issuer: String,
-        asset_b_code: String,
-        asset_b_issuer: String,
-    ) -> Self {
-        let mut corridor = Corridor {
-            asset_a_code,
-            asset_a_issuer,
-            asset_b_code,
-            asset_b_issuer,
-        };
-        corridor.normalize_ordering();
-        corridor
-    }
-
-    fn normalize_ordering(&mut self) {
-        let asset_a_key = format!("{}:{}", self.asset_a_code, self.asset_a_issuer);
-        let asset_b_key = format!("{}:{}", self.asset_b_code, self.asset_b_issuer);
-
-        if asset_a_key > asset_b_key {
-            std::mem::swap(&mut self.asset_a_code, &mut self.asset_b_code);
-            std::mem::swap(&mut self.asset_a_issuer, &mut self.asset_b_issuer);
-        }
-    }
-
-    pub fn to_string_key(&self) -> String {
-        format!(
-            "{}:{}->{}:{}",
-            self.asset_a_code, self.asset_a_issuer, self.asset_b_code, self.asset_b_issuer
-        )
-    }
-}
+pub use stellar_insights_core::corridor::{Corridor, CorridorKey, CorridorKeyError};
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct CorridorMetrics {
@@ -67,6 +28,9 @@ pub struct CorridorMetrics {
     pub liquidity_depth_usd: f64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -91,6 +55,8 @@ pub struct CorridorAnalytics {
     pub successful_transactions: i64,
     pub failed_transactions: i64,
     pub volume_usd: f64,
+    #[serde(default)]
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +73,8 @@ pub struct PaymentRecord {
     pub submission_time: Option<DateTime<Utc>>,
     /// Time when the transaction was confirmed
     pub confirmation_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub source: MetricSource,
 }
 
 impl PaymentRecord {
@@ -151,57 +119,6 @@ pub fn compute_median(values: &mut [i64]) -> Option<i64> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_corridor_normalization() {
-        let corridor1 = Corridor::new(
-            "USDC".to_string(),
-            "issuer1".to_string(),
-            "EURC".to_string(),
-            "issuer2".to_string(),
-        );
-
-        let corridor2 = Corridor::new(
-            "EURC".to_string(),
-            "issuer2".to_string(),
-            "USDC".to_string(),
-            "issuer1".to_string(),
-        );
-
-        assert_eq!(corridor1, corridor2);
-        assert_eq!(corridor1.asset_a_code, "EURC");
-        assert_eq!(corridor1.asset_b_code, "USDC");
-    }
-
-    #[test]
-    fn test_corridor_same_asset_order() {
-        let corridor = Corridor::new(
-            "USDC".to_string(),
-            "issuer1".to_string(),
-            "USDC".to_string(),
-            "issuer2".to_string(),
-        );
-
-        assert_eq!(corridor.asset_a_code, "USDC");
-        assert_eq!(corridor.asset_b_code, "USDC");
-        assert_eq!(corridor.asset_a_issuer, "issuer1");
-        assert_eq!(corridor.asset_b_issuer, "issuer2");
-    }
-
-    #[test]
-    fn test_corridor_to_string_key() {
-        let corridor = Corridor::new(
-            "USDC".to_string(),
-            "issuer1".to_string(),
-            "EURC".to_string(),
-            "issuer2".to_string(),
-        );
-
-        let key = corridor.to_string_key();
-        assert!(key.contains("EURC:issuer2"));
-        assert!(key.contains("USDC:issuer1"));
-        assert!(key.contains("->"));
-    }
-
     #[test]
     fn test_payment_record_get_corridor() {
         let payment = PaymentRecord {
@@ -215,6 +132,7 @@ mod tests {
             timestamp: Utc::now(),
             submission_time: None,
             confirmation_time: None,
+            source: MetricSource::Horizon,
         };
 
         let corridor = payment.get_corridor();
@@ -238,6 +156,7 @@ mod tests {
             timestamp: now,
             submission_time: Some(submitted),
             confirmation_time: Some(now),
+            source: MetricSource::Horizon,
         };
 
         assert_eq!(payment.settlement_latency_ms(), Some(1500));
@@ -256,6 +175,7 @@ mod tests {
             timestamp: Utc::now(),
             submission_time: None,
             confirmation_time: None,
+            source: MetricSource::Horizon,
         };
 
         assert_eq!(payment.settlement_latency_ms(), None);
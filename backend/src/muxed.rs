@@ -123,6 +123,57 @@ pub fn normalize_account_input(addr: &str) -> Option<&str> {
     None
 }
 
+/// Version byte of an ed25519 public key (G-address) as it appears in the decoded strkey
+const ACCOUNT_ID_VERSION_BYTE: u8 = 6 << 3;
+
+/// Version byte of an ed25519 secret seed (S-address) as it appears in the decoded strkey
+const SECRET_SEED_VERSION_BYTE: u8 = 18 << 3;
+
+/// Decode a 56-character ed25519 strkey with the given prefix and version byte.
+/// Returns None if the version byte or (little-endian) checksum does not match.
+fn decode_ed25519_strkey(key: &str, prefix: char, version: u8) -> Option<[u8; 32]> {
+    let key = key.trim();
+    if !key.starts_with(prefix) || key.len() != G_ADDRESS_LEN {
+        return None;
+    }
+
+    let decoded = BASE32.decode(key.as_bytes()).ok()?;
+    if decoded.len() != 35 || decoded[0] != version {
+        return None;
+    }
+    let checksum = u16::from_le_bytes([decoded[33], decoded[34]]);
+    if crc16(&decoded[..33]) != checksum {
+        return None;
+    }
+
+    decoded[1..33].try_into().ok()
+}
+
+fn encode_ed25519_strkey(key: &[u8; 32], version: u8) -> String {
+    let mut payload = [0u8; 35];
+    payload[0] = version;
+    payload[1..33].copy_from_slice(key);
+    let checksum = crc16(&payload[..33]).to_le_bytes();
+    payload[33..].copy_from_slice(&checksum);
+    BASE32.encode(&payload)
+}
+
+/// Decode a G-address into its raw 32-byte ed25519 public key.
+/// Returns None if the version byte or (little-endian) checksum does not match.
+pub fn decode_account_id(addr: &str) -> Option<[u8; 32]> {
+    decode_ed25519_strkey(addr, 'G', ACCOUNT_ID_VERSION_BYTE)
+}
+
+/// Encode a raw 32-byte ed25519 public key as a G-address
+pub fn encode_account_id(key: &[u8; 32]) -> String {
+    encode_ed25519_strkey(key, ACCOUNT_ID_VERSION_BYTE)
+}
+
+/// Decode an S-address secret seed into its raw 32-byte ed25519 seed
+pub fn decode_secret_seed(seed: &str) -> Option<[u8; 32]> {
+    decode_ed25519_strkey(seed, 'S', SECRET_SEED_VERSION_BYTE)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +217,26 @@ mod tests {
         // Too short M string
         assert!(parse_muxed_address("M").is_none());
     }
+
+    #[test]
+    fn test_decode_account_id_roundtrip() {
+        let g = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+        let key = decode_account_id(g).expect("valid G-address");
+        assert_eq!(encode_account_id(&key), g);
+
+        // Corrupted checksum
+        assert!(decode_account_id("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGA").is_none());
+        assert!(decode_account_id("invalid").is_none());
+    }
+
+    #[test]
+    fn test_decode_secret_seed() {
+        let seed = [3u8; 32];
+        let encoded = encode_ed25519_strkey(&seed, SECRET_SEED_VERSION_BYTE);
+        assert!(encoded.starts_with('S'));
+        assert_eq!(decode_secret_seed(&encoded), Some(seed));
+
+        // A public key is not a seed
+        assert!(decode_secret_seed(&encode_account_id(&seed)).is_none());
+    }
 }
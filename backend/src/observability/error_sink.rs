@@ -0,0 +1,251 @@
+//! Error reporting to an external sink, configured by
+//! [`crate::config::ErrorSinkSettings`].
+//!
+//! A Sentry DSN (`https://<key>@<host>/<project>`) sends events to Sentry's
+//! store endpoint; any other URL receives each [`ErrorEvent`] as a JSON POST.
+//! Without a DSN, [`capture`] is a no-op. Events are queued and sent from a
+//! background task, so capturing never blocks and is safe inside panic hooks.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::http_client;
+
+/// Events buffered while the sink is slow; further events are dropped
+const QUEUE_CAPACITY: usize = 256;
+
+static SINK: OnceLock<mpsc::Sender<ErrorEvent>> = OnceLock::new();
+
+/// Where an error was captured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSource {
+    ApiInternalError,
+    Panic,
+    WebhookDispatch,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorEvent {
+    pub event_id: String,
+    pub source: ErrorSource,
+    pub message: String,
+    pub request_id: Option<String>,
+    pub user_id: Option<String>,
+    pub context: Value,
+    pub timestamp: DateTime<Utc>,
+    /// Deployment name, filled in by the sink when the event is sent
+    pub environment: Option<String>,
+}
+
+/// Where events go; no DSN disables reporting
+#[derive(Clone, Default)]
+pub struct ErrorSinkConfig {
+    pub dsn: Option<String>,
+    /// Deployment name attached to every event, e.g. `production`
+    pub environment: Option<String>,
+}
+
+impl ErrorEvent {
+    /// New event tagged with the current request's ID and user, if any
+    pub fn new(source: ErrorSource, message: impl Into<String>, context: Value) -> Self {
+        Self {
+            event_id: Uuid::new_v4().simple().to_string(),
+            source,
+            message: message.into(),
+            request_id: crate::request_id::current_request_id(),
+            user_id: crate::request_id::current_user_id(),
+            context,
+            timestamp: Utc::now(),
+            environment: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Sentry { store_url: String, public_key: String },
+    Http { url: String },
+}
+
+impl Target {
+    fn from_dsn(dsn: &str) -> Option<Self> {
+        let url = reqwest::Url::parse(dsn).ok()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        let project_id = url.path().trim_matches('/');
+        let is_sentry = !url.username().is_empty()
+            && !project_id.is_empty()
+            && project_id.chars().all(|c| c.is_ascii_digit());
+
+        if is_sentry {
+            let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
+            Some(Self::Sentry {
+                store_url: format!(
+                    "{}://{}{}/api/{}/store/",
+                    url.scheme(),
+                    url.host_str()?,
+                    port,
+                    project_id
+                ),
+                public_key: url.username().to_string(),
+            })
+        } else {
+            Some(Self::Http {
+                url: dsn.to_string(),
+            })
+        }
+    }
+}
+
+/// Sentry store-API representation of an event
+fn sentry_payload(event: &ErrorEvent) -> Value {
+    serde_json::json!({
+        "event_id": event.event_id,
+        "timestamp": event.timestamp.to_rfc3339(),
+        "platform": "other",
+        "level": "error",
+        "logger": "stellar-insights-backend",
+        "environment": event.environment,
+        "message": { "formatted": event.message },
+        "tags": {
+            "source": event.source,
+            "request_id": event.request_id,
+        },
+        "user": event.user_id.as_ref().map(|id| serde_json::json!({ "id": id })),
+        "extra": event.context,
+    })
+}
+
+/// Start the sink if `config` has a DSN. Must be called from within the runtime.
+pub fn init(config: ErrorSinkConfig) {
+    let ErrorSinkConfig { dsn, environment } = config;
+    let Some(dsn) = dsn else {
+        return;
+    };
+    let Some(target) = Target::from_dsn(dsn.trim()) else {
+        tracing::warn!("Error sink DSN is not a valid http(s) URL; error reporting disabled");
+        return;
+    };
+
+    let (tx, mut rx) = mpsc::channel::<ErrorEvent>(QUEUE_CAPACITY);
+    if SINK.set(tx).is_err() {
+        return;
+    }
+
+    let client = http_client::shared().client(Duration::from_secs(5));
+
+    tokio::spawn(async move {
+        while let Some(mut event) = rx.recv().await {
+            event.environment = environment.clone();
+            let request = match &target {
+                Target::Sentry {
+                    store_url,
+                    public_key,
+                } => client
+                    .post(store_url)
+                    .header(
+                        "X-Sentry-Auth",
+                        format!(
+                            "Sentry sentry_version=7, sentry_key={}, sentry_client=stellar-insights/1.0",
+                            public_key
+                        ),
+                    )
+                    .json(&sentry_payload(&event)),
+                Target::Http { url } => client.post(url).json(&event),
+            };
+
+            match request.send().await {
+                Ok(resp) if !resp.status().is_success() => {
+                    tracing::warn!("Error sink rejected event: {}", resp.status());
+                }
+                Err(e) => tracing::warn!("Failed to send event to error sink: {}", e),
+                Ok(_) => {}
+            }
+        }
+    });
+
+    tracing::info!("Error reporting enabled");
+}
+
+/// Queue an event for the sink; dropped if the sink is disabled or backed up
+pub fn capture(event: ErrorEvent) {
+    if let Some(sink) = SINK.get() {
+        let _ = sink.try_send(event);
+    }
+}
+
+/// Report panics (including those in spawned background tasks) to the sink,
+/// then run the previously installed hook
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()));
+        let thread = std::thread::current().name().map(str::to_string);
+
+        capture(ErrorEvent::new(
+            ErrorSource::Panic,
+            message,
+            serde_json::json!({ "location": location, "thread": thread }),
+        ));
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_from_sentry_dsn() {
+        assert_eq!(
+            Target::from_dsn("https://abc123@o1.ingest.sentry.io/42"),
+            Some(Target::Sentry {
+                store_url: "https://o1.ingest.sentry.io/api/42/store/".to_string(),
+                public_key: "abc123".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_target_from_generic_url() {
+        assert_eq!(
+            Target::from_dsn("https://errors.example.com/ingest"),
+            Some(Target::Http {
+                url: "https://errors.example.com/ingest".to_string()
+            })
+        );
+        assert_eq!(Target::from_dsn("not a url"), None);
+    }
+
+    #[test]
+    fn test_sentry_payload_carries_request_context() {
+        let mut event = ErrorEvent::new(
+            ErrorSource::WebhookDispatch,
+            "delivery failed",
+            serde_json::json!({ "webhook_id": "w1" }),
+        );
+        event.request_id = Some("req-1".to_string());
+        event.user_id = Some("u1".to_string());
+
+        let payload = sentry_payload(&event);
+        assert_eq!(payload["tags"]["request_id"], "req-1");
+        assert_eq!(payload["tags"]["source"], "webhook_dispatch");
+        assert_eq!(payload["user"]["id"], "u1");
+        assert_eq!(payload["extra"]["webhook_id"], "w1");
+    }
+}
@@ -1,6 +1,13 @@
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicI64, Ordering};
-use std::sync::{Mutex, OnceLock};
+//! Prometheus metrics recorded on the request hot path.
+//!
+//! Labelled series live in sharded [`DashMap`]s of atomics: recording takes a
+//! shard read lock and bumps an atomic, and only the first sighting of a label
+//! set takes a write lock. Requests on different routes therefore never
+//! serialize on a global lock.
+
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::OnceLock;
 use std::time::Instant;
 
 use axum::{
@@ -10,25 +17,113 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
+/// Counters keyed by label set (see [`make_key`])
+#[derive(Default)]
+struct CounterVec(DashMap<String, AtomicU64>);
+
+impl CounterVec {
+    fn add(&self, key: &str, n: u64) {
+        if let Some(counter) = self.0.get(key) {
+            counter.fetch_add(n, Ordering::Relaxed);
+            return;
+        }
+        self.0
+            .entry(key.to_string())
+            .or_default()
+            .fetch_add(n, Ordering::Relaxed);
+    }
+
+    fn inc(&self, key: &str) {
+        self.add(key, 1);
+    }
+
+    /// Replace every series, for gauges reported as a whole
+    fn replace(&self, values: impl IntoIterator<Item = (String, u64)>) {
+        self.0.clear();
+        for (key, value) in values {
+            self.0.insert(key, AtomicU64::new(value));
+        }
+    }
+
+    fn snapshot(&self) -> Vec<(String, u64)> {
+        self.0
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
 #[derive(Default)]
 struct DurationSeries {
+    count: AtomicU64,
+    sum_nanos: AtomicU64,
+}
+
+struct DurationSnapshot {
     count: u64,
     sum: f64,
 }
 
+/// Count and total duration keyed by label set
+#[derive(Default)]
+struct DurationVec(DashMap<String, DurationSeries>);
+
+impl DurationVec {
+    fn observe(&self, key: &str, seconds: f64) {
+        let nanos = (seconds.max(0.0) * 1e9) as u64;
+        let record = |series: &DurationSeries| {
+            series.count.fetch_add(1, Ordering::Relaxed);
+            series.sum_nanos.fetch_add(nanos, Ordering::Relaxed);
+        };
+        if let Some(series) = self.0.get(key) {
+            record(&series);
+            return;
+        }
+        record(&self.0.entry(key.to_string()).or_default());
+    }
+
+    fn snapshot(&self) -> Vec<(String, DurationSnapshot)> {
+        self.0
+            .iter()
+            .map(|entry| {
+                let series = entry.value();
+                (
+                    entry.key().clone(),
+                    DurationSnapshot {
+                        count: series.count.load(Ordering::Relaxed),
+                        sum: series.sum_nanos.load(Ordering::Relaxed) as f64 / 1e9,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
 #[derive(Default)]
 struct MetricsState {
-    http_requests_total: Mutex<HashMap<String, u64>>,
-    http_request_duration_seconds: Mutex<HashMap<String, DurationSeries>>,
-    rpc_calls_total: Mutex<HashMap<String, u64>>,
-    rpc_call_duration_seconds: Mutex<HashMap<String, DurationSeries>>,
-    cache_operations_total: Mutex<HashMap<String, u64>>,
-    errors_total: Mutex<HashMap<String, u64>>,
-    db_query_duration_seconds: Mutex<HashMap<String, DurationSeries>>,
-    background_jobs_total: Mutex<HashMap<String, u64>>,
+    http_requests_total: CounterVec,
+    http_request_duration_seconds: DurationVec,
+    rpc_calls_total: CounterVec,
+    rpc_call_duration_seconds: DurationVec,
+    cache_operations_total: CounterVec,
+    errors_total: CounterVec,
+    db_query_duration_seconds: DurationVec,
+    background_jobs_total: CounterVec,
+    task_restarts_total: CounterVec,
+    db_pool_acquire_wait_seconds: DurationVec,
+    db_pool_in_use: AtomicI64,
+    db_pool_idle: AtomicI64,
+    db_pool_max_connections: AtomicI64,
     active_connections: AtomicI64,
     corridors_tracked: AtomicI64,
     http_in_flight_requests: AtomicI64,
+    webhook_event_rows: CounterVec,
+    webhook_event_payload_bytes: AtomicI64,
+    webhook_event_summary_rows: AtomicI64,
+    webhook_events_compacted_total: AtomicI64,
+    ledger_gaps_unresolved: AtomicI64,
+    ledger_gap_missing_ledgers: AtomicI64,
+    ledger_gaps_repaired_total: AtomicI64,
 }
 
 static METRICS: OnceLock<MetricsState> = OnceLock::new();
@@ -64,37 +159,6 @@ fn key_to_prom_labels(key: &str) -> String {
     format!("{{{labels}}}")
 }
 
-fn inc_counter(map: &Mutex<HashMap<String, u64>>, key: String) {
-    if let Ok(mut guard) = map.lock() {
-        *guard.entry(key).or_insert(0) += 1;
-    }
-}
-
-fn observe_duration(map: &Mutex<HashMap<String, DurationSeries>>, key: String, seconds: f64) {
-    if let Ok(mut guard) = map.lock() {
-        let entry = guard.entry(key).or_default();
-        entry.count += 1;
-        entry.sum += seconds;
-    }
-}
-
-fn snapshot_counters(map: &Mutex<HashMap<String, u64>>) -> Vec<(String, u64)> {
-    map.lock()
-        .map(|guard| guard.iter().map(|(k, v)| (k.clone(), *v)).collect())
-        .unwrap_or_default()
-}
-
-fn snapshot_durations(map: &Mutex<HashMap<String, DurationSeries>>) -> Vec<(String, DurationSeries)> {
-    map.lock()
-        .map(|guard| {
-            guard
-                .iter()
-                .map(|(k, v)| (k.clone(), DurationSeries { count: v.count, sum: v.sum }))
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
 pub fn init_metrics() {
     let _ = state();
 }
@@ -105,7 +169,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP http_requests_total Total HTTP requests\n");
     out.push_str("# TYPE http_requests_total counter\n");
-    for (key, value) in snapshot_counters(&metrics.http_requests_total) {
+    for (key, value) in metrics.http_requests_total.snapshot() {
         out.push_str(&format!(
             "http_requests_total{} {}\n",
             key_to_prom_labels(&key),
@@ -115,7 +179,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP http_request_duration_seconds HTTP request duration in seconds\n");
     out.push_str("# TYPE http_request_duration_seconds summary\n");
-    for (key, series) in snapshot_durations(&metrics.http_request_duration_seconds) {
+    for (key, series) in metrics.http_request_duration_seconds.snapshot() {
         let labels = key_to_prom_labels(&key);
         out.push_str(&format!(
             "http_request_duration_seconds_count{} {}\n",
@@ -129,7 +193,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP rpc_calls_total Total RPC calls\n");
     out.push_str("# TYPE rpc_calls_total counter\n");
-    for (key, value) in snapshot_counters(&metrics.rpc_calls_total) {
+    for (key, value) in metrics.rpc_calls_total.snapshot() {
         out.push_str(&format!(
             "rpc_calls_total{} {}\n",
             key_to_prom_labels(&key),
@@ -139,7 +203,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP rpc_call_duration_seconds RPC call duration in seconds\n");
     out.push_str("# TYPE rpc_call_duration_seconds summary\n");
-    for (key, series) in snapshot_durations(&metrics.rpc_call_duration_seconds) {
+    for (key, series) in metrics.rpc_call_duration_seconds.snapshot() {
         let labels = key_to_prom_labels(&key);
         out.push_str(&format!("rpc_call_duration_seconds_count{} {}\n", labels, series.count));
         out.push_str(&format!("rpc_call_duration_seconds_sum{} {}\n", labels, series.sum));
@@ -147,7 +211,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP cache_operations_total Cache operations by result\n");
     out.push_str("# TYPE cache_operations_total counter\n");
-    for (key, value) in snapshot_counters(&metrics.cache_operations_total) {
+    for (key, value) in metrics.cache_operations_total.snapshot() {
         out.push_str(&format!(
             "cache_operations_total{} {}\n",
             key_to_prom_labels(&key),
@@ -157,7 +221,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP errors_total Total errors by type\n");
     out.push_str("# TYPE errors_total counter\n");
-    for (key, value) in snapshot_counters(&metrics.errors_total) {
+    for (key, value) in metrics.errors_total.snapshot() {
         out.push_str(&format!(
             "errors_total{} {}\n",
             key_to_prom_labels(&key),
@@ -167,7 +231,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP db_query_duration_seconds Database query duration in seconds\n");
     out.push_str("# TYPE db_query_duration_seconds summary\n");
-    for (key, series) in snapshot_durations(&metrics.db_query_duration_seconds) {
+    for (key, series) in metrics.db_query_duration_seconds.snapshot() {
         let labels = key_to_prom_labels(&key);
         out.push_str(&format!("db_query_duration_seconds_count{} {}\n", labels, series.count));
         out.push_str(&format!("db_query_duration_seconds_sum{} {}\n", labels, series.sum));
@@ -175,7 +239,7 @@ pub async fn metrics_handler() -> Response {
 
     out.push_str("# HELP background_jobs_total Background jobs by name and status\n");
     out.push_str("# TYPE background_jobs_total counter\n");
-    for (key, value) in snapshot_counters(&metrics.background_jobs_total) {
+    for (key, value) in metrics.background_jobs_total.snapshot() {
         out.push_str(&format!(
             "background_jobs_total{} {}\n",
             key_to_prom_labels(&key),
@@ -183,6 +247,42 @@ pub async fn metrics_handler() -> Response {
         ));
     }
 
+    out.push_str("# HELP task_restarts_total Supervised background task restarts\n");
+    out.push_str("# TYPE task_restarts_total counter\n");
+    for (key, value) in metrics.task_restarts_total.snapshot() {
+        out.push_str(&format!(
+            "task_restarts_total{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str("# HELP db_pool_connections Database pool connections by state\n");
+    out.push_str("# TYPE db_pool_connections gauge\n");
+    out.push_str(&format!(
+        "db_pool_connections{{state=\"in_use\"}} {}\n",
+        metrics.db_pool_in_use.load(Ordering::Relaxed)
+    ));
+    out.push_str(&format!(
+        "db_pool_connections{{state=\"idle\"}} {}\n",
+        metrics.db_pool_idle.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP db_pool_max_connections Configured database pool size limit\n");
+    out.push_str("# TYPE db_pool_max_connections gauge\n");
+    out.push_str(&format!(
+        "db_pool_max_connections {}\n",
+        metrics.db_pool_max_connections.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP db_pool_acquire_wait_seconds Time to check a connection out of the pool\n");
+    out.push_str("# TYPE db_pool_acquire_wait_seconds summary\n");
+    for (key, series) in metrics.db_pool_acquire_wait_seconds.snapshot() {
+        let labels = key_to_prom_labels(&key);
+        out.push_str(&format!("db_pool_acquire_wait_seconds_count{} {}\n", labels, series.count));
+        out.push_str(&format!("db_pool_acquire_wait_seconds_sum{} {}\n", labels, series.sum));
+    }
+
     out.push_str("# HELP active_connections Active websocket connections\n");
     out.push_str("# TYPE active_connections gauge\n");
     out.push_str(&format!(
@@ -197,6 +297,58 @@ pub async fn metrics_handler() -> Response {
         metrics.corridors_tracked.load(Ordering::Relaxed)
     ));
 
+    out.push_str("# HELP webhook_event_rows Rows in webhook_events by status\n");
+    out.push_str("# TYPE webhook_event_rows gauge\n");
+    for (key, value) in metrics.webhook_event_rows.snapshot() {
+        out.push_str(&format!(
+            "webhook_event_rows{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str("# HELP webhook_event_payload_bytes Payload bytes stored in webhook_events\n");
+    out.push_str("# TYPE webhook_event_payload_bytes gauge\n");
+    out.push_str(&format!(
+        "webhook_event_payload_bytes {}\n",
+        metrics.webhook_event_payload_bytes.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP webhook_event_summary_rows Rows in webhook_event_summaries\n");
+    out.push_str("# TYPE webhook_event_summary_rows gauge\n");
+    out.push_str(&format!(
+        "webhook_event_summary_rows {}\n",
+        metrics.webhook_event_summary_rows.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP webhook_events_compacted_total Webhook events folded into daily summaries\n");
+    out.push_str("# TYPE webhook_events_compacted_total counter\n");
+    out.push_str(&format!(
+        "webhook_events_compacted_total {}\n",
+        metrics.webhook_events_compacted_total.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP ledger_gaps_unresolved Missing ledger ranges not yet backfilled\n");
+    out.push_str("# TYPE ledger_gaps_unresolved gauge\n");
+    out.push_str(&format!(
+        "ledger_gaps_unresolved {}\n",
+        metrics.ledger_gaps_unresolved.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP ledger_gap_missing_ledgers Ledgers in unresolved gaps\n");
+    out.push_str("# TYPE ledger_gap_missing_ledgers gauge\n");
+    out.push_str(&format!(
+        "ledger_gap_missing_ledgers {}\n",
+        metrics.ledger_gap_missing_ledgers.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP ledger_gaps_repaired_total Ledger gaps filled by backfill\n");
+    out.push_str("# TYPE ledger_gaps_repaired_total counter\n");
+    out.push_str(&format!(
+        "ledger_gaps_repaired_total {}\n",
+        metrics.ledger_gaps_repaired_total.load(Ordering::Relaxed)
+    ));
+
     out.push_str("# HELP http_in_flight_requests In-flight HTTP requests\n");
     out.push_str("# TYPE http_in_flight_requests gauge\n");
     out.push_str(&format!(
@@ -235,8 +387,10 @@ pub async fn http_metrics_middleware(req: Request<Body>, next: Next) -> Response
         ("endpoint", endpoint.as_str()),
         ("status", status.as_str()),
     ]);
-    inc_counter(&state().http_requests_total, key.clone());
-    observe_duration(&state().http_request_duration_seconds, key, duration);
+    state().http_requests_total.inc(&key);
+    state()
+        .http_request_duration_seconds
+        .observe(&key, duration);
 
     if response.status().is_server_error() {
         record_error("http_5xx");
@@ -249,20 +403,23 @@ pub async fn http_metrics_middleware(req: Request<Body>, next: Next) -> Response
 
 pub fn record_rpc_call(method: &str, status: &str, duration_seconds: f64) {
     let key = make_key(&[("method", method), ("status", status)]);
-    inc_counter(&state().rpc_calls_total, key.clone());
-    observe_duration(&state().rpc_call_duration_seconds, key, duration_seconds);
+    state().rpc_calls_total.inc(&key);
+    state()
+        .rpc_call_duration_seconds
+        .observe(&key, duration_seconds);
 }
 
 pub fn record_cache_lookup(hit: bool) {
     let result = if hit { "hit" } else { "miss" };
-    inc_counter(
-        &state().cache_operations_total,
-        make_key(&[("result", result)]),
-    );
+    state()
+        .cache_operations_total
+        .inc(&make_key(&[("result", result)]));
 }
 
 pub fn record_error(error_type: &str) {
-    inc_counter(&state().errors_total, make_key(&[("error_type", error_type)]));
+    state()
+        .errors_total
+        .inc(&make_key(&[("error_type", error_type)]));
 }
 
 pub fn set_active_connections(count: i64) {
@@ -270,24 +427,82 @@ pub fn set_active_connections(count: i64) {
 }
 
 pub fn observe_db_query(query: &str, status: &str, duration_seconds: f64) {
-    observe_duration(
-        &state().db_query_duration_seconds,
-        make_key(&[("query", query), ("status", status)]),
+    state().db_query_duration_seconds.observe(
+        &make_key(&[("query", query), ("status", status)]),
         duration_seconds,
     );
 }
 
 pub fn record_background_job(job: &str, status: &str) {
-    inc_counter(
-        &state().background_jobs_total,
-        make_key(&[("job", job), ("status", status)]),
-    );
+    state()
+        .background_jobs_total
+        .inc(&make_key(&[("job", job), ("status", status)]));
+}
+
+pub fn record_task_restart(task: &str) {
+    state()
+        .task_restarts_total
+        .inc(&make_key(&[("task", task)]));
+}
+
+pub fn set_db_pool_stats(in_use: u32, idle: u32, max_connections: u32) {
+    let metrics = state();
+    metrics.db_pool_in_use.store(in_use.into(), Ordering::Relaxed);
+    metrics.db_pool_idle.store(idle.into(), Ordering::Relaxed);
+    metrics
+        .db_pool_max_connections
+        .store(max_connections.into(), Ordering::Relaxed);
+}
+
+pub fn observe_db_pool_acquire(seconds: f64) {
+    state().db_pool_acquire_wait_seconds.observe("", seconds);
 }
 
 pub fn set_corridors_tracked(count: i64) {
     state().corridors_tracked.store(count, Ordering::Relaxed);
 }
 
+pub fn set_webhook_event_table_stats(
+    rows_by_status: &[(String, i64)],
+    payload_bytes: i64,
+    summary_rows: i64,
+) {
+    let metrics = state();
+    metrics.webhook_event_rows.replace(
+        rows_by_status
+            .iter()
+            .map(|(status, count)| (make_key(&[("status", status)]), (*count).max(0) as u64)),
+    );
+    metrics
+        .webhook_event_payload_bytes
+        .store(payload_bytes, Ordering::Relaxed);
+    metrics
+        .webhook_event_summary_rows
+        .store(summary_rows, Ordering::Relaxed);
+}
+
+pub fn record_webhook_events_compacted(count: u64) {
+    state()
+        .webhook_events_compacted_total
+        .fetch_add(count as i64, Ordering::Relaxed);
+}
+
+pub fn set_ledger_gap_stats(unresolved_gaps: i64, missing_ledgers: i64) {
+    let metrics = state();
+    metrics
+        .ledger_gaps_unresolved
+        .store(unresolved_gaps, Ordering::Relaxed);
+    metrics
+        .ledger_gap_missing_ledgers
+        .store(missing_ledgers, Ordering::Relaxed);
+}
+
+pub fn record_ledger_gaps_repaired(count: u64) {
+    state()
+        .ledger_gaps_repaired_total
+        .fetch_add(count as i64, Ordering::Relaxed);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,4 +558,37 @@ mod tests {
 
         assert!(text.contains("http_requests_total{method=\"GET\",endpoint=\"/ping\",status=\"200\"}"));
     }
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let counters = CounterVec::default();
+        let durations = DurationVec::default();
+
+        std::thread::scope(|scope| {
+            for thread in 0..8 {
+                let (counters, durations) = (&counters, &durations);
+                scope.spawn(move || {
+                    let key = make_key(&[("thread", if thread % 2 == 0 { "even" } else { "odd" })]);
+                    for _ in 0..1_000 {
+                        counters.inc(&key);
+                        durations.observe(&key, 0.001);
+                    }
+                });
+            }
+        });
+
+        let mut totals = counters.snapshot();
+        totals.sort();
+        assert_eq!(
+            totals,
+            vec![
+                ("thread=even".to_string(), 4_000),
+                ("thread=odd".to_string(), 4_000)
+            ]
+        );
+        for (_, series) in durations.snapshot() {
+            assert_eq!(series.count, 4_000);
+            assert!((series.sum - 4.0).abs() < 1e-6);
+        }
+    }
 }
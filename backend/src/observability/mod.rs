@@ -1,3 +1,4 @@
+pub mod error_sink;
 pub mod metrics;
+pub mod slow_queries;
 pub mod tracing;
-
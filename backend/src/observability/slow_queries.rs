@@ -0,0 +1,355 @@
+//! Slow query tracking.
+//!
+//! [`PoolConfig::create_pool`](crate::database::PoolConfig::create_pool) has
+//! sqlx emit a `sqlx::query` warning for every statement slower than the
+//! configured threshold. [`SlowQueryLayer`] picks those events out of the
+//! tracing pipeline and feeds them to a [`SlowQueryTracker`], which keeps the
+//! slowest N distinct statements for `GET /api/admin/slow-queries`.
+//!
+//! sqlx never logs bind parameter values; literals written inline in the SQL
+//! are replaced with `?` before a statement is stored, so tracked statements
+//! can't leak account or payment data.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Number of distinct statements kept
+pub const DEFAULT_CAPACITY: usize = 50;
+pub const DEFAULT_THRESHOLD_MS: u64 = 500;
+
+const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowQuery {
+    /// Statement with inline literals redacted
+    pub statement: String,
+    /// `db_query_duration_seconds` label the statement is recorded under
+    pub label: String,
+    pub count: u64,
+    pub max_ms: f64,
+    pub mean_ms: f64,
+    pub last_ms: f64,
+    pub last_rows_returned: Option<u64>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+pub struct SlowQueryTracker {
+    capacity: usize,
+    threshold_ms: AtomicU64,
+    entries: Mutex<HashMap<String, SlowQuery>>,
+}
+
+impl SlowQueryTracker {
+    pub fn new(capacity: usize, threshold: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            threshold_ms: AtomicU64::new(threshold.as_millis() as u64),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn threshold(&self) -> Duration {
+        Duration::from_millis(self.threshold_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn set_threshold(&self, threshold: Duration) {
+        self.threshold_ms
+            .store(threshold.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Record one execution of `sql`. Executions under the threshold are
+    /// ignored, as is a new statement slower than nothing already tracked
+    /// once the list is full.
+    pub fn record(&self, sql: &str, elapsed: Duration, rows_returned: Option<u64>) {
+        if elapsed < self.threshold() {
+            return;
+        }
+        let statement = redact_sql(sql);
+        if statement.is_empty() {
+            return;
+        }
+        let label = query_label(&statement);
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        crate::observability::metrics::observe_db_query(&label, "slow", elapsed.as_secs_f64());
+
+        let now = Utc::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.get_mut(&statement) {
+            entry.mean_ms =
+                (entry.mean_ms * entry.count as f64 + elapsed_ms) / (entry.count + 1) as f64;
+            entry.count += 1;
+            entry.max_ms = entry.max_ms.max(elapsed_ms);
+            entry.last_ms = elapsed_ms;
+            entry.last_rows_returned = rows_returned;
+            entry.last_seen = now;
+            return;
+        }
+
+        if entries.len() >= self.capacity {
+            let fastest = entries
+                .values()
+                .min_by(|a, b| a.max_ms.total_cmp(&b.max_ms))
+                .map(|e| (e.statement.clone(), e.max_ms));
+            match fastest {
+                Some((key, max_ms)) if max_ms < elapsed_ms => {
+                    entries.remove(&key);
+                }
+                _ => return,
+            }
+        }
+        entries.insert(
+            statement.clone(),
+            SlowQuery {
+                statement,
+                label,
+                count: 1,
+                max_ms: elapsed_ms,
+                mean_ms: elapsed_ms,
+                last_ms: elapsed_ms,
+                last_rows_returned: rows_returned,
+                first_seen: now,
+                last_seen: now,
+            },
+        );
+    }
+
+    /// Tracked statements, slowest first
+    pub fn top(&self) -> Vec<SlowQuery> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut top: Vec<SlowQuery> = entries.values().cloned().collect();
+        top.sort_by(|a, b| b.max_ms.total_cmp(&a.max_ms));
+        top
+    }
+
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Process-wide tracker fed by [`SlowQueryLayer::default`]
+pub fn tracker() -> &'static Arc<SlowQueryTracker> {
+    static TRACKER: OnceLock<Arc<SlowQueryTracker>> = OnceLock::new();
+    TRACKER.get_or_init(|| {
+        Arc::new(SlowQueryTracker::new(
+            DEFAULT_CAPACITY,
+            Duration::from_millis(DEFAULT_THRESHOLD_MS),
+        ))
+    })
+}
+
+/// Replace inline string and numeric literals with `?` and collapse
+/// whitespace. Placeholders such as `$1` and identifiers like `table_2` are
+/// kept.
+pub fn redact_sql(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut prev: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // '' inside a literal is an escaped quote
+            while let Some(n) = chars.next() {
+                if n == '\'' {
+                    if chars.peek() == Some(&'\'') {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+            }
+            out.push('?');
+        } else if c.is_ascii_digit()
+            && !prev.is_some_and(|p| p.is_alphanumeric() || matches!(p, '_' | '$' | '?' | ':'))
+        {
+            while chars
+                .peek()
+                .is_some_and(|n| n.is_ascii_alphanumeric() || *n == '.')
+            {
+                chars.next();
+            }
+            out.push('?');
+        } else if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+        prev = out.chars().last();
+    }
+
+    out.trim_end().to_string()
+}
+
+/// Short metric label for a statement: its verb and first table, e.g.
+/// `select anchors` or `insert corridor_metrics`
+pub fn query_label(statement: &str) -> String {
+    let words: Vec<String> = statement
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ',')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.trim_matches('"').to_ascii_lowercase())
+        .collect();
+    let Some(verb) = words.first() else {
+        return "unknown".to_string();
+    };
+    let table = words
+        .windows(2)
+        .find(|w| matches!(w[0].as_str(), "from" | "into" | "update"))
+        .map(|w| w[1].as_str());
+    match table {
+        Some(table) => format!("{} {}", verb, table),
+        None => verb.clone(),
+    }
+}
+
+/// Feeds sqlx's slow statement warnings to a [`SlowQueryTracker`]. The
+/// `sqlx::query` target must be enabled at `warn` by the subscriber's filter.
+pub struct SlowQueryLayer {
+    tracker: Arc<SlowQueryTracker>,
+}
+
+impl SlowQueryLayer {
+    pub fn new(tracker: Arc<SlowQueryTracker>) -> Self {
+        Self { tracker }
+    }
+}
+
+impl Default for SlowQueryLayer {
+    fn default() -> Self {
+        Self::new(Arc::clone(tracker()))
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SlowQueryLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != SQLX_QUERY_TARGET {
+            return;
+        }
+        let mut fields = SqlxQueryFields::default();
+        event.record(&mut fields);
+        // Only the slow statement event carries `slow_threshold`
+        if !fields.slow {
+            return;
+        }
+        let Some(elapsed_secs) = fields.elapsed_secs else {
+            return;
+        };
+        // `db.statement` is left empty when the summary is the whole statement
+        let sql = match fields.statement.as_deref().map(str::trim) {
+            Some(statement) if !statement.is_empty() => statement.to_string(),
+            _ => fields.summary.unwrap_or_default(),
+        };
+        self.tracker.record(
+            &sql,
+            Duration::from_secs_f64(elapsed_secs.max(0.0)),
+            fields.rows_returned,
+        );
+    }
+}
+
+#[derive(Default)]
+struct SqlxQueryFields {
+    summary: Option<String>,
+    statement: Option<String>,
+    elapsed_secs: Option<f64>,
+    rows_returned: Option<u64>,
+    slow: bool,
+}
+
+impl Visit for SqlxQueryFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = Some(value.to_string()),
+            "db.statement" => self.statement = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = Some(value);
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "rows_returned" {
+            self.rows_returned = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, _value: &dyn std::fmt::Debug) {
+        if field.name() == "slow_threshold" {
+            self.slow = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_sql() {
+        assert_eq!(
+            redact_sql("SELECT * FROM anchors\n  WHERE name = 'O''Brien' AND id = $1 LIMIT 10"),
+            "SELECT * FROM anchors WHERE name = ? AND id = $1 LIMIT ?"
+        );
+        assert_eq!(
+            redact_sql("UPDATE table_2 SET score = 0.95 WHERE x = ?1"),
+            "UPDATE table_2 SET score = ? WHERE x = ?1"
+        );
+        assert_eq!(
+            query_label("SELECT * FROM anchors WHERE id = ?"),
+            "select anchors"
+        );
+        assert_eq!(
+            query_label("INSERT INTO corridors (a) VALUES (?)"),
+            "insert corridors"
+        );
+        assert_eq!(query_label("PRAGMA optimize"), "pragma");
+    }
+
+    #[test]
+    fn test_tracker_keeps_slowest() {
+        let tracker = SlowQueryTracker::new(2, Duration::from_millis(10));
+        tracker.record("SELECT 1", Duration::from_millis(5), None);
+        assert!(tracker.top().is_empty());
+
+        tracker.record(
+            "SELECT * FROM a WHERE id = 1",
+            Duration::from_millis(20),
+            None,
+        );
+        tracker.record(
+            "SELECT * FROM a WHERE id = 2",
+            Duration::from_millis(40),
+            Some(3),
+        );
+        tracker.record("SELECT * FROM b", Duration::from_millis(30), None);
+        tracker.record("SELECT * FROM c", Duration::from_millis(15), None);
+
+        let top = tracker.top();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].statement, "SELECT * FROM a WHERE id = ?");
+        assert_eq!(top[0].count, 2);
+        assert_eq!(top[0].max_ms, 40.0);
+        assert_eq!(top[0].mean_ms, 30.0);
+        assert_eq!(top[0].last_rows_returned, Some(3));
+        assert_eq!(top[1].statement, "SELECT * FROM b");
+    }
+}
@@ -1,9 +1,12 @@
 use anyhow::Result;
+use axum::http::{Request, Uri};
 use opentelemetry::sdk::{trace as sdktrace, Resource};
 use opentelemetry::KeyValue;
 use opentelemetry_otlp::WithExportConfig;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use super::slow_queries::SlowQueryLayer;
+
 fn init_otel_tracer(service_name: &str) -> Result<sdktrace::Tracer> {
     let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
         .unwrap_or_else(|_| "http://localhost:4317".to_string());
@@ -28,7 +31,15 @@ fn init_otel_tracer(service_name: &str) -> Result<sdktrace::Tracer> {
 
 pub fn init_tracing(service_name: &str) -> Result<()> {
     let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| "backend=info,tower_http=info".into());
+        .unwrap_or_else(|_| "backend=info,stellar_insights_backend=info,access_log=info,tower_http=info".into());
+    // Slow statement warnings feed the slow query tracker, so keep them
+    // unless RUST_LOG already says what to do with sqlx
+    let sqlx_configured = std::env::var("RUST_LOG").is_ok_and(|v| v.contains("sqlx"));
+    let env_filter = if sqlx_configured {
+        env_filter
+    } else {
+        env_filter.add_directive("sqlx::query=warn".parse()?)
+    };
     let log_format = std::env::var("LOG_FORMAT").unwrap_or_else(|_| "json".to_string());
     let otel_enabled = std::env::var("OTEL_ENABLED")
         .map(|v| v.eq_ignore_ascii_case("true"))
@@ -40,12 +51,14 @@ pub fn init_tracing(service_name: &str) -> Result<()> {
         if log_format.eq_ignore_ascii_case("json") {
             tracing_subscriber::registry()
                 .with(env_filter)
+            .with(SlowQueryLayer::default())
                 .with(tracing_subscriber::fmt::layer().json())
                 .with(tracing_opentelemetry::layer().with_tracer(tracer))
                 .init();
         } else {
             tracing_subscriber::registry()
                 .with(env_filter)
+            .with(SlowQueryLayer::default())
                 .with(tracing_subscriber::fmt::layer())
                 .with(tracing_opentelemetry::layer().with_tracer(tracer))
                 .init();
@@ -55,11 +68,13 @@ pub fn init_tracing(service_name: &str) -> Result<()> {
     } else if log_format.eq_ignore_ascii_case("json") {
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(SlowQueryLayer::default())
             .with(tracing_subscriber::fmt::layer().json())
             .init();
     } else {
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(SlowQueryLayer::default())
             .with(tracing_subscriber::fmt::layer())
             .init();
     }
@@ -67,6 +82,56 @@ pub fn init_tracing(service_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Query parameters whose values never appear in request spans
+const SENSITIVE_QUERY_PARAMS: &[&str] = &["jwt", "token", "access_token", "api_key"];
+
+/// Path and query of `uri` with sensitive query parameter values replaced
+pub fn redact_uri(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.path().to_string();
+    };
+
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if SENSITIVE_QUERY_PARAMS.contains(&key.to_ascii_lowercase().as_str()) => {
+                format!("{}=[REDACTED]", key)
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{}?{}", uri.path(), query)
+}
+
+/// Span for `TraceLayer` that records the request URI with credentials redacted
+pub fn make_request_span<B>(request: &Request<B>) -> tracing::Span {
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %redact_uri(request.uri()),
+        version = ?request.version(),
+    )
+}
+
 pub fn shutdown_tracing() {
     opentelemetry::global::shutdown_tracer_provider();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_uri() {
+        let uri: Uri = "/api/sep24/transaction?transfer_server=https%3A%2F%2Fa.com&id=1&jwt=secret"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            redact_uri(&uri),
+            "/api/sep24/transaction?transfer_server=https%3A%2F%2Fa.com&id=1&jwt=[REDACTED]"
+        );
+        assert_eq!(redact_uri(&"/api/status".parse().unwrap()), "/api/status");
+    }
+}
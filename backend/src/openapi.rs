@@ -23,11 +23,26 @@ use utoipa::OpenApi;
         crate::api::anchors_cached::get_anchors,
         crate::api::corridors_cached::list_corridors,
         crate::api::corridors_cached::get_corridor_detail,
+        crate::api::corridor_seasonality::get_corridor_seasonality,
+        crate::api::corridor_cohorts::get_corridor_cohorts,
+        crate::api::corridor_health::get_corridor_health_history,
+        crate::api::corridor_anomalies::get_corridor_anomalies,
         crate::api::price_feed::get_price,
         crate::api::price_feed::get_prices,
         crate::api::price_feed::convert_to_usd,
         crate::api::price_feed::get_cache_stats,
         crate::api::cost_calculator::estimate_costs,
+        crate::api::snapshot_signatures::get_signing_key,
+        crate::api::snapshot_signatures::get_snapshot_signature,
+        crate::api::snapshot_sla::get_snapshot_sla,
+        crate::api::snapshot_sla::verify_snapshot_sla,
+        crate::api::webhooks::register_webhook,
+        crate::api::webhooks::list_webhooks,
+        crate::api::webhooks::delete_webhook,
+        crate::api::webhooks::test_webhook,
+        crate::api::webhooks::rotate_secret,
+        crate::api::webhooks::list_deliveries,
+        crate::api::webhooks::redrive_deliveries,
     ),
     components(
         schemas(
@@ -38,6 +53,21 @@ use utoipa::OpenApi;
             crate::api::corridors_cached::SuccessRateDataPoint,
             crate::api::corridors_cached::LatencyDataPoint,
             crate::api::corridors_cached::LiquidityDataPoint,
+            crate::api::corridors_cached::RecentTradesSummary,
+            crate::api::corridors_cached::CorridorAnchor,
+            crate::api::corridors_cached::ConcentrationPoint,
+            crate::services::settlement_times::SettlementTimes,
+            crate::api::corridor_seasonality::SeasonalityResponse,
+            crate::api::corridor_seasonality::HourOfDayEffect,
+            crate::api::corridor_seasonality::WeekdayEffect,
+            crate::api::corridor_seasonality::SeasonalityPoint,
+            crate::api::corridor_seasonality::LatestHour,
+            crate::api::corridor_cohorts::CohortRetentionResponse,
+            crate::api::corridor_cohorts::Cohort,
+            crate::api::corridor_health::HealthHistoryResponse,
+            crate::api::corridor_health::HealthPoint,
+            crate::api::corridor_anomalies::CorridorAnomaliesResponse,
+            crate::api::corridor_anomalies::AnomalyIntervalResponse,
             crate::api::price_feed::PriceResponse,
             crate::api::price_feed::PricesResponse,
             crate::api::price_feed::ConvertResponse,
@@ -48,6 +78,23 @@ use utoipa::OpenApi;
             crate::api::cost_calculator::RouteEstimate,
             crate::api::cost_calculator::CostCalculationResponse,
             crate::api::cost_calculator::ErrorResponse,
+            crate::api::snapshot_signatures::SigningKeyResponse,
+            crate::api::snapshot_signatures::SnapshotSignatureResponse,
+            crate::services::snapshot::SnapshotSignature,
+            crate::api::snapshot_sla::OnChainSla,
+            crate::api::snapshot_sla::SnapshotSlaResponse,
+            crate::api::snapshot_sla::VerifySlaResponse,
+            crate::services::snapshot::AnchoredSla,
+            crate::webhooks::CreateWebhookRequest,
+            crate::webhooks::WebhookResponse,
+            crate::webhooks::WebhookListResponse,
+            crate::webhooks::DeliverySettings,
+            crate::webhooks::RotateSecretRequest,
+            crate::webhooks::RotateSecretResponse,
+            crate::webhooks::deliveries::WebhookDelivery,
+            crate::webhooks::deliveries::WebhookDeliveriesResponse,
+            crate::webhooks::deliveries::RedriveRequest,
+            crate::webhooks::deliveries::RedriveResponse,
         )
     ),
     tags(
@@ -55,6 +102,8 @@ use utoipa::OpenApi;
         (name = "Corridors", description = "Payment corridor analytics endpoints"),
         (name = "Prices", description = "Real-time asset price feed endpoints"),
         (name = "Cost Calculator", description = "Cross-border payment cost estimation and route comparison"),
+        (name = "Snapshots", description = "Signed analytics snapshot and anchored SLA verification"),
+        (name = "Webhooks", description = "Webhook registration and delivery settings (bearer token required)"),
         (name = "RPC", description = "Stellar RPC integration endpoints"),
         (name = "Fee Bumps", description = "Fee bump transaction tracking"),
         (name = "Cache", description = "Cache management and statistics"),
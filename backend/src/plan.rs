@@ -0,0 +1,229 @@
+//! Subscription plans and their limits.
+//!
+//! Each tenant is on a [`Plan`]. [`plan_middleware`] runs after
+//! [`tenant_middleware`](crate::tenant::tenant_middleware), loads the
+//! tenant's plan and rejects requests whose history window (`period`/`from`)
+//! reaches further back than the plan allows. Limits that depend on stored state (webhook count, watchlist
+//! size) are checked by the handlers with [`Plan::check_webhooks`] and
+//! [`Plan::check_watchlist`]. Every violation is a 403 `PLAN_LIMIT_EXCEEDED`
+//! whose details name the limit.
+//!
+//! Requests that are not tenant-scoped, including every request in
+//! single-tenant mode, are unrestricted.
+
+use axum::{
+    extract::{FromRequestParts, Query, Request, State},
+    http::request::Parts,
+    middleware::Next,
+    response::Response,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::tenant::TenantScope;
+use crate::time_range::{TimeRange, TimeRangeQuery};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Plan {
+    Free,
+    Pro,
+    Enterprise,
+}
+
+/// What a plan allows; `None` is unlimited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlanLimits {
+    /// Active webhooks across the tenant
+    pub max_webhooks: Option<usize>,
+    /// Corridors one webhook may watch (`filters.corridors`)
+    pub max_watchlist: Option<usize>,
+    /// How far back a history window may start
+    pub max_history_days: Option<i64>,
+}
+
+impl Plan {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Plan::Free => "free",
+            Plan::Pro => "pro",
+            Plan::Enterprise => "enterprise",
+        }
+    }
+
+    pub fn parse(plan: &str) -> Option<Self> {
+        match plan {
+            "free" => Some(Plan::Free),
+            "pro" => Some(Plan::Pro),
+            "enterprise" => Some(Plan::Enterprise),
+            _ => None,
+        }
+    }
+
+    pub fn limits(&self) -> PlanLimits {
+        match self {
+            Plan::Free => PlanLimits {
+                max_webhooks: Some(2),
+                max_watchlist: Some(5),
+                max_history_days: Some(30),
+            },
+            Plan::Pro => PlanLimits {
+                max_webhooks: Some(20),
+                max_watchlist: Some(50),
+                max_history_days: Some(365),
+            },
+            Plan::Enterprise => PlanLimits {
+                max_webhooks: None,
+                max_watchlist: None,
+                max_history_days: None,
+            },
+        }
+    }
+
+    /// Whether the tenant may have one more webhook than `active`
+    pub fn check_webhooks(&self, active: usize) -> Result<(), ApiError> {
+        match self.limits().max_webhooks {
+            Some(max) if active >= max => Err(self.limit_exceeded("webhooks", max, active + 1)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether one webhook may watch `corridors` corridors
+    pub fn check_watchlist(&self, corridors: usize) -> Result<(), ApiError> {
+        match self.limits().max_watchlist {
+            Some(max) if corridors > max => Err(self.limit_exceeded("watchlist", max, corridors)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether a history window starting `depth` ago is allowed
+    pub fn check_history(&self, depth: Duration) -> Result<(), ApiError> {
+        match self.limits().max_history_days {
+            Some(max) if depth > Duration::days(max) => {
+                Err(self.limit_exceeded("history_days", max, depth.num_days()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn limit_exceeded(
+        &self,
+        limit: &str,
+        allowed: impl fmt::Display,
+        requested: impl fmt::Display,
+    ) -> ApiError {
+        let details = HashMap::from([
+            ("plan".to_string(), self.as_str().into()),
+            ("limit".to_string(), limit.into()),
+            ("allowed".to_string(), allowed.to_string().into()),
+            ("requested".to_string(), requested.to_string().into()),
+        ]);
+        ApiError::forbidden(
+            "PLAN_LIMIT_EXCEEDED",
+            format!(
+                "The {} plan does not allow this {} ({} allowed, {} requested)",
+                self.as_str(),
+                limit,
+                allowed,
+                requested
+            ),
+        )
+        .with_details(details)
+    }
+}
+
+/// Load the tenant's plan into the request extensions and enforce the limits
+/// visible in the query string
+pub async fn plan_middleware(
+    State(db): State<Arc<Database>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let tenant_id = req
+        .extensions()
+        .get::<TenantScope>()
+        .and_then(|scope| scope.id().map(str::to_string));
+    let plan = match tenant_id {
+        Some(tenant_id) => db.tenants().plan(&tenant_id).await?,
+        None => Plan::Enterprise,
+    };
+
+    // Malformed ranges are left for the handler's TimeRange to reject
+    let now = Utc::now();
+    if let Ok(Query(range)) = Query::<TimeRangeQuery>::try_from_uri(req.uri()) {
+        if range.period.is_some() || range.from.is_some() {
+            if let Ok(range) = TimeRange::from_query(&range, now) {
+                plan.check_history(now - range.from)?;
+            }
+        }
+    }
+
+    req.extensions_mut().insert(plan);
+    Ok(next.run(req).await)
+}
+
+/// Routes without [`plan_middleware`] are unrestricted
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for Plan
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<Plan>()
+            .copied()
+            .unwrap_or(Plan::Enterprise))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_plan_limits() {
+        let plan = Plan::Free;
+        assert!(plan.check_webhooks(1).is_ok());
+        assert!(plan.check_webhooks(2).is_err());
+        assert!(plan.check_watchlist(5).is_ok());
+        assert!(plan.check_watchlist(6).is_err());
+        assert!(plan.check_history(Duration::days(30)).is_ok());
+        assert!(plan.check_history(Duration::days(90)).is_err());
+    }
+
+    #[test]
+    fn test_enterprise_is_unlimited() {
+        let plan = Plan::Enterprise;
+        assert!(plan.check_webhooks(10_000).is_ok());
+        assert!(plan.check_watchlist(10_000).is_ok());
+        assert!(plan.check_history(Duration::days(366)).is_ok());
+    }
+
+    #[test]
+    fn test_limit_error_names_the_limit() {
+        let err = Plan::Pro.check_watchlist(51).unwrap_err();
+        let body = err.to_error_response(None);
+        assert_eq!(body.error.code, "PLAN_LIMIT_EXCEEDED");
+        let details = body.error.details.unwrap();
+        assert_eq!(details["plan"], "pro");
+        assert_eq!(details["limit"], "watchlist");
+        assert_eq!(details["allowed"], "50");
+        assert_eq!(details["requested"], "51");
+    }
+
+    #[test]
+    fn test_plan_round_trips() {
+        for plan in [Plan::Free, Plan::Pro, Plan::Enterprise] {
+            assert_eq!(Plan::parse(plan.as_str()), Some(plan));
+        }
+        assert_eq!(Plan::parse("gold"), None);
+    }
+}
@@ -0,0 +1,475 @@
+//! Per-user preferences (timezone, default network, base currency, locale and
+//! notification settings).
+//!
+//! `preferences_middleware` loads the caller's preferences when the request
+//! carries a valid access token and stores them in request extensions, along
+//! with the matching [`Locale`]. Handlers read them through the [`Preferences`]
+//! extractor, which falls back to defaults for anonymous requests.
+
+use anyhow::Result;
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts},
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use chrono::{DateTime, Offset, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::auth_middleware::{validate_access_token, JwtSecret};
+use crate::crypto::FieldCipher;
+use crate::database::Database;
+use crate::email::unsubscribe::UnsubscribeScope;
+use crate::error::ApiError;
+use crate::i18n::Locale;
+use crate::network::StellarNetwork;
+
+/// Currencies amounts can be expressed in
+pub const SUPPORTED_BASE_CURRENCIES: &[&str] = &[
+    "USD", "EUR", "GBP", "NGN", "KES", "GHS", "PHP", "INR", "XLM", "USDC",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    #[default]
+    None,
+    Weekly,
+    Monthly,
+}
+
+impl DigestFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "weekly" => Self::Weekly,
+            "monthly" => Self::Monthly,
+            _ => Self::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// Address digests are delivered to
+    pub email: Option<String>,
+    #[serde(default)]
+    pub digest_frequency: DigestFrequency,
+    /// Whether corridor alerts are delivered to this user
+    #[serde(default = "default_true")]
+    pub alerts: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            email: None,
+            digest_frequency: DigestFrequency::None,
+            alerts: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPreferences {
+    /// IANA timezone name, e.g. `America/Sao_Paulo`
+    pub timezone: String,
+    pub default_network: StellarNetwork,
+    /// Currency valuations are expressed in, e.g. `USD` or `EUR`
+    pub base_currency: String,
+    pub locale: Locale,
+    pub notifications: NotificationSettings,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        Self {
+            timezone: "UTC".to_string(),
+            default_network: StellarNetwork::Mainnet,
+            base_currency: "USD".to_string(),
+            locale: Locale::En,
+            notifications: NotificationSettings::default(),
+        }
+    }
+}
+
+impl UserPreferences {
+    /// Check and normalize user-supplied values
+    pub fn validate(mut self) -> Result<Self, ApiError> {
+        self.timezone = self.timezone.trim().to_string();
+        if self.timezone.parse::<Tz>().is_err() {
+            return Err(ApiError::bad_request(
+                "INVALID_PREFERENCES",
+                format!("Unknown timezone '{}'", self.timezone),
+            ));
+        }
+
+        self.base_currency = self.base_currency.trim().to_uppercase();
+        if !SUPPORTED_BASE_CURRENCIES.contains(&self.base_currency.as_str()) {
+            return Err(ApiError::bad_request(
+                "INVALID_PREFERENCES",
+                format!(
+                    "Unsupported base currency '{}': expected one of {}",
+                    self.base_currency,
+                    SUPPORTED_BASE_CURRENCIES.join(", ")
+                ),
+            ));
+        }
+
+        if let Some(email) = &self.notifications.email {
+            let email = email.trim();
+            if email.is_empty() {
+                self.notifications.email = None;
+            } else if email.len() > 254 || !email.contains('@') {
+                return Err(ApiError::bad_request(
+                    "INVALID_PREFERENCES",
+                    "notifications.email is not a valid email address",
+                ));
+            } else {
+                self.notifications.email = Some(email.to_string());
+            }
+        }
+
+        if self.notifications.digest_frequency != DigestFrequency::None
+            && self.notifications.email.is_none()
+        {
+            return Err(ApiError::bad_request(
+                "INVALID_PREFERENCES",
+                "notifications.email is required to receive digests",
+            ));
+        }
+
+        Ok(self)
+    }
+
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// Format a UTC timestamp in the user's timezone (RFC 3339 with offset)
+    pub fn localize(&self, ts: DateTime<Utc>) -> String {
+        ts.with_timezone(&self.tz())
+            .to_rfc3339_opts(SecondsFormat::Secs, false)
+    }
+
+    /// Offset of the user's timezone from UTC at `at`, in minutes
+    pub fn utc_offset_minutes(&self, at: DateTime<Utc>) -> i32 {
+        self.tz()
+            .offset_from_utc_datetime(&at.naive_utc())
+            .fix()
+            .local_minus_utc()
+            / 60
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PreferencesRow {
+    timezone: String,
+    default_network: String,
+    base_currency: String,
+    locale: String,
+    notification_email: Option<String>,
+    digest_frequency: String,
+    alert_notifications: bool,
+}
+
+impl From<PreferencesRow> for UserPreferences {
+    fn from(row: PreferencesRow) -> Self {
+        Self {
+            timezone: row.timezone,
+            default_network: row
+                .default_network
+                .parse()
+                .unwrap_or(StellarNetwork::Mainnet),
+            base_currency: row.base_currency,
+            locale: Locale::parse(&row.locale).unwrap_or_default(),
+            notifications: NotificationSettings {
+                email: row.notification_email,
+                digest_frequency: DigestFrequency::from_db(&row.digest_frequency),
+                alerts: row.alert_notifications,
+            },
+        }
+    }
+}
+
+/// A digest or alert email subscriber resolved from stored preferences
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DigestSubscriber {
+    pub user_id: String,
+    pub email: String,
+    pub locale: String,
+}
+
+/// Notification emails are encrypted at rest and decrypted transparently here
+pub struct PreferencesStore {
+    pool: SqlitePool,
+    cipher: FieldCipher,
+}
+
+impl PreferencesStore {
+    pub fn new(pool: SqlitePool, cipher: FieldCipher) -> Self {
+        Self { pool, cipher }
+    }
+
+    /// Decrypt subscriber emails, skipping (and logging) rows that can't be
+    /// read so one bad row doesn't hold up every other recipient
+    fn decrypt_subscribers(&self, subscribers: Vec<DigestSubscriber>) -> Vec<DigestSubscriber> {
+        subscribers
+            .into_iter()
+            .filter_map(
+                |mut subscriber| match self.cipher.decrypt(&subscriber.email) {
+                    Ok(email) => {
+                        subscriber.email = email;
+                        Some(subscriber)
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to decrypt notification email for user {}: {}",
+                            subscriber.user_id,
+                            e
+                        );
+                        None
+                    }
+                },
+            )
+            .collect()
+    }
+
+    /// Stored preferences for a user, or defaults if none have been saved
+    pub async fn get(&self, user_id: &str) -> Result<UserPreferences> {
+        let row = sqlx::query_as::<_, PreferencesRow>(
+            r#"
+            SELECT timezone, default_network, base_currency, locale,
+                   notification_email, digest_frequency, alert_notifications
+            FROM user_preferences
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(mut row) => {
+                row.notification_email =
+                    self.cipher.decrypt_opt(row.notification_email.as_deref())?;
+                Ok(UserPreferences::from(row))
+            }
+            None => Ok(UserPreferences::default()),
+        }
+    }
+
+    pub async fn upsert(&self, user_id: &str, prefs: &UserPreferences) -> Result<()> {
+        let email = self
+            .cipher
+            .encrypt_opt(prefs.notifications.email.as_deref())?;
+        sqlx::query(
+            r#"
+            INSERT INTO user_preferences (
+                user_id, timezone, default_network, base_currency, locale,
+                notification_email, digest_frequency, alert_notifications, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, datetime('now'))
+            ON CONFLICT(user_id) DO UPDATE SET
+                timezone = excluded.timezone,
+                default_network = excluded.default_network,
+                base_currency = excluded.base_currency,
+                locale = excluded.locale,
+                notification_email = excluded.notification_email,
+                digest_frequency = excluded.digest_frequency,
+                alert_notifications = excluded.alert_notifications,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(&prefs.timezone)
+        .bind(prefs.default_network.to_string())
+        .bind(&prefs.base_currency)
+        .bind(prefs.locale.as_str())
+        .bind(email)
+        .bind(prefs.notifications.digest_frequency.as_str())
+        .bind(prefs.notifications.alerts)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Users who opted into digests at the given frequency
+    pub async fn digest_subscribers(
+        &self,
+        frequency: DigestFrequency,
+    ) -> Result<Vec<DigestSubscriber>> {
+        let subscribers = sqlx::query_as::<_, DigestSubscriber>(
+            r#"
+            SELECT user_id, notification_email AS email, locale
+            FROM user_preferences
+            WHERE digest_frequency = $1 AND notification_email IS NOT NULL
+            "#,
+        )
+        .bind(frequency.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(self.decrypt_subscribers(subscribers))
+    }
+
+    /// Turn off digests or alert emails for a user. Returns false when the
+    /// user has no stored preferences (and so receives nothing anyway).
+    pub async fn unsubscribe(&self, user_id: &str, scope: UnsubscribeScope) -> Result<bool> {
+        let query = match scope {
+            UnsubscribeScope::Digest => {
+                "UPDATE user_preferences SET digest_frequency = 'none', updated_at = datetime('now') WHERE user_id = $1"
+            }
+            UnsubscribeScope::Alerts => {
+                "UPDATE user_preferences SET alert_notifications = 0, updated_at = datetime('now') WHERE user_id = $1"
+            }
+        };
+        let result = sqlx::query(query).bind(user_id).execute(&self.pool).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Users who want corridor alerts by email
+    pub async fn alert_subscribers(&self) -> Result<Vec<DigestSubscriber>> {
+        let subscribers = sqlx::query_as::<_, DigestSubscriber>(
+            r#"
+            SELECT user_id, notification_email AS email, locale
+            FROM user_preferences
+            WHERE alert_notifications = 1 AND notification_email IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(self.decrypt_subscribers(subscribers))
+    }
+}
+
+/// Preferences for the current request (defaults for anonymous callers)
+#[derive(Debug, Clone, Default)]
+pub struct Preferences(pub UserPreferences);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Preferences
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(
+            parts
+                .extensions
+                .get::<UserPreferences>()
+                .cloned()
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+/// Attach the caller's stored preferences and locale to the request.
+///
+/// Never rejects: a missing or invalid token simply leaves the defaults in
+/// place, and authentication is still enforced by `auth_middleware` where required.
+pub async fn preferences_middleware(
+    State(db): State<Arc<Database>>,
+    jwt_secret: Option<Extension<JwtSecret>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let user_id = jwt_secret.and_then(|Extension(JwtSecret(secret))| {
+        req.headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .and_then(|token| validate_access_token(token, secret.as_ref()).ok())
+            .map(|claims| claims.sub)
+    });
+
+    if let Some(user_id) = user_id {
+        match db.preferences().get(&user_id).await {
+            Ok(prefs) => {
+                req.extensions_mut().insert(prefs.locale);
+                req.extensions_mut().insert(prefs);
+            }
+            Err(e) => tracing::warn!(user_id = %user_id, "Failed to load user preferences: {}", e),
+        }
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_normalizes_values() {
+        let prefs = UserPreferences {
+            timezone: " Europe/Paris ".to_string(),
+            base_currency: "eur".to_string(),
+            ..UserPreferences::default()
+        }
+        .validate()
+        .unwrap();
+
+        assert_eq!(prefs.timezone, "Europe/Paris");
+        assert_eq!(prefs.base_currency, "EUR");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_values() {
+        let bad_tz = UserPreferences {
+            timezone: "Mars/Olympus".to_string(),
+            ..UserPreferences::default()
+        };
+        assert!(bad_tz.validate().is_err());
+
+        let bad_currency = UserPreferences {
+            base_currency: "DOGE".to_string(),
+            ..UserPreferences::default()
+        };
+        assert!(bad_currency.validate().is_err());
+
+        let digest_without_email = UserPreferences {
+            notifications: NotificationSettings {
+                digest_frequency: DigestFrequency::Weekly,
+                ..NotificationSettings::default()
+            },
+            ..UserPreferences::default()
+        };
+        assert!(digest_without_email.validate().is_err());
+    }
+
+    #[test]
+    fn test_localize_and_offset() {
+        let prefs = UserPreferences {
+            timezone: "Asia/Kolkata".to_string(),
+            ..UserPreferences::default()
+        };
+        let ts = DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(prefs.localize(ts), "2024-06-01T05:30:00+05:30");
+        assert_eq!(prefs.utc_offset_minutes(ts), 330);
+    }
+}
@@ -34,11 +34,8 @@ pub struct RateLimiter {
 }
 
 impl RateLimiter {
-    pub async fn new() -> anyhow::Result<Self> {
-        let redis_url =
-            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-
-        let connection = if let Ok(client) = redis::Client::open(redis_url.as_str()) {
+    pub async fn new(redis_url: &str) -> anyhow::Result<Self> {
+        let connection = if let Ok(client) = redis::Client::open(redis_url) {
             match client.get_multiplexed_tokio_connection().await {
                 Ok(conn) => {
                     tracing::info!("Connected to Redis for rate limiting");
@@ -1,13 +1,59 @@
 use axum::{
     body::Body,
-    extract::Request,
+    extract::{MatchedPath, Request},
     http::{HeaderValue, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::Instrument;
 use uuid::Uuid;
 
+/// Longest upstream request ID that is propagated rather than replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Per-request state visible to everything running inside the request
+#[derive(Clone)]
+struct RequestContext {
+    request_id: String,
+    span: tracing::Span,
+    user_id: Arc<Mutex<Option<String>>>,
+}
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+/// ID of the request being handled by the current task, if any
+pub fn current_request_id() -> Option<String> {
+    REQUEST_CONTEXT.try_with(|ctx| ctx.request_id.clone()).ok()
+}
+
+/// Authenticated user of the request handled by the current task, if any
+pub fn current_user_id() -> Option<String> {
+    REQUEST_CONTEXT
+        .try_with(|ctx| ctx.user_id.lock().ok().and_then(|u| u.clone()))
+        .ok()
+        .flatten()
+}
+
+/// Attach the authenticated user to the current request's span and access log line
+pub fn record_user(user_id: &str) {
+    let _ = REQUEST_CONTEXT.try_with(|ctx| {
+        ctx.span.record("user_id", user_id);
+        if let Ok(mut user) = ctx.user_id.lock() {
+            *user = Some(user_id.to_string());
+        }
+    });
+}
+
+/// Upstream IDs are kept only if they are short, printable ASCII
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
 /// Request ID wrapper for storing in request extensions
 #[derive(Clone, Debug)]
 pub struct RequestId(pub String);
@@ -39,37 +85,65 @@ impl fmt::Display for RequestId {
 /// Middleware to add request ID tracking
 ///
 /// This middleware:
-/// - Generates a unique request ID for each request
+/// - Reuses a valid upstream X-Request-ID or generates a new one
 /// - Adds it to request extensions for use in handlers
-/// - Includes it in response headers as X-Request-ID
-/// - Logs the request ID for tracing
+/// - Runs the request inside a `request` span carrying the ID, so every span
+///   and event emitted while handling it is tagged with it
+/// - Includes it in response headers as X-Request-ID and in `ApiError` bodies
+/// - Logs one structured access line per request with status, latency, user and endpoint
 pub async fn request_id_middleware(mut req: Request<Body>, next: Next) -> Response {
     // Check if request already has an X-Request-ID header (from upstream)
-    let request_id = if let Some(existing_id) = req.headers().get("X-Request-ID") {
-        existing_id
-            .to_str()
-            .ok()
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| Uuid::new_v4().to_string())
-    } else {
-        Uuid::new_v4().to_string()
-    };
+    let request_id = req
+        .headers()
+        .get("X-Request-ID")
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| is_valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
 
     // Store request ID in extensions for handlers to access
     req.extensions_mut().insert(RequestId(request_id.clone()));
 
-    // Log the request with ID
     let method = req.method().clone();
-    let uri = req.uri().clone();
-    tracing::info!(
+    let endpoint = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+
+    let span = tracing::info_span!(
+        "request",
         request_id = %request_id,
         method = %method,
-        uri = %uri,
-        "Incoming request"
+        endpoint = %endpoint,
+        user_id = tracing::field::Empty,
     );
+    let context = RequestContext {
+        request_id: request_id.clone(),
+        span: span.clone(),
+        user_id: Arc::new(Mutex::new(None)),
+    };
 
-    // Process the request
-    let response = next.run(req).await;
+    let start = Instant::now();
+    let response = REQUEST_CONTEXT
+        .scope(context.clone(), next.run(req).instrument(span.clone()))
+        .await;
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let user_id = context.user_id.lock().ok().and_then(|u| u.clone());
+    let status = response.status().as_u16();
+    span.in_scope(|| {
+        tracing::info!(
+            target: "access_log",
+            status,
+            latency_ms,
+            user_id = user_id.as_deref().unwrap_or("-"),
+            "{} {} {}",
+            method,
+            endpoint,
+            status
+        );
+    });
 
     // Add request ID to response headers
     let (mut parts, body) = response.into_parts();
@@ -179,6 +253,58 @@ mod tests {
         assert!(response.headers().get("X-Request-ID").is_some());
     }
 
+    #[tokio::test]
+    async fn middleware_replaces_invalid_upstream_request_id() {
+        let app = Router::new()
+            .route("/health", get(|| async { StatusCode::OK }))
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let long_id = "x".repeat(MAX_REQUEST_ID_LEN + 1);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/health")
+                    .header("X-Request-ID", long_id.as_str())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let id = response.headers().get("X-Request-ID").unwrap().to_str().unwrap();
+        assert_ne!(id, long_id);
+        assert_eq!(id.len(), 36);
+    }
+
+    #[tokio::test]
+    async fn api_errors_include_request_id() {
+        let app = Router::new()
+            .route(
+                "/missing",
+                get(|| async {
+                    Err::<(), _>(crate::error::ApiError::not_found("NOT_FOUND", "missing"))
+                }),
+            )
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/missing")
+                    .header("X-Request-ID", "req-123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["request_id"], "req-123");
+    }
+
     #[tokio::test]
     async fn middleware_preserves_upstream_request_id() {
         let app = Router::new()
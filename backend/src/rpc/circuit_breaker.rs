@@ -4,6 +4,7 @@
 //! fail fast. After a timeout, the circuit moves to half-open and allows
 //! a limited number of test requests; success closes the circuit.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
@@ -153,6 +154,43 @@ impl CircuitBreaker {
     }
 }
 
+/// Circuit breakers keyed by destination, created on first use, so a
+/// failing host trips only its own breaker.
+pub struct CircuitBreakerRegistry {
+    config: CircuitBreakerConfig,
+    breakers: std::sync::Mutex<HashMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Breaker for `destination`, shared by every caller naming it
+    pub fn get(&self, destination: &str) -> Arc<CircuitBreaker> {
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        let breaker = breakers
+            .entry(destination.to_string())
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(self.config.clone(), destination)));
+        Arc::clone(breaker)
+    }
+
+    /// Number of destinations with a breaker
+    pub fn len(&self) -> usize {
+        self.breakers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +271,25 @@ mod tests {
         let r3 = cb.call(|| async { Ok(3) }).await;
         assert_eq!(r3.unwrap(), 3);
     }
+
+    #[tokio::test]
+    async fn registry_isolates_destinations() {
+        let registry = CircuitBreakerRegistry::new(test_config());
+        let failing = registry.get("anchor.example:443");
+        for _ in 0..2 {
+            let _: Result<(), _> = failing
+                .call(|| async { Err(RpcError::ServerError { status: 503, message: "x".into() }) })
+                .await;
+        }
+
+        // The same destination shares the open breaker
+        let again = registry.get("anchor.example:443");
+        assert!(matches!(
+            again.call(|| async { Ok(()) }).await,
+            Err(RpcError::CircuitBreakerOpen)
+        ));
+        let other = registry.get("api.coingecko.com:443");
+        assert_eq!(other.call(|| async { Ok(1) }).await.unwrap(), 1);
+        assert_eq!(registry.len(), 2);
+    }
 }
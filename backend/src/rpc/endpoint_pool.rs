@@ -0,0 +1,230 @@
+//! Pool of Horizon endpoints with latency-based selection.
+//!
+//! Operators can list several Horizon URLs (typically their own instance plus
+//! a public fallback). Each request goes to the endpoint with the best recent
+//! latency and error rate; an endpoint that keeps failing is benched for a
+//! cooldown before it is tried again.
+
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use super::metrics;
+
+/// Weight of the newest sample in the moving averages
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Consecutive failures after which an endpoint is benched
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// How long a benched endpoint is skipped
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Latency penalty per unit of error rate; an endpoint failing half its
+/// requests ranks like one three times slower
+const ERROR_PENALTY: f64 = 4.0;
+
+#[derive(Debug)]
+struct EndpointState {
+    url: String,
+    /// Moving average latency; `None` until the first response
+    latency_ms: Option<f64>,
+    /// Moving average of failures (0-1)
+    error_rate: f64,
+    consecutive_failures: u32,
+    benched_until: Option<Instant>,
+    requests: u64,
+    failures: u64,
+}
+
+impl EndpointState {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            latency_ms: None,
+            error_rate: 0.0,
+            consecutive_failures: 0,
+            benched_until: None,
+            requests: 0,
+            failures: 0,
+        }
+    }
+
+    /// Lower is better. Unmeasured endpoints score zero so each is probed once.
+    fn score(&self) -> f64 {
+        self.latency_ms.unwrap_or(0.0) * (1.0 + ERROR_PENALTY * self.error_rate)
+    }
+
+    fn is_benched(&self, now: Instant) -> bool {
+        self.benched_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Point-in-time view of one endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct HorizonEndpointMetrics {
+    pub url: String,
+    pub avg_latency_ms: Option<f64>,
+    pub error_rate: f64,
+    pub requests: u64,
+    pub failures: u64,
+    pub healthy: bool,
+}
+
+/// Shared by every clone of a client
+#[derive(Debug, Clone)]
+pub struct HorizonEndpointPool {
+    endpoints: Arc<Mutex<Vec<EndpointState>>>,
+}
+
+impl HorizonEndpointPool {
+    pub fn new(urls: Vec<String>) -> Self {
+        let mut unique: Vec<String> = Vec::new();
+        for url in urls {
+            let url = url.trim().trim_end_matches('/').to_string();
+            if !url.is_empty() && !unique.contains(&url) {
+                unique.push(url);
+            }
+        }
+
+        Self {
+            endpoints: Arc::new(Mutex::new(
+                unique.into_iter().map(EndpointState::new).collect(),
+            )),
+        }
+    }
+
+    /// Endpoint URLs in the order they were listed
+    pub fn urls(&self) -> Vec<String> {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| e.url.clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Base URL for the next request. Benched endpoints are skipped unless
+    /// every endpoint is benched, in which case the one freed soonest wins.
+    pub fn select(&self) -> String {
+        let endpoints = self.endpoints.lock().unwrap();
+        let now = Instant::now();
+
+        endpoints
+            .iter()
+            .filter(|e| !e.is_benched(now))
+            .min_by(|a, b| a.score().total_cmp(&b.score()))
+            .or_else(|| endpoints.iter().min_by_key(|e| e.benched_until))
+            .map(|e| e.url.clone())
+            .unwrap_or_default()
+    }
+
+    /// Record the outcome of a request sent to `url`
+    pub fn record(&self, url: &str, latency: Duration, success: bool) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let Some(endpoint) = endpoints.iter_mut().find(|e| e.url == url) else {
+            return;
+        };
+
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        endpoint.requests += 1;
+        endpoint.latency_ms = Some(match endpoint.latency_ms {
+            Some(avg) => avg + EWMA_ALPHA * (latency_ms - avg),
+            None => latency_ms,
+        });
+        let failed = if success { 0.0 } else { 1.0 };
+        endpoint.error_rate += EWMA_ALPHA * (failed - endpoint.error_rate);
+
+        if success {
+            endpoint.consecutive_failures = 0;
+            endpoint.benched_until = None;
+        } else {
+            endpoint.failures += 1;
+            endpoint.consecutive_failures += 1;
+            if endpoint.consecutive_failures >= FAILURE_THRESHOLD {
+                endpoint.benched_until = Some(Instant::now() + COOLDOWN);
+            }
+        }
+
+        metrics::record_horizon_request(url, latency_ms, success);
+    }
+
+    pub fn metrics(&self) -> Vec<HorizonEndpointMetrics> {
+        let now = Instant::now();
+        self.endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| HorizonEndpointMetrics {
+                url: e.url.clone(),
+                avg_latency_ms: e.latency_ms,
+                error_rate: e.error_rate,
+                requests: e.requests,
+                failures: e.failures,
+                healthy: !e.is_benched(now),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> HorizonEndpointPool {
+        HorizonEndpointPool::new(vec![
+            "https://horizon.internal/".to_string(),
+            "https://horizon.stellar.org".to_string(),
+            "https://horizon.internal".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_prefers_faster_endpoint() {
+        let pool = pool();
+        assert_eq!(pool.len(), 2);
+
+        pool.record("https://horizon.internal", Duration::from_millis(40), true);
+        pool.record(
+            "https://horizon.stellar.org",
+            Duration::from_millis(300),
+            true,
+        );
+        assert_eq!(pool.select(), "https://horizon.internal");
+
+        pool.record("https://horizon.internal", Duration::from_millis(900), true);
+        pool.record("https://horizon.internal", Duration::from_millis(900), true);
+        pool.record("https://horizon.internal", Duration::from_millis(900), true);
+        assert_eq!(pool.select(), "https://horizon.stellar.org");
+    }
+
+    #[test]
+    fn test_failing_endpoint_is_benched() {
+        let pool = pool();
+        pool.record("https://horizon.internal", Duration::from_millis(10), true);
+        pool.record(
+            "https://horizon.stellar.org",
+            Duration::from_millis(200),
+            true,
+        );
+
+        for _ in 0..FAILURE_THRESHOLD {
+            pool.record("https://horizon.internal", Duration::from_millis(10), false);
+        }
+        assert_eq!(pool.select(), "https://horizon.stellar.org");
+
+        let metrics = pool.metrics();
+        assert!(!metrics[0].healthy);
+        assert_eq!(metrics[0].failures, u64::from(FAILURE_THRESHOLD));
+        assert!(metrics[1].healthy);
+    }
+}
@@ -1,7 +1,10 @@
 //! Prometheus metrics for RPC error rates and circuit breaker state.
 
 use lazy_static::lazy_static;
-use prometheus::{register_int_counter_vec, register_int_gauge_vec, IntCounterVec, IntGaugeVec};
+use prometheus::{
+    register_gauge_vec, register_int_counter_vec, register_int_gauge_vec, GaugeVec, IntCounterVec,
+    IntGaugeVec,
+};
 
 lazy_static! {
     static ref RPC_ERRORS: IntCounterVec = register_int_counter_vec!(
@@ -17,6 +20,20 @@ lazy_static! {
         &["endpoint"]
     )
     .expect("circuit_breaker_state metric");
+
+    static ref HORIZON_ENDPOINT_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "horizon_endpoint_requests_total",
+        "Requests sent to each Horizon endpoint by outcome",
+        &["endpoint", "outcome"]
+    )
+    .expect("horizon_endpoint_requests_total metric");
+
+    static ref HORIZON_ENDPOINT_LATENCY: GaugeVec = register_gauge_vec!(
+        "horizon_endpoint_latency_ms",
+        "Latency of the most recent request to each Horizon endpoint",
+        &["endpoint"]
+    )
+    .expect("horizon_endpoint_latency_ms metric");
 }
 
 /// Record an RPC error for metrics.
@@ -32,3 +49,14 @@ pub fn set_circuit_breaker_state(endpoint: &str, state: i64) {
         .with_label_values(&[endpoint])
         .set(state);
 }
+
+/// Record a request to one Horizon endpoint of the pool.
+pub fn record_horizon_request(endpoint: &str, latency_ms: f64, success: bool) {
+    let outcome = if success { "success" } else { "failure" };
+    HORIZON_ENDPOINT_REQUESTS
+        .with_label_values(&[endpoint, outcome])
+        .inc();
+    HORIZON_ENDPOINT_LATENCY
+        .with_label_values(&[endpoint])
+        .set(latency_ms);
+}
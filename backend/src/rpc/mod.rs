@@ -1,14 +1,16 @@
 pub mod circuit_breaker;
 pub mod config;
+pub mod endpoint_pool;
 pub mod error;
 pub mod metrics;
 pub mod rate_limiter;
 pub mod stellar;
 
-pub use rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+pub use endpoint_pool::{HorizonEndpointMetrics, HorizonEndpointPool};
+pub use rate_limiter::{RequestPriority, RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
 pub use stellar::{
-    Asset, FeeBumpTransactionInfo, GetLedgersResult, HealthResponse, HorizonAsset, HorizonEffect,
-    HorizonLiquidityPool, HorizonOperation, HorizonPoolReserve, HorizonTransaction,
-    InnerTransaction, LedgerInfo, OrderBook, OrderBookEntry, Payment, Price, RpcLedger,
-    StellarRpcClient, Trade,
+    FeeBumpTransactionInfo, FeeDistribution, FeeStats, GetLedgersResult, HealthResponse,
+    HorizonAccount, HorizonAsset, HorizonBalance, HorizonEffect, HorizonLiquidityPool,
+    HorizonOperation, HorizonPoolReserve, HorizonTransaction, InnerTransaction, LedgerInfo,
+    OrderBook, OrderBookEntry, Payment, PaymentPath, Price, RpcLedger, StellarRpcClient, Trade,
 };
@@ -8,11 +8,39 @@ use tokio::time::Instant;
 
 const DEFAULT_RETRY_AFTER_SECONDS: u64 = 5;
 
+/// Who a Horizon/RPC call is made for. All callers share one budget; lower
+/// classes only spend tokens while enough are left over for the classes above
+/// them, so background jobs yield as the budget nears exhaustion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RequestPriority {
+    /// Ingestion, backfills and periodic analyzers
+    Background,
+    /// Monitoring: probes, alerting and live broadcasts
+    Standard,
+    /// Serving an API request
+    #[default]
+    Interactive,
+}
+
+impl RequestPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Background => "background",
+            Self::Standard => "standard",
+            Self::Interactive => "interactive",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RpcRateLimitConfig {
     pub requests_per_minute: f64,
     pub burst_size: f64,
     pub queue_size: usize,
+    /// Fraction of the bucket that standard requests leave for interactive ones
+    pub standard_reserve: f64,
+    /// Fraction of the bucket that background requests leave for everyone else
+    pub background_reserve: f64,
 }
 
 impl Default for RpcRateLimitConfig {
@@ -21,36 +49,8 @@ impl Default for RpcRateLimitConfig {
             requests_per_minute: 90.0,
             burst_size: 10.0,
             queue_size: 100,
-        }
-    }
-}
-
-impl RpcRateLimitConfig {
-    pub fn from_env() -> Self {
-        let default = Self::default();
-
-        let requests_per_minute = std::env::var("RPC_RATE_LIMIT_REQUESTS_PER_MINUTE")
-            .ok()
-            .and_then(|v| v.parse::<f64>().ok())
-            .filter(|v| *v > 0.0)
-            .unwrap_or(default.requests_per_minute);
-
-        let burst_size = std::env::var("RPC_RATE_LIMIT_BURST_SIZE")
-            .ok()
-            .and_then(|v| v.parse::<f64>().ok())
-            .filter(|v| *v > 0.0)
-            .unwrap_or(default.burst_size);
-
-        let queue_size = std::env::var("RPC_RATE_LIMIT_QUEUE_SIZE")
-            .ok()
-            .and_then(|v| v.parse::<usize>().ok())
-            .filter(|v| *v > 0)
-            .unwrap_or(default.queue_size);
-
-        Self {
-            requests_per_minute,
-            burst_size,
-            queue_size,
+            standard_reserve: 0.2,
+            background_reserve: 0.5,
         }
     }
 }
@@ -61,6 +61,8 @@ pub struct RpcRateLimitMetrics {
     pub throttled_requests: u64,
     pub rejected_requests: u64,
     pub rate_limited_responses: u64,
+    /// Standard and background requests held back to keep tokens for higher classes
+    pub deferred_requests: u64,
 }
 
 #[derive(Debug)]
@@ -90,10 +92,13 @@ struct TokenBucketState {
 pub struct RpcRateLimiter {
     state: Arc<Mutex<TokenBucketState>>,
     queue: Arc<Semaphore>,
+    standard_reserve: f64,
+    background_reserve: f64,
     total_requests: Arc<AtomicU64>,
     throttled_requests: Arc<AtomicU64>,
     rejected_requests: Arc<AtomicU64>,
     rate_limited_responses: Arc<AtomicU64>,
+    deferred_requests: Arc<AtomicU64>,
 }
 
 pub struct QueuePermit {
@@ -113,14 +118,36 @@ impl RpcRateLimiter {
                 last_refill: Instant::now(),
             })),
             queue: Arc::new(Semaphore::new(config.queue_size)),
+            standard_reserve: config.standard_reserve.clamp(0.0, 1.0),
+            background_reserve: config.background_reserve.clamp(0.0, 1.0),
             total_requests: Arc::new(AtomicU64::new(0)),
             throttled_requests: Arc::new(AtomicU64::new(0)),
             rejected_requests: Arc::new(AtomicU64::new(0)),
             rate_limited_responses: Arc::new(AtomicU64::new(0)),
+            deferred_requests: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Acquire a token for an interactive request
     pub async fn acquire(&self) -> Result<QueuePermit, RpcRateLimitError> {
+        self.acquire_with_priority(RequestPriority::Interactive).await
+    }
+
+    /// Tokens that must be in the bucket before a request of `priority` may
+    /// take one. Never more than the bucket holds, so every class progresses.
+    fn required_tokens(&self, priority: RequestPriority, capacity: f64) -> f64 {
+        let reserve = match priority {
+            RequestPriority::Interactive => 0.0,
+            RequestPriority::Standard => self.standard_reserve,
+            RequestPriority::Background => self.background_reserve,
+        };
+        (capacity * reserve + 1.0).min(capacity).max(1.0)
+    }
+
+    pub async fn acquire_with_priority(
+        &self,
+        priority: RequestPriority,
+    ) -> Result<QueuePermit, RpcRateLimitError> {
         self.total_requests.fetch_add(1, Ordering::Relaxed);
 
         let permit = self.queue.clone().try_acquire_owned().map_err(|_| {
@@ -132,13 +159,20 @@ impl RpcRateLimiter {
             let wait_time = {
                 let mut state = self.state.lock().await;
                 Self::refill_locked(&mut state);
+                let required = self.required_tokens(priority, state.capacity);
 
-                if state.tokens >= 1.0 {
+                if state.tokens >= required {
                     state.tokens -= 1.0;
                     Duration::from_secs(0)
                 } else {
-                    self.throttled_requests.fetch_add(1, Ordering::Relaxed);
-                    let seconds = ((1.0 - state.tokens) / state.refill_rate_per_second).max(0.001);
+                    if state.tokens >= 1.0 {
+                        // A token is free, but it is held for higher classes
+                        self.deferred_requests.fetch_add(1, Ordering::Relaxed);
+                    } else {
+                        self.throttled_requests.fetch_add(1, Ordering::Relaxed);
+                    }
+                    let seconds =
+                        ((required - state.tokens) / state.refill_rate_per_second).max(0.001);
                     Duration::from_secs_f64(seconds)
                 }
             };
@@ -199,6 +233,7 @@ impl RpcRateLimiter {
             throttled_requests: self.throttled_requests.load(Ordering::Relaxed),
             rejected_requests: self.rejected_requests.load(Ordering::Relaxed),
             rate_limited_responses: self.rate_limited_responses.load(Ordering::Relaxed),
+            deferred_requests: self.deferred_requests.load(Ordering::Relaxed),
         }
     }
 
@@ -249,6 +284,7 @@ mod tests {
             requests_per_minute: 60.0,
             burst_size: 1.0,
             queue_size: 10,
+            ..RpcRateLimitConfig::default()
         });
 
         limiter.acquire().await.unwrap();
@@ -263,6 +299,7 @@ mod tests {
             requests_per_minute: 60.0,
             burst_size: 0.1,
             queue_size: 1,
+            ..RpcRateLimitConfig::default()
         });
 
         let limiter_clone = limiter.clone();
@@ -314,6 +351,7 @@ mod tests {
             requests_per_minute: 60.0,
             burst_size: 0.1,
             queue_size: 1,
+            ..RpcRateLimitConfig::default()
         });
 
         let limiter_clone = limiter.clone();
@@ -331,6 +369,55 @@ mod tests {
         holder.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn background_requests_yield_to_interactive() {
+        let limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            requests_per_minute: 60.0,
+            burst_size: 4.0,
+            queue_size: 10,
+            ..RpcRateLimitConfig::default()
+        });
+
+        // With the bucket full, background traffic may spend down to half of it
+        limiter
+            .acquire_with_priority(RequestPriority::Background)
+            .await
+            .unwrap();
+        limiter
+            .acquire_with_priority(RequestPriority::Background)
+            .await
+            .unwrap();
+
+        // ...leaving the remaining tokens to interactive requests
+        let start = Instant::now();
+        limiter.acquire().await.unwrap();
+        limiter.acquire().await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        let background = limiter.clone();
+        let waiting = tokio::spawn(async move {
+            background
+                .acquire_with_priority(RequestPriority::Background)
+                .await
+                .unwrap();
+        });
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        assert!(!waiting.is_finished());
+        waiting.abort();
+    }
+
+    #[test]
+    fn reserve_never_exceeds_capacity() {
+        let limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            burst_size: 1.0,
+            ..RpcRateLimitConfig::default()
+        });
+        assert_eq!(limiter.required_tokens(RequestPriority::Background, 1.0), 1.0);
+        assert_eq!(limiter.required_tokens(RequestPriority::Interactive, 10.0), 1.0);
+        assert_eq!(limiter.required_tokens(RequestPriority::Standard, 10.0), 3.0);
+        assert_eq!(limiter.required_tokens(RequestPriority::Background, 10.0), 6.0);
+    }
+
     #[test]
     fn retry_after_parses_http_date_format() {
         let retry_at = chrono::DateTime::<chrono::Utc>::from(SystemTime::now() + Duration::from_secs(2));
@@ -4,15 +4,19 @@ use crate::rpc::config::{
     max_retries_from_env,
 };
 use crate::rpc::circuit_breaker::CircuitBreaker;
+use crate::rpc::endpoint_pool::{HorizonEndpointMetrics, HorizonEndpointPool};
 use crate::rpc::error::{with_retry, RetryConfig, RpcError};
 use crate::rpc::metrics;
-use crate::rpc::rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+use crate::rpc::rate_limiter::{
+    RequestPriority, RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter,
+};
 use anyhow::{anyhow, Context, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use stellar_insights_core::Asset;
 use tracing::{debug, info, warn};
 
 const MAX_RETRIES: u32 = 3;
@@ -54,6 +58,36 @@ pub struct AssetBalances {
     pub unauthorized: String,
 }
 
+/// Account record from Horizon `/accounts`, reduced to its balances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonAccount {
+    pub id: String,
+    pub paging_token: String,
+    pub balances: Vec<HorizonBalance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonBalance {
+    pub balance: String,
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+}
+
+impl HorizonAccount {
+    /// Balance of an issued asset, or 0 without a trustline
+    pub fn balance_of(&self, asset_code: &str, asset_issuer: &str) -> f64 {
+        self.balances
+            .iter()
+            .find(|b| {
+                b.asset_code.as_deref() == Some(asset_code)
+                    && b.asset_issuer.as_deref() == Some(asset_issuer)
+            })
+            .and_then(|b| b.balance.parse().ok())
+            .unwrap_or(0.0)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetFlags {
     pub auth_required: bool,
@@ -66,10 +100,14 @@ pub struct AssetFlags {
 pub struct StellarRpcClient {
     client: Client,
     rpc_url: String,
-    horizon_url: String,
+    /// Horizon endpoints requests are spread across, shared by every clone
+    horizon_pool: HorizonEndpointPool,
     network_config: NetworkConfig,
     mock_mode: bool,
+    /// Shared by every clone, so all callers draw on one Horizon budget
     rate_limiter: RpcRateLimiter,
+    /// Budget class this handle's requests are charged to
+    priority: RequestPriority,
     circuit_breaker: Arc<CircuitBreaker>,
     /// Maximum records per single request (default: 200)
 
@@ -119,16 +157,60 @@ pub struct JsonRpcError {
 pub struct LedgerInfo {
     pub sequence: u64,
     pub hash: String,
+    #[serde(alias = "prev_hash")]
     pub previous_hash: String,
+    #[serde(alias = "successful_transaction_count")]
     pub transaction_count: u32,
     pub operation_count: u32,
     pub closed_at: String,
     pub total_coins: String,
     pub fee_pool: String,
+    #[serde(alias = "base_fee_in_stroops")]
     pub base_fee: u32,
+    /// Not part of Horizon's ledger record, which reports `base_reserve_in_stroops`
+    #[serde(default)]
     pub base_reserve: String,
 }
 
+/// Horizon `/fee_stats`: fees are stroop amounts encoded as strings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeStats {
+    pub last_ledger: String,
+    pub last_ledger_base_fee: String,
+    /// Fraction (0-1) of the last ledgers' capacity that was used
+    pub ledger_capacity_usage: String,
+    pub fee_charged: FeeDistribution,
+    pub max_fee: FeeDistribution,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeDistribution {
+    pub max: String,
+    pub min: String,
+    pub mode: String,
+    pub p50: String,
+    pub p90: String,
+    pub p95: String,
+    pub p99: String,
+}
+
+impl FeeStats {
+    pub fn base_fee(&self) -> u64 {
+        self.last_ledger_base_fee.parse().unwrap_or(100)
+    }
+
+    pub fn capacity_usage(&self) -> f64 {
+        self.ledger_capacity_usage.parse().unwrap_or(0.0)
+    }
+}
+
+impl FeeDistribution {
+    /// Parse a percentile field, treating malformed values as zero
+    pub fn stroops(value: &str) -> u64 {
+        value.parse().unwrap_or(0)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Payment {
     pub id: String,
@@ -156,7 +238,27 @@ pub struct Payment {
     pub to: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Payment {
+    /// Asset received, `None` for pool shares or incomplete records
+    pub fn asset(&self) -> Option<Asset> {
+        Asset::from_horizon_fields(
+            &self.asset_type,
+            self.asset_code.as_deref(),
+            self.asset_issuer.as_deref(),
+        )
+    }
+
+    /// Asset sent by a path payment
+    pub fn source_asset(&self) -> Option<Asset> {
+        Asset::from_horizon_fields(
+            self.source_asset_type.as_deref()?,
+            self.source_asset_code.as_deref(),
+            self.source_asset_issuer.as_deref(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HorizonOperation {
     pub id: String,
     pub paging_token: String,
@@ -168,6 +270,16 @@ pub struct HorizonOperation {
     pub account: Option<String>,
     pub into: Option<String>,
     pub amount: Option<String>,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    // Account clawed back from (clawback)
+    pub from: Option<String>,
+    // Trustline owner (set_trust_line_flags)
+    pub trustor: Option<String>,
+    #[serde(default)]
+    pub set_flags_s: Vec<String>,
+    #[serde(default)]
+    pub clear_flags_s: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -200,6 +312,9 @@ pub struct HorizonTransaction {
     pub fee_bump_transaction: Option<FeeBumpTransactionInfo>,
     #[serde(rename = "inner_transaction")]
     pub inner_transaction: Option<InnerTransaction>,
+    /// Absent for transactions without a memo
+    #[serde(default)]
+    pub memo: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -234,6 +349,24 @@ pub struct Trade {
     pub trade_type: String,
 }
 
+impl Trade {
+    pub fn base_asset(&self) -> Option<Asset> {
+        Asset::from_horizon_fields(
+            &self.base_asset_type,
+            self.base_asset_code.as_deref(),
+            self.base_asset_issuer.as_deref(),
+        )
+    }
+
+    pub fn counter_asset(&self) -> Option<Asset> {
+        Asset::from_horizon_fields(
+            &self.counter_asset_type,
+            self.counter_asset_code.as_deref(),
+            self.counter_asset_issuer.as_deref(),
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Price {
     pub n: i64,
@@ -255,11 +388,19 @@ pub struct OrderBookEntry {
     pub price_r: Price,
 }
 
+/// A route found by Horizon's path finding
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Asset {
-    pub asset_type: String,
-    pub asset_code: Option<String>,
-    pub asset_issuer: Option<String>,
+pub struct PaymentPath {
+    pub source_asset_type: String,
+    pub source_asset_code: Option<String>,
+    pub source_asset_issuer: Option<String>,
+    pub source_amount: String,
+    pub destination_asset_type: String,
+    pub destination_asset_code: Option<String>,
+    pub destination_asset_issuer: Option<String>,
+    pub destination_amount: String,
+    // Intermediate assets, excluding source and destination
+    pub path: Vec<Asset>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -369,14 +510,15 @@ impl StellarRpcClient {
     ///
     /// # Arguments
     /// * `rpc_url` - The Stellar RPC endpoint URL (e.g., OnFinality)
-    /// * `horizon_url` - The Horizon API endpoint URL
+    /// * `horizon_url` - The primary Horizon API endpoint URL; more can be
+    ///   added with [`Self::with_horizon_fallbacks`]
     /// * `mock_mode` - If true, returns mock data instead of making real API calls
     pub fn new(rpc_url: String, horizon_url: String, mock_mode: bool) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to build HTTP client");
-        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::from_env());
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::default());
         
         // Determine network based on URLs
         let network = if horizon_url.contains("testnet") {
@@ -408,10 +550,11 @@ impl StellarRpcClient {
         Self {
             client,
             rpc_url,
-            horizon_url,
+            horizon_pool: HorizonEndpointPool::new(vec![horizon_url]),
             network_config,
             mock_mode,
             rate_limiter,
+            priority: RequestPriority::default(),
             circuit_breaker,
             max_records_per_request,
             max_total_records,
@@ -430,7 +573,7 @@ impl StellarRpcClient {
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to build HTTP client");
-        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::from_env());
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::default());
         let cb_config = circuit_breaker_config_from_env();
         let circuit_breaker = Arc::new(CircuitBreaker::new(cb_config, "rpc"));
 
@@ -453,10 +596,11 @@ impl StellarRpcClient {
         Self {
             client,
             rpc_url: network_config.rpc_url.clone(),
-            horizon_url: network_config.horizon_url.clone(),
+            horizon_pool: HorizonEndpointPool::new(vec![network_config.horizon_url.clone()]),
             network_config,
             mock_mode,
             rate_limiter,
+            priority: RequestPriority::default(),
             circuit_breaker,
             max_records_per_request,
             max_total_records,
@@ -497,6 +641,39 @@ impl StellarRpcClient {
         self.rate_limiter.metrics()
     }
 
+    /// A handle sharing this client's budget whose requests are charged to
+    /// `priority`; give background jobs `RequestPriority::Background`
+    pub fn with_priority(&self, priority: RequestPriority) -> Self {
+        Self {
+            priority,
+            ..self.clone()
+        }
+    }
+
+    pub fn priority(&self) -> RequestPriority {
+        self.priority
+    }
+
+    /// Replace the default outbound budget; every handle derived from the
+    /// returned client shares the new limiter
+    pub fn with_rate_limit(mut self, config: RpcRateLimitConfig) -> Self {
+        self.rate_limiter = RpcRateLimiter::new(config);
+        self
+    }
+
+    /// Spread Horizon requests across `urls` as well as the primary endpoint
+    pub fn with_horizon_fallbacks(mut self, urls: Vec<String>) -> Self {
+        let mut all = self.horizon_pool.urls();
+        all.extend(urls);
+        self.horizon_pool = HorizonEndpointPool::new(all);
+        self
+    }
+
+    /// Latency and error rate of every configured Horizon endpoint
+    pub fn horizon_endpoint_metrics(&self) -> Vec<HorizonEndpointMetrics> {
+        self.horizon_pool.metrics()
+    }
+
     async fn execute_with_retry<F, Fut, T>(&self, operation: F) -> Result<T, RpcError>
     where
         F: Fn() -> Fut,
@@ -508,8 +685,38 @@ impl StellarRpcClient {
             max_delay_ms: self.max_backoff.as_millis() as u64,
         };
 
-        with_retry(operation, retry_config, self.circuit_breaker.clone()).await
+        with_retry(
+            || async {
+                let _queue_permit = self
+                    .rate_limiter
+                    .acquire_with_priority(self.priority)
+                    .await
+                    .map_err(|_| RpcError::RateLimitError { retry_after: None })?;
+                operation().await
+            },
+            retry_config,
+            self.circuit_breaker.clone(),
+        )
+        .await
+    }
 
+    /// Like `execute_with_retry`, but each attempt is sent to the best Horizon
+    /// endpoint at that moment and its outcome feeds back into the pool
+    async fn execute_horizon<F, Fut, T>(&self, operation: F) -> Result<T, RpcError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: std::future::Future<Output = Result<T, RpcError>>,
+    {
+        self.execute_with_retry(|| async {
+            let horizon_url = self.horizon_pool.select();
+            let start_time = Instant::now();
+            let result = operation(horizon_url.clone()).await;
+            let endpoint_failed = matches!(&result, Err(e) if e.is_retryable());
+            self.horizon_pool
+                .record(&horizon_url, start_time.elapsed(), !endpoint_failed);
+            result
+        })
+        .await
     }
 
     /// Check the health of the RPC endpoint
@@ -570,7 +777,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_ledger_info());
         }
 
-        let result = self.execute_with_retry(|| self.fetch_latest_ledger_internal()).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_latest_ledger_internal(horizon_url)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -578,8 +785,8 @@ impl StellarRpcClient {
         })
     }
 
-    async fn fetch_latest_ledger_internal(&self) -> Result<LedgerInfo, RpcError> {
-        let url = format!("{}/ledgers?order=desc&limit=1", self.horizon_url);
+    async fn fetch_latest_ledger_internal(&self, horizon_url: String) -> Result<LedgerInfo, RpcError> {
+        let url = format!("{}/ledgers?order=desc&limit=1", horizon_url);
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
@@ -594,6 +801,66 @@ impl StellarRpcClient {
             .ok_or_else(|| RpcError::ParseError("No ledger data found".to_string()))
     }
 
+    /// Fetch the most recent closed ledgers from Horizon, newest first
+    pub async fn fetch_recent_ledgers(&self, limit: u32) -> Result<Vec<LedgerInfo>, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_recent_ledgers(limit));
+        }
+
+        let result = self.execute_horizon(|horizon_url| self.fetch_recent_ledgers_internal(horizon_url, limit)).await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_recent_ledgers_internal(
+        &self,
+        horizon_url: String,
+        limit: u32,
+    ) -> Result<Vec<LedgerInfo>, RpcError> {
+        let url = format!("{}/ledgers?order=desc&limit={}", horizon_url, limit);
+        let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<LedgerInfo> = response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    /// Fetch fee percentiles and capacity usage over the last few ledgers
+    pub async fn fetch_fee_stats(&self) -> Result<FeeStats, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_fee_stats());
+        }
+
+        let result = self.execute_horizon(|horizon_url| self.fetch_fee_stats_internal(horizon_url)).await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_fee_stats_internal(&self, horizon_url: String) -> Result<FeeStats, RpcError> {
+        let url = format!("{}/fee_stats", horizon_url);
+        let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))
+    }
+
     /// I'm fetching ledgers via RPC getLedgers for sequential ingestion (issue #2)
     pub async fn fetch_ledgers(
         &self,
@@ -678,7 +945,7 @@ impl StellarRpcClient {
 
         info!("Fetching {} payments from Horizon API", limit);
 
-        let result = self.execute_with_retry(|| self.fetch_payments_internal(limit, cursor)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_payments_internal(horizon_url, limit, cursor)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -688,10 +955,11 @@ impl StellarRpcClient {
 
     async fn fetch_payments_internal(
         &self,
+        horizon_url: String,
         limit: u32,
         cursor: Option<&str>,
     ) -> Result<Vec<Payment>, RpcError> {
-        let mut url = format!("{}/payments?order=desc&limit={}", self.horizon_url, limit);
+        let mut url = format!("{}/payments?order=desc&limit={}", horizon_url, limit);
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
@@ -715,7 +983,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_trades(limit));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_trades_internal(limit, cursor)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_trades_internal(horizon_url, limit, cursor)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -725,10 +993,11 @@ impl StellarRpcClient {
 
     async fn fetch_trades_internal(
         &self,
+        horizon_url: String,
         limit: u32,
         cursor: Option<&str>,
     ) -> Result<Vec<Trade>, RpcError> {
-        let mut url = format!("{}/trades?order=desc&limit={}", self.horizon_url, limit);
+        let mut url = format!("{}/trades?order=desc&limit={}", horizon_url, limit);
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
@@ -757,7 +1026,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_order_book(selling_asset, buying_asset));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_order_book_internal(selling_asset, buying_asset, limit)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_order_book_internal(horizon_url, selling_asset, buying_asset, limit)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -767,15 +1036,16 @@ impl StellarRpcClient {
 
     async fn fetch_order_book_internal(
         &self,
+        horizon_url: String,
         selling_asset: &Asset,
         buying_asset: &Asset,
         limit: u32,
     ) -> Result<OrderBook, RpcError> {
-        let selling_params = Self::asset_to_query_params("selling", selling_asset);
-        let buying_params = Self::asset_to_query_params("buying", buying_asset);
+        let selling_params = selling_asset.horizon_query("selling");
+        let buying_params = buying_asset.horizon_query("buying");
         let url = format!(
             "{}/order_book?{}&{}&limit={}",
-            self.horizon_url, selling_params, buying_params, limit
+            horizon_url, selling_params, buying_params, limit
         );
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
@@ -792,7 +1062,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_payments(5));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_payments_for_ledger_internal(sequence)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_payments_for_ledger_internal(horizon_url, sequence)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -802,9 +1072,10 @@ impl StellarRpcClient {
 
     async fn fetch_payments_for_ledger_internal(
         &self,
+        horizon_url: String,
         sequence: u64,
     ) -> Result<Vec<Payment>, RpcError> {
-        let url = format!("{}/ledgers/{}/payments?limit=200", self.horizon_url, sequence);
+        let url = format!("{}/ledgers/{}/payments?limit=200", horizon_url, sequence);
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
@@ -828,7 +1099,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_transactions(5, sequence));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_transactions_for_ledger_internal(sequence)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_transactions_for_ledger_internal(horizon_url, sequence)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -838,11 +1109,12 @@ impl StellarRpcClient {
 
     async fn fetch_transactions_for_ledger_internal(
         &self,
+        horizon_url: String,
         sequence: u64,
     ) -> Result<Vec<HorizonTransaction>, RpcError> {
         let url = format!(
             "{}/ledgers/{}/transactions?limit=200&include_failed=true",
-            self.horizon_url, sequence
+            horizon_url, sequence
         );
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
@@ -867,7 +1139,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_operations_for_ledger(sequence));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_operations_for_ledger_internal(sequence)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_operations_for_ledger_internal(horizon_url, sequence)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -877,9 +1149,10 @@ impl StellarRpcClient {
 
     async fn fetch_operations_for_ledger_internal(
         &self,
+        horizon_url: String,
         sequence: u64,
     ) -> Result<Vec<HorizonOperation>, RpcError> {
-        let url = format!("{}/ledgers/{}/operations?limit=200", self.horizon_url, sequence);
+        let url = format!("{}/ledgers/{}/operations?limit=200", horizon_url, sequence);
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
@@ -903,7 +1176,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_effects_for_operation(operation_id));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_operation_effects_internal(operation_id)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_operation_effects_internal(horizon_url, operation_id)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -913,11 +1186,12 @@ impl StellarRpcClient {
 
     async fn fetch_operation_effects_internal(
         &self,
+        horizon_url: String,
         operation_id: &str,
     ) -> Result<Vec<HorizonEffect>, RpcError> {
         let url = format!(
             "{}/operations/{}/effects?limit=200",
-            self.horizon_url, operation_id
+            horizon_url, operation_id
         );
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
@@ -943,7 +1217,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_payments(limit));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_account_payments_internal(account_id, limit)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_account_payments_internal(horizon_url, account_id, limit)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -953,12 +1227,13 @@ impl StellarRpcClient {
 
     async fn fetch_account_payments_internal(
         &self,
+        horizon_url: String,
         account_id: &str,
         limit: u32,
     ) -> Result<Vec<Payment>, RpcError> {
         let url = format!(
             "{}/accounts/{}/payments?order=desc&limit={}",
-            self.horizon_url, account_id, limit
+            horizon_url, account_id, limit
         );
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
@@ -1147,17 +1422,19 @@ impl StellarRpcClient {
         while fetched < max_records {
             let limit = std::cmp::min(self.max_records_per_request, max_records - fetched);
 
-            let mut url = format!(
-                "{}/accounts/{}/payments?order=desc&limit={}",
-                self.horizon_url, account_id, limit
+            let mut path = format!(
+                "/accounts/{}/payments?order=desc&limit={}",
+                account_id, limit
             );
 
             if let Some(ref cursor_val) = cursor {
-                url.push_str(&format!("&cursor={}", cursor_val));
+                path.push_str(&format!("&cursor={}", cursor_val));
             }
 
             let response = self
-                .retry_request(|| async { self.client.get(&url).send().await })
+                .retry_request(|horizon_url| {
+                    self.client.get(format!("{}{}", horizon_url, path)).send()
+                })
                 .await
                 .context("Failed to fetch account payments page")?;
 
@@ -1213,27 +1490,11 @@ impl StellarRpcClient {
     // Helper Methods
     // ============================================================================
 
-    /// Convert asset to query parameters for Horizon API
-    fn asset_to_query_params(prefix: &str, asset: &Asset) -> String {
-        if asset.asset_type == "native" {
-            format!("{}_asset_type=native", prefix)
-        } else {
-            format!(
-                "{}_asset_type={}&{}_asset_code={}&{}_asset_issuer={}",
-                prefix,
-                asset.asset_type,
-                prefix,
-                asset.asset_code.as_ref().unwrap(),
-                prefix,
-                asset.asset_issuer.as_ref().unwrap()
-            )
-        }
-    }
-
-    /// Retry a request with exponential backoff
+    /// Retry a request with exponential backoff. `request_fn` is given the
+    /// Horizon endpoint to send each attempt to.
     async fn retry_request<F, Fut>(&self, request_fn: F) -> Result<reqwest::Response>
     where
-        F: Fn() -> Fut,
+        F: Fn(String) -> Fut,
         Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
     {
         let retry_config = RetryConfig {
@@ -1244,17 +1505,29 @@ impl StellarRpcClient {
 
         with_retry(
             || async {
-                let queue_permit = self.rate_limiter.acquire().await.map_err(|_| {
-                    RpcError::RateLimitError { retry_after: None }
-                })?;
+                let queue_permit = self
+                    .rate_limiter
+                    .acquire_with_priority(self.priority)
+                    .await
+                    .map_err(|_| RpcError::RateLimitError { retry_after: None })?;
 
+                let horizon_url = self.horizon_pool.select();
                 let start_time = Instant::now();
-                let response = request_fn()
-                    .await
-                    .map_err(|e| RpcError::categorize(&e.to_string()))?;
+                let response = match request_fn(horizon_url.clone()).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        self.horizon_pool
+                            .record(&horizon_url, start_time.elapsed(), false);
+                        return Err(RpcError::categorize(&e.to_string()));
+                    }
+                };
                 let elapsed = start_time.elapsed().as_millis();
                 let status = response.status();
                 let headers = response.headers().clone();
+                let endpoint_failed = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    || status.is_server_error();
+                self.horizon_pool
+                    .record(&horizon_url, start_time.elapsed(), !endpoint_failed);
 
                 drop(queue_permit);
                 self.rate_limiter.observe_headers(&headers).await;
@@ -1336,6 +1609,39 @@ impl StellarRpcClient {
         }
     }
 
+    fn mock_recent_ledgers(limit: u32) -> Vec<LedgerInfo> {
+        let latest = Self::mock_ledger_info();
+        let closed_at = chrono::DateTime::parse_from_rfc3339(&latest.closed_at)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now());
+        (0..limit as u64)
+            .map(|i| LedgerInfo {
+                sequence: latest.sequence - i,
+                closed_at: (closed_at - chrono::Duration::seconds(5 * i as i64)).to_rfc3339(),
+                ..latest.clone()
+            })
+            .collect()
+    }
+
+    fn mock_fee_stats() -> FeeStats {
+        let distribution = |p50: &str, p90: &str| FeeDistribution {
+            max: "10000".to_string(),
+            min: "100".to_string(),
+            mode: "100".to_string(),
+            p50: p50.to_string(),
+            p90: p90.to_string(),
+            p95: p90.to_string(),
+            p99: p90.to_string(),
+        };
+        FeeStats {
+            last_ledger: MOCK_LATEST_LEDGER.to_string(),
+            last_ledger_base_fee: "100".to_string(),
+            ledger_capacity_usage: "0.45".to_string(),
+            fee_charged: distribution("100", "100"),
+            max_fee: distribution("1000", "5000"),
+        }
+    }
+
     // I'm mocking getLedgers response for testing
     fn mock_get_ledgers(start: u64, limit: u32) -> GetLedgersResult {
         if start > MOCK_LATEST_LEDGER {
@@ -1569,6 +1875,7 @@ impl StellarRpcClient {
                     } else {
                         None
                     },
+                    memo: None,
                 }
             })
             .collect()
@@ -1579,6 +1886,8 @@ impl StellarRpcClient {
         let source_b = "GBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".to_string();
         let dest_a = "GDESTAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string();
         let dest_b = "GDESTBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".to_string();
+        let usdc_issuer = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let holder = "GHOLDERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string();
 
         vec![
             HorizonOperation {
@@ -1591,6 +1900,7 @@ impl StellarRpcClient {
                 account: Some(source_a),
                 into: Some(dest_a),
                 amount: None,
+                ..Default::default()
             },
             HorizonOperation {
                 id: format!("op_{}_1", sequence),
@@ -1603,6 +1913,7 @@ impl StellarRpcClient {
                 account: None,
                 into: None,
                 amount: Some("25.0000000".to_string()),
+                ..Default::default()
             },
             HorizonOperation {
                 id: format!("op_{}_2", sequence),
@@ -1614,6 +1925,33 @@ impl StellarRpcClient {
                 account: Some(source_b),
                 into: Some(dest_b),
                 amount: None,
+                ..Default::default()
+            },
+            HorizonOperation {
+                id: format!("op_{}_3", sequence),
+                paging_token: format!("pt_{}_3", sequence),
+                transaction_hash: format!("txhash_{}_3", sequence),
+                source_account: usdc_issuer.to_string(),
+                operation_type: "clawback".to_string(),
+                created_at: "2026-01-22T10:33:00Z".to_string(),
+                amount: Some("40.0000000".to_string()),
+                asset_code: Some("USDC".to_string()),
+                asset_issuer: Some(usdc_issuer.to_string()),
+                from: Some(holder.clone()),
+                ..Default::default()
+            },
+            HorizonOperation {
+                id: format!("op_{}_4", sequence),
+                paging_token: format!("pt_{}_4", sequence),
+                transaction_hash: format!("txhash_{}_4", sequence),
+                source_account: usdc_issuer.to_string(),
+                operation_type: "set_trust_line_flags".to_string(),
+                created_at: "2026-01-22T10:34:00Z".to_string(),
+                asset_code: Some("USDC".to_string()),
+                asset_issuer: Some(usdc_issuer.to_string()),
+                trustor: Some(holder),
+                clear_flags_s: vec!["authorized".to_string()],
+                ..Default::default()
             },
         ]
     }
@@ -1671,7 +2009,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_liquidity_pools(limit));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_liquidity_pools_internal(limit, cursor)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_liquidity_pools_internal(horizon_url, limit, cursor)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -1681,12 +2019,13 @@ impl StellarRpcClient {
 
     async fn fetch_liquidity_pools_internal(
         &self,
+        horizon_url: String,
         limit: u32,
         cursor: Option<&str>,
     ) -> Result<Vec<HorizonLiquidityPool>, RpcError> {
         let mut url = format!(
             "{}/liquidity_pools?order=desc&limit={}",
-            self.horizon_url, limit
+            horizon_url, limit
         );
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
@@ -1717,7 +2056,7 @@ impl StellarRpcClient {
             return Ok(pool);
         }
 
-        let result = self.execute_with_retry(|| self.fetch_liquidity_pool_internal(pool_id)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_liquidity_pool_internal(horizon_url, pool_id)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -1727,9 +2066,10 @@ impl StellarRpcClient {
 
     async fn fetch_liquidity_pool_internal(
         &self,
+        horizon_url: String,
         pool_id: &str,
     ) -> Result<HorizonLiquidityPool, RpcError> {
-        let url = format!("{}/liquidity_pools/{}", self.horizon_url, pool_id);
+        let url = format!("{}/liquidity_pools/{}", horizon_url, pool_id);
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
@@ -1750,7 +2090,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_trades(limit));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_pool_trades_internal(pool_id, limit)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_pool_trades_internal(horizon_url, pool_id, limit)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -1760,12 +2100,13 @@ impl StellarRpcClient {
 
     async fn fetch_pool_trades_internal(
         &self,
+        horizon_url: String,
         pool_id: &str,
         limit: u32,
     ) -> Result<Vec<Trade>, RpcError> {
         let url = format!(
             "{}/liquidity_pools/{}/trades?order=desc&limit={}",
-            self.horizon_url, pool_id, limit
+            horizon_url, pool_id, limit
         );
         let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
         if !response.status().is_success() {
@@ -1791,7 +2132,7 @@ impl StellarRpcClient {
             return Ok(Self::mock_assets(limit));
         }
 
-        let result = self.execute_with_retry(|| self.fetch_assets_internal(limit, rating_sort)).await;
+        let result = self.execute_horizon(|horizon_url| self.fetch_assets_internal(horizon_url, limit, rating_sort)).await;
 
         result.map_err(|e| {
             metrics::record_rpc_error(e.error_type_label(), "stellar");
@@ -1801,10 +2142,11 @@ impl StellarRpcClient {
 
     async fn fetch_assets_internal(
         &self,
+        horizon_url: String,
         limit: u32,
         rating_sort: bool,
     ) -> Result<Vec<HorizonAsset>, RpcError> {
-        let mut url = format!("{}/assets?limit={}", self.horizon_url, limit);
+        let mut url = format!("{}/assets?limit={}", horizon_url, limit);
         if rating_sort {
             url.push_str("&order=desc&sort=rating");
         } else {
@@ -1824,6 +2166,179 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    /// Fetch a single issued asset, or None when Horizon doesn't know it
+    pub async fn fetch_asset(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<Option<HorizonAsset>, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_assets(u32::MAX)
+                .into_iter()
+                .find(|a| a.asset_code == asset_code && a.asset_issuer == asset_issuer));
+        }
+
+        let result = self.execute_horizon(|horizon_url| self.fetch_asset_internal(horizon_url, asset_code, asset_issuer)).await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_asset_internal(
+        &self,
+        horizon_url: String,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<Option<HorizonAsset>, RpcError> {
+        let url = format!(
+            "{}/assets?asset_code={}&asset_issuer={}&limit=1",
+            horizon_url, asset_code, asset_issuer
+        );
+        let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<HorizonAsset> = response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        Ok(horizon_response
+            .embedded
+            .and_then(|e| e.records.into_iter().next()))
+    }
+
+    /// Fetch one page of accounts holding a trustline to an issued asset
+    pub async fn fetch_asset_holders(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<HorizonAccount>, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_asset_holders(asset_code, asset_issuer, limit, cursor));
+        }
+
+        let result = self.execute_horizon(|horizon_url| self.fetch_asset_holders_internal(horizon_url, asset_code, asset_issuer, limit, cursor)).await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_asset_holders_internal(
+        &self,
+        horizon_url: String,
+        asset_code: &str,
+        asset_issuer: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<HorizonAccount>, RpcError> {
+        let mut url = format!(
+            "{}/accounts?asset={}:{}&order=asc&limit={}",
+            horizon_url, asset_code, asset_issuer, limit
+        );
+        if let Some(c) = cursor {
+            url.push_str(&format!("&cursor={}", c));
+        }
+        let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<HorizonAccount> = response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    /// Find strict-send payment paths from `source_amount` of one asset to
+    /// another
+    pub async fn fetch_strict_send_paths(
+        &self,
+        source_asset: &Asset,
+        source_amount: &str,
+        destination_asset: &Asset,
+    ) -> Result<Vec<PaymentPath>, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_strict_send_paths(
+                source_asset,
+                source_amount,
+                destination_asset,
+            ));
+        }
+
+        let result = self.execute_horizon(|horizon_url| self.fetch_strict_send_paths_internal(horizon_url, source_asset, source_amount, destination_asset)).await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_strict_send_paths_internal(
+        &self,
+        horizon_url: String,
+        source_asset: &Asset,
+        source_amount: &str,
+        destination_asset: &Asset,
+    ) -> Result<Vec<PaymentPath>, RpcError> {
+        let url = format!(
+            "{}/paths/strict-send?{}&source_amount={}&destination_assets={}",
+            horizon_url,
+            source_asset.horizon_query("source"),
+            source_amount,
+            destination_asset
+        );
+        let response = self.client.get(&url).send().await.map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<PaymentPath> = response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    /// A direct path and a slightly worse one through XLM
+    fn mock_strict_send_paths(
+        source_asset: &Asset,
+        source_amount: &str,
+        destination_asset: &Asset,
+    ) -> Vec<PaymentPath> {
+        let amount: f64 = source_amount.parse().unwrap_or(0.0);
+        let (source_asset_type, source_asset_code, source_asset_issuer) =
+            source_asset.horizon_fields();
+        let (destination_asset_type, destination_asset_code, destination_asset_issuer) =
+            destination_asset.horizon_fields();
+        let path = |destination_amount: f64, hops: Vec<Asset>| PaymentPath {
+            source_asset_type: source_asset_type.clone(),
+            source_asset_code: source_asset_code.clone(),
+            source_asset_issuer: source_asset_issuer.clone(),
+            source_amount: source_amount.to_string(),
+            destination_asset_type: destination_asset_type.clone(),
+            destination_asset_code: destination_asset_code.clone(),
+            destination_asset_issuer: destination_asset_issuer.clone(),
+            destination_amount: format!("{:.7}", destination_amount),
+            path: hops,
+        };
+
+        vec![
+            path(amount * 0.9950, Vec::new()),
+            path(amount * 0.9920, vec![Asset::Native]),
+        ]
+    }
+
     // ============================================================================
     // Liquidity Pool Mock Data
     // ============================================================================
@@ -1917,6 +2432,31 @@ impl StellarRpcClient {
             .collect()
     }
 
+    /// 25 holders per mock asset with geometrically shrinking balances, so
+    /// the first few hold most of the supply
+    fn mock_asset_holders(
+        asset_code: &str,
+        asset_issuer: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Vec<HorizonAccount> {
+        const HOLDERS: usize = 25;
+        let start = cursor.and_then(|c| c.parse::<usize>().ok()).unwrap_or(0);
+        (start..HOLDERS)
+            .take(limit as usize)
+            .map(|i| HorizonAccount {
+                id: format!("GMOCKHOLDER{:02}", i),
+                paging_token: (i + 1).to_string(),
+                balances: vec![HorizonBalance {
+                    balance: format!("{:.7}", 1_000_000.0 * 0.8_f64.powi(i as i32)),
+                    asset_type: "credit_alphanum4".to_string(),
+                    asset_code: Some(asset_code.to_string()),
+                    asset_issuer: Some(asset_issuer.to_string()),
+                }],
+            })
+            .collect()
+    }
+
     fn mock_assets(limit: u32) -> Vec<HorizonAsset> {
         let mut assets = Vec::new();
         let issues = vec![
@@ -2020,17 +2560,8 @@ mod tests {
     async fn test_mock_fetch_order_book() {
         let client = StellarRpcClient::new_with_defaults(true);
 
-        let selling = Asset {
-            asset_type: "native".to_string(),
-            asset_code: None,
-            asset_issuer: None,
-        };
-
-        let buying = Asset {
-            asset_type: "credit_alphanum4".to_string(),
-            asset_code: Some("USDC".to_string()),
-            asset_issuer: Some("GBXXXXXXX".to_string()),
-        };
+        let selling = Asset::Native;
+        let buying = Asset::credit("USDC", "GBXXXXXXX");
 
         let order_book = client
             .fetch_order_book(&selling, &buying, 10)
@@ -2075,8 +2606,9 @@ mod tests {
         let client = StellarRpcClient::new_with_defaults(true);
         let operations = client.fetch_operations_for_ledger(123).await.unwrap();
 
-        assert_eq!(operations.len(), 3);
+        assert_eq!(operations.len(), 5);
         assert_eq!(operations[0].operation_type, "account_merge");
+        assert_eq!(operations[3].operation_type, "clawback");
     }
 
     #[tokio::test]
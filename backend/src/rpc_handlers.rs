@@ -6,8 +6,9 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use stellar_insights_core::Asset;
 
-use crate::rpc::{Asset, StellarRpcClient};
+use crate::rpc::StellarRpcClient;
 
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
@@ -53,6 +54,13 @@ pub async fn rpc_health_check(
     }
 }
 
+/// Latency, error rate and health of each configured Horizon endpoint
+pub async fn get_horizon_endpoints(
+    State(client): State<Arc<StellarRpcClient>>,
+) -> impl IntoResponse {
+    Json(client.horizon_endpoint_metrics())
+}
+
 /// Get latest ledger information
 #[tracing::instrument(skip(client))]
 pub async fn get_latest_ledger(
@@ -132,17 +140,26 @@ pub async fn get_order_book(
     State(client): State<Arc<StellarRpcClient>>,
     Query(params): Query<OrderBookQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
-    let selling_asset = Asset {
-        asset_type: params.selling_asset_type,
-        asset_code: params.selling_asset_code,
-        asset_issuer: params.selling_asset_issuer,
-    };
-
-    let buying_asset = Asset {
-        asset_type: params.buying_asset_type,
-        asset_code: params.buying_asset_code,
-        asset_issuer: params.buying_asset_issuer,
+    let invalid_asset = |side: &str| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Invalid {} asset", side),
+            }),
+        )
     };
+    let selling_asset = Asset::from_horizon_fields(
+        &params.selling_asset_type,
+        params.selling_asset_code.as_deref(),
+        params.selling_asset_issuer.as_deref(),
+    )
+    .ok_or_else(|| invalid_asset("selling"))?;
+    let buying_asset = Asset::from_horizon_fields(
+        &params.buying_asset_type,
+        params.buying_asset_code.as_deref(),
+        params.buying_asset_issuer.as_deref(),
+    )
+    .ok_or_else(|| invalid_asset("buying"))?;
 
     match client
         .fetch_order_book(&selling_asset, &buying_asset, params.limit)
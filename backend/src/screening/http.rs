@@ -0,0 +1,99 @@
+//! Screening through an HTTP screening API.
+//!
+//! Accounts are POSTed as `{"accounts": ["G...", ...]}` to `SCREENING_URL`,
+//! with `SCREENING_API_KEY` as a bearer token when set. The service answers
+//! `{"results": {"G...": "clear" | "flagged", ...}}`; accounts it leaves out
+//! or marks with any other value count as unscreened.
+
+use anyhow::{bail, Result};
+use axum::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{ScreeningProvider, ScreeningStatus};
+use crate::http_client;
+
+pub struct HttpScreening {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl HttpScreening {
+    pub fn new(url: String, api_key: Option<String>) -> Self {
+        let client = http_client::shared().client(Duration::from_secs(15));
+        Self {
+            client,
+            url,
+            api_key,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ScreeningResponse {
+    #[serde(default)]
+    results: HashMap<String, String>,
+}
+
+/// Statuses from a screening API response body
+fn parse_results(body: &str) -> Result<HashMap<String, ScreeningStatus>> {
+    let response: ScreeningResponse = serde_json::from_str(body)?;
+    Ok(response
+        .results
+        .into_iter()
+        .filter_map(|(account, status)| {
+            let status = match status.to_lowercase().as_str() {
+                "clear" => ScreeningStatus::Clear,
+                "flagged" => ScreeningStatus::Flagged,
+                _ => return None,
+            };
+            Some((account, status))
+        })
+        .collect())
+}
+
+#[async_trait]
+impl ScreeningProvider for HttpScreening {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn screen(&self, accounts: &[String]) -> Result<HashMap<String, ScreeningStatus>> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({ "accounts": accounts }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            bail!("Screening API returned {}: {}", status, body);
+        }
+        parse_results(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_results_keeps_known_statuses() {
+        let statuses =
+            parse_results(r#"{"results": {"GA": "clear", "GB": "FLAGGED", "GC": "pending"}}"#)
+                .unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses["GA"], ScreeningStatus::Clear);
+        assert_eq!(statuses["GB"], ScreeningStatus::Flagged);
+
+        assert!(parse_results("{}").unwrap().is_empty());
+        assert!(parse_results("not json").is_err());
+    }
+}
@@ -0,0 +1,332 @@
+//! Counterparty screening of accounts shown in analytics responses.
+//!
+//! Endpoints that list individual accounts annotate each item with a
+//! [`ScreeningStatus`], so compliance teams can filter out sanctioned
+//! addresses. `Settings::screening` (`SCREENING_PROVIDER`) selects the
+//! screening service:
+//!
+//! - `none` (default): every account is reported as `unscreened`
+//! - `http`: a screening API at the configured URL (see [`HttpScreening`])
+//!
+//! Results are cached for `cache_ttl_secs` (default one day).
+//! Accounts the provider could not screen are reported as `unscreened` and
+//! asked about again on the next request.
+
+pub mod http;
+
+pub use http::HttpScreening;
+
+use anyhow::{Context, Result};
+use async_lock::RwLock;
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::config::ScreeningSettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningStatus {
+    /// Screened with no match
+    Clear,
+    /// Matched a sanctions or watch list
+    Flagged,
+    /// Not screened: screening is off or the provider failed
+    Unscreened,
+}
+
+impl ScreeningStatus {
+    /// Status of an item involving accounts with `statuses`: flagged if any
+    /// is, otherwise unscreened if any is, otherwise clear
+    pub fn combine(statuses: impl IntoIterator<Item = ScreeningStatus>) -> Self {
+        statuses
+            .into_iter()
+            .fold(ScreeningStatus::Clear, |combined, status| {
+                match (combined, status) {
+                    (ScreeningStatus::Flagged, _) | (_, ScreeningStatus::Flagged) => {
+                        ScreeningStatus::Flagged
+                    }
+                    (ScreeningStatus::Unscreened, _) | (_, ScreeningStatus::Unscreened) => {
+                        ScreeningStatus::Unscreened
+                    }
+                    _ => ScreeningStatus::Clear,
+                }
+            })
+    }
+}
+
+#[async_trait]
+pub trait ScreeningProvider: Send + Sync {
+    /// Provider name for logs ("none", "http")
+    fn name(&self) -> &'static str;
+
+    /// Screen `accounts`. Accounts missing from the result count as
+    /// unscreened.
+    async fn screen(&self, accounts: &[String]) -> Result<HashMap<String, ScreeningStatus>>;
+}
+
+/// Screens nothing; the default
+pub struct NoScreening;
+
+#[async_trait]
+impl ScreeningProvider for NoScreening {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    async fn screen(&self, _accounts: &[String]) -> Result<HashMap<String, ScreeningStatus>> {
+        Ok(HashMap::new())
+    }
+}
+
+/// Screening service selected with `SCREENING_PROVIDER`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreeningProviderKind {
+    #[default]
+    None,
+    Http,
+}
+
+impl FromStr for ScreeningProviderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" | "" => Ok(Self::None),
+            "http" => Ok(Self::Http),
+            other => Err(format!(
+                "unknown screening provider '{}', expected none or http",
+                other
+            )),
+        }
+    }
+}
+
+/// Build the provider selected in `screening`
+pub fn provider_from_settings(screening: &ScreeningSettings) -> Result<Arc<dyn ScreeningProvider>> {
+    let provider: Arc<dyn ScreeningProvider> = match screening.provider {
+        ScreeningProviderKind::None => Arc::new(NoScreening),
+        ScreeningProviderKind::Http => Arc::new(HttpScreening::new(
+            screening
+                .url
+                .clone()
+                .context("SCREENING_URL must be set when SCREENING_PROVIDER=http")?,
+            screening.api_key.clone(),
+        )),
+    };
+
+    Ok(provider)
+}
+
+struct CachedStatus {
+    status: ScreeningStatus,
+    screened_at: Instant,
+}
+
+/// Screens accounts through a [`ScreeningProvider`], caching its answers
+pub struct AccountScreener {
+    provider: Arc<dyn ScreeningProvider>,
+    ttl: Duration,
+    cache: RwLock<HashMap<String, CachedStatus>>,
+}
+
+impl AccountScreener {
+    pub fn new(provider: Arc<dyn ScreeningProvider>, ttl: Duration) -> Self {
+        Self {
+            provider,
+            ttl,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The provider from [`provider_from_settings`], caching answers for
+    /// `screening.cache_ttl_secs`
+    pub fn from_settings(screening: &ScreeningSettings) -> Result<Self> {
+        Ok(Self::new(
+            provider_from_settings(screening)?,
+            Duration::from_secs(screening.cache_ttl_secs),
+        ))
+    }
+
+    /// Status of each of `accounts`. Cached answers are reused; the rest are
+    /// screened in one provider call.
+    pub async fn statuses<'a>(
+        &self,
+        accounts: impl IntoIterator<Item = &'a str>,
+    ) -> HashMap<String, ScreeningStatus> {
+        let mut statuses = HashMap::new();
+        let mut to_screen = Vec::new();
+        {
+            let cache = self.cache.read().await;
+            for account in accounts {
+                if statuses.contains_key(account) || to_screen.iter().any(|a| a == account) {
+                    continue;
+                }
+                match cache.get(account) {
+                    Some(cached) if cached.screened_at.elapsed() < self.ttl => {
+                        statuses.insert(account.to_string(), cached.status);
+                    }
+                    _ => to_screen.push(account.to_string()),
+                }
+            }
+        }
+        if to_screen.is_empty() {
+            return statuses;
+        }
+
+        let screened = match self.provider.screen(&to_screen).await {
+            Ok(screened) => screened,
+            Err(e) => {
+                tracing::warn!(
+                    "{} screening of {} accounts failed: {}",
+                    self.provider.name(),
+                    to_screen.len(),
+                    e
+                );
+                HashMap::new()
+            }
+        };
+
+        let mut cache = self.cache.write().await;
+        for account in to_screen {
+            let status = screened
+                .get(&account)
+                .copied()
+                .unwrap_or(ScreeningStatus::Unscreened);
+            if status != ScreeningStatus::Unscreened {
+                cache.insert(
+                    account.clone(),
+                    CachedStatus {
+                        status,
+                        screened_at: Instant::now(),
+                    },
+                );
+            }
+            statuses.insert(account, status);
+        }
+        statuses
+    }
+}
+
+/// A response item annotated with the screening of the accounts it involves
+#[derive(Debug, Clone, Serialize)]
+pub struct Screened<T> {
+    #[serde(flatten)]
+    pub item: T,
+    pub screening_status: ScreeningStatus,
+    /// Accounts in the item that matched a list
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub flagged_accounts: Vec<String>,
+}
+
+impl<T> Screened<T> {
+    /// Annotate `item`, which involves `accounts`, from `statuses`
+    pub fn new(item: T, accounts: &[&str], statuses: &HashMap<String, ScreeningStatus>) -> Self {
+        let status_of = |account: &str| {
+            statuses
+                .get(account)
+                .copied()
+                .unwrap_or(ScreeningStatus::Unscreened)
+        };
+        Self {
+            screening_status: ScreeningStatus::combine(accounts.iter().map(|a| status_of(a))),
+            flagged_accounts: accounts
+                .iter()
+                .filter(|a| status_of(a) == ScreeningStatus::Flagged)
+                .map(|a| a.to_string())
+                .collect(),
+            item,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Flags accounts starting with "GBAD", fails for "GERR", and counts calls
+    struct Fixed {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ScreeningProvider for Fixed {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        async fn screen(&self, accounts: &[String]) -> Result<HashMap<String, ScreeningStatus>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if accounts.iter().any(|a| a.starts_with("GERR")) {
+                bail!("provider unavailable");
+            }
+            Ok(accounts
+                .iter()
+                .map(|a| {
+                    let status = if a.starts_with("GBAD") {
+                        ScreeningStatus::Flagged
+                    } else {
+                        ScreeningStatus::Clear
+                    };
+                    (a.clone(), status)
+                })
+                .collect())
+        }
+    }
+
+    fn screener() -> (Arc<Fixed>, AccountScreener) {
+        let provider = Arc::new(Fixed {
+            calls: AtomicUsize::new(0),
+        });
+        let screener = AccountScreener::new(provider.clone(), Duration::from_secs(60));
+        (provider, screener)
+    }
+
+    #[tokio::test]
+    async fn test_statuses_are_cached() {
+        let (provider, screener) = screener();
+        let statuses = screener.statuses(["GOOD", "GBAD1", "GOOD"]).await;
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses["GOOD"], ScreeningStatus::Clear);
+        assert_eq!(statuses["GBAD1"], ScreeningStatus::Flagged);
+
+        let statuses = screener.statuses(["GBAD1"]).await;
+        assert_eq!(statuses["GBAD1"], ScreeningStatus::Flagged);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_screening_is_unscreened_and_retried() {
+        let (provider, screener) = screener();
+        let statuses = screener.statuses(["GERR", "GOOD"]).await;
+        assert_eq!(statuses["GERR"], ScreeningStatus::Unscreened);
+        assert_eq!(statuses["GOOD"], ScreeningStatus::Unscreened);
+
+        screener.statuses(["GOOD"]).await;
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_screened_item_combines_its_accounts() {
+        let statuses = HashMap::from([
+            ("GOOD".to_string(), ScreeningStatus::Clear),
+            ("GBAD".to_string(), ScreeningStatus::Flagged),
+        ]);
+
+        let clear = Screened::new((), &["GOOD"], &statuses);
+        assert_eq!(clear.screening_status, ScreeningStatus::Clear);
+        assert!(clear.flagged_accounts.is_empty());
+
+        let unknown = Screened::new((), &["GOOD", "GNEW"], &statuses);
+        assert_eq!(unknown.screening_status, ScreeningStatus::Unscreened);
+
+        let flagged = Screened::new((), &["GNEW", "GBAD"], &statuses);
+        assert_eq!(flagged.screening_status, ScreeningStatus::Flagged);
+        assert_eq!(flagged.flagged_accounts, vec!["GBAD".to_string()]);
+    }
+}
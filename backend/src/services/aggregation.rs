@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::database::Database;
 use crate::models::corridor::CorridorMetrics;
+use crate::models::MetricSource;
 use crate::services::analytics::compute_metrics_from_payments;
 
 const MAX_RETRIES: i32 = 3;
@@ -141,6 +142,14 @@ impl AggregationService {
 
         // Store aggregated metrics
         let stored_count = self.store_hourly_metrics(hourly_metrics).await?;
+        self.db
+            .refresh_corridor_summaries()
+            .await
+            .context("Failed to refresh corridor summaries")?;
+        self.db
+            .corridor_cohorts()
+            .record_activity(start_time, end_time)
+            .await?;
 
         // Update last processed hour
         let last_hour = self.truncate_to_hour(end_time);
@@ -185,6 +194,7 @@ impl AggregationService {
 
                     existing.liquidity_depth_usd =
                         (existing.liquidity_depth_usd + metric.liquidity_depth_usd) / 2.0;
+                    existing.source = existing.source.least_trusted(metric.source);
                 })
                 .or_insert_with(|| HourlyCorridorMetrics {
                     id: Uuid::new_v4().to_string(),
@@ -202,6 +212,7 @@ impl AggregationService {
                     avg_slippage_bps: 0.0, // TODO: Calculate from order book data
                     avg_settlement_latency_ms: metric.avg_settlement_latency_ms,
                     liquidity_depth_usd: metric.liquidity_depth_usd,
+                    source: metric.source,
                 });
         }
 
@@ -412,6 +423,7 @@ pub struct HourlyCorridorMetrics {
     pub avg_slippage_bps: f64,
     pub avg_settlement_latency_ms: Option<i32>,
     pub liquidity_depth_usd: f64,
+    pub source: MetricSource,
 }
 
 #[derive(Debug, Clone)]
@@ -471,6 +483,7 @@ mod tests {
                 avg_slippage_bps: 10.0,
                 avg_settlement_latency_ms: Some(500),
                 liquidity_depth_usd: 50000.0,
+                source: MetricSource::Horizon,
             },
             HourlyCorridorMetrics {
                 id: "2".to_string(),
@@ -488,6 +501,7 @@ mod tests {
                 avg_slippage_bps: 12.0,
                 avg_settlement_latency_ms: Some(450),
                 liquidity_depth_usd: 55000.0,
+                source: MetricSource::Horizon,
             },
         ];
 
@@ -1,4 +1,5 @@
 use crate::models::corridor::{compute_median, CorridorMetrics, PaymentRecord};
+use crate::models::MetricSource;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -81,6 +82,7 @@ pub fn compute_corridor_metrics(
             failed_transactions: 0,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            source: MetricSource::Derived,
         };
     }
 
@@ -137,6 +139,7 @@ pub fn compute_corridor_metrics(
         liquidity_depth_usd,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
+        source: MetricSource::Derived,
     }
 }
 
@@ -165,6 +168,10 @@ pub fn compute_metrics_from_payments(payments: &[PaymentRecord]) -> Vec<Corridor
         let mut volume_usd = 0.0;
         let mut latency_sum = 0i64;
         let mut latency_values: Vec<i64> = Vec::new();
+        // A corridor's metrics are only as trusted as its least trusted payment
+        let source = corridor_payments
+            .iter()
+            .fold(first.source, |acc, p| acc.least_trusted(p.source));
 
         for p in &corridor_payments {
             if p.successful {
@@ -214,6 +221,7 @@ pub fn compute_metrics_from_payments(payments: &[PaymentRecord]) -> Vec<Corridor
             liquidity_depth_usd: 0.0, // Needs order book
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            source,
         });
     }
 
@@ -260,6 +268,7 @@ mod tests {
             timestamp,
             submission_time: None,
             confirmation_time: None,
+            source: MetricSource::Horizon,
         }
     }
 
@@ -283,6 +292,7 @@ mod tests {
             timestamp,
             submission_time: Some(submission),
             confirmation_time: Some(timestamp),
+            source: MetricSource::Horizon,
         }
     }
 
@@ -309,6 +319,27 @@ mod tests {
         assert_eq!(usdc_metrics.volume_usd, 150.0);
     }
 
+    #[test]
+    fn test_metrics_take_least_trusted_payment_source() {
+        let mut reported = create_test_payment_record("USDC", "EURC", 50.0, true, Utc::now());
+        reported.source = MetricSource::SelfReported;
+        let payments = vec![
+            create_test_payment_record("USDC", "EURC", 100.0, true, Utc::now()),
+            reported,
+            create_test_payment_record("BTC", "ETH", 10.0, true, Utc::now()),
+        ];
+
+        let metrics = compute_metrics_from_payments(&payments);
+        for m in &metrics {
+            let expected = if m.total_transactions == 2 {
+                MetricSource::SelfReported
+            } else {
+                MetricSource::Horizon
+            };
+            assert_eq!(m.source, expected);
+        }
+    }
+
     #[test]
     fn test_compute_metrics_by_window() {
         let now = Utc::now();
@@ -0,0 +1,168 @@
+//! Periodic refresh of anchors' stellar.toml files.
+//!
+//! Each run fetches the stellar.toml of every anchor with a home domain,
+//! refreshing the cache, and compares it with the copy kept in
+//! `anchor_toml_snapshots`. When top-level fields were added, removed or
+//! changed, an `anchor.toml_changed` webhook event is queued. The first fetch
+//! of an anchor only records its file.
+
+use anyhow::Result;
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use super::stellar_toml::StellarTomlClient;
+use crate::webhooks::events::AnchorTomlChangedEvent;
+use crate::webhooks::{WebhookEventType, WebhookService};
+
+/// Top-level fields that differ between two stellar.toml files, sorted
+pub fn changed_fields(previous: &Value, current: &Value) -> Vec<String> {
+    let (Some(previous), Some(current)) = (previous.as_object(), current.as_object()) else {
+        return Vec::new();
+    };
+
+    previous
+        .keys()
+        .chain(current.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|field| {
+            let before = previous.get(*field).filter(|v| !v.is_null());
+            let after = current.get(*field).filter(|v| !v.is_null());
+            before != after
+        })
+        .cloned()
+        .collect()
+}
+
+pub struct AnchorTomlRefresher {
+    pool: SqlitePool,
+    toml: StellarTomlClient,
+    webhooks: Arc<WebhookService>,
+}
+
+impl AnchorTomlRefresher {
+    pub fn new(pool: SqlitePool, toml: StellarTomlClient, webhooks: Arc<WebhookService>) -> Self {
+        Self {
+            pool,
+            toml,
+            webhooks,
+        }
+    }
+
+    /// Refresh every anchor's stellar.toml. Anchors whose file cannot be
+    /// fetched are skipped. Returns the number of anchors whose file changed.
+    pub async fn run_once(&self) -> Result<usize> {
+        let anchors = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, home_domain FROM anchors
+             WHERE home_domain IS NOT NULL AND home_domain != ''",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut changed = 0;
+        for (anchor_id, domain) in anchors {
+            // Failures are logged by the client
+            let Ok(toml) = self.toml.background_refresh(&domain).await else {
+                continue;
+            };
+            if self
+                .record(&anchor_id, &domain, serde_json::to_value(&toml)?)
+                .await?
+            {
+                changed += 1;
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Store the stellar.toml fetched for an anchor, queueing
+    /// `anchor.toml_changed` if it differs from the previous fetch. Returns
+    /// whether it changed.
+    pub async fn record(&self, anchor_id: &str, domain: &str, content: Value) -> Result<bool> {
+        let previous = sqlx::query_scalar::<_, String>(
+            "SELECT content FROM anchor_toml_snapshots WHERE anchor_id = ?",
+        )
+        .bind(anchor_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let fetched_at = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_toml_snapshots (anchor_id, domain, content, fetched_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(anchor_id) DO UPDATE SET
+                domain = excluded.domain,
+                content = excluded.content,
+                fetched_at = excluded.fetched_at
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(domain)
+        .bind(content.to_string())
+        .bind(&fetched_at)
+        .execute(&self.pool)
+        .await?;
+
+        let Some(previous) = previous else {
+            return Ok(false);
+        };
+        let changed_fields = changed_fields(&serde_json::from_str(&previous)?, &content);
+        if changed_fields.is_empty() {
+            return Ok(false);
+        }
+
+        tracing::info!(
+            "stellar.toml of anchor {} ({}) changed: {:?}",
+            anchor_id,
+            domain,
+            changed_fields
+        );
+        let event = AnchorTomlChangedEvent {
+            anchor_id: anchor_id.to_string(),
+            domain: domain.to_string(),
+            changed_fields,
+            fetched_at,
+        };
+        self.webhooks
+            .enqueue_for_subscribers(
+                WebhookEventType::AnchorTomlChanged,
+                serde_json::to_value(&event)?,
+            )
+            .await?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_changed_fields() {
+        let previous = json!({
+            "version": "2.0.0",
+            "signing_key": "GA",
+            "currencies": [{"code": "USDC"}],
+            "transfer_server": null
+        });
+        let current = json!({
+            "version": "2.0.0",
+            "signing_key": "GB",
+            "currencies": [{"code": "USDC"}, {"code": "EURC"}],
+            "web_auth_endpoint": "https://anchor.example/auth"
+        });
+
+        assert_eq!(
+            changed_fields(&previous, &current),
+            vec!["currencies", "signing_key", "web_auth_endpoint"]
+        );
+        assert!(changed_fields(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+//! Issuer compliance actions on tracked anchor assets.
+//!
+//! `clawback` and `set_trust_line_flags` operations on assets listed in
+//! `assets` are stored in `asset_compliance_events`, so users evaluating a
+//! corridor can see how often its assets are clawed back or frozen.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::rpc::{HorizonOperation, StellarRpcClient};
+
+const AUTHORIZED_FLAG: &str = "authorized";
+
+/// What an issuer did to a holder's trustline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceAction {
+    Clawback,
+    /// Authorization revoked; the holder can no longer send or receive
+    Freeze,
+    /// Authorization granted again
+    Unfreeze,
+    /// Other flag changes, e.g. clawback_enabled cleared
+    FlagsChanged,
+}
+
+impl ComplianceAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Clawback => "clawback",
+            Self::Freeze => "freeze",
+            Self::Unfreeze => "unfreeze",
+            Self::FlagsChanged => "flags_changed",
+        }
+    }
+
+    pub fn parse(action: &str) -> Option<Self> {
+        match action {
+            "clawback" => Some(Self::Clawback),
+            "freeze" => Some(Self::Freeze),
+            "unfreeze" => Some(Self::Unfreeze),
+            "flags_changed" => Some(Self::FlagsChanged),
+            _ => None,
+        }
+    }
+
+    /// Classify a Horizon operation, or None when it isn't a compliance action
+    pub fn of(operation: &HorizonOperation) -> Option<Self> {
+        match operation.operation_type.as_str() {
+            "clawback" => Some(Self::Clawback),
+            "set_trust_line_flags" => {
+                let touches = |flags: &[String]| flags.iter().any(|f| f == AUTHORIZED_FLAG);
+                if touches(&operation.clear_flags_s) {
+                    Some(Self::Freeze)
+                } else if touches(&operation.set_flags_s) {
+                    Some(Self::Unfreeze)
+                } else {
+                    Some(Self::FlagsChanged)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetComplianceEvent {
+    pub operation_id: String,
+    pub transaction_hash: String,
+    pub ledger_sequence: i64,
+    pub operation_type: String,
+    pub action: ComplianceAction,
+    pub asset_code: String,
+    pub asset_issuer: String,
+    /// Holder whose balance was clawed back or whose trustline changed
+    pub account: String,
+    pub amount: Option<f64>,
+    pub flags_set: Vec<String>,
+    pub flags_cleared: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ComplianceEventRow {
+    operation_id: String,
+    transaction_hash: String,
+    ledger_sequence: i64,
+    operation_type: String,
+    action: String,
+    asset_code: String,
+    asset_issuer: String,
+    account: String,
+    amount: Option<f64>,
+    flags_set: String,
+    flags_cleared: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<ComplianceEventRow> for AssetComplianceEvent {
+    fn from(row: ComplianceEventRow) -> Self {
+        Self {
+            operation_id: row.operation_id,
+            transaction_hash: row.transaction_hash,
+            ledger_sequence: row.ledger_sequence,
+            operation_type: row.operation_type,
+            action: ComplianceAction::parse(&row.action).unwrap_or(ComplianceAction::FlagsChanged),
+            asset_code: row.asset_code,
+            asset_issuer: row.asset_issuer,
+            account: row.account,
+            amount: row.amount,
+            flags_set: serde_json::from_str(&row.flags_set).unwrap_or_default(),
+            flags_cleared: serde_json::from_str(&row.flags_cleared).unwrap_or_default(),
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub struct AssetComplianceTracker {
+    pool: Pool<Sqlite>,
+    rpc_client: Arc<StellarRpcClient>,
+}
+
+impl AssetComplianceTracker {
+    pub fn new(pool: Pool<Sqlite>, rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self { pool, rpc_client }
+    }
+
+    /// Fetches operations for a ledger and stores compliance actions on
+    /// tracked assets. Returns the number of new events.
+    pub async fn process_ledger_operations(&self, ledger_sequence: u64) -> Result<u64> {
+        let operations = self
+            .rpc_client
+            .fetch_operations_for_ledger(ledger_sequence)
+            .await?;
+
+        let candidates: Vec<&HorizonOperation> = operations
+            .iter()
+            .filter(|op| ComplianceAction::of(op).is_some())
+            .collect();
+        if candidates.is_empty() {
+            return Ok(0);
+        }
+
+        let tracked = self.tracked_assets().await?;
+        let mut inserted = 0_u64;
+
+        for operation in candidates {
+            let (Some(code), Some(issuer)) = (&operation.asset_code, &operation.asset_issuer)
+            else {
+                continue;
+            };
+            if !tracked.contains(&(code.clone(), issuer.clone())) {
+                continue;
+            }
+            let Some(event) = Self::event_from_operation(ledger_sequence, operation) else {
+                warn!(
+                    "Skipping {} operation {} without an affected account",
+                    operation.operation_type, operation.id
+                );
+                continue;
+            };
+            if self.persist_event(&event).await? {
+                inserted += 1;
+            }
+        }
+
+        if inserted > 0 {
+            info!(
+                "Stored {} asset compliance events for ledger {}",
+                inserted, ledger_sequence
+            );
+        }
+
+        Ok(inserted)
+    }
+
+    async fn tracked_assets(&self) -> Result<HashSet<(String, String)>> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT asset_code, asset_issuer FROM assets")
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    fn event_from_operation(
+        ledger_sequence: u64,
+        operation: &HorizonOperation,
+    ) -> Option<AssetComplianceEvent> {
+        let action = ComplianceAction::of(operation)?;
+        let account = match action {
+            ComplianceAction::Clawback => operation.from.clone(),
+            _ => operation.trustor.clone(),
+        }?;
+
+        Some(AssetComplianceEvent {
+            operation_id: operation.id.clone(),
+            transaction_hash: operation.transaction_hash.clone(),
+            ledger_sequence: ledger_sequence as i64,
+            operation_type: operation.operation_type.clone(),
+            action,
+            asset_code: operation.asset_code.clone()?,
+            asset_issuer: operation.asset_issuer.clone()?,
+            account,
+            amount: operation.amount.as_deref().and_then(|a| a.parse().ok()),
+            flags_set: operation.set_flags_s.clone(),
+            flags_cleared: operation.clear_flags_s.clone(),
+            created_at: DateTime::parse_from_rfc3339(&operation.created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+
+    async fn persist_event(&self, event: &AssetComplianceEvent) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO asset_compliance_events (
+                operation_id, transaction_hash, ledger_sequence, operation_type, action,
+                asset_code, asset_issuer, account, amount, flags_set, flags_cleared, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (operation_id) DO NOTHING
+            "#,
+        )
+        .bind(&event.operation_id)
+        .bind(&event.transaction_hash)
+        .bind(event.ledger_sequence)
+        .bind(&event.operation_type)
+        .bind(event.action.as_str())
+        .bind(&event.asset_code)
+        .bind(&event.asset_issuer)
+        .bind(&event.account)
+        .bind(event.amount)
+        .bind(serde_json::to_string(&event.flags_set)?)
+        .bind(serde_json::to_string(&event.flags_cleared)?)
+        .bind(event.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Most recent events for an asset, optionally of one action
+    pub async fn get_events(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        action: Option<ComplianceAction>,
+        limit: i64,
+    ) -> Result<Vec<AssetComplianceEvent>> {
+        let rows = sqlx::query_as::<_, ComplianceEventRow>(
+            r#"
+            SELECT operation_id, transaction_hash, ledger_sequence, operation_type, action,
+                   asset_code, asset_issuer, account, amount, flags_set, flags_cleared, created_at
+            FROM asset_compliance_events
+            WHERE asset_code = $1 AND asset_issuer = $2 AND ($3 IS NULL OR action = $3)
+            ORDER BY created_at DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(action.map(ComplianceAction::as_str))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags_op(set: &[&str], clear: &[&str]) -> HorizonOperation {
+        HorizonOperation {
+            operation_type: "set_trust_line_flags".to_string(),
+            set_flags_s: set.iter().map(|f| f.to_string()).collect(),
+            clear_flags_s: clear.iter().map(|f| f.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_classifies_trustline_flag_changes() {
+        assert_eq!(
+            ComplianceAction::of(&flags_op(
+                &["authorized_to_maintain_liabilities"],
+                &["authorized"]
+            )),
+            Some(ComplianceAction::Freeze)
+        );
+        assert_eq!(
+            ComplianceAction::of(&flags_op(&["authorized"], &[])),
+            Some(ComplianceAction::Unfreeze)
+        );
+        assert_eq!(
+            ComplianceAction::of(&flags_op(&[], &["clawback_enabled"])),
+            Some(ComplianceAction::FlagsChanged)
+        );
+        let payment = HorizonOperation {
+            operation_type: "payment".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(ComplianceAction::of(&payment), None);
+    }
+}
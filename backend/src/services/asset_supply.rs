@@ -0,0 +1,185 @@
+//! Supply and holder distribution of anchor-issued assets.
+//!
+//! Every hour each asset in `assets` is looked up on Horizon: its total supply
+//! (trustline balances plus amounts in claimable balances, liquidity pools and
+//! contracts), its number of holders, and how much of it the ten largest
+//! holders own. Snapshots are stored in `asset_supply_snapshots`, and a supply
+//! change of at least [`SupplyTrackerConfig::alert_threshold_pct`] since the
+//! previous snapshot raises a mint or burn alert.
+
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::alerts::AlertManager;
+use crate::database::Database;
+use crate::db::asset_supply::AssetSupplySnapshot;
+use crate::rpc::{HorizonAsset, StellarRpcClient};
+
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Accounts per Horizon page when scanning holders
+const HOLDER_PAGE_SIZE: u32 = 200;
+
+/// Holder pages scanned per asset; larger assets get a partial scan
+const MAX_HOLDER_PAGES: usize = 50;
+
+const TOP_HOLDERS: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct SupplyTrackerConfig {
+    /// Supply change (percent) between snapshots that raises an alert
+    pub alert_threshold_pct: f64,
+}
+
+impl Default for SupplyTrackerConfig {
+    fn default() -> Self {
+        Self {
+            alert_threshold_pct: 10.0,
+        }
+    }
+}
+
+fn amount(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+/// Everything issued and not yet burned, wherever it is held
+pub fn total_supply(asset: &HorizonAsset) -> f64 {
+    amount(&asset.balances.authorized)
+        + amount(&asset.balances.authorized_to_maintain_liabilities)
+        + amount(&asset.balances.unauthorized)
+        + amount(&asset.claimable_balances_amount)
+        + amount(&asset.liquidity_pools_amount)
+        + amount(&asset.contracts_amount)
+}
+
+/// Combined balance of the `n` largest holders
+pub fn top_holders_balance(mut balances: Vec<f64>, n: usize) -> f64 {
+    balances.sort_by(|a, b| b.total_cmp(a));
+    balances.iter().take(n).sum()
+}
+
+pub struct AssetSupplyTracker {
+    db: Arc<Database>,
+    rpc: Arc<StellarRpcClient>,
+    config: SupplyTrackerConfig,
+}
+
+impl AssetSupplyTracker {
+    pub fn new(db: Arc<Database>, rpc: Arc<StellarRpcClient>, config: SupplyTrackerConfig) -> Self {
+        Self { db, rpc, config }
+    }
+
+    /// Current supply and distribution of one asset, or None when Horizon
+    /// doesn't know it
+    pub async fn snapshot(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<Option<AssetSupplySnapshot>> {
+        let Some(asset) = self.rpc.fetch_asset(asset_code, asset_issuer).await? else {
+            return Ok(None);
+        };
+
+        let mut balances = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut complete = false;
+        for _ in 0..MAX_HOLDER_PAGES {
+            let page = self
+                .rpc
+                .fetch_asset_holders(
+                    asset_code,
+                    asset_issuer,
+                    HOLDER_PAGE_SIZE,
+                    cursor.as_deref(),
+                )
+                .await?;
+            balances.extend(page.iter().map(|a| a.balance_of(asset_code, asset_issuer)));
+            if page.len() < HOLDER_PAGE_SIZE as usize {
+                complete = true;
+                break;
+            }
+            cursor = page.last().map(|a| a.paging_token.clone());
+        }
+
+        let supply = total_supply(&asset);
+        let holders_scanned = balances.len() as i64;
+        let top10_balance = top_holders_balance(balances, TOP_HOLDERS);
+        Ok(Some(AssetSupplySnapshot {
+            asset_code: asset_code.to_string(),
+            asset_issuer: asset_issuer.to_string(),
+            total_supply: supply,
+            num_holders: (asset.accounts.authorized
+                + asset.accounts.authorized_to_maintain_liabilities)
+                as i64,
+            top10_balance,
+            top10_share: if supply > 0.0 {
+                (top10_balance / supply).min(1.0)
+            } else {
+                0.0
+            },
+            holders_scanned,
+            holders_complete: complete,
+            recorded_at: Utc::now(),
+        }))
+    }
+
+    /// Snapshot every tracked asset, alerting on large supply changes.
+    /// Returns the number of snapshots stored.
+    pub async fn run_once(&self, alert_manager: &AlertManager) -> Result<usize> {
+        let history = self.db.asset_supply();
+        let mut recorded = 0;
+
+        for (code, issuer) in history.tracked_assets().await? {
+            let snapshot = match self.snapshot(&code, &issuer).await {
+                Ok(Some(snapshot)) => snapshot,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Failed to snapshot supply of {}:{}: {}", code, issuer, e);
+                    continue;
+                }
+            };
+
+            if let Some(previous) = history.latest(&code, &issuer).await? {
+                alert_manager.check_supply_change(
+                    &format!("{}:{}", code, issuer),
+                    previous.total_supply,
+                    snapshot.total_supply,
+                    self.config.alert_threshold_pct,
+                );
+            }
+            history.record(&snapshot).await?;
+            recorded += 1;
+        }
+
+        info!("Recorded supply snapshots for {} anchor assets", recorded);
+        Ok(recorded)
+    }
+
+    /// Snapshot on [`REFRESH_INTERVAL`] until the process stops
+    pub async fn run(&self, alert_manager: Arc<AlertManager>) {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.run_once(&alert_manager).await {
+                warn!("Failed to track anchor asset supply: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_holders_balance() {
+        let balances = vec![5.0, 100.0, 1.0, 50.0, 20.0];
+        assert_eq!(top_holders_balance(balances.clone(), 2), 150.0);
+        assert_eq!(top_holders_balance(balances, 10), 176.0);
+        assert_eq!(top_holders_balance(Vec::new(), 10), 0.0);
+    }
+}
@@ -0,0 +1,255 @@
+//! Daily concentration risk per corridor.
+//!
+//! [`ConcentrationService::run_once`] computes the Herfindahl–Hirschman index
+//! (see [`crate::analytics::concentration`]) of each corridor's volume over
+//! the last complete [`WINDOW_HOURS`] across sending accounts and receiving
+//! accounts, from stored payments, and across market makers, from the latest
+//! network trades: both accounts of a trade between the corridor's assets are
+//! credited with its amount in the corridor's first asset. Each window is
+//! stored in `corridor_concentration` and shown in the corridor detail.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::analytics::concentration::{concentration, Concentration};
+use crate::database::Database;
+use crate::db::concentration::{CorridorConcentration, PaymentSide};
+use crate::models::corridor::CorridorKey;
+use crate::rpc::{StellarRpcClient, Trade};
+
+/// Length of each concentration window
+pub const WINDOW_HOURS: i64 = 24;
+
+/// Latest network trades scanned for market makers
+const MAX_TRADES: u32 = 10_000;
+
+/// Volume per account, per corridor key
+pub type AccountVolumes = BTreeMap<String, HashMap<String, f64>>;
+
+fn group_volumes(rows: Vec<(String, String, f64)>) -> AccountVolumes {
+    let mut volumes = AccountVolumes::new();
+    for (corridor_key, account, volume) in rows {
+        *volumes
+            .entry(corridor_key)
+            .or_default()
+            .entry(account)
+            .or_default() += volume;
+    }
+    volumes
+}
+
+/// The corridor of a trade closed in `[start, end)` and its amount in the
+/// corridor's first asset. `None` for trades outside the window or with an
+/// unparseable time, asset or amount.
+pub fn corridor_trade(
+    trade: &Trade,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Option<(CorridorKey, f64)> {
+    let closed_at = DateTime::parse_from_rfc3339(&trade.ledger_close_time)
+        .ok()?
+        .with_timezone(&Utc);
+    if closed_at < start || closed_at >= end {
+        return None;
+    }
+    let (base, counter) = (trade.base_asset()?, trade.counter_asset()?);
+    let key = format!("{}->{}", base.key(), counter.key())
+        .parse::<CorridorKey>()
+        .ok()?;
+    let base_first = key.as_str().starts_with(&format!("{}->", base.key()));
+    let amount = if base_first {
+        &trade.base_amount
+    } else {
+        &trade.counter_amount
+    };
+    Some((key, amount.parse::<f64>().ok()?))
+}
+
+/// Volume traded per account in each corridor, for trades closed in
+/// `[start, end)`. Amounts are in the corridor's first asset.
+pub fn market_maker_volumes(
+    trades: &[Trade],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> AccountVolumes {
+    let mut volumes = AccountVolumes::new();
+    for trade in trades {
+        let Some((key, amount)) = corridor_trade(trade, start, end) else {
+            continue;
+        };
+        let accounts = volumes.entry(key.to_string()).or_default();
+        *accounts.entry(trade.base_account.clone()).or_default() += amount;
+        *accounts.entry(trade.counter_account.clone()).or_default() += amount;
+    }
+    volumes
+}
+
+/// One row per corridor with volume on any side
+pub fn build_rows(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    senders: &AccountVolumes,
+    receivers: &AccountVolumes,
+    market_makers: &AccountVolumes,
+) -> Vec<CorridorConcentration> {
+    let side = |volumes: &AccountVolumes, key: &str| -> Option<Concentration> {
+        concentration(volumes.get(key)?.values().copied())
+    };
+    let keys: BTreeSet<&String> = senders
+        .keys()
+        .chain(receivers.keys())
+        .chain(market_makers.keys())
+        .collect();
+
+    keys.into_iter()
+        .map(|key| {
+            let sending = side(senders, key);
+            let receiving = side(receivers, key);
+            let making = side(market_makers, key);
+            CorridorConcentration {
+                corridor_key: key.clone(),
+                window_start: start.to_rfc3339(),
+                window_end: end.to_rfc3339(),
+                sender_hhi: sending.map(|c| c.hhi),
+                senders: sending.map_or(0, |c| c.participants),
+                top_sender_share: sending.map(|c| c.top_share),
+                receiver_hhi: receiving.map(|c| c.hhi),
+                receivers: receiving.map_or(0, |c| c.participants),
+                top_receiver_share: receiving.map(|c| c.top_share),
+                market_maker_hhi: making.map(|c| c.hhi),
+                market_makers: making.map_or(0, |c| c.participants),
+                top_market_maker_share: making.map(|c| c.top_share),
+            }
+        })
+        .collect()
+}
+
+pub struct ConcentrationService {
+    db: Arc<Database>,
+    rpc: Arc<StellarRpcClient>,
+}
+
+impl ConcentrationService {
+    pub fn new(db: Arc<Database>, rpc: Arc<StellarRpcClient>) -> Self {
+        Self { db, rpc }
+    }
+
+    /// Compute and store the window ending at the start of the current hour.
+    /// If trades cannot be fetched the window is stored without market makers.
+    /// Returns the number of corridors stored.
+    pub async fn run_once(&self) -> Result<usize> {
+        let end = Utc::now()
+            .duration_trunc(Duration::hours(1))
+            .unwrap_or_else(|_| Utc::now());
+        let start = end - Duration::hours(WINDOW_HOURS);
+        let store = self.db.corridor_concentrations();
+
+        let senders = group_volumes(
+            store
+                .payment_volumes(PaymentSide::Sender, start, end)
+                .await?,
+        );
+        let receivers = group_volumes(
+            store
+                .payment_volumes(PaymentSide::Receiver, start, end)
+                .await?,
+        );
+        let market_makers = match self.rpc.fetch_all_trades(Some(MAX_TRADES)).await {
+            Ok(trades) => market_maker_volumes(&trades, start, end),
+            Err(e) => {
+                warn!("Concentration window without market makers: {}", e);
+                AccountVolumes::new()
+            }
+        };
+
+        let rows = build_rows(start, end, &senders, &receivers, &market_makers);
+        store.record(&rows).await?;
+        info!(
+            "Recorded concentration for {} corridors ({} to {})",
+            rows.len(),
+            start,
+            end
+        );
+        Ok(rows.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::Price;
+    use chrono::TimeZone;
+
+    const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn trade(closed_at: &str, base_account: &str, counter_account: &str, usdc: &str) -> Trade {
+        // Native base, USDC counter; corridor order puts USDC first
+        Trade {
+            id: format!("{}-{}", closed_at, base_account),
+            ledger_close_time: closed_at.to_string(),
+            base_account: base_account.to_string(),
+            base_amount: "1000.0".to_string(),
+            base_asset_type: "native".to_string(),
+            base_asset_code: None,
+            base_asset_issuer: None,
+            counter_account: counter_account.to_string(),
+            counter_amount: usdc.to_string(),
+            counter_asset_type: "credit_alphanum4".to_string(),
+            counter_asset_code: Some("USDC".to_string()),
+            counter_asset_issuer: Some(USDC_ISSUER.to_string()),
+            price: Price { n: 1, d: 10 },
+            trade_type: "orderbook".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_market_makers_are_credited_in_corridor_first_asset() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = start + Duration::hours(WINDOW_HOURS);
+        let trades = [
+            trade("2024-01-01T05:00:00Z", "GMAKER", "GTAKER1", "90.0"),
+            trade("2024-01-01T06:00:00Z", "GMAKER", "GTAKER2", "10.0"),
+            // Outside the window
+            trade("2024-01-02T00:00:00Z", "GOTHER", "GTAKER3", "500.0"),
+        ];
+
+        let volumes = market_maker_volumes(&trades, start, end);
+        let key = format!("USDC:{}->XLM:native", USDC_ISSUER);
+        let accounts = &volumes[&key];
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts["GMAKER"], 100.0);
+        assert_eq!(accounts["GTAKER1"], 90.0);
+    }
+
+    #[test]
+    fn test_build_rows_covers_every_side() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = start + Duration::hours(WINDOW_HOURS);
+        let senders = group_volumes(vec![
+            ("A".to_string(), "GWHALE".to_string(), 90.0),
+            ("A".to_string(), "GSMALL".to_string(), 10.0),
+        ]);
+        let receivers = group_volumes(vec![("A".to_string(), "GSHOP".to_string(), 100.0)]);
+        let market_makers = group_volumes(vec![("B".to_string(), "GMAKER".to_string(), 5.0)]);
+
+        let rows = build_rows(start, end, &senders, &receivers, &market_makers);
+        assert_eq!(rows.len(), 2);
+
+        let a = &rows[0];
+        assert_eq!(a.corridor_key, "A");
+        assert!((a.sender_hhi.unwrap() - 8_200.0).abs() < 1e-6);
+        assert_eq!(a.senders, 2);
+        assert_eq!(a.top_sender_share, Some(0.9));
+        assert_eq!(a.receiver_hhi, Some(10_000.0));
+        assert_eq!(a.market_maker_hhi, None);
+        assert_eq!(a.market_makers, 0);
+
+        let b = &rows[1];
+        assert_eq!(b.sender_hhi, None);
+        assert_eq!(b.market_maker_hhi, Some(10_000.0));
+        assert_eq!(b.window_end, end.to_rfc3339());
+    }
+}
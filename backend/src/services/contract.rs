@@ -1,18 +1,31 @@
-//! Contract Service for submitting snapshots to Soroban smart contracts
+//! Contract Service for submitting snapshots to the analytics contract
 //!
 //! This service handles:
 //! - Connecting to Soroban RPC endpoints
-//! - Submitting snapshot hashes on-chain
+//! - Submitting snapshot hashes on-chain through the
+//!   `stellar-insights-contract-bindings` crate
+//! - Extending the TTL of on-chain state so it is not archived
 //! - Retry logic with exponential backoff
 //! - Comprehensive error handling and logging
 
 use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::sync::Arc;
 use std::time::Duration;
+use stellar_insights_contract_bindings::ttl::extend_ttl_envelope;
+use stellar_insights_contract_bindings::{
+    account_address, AnalyticsContract, Call, FromScVal, SnapshotMetadata,
+};
+use stellar_xdr::curr::{LedgerKey, Limits, TransactionEnvelope, WriteXdr};
 use tracing::{debug, error, info, warn};
 
+use crate::config::SigningSettings;
+use crate::contract_events::decode::decode_scval;
+use crate::signer::{build_signer, Signer};
+
 const MAX_RETRIES: u32 = 3;
 const INITIAL_BACKOFF_MS: u64 = 1000;
 const BACKOFF_MULTIPLIER: u64 = 2;
@@ -27,15 +40,16 @@ pub struct ContractConfig {
     pub contract_id: String,
     /// Network passphrase (e.g., "Test SDF Network ; September 2015" for testnet)
     pub network_passphrase: String,
-    /// Source account secret key for signing transactions
-    pub source_secret_key: String,
 }
 
-/// Service for interacting with the Soroban snapshot contract
+/// Service for interacting with the analytics contract
 #[derive(Clone)]
 pub struct ContractService {
     client: Client,
     config: ContractConfig,
+    contract: AnalyticsContract,
+    /// Key of the source account that signs submission transactions
+    signer: Arc<dyn Signer>,
 }
 
 /// RPC request structure for Soroban
@@ -86,32 +100,42 @@ impl std::error::Error for RpcError {}
 pub struct SubmissionResult {
     /// Transaction hash
     pub transaction_hash: String,
-    /// Epoch number
+    /// Epoch number (0 for calls not tied to an epoch)
     pub epoch: u64,
     /// Ledger number where the transaction was included
     pub ledger: u64,
-    /// Timestamp from the contract
+    /// Timestamp from the contract (0 for calls that return none)
     pub timestamp: u64,
 }
 
 impl ContractService {
     /// Create a new contract service instance
-    pub fn new(config: ContractConfig) -> Result<Self> {
+    pub fn new(config: ContractConfig, signer: Arc<dyn Signer>) -> Result<Self> {
         let client = Client::builder()
             .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()
             .context("Failed to create HTTP client")?;
+        let contract =
+            AnalyticsContract::new(&config.contract_id).context("Invalid contract ID")?;
 
         info!(
-            "Initialized ContractService with RPC URL: {}, Contract ID: {}",
-            config.rpc_url, config.contract_id
+            "Initialized ContractService with RPC URL: {}, Contract ID: {}, signer: {}",
+            config.rpc_url,
+            config.contract_id,
+            signer.backend()
         );
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            contract,
+            signer,
+        })
     }
 
-    /// Create from environment variables
-    pub fn from_env() -> Result<Self> {
+    /// Create from environment variables. The source account key is
+    /// `signing.stellar_source` (local, vault or kms).
+    pub async fn from_env(signing: &SigningSettings) -> Result<Self> {
         let config = ContractConfig {
             rpc_url: std::env::var("SOROBAN_RPC_URL")
                 .unwrap_or_else(|_| "https://soroban-testnet.stellar.org".to_string()),
@@ -119,11 +143,12 @@ impl ContractService {
                 .context("SNAPSHOT_CONTRACT_ID environment variable not set")?,
             network_passphrase: std::env::var("STELLAR_NETWORK_PASSPHRASE")
                 .unwrap_or_else(|_| "Test SDF Network ; September 2015".to_string()),
-            source_secret_key: std::env::var("STELLAR_SOURCE_SECRET_KEY")
-                .context("STELLAR_SOURCE_SECRET_KEY environment variable not set")?,
         };
+        let signer = build_signer(&signing.stellar_source, signing)
+            .await?
+            .context("STELLAR_SOURCE_SECRET_KEY environment variable not set")?;
 
-        Self::new(config)
+        Self::new(config, signer)
     }
 
     /// Submit a snapshot hash to the on-chain contract
@@ -171,13 +196,41 @@ impl ContractService {
             hex::encode(hash)
         );
 
+        self.submit_with_retry(hash, None, epoch).await
+    }
+
+    /// Submit a snapshot hash together with the hash of its SLA section, so
+    /// the SLA figures can be verified on their own
+    pub async fn submit_snapshot_with_sla(
+        &self,
+        hash: [u8; 32],
+        sla_hash: [u8; 32],
+        epoch: u64,
+    ) -> Result<SubmissionResult> {
+        info!(
+            "Submitting snapshot hash for epoch {}: {} (SLA hash {})",
+            epoch,
+            hex::encode(hash),
+            hex::encode(sla_hash)
+        );
+
+        self.submit_with_retry(hash, Some(sla_hash), epoch).await
+    }
+
+    /// Submit with retries and exponential backoff on transient failures
+    async fn submit_with_retry(
+        &self,
+        hash: [u8; 32],
+        sla_hash: Option<[u8; 32]>,
+        epoch: u64,
+    ) -> Result<SubmissionResult> {
         let mut attempt = 0;
         let mut backoff_ms = INITIAL_BACKOFF_MS;
 
         loop {
             attempt += 1;
 
-            match self.try_submit_snapshot(hash, epoch).await {
+            match self.try_submit_snapshot(hash, sla_hash, epoch).await {
                 Ok(result) => {
                     info!(
                         "✓ Successfully submitted snapshot for epoch {} (tx: {}, ledger: {})",
@@ -210,14 +263,34 @@ impl ContractService {
     }
 
     /// Single attempt to submit snapshot (without retry logic)
-    async fn try_submit_snapshot(&self, hash: [u8; 32], epoch: u64) -> Result<SubmissionResult> {
-        // Step 1: Build the contract invocation
+    async fn try_submit_snapshot(
+        &self,
+        hash: [u8; 32],
+        sla_hash: Option<[u8; 32]>,
+        epoch: u64,
+    ) -> Result<SubmissionResult> {
+        // Step 1: Build the contract invocation; the signer is the caller
         debug!("Building contract invocation for epoch {}", epoch);
-        let invoke_args = self.build_invoke_args(hash, epoch)?;
+        let caller = account_address(self.signer.public_key().await?);
+        let call = match sla_hash {
+            Some(sla_hash) => self
+                .contract
+                .submit_snapshot_with_sla(epoch, hash, sla_hash, &caller),
+            None => self.contract.submit_snapshot(epoch, hash, &caller),
+        };
+
+        self.send_call(&call, epoch).await
+    }
 
+    /// Simulate, sign and send `call`, then wait for it to be confirmed
+    async fn send_call<T: FromScVal>(
+        &self,
+        call: &Call<T>,
+        epoch: u64,
+    ) -> Result<SubmissionResult> {
         // Step 2: Simulate the transaction
         debug!("Simulating transaction");
-        let simulated = self.simulate_transaction(&invoke_args).await?;
+        let simulated = self.simulate_transaction(call).await?;
 
         // Step 3: Prepare and sign the transaction
         debug!("Preparing and signing transaction");
@@ -234,40 +307,31 @@ impl ContractService {
         Ok(result)
     }
 
-    /// Build contract invocation arguments
-    fn build_invoke_args(&self, hash: [u8; 32], epoch: u64) -> Result<serde_json::Value> {
-        // Convert hash to hex for the contract call
-        let hash_hex = hex::encode(hash);
-
-        // Build Soroban contract invocation parameters
-        // Format: invoke contract_id submit_snapshot [hash_bytes, epoch_u64]
-        Ok(json!({
-            "contractId": self.config.contract_id,
-            "function": "submit_snapshot",
-            "args": [
-                {
-                    "type": "bytes",
-                    "value": hash_hex
-                },
-                {
-                    "type": "u64",
-                    "value": epoch.to_string()
-                }
-            ]
-        }))
+    /// Simulate the transaction to get resource estimates
+    async fn simulate_transaction<T: FromScVal>(
+        &self,
+        call: &Call<T>,
+    ) -> Result<serde_json::Value> {
+        let envelope = call.simulation_envelope(self.signer.public_key().await?);
+        self.simulate_envelope(envelope, call.function()).await
     }
 
-    /// Simulate the transaction to get resource estimates
-    async fn simulate_transaction(
+    /// Simulate an unsigned transaction; `label` names it in errors
+    async fn simulate_envelope(
         &self,
-        invoke_args: &serde_json::Value,
+        envelope: TransactionEnvelope,
+        label: &str,
     ) -> Result<serde_json::Value> {
+        let envelope = envelope
+            .to_xdr(Limits::none())
+            .context("Failed to encode transaction")?;
+
         let request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id: 1,
             method: "simulateTransaction".to_string(),
             params: json!({
-                "transaction": invoke_args
+                "transaction": STANDARD.encode(envelope)
             }),
         };
 
@@ -293,8 +357,29 @@ impl ContractService {
             ));
         }
 
-        body.result
-            .ok_or_else(|| anyhow::anyhow!("No simulation result returned (status: {})", status))
+        let result = body
+            .result
+            .ok_or_else(|| anyhow::anyhow!("No simulation result returned (status: {})", status))?;
+
+        // Contract panics come back as a successful RPC call with an error
+        if let Some(error) = result.get("error").and_then(|e| e.as_str()) {
+            return Err(anyhow::anyhow!("{} simulation failed: {}", label, error));
+        }
+
+        Ok(result)
+    }
+
+    /// Simulate a read-only call and decode its return value
+    async fn simulate_call<T: FromScVal>(&self, call: &Call<T>) -> Result<T> {
+        let result = self.simulate_transaction(call).await?;
+        let xdr = result
+            .get("results")
+            .and_then(|r| r.get(0))
+            .and_then(|r| r.get("xdr"))
+            .and_then(|x| x.as_str())
+            .ok_or_else(|| anyhow::anyhow!("No return value in {} simulation", call.function()))?;
+
+        Ok(call.decode(decode_scval(xdr)?)?)
     }
 
     /// Prepare and sign the transaction
@@ -302,13 +387,16 @@ impl ContractService {
         // In a real implementation, this would:
         // 1. Extract the transaction envelope from simulation
         // 2. Set appropriate fees and sequence number
-        // 3. Sign with the source account's secret key
+        // 3. Sign the transaction hash with `self.signer`
         // 4. Return the signed XDR
 
         // For now, return a placeholder that would need stellar-sdk integration
         // TODO: Integrate stellar-sdk for proper transaction signing
 
-        warn!("Transaction signing not yet implemented - requires stellar-sdk integration");
+        warn!(
+            "Transaction signing not yet implemented - requires stellar-sdk integration ({} signer configured)",
+            self.signer.backend()
+        );
         Err(anyhow::anyhow!(
             "Transaction signing requires stellar-sdk library integration"
         ))
@@ -417,7 +505,9 @@ impl ContractService {
                         // Get timestamp from contract return value
                         let timestamp = result
                             .get("returnValue")
-                            .and_then(|rv| rv.as_u64())
+                            .and_then(|rv| rv.as_str())
+                            .and_then(|xdr| decode_scval(xdr).ok())
+                            .and_then(|value| u64::from_scval(value).ok())
                             .unwrap_or(0);
 
                         return Ok(SubmissionResult {
@@ -484,7 +574,7 @@ impl ContractService {
             epoch, hash
         );
 
-        // Convert hex hash back to bytes for contract call
+        // Convert hex hash back to bytes to compare with the stored hash
         let hash_bytes = hex::decode(hash).context("Invalid hash format")?;
 
         if hash_bytes.len() != 32 {
@@ -494,141 +584,126 @@ impl ContractService {
         let mut hash_array = [0u8; 32];
         hash_array.copy_from_slice(&hash_bytes);
 
-        // Call the contract's verify_snapshot function
-        let verify_args = json!({
-            "contractId": self.config.contract_id,
-            "function": "verify_snapshot",
-            "args": [
-                {
-                    "type": "bytes",
-                    "value": hash
-                }
-            ]
-        });
+        match self.simulate_call(&self.contract.get_snapshot(epoch)).await {
+            Ok(snapshot) => {
+                let verified = snapshot.is_some_and(|s| s.matches(&hash_array));
+                debug!("Verification result for epoch {}: {}", epoch, verified);
+                Ok(verified)
+            }
+            Err(e) => {
+                warn!("Verification request failed: {}", e);
+                Ok(false)
+            }
+        }
+    }
 
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: "simulateTransaction".to_string(),
-            params: json!({
-                "transaction": verify_args
-            }),
-        };
+    /// Get the hex snapshot hash stored for a specific epoch
+    pub async fn get_snapshot_by_epoch(&self, epoch: u64) -> Result<Option<String>> {
+        debug!("Getting snapshot for epoch {}", epoch);
 
-        let response = self
-            .client
-            .post(&self.config.rpc_url)
-            .json(&request)
-            .send()
+        let snapshot = self
+            .simulate_call(&self.contract.get_snapshot(epoch))
             .await
-            .context("Failed to send verification request")?;
+            .context("Get snapshot failed")?;
 
-        let body: JsonRpcResponse<serde_json::Value> = response
-            .json()
+        Ok(snapshot.map(|s| hex::encode(s.hash)))
+    }
+
+    /// Get the full snapshot metadata stored for a specific epoch
+    pub async fn get_snapshot_metadata(&self, epoch: u64) -> Result<Option<SnapshotMetadata>> {
+        self.simulate_call(&self.contract.get_snapshot(epoch))
             .await
-            .context("Failed to parse verification response")?;
+            .context("Get snapshot failed")
+    }
 
-        if let Some(error) = body.error {
-            warn!("Verification request failed: {}", error.message);
-            return Ok(false);
-        }
+    /// Epochs of the `n` newest snapshots on-chain, newest first
+    pub async fn get_recent_epochs(&self, n: u32) -> Result<Vec<u64>> {
+        let snapshots = self
+            .simulate_call(&self.contract.get_recent_snapshots(n))
+            .await
+            .context("Get recent snapshots failed")?;
 
-        if let Some(result) = body.result {
-            // Extract the return value from the simulation
-            let return_value = result
-                .get("returnValue")
-                .and_then(|rv| rv.as_bool())
-                .unwrap_or(false);
-
-            debug!("Verification result for epoch {}: {}", epoch, return_value);
-            Ok(return_value)
-        } else {
-            Ok(false)
-        }
+        Ok(snapshots.into_iter().map(|s| s.epoch).collect())
     }
 
-    /// Get snapshot data for a specific epoch from the contract
-    pub async fn get_snapshot_by_epoch(&self, epoch: u64) -> Result<Option<String>> {
-        debug!("Getting snapshot for epoch {}", epoch);
+    /// Extend the TTL of an epoch's on-chain entries to at least `ledgers`
+    /// ledgers, so the snapshot is not archived for unpaid rent
+    pub async fn extend_snapshot_ttl(&self, epoch: u64, ledgers: u32) -> Result<SubmissionResult> {
+        debug!("Extending TTL of epoch {} to {} ledgers", epoch, ledgers);
 
-        let get_args = json!({
-            "contractId": self.config.contract_id,
-            "function": "get_snapshot",
-            "args": [
-                {
-                    "type": "u64",
-                    "value": epoch.to_string()
-                }
-            ]
-        });
+        self.send_call(&self.contract.extend_snapshot_ttl(epoch, ledgers), epoch)
+            .await
+            .with_context(|| format!("Extending TTL of epoch {} failed", epoch))
+    }
 
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: "simulateTransaction".to_string(),
-            params: json!({
-                "transaction": get_args
-            }),
-        };
+    /// Extend the TTL of arbitrary ledger entries, such as a contract's
+    /// instance and code, to at least `extend_to` ledgers
+    pub async fn extend_footprint_ttl(
+        &self,
+        keys: Vec<LedgerKey>,
+        extend_to: u32,
+    ) -> Result<SubmissionResult> {
+        debug!(
+            "Extending TTL of {} entries to {} ledgers",
+            keys.len(),
+            extend_to
+        );
 
-        let response = self
-            .client
-            .post(&self.config.rpc_url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send get snapshot request")?;
+        let envelope = extend_ttl_envelope(self.signer.public_key().await?, keys, extend_to);
+        let simulated = self
+            .simulate_envelope(envelope, "extend_footprint_ttl")
+            .await?;
+        let signed_xdr = self.prepare_and_sign_transaction(&simulated)?;
+        let tx_hash = self.send_transaction(&signed_xdr).await?;
 
-        let body: JsonRpcResponse<serde_json::Value> = response
-            .json()
-            .await
-            .context("Failed to parse get snapshot response")?;
+        self.wait_for_transaction(&tx_hash, 0).await
+    }
 
-        if let Some(error) = body.error {
-            if error.message.contains("not found") {
-                return Ok(None);
-            }
-            return Err(anyhow::anyhow!("Get snapshot failed: {}", error.message));
-        }
+    /// Get the hex SLA section hash stored for a specific epoch
+    pub async fn get_sla_hash(&self, epoch: u64) -> Result<Option<String>> {
+        debug!("Getting SLA hash for epoch {}", epoch);
 
-        if let Some(result) = body.result {
-            let hash_hex = result
-                .get("returnValue")
-                .and_then(|rv| rv.as_str())
-                .map(|s| s.to_string());
+        let sla_hash = self
+            .simulate_call(&self.contract.get_sla_hash(epoch))
+            .await
+            .context("Get SLA hash failed")?;
 
-            Ok(hash_hex)
-        } else {
-            Ok(None)
-        }
+        Ok(sla_hash.map(hex::encode))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::signer::LocalSigner;
+    use ed25519_dalek::SigningKey;
 
     #[test]
-    fn test_build_invoke_args() {
+    fn test_targets_configured_contract() {
         let config = ContractConfig {
             rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-            contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+            contract_id: "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O".to_string(),
             network_passphrase: "Test SDF Network ; September 2015".to_string(),
-            source_secret_key: "S...".to_string(),
         };
+        let signer = Arc::new(LocalSigner::new(SigningKey::from_bytes(&[1u8; 32])));
 
-        let service = ContractService::new(config).unwrap();
-        let hash = [0u8; 32];
-        let epoch = 123;
-
-        let args = service.build_invoke_args(hash, epoch).unwrap();
+        let service = ContractService::new(config.clone(), signer.clone()).unwrap();
+        let call = service
+            .contract
+            .submit_snapshot(123, [0u8; 32], &account_address([2u8; 32]));
 
         assert_eq!(
-            args["contractId"],
-            "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA"
+            call.args.contract_address.to_string(),
+            "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O"
         );
-        assert_eq!(args["function"], "submit_snapshot");
-        assert!(args["args"].is_array());
+        assert_eq!(call.function(), "submit_snapshot");
+        assert_eq!(call.args.args.len(), 3);
+
+        let invalid = ContractConfig {
+            contract_id: "not-a-contract".to_string(),
+            ..config
+        };
+        assert!(ContractService::new(invalid, signer).is_err());
     }
 
     #[tokio::test]
@@ -0,0 +1,361 @@
+//! Watches the TTL of deployed contracts' state.
+//!
+//! Soroban archives a persistent entry once its TTL runs out, and a contract
+//! whose instance or Wasm code entry is archived can no longer be called until
+//! someone restores it. [`ContractTtlWatcher::run_once`] reads both entries of
+//! every watched contract with Soroban RPC `getLedgerEntries` and, for those
+//! with at most [`ContractTtlConfig::warn_ledgers`] ledgers left, queues a
+//! `contract.ttl_expiring` webhook event. With auto-extension enabled it also
+//! submits an `ExtendFootprintTtl` transaction for them first.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_insights_contract_bindings::parse_address;
+use stellar_insights_contract_bindings::ttl::{
+    contract_code_key, contract_instance_key, instance_wasm_hash,
+};
+use stellar_xdr::curr::{LedgerEntryData, LedgerKey, Limits, ReadXdr, WriteXdr};
+use tracing::{info, warn};
+
+use super::contract::ContractService;
+use crate::webhooks::events::ContractTtlExpiringEvent;
+use crate::webhooks::{WebhookEventType, WebhookService};
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Which contracts are watched and what happens to expiring entries, built
+/// from [`ContractTtlSettings`](crate::config::ContractTtlSettings)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractTtlConfig {
+    /// Soroban RPC endpoint URL
+    pub rpc_url: String,
+    /// Contract IDs (`C...`) whose instance and code are watched
+    pub contract_ids: Vec<String>,
+    /// Entries with at most this many ledgers left are reported
+    pub warn_ledgers: u32,
+    /// When set, expiring entries are extended to this many ledgers
+    pub auto_extend_to: Option<u32>,
+}
+
+impl Default for ContractTtlConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: "https://soroban-testnet.stellar.org".to_string(),
+            contract_ids: Vec::new(),
+            // About 7 days of 5-second ledgers
+            warn_ledgers: 120_960,
+            auto_extend_to: None,
+        }
+    }
+}
+
+/// `result` of a `getLedgerEntries` call
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LedgerEntriesResult {
+    /// Null when none of the keys exist
+    #[serde(default)]
+    entries: Option<Vec<RawLedgerEntry>>,
+    latest_ledger: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawLedgerEntry {
+    /// Base64 XDR `LedgerEntryData`
+    xdr: String,
+    /// Absent for entries without a TTL
+    #[serde(default)]
+    live_until_ledger_seq: Option<u32>,
+}
+
+/// A ledger entry as read from Soroban RPC
+#[derive(Debug, Clone, PartialEq)]
+struct LiveEntry {
+    data: LedgerEntryData,
+    /// `None` for entries without a TTL
+    live_until_ledger: Option<u32>,
+}
+
+/// The TTL of one watched entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryTtl {
+    pub contract_id: String,
+    /// "instance" or "code"
+    pub entry: &'static str,
+    pub key: LedgerKey,
+    pub live_until_ledger: u32,
+    pub latest_ledger: u32,
+}
+
+impl EntryTtl {
+    pub fn ledgers_remaining(&self) -> u32 {
+        self.live_until_ledger.saturating_sub(self.latest_ledger)
+    }
+}
+
+/// Checks watched contracts and extends or reports expiring state
+pub struct ContractTtlWatcher {
+    client: Client,
+    config: ContractTtlConfig,
+    /// Submits extensions; only needed with auto-extension
+    contracts: Option<Arc<ContractService>>,
+    webhooks: Arc<WebhookService>,
+}
+
+impl ContractTtlWatcher {
+    pub fn new(
+        config: ContractTtlConfig,
+        contracts: Option<Arc<ContractService>>,
+        webhooks: Arc<WebhookService>,
+    ) -> Result<Self> {
+        for id in &config.contract_ids {
+            parse_address(id).with_context(|| format!("Invalid watched contract ID {}", id))?;
+        }
+        if config.auto_extend_to.is_some() && contracts.is_none() {
+            warn!(
+                "CONTRACT_TTL_AUTO_EXTEND is set without a contract source account; \
+                 expiring entries will only be reported"
+            );
+        }
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            config,
+            contracts,
+            webhooks,
+        })
+    }
+
+    /// Check every watched contract. Returns the number of expiring entries.
+    pub async fn run_once(&self) -> Result<usize> {
+        let mut expiring = 0;
+        for contract_id in &self.config.contract_ids {
+            let entries = match self.contract_ttls(contract_id).await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    warn!("Checking TTL of contract {} failed: {:#}", contract_id, e);
+                    continue;
+                }
+            };
+            let entries: Vec<EntryTtl> = entries
+                .into_iter()
+                .filter(|entry| entry.ledgers_remaining() <= self.config.warn_ledgers)
+                .collect();
+            if entries.is_empty() {
+                continue;
+            }
+            expiring += entries.len();
+
+            let extended_to = self.extend(contract_id, &entries).await;
+            for entry in entries {
+                warn!(
+                    "Contract {} {} entry expires in {} ledgers (at ledger {})",
+                    entry.contract_id,
+                    entry.entry,
+                    entry.ledgers_remaining(),
+                    entry.live_until_ledger
+                );
+                let event = ContractTtlExpiringEvent {
+                    ledgers_remaining: entry.ledgers_remaining(),
+                    contract_id: entry.contract_id,
+                    entry: entry.entry.to_string(),
+                    live_until_ledger: entry.live_until_ledger,
+                    latest_ledger: entry.latest_ledger,
+                    extended_to,
+                };
+                self.webhooks
+                    .enqueue_for_subscribers(
+                        WebhookEventType::ContractTtlExpiring,
+                        serde_json::to_value(&event)?,
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(expiring)
+    }
+
+    /// Extend `entries` when auto-extension is on, returning the TTL they
+    /// were extended to
+    async fn extend(&self, contract_id: &str, entries: &[EntryTtl]) -> Option<u32> {
+        let extend_to = self.config.auto_extend_to?;
+        let contracts = self.contracts.as_ref()?;
+
+        let keys = entries.iter().map(|entry| entry.key.clone()).collect();
+        match contracts.extend_footprint_ttl(keys, extend_to).await {
+            Ok(result) => {
+                info!(
+                    "Extended TTL of contract {} to {} ledgers in transaction {}",
+                    contract_id, extend_to, result.transaction_hash
+                );
+                Some(extend_to)
+            }
+            Err(e) => {
+                warn!("Extending TTL of contract {} failed: {:#}", contract_id, e);
+                None
+            }
+        }
+    }
+
+    /// TTLs of a contract's instance entry and, for Wasm contracts, its code
+    pub async fn contract_ttls(&self, contract_id: &str) -> Result<Vec<EntryTtl>> {
+        let address = parse_address(contract_id)?;
+        let instance_key = contract_instance_key(&address);
+        let (latest_ledger, instance) = self.get_ledger_entry(&instance_key).await?;
+        let instance = instance
+            .ok_or_else(|| anyhow!("instance entry not found (archived or not deployed)"))?;
+
+        let mut ttls = Vec::with_capacity(2);
+        if let Some(live_until_ledger) = instance.live_until_ledger {
+            ttls.push(EntryTtl {
+                contract_id: contract_id.to_string(),
+                entry: "instance",
+                key: instance_key,
+                live_until_ledger,
+                latest_ledger,
+            });
+        }
+
+        if let Some(wasm_hash) = instance_wasm_hash(&instance.data) {
+            let code_key = contract_code_key(wasm_hash);
+            let (latest_ledger, code) = self.get_ledger_entry(&code_key).await?;
+            let code = code.ok_or_else(|| anyhow!("code entry not found (archived)"))?;
+            if let Some(live_until_ledger) = code.live_until_ledger {
+                ttls.push(EntryTtl {
+                    contract_id: contract_id.to_string(),
+                    entry: "code",
+                    key: code_key,
+                    live_until_ledger,
+                    latest_ledger,
+                });
+            }
+        }
+
+        Ok(ttls)
+    }
+
+    /// The RPC's latest ledger and the entry at `key`, if it is live
+    async fn get_ledger_entry(&self, key: &LedgerKey) -> Result<(u32, Option<LiveEntry>)> {
+        let key = STANDARD.encode(
+            key.to_xdr(Limits::none())
+                .context("Failed to encode ledger key")?,
+        );
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getLedgerEntries",
+            "params": { "keys": [key] }
+        });
+
+        let body: serde_json::Value = self
+            .client
+            .post(&self.config.rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send getLedgerEntries request")?
+            .json()
+            .await
+            .context("Failed to parse getLedgerEntries response")?;
+
+        if let Some(error) = body.get("error") {
+            bail!("getLedgerEntries failed: {}", error);
+        }
+        let result: LedgerEntriesResult = serde_json::from_value(
+            body.get("result")
+                .cloned()
+                .ok_or_else(|| anyhow!("No getLedgerEntries result returned"))?,
+        )?;
+
+        let entry = result
+            .entries
+            .unwrap_or_default()
+            .into_iter()
+            .next()
+            .map(|raw| decode_entry(&raw))
+            .transpose()?;
+        Ok((result.latest_ledger, entry))
+    }
+}
+
+fn decode_entry(raw: &RawLedgerEntry) -> Result<LiveEntry> {
+    let bytes = STANDARD
+        .decode(&raw.xdr)
+        .context("Ledger entry is not base64")?;
+    let data = LedgerEntryData::from_xdr(bytes, Limits::none())
+        .context("Ledger entry is not valid XDR")?;
+    Ok(LiveEntry {
+        data,
+        live_until_ledger: raw.live_until_ledger_seq,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{ContractCodeEntry, ContractCodeEntryExt, Hash};
+
+    #[test]
+    fn test_decode_ledger_entries_result() {
+        let code = LedgerEntryData::ContractCode(ContractCodeEntry {
+            ext: ContractCodeEntryExt::V0,
+            hash: Hash([3u8; 32]),
+            code: vec![0u8, 97, 115, 109].try_into().unwrap(),
+        });
+        let result: LedgerEntriesResult = serde_json::from_value(json!({
+            "entries": [{
+                "key": "",
+                "xdr": STANDARD.encode(code.to_xdr(Limits::none()).unwrap()),
+                "lastModifiedLedgerSeq": 90,
+                "liveUntilLedgerSeq": 1_100
+            }],
+            "latestLedger": 1_000
+        }))
+        .unwrap();
+
+        assert_eq!(result.latest_ledger, 1_000);
+        let entries = result.entries.unwrap();
+        assert_eq!(
+            decode_entry(&entries[0]).unwrap(),
+            LiveEntry {
+                data: code,
+                live_until_ledger: Some(1_100),
+            }
+        );
+
+        let missing: LedgerEntriesResult =
+            serde_json::from_value(json!({"entries": null, "latestLedger": 1_000})).unwrap();
+        assert!(missing.entries.is_none());
+    }
+
+    #[test]
+    fn test_ledgers_remaining_saturates() {
+        let entry = EntryTtl {
+            contract_id: "C".to_string(),
+            entry: "instance",
+            key: contract_code_key([0u8; 32]),
+            live_until_ledger: 1_100,
+            latest_ledger: 1_000,
+        };
+        assert_eq!(entry.ledgers_remaining(), 100);
+        assert_eq!(
+            EntryTtl {
+                latest_ledger: 1_200,
+                ..entry
+            }
+            .ledgers_remaining(),
+            0
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Bloom filter of known corridor keys.
+//!
+//! Crawlers request large numbers of made-up corridor keys. The corridor
+//! detail and badge handlers check this filter first and answer 404 without
+//! touching the cache or database when a key was never seen. A bloom filter
+//! has no false negatives, so a real corridor is never rejected. The filter is
+//! rebuilt from `corridor_metrics` and `corridors` on an interval, and
+//! corridors seen live on Horizon are added as the corridor list computes them.
+
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+use crate::database::Database;
+use crate::models::corridor::Corridor;
+
+/// Target false positive rate
+const FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Headroom for corridors that appear between refreshes
+const CAPACITY_HEADROOM: f64 = 1.5;
+
+const MIN_CAPACITY: usize = 1024;
+
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Fixed-size bloom filter using double hashing over two seeded SipHashes
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Size a filter for `capacity` items at `false_positive_rate`
+    pub fn with_capacity(capacity: usize, false_positive_rate: f64) -> Self {
+        let n = capacity.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-(n * false_positive_rate.ln()) / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / n) * ln2).round().clamp(1.0, 16.0) as u32;
+
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    fn hashes(item: &str) -> (u64, u64) {
+        let mut first = DefaultHasher::new();
+        item.hash(&mut first);
+        let mut second = DefaultHasher::new();
+        0x9e37_79b9_7f4a_7c15_u64.hash(&mut second);
+        item.hash(&mut second);
+        (first.finish(), second.finish() | 1)
+    }
+
+    fn positions(&self, item: &str) -> impl Iterator<Item = u64> + '_ {
+        let (h1, h2) = Self::hashes(item);
+        (0..self.num_hashes as u64)
+            .map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits)
+    }
+
+    pub fn insert(&mut self, item: &str) {
+        let positions: Vec<u64> = self.positions(item).collect();
+        for bit in positions {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    /// False means `item` was definitely never inserted
+    pub fn might_contain(&self, item: &str) -> bool {
+        self.positions(item)
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+}
+
+/// Process-wide filter of canonical corridor keys (see [`CorridorKey`](crate::models::corridor::CorridorKey))
+#[derive(Default)]
+pub struct CorridorKeyFilter {
+    filter: RwLock<Option<BloomFilter>>,
+}
+
+impl CorridorKeyFilter {
+    /// Until the first refresh every key is allowed through
+    pub fn might_contain(&self, corridor_key: &str) -> bool {
+        match self.filter.read() {
+            Ok(filter) => filter
+                .as_ref()
+                .map_or(true, |filter| filter.might_contain(corridor_key)),
+            Err(_) => true,
+        }
+    }
+
+    /// Add a key seen since the last refresh
+    pub fn insert(&self, corridor_key: &str) {
+        if let Ok(mut filter) = self.filter.write() {
+            if let Some(filter) = filter.as_mut() {
+                filter.insert(corridor_key);
+            }
+        }
+    }
+
+    pub fn replace<'a>(&self, keys: impl ExactSizeIterator<Item = &'a str>) {
+        let capacity = ((keys.len() as f64 * CAPACITY_HEADROOM) as usize).max(MIN_CAPACITY);
+        let mut filter = BloomFilter::with_capacity(capacity, FALSE_POSITIVE_RATE);
+        for key in keys {
+            filter.insert(key);
+        }
+        if let Ok(mut current) = self.filter.write() {
+            *current = Some(filter);
+        }
+    }
+
+    /// Rebuild from every corridor with stored metrics or a `corridors` row.
+    /// Returns the number of keys.
+    pub async fn refresh(&self, db: &Database) -> Result<usize> {
+        let mut keys: Vec<String> =
+            sqlx::query_scalar("SELECT DISTINCT corridor_key FROM corridor_metrics")
+                .fetch_all(db.pool())
+                .await?;
+        let registered: Vec<(String, String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT source_asset_code, source_asset_issuer,
+                   destination_asset_code, destination_asset_issuer
+            FROM corridors
+            "#,
+        )
+        .fetch_all(db.pool())
+        .await?;
+        keys.extend(
+            registered
+                .into_iter()
+                .map(|(a_code, a_issuer, b_code, b_issuer)| {
+                    Corridor::new(a_code, a_issuer, b_code, b_issuer).to_string_key()
+                }),
+        );
+
+        self.replace(keys.iter().map(String::as_str));
+        Ok(keys.len())
+    }
+
+    /// Refresh on [`REFRESH_INTERVAL`] until the process stops
+    pub async fn run(&self, db: &Database) {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            match self.refresh(db).await {
+                Ok(count) => tracing::debug!("Corridor key filter rebuilt with {} keys", count),
+                Err(e) => tracing::warn!("Failed to refresh corridor key filter: {}", e),
+            }
+        }
+    }
+}
+
+/// Shared filter checked by the corridor handlers
+pub fn corridor_key_filter() -> &'static CorridorKeyFilter {
+    static FILTER: OnceLock<CorridorKeyFilter> = OnceLock::new();
+    FILTER.get_or_init(CorridorKeyFilter::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter_has_no_false_negatives() {
+        let mut filter = BloomFilter::with_capacity(1000, FALSE_POSITIVE_RATE);
+        let keys: Vec<String> = (0..1000)
+            .map(|i| format!("USDC:G{}->XLM:native", i))
+            .collect();
+        for key in &keys {
+            filter.insert(key);
+        }
+        assert!(keys.iter().all(|key| filter.might_contain(key)));
+
+        let false_positives = (0..10_000)
+            .filter(|i| filter.might_contain(&format!("FAKE:G{}->XLM:native", i)))
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn test_corridor_key_filter_allows_everything_until_loaded() {
+        let filter = CorridorKeyFilter::default();
+        assert!(filter.might_contain("anything"));
+
+        filter.replace(["USDC:GA->XLM:native"].into_iter());
+        assert!(filter.might_contain("USDC:GA->XLM:native"));
+        assert!(!filter.might_contain("EURC:GB->XLM:native"));
+    }
+}
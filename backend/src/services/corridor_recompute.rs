@@ -0,0 +1,378 @@
+//! Recompute hourly corridor metrics from stored payments.
+//!
+//! Incremental aggregation adds each run's payments to the existing hourly
+//! rows, so a fix to the aggregation logic never reaches hours already
+//! aggregated. [`CorridorRecomputeService::recompute`] rebuilds every hour in
+//! a range from the raw `payments` table and replaces the stored rows, or in
+//! dry-run mode only reports how they would change. Used by
+//! `POST /api/admin/corridors/recompute` and the `recompute_corridor_metrics`
+//! binary.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::models::corridor::PaymentRecord;
+use crate::services::aggregation::HourlyCorridorMetrics;
+use crate::services::analytics::compute_metrics_from_payments;
+
+/// Longest range a single recompute may cover
+pub const MAX_RANGE_HOURS: i64 = 24 * 90;
+
+/// Payments loaded per hour; an hour at the limit is flagged in the logs
+const MAX_PAYMENTS_PER_HOUR: i64 = 1_000_000;
+
+/// Diff entries kept in a progress report
+const MAX_DIFF_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecomputeRequest {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl RecomputeRequest {
+    /// Hour buckets covered by the request: from the hour containing `from`
+    /// up to, but excluding, the hour containing `to` (unless `to` is mid-hour)
+    pub fn hours(&self) -> Result<Vec<DateTime<Utc>>> {
+        if self.to <= self.from {
+            bail!("`to` must be after `from`");
+        }
+        let start = truncate_to_hour(self.from);
+        let hours = ((self.to - start).num_minutes() + 59) / 60;
+        if hours > MAX_RANGE_HOURS {
+            bail!("Range covers {} hours; the limit is {}", hours, MAX_RANGE_HOURS);
+        }
+        Ok((0..hours).map(|h| start + Duration::hours(h)).collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecomputeStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// The metrics compared between stored and recomputed rows
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricValues {
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    pub success_rate: f64,
+    pub volume_usd: f64,
+    pub avg_settlement_latency_ms: Option<i32>,
+}
+
+impl From<&HourlyCorridorMetrics> for MetricValues {
+    fn from(m: &HourlyCorridorMetrics) -> Self {
+        Self {
+            total_transactions: m.total_transactions,
+            successful_transactions: m.successful_transactions,
+            failed_transactions: m.failed_transactions,
+            success_rate: m.success_rate,
+            volume_usd: m.volume_usd,
+            avg_settlement_latency_ms: m.avg_settlement_latency_ms,
+        }
+    }
+}
+
+impl MetricValues {
+    fn differs_from(&self, other: &Self) -> bool {
+        const EPSILON: f64 = 1e-6;
+        self.total_transactions != other.total_transactions
+            || self.successful_transactions != other.successful_transactions
+            || self.failed_transactions != other.failed_transactions
+            || self.avg_settlement_latency_ms != other.avg_settlement_latency_ms
+            || (self.success_rate - other.success_rate).abs() > EPSILON
+            || (self.volume_usd - other.volume_usd).abs() > EPSILON
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CorridorDiff {
+    pub corridor_key: String,
+    pub hour_bucket: DateTime<Utc>,
+    pub change: ChangeKind,
+    pub before: Option<MetricValues>,
+    pub after: Option<MetricValues>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecomputeProgress {
+    pub id: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub dry_run: bool,
+    pub status: RecomputeStatus,
+    pub hours_total: usize,
+    pub hours_done: usize,
+    pub payments_processed: usize,
+    /// Hourly rows written (always 0 in dry-run mode)
+    pub rows_written: usize,
+    /// Total number of differing rows; `diff` holds at most the first 500
+    pub changes: usize,
+    pub diff: Vec<CorridorDiff>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl RecomputeProgress {
+    pub fn new(req: &RecomputeRequest, hours_total: usize) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            from: req.from,
+            to: req.to,
+            dry_run: req.dry_run,
+            status: RecomputeStatus::Running,
+            hours_total,
+            hours_done: 0,
+            payments_processed: 0,
+            rows_written: 0,
+            changes: 0,
+            diff: Vec::new(),
+            error: None,
+            started_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+}
+
+fn truncate_to_hour(dt: DateTime<Utc>) -> DateTime<Utc> {
+    dt.duration_trunc(Duration::hours(1)).unwrap_or(dt)
+}
+
+/// Hourly rows for one hour's payments
+pub fn hourly_metrics(
+    hour: DateTime<Utc>,
+    payments: &[PaymentRecord],
+) -> Vec<HourlyCorridorMetrics> {
+    compute_metrics_from_payments(payments)
+        .into_iter()
+        .map(|m| HourlyCorridorMetrics {
+            id: Uuid::new_v4().to_string(),
+            corridor_key: m.corridor_key,
+            asset_a_code: m.asset_a_code,
+            asset_a_issuer: m.asset_a_issuer,
+            asset_b_code: m.asset_b_code,
+            asset_b_issuer: m.asset_b_issuer,
+            hour_bucket: hour,
+            total_transactions: m.total_transactions,
+            successful_transactions: m.successful_transactions,
+            failed_transactions: m.failed_transactions,
+            success_rate: m.success_rate,
+            volume_usd: m.volume_usd,
+            avg_slippage_bps: 0.0,
+            avg_settlement_latency_ms: m.avg_settlement_latency_ms,
+            liquidity_depth_usd: m.liquidity_depth_usd,
+            source: m.source,
+        })
+        .collect()
+}
+
+/// Rows added, removed or changed between the stored and recomputed hour
+pub fn diff_hour(
+    hour: DateTime<Utc>,
+    stored: &[HourlyCorridorMetrics],
+    recomputed: &[HourlyCorridorMetrics],
+) -> Vec<CorridorDiff> {
+    let mut rows: BTreeMap<&str, (Option<MetricValues>, Option<MetricValues>)> = BTreeMap::new();
+    for m in stored {
+        rows.entry(&m.corridor_key).or_default().0 = Some(m.into());
+    }
+    for m in recomputed {
+        rows.entry(&m.corridor_key).or_default().1 = Some(m.into());
+    }
+
+    rows.into_iter()
+        .filter_map(|(key, (before, after))| {
+            let change = match (&before, &after) {
+                (None, Some(_)) => ChangeKind::Added,
+                (Some(_), None) => ChangeKind::Removed,
+                (Some(b), Some(a)) if b.differs_from(a) => ChangeKind::Changed,
+                _ => return None,
+            };
+            Some(CorridorDiff {
+                corridor_key: key.to_string(),
+                hour_bucket: hour,
+                change,
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+pub struct CorridorRecomputeService {
+    db: Arc<Database>,
+}
+
+impl CorridorRecomputeService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Recompute every hour in the request, calling `on_progress` after each
+    /// hour. Each hour is replaced atomically, so a failure leaves earlier
+    /// hours recomputed and later ones untouched.
+    pub async fn recompute(
+        &self,
+        req: &RecomputeRequest,
+        mut progress: RecomputeProgress,
+        mut on_progress: impl FnMut(&RecomputeProgress),
+    ) -> RecomputeProgress {
+        let result = self.run(req, &mut progress, &mut on_progress).await;
+
+        progress.finished_at = Some(Utc::now());
+        match result {
+            Ok(()) => progress.status = RecomputeStatus::Completed,
+            Err(e) => {
+                warn!("Corridor recompute {} failed: {:#}", progress.id, e);
+                progress.status = RecomputeStatus::Failed;
+                progress.error = Some(format!("{:#}", e));
+            }
+        }
+        on_progress(&progress);
+        progress
+    }
+
+    async fn run(
+        &self,
+        req: &RecomputeRequest,
+        progress: &mut RecomputeProgress,
+        on_progress: &mut impl FnMut(&RecomputeProgress),
+    ) -> Result<()> {
+        let aggregation = self.db.aggregation_db();
+
+        for hour in req.hours()? {
+            let end = hour + Duration::hours(1) - Duration::milliseconds(1);
+            let payments = aggregation
+                .fetch_payments_by_timerange(hour, end, MAX_PAYMENTS_PER_HOUR)
+                .await
+                .with_context(|| format!("Failed to load payments for {}", hour))?;
+            if payments.len() as i64 >= MAX_PAYMENTS_PER_HOUR {
+                warn!("Hour {} reached the payment limit; metrics may be partial", hour);
+            }
+
+            let recomputed = hourly_metrics(hour, &payments);
+            let stored = aggregation.fetch_hourly_metrics_by_timerange(hour, hour).await?;
+            let changes = diff_hour(hour, &stored, &recomputed);
+
+            if !req.dry_run && !changes.is_empty() {
+                aggregation
+                    .replace_hourly_corridor_metrics(hour, &recomputed)
+                    .await
+                    .with_context(|| format!("Failed to replace metrics for {}", hour))?;
+                progress.rows_written += recomputed.len();
+            }
+
+            progress.payments_processed += payments.len();
+            progress.changes += changes.len();
+            let room = MAX_DIFF_ENTRIES.saturating_sub(progress.diff.len());
+            progress.diff.extend(changes.into_iter().take(room));
+            progress.hours_done += 1;
+            on_progress(progress);
+        }
+
+        info!(
+            "Corridor recompute {} finished: {} hours, {} payments, {} changes{}",
+            progress.id,
+            progress.hours_done,
+            progress.payments_processed,
+            progress.changes,
+            if req.dry_run { " (dry run)" } else { "" }
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MetricSource;
+    use chrono::TimeZone;
+
+    fn payment(code: &str, amount: f64, at: DateTime<Utc>) -> PaymentRecord {
+        PaymentRecord {
+            id: Uuid::new_v4(),
+            source_asset_code: code.to_string(),
+            source_asset_issuer: "issuer".to_string(),
+            destination_asset_code: code.to_string(),
+            destination_asset_issuer: "issuer".to_string(),
+            amount,
+            successful: true,
+            timestamp: at,
+            submission_time: None,
+            confirmation_time: None,
+            source: MetricSource::Horizon,
+        }
+    }
+
+    #[test]
+    fn test_hours_cover_range() {
+        let from = Utc.with_ymd_and_hms(2026, 1, 1, 10, 30, 0).unwrap();
+        let req = RecomputeRequest {
+            from,
+            to: Utc.with_ymd_and_hms(2026, 1, 1, 13, 0, 0).unwrap(),
+            dry_run: true,
+        };
+        let hours = req.hours().unwrap();
+        assert_eq!(hours.len(), 3);
+        assert_eq!(hours[0], Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap());
+
+        let backwards = RecomputeRequest {
+            from,
+            to: from,
+            dry_run: true,
+        };
+        assert!(backwards.hours().is_err());
+    }
+
+    #[test]
+    fn test_diff_reports_changed_added_and_removed() {
+        let hour = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
+        let at = hour + Duration::minutes(5);
+
+        let stored = hourly_metrics(
+            hour,
+            &[payment("USDC", 10.0, at), payment("EURC", 5.0, at)],
+        );
+        let recomputed = hourly_metrics(
+            hour,
+            &[
+                payment("USDC", 10.0, at),
+                payment("USDC", 2.0, at),
+                payment("BRL", 1.0, at),
+            ],
+        );
+
+        let diff = diff_hour(hour, &stored, &recomputed);
+        let kinds: Vec<ChangeKind> = diff.iter().map(|d| d.change).collect();
+        assert_eq!(diff.len(), 3);
+        assert!(kinds.contains(&ChangeKind::Added));
+        assert!(kinds.contains(&ChangeKind::Removed));
+        let changed = diff.iter().find(|d| d.change == ChangeKind::Changed).unwrap();
+        assert_eq!(changed.before.as_ref().unwrap().total_transactions, 1);
+        assert_eq!(changed.after.as_ref().unwrap().total_transactions, 2);
+
+        assert!(diff_hour(hour, &stored, &stored).is_empty());
+    }
+}
@@ -0,0 +1,319 @@
+//! What-if report for a corridor between any two assets.
+//!
+//! Anchors evaluating a corridor we don't track yet can see, for a given
+//! amount, which DEX paths Horizon finds, how deep the direct order book is
+//! and what the transfer would cost against the mid-market rate.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::Arc;
+use stellar_insights_core::Asset;
+
+use crate::rpc::{OrderBook, PaymentPath, StellarRpcClient};
+use crate::services::price_feed::PriceFeedClient;
+use crate::services::valuation::fallback_usd_rate;
+
+const ORDER_BOOK_LIMIT: u32 = 200;
+
+/// Band around the best bid counted as "near the top of the book"
+const DEPTH_BAND_PCT: f64 = 1.0;
+
+/// One path payment operation per transfer
+const OPERATIONS_PER_TRANSFER: u64 = 1;
+
+const STROOPS_PER_XLM: f64 = 10_000_000.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedPath {
+    /// Assets traded through, source and destination included
+    pub hops: Vec<String>,
+    pub destination_amount: f64,
+    /// Destination units received per source unit
+    pub effective_rate: f64,
+}
+
+impl SimulatedPath {
+    fn from_horizon(path: &PaymentPath, source: &Asset, destination: &Asset, amount: f64) -> Self {
+        let destination_amount: f64 = path.destination_amount.parse().unwrap_or(0.0);
+        let mut hops = vec![source.key()];
+        hops.extend(path.path.iter().map(Asset::key));
+        hops.push(destination.key());
+
+        Self {
+            hops,
+            destination_amount,
+            effective_rate: if amount > 0.0 {
+                destination_amount / amount
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Selling the amount straight into the source/destination order book
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct OrderBookDepth {
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub spread_bps: Option<f64>,
+    /// Source units the bids within 1% of the best bid would absorb
+    pub depth_near_top: f64,
+    /// Source units the whole returned book would absorb
+    pub total_depth: f64,
+    pub filled_amount: f64,
+    pub unfilled_amount: f64,
+    pub destination_amount: f64,
+    pub average_price: Option<f64>,
+    /// Average fill price below the best bid
+    pub slippage_bps: Option<f64>,
+}
+
+impl OrderBookDepth {
+    /// Walk the bids (offers buying the source asset) from the best price.
+    /// Horizon states bid amounts in the counter (destination) asset.
+    pub fn simulate(book: &OrderBook, amount: f64) -> Self {
+        let levels: Vec<(f64, f64)> = book
+            .bids
+            .iter()
+            .filter_map(|bid| {
+                let price: f64 = bid.price.parse().ok()?;
+                let counter: f64 = bid.amount.parse().ok()?;
+                (price > 0.0).then(|| (price, counter / price))
+            })
+            .collect();
+        let best_bid = levels.first().map(|(price, _)| *price);
+        let best_ask = book
+            .asks
+            .first()
+            .and_then(|ask| ask.price.parse::<f64>().ok());
+
+        let mut remaining = amount;
+        let mut received = 0.0;
+        for (price, capacity) in &levels {
+            if remaining <= 0.0 {
+                break;
+            }
+            let fill = remaining.min(*capacity);
+            received += fill * price;
+            remaining -= fill;
+        }
+        let filled = amount - remaining;
+        let average_price = (filled > 0.0).then(|| received / filled);
+
+        Self {
+            best_bid,
+            best_ask,
+            spread_bps: match (best_bid, best_ask) {
+                (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => {
+                    Some((ask - bid) / ((ask + bid) / 2.0) * 10_000.0)
+                }
+                _ => None,
+            },
+            depth_near_top: best_bid
+                .map(|best| {
+                    levels
+                        .iter()
+                        .take_while(|(price, _)| *price >= best * (1.0 - DEPTH_BAND_PCT / 100.0))
+                        .map(|(_, capacity)| capacity)
+                        .sum()
+                })
+                .unwrap_or(0.0),
+            total_depth: levels.iter().map(|(_, capacity)| capacity).sum(),
+            filled_amount: filled,
+            unfilled_amount: remaining.max(0.0),
+            destination_amount: received,
+            average_price,
+            slippage_bps: match (best_bid, average_price) {
+                (Some(best), Some(average)) => Some((best - average) / best * 10_000.0),
+                _ => None,
+            },
+        }
+    }
+
+    fn mid_price(&self) -> Option<f64> {
+        match (self.best_bid, self.best_ask) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MidMarketSource {
+    PriceFeed,
+    OrderBook,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedCost {
+    /// Destination units per source unit at mid-market
+    pub mid_market_rate: Option<f64>,
+    pub mid_market_source: Option<MidMarketSource>,
+    /// Best of the found paths and the direct order book
+    pub expected_destination_amount: f64,
+    pub effective_rate: f64,
+    /// Shortfall against mid-market, in basis points
+    pub total_cost_bps: Option<f64>,
+    pub network_fee_xlm: f64,
+    pub source_usd_rate: Option<f64>,
+    pub destination_usd_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CorridorSimulation {
+    pub source_asset: String,
+    pub destination_asset: String,
+    pub amount: f64,
+    pub paths: Vec<SimulatedPath>,
+    pub best_path: Option<SimulatedPath>,
+    pub order_book: OrderBookDepth,
+    pub cost: SimulatedCost,
+    /// False when neither a path nor the order book can fill the amount
+    pub viable: bool,
+    pub warnings: Vec<String>,
+}
+
+pub struct CorridorSimulator {
+    rpc: Arc<StellarRpcClient>,
+    price_feed: Arc<PriceFeedClient>,
+}
+
+impl CorridorSimulator {
+    pub fn new(rpc: Arc<StellarRpcClient>, price_feed: Arc<PriceFeedClient>) -> Self {
+        Self { rpc, price_feed }
+    }
+
+    async fn usd_rate(&self, asset: &Asset) -> Option<f64> {
+        match self.price_feed.get_price(&asset.key()).await {
+            Ok(rate) if rate > 0.0 && rate.is_finite() => Some(rate),
+            _ => fallback_usd_rate(&asset.code().to_uppercase()),
+        }
+    }
+
+    pub async fn simulate(
+        &self,
+        source: &Asset,
+        destination: &Asset,
+        amount: f64,
+    ) -> Result<CorridorSimulation> {
+        let mut warnings = Vec::new();
+
+        let horizon_paths = self
+            .rpc
+            .fetch_strict_send_paths(source, &format!("{:.7}", amount), destination)
+            .await?;
+        let mut paths: Vec<SimulatedPath> = horizon_paths
+            .iter()
+            .map(|path| SimulatedPath::from_horizon(path, source, destination, amount))
+            .collect();
+        paths.sort_by(|a, b| b.destination_amount.total_cmp(&a.destination_amount));
+        if paths.is_empty() {
+            warnings.push("Horizon found no payment path for this amount".to_string());
+        }
+
+        let book = self
+            .rpc
+            .fetch_order_book(source, destination, ORDER_BOOK_LIMIT)
+            .await?;
+        let order_book = OrderBookDepth::simulate(&book, amount);
+        if order_book.unfilled_amount > 0.0 {
+            warnings.push(format!(
+                "The direct order book can only absorb {:.2} of {:.2}",
+                order_book.filled_amount, amount
+            ));
+        }
+
+        let source_usd_rate = self.usd_rate(source).await;
+        let destination_usd_rate = self.usd_rate(destination).await;
+        let (mid_market_rate, mid_market_source) = match (source_usd_rate, destination_usd_rate) {
+            (Some(s), Some(d)) if d > 0.0 => (Some(s / d), Some(MidMarketSource::PriceFeed)),
+            _ => match order_book.mid_price() {
+                Some(mid) => (Some(mid), Some(MidMarketSource::OrderBook)),
+                None => (None, None),
+            },
+        };
+        if mid_market_rate.is_none() {
+            warnings.push("No price reference for this pair; cost is unavailable".to_string());
+        }
+
+        let direct_amount = if order_book.unfilled_amount > 0.0 {
+            0.0
+        } else {
+            order_book.destination_amount
+        };
+        let expected_destination_amount = paths
+            .first()
+            .map(|p| p.destination_amount)
+            .unwrap_or(0.0)
+            .max(direct_amount);
+        let effective_rate = expected_destination_amount / amount;
+
+        let network_fee_xlm = match self.rpc.fetch_fee_stats().await {
+            Ok(stats) => (stats.base_fee() * OPERATIONS_PER_TRANSFER) as f64 / STROOPS_PER_XLM,
+            Err(e) => {
+                warnings.push(format!("Fee stats unavailable: {}", e));
+                100.0 / STROOPS_PER_XLM
+            }
+        };
+
+        Ok(CorridorSimulation {
+            source_asset: source.key(),
+            destination_asset: destination.key(),
+            amount,
+            best_path: paths.first().cloned(),
+            paths,
+            order_book,
+            cost: SimulatedCost {
+                total_cost_bps: mid_market_rate
+                    .filter(|mid| *mid > 0.0 && expected_destination_amount > 0.0)
+                    .map(|mid| (1.0 - effective_rate / mid) * 10_000.0),
+                mid_market_rate,
+                mid_market_source,
+                expected_destination_amount,
+                effective_rate,
+                network_fee_xlm,
+                source_usd_rate,
+                destination_usd_rate,
+            },
+            viable: expected_destination_amount > 0.0,
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{OrderBookEntry, Price};
+
+    fn entry(price: &str, amount: &str) -> OrderBookEntry {
+        OrderBookEntry {
+            price: price.to_string(),
+            amount: amount.to_string(),
+            price_r: Price { n: 1, d: 1 },
+        }
+    }
+
+    #[test]
+    fn test_order_book_walk() {
+        let book = OrderBook {
+            // 100 and 200 source units of capacity
+            bids: vec![entry("2.0", "200.0"), entry("1.0", "200.0")],
+            asks: vec![entry("2.2", "50.0")],
+            base: Asset::Native,
+            counter: Asset::Native,
+        };
+
+        let depth = OrderBookDepth::simulate(&book, 150.0);
+        assert_eq!(depth.filled_amount, 150.0);
+        assert_eq!(depth.destination_amount, 250.0);
+        assert_eq!(depth.depth_near_top, 100.0);
+        assert_eq!(depth.total_depth, 300.0);
+        assert!((depth.slippage_bps.unwrap() - 1_666.666).abs() < 0.01);
+
+        let thin = OrderBookDepth::simulate(&book, 500.0);
+        assert_eq!(thin.unfilled_amount, 200.0);
+    }
+}
@@ -0,0 +1,254 @@
+//! Corridor health scores.
+//!
+//! A corridor scores 0–100 as the weighted average of four components, each
+//! itself 0–100:
+//!
+//! - success rate: the share of payments that succeeded over the last 24 hours
+//! - liquidity: average liquidity depth, on a log scale reaching 100 at
+//!   [`FULL_LIQUIDITY_USD`]
+//! - volume trend: 50 when volume held steady against the 24 hours before,
+//!   100 when it doubled (or started) and 0 when it stopped
+//! - anchor reliability: the average reliability of the anchors issuing
+//!   either asset
+//!
+//! A component without data (no payments, no volume in either window, no
+//! known anchor) is left out and its weight shared among the others. Weights
+//! come from the `[health_score]` settings (`HEALTH_SCORE_WEIGHT_*`),
+//! overridden per component by the `health_score_weights` table.
+//! [`HealthScoreService::run_once`] scores every recently active corridor and
+//! stores the result for the health history.
+
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::database::Database;
+use crate::db::health_scores::{CorridorHealthScore, HealthInputsRow};
+
+/// Liquidity depth scoring 100
+pub const FULL_LIQUIDITY_USD: f64 = 1_000_000.0;
+
+/// Relative weight of each score component. Weights need not sum to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthWeights {
+    pub success_rate: f64,
+    pub liquidity: f64,
+    pub volume_trend: f64,
+    pub anchor_reliability: f64,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            success_rate: 0.4,
+            liquidity: 0.25,
+            volume_trend: 0.15,
+            anchor_reliability: 0.2,
+        }
+    }
+}
+
+impl HealthWeights {
+    /// These weights with each (component, weight) in `overrides` applied;
+    /// unknown components and negative weights are ignored
+    pub fn with_overrides(mut self, overrides: &[(String, f64)]) -> Self {
+        for (component, weight) in overrides {
+            if !weight.is_finite() || *weight < 0.0 {
+                continue;
+            }
+            match component.as_str() {
+                "success_rate" => self.success_rate = *weight,
+                "liquidity" => self.liquidity = *weight,
+                "volume_trend" => self.volume_trend = *weight,
+                "anchor_reliability" => self.anchor_reliability = *weight,
+                _ => {}
+            }
+        }
+        self
+    }
+}
+
+/// A corridor's score and the components it was computed from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthScore {
+    pub score: f64,
+    pub success_rate: Option<f64>,
+    pub liquidity: Option<f64>,
+    pub volume_trend: Option<f64>,
+    pub anchor_reliability: Option<f64>,
+}
+
+fn liquidity_component(depth_usd: f64) -> f64 {
+    (depth_usd.max(0.0).ln_1p() / FULL_LIQUIDITY_USD.ln_1p() * 100.0).min(100.0)
+}
+
+fn volume_trend_component(volume_usd: f64, previous_volume_usd: f64) -> Option<f64> {
+    if previous_volume_usd <= 0.0 {
+        return (volume_usd > 0.0).then_some(100.0);
+    }
+    let change = ((volume_usd - previous_volume_usd) / previous_volume_usd).clamp(-1.0, 1.0);
+    Some(50.0 + 50.0 * change)
+}
+
+/// Score `inputs` with `weights`. `None` when no component with a positive
+/// weight has data.
+pub fn score(inputs: &HealthInputsRow, weights: &HealthWeights) -> Option<HealthScore> {
+    let success_rate = (inputs.total_transactions > 0)
+        .then(|| inputs.successful_transactions as f64 / inputs.total_transactions as f64 * 100.0);
+    let liquidity = Some(liquidity_component(inputs.liquidity_depth_usd));
+    let volume_trend = volume_trend_component(inputs.volume_usd, inputs.previous_volume_usd);
+    let anchor_reliability = inputs.anchor_reliability.map(|r| r.clamp(0.0, 100.0));
+
+    let (total, weight) = [
+        (success_rate, weights.success_rate),
+        (liquidity, weights.liquidity),
+        (volume_trend, weights.volume_trend),
+        (anchor_reliability, weights.anchor_reliability),
+    ]
+    .into_iter()
+    .filter_map(|(component, weight)| Some((component? * weight, weight)))
+    .fold((0.0, 0.0), |(total, sum), (value, weight)| {
+        (total + value, sum + weight)
+    });
+    if weight <= 0.0 {
+        return None;
+    }
+
+    Some(HealthScore {
+        score: total / weight,
+        success_rate,
+        liquidity,
+        volume_trend,
+        anchor_reliability,
+    })
+}
+
+pub struct HealthScoreService {
+    db: Arc<Database>,
+    weights: HealthWeights,
+}
+
+impl HealthScoreService {
+    /// Scores with `weights` unless the database overrides them
+    pub fn new(db: Arc<Database>, weights: HealthWeights) -> Self {
+        Self { db, weights }
+    }
+
+    /// Score every corridor active in the last 48 hours and store the scores.
+    /// Returns the number of corridors scored.
+    pub async fn run_once(&self) -> Result<usize> {
+        let now = Utc::now();
+        let store = self.db.corridor_health_scores();
+        let weights = self
+            .weights
+            .with_overrides(&store.weight_overrides().await?);
+
+        let computed_at = now.to_rfc3339();
+        let scores: Vec<CorridorHealthScore> = store
+            .inputs(now)
+            .await?
+            .iter()
+            .filter_map(|inputs| {
+                let health = score(inputs, &weights)?;
+                Some(CorridorHealthScore {
+                    corridor_key: inputs.corridor_key.clone(),
+                    computed_at: computed_at.clone(),
+                    score: health.score,
+                    success_rate_score: health.success_rate,
+                    liquidity_score: health.liquidity,
+                    volume_trend_score: health.volume_trend,
+                    anchor_reliability_score: health.anchor_reliability,
+                })
+            })
+            .collect();
+
+        store.record(&scores).await?;
+        info!("Scored the health of {} corridors", scores.len());
+        Ok(scores.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> HealthInputsRow {
+        HealthInputsRow {
+            corridor_key: "K".to_string(),
+            total_transactions: 100,
+            successful_transactions: 90,
+            volume_usd: 150.0,
+            previous_volume_usd: 100.0,
+            liquidity_depth_usd: FULL_LIQUIDITY_USD,
+            anchor_reliability: Some(80.0),
+        }
+    }
+
+    #[test]
+    fn test_score_weights_every_component() {
+        let health = score(&inputs(), &HealthWeights::default()).unwrap();
+        assert_eq!(health.success_rate, Some(90.0));
+        assert_eq!(health.liquidity, Some(100.0));
+        assert_eq!(health.volume_trend, Some(75.0));
+        assert_eq!(health.anchor_reliability, Some(80.0));
+        // 0.4 * 90 + 0.25 * 100 + 0.15 * 75 + 0.2 * 80
+        assert!((health.score - 88.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing_components_share_their_weight() {
+        let quiet = HealthInputsRow {
+            total_transactions: 0,
+            successful_transactions: 0,
+            volume_usd: 0.0,
+            previous_volume_usd: 0.0,
+            anchor_reliability: None,
+            ..inputs()
+        };
+        let health = score(&quiet, &HealthWeights::default()).unwrap();
+        assert_eq!(health.success_rate, None);
+        assert_eq!(health.volume_trend, None);
+        // Only liquidity is left
+        assert_eq!(health.score, 100.0);
+
+        let no_liquidity_weight = HealthWeights {
+            liquidity: 0.0,
+            ..HealthWeights::default()
+        };
+        assert_eq!(score(&quiet, &no_liquidity_weight), None);
+    }
+
+    #[test]
+    fn test_volume_trend_component() {
+        assert_eq!(volume_trend_component(100.0, 100.0), Some(50.0));
+        assert_eq!(volume_trend_component(500.0, 100.0), Some(100.0));
+        assert_eq!(volume_trend_component(0.0, 100.0), Some(0.0));
+        assert_eq!(volume_trend_component(10.0, 0.0), Some(100.0));
+        assert_eq!(volume_trend_component(0.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_liquidity_component_is_logarithmic() {
+        assert_eq!(liquidity_component(0.0), 0.0);
+        assert_eq!(liquidity_component(10.0 * FULL_LIQUIDITY_USD), 100.0);
+        let thousand = liquidity_component(1_000.0);
+        assert!(thousand > 49.0 && thousand < 51.0);
+    }
+
+    #[test]
+    fn test_overrides_replace_known_components() {
+        let weights = HealthWeights::default().with_overrides(&[
+            ("liquidity".to_string(), 0.5),
+            ("anchor_reliability".to_string(), -1.0),
+            ("unknown".to_string(), 3.0),
+        ]);
+        assert_eq!(
+            weights,
+            HealthWeights {
+                liquidity: 0.5,
+                ..HealthWeights::default()
+            }
+        );
+    }
+}
@@ -2,10 +2,12 @@ use anyhow::Result;
 use chrono::Utc;
 use sqlx::{Pool, Sqlite};
 use std::sync::Arc;
+use stellar_insights_core::Asset;
 use tracing::info;
 
 use crate::models::{LiquidityPool, LiquidityPoolSnapshot, LiquidityPoolStats};
 use crate::rpc::StellarRpcClient;
+use crate::time_range::TimeRange;
 
 pub struct LiquidityPoolAnalyzer {
     pool: Pool<Sqlite>,
@@ -17,6 +19,10 @@ impl LiquidityPoolAnalyzer {
         Self { pool, rpc_client }
     }
 
+    pub fn db_pool(&self) -> &Pool<Sqlite> {
+        &self.pool
+    }
+
     // ========================================================================
     // Sync from Horizon
     // ========================================================================
@@ -220,6 +226,30 @@ impl LiquidityPoolAnalyzer {
         Ok(snapshots)
     }
 
+    /// Get snapshot history for a pool within a time range
+    pub async fn get_pool_snapshots_in_range(
+        &self,
+        pool_id: &str,
+        range: &TimeRange,
+        limit: i64,
+    ) -> Result<Vec<LiquidityPoolSnapshot>> {
+        let snapshots = sqlx::query_as::<_, LiquidityPoolSnapshot>(
+            r#"
+            SELECT * FROM liquidity_pool_snapshots
+            WHERE pool_id = $1 AND snapshot_at >= $2 AND snapshot_at <= $3
+            ORDER BY snapshot_at DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(pool_id)
+        .bind(range.from)
+        .bind(range.to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(snapshots)
+    }
+
     /// Get pools ranked by a specific metric
     pub async fn get_pool_rankings(&self, sort_by: &str, limit: i64) -> Result<Vec<LiquidityPool>> {
         let order_clause = match sort_by {
@@ -333,12 +363,9 @@ impl LiquidityPoolAnalyzer {
 
     /// Parse a Horizon asset string ("native" or "CODE:ISSUER")
     fn parse_asset(asset_str: &str) -> (String, Option<String>) {
-        if asset_str == "native" {
-            ("XLM".to_string(), None)
-        } else if let Some((code, issuer)) = asset_str.split_once(':') {
-            (code.to_string(), Some(issuer.to_string()))
-        } else {
-            (asset_str.to_string(), None)
+        match Asset::from_horizon_str(asset_str) {
+            Some(asset) => (asset.code().to_string(), asset.issuer().map(str::to_string)),
+            None => (asset_str.to_string(), None),
         }
     }
 }
@@ -1,16 +1,33 @@
 pub mod account_merge_detector;
 pub mod aggregation;
 pub mod analytics;
+pub mod anchor_toml_refresh;
+pub mod asset_compliance;
+pub mod asset_supply;
+pub mod concentration;
 pub mod contract;
+pub mod contract_ttl;
+pub mod corridor_key_filter;
+pub mod corridor_simulation;
+pub mod corridor_recompute;
 pub mod fee_bump_tracker;
 pub mod governance;
+pub mod health_score;
 pub mod indexing;
 pub mod liquidity_pool_analyzer;
+pub mod movers;
+pub mod network_congestion;
+pub mod network_events;
 pub mod price_feed;
 pub mod realtime_broadcaster;
+pub mod sep_transactions;
+pub mod settlement_times;
 pub mod snapshot;
+pub mod status_monitor;
 pub mod stellar_toml;
+pub mod trade_surveillance;
 pub mod trustline_analyzer;
+pub mod valuation;
 pub mod verification_rewards;
 pub mod webhook_dispatcher;
 pub mod slack_bot;
@@ -0,0 +1,239 @@
+//! Top movers: corridors whose volume, health or spread changed most over the
+//! last hour and the last day.
+//!
+//! Computed from `corridor_metrics_hourly` by comparing the latest complete
+//! window against the one before it. The rollups carry no order-book spread,
+//! so average slippage (bps) stands in for it. [`MoversService::run`] pushes
+//! the report on the `movers` WebSocket channel whenever it changes.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::api::corridors::calculate_health_score;
+use crate::database::Database;
+use crate::db::aggregation::CorridorWindowTotals;
+use crate::models::corridor::CorridorKey;
+use crate::tenant::TenantScope;
+use crate::websocket::{WsMessage, WsState};
+
+pub use stellar_insights_core::movers::{Mover, MoverMetric, MoverWindow};
+
+pub const WS_CHANNEL: &str = "movers";
+
+/// Movers returned per metric and window unless the caller asks otherwise
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// How often the push loop recomputes the report
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+const WINDOWS: [(&str, i64); 2] = [("1h", 1), ("24h", 24)];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MoversReport {
+    pub generated_at: DateTime<Utc>,
+    pub windows: Vec<MoverWindow>,
+}
+
+/// Largest changes of one metric, biggest first. Volume and spread rank by
+/// relative change, with corridors rising from zero first; health is already
+/// a 0-100 score and ranks by points.
+pub fn rank_movers(
+    metric: MoverMetric,
+    previous: &HashMap<String, f64>,
+    current: &HashMap<String, f64>,
+    limit: usize,
+) -> Vec<Mover> {
+    let keys: HashSet<&String> = match metric {
+        // A corridor with no traffic in one window has volume zero there
+        MoverMetric::Volume => previous.keys().chain(current.keys()).collect(),
+        // ...but no health or spread to compare
+        MoverMetric::Health | MoverMetric::Spread => previous
+            .keys()
+            .filter(|k| current.contains_key(*k))
+            .collect(),
+    };
+
+    let mut movers: Vec<(f64, Mover)> = keys
+        .into_iter()
+        .filter_map(|key| {
+            let before = previous.get(key).copied().unwrap_or(0.0);
+            let after = current.get(key).copied().unwrap_or(0.0);
+            let change = after - before;
+            if change == 0.0 {
+                return None;
+            }
+            let change_pct = (before != 0.0).then(|| change / before.abs() * 100.0);
+            let rank = match metric {
+                MoverMetric::Health => change.abs(),
+                _ => change_pct.map_or(f64::INFINITY, f64::abs),
+            };
+            Some((
+                rank,
+                Mover {
+                    corridor_key: key.clone(),
+                    metric,
+                    previous: before,
+                    current: after,
+                    change,
+                    change_pct,
+                },
+            ))
+        })
+        .collect();
+
+    movers.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| a.1.corridor_key.cmp(&b.1.corridor_key))
+    });
+    movers.into_iter().take(limit).map(|(_, m)| m).collect()
+}
+
+fn metric_values(
+    totals: &[CorridorWindowTotals],
+    value: impl Fn(&CorridorWindowTotals) -> f64,
+) -> HashMap<String, f64> {
+    totals
+        .iter()
+        .map(|t| (t.corridor_key.clone(), value(t)))
+        .collect()
+}
+
+fn health(t: &CorridorWindowTotals) -> f64 {
+    let success_rate = if t.total_transactions > 0 {
+        t.successful_transactions as f64 / t.total_transactions as f64 * 100.0
+    } else {
+        0.0
+    };
+    calculate_health_score(success_rate, t.total_transactions, t.volume_usd)
+}
+
+pub struct MoversService {
+    db: Arc<Database>,
+    ws_state: Arc<WsState>,
+}
+
+impl MoversService {
+    pub fn new(db: Arc<Database>, ws_state: Arc<WsState>) -> Self {
+        Self { db, ws_state }
+    }
+
+    /// Movers over the latest complete hour and day, `limit` per metric,
+    /// among the corridors `tenant` may see
+    pub async fn report(&self, limit: usize, tenant: &TenantScope) -> Result<MoversReport> {
+        let now = Utc::now();
+        let end = now.duration_trunc(Duration::hours(1)).unwrap_or(now);
+        let aggregation = self.db.aggregation_db();
+        let tenant_keys = self.db.tenants().corridor_keys(tenant).await?;
+        let visible = |t: &CorridorWindowTotals| match &tenant_keys {
+            Some(keys) => t
+                .corridor_key
+                .parse::<CorridorKey>()
+                .is_ok_and(|key| keys.contains(&key)),
+            None => true,
+        };
+
+        let mut windows = Vec::with_capacity(WINDOWS.len());
+        for (name, hours) in WINDOWS {
+            let start = end - Duration::hours(hours);
+            let mut current = aggregation.fetch_corridor_window_totals(start, end).await?;
+            let mut previous = aggregation
+                .fetch_corridor_window_totals(start - Duration::hours(hours), start)
+                .await?;
+            current.retain(visible);
+            previous.retain(visible);
+
+            let rank = |metric, value: fn(&CorridorWindowTotals) -> f64| {
+                rank_movers(
+                    metric,
+                    &metric_values(&previous, value),
+                    &metric_values(&current, value),
+                    limit,
+                )
+            };
+            windows.push(MoverWindow {
+                window: name.to_string(),
+                start,
+                end,
+                volume: rank(MoverMetric::Volume, |t| t.volume_usd),
+                health: rank(MoverMetric::Health, health),
+                spread: rank(MoverMetric::Spread, |t| t.avg_slippage_bps),
+            });
+        }
+
+        Ok(MoversReport {
+            generated_at: now,
+            windows,
+        })
+    }
+
+    /// Recompute every minute and push to `movers` subscribers on change
+    pub async fn run(&self) {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        let mut last_pushed: Option<Vec<MoverWindow>> = None;
+
+        loop {
+            interval.tick().await;
+            let report = match self.report(DEFAULT_LIMIT, &TenantScope::All).await {
+                Ok(report) => report,
+                Err(e) => {
+                    warn!("Failed to compute movers: {}", e);
+                    continue;
+                }
+            };
+            if last_pushed.as_ref() == Some(&report.windows) {
+                continue;
+            }
+
+            self.ws_state
+                .broadcast_to_channel(
+                    WS_CHANNEL,
+                    WsMessage::MoversUpdate {
+                        generated_at: report.generated_at.to_rfc3339(),
+                        windows: report.windows.clone(),
+                    },
+                )
+                .await;
+            last_pushed = Some(report.windows);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_volume_ranks_by_relative_change() {
+        let previous = values(&[("a", 1000.0), ("b", 10.0), ("c", 50.0)]);
+        let current = values(&[("a", 1500.0), ("b", 40.0), ("c", 50.0), ("d", 5.0)]);
+
+        let movers = rank_movers(MoverMetric::Volume, &previous, &current, 10);
+        let keys: Vec<&str> = movers.iter().map(|m| m.corridor_key.as_str()).collect();
+        // d is new, b tripled, a rose 50%, c is flat
+        assert_eq!(keys, vec!["d", "b", "a"]);
+        assert_eq!(movers[0].change_pct, None);
+        assert_eq!(movers[1].change_pct, Some(300.0));
+
+        let top = rank_movers(MoverMetric::Volume, &previous, &current, 1);
+        assert_eq!(top.len(), 1);
+    }
+
+    #[test]
+    fn test_health_needs_both_windows_and_ranks_by_points() {
+        let previous = values(&[("a", 90.0), ("b", 20.0), ("gone", 80.0)]);
+        let current = values(&[("a", 60.0), ("b", 30.0), ("new", 70.0)]);
+
+        let movers = rank_movers(MoverMetric::Health, &previous, &current, 10);
+        let keys: Vec<&str> = movers.iter().map(|m| m.corridor_key.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b"]);
+        assert_eq!(movers[0].change, -30.0);
+    }
+}
@@ -0,0 +1,347 @@
+//! Network-wide congestion and surge detection.
+//!
+//! Correlates ledger close times, Horizon fee percentiles, ledger capacity
+//! usage and transaction counts into a single [`CongestionLevel`]. The level
+//! is served at `GET /api/network/congestion` and handed to the
+//! [`AlertManager`] so corridor alerts raised during a surge say the network,
+//! not the anchor, is the likely cause.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::alerts::AlertManager;
+use crate::rpc::{FeeDistribution, FeeStats, LedgerInfo, StellarRpcClient};
+
+/// Ledgers sampled per check; about eight minutes at a 5s close time
+const SAMPLE_LEDGERS: u32 = 100;
+
+/// How long a computed report is served before it is refreshed
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Target ledger close time
+const EXPECTED_CLOSE_SECS: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CongestionLevel {
+    Normal,
+    Elevated,
+    Congested,
+    Surge,
+}
+
+impl CongestionLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Elevated => "elevated",
+            Self::Congested => "congested",
+            Self::Surge => "surge",
+        }
+    }
+
+    fn from_score(score: u32) -> Self {
+        match score {
+            0..=1 => Self::Normal,
+            2..=3 => Self::Elevated,
+            4..=5 => Self::Congested,
+            _ => Self::Surge,
+        }
+    }
+
+    /// Whether degraded corridors should be attributed to the network
+    pub fn is_network_wide(&self) -> bool {
+        *self >= Self::Congested
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CongestionSignals {
+    pub avg_close_time_secs: f64,
+    /// Fraction (0-1) of ledger capacity used, as reported by Horizon
+    pub capacity_usage: f64,
+    pub base_fee: u64,
+    pub fee_charged_p50: u64,
+    pub fee_charged_p90: u64,
+    pub fee_charged_p99: u64,
+    pub avg_transactions_per_ledger: f64,
+    /// Transactions per ledger in the newest quarter of the sample relative
+    /// to the rest; above 1 means traffic is climbing
+    pub transaction_growth: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkCongestion {
+    pub level: CongestionLevel,
+    /// Sum of the points awarded by each signal
+    pub score: u32,
+    pub signals: CongestionSignals,
+    /// Human-readable explanation for every signal that scored
+    pub reasons: Vec<String>,
+    pub latest_ledger: u64,
+    pub sampled_ledgers: usize,
+    pub observed_at: DateTime<Utc>,
+}
+
+fn average(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(s, c), v| (s + v, c + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// Derive the raw signals from recent ledgers (newest first) and fee stats
+pub fn signals(ledgers: &[LedgerInfo], fees: &FeeStats) -> CongestionSignals {
+    let closed: Vec<DateTime<Utc>> = ledgers
+        .iter()
+        .filter_map(|l| DateTime::parse_from_rfc3339(&l.closed_at).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .collect();
+    let avg_close_time_secs = average(
+        closed
+            .windows(2)
+            .map(|pair| (pair[0] - pair[1]).num_milliseconds() as f64 / 1000.0)
+            .filter(|secs| *secs > 0.0),
+    );
+
+    let counts: Vec<f64> = ledgers.iter().map(|l| l.transaction_count as f64).collect();
+    let recent = counts.len().div_ceil(4);
+    let recent_avg = average(counts.iter().take(recent).copied());
+    let baseline_avg = average(counts.iter().skip(recent).copied());
+    let transaction_growth = if baseline_avg > 0.0 {
+        recent_avg / baseline_avg
+    } else {
+        1.0
+    };
+
+    CongestionSignals {
+        avg_close_time_secs,
+        capacity_usage: fees.capacity_usage(),
+        base_fee: fees.base_fee(),
+        fee_charged_p50: FeeDistribution::stroops(&fees.fee_charged.p50),
+        fee_charged_p90: FeeDistribution::stroops(&fees.fee_charged.p90),
+        fee_charged_p99: FeeDistribution::stroops(&fees.fee_charged.p99),
+        avg_transactions_per_ledger: average(counts.iter().copied()),
+        transaction_growth,
+    }
+}
+
+/// Score the signals and classify the congestion level
+pub fn classify(signals: &CongestionSignals) -> (CongestionLevel, u32, Vec<String>) {
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    if signals.capacity_usage >= 0.9 {
+        score += 2;
+        reasons.push(format!(
+            "Ledgers are {:.0}% full",
+            signals.capacity_usage * 100.0
+        ));
+    } else if signals.capacity_usage >= 0.7 {
+        score += 1;
+        reasons.push(format!(
+            "Ledger capacity usage is high ({:.0}%)",
+            signals.capacity_usage * 100.0
+        ));
+    }
+
+    let base_fee = signals.base_fee.max(1);
+    if signals.fee_charged_p50 > base_fee {
+        // Surge pricing: even the median transaction pays above the base fee
+        score += 2;
+        reasons.push(format!(
+            "Median fee charged is {} stroops, above the {} stroop base fee",
+            signals.fee_charged_p50, base_fee
+        ));
+    } else if signals.fee_charged_p90 >= base_fee * 10 {
+        score += 1;
+        reasons.push(format!(
+            "90th percentile fee is {}x the base fee",
+            signals.fee_charged_p90 / base_fee
+        ));
+    }
+
+    if signals.avg_close_time_secs >= EXPECTED_CLOSE_SECS * 2.0 {
+        score += 2;
+        reasons.push(format!(
+            "Ledgers are closing every {:.1}s",
+            signals.avg_close_time_secs
+        ));
+    } else if signals.avg_close_time_secs >= EXPECTED_CLOSE_SECS * 1.4 {
+        score += 1;
+        reasons.push(format!(
+            "Ledger close time is slow ({:.1}s)",
+            signals.avg_close_time_secs
+        ));
+    }
+
+    if signals.transaction_growth >= 2.0 {
+        score += 1;
+        reasons.push(format!(
+            "Transaction volume is {:.1}x the recent baseline",
+            signals.transaction_growth
+        ));
+    }
+
+    (CongestionLevel::from_score(score), score, reasons)
+}
+
+pub struct NetworkCongestionService {
+    rpc_client: Arc<StellarRpcClient>,
+    latest: RwLock<Option<NetworkCongestion>>,
+}
+
+impl NetworkCongestionService {
+    pub fn new(rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self {
+            rpc_client,
+            latest: RwLock::new(None),
+        }
+    }
+
+    /// Sample the network and classify it
+    pub async fn measure(&self) -> Result<NetworkCongestion> {
+        let ledgers = self.rpc_client.fetch_recent_ledgers(SAMPLE_LEDGERS).await?;
+        let fees = self.rpc_client.fetch_fee_stats().await?;
+
+        let signals = signals(&ledgers, &fees);
+        let (level, score, reasons) = classify(&signals);
+        Ok(NetworkCongestion {
+            level,
+            score,
+            signals,
+            reasons,
+            latest_ledger: ledgers.first().map(|l| l.sequence).unwrap_or_default(),
+            sampled_ledgers: ledgers.len(),
+            observed_at: Utc::now(),
+        })
+    }
+
+    /// The latest report, measuring again if it is older than the refresh interval
+    pub async fn current(&self) -> Result<NetworkCongestion> {
+        if let Some(report) = self.latest.read().await.as_ref() {
+            let age = (Utc::now() - report.observed_at).to_std().unwrap_or_default();
+            if age < REFRESH_INTERVAL {
+                return Ok(report.clone());
+            }
+        }
+
+        let report = self.measure().await?;
+        *self.latest.write().await = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Keep the report fresh and tell the alert engine about the current level
+    pub async fn run(&self, alert_manager: Arc<AlertManager>) {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            match self.current().await {
+                Ok(report) => alert_manager.set_network_congestion(report.level),
+                Err(e) => warn!("Failed to measure network congestion: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quiet() -> CongestionSignals {
+        CongestionSignals {
+            avg_close_time_secs: 5.1,
+            capacity_usage: 0.4,
+            base_fee: 100,
+            fee_charged_p50: 100,
+            fee_charged_p90: 100,
+            fee_charged_p99: 200,
+            avg_transactions_per_ledger: 200.0,
+            transaction_growth: 1.1,
+        }
+    }
+
+    #[test]
+    fn test_classify_levels() {
+        let (level, score, reasons) = classify(&quiet());
+        assert_eq!(level, CongestionLevel::Normal);
+        assert_eq!(score, 0);
+        assert!(reasons.is_empty());
+
+        let busy = CongestionSignals {
+            capacity_usage: 0.8,
+            fee_charged_p90: 1500,
+            ..quiet()
+        };
+        assert_eq!(classify(&busy).0, CongestionLevel::Elevated);
+
+        let surge = CongestionSignals {
+            capacity_usage: 0.97,
+            fee_charged_p50: 500,
+            avg_close_time_secs: 11.0,
+            transaction_growth: 2.5,
+            ..quiet()
+        };
+        let (level, score, reasons) = classify(&surge);
+        assert_eq!(level, CongestionLevel::Surge);
+        assert_eq!(score, 7);
+        assert_eq!(reasons.len(), 4);
+        assert!(level.is_network_wide());
+    }
+
+    #[test]
+    fn test_signals_from_ledgers() {
+        let start = Utc::now();
+        let ledgers: Vec<LedgerInfo> = (0..8u32)
+            .map(|i| LedgerInfo {
+                sequence: 1000 - i as u64,
+                hash: String::new(),
+                previous_hash: String::new(),
+                // The two newest ledgers carry triple the traffic
+                transaction_count: if i < 2 { 300 } else { 100 },
+                operation_count: 0,
+                closed_at: (start - chrono::Duration::seconds(6 * i as i64)).to_rfc3339(),
+                total_coins: String::new(),
+                fee_pool: String::new(),
+                base_fee: 100,
+                base_reserve: String::new(),
+            })
+            .collect();
+        let fees = FeeStats {
+            last_ledger: "1000".to_string(),
+            last_ledger_base_fee: "100".to_string(),
+            ledger_capacity_usage: "0.5".to_string(),
+            fee_charged: FeeDistribution {
+                max: "100".to_string(),
+                min: "100".to_string(),
+                mode: "100".to_string(),
+                p50: "100".to_string(),
+                p90: "100".to_string(),
+                p95: "100".to_string(),
+                p99: "250".to_string(),
+            },
+            max_fee: FeeDistribution {
+                max: "100".to_string(),
+                min: "100".to_string(),
+                mode: "100".to_string(),
+                p50: "100".to_string(),
+                p90: "100".to_string(),
+                p95: "100".to_string(),
+                p99: "100".to_string(),
+            },
+        };
+
+        let signals = signals(&ledgers, &fees);
+        assert!((signals.avg_close_time_secs - 6.0).abs() < 0.01);
+        assert!((signals.transaction_growth - 3.0).abs() < 0.01);
+        assert_eq!(signals.fee_charged_p99, 250);
+        assert_eq!(signals.capacity_usage, 0.5);
+    }
+}
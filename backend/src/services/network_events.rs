@@ -0,0 +1,159 @@
+//! Network-wide webhook events raised during ledger ingestion.
+//!
+//! Ledger ingestion hands each new ledger to
+//! [`NetworkEventDetector::process_ledger`], which queues
+//! `ledger.close_delayed` when the ledger closed more than
+//! [`NetworkEventConfig::close_delay_secs`] after the one before it, and
+//! `network.fee_spike` when the median fee per operation of its transactions
+//! reaches [`NetworkEventConfig::fee_spike_multiplier`] times the base fee. A
+//! fee spike is reported once, on the first ledger above the threshold, and
+//! again only after fees have come back down. Webhooks set their own, higher
+//! thresholds with per-event-type filters.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::db::settlements::PaymentSettlements;
+use crate::rpc::HorizonTransaction;
+use crate::webhooks::events::{LedgerCloseDelayedEvent, NetworkFeeSpikeEvent};
+use crate::webhooks::{WebhookEventType, WebhookService};
+
+/// Minimum fee per operation, in stroops
+pub const BASE_FEE_STROOPS: i64 = 100;
+
+/// Thresholds at which network events are raised
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkEventConfig {
+    /// Median fee per operation, as a multiple of the base fee
+    pub fee_spike_multiplier: f64,
+    /// Seconds between two ledger closes
+    pub close_delay_secs: f64,
+}
+
+impl Default for NetworkEventConfig {
+    fn default() -> Self {
+        Self {
+            fee_spike_multiplier: 3.0,
+            // Ledgers close about every 5 seconds
+            close_delay_secs: 15.0,
+        }
+    }
+}
+
+/// A fee spike in ledger `sequence`, if the median fee per operation of its
+/// transactions is at least `multiplier` times the base fee
+pub fn fee_spike(
+    sequence: u64,
+    transactions: &[HorizonTransaction],
+    multiplier: f64,
+) -> Option<NetworkFeeSpikeEvent> {
+    let mut fees: Vec<i64> = transactions
+        .iter()
+        .filter_map(|tx| {
+            let charged = tx.fee_charged.as_deref()?.parse::<i64>().ok()?;
+            Some(charged / i64::from(tx.operation_count.max(1)))
+        })
+        .collect();
+    fees.sort_unstable();
+
+    let median = *fees.get((fees.len().checked_sub(1)?) / 2)?;
+    let fee_multiplier = median as f64 / BASE_FEE_STROOPS as f64;
+    (fee_multiplier >= multiplier).then(|| NetworkFeeSpikeEvent {
+        ledger_sequence: sequence,
+        median_fee_stroops: median,
+        max_fee_stroops: fees.last().copied().unwrap_or(median),
+        base_fee_stroops: BASE_FEE_STROOPS,
+        fee_multiplier,
+        transaction_count: fees.len(),
+    })
+}
+
+/// A close delay of ledger `sequence`, if it closed more than
+/// `threshold_secs` after the ledger before it
+pub fn close_delay(
+    sequence: u64,
+    previous_close: DateTime<Utc>,
+    close: DateTime<Utc>,
+    threshold_secs: f64,
+) -> Option<LedgerCloseDelayedEvent> {
+    let close_seconds = (close - previous_close).num_milliseconds() as f64 / 1000.0;
+    (close_seconds > threshold_secs).then(|| LedgerCloseDelayedEvent {
+        ledger_sequence: sequence,
+        close_time: close.to_rfc3339(),
+        previous_close_time: previous_close.to_rfc3339(),
+        close_seconds,
+        threshold_seconds: threshold_secs,
+    })
+}
+
+/// Raises network events for newly ingested ledgers
+pub struct NetworkEventDetector {
+    ledgers: PaymentSettlements,
+    webhooks: Arc<WebhookService>,
+    config: NetworkEventConfig,
+    in_fee_spike: AtomicBool,
+}
+
+impl NetworkEventDetector {
+    pub fn new(
+        pool: SqlitePool,
+        webhooks: Arc<WebhookService>,
+        config: NetworkEventConfig,
+    ) -> Self {
+        Self {
+            ledgers: PaymentSettlements::new(pool),
+            webhooks,
+            config,
+            in_fee_spike: AtomicBool::new(false),
+        }
+    }
+
+    /// Queue the events raised by ingested ledger `sequence`; fee spikes are
+    /// only checked when its transactions were fetched. Returns the number of
+    /// webhook events queued.
+    pub async fn process_ledger(
+        &self,
+        sequence: u64,
+        transactions: Option<&[HorizonTransaction]>,
+    ) -> Result<usize> {
+        let mut queued = 0;
+
+        let previous = self
+            .ledgers
+            .ledger_close_time(sequence.saturating_sub(1))
+            .await?;
+        let close = self.ledgers.ledger_close_time(sequence).await?;
+        if let (Some(previous), Some(close)) = (previous, close) {
+            if let Some(event) =
+                close_delay(sequence, previous, close, self.config.close_delay_secs)
+            {
+                queued += self
+                    .webhooks
+                    .enqueue_for_subscribers(
+                        WebhookEventType::LedgerCloseDelayed,
+                        serde_json::to_value(&event)?,
+                    )
+                    .await?;
+            }
+        }
+
+        if let Some(transactions) = transactions {
+            let spike = fee_spike(sequence, transactions, self.config.fee_spike_multiplier);
+            let was_spiking = self.in_fee_spike.swap(spike.is_some(), Ordering::Relaxed);
+            if let Some(event) = spike.filter(|_| !was_spiking) {
+                queued += self
+                    .webhooks
+                    .enqueue_for_subscribers(
+                        WebhookEventType::NetworkFeeSpike,
+                        serde_json::to_value(&event)?,
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(queued)
+    }
+}
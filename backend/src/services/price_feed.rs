@@ -7,6 +7,8 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+use crate::http_client;
+
 /// Configuration for price feed service
 #[derive(Debug, Clone)]
 pub struct PriceFeedConfig {
@@ -65,6 +67,9 @@ pub trait PriceFeedProvider: Send + Sync {
     /// Fetch prices for multiple assets
     async fn fetch_prices(&self, asset_ids: &[String]) -> Result<HashMap<String, f64>>;
 
+    /// USD price of each fiat currency (ISO 4217 codes) the provider quotes
+    async fn fetch_fiat_rates(&self, currencies: &[&str]) -> Result<HashMap<String, f64>>;
+
     /// Get provider name
     fn name(&self) -> &str;
 }
@@ -77,10 +82,7 @@ pub struct CoinGeckoProvider {
 
 impl CoinGeckoProvider {
     pub fn new(api_key: Option<String>, timeout: Duration) -> Self {
-        let client = Client::builder()
-            .timeout(timeout)
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = http_client::shared().client(timeout);
 
         Self { client, api_key }
     }
@@ -91,6 +93,24 @@ struct CoinGeckoSimplePrice {
     usd: f64,
 }
 
+/// CoinGecko id of the asset fiat rates are derived from
+const FIAT_REFERENCE_ID: &str = "tether";
+
+/// USD price of each fiat currency, from one asset's price quoted in USD and
+/// in those currencies
+fn fiat_usd_prices(quotes: &HashMap<String, f64>, currencies: &[&str]) -> HashMap<String, f64> {
+    let Some(usd) = quotes.get("usd").copied().filter(|p| *p > 0.0) else {
+        return HashMap::new();
+    };
+    currencies
+        .iter()
+        .filter_map(|code| {
+            let local = quotes.get(&code.to_lowercase()).copied()?;
+            (local > 0.0).then(|| (code.to_uppercase(), usd / local))
+        })
+        .collect()
+}
+
 #[async_trait::async_trait]
 impl PriceFeedProvider for CoinGeckoProvider {
     async fn fetch_price(&self, asset_id: &str) -> Result<f64> {
@@ -106,10 +126,8 @@ impl PriceFeedProvider for CoinGeckoProvider {
             )
         };
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
+        let response = http_client::shared()
+            .send(self.client.get(&url))
             .await
             .context("Failed to send request to CoinGecko")?;
 
@@ -148,10 +166,8 @@ impl PriceFeedProvider for CoinGeckoProvider {
             )
         };
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
+        let response = http_client::shared()
+            .send(self.client.get(&url))
             .await
             .context("Failed to send request to CoinGecko")?;
 
@@ -169,6 +185,49 @@ impl PriceFeedProvider for CoinGeckoProvider {
         Ok(prices.into_iter().map(|(k, v)| (k, v.usd)).collect())
     }
 
+    async fn fetch_fiat_rates(&self, currencies: &[&str]) -> Result<HashMap<String, f64>> {
+        if currencies.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let vs_currencies = std::iter::once("usd".to_string())
+            .chain(currencies.iter().map(|c| c.to_lowercase()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let url = if let Some(api_key) = &self.api_key {
+            format!(
+                "https://pro-api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies={}&x_cg_pro_api_key={}",
+                FIAT_REFERENCE_ID, vs_currencies, api_key
+            )
+        } else {
+            format!(
+                "https://api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies={}",
+                FIAT_REFERENCE_ID, vs_currencies
+            )
+        };
+
+        let response = http_client::shared()
+            .send(self.client.get(&url))
+            .await
+            .context("Failed to send request to CoinGecko")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("CoinGecko API error: {} - {}", status, body);
+        }
+
+        let prices: HashMap<String, HashMap<String, f64>> = response
+            .json()
+            .await
+            .context("Failed to parse CoinGecko response")?;
+
+        let quotes = prices
+            .get(FIAT_REFERENCE_ID)
+            .ok_or_else(|| anyhow::anyhow!("Price not found for asset: {}", FIAT_REFERENCE_ID))?;
+        Ok(fiat_usd_prices(quotes, currencies))
+    }
+
     fn name(&self) -> &str {
         "CoinGecko"
     }
@@ -362,10 +421,32 @@ impl PriceFeedClient {
 
     /// Warm cache by fetching prices for common assets
     pub async fn warm_cache(&self) -> Result<()> {
+        let _ = self.refresh_all().await;
+        Ok(())
+    }
+
+    /// Fetch prices for every mapped asset, returning what was obtained
+    pub async fn refresh_all(&self) -> HashMap<String, f64> {
         let common_assets: Vec<String> = self.asset_mapping.keys().cloned().collect();
         info!("Warming price cache for {} assets", common_assets.len());
-        let _ = self.get_prices(&common_assets).await;
-        Ok(())
+        self.get_prices(&common_assets).await
+    }
+
+    /// USD price of each fiat currency the provider quotes; empty if the
+    /// provider is unavailable
+    pub async fn fiat_rates(&self, currencies: &[&str]) -> HashMap<String, f64> {
+        match self.provider.fetch_fiat_rates(currencies).await {
+            Ok(rates) => rates,
+            Err(e) => {
+                error!("Failed to fetch fiat rates: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Name of the upstream price provider
+    pub fn provider_name(&self) -> &str {
+        self.provider.name()
     }
 }
 
@@ -426,6 +507,18 @@ pub fn default_asset_mapping() -> HashMap<String, String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fiat_usd_prices() {
+        let quotes = HashMap::from([
+            ("usd".to_string(), 1.0),
+            ("eur".to_string(), 0.8),
+            ("ngn".to_string(), 0.0),
+        ]);
+        let rates = fiat_usd_prices(&quotes, &["EUR", "NGN", "GBP"]);
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates["EUR"], 1.25);
+    }
+
     #[test]
     fn test_config_from_env() {
         std::env::set_var("PRICE_FEED_PROVIDER", "coingecko");
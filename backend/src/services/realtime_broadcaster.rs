@@ -1,12 +1,12 @@
 use crate::cache::CacheManager;
 use crate::database::Database;
-use crate::models::corridor::CorridorMetrics;
-use crate::models::{AnchorMetrics, AnchorStatus, PaymentRecord};
+use crate::models::corridor::{CorridorKey, CorridorMetrics};
+use crate::models::{AnchorMetrics, AnchorStatus, MetricSource, PaymentRecord};
 use crate::rpc::StellarRpcClient;
-use crate::websocket::{WsMessage, WsState};
+use crate::websocket::{normalize_channel, WsMessage, WsState};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 use uuid::Uuid;
@@ -41,6 +41,8 @@ pub enum SubscriptionMessage {
 pub enum BroadcastMessage {
     CorridorUpdate {
         corridor: CorridorMetrics,
+        /// Latest stored health score, if the corridor has been scored
+        health_score: Option<f64>,
         channel: String,
     },
     AnchorStatusChange {
@@ -133,9 +135,18 @@ impl RealtimeBroadcaster {
                 // Fetch latest corridor metrics from database
                 match Self::fetch_corridor_updates(&db).await {
                     Ok(corridors) => {
+                        let health_scores: HashMap<String, f64> =
+                            match db.corridor_health_scores().latest().await {
+                                Ok(scores) => scores.into_iter().collect(),
+                                Err(e) => {
+                                    warn!("Failed to fetch corridor health scores: {}", e);
+                                    HashMap::new()
+                                }
+                            };
                         for corridor in corridors {
                             let channel = format!("corridor:{}", corridor.corridor_key);
                             let message = BroadcastMessage::CorridorUpdate {
+                                health_score: health_scores.get(&corridor.corridor_key).copied(),
                                 corridor: corridor.clone(),
                                 channel: channel.clone(),
                             };
@@ -211,6 +222,7 @@ impl RealtimeBroadcaster {
                         liquidity_depth_usd: 0.0,
                         created_at: now,
                         updated_at: now,
+                        source: MetricSource::Derived,
                     };
                     corridor_metrics.push(metrics);
                 }
@@ -224,10 +236,15 @@ impl RealtimeBroadcaster {
     }
 
     /// Broadcast corridor update to all subscribed clients
-    pub async fn broadcast_corridor_update(&self, corridor: CorridorMetrics) {
+    pub async fn broadcast_corridor_update(
+        &self,
+        corridor: CorridorMetrics,
+        health_score: Option<f64>,
+    ) {
         let channel = format!("corridor:{}", corridor.corridor_key);
         let message = BroadcastMessage::CorridorUpdate {
             corridor,
+            health_score,
             channel: channel.clone(),
         };
 
@@ -249,10 +266,16 @@ impl RealtimeBroadcaster {
     }
 
     /// Broadcast new payment to all subscribed clients
-    pub async fn broadcast_payment(&self, corridor_key: String, amount: f64, successful: bool, timestamp: String) {
-        let channel = format!("corridor:{}", corridor_key);
+    pub async fn broadcast_payment(
+        &self,
+        corridor_key: &CorridorKey,
+        amount: f64,
+        successful: bool,
+        timestamp: String,
+    ) {
+        let channel = corridor_key.channel();
         let message = BroadcastMessage::NewPayment {
-            corridor_key: corridor_key.clone(),
+            corridor_key: corridor_key.to_string(),
             amount,
             successful,
             timestamp,
@@ -278,8 +301,7 @@ impl RealtimeBroadcaster {
         };
 
         // Broadcast to all connections for health alerts
-        self.ws_state
-            .broadcast(WsMessage::from_broadcast_message(alert));
+        self.ws_state.broadcast(WsMessage::from(alert));
     }
 
     /// Subscribe a connection to specific channels
@@ -289,7 +311,7 @@ impl RealtimeBroadcaster {
             .entry(connection_id)
             .or_insert_with(HashSet::new);
 
-        for channel in channels {
+        for channel in channels.into_iter().map(normalize_channel) {
             subscription_set.insert(channel.clone());
             info!(
                 "Connection {} subscribed to channel: {}",
@@ -301,7 +323,7 @@ impl RealtimeBroadcaster {
     /// Unsubscribe a connection from specific channels
     pub fn unsubscribe_connection(&self, connection_id: Uuid, channels: Vec<String>) {
         if let Some(mut subscription_set) = self.subscriptions.get_mut(&connection_id) {
-            for channel in channels {
+            for channel in channels.into_iter().map(normalize_channel) {
                 subscription_set.remove(&channel);
                 info!(
                     "Connection {} unsubscribed from channel: {}",
@@ -318,7 +340,7 @@ impl RealtimeBroadcaster {
         channel: &str,
         message: BroadcastMessage,
     ) {
-        let ws_message = WsMessage::from_broadcast_message(message);
+        let ws_message = WsMessage::from(message);
 
         // Find all connections subscribed to this channel
         let mut target_connections = Vec::new();
@@ -367,22 +389,23 @@ impl RealtimeBroadcaster {
     }
 }
 
-impl WsMessage {
-    /// Convert BroadcastMessage to WsMessage
-    fn from_broadcast_message(broadcast_msg: BroadcastMessage) -> Self {
+impl From<BroadcastMessage> for WsMessage {
+    fn from(broadcast_msg: BroadcastMessage) -> Self {
         match broadcast_msg {
-            BroadcastMessage::CorridorUpdate { corridor, .. } => {
-                WsMessage::CorridorUpdate {
-                    corridor_key: corridor.corridor_key,
-                    asset_a_code: corridor.asset_a_code,
-                    asset_a_issuer: corridor.asset_a_issuer,
-                    asset_b_code: corridor.asset_b_code,
-                    asset_b_issuer: corridor.asset_b_issuer,
-                    success_rate: Some(corridor.success_rate),
-                    health_score: Some(corridor.success_rate * 100.0), // Simple health score calculation
-                    last_updated: Some(corridor.updated_at.to_rfc3339()),
-                }
-            }
+            BroadcastMessage::CorridorUpdate {
+                corridor,
+                health_score,
+                ..
+            } => WsMessage::CorridorUpdate {
+                corridor_key: corridor.corridor_key,
+                asset_a_code: corridor.asset_a_code,
+                asset_a_issuer: corridor.asset_a_issuer,
+                asset_b_code: corridor.asset_b_code,
+                asset_b_issuer: corridor.asset_b_issuer,
+                success_rate: Some(corridor.success_rate),
+                health_score,
+                last_updated: Some(corridor.updated_at.to_rfc3339()),
+            },
             BroadcastMessage::AnchorStatusChange {
                 anchor,
                 old_status: _,
@@ -0,0 +1,269 @@
+//! Status tracking for SEP-24/31 transactions started through the transfer proxies.
+//!
+//! Anchor callbacks and [`SepTransactionPoller`] feed status changes in through
+//! [`SepTransactionTracker`], which stores them and notifies the owning user
+//! through their webhooks and the `sep_transaction:<id>` WebSocket channel.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::database::Database;
+use crate::db::sep_transactions::{PollTarget, SepTransaction, StatusUpdate};
+use crate::http_client;
+use crate::webhooks::events::SepTransactionStatusChangedEvent;
+use crate::webhooks::{WebhookEventType, WebhookService};
+use crate::websocket::{WsMessage, WsState};
+
+/// WebSocket channel carrying updates for one transaction
+pub fn ws_channel(transaction_id: &str) -> String {
+    format!("sep_transaction:{}", transaction_id)
+}
+
+pub struct SepTransactionTracker {
+    db: Arc<Database>,
+    ws_state: Arc<WsState>,
+    webhooks: WebhookService,
+}
+
+impl SepTransactionTracker {
+    pub fn new(db: Arc<Database>, ws_state: Arc<WsState>, webhooks: WebhookService) -> Self {
+        Self {
+            db,
+            ws_state,
+            webhooks,
+        }
+    }
+
+    /// Store an anchor-reported status and notify the owner if it changed
+    pub async fn record_status(
+        &self,
+        tx: &SepTransaction,
+        status: &str,
+        message: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<StatusUpdate> {
+        let update = self
+            .db
+            .sep_transactions()
+            .apply_status(tx, status, message, at)
+            .await?;
+
+        if let StatusUpdate::Changed { previous } = &update {
+            tracing::info!(
+                transaction_id = %tx.id,
+                "SEP transaction status changed: {} -> {}",
+                previous,
+                status
+            );
+            self.notify(tx, previous, status, message, at).await;
+        }
+
+        Ok(update)
+    }
+
+    async fn notify(
+        &self,
+        tx: &SepTransaction,
+        old_status: &str,
+        new_status: &str,
+        message: Option<&str>,
+        at: DateTime<Utc>,
+    ) {
+        let event = SepTransactionStatusChangedEvent {
+            transaction_id: tx.id.clone(),
+            anchor_id: tx.anchor_id.clone(),
+            anchor_transaction_id: tx.anchor_transaction_id.clone(),
+            protocol: tx.protocol.clone(),
+            kind: tx.kind.clone(),
+            old_status: old_status.to_string(),
+            new_status: new_status.to_string(),
+            message: message.map(str::to_string),
+            updated_at: at.to_rfc3339(),
+        };
+
+        match serde_json::to_value(&event) {
+            Ok(payload) => {
+                if let Err(e) = self
+                    .webhooks
+                    .enqueue_for_user(
+                        &tx.user_id,
+                        WebhookEventType::SepTransactionStatusChanged,
+                        payload,
+                    )
+                    .await
+                {
+                    tracing::warn!(transaction_id = %tx.id, "Failed to queue webhook events: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize SEP transaction event: {}", e),
+        }
+
+        self.ws_state
+            .broadcast_to_channel(
+                &ws_channel(&tx.id),
+                WsMessage::SepTransactionUpdate {
+                    transaction_id: tx.id.clone(),
+                    anchor_id: tx.anchor_id.clone(),
+                    old_status: old_status.to_string(),
+                    new_status: new_status.to_string(),
+                    updated_at: at.to_rfc3339(),
+                },
+            )
+            .await;
+    }
+}
+
+/// Open transactions polled per run
+const POLL_BATCH_SIZE: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+struct AnchorTransactionResponse {
+    transaction: AnchorTransactionStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnchorTransactionStatus {
+    status: String,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Anchor endpoint returning a single transaction for the given protocol
+fn transaction_url(tx: &SepTransaction) -> String {
+    let base = tx.transfer_server.trim().trim_end_matches('/');
+    let id = urlencoding::encode(&tx.anchor_transaction_id);
+    if tx.protocol == "sep31" {
+        format!("{}/transactions/{}", base, id)
+    } else {
+        format!("{}/transaction?id={}", base, id)
+    }
+}
+
+/// Refreshes open transactions from their anchors until they reach a terminal status
+pub struct SepTransactionPoller {
+    db: Arc<Database>,
+    tracker: Arc<SepTransactionTracker>,
+    http_client: Client,
+    interval: Duration,
+}
+
+impl SepTransactionPoller {
+    pub fn new(db: Arc<Database>, tracker: Arc<SepTransactionTracker>, interval: Duration) -> Self {
+        let http_client = http_client::shared().client(Duration::from_secs(15));
+
+        Self {
+            db,
+            tracker,
+            http_client,
+            interval,
+        }
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!("Starting SEP transaction poller");
+        let mut interval = tokio::time::interval(self.interval);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.poll_once().await {
+                tracing::error!("Error polling SEP transactions: {}", e);
+            }
+        }
+    }
+
+    /// Poll every due transaction once; returns how many changed status
+    pub async fn poll_once(&self) -> Result<usize> {
+        let store = self.db.sep_transactions();
+        let mut changed = 0;
+
+        for target in store.due_for_poll(POLL_BATCH_SIZE).await? {
+            let tx = &target.transaction;
+            match self.fetch_status(&target).await {
+                Ok(status) => {
+                    let update = self
+                        .tracker
+                        .record_status(tx, &status.status, status.message.as_deref(), Utc::now())
+                        .await?;
+                    if matches!(update, StatusUpdate::Changed { .. }) {
+                        changed += 1;
+                    }
+                    store.record_poll(&tx.id, true).await?;
+                }
+                Err(e) => {
+                    tracing::warn!(transaction_id = %tx.id, "Failed to poll anchor: {:#}", e);
+                    store.record_poll(&tx.id, false).await?;
+                }
+            }
+        }
+
+        Ok(changed)
+    }
+
+    async fn fetch_status(&self, target: &PollTarget) -> Result<AnchorTransactionStatus> {
+        let mut req = self.http_client.get(transaction_url(&target.transaction));
+        if let Some(token) = &target.auth_token {
+            req = req.bearer_auth(token);
+        }
+
+        let resp = http_client::shared().send(req).await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("anchor returned {}", resp.status());
+        }
+        let body: AnchorTransactionResponse = resp
+            .json()
+            .await
+            .context("unexpected transaction response")?;
+
+        Ok(body.transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(protocol: &str) -> SepTransaction {
+        let now = Utc::now();
+        SepTransaction {
+            id: "t1".to_string(),
+            user_id: "u1".to_string(),
+            anchor_id: None,
+            protocol: protocol.to_string(),
+            kind: "deposit".to_string(),
+            transfer_server: "https://anchor.example.com/sep24/".to_string(),
+            anchor_transaction_id: "abc 1".to_string(),
+            asset_code: None,
+            amount: None,
+            status: "incomplete".to_string(),
+            status_message: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_transaction_url() {
+        assert_eq!(
+            transaction_url(&transaction("sep24")),
+            "https://anchor.example.com/sep24/transaction?id=abc%201"
+        );
+        assert_eq!(
+            transaction_url(&transaction("sep31")),
+            "https://anchor.example.com/sep24/transactions/abc%201"
+        );
+    }
+
+    #[test]
+    fn test_parse_anchor_transaction() {
+        let body = r#"{"transaction":{"id":"abc","status":"pending_anchor","more_info_url":"x"}}"#;
+        let parsed: AnchorTransactionResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(parsed.transaction.status, "pending_anchor");
+        assert!(parsed.transaction.message.is_none());
+    }
+}
@@ -0,0 +1,197 @@
+//! Time to settle path payments, per corridor.
+//!
+//! Ledger ingestion hands each ledger's transactions and payments to
+//! [`SettlementTracker::process_ledger`]. A path payment settles in the ledger
+//! of its successful transaction; its settlement time runs from the close of
+//! the ledger before its first attempt, the earliest it could have been
+//! submitted, to the close of the ledger that settled it.
+//!
+//! Failed transactions with a memo are attempts of the next successful path
+//! payment from the same source with the same memo, if it comes within
+//! [`RETRY_WINDOW_MINUTES`]. Without a memo a payment counts as a single
+//! attempt. Corridor detail reports the distribution as percentiles.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::db::settlements::{PaymentSettlement, PaymentSettlements};
+use crate::models::corridor::CorridorKey;
+use crate::rpc::{HorizonTransaction, Payment};
+
+/// How long failed attempts wait for a successful retry
+pub const RETRY_WINDOW_MINUTES: i64 = 60;
+
+/// Distribution of a corridor's settlement times
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SettlementTimes {
+    /// Settled path payments measured
+    #[schema(example = 1250)]
+    pub payments: i64,
+    /// Share of payments that settled after a failed attempt, 0 to 1
+    #[schema(example = 0.04)]
+    pub retried_share: f64,
+    #[schema(example = 5200)]
+    pub p50_ms: i64,
+    #[schema(example = 6100)]
+    pub p90_ms: i64,
+    #[schema(example = 11000)]
+    pub p95_ms: i64,
+    #[schema(example = 42000)]
+    pub p99_ms: i64,
+}
+
+impl SettlementTimes {
+    /// Percentiles of (settlement time in milliseconds, attempts) samples;
+    /// `None` without samples
+    pub fn from_samples(samples: &[(i64, i64)]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut times: Vec<i64> = samples.iter().map(|(ms, _)| *ms).collect();
+        times.sort_unstable();
+        // Nearest rank
+        let percentile = |p: f64| {
+            let rank = (p / 100.0 * times.len() as f64).ceil() as usize;
+            times[rank.clamp(1, times.len()) - 1]
+        };
+        let retried = samples.iter().filter(|(_, attempts)| *attempts > 1).count();
+        Some(Self {
+            payments: samples.len() as i64,
+            retried_share: retried as f64 / samples.len() as f64,
+            p50_ms: percentile(50.0),
+            p90_ms: percentile(90.0),
+            p95_ms: percentile(95.0),
+            p99_ms: percentile(99.0),
+        })
+    }
+}
+
+/// Corridor of the first path payment in each transaction, by hash
+pub fn path_payment_corridors(payments: &[Payment]) -> HashMap<&str, CorridorKey> {
+    let mut corridors = HashMap::new();
+    for payment in payments {
+        let operation_type = payment.operation_type.as_deref().unwrap_or("payment");
+        if !matches!(
+            operation_type,
+            "path_payment_strict_send" | "path_payment_strict_receive"
+        ) {
+            continue;
+        }
+        let (Some(source), Some(destination)) = (payment.source_asset(), payment.asset()) else {
+            continue;
+        };
+        let Ok(key) = format!("{}->{}", source.key(), destination.key()).parse::<CorridorKey>()
+        else {
+            continue;
+        };
+        corridors
+            .entry(payment.transaction_hash.as_str())
+            .or_insert(key);
+    }
+    corridors
+}
+
+pub struct SettlementTracker {
+    settlements: PaymentSettlements,
+}
+
+impl SettlementTracker {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            settlements: PaymentSettlements::new(pool),
+        }
+    }
+
+    /// Record the attempts and settlements in ledger `sequence`. Returns the
+    /// number of payments settled. Skipped when the ledger before it was not
+    /// ingested, as there is nothing to measure from.
+    pub async fn process_ledger(
+        &self,
+        sequence: u64,
+        transactions: &[HorizonTransaction],
+        payments: &[Payment],
+    ) -> Result<u64> {
+        let Some(opened_at) = self
+            .settlements
+            .ledger_close_time(sequence.saturating_sub(1))
+            .await?
+        else {
+            debug!(
+                "No ledger before {}, settlement times not measured",
+                sequence
+            );
+            return Ok(0);
+        };
+        self.settlements
+            .expire_attempts(opened_at - Duration::minutes(RETRY_WINDOW_MINUTES))
+            .await?;
+
+        let corridors = path_payment_corridors(payments);
+        let mut settled = 0;
+        for tx in transactions {
+            let memo = tx.memo.as_deref().filter(|m| !m.is_empty());
+            if !tx.successful {
+                if let Some(memo) = memo {
+                    self.settlements
+                        .record_attempt(&tx.source_account, memo, opened_at)
+                        .await?;
+                }
+                continue;
+            }
+
+            let Some(corridor_key) = corridors.get(tx.hash.as_str()) else {
+                continue;
+            };
+            let Ok(settled_at) = DateTime::parse_from_rfc3339(&tx.created_at) else {
+                continue;
+            };
+            let (started_at, failed) = match memo {
+                Some(memo) => self
+                    .settlements
+                    .take_attempts(&tx.source_account, memo)
+                    .await?
+                    .unwrap_or((opened_at, 0)),
+                None => (opened_at, 0),
+            };
+            self.settlements
+                .record(&PaymentSettlement {
+                    transaction_hash: tx.hash.clone(),
+                    corridor_key: corridor_key.to_string(),
+                    started_at,
+                    settled_at: settled_at.with_timezone(&Utc),
+                    attempts: failed + 1,
+                })
+                .await?;
+            settled += 1;
+        }
+        Ok(settled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_use_nearest_rank() {
+        let samples: Vec<(i64, i64)> = (1..=100).map(|i| (i * 100, 1)).collect();
+        let times = SettlementTimes::from_samples(&samples).unwrap();
+        assert_eq!(times.payments, 100);
+        assert_eq!(times.p50_ms, 5000);
+        assert_eq!(times.p90_ms, 9000);
+        assert_eq!(times.p99_ms, 9900);
+        assert_eq!(times.retried_share, 0.0);
+
+        let times = SettlementTimes::from_samples(&[(6000, 1), (30_000, 3)]).unwrap();
+        assert_eq!(times.p50_ms, 6000);
+        assert_eq!(times.p95_ms, 30_000);
+        assert_eq!(times.retried_share, 0.5);
+
+        assert!(SettlementTimes::from_samples(&[]).is_none());
+    }
+}
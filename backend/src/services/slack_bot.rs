@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use reqwest::{Client, StatusCode};
 use tokio::sync::broadcast;
 use crate::alerts::{Alert, AlertType};
+use crate::http_client;
 
 /// Slack Bot Service for sending alerts to Slack channels
 pub struct SlackBotService {
@@ -13,10 +14,7 @@ pub struct SlackBotService {
 impl SlackBotService {
     /// Create a new SlackBotService
     pub fn new(webhook_url: String, alert_rx: broadcast::Receiver<Alert>) -> Self {
-        let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(5))
-            .build()
-            .unwrap_or_else(|_| Client::new());
+        let http_client = http_client::shared().client(std::time::Duration::from_secs(5));
 
         Self {
             webhook_url,
@@ -42,12 +40,16 @@ impl SlackBotService {
             AlertType::SuccessRateDrop => "🔴 Success Rate Drop",
             AlertType::LatencyIncrease => "🟡 Latency Increase",
             AlertType::LiquidityDecrease => "🟠 Liquidity Decrease",
+            AlertType::SupplyMint => "🔵 Large Mint",
+            AlertType::SupplyBurn => "🟣 Large Burn",
         };
 
         let color = match alert.alert_type {
             AlertType::SuccessRateDrop => "#E01E5A", // Red
             AlertType::LatencyIncrease => "#ECB22E", // Yellow
             AlertType::LiquidityDecrease => "#E8912D", // Orange
+            AlertType::SupplyMint => "#36C5F0", // Blue
+            AlertType::SupplyBurn => "#7C3085", // Purple
         };
 
         let payload = serde_json::json!({
@@ -1,16 +1,21 @@
 use crate::database::Database;
 use crate::snapshot::schema::{
-    AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
+    AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SnapshotSlaSummary,
+    SCHEMA_VERSION,
 };
+use crate::snapshot::signing::SnapshotSigner;
+use crate::webhooks::events::SnapshotPublishedEvent;
+use crate::webhooks::{WebhookEventType, WebhookService};
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
-use serde::Serialize;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use sha2::{Digest, Sha256};
 use sqlx::Row;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::contract::{ContractService, SubmissionResult};
@@ -22,13 +27,72 @@ pub struct SnapshotGenerationResult {
     pub epoch: u64,
     pub hash: String,
     pub canonical_json: String,
+    /// Base64 ed25519 signature of the hash, when a signing key is configured
+    pub signature: Option<String>,
     pub anchor_count: usize,
     pub corridor_count: usize,
+    /// Hex SHA-256 hash of the SLA section, when the snapshot has SLA figures
+    pub sla_hash: Option<String>,
     pub submission_result: Option<SubmissionResult>,
     pub verification_successful: bool,
     pub timestamp: DateTime<Utc>,
 }
 
+/// Stored signature of one epoch's snapshot
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct SnapshotSignature {
+    pub epoch: i64,
+    /// Hex SHA-256 hash of the canonical snapshot JSON; this is what is signed
+    pub hash: String,
+    /// Base64 ed25519 signature
+    pub signature: String,
+    /// Hex ed25519 public key
+    pub signing_key: String,
+    pub created_at: String,
+}
+
+/// An epoch's stored SLA section, as anchored with its snapshot
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AnchoredSla {
+    pub epoch: u64,
+    pub schema_version: u32,
+    /// When the snapshot was taken
+    pub snapshot_timestamp: String,
+    /// Hex SHA-256 hash of the canonical snapshot JSON
+    pub snapshot_hash: String,
+    /// Hex SHA-256 hash of the canonical `sla_summaries` JSON array; `None`
+    /// for snapshots without SLA figures
+    pub sla_hash: Option<String>,
+    #[schema(value_type = Vec<Object>)]
+    pub summaries: Vec<SnapshotSlaSummary>,
+}
+
+/// Hours whose success rate is at least this percentage count as up
+pub const SLA_UPTIME_SUCCESS_THRESHOLD: f64 = 95.0;
+
+/// Most snapshots the contract returns per `get_recent_snapshots` query
+pub const MAX_KEEPALIVE_EPOCHS: u32 = 50;
+
+/// Which on-chain snapshots the TTL keep-alive job extends, and by how much
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotTtlConfig {
+    /// Newest epochs kept alive, at most [`MAX_KEEPALIVE_EPOCHS`]
+    pub epochs: u32,
+    /// Ledgers each of them is kept alive for
+    pub ledgers: u32,
+}
+
+impl Default for SnapshotTtlConfig {
+    fn default() -> Self {
+        Self {
+            // A week of 6-hour epochs
+            epochs: 28,
+            // About 30 days of 5-second ledgers
+            ledgers: 518_400,
+        }
+    }
+}
+
 /// Service for creating cryptographically verifiable analytics snapshots
 ///
 /// This service ensures that:
@@ -40,6 +104,8 @@ pub struct SnapshotGenerationResult {
 pub struct SnapshotService {
     db: Arc<Database>,
     contract_service: Option<Arc<ContractService>>,
+    signer: Option<Arc<SnapshotSigner>>,
+    webhooks: Option<Arc<WebhookService>>,
 }
 
 impl SnapshotService {
@@ -48,9 +114,23 @@ impl SnapshotService {
         Self {
             db,
             contract_service,
+            signer: None,
+            webhooks: None,
         }
     }
 
+    /// Sign every generated snapshot's hash with `signer`
+    pub fn with_signer(mut self, signer: Option<Arc<SnapshotSigner>>) -> Self {
+        self.signer = signer;
+        self
+    }
+
+    /// Queue `snapshot.published` webhook events for every published snapshot
+    pub fn with_webhooks(mut self, webhooks: Option<Arc<WebhookService>>) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
     /// Generate a complete analytics snapshot with hash generation and submission
     ///
     /// This is the main entry point that fulfills all acceptance criteria:
@@ -88,9 +168,21 @@ impl SnapshotService {
 
         info!("Generated snapshot hash: {}", hash_hex);
 
+        let sla_hash = Self::hash_sla_summaries(snapshot.sla_summaries.clone())
+            .context("Failed to hash SLA summaries")?;
+
+        let signed = self.sign_hash(&hash).await;
+
         // Step 4: Store hash in database
         let snapshot_id = self
-            .store_snapshot_in_database(&snapshot, &hash_hex, &canonical_json)
+            .store_snapshot_in_database(
+                &snapshot,
+                &hash_hex,
+                &canonical_json,
+                signed
+                    .as_ref()
+                    .map(|(sig, key)| (sig.as_str(), key.as_str())),
+            )
             .await
             .context("Failed to store snapshot in database")?;
 
@@ -98,7 +190,15 @@ impl SnapshotService {
 
         // Step 5: Submit to smart contract (if configured)
         let submission_result = if let Some(contract_service) = &self.contract_service {
-            match contract_service.submit_snapshot(hash, epoch).await {
+            let submission = match sla_hash {
+                Some(sla_hash) => {
+                    contract_service
+                        .submit_snapshot_with_sla(hash, sla_hash, epoch)
+                        .await
+                }
+                None => contract_service.submit_snapshot(hash, epoch).await,
+            };
+            match submission {
                 Ok(result) => {
                     info!("Successfully submitted snapshot to contract: {:?}", result);
                     Some(result)
@@ -122,17 +222,58 @@ impl SnapshotService {
             false
         };
 
-        Ok(SnapshotGenerationResult {
+        let result = SnapshotGenerationResult {
             snapshot_id,
             epoch,
             hash: hash_hex,
             canonical_json,
+            signature: signed.map(|(signature, _)| signature),
             anchor_count: snapshot.anchor_metrics.len(),
             corridor_count: snapshot.corridor_metrics.len(),
+            sla_hash: sla_hash.map(hex::encode),
             submission_result,
             verification_successful: verification_result,
             timestamp: snapshot.timestamp,
-        })
+        };
+        self.notify_published(&result).await;
+
+        Ok(result)
+    }
+
+    /// Queue `snapshot.published` for subscribed webhooks; failures are
+    /// logged, as the snapshot itself was published
+    async fn notify_published(&self, result: &SnapshotGenerationResult) {
+        let Some(webhooks) = &self.webhooks else {
+            return;
+        };
+
+        let event = SnapshotPublishedEvent {
+            epoch: result.epoch,
+            hash: result.hash.clone(),
+            sla_hash: result.sla_hash.clone(),
+            anchor_count: result.anchor_count,
+            corridor_count: result.corridor_count,
+            transaction_hash: result
+                .submission_result
+                .as_ref()
+                .map(|s| s.transaction_hash.clone()),
+            ledger: result.submission_result.as_ref().map(|s| s.ledger),
+            published_at: Utc::now().to_rfc3339(),
+        };
+        let queued = match serde_json::to_value(&event) {
+            Ok(payload) => {
+                webhooks
+                    .enqueue_for_subscribers(WebhookEventType::SnapshotPublished, payload)
+                    .await
+            }
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = queued {
+            warn!(
+                "Failed to queue snapshot.published for epoch {}: {}",
+                result.epoch, e
+            );
+        }
     }
 
     /// Aggregate all metrics from the database into a snapshot
@@ -160,9 +301,92 @@ impl SnapshotService {
             snapshot.add_corridor_metrics(metrics);
         }
 
+        // Corridor SLA figures for the month so far
+        let sla_summaries = self
+            .aggregate_sla_summaries(timestamp)
+            .await
+            .context("Failed to aggregate SLA summaries")?;
+
+        for summary in sla_summaries {
+            snapshot.add_sla_summary(summary);
+        }
+
         Ok(snapshot)
     }
 
+    /// Month-to-date uptime and success rate of every corridor with hourly
+    /// metrics in the month of `at`
+    async fn aggregate_sla_summaries(&self, at: DateTime<Utc>) -> Result<Vec<SnapshotSlaSummary>> {
+        let month_start = Utc
+            .with_ymd_and_hms(at.year(), at.month(), 1, 0, 0, 0)
+            .single()
+            .context("Invalid month start")?;
+        let next_month = if at.month() == 12 {
+            Utc.with_ymd_and_hms(at.year() + 1, 1, 1, 0, 0, 0)
+        } else {
+            Utc.with_ymd_and_hms(at.year(), at.month() + 1, 1, 0, 0, 0)
+        }
+        .single()
+        .context("Invalid month end")?;
+
+        let query = r#"
+            SELECT
+                corridor_key,
+                COUNT(*) AS hours_observed,
+                SUM(CASE WHEN success_rate >= ?3 THEN 1 ELSE 0 END) AS hours_up,
+                SUM(total_transactions) AS total_transactions,
+                SUM(successful_transactions) AS successful_transactions,
+                CAST(AVG(avg_settlement_latency_ms) AS INTEGER) AS avg_settlement_latency_ms
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= ?1 AND hour_bucket < ?2
+            GROUP BY corridor_key
+            ORDER BY corridor_key
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(month_start.to_rfc3339())
+            .bind(next_month.to_rfc3339())
+            .bind(SLA_UPTIME_SUCCESS_THRESHOLD)
+            .fetch_all(self.db.pool())
+            .await
+            .context("Failed to fetch hourly corridor metrics")?;
+
+        let period = month_start.format("%Y-%m").to_string();
+        let summaries: Vec<SnapshotSlaSummary> = rows
+            .into_iter()
+            .map(|row| {
+                let hours_observed: i64 = row.get("hours_observed");
+                let hours_up: i64 = row.get("hours_up");
+                let total_transactions: i64 = row.get("total_transactions");
+                let successful_transactions: i64 = row.get("successful_transactions");
+
+                SnapshotSlaSummary {
+                    corridor_key: row.get("corridor_key"),
+                    period: period.clone(),
+                    hours_observed,
+                    hours_up,
+                    uptime_percent: if hours_observed > 0 {
+                        hours_up as f64 / hours_observed as f64 * 100.0
+                    } else {
+                        0.0
+                    },
+                    success_rate: if total_transactions > 0 {
+                        successful_transactions as f64 / total_transactions as f64 * 100.0
+                    } else {
+                        0.0
+                    },
+                    total_transactions,
+                    avg_settlement_latency_ms: row
+                        .get::<Option<i64>, _>("avg_settlement_latency_ms")
+                        .map(|ms| ms as i32),
+                }
+            })
+            .collect();
+
+        debug!("Aggregated {} corridor SLA summaries", summaries.len());
+        Ok(summaries)
+    }
+
     /// Aggregate anchor metrics from database
     async fn aggregate_anchor_metrics(&self) -> Result<Vec<SnapshotAnchorMetrics>> {
         let query = r#"
@@ -291,13 +515,16 @@ impl SnapshotService {
         snapshot: &AnalyticsSnapshot,
         hash: &str,
         canonical_json: &str,
+        signed: Option<(&str, &str)>,
     ) -> Result<String> {
         let snapshot_id = Uuid::new_v4().to_string();
+        let (signature, signing_key) = signed.unzip();
 
         let query = r#"
             INSERT INTO snapshots (
-                id, entity_id, entity_type, data, hash, epoch, timestamp, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                id, entity_id, entity_type, data, hash, epoch, timestamp, created_at,
+                signature, signing_key
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
         sqlx::query(query)
@@ -309,6 +536,8 @@ impl SnapshotService {
             .bind(snapshot.epoch as i64)
             .bind(snapshot.timestamp)
             .bind(Utc::now())
+            .bind(signature)
+            .bind(signing_key)
             .execute(self.db.pool())
             .await
             .context("Failed to insert snapshot record")?;
@@ -412,6 +641,12 @@ impl SnapshotService {
             Value::Array(corridor_metrics),
         );
 
+        // Serialize SLA summaries (already sorted by normalize())
+        map.insert(
+            "sla_summaries".to_string(),
+            Self::serialize_sla_summaries(&snapshot.sla_summaries),
+        );
+
         // Convert to JSON string with no extra whitespace
         // Note: serde_json::Map uses IndexMap internally which preserves insertion order.
         // Since we iterate over BTreeMap (sorted), insertion order is sorted, ensuring determinism.
@@ -552,6 +787,72 @@ impl SnapshotService {
         Value::Object(json_map)
     }
 
+    /// Serialize sorted SLA summaries to the deterministic JSON array that
+    /// both the snapshot and the SLA hash cover
+    fn serialize_sla_summaries(summaries: &[SnapshotSlaSummary]) -> Value {
+        let summaries = summaries
+            .iter()
+            .map(|summary| {
+                let mut map = BTreeMap::new();
+
+                map.insert(
+                    "corridor_key".to_string(),
+                    Value::String(summary.corridor_key.clone()),
+                );
+                map.insert("period".to_string(), Value::String(summary.period.clone()));
+                map.insert(
+                    "hours_observed".to_string(),
+                    Value::Number(summary.hours_observed.into()),
+                );
+                map.insert(
+                    "hours_up".to_string(),
+                    Value::Number(summary.hours_up.into()),
+                );
+                map.insert(
+                    "uptime_percent".to_string(),
+                    Self::serialize_f64(summary.uptime_percent),
+                );
+                map.insert(
+                    "success_rate".to_string(),
+                    Self::serialize_f64(summary.success_rate),
+                );
+                map.insert(
+                    "total_transactions".to_string(),
+                    Value::Number(summary.total_transactions.into()),
+                );
+                map.insert(
+                    "avg_settlement_latency_ms".to_string(),
+                    summary
+                        .avg_settlement_latency_ms
+                        .map_or(Value::Null, |ms| Value::Number(ms.into())),
+                );
+
+                let mut json_map = Map::new();
+                for (k, v) in map {
+                    json_map.insert(k, v);
+                }
+                Value::Object(json_map)
+            })
+            .collect();
+        Value::Array(summaries)
+    }
+
+    /// SHA-256 hash of the canonical SLA section, or `None` when there are no
+    /// SLA figures to anchor
+    ///
+    /// Verifiers recompute it from the `sla_summaries` array of the canonical
+    /// snapshot JSON, serialized compactly with sorted keys.
+    pub fn hash_sla_summaries(
+        mut summaries: Vec<SnapshotSlaSummary>,
+    ) -> Result<Option<[u8; 32]>, serde_json::Error> {
+        if summaries.is_empty() {
+            return Ok(None);
+        }
+        summaries.sort_by(|a, b| (&a.corridor_key, &a.period).cmp(&(&b.corridor_key, &b.period)));
+        let json = serde_json::to_string(&Self::serialize_sla_summaries(&summaries))?;
+        Ok(Some(Self::compute_sha256_hash_bytes(&json)))
+    }
+
     /// Serialize f64 to a deterministic JSON number representation
     ///
     /// This ensures that floating point numbers are always serialized
@@ -685,6 +986,221 @@ impl SnapshotService {
     }
 }
 
+/// Epochs a scheduled run should publish: every epoch after `last_published`
+/// up to `current`, keeping only the `max_catch_up` most recent after a long
+/// outage. With nothing published yet, only the current epoch is due.
+pub fn due_epochs(last_published: Option<u64>, current: u64, max_catch_up: u64) -> Vec<u64> {
+    let Some(last) = last_published else {
+        return vec![current];
+    };
+    if last >= current || max_catch_up == 0 {
+        return Vec::new();
+    }
+    let oldest = (last + 1).max(current.saturating_sub(max_catch_up - 1));
+    (oldest..=current).collect()
+}
+
+impl SnapshotService {
+    pub fn signer(&self) -> Option<&SnapshotSigner> {
+        self.signer.as_deref()
+    }
+
+    pub fn contract_service(&self) -> Option<&ContractService> {
+        self.contract_service.as_deref()
+    }
+
+    /// Signature and hex public key for a snapshot hash. Signing is
+    /// best-effort: if the key manager is unreachable the snapshot is still
+    /// stored and submitted, just unsigned.
+    async fn sign_hash(&self, hash: &[u8; 32]) -> Option<(String, String)> {
+        let signer = self.signer.as_ref()?;
+        let signed = async {
+            Ok::<_, anyhow::Error>((
+                signer.sign_hash(hash).await?,
+                signer.public_key_hex().await?,
+            ))
+        }
+        .await;
+
+        match signed {
+            Ok(signed) => Some(signed),
+            Err(e) => {
+                warn!(
+                    "Failed to sign snapshot with {} signer, storing it unsigned: {}",
+                    signer.backend(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Signature of the most recently stored snapshot for `epoch`, if it was signed
+    pub async fn signature_for_epoch(&self, epoch: u64) -> Result<Option<SnapshotSignature>> {
+        let row = sqlx::query_as::<_, SnapshotSignature>(
+            r#"
+            SELECT epoch, hash, signature, signing_key, created_at FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+              AND signature IS NOT NULL AND signing_key IS NOT NULL
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to load snapshot signature")?;
+
+        Ok(row)
+    }
+
+    /// SLA section of the most recently stored snapshot for `epoch`
+    pub async fn anchored_sla(&self, epoch: u64) -> Result<Option<AnchoredSla>> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT hash, data FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to load snapshot")?;
+
+        let Some((snapshot_hash, data)) = row else {
+            return Ok(None);
+        };
+        #[derive(Deserialize)]
+        struct StoredSla {
+            schema_version: u32,
+            timestamp: String,
+            // Snapshots before schema version 2 have no SLA section
+            #[serde(default)]
+            sla_summaries: Vec<SnapshotSlaSummary>,
+        }
+
+        let stored: StoredSla =
+            serde_json::from_str(&data).context("Stored snapshot is not valid JSON")?;
+        let sla_hash = Self::hash_sla_summaries(stored.sla_summaries.clone())
+            .context("Failed to hash SLA summaries")?;
+
+        Ok(Some(AnchoredSla {
+            epoch,
+            schema_version: stored.schema_version,
+            snapshot_timestamp: stored.timestamp,
+            snapshot_hash,
+            sla_hash: sla_hash.map(hex::encode),
+            summaries: stored.sla_summaries,
+        }))
+    }
+
+    /// Latest epoch (and its hash) stored for the system-wide analytics snapshot
+    pub async fn latest_stored_epoch(&self) -> Result<Option<(u64, String)>> {
+        let row: Option<(i64, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT epoch, hash FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch IS NOT NULL
+            ORDER BY epoch DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to load latest snapshot epoch")?;
+
+        Ok(row.map(|(epoch, hash)| (epoch as u64, hash.unwrap_or_default())))
+    }
+
+    /// Publish the snapshot for the current epoch, plus any epochs missed while
+    /// the service was down. Epoch `n` covers `[n * epoch_length, (n + 1) * epoch_length)`
+    /// since the Unix epoch. Returns the epochs published by this run.
+    ///
+    /// A stored epoch whose contract submission failed is resubmitted with its
+    /// stored hash first, so catch-up never leaves gaps on-chain. Catch-up
+    /// snapshots carry their historical epoch but the metrics as of this run.
+    pub async fn publish_scheduled(
+        &self,
+        epoch_length: std::time::Duration,
+        max_catch_up: u64,
+    ) -> Result<Vec<u64>> {
+        let epoch_secs = epoch_length.as_secs().max(1);
+        let current = Utc::now().timestamp().max(0) as u64 / epoch_secs;
+        let latest = self.latest_stored_epoch().await?;
+
+        if let (Some(contract), Some((epoch, hash))) = (&self.contract_service, &latest) {
+            if contract.get_snapshot_by_epoch(*epoch).await?.is_none() {
+                let mut bytes = [0u8; 32];
+                hex::decode_to_slice(hash, &mut bytes)
+                    .with_context(|| format!("Stored hash for epoch {} is invalid", epoch))?;
+                warn!("Epoch {} is stored but not on-chain; resubmitting", epoch);
+                let sla_hash = self
+                    .anchored_sla(*epoch)
+                    .await?
+                    .and_then(|sla| sla.sla_hash);
+                let resubmission = match sla_hash {
+                    Some(sla_hash) => {
+                        let mut sla_bytes = [0u8; 32];
+                        hex::decode_to_slice(&sla_hash, &mut sla_bytes).with_context(|| {
+                            format!("Stored SLA hash for epoch {} is invalid", epoch)
+                        })?;
+                        contract
+                            .submit_snapshot_with_sla(bytes, sla_bytes, *epoch)
+                            .await
+                    }
+                    None => contract.submit_snapshot(bytes, *epoch).await,
+                };
+                resubmission.with_context(|| format!("Resubmission of epoch {} failed", epoch))?;
+            }
+        }
+
+        let due = due_epochs(latest.map(|(epoch, _)| epoch), current, max_catch_up);
+        if due.len() > 1 {
+            info!(
+                "Catching up {} missed snapshot epochs ({}..={})",
+                due.len() - 1,
+                due[0],
+                current
+            );
+        }
+
+        let mut published = Vec::with_capacity(due.len());
+        for epoch in due {
+            self.generate_and_submit_snapshot(epoch).await.with_context(|| {
+                format!(
+                    "Snapshot publication failed at epoch {} (published {:?})",
+                    epoch, published
+                )
+            })?;
+            published.push(epoch);
+        }
+
+        Ok(published)
+    }
+
+    /// Extend the TTL of the newest `config.epochs` on-chain snapshots so
+    /// their entries are not archived for unpaid rent. An epoch that fails
+    /// is logged and skipped. Returns the epochs extended, none without a
+    /// contract.
+    pub async fn keep_recent_epochs_alive(&self, config: SnapshotTtlConfig) -> Result<Vec<u64>> {
+        let Some(contract) = &self.contract_service else {
+            return Ok(Vec::new());
+        };
+
+        let epochs = contract.get_recent_epochs(config.epochs).await?;
+        let mut extended = Vec::with_capacity(epochs.len());
+        for epoch in epochs {
+            match contract.extend_snapshot_ttl(epoch, config.ledgers).await {
+                Ok(_) => extended.push(epoch),
+                Err(e) => warn!("{:#}", e),
+            }
+        }
+
+        Ok(extended)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -930,4 +1446,63 @@ mod tests {
             );
         }
     }
+
+    fn create_test_sla_summary(key: &str, uptime_percent: f64) -> SnapshotSlaSummary {
+        SnapshotSlaSummary {
+            corridor_key: key.to_string(),
+            period: "2024-01".to_string(),
+            hours_observed: 100,
+            hours_up: uptime_percent as i64,
+            uptime_percent,
+            success_rate: 97.5,
+            total_transactions: 1000,
+            avg_settlement_latency_ms: Some(300),
+        }
+    }
+
+    #[test]
+    fn test_sla_hash_covers_the_snapshot_sla_section() {
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        snapshot.add_sla_summary(create_test_sla_summary("corridor2", 99.0));
+        snapshot.add_sla_summary(create_test_sla_summary("corridor1", 98.0));
+
+        let sla_hash = SnapshotService::hash_sla_summaries(snapshot.sla_summaries.clone())
+            .unwrap()
+            .unwrap();
+
+        // A verifier hashes the `sla_summaries` array of the canonical JSON
+        let json = SnapshotService::serialize_deterministically(snapshot.clone()).unwrap();
+        let parsed: Value = serde_json::from_str(&json).unwrap();
+        let section = serde_json::to_string(&parsed["sla_summaries"]).unwrap();
+        assert_eq!(
+            SnapshotService::compute_sha256_hash_bytes(&section),
+            sla_hash
+        );
+
+        // Changing a reported figure changes both hashes
+        let original = SnapshotService::hash_snapshot(snapshot.clone()).unwrap();
+        snapshot.sla_summaries[0].uptime_percent = 99.5;
+        assert_ne!(
+            SnapshotService::hash_snapshot(snapshot.clone()).unwrap(),
+            original
+        );
+        assert_ne!(
+            SnapshotService::hash_sla_summaries(snapshot.sla_summaries).unwrap(),
+            Some(sla_hash)
+        );
+
+        assert_eq!(
+            SnapshotService::hash_sla_summaries(Vec::new()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_due_epochs() {
+        assert_eq!(due_epochs(None, 10, 24), vec![10]);
+        assert_eq!(due_epochs(Some(10), 10, 24), Vec::<u64>::new());
+        assert_eq!(due_epochs(Some(7), 10, 24), vec![8, 9, 10]);
+        // Long outage: only the most recent epochs are caught up
+        assert_eq!(due_epochs(Some(1), 100, 3), vec![98, 99, 100]);
+    }
 }
@@ -0,0 +1,227 @@
+//! Periodic health probes for the public status page.
+//!
+//! Each run checks ledger ingestion freshness, webhook delivery failures and
+//! RPC connectivity, records one probe per component and opens or resolves
+//! incidents when a component changes state.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::database::Database;
+use crate::db::status::{Component, ComponentStatus, StatusProbe, StatusStore};
+use crate::rpc::StellarRpcClient;
+
+/// Days of probe history kept (and shown) on the status page
+pub const STATUS_HISTORY_DAYS: i64 = 90;
+
+/// Ingestion cursors older than this are reported as degraded
+const INGESTION_DEGRADED_SECS: i64 = 10 * 60;
+/// Ingestion cursors older than this are reported as an outage
+const INGESTION_OUTAGE_SECS: i64 = 30 * 60;
+
+/// Share of failed webhook deliveries in the last hour that counts as degraded
+const WEBHOOK_DEGRADED_FAILURE_RATE: f64 = 0.10;
+/// Share of failed webhook deliveries in the last hour that counts as an outage
+const WEBHOOK_OUTAGE_FAILURE_RATE: f64 = 0.50;
+
+/// RPC health checks slower than this are reported as degraded
+const RPC_DEGRADED_LATENCY_MS: i64 = 2_000;
+
+pub struct StatusMonitor {
+    db: Arc<Database>,
+    rpc_client: Arc<StellarRpcClient>,
+}
+
+impl StatusMonitor {
+    pub fn new(db: Arc<Database>, rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self { db, rpc_client }
+    }
+
+    fn store(&self) -> StatusStore {
+        self.db.status()
+    }
+
+    /// Probe every component, record the results and update incidents
+    pub async fn run_probes(&self) -> Result<Vec<StatusProbe>> {
+        let now = Utc::now();
+        let probes = vec![
+            self.probe_ingestion(now).await,
+            self.probe_webhooks(now).await,
+            self.probe_rpc(now).await,
+        ];
+
+        let store = self.store();
+        for probe in &probes {
+            store.record_probe(probe).await?;
+            self.track_incident(&store, probe).await?;
+        }
+        store
+            .prune_probes(now - Duration::days(STATUS_HISTORY_DAYS))
+            .await?;
+
+        Ok(probes)
+    }
+
+    async fn probe_ingestion(&self, now: DateTime<Utc>) -> StatusProbe {
+        let (status, message) = match self.store().ingestion_lag_secs().await {
+            Ok(lag) => classify_ingestion_lag(lag),
+            Err(e) => (ComponentStatus::Outage, format!("Failed to read ingestion state: {}", e)),
+        };
+        probe(Component::Ingestion, status, None, message, now)
+    }
+
+    async fn probe_webhooks(&self, now: DateTime<Utc>) -> StatusProbe {
+        let counts = self
+            .store()
+            .webhook_delivery_counts(now - Duration::hours(1))
+            .await;
+
+        let (status, message) = match counts {
+            Ok((failed, total)) => classify_webhook_failures(failed, total),
+            Err(e) => (ComponentStatus::Outage, format!("Failed to read webhook events: {}", e)),
+        };
+        probe(Component::WebhookDispatch, status, None, message, now)
+    }
+
+    async fn probe_rpc(&self, now: DateTime<Utc>) -> StatusProbe {
+        let started = Instant::now();
+        let result = self.rpc_client.check_health().await;
+        let latency_ms = started.elapsed().as_millis() as i64;
+
+        let (status, message) = match result {
+            Ok(health) => classify_rpc(&health.status, latency_ms),
+            Err(e) => (ComponentStatus::Outage, format!("RPC health check failed: {}", e)),
+        };
+        probe(Component::Rpc, status, Some(latency_ms), message, now)
+    }
+
+    /// Open an incident when a component leaves `operational`, escalate it if
+    /// the component gets worse, and resolve it once the component recovers
+    async fn track_incident(&self, store: &StatusStore, probe: &StatusProbe) -> Result<()> {
+        let open = store.open_incident(probe.component).await?;
+
+        match (open, probe.status) {
+            (None, ComponentStatus::Operational) => {}
+            (None, status) => {
+                let title = format!(
+                    "{} {}",
+                    probe.component.display_name(),
+                    match status {
+                        ComponentStatus::Outage => "outage",
+                        _ => "degraded",
+                    }
+                );
+                store
+                    .open_new_incident(probe.component, status, &title, probe.checked_at)
+                    .await?;
+                tracing::warn!(
+                    component = probe.component.as_str(),
+                    status = status.as_str(),
+                    "Status incident opened: {}",
+                    probe.message.as_deref().unwrap_or_default()
+                );
+            }
+            (Some(incident), ComponentStatus::Operational) => {
+                store.resolve_incident(&incident.id, probe.checked_at).await?;
+                tracing::info!(component = probe.component.as_str(), "Status incident resolved");
+            }
+            (Some(incident), status) => {
+                if status > ComponentStatus::from_db(&incident.status) {
+                    store.update_incident_status(&incident.id, status).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn probe(
+    component: Component,
+    status: ComponentStatus,
+    latency_ms: Option<i64>,
+    message: String,
+    checked_at: DateTime<Utc>,
+) -> StatusProbe {
+    StatusProbe {
+        component,
+        status,
+        latency_ms,
+        message: Some(message),
+        checked_at,
+    }
+}
+
+/// Classify ingestion by the age of the most recently advanced cursor
+fn classify_ingestion_lag(lag_secs: Option<i64>) -> (ComponentStatus, String) {
+    match lag_secs {
+        None => (ComponentStatus::Outage, "No ingestion progress recorded".to_string()),
+        Some(secs) if secs >= INGESTION_OUTAGE_SECS => (
+            ComponentStatus::Outage,
+            format!("Ingestion has not advanced for {} minutes", secs / 60),
+        ),
+        Some(secs) if secs >= INGESTION_DEGRADED_SECS => (
+            ComponentStatus::Degraded,
+            format!("Ingestion is {} minutes behind", secs / 60),
+        ),
+        Some(secs) => (ComponentStatus::Operational, format!("Last advanced {}s ago", secs)),
+    }
+}
+
+/// Classify webhook delivery by the share of failed events in the last hour
+fn classify_webhook_failures(failed: i64, total: i64) -> (ComponentStatus, String) {
+    if total == 0 {
+        return (ComponentStatus::Operational, "No deliveries in the last hour".to_string());
+    }
+
+    let rate = failed as f64 / total as f64;
+    let status = if rate >= WEBHOOK_OUTAGE_FAILURE_RATE {
+        ComponentStatus::Outage
+    } else if rate >= WEBHOOK_DEGRADED_FAILURE_RATE {
+        ComponentStatus::Degraded
+    } else {
+        ComponentStatus::Operational
+    };
+    (status, format!("{} of {} deliveries failed in the last hour", failed, total))
+}
+
+/// Classify RPC connectivity by the node's reported health and response time
+fn classify_rpc(node_status: &str, latency_ms: i64) -> (ComponentStatus, String) {
+    if !node_status.eq_ignore_ascii_case("healthy") {
+        (ComponentStatus::Degraded, format!("RPC node reports '{}'", node_status))
+    } else if latency_ms >= RPC_DEGRADED_LATENCY_MS {
+        (ComponentStatus::Degraded, format!("RPC responded in {}ms", latency_ms))
+    } else {
+        (ComponentStatus::Operational, format!("RPC responded in {}ms", latency_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_ingestion_lag() {
+        assert_eq!(classify_ingestion_lag(Some(30)).0, ComponentStatus::Operational);
+        assert_eq!(classify_ingestion_lag(Some(15 * 60)).0, ComponentStatus::Degraded);
+        assert_eq!(classify_ingestion_lag(Some(2 * 3600)).0, ComponentStatus::Outage);
+        assert_eq!(classify_ingestion_lag(None).0, ComponentStatus::Outage);
+    }
+
+    #[test]
+    fn test_classify_webhook_failures() {
+        assert_eq!(classify_webhook_failures(0, 0).0, ComponentStatus::Operational);
+        assert_eq!(classify_webhook_failures(1, 100).0, ComponentStatus::Operational);
+        assert_eq!(classify_webhook_failures(20, 100).0, ComponentStatus::Degraded);
+        assert_eq!(classify_webhook_failures(60, 100).0, ComponentStatus::Outage);
+    }
+
+    #[test]
+    fn test_classify_rpc() {
+        assert_eq!(classify_rpc("healthy", 120).0, ComponentStatus::Operational);
+        assert_eq!(classify_rpc("healthy", 5_000).0, ComponentStatus::Degraded);
+        assert_eq!(classify_rpc("unhealthy", 120).0, ComponentStatus::Degraded);
+    }
+}
@@ -8,6 +8,8 @@ use std::time::Duration;
 use tokio::sync::RwLock;
 use url::Url;
 
+use crate::http_client;
+
 /// Cache TTL for successful stellar.toml fetches (24 hours)
 const SUCCESS_CACHE_TTL: u64 = 24 * 60 * 60;
 
@@ -184,9 +186,9 @@ impl StellarTomlClient {
         redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>,
         network_passphrase: Option<String>,
     ) -> Result<Self> {
-        let http_client = Client::builder()
+        let http_client = http_client::shared()
+            .builder()
             .timeout(REQUEST_TIMEOUT)
-            .user_agent("StellarInsights/1.0")
             .redirect(reqwest::redirect::Policy::limited(3))
             .build()?;
 
@@ -231,6 +233,17 @@ impl StellarTomlClient {
         self.fetch_toml_from_network(domain).await
     }
 
+    /// Fetch a raw file from a domain's `/.well-known/` directory over HTTPS,
+    /// bypassing the cache (used to check ownership proofs)
+    pub async fn fetch_well_known(&self, domain: &str, file: &str) -> Result<String> {
+        self.validate_domain(domain)?;
+        if file.contains('/') || file.contains("..") {
+            return Err(anyhow!("Invalid well-known file name"));
+        }
+        self.fetch_url(&format!("https://{}/.well-known/{}", domain, file))
+            .await
+    }
+
     /// Invalidate cache for a domain
     pub async fn invalidate_cache(&self, domain: &str) -> Result<()> {
         if let Some(conn) = self.redis_connection.read().await.as_ref() {
@@ -243,13 +256,13 @@ impl StellarTomlClient {
         Ok(())
     }
 
-    /// Background refresh for popular anchors
-    pub async fn background_refresh(&self, domain: &str) -> Result<()> {
+    /// Background refresh for popular anchors; returns the fresh stellar.toml
+    pub async fn background_refresh(&self, domain: &str) -> Result<StellarToml> {
         match self.fetch_toml_no_cache(domain).await {
             Ok(toml) => {
                 self.cache_success(domain, &toml).await?;
                 tracing::info!("Background refresh successful for domain: {}", domain);
-                Ok(())
+                Ok(toml)
             }
             Err(e) => {
                 tracing::warn!("Background refresh failed for domain {}: {}", domain, e);
@@ -334,10 +347,8 @@ impl StellarTomlClient {
         }
 
         // Fetch content
-        let response = self
-            .http_client
-            .get(url)
-            .send()
+        let response = http_client::shared()
+            .send(self.http_client.get(url))
             .await
             .map_err(|e| anyhow!("Request failed: {}", e))?;
 
@@ -0,0 +1,298 @@
+//! Wash-trading surveillance over network trades.
+//!
+//! [`TradeSurveillanceService::run_once`] scans the latest network trades
+//! closed in the last complete [`WINDOW_HOURS`] and flags, per corridor:
+//!
+//! - self trades: an account trading against its own offer
+//! - round trips: two accounts trading with each other at least
+//!   [`MIN_ROUND_TRIP_TRADES`] times
+//! - circular flows: three accounts that all trade with each other, each pair
+//!   at least [`MIN_CIRCLE_LEG_TRADES`] times
+//!
+//! Round trips and circles are only flagged between related accounts: ones
+//! for which trades within the group are at least [`RELATED_SHARE`] of their
+//! trades in the corridor, so a market maker serving many takers is not
+//! flagged for its regulars. Flags are stored in `surveillance_flags` for
+//! review through the admin API.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::database::Database;
+use crate::db::surveillance::NewSurveillanceFlag;
+use crate::rpc::{StellarRpcClient, Trade};
+use crate::services::concentration::corridor_trade;
+
+/// Length of each surveillance window
+pub const WINDOW_HOURS: i64 = 24;
+
+/// Latest network trades scanned
+const MAX_TRADES: u32 = 10_000;
+
+/// Trades between two accounts that make a round trip
+pub const MIN_ROUND_TRIP_TRADES: i64 = 3;
+
+/// Trades between each pair of accounts in a circular flow
+pub const MIN_CIRCLE_LEG_TRADES: i64 = 2;
+
+/// Share of an account's trades in the corridor that must be within the
+/// group for it to count as related
+pub const RELATED_SHARE: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    SelfTrade,
+    RoundTrip,
+    CircularFlow,
+}
+
+impl Pattern {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Pattern::SelfTrade => "self_trade",
+            Pattern::RoundTrip => "round_trip",
+            Pattern::CircularFlow => "circular_flow",
+        }
+    }
+}
+
+/// Trades between one pair of accounts
+#[derive(Debug, Clone, Copy, Default)]
+struct Leg {
+    trades: i64,
+    volume: f64,
+}
+
+/// One corridor's trades, by unordered pair of accounts
+#[derive(Debug, Default)]
+struct CorridorTrades {
+    legs: BTreeMap<(String, String), Leg>,
+    trades_per_account: HashMap<String, i64>,
+}
+
+impl CorridorTrades {
+    fn leg(&self, a: &str, b: &str) -> Leg {
+        let pair = if a <= b { (a, b) } else { (b, a) };
+        self.legs
+            .get(&(pair.0.to_string(), pair.1.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether `trades_within` is enough of `account`'s trades to relate it
+    /// to the group
+    fn related(&self, account: &str, trades_within: i64) -> bool {
+        let total = self.trades_per_account.get(account).copied().unwrap_or(0);
+        total > 0 && trades_within as f64 >= RELATED_SHARE * total as f64
+    }
+}
+
+/// Flags for trades closed in `[start, end)`, ordered by corridor
+pub fn detect(
+    trades: &[Trade],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<NewSurveillanceFlag> {
+    let mut corridors: BTreeMap<String, CorridorTrades> = BTreeMap::new();
+    for trade in trades {
+        let Some((key, amount)) = corridor_trade(trade, start, end) else {
+            continue;
+        };
+        let corridor = corridors.entry(key.to_string()).or_default();
+        let (a, b) = (&trade.base_account, &trade.counter_account);
+        let pair = if a <= b {
+            (a.clone(), b.clone())
+        } else {
+            (b.clone(), a.clone())
+        };
+        let leg = corridor.legs.entry(pair).or_default();
+        leg.trades += 1;
+        leg.volume += amount;
+        *corridor.trades_per_account.entry(a.clone()).or_default() += 1;
+        if a != b {
+            *corridor.trades_per_account.entry(b.clone()).or_default() += 1;
+        }
+    }
+
+    let mut flags = Vec::new();
+    for (corridor_key, corridor) in &corridors {
+        let mut flag = |pattern: Pattern, accounts: Vec<&String>, legs: &[Leg]| {
+            flags.push(NewSurveillanceFlag {
+                corridor_key: corridor_key.clone(),
+                pattern: pattern.as_str(),
+                accounts: accounts.into_iter().cloned().collect(),
+                trade_count: legs.iter().map(|l| l.trades).sum(),
+                volume: legs.iter().map(|l| l.volume).sum(),
+                window_start: start.to_rfc3339(),
+                window_end: end.to_rfc3339(),
+            });
+        };
+
+        // Accounts linked by enough trades to be part of a circle
+        let mut circle_links: BTreeMap<&String, BTreeSet<&String>> = BTreeMap::new();
+        for ((a, b), leg) in &corridor.legs {
+            if a == b {
+                flag(Pattern::SelfTrade, vec![a], &[*leg]);
+                continue;
+            }
+            if leg.trades >= MIN_ROUND_TRIP_TRADES
+                && corridor.related(a, leg.trades)
+                && corridor.related(b, leg.trades)
+            {
+                flag(Pattern::RoundTrip, vec![a, b], &[*leg]);
+            }
+            if leg.trades >= MIN_CIRCLE_LEG_TRADES {
+                circle_links.entry(a).or_default().insert(b);
+                circle_links.entry(b).or_default().insert(a);
+            }
+        }
+
+        // Each circle once, as a < b < c
+        for (a, a_links) in &circle_links {
+            for b in a_links.iter().filter(|b| *b > a) {
+                for c in circle_links[b].iter().filter(|c| *c > b) {
+                    if !a_links.contains(c) {
+                        continue;
+                    }
+                    let (ab, bc, ac) = (corridor.leg(a, b), corridor.leg(b, c), corridor.leg(a, c));
+                    if corridor.related(a, ab.trades + ac.trades)
+                        && corridor.related(b, ab.trades + bc.trades)
+                        && corridor.related(c, ac.trades + bc.trades)
+                    {
+                        flag(Pattern::CircularFlow, vec![a, b, c], &[ab, bc, ac]);
+                    }
+                }
+            }
+        }
+    }
+    flags
+}
+
+pub struct TradeSurveillanceService {
+    db: Arc<Database>,
+    rpc: Arc<StellarRpcClient>,
+}
+
+impl TradeSurveillanceService {
+    pub fn new(db: Arc<Database>, rpc: Arc<StellarRpcClient>) -> Self {
+        Self { db, rpc }
+    }
+
+    /// Scan the window ending at the start of the current hour and store new
+    /// flags. Returns the number stored.
+    pub async fn run_once(&self) -> Result<u64> {
+        let end = Utc::now()
+            .duration_trunc(Duration::hours(1))
+            .unwrap_or_else(|_| Utc::now());
+        let start = end - Duration::hours(WINDOW_HOURS);
+
+        let trades = self.rpc.fetch_all_trades(Some(MAX_TRADES)).await?;
+        let flags = detect(&trades, start, end);
+        let stored = self.db.surveillance_flags().record(&flags).await?;
+        info!(
+            "Trade surveillance found {} patterns in {} trades ({} new)",
+            flags.len(),
+            trades.len(),
+            stored
+        );
+        Ok(stored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::Price;
+    use chrono::TimeZone;
+
+    const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn trade(id: usize, base_account: &str, counter_account: &str) -> Trade {
+        Trade {
+            id: id.to_string(),
+            ledger_close_time: "2024-01-01T05:00:00Z".to_string(),
+            base_account: base_account.to_string(),
+            base_amount: "1000.0".to_string(),
+            base_asset_type: "native".to_string(),
+            base_asset_code: None,
+            base_asset_issuer: None,
+            counter_account: counter_account.to_string(),
+            counter_amount: "100.0".to_string(),
+            counter_asset_type: "credit_alphanum4".to_string(),
+            counter_asset_code: Some("USDC".to_string()),
+            counter_asset_issuer: Some(USDC_ISSUER.to_string()),
+            price: Price { n: 1, d: 10 },
+            trade_type: "orderbook".to_string(),
+        }
+    }
+
+    fn detect_all(pairs: &[(&str, &str)]) -> Vec<NewSurveillanceFlag> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let trades: Vec<Trade> = pairs
+            .iter()
+            .enumerate()
+            .map(|(i, (a, b))| trade(i, a, b))
+            .collect();
+        detect(&trades, start, start + Duration::hours(WINDOW_HOURS))
+    }
+
+    fn patterns(flags: &[NewSurveillanceFlag]) -> Vec<(&str, String)> {
+        flags
+            .iter()
+            .map(|f| (f.pattern, f.accounts.join(",")))
+            .collect()
+    }
+
+    #[test]
+    fn test_self_trades_are_flagged() {
+        let flags = detect_all(&[("GSELF", "GSELF"), ("GSELF", "GSELF"), ("GA", "GB")]);
+        assert_eq!(patterns(&flags), vec![("self_trade", "GSELF".to_string())]);
+        assert_eq!(flags[0].trade_count, 2);
+        // In USDC, the corridor's first asset
+        assert_eq!(flags[0].volume, 200.0);
+        assert_eq!(
+            flags[0].corridor_key,
+            format!("USDC:{}->XLM:native", USDC_ISSUER)
+        );
+    }
+
+    #[test]
+    fn test_round_trips_need_related_accounts() {
+        let flags = detect_all(&[("GA", "GB"), ("GB", "GA"), ("GA", "GB")]);
+        assert_eq!(patterns(&flags), vec![("round_trip", "GA,GB".to_string())]);
+
+        // A market maker trading mostly with others is not related to a regular
+        let flags = detect_all(&[
+            ("GMAKER", "GREGULAR"),
+            ("GMAKER", "GREGULAR"),
+            ("GMAKER", "GREGULAR"),
+            ("GMAKER", "GT1"),
+            ("GMAKER", "GT2"),
+            ("GMAKER", "GT3"),
+            ("GMAKER", "GT4"),
+        ]);
+        assert!(flags.is_empty());
+    }
+
+    #[test]
+    fn test_circular_flows_are_flagged_once() {
+        let flags = detect_all(&[
+            ("GA", "GB"),
+            ("GB", "GA"),
+            ("GB", "GC"),
+            ("GC", "GB"),
+            ("GC", "GA"),
+            ("GA", "GC"),
+            ("GA", "GOTHER"),
+        ]);
+        assert_eq!(
+            patterns(&flags),
+            vec![("circular_flow", "GA,GB,GC".to_string())]
+        );
+        assert_eq!(flags[0].trade_count, 6);
+        assert_eq!(flags[0].volume, 600.0);
+    }
+}
@@ -5,6 +5,7 @@ use tracing::info;
 
 use crate::models::{TrustlineMetrics, TrustlineSnapshot, TrustlineStat};
 use crate::rpc::StellarRpcClient;
+use crate::time_range::TimeRange;
 
 pub struct TrustlineAnalyzer {
     pool: Pool<Sqlite>,
@@ -163,4 +164,32 @@ impl TrustlineAnalyzer {
 
         Ok(history)
     }
+
+    /// Get snapshot history for an asset within a time range
+    pub async fn get_asset_history_in_range(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        range: &TimeRange,
+        limit: i64,
+    ) -> Result<Vec<TrustlineSnapshot>> {
+        let history = sqlx::query_as::<_, TrustlineSnapshot>(
+            r#"
+            SELECT * FROM trustline_snapshots
+            WHERE asset_code = ?1 AND asset_issuer = ?2
+              AND snapshot_at >= ?3 AND snapshot_at <= ?4
+            ORDER BY snapshot_at DESC
+            LIMIT ?5
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(range.from)
+        .bind(range.to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(history)
+    }
 }
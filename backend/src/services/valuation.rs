@@ -0,0 +1,335 @@
+//! Fiat valuation of metric amounts.
+//!
+//! Volumes are stored in USD (or in asset units for raw payments). The
+//! valuation service converts them into a quote currency using the price that
+//! was in effect when the metric was recorded, looked up in the historical
+//! price store. Fiat currencies are recorded there as `fiat:<CODE>` by the
+//! price feed job. When no observation is close enough, static reference
+//! rates are used so responses always carry a value; `rate_source` tells
+//! clients which one they got.
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use utoipa::IntoParams;
+
+use crate::db::price_history::PriceHistory;
+use crate::error::ApiError;
+use crate::preferences::{UserPreferences, SUPPORTED_BASE_CURRENCIES};
+
+/// Price feed asset id for well-known currency codes
+pub fn price_feed_asset_id(currency: &str) -> Option<&'static str> {
+    match currency {
+        "XLM" => Some("XLM:native"),
+        "USDC" => Some("USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"),
+        "EURC" => Some("EURC:GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2"),
+        _ => None,
+    }
+}
+
+/// Static reference USD rates used when no live or historical price is available
+pub fn fallback_usd_rate(currency: &str) -> Option<f64> {
+    match currency {
+        "USD" | "USDC" | "USDT" => Some(1.0),
+        "EUR" | "EURC" => Some(1.08),
+        "GBP" => Some(1.27),
+        "NGN" => Some(0.00065),
+        "KES" => Some(0.0077),
+        "GHS" => Some(0.064),
+        "PHP" => Some(0.0178),
+        "INR" => Some(0.012),
+        "XLM" => Some(0.12),
+        "BTC" => Some(62000.0),
+        "ETH" => Some(3200.0),
+        _ => None,
+    }
+}
+
+/// Fiat quote currencies whose USD rates the price feed job records
+pub const FIAT_CURRENCIES: &[&str] = &["EUR", "GBP", "NGN", "KES", "GHS", "PHP", "INR"];
+
+/// Key under which a fiat currency's USD price is recorded in the price store
+pub fn fiat_history_key(currency: &str) -> String {
+    format!("fiat:{}", currency)
+}
+
+/// Key under which a quote currency's USD price is recorded in the price store
+fn history_key(currency: &str) -> String {
+    match price_feed_asset_id(currency) {
+        Some(asset_id) => asset_id.to_string(),
+        None => fiat_history_key(currency),
+    }
+}
+
+/// Where a conversion rate came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RateSource {
+    /// No conversion was needed (USD)
+    Par,
+    /// An observation in the historical price store
+    PriceHistory,
+    /// A static reference rate
+    Reference,
+}
+
+impl RateSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateSource::Par => "par",
+            RateSource::PriceHistory => "price_history",
+            RateSource::Reference => "reference",
+        }
+    }
+}
+
+/// Quote units per 1 USD and where the rate came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteRate {
+    pub quote_per_usd: f64,
+    pub source: RateSource,
+}
+
+/// `?quote=` parameter accepted by volume-returning endpoints
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QuoteQuery {
+    /// Currency to express volumes in (defaults to the caller's base currency)
+    #[param(example = "EUR")]
+    pub quote: Option<String>,
+}
+
+impl QuoteQuery {
+    /// The requested quote currency, falling back to the user's base currency
+    pub fn resolve(&self, prefs: &UserPreferences) -> Result<Quote, ApiError> {
+        match self.quote.as_deref().map(str::trim) {
+            Some(raw) if !raw.is_empty() => Quote::parse(raw),
+            _ => Quote::parse(&prefs.base_currency),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quote(String);
+
+impl Quote {
+    pub fn parse(raw: &str) -> Result<Self, ApiError> {
+        let code = raw.trim().to_uppercase();
+        if SUPPORTED_BASE_CURRENCIES.contains(&code.as_str()) {
+            Ok(Self(code))
+        } else {
+            Err(ApiError::bad_request(
+                "INVALID_QUOTE",
+                format!(
+                    "Unsupported quote currency '{}': expected one of {}",
+                    raw,
+                    SUPPORTED_BASE_CURRENCIES.join(", ")
+                ),
+            ))
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+
+    /// USD amounts need no conversion
+    pub fn is_usd(&self) -> bool {
+        self.0 == "USD"
+    }
+}
+
+/// Converts USD and asset-denominated amounts into a quote currency.
+///
+/// Create one per request: rates are memoized per asset and hour so that
+/// valuing a long series costs one lookup per distinct hour.
+pub struct ValuationService {
+    history: PriceHistory,
+    memo: HashMap<(String, i64), Option<(f64, RateSource)>>,
+}
+
+impl ValuationService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            history: PriceHistory::new(pool),
+            memo: HashMap::new(),
+        }
+    }
+
+    /// USD price of `key` (a price feed asset id or `fiat:<code>`) at `at`
+    async fn usd_price(
+        &mut self,
+        key: &str,
+        fallback_code: &str,
+        at: DateTime<Utc>,
+    ) -> Option<(f64, RateSource)> {
+        let hour = at.with_minute(0).and_then(|t| t.with_second(0)).unwrap_or(at);
+        let memo_key = (key.to_string(), hour.timestamp());
+        if let Some(price) = self.memo.get(&memo_key) {
+            return *price;
+        }
+
+        let reference = || fallback_usd_rate(fallback_code).map(|p| (p, RateSource::Reference));
+        let price = match self.history.price_at(key, at).await {
+            Ok(Some(point)) if point.price_usd > 0.0 => {
+                Some((point.price_usd, RateSource::PriceHistory))
+            }
+            Ok(_) => reference(),
+            Err(e) => {
+                tracing::warn!("Price history lookup failed for {}: {}", key, e);
+                reference()
+            }
+        };
+        self.memo.insert(memo_key, price);
+        price
+    }
+
+    /// Units of `quote` per 1 USD at `at`
+    pub async fn quote_per_usd(&mut self, quote: &Quote, at: DateTime<Utc>) -> Option<QuoteRate> {
+        if quote.is_usd() {
+            return Some(QuoteRate {
+                quote_per_usd: 1.0,
+                source: RateSource::Par,
+            });
+        }
+        let (price, source) = self
+            .usd_price(&history_key(quote.code()), quote.code(), at)
+            .await?;
+        Some(QuoteRate {
+            quote_per_usd: 1.0 / price,
+            source,
+        })
+    }
+
+    /// Convert a USD amount recorded at `at` into `quote`
+    pub async fn usd_to_quote(
+        &mut self,
+        amount_usd: f64,
+        quote: &Quote,
+        at: DateTime<Utc>,
+    ) -> Option<(f64, RateSource)> {
+        let rate = self.quote_per_usd(quote, at).await?;
+        Some((amount_usd * rate.quote_per_usd, rate.source))
+    }
+
+    /// Convert an amount of `asset` (`CODE:ISSUER` or `XLM:native`) recorded
+    /// at `at` into `quote`. The source is the less precise of the two rates used.
+    pub async fn asset_to_quote(
+        &mut self,
+        asset: &str,
+        amount: f64,
+        quote: &Quote,
+        at: DateTime<Utc>,
+    ) -> Option<(f64, RateSource)> {
+        let code = asset.split(':').next().unwrap_or(asset).to_uppercase();
+        let (asset_usd, asset_source) = self.usd_price(asset, &code, at).await?;
+        let (value, quote_source) = self.usd_to_quote(amount * asset_usd, quote, at).await?;
+        Some((value, asset_source.max(quote_source)))
+    }
+
+    /// Annotate each serialized item with quote values, using the rate at the
+    /// item's own `time_field` timestamp
+    pub async fn annotate_all(&mut self, items: &mut [Value], quote: &Quote, time_field: &str) {
+        for item in items {
+            let at = timestamp_field(item, time_field);
+            if let Some(rate) = self.quote_per_usd(quote, at).await {
+                annotate_usd_fields(item, quote, rate);
+            }
+        }
+    }
+}
+
+/// Add quote-currency siblings to every numeric `*_usd` field of a JSON object.
+///
+/// `volume_usd` gains `volume_eur` (for quote EUR), and the object gains
+/// `quote_currency`, `quote_rate` (quote units per USD) and `rate_source`.
+/// Existing USD fields are left untouched so clients that ignore `quote`
+/// keep working.
+pub fn annotate_usd_fields(value: &mut Value, quote: &Quote, rate: QuoteRate) {
+    let quote_per_usd = rate.quote_per_usd;
+    let Value::Object(map) = value else {
+        return;
+    };
+
+    let suffix = format!("_{}", quote.code().to_lowercase());
+    let converted: Vec<(String, f64)> = map
+        .iter()
+        .filter_map(|(key, v)| {
+            let stem = key.strip_suffix("_usd")?;
+            Some((format!("{}{}", stem, suffix), v.as_f64()? * quote_per_usd))
+        })
+        .collect();
+
+    for (key, amount) in converted {
+        map.insert(key, serde_json::json!(amount));
+    }
+    map.insert("quote_currency".to_string(), Value::from(quote.code()));
+    map.insert("quote_rate".to_string(), serde_json::json!(quote_per_usd));
+    map.insert("rate_source".to_string(), Value::from(rate.source.as_str()));
+}
+
+/// Parse a serialized timestamp field, defaulting to now when absent or malformed
+pub fn timestamp_field(value: &Value, field: &str) -> DateTime<Utc> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_quote_parse() {
+        assert_eq!(Quote::parse("eur").unwrap().code(), "EUR");
+        assert!(Quote::parse("USD").unwrap().is_usd());
+        assert!(Quote::parse("DOGE").is_err());
+    }
+
+    #[test]
+    fn test_quote_query_defaults_to_preferences() {
+        let prefs = UserPreferences {
+            base_currency: "GBP".to_string(),
+            ..UserPreferences::default()
+        };
+        assert_eq!(QuoteQuery::default().resolve(&prefs).unwrap().code(), "GBP");
+
+        let explicit = QuoteQuery {
+            quote: Some("ngn".to_string()),
+        };
+        assert_eq!(explicit.resolve(&prefs).unwrap().code(), "NGN");
+    }
+
+    #[test]
+    fn test_annotate_usd_fields() {
+        let quote = Quote::parse("EUR").unwrap();
+        let mut value = json!({"id": "x", "volume_usd": 108.0, "health_score": 90.0});
+        annotate_usd_fields(
+            &mut value,
+            &quote,
+            QuoteRate {
+                quote_per_usd: 0.5,
+                source: RateSource::Reference,
+            },
+        );
+
+        assert_eq!(value["volume_usd"], json!(108.0));
+        assert_eq!(value["volume_eur"], json!(54.0));
+        assert_eq!(value["quote_currency"], json!("EUR"));
+        assert_eq!(value["rate_source"], json!("reference"));
+        assert!(value.get("health_score_eur").is_none());
+    }
+
+    #[test]
+    fn test_history_key() {
+        assert_eq!(history_key("EUR"), "fiat:EUR");
+        assert_eq!(history_key("EURC"), price_feed_asset_id("EURC").unwrap());
+        assert_eq!(history_key("XLM"), "XLM:native");
+        assert!(history_key("USDC").starts_with("USDC:G"));
+    }
+}
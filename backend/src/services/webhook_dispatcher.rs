@@ -1,29 +1,96 @@
 /// Webhook Dispatcher Service
-/// Processes webhook events and sends them to registered webhooks with retry logic
+/// Processes webhook events and sends them to registered webhooks, honouring each
+/// webhook's batching and delivery rate settings. Failed deliveries are retried with
+/// exponential backoff and dead-lettered once their retries run out
 
 use anyhow::Result;
 use reqwest::Client;
 use sqlx::SqlitePool;
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use crate::webhooks::{WebhookService, WebhookSignature, WebhookEventEnvelope};
+use crate::billing::{UsageKind, UsageRecorder};
+use crate::http_client;
+use crate::observability::error_sink::{self, ErrorEvent, ErrorSource};
+use crate::webhooks::deliveries::{retry_delay, MAX_DELIVERY_RETRIES};
+use crate::webhooks::{
+    DeliverySettings, PendingWebhookEvent, Webhook, WebhookBatchEnvelope, WebhookEventEnvelope,
+    WebhookService, WebhookSignature,
+};
+
+/// Pending events fetched per dispatcher run
+const PENDING_EVENTS_PER_RUN: usize = 500;
+
+/// Window over which per-endpoint delivery caps are enforced
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Sliding-window counter of deliveries made to each webhook endpoint
+#[derive(Default)]
+pub struct DeliveryRateLimiter {
+    sent: HashMap<String, VecDeque<Instant>>,
+}
+
+impl DeliveryRateLimiter {
+    /// Record a delivery to `webhook_id` if it is under `max_per_minute`;
+    /// returns false when the cap has been reached
+    pub fn try_acquire(&mut self, webhook_id: &str, max_per_minute: Option<u32>, now: Instant) -> bool {
+        let Some(cap) = max_per_minute else {
+            return true;
+        };
+
+        let sent = self.sent.entry(webhook_id.to_string()).or_default();
+        while sent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW)
+        {
+            sent.pop_front();
+        }
+
+        if sent.len() >= cap as usize {
+            return false;
+        }
+        sent.push_back(now);
+        true
+    }
+}
+
+/// Whether a webhook's pending events should be sent now, or held so more
+/// events can join the batch
+fn batch_ready(settings: &DeliverySettings, pending: usize, oldest_age: Duration) -> bool {
+    settings.max_batch_size <= 1
+        || pending >= settings.max_batch_size as usize
+        || oldest_age >= Duration::from_secs(settings.batch_window_secs as u64)
+}
+
+/// Age of an event from its RFC 3339 `created_at`; unparseable timestamps count as old
+fn event_age(event: &PendingWebhookEvent, now: chrono::DateTime<chrono::Utc>) -> Duration {
+    chrono::DateTime::parse_from_rfc3339(&event.created_at)
+        .ok()
+        .and_then(|created| (now - created.with_timezone(&chrono::Utc)).to_std().ok())
+        .unwrap_or(Duration::MAX)
+}
 
 /// Webhook dispatcher - sends events to webhooks asynchronously
 pub struct WebhookDispatcher {
     db: SqlitePool,
+    encryption_key: String,
     http_client: Client,
+    rate_limiter: Mutex<DeliveryRateLimiter>,
 }
 
 impl WebhookDispatcher {
     /// Create new webhook dispatcher
-    pub fn new(db: SqlitePool) -> Self {
-        let http_client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .unwrap_or_else(|_| Client::new());
+    pub fn new(db: SqlitePool, encryption_key: impl Into<String>) -> Self {
+        let http_client = http_client::shared().client(Duration::from_secs(10));
 
-        Self { db, http_client }
+        Self {
+            db,
+            encryption_key: encryption_key.into(),
+            http_client,
+            rate_limiter: Mutex::new(DeliveryRateLimiter::default()),
+        }
     }
 
     /// Run dispatcher loop - processes pending webhook events
@@ -41,102 +108,212 @@ impl WebhookDispatcher {
         }
     }
 
-    /// Process all pending webhook events
+    /// Process pending webhook events, grouped per webhook
     async fn process_pending_events(&self) -> Result<()> {
-        let service = WebhookService::new(self.db.clone());
+        let service = WebhookService::new(self.db.clone(), self.encryption_key.clone());
 
-        // Fetch pending events (max 10 per run)
-        let events = service.get_pending_events(10).await?;
+        let events = service.get_pending_events(PENDING_EVENTS_PER_RUN).await?;
 
-        for (event_id, webhook_id, event_type, payload_str) in events {
-            // Get webhook details
-            let webhook = match service.get_webhook(&webhook_id).await? {
-                Some(w) => w,
-                None => {
-                    // Webhook was deleted, mark event as failed
+        // Group by webhook, keeping oldest-first order within each group
+        let mut order: Vec<String> = Vec::new();
+        let mut by_webhook: HashMap<String, Vec<PendingWebhookEvent>> = HashMap::new();
+        for event in events {
+            if !by_webhook.contains_key(&event.webhook_id) {
+                order.push(event.webhook_id.clone());
+            }
+            by_webhook
+                .entry(event.webhook_id.clone())
+                .or_default()
+                .push(event);
+        }
+
+        for webhook_id in order {
+            let events = by_webhook.remove(&webhook_id).unwrap_or_default();
+            self.process_webhook_events(&service, &webhook_id, events)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn process_webhook_events(
+        &self,
+        service: &WebhookService,
+        webhook_id: &str,
+        events: Vec<PendingWebhookEvent>,
+    ) -> Result<()> {
+        // Get webhook details
+        let webhook = match service.get_webhook(webhook_id).await? {
+            Some(w) if w.is_active => w,
+            other => {
+                // Webhook was deleted or deactivated, mark its events as failed
+                let reason = if other.is_some() {
+                    "webhook_inactive"
+                } else {
+                    "webhook_deleted"
+                };
+                for event in &events {
                     let _ = service
-                        .update_event_status(&event_id, "failed", Some("webhook_deleted"), 0)
+                        .update_event_status(&event.id, "failed", Some(reason), 0)
                         .await;
-                    continue;
                 }
-            };
+                return Ok(());
+            }
+        };
 
-            if !webhook.is_active {
-                let _ = service
-                    .update_event_status(&event_id, "failed", Some("webhook_inactive"), 0)
-                    .await;
-                continue;
+        let settings = webhook.delivery_settings();
+        let now = chrono::Utc::now();
+        let oldest_age = events
+            .first()
+            .map(|e| event_age(e, now))
+            .unwrap_or_default();
+        if !batch_ready(&settings, events.len(), oldest_age) {
+            return Ok(());
+        }
+
+        let chunk_size = settings.max_batch_size.max(1) as usize;
+        for chunk in events.chunks(chunk_size) {
+            let allowed = self
+                .rate_limiter
+                .lock()
+                .map(|mut limiter| {
+                    limiter.try_acquire(webhook_id, settings.max_deliveries_per_minute, Instant::now())
+                })
+                .unwrap_or(true);
+            if !allowed {
+                // Remaining events stay pending until the endpoint is under its cap
+                tracing::debug!(
+                    "Webhook delivery rate cap reached: webhook_id={}, deferred={}",
+                    webhook_id,
+                    events.len()
+                );
+                break;
             }
 
-            // Attempt delivery
-            match self
-                .deliver_webhook(&webhook.url, &payload_str, &webhook.secret, &event_type)
-                .await
-            {
+            let result = if chunk_size > 1 {
+                self.deliver_batch(&webhook, chunk).await
+            } else {
+                let event = &chunk[0];
+                self.deliver_webhook(&webhook, &event.payload, &event.event_type)
+                    .await
+            };
+
+            match result {
                 Ok(_) => {
-                    // Success
-                    let _ = service
-                        .update_event_status(&event_id, "delivered", None, 0)
-                        .await;
+                    for event in chunk {
+                        let _ = service.mark_delivered(&event.id, event.retries).await;
+                    }
 
                     // Update webhook's last_fired_at
-                    let _ = service.update_last_fired(&webhook_id).await;
+                    let _ = service.update_last_fired(webhook_id).await;
+
+                    if let Some(tenant_id) = &webhook.tenant_id {
+                        UsageRecorder::new(self.db.clone()).record(
+                            tenant_id,
+                            UsageKind::WebhookDelivery,
+                            chunk.len() as i64,
+                        );
+                    }
 
                     tracing::info!(
-                        "Webhook delivered successfully: webhook_id={}, event={}",
+                        "Webhook delivered successfully: webhook_id={}, events={}",
                         webhook_id,
-                        event_type
+                        chunk.len()
                     );
                 }
-                Err(e) => {
-                    // Determine retry count from event
-                    let current_retries = self
-                        .get_event_retries(&event_id)
-                        .await
-                        .unwrap_or(0);
-
-                    if current_retries < 3 {
-                        // Retry later
-                        let _ = service
-                            .update_event_status(
-                                &event_id,
-                                "pending",
-                                Some(&e.to_string()),
-                                current_retries + 1,
-                            )
-                            .await;
-
-                        tracing::warn!(
-                            "Webhook delivery failed (will retry): webhook_id={}, error={}, retries={}",
-                            webhook_id,
-                            e,
-                            current_retries + 1
-                        );
-                    } else {
-                        // Max retries exceeded
-                        let _ = service
-                            .update_event_status(&event_id, "failed", Some(&e.to_string()), 3)
-                            .await;
-
-                        tracing::error!(
-                            "Webhook delivery failed (max retries): webhook_id={}, error={}",
-                            webhook_id,
-                            e
-                        );
-                    }
-                }
+                Err(e) => self.record_failure(service, &webhook, chunk, &e).await,
             }
         }
 
         Ok(())
     }
 
+    /// Schedule a retry for each event after its backoff, or move it to the
+    /// dead letters once retries are exhausted
+    async fn record_failure(
+        &self,
+        service: &WebhookService,
+        webhook: &Webhook,
+        events: &[PendingWebhookEvent],
+        error: &anyhow::Error,
+    ) {
+        let webhook_id = webhook.id.as_str();
+        let message = error.to_string();
+        for event in events {
+            let current_retries = event.retries;
+
+            if current_retries < MAX_DELIVERY_RETRIES {
+                // Retry once the backoff has elapsed
+                let next_attempt_at = chrono::Utc::now() + retry_delay(current_retries);
+                let _ = service
+                    .schedule_retry(&event.id, &message, current_retries + 1, next_attempt_at)
+                    .await;
+
+                tracing::warn!(
+                    "Webhook delivery failed (will retry): webhook_id={}, error={}, retries={}, next_attempt_at={}",
+                    webhook_id,
+                    error,
+                    current_retries + 1,
+                    next_attempt_at.to_rfc3339()
+                );
+            } else {
+                // Max retries exceeded
+                if let Err(e) = service.dead_letter(event, &message, current_retries).await {
+                    tracing::error!("Failed to dead-letter webhook event {}: {}", event.id, e);
+                }
+
+                tracing::error!(
+                    "Webhook delivery failed (max retries): webhook_id={}, error={}",
+                    webhook_id,
+                    error
+                );
+                let mut report = ErrorEvent::new(
+                    ErrorSource::WebhookDispatch,
+                    format!("Webhook delivery failed after retries: {}", error),
+                    serde_json::json!({
+                        "webhook_id": webhook_id,
+                        "event_id": event.id,
+                        "event_type": event.event_type,
+                    }),
+                );
+                report.user_id = Some(webhook.user_id.clone());
+                error_sink::capture(report);
+            }
+        }
+    }
+
+    /// Deliver several events in one request
+    async fn deliver_batch(&self, webhook: &Webhook, events: &[PendingWebhookEvent]) -> Result<()> {
+        let timestamp = chrono::Utc::now().timestamp();
+
+        let envelopes = events
+            .iter()
+            .map(|event| {
+                Ok(WebhookEventEnvelope {
+                    id: event.id.clone(),
+                    event: event.event_type.clone(),
+                    timestamp,
+                    data: serde_json::from_str(&event.payload)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let envelope = WebhookBatchEnvelope {
+            id: Uuid::new_v4().to_string(),
+            event: "batch".to_string(),
+            timestamp,
+            events: envelopes,
+        };
+
+        self.post_signed(webhook, "batch", &envelope.id, timestamp, &envelope)
+            .await
+    }
+
     /// Deliver webhook to URL
     async fn deliver_webhook(
         &self,
-        url: &str,
+        webhook: &Webhook,
         payload: &str,
-        secret: &str,
         event_type: &str,
     ) -> Result<()> {
         let delivery_id = Uuid::new_v4().to_string();
@@ -150,27 +327,45 @@ impl WebhookDispatcher {
             data: serde_json::from_str(payload)?,
         };
 
-        let body = serde_json::to_string(&envelope)?;
-        let signature = WebhookSignature::sign(&body, secret);
+        self.post_signed(webhook, event_type, &delivery_id, timestamp, &envelope)
+            .await
+    }
+
+    /// Sign and POST a serialized envelope. While a secret rotation's grace
+    /// window is open, the envelope is also signed with the previous secret.
+    async fn post_signed<T: serde::Serialize>(
+        &self,
+        webhook: &Webhook,
+        event_type: &str,
+        delivery_id: &str,
+        timestamp: i64,
+        envelope: &T,
+    ) -> Result<()> {
+        let body = serde_json::to_string(envelope)?;
+        let signature = WebhookSignature::sign(&body, timestamp, &webhook.secret);
 
         tracing::debug!(
             "Sending webhook to {}: delivery_id={}, signature={}...",
-            url,
+            webhook.url,
             delivery_id,
             &signature[..20]
         );
 
-        let response = self
+        let mut request = self
             .http_client
-            .post(url)
+            .post(&webhook.url)
             .header("X-Zapier-Event", event_type)
             .header("X-Zapier-Signature", signature)
             .header("X-Zapier-Timestamp", timestamp.to_string())
             .header("X-Zapier-Delivery-ID", delivery_id)
-            .header("Content-Type", "application/json")
-            .body(body)
-            .send()
-            .await?;
+            .header("Content-Type", "application/json");
+        if let Some(previous) = webhook.previous_secret_at(chrono::Utc::now()) {
+            request = request.header(
+                "X-Zapier-Signature-Previous",
+                WebhookSignature::sign(&body, timestamp, previous),
+            );
+        }
+        let response = request.body(body).send().await?;
 
         if response.status().is_success() {
             Ok(())
@@ -182,16 +377,6 @@ impl WebhookDispatcher {
             )
         }
     }
-
-    /// Get current retry count for an event
-    async fn get_event_retries(&self, event_id: &str) -> Result<i32> {
-        let retries: Option<i64> = sqlx::query_scalar("SELECT retries FROM webhook_events WHERE id = ?")
-            .bind(event_id)
-            .fetch_optional(&self.db)
-            .await?;
-
-        Ok(retries.unwrap_or(0) as i32)
-    }
 }
 
 #[cfg(test)]
@@ -203,4 +388,32 @@ mod tests {
         // This is a smoke test for basic creation
         // Full tests would require mocking the database and HTTP client
     }
+
+    #[test]
+    fn test_rate_limiter_caps_per_window() {
+        let mut limiter = DeliveryRateLimiter::default();
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire("a", Some(2), start));
+        assert!(limiter.try_acquire("a", Some(2), start));
+        assert!(!limiter.try_acquire("a", Some(2), start));
+        // Other endpoints and uncapped webhooks are unaffected
+        assert!(limiter.try_acquire("b", Some(2), start));
+        assert!(limiter.try_acquire("a", None, start));
+        // The window slides
+        assert!(limiter.try_acquire("a", Some(2), start + RATE_WINDOW));
+    }
+
+    #[test]
+    fn test_batch_ready() {
+        let settings = DeliverySettings {
+            max_batch_size: 10,
+            batch_window_secs: 30,
+            max_deliveries_per_minute: None,
+        };
+        assert!(!batch_ready(&settings, 3, Duration::from_secs(5)));
+        assert!(batch_ready(&settings, 10, Duration::from_secs(5)));
+        assert!(batch_ready(&settings, 3, Duration::from_secs(30)));
+        assert!(batch_ready(&DeliverySettings::default(), 1, Duration::ZERO));
+    }
 }
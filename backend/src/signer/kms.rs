@@ -0,0 +1,139 @@
+//! Signer backed by a Google Cloud KMS asymmetric key (`EC_SIGN_ED25519`).
+//!
+//! `key_name` is the full key version resource name,
+//! `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`.
+//! Requests are authorised with the configured access token when set
+//! (`KMS_ACCESS_TOKEN`), otherwise with a token from the GCE metadata server
+//! (workload identity).
+
+use anyhow::{anyhow, Context, Result};
+use axum::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::{ed25519_key_from_spki, Signer};
+
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+#[derive(Deserialize)]
+struct PublicKeyResponse {
+    pem: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct MetadataToken {
+    access_token: String,
+}
+
+pub struct KmsSigner {
+    client: Client,
+    endpoint: String,
+    key_name: String,
+    static_token: Option<String>,
+    public_key: OnceCell<[u8; 32]>,
+}
+
+impl KmsSigner {
+    pub fn new(key_name: String, endpoint: &str, access_token: Option<String>) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            key_name,
+            static_token: access_token,
+            public_key: OnceCell::new(),
+        })
+    }
+
+    async fn access_token(&self) -> Result<String> {
+        if let Some(token) = &self.static_token {
+            return Ok(token.clone());
+        }
+
+        let token: MetadataToken = self
+            .client
+            .get(METADATA_TOKEN_URL)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .context("Failed to reach the metadata server for a KMS token")?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(token.access_token)
+    }
+
+    async fn fetch_public_key(&self) -> Result<[u8; 32]> {
+        let url = format!("{}/v1/{}/publicKey", self.endpoint, self.key_name);
+        let response: PublicKeyResponse = self
+            .client
+            .get(&url)
+            .bearer_auth(self.access_token().await?)
+            .send()
+            .await?
+            .error_for_status()
+            .context("KMS publicKey request failed")?
+            .json()
+            .await?;
+
+        let der_b64: String = response
+            .pem
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect();
+        let der = BASE64
+            .decode(der_b64.trim())
+            .context("KMS returned an invalid PEM")?;
+        ed25519_key_from_spki(&der)
+    }
+}
+
+#[async_trait]
+impl Signer for KmsSigner {
+    fn backend(&self) -> &'static str {
+        "kms"
+    }
+
+    async fn public_key(&self) -> Result<[u8; 32]> {
+        Ok(*self
+            .public_key
+            .get_or_try_init(|| self.fetch_public_key())
+            .await?)
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+        let url = format!("{}/v1/{}:asymmetricSign", self.endpoint, self.key_name);
+        let response: SignResponse = self
+            .client
+            .post(&url)
+            .bearer_auth(self.access_token().await?)
+            .json(&serde_json::json!({ "data": BASE64.encode(message) }))
+            .send()
+            .await?
+            .error_for_status()
+            .context("KMS asymmetricSign request failed")?
+            .json()
+            .await?;
+
+        let signature = BASE64
+            .decode(&response.signature)
+            .context("KMS returned an invalid signature")?;
+        signature
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("KMS key {} is not an ed25519 key", self.key_name))
+    }
+}
@@ -0,0 +1,66 @@
+//! In-process signer holding the seed in memory. Meant for development and
+//! tests; production deployments should use Vault transit or KMS.
+
+use anyhow::{anyhow, Context, Result};
+use axum::async_trait;
+use ed25519_dalek::{Signer as _, SigningKey};
+
+use super::Signer;
+use crate::muxed::decode_secret_seed;
+
+pub struct LocalSigner {
+    signing_key: SigningKey,
+}
+
+impl LocalSigner {
+    pub fn new(signing_key: SigningKey) -> Self {
+        Self { signing_key }
+    }
+
+    /// Accepts either a Stellar S-address secret seed or a hex-encoded 32-byte seed
+    pub fn from_seed_str(seed: &str) -> Result<Self> {
+        let seed = seed.trim();
+        let bytes = if seed.starts_with('S') {
+            decode_secret_seed(seed).ok_or_else(|| anyhow!("Invalid Stellar secret seed"))?
+        } else {
+            let mut bytes = [0u8; 32];
+            hex::decode_to_slice(seed, &mut bytes).context("Seed must be 32 bytes of hex")?;
+            bytes
+        };
+        Ok(Self::new(SigningKey::from_bytes(&bytes)))
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn public_key(&self) -> Result<[u8; 32]> {
+        Ok(self.signing_key.verifying_key().to_bytes())
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+        Ok(self.signing_key.sign(message).to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    #[tokio::test]
+    async fn test_local_signer_signs_verifiably() {
+        let signer = LocalSigner::from_seed_str(&hex::encode([7u8; 32])).unwrap();
+        let signature = signer.sign(b"payload").await.unwrap();
+
+        let key = VerifyingKey::from_bytes(&signer.public_key().await.unwrap()).unwrap();
+        assert!(key
+            .verify(b"payload", &Signature::from_bytes(&signature))
+            .is_ok());
+        assert!(signer.stellar_address().await.unwrap().starts_with('G'));
+        assert!(LocalSigner::from_seed_str("not-a-seed").is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! Key management for signing operations.
+//!
+//! Everything the backend signs (snapshot hashes, contract transactions) goes
+//! through the [`Signer`] trait, so the key itself can live outside the
+//! process. Backends are selected per key in `Settings::signing`
+//! (`{PREFIX}_SIGNER`):
+//!
+//! - `local` (default): ed25519 seed from the settings, for development
+//! - `vault`: HashiCorp Vault transit engine (`{PREFIX}_VAULT_KEY`)
+//! - `kms`: Google Cloud KMS asymmetric key (`{PREFIX}_KMS_KEY`)
+//!
+//! With `vault` or `kms` the private key never leaves the key manager.
+
+pub mod kms;
+pub mod local;
+pub mod vault_transit;
+
+pub use kms::KmsSigner;
+pub use local::LocalSigner;
+pub use vault_transit::VaultTransitSigner;
+
+use anyhow::{bail, Context, Result};
+use axum::async_trait;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::config::{SignerSettings, SigningSettings};
+use crate::muxed::encode_account_id;
+
+/// An ed25519 signing key, wherever it is held
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Short backend name for logs ("local", "vault", "kms")
+    fn backend(&self) -> &'static str;
+
+    /// Raw 32-byte ed25519 public key
+    async fn public_key(&self) -> Result<[u8; 32]>;
+
+    /// Raw 64-byte ed25519 signature over `message`
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64]>;
+
+    /// The public key as a Stellar G-address
+    async fn stellar_address(&self) -> Result<String> {
+        Ok(encode_account_id(&self.public_key().await?))
+    }
+}
+
+/// Where a key is held, selected per key with `{PREFIX}_SIGNER`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignerBackend {
+    #[default]
+    Local,
+    Vault,
+    Kms,
+}
+
+impl SignerBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Vault => "vault",
+            Self::Kms => "kms",
+        }
+    }
+}
+
+impl FromStr for SignerBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "vault" => Ok(Self::Vault),
+            "kms" => Ok(Self::Kms),
+            other => Err(format!(
+                "unknown signer '{}', expected local, vault or kms",
+                other
+            )),
+        }
+    }
+}
+
+/// Build the signer for `key` (validated by [`Settings::validate`]).
+///
+/// For the local backend `Ok(None)` is returned when no seed is set, so
+/// callers can treat signing as optional. Remote backends must be fully
+/// configured.
+///
+/// [`Settings::validate`]: crate::config::Settings::validate
+pub async fn build_signer(
+    key: &SignerSettings,
+    signing: &SigningSettings,
+) -> Result<Option<Arc<dyn Signer>>> {
+    let signer: Arc<dyn Signer> = match key.backend {
+        SignerBackend::Local => {
+            let Some(seed) = &key.seed else {
+                return Ok(None);
+            };
+            Arc::new(LocalSigner::from_seed_str(seed).context("Invalid ed25519 seed")?)
+        }
+        SignerBackend::Vault => {
+            let key_name = key.vault_key.clone().context("No Vault transit key set")?;
+            Arc::new(
+                VaultTransitSigner::connect(key_name, signing.vault_transit_mount.clone()).await?,
+            )
+        }
+        SignerBackend::Kms => {
+            let key_name = key.kms_key.clone().context("No KMS key set")?;
+            Arc::new(KmsSigner::new(
+                key_name,
+                &signing.kms_endpoint,
+                signing.kms_access_token.clone(),
+            )?)
+        }
+    };
+
+    Ok(Some(signer))
+}
+
+/// Read the raw key out of a DER (SPKI) encoded ed25519 public key
+pub(crate) fn ed25519_key_from_spki(der: &[u8]) -> Result<[u8; 32]> {
+    // SPKI for ed25519 is a fixed 12-byte header followed by the raw key
+    const SPKI_LEN: usize = 44;
+    if der.len() != SPKI_LEN {
+        bail!(
+            "Expected a {}-byte ed25519 SPKI key, got {}",
+            SPKI_LEN,
+            der.len()
+        );
+    }
+    Ok(der[SPKI_LEN - 32..].try_into()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unconfigured_local_signer_is_none() {
+        let signer = build_signer(&SignerSettings::default(), &SigningSettings::default())
+            .await
+            .unwrap();
+        assert!(signer.is_none());
+    }
+
+    #[test]
+    fn test_ed25519_key_from_spki() {
+        let mut der = hex::decode("302a300506032b6570032100").unwrap();
+        der.extend_from_slice(&[5u8; 32]);
+        assert_eq!(ed25519_key_from_spki(&der).unwrap(), [5u8; 32]);
+        assert!(ed25519_key_from_spki(&der[1..]).is_err());
+    }
+}
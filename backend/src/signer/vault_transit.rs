@@ -0,0 +1,69 @@
+//! Signer backed by the HashiCorp Vault transit engine. The key is created in
+//! Vault as type `ed25519` and never leaves it; only signatures come back.
+
+use anyhow::{anyhow, Result};
+use axum::async_trait;
+use tokio::sync::OnceCell;
+
+use super::Signer;
+use crate::vault::{VaultClient, VaultConfig};
+
+pub struct VaultTransitSigner {
+    client: VaultClient,
+    mount: String,
+    key_name: String,
+    public_key: OnceCell<[u8; 32]>,
+}
+
+impl VaultTransitSigner {
+    pub fn new(client: VaultClient, mount: String, key_name: String) -> Self {
+        Self {
+            client,
+            mount,
+            key_name,
+            public_key: OnceCell::new(),
+        }
+    }
+
+    /// Connect with the standard `VAULT_*` settings and use the transit
+    /// engine at `mount`
+    pub async fn connect(key_name: String, mount: String) -> Result<Self> {
+        let client = VaultClient::new(VaultConfig::from_env()?).await?;
+        Ok(Self::new(client, mount, key_name))
+    }
+}
+
+#[async_trait]
+impl Signer for VaultTransitSigner {
+    fn backend(&self) -> &'static str {
+        "vault"
+    }
+
+    async fn public_key(&self) -> Result<[u8; 32]> {
+        let key = self
+            .public_key
+            .get_or_try_init(|| async {
+                let bytes = self
+                    .client
+                    .transit_public_key(&self.mount, &self.key_name)
+                    .await?;
+                <[u8; 32]>::try_from(bytes.as_slice())
+                    .map_err(|_| anyhow!("Transit key {} is not an ed25519 key", self.key_name))
+            })
+            .await?;
+        Ok(*key)
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+        let signature = self
+            .client
+            .transit_sign(&self.mount, &self.key_name, message)
+            .await?;
+        <[u8; 64]>::try_from(signature.as_slice()).map_err(|_| {
+            anyhow!(
+                "Transit key {} returned a non-ed25519 signature",
+                self.key_name
+            )
+        })
+    }
+}
@@ -1,7 +1,9 @@
 pub mod generator;
 pub mod schema;
+pub mod signing;
 
 pub use generator::SnapshotGenerator;
 pub use schema::{
-    AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
+    AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SnapshotSlaSummary,
+    SCHEMA_VERSION,
 };
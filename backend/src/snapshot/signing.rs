@@ -0,0 +1,92 @@
+//! Ed25519 signatures over published snapshots.
+//!
+//! Each snapshot's SHA-256 hash (the same 32 bytes anchored on-chain) is
+//! signed with the backend's key, so consumers can check a snapshot document
+//! came from this deployment before the contract submission confirms.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use std::sync::Arc;
+
+use crate::config::SigningSettings;
+use crate::signer::{build_signer, Signer};
+
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+pub struct SnapshotSigner {
+    signer: Arc<dyn Signer>,
+}
+
+impl SnapshotSigner {
+    pub fn new(signer: Arc<dyn Signer>) -> Self {
+        Self { signer }
+    }
+
+    /// Build the signer in `signing.snapshot` (local, vault or kms).
+    /// `Ok(None)` is returned when the local backend has no seed, i.e.
+    /// signing is not configured.
+    pub async fn from_settings(signing: &SigningSettings) -> Result<Option<Self>> {
+        Ok(build_signer(&signing.snapshot, signing)
+            .await?
+            .map(Self::new))
+    }
+
+    pub fn backend(&self) -> &'static str {
+        self.signer.backend()
+    }
+
+    /// Hex-encoded public key
+    pub async fn public_key_hex(&self) -> Result<String> {
+        Ok(hex::encode(self.signer.public_key().await?))
+    }
+
+    /// The public key as a Stellar G-address
+    pub async fn stellar_address(&self) -> Result<String> {
+        self.signer.stellar_address().await
+    }
+
+    /// Base64 signature of the snapshot hash
+    pub async fn sign_hash(&self, hash: &[u8; 32]) -> Result<String> {
+        Ok(BASE64.encode(self.signer.sign(hash).await?))
+    }
+}
+
+/// Check a base64 signature of a hex snapshot hash against a hex public key
+pub fn verify_hash_signature(public_key_hex: &str, hash_hex: &str, signature: &str) -> Result<()> {
+    let mut key_bytes = [0u8; 32];
+    hex::decode_to_slice(public_key_hex, &mut key_bytes).context("Invalid public key")?;
+    let key = VerifyingKey::from_bytes(&key_bytes).context("Invalid public key")?;
+
+    let mut hash = [0u8; 32];
+    hex::decode_to_slice(hash_hex, &mut hash).context("Invalid snapshot hash")?;
+
+    let signature_bytes = BASE64
+        .decode(signature)
+        .context("Signature is not valid base64")?;
+    let signature = Signature::from_slice(&signature_bytes).context("Invalid signature length")?;
+
+    key.verify(&hash, &signature)
+        .map_err(|_| anyhow!("Signature does not match"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::LocalSigner;
+    use ed25519_dalek::SigningKey;
+
+    #[tokio::test]
+    async fn test_signature_round_trip() {
+        let signer = SnapshotSigner::new(Arc::new(LocalSigner::new(SigningKey::from_bytes(
+            &[7u8; 32],
+        ))));
+        let hash = [42u8; 32];
+        let signature = signer.sign_hash(&hash).await.unwrap();
+        let public_key = signer.public_key_hex().await.unwrap();
+
+        assert!(signer.stellar_address().await.unwrap().starts_with('G'));
+        assert!(verify_hash_signature(&public_key, &hex::encode(hash), &signature).is_ok());
+        assert!(verify_hash_signature(&public_key, &hex::encode([0u8; 32]), &signature).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Sparse fieldsets (`?fields=`) for heavy API responses.
+//!
+//! Clients pass a comma-separated list of field names, optionally dotted to
+//! reach into nested objects (e.g. `?fields=corridor.success_rate,latency_distribution`).
+//! The response is serialized as usual and then pruned down to the requested
+//! fields. Arrays are traversed transparently, so a path applies to every element.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use utoipa::IntoParams;
+
+use crate::error::ApiError;
+
+/// Maximum number of fields a client may request at once
+const MAX_FIELDS: usize = 64;
+
+/// Maximum nesting depth of a single dotted path
+const MAX_PATH_DEPTH: usize = 4;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FieldsQuery {
+    /// Comma-separated list of fields to return (e.g. `id,name,reliability_score`)
+    #[param(example = "id,name,reliability_score")]
+    pub fields: Option<String>,
+}
+
+impl FieldsQuery {
+    /// Parse the raw query value into a selection (`None` means "all fields")
+    pub fn selection(&self) -> Result<Option<FieldSelection>, ApiError> {
+        match self.fields.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(raw) => FieldSelection::parse(raw).map(Some),
+        }
+    }
+}
+
+/// A parsed set of field paths, stored as a tree keyed by path segment.
+///
+/// A node with no children selects the whole sub-value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSelection {
+    children: BTreeMap<String, FieldSelection>,
+}
+
+impl FieldSelection {
+    /// Parse a `fields` query value such as `id,name,corridor.success_rate`
+    pub fn parse(raw: &str) -> Result<Self, ApiError> {
+        let paths: Vec<&str> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        if paths.is_empty() {
+            return Err(ApiError::bad_request(
+                "INVALID_FIELDS",
+                "fields must list at least one field name",
+            ));
+        }
+
+        if paths.len() > MAX_FIELDS {
+            return Err(ApiError::bad_request(
+                "INVALID_FIELDS",
+                format!("At most {} fields may be requested", MAX_FIELDS),
+            ));
+        }
+
+        let mut root = Self::default();
+        for path in paths {
+            let segments: Vec<&str> = path.split('.').collect();
+            if segments.len() > MAX_PATH_DEPTH {
+                return Err(ApiError::bad_request(
+                    "INVALID_FIELDS",
+                    format!("Field '{}' is nested too deeply", path),
+                ));
+            }
+            if segments.iter().any(|s| !is_valid_segment(s)) {
+                return Err(ApiError::bad_request(
+                    "INVALID_FIELDS",
+                    format!("Field '{}' is not a valid field name", path),
+                ));
+            }
+            root.insert(&segments);
+        }
+
+        Ok(root)
+    }
+
+    fn insert(&mut self, segments: &[&str]) {
+        let Some((head, rest)) = segments.split_first() else {
+            return;
+        };
+
+        // An existing leaf already selects the whole sub-value
+        if let Some(existing) = self.children.get(*head) {
+            if existing.children.is_empty() {
+                return;
+            }
+        }
+
+        let child = self.children.entry((*head).to_string()).or_default();
+        if rest.is_empty() {
+            child.children.clear();
+        } else {
+            child.insert(rest);
+        }
+    }
+
+    /// Serialize `payload` and keep only the selected fields
+    pub fn apply<T: Serialize>(&self, payload: &T) -> anyhow::Result<Value> {
+        Ok(self.project(serde_json::to_value(payload)?))
+    }
+
+    /// Prune an already-serialized value down to the selected fields
+    pub fn project(&self, value: Value) -> Value {
+        if self.children.is_empty() {
+            return value;
+        }
+
+        match value {
+            Value::Object(map) => {
+                let mut out = Map::with_capacity(self.children.len());
+                for (key, mut field) in map {
+                    if let Some(child) = self.children.get(&key) {
+                        field = child.project(field);
+                        out.insert(key, field);
+                    }
+                }
+                Value::Object(out)
+            }
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|item| self.project(item)).collect())
+            }
+            other => other,
+        }
+    }
+
+    /// Stable textual form, used to key cached variants of a response
+    pub fn cache_suffix(&self) -> String {
+        let mut paths = Vec::new();
+        self.collect_paths("", &mut paths);
+        paths.join(",")
+    }
+
+    fn collect_paths(&self, prefix: &str, out: &mut Vec<String>) {
+        for (key, child) in &self.children {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            if child.children.is_empty() {
+                out.push(path);
+            } else {
+                child.collect_paths(&path, out);
+            }
+        }
+    }
+}
+
+fn is_valid_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment.len() <= 64
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_rejects_empty_and_invalid() {
+        assert!(FieldSelection::parse(" , ").is_err());
+        assert!(FieldSelection::parse("name;drop").is_err());
+        assert!(FieldSelection::parse("a..b").is_err());
+        assert!(FieldSelection::parse("a.b.c.d.e").is_err());
+    }
+
+    #[test]
+    fn test_project_top_level_fields() {
+        let selection = FieldSelection::parse("id,name").unwrap();
+        let value = json!({"id": "1", "name": "Anchor", "status": "green"});
+
+        assert_eq!(selection.project(value), json!({"id": "1", "name": "Anchor"}));
+    }
+
+    #[test]
+    fn test_project_nested_and_arrays() {
+        let selection = FieldSelection::parse("corridor.success_rate,latency_distribution.count")
+            .unwrap();
+        let value = json!({
+            "corridor": {"id": "USDC->XLM", "success_rate": 99.0},
+            "latency_distribution": [
+                {"latency_bucket_ms": 100, "count": 3},
+                {"latency_bucket_ms": 200, "count": 5}
+            ],
+            "liquidity_trends": []
+        });
+
+        assert_eq!(
+            selection.project(value),
+            json!({
+                "corridor": {"success_rate": 99.0},
+                "latency_distribution": [{"count": 3}, {"count": 5}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_whole_field_wins_over_subpath() {
+        let a = FieldSelection::parse("corridor,corridor.id").unwrap();
+        let b = FieldSelection::parse("corridor.id,corridor").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.cache_suffix(), "corridor");
+    }
+
+    #[test]
+    fn test_query_without_fields_selects_everything() {
+        let query = FieldsQuery { fields: None };
+        assert!(query.selection().unwrap().is_none());
+    }
+}
@@ -0,0 +1,261 @@
+//! Server-Sent Events fallback for the real-time feed.
+//!
+//! Some corporate proxies block WebSocket upgrades but pass plain HTTP
+//! streams. `/sse/stream` carries the same [`WsMessage`] JSON frames as
+//! `/ws`: every client receives the broadcasts, and channel updates arrive
+//! for the channels listed in `?channels=`. SSE is one-way, so the channel
+//! list is fixed for the life of the stream; clients reconnect to change it.
+//! Connections are registered in the shared [`WsState`], so channel
+//! publishing, connection counts and usage metering treat both transports
+//! alike.
+
+use axum::{
+    extract::{Query, State},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Json,
+};
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::tenant::TenantScope;
+use crate::websocket::{WsIdentity, WsMessage, WsState};
+
+/// Interval between heartbeat comments, short enough to keep idle proxies
+/// from closing the stream
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Deserialize)]
+pub struct SseQueryParams {
+    /// Optional authentication token
+    pub token: Option<String>,
+    /// Comma-separated channels to subscribe to, e.g.
+    /// `corridor:USDC:GA...->XLM:native,movers`
+    pub channels: Option<String>,
+}
+
+/// Channels named in a comma-separated `channels` parameter
+fn parse_channels(channels: Option<&str>) -> Vec<String> {
+    channels
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|channel| !channel.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A registered SSE connection. Dropping it (when the client goes away and
+/// axum drops the stream) removes the connection from [`WsState`].
+struct SseConnection {
+    state: Arc<WsState>,
+    connection_id: Uuid,
+    tenant: TenantScope,
+    connected_at: Instant,
+    broadcast_rx: broadcast::Receiver<WsMessage>,
+    rx: mpsc::Receiver<WsMessage>,
+}
+
+impl SseConnection {
+    /// Next message for the client, or `None` once the connection has been
+    /// closed server-side
+    async fn next_message(&mut self) -> Option<WsMessage> {
+        loop {
+            tokio::select! {
+                result = self.broadcast_rx.recv() => match result {
+                    Ok(message) => return Some(message),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(
+                            "SSE connection {} lagged, skipped {} broadcast messages",
+                            self.connection_id, skipped
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                },
+                message = self.rx.recv() => return message,
+            }
+        }
+    }
+}
+
+impl Drop for SseConnection {
+    fn drop(&mut self) {
+        self.state
+            .close_connection(self.connection_id, &self.tenant, self.connected_at);
+        info!(
+            "SSE connection {} closed. Active connections: {}",
+            self.connection_id,
+            self.state.connection_count()
+        );
+    }
+}
+
+fn to_event(message: &WsMessage) -> Option<Event> {
+    match Event::default().json_data(message) {
+        Ok(event) => Some(event),
+        Err(e) => {
+            warn!("Failed to serialize SSE message: {}", e);
+            None
+        }
+    }
+}
+
+/// Register a connection subscribed to `channels` and stream its messages,
+/// starting with the same `connected` and `subscription_confirm` frames a
+/// WebSocket client receives. `channels` must already be authorized for
+/// `identity`.
+fn message_stream(
+    state: Arc<WsState>,
+    identity: WsIdentity,
+    channels: Vec<String>,
+    tenant: TenantScope,
+) -> impl Stream<Item = WsMessage> {
+    let connected_at = Instant::now();
+    let broadcast_rx = state.tx.subscribe();
+    let (connection_id, rx) = state.register_connection(identity);
+    info!(
+        "New SSE connection: {} (channels: {:?})",
+        connection_id, channels
+    );
+
+    let mut initial = vec![WsMessage::Connected {
+        connection_id: connection_id.to_string(),
+    }];
+    if !channels.is_empty() {
+        state.subscribe_connection(connection_id, channels.clone());
+        initial.push(WsMessage::SubscriptionConfirm {
+            channels,
+            status: "subscribed".to_string(),
+        });
+    }
+
+    let connection = SseConnection {
+        state,
+        connection_id,
+        tenant,
+        connected_at,
+        broadcast_rx,
+        rx,
+    };
+    let live = stream::unfold(connection, |mut connection| async move {
+        let message = connection.next_message().await?;
+        Some((message, connection))
+    });
+
+    stream::iter(initial).chain(live)
+}
+
+/// SSE handler endpoint
+pub async fn sse_handler(
+    Query(params): Query<SseQueryParams>,
+    State(state): State<Arc<WsState>>,
+    tenant: TenantScope,
+) -> Response {
+    let Some(identity) = state.authenticate(params.token.as_deref()) else {
+        return (
+            axum::http::StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Unauthorized"})),
+        )
+            .into_response();
+    };
+
+    // The channel list is fixed for the stream, so refuse it up front
+    // rather than streaming a subset
+    let (channels, denied) = state
+        .authorize(&identity, parse_channels(params.channels.as_deref()))
+        .await;
+    if !denied.is_empty() {
+        return (
+            axum::http::StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": "Not authorized to subscribe to these channels",
+                "channels": denied,
+            })),
+        )
+            .into_response();
+    }
+
+    let events = message_stream(state, identity, channels, tenant)
+        .filter_map(|message| async move { to_event(&message).map(Ok::<_, Infallible>) });
+
+    Sse::new(events)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(HEARTBEAT_INTERVAL)
+                .text("heartbeat"),
+        )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_channels() {
+        assert!(parse_channels(None).is_empty());
+        assert!(parse_channels(Some(" , ")).is_empty());
+        assert_eq!(
+            parse_channels(Some("movers, corridor:USDC:GA->XLM:native,")),
+            vec!["movers", "corridor:USDC:GA->XLM:native"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_receives_broadcasts_and_subscribed_channels() {
+        let state = Arc::new(WsState::new());
+        let mut messages = Box::pin(message_stream(
+            Arc::clone(&state),
+            WsIdentity::Anonymous,
+            vec!["corridor:native->USDC:GA".to_string()],
+            TenantScope::All,
+        ));
+
+        assert!(matches!(
+            messages.next().await,
+            Some(WsMessage::Connected { .. })
+        ));
+        assert!(matches!(
+            messages.next().await,
+            Some(WsMessage::SubscriptionConfirm { .. })
+        ));
+        assert_eq!(state.connection_count(), 1);
+
+        // Subscribed through the same normalization as WebSocket clients
+        state
+            .broadcast_to_channel(
+                "corridor:USDC:GA->XLM:native",
+                WsMessage::Pong { timestamp: 1 },
+            )
+            .await;
+        state
+            .broadcast_to_channel("movers", WsMessage::Pong { timestamp: 2 })
+            .await;
+        state.broadcast(WsMessage::Pong { timestamp: 3 });
+
+        let mut timestamps = Vec::new();
+        for _ in 0..2 {
+            match messages.next().await {
+                Some(WsMessage::Pong { timestamp }) => timestamps.push(timestamp),
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+        timestamps.sort_unstable();
+        assert_eq!(timestamps, vec![1, 3]);
+
+        drop(messages);
+        assert_eq!(state.connection_count(), 0);
+        assert_eq!(
+            state.channel_subscription_count("corridor:USDC:GA->XLM:native"),
+            0
+        );
+    }
+}
@@ -1,5 +1,17 @@
+use axum::extract::FromRef;
+use sqlx::SqlitePool;
+
+use crate::cache::CacheManager;
+use crate::config::Settings;
 use crate::database::Database;
 use crate::ingestion::DataIngestionService;
+use crate::rate_limit::RateLimiter;
+use crate::rpc::StellarRpcClient;
+use crate::screening::AccountScreener;
+use crate::services::account_merge_detector::AccountMergeDetector;
+use crate::services::fee_bump_tracker::FeeBumpTrackerService;
+use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
+use crate::services::price_feed::PriceFeedClient;
 use crate::websocket::WsState;
 use std::sync::Arc;
 
@@ -9,6 +21,7 @@ pub struct AppState {
     pub db: Arc<Database>,
     pub ws_state: Arc<WsState>,
     pub ingestion: Arc<DataIngestionService>,
+    pub settings: Arc<Settings>,
 }
 
 impl AppState {
@@ -16,11 +29,123 @@ impl AppState {
         db: Arc<Database>,
         ws_state: Arc<WsState>,
         ingestion: Arc<DataIngestionService>,
+        settings: Arc<Settings>,
     ) -> Self {
         Self {
             db,
             ws_state,
             ingestion,
+            settings,
         }
     }
 }
+
+/// State tuple extracted by the cached anchor and corridor handlers
+pub type CachedState = (
+    Arc<Database>,
+    Arc<CacheManager>,
+    Arc<StellarRpcClient>,
+    Arc<PriceFeedClient>,
+);
+
+/// Every service the versioned API depends on.
+///
+/// Routers take one `ServicesState` and handlers extract just the part they
+/// need (`State<Arc<CacheManager>>`, `State<AppState>`, ...) through
+/// [`FromRef`]. A new service is a new field plus a `FromRef` impl; existing
+/// call sites don't change.
+#[derive(Clone)]
+pub struct ServicesState {
+    pub app: AppState,
+    pub cache: Arc<CacheManager>,
+    pub rpc_client: Arc<StellarRpcClient>,
+    pub price_feed: Arc<PriceFeedClient>,
+    pub fee_bump_tracker: Arc<FeeBumpTrackerService>,
+    pub account_merge_detector: Arc<AccountMergeDetector>,
+    pub lp_analyzer: Arc<LiquidityPoolAnalyzer>,
+    pub account_screener: Arc<AccountScreener>,
+    pub rate_limiter: Arc<RateLimiter>,
+}
+
+impl FromRef<ServicesState> for AppState {
+    fn from_ref(state: &ServicesState) -> Self {
+        state.app.clone()
+    }
+}
+
+impl FromRef<ServicesState> for Arc<Database> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.app.db)
+    }
+}
+
+impl FromRef<ServicesState> for SqlitePool {
+    fn from_ref(state: &ServicesState) -> Self {
+        state.app.db.pool().clone()
+    }
+}
+
+impl FromRef<ServicesState> for Arc<Settings> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.app.settings)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<CacheManager> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.cache)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<StellarRpcClient> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.rpc_client)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<PriceFeedClient> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.price_feed)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<FeeBumpTrackerService> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.fee_bump_tracker)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<AccountMergeDetector> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.account_merge_detector)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<LiquidityPoolAnalyzer> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.lp_analyzer)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<AccountScreener> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.account_screener)
+    }
+}
+
+impl FromRef<ServicesState> for Arc<RateLimiter> {
+    fn from_ref(state: &ServicesState) -> Self {
+        Arc::clone(&state.rate_limiter)
+    }
+}
+
+impl FromRef<ServicesState> for CachedState {
+    fn from_ref(state: &ServicesState) -> Self {
+        (
+            Arc::clone(&state.app.db),
+            Arc::clone(&state.cache),
+            Arc::clone(&state.rpc_client),
+            Arc::clone(&state.price_feed),
+        )
+    }
+}
@@ -2,6 +2,8 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use crate::http_client;
+
 /// Telegram Bot API client using reqwest directly.
 pub struct TelegramClient {
     client: Client,
@@ -74,10 +76,7 @@ struct SetMyCommandsRequest<'a> {
 
 impl TelegramClient {
     pub fn new(token: &str) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(35))
-            .build()
-            .expect("Failed to build Telegram HTTP client");
+        let client = http_client::shared().client(Duration::from_secs(35));
 
         Self {
             client,
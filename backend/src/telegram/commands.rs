@@ -2,9 +2,11 @@ use std::sync::Arc;
 
 use crate::cache::CacheManager;
 use crate::database::Database;
+use crate::models::corridor::CorridorKey;
 use crate::rpc::StellarRpcClient;
 use crate::telegram::formatter;
 use crate::telegram::subscription::SubscriptionService;
+use crate::tenant::TenantScope;
 
 pub struct CommandHandler {
     db: Arc<Database>,
@@ -54,7 +56,11 @@ impl CommandHandler {
     }
 
     async fn handle_status(&self) -> String {
-        let anchors = self.db.list_anchors(1000, 0).await.unwrap_or_default();
+        let anchors = self
+            .db
+            .list_anchors(1000, 0, &TenantScope::All)
+            .await
+            .unwrap_or_default();
         let anchor_count = anchors.len();
 
         let corridor_count = match self.rpc_client.fetch_payments(200, None).await {
@@ -121,6 +127,10 @@ impl CommandHandler {
         if key.is_empty() {
             return formatter::escape_markdown("Usage: /corridor <corridor_key>\nExample: /corridor USDC:GA5Z->XLM:native");
         }
+        let key = match key.parse::<CorridorKey>() {
+            Ok(key) => key,
+            Err(e) => return formatter::escape_markdown(&e.to_string()),
+        };
 
         let payments = match self.rpc_client.fetch_payments(200, None).await {
             Ok(p) => p,
@@ -141,7 +151,7 @@ impl CommandHandler {
                 payment.asset_code.as_deref().unwrap_or("XLM"),
                 payment.asset_issuer.as_deref().unwrap_or("native")
             );
-            if corridor_key == key {
+            if corridor_key.parse::<CorridorKey>().is_ok_and(|k| k == key) {
                 count += 1;
                 volume += payment.amount.parse::<f64>().unwrap_or(0.0);
             }
@@ -151,18 +161,24 @@ impl CommandHandler {
             return formatter::escape_markdown(&format!("Corridor '{}' not found.", key));
         }
 
-        let parts: Vec<&str> = key.split("->").collect();
-        let (src, dst) = if parts.len() == 2 {
-            (parts[0], parts[1])
-        } else {
-            (key, "unknown")
-        };
+        let corridor = key.corridor();
+        let src = format!("{}:{}", corridor.asset_a_code, corridor.asset_a_issuer);
+        let dst = format!("{}:{}", corridor.asset_b_code, corridor.asset_b_issuer);
 
-        formatter::format_corridor_detail(key, src, dst, 100.0, count, 400.0, volume, 95.0)
+        formatter::format_corridor_detail(
+            key.as_str(),
+            &src,
+            &dst,
+            100.0,
+            count,
+            400.0,
+            volume,
+            95.0,
+        )
     }
 
     async fn handle_anchors(&self) -> String {
-        let anchors = match self.db.list_anchors(50, 0).await {
+        let anchors = match self.db.list_anchors(50, 0, &TenantScope::All).await {
             Ok(a) => a,
             Err(e) => {
                 return formatter::escape_markdown(&format!(
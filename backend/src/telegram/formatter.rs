@@ -18,12 +18,16 @@ pub fn format_alert(alert: &Alert) -> String {
         AlertType::SuccessRateDrop => "\u{1F534}",    // red circle
         AlertType::LatencyIncrease => "\u{1F7E1}",    // yellow circle
         AlertType::LiquidityDecrease => "\u{1F7E0}",  // orange circle
+        AlertType::SupplyMint => "\u{1F535}",         // blue circle
+        AlertType::SupplyBurn => "\u{1F7E3}",         // purple circle
     };
 
     let type_label = match alert.alert_type {
         AlertType::SuccessRateDrop => "Success Rate Drop",
         AlertType::LatencyIncrease => "Latency Increase",
         AlertType::LiquidityDecrease => "Liquidity Decrease",
+        AlertType::SupplyMint => "Large Mint",
+        AlertType::SupplyBurn => "Large Burn",
     };
 
     let corridor = escape_markdown(&alert.corridor_id);
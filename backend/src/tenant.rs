@@ -0,0 +1,158 @@
+//! Optional multi-tenant mode.
+//!
+//! With `deployment.multi_tenant` (`MULTI_TENANT_MODE=true`), requests to
+//! tenant-scoped routes must carry an `X-API-Key` issued to a tenant.
+//! [`tenant_middleware`] resolves the key to a [`TenantScope`], and the data
+//! layer filters anchors, corridors and webhooks to that tenant's rows. In the default single-tenant mode every
+//! request is [`TenantScope::All`] and sees the whole directory.
+//!
+//! Every tenant-scoped request is metered as one API call for billing.
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::request::Parts,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+use crate::billing::{UsageKind, UsageRecorder};
+use crate::database::Database;
+use crate::error::ApiError;
+
+/// Header carrying the API key that identifies the tenant
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Rows a request may see
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TenantScope {
+    /// Single-tenant mode, or an internal caller such as a background job
+    All,
+    /// Only the rows of this tenant
+    Tenant(String),
+}
+
+impl TenantScope {
+    /// Tenant ID to bind into queries; `None` disables the filter
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            TenantScope::All => None,
+            TenantScope::Tenant(id) => Some(id),
+        }
+    }
+
+    /// Suffix a cache key so tenants never share cached responses. The
+    /// suffix keeps prefix-based invalidation (`anchor:*`) working.
+    pub fn cache_key(&self, key: String) -> String {
+        match self {
+            TenantScope::All => key,
+            TenantScope::Tenant(id) => format!("{}:tenant:{}", key, id),
+        }
+    }
+}
+
+/// State for [`tenant_middleware`]
+#[derive(Clone)]
+pub struct TenantResolver {
+    pub db: Arc<Database>,
+    pub multi_tenant: bool,
+}
+
+/// Resolve the request's [`TenantScope`] and store it in the request
+/// extensions. In multi-tenant mode a missing, invalid or tenantless API key
+/// is rejected.
+pub async fn tenant_middleware(
+    State(resolver): State<TenantResolver>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let scope = if resolver.multi_tenant {
+        let key = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                ApiError::unauthorized("API_KEY_REQUIRED", "An X-API-Key header is required")
+            })?;
+        let api_key = resolver.db.validate_api_key(&key).await?.ok_or_else(|| {
+            ApiError::unauthorized("INVALID_API_KEY", "Invalid or revoked API key")
+        })?;
+        let tenant_id = api_key.tenant_id.ok_or_else(|| {
+            ApiError::forbidden("TENANT_REQUIRED", "The API key is not assigned to a tenant")
+        })?;
+        UsageRecorder::new(resolver.db.pool().clone()).record(&tenant_id, UsageKind::ApiCall, 1);
+        TenantScope::Tenant(tenant_id)
+    } else {
+        TenantScope::All
+    };
+
+    req.extensions_mut().insert(scope);
+    Ok(next.run(req).await)
+}
+
+/// Fails closed: a handler extracting [`TenantScope`] on a route mounted
+/// without [`tenant_middleware`] is a wiring bug, and answering it with every
+/// tenant's rows would leak them, so the request is rejected instead
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for TenantScope
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<TenantScope>()
+            .cloned()
+            .ok_or_else(|| {
+                tracing::error!(
+                    path = %parts.uri.path(),
+                    "Tenant scope missing; route lacks tenant_middleware"
+                );
+                ApiError::internal(
+                    "TENANT_SCOPE_MISSING",
+                    "Request tenant could not be resolved",
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_per_tenant() {
+        assert_eq!(
+            TenantScope::All.cache_key("anchor:list:50:0".into()),
+            "anchor:list:50:0"
+        );
+        assert_eq!(
+            TenantScope::Tenant("acme".into()).cache_key("anchor:list:50:0".into()),
+            "anchor:list:50:0:tenant:acme"
+        );
+        assert_eq!(TenantScope::Tenant("acme".into()).id(), Some("acme"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_scope_is_rejected() {
+        let (mut parts, _) = axum::http::Request::new(()).into_parts();
+        let rejection = TenantScope::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            axum::response::IntoResponse::into_response(rejection).status(),
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        parts.extensions.insert(TenantScope::Tenant("acme".into()));
+        assert_eq!(
+            TenantScope::from_request_parts(&mut parts, &())
+                .await
+                .unwrap(),
+            TenantScope::Tenant("acme".into())
+        );
+    }
+}
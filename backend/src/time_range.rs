@@ -0,0 +1,342 @@
+//! Shared time-range and granularity query parsing.
+//!
+//! Endpoints that return history take `TimeRange` as an extractor instead of
+//! parsing dates themselves. Supported query parameters:
+//!
+//! - `period=24h|7d|30d|90d` — a window ending now
+//! - `from=<RFC 3339>&to=<RFC 3339>` — an explicit window (`to` defaults to now)
+//! - `granularity=hour|day|week` — bucket size for time series (derived from
+//!   the span when omitted)
+//!
+//! `period` and `from`/`to` are mutually exclusive. Spans longer than
+//! [`MAX_SPAN_DAYS`] or producing more than [`MAX_BUCKETS`] buckets are rejected.
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::error::ApiError;
+
+/// Longest window any endpoint will serve
+pub const MAX_SPAN_DAYS: i64 = 366;
+
+/// Upper bound on the number of buckets a single time series may contain
+pub const MAX_BUCKETS: i64 = 2_000;
+
+/// Window applied when the client supplies neither `period` nor `from`
+pub const DEFAULT_PERIOD: Period = Period::Days30;
+
+/// Raw query parameters, before validation
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TimeRangeQuery {
+    /// Relative window ending now (24h, 7d, 30d, 90d)
+    #[param(example = "7d")]
+    pub period: Option<String>,
+    /// Start of the window (RFC 3339)
+    #[param(example = "2024-01-01T00:00:00Z")]
+    pub from: Option<String>,
+    /// End of the window (RFC 3339, defaults to now)
+    #[param(example = "2024-01-08T00:00:00Z")]
+    pub to: Option<String>,
+    /// Bucket size for time series (hour, day, week)
+    #[param(example = "day")]
+    pub granularity: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum Period {
+    #[serde(rename = "24h")]
+    Hours24,
+    #[serde(rename = "7d")]
+    Days7,
+    #[serde(rename = "30d")]
+    Days30,
+    #[serde(rename = "90d")]
+    Days90,
+}
+
+impl Period {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "24h" | "1d" => Some(Self::Hours24),
+            "7d" => Some(Self::Days7),
+            "30d" => Some(Self::Days30),
+            "90d" => Some(Self::Days90),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hours24 => "24h",
+            Self::Days7 => "7d",
+            Self::Days30 => "30d",
+            Self::Days90 => "90d",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Hours24 => Duration::hours(24),
+            Self::Days7 => Duration::days(7),
+            Self::Days30 => Duration::days(30),
+            Self::Days90 => Duration::days(90),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Hour,
+    Day,
+    Week,
+}
+
+impl Granularity {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "hour" | "1h" | "hourly" => Some(Self::Hour),
+            "day" | "1d" | "daily" => Some(Self::Day),
+            "week" | "7d" | "weekly" => Some(Self::Week),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Hour => Duration::hours(1),
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::weeks(1),
+        }
+    }
+
+    /// SQLite `strftime` format that truncates a timestamp to this bucket
+    pub fn sqlite_bucket_format(&self) -> &'static str {
+        match self {
+            Self::Hour => "%Y-%m-%dT%H:00:00Z",
+            Self::Day => "%Y-%m-%dT00:00:00Z",
+            Self::Week => "%Y-W%W",
+        }
+    }
+
+    /// Sensible default bucket size for a window of the given length
+    pub fn for_span(span: Duration) -> Self {
+        if span <= Duration::days(2) {
+            Self::Hour
+        } else if span <= Duration::days(90) {
+            Self::Day
+        } else {
+            Self::Week
+        }
+    }
+}
+
+/// A validated, absolute time window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TimeRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub granularity: Granularity,
+}
+
+impl TimeRange {
+    /// Validate raw query parameters relative to `now`
+    pub fn from_query(query: &TimeRangeQuery, now: DateTime<Utc>) -> Result<Self, ApiError> {
+        if query.period.is_some() && (query.from.is_some() || query.to.is_some()) {
+            return Err(ApiError::bad_request(
+                "INVALID_TIME_RANGE",
+                "Use either period or from/to, not both",
+            ));
+        }
+
+        let (from, to) = match (&query.period, &query.from) {
+            (Some(period), _) => {
+                let period = Period::parse(period).ok_or_else(|| {
+                    ApiError::bad_request(
+                        "INVALID_TIME_RANGE",
+                        format!("Unsupported period '{}': expected 24h, 7d, 30d or 90d", period),
+                    )
+                })?;
+                (now - period.duration(), now)
+            }
+            (None, Some(from)) => {
+                let from = parse_timestamp("from", from)?;
+                let to = match &query.to {
+                    Some(to) => parse_timestamp("to", to)?,
+                    None => now,
+                };
+                (from, to)
+            }
+            (None, None) if query.to.is_some() => {
+                return Err(ApiError::bad_request(
+                    "INVALID_TIME_RANGE",
+                    "to requires from",
+                ));
+            }
+            (None, None) => (now - DEFAULT_PERIOD.duration(), now),
+        };
+
+        if from >= to {
+            return Err(ApiError::bad_request(
+                "INVALID_TIME_RANGE",
+                "from must be earlier than to",
+            ));
+        }
+
+        let span = to - from;
+        if span > Duration::days(MAX_SPAN_DAYS) {
+            return Err(ApiError::bad_request(
+                "INVALID_TIME_RANGE",
+                format!("Time range may not exceed {} days", MAX_SPAN_DAYS),
+            ));
+        }
+
+        let granularity = match &query.granularity {
+            Some(raw) => Granularity::parse(raw).ok_or_else(|| {
+                ApiError::bad_request(
+                    "INVALID_GRANULARITY",
+                    format!("Unsupported granularity '{}': expected hour, day or week", raw),
+                )
+            })?,
+            None => Granularity::for_span(span),
+        };
+
+        let buckets = span.num_seconds() / granularity.duration().num_seconds();
+        if buckets > MAX_BUCKETS {
+            return Err(ApiError::bad_request(
+                "INVALID_GRANULARITY",
+                format!(
+                    "Granularity '{}' yields {} buckets for this range (max {})",
+                    granularity.as_str(),
+                    buckets,
+                    MAX_BUCKETS
+                ),
+            ));
+        }
+
+        Ok(Self {
+            from,
+            to,
+            granularity,
+        })
+    }
+
+    pub fn span(&self) -> Duration {
+        self.to - self.from
+    }
+
+    pub fn contains(&self, ts: DateTime<Utc>) -> bool {
+        ts >= self.from && ts <= self.to
+    }
+}
+
+fn parse_timestamp(name: &str, value: &str) -> Result<DateTime<Utc>, ApiError> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| {
+            ApiError::bad_request(
+                "INVALID_TIME_RANGE",
+                format!("{} must be an RFC 3339 timestamp", name),
+            )
+        })
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for TimeRange
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<TimeRangeQuery>::try_from_uri(&parts.uri)
+            .map_err(|e| ApiError::bad_request("INVALID_TIME_RANGE", e.body_text()))?;
+        Self::from_query(&query, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn query(period: Option<&str>, from: Option<&str>, to: Option<&str>) -> TimeRangeQuery {
+        TimeRangeQuery {
+            period: period.map(String::from),
+            from: from.map(String::from),
+            to: to.map(String::from),
+            granularity: None,
+        }
+    }
+
+    #[test]
+    fn test_default_period() {
+        let range = TimeRange::from_query(&TimeRangeQuery::default(), now()).unwrap();
+        assert_eq!(range.span(), Duration::days(30));
+        assert_eq!(range.granularity, Granularity::Day);
+    }
+
+    #[test]
+    fn test_period_sets_window_and_granularity() {
+        let range = TimeRange::from_query(&query(Some("24h"), None, None), now()).unwrap();
+        assert_eq!(range.to, now());
+        assert_eq!(range.span(), Duration::hours(24));
+        assert_eq!(range.granularity, Granularity::Hour);
+    }
+
+    #[test]
+    fn test_explicit_window() {
+        let q = query(None, Some("2024-05-01T00:00:00Z"), Some("2024-05-08T00:00:00Z"));
+        let range = TimeRange::from_query(&q, now()).unwrap();
+        assert_eq!(range.span(), Duration::days(7));
+    }
+
+    #[test]
+    fn test_rejects_invalid_ranges() {
+        assert!(TimeRange::from_query(&query(Some("1y"), None, None), now()).is_err());
+        assert!(TimeRange::from_query(
+            &query(Some("7d"), Some("2024-05-01T00:00:00Z"), None),
+            now()
+        )
+        .is_err());
+        assert!(TimeRange::from_query(
+            &query(None, Some("2024-05-08T00:00:00Z"), Some("2024-05-01T00:00:00Z")),
+            now()
+        )
+        .is_err());
+        assert!(TimeRange::from_query(&query(None, Some("2022-01-01T00:00:00Z"), None), now())
+            .is_err());
+        assert!(TimeRange::from_query(&query(None, None, Some("2024-05-01T00:00:00Z")), now())
+            .is_err());
+    }
+
+    #[test]
+    fn test_rejects_too_many_buckets() {
+        let mut q = query(Some("90d"), None, None);
+        q.granularity = Some("hour".to_string());
+        assert!(TimeRange::from_query(&q, now()).is_err());
+
+        q.granularity = Some("day".to_string());
+        assert!(TimeRange::from_query(&q, now()).is_ok());
+    }
+}
@@ -0,0 +1,184 @@
+//! Per-route request time budgets.
+//!
+//! [`timeout_middleware`] classifies each request by path and method, and
+//! answers `504 Gateway Timeout` with a structured [`ApiError`] once the
+//! route's budget is spent. On timeout the handler future is dropped, which
+//! cancels everything it is awaiting: in-flight `reqwest` calls are aborted
+//! and `sqlx` queries release their pool connection. Work a handler hands to
+//! `tokio::spawn` is not cancelled and must not be used for request-scoped
+//! calls.
+
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::TimeoutSettings;
+use crate::error::ApiError;
+use crate::observability::metrics as obs_metrics;
+
+/// Served from the cache (anchor/corridor lists, metrics overview)
+const CACHED_READ_ROUTES: &[&str] = &["/api/anchors", "/api/corridors", "/api/metrics/overview"];
+
+/// Prefixes of endpoints that call Horizon, Soroban RPC or anchor servers
+const UPSTREAM_PREFIXES: &[&str] = &[
+    "/api/rpc/",
+    "/api/sep24/",
+    "/api/sep31/",
+    "/api/network",
+    "/api/liquidity-pools",
+    "/api/trustlines",
+    "/api/prices",
+    "/api/cost-calculator",
+    "/api/tools/",
+    "/api/fee-bumps",
+    "/api/account-merges",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    CachedRead,
+    Upstream,
+    Default,
+}
+
+impl RouteClass {
+    pub fn classify(method: &Method, path: &str) -> Self {
+        // Versioned routes share budgets with their unversioned equivalents
+        let path = match path.strip_prefix("/api/v1/") {
+            Some(rest) => format!("/api/{}", rest),
+            None => path.to_string(),
+        };
+        let path = path.trim_end_matches('/');
+
+        if UPSTREAM_PREFIXES.iter().any(|p| path.starts_with(p)) {
+            return Self::Upstream;
+        }
+        let cached = CACHED_READ_ROUTES.contains(&path)
+            || (path.starts_with("/api/corridors/") && path.matches('/').count() == 3);
+        if *method == Method::GET && cached {
+            return Self::CachedRead;
+        }
+        Self::Default
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CachedRead => "cached_read",
+            Self::Upstream => "upstream",
+            Self::Default => "default",
+        }
+    }
+}
+
+/// Time budgets for each [`RouteClass`]
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutBudgets {
+    pub cached_read: Duration,
+    pub upstream: Duration,
+    pub default: Duration,
+}
+
+impl TimeoutBudgets {
+    pub fn for_class(&self, class: RouteClass) -> Duration {
+        match class {
+            RouteClass::CachedRead => self.cached_read,
+            RouteClass::Upstream => self.upstream,
+            RouteClass::Default => self.default,
+        }
+    }
+}
+
+impl From<&TimeoutSettings> for TimeoutBudgets {
+    fn from(settings: &TimeoutSettings) -> Self {
+        Self {
+            cached_read: Duration::from_secs(settings.cached_read_secs),
+            upstream: Duration::from_secs(settings.upstream_secs),
+            default: Duration::from_secs(settings.default_secs),
+        }
+    }
+}
+
+pub async fn timeout_middleware(
+    State(budgets): State<TimeoutBudgets>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let class = RouteClass::classify(req.method(), req.uri().path());
+    let budget = budgets.for_class(class);
+
+    match tokio::time::timeout(budget, next.run(req)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(
+                route_class = class.as_str(),
+                budget_ms = budget.as_millis() as u64,
+                "Request exceeded its time budget"
+            );
+            obs_metrics::record_error("request_timeout");
+
+            let details = HashMap::from([
+                ("route_class".to_string(), serde_json::json!(class.as_str())),
+                ("budget_ms".to_string(), serde_json::json!(budget.as_millis() as u64)),
+            ]);
+            ApiError::gateway_timeout(
+                "REQUEST_TIMEOUT",
+                "The request did not complete in time",
+            )
+            .with_details(details)
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_classify() {
+        let get = Method::GET;
+        assert_eq!(RouteClass::classify(&get, "/api/anchors"), RouteClass::CachedRead);
+        assert_eq!(RouteClass::classify(&get, "/api/corridors/USDC-XLM"), RouteClass::CachedRead);
+        assert_eq!(RouteClass::classify(&get, "/api/v1/corridors"), RouteClass::CachedRead);
+        assert_eq!(RouteClass::classify(&Method::POST, "/api/corridors"), RouteClass::Default);
+        assert_eq!(RouteClass::classify(&get, "/api/anchors/123"), RouteClass::Default);
+        assert_eq!(RouteClass::classify(&get, "/api/rpc/payments"), RouteClass::Upstream);
+        assert_eq!(RouteClass::classify(&Method::POST, "/api/sep24/deposit/interactive"), RouteClass::Upstream);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_request_gets_504() {
+        let budgets = TimeoutBudgets {
+            cached_read: Duration::from_secs(2),
+            upstream: Duration::from_secs(15),
+            default: Duration::from_secs(10),
+        };
+        let app = Router::new()
+            .route(
+                "/api/anchors",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "late"
+                }),
+            )
+            .layer(middleware::from_fn_with_state(budgets, timeout_middleware));
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/anchors").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "REQUEST_TIMEOUT");
+        assert_eq!(json["error"]["details"]["budget_ms"], 2000);
+    }
+}
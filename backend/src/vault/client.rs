@@ -1,3 +1,4 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -230,6 +231,99 @@ impl VaultClient {
             Err(VaultError::LeaseRevokeFailed(lease_id.to_string()))
         }
     }
+
+    /// Sign `input` with a transit key. Returns the raw signature bytes.
+    pub async fn transit_sign(
+        &self,
+        mount: &str,
+        key_name: &str,
+        input: &[u8],
+    ) -> Result<Vec<u8>, VaultError> {
+        let url = format!("{}/v1/{}/sign/{}", self.config.vault_addr, mount, key_name);
+
+        let body = serde_json::json!({ "input": BASE64.encode(input) });
+
+        let resp = self
+            .http_client
+            .post(&url)
+            .header("X-Vault-Token", &self.config.vault_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| VaultError::RequestError(e.to_string()))?;
+
+        if !resp.status().is_success() {
+            return Err(VaultError::RequestError(format!(
+                "Transit sign with key {} failed: {}",
+                key_name,
+                resp.status()
+            )));
+        }
+
+        let secret: VaultSecretResponse = resp
+            .json()
+            .await
+            .map_err(|e| VaultError::ParseError(e.to_string()))?;
+
+        // Signatures come back as "vault:v<key version>:<base64>"
+        let signature = secret
+            .data
+            .get("signature")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.rsplit(':').next())
+            .ok_or_else(|| VaultError::FieldNotFound("signature".to_string()))?;
+
+        BASE64
+            .decode(signature)
+            .map_err(|e| VaultError::ParseError(e.to_string()))
+    }
+
+    /// Public key of the latest version of a transit key
+    pub async fn transit_public_key(
+        &self,
+        mount: &str,
+        key_name: &str,
+    ) -> Result<Vec<u8>, VaultError> {
+        let url = format!("{}/v1/{}/keys/{}", self.config.vault_addr, mount, key_name);
+
+        let resp = self
+            .http_client
+            .get(&url)
+            .header("X-Vault-Token", &self.config.vault_token)
+            .send()
+            .await
+            .map_err(|e| VaultError::RequestError(e.to_string()))?;
+
+        if !resp.status().is_success() {
+            return Err(VaultError::SecretNotFound(format!(
+                "{}/keys/{}",
+                mount, key_name
+            )));
+        }
+
+        let secret: VaultSecretResponse = resp
+            .json()
+            .await
+            .map_err(|e| VaultError::ParseError(e.to_string()))?;
+
+        let latest = secret
+            .data
+            .get("latest_version")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| VaultError::FieldNotFound("latest_version".to_string()))?;
+
+        let public_key = secret
+            .data
+            .get("keys")
+            .and_then(|keys| keys.get(latest.to_string()))
+            .and_then(|key| key.get("public_key"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| VaultError::FieldNotFound("public_key".to_string()))?;
+
+        BASE64
+            .decode(public_key)
+            .map_err(|e| VaultError::ParseError(e.to_string()))
+    }
 }
 
 #[cfg(test)]
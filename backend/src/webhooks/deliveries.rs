@@ -0,0 +1,260 @@
+//! Delivery history and dead letters of webhook events.
+//!
+//! The dispatcher retries a failed delivery with exponential backoff, holding
+//! the event back until its `next_attempt_at`. Once [`MAX_DELIVERY_RETRIES`]
+//! are used up the event fails and a copy, payload included, moves to
+//! `webhook_dead_letters`, where it stays until the webhook's owner re-drives
+//! it.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{PendingWebhookEvent, WebhookService};
+
+/// Retries after the first failed attempt before an event is dead-lettered
+pub const MAX_DELIVERY_RETRIES: i64 = 5;
+
+/// Delay before the first retry, doubling for each one after
+const BASE_RETRY_DELAY_SECS: i64 = 30;
+
+/// Longest delay between two attempts
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// Most deliveries returned by one history request
+pub const MAX_DELIVERIES_PER_PAGE: i64 = 200;
+
+/// Delay before the next attempt of an event that failed after `retries`
+/// earlier retries
+pub fn retry_delay(retries: i64) -> Duration {
+    let secs = BASE_RETRY_DELAY_SECS.saturating_mul(1 << retries.clamp(0, 20));
+    Duration::seconds(secs.min(MAX_RETRY_DELAY_SECS))
+}
+
+/// One event's delivery to a webhook
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    pub event_id: String,
+    #[schema(example = "corridor.health_degraded")]
+    pub event_type: String,
+    /// `pending`, `delivered` or `failed`
+    #[schema(example = "failed")]
+    pub status: String,
+    /// Failed attempts retried so far
+    pub retries: i64,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    /// When a pending event is next attempted; absent when due now
+    pub next_attempt_at: Option<String>,
+    pub delivered_at: Option<String>,
+    /// When the event exhausted its retries; present while it can be re-driven
+    pub dead_lettered_at: Option<String>,
+}
+
+/// A webhook's deliveries, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveriesResponse {
+    pub webhook_id: String,
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+/// Dead-lettered events to deliver again; all of the webhook's when
+/// `event_ids` is absent
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RedriveRequest {
+    pub event_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RedriveResponse {
+    /// Events queued for delivery again
+    pub redriven: u64,
+}
+
+impl WebhookService {
+    /// Deliveries to `webhook_id`, newest first, optionally only those with
+    /// `status`. Dead letters whose event has been archived are included as
+    /// failed deliveries.
+    pub async fn list_deliveries(
+        &self,
+        webhook_id: &str,
+        status: Option<&str>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT e.id AS event_id, e.event_type, e.status, e.retries, e.last_error,
+                   e.created_at, e.next_attempt_at, e.delivered_at,
+                   d.failed_at AS dead_lettered_at
+            FROM webhook_events e
+            LEFT JOIN webhook_dead_letters d ON d.event_id = e.id
+            WHERE e.webhook_id = ?1 AND (?2 IS NULL OR e.status = ?2)
+            UNION ALL
+            SELECT d.event_id, d.event_type, 'failed', d.retries, d.last_error,
+                   d.created_at, NULL, NULL, d.failed_at
+            FROM webhook_dead_letters d
+            WHERE d.webhook_id = ?1 AND (?2 IS NULL OR ?2 = 'failed')
+              AND NOT EXISTS (SELECT 1 FROM webhook_events e WHERE e.id = d.event_id)
+            ORDER BY created_at DESC
+            LIMIT ?3
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(status)
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Mark an event delivered
+    pub async fn mark_delivered(&self, event_id: &str, retries: i64) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE webhook_events
+             SET status = 'delivered', last_error = NULL, retries = ?, next_attempt_at = NULL,
+                 delivered_at = ?
+             WHERE id = ?",
+        )
+        .bind(retries)
+        .bind(Utc::now().to_rfc3339())
+        .bind(event_id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Keep a failed event pending until `next_attempt_at`
+    pub async fn schedule_retry(
+        &self,
+        event_id: &str,
+        error: &str,
+        retries: i64,
+        next_attempt_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE webhook_events
+             SET status = 'pending', last_error = ?, retries = ?, next_attempt_at = ?
+             WHERE id = ?",
+        )
+        .bind(error)
+        .bind(retries)
+        .bind(next_attempt_at.to_rfc3339())
+        .bind(event_id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fail an event that has exhausted its retries and copy it to the dead
+    /// letters
+    pub async fn dead_letter(
+        &self,
+        event: &PendingWebhookEvent,
+        error: &str,
+        retries: i64,
+    ) -> anyhow::Result<()> {
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query(
+            "UPDATE webhook_events
+             SET status = 'failed', last_error = ?, retries = ?, next_attempt_at = NULL
+             WHERE id = ?",
+        )
+        .bind(error)
+        .bind(retries)
+        .bind(&event.id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_dead_letters (
+                event_id, webhook_id, event_type, payload, retries, last_error, created_at,
+                failed_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(event_id) DO UPDATE SET
+                retries = excluded.retries,
+                last_error = excluded.last_error,
+                failed_at = excluded.failed_at
+            "#,
+        )
+        .bind(&event.id)
+        .bind(&event.webhook_id)
+        .bind(&event.event_type)
+        .bind(&event.payload)
+        .bind(retries)
+        .bind(error)
+        .bind(&event.created_at)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Queue dead-lettered events of `webhook_id` for delivery again with
+    /// fresh retries, restoring events that retention has archived; only
+    /// those in `event_ids` when given. Returns the number re-driven.
+    pub async fn redrive_dead_letters(
+        &self,
+        webhook_id: &str,
+        event_ids: Option<&[String]>,
+    ) -> anyhow::Result<u64> {
+        let event_ids = event_ids.map(serde_json::to_string).transpose()?;
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_events (
+                id, webhook_id, event_type, payload, status, retries, created_at
+            )
+            SELECT event_id, webhook_id, event_type, payload, 'pending', 0, created_at
+            FROM webhook_dead_letters
+            WHERE webhook_id = ?1
+              AND (?2 IS NULL OR event_id IN (SELECT value FROM json_each(?2)))
+            ON CONFLICT(id) DO UPDATE SET
+                payload = excluded.payload,
+                status = 'pending',
+                retries = 0,
+                last_error = NULL,
+                next_attempt_at = NULL
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(event_ids.as_deref())
+        .execute(&mut *tx)
+        .await?;
+
+        let redriven = sqlx::query(
+            "DELETE FROM webhook_dead_letters
+             WHERE webhook_id = ?1
+               AND (?2 IS NULL OR event_id IN (SELECT value FROM json_each(?2)))",
+        )
+        .bind(webhook_id)
+        .bind(event_ids.as_deref())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(redriven.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_cap() {
+        assert_eq!(retry_delay(0), Duration::seconds(30));
+        assert_eq!(retry_delay(1), Duration::seconds(60));
+        assert_eq!(retry_delay(4), Duration::seconds(480));
+        assert_eq!(retry_delay(7), Duration::seconds(MAX_RETRY_DELAY_SECS));
+        assert_eq!(retry_delay(100), Duration::seconds(MAX_RETRY_DELAY_SECS));
+    }
+}
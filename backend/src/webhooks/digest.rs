@@ -0,0 +1,309 @@
+//! Periodic `corridor.digest` events.
+//!
+//! Integrations that prefer batched updates subscribe to `corridor.digest`
+//! instead of consuming every `payment.created`. Each run of the digest job
+//! queues one event per subscribed webhook summarizing the corridors it
+//! watches (`{"corridors": [...]}` in its filters) over the last complete
+//! window, built from the hourly corridor rollups. Without a corridor filter
+//! the digest covers the busiest corridors of the window. Each webhook keeps
+//! the end of the last window it was sent, so a window is never queued twice.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::events::{CorridorDigestEntry, CorridorDigestEvent};
+use super::WebhookEventType;
+use crate::api::corridors::calculate_health_score;
+use crate::db::aggregation::{AggregationDb, CorridorWindowTotals};
+use crate::models::corridor::CorridorKey;
+
+/// Most corridors listed in one digest, watched or not
+pub const MAX_DIGEST_CORRIDORS: usize = 50;
+
+/// Corridors a webhook watches, normalized, from its `filters` JSON. `None`
+/// when the webhook has no corridor filter; malformed keys are dropped.
+pub fn watched_corridors(filters: Option<&str>) -> Option<Vec<String>> {
+    let filters: serde_json::Value = serde_json::from_str(filters?).ok()?;
+    let keys = filters.get("corridors")?.as_array()?;
+
+    let mut seen = HashSet::new();
+    let watched = keys
+        .iter()
+        .filter_map(|key| key.as_str())
+        .filter_map(|key| key.parse::<CorridorKey>().ok())
+        .map(String::from)
+        .filter(|key| seen.insert(key.clone()))
+        .take(MAX_DIGEST_CORRIDORS)
+        .collect();
+    Some(watched)
+}
+
+/// Reject a `corridors` filter that is not a list of at most
+/// [`MAX_DIGEST_CORRIDORS`] corridor keys (`CODE:ISSUER->CODE:ISSUER`)
+pub fn validate_corridor_filter(filters: Option<&serde_json::Value>) -> Result<(), String> {
+    let Some(corridors) = filters.and_then(|f| f.get("corridors")) else {
+        return Ok(());
+    };
+    let keys = corridors
+        .as_array()
+        .ok_or("filters.corridors must be a list of corridor keys")?;
+    if keys.len() > MAX_DIGEST_CORRIDORS {
+        return Err(format!(
+            "filters.corridors may list at most {} corridors",
+            MAX_DIGEST_CORRIDORS
+        ));
+    }
+    for key in keys {
+        if !key
+            .as_str()
+            .is_some_and(|key| key.parse::<CorridorKey>().is_ok())
+        {
+            return Err(format!(
+                "Invalid corridor key in filters.corridors: {}",
+                key
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn digest_entry(corridor_key: &str, totals: Option<&CorridorWindowTotals>) -> CorridorDigestEntry {
+    let Some(t) = totals else {
+        return CorridorDigestEntry {
+            corridor_key: corridor_key.to_string(),
+            total_transactions: 0,
+            successful_transactions: 0,
+            success_rate: 0.0,
+            volume_usd: 0.0,
+            avg_slippage_bps: 0.0,
+            health_score: 0.0,
+        };
+    };
+
+    let success_rate = if t.total_transactions > 0 {
+        t.successful_transactions as f64 / t.total_transactions as f64 * 100.0
+    } else {
+        0.0
+    };
+    CorridorDigestEntry {
+        corridor_key: corridor_key.to_string(),
+        total_transactions: t.total_transactions,
+        successful_transactions: t.successful_transactions,
+        success_rate,
+        volume_usd: t.volume_usd,
+        avg_slippage_bps: t.avg_slippage_bps,
+        health_score: calculate_health_score(success_rate, t.total_transactions, t.volume_usd),
+    }
+}
+
+/// Digest entries for one webhook. Watched corridors are listed in the order
+/// given, including those without traffic; otherwise the busiest corridors
+/// by volume.
+pub fn digest_entries(
+    watched: Option<&[String]>,
+    totals: &HashMap<String, CorridorWindowTotals>,
+) -> Vec<CorridorDigestEntry> {
+    match watched {
+        Some(keys) => keys
+            .iter()
+            .map(|key| digest_entry(key, totals.get(key)))
+            .collect(),
+        None => {
+            let mut busiest: Vec<&CorridorWindowTotals> = totals.values().collect();
+            busiest.sort_by(|a, b| {
+                b.volume_usd
+                    .total_cmp(&a.volume_usd)
+                    .then_with(|| a.corridor_key.cmp(&b.corridor_key))
+            });
+            busiest
+                .into_iter()
+                .take(MAX_DIGEST_CORRIDORS)
+                .map(|t| digest_entry(&t.corridor_key, Some(t)))
+                .collect()
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DigestSubscriber {
+    id: String,
+    filters: Option<String>,
+    last_digest_at: Option<String>,
+}
+
+pub struct CorridorDigestBuilder {
+    db: SqlitePool,
+    window: Duration,
+}
+
+impl CorridorDigestBuilder {
+    /// `window` is rounded down to whole hours (at least one), the
+    /// granularity of the corridor rollups
+    pub fn new(db: SqlitePool, window: std::time::Duration) -> Self {
+        let hours = (window.as_secs() / 3600).max(1) as i64;
+        Self {
+            db,
+            window: Duration::hours(hours),
+        }
+    }
+
+    /// Queue digests for the window ending at the top of the current hour
+    pub async fn run_once(&self) -> Result<usize> {
+        self.run_at(Utc::now()).await
+    }
+
+    /// Queue digests for the window ending at the top of the hour of `now`.
+    /// Returns the number of events queued.
+    pub async fn run_at(&self, now: DateTime<Utc>) -> Result<usize> {
+        let end = now.duration_trunc(Duration::hours(1)).unwrap_or(now);
+        let start = end - self.window;
+
+        let subscribers = sqlx::query_as::<_, DigestSubscriber>(
+            r#"
+            SELECT id, filters, last_digest_at FROM webhooks
+            WHERE is_active = 1
+              AND (',' || replace(event_types, ' ', '') || ',') LIKE ?
+            "#,
+        )
+        .bind(format!("%,{},%", WebhookEventType::CorridorDigest.as_str()))
+        .fetch_all(&self.db)
+        .await?;
+
+        let due: Vec<DigestSubscriber> = subscribers
+            .into_iter()
+            .filter(|s| {
+                s.last_digest_at
+                    .as_deref()
+                    .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                    .map_or(true, |at| at.with_timezone(&Utc) < end)
+            })
+            .collect();
+        if due.is_empty() {
+            return Ok(0);
+        }
+
+        let totals: HashMap<String, CorridorWindowTotals> = AggregationDb::new(self.db.clone())
+            .fetch_corridor_window_totals(start, end)
+            .await?
+            .into_iter()
+            .map(|t| (t.corridor_key.clone(), t))
+            .collect();
+
+        let mut queued = 0;
+        for subscriber in due {
+            let watched = watched_corridors(subscriber.filters.as_deref());
+            let event = CorridorDigestEvent {
+                window_start: start.to_rfc3339(),
+                window_end: end.to_rfc3339(),
+                corridors: digest_entries(watched.as_deref(), &totals),
+            };
+            self.queue(&subscriber.id, &event, end).await?;
+            queued += 1;
+        }
+
+        if queued > 0 {
+            tracing::info!("Queued {} corridor digests for {} - {}", queued, start, end);
+        }
+        Ok(queued)
+    }
+
+    /// Insert the event and advance the webhook's digest cursor together
+    async fn queue(
+        &self,
+        webhook_id: &str,
+        event: &CorridorDigestEvent,
+        window_end: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO webhook_events (id, webhook_id, event_type, payload, status, retries, created_at)
+             VALUES (?, ?, ?, ?, 'pending', 0, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(webhook_id)
+        .bind(WebhookEventType::CorridorDigest.as_str())
+        .bind(serde_json::to_string(event)?)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE webhooks SET last_digest_at = ? WHERE id = ?")
+            .bind(window_end.to_rfc3339())
+            .bind(webhook_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn totals(key: &str, volume_usd: f64) -> CorridorWindowTotals {
+        CorridorWindowTotals {
+            corridor_key: key.to_string(),
+            total_transactions: 10,
+            successful_transactions: 9,
+            volume_usd,
+            avg_slippage_bps: 4.0,
+        }
+    }
+
+    #[test]
+    fn test_watched_corridors_from_filters() {
+        assert_eq!(watched_corridors(None), None);
+        assert_eq!(watched_corridors(Some(r#"{"asset": "USDC"}"#)), None);
+        assert_eq!(
+            watched_corridors(Some(
+                r#"{"corridors": ["USDC:GA->XLM:native", "garbage", "USDC:GA->XLM:native"]}"#
+            )),
+            Some(vec!["USDC:GA->XLM:native".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_validate_corridor_filter() {
+        assert!(validate_corridor_filter(None).is_ok());
+        assert!(validate_corridor_filter(Some(&serde_json::json!({"min_amount": 5}))).is_ok());
+        assert!(validate_corridor_filter(Some(
+            &serde_json::json!({"corridors": ["USDC:GA->XLM:native"]})
+        ))
+        .is_ok());
+        assert!(validate_corridor_filter(Some(&serde_json::json!({"corridors": "USDC"}))).is_err());
+        assert!(
+            validate_corridor_filter(Some(&serde_json::json!({"corridors": ["USDC"]}))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_digest_entries_for_watched_and_busiest() {
+        let totals: HashMap<String, CorridorWindowTotals> = [
+            totals("USDC:GA->XLM:native", 500.0),
+            totals("EURC:GB->XLM:native", 900.0),
+        ]
+        .into_iter()
+        .map(|t| (t.corridor_key.clone(), t))
+        .collect();
+
+        let watched = vec![
+            "USDC:GA->XLM:native".to_string(),
+            "BRL:GC->XLM:native".to_string(),
+        ];
+        let entries = digest_entries(Some(&watched), &totals);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].success_rate, 90.0);
+        assert_eq!(entries[1].corridor_key, "BRL:GC->XLM:native");
+        assert_eq!(entries[1].total_transactions, 0);
+
+        let busiest = digest_entries(None, &totals);
+        assert_eq!(busiest[0].corridor_key, "EURC:GB->XLM:native");
+        assert_eq!(busiest[1].corridor_key, "USDC:GA->XLM:native");
+    }
+}
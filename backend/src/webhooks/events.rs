@@ -44,6 +44,102 @@ pub struct CorridorLiquidityDroppedEvent {
     pub severity: String,        // "warning" | "critical"
 }
 
+/// SEP-24/31 Transaction Status Changed Event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SepTransactionStatusChangedEvent {
+    pub transaction_id: String,
+    pub anchor_id: Option<String>,
+    pub anchor_transaction_id: String,
+    pub protocol: String, // "sep24" | "sep31"
+    pub kind: String,
+    pub old_status: String,
+    pub new_status: String,
+    pub message: Option<String>,
+    pub updated_at: String,
+}
+
+/// Corridor Digest Event - periodic summary of the corridors a webhook watches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorridorDigestEvent {
+    pub window_start: String,
+    pub window_end: String,
+    pub corridors: Vec<CorridorDigestEntry>,
+}
+
+/// One corridor's activity over a digest window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorridorDigestEntry {
+    pub corridor_key: String,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub success_rate: f64, // percent, 0 when there was no traffic
+    pub volume_usd: f64,
+    pub avg_slippage_bps: f64,
+    pub health_score: f64,
+}
+
+/// Network Fee Spike Event - the median fee of a ledger's transactions rose
+/// to a multiple of the base fee
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkFeeSpikeEvent {
+    pub ledger_sequence: u64,
+    /// Median fee charged per operation, in stroops
+    pub median_fee_stroops: i64,
+    /// Highest fee charged per operation, in stroops
+    pub max_fee_stroops: i64,
+    pub base_fee_stroops: i64,
+    /// `median_fee_stroops / base_fee_stroops`
+    pub fee_multiplier: f64,
+    pub transaction_count: usize,
+}
+
+/// Ledger Close Delayed Event - a ledger closed long after the one before it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerCloseDelayedEvent {
+    pub ledger_sequence: u64,
+    pub close_time: String,
+    pub previous_close_time: String,
+    pub close_seconds: f64,
+    pub threshold_seconds: f64,
+}
+
+/// Anchor stellar.toml Changed Event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnchorTomlChangedEvent {
+    pub anchor_id: String,
+    pub domain: String,
+    /// Top-level stellar.toml fields that were added, removed or changed
+    pub changed_fields: Vec<String>,
+    pub fetched_at: String,
+}
+
+/// Snapshot Published Event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotPublishedEvent {
+    pub epoch: u64,
+    pub hash: String,
+    pub sla_hash: Option<String>,
+    pub anchor_count: usize,
+    pub corridor_count: usize,
+    /// Present when the hash was submitted on-chain
+    pub transaction_hash: Option<String>,
+    pub ledger: Option<u64>,
+    pub published_at: String,
+}
+
+/// Contract TTL Expiring Event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractTtlExpiringEvent {
+    pub contract_id: String,
+    /// "instance" or "code"
+    pub entry: String,
+    pub live_until_ledger: u32,
+    pub latest_ledger: u32,
+    pub ledgers_remaining: u32,
+    /// Set when an extension was submitted for the entry
+    pub extended_to: Option<u32>,
+}
+
 /// Corridor Metrics snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorridorMetrics {
@@ -0,0 +1,383 @@
+//! Webhook event filters.
+//!
+//! A webhook's `filters` is a JSON object, and an event is delivered only
+//! when every entry matches its payload:
+//!
+//! - `"corridors": [keys]` - events carrying a `corridor_key` must be for one
+//!   of these corridors (also the watch list of `corridor.digest`)
+//! - `"<path>": value` - the payload field at the dot-separated `path` equals
+//!   `value`
+//! - `"<path>": {"<op>": operand, ...}` - every operator holds, with `op` one
+//!   of `eq`, `ne`, `gt`, `gte`, `lt`, `lte`, `in`, `not_in`, `contains` and
+//!   `exists`
+//! - `"event_types": {"<event type>": {...}}` - entries that only apply to
+//!   events of that type, e.g. `{"network.fee_spike": {"fee_multiplier":
+//!   {"gte": 10}}}` for a higher fee threshold
+//!
+//! [`EventFilter::evaluate`] records a step per check so users can see why a
+//! filter did or did not match (`POST /api/webhooks/filters/test`).
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::digest::{validate_corridor_filter, watched_corridors};
+use super::WebhookEventType;
+
+const CORRIDORS_KEY: &str = "corridors";
+const EVENT_TYPES_KEY: &str = "event_types";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    In,
+    NotIn,
+    Contains,
+    Exists,
+}
+
+impl FilterOp {
+    pub fn parse(op: &str) -> Option<Self> {
+        match op {
+            "eq" => Some(Self::Eq),
+            "ne" => Some(Self::Ne),
+            "gt" => Some(Self::Gt),
+            "gte" => Some(Self::Gte),
+            "lt" => Some(Self::Lt),
+            "lte" => Some(Self::Lte),
+            "in" => Some(Self::In),
+            "not_in" => Some(Self::NotIn),
+            "contains" => Some(Self::Contains),
+            "exists" => Some(Self::Exists),
+            _ => None,
+        }
+    }
+
+    fn check_operand(self, operand: &Value) -> Result<(), String> {
+        match self {
+            Self::Gt | Self::Gte | Self::Lt | Self::Lte if !operand.is_number() => {
+                Err("expects a number".to_string())
+            }
+            Self::In | Self::NotIn if !operand.is_array() => Err("expects a list".to_string()),
+            Self::Exists if !operand.is_boolean() => Err("expects true or false".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn holds(self, actual: Option<&Value>, operand: &Value) -> bool {
+        let ordered =
+            |cmp: fn(f64, f64) -> bool| match (actual.and_then(Value::as_f64), operand.as_f64()) {
+                (Some(a), Some(b)) => cmp(a, b),
+                _ => false,
+            };
+
+        match self {
+            Self::Eq => actual.is_some_and(|a| values_equal(a, operand)),
+            Self::Ne => !actual.is_some_and(|a| values_equal(a, operand)),
+            Self::Gt => ordered(|a, b| a > b),
+            Self::Gte => ordered(|a, b| a >= b),
+            Self::Lt => ordered(|a, b| a < b),
+            Self::Lte => ordered(|a, b| a <= b),
+            Self::In => actual.is_some_and(|a| contains_value(operand, a)),
+            Self::NotIn => !actual.is_some_and(|a| contains_value(operand, a)),
+            Self::Contains => match actual {
+                Some(Value::String(s)) => operand.as_str().is_some_and(|needle| s.contains(needle)),
+                Some(list @ Value::Array(_)) => contains_value(list, operand),
+                _ => false,
+            },
+            Self::Exists => {
+                actual.is_some_and(|a| !a.is_null()) == operand.as_bool().unwrap_or(true)
+            }
+        }
+    }
+}
+
+/// Numbers compare by value so `100` matches `100.0`
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) if a.is_finite() => a == b,
+        _ => a == b,
+    }
+}
+
+fn contains_value(list: &Value, item: &Value) -> bool {
+    list.as_array()
+        .is_some_and(|items| items.iter().any(|v| values_equal(v, item)))
+}
+
+/// Payload field at a dot-separated path; numeric segments index arrays
+pub fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    path: String,
+    op: FilterOp,
+    operand: Value,
+}
+
+/// A parsed webhook filter
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    corridors: Option<Vec<String>>,
+    conditions: Vec<Condition>,
+    /// Filters applied only to events of the given type
+    by_event_type: Vec<(String, EventFilter)>,
+}
+
+/// One check made while evaluating a filter
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilterStep {
+    pub field: String,
+    pub op: FilterOp,
+    pub expected: Value,
+    /// Payload value at `field`, `null` when absent
+    pub actual: Value,
+    pub matched: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilterEvaluation {
+    pub matched: bool,
+    pub trace: Vec<FilterStep>,
+}
+
+impl EventFilter {
+    /// Parse a webhook's `filters` object, rejecting unknown operators and
+    /// operands of the wrong type
+    pub fn parse(filters: &Value) -> Result<Self, String> {
+        let object: &Map<String, Value> = match filters {
+            Value::Null => return Ok(Self::default()),
+            Value::Object(object) => object,
+            _ => return Err("filters must be a JSON object".to_string()),
+        };
+
+        validate_corridor_filter(Some(filters))?;
+        let corridors = watched_corridors(Some(&filters.to_string()));
+
+        let mut by_event_type = Vec::new();
+        match object.get(EVENT_TYPES_KEY) {
+            None => {}
+            Some(Value::Object(scoped)) => {
+                for (event_type, spec) in scoped {
+                    if WebhookEventType::from_str(event_type).is_none() {
+                        return Err(format!("Unknown event type '{}' in filters", event_type));
+                    }
+                    if !spec.is_object() || spec.get(EVENT_TYPES_KEY).is_some() {
+                        return Err(format!(
+                            "Filters for '{}' must be an object without '{}'",
+                            event_type, EVENT_TYPES_KEY
+                        ));
+                    }
+                    by_event_type.push((event_type.clone(), Self::parse(spec)?));
+                }
+            }
+            Some(_) => return Err(format!("'{}' must be an object", EVENT_TYPES_KEY)),
+        }
+
+        let mut conditions = Vec::new();
+        for (path, spec) in object
+            .iter()
+            .filter(|(k, _)| k.as_str() != CORRIDORS_KEY && k.as_str() != EVENT_TYPES_KEY)
+        {
+            if path.is_empty() || path.split('.').any(str::is_empty) {
+                return Err(format!("Invalid filter field '{}'", path));
+            }
+            match spec {
+                Value::Object(ops) if !ops.is_empty() => {
+                    for (op_name, operand) in ops {
+                        let op = FilterOp::parse(op_name).ok_or_else(|| {
+                            format!("Unknown filter operator '{}' on '{}'", op_name, path)
+                        })?;
+                        op.check_operand(operand)
+                            .map_err(|e| format!("Operator '{}' on '{}' {}", op_name, path, e))?;
+                        conditions.push(Condition {
+                            path: path.clone(),
+                            op,
+                            operand: operand.clone(),
+                        });
+                    }
+                }
+                Value::Object(_) => {
+                    return Err(format!("Filter on '{}' has no operators", path));
+                }
+                literal => conditions.push(Condition {
+                    path: path.clone(),
+                    op: FilterOp::Eq,
+                    operand: literal.clone(),
+                }),
+            }
+        }
+
+        Ok(Self {
+            corridors,
+            conditions,
+            by_event_type,
+        })
+    }
+
+    /// Parse a webhook's stored `filters` column
+    pub fn from_stored(filters: Option<&str>) -> Result<Self, String> {
+        match filters {
+            None => Ok(Self::default()),
+            Some(raw) => {
+                let value: Value = serde_json::from_str(raw)
+                    .map_err(|e| format!("Invalid filters JSON: {}", e))?;
+                Self::parse(&value)
+            }
+        }
+    }
+
+    /// Evaluate every check against an event payload, including those scoped
+    /// to `event_type` when it is known; the trace includes checks after the
+    /// first failure so all problems show at once
+    pub fn evaluate(&self, event_type: Option<&str>, payload: &Value) -> FilterEvaluation {
+        let mut trace = Vec::with_capacity(self.conditions.len() + 1);
+
+        if let Some(corridors) = &self.corridors {
+            if let Some(actual) = lookup(payload, "corridor_key") {
+                let expected = Value::from(corridors.clone());
+                trace.push(FilterStep {
+                    field: "corridor_key".to_string(),
+                    op: FilterOp::In,
+                    matched: FilterOp::In.holds(Some(actual), &expected),
+                    expected,
+                    actual: actual.clone(),
+                });
+            }
+        }
+
+        for condition in &self.conditions {
+            let actual = lookup(payload, &condition.path);
+            trace.push(FilterStep {
+                field: condition.path.clone(),
+                op: condition.op,
+                expected: condition.operand.clone(),
+                actual: actual.cloned().unwrap_or(Value::Null),
+                matched: condition.op.holds(actual, &condition.operand),
+            });
+        }
+
+        if let Some(event_type) = event_type {
+            for (_, scoped) in self.by_event_type.iter().filter(|(t, _)| t == event_type) {
+                trace.extend(scoped.evaluate(None, payload).trace);
+            }
+        }
+
+        FilterEvaluation {
+            matched: trace.iter().all(|step| step.matched),
+            trace,
+        }
+    }
+
+    /// Whether an event of `event_type` with `payload` should be delivered
+    pub fn matches(&self, event_type: &str, payload: &Value) -> bool {
+        self.evaluate(Some(event_type), payload).matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sep_event() -> Value {
+        json!({
+            "transaction_id": "tx-1",
+            "protocol": "sep24",
+            "new_status": "completed",
+            "amounts": {"in": 250.0},
+            "tags": ["priority"]
+        })
+    }
+
+    #[test]
+    fn test_literal_and_operator_conditions() {
+        let filter = EventFilter::parse(&json!({
+            "protocol": "sep24",
+            "new_status": {"in": ["completed", "refunded"]},
+            "amounts.in": {"gte": 100, "lt": 1000},
+            "tags": {"contains": "priority"},
+            "message": {"exists": false}
+        }))
+        .unwrap();
+
+        let evaluation = filter.evaluate(None, &sep_event());
+        assert!(evaluation.matched);
+        assert_eq!(evaluation.trace.len(), 6);
+    }
+
+    #[test]
+    fn test_trace_shows_every_failed_check() {
+        let filter = EventFilter::parse(&json!({
+            "protocol": "sep31",
+            "amounts.in": {"gt": 500}
+        }))
+        .unwrap();
+
+        let evaluation = filter.evaluate(None, &sep_event());
+        assert!(!evaluation.matched);
+        let failed: Vec<&str> = evaluation
+            .trace
+            .iter()
+            .filter(|s| !s.matched)
+            .map(|s| s.field.as_str())
+            .collect();
+        assert_eq!(failed, vec!["amounts.in", "protocol"]);
+        assert_eq!(evaluation.trace[0].actual, json!(250.0));
+    }
+
+    #[test]
+    fn test_corridor_filter_applies_to_corridor_events_only() {
+        let filter = EventFilter::parse(&json!({"corridors": ["USDC:GA->XLM:native"]})).unwrap();
+        let event_type = "corridor.health_degraded";
+        assert!(filter.matches(event_type, &json!({"corridor_key": "USDC:GA->XLM:native"})));
+        assert!(!filter.matches(event_type, &json!({"corridor_key": "EURC:GB->XLM:native"})));
+        assert!(filter.matches(event_type, &json!({"anchor_id": "anchor-1"})));
+    }
+
+    #[test]
+    fn test_event_type_filters_apply_to_their_type_only() {
+        let filter = EventFilter::parse(&json!({
+            "event_types": {"network.fee_spike": {"fee_multiplier": {"gte": 10}}}
+        }))
+        .unwrap();
+
+        let spike = json!({"ledger_sequence": 1, "fee_multiplier": 4.0});
+        assert!(!filter.matches("network.fee_spike", &spike));
+        assert!(filter.matches("ledger.close_delayed", &spike));
+        assert!(filter.matches("network.fee_spike", &json!({"fee_multiplier": 12.5})));
+
+        let evaluation = filter.evaluate(Some("network.fee_spike"), &spike);
+        assert_eq!(evaluation.trace.len(), 1);
+        assert_eq!(evaluation.trace[0].field, "fee_multiplier");
+        assert!(filter.evaluate(None, &spike).trace.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_filters() {
+        assert!(EventFilter::parse(&json!(["protocol"])).is_err());
+        assert!(EventFilter::parse(&json!({"amount": {"between": [1, 2]}})).is_err());
+        assert!(EventFilter::parse(&json!({"amount": {"gt": "100"}})).is_err());
+        assert!(EventFilter::parse(&json!({"status": {}})).is_err());
+        assert!(EventFilter::parse(&json!({"a..b": 1})).is_err());
+        assert!(EventFilter::parse(&json!({"event_types": ["network.fee_spike"]})).is_err());
+        assert!(EventFilter::parse(&json!({"event_types": {"network.fee": {}}})).is_err());
+        assert!(EventFilter::parse(&json!({
+            "event_types": {"network.fee_spike": {"event_types": {}}}
+        }))
+        .is_err());
+        assert_eq!(EventFilter::parse(&Value::Null), Ok(EventFilter::default()));
+    }
+}
@@ -1,31 +1,81 @@
 /// Webhooks module for Zapier integration
 /// Manages webhook registrations, event definitions, and dispatching
+pub mod deliveries;
+pub mod digest;
 pub mod events;
+pub mod filters;
+pub mod retention;
 
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use sqlx::SqlitePool;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::tenant::TenantScope;
+use filters::EventFilter;
+
+pub use stellar_insights_core::webhook::{WebhookBatchEnvelope, WebhookEventEnvelope};
+
 type HmacSha256 = Hmac<Sha256>;
 
+/// Largest number of events combined into a single batched delivery
+pub const MAX_BATCH_SIZE: u32 = 100;
+
+/// Longest time the dispatcher may hold events while a batch fills
+pub const MAX_BATCH_WINDOW_SECS: u32 = 300;
+
+/// Highest configurable per-endpoint delivery rate
+pub const MAX_DELIVERIES_PER_MINUTE: u32 = 600;
+
+/// Largest gap between a signature's timestamp and the time it is verified
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+/// Default time deliveries stay signed with the previous secret after a rotation
+pub const DEFAULT_ROTATION_GRACE_HOURS: u32 = 24;
+
+/// Longest configurable grace window of a secret rotation
+pub const MAX_ROTATION_GRACE_HOURS: u32 = 168;
+
 /// Webhook signature - for verifying webhook requests
+///
+/// The HMAC-SHA256 covers `{timestamp}.{payload}`, with the timestamp sent in
+/// `X-Zapier-Timestamp`, so a captured delivery cannot be replayed later
+/// under a fresh timestamp.
 pub struct WebhookSignature;
 
 impl WebhookSignature {
-    /// Generate HMAC-SHA256 signature for webhook payload
-    pub fn sign(payload: &str, secret: &str) -> String {
+    fn mac(payload: &str, timestamp: i64, secret: &str) -> HmacSha256 {
         let mut mac =
             HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
         mac.update(payload.as_bytes());
+        mac
+    }
+
+    /// Generate HMAC-SHA256 signature for webhook payload sent at `timestamp`
+    pub fn sign(payload: &str, timestamp: i64, secret: &str) -> String {
+        let mac = Self::mac(payload, timestamp, secret);
         format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
     }
 
-    /// Verify webhook signature
-    pub fn verify(payload: &str, secret: &str, signature: &str) -> bool {
-        let expected = Self::sign(payload, secret);
-        signature == expected
+    /// Verify webhook signature in constant time, rejecting timestamps more
+    /// than `SIGNATURE_TOLERANCE_SECS` away from `now`
+    pub fn verify(payload: &str, timestamp: i64, secret: &str, signature: &str, now: i64) -> bool {
+        if (now - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+            return false;
+        }
+        let Some(digest) = signature
+            .strip_prefix("sha256=")
+            .and_then(|hex_digest| hex::decode(hex_digest).ok())
+        else {
+            return false;
+        };
+        Self::mac(payload, timestamp, secret)
+            .verify_slice(&digest)
+            .is_ok()
     }
 }
 
@@ -41,34 +91,151 @@ pub struct Webhook {
     pub is_active: bool,
     pub created_at: String,
     pub last_fired_at: Option<String>,
+    pub max_batch_size: i64,
+    pub batch_window_secs: i64,
+    pub max_deliveries_per_minute: Option<i64>,
+    /// Tenant the webhook was registered under, whose usage its deliveries are
+    #[sqlx(default)]
+    pub tenant_id: Option<String>,
+    /// Secret replaced by the last rotation
+    #[sqlx(default)]
+    pub previous_secret: Option<String>,
+    /// End of the rotation's grace window (RFC 3339)
+    #[sqlx(default)]
+    pub previous_secret_expires_at: Option<String>,
+}
+
+impl Webhook {
+    /// The previous secret while its rotation grace window is open
+    pub fn previous_secret_at(&self, now: chrono::DateTime<chrono::Utc>) -> Option<&str> {
+        let expires_at = self.previous_secret_expires_at.as_deref()?;
+        let expires_at = chrono::DateTime::parse_from_rfc3339(expires_at).ok()?;
+        if now >= expires_at {
+            return None;
+        }
+        self.previous_secret.as_deref().filter(|s| !s.is_empty())
+    }
+
+    pub fn delivery_settings(&self) -> DeliverySettings {
+        DeliverySettings {
+            max_batch_size: self.max_batch_size.clamp(1, MAX_BATCH_SIZE as i64) as u32,
+            batch_window_secs: self.batch_window_secs.clamp(0, MAX_BATCH_WINDOW_SECS as i64) as u32,
+            max_deliveries_per_minute: self
+                .max_deliveries_per_minute
+                .filter(|cap| *cap > 0)
+                .map(|cap| cap.min(MAX_DELIVERIES_PER_MINUTE as i64) as u32),
+        }
+    }
+}
+
+/// How events are delivered to a webhook endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DeliverySettings {
+    /// Events combined into one POST (1 disables batching)
+    #[serde(default = "default_batch_size")]
+    pub max_batch_size: u32,
+    /// Seconds to wait for a batch to fill before sending a partial one
+    #[serde(default)]
+    pub batch_window_secs: u32,
+    /// Maximum POSTs per minute to this endpoint (`None` = unlimited)
+    #[serde(default)]
+    pub max_deliveries_per_minute: Option<u32>,
+}
+
+impl Default for DeliverySettings {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 1,
+            batch_window_secs: 0,
+            max_deliveries_per_minute: None,
+        }
+    }
+}
+
+fn default_batch_size() -> u32 {
+    1
+}
+
+impl DeliverySettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_BATCH_SIZE).contains(&self.max_batch_size) {
+            return Err(format!("max_batch_size must be between 1 and {}", MAX_BATCH_SIZE));
+        }
+        if self.batch_window_secs > MAX_BATCH_WINDOW_SECS {
+            return Err(format!(
+                "batch_window_secs must be at most {}",
+                MAX_BATCH_WINDOW_SECS
+            ));
+        }
+        if let Some(cap) = self.max_deliveries_per_minute {
+            if !(1..=MAX_DELIVERIES_PER_MINUTE).contains(&cap) {
+                return Err(format!(
+                    "max_deliveries_per_minute must be between 1 and {}",
+                    MAX_DELIVERIES_PER_MINUTE
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Webhook creation request
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateWebhookRequest {
     pub url: String,
     pub event_types: Vec<String>,
+    #[schema(value_type = Option<Object>)]
     pub filters: Option<serde_json::Value>,
+    #[serde(default)]
+    pub delivery: DeliverySettings,
 }
 
 /// Webhook creation response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct WebhookResponse {
     pub id: String,
     pub url: String,
     pub event_types: Vec<String>,
+    #[schema(value_type = Option<Object>)]
     pub filters: Option<serde_json::Value>,
     pub is_active: bool,
     pub created_at: String,
+    pub delivery: DeliverySettings,
+}
+
+/// Secret rotation request
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RotateSecretRequest {
+    /// Hours deliveries stay signed with the old secret as well (0-168,
+    /// default 24)
+    pub grace_period_hours: Option<u32>,
 }
 
-/// Webhook event envelope
-#[derive(Debug, Serialize, Deserialize)]
-pub struct WebhookEventEnvelope {
-    pub id: String, // Delivery ID for idempotency
-    pub event: String,
-    pub timestamp: i64,
-    pub data: serde_json::Value,
+/// The webhook's new signing secret; it is not shown again
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateSecretResponse {
+    pub id: String,
+    pub secret: String,
+    /// Until when deliveries also carry `X-Zapier-Signature-Previous`, made
+    /// with the old secret
+    pub previous_secret_expires_at: String,
+}
+
+/// The caller's webhooks
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+/// A webhook event waiting to be delivered
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingWebhookEvent {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub retries: i64,
+    pub created_at: String,
 }
 
 /// Event types that can trigger webhooks
@@ -78,6 +245,13 @@ pub enum WebhookEventType {
     AnchorStatusChanged,
     PaymentCreated,
     CorridorLiquidityDropped,
+    SepTransactionStatusChanged,
+    CorridorDigest,
+    NetworkFeeSpike,
+    LedgerCloseDelayed,
+    AnchorTomlChanged,
+    SnapshotPublished,
+    ContractTtlExpiring,
 }
 
 impl WebhookEventType {
@@ -87,6 +261,13 @@ impl WebhookEventType {
             Self::AnchorStatusChanged => "anchor.status_changed",
             Self::PaymentCreated => "payment.created",
             Self::CorridorLiquidityDropped => "corridor.liquidity_dropped",
+            Self::SepTransactionStatusChanged => "sep.transaction_status_changed",
+            Self::CorridorDigest => "corridor.digest",
+            Self::NetworkFeeSpike => "network.fee_spike",
+            Self::LedgerCloseDelayed => "ledger.close_delayed",
+            Self::AnchorTomlChanged => "anchor.toml_changed",
+            Self::SnapshotPublished => "snapshot.published",
+            Self::ContractTtlExpiring => "contract.ttl_expiring",
         }
     }
 
@@ -96,6 +277,13 @@ impl WebhookEventType {
             "anchor.status_changed" => Some(Self::AnchorStatusChanged),
             "payment.created" => Some(Self::PaymentCreated),
             "corridor.liquidity_dropped" => Some(Self::CorridorLiquidityDropped),
+            "sep.transaction_status_changed" => Some(Self::SepTransactionStatusChanged),
+            "corridor.digest" => Some(Self::CorridorDigest),
+            "network.fee_spike" => Some(Self::NetworkFeeSpike),
+            "ledger.close_delayed" => Some(Self::LedgerCloseDelayed),
+            "anchor.toml_changed" => Some(Self::AnchorTomlChanged),
+            "snapshot.published" => Some(Self::SnapshotPublished),
+            "contract.ttl_expiring" => Some(Self::ContractTtlExpiring),
             _ => None,
         }
     }
@@ -108,16 +296,19 @@ pub struct WebhookService {
 }
 
 impl WebhookService {
-    pub fn new(db: SqlitePool) -> Self {
-        let encryption_key = std::env::var("ENCRYPTION_KEY")
-            .unwrap_or_else(|_| "0000000000000000000000000000000000000000000000000000000000000000".to_string());
-        Self { db, encryption_key }
+    /// `encryption_key` protects webhook secrets at rest (`security.encryption_key`)
+    pub fn new(db: SqlitePool, encryption_key: impl Into<String>) -> Self {
+        Self {
+            db,
+            encryption_key: encryption_key.into(),
+        }
     }
 
-    /// Register a new webhook
+    /// Register a new webhook, under the tenant when `tenant` is scoped
     pub async fn register_webhook(
         &self,
         user_id: &str,
+        tenant: &TenantScope,
         request: CreateWebhookRequest,
     ) -> anyhow::Result<WebhookResponse> {
         let id = Uuid::new_v4().to_string();
@@ -131,8 +322,11 @@ impl WebhookService {
 
         sqlx::query(
             r#"
-            INSERT INTO webhooks (id, user_id, url, event_types, filters, secret, is_active, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO webhooks (
+                id, user_id, url, event_types, filters, secret, is_active, created_at,
+                max_batch_size, batch_window_secs, max_deliveries_per_minute, tenant_id
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&id)
@@ -143,6 +337,10 @@ impl WebhookService {
         .bind(&encrypted_secret)
         .bind(true)
         .bind(&now)
+        .bind(request.delivery.max_batch_size)
+        .bind(request.delivery.batch_window_secs)
+        .bind(request.delivery.max_deliveries_per_minute)
+        .bind(tenant.id())
         .execute(&self.db)
         .await?;
 
@@ -153,13 +351,14 @@ impl WebhookService {
             filters: request.filters,
             is_active: true,
             created_at: now,
+            delivery: request.delivery,
         })
     }
 
     /// Get webhook by ID
     pub async fn get_webhook(&self, webhook_id: &str) -> anyhow::Result<Option<Webhook>> {
         let mut webhook = sqlx::query_as::<_, Webhook>(
-            "SELECT id, user_id, url, event_types, filters, secret, is_active, created_at, last_fired_at FROM webhooks WHERE id = ?"
+            "SELECT id, user_id, url, event_types, filters, secret, is_active, created_at, last_fired_at, max_batch_size, batch_window_secs, max_deliveries_per_minute, tenant_id, previous_secret, previous_secret_expires_at FROM webhooks WHERE id = ?"
         )
         .bind(webhook_id)
         .fetch_optional(&self.db)
@@ -168,17 +367,27 @@ impl WebhookService {
         if let Some(ref mut w) = webhook {
             w.secret = crate::crypto::decrypt_data(&w.secret, &self.encryption_key)
                 .unwrap_or_else(|_| w.secret.clone());
+            w.previous_secret = w.previous_secret.as_ref().map(|secret| {
+                crate::crypto::decrypt_data(secret, &self.encryption_key)
+                    .unwrap_or_else(|_| secret.clone())
+            });
         }
 
         Ok(webhook)
     }
 
-    /// List webhooks for a user
-    pub async fn list_webhooks(&self, user_id: &str) -> anyhow::Result<Vec<Webhook>> {
+    /// List webhooks for a user, only those registered under the tenant when
+    /// `tenant` is scoped
+    pub async fn list_webhooks(
+        &self,
+        user_id: &str,
+        tenant: &TenantScope,
+    ) -> anyhow::Result<Vec<Webhook>> {
         let mut webhooks = sqlx::query_as::<_, Webhook>(
-            "SELECT id, user_id, url, event_types, filters, secret, is_active, created_at, last_fired_at FROM webhooks WHERE user_id = ? AND is_active = 1 ORDER BY created_at DESC"
+            "SELECT id, user_id, url, event_types, filters, secret, is_active, created_at, last_fired_at, max_batch_size, batch_window_secs, max_deliveries_per_minute FROM webhooks WHERE user_id = ?1 AND is_active = 1 AND (?2 IS NULL OR tenant_id = ?2) ORDER BY created_at DESC"
         )
         .bind(user_id)
+        .bind(tenant.id())
         .fetch_all(&self.db)
         .await?;
 
@@ -190,6 +399,18 @@ impl WebhookService {
         Ok(webhooks)
     }
 
+    /// Active webhooks registered under a tenant, across its users
+    pub async fn count_tenant_webhooks(&self, tenant_id: &str) -> anyhow::Result<usize> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM webhooks WHERE tenant_id = ? AND is_active = 1",
+        )
+        .bind(tenant_id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(count as usize)
+    }
+
     /// Delete/deactivate webhook
     pub async fn delete_webhook(&self, webhook_id: &str, user_id: &str) -> anyhow::Result<bool> {
         let result = sqlx::query("UPDATE webhooks SET is_active = 0 WHERE id = ? AND user_id = ?")
@@ -201,6 +422,59 @@ impl WebhookService {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Replace the signing secret of a webhook owned by `user_id`, keeping the
+    /// old one valid for `grace`. Returns the new secret and the end of the
+    /// grace window, or `None` if the webhook was not found.
+    pub async fn rotate_secret(
+        &self,
+        webhook_id: &str,
+        user_id: &str,
+        grace: chrono::Duration,
+    ) -> anyhow::Result<Option<(String, String)>> {
+        let secret = Uuid::new_v4().to_string();
+        let encrypted_secret = crate::crypto::encrypt_data(&secret, &self.encryption_key)
+            .unwrap_or_else(|_| secret.clone());
+        let expires_at = (chrono::Utc::now() + grace).to_rfc3339();
+
+        let result = sqlx::query(
+            "UPDATE webhooks SET previous_secret = secret, previous_secret_expires_at = ?, secret = ?
+             WHERE id = ? AND user_id = ? AND is_active = 1",
+        )
+        .bind(&expires_at)
+        .bind(&encrypted_secret)
+        .bind(webhook_id)
+        .bind(user_id)
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Ok(Some((secret, expires_at)))
+    }
+
+    /// Update delivery settings of a webhook owned by `user_id`
+    pub async fn update_delivery_settings(
+        &self,
+        webhook_id: &str,
+        user_id: &str,
+        settings: &DeliverySettings,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            "UPDATE webhooks SET max_batch_size = ?, batch_window_secs = ?, max_deliveries_per_minute = ?
+             WHERE id = ? AND user_id = ? AND is_active = 1",
+        )
+        .bind(settings.max_batch_size)
+        .bind(settings.batch_window_secs)
+        .bind(settings.max_deliveries_per_minute)
+        .bind(webhook_id)
+        .bind(user_id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Record webhook event for delivery
     pub async fn create_webhook_event(
         &self,
@@ -229,33 +503,105 @@ impl WebhookService {
         Ok(id)
     }
 
-    /// Get pending webhook events
-    pub async fn get_pending_events(
+    /// Queue an event for every active webhook of `user_id` subscribed to `event_type`.
+    /// Returns the number of events queued.
+    pub async fn enqueue_for_user(
         &self,
-        limit: usize,
-    ) -> anyhow::Result<Vec<(String, String, String, String)>> {
-        let query_limit = limit as i64;
-
-        let rows = sqlx::query(
-            "SELECT we.id, we.webhook_id, we.event_type, we.payload
-             FROM webhook_events we
-             WHERE we.status = 'pending' AND we.retries < 3
-             ORDER BY we.created_at ASC
-             LIMIT ?"
+        user_id: &str,
+        event_type: WebhookEventType,
+        payload: serde_json::Value,
+    ) -> anyhow::Result<usize> {
+        let webhooks = self.list_webhooks(user_id, &TenantScope::All).await?;
+        let mut queued = 0;
+        for webhook in webhooks
+            .iter()
+            .filter(|w| w.event_types.split(',').any(|t| t.trim() == event_type.as_str()))
+        {
+            match EventFilter::from_stored(webhook.filters.as_deref()) {
+                Ok(filter) if !filter.matches(event_type.as_str(), &payload) => continue,
+                Ok(_) => {}
+                // Filters stored before they were validated; deliver unfiltered
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid filters on webhook {}: {}", webhook.id, e)
+                }
+            }
+            self.create_webhook_event(&webhook.id, event_type.as_str(), payload.clone())
+                .await?;
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+
+    /// Queue a network-wide event for every active webhook, of any user,
+    /// subscribed to `event_type` whose filters match. Returns the number of
+    /// events queued.
+    pub async fn enqueue_for_subscribers(
+        &self,
+        event_type: WebhookEventType,
+        payload: serde_json::Value,
+    ) -> anyhow::Result<usize> {
+        let subscribers = sqlx::query_as::<_, (String, Option<String>)>(
+            r#"
+            SELECT id, filters FROM webhooks
+            WHERE is_active = 1
+              AND (',' || replace(event_types, ' ', '') || ',') LIKE ?
+            "#,
         )
-        .bind(query_limit)
+        .bind(format!("%,{},%", event_type.as_str()))
         .fetch_all(&self.db)
         .await?;
 
-        let events: Vec<(String, String, String, String)> = rows.into_iter().map(|row| {
-            use sqlx::Row;
-            (
-                row.get::<String, _>(0),
-                row.get::<String, _>(1),
-                row.get::<String, _>(2),
-                row.get::<String, _>(3),
-            )
-        }).collect();
+        let mut queued = 0;
+        for (webhook_id, filters) in subscribers {
+            match EventFilter::from_stored(filters.as_deref()) {
+                Ok(filter) if !filter.matches(event_type.as_str(), &payload) => continue,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid filters on webhook {}: {}", webhook_id, e)
+                }
+            }
+            self.create_webhook_event(&webhook_id, event_type.as_str(), payload.clone())
+                .await?;
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+
+    /// An event queued for one of `user_id`'s webhooks
+    pub async fn get_event_for_user(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> anyhow::Result<Option<PendingWebhookEvent>> {
+        let event = sqlx::query_as::<_, PendingWebhookEvent>(
+            "SELECT e.id, e.webhook_id, e.event_type, e.payload, e.retries, e.created_at
+             FROM webhook_events e
+             JOIN webhooks w ON w.id = e.webhook_id
+             WHERE e.id = ? AND w.user_id = ?",
+        )
+        .bind(event_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(event)
+    }
+
+    /// Get pending webhook events whose retry backoff has elapsed, oldest first
+    pub async fn get_pending_events(&self, limit: usize) -> anyhow::Result<Vec<PendingWebhookEvent>> {
+        let events = sqlx::query_as::<_, PendingWebhookEvent>(
+            "SELECT id, webhook_id, event_type, payload, retries, created_at
+             FROM webhook_events
+             WHERE status = 'pending' AND (next_attempt_at IS NULL OR next_attempt_at <= ?)
+             ORDER BY created_at ASC
+             LIMIT ?",
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&self.db)
+        .await?;
 
         Ok(events)
     }
@@ -300,9 +646,82 @@ mod tests {
     fn test_webhook_signature() {
         let payload = r#"{"event":"test"}"#;
         let secret = "my-secret";
+        let timestamp = 1_700_000_000;
+
+        let signature = WebhookSignature::sign(payload, timestamp, secret);
+        assert!(WebhookSignature::verify(
+            payload, timestamp, secret, &signature, timestamp
+        ));
+        assert!(WebhookSignature::verify(
+            payload,
+            timestamp,
+            secret,
+            &signature,
+            timestamp + SIGNATURE_TOLERANCE_SECS
+        ));
+
+        // The timestamp is signed, and stale signatures are rejected
+        assert!(!WebhookSignature::verify(
+            payload,
+            timestamp + 60,
+            secret,
+            &signature,
+            timestamp + 60
+        ));
+        assert!(!WebhookSignature::verify(
+            payload,
+            timestamp,
+            secret,
+            &signature,
+            timestamp + SIGNATURE_TOLERANCE_SECS + 1
+        ));
+        assert!(!WebhookSignature::verify(
+            payload, timestamp, "other", &signature, timestamp
+        ));
+        assert!(!WebhookSignature::verify(
+            payload,
+            timestamp,
+            secret,
+            "sha256=zz",
+            timestamp
+        ));
+        assert!(!WebhookSignature::verify(
+            payload,
+            timestamp,
+            secret,
+            signature.trim_start_matches("sha256="),
+            timestamp
+        ));
+    }
 
-        let signature = WebhookSignature::sign(payload, secret);
-        assert!(WebhookSignature::verify(payload, secret, &signature));
+    #[test]
+    fn test_previous_secret_only_within_grace_window() {
+        let now = chrono::Utc::now();
+        let mut webhook = Webhook {
+            id: "hook-1".to_string(),
+            user_id: "user-1".to_string(),
+            url: "https://example.com/hook".to_string(),
+            event_types: "corridor.health_degraded".to_string(),
+            filters: None,
+            secret: "new".to_string(),
+            is_active: true,
+            created_at: now.to_rfc3339(),
+            last_fired_at: None,
+            max_batch_size: 1,
+            batch_window_secs: 0,
+            max_deliveries_per_minute: None,
+            tenant_id: None,
+            previous_secret: Some("old".to_string()),
+            previous_secret_expires_at: Some((now + chrono::Duration::hours(1)).to_rfc3339()),
+        };
+        assert_eq!(webhook.previous_secret_at(now), Some("old"));
+        assert_eq!(
+            webhook.previous_secret_at(now + chrono::Duration::hours(2)),
+            None
+        );
+
+        webhook.previous_secret_expires_at = None;
+        assert_eq!(webhook.previous_secret_at(now), None);
     }
 
     #[test]
@@ -313,5 +732,39 @@ mod tests {
             WebhookEventType::from_str("corridor.health_degraded"),
             Some(WebhookEventType::CorridorHealthDegraded)
         );
+
+        for event in [
+            WebhookEventType::NetworkFeeSpike,
+            WebhookEventType::LedgerCloseDelayed,
+            WebhookEventType::AnchorTomlChanged,
+            WebhookEventType::SnapshotPublished,
+            WebhookEventType::ContractTtlExpiring,
+        ] {
+            assert_eq!(WebhookEventType::from_str(event.as_str()), Some(event));
+        }
+    }
+
+    #[test]
+    fn test_delivery_settings_validation() {
+        assert!(DeliverySettings::default().validate().is_ok());
+
+        let batching = DeliverySettings {
+            max_batch_size: 50,
+            batch_window_secs: 30,
+            max_deliveries_per_minute: Some(10),
+        };
+        assert!(batching.validate().is_ok());
+
+        let too_large = DeliverySettings {
+            max_batch_size: MAX_BATCH_SIZE + 1,
+            ..DeliverySettings::default()
+        };
+        assert!(too_large.validate().is_err());
+
+        let zero_cap = DeliverySettings {
+            max_deliveries_per_minute: Some(0),
+            ..DeliverySettings::default()
+        };
+        assert!(zero_cap.validate().is_err());
     }
 }
@@ -0,0 +1,204 @@
+//! Retention for `webhook_events`.
+//!
+//! Finished events (delivered or failed) older than the retention window are
+//! folded into `webhook_event_summaries`, one row per webhook, day, event type
+//! and status, and then deleted, or kept with the payload stripped. Pending
+//! events are never touched.
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use std::str::FromStr;
+
+use crate::observability::metrics;
+
+pub const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// What happens to raw events once they are summarized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveMode {
+    /// Remove the rows entirely
+    Delete,
+    /// Keep the rows for auditing but blank their payload
+    #[serde(rename = "strip")]
+    StripPayload,
+}
+
+impl ArchiveMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "delete" => Some(Self::Delete),
+            "strip" => Some(Self::StripPayload),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ArchiveMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| {
+            format!(
+                "unknown archive mode '{}', expected delete or strip",
+                s.trim()
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetentionConfig {
+    pub retention_days: i64,
+    pub mode: ArchiveMode,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_RETENTION_DAYS,
+            mode: ArchiveMode::Delete,
+        }
+    }
+}
+
+/// Outcome of one compaction pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionResult {
+    pub events_compacted: u64,
+    pub summary_rows_written: u64,
+}
+
+pub struct WebhookEventRetention {
+    db: SqlitePool,
+    config: RetentionConfig,
+}
+
+impl WebhookEventRetention {
+    pub fn new(db: SqlitePool, config: RetentionConfig) -> Self {
+        Self { db, config }
+    }
+
+    /// Summarize and archive every finished event created before the
+    /// retention cutoff (whole UTC days), in one transaction
+    pub async fn compact(&self) -> Result<CompactionResult> {
+        let cutoff = (Utc::now() - Duration::days(self.config.retention_days))
+            .format("%Y-%m-%d")
+            .to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let mut tx = self.db.begin().await?;
+
+        let summarized = sqlx::query(
+            r#"
+            INSERT INTO webhook_event_summaries (
+                webhook_id, date, event_type, status, event_count, total_retries, updated_at
+            )
+            SELECT webhook_id, substr(created_at, 1, 10), event_type, status,
+                   COUNT(*), SUM(retries), ?
+            FROM webhook_events
+            WHERE status IN ('delivered', 'failed')
+              AND compacted_at IS NULL
+              AND substr(created_at, 1, 10) < ?
+            GROUP BY webhook_id, substr(created_at, 1, 10), event_type, status
+            ON CONFLICT(webhook_id, date, event_type, status) DO UPDATE SET
+                event_count = event_count + excluded.event_count,
+                total_retries = total_retries + excluded.total_retries,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&now)
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await?;
+
+        let archived = match self.config.mode {
+            ArchiveMode::Delete => {
+                sqlx::query(
+                    r#"
+                    DELETE FROM webhook_events
+                    WHERE status IN ('delivered', 'failed')
+                      AND compacted_at IS NULL
+                      AND substr(created_at, 1, 10) < ?
+                    "#,
+                )
+                .bind(&cutoff)
+                .execute(&mut *tx)
+                .await?
+            }
+            ArchiveMode::StripPayload => {
+                sqlx::query(
+                    r#"
+                    UPDATE webhook_events SET payload = '', compacted_at = ?
+                    WHERE status IN ('delivered', 'failed')
+                      AND compacted_at IS NULL
+                      AND substr(created_at, 1, 10) < ?
+                    "#,
+                )
+                .bind(&now)
+                .bind(&cutoff)
+                .execute(&mut *tx)
+                .await?
+            }
+        };
+
+        tx.commit().await?;
+
+        let result = CompactionResult {
+            events_compacted: archived.rows_affected(),
+            summary_rows_written: summarized.rows_affected(),
+        };
+        metrics::record_webhook_events_compacted(result.events_compacted);
+        Ok(result)
+    }
+
+    /// Publish row counts and payload size of the webhook event tables
+    pub async fn record_table_metrics(&self) -> Result<()> {
+        let by_status: Vec<(String, i64)> =
+            sqlx::query_as("SELECT status, COUNT(*) FROM webhook_events GROUP BY status")
+                .fetch_all(&self.db)
+                .await?;
+        let payload_bytes: i64 =
+            sqlx::query_scalar("SELECT COALESCE(SUM(length(payload)), 0) FROM webhook_events")
+                .fetch_one(&self.db)
+                .await?;
+        let summary_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhook_event_summaries")
+            .fetch_one(&self.db)
+            .await?;
+
+        metrics::set_webhook_event_table_stats(&by_status, payload_bytes, summary_rows);
+        Ok(())
+    }
+
+    /// One retention pass: compact, then refresh the table metrics
+    pub async fn run_once(&self) -> Result<CompactionResult> {
+        let result = self.compact().await?;
+        self.record_table_metrics().await?;
+        if result.events_compacted > 0 {
+            tracing::info!(
+                "Compacted {} webhook events older than {} days into {} summary rows",
+                result.events_compacted,
+                self.config.retention_days,
+                result.summary_rows_written
+            );
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_mode_parse() {
+        assert_eq!(ArchiveMode::parse("delete"), Some(ArchiveMode::Delete));
+        assert_eq!(
+            ArchiveMode::parse(" Strip "),
+            Some(ArchiveMode::StripPayload)
+        );
+        assert_eq!(ArchiveMode::parse("keep"), None);
+    }
+}
@@ -8,21 +8,100 @@ use axum::{
 };
 use dashmap::DashMap;
 use futures::{sink::SinkExt, stream::StreamExt};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use std::collections::HashSet;
 use std::sync::Arc;
+use subtle::ConstantTimeEq;
 use tokio::sync::broadcast;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::auth::AuthService;
+use crate::billing::{UsageKind, UsageRecorder};
+use crate::database::Database;
+use crate::models::corridor::CorridorKey;
+use crate::tenant::TenantScope;
+
+pub use stellar_insights_core::ws::WsMessage;
+
+/// Canonical form of a subscription channel. `corridor:<key>` channels are
+/// rewritten with the canonical corridor key, so subscribing with either
+/// asset order or another spelling of the native asset still matches the
+/// channel updates are published on. Other channels pass through unchanged.
+pub fn normalize_channel(channel: String) -> String {
+    match channel
+        .strip_prefix("corridor:")
+        .and_then(|key| key.parse::<CorridorKey>().ok())
+    {
+        Some(key) => key.channel(),
+        None => channel,
+    }
+}
+
+/// Who a connection authenticated as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsIdentity {
+    /// Connected without a token
+    Anonymous,
+    /// Presented the shared `websocket.auth_token`
+    Service,
+    /// Presented a JWT access token
+    User {
+        user_id: String,
+        username: String,
+        /// Listed in `websocket.admin_user_ids`
+        admin: bool,
+    },
+}
+
+impl WsIdentity {
+    fn is_admin(&self) -> bool {
+        matches!(
+            self,
+            WsIdentity::Service | WsIdentity::User { admin: true, .. }
+        )
+    }
+}
+
+/// Whether `identity` may subscribe to `channel`, judged on the identity
+/// alone. `admin.*` channels are limited to admins and the shared service
+/// token, and `sep_transaction:<id>` channels to authenticated connections;
+/// every other channel is public. This is not the whole check for
+/// `sep_transaction:<id>`: [`WsState::authorize`] sends users who are not
+/// admins to an ownership lookup instead, so they only get transactions
+/// they started.
+pub fn may_subscribe(identity: &WsIdentity, channel: &str) -> bool {
+    if channel.starts_with("admin.") || channel.starts_with("admin:") {
+        identity.is_admin()
+    } else if channel.starts_with("sep_transaction:") {
+        *identity != WsIdentity::Anonymous
+    } else {
+        true
+    }
+}
+
 /// WebSocket connection state
 pub struct WsState {
     /// Map of connection ID to broadcast sender
     pub connections: DashMap<Uuid, tokio::sync::mpsc::Sender<WsMessage>>,
     /// Map of connection ID to subscribed channels
     pub subscriptions: DashMap<Uuid, HashSet<String>>,
+    /// Map of connection ID to the identity it authenticated as
+    pub identities: DashMap<Uuid, WsIdentity>,
     ///Broadcast channel for sending messages to all connections
     pub tx: broadcast::Sender<WsMessage>,
+    /// Shared token for trusted clients (`websocket.auth_token`). When set,
+    /// connections without a token are refused.
+    auth_token: Option<String>,
+    /// Validates user JWTs presented as the connection token
+    auth_service: Option<Arc<AuthService>>,
+    /// Users allowed on `admin.*` channels (`websocket.admin_user_ids`)
+    admin_user_ids: HashSet<String>,
+    /// Meters tenant connection minutes when set
+    usage: Option<UsageRecorder>,
+    /// Looks up the owners of `sep_transaction:<id>` channels; without it
+    /// only admins may subscribe to them
+    db: Option<Arc<Database>>,
 }
 
 impl WsState {
@@ -31,7 +110,137 @@ impl WsState {
         Self {
             connections: DashMap::new(),
             subscriptions: DashMap::new(),
+            identities: DashMap::new(),
             tx,
+            auth_token: None,
+            auth_service: None,
+            admin_user_ids: HashSet::new(),
+            usage: None,
+            db: None,
+        }
+    }
+
+    pub fn with_auth_token(mut self, auth_token: Option<String>) -> Self {
+        self.auth_token = auth_token;
+        self
+    }
+
+    pub fn with_auth_service(mut self, auth_service: Arc<AuthService>) -> Self {
+        self.auth_service = Some(auth_service);
+        self
+    }
+
+    pub fn with_admin_user_ids(mut self, user_ids: Vec<String>) -> Self {
+        self.admin_user_ids = user_ids.into_iter().collect();
+        self
+    }
+
+    pub fn with_usage(mut self, usage: UsageRecorder) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    pub fn with_database(mut self, db: Arc<Database>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Identify a client by the token it connected with, or `None` to refuse
+    /// the connection. A token must be the shared token or a valid access
+    /// token; only without either configured is any token accepted
+    /// (development only).
+    pub(crate) fn authenticate(&self, token: Option<&str>) -> Option<WsIdentity> {
+        let Some(token) = token else {
+            return self.auth_token.is_none().then_some(WsIdentity::Anonymous);
+        };
+        // Constant-time, so response timing doesn't reveal the shared token
+        let is_service = self
+            .auth_token
+            .as_deref()
+            .is_some_and(|expected| bool::from(expected.as_bytes().ct_eq(token.as_bytes())));
+        if is_service {
+            return Some(WsIdentity::Service);
+        }
+        if let Some(auth_service) = &self.auth_service {
+            if let Ok(claims) = auth_service.validate_token(token) {
+                if claims.token_type == "access" {
+                    let admin = self.admin_user_ids.contains(&claims.sub);
+                    return Some(WsIdentity::User {
+                        user_id: claims.sub,
+                        username: claims.username,
+                        admin,
+                    });
+                }
+            }
+            return None;
+        }
+        if self.auth_token.is_none() {
+            warn!("WebSocket authentication not configured, accepting any token");
+            return Some(WsIdentity::Anonymous);
+        }
+        None
+    }
+
+    /// Split `channels` into those the connection may subscribe to and
+    /// those it may not
+    pub async fn authorize_channels(
+        &self,
+        connection_id: Uuid,
+        channels: Vec<String>,
+    ) -> (Vec<String>, Vec<String>) {
+        let identity = self
+            .identities
+            .get(&connection_id)
+            .map(|identity| identity.clone())
+            .unwrap_or(WsIdentity::Anonymous);
+        self.authorize(&identity, channels).await
+    }
+
+    /// Split `channels` into those `identity` may subscribe to and those it
+    /// may not. A `sep_transaction:<id>` channel is only open to a user who
+    /// owns the transaction, or to an admin.
+    pub async fn authorize(
+        &self,
+        identity: &WsIdentity,
+        channels: Vec<String>,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut allowed = Vec::new();
+        let mut denied = Vec::new();
+        for channel in channels {
+            let permitted = match (identity, channel.strip_prefix("sep_transaction:")) {
+                (
+                    WsIdentity::User {
+                        user_id,
+                        admin: false,
+                        ..
+                    },
+                    Some(transaction_id),
+                ) => self.owns_transaction(user_id, transaction_id).await,
+                _ => may_subscribe(identity, &channel),
+            };
+            if permitted {
+                allowed.push(channel);
+            } else {
+                denied.push(channel);
+            }
+        }
+        (allowed, denied)
+    }
+
+    /// Whether the SEP transaction was started by `user_id`
+    async fn owns_transaction(&self, user_id: &str, transaction_id: &str) -> bool {
+        let Some(db) = &self.db else {
+            return false;
+        };
+        match db.sep_transactions().get(transaction_id).await {
+            Ok(transaction) => transaction.is_some_and(|tx| tx.user_id == user_id),
+            Err(e) => {
+                warn!(
+                    "Failed to look up SEP transaction {}: {}",
+                    transaction_id, e
+                );
+                false
+            }
         }
     }
 
@@ -74,7 +283,7 @@ impl WsState {
             .entry(connection_id)
             .or_insert_with(HashSet::new);
 
-        for channel in channels {
+        for channel in channels.into_iter().map(normalize_channel) {
             subscription_set.insert(channel.clone());
             info!(
                 "Connection {} subscribed to channel: {}",
@@ -86,7 +295,7 @@ impl WsState {
     /// Unsubscribe a connection from channels
     pub fn unsubscribe_connection(&self, connection_id: Uuid, channels: Vec<String>) {
         if let Some(mut subscription_set) = self.subscriptions.get_mut(&connection_id) {
-            for channel in channels {
+            for channel in channels.into_iter().map(normalize_channel) {
                 subscription_set.remove(&channel);
                 info!(
                     "Connection {} unsubscribed from channel: {}",
@@ -96,6 +305,38 @@ impl WsState {
         }
     }
 
+    /// Register a new connection, returning its ID and the receiver for
+    /// messages sent to its channels
+    pub(crate) fn register_connection(
+        &self,
+        identity: WsIdentity,
+    ) -> (Uuid, tokio::sync::mpsc::Receiver<WsMessage>) {
+        let connection_id = Uuid::new_v4();
+        let (tx, rx) = tokio::sync::mpsc::channel::<WsMessage>(32);
+        self.connections.insert(connection_id, tx);
+        self.identities.insert(connection_id, identity);
+        crate::observability::metrics::set_active_connections(self.connection_count() as i64);
+        (connection_id, rx)
+    }
+
+    /// Remove a closed connection and meter its minutes against the tenant
+    pub(crate) fn close_connection(
+        &self,
+        connection_id: Uuid,
+        tenant: &TenantScope,
+        connected_at: std::time::Instant,
+    ) {
+        self.cleanup_connection(connection_id);
+        crate::observability::metrics::set_active_connections(self.connection_count() as i64);
+        if let (Some(usage), Some(tenant_id)) = (&self.usage, tenant.id()) {
+            usage.record(
+                tenant_id,
+                UsageKind::WsMinutes,
+                billable_minutes(connected_at.elapsed()),
+            );
+        }
+    }
+
     /// Get the number of active connections
     pub fn connection_count(&self) -> usize {
         self.connections.len()
@@ -113,6 +354,7 @@ impl WsState {
     pub fn cleanup_connection(&self, connection_id: Uuid) {
         self.connections.remove(&connection_id);
         self.subscriptions.remove(&connection_id);
+        self.identities.remove(&connection_id);
     }
 
     /// Close all WebSocket connections gracefully
@@ -127,89 +369,6 @@ impl WsState {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
-pub enum WsMessage {
-    /// New snapshot available
-    SnapshotUpdate {
-        snapshot_id: String,
-        epoch: i64,
-        timestamp: String,
-        hash: String,
-    },
-    /// Corridor metrics updated
-    CorridorUpdate {
-        corridor_key: String,
-        asset_a_code: String,
-        asset_a_issuer: String,
-        asset_b_code: String,
-        asset_b_issuer: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        success_rate: Option<f64>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        health_score: Option<f64>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        last_updated: Option<String>,
-    },
-    /// Anchor metrics updated
-    AnchorUpdate {
-        anchor_id: String,
-        name: String,
-        reliability_score: f64,
-        status: String,
-    },
-    /// New payment event
-    NewPayment {
-        corridor_id: String,
-        amount: f64,
-        successful: bool,
-        timestamp: String,
-    },
-    /// Health alert for corridor
-    HealthAlert {
-        corridor_id: String,
-        severity: String,
-        message: String,
-        timestamp: String,
-    },
-    /// Subscription management
-    Subscribe {
-        channels: Vec<String>,
-    },
-    Unsubscribe {
-        channels: Vec<String>,
-    },
-    /// Subscription confirmation
-    SubscriptionConfirm {
-        channels: Vec<String>,
-        status: String,
-    },
-    /// Heartbeat/Ping message
-    Ping {
-        timestamp: i64,
-    },
-    /// Pong response
-    Pong {
-        timestamp: i64,
-    },
-    /// Connection established
-    Connected {
-        connection_id: String,
-    },
-    /// Connection status update
-    ConnectionStatus {
-        status: String,
-    },
-    /// Error message
-    Error {
-        message: String,
-    },
-    /// Server is shutting down
-    ServerShutdown {
-        message: String,
-    },
-}
-
 #[derive(Debug, Deserialize)]
 pub struct WsQueryParams {
     /// Optional authentication token
@@ -221,53 +380,39 @@ pub async fn ws_handler(
     ws: WebSocketUpgrade,
     Query(params): Query<WsQueryParams>,
     State(state): State<Arc<WsState>>,
+    tenant: TenantScope,
 ) -> Response {
-    // Validate authentication token if provided
-    if let Some(token) = params.token {
-        if !validate_token(&token) {
-            return (
-                axum::http::StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"error": "Unauthorized"})),
-            )
-                .into_response();
-        }
-    }
+    let Some(identity) = state.authenticate(params.token.as_deref()) else {
+        return (
+            axum::http::StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Unauthorized"})),
+        )
+            .into_response();
+    };
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, tenant, identity))
 }
 
-/// Validate authentication token
-fn validate_token(token: &str) -> bool {
-    // For now, implement basic token validation
-    // In production, use JWT or other robust auth mechanism
-
-    // If WS_AUTH_TOKEN env var is set, validate against it
-    // Otherwise, accept all tokens (for development)
-    match std::env::var("WS_AUTH_TOKEN") {
-        Ok(expected_token) => token == expected_token,
-        Err(_) => {
-            // No token configured, allow all connections
-            warn!("WS_AUTH_TOKEN not configured, allowing all WebSocket connections");
-            true
-        }
-    }
+/// Whole minutes billed for a connection, rounded up
+fn billable_minutes(connected: std::time::Duration) -> i64 {
+    connected.as_secs().div_ceil(60).max(1) as i64
 }
 
 /// Handle individual WebSocket connection
-async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
-    let connection_id = Uuid::new_v4();
+async fn handle_socket(
+    socket: WebSocket,
+    state: Arc<WsState>,
+    tenant: TenantScope,
+    identity: WsIdentity,
+) {
+    let connected_at = std::time::Instant::now();
+    // Register the connection with its own channel
+    let (connection_id, mut rx) = state.register_connection(identity);
     info!("New WebSocket connection: {}", connection_id);
 
     let (sender, receiver) = socket.split();
     let sender = Arc::new(tokio::sync::Mutex::new(sender));
 
-    // Create a channel for this specific connection
-    let (tx, mut rx) = tokio::sync::mpsc::channel::<WsMessage>(32);
-
-    // Register the connection
-    state.connections.insert(connection_id, tx);
-    crate::observability::metrics::set_active_connections(state.connection_count() as i64);
-
     // Subscribe to broadcast messages
     let mut broadcast_rx = state.tx.subscribe();
 
@@ -308,15 +453,36 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
                                         "Connection {} subscribing to channels: {:?}",
                                         connection_id, channels
                                     );
-                                    state_clone
-                                        .subscribe_connection(connection_id, channels.clone());
-                                    let confirm = WsMessage::SubscriptionConfirm {
-                                        channels: channels.clone(),
-                                        status: "subscribed".to_string(),
-                                    };
-                                    if let Ok(json) = serde_json::to_string(&confirm) {
-                                        let mut sender_guard = recv_sender.lock().await;
-                                        let _ = sender_guard.send(Message::Text(json)).await;
+                                    let (allowed, denied) = state_clone
+                                        .authorize_channels(connection_id, channels)
+                                        .await;
+                                    if !denied.is_empty() {
+                                        warn!(
+                                            "Connection {} not authorized for channels: {:?}",
+                                            connection_id, denied
+                                        );
+                                        let error = WsMessage::Error {
+                                            message: format!(
+                                                "Not authorized to subscribe to: {}",
+                                                denied.join(", ")
+                                            ),
+                                        };
+                                        if let Ok(json) = serde_json::to_string(&error) {
+                                            let mut sender_guard = recv_sender.lock().await;
+                                            let _ = sender_guard.send(Message::Text(json)).await;
+                                        }
+                                    }
+                                    if !allowed.is_empty() {
+                                        state_clone
+                                            .subscribe_connection(connection_id, allowed.clone());
+                                        let confirm = WsMessage::SubscriptionConfirm {
+                                            channels: allowed,
+                                            status: "subscribed".to_string(),
+                                        };
+                                        if let Ok(json) = serde_json::to_string(&confirm) {
+                                            let mut sender_guard = recv_sender.lock().await;
+                                            let _ = sender_guard.send(Message::Text(json)).await;
+                                        }
                                     }
                                 }
                                 WsMessage::Unsubscribe { channels } => {
@@ -415,8 +581,7 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
     }
 
     // Clean up connection
-    state.cleanup_connection(connection_id);
-    crate::observability::metrics::set_active_connections(state.connection_count() as i64);
+    state.close_connection(connection_id, &tenant, connected_at);
     info!(
         "WebSocket connection {} closed. Active connections: {}",
         connection_id,
@@ -428,6 +593,14 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_billable_minutes_round_up() {
+        use std::time::Duration;
+        assert_eq!(billable_minutes(Duration::from_secs(5)), 1);
+        assert_eq!(billable_minutes(Duration::from_secs(60)), 1);
+        assert_eq!(billable_minutes(Duration::from_secs(61)), 2);
+    }
+
     #[test]
     fn test_ws_state_creation() {
         let state = WsState::new();
@@ -435,9 +608,94 @@ mod tests {
     }
 
     #[test]
-    fn test_validate_token_no_env() {
-        // Without WS_AUTH_TOKEN env var, should accept any token
-        assert!(validate_token("any_token"));
+    fn test_authenticate() {
+        // Without any authentication configured, any token is accepted
+        let open = WsState::new();
+        assert_eq!(
+            open.authenticate(Some("any_token")),
+            Some(WsIdentity::Anonymous)
+        );
+        assert_eq!(open.authenticate(None), Some(WsIdentity::Anonymous));
+
+        let state = WsState::new().with_auth_token(Some("secret".to_string()));
+        assert_eq!(
+            state.authenticate(Some("secret")),
+            Some(WsIdentity::Service)
+        );
+        assert_eq!(state.authenticate(Some("any_token")), None);
+        assert_eq!(state.authenticate(Some("secre")), None);
+        assert_eq!(state.authenticate(Some("secret2")), None);
+        assert_eq!(state.authenticate(None), None);
+    }
+
+    #[tokio::test]
+    async fn test_channel_acl() {
+        let user = |admin| WsIdentity::User {
+            user_id: "user-1".to_string(),
+            username: "alice".to_string(),
+            admin,
+        };
+
+        for identity in [WsIdentity::Anonymous, user(false)] {
+            assert!(may_subscribe(&identity, "corridor:USDC:GA->XLM:native"));
+            assert!(!may_subscribe(&identity, "admin.alerts"));
+        }
+        assert!(may_subscribe(&user(true), "admin.alerts"));
+        assert!(may_subscribe(&WsIdentity::Service, "admin:jobs"));
+
+        assert!(!may_subscribe(
+            &WsIdentity::Anonymous,
+            "sep_transaction:abc"
+        ));
+        assert!(may_subscribe(&user(false), "sep_transaction:abc"));
+
+        let state = WsState::new();
+        let (connection_id, _rx) = state.register_connection(user(false));
+        let (allowed, denied) = state
+            .authorize_channels(
+                connection_id,
+                vec![
+                    "movers".to_string(),
+                    "admin.alerts".to_string(),
+                    "sep_transaction:abc".to_string(),
+                ],
+            )
+            .await;
+        assert_eq!(allowed, vec!["movers"]);
+        assert_eq!(denied, vec!["admin.alerts", "sep_transaction:abc"]);
+
+        // Admins may follow any transaction without a lookup
+        let (allowed, _) = state
+            .authorize(&user(true), vec!["sep_transaction:abc".to_string()])
+            .await;
+        assert_eq!(allowed, vec!["sep_transaction:abc"]);
+
+        state.cleanup_connection(connection_id);
+        assert!(state.identities.is_empty());
+    }
+
+    #[test]
+    fn test_corridor_channels_are_normalized() {
+        let state = WsState::new();
+        let connection_id = Uuid::new_v4();
+        state.subscribe_connection(
+            connection_id,
+            vec![
+                "corridor:native->USDC:GA".to_string(),
+                "anchor:status".to_string(),
+            ],
+        );
+        assert_eq!(
+            state.channel_subscription_count("corridor:USDC:GA->XLM:native"),
+            1
+        );
+        assert_eq!(state.channel_subscription_count("anchor:status"), 1);
+
+        state.unsubscribe_connection(connection_id, vec!["corridor:XLM-USDC:GA".to_string()]);
+        assert_eq!(
+            state.channel_subscription_count("corridor:USDC:GA->XLM:native"),
+            0
+        );
     }
 
     #[test]
@@ -1,12 +1,34 @@
+use anyhow::Result;
+use axum::async_trait;
 use sqlx::SqlitePool;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::body::{to_bytes, Body};
 use axum::http::{Request, StatusCode};
 use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::screening::{AccountScreener, ScreeningProvider, ScreeningStatus};
 use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
 use tower::util::ServiceExt;
 
+/// Flags every account
+struct FlagAll;
+
+#[async_trait]
+impl ScreeningProvider for FlagAll {
+    fn name(&self) -> &'static str {
+        "flag-all"
+    }
+
+    async fn screen(&self, accounts: &[String]) -> Result<HashMap<String, ScreeningStatus>> {
+        Ok(accounts
+            .iter()
+            .map(|a| (a.clone(), ScreeningStatus::Flagged))
+            .collect())
+    }
+}
+
 #[sqlx::test]
 async fn test_account_merge_detector_process_and_stats(pool: SqlitePool) {
 
@@ -79,7 +101,11 @@ async fn test_account_merge_routes(pool: SqlitePool) {
 
     detector.process_ledger_operations(202).await.unwrap();
 
-    let app = stellar_insights_backend::api::account_merges::routes(detector);
+    let screener = Arc::new(AccountScreener::new(
+        Arc::new(FlagAll),
+        Duration::from_secs(60),
+    ));
+    let app = stellar_insights_backend::api::account_merges::routes(detector, screener);
 
     let stats_res = app
         .clone()
@@ -110,6 +136,8 @@ async fn test_account_merge_routes(pool: SqlitePool) {
     let recent_body = to_bytes(recent_res.into_body(), usize::MAX).await.unwrap();
     let recent: Vec<serde_json::Value> = serde_json::from_slice(&recent_body).unwrap();
     assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0]["screening_status"], "flagged");
+    assert_eq!(recent[0]["flagged_accounts"].as_array().unwrap().len(), 2);
 
     let destinations_res = app
         .oneshot(
@@ -126,4 +154,5 @@ async fn test_account_merge_routes(pool: SqlitePool) {
         .unwrap();
     let destinations: Vec<serde_json::Value> = serde_json::from_slice(&destinations_body).unwrap();
     assert_eq!(destinations.len(), 2);
+    assert_eq!(destinations[0]["screening_status"], "flagged");
 }
@@ -0,0 +1,67 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+
+async fn create_anchor(db: &Database, name: &str, account: &str, domain: &str) -> String {
+    db.create_anchor(CreateAnchorRequest {
+        name: name.to_string(),
+        stellar_account: account.to_string(),
+        home_domain: Some(domain.to_string()),
+    })
+    .await
+    .expect("failed to create anchor")
+    .id
+}
+
+#[sqlx::test]
+async fn test_merge_moves_assets_and_keeps_aliases(pool: SqlitePool) {
+    let db = Database::new(pool.clone());
+    let survivor = create_anchor(&db, "Acme", "GMERGESURVIVOR", "acme.example").await;
+    let duplicate = create_anchor(&db, "Acme Inc", "GMERGEDUPLICATE", "pay.acme.example").await;
+
+    db.create_asset(
+        uuid::Uuid::parse_str(&duplicate).unwrap(),
+        "ACME".to_string(),
+        "GMERGEDUPLICATE".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let summary = db.anchor_merges().merge(&survivor, &duplicate).await.unwrap();
+    assert_eq!(summary.assets_moved, 1);
+    assert_eq!(summary.aliases_created, 3);
+
+    let assets = db
+        .get_assets_by_anchor(uuid::Uuid::parse_str(&survivor).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(assets.len(), 1);
+
+    // Old id and account still resolve to the surviving record
+    let by_old_id = db
+        .get_anchor_by_id(uuid::Uuid::parse_str(&duplicate).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_old_id.id, survivor);
+
+    let by_old_account = db
+        .get_anchor_by_stellar_account("GMERGEDUPLICATE")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_old_account.id, survivor);
+}
+
+#[sqlx::test]
+async fn test_merge_rejects_self_and_missing(pool: SqlitePool) {
+    let db = Database::new(pool.clone());
+    let survivor = create_anchor(&db, "Solo", "GMERGESOLO", "solo.example").await;
+
+    assert!(db.anchor_merges().merge(&survivor, &survivor).await.is_err());
+    assert!(db
+        .anchor_merges()
+        .merge(&survivor, &uuid::Uuid::new_v4().to_string())
+        .await
+        .is_err());
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::ml::anomaly::{AnomalyService, RobustZScore};
+
+const KEY: &str = "USDC:GISSUER->XLM:native";
+const NEW_KEY: &str = "EURC:GISSUER->XLM:native";
+
+async fn store_hour(
+    pool: &SqlitePool,
+    corridor_key: &str,
+    hour: DateTime<Utc>,
+    transactions: i64,
+    successful: i64,
+    volume_usd: f64,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridor_metrics_hourly (
+            id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            hour_bucket, total_transactions, successful_transactions, failed_transactions,
+            success_rate, volume_usd, liquidity_depth_usd
+        )
+        VALUES ($1, $2, 'USDC', 'GISSUER', 'XLM', 'native', $3, $4, $5, $6, 0, $7, 0)
+        "#,
+    )
+    .bind(format!("{}:{}", corridor_key, hour.to_rfc3339()))
+    .bind(corridor_key)
+    .bind(hour.to_rfc3339())
+    .bind(transactions)
+    .bind(successful)
+    .bind(transactions - successful)
+    .bind(volume_usd)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_retrained_model_flags_unusual_hours(pool: SqlitePool) {
+    let now = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    for h in 1..=96 {
+        let hour = now - Duration::hours(h);
+        if h == 10 || h == 11 {
+            // A volume spike while most payments failed
+            store_hour(&pool, KEY, hour, 20, 5, 40_000.0).await;
+        } else {
+            store_hour(
+                &pool,
+                KEY,
+                hour,
+                20,
+                19 + h % 2,
+                1000.0 + (h % 5) as f64 * 40.0,
+            )
+            .await;
+        }
+    }
+    // Too little history to train on
+    for h in 1..=12 {
+        store_hour(&pool, NEW_KEY, now - Duration::hours(h), 5, 5, 100.0).await;
+    }
+
+    let service = AnomalyService::new(Arc::new(Database::new(pool)), Arc::new(RobustZScore));
+    assert_eq!(service.retrain().await.unwrap(), 1);
+    assert!(service
+        .anomalies(NEW_KEY, now - Duration::days(7))
+        .await
+        .unwrap()
+        .is_none());
+
+    let anomalies = service
+        .anomalies(KEY, now - Duration::days(7))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(anomalies.model, "robust_z");
+    assert_eq!(anomalies.model_version, 1);
+    assert_eq!(anomalies.intervals.len(), 1);
+    let interval = &anomalies.intervals[0];
+    assert_eq!(interval.start, now - Duration::hours(11));
+    assert_eq!(interval.end, now - Duration::hours(9));
+    assert_eq!(interval.volume_usd, 80_000.0);
+    assert_eq!(interval.success_rate, Some(0.25));
+
+    // Retraining replaces the artifact under a new version
+    service.retrain().await.unwrap();
+    let anomalies = service
+        .anomalies(KEY, now - Duration::days(7))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(anomalies.model_version, 2);
+}
@@ -10,6 +10,7 @@ use tower::util::ServiceExt;
 
 // Use correct handlers from the updated API
 use stellar_insights_backend::api::corridors::{get_corridor_detail, list_corridors};
+use stellar_insights_backend::config::Settings;
 use stellar_insights_backend::database::Database;
 use stellar_insights_backend::ingestion::DataIngestionService;
 use stellar_insights_backend::rpc::StellarRpcClient;
@@ -33,6 +34,7 @@ fn create_test_router(db: Arc<Database>) -> Router {
         db,
         ws_state,
         ingestion,
+        settings: Arc::new(Settings::default()),
     };
     Router::new()
         .route("/api/corridors", axum::routing::get(list_corridors))
@@ -0,0 +1,99 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use stellar_insights_backend::api::asset_compliance;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::asset_compliance::{
+    AssetComplianceTracker, ComplianceAction,
+};
+use tower::util::ServiceExt;
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+async fn track_usdc(pool: &SqlitePool) {
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account) VALUES ('a1', 'Circle', ?)")
+        .bind(USDC_ISSUER)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO assets (id, anchor_id, asset_code, asset_issuer) VALUES ('x1', 'a1', 'USDC', ?)",
+    )
+    .bind(USDC_ISSUER)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_compliance_events_are_stored_for_tracked_assets_only(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let tracker = AssetComplianceTracker::new(pool.clone(), rpc_client);
+
+    // Untracked assets are ignored
+    assert_eq!(tracker.process_ledger_operations(300).await.unwrap(), 0);
+
+    track_usdc(&pool).await;
+    assert_eq!(tracker.process_ledger_operations(301).await.unwrap(), 2);
+    assert_eq!(tracker.process_ledger_operations(301).await.unwrap(), 0);
+
+    let events = tracker
+        .get_events("USDC", USDC_ISSUER, None, 10)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].action, ComplianceAction::Freeze);
+    assert_eq!(events[0].flags_cleared, vec!["authorized".to_string()]);
+    assert_eq!(events[1].action, ComplianceAction::Clawback);
+    assert_eq!(events[1].amount, Some(40.0));
+
+    let clawbacks = tracker
+        .get_events("USDC", USDC_ISSUER, Some(ComplianceAction::Clawback), 10)
+        .await
+        .unwrap();
+    assert_eq!(clawbacks.len(), 1);
+}
+
+#[sqlx::test]
+async fn test_compliance_events_route(pool: SqlitePool) {
+    track_usdc(&pool).await;
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let tracker = Arc::new(AssetComplianceTracker::new(pool.clone(), rpc_client));
+    tracker.process_ledger_operations(302).await.unwrap();
+
+    let app = asset_compliance::routes(tracker);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/api/assets/USDC/{}/compliance-events?action=freeze",
+                    USDC_ISSUER
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["events"].as_array().unwrap().len(), 1);
+    assert_eq!(json["events"][0]["action"], "freeze");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/api/assets/USDC/{}/compliance-events?action=mint",
+                    USDC_ISSUER
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,62 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::asset_supply::AssetSupplySnapshot;
+
+const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+fn snapshot(total_supply: f64, days_ago: i64) -> AssetSupplySnapshot {
+    AssetSupplySnapshot {
+        asset_code: "USDC".to_string(),
+        asset_issuer: ISSUER.to_string(),
+        total_supply,
+        num_holders: 120,
+        top10_balance: total_supply * 0.6,
+        top10_share: 0.6,
+        holders_scanned: 120,
+        holders_complete: true,
+        recorded_at: Utc::now() - Duration::days(days_ago),
+    }
+}
+
+#[sqlx::test]
+async fn test_supply_snapshots_update_asset_and_history(pool: SqlitePool) {
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account) VALUES ('a1', 'Anchor', ?)")
+        .bind(ISSUER)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO assets (id, anchor_id, asset_code, asset_issuer) VALUES ('x1', 'a1', 'USDC', ?)",
+    )
+    .bind(ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let db = Database::new(pool.clone());
+    let history = db.asset_supply();
+    assert_eq!(
+        history.tracked_assets().await.unwrap(),
+        vec![("USDC".to_string(), ISSUER.to_string())]
+    );
+
+    history.record(&snapshot(1_000.0, 40)).await.unwrap();
+    history.record(&snapshot(1_500.0, 2)).await.unwrap();
+
+    let latest = history.latest("USDC", ISSUER).await.unwrap().unwrap();
+    assert_eq!(latest.total_supply, 1_500.0);
+
+    let recent = history
+        .history("USDC", ISSUER, Utc::now() - Duration::days(30))
+        .await
+        .unwrap();
+    assert_eq!(recent.len(), 1);
+
+    let (supply, holders): (f64, i64) =
+        sqlx::query_as("SELECT total_supply, num_holders FROM assets WHERE id = 'x1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!((supply, holders), (1_500.0, 120));
+}
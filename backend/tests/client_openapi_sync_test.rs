@@ -0,0 +1,108 @@
+//! Keeps `stellar-insights-client` in step with the OpenAPI spec: every
+//! endpoint it calls must be documented, and every model must carry exactly
+//! the properties of the schema it is named after.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use stellar_insights_backend::openapi::ApiDoc;
+use stellar_insights_client::models;
+use utoipa::OpenApi;
+
+fn spec() -> Value {
+    serde_json::to_value(ApiDoc::openapi()).unwrap()
+}
+
+/// Property names of a component schema, following `allOf` (from
+/// `#[serde(flatten)]`) into referenced schemas
+fn schema_properties(spec: &Value, schema: &Value) -> BTreeSet<String> {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/components/schemas/");
+        return schema_properties(spec, &spec["components"]["schemas"][name]);
+    }
+    let mut properties: BTreeSet<String> = schema["properties"]
+        .as_object()
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+    for part in schema["allOf"].as_array().into_iter().flatten() {
+        properties.extend(schema_properties(spec, part));
+    }
+    properties
+}
+
+#[test]
+fn test_client_endpoints_are_documented() {
+    let spec = spec();
+    for (method, path) in stellar_insights_client::ENDPOINTS {
+        assert!(
+            spec["paths"][path][method].is_object(),
+            "client calls {} {}, which is missing from the OpenAPI spec",
+            method.to_uppercase(),
+            path
+        );
+    }
+}
+
+/// Field names a client model serializes
+fn fields<T: Serialize>(model: T) -> BTreeSet<String> {
+    match serde_json::to_value(model).unwrap() {
+        Value::Object(map) => map.keys().cloned().collect(),
+        other => panic!("expected an object, got {}", other),
+    }
+}
+
+/// (schema name, client fields) for each model
+macro_rules! client_models {
+    ($($model:ident),* $(,)?) => {
+        vec![$((stringify!($model), fields(models::$model::default()))),*]
+    };
+}
+
+#[test]
+fn test_client_models_match_schemas() {
+    let spec = spec();
+    let models = client_models![
+        AnchorsResponse,
+        AnchorMetricsResponse,
+        CorridorResponse,
+        CorridorDetailResponse,
+        SuccessRateDataPoint,
+        LatencyDataPoint,
+        LiquidityDataPoint,
+        RecentTradesSummary,
+        CorridorAnchor,
+        ConcentrationPoint,
+        SettlementTimes,
+        SeasonalityResponse,
+        HourOfDayEffect,
+        WeekdayEffect,
+        SeasonalityPoint,
+        LatestHour,
+        CohortRetentionResponse,
+        Cohort,
+        HealthHistoryResponse,
+        HealthPoint,
+        SigningKeyResponse,
+        SnapshotSignatureResponse,
+        OnChainSla,
+        SnapshotSlaResponse,
+        VerifySlaResponse,
+        CreateWebhookRequest,
+        WebhookResponse,
+        RotateSecretRequest,
+        RotateSecretResponse,
+        WebhookListResponse,
+        DeliverySettings,
+    ];
+
+    for (name, client_fields) in models {
+        let schema = &spec["components"]["schemas"][name];
+        assert!(schema.is_object(), "schema {} is missing", name);
+        assert_eq!(
+            client_fields,
+            schema_properties(&spec, schema),
+            "client model {} differs from its OpenAPI schema",
+            name
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::db::concentration::{
+    CorridorConcentration, CorridorConcentrations, PaymentSide,
+};
+
+const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+async fn store_payment(pool: &SqlitePool, id: &str, from: &str, to: &str, amount: f64) {
+    sqlx::query(
+        r#"
+        INSERT INTO payments (
+            id, transaction_hash, source_account, destination_account,
+            asset_type, asset_code, asset_issuer, amount, created_at
+        )
+        VALUES ($1, $2, $3, $4, 'credit_alphanum4', 'USDC', $5, $6,
+                '2024-01-01T12:00:00+00:00')
+        "#,
+    )
+    .bind(id)
+    .bind(format!("tx_{}", id))
+    .bind(from)
+    .bind(to)
+    .bind(USDC.split_once(':').unwrap().1)
+    .bind(amount)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn at(timestamp: &str) -> DateTime<Utc> {
+    timestamp.parse().unwrap()
+}
+
+#[sqlx::test]
+async fn test_payment_volumes_per_account(pool: SqlitePool) {
+    store_payment(&pool, "1", "GWHALE", "GSHOP", 90.0).await;
+    store_payment(&pool, "2", "GWHALE", "GSHOP", 5.0).await;
+    store_payment(&pool, "3", "GSMALL", "GSHOP", 5.0).await;
+
+    let store = CorridorConcentrations::new(pool);
+    let (start, end) = (at("2024-01-01T00:00:00Z"), at("2024-01-02T00:00:00Z"));
+    let key = format!("{}->{}", USDC, USDC);
+
+    let mut senders = store
+        .payment_volumes(PaymentSide::Sender, start, end)
+        .await
+        .unwrap();
+    senders.sort_by(|a, b| a.1.cmp(&b.1));
+    assert_eq!(
+        senders,
+        vec![
+            (key.clone(), "GSMALL".to_string(), 5.0),
+            (key.clone(), "GWHALE".to_string(), 95.0),
+        ]
+    );
+
+    let receivers = store
+        .payment_volumes(PaymentSide::Receiver, start, end)
+        .await
+        .unwrap();
+    assert_eq!(receivers, vec![(key, "GSHOP".to_string(), 100.0)]);
+
+    // The window's end is exclusive
+    let later = store
+        .payment_volumes(PaymentSide::Sender, end, at("2024-01-03T00:00:00Z"))
+        .await
+        .unwrap();
+    assert!(later.is_empty());
+}
+
+#[sqlx::test]
+async fn test_record_replaces_window_and_history_is_oldest_first(pool: SqlitePool) {
+    let store = CorridorConcentrations::new(pool);
+    let row = |window_end: &str, sender_hhi| CorridorConcentration {
+        corridor_key: "A".to_string(),
+        window_start: "2024-01-01T00:00:00+00:00".to_string(),
+        window_end: window_end.to_string(),
+        sender_hhi: Some(sender_hhi),
+        senders: 2,
+        top_sender_share: Some(0.5),
+        ..Default::default()
+    };
+
+    store
+        .record(&[
+            row("2024-01-03T00:00:00+00:00", 6_000.0),
+            row("2024-01-02T00:00:00+00:00", 5_000.0),
+        ])
+        .await
+        .unwrap();
+    // Recomputing a window replaces it
+    store
+        .record(&[row("2024-01-03T00:00:00+00:00", 7_000.0)])
+        .await
+        .unwrap();
+
+    let history = store
+        .history("A", at("2024-01-01T00:00:00Z"))
+        .await
+        .unwrap();
+    assert_eq!(
+        history,
+        vec![
+            row("2024-01-02T00:00:00+00:00", 5_000.0),
+            row("2024-01-03T00:00:00+00:00", 7_000.0),
+        ]
+    );
+
+    let recent = store
+        .history("A", at("2024-01-02T12:00:00Z"))
+        .await
+        .unwrap();
+    assert_eq!(recent.len(), 1);
+    assert!(store
+        .history("B", at("2024-01-01T00:00:00Z"))
+        .await
+        .unwrap()
+        .is_empty());
+}
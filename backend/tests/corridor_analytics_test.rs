@@ -4,6 +4,7 @@ use stellar_insights_backend::analytics::corridor::{
     get_top_corridors_by_transactions, get_top_corridors_by_volume,
 };
 use stellar_insights_backend::models::corridor::PaymentRecord;
+use stellar_insights_backend::models::MetricSource;
 use uuid::Uuid;
 
 fn create_test_payment(
@@ -26,6 +27,7 @@ fn create_test_payment(
         timestamp,
         submission_time: None,
         confirmation_time: None,
+        source: MetricSource::Horizon,
     }
 }
 
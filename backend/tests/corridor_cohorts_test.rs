@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::db::corridor_cohorts::{CohortActivity, CorridorCohorts};
+
+const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+async fn store_payment(pool: &SqlitePool, id: &str, account: &str, created_at: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO payments (
+            id, transaction_hash, source_account, destination_account,
+            asset_type, asset_code, asset_issuer, amount, created_at
+        )
+        VALUES ($1, $2, $3, 'GDEST', 'credit_alphanum4', 'USDC', $4, 10.0, $5)
+        "#,
+    )
+    .bind(id)
+    .bind(format!("tx_{}", id))
+    .bind(account)
+    .bind(USDC.split_once(':').unwrap().1)
+    .bind(created_at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn at(timestamp: &str) -> DateTime<Utc> {
+    timestamp.parse().unwrap()
+}
+
+#[sqlx::test]
+async fn test_activity_groups_accounts_by_first_month(pool: SqlitePool) {
+    store_payment(&pool, "1", "GALICE", "2024-01-05T10:00:00+00:00").await;
+    store_payment(&pool, "2", "GBOB", "2024-01-20T10:00:00+00:00").await;
+    store_payment(&pool, "3", "GALICE", "2024-02-03T10:00:00+00:00").await;
+    store_payment(&pool, "4", "GALICE", "2024-02-04T10:00:00+00:00").await;
+    store_payment(&pool, "5", "GCAROL", "2024-02-10T10:00:00+00:00").await;
+
+    let cohorts = CorridorCohorts::new(pool);
+    let window = (at("2024-01-01T00:00:00Z"), at("2024-03-01T00:00:00Z"));
+    assert_eq!(
+        cohorts.record_activity(window.0, window.1).await.unwrap(),
+        4
+    );
+    // Overlapping windows add nothing twice
+    assert_eq!(
+        cohorts.record_activity(window.0, window.1).await.unwrap(),
+        0
+    );
+
+    let key = format!("{}->{}", USDC, USDC);
+    let row = |cohort: &str, month: &str, accounts| CohortActivity {
+        cohort: cohort.to_string(),
+        month: month.to_string(),
+        accounts,
+    };
+    assert_eq!(
+        cohorts.activity(&key, "2024-01").await.unwrap(),
+        vec![
+            row("2024-01", "2024-01", 2),
+            row("2024-01", "2024-02", 1),
+            row("2024-02", "2024-02", 1),
+        ]
+    );
+
+    // GALICE stays in the January cohort when the window starts later
+    assert_eq!(
+        cohorts.activity(&key, "2024-02").await.unwrap(),
+        vec![row("2024-02", "2024-02", 1)]
+    );
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::body::to_bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use stellar_insights_backend::api::corridors_cached::get_corridor_detail;
+use stellar_insights_backend::cache::{CacheConfig, CacheManager};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::concentration::CorridorConcentration;
+use stellar_insights_backend::models::corridor::{Corridor, CorridorAnalytics};
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::corridor_key_filter::corridor_key_filter;
+use stellar_insights_backend::services::price_feed::{PriceFeedClient, PriceFeedConfig};
+use stellar_insights_backend::sparse_fields::FieldsQuery;
+use stellar_insights_backend::tenant::TenantScope;
+
+// Issuer of the mock RPC client's USDC trades
+const USDC_ISSUER: &str = "GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+async fn fetch_detail(db: Database, corridor_key: &str) -> (StatusCode, Value) {
+    let cache = Arc::new(CacheManager::new(CacheConfig::default()).await.unwrap());
+    let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+    // No asset mapping, so pricing the order book fails
+    let price_feed = Arc::new(PriceFeedClient::new(
+        PriceFeedConfig::default(),
+        HashMap::new(),
+    ));
+
+    let response = match get_corridor_detail(
+        State((Arc::new(db), cache, rpc, price_feed)),
+        TenantScope::All,
+        Path(corridor_key.to_string()),
+        Query(FieldsQuery::default()),
+    )
+    .await
+    {
+        Ok(response) => response,
+        Err(e) => axum::response::IntoResponse::into_response(e),
+    };
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_corridor_detail_returns_partial_result(pool: SqlitePool) {
+    let corridor = Corridor::new(
+        "XLM".to_string(),
+        "native".to_string(),
+        "USDC".to_string(),
+        USDC_ISSUER.to_string(),
+    );
+    let corridor_key = corridor.to_string_key();
+    corridor_key_filter().insert(&corridor_key);
+
+    let db = Database::new(pool.clone());
+    db.corridor_aggregates()
+        .store_daily_corridor_metrics(
+            &CorridorAnalytics {
+                corridor,
+                success_rate: 98.0,
+                total_transactions: 100,
+                successful_transactions: 98,
+                failed_transactions: 2,
+                volume_usd: 50_000.0,
+                source: Default::default(),
+            },
+            Utc::now().date_naive(),
+        )
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account) VALUES ('a1', 'Circle', ?)")
+        .bind(USDC_ISSUER)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO assets (id, anchor_id, asset_code, asset_issuer) VALUES ('x1', 'a1', 'USDC', ?)",
+    )
+    .bind(USDC_ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let window_end = Utc::now() - chrono::Duration::days(1);
+    db.corridor_concentrations()
+        .record(&[CorridorConcentration {
+            corridor_key: corridor_key.clone(),
+            window_start: (window_end - chrono::Duration::days(1)).to_rfc3339(),
+            window_end: window_end.to_rfc3339(),
+            sender_hhi: Some(1_800.0),
+            senders: 6,
+            top_sender_share: Some(0.3),
+            ..Default::default()
+        }])
+        .await
+        .unwrap();
+
+    let (status, detail) = fetch_detail(db, &corridor_key).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["corridor"]["id"], corridor_key);
+    assert_eq!(detail["corridor"]["total_attempts"], 100);
+
+    // The order book couldn't be priced; every other part is present
+    assert_eq!(detail["unavailable"], serde_json::json!(["order_book"]));
+    assert!(detail["order_book_depth_usd"].is_null());
+    assert_eq!(detail["anchors"][0]["name"], "Circle");
+    assert_eq!(detail["anchors"][0]["asset_code"], "USDC");
+    // Every mock trade is XLM/USDC; volume is counted in USDC
+    assert_eq!(detail["recent_trades"]["trade_count"], 200);
+    assert_eq!(detail["recent_trades"]["volume"], 1_095_000.0);
+    assert_eq!(
+        detail["recent_trades"]["last_trade_at"],
+        "2026-01-22T10:00:00Z"
+    );
+    assert_eq!(detail["related_corridors"], serde_json::json!([]));
+    assert_eq!(detail["concentration"][0]["sender_hhi"], 1_800.0);
+    assert_eq!(detail["concentration"][0]["level"], "moderate");
+    assert!(detail["concentration"][0]["receiver_hhi"].is_null());
+}
+
+#[sqlx::test]
+async fn test_corridor_detail_without_metrics_is_not_found(pool: SqlitePool) {
+    let corridor_key = format!("EURC:GEURISSUER->USDC:{}", USDC_ISSUER);
+    corridor_key_filter().insert(&corridor_key);
+
+    let (status, body) = fetch_detail(Database::new(pool), &corridor_key).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["code"], "CORRIDOR_NOT_FOUND");
+}
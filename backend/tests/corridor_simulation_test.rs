@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::corridor_simulation::CorridorSimulator;
+use stellar_insights_backend::services::price_feed::{PriceFeedClient, PriceFeedConfig};
+use tower::util::ServiceExt;
+
+const NEW_ASSET: &str = "ABCD:GDPJALI4AZKUU2W426U5WKMAT6CN3AJRPIIRYR2YM54TL2GDEMNQERFT";
+
+fn test_app() -> axum::Router {
+    let price_feed = Arc::new(PriceFeedClient::new(
+        PriceFeedConfig::default(),
+        HashMap::new(),
+    ));
+    let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+    stellar_insights_backend::api::corridor_simulation::routes(Arc::new(CorridorSimulator::new(
+        rpc, price_feed,
+    )))
+}
+
+async fn simulate(body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+    let response = test_app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/tools/simulate-corridor")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn simulate_reports_paths_depth_and_cost() {
+    let (status, report) = simulate(serde_json::json!({
+        "source_asset": NEW_ASSET,
+        "destination_asset": "native",
+        "amount": 100.0
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["destination_asset"], "XLM:native");
+    assert_eq!(report["paths"].as_array().unwrap().len(), 2);
+    assert_eq!(report["best_path"]["hops"].as_array().unwrap().len(), 2);
+    assert_eq!(report["order_book"]["unfilled_amount"], 0.0);
+    assert_eq!(report["viable"], true);
+
+    // No price feed rate for the new asset, so the book's mid price is used
+    assert_eq!(report["cost"]["mid_market_source"], "order_book");
+    let cost_bps = report["cost"]["total_cost_bps"].as_f64().unwrap();
+    assert!((cost_bps - 50.0).abs() < 0.01);
+}
+
+#[tokio::test]
+async fn simulate_rejects_invalid_input() {
+    let (status, _) = simulate(serde_json::json!({
+        "source_asset": "ABCD",
+        "destination_asset": "native",
+        "amount": 100.0
+    }))
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = simulate(serde_json::json!({
+        "source_asset": NEW_ASSET,
+        "destination_asset": "native",
+        "amount": -5.0
+    }))
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::{Extension, Router};
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use stellar_insights_backend::api::corridors_cached::list_corridors;
+use stellar_insights_backend::cache::{CacheConfig, CacheManager};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::price_feed::{PriceFeedClient, PriceFeedConfig};
+use stellar_insights_backend::tenant::TenantScope;
+use tower::util::ServiceExt;
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+const EURC_ISSUER: &str = "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+
+async fn insert_hourly(
+    pool: &SqlitePool,
+    id: &str,
+    a: (&str, &str),
+    b: (&str, &str),
+    hours_ago: i64,
+    total: i64,
+    ok: i64,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridor_metrics_hourly (
+            id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            hour_bucket, total_transactions, successful_transactions, failed_transactions,
+            volume_usd, avg_settlement_latency_ms
+        ) VALUES (?, 'key', ?, ?, ?, ?, ?, ?, ?, ?, ?, 400)
+        "#,
+    )
+    .bind(id)
+    .bind(a.0)
+    .bind(a.1)
+    .bind(b.0)
+    .bind(b.1)
+    .bind((Utc::now() - Duration::hours(hours_ago)).to_rfc3339())
+    .bind(total)
+    .bind(ok)
+    .bind(total - ok)
+    .bind(total as f64 * 100.0)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_refresh_builds_corridor_summaries(pool: SqlitePool) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridors (
+            id, source_asset_code, source_asset_issuer,
+            destination_asset_code, destination_asset_issuer, reliability_score
+        ) VALUES ('summary-test', 'USDC', ?, 'EURC', ?, 99.5)
+        "#,
+    )
+    .bind(USDC_ISSUER)
+    .bind(EURC_ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let usdc = ("USDC", USDC_ISSUER);
+    let eurc = ("EURC", EURC_ISSUER);
+    // Hourly rows in either asset order count; rows older than a day don't
+    insert_hourly(&pool, "h1", usdc, eurc, 1, 10, 9).await;
+    insert_hourly(&pool, "h2", eurc, usdc, 2, 10, 10).await;
+    insert_hourly(&pool, "h3", usdc, eurc, 30, 50, 0).await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO liquidity_pools (
+            pool_id, reserve_a_asset_code, reserve_a_asset_issuer,
+            reserve_b_asset_code, reserve_b_asset_issuer, total_value_usd
+        ) VALUES ('pool-1', 'EURC', ?, 'USDC', ?, 25000.0)
+        "#,
+    )
+    .bind(EURC_ISSUER)
+    .bind(USDC_ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account) VALUES ('a1', 'Anchor', ?)")
+        .bind(USDC_ISSUER)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO assets (id, anchor_id, asset_code, asset_issuer) VALUES ('x1', 'a1', 'USDC', ?)",
+    )
+    .bind(USDC_ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let db = Database::new(pool.clone());
+    assert!(db.refresh_corridor_summaries().await.unwrap() >= 1);
+
+    let summaries = db
+        .list_corridor_summaries(100, 0, &TenantScope::All)
+        .await
+        .unwrap();
+    let summary = summaries
+        .iter()
+        .find(|s| s.corridor_id == "summary-test")
+        .expect("corridor summarized");
+    assert_eq!(summary.total_transactions_24h, 20);
+    assert_eq!(summary.successful_transactions_24h, 19);
+    assert_eq!(summary.failed_transactions_24h, 1);
+    assert_eq!(summary.success_rate_24h, 95.0);
+    assert_eq!(summary.volume_usd_24h, 2000.0);
+    assert_eq!(summary.avg_settlement_latency_ms, Some(400));
+    assert_eq!(summary.liquidity_depth_usd, 25000.0);
+    assert_eq!(summary.anchor_count, 1);
+
+    // The corridor list reads the same rows, most reliable first
+    let corridors = db.list_corridors(1, 0).await.unwrap();
+    assert_eq!(
+        corridors[0].to_string_key(),
+        format!("EURC:{}->USDC:{}", EURC_ISSUER, USDC_ISSUER)
+    );
+}
+
+#[sqlx::test]
+async fn test_corridor_route_reads_summaries(pool: SqlitePool) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridors (
+            id, source_asset_code, source_asset_issuer,
+            destination_asset_code, destination_asset_issuer, reliability_score
+        ) VALUES ('route-test', 'USDC', ?, 'EURC', ?, 99.5)
+        "#,
+    )
+    .bind(USDC_ISSUER)
+    .bind(EURC_ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let usdc = ("USDC", USDC_ISSUER);
+    let eurc = ("EURC", EURC_ISSUER);
+    insert_hourly(&pool, "h1", usdc, eurc, 1, 10, 9).await;
+
+    let db = Arc::new(Database::new(pool));
+    let summarized = db.refresh_corridor_summaries().await.unwrap();
+
+    // The `/api/corridors` route as main.rs mounts it
+    let state = (
+        Arc::clone(&db),
+        Arc::new(CacheManager::new(CacheConfig::default()).await.unwrap()),
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+        Arc::new(PriceFeedClient::new(
+            PriceFeedConfig::default(),
+            HashMap::new(),
+        )),
+    );
+    let app = Router::new()
+        .route("/api/corridors", get(list_corridors))
+        .with_state(state)
+        .layer(Extension(TenantScope::All));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/corridors?limit=100")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let corridors: Vec<Value> = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(corridors.len() as u64, summarized);
+    let corridor = corridors
+        .iter()
+        .find(|c| c["id"] == format!("EURC:{}->USDC:{}", EURC_ISSUER, USDC_ISSUER))
+        .expect("summarized corridor listed");
+    assert_eq!(corridor["total_attempts"], 10);
+    assert_eq!(corridor["successful_payments"], 9);
+    assert_eq!(corridor["success_rate"], 90.0);
+    assert_eq!(corridor["liquidity_volume_24h_usd"], 1000.0);
+    assert_eq!(corridor["average_latency_ms"], 400.0);
+    assert_eq!(corridor["source"], "horizon");
+    // Mock RPC payments are issued by `GISSUER..` accounts
+    assert!(corridors
+        .iter()
+        .all(|c| !c["id"].as_str().unwrap().contains("GISSUER")));
+}
@@ -0,0 +1,116 @@
+use axum::async_trait;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::email::outbox::{
+    deliveries_for, delivery_summaries, EmailKind, EmailOutbox,
+};
+use stellar_insights_backend::email::provider::{EmailMessage, EmailProvider, SendError};
+use stellar_insights_backend::email::EmailService;
+
+/// Fails according to the recipient's local part
+struct FakeProvider;
+
+#[async_trait]
+impl EmailProvider for FakeProvider {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    async fn send(&self, message: &EmailMessage) -> Result<Option<String>, SendError> {
+        match message.to.split('@').next() {
+            Some("flaky") => Err(SendError::Transient("421 try again later".into())),
+            Some("gone") => Err(SendError::Bounced("550 mailbox unavailable".into())),
+            _ => Ok(Some(format!("msg-{}", message.to))),
+        }
+    }
+}
+
+async fn setup() -> (Arc<Database>, EmailOutbox) {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+    let db = Arc::new(Database::new(pool));
+    let service = Arc::new(EmailService::new(
+        Arc::new(FakeProvider),
+        "digest@example.com".to_string(),
+    ));
+    let outbox = EmailOutbox::new(Arc::clone(&db), service);
+    (db, outbox)
+}
+
+#[tokio::test]
+async fn test_outbox_tracks_delivery_status_per_digest() {
+    let (db, outbox) = setup().await;
+    for to in ["ok@example.com", "flaky@example.com", "gone@example.com"] {
+        outbox
+            .enqueue(
+                EmailKind::Digest,
+                "weekly-2026-10-12",
+                to,
+                "Weekly",
+                "<p>hi</p>",
+            )
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(outbox.process_due().await.unwrap(), 3);
+    // The flaky message is waiting out its backoff, so nothing is due yet
+    assert_eq!(outbox.process_due().await.unwrap(), 0);
+
+    let deliveries = deliveries_for(&db, EmailKind::Digest, "weekly-2026-10-12")
+        .await
+        .unwrap();
+    let status = |to: &str| {
+        deliveries
+            .iter()
+            .find(|d| d.recipient == to)
+            .map(|d| (d.status.clone(), d.attempts))
+            .unwrap()
+    };
+    assert_eq!(status("ok@example.com"), ("sent".to_string(), 1));
+    assert_eq!(status("flaky@example.com"), ("pending".to_string(), 1));
+    assert_eq!(status("gone@example.com"), ("bounced".to_string(), 1));
+
+    let summaries = delivery_summaries(&db, Some(EmailKind::Digest), 10)
+        .await
+        .unwrap();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].total, 3);
+    assert_eq!(summaries[0].sent, 1);
+    assert_eq!(summaries[0].pending, 1);
+    assert_eq!(summaries[0].bounced, 1);
+}
+
+#[tokio::test]
+async fn test_bounced_recipient_is_suppressed() {
+    let (db, outbox) = setup().await;
+    outbox
+        .enqueue(
+            EmailKind::Alert,
+            "corridor-a@1",
+            "gone@example.com",
+            "Alert",
+            "<p>1</p>",
+        )
+        .await
+        .unwrap();
+    outbox.process_due().await.unwrap();
+
+    outbox
+        .enqueue(
+            EmailKind::Alert,
+            "corridor-a@2",
+            "Gone@Example.com",
+            "Alert",
+            "<p>2</p>",
+        )
+        .await
+        .unwrap();
+    assert_eq!(outbox.process_due().await.unwrap(), 0);
+
+    let deliveries = deliveries_for(&db, EmailKind::Alert, "corridor-a@2")
+        .await
+        .unwrap();
+    assert_eq!(deliveries[0].status, "suppressed");
+}
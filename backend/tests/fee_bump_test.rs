@@ -30,6 +30,7 @@ async fn test_fee_bump_tracker_process_transactions(pool: SqlitePool) {
             max_fee: Some("500".to_string()),
             signatures: vec!["sig1".to_string()],
         }),
+        memo: None,
     };
 
     let tx2 = HorizonTransaction {
@@ -46,6 +47,7 @@ async fn test_fee_bump_tracker_process_transactions(pool: SqlitePool) {
         paging_token: "pt2".to_string(),
         fee_bump_transaction: None,
         inner_transaction: None,
+        memo: None,
     };
 
     let transactions = vec![tx1, tx2];
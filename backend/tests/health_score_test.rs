@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::health_scores::CorridorHealthScore;
+use stellar_insights_backend::services::health_score::{HealthScoreService, HealthWeights};
+
+const KEY: &str = "USDC:GISSUER->XLM:native";
+
+async fn store_hour(
+    pool: &SqlitePool,
+    hour: DateTime<Utc>,
+    transactions: i64,
+    successful: i64,
+    volume_usd: f64,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridor_metrics_hourly (
+            id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            hour_bucket, total_transactions, successful_transactions, failed_transactions,
+            success_rate, volume_usd, liquidity_depth_usd
+        )
+        VALUES ($1, $2, 'USDC', 'GISSUER', 'XLM', 'native', $3, $4, $5, $6, 0, $7, 0)
+        "#,
+    )
+    .bind(hour.to_rfc3339())
+    .bind(KEY)
+    .bind(hour.to_rfc3339())
+    .bind(transactions)
+    .bind(successful)
+    .bind(transactions - successful)
+    .bind(volume_usd)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_run_once_scores_recent_corridors(pool: SqlitePool) {
+    let now = Utc::now();
+    // Steady volume, 90% success in the last day
+    store_hour(&pool, now - Duration::hours(30), 10, 10, 100.0).await;
+    store_hour(&pool, now - Duration::hours(2), 10, 9, 100.0).await;
+    sqlx::query(
+        "INSERT INTO anchors (id, name, stellar_account, reliability_score) \
+         VALUES ('a1', 'Issuer', 'GISSUER', 70.0)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO assets (id, anchor_id, asset_code, asset_issuer) \
+         VALUES ('x1', 'a1', 'USDC', 'GISSUER')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let db = Arc::new(Database::new(pool));
+    // Liquidity left out of the score
+    let weights = HealthWeights {
+        liquidity: 0.0,
+        ..HealthWeights::default()
+    };
+    let service = HealthScoreService::new(Arc::clone(&db), weights);
+    assert_eq!(service.run_once().await.unwrap(), 1);
+
+    let history = db
+        .corridor_health_scores()
+        .history(KEY, now - Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    let point = &history[0];
+    assert_eq!(point.success_rate_score, Some(90.0));
+    assert_eq!(point.volume_trend_score, Some(50.0));
+    assert_eq!(point.anchor_reliability_score, Some(70.0));
+    assert_eq!(point.liquidity_score, Some(0.0));
+    // (0.4 * 90 + 0.15 * 50 + 0.2 * 70) / 0.75
+    assert!((point.score - 57.5 / 0.75).abs() < 1e-9);
+}
+
+#[sqlx::test]
+async fn test_database_weights_override_configured_weights(pool: SqlitePool) {
+    store_hour(&pool, Utc::now() - Duration::hours(1), 4, 3, 10.0).await;
+    for (component, weight) in [("liquidity", 0.0), ("volume_trend", 0.0)] {
+        sqlx::query("INSERT INTO health_score_weights (component, weight) VALUES (?, ?)")
+            .bind(component)
+            .bind(weight)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let db = Arc::new(Database::new(pool));
+    HealthScoreService::new(Arc::clone(&db), HealthWeights::default())
+        .run_once()
+        .await
+        .unwrap();
+
+    // Only the success rate is left: no anchor issues the corridor's assets
+    let latest = db.corridor_health_scores().latest().await.unwrap();
+    assert_eq!(latest, vec![(KEY.to_string(), 75.0)]);
+}
+
+#[sqlx::test]
+async fn test_history_is_oldest_first(pool: SqlitePool) {
+    let store = Database::new(pool).corridor_health_scores();
+    let score = |computed_at: &str, score| CorridorHealthScore {
+        corridor_key: KEY.to_string(),
+        computed_at: computed_at.to_string(),
+        score,
+        ..Default::default()
+    };
+    store
+        .record(&[
+            score("2024-01-02T00:00:00+00:00", 80.0),
+            score("2024-01-01T00:00:00+00:00", 60.0),
+        ])
+        .await
+        .unwrap();
+
+    let since = "2024-01-01T00:00:00Z".parse().unwrap();
+    assert_eq!(
+        store.history(KEY, since).await.unwrap(),
+        vec![
+            score("2024-01-01T00:00:00+00:00", 60.0),
+            score("2024-01-02T00:00:00+00:00", 80.0),
+        ]
+    );
+    assert_eq!(store.latest().await.unwrap(), vec![(KEY.to_string(), 80.0)]);
+}
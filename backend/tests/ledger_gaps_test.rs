@@ -0,0 +1,75 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::db::ledger_gaps::{GapTotals, LedgerGaps};
+
+async fn store_ledgers(pool: &SqlitePool, sequences: &[i64]) {
+    for sequence in sequences {
+        sqlx::query("INSERT INTO ledgers (sequence, hash, close_time) VALUES ($1, $2, $3)")
+            .bind(sequence)
+            .bind(format!("hash_{}", sequence))
+            .bind("2024-01-01T00:00:00Z")
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+}
+
+#[sqlx::test]
+async fn test_scan_finds_missing_ranges(pool: SqlitePool) {
+    store_ledgers(&pool, &[100, 101, 104, 105, 107]).await;
+
+    let gaps = LedgerGaps::new(pool);
+    assert_eq!(gaps.scan().await.unwrap(), vec![(102, 103), (106, 106)]);
+}
+
+#[sqlx::test]
+async fn test_sync_tracks_gaps_until_filled(pool: SqlitePool) {
+    store_ledgers(&pool, &[100, 105, 110]).await;
+    let gaps = LedgerGaps::new(pool.clone());
+
+    assert_eq!(gaps.sync(&gaps.scan().await.unwrap()).await.unwrap(), 2);
+    // Already recorded
+    assert_eq!(gaps.sync(&gaps.scan().await.unwrap()).await.unwrap(), 0);
+    assert_eq!(
+        gaps.totals().await.unwrap(),
+        GapTotals {
+            unresolved_gaps: 2,
+            missing_ledgers: 8,
+        }
+    );
+
+    // A failed backfill stays open and counts against the retry budget
+    let first = gaps.pending(2).await.unwrap().remove(0);
+    assert_eq!((first.start_ledger, first.end_ledger), (101, 104));
+    gaps.record_attempt(first.id, Some("rpc unavailable"))
+        .await
+        .unwrap();
+    gaps.record_attempt(first.id, Some("rpc unavailable"))
+        .await
+        .unwrap();
+    let pending = gaps.pending(2).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].start_ledger, 106);
+
+    // Partly filling a gap replaces it with what is still missing
+    store_ledgers(&pool, &[101, 102]).await;
+    assert_eq!(gaps.sync(&gaps.scan().await.unwrap()).await.unwrap(), 1);
+    let open = gaps.list(false).await.unwrap();
+    let ranges: Vec<_> = open
+        .iter()
+        .map(|g| (g.start_ledger, g.end_ledger))
+        .collect();
+    assert_eq!(ranges, vec![(103, 104), (106, 109)]);
+    assert_eq!(open[0].attempts, 0);
+
+    // A successful backfill resolves the gap
+    gaps.record_attempt(open[1].id, None).await.unwrap();
+    store_ledgers(&pool, &[103, 104, 106, 107, 108, 109]).await;
+    assert_eq!(gaps.sync(&gaps.scan().await.unwrap()).await.unwrap(), 0);
+    assert_eq!(gaps.totals().await.unwrap(), GapTotals::default());
+    assert!(gaps
+        .list(true)
+        .await
+        .unwrap()
+        .iter()
+        .all(|g| g.resolved_at.is_some()));
+}
@@ -0,0 +1,107 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::put,
+    Extension, Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::util::ServiceExt;
+
+use stellar_insights_backend::config::Settings;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::handlers::update_anchor_metrics;
+use stellar_insights_backend::ingestion::DataIngestionService;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::state::AppState;
+use stellar_insights_backend::tenant::TenantScope;
+use stellar_insights_backend::websocket::WsState;
+
+fn router(db: Arc<Database>) -> Router {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let ingestion = Arc::new(DataIngestionService::new(rpc_client, Arc::clone(&db)));
+    let state = AppState {
+        db,
+        ws_state: Arc::new(WsState::new()),
+        ingestion,
+        settings: Arc::new(Settings::default()),
+    };
+    Router::new()
+        .route("/api/anchors/:id/metrics", put(update_anchor_metrics))
+        .with_state(state)
+        .layer(Extension(TenantScope::All))
+}
+
+fn metrics_request(id: &str, if_match: Option<&str>, body: Value) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/anchors/{}/metrics", id))
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(tag) = if_match {
+        builder = builder.header(header::IF_MATCH, tag);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+async fn json_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[sqlx::test]
+async fn test_stale_version_is_rejected_with_current_state(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Test Anchor".to_string(),
+            stellar_account: "GTESTANCHOR".to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(anchor.version, 1);
+    let app = router(db);
+
+    let update = json!({
+        "total_transactions": 100,
+        "successful_transactions": 99,
+        "failed_transactions": 1
+    });
+
+    // First editor wins and gets the new version back
+    let response = app
+        .clone()
+        .oneshot(metrics_request(&anchor.id, Some("\"1\""), update.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ETAG], "\"2\"");
+    assert_eq!(json_body(response).await["version"], 2);
+
+    // Second editor read version 1 too and must not overwrite
+    let mut stale = update.clone();
+    stale["version"] = json!(1);
+    stale["total_transactions"] = json!(5);
+    let response = app
+        .clone()
+        .oneshot(metrics_request(&anchor.id, None, stale))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = json_body(response).await;
+    assert_eq!(body["error"]["code"], "VERSION_CONFLICT");
+    assert_eq!(body["error"]["details"]["current_version"], 2);
+    assert_eq!(body["error"]["details"]["current"]["total_transactions"], 100);
+
+    // Without a precondition the update is unconditional
+    let response = app
+        .oneshot(metrics_request(&anchor.id, None, update))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["version"], 3);
+}
@@ -0,0 +1,144 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::crypto::{lookup_hash, FieldCipher};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::jobs::reencrypt::PiiReencryptor;
+
+const OLD_KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const NEW_KEY: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+
+async fn setup() -> SqlitePool {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+    sqlx::query("INSERT INTO users (id, username) VALUES ('u1', 'alice'), ('u2', 'bob')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool
+}
+
+async fn stored_email(pool: &SqlitePool, user_id: &str) -> String {
+    sqlx::query_scalar("SELECT notification_email FROM user_preferences WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_reencrypts_plaintext_and_retired_key_values() {
+    let pool = setup().await;
+    let old = FieldCipher::new(OLD_KEY, vec![]);
+    let rotated = FieldCipher::new(NEW_KEY, vec![OLD_KEY.to_string()]);
+
+    sqlx::query(
+        "INSERT INTO user_preferences (user_id, notification_email) VALUES ('u1', 'alice@example.com'), ('u2', ?)",
+    )
+    .bind(old.encrypt("bob@example.com").unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let reencryptor = PiiReencryptor::new(pool.clone(), rotated.clone());
+    let result = reencryptor.run_once().await.unwrap();
+    assert_eq!(result.values_rewritten, 2);
+    assert_eq!(result.failures, 0);
+
+    let alice = stored_email(&pool, "u1").await;
+    let bob = stored_email(&pool, "u2").await;
+    assert!(!rotated.needs_rotation(&alice));
+    assert!(!rotated.needs_rotation(&bob));
+    assert_eq!(rotated.decrypt(&alice).unwrap(), "alice@example.com");
+    assert_eq!(
+        FieldCipher::new(NEW_KEY, vec![]).decrypt(&bob).unwrap(),
+        "bob@example.com"
+    );
+
+    // Nothing left to do on the next pass
+    assert_eq!(reencryptor.run_once().await.unwrap().values_rewritten, 0);
+}
+
+#[tokio::test]
+async fn test_reencrypts_sep_auth_tokens() {
+    let pool = setup().await;
+    let old = Database::new(pool.clone()).with_cipher(FieldCipher::new(OLD_KEY, vec![]));
+    old.sep_transactions()
+        .save_anchor_session("u1", "https://anchor.example/sep24", "session-token", None)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO sep_transactions (id, user_id, protocol, kind, transfer_server,
+             anchor_transaction_id, status, auth_token, created_at, updated_at)
+         VALUES ('tx-1', 'u1', 'sep24', 'deposit', 'https://anchor.example/sep24',
+             'a-1', 'pending_user_transfer_start', ?, '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z')",
+    )
+    .bind(old.cipher().encrypt("poll-token").unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let rotated = FieldCipher::new(NEW_KEY, vec![OLD_KEY.to_string()]);
+    let result = PiiReencryptor::new(pool.clone(), rotated)
+        .run_once()
+        .await
+        .unwrap();
+    assert_eq!(result.values_rewritten, 2);
+
+    // Readable once the old key is dropped
+    let new = Database::new(pool.clone()).with_cipher(FieldCipher::new(NEW_KEY, vec![]));
+    let store = new.sep_transactions();
+    assert_eq!(
+        store
+            .anchor_session("u1", "https://anchor.example/sep24")
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("session-token")
+    );
+    let due = store.due_for_poll(10).await.unwrap();
+    assert_eq!(due[0].auth_token.as_deref(), Some("poll-token"));
+}
+
+#[tokio::test]
+async fn test_backfills_oauth_token_lookup_hash() {
+    let pool = setup().await;
+    let cipher = FieldCipher::new(NEW_KEY, vec![]);
+
+    sqlx::query(
+        "INSERT INTO oauth_tokens (id, user_id, access_token, refresh_token, expires_at)
+         VALUES ('t1', 'u1', ?, ?, '2099-01-01T00:00:00Z')",
+    )
+    .bind(cipher.encrypt("access-token").unwrap())
+    .bind(cipher.encrypt("refresh-token").unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    PiiReencryptor::new(pool.clone(), cipher)
+        .run_once()
+        .await
+        .unwrap();
+
+    let hash: String =
+        sqlx::query_scalar("SELECT access_token_hash FROM oauth_tokens WHERE id = 't1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(hash, lookup_hash("access-token"));
+}
+
+#[tokio::test]
+async fn test_preferences_read_plaintext_rows_from_before_encryption() {
+    let pool = setup().await;
+    sqlx::query(
+        "INSERT INTO user_preferences (user_id, notification_email, digest_frequency) VALUES ('u1', 'alice@example.com', 'weekly')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let prefs = Database::new(pool).preferences().get("u1").await.unwrap();
+    assert_eq!(
+        prefs.notifications.email.as_deref(),
+        Some("alice@example.com")
+    );
+}
@@ -0,0 +1,17 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::plan::Plan;
+
+#[sqlx::test]
+async fn test_tenants_start_on_free_plan(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let tenant = db.tenants().create("acme").await.unwrap();
+    assert_eq!(tenant.plan, "free");
+    assert_eq!(db.tenants().plan(&tenant.id).await.unwrap(), Plan::Free);
+
+    assert!(db.tenants().set_plan(&tenant.id, Plan::Pro).await.unwrap());
+    assert_eq!(db.tenants().plan(&tenant.id).await.unwrap(), Plan::Pro);
+
+    assert!(!db.tenants().set_plan("missing", Plan::Pro).await.unwrap());
+    assert_eq!(db.tenants().plan("missing").await.unwrap(), Plan::Free);
+}
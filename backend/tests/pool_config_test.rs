@@ -90,6 +90,7 @@ async fn test_pool_creation() {
         connect_timeout_seconds: 10,
         idle_timeout_seconds: 300,
         max_lifetime_seconds: 900,
+        slow_query_threshold_ms: 500,
     };
 
     // Use in-memory SQLite for testing
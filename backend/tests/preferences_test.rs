@@ -0,0 +1,62 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::i18n::Locale;
+use stellar_insights_backend::preferences::{
+    DigestFrequency, NotificationSettings, UserPreferences,
+};
+
+async fn create_user(pool: &SqlitePool, id: &str) {
+    sqlx::query("INSERT INTO users (id, username) VALUES ($1, $2)")
+        .bind(id)
+        .bind(format!("user-{}", id))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test]
+async fn test_preferences_default_then_roundtrip(pool: SqlitePool) {
+    create_user(&pool, "u1").await;
+    let db = Database::new(pool.clone());
+    let store = db.preferences();
+
+    assert_eq!(store.get("u1").await.unwrap(), UserPreferences::default());
+
+    let prefs = UserPreferences {
+        timezone: "America/Sao_Paulo".to_string(),
+        base_currency: "EUR".to_string(),
+        locale: Locale::Es,
+        notifications: NotificationSettings {
+            email: Some("ops@example.com".to_string()),
+            digest_frequency: DigestFrequency::Weekly,
+            alerts: false,
+        },
+        ..UserPreferences::default()
+    }
+    .validate()
+    .unwrap();
+
+    store.upsert("u1", &prefs).await.unwrap();
+    assert_eq!(store.get("u1").await.unwrap(), prefs);
+
+    let stored: String =
+        sqlx::query_scalar("SELECT notification_email FROM user_preferences WHERE user_id = 'u1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!stored.contains("ops@example.com"));
+
+    let weekly = store
+        .digest_subscribers(DigestFrequency::Weekly)
+        .await
+        .unwrap();
+    assert_eq!(weekly.len(), 1);
+    assert_eq!(weekly[0].email, "ops@example.com");
+    assert_eq!(weekly[0].locale, "es");
+
+    assert!(store
+        .digest_subscribers(DigestFrequency::Monthly)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,103 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::search::{ParsedSearchQuery, TaggableEntity};
+use stellar_insights_backend::tenant::TenantScope;
+
+const USDC_EURC_CORRIDOR: &str = "d1d1d1d1-1111-4111-a111-111111111111";
+const CIRCLE_ANCHOR: &str = "c1b1f1a1-1111-4111-a111-111111111111";
+
+#[sqlx::test]
+async fn test_search_corridors_by_asset_and_tag(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let search = db.entity_search();
+
+    search
+        .add_tags(
+            TaggableEntity::Corridor,
+            USDC_EURC_CORRIDOR,
+            &["latam".to_string()],
+            "tester",
+        )
+        .await
+        .unwrap();
+
+    let hits = search
+        .search(
+            &ParsedSearchQuery::parse("EURC corridors in LATAM"),
+            &TenantScope::All,
+            10,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].entity_type, "corridor");
+    assert_eq!(hits[0].entity_id, USDC_EURC_CORRIDOR);
+    assert_eq!(hits[0].tags, "latam");
+}
+
+#[sqlx::test]
+async fn test_search_anchor_by_prefix_and_tag_removal(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let search = db.entity_search();
+
+    let hits = search
+        .search(&ParsedSearchQuery::parse("circ"), &TenantScope::All, 10)
+        .await
+        .unwrap();
+    assert!(hits
+        .iter()
+        .any(|h| h.entity_type == "anchor" && h.title == "Circle"));
+
+    search
+        .add_tags(
+            TaggableEntity::Corridor,
+            USDC_EURC_CORRIDOR,
+            &["europe".to_string()],
+            "tester",
+        )
+        .await
+        .unwrap();
+    assert!(search
+        .remove_tag(TaggableEntity::Corridor, USDC_EURC_CORRIDOR, "europe")
+        .await
+        .unwrap());
+
+    let hits = search
+        .search(&ParsedSearchQuery::parse("europe"), &TenantScope::All, 10)
+        .await
+        .unwrap();
+    assert!(hits.is_empty());
+}
+
+#[sqlx::test]
+async fn test_search_is_scoped_to_tenant(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let acme = TenantScope::Tenant(db.tenants().create("acme").await.unwrap().id);
+    let globex = TenantScope::Tenant(db.tenants().create("globex").await.unwrap().id);
+    db.tenants().add_anchor(&acme, CIRCLE_ANCHOR).await.unwrap();
+
+    let search = db.entity_search();
+    let query = ParsedSearchQuery::parse("circ");
+
+    let hits = search.search(&query, &acme, 10).await.unwrap();
+    assert!(hits.iter().any(|h| h.entity_id == CIRCLE_ANCHOR));
+    assert!(search.search(&query, &globex, 10).await.unwrap().is_empty());
+
+    let query = ParsedSearchQuery::parse("EURC corridors");
+    assert!(search.search(&query, &acme, 10).await.unwrap().is_empty());
+    assert!(!search
+        .search(&query, &TenantScope::All, 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    assert!(search
+        .entity_exists(TaggableEntity::Anchor, CIRCLE_ANCHOR, &acme)
+        .await
+        .unwrap());
+    assert!(!search
+        .entity_exists(TaggableEntity::Anchor, CIRCLE_ANCHOR, &globex)
+        .await
+        .unwrap());
+}
@@ -0,0 +1,244 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::sep_transactions::{NewSepTransaction, StatusUpdate};
+use stellar_insights_backend::services::sep_transactions::SepTransactionTracker;
+use stellar_insights_backend::tenant::TenantScope;
+use stellar_insights_backend::webhooks::{CreateWebhookRequest, WebhookService};
+use stellar_insights_backend::websocket::{WsIdentity, WsState};
+
+async fn create_user(pool: &SqlitePool, id: &str) {
+    sqlx::query("INSERT INTO users (id, username) VALUES ($1, $2)")
+        .bind(id)
+        .bind(format!("user-{}", id))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn insert_transaction(pool: &SqlitePool, id: &str, user_id: &str, anchor_tx_id: &str) {
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        INSERT INTO sep_transactions (
+            id, user_id, anchor_id, protocol, kind, transfer_server, anchor_transaction_id,
+            status, created_at, updated_at
+        )
+        VALUES ($1, $2, NULL, 'sep24', 'deposit', 'https://anchor.example.com/sep24', $3,
+                'pending_user_transfer_start', $4, $4)
+        "#,
+    )
+    .bind(id)
+    .bind(user_id)
+    .bind(anchor_tx_id)
+    .bind(now)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn webhooks(pool: &SqlitePool) -> WebhookService {
+    WebhookService::new(pool.clone(), "00".repeat(32))
+}
+
+async fn webhook_event_count(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM webhook_events WHERE event_type = 'sep.transaction_status_changed'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_status_change_notifies_subscribed_webhooks(pool: SqlitePool) {
+    create_user(&pool, "u1").await;
+    insert_transaction(&pool, "tx-1", "u1", "anchor-tx-1").await;
+
+    webhooks(&pool)
+        .register_webhook(
+            "u1",
+            &TenantScope::All,
+            CreateWebhookRequest {
+                url: "https://hooks.example.com/sep".to_string(),
+                event_types: vec!["sep.transaction_status_changed".to_string()],
+                filters: None,
+                delivery: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let db = Arc::new(Database::new(pool.clone()));
+    let tracker = SepTransactionTracker::new(
+        Arc::clone(&db),
+        Arc::new(WsState::new()),
+        webhooks(&pool),
+    );
+    let tx = db.sep_transactions().get("tx-1").await.unwrap().unwrap();
+
+    let update = tracker
+        .record_status(&tx, "completed", None, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(
+        update,
+        StatusUpdate::Changed {
+            previous: "pending_user_transfer_start".to_string()
+        }
+    );
+    assert_eq!(webhook_event_count(&pool).await, 1);
+
+    let stored = db.sep_transactions().get("tx-1").await.unwrap().unwrap();
+    assert_eq!(stored.status, "completed");
+    assert!(stored.completed_at.is_some());
+
+    // Terminal transactions are not reopened by late callbacks
+    let late = tracker
+        .record_status(&stored, "pending_anchor", None, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(late, StatusUpdate::AlreadyTerminal);
+    assert_eq!(webhook_event_count(&pool).await, 1);
+}
+
+fn new_transaction(user_id: &str, anchor_tx_id: &str, auth_token: Option<&str>) -> NewSepTransaction {
+    NewSepTransaction {
+        user_id: user_id.to_string(),
+        anchor_id: None,
+        protocol: "sep24".to_string(),
+        kind: "deposit".to_string(),
+        transfer_server: "https://anchor.example.com/sep24".to_string(),
+        anchor_transaction_id: anchor_tx_id.to_string(),
+        asset_code: Some("USDC".to_string()),
+        amount: Some("100".to_string()),
+        status: "incomplete".to_string(),
+        auth_token: auth_token.map(str::to_string),
+    }
+}
+
+#[sqlx::test]
+async fn test_create_is_idempotent_and_lists_per_user(pool: SqlitePool) {
+    create_user(&pool, "u1").await;
+    create_user(&pool, "u2").await;
+    let db = Database::new(pool.clone());
+    let store = db.sep_transactions();
+
+    let first = store.create(new_transaction("u1", "a-1", None)).await.unwrap();
+    let again = store.create(new_transaction("u1", "a-1", None)).await.unwrap();
+    assert_eq!(first.id, again.id);
+    store.create(new_transaction("u1", "a-2", None)).await.unwrap();
+    store.create(new_transaction("u2", "a-3", None)).await.unwrap();
+
+    let u1 = store.list_for_user("u1", None, 50, 0).await.unwrap();
+    assert_eq!(u1.len(), 2);
+    assert!(u1.iter().all(|tx| tx.user_id == "u1"));
+
+    let completed = store.list_for_user("u1", Some("completed"), 50, 0).await.unwrap();
+    assert!(completed.is_empty());
+}
+
+#[sqlx::test]
+async fn test_due_for_poll_decrypts_token_and_backs_off(pool: SqlitePool) {
+    create_user(&pool, "u1").await;
+    let db = Database::new(pool.clone());
+    let store = db.sep_transactions();
+
+    let tx = store
+        .create(new_transaction("u1", "a-1", Some("sep10-token")))
+        .await
+        .unwrap();
+    let stored: Option<String> = sqlx::query_scalar("SELECT auth_token FROM sep_transactions WHERE id = $1")
+        .bind(&tx.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_ne!(stored.as_deref(), Some("sep10-token"));
+
+    let due = store.due_for_poll(10).await.unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].auth_token.as_deref(), Some("sep10-token"));
+
+    // Just polled: not due again until the interval elapses
+    store.record_poll(&tx.id, false).await.unwrap();
+    assert!(store.due_for_poll(10).await.unwrap().is_empty());
+
+    // Terminal transactions are never polled
+    sqlx::query("UPDATE sep_transactions SET last_polled_at = NULL WHERE id = $1")
+        .bind(&tx.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    store
+        .apply_status(&tx, "completed", None, Utc::now())
+        .await
+        .unwrap();
+    assert!(store.due_for_poll(10).await.unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn test_anchor_sessions_are_encrypted_and_expire(pool: SqlitePool) {
+    create_user(&pool, "u1").await;
+    let db = Database::new(pool.clone());
+    let store = db.sep_transactions();
+    let server = "https://anchor.example.com/sep24";
+
+    store
+        .save_anchor_session("u1", server, "sep10-token", None)
+        .await
+        .unwrap();
+    let stored: String = sqlx::query_scalar("SELECT auth_token FROM sep_anchor_sessions WHERE user_id = 'u1'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_ne!(stored, "sep10-token");
+    assert_eq!(
+        store.anchor_session("u1", server).await.unwrap().as_deref(),
+        Some("sep10-token")
+    );
+
+    // An expired token is not returned
+    store
+        .save_anchor_session("u1", server, "old-token", Some(Utc::now() - chrono::Duration::minutes(1)))
+        .await
+        .unwrap();
+    assert!(store.anchor_session("u1", server).await.unwrap().is_none());
+
+    assert!(store.delete_anchor_session("u1", server).await.unwrap());
+    assert!(!store.delete_anchor_session("u1", server).await.unwrap());
+}
+
+#[sqlx::test]
+async fn test_only_owner_may_follow_transaction_channel(pool: SqlitePool) {
+    create_user(&pool, "u1").await;
+    create_user(&pool, "u2").await;
+    insert_transaction(&pool, "tx-1", "u1", "anchor-tx-1").await;
+
+    let ws_state = WsState::new().with_database(Arc::new(Database::new(pool)));
+    let user = |user_id: &str, admin| WsIdentity::User {
+        user_id: user_id.to_string(),
+        username: format!("user-{}", user_id),
+        admin,
+    };
+    let channels = || {
+        vec![
+            "sep_transaction:tx-1".to_string(),
+            "sep_transaction:missing".to_string(),
+        ]
+    };
+
+    let (allowed, denied) = ws_state.authorize(&user("u1", false), channels()).await;
+    assert_eq!(allowed, vec!["sep_transaction:tx-1"]);
+    assert_eq!(denied, vec!["sep_transaction:missing"]);
+
+    let (allowed, denied) = ws_state.authorize(&user("u2", false), channels()).await;
+    assert!(allowed.is_empty());
+    assert_eq!(denied, channels());
+
+    let (allowed, _) = ws_state.authorize(&WsIdentity::Anonymous, channels()).await;
+    assert!(allowed.is_empty());
+
+    for identity in [user("u2", true), WsIdentity::Service] {
+        let (allowed, _) = ws_state.authorize(&identity, channels()).await;
+        assert_eq!(allowed, channels());
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::sessions::ClientInfo;
+
+fn firefox() -> ClientInfo {
+    ClientInfo {
+        user_agent: Some(
+            "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0".to_string(),
+        ),
+        ip_address: Some("203.0.113.7".to_string()),
+    }
+}
+
+#[sqlx::test]
+async fn test_sessions_are_listed_and_revoked_individually(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let sessions = db.sessions();
+    let expires_at = Utc::now() + Duration::days(7);
+
+    sessions
+        .create("s1", "u1", "refresh-1", &firefox(), expires_at)
+        .await
+        .unwrap();
+    sessions
+        .create("s2", "u1", "refresh-2", &ClientInfo::default(), expires_at)
+        .await
+        .unwrap();
+    sessions
+        .create("s3", "u2", "refresh-3", &firefox(), expires_at)
+        .await
+        .unwrap();
+
+    let active = sessions.list_active("u1").await.unwrap();
+    assert_eq!(active.len(), 2);
+    let s1 = active.iter().find(|s| s.id == "s1").unwrap();
+    assert_eq!(s1.device, "Firefox on Linux");
+    assert_eq!(s1.ip_address.as_deref(), Some("203.0.113.7"));
+
+    // The refresh token must match the session it was issued for
+    assert!(sessions
+        .find_active("s1", "refresh-1")
+        .await
+        .unwrap()
+        .is_some());
+    assert!(sessions
+        .find_active("s1", "refresh-2")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Users can't revoke each other's sessions
+    assert!(!sessions.revoke("u1", "s3").await.unwrap());
+
+    assert!(sessions.revoke("u1", "s1").await.unwrap());
+    assert!(!sessions.revoke("u1", "s1").await.unwrap());
+    assert!(sessions
+        .find_active("s1", "refresh-1")
+        .await
+        .unwrap()
+        .is_none());
+
+    let active: Vec<String> = sessions
+        .list_active("u1")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    assert_eq!(active, vec!["s2"]);
+}
+
+#[sqlx::test]
+async fn test_expired_sessions_are_not_active(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let sessions = db.sessions();
+
+    sessions
+        .create(
+            "old",
+            "u1",
+            "refresh-old",
+            &firefox(),
+            Utc::now() - Duration::minutes(1),
+        )
+        .await
+        .unwrap();
+
+    assert!(sessions.list_active("u1").await.unwrap().is_empty());
+    assert!(sessions
+        .find_active("old", "refresh-old")
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,152 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::db::settlements::PaymentSettlements;
+use stellar_insights_backend::rpc::{HorizonTransaction, Payment};
+use stellar_insights_backend::services::settlement_times::{SettlementTimes, SettlementTracker};
+
+const KEY: &str = "USDC:GISSUER->XLM:native";
+
+async fn store_ledger(pool: &SqlitePool, sequence: i64, close_time: &str) {
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count) VALUES (?, ?, ?, 0, 0)",
+    )
+    .bind(sequence)
+    .bind(format!("ledger_hash_{}", sequence))
+    .bind(close_time)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn transaction(
+    hash: &str,
+    ledger: u64,
+    created_at: &str,
+    successful: bool,
+    memo: Option<&str>,
+) -> HorizonTransaction {
+    HorizonTransaction {
+        id: hash.to_string(),
+        hash: hash.to_string(),
+        ledger,
+        created_at: created_at.to_string(),
+        source_account: "GSENDER".to_string(),
+        fee_account: None,
+        fee_charged: Some("100".to_string()),
+        max_fee: Some("100".to_string()),
+        operation_count: 1,
+        successful,
+        paging_token: hash.to_string(),
+        fee_bump_transaction: None,
+        inner_transaction: None,
+        memo: memo.map(str::to_string),
+    }
+}
+
+fn path_payment(transaction_hash: &str, created_at: &str) -> Payment {
+    Payment {
+        id: transaction_hash.to_string(),
+        paging_token: transaction_hash.to_string(),
+        transaction_hash: transaction_hash.to_string(),
+        source_account: "GSENDER".to_string(),
+        destination: "GRECEIVER".to_string(),
+        asset_type: "native".to_string(),
+        asset_code: None,
+        asset_issuer: None,
+        amount: "100.0".to_string(),
+        created_at: created_at.to_string(),
+        operation_type: Some("path_payment_strict_send".to_string()),
+        source_asset_type: Some("credit_alphanum4".to_string()),
+        source_asset_code: Some("USDC".to_string()),
+        source_asset_issuer: Some("GISSUER".to_string()),
+        source_amount: Some("10.0".to_string()),
+        from: Some("GSENDER".to_string()),
+        to: Some("GRECEIVER".to_string()),
+    }
+}
+
+#[sqlx::test]
+async fn test_retried_path_payment_settles_from_first_attempt(pool: SqlitePool) {
+    store_ledger(&pool, 99, "2026-01-22T10:00:00Z").await;
+    store_ledger(&pool, 100, "2026-01-22T10:00:05Z").await;
+    store_ledger(&pool, 101, "2026-01-22T10:00:10Z").await;
+    let tracker = SettlementTracker::new(pool.clone());
+
+    // The first attempt fails
+    let settled = tracker
+        .process_ledger(
+            100,
+            &[transaction(
+                "tx_failed",
+                100,
+                "2026-01-22T10:00:05Z",
+                false,
+                Some("invoice-7"),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(settled, 0);
+
+    // The retry settles alongside a first-time payment without a memo
+    let settled = tracker
+        .process_ledger(
+            101,
+            &[
+                transaction(
+                    "tx_retry",
+                    101,
+                    "2026-01-22T10:00:10Z",
+                    true,
+                    Some("invoice-7"),
+                ),
+                transaction("tx_single", 101, "2026-01-22T10:00:10Z", true, None),
+            ],
+            &[
+                path_payment("tx_retry", "2026-01-22T10:00:10Z"),
+                path_payment("tx_single", "2026-01-22T10:00:10Z"),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(settled, 2);
+
+    let since = chrono::DateTime::parse_from_rfc3339("2026-01-22T00:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let mut samples = PaymentSettlements::new(pool.clone())
+        .samples(KEY, since)
+        .await
+        .unwrap();
+    samples.sort();
+    assert_eq!(samples, vec![(5_000, 1), (10_000, 2)]);
+
+    let times = SettlementTimes::from_samples(&samples).unwrap();
+    assert_eq!(times.payments, 2);
+    assert_eq!(times.retried_share, 0.5);
+    assert_eq!(times.p50_ms, 5_000);
+    assert_eq!(times.p99_ms, 10_000);
+
+    // Nothing to measure from without the previous ledger
+    let settled = tracker
+        .process_ledger(
+            500,
+            &[transaction(
+                "tx_late",
+                500,
+                "2026-01-22T11:00:00Z",
+                true,
+                None,
+            )],
+            &[path_payment("tx_late", "2026-01-22T11:00:00Z")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(settled, 0);
+    assert!(PaymentSettlements::new(pool)
+        .samples(KEY, since + Duration::hours(10) + Duration::minutes(30))
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use stellar_insights_backend::database::PoolConfig;
+use stellar_insights_backend::observability::slow_queries::{SlowQueryLayer, SlowQueryTracker};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::test]
+async fn slow_statements_are_tracked_with_literals_redacted() {
+    let tracker = Arc::new(SlowQueryTracker::new(10, Duration::ZERO));
+    // sqlx logs from its worker thread, so the subscriber has to be global
+    tracing_subscriber::registry()
+        .with(SlowQueryLayer::new(Arc::clone(&tracker)))
+        .init();
+
+    let config = PoolConfig {
+        slow_query_threshold_ms: 0,
+        ..PoolConfig::default()
+    };
+    let pool = config.create_pool("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE accounts (id TEXT PRIMARY KEY, balance REAL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO accounts (id, balance) VALUES ('GSECRET', 250.5)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("SELECT balance FROM accounts WHERE id = $1")
+        .bind("GSECRET")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+    let top = tracker.top();
+    let insert = top
+        .iter()
+        .find(|q| q.label == "insert accounts")
+        .expect("insert tracked");
+    assert_eq!(
+        insert.statement,
+        "INSERT INTO accounts (id, balance) VALUES (?, ?)"
+    );
+    let select = top
+        .iter()
+        .find(|q| q.label == "select accounts")
+        .expect("select tracked");
+    assert_eq!(
+        select.statement,
+        "SELECT balance FROM accounts WHERE id = $1"
+    );
+    assert!(top.iter().all(|q| !q.statement.contains("GSECRET")));
+}
@@ -0,0 +1,108 @@
+use chrono::{Duration, NaiveDate, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::status::{Component, ComponentStatus, StatusProbe};
+
+fn probe(component: Component, status: ComponentStatus, minutes_ago: i64) -> StatusProbe {
+    StatusProbe {
+        component,
+        status,
+        latency_ms: None,
+        message: None,
+        checked_at: Utc::now() - Duration::minutes(minutes_ago),
+    }
+}
+
+#[sqlx::test]
+async fn test_latest_probe_and_uptime(pool: SqlitePool) {
+    let store = Database::new(pool).status();
+    store
+        .record_probe(&probe(Component::Rpc, ComponentStatus::Operational, 3))
+        .await
+        .unwrap();
+    store
+        .record_probe(&probe(Component::Rpc, ComponentStatus::Outage, 2))
+        .await
+        .unwrap();
+    store
+        .record_probe(&probe(Component::Rpc, ComponentStatus::Degraded, 1))
+        .await
+        .unwrap();
+
+    let latest = store.latest_probe(Component::Rpc).await.unwrap().unwrap();
+    assert_eq!(latest.status, ComponentStatus::Degraded);
+
+    let since = Utc::now() - Duration::days(90);
+    let uptime = store.uptime_percent(Component::Rpc, since).await.unwrap().unwrap();
+    assert!((uptime - 200.0 / 3.0).abs() < 1e-9);
+    assert!(store
+        .uptime_percent(Component::Ingestion, since)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[sqlx::test]
+async fn test_incident_lifecycle(pool: SqlitePool) {
+    let store = Database::new(pool).status();
+    let started = Utc::now() - Duration::minutes(10);
+    let id = store
+        .open_new_incident(
+            Component::WebhookDispatch,
+            ComponentStatus::Degraded,
+            "Webhook delivery degraded",
+            started,
+        )
+        .await
+        .unwrap();
+
+    let open = store.open_incident(Component::WebhookDispatch).await.unwrap().unwrap();
+    assert_eq!(open.id, id);
+
+    store.resolve_incident(&id, Utc::now()).await.unwrap();
+    assert!(store
+        .open_incident(Component::WebhookDispatch)
+        .await
+        .unwrap()
+        .is_none());
+
+    let recent = store
+        .incidents_since(Utc::now() - Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(recent.len(), 1);
+    assert!(recent[0].resolved_at.is_some());
+}
+
+#[sqlx::test]
+async fn test_corridor_days_window(pool: SqlitePool) {
+    for (date, rate) in [("2024-01-01", 90.0), ("2024-06-01", 99.5), ("2024-06-02", 97.0)] {
+        sqlx::query(
+            r#"
+            INSERT INTO corridor_metrics (
+                corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                date, total_transactions, successful_transactions, failed_transactions,
+                success_rate, volume_usd
+            )
+            VALUES ('USDC:issuer->EURC:issuer', 'USDC', 'issuer', 'EURC', 'issuer', $1, 100, 0, 0, $2, 1000.0)
+            "#,
+        )
+        .bind(date)
+        .bind(rate)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let store = Database::new(pool).status();
+    let since = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let days = store.corridor_days(since, 10, None).await.unwrap();
+
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0].date, "2024-06-01");
+
+    // A tenant only sees its own corridors
+    let other = vec!["XLM:native->USDC:issuer".to_string()];
+    let days = store.corridor_days(since, 10, Some(&other)).await.unwrap();
+    assert!(days.is_empty());
+}
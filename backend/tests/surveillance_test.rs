@@ -0,0 +1,93 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::surveillance::{NewSurveillanceFlag, ReviewStatus};
+
+const KEY: &str = "USDC:GISSUER->XLM:native";
+
+fn round_trip(window_start: &str, window_end: &str) -> NewSurveillanceFlag {
+    NewSurveillanceFlag {
+        corridor_key: KEY.to_string(),
+        pattern: "round_trip",
+        accounts: vec!["GA".to_string(), "GB".to_string()],
+        trade_count: 3,
+        volume: 300.0,
+        window_start: window_start.to_string(),
+        window_end: window_end.to_string(),
+    }
+}
+
+#[sqlx::test]
+async fn test_overlapping_windows_are_flagged_once(pool: SqlitePool) {
+    let flags = Database::new(pool).surveillance_flags();
+
+    let stored = flags
+        .record(&[round_trip(
+            "2024-01-01T00:00:00+00:00",
+            "2024-01-02T00:00:00+00:00",
+        )])
+        .await
+        .unwrap();
+    assert_eq!(stored, 1);
+
+    // An hour later the window overlaps; the next day's does not
+    let stored = flags
+        .record(&[
+            round_trip("2024-01-01T01:00:00+00:00", "2024-01-02T01:00:00+00:00"),
+            round_trip("2024-01-02T00:00:00+00:00", "2024-01-03T00:00:00+00:00"),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(stored, 1);
+
+    let all = flags.list(None, 10).await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].accounts, "GA,GB");
+    assert_eq!(all[0].status, "open");
+}
+
+#[sqlx::test]
+async fn test_review_records_decision(pool: SqlitePool) {
+    let flags = Database::new(pool).surveillance_flags();
+    flags
+        .record(&[round_trip(
+            "2024-01-01T00:00:00+00:00",
+            "2024-01-02T00:00:00+00:00",
+        )])
+        .await
+        .unwrap();
+    let id = flags.list(None, 10).await.unwrap()[0].id;
+
+    let reviewed = flags
+        .review(
+            id,
+            ReviewStatus::Dismissed,
+            "compliance",
+            Some("Known market maker"),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reviewed.status, "dismissed");
+    assert_eq!(reviewed.reviewed_by.as_deref(), Some("compliance"));
+    assert_eq!(reviewed.review_note.as_deref(), Some("Known market maker"));
+    assert!(reviewed.reviewed_at.is_some());
+
+    assert!(flags
+        .list(Some(ReviewStatus::Open), 10)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        flags
+            .list(Some(ReviewStatus::Dismissed), 10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(flags
+        .review(id + 1, ReviewStatus::Confirmed, "compliance", None)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,284 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::{get, put},
+    Extension, Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+use stellar_insights_backend::config::Settings;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::handlers::{
+    create_anchor_asset, get_anchor_assets, update_anchor_metrics,
+    update_corridor_metrics_from_transactions,
+};
+use stellar_insights_backend::ingestion::DataIngestionService;
+use stellar_insights_backend::models::api_key::CreateApiKeyRequest;
+use stellar_insights_backend::models::{CreateAnchorRequest, CreateCorridorRequest};
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::state::AppState;
+use stellar_insights_backend::tenant::TenantScope;
+use stellar_insights_backend::websocket::WsState;
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+const EURC_ISSUER: &str = "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+
+async fn create_anchor(db: &Database, name: &str, account: &str) -> String {
+    db.create_anchor(CreateAnchorRequest {
+        name: name.to_string(),
+        stellar_account: account.to_string(),
+        home_domain: None,
+    })
+    .await
+    .expect("failed to create anchor")
+    .id
+}
+
+#[sqlx::test]
+async fn test_anchors_are_scoped_to_tenant(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let acme = db.tenants().create("acme").await.unwrap();
+    let globex = db.tenants().create("globex").await.unwrap();
+    let acme_scope = TenantScope::Tenant(acme.id.clone());
+    let globex_scope = TenantScope::Tenant(globex.id.clone());
+
+    let shared = create_anchor(&db, "Shared", "GTENANTSHARED").await;
+    let acme_only = create_anchor(&db, "Acme Only", "GTENANTACME").await;
+    db.tenants().add_anchor(&acme_scope, &shared).await.unwrap();
+    db.tenants()
+        .add_anchor(&acme_scope, &acme_only)
+        .await
+        .unwrap();
+    db.tenants()
+        .add_anchor(&globex_scope, &shared)
+        .await
+        .unwrap();
+
+    let ids = |anchors: Vec<stellar_insights_backend::models::Anchor>| {
+        let mut ids: Vec<String> = anchors.into_iter().map(|a| a.id).collect();
+        ids.sort();
+        ids
+    };
+    let mut both = vec![shared.clone(), acme_only.clone()];
+    both.sort();
+    assert_eq!(
+        ids(db.list_anchors(50, 0, &acme_scope).await.unwrap()),
+        both
+    );
+    assert_eq!(
+        ids(db.list_anchors(50, 0, &globex_scope).await.unwrap()),
+        vec![shared.clone()]
+    );
+    assert_eq!(
+        db.list_anchors(50, 0, &TenantScope::All)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    assert!(db
+        .tenants()
+        .has_anchor(&acme_scope, &acme_only)
+        .await
+        .unwrap());
+    assert!(!db
+        .tenants()
+        .has_anchor(&globex_scope, &acme_only)
+        .await
+        .unwrap());
+    assert!(db
+        .tenants()
+        .has_anchor(&TenantScope::All, &acme_only)
+        .await
+        .unwrap());
+}
+
+#[sqlx::test]
+async fn test_corridors_are_scoped_to_tenant(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let acme = TenantScope::Tenant(db.tenants().create("acme").await.unwrap().id);
+    let globex = TenantScope::Tenant(db.tenants().create("globex").await.unwrap().id);
+
+    let corridor = db
+        .create_corridor(CreateCorridorRequest {
+            source_asset_code: "USDC".to_string(),
+            source_asset_issuer: USDC_ISSUER.to_string(),
+            dest_asset_code: "EURC".to_string(),
+            dest_asset_issuer: EURC_ISSUER.to_string(),
+        })
+        .await
+        .unwrap();
+    db.tenants().add_corridor(&acme, &corridor).await.unwrap();
+
+    let acme_keys = db.tenants().corridor_keys(&acme).await.unwrap().unwrap();
+    assert!(acme_keys.contains(&corridor.key()));
+    assert!(db
+        .tenants()
+        .corridor_keys(&globex)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_empty());
+    assert!(db
+        .tenants()
+        .corridor_keys(&TenantScope::All)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+/// Anchor and corridor write routes, as seen by a request `tenant_middleware`
+/// resolved to `tenant`
+fn tenant_router(db: Arc<Database>, tenant: TenantScope) -> Router {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let ingestion = Arc::new(DataIngestionService::new(rpc_client, Arc::clone(&db)));
+    let state = AppState {
+        db,
+        ws_state: Arc::new(WsState::new()),
+        ingestion,
+        settings: Arc::new(Settings::default()),
+    };
+    Router::new()
+        .route("/api/anchors/:id/metrics", put(update_anchor_metrics))
+        .route(
+            "/api/anchors/:id/assets",
+            get(get_anchor_assets).post(create_anchor_asset),
+        )
+        .route(
+            "/api/corridors/:id/metrics-from-transactions",
+            put(update_corridor_metrics_from_transactions),
+        )
+        .with_state(state)
+        .layer(Extension(tenant))
+}
+
+fn json_request(method: &str, uri: &str, body: Value) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_tenant_cannot_write_other_tenants_rows(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let acme = TenantScope::Tenant(db.tenants().create("acme").await.unwrap().id);
+    let globex = TenantScope::Tenant(db.tenants().create("globex").await.unwrap().id);
+
+    let anchor = create_anchor(&db, "Acme Only", "GTENANTACME").await;
+    db.tenants().add_anchor(&acme, &anchor).await.unwrap();
+    let corridor = db
+        .create_corridor(CreateCorridorRequest {
+            source_asset_code: "USDC".to_string(),
+            source_asset_issuer: USDC_ISSUER.to_string(),
+            dest_asset_code: "EURC".to_string(),
+            dest_asset_issuer: EURC_ISSUER.to_string(),
+        })
+        .await
+        .unwrap();
+    db.tenants().add_corridor(&acme, &corridor).await.unwrap();
+    let corridor_id: String = sqlx::query_scalar("SELECT id FROM corridors")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+
+    let metrics = json!({
+        "total_transactions": 100,
+        "successful_transactions": 99,
+        "failed_transactions": 1
+    });
+    let asset = json!({"asset_code": "USDC", "asset_issuer": USDC_ISSUER});
+    let corridor_metrics = json!({
+        "transactions": [{"successful": true, "settlement_latency_ms": 1000, "amount_usd": 10.0}]
+    });
+    let requests = || {
+        vec![
+            json_request(
+                "PUT",
+                &format!("/api/anchors/{}/metrics", anchor),
+                metrics.clone(),
+            ),
+            json_request(
+                "POST",
+                &format!("/api/anchors/{}/assets", anchor),
+                asset.clone(),
+            ),
+            json_request("GET", &format!("/api/anchors/{}/assets", anchor), json!({})),
+            json_request(
+                "PUT",
+                &format!("/api/corridors/{}/metrics-from-transactions", corridor_id),
+                corridor_metrics.clone(),
+            ),
+        ]
+    };
+
+    // Another tenant's anchor and corridor are reported as missing
+    let globex_app = tenant_router(Arc::clone(&db), globex);
+    for request in requests() {
+        let uri = request.uri().to_string();
+        let response = globex_app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", uri);
+    }
+    let anchor_id = Uuid::parse_str(&anchor).unwrap();
+    let unchanged = db.get_anchor_by_id(anchor_id).await.unwrap().unwrap();
+    assert_eq!(unchanged.total_transactions, 0);
+    assert!(db.get_assets_by_anchor(anchor_id).await.unwrap().is_empty());
+
+    // The owning tenant can write them
+    let acme_app = tenant_router(Arc::clone(&db), acme);
+    for request in requests() {
+        let uri = request.uri().to_string();
+        let response = acme_app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+    }
+    assert_eq!(db.get_assets_by_anchor(anchor_id).await.unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_api_key_resolves_to_tenant(pool: SqlitePool) {
+    let db = Database::new(pool);
+    let tenant = db.tenants().create("acme").await.unwrap();
+    let created = db
+        .create_api_key(
+            "GWALLET",
+            CreateApiKeyRequest {
+                name: "acme key".to_string(),
+                scopes: None,
+                expires_at: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let key = db
+        .validate_api_key(&created.plain_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(key.tenant_id, None);
+
+    assert!(db
+        .tenants()
+        .assign_api_key(&created.key.id, &tenant.id)
+        .await
+        .unwrap());
+    assert!(!db
+        .tenants()
+        .assign_api_key("missing", &tenant.id)
+        .await
+        .unwrap());
+
+    let key = db
+        .validate_api_key(&created.plain_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(key.tenant_id, Some(tenant.id));
+}
@@ -0,0 +1,141 @@
+use axum::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::sync::{Arc, Mutex};
+use stellar_insights_backend::billing::{BillingExporter, UsageExport, UsageKind, UsageReport};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::usage_events::{UsageEvents, UsageTotals};
+
+/// Exporter that keeps reports in memory, failing while `fail` is set
+#[derive(Default)]
+struct RecordingExporter {
+    reports: Mutex<Vec<UsageReport>>,
+    failed: Mutex<Vec<UsageReport>>,
+    fail: Mutex<bool>,
+}
+
+#[async_trait]
+impl BillingExporter for RecordingExporter {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    async fn export(&self, report: &UsageReport) -> anyhow::Result<()> {
+        if *self.fail.lock().unwrap() {
+            self.failed.lock().unwrap().push(report.clone());
+            anyhow::bail!("billing provider unavailable");
+        }
+        self.reports.lock().unwrap().push(report.clone());
+        Ok(())
+    }
+}
+
+#[sqlx::test]
+async fn test_totals_cover_the_window(pool: SqlitePool) {
+    let db = Database::new(pool.clone());
+    let tenant = db.tenants().create("acme").await.unwrap();
+    let events = UsageEvents::new(pool);
+    let now = Utc::now();
+
+    events
+        .record_at(&tenant.id, UsageKind::ApiCall, 1, now - Duration::hours(1))
+        .await
+        .unwrap();
+    events
+        .record_at(&tenant.id, UsageKind::ApiCall, 1, now - Duration::hours(2))
+        .await
+        .unwrap();
+    events
+        .record_at(
+            &tenant.id,
+            UsageKind::WsMinutes,
+            7,
+            now - Duration::hours(1),
+        )
+        .await
+        .unwrap();
+    // Outside the window
+    events
+        .record_at(&tenant.id, UsageKind::ApiCall, 1, now - Duration::days(3))
+        .await
+        .unwrap();
+
+    let totals = events
+        .totals(&tenant.id, now - Duration::days(1), now)
+        .await
+        .unwrap();
+    assert_eq!(
+        totals,
+        UsageTotals {
+            api_calls: 2,
+            webhook_deliveries: 0,
+            ws_minutes: 7,
+        }
+    );
+}
+
+#[sqlx::test]
+async fn test_export_reports_billable_usage_once(pool: SqlitePool) {
+    let db = Database::new(pool.clone());
+    let billed = db.tenants().create("acme").await.unwrap();
+    let unbilled = db.tenants().create("globex").await.unwrap();
+    db.tenants()
+        .set_billing_customer(&billed.id, Some("cus_acme"))
+        .await
+        .unwrap();
+
+    let events = UsageEvents::new(pool.clone());
+    for tenant in [&billed, &unbilled] {
+        events
+            .record(&tenant.id, UsageKind::ApiCall, 1)
+            .await
+            .unwrap();
+        events
+            .record(&tenant.id, UsageKind::ApiCall, 1)
+            .await
+            .unwrap();
+        events
+            .record(&tenant.id, UsageKind::WebhookDelivery, 3)
+            .await
+            .unwrap();
+    }
+
+    let exporter = Arc::new(RecordingExporter::default());
+    let export = UsageExport::new(pool.clone(), exporter.clone());
+
+    // A failed export leaves the batches unacknowledged
+    *exporter.fail.lock().unwrap() = true;
+    assert_eq!(export.run_once().await.unwrap(), 0);
+    assert_eq!(export.run_once().await.unwrap(), 0);
+    *exporter.fail.lock().unwrap() = false;
+    let failed = exporter.failed.lock().unwrap().clone();
+    assert_eq!(failed.len(), 4);
+    assert_eq!(failed[..2], failed[2..]);
+
+    // Usage recorded after a batch was written goes into the next batch
+    events
+        .record(&billed.id, UsageKind::ApiCall, 5)
+        .await
+        .unwrap();
+
+    assert_eq!(export.run_once().await.unwrap(), 3);
+    let reports = exporter.reports.lock().unwrap().clone();
+    assert_eq!(reports[..2], failed[..2]);
+    assert_eq!(reports[2].quantity, 5);
+    assert_ne!(reports[2].identifier, reports[0].identifier);
+    let reports = reports[..2].to_vec();
+    assert!(reports.iter().all(|r| r.customer_id == "cus_acme"));
+    let api_calls = reports
+        .iter()
+        .find(|r| r.kind == UsageKind::ApiCall)
+        .unwrap();
+    assert_eq!(api_calls.quantity, 2);
+    let deliveries = reports
+        .iter()
+        .find(|r| r.kind == UsageKind::WebhookDelivery)
+        .unwrap();
+    assert_eq!(deliveries.quantity, 3);
+
+    // Nothing new to report
+    assert_eq!(export.run_once().await.unwrap(), 0);
+}
@@ -0,0 +1,60 @@
+use chrono::{Duration, TimeZone, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::valuation::{
+    fiat_history_key, Quote, RateSource, ValuationService,
+};
+
+#[sqlx::test]
+async fn test_usd_to_quote_uses_price_at_metric_time(pool: SqlitePool) {
+    let db = Database::new(pool.clone());
+    let history = db.price_history();
+    let jan = Utc.with_ymd_and_hms(2024, 1, 10, 12, 0, 0).unwrap();
+    let jun = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+
+    history
+        .record(&fiat_history_key("EUR"), 1.25, "test", jan)
+        .await
+        .unwrap();
+    history
+        .record(&fiat_history_key("EUR"), 1.0, "test", jun)
+        .await
+        .unwrap();
+
+    let eur = Quote::parse("EUR").unwrap();
+    let mut valuation = ValuationService::new(pool);
+
+    let (january, source) = valuation
+        .usd_to_quote(100.0, &eur, jan + Duration::hours(3))
+        .await
+        .unwrap();
+    assert!((january - 80.0).abs() < 1e-9);
+    assert_eq!(source, RateSource::PriceHistory);
+
+    let (june, _) = valuation.usd_to_quote(100.0, &eur, jun).await.unwrap();
+    assert!((june - 100.0).abs() < 1e-9);
+
+    // Far from any observation: static reference rate
+    let march = Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap();
+    let (fallback, source) = valuation.usd_to_quote(108.0, &eur, march).await.unwrap();
+    assert!((fallback - 100.0).abs() < 1e-9);
+    assert_eq!(source, RateSource::Reference);
+}
+
+#[sqlx::test]
+async fn test_asset_to_quote(pool: SqlitePool) {
+    let db = Database::new(pool.clone());
+    let at = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    db.price_history()
+        .record("XLM:native", 0.1, "test", at)
+        .await
+        .unwrap();
+
+    let usd = Quote::parse("USD").unwrap();
+    let (value, source) = ValuationService::new(pool)
+        .asset_to_quote("XLM:native", 1_000.0, &usd, at)
+        .await
+        .unwrap();
+    assert!((value - 100.0).abs() < 1e-9);
+    assert_eq!(source, RateSource::PriceHistory);
+}
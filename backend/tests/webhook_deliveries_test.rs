@@ -0,0 +1,140 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::webhooks::deliveries::{retry_delay, MAX_DELIVERY_RETRIES};
+use stellar_insights_backend::webhooks::retention::{
+    ArchiveMode, RetentionConfig, WebhookEventRetention,
+};
+use stellar_insights_backend::webhooks::WebhookService;
+
+async fn setup() -> (SqlitePool, WebhookService) {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    sqlx::query("INSERT INTO users (id, username) VALUES ('user-1', 'alice')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO webhooks (id, user_id, url, event_types, secret)
+         VALUES ('hook-1', 'user-1', 'https://example.com/hook', 'corridor.health_degraded', 's')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let service = WebhookService::new(pool.clone(), "test-encryption-key");
+    (pool, service)
+}
+
+async fn insert_event(pool: &SqlitePool, id: &str, days_ago: i64) {
+    sqlx::query(
+        "INSERT INTO webhook_events (id, webhook_id, event_type, payload, status, retries, created_at)
+         VALUES (?, 'hook-1', 'corridor.health_degraded', '{\"score\":42}', 'pending', 0, ?)",
+    )
+    .bind(id)
+    .bind((Utc::now() - Duration::days(days_ago)).to_rfc3339())
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn pending_ids(service: &WebhookService) -> Vec<String> {
+    service
+        .get_pending_events(100)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| e.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_failed_delivery_waits_for_backoff() {
+    let (pool, service) = setup().await;
+    insert_event(&pool, "event-1", 0).await;
+
+    service
+        .schedule_retry("event-1", "HTTP 503", 1, Utc::now() + retry_delay(0))
+        .await
+        .unwrap();
+    assert!(pending_ids(&service).await.is_empty());
+
+    service
+        .schedule_retry("event-1", "HTTP 503", 1, Utc::now() - Duration::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(pending_ids(&service).await, vec!["event-1".to_string()]);
+
+    service.mark_delivered("event-1", 1).await.unwrap();
+    let deliveries = service.list_deliveries("hook-1", None, 50).await.unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, "delivered");
+    assert_eq!(deliveries[0].retries, 1);
+    assert!(deliveries[0].delivered_at.is_some());
+    assert!(deliveries[0].last_error.is_none());
+}
+
+#[tokio::test]
+async fn test_dead_letters_survive_retention_and_redrive() {
+    let (pool, service) = setup().await;
+    insert_event(&pool, "old-event", 45).await;
+    insert_event(&pool, "new-event", 0).await;
+
+    for event in service.get_pending_events(100).await.unwrap() {
+        service
+            .dead_letter(&event, "HTTP 410", MAX_DELIVERY_RETRIES)
+            .await
+            .unwrap();
+    }
+    assert!(pending_ids(&service).await.is_empty());
+
+    // Retention archives the old failure; its dead letter remains
+    WebhookEventRetention::new(
+        pool.clone(),
+        RetentionConfig {
+            retention_days: 30,
+            mode: ArchiveMode::Delete,
+        },
+    )
+    .compact()
+    .await
+    .unwrap();
+
+    let failed = service
+        .list_deliveries("hook-1", Some("failed"), 50)
+        .await
+        .unwrap();
+    assert_eq!(failed.len(), 2);
+    assert_eq!(failed[0].event_id, "new-event");
+    assert_eq!(failed[1].event_id, "old-event");
+    assert!(failed.iter().all(|d| d.dead_lettered_at.is_some()));
+    assert_eq!(failed[1].last_error.as_deref(), Some("HTTP 410"));
+    assert!(service
+        .list_deliveries("hook-1", Some("delivered"), 50)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Re-drive one failure, then the rest
+    assert_eq!(
+        service
+            .redrive_dead_letters("hook-1", Some(&["old-event".to_string()]))
+            .await
+            .unwrap(),
+        1
+    );
+    let pending = service.get_pending_events(100).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, "old-event");
+    assert_eq!(pending[0].retries, 0);
+    assert_eq!(pending[0].payload, "{\"score\":42}");
+
+    assert_eq!(
+        service.redrive_dead_letters("hook-1", None).await.unwrap(),
+        1
+    );
+    assert_eq!(pending_ids(&service).await.len(), 2);
+    let deliveries = service.list_deliveries("hook-1", None, 50).await.unwrap();
+    assert!(deliveries
+        .iter()
+        .all(|d| d.status == "pending" && d.dead_lettered_at.is_none()));
+}
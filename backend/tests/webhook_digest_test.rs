@@ -0,0 +1,142 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::webhooks::digest::CorridorDigestBuilder;
+use stellar_insights_backend::webhooks::events::CorridorDigestEvent;
+
+const USDC_XLM: &str = "USDC:GA->XLM:native";
+const EURC_XLM: &str = "EURC:GB->XLM:native";
+
+async fn setup() -> SqlitePool {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    sqlx::query("INSERT INTO users (id, username) VALUES ('user-1', 'alice')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool
+}
+
+async fn insert_webhook(pool: &SqlitePool, id: &str, event_types: &str, filters: Option<&str>) {
+    sqlx::query(
+        "INSERT INTO webhooks (id, user_id, url, event_types, filters, secret)
+         VALUES (?, 'user-1', 'https://example.com/hook', ?, ?, 's')",
+    )
+    .bind(id)
+    .bind(event_types)
+    .bind(filters)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn insert_hour(pool: &SqlitePool, corridor_key: &str, hour: DateTime<Utc>, txns: i64) {
+    let (a, b) = corridor_key.split_once("->").unwrap();
+    let (a_code, a_issuer) = a.split_once(':').unwrap();
+    let (b_code, b_issuer) = b.split_once(':').unwrap();
+    sqlx::query(
+        "INSERT INTO corridor_metrics_hourly (
+            id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            hour_bucket, total_transactions, successful_transactions, failed_transactions,
+            success_rate, volume_usd, avg_slippage_bps
+         ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 100.0, ?, 2.0)",
+    )
+    .bind(format!("{}-{}", corridor_key, hour))
+    .bind(corridor_key)
+    .bind(a_code)
+    .bind(a_issuer)
+    .bind(b_code)
+    .bind(b_issuer)
+    .bind(hour.to_rfc3339())
+    .bind(txns)
+    .bind(txns)
+    .bind(txns as f64 * 100.0)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn digests(pool: &SqlitePool, webhook_id: &str) -> Vec<CorridorDigestEvent> {
+    let payloads: Vec<String> = sqlx::query_scalar(
+        "SELECT payload FROM webhook_events
+         WHERE webhook_id = ? AND event_type = 'corridor.digest' AND status = 'pending'
+         ORDER BY created_at",
+    )
+    .bind(webhook_id)
+    .fetch_all(pool)
+    .await
+    .unwrap();
+    payloads
+        .iter()
+        .map(|p| serde_json::from_str(p).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_digest_summarizes_watched_corridors_once_per_window() {
+    let pool = setup().await;
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 20, 0).unwrap();
+    let top_of_hour = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+
+    insert_hour(&pool, USDC_XLM, top_of_hour - Duration::hours(1), 10).await;
+    insert_hour(&pool, EURC_XLM, top_of_hour - Duration::hours(1), 4).await;
+    // Outside the one-hour window
+    insert_hour(&pool, USDC_XLM, top_of_hour - Duration::hours(2), 99).await;
+
+    insert_webhook(
+        &pool,
+        "watching",
+        "payment.created,corridor.digest",
+        Some(r#"{"corridors": ["USDC:GA->XLM:native", "BRL:GC->XLM:native"]}"#),
+    )
+    .await;
+    insert_webhook(&pool, "everything", "corridor.digest", None).await;
+    insert_webhook(&pool, "payments-only", "payment.created", None).await;
+
+    let builder = CorridorDigestBuilder::new(pool.clone(), std::time::Duration::from_secs(3600));
+    assert_eq!(builder.run_at(now).await.unwrap(), 2);
+
+    let watching = digests(&pool, "watching").await;
+    assert_eq!(watching.len(), 1);
+    assert_eq!(watching[0].window_end, top_of_hour.to_rfc3339());
+    let keys: Vec<&str> = watching[0]
+        .corridors
+        .iter()
+        .map(|c| c.corridor_key.as_str())
+        .collect();
+    assert_eq!(keys, vec![USDC_XLM, "BRL:GC->XLM:native"]);
+    assert_eq!(watching[0].corridors[0].total_transactions, 10);
+    assert_eq!(watching[0].corridors[1].total_transactions, 0);
+
+    let everything = digests(&pool, "everything").await;
+    let keys: Vec<&str> = everything[0]
+        .corridors
+        .iter()
+        .map(|c| c.corridor_key.as_str())
+        .collect();
+    assert_eq!(keys, vec![USDC_XLM, EURC_XLM]);
+
+    assert!(digests(&pool, "payments-only").await.is_empty());
+
+    // Running again within the same hour queues nothing new
+    assert_eq!(
+        builder.run_at(now + Duration::minutes(30)).await.unwrap(),
+        0
+    );
+    // The next hour is a new window
+    assert_eq!(builder.run_at(now + Duration::hours(1)).await.unwrap(), 2);
+    assert_eq!(digests(&pool, "watching").await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_inactive_webhooks_get_no_digest() {
+    let pool = setup().await;
+    insert_webhook(&pool, "hook-1", "corridor.digest", None).await;
+    sqlx::query("UPDATE webhooks SET is_active = 0 WHERE id = 'hook-1'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let builder = CorridorDigestBuilder::new(pool.clone(), std::time::Duration::from_secs(3600));
+    assert_eq!(builder.run_once().await.unwrap(), 0);
+}
@@ -0,0 +1,107 @@
+use serde_json::json;
+use sqlx::SqlitePool;
+use stellar_insights_backend::tenant::TenantScope;
+use stellar_insights_backend::webhooks::{
+    CreateWebhookRequest, DeliverySettings, WebhookEventType, WebhookService,
+};
+
+async fn setup() -> (SqlitePool, WebhookService) {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    for (id, username) in [("user-1", "alice"), ("user-2", "bob")] {
+        sqlx::query("INSERT INTO users (id, username) VALUES (?, ?)")
+            .bind(id)
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let service = WebhookService::new(pool.clone(), "test-encryption-key");
+    (pool, service)
+}
+
+fn request(filters: Option<serde_json::Value>) -> CreateWebhookRequest {
+    CreateWebhookRequest {
+        url: "https://example.com/hook".to_string(),
+        event_types: vec!["sep.transaction_status_changed".to_string()],
+        filters,
+        delivery: DeliverySettings::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_enqueue_only_queues_events_matching_filters() {
+    let (pool, service) = setup().await;
+    let completed_only = service
+        .register_webhook(
+            "user-1",
+            &TenantScope::All,
+            request(Some(json!({"new_status": {"in": ["completed"]}}))),
+        )
+        .await
+        .unwrap();
+    let everything = service
+        .register_webhook("user-1", &TenantScope::All, request(None))
+        .await
+        .unwrap();
+
+    let pending = json!({"transaction_id": "tx-1", "new_status": "pending_anchor"});
+    let completed = json!({"transaction_id": "tx-1", "new_status": "completed"});
+    let event_type = WebhookEventType::SepTransactionStatusChanged;
+    assert_eq!(
+        service
+            .enqueue_for_user("user-1", event_type.clone(), pending)
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        service
+            .enqueue_for_user("user-1", event_type, completed)
+            .await
+            .unwrap(),
+        2
+    );
+
+    let count = |webhook_id: String| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM webhook_events WHERE webhook_id = ?")
+                .bind(webhook_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(count(completed_only.id).await, 1);
+    assert_eq!(count(everything.id).await, 2);
+}
+
+#[tokio::test]
+async fn test_events_are_only_visible_to_their_owner() {
+    let (_pool, service) = setup().await;
+    let webhook = service
+        .register_webhook("user-1", &TenantScope::All, request(None))
+        .await
+        .unwrap();
+    let event_id = service
+        .create_webhook_event(
+            &webhook.id,
+            "sep.transaction_status_changed",
+            json!({"new_status": "completed"}),
+        )
+        .await
+        .unwrap();
+
+    let own = service
+        .get_event_for_user(&event_id, "user-1")
+        .await
+        .unwrap();
+    assert_eq!(own.unwrap().event_type, "sep.transaction_status_changed");
+    assert!(service
+        .get_event_for_user(&event_id, "user-2")
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,191 @@
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::rpc::HorizonTransaction;
+use stellar_insights_backend::services::network_events::{
+    close_delay, fee_spike, NetworkEventConfig, NetworkEventDetector,
+};
+use stellar_insights_backend::webhooks::{WebhookEventType, WebhookService};
+
+async fn setup() -> (SqlitePool, Arc<WebhookService>) {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    for (id, username) in [("user-1", "alice"), ("user-2", "bob")] {
+        sqlx::query("INSERT INTO users (id, username) VALUES (?, ?)")
+            .bind(id)
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let service = Arc::new(WebhookService::new(pool.clone(), "test-encryption-key"));
+    (pool, service)
+}
+
+async fn insert_webhook(
+    pool: &SqlitePool,
+    id: &str,
+    user_id: &str,
+    event_types: &str,
+    filters: Option<serde_json::Value>,
+) {
+    sqlx::query(
+        "INSERT INTO webhooks (id, user_id, url, event_types, filters, secret)
+         VALUES (?, ?, 'https://example.com/hook', ?, ?, 's')",
+    )
+    .bind(id)
+    .bind(user_id)
+    .bind(event_types)
+    .bind(filters.map(|f| f.to_string()))
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn queued(pool: &SqlitePool, webhook_id: &str, event_type: &str) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM webhook_events WHERE webhook_id = ? AND event_type = ?",
+    )
+    .bind(webhook_id)
+    .bind(event_type)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+async fn store_ledger(pool: &SqlitePool, sequence: i64, close_time: &str) {
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count) VALUES (?, ?, ?, 0, 0)",
+    )
+    .bind(sequence)
+    .bind(format!("ledger_hash_{}", sequence))
+    .bind(close_time)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn transaction(ledger: u64, fee_charged: i64, operation_count: u32) -> HorizonTransaction {
+    let hash = format!("tx-{}-{}", ledger, fee_charged);
+    HorizonTransaction {
+        id: hash.clone(),
+        hash: hash.clone(),
+        ledger,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        source_account: "GSENDER".to_string(),
+        fee_account: None,
+        fee_charged: Some(fee_charged.to_string()),
+        max_fee: Some(fee_charged.to_string()),
+        operation_count,
+        successful: true,
+        paging_token: hash,
+        fee_bump_transaction: None,
+        inner_transaction: None,
+        memo: None,
+    }
+}
+
+#[test]
+fn test_fee_spike_uses_median_fee_per_operation() {
+    // Per operation: 100, 400, 500 and 2000 stroops
+    let transactions = vec![
+        transaction(1, 100, 1),
+        transaction(1, 800, 2),
+        transaction(1, 500, 1),
+        transaction(1, 2000, 1),
+    ];
+
+    let spike = fee_spike(1, &transactions, 3.0).unwrap();
+    assert_eq!(spike.median_fee_stroops, 400);
+    assert_eq!(spike.max_fee_stroops, 2000);
+    assert_eq!(spike.fee_multiplier, 4.0);
+    assert_eq!(spike.transaction_count, 4);
+
+    assert!(fee_spike(1, &transactions, 5.0).is_none());
+    assert!(fee_spike(1, &[], 1.0).is_none());
+}
+
+#[test]
+fn test_close_delay_above_threshold() {
+    let previous = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+    let delayed = close_delay(2, previous, previous + Duration::seconds(21), 15.0).unwrap();
+    assert_eq!(delayed.ledger_sequence, 2);
+    assert_eq!(delayed.close_seconds, 21.0);
+    assert!(close_delay(2, previous, previous + Duration::seconds(6), 15.0).is_none());
+}
+
+#[tokio::test]
+async fn test_network_events_reach_matching_subscribers_of_every_user() {
+    let (pool, webhooks) = setup().await;
+    insert_webhook(&pool, "any-spike", "user-1", "network.fee_spike", None).await;
+    insert_webhook(
+        &pool,
+        "big-spike",
+        "user-2",
+        "network.fee_spike,ledger.close_delayed",
+        Some(json!({
+            "event_types": {"network.fee_spike": {"fee_multiplier": {"gte": 10}}}
+        })),
+    )
+    .await;
+    insert_webhook(&pool, "digest", "user-2", "corridor.digest", None).await;
+
+    let queued_count = webhooks
+        .enqueue_for_subscribers(
+            WebhookEventType::NetworkFeeSpike,
+            json!({"ledger_sequence": 7, "fee_multiplier": 4.0}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(queued_count, 1);
+    assert_eq!(queued(&pool, "any-spike", "network.fee_spike").await, 1);
+    assert_eq!(queued(&pool, "big-spike", "network.fee_spike").await, 0);
+
+    // The fee threshold does not apply to other event types
+    let queued_count = webhooks
+        .enqueue_for_subscribers(
+            WebhookEventType::LedgerCloseDelayed,
+            json!({"ledger_sequence": 7, "close_seconds": 20.0}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(queued_count, 1);
+    assert_eq!(queued(&pool, "big-spike", "ledger.close_delayed").await, 1);
+    assert_eq!(queued(&pool, "digest", "ledger.close_delayed").await, 0);
+}
+
+#[tokio::test]
+async fn test_detector_reports_a_fee_spike_once() {
+    let (pool, webhooks) = setup().await;
+    insert_webhook(
+        &pool,
+        "hook-1",
+        "user-1",
+        "network.fee_spike,ledger.close_delayed",
+        None,
+    )
+    .await;
+    store_ledger(&pool, 1, "2026-01-01T00:00:00Z").await;
+    store_ledger(&pool, 2, "2026-01-01T00:00:20Z").await;
+    store_ledger(&pool, 3, "2026-01-01T00:00:25Z").await;
+    store_ledger(&pool, 4, "2026-01-01T00:00:30Z").await;
+    store_ledger(&pool, 5, "2026-01-01T00:00:35Z").await;
+
+    let detector = NetworkEventDetector::new(pool.clone(), webhooks, NetworkEventConfig::default());
+    let spiking = vec![transaction(2, 1000, 1)];
+    let calm = vec![transaction(4, 100, 1)];
+
+    // Slow close and the start of a spike
+    assert_eq!(detector.process_ledger(2, Some(&spiking)).await.unwrap(), 2);
+    // Still spiking
+    assert_eq!(detector.process_ledger(3, Some(&spiking)).await.unwrap(), 0);
+    // Fees recover, then spike again
+    assert_eq!(detector.process_ledger(4, Some(&calm)).await.unwrap(), 0);
+    assert_eq!(detector.process_ledger(5, Some(&spiking)).await.unwrap(), 1);
+
+    assert_eq!(queued(&pool, "hook-1", "network.fee_spike").await, 2);
+    assert_eq!(queued(&pool, "hook-1", "ledger.close_delayed").await, 1);
+}
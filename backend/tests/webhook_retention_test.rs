@@ -0,0 +1,122 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::webhooks::retention::{
+    ArchiveMode, RetentionConfig, WebhookEventRetention,
+};
+
+async fn setup() -> SqlitePool {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    sqlx::query("INSERT INTO users (id, username) VALUES ('user-1', 'alice')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO webhooks (id, user_id, url, event_types, secret)
+         VALUES ('hook-1', 'user-1', 'https://example.com/hook', 'corridor.health_degraded', 's')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool
+}
+
+async fn insert_event(pool: &SqlitePool, id: &str, status: &str, retries: i64, days_ago: i64) {
+    let created_at = (Utc::now() - Duration::days(days_ago)).to_rfc3339();
+    sqlx::query(
+        "INSERT INTO webhook_events (id, webhook_id, event_type, payload, status, retries, created_at)
+         VALUES (?, 'hook-1', 'corridor.health_degraded', '{\"score\":42}', ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(retries)
+    .bind(created_at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn seed_events(pool: &SqlitePool) {
+    insert_event(pool, "old-delivered-1", "delivered", 0, 45).await;
+    insert_event(pool, "old-delivered-2", "delivered", 2, 45).await;
+    insert_event(pool, "old-failed", "failed", 5, 45).await;
+    insert_event(pool, "old-pending", "pending", 1, 45).await;
+    insert_event(pool, "recent-delivered", "delivered", 0, 1).await;
+}
+
+fn config(mode: ArchiveMode) -> RetentionConfig {
+    RetentionConfig {
+        retention_days: 30,
+        mode,
+    }
+}
+
+#[tokio::test]
+async fn test_compaction_summarizes_and_deletes_old_finished_events() {
+    let pool = setup().await;
+    seed_events(&pool).await;
+
+    let retention = WebhookEventRetention::new(pool.clone(), config(ArchiveMode::Delete));
+    let result = retention.compact().await.unwrap();
+    assert_eq!(result.events_compacted, 3);
+    assert_eq!(result.summary_rows_written, 2);
+
+    let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM webhook_events ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, vec!["old-pending", "recent-delivered"]);
+
+    let summaries: Vec<(String, i64, i64)> = sqlx::query_as(
+        "SELECT status, event_count, total_retries FROM webhook_event_summaries ORDER BY status",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        summaries,
+        vec![
+            ("delivered".to_string(), 2, 2),
+            ("failed".to_string(), 1, 5)
+        ]
+    );
+
+    // A second pass has nothing left to fold in
+    let again = retention.compact().await.unwrap();
+    assert_eq!(again.events_compacted, 0);
+}
+
+#[tokio::test]
+async fn test_strip_mode_keeps_rows_and_never_double_counts() {
+    let pool = setup().await;
+    seed_events(&pool).await;
+
+    let retention = WebhookEventRetention::new(pool.clone(), config(ArchiveMode::StripPayload));
+    let first = retention.compact().await.unwrap();
+    assert_eq!(first.events_compacted, 3);
+    let second = retention.compact().await.unwrap();
+    assert_eq!(second.events_compacted, 0);
+
+    let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhook_events")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, 5);
+
+    let stripped: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM webhook_events WHERE payload = '' AND compacted_at IS NOT NULL",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(stripped, 3);
+
+    let delivered: i64 = sqlx::query_scalar(
+        "SELECT event_count FROM webhook_event_summaries WHERE status = 'delivered'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(delivered, 2);
+}
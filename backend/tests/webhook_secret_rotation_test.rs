@@ -0,0 +1,80 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use stellar_insights_backend::tenant::TenantScope;
+use stellar_insights_backend::webhooks::{
+    CreateWebhookRequest, DeliverySettings, WebhookService, WebhookSignature,
+};
+
+async fn setup() -> WebhookService {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    for (id, username) in [("user-1", "alice"), ("user-2", "bob")] {
+        sqlx::query("INSERT INTO users (id, username) VALUES (?, ?)")
+            .bind(id)
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    WebhookService::new(pool, "00".repeat(32))
+}
+
+#[tokio::test]
+async fn test_rotated_secret_keeps_old_one_through_grace_window() {
+    let service = setup().await;
+    let webhook = service
+        .register_webhook(
+            "user-1",
+            &TenantScope::All,
+            CreateWebhookRequest {
+                url: "https://example.com/hook".to_string(),
+                event_types: vec!["corridor.health_degraded".to_string()],
+                filters: None,
+                delivery: DeliverySettings::default(),
+            },
+        )
+        .await
+        .unwrap();
+    let original = service.get_webhook(&webhook.id).await.unwrap().unwrap();
+    assert!(original.previous_secret_at(Utc::now()).is_none());
+
+    // Only the owner can rotate
+    assert!(service
+        .rotate_secret(&webhook.id, "user-2", Duration::hours(1))
+        .await
+        .unwrap()
+        .is_none());
+
+    let (secret, _) = service
+        .rotate_secret(&webhook.id, "user-1", Duration::hours(1))
+        .await
+        .unwrap()
+        .unwrap();
+    let rotated = service.get_webhook(&webhook.id).await.unwrap().unwrap();
+    assert_eq!(rotated.secret, secret);
+    assert_ne!(rotated.secret, original.secret);
+    assert_eq!(
+        rotated.previous_secret_at(Utc::now()),
+        Some(original.secret.as_str())
+    );
+    assert!(rotated
+        .previous_secret_at(Utc::now() + Duration::hours(2))
+        .is_none());
+
+    // Receivers still on the old secret verify the previous signature
+    let body = r#"{"event":"corridor.health_degraded"}"#;
+    let now = Utc::now().timestamp();
+    let previous =
+        WebhookSignature::sign(body, now, rotated.previous_secret_at(Utc::now()).unwrap());
+    assert!(WebhookSignature::verify(
+        body,
+        now,
+        &original.secret,
+        &previous,
+        now
+    ));
+    assert!(!WebhookSignature::verify(
+        body, now, &secret, &previous, now
+    ));
+}
@@ -35,6 +35,12 @@ export interface LiquidityMetrics {
   mid_price: number;
   depth_at_1_percent: number;
   depth_at_5_percent: number;
+  /** (bid - ask) / (bid + ask) volume within 5% of mid, from -1 to 1 */
+  imbalance: number;
+  /** Levels added, removed or resized per minute; null on the first snapshot */
+  cancel_replace_per_minute: number | null;
+  large_orders_appeared: number;
+  large_orders_pulled: number;
   fetched_at: number;
 }
 
@@ -144,7 +150,7 @@ export default function MarketDepthChart({
     <div className="bg-gray-900 rounded-xl p-4 space-y-4">
       {/* Header metrics */}
       {metrics && (
-        <div className="grid grid-cols-2 sm:grid-cols-4 gap-3">
+        <div className="grid grid-cols-2 sm:grid-cols-3 lg:grid-cols-6 gap-3">
           <MetricCard label="Mid Price" value={midPrice?.toFixed(6) ?? "—"} />
           <MetricCard
             label="Spread"
@@ -159,6 +165,20 @@ export default function MarketDepthChart({
             label="5% Depth"
             value={`${metrics.depth_at_5_percent.toLocaleString()} ${baseCurrency}`}
           />
+          <MetricCard
+            label="Imbalance"
+            value={`${(metrics.imbalance * 100).toFixed(1)}%`}
+            sub={metrics.imbalance >= 0 ? "bid heavy" : "ask heavy"}
+          />
+          <MetricCard
+            label="Cancel/Replace"
+            value={
+              metrics.cancel_replace_per_minute === null
+                ? "—"
+                : `${metrics.cancel_replace_per_minute.toFixed(1)} / min`
+            }
+            sub={`${metrics.large_orders_pulled} large pulled`}
+          />
         </div>
       )}
 
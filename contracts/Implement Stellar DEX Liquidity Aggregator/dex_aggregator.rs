@@ -83,9 +83,122 @@ pub struct LiquidityMetrics {
     pub mid_price: f64,
     pub depth_at_1_percent: f64,
     pub depth_at_5_percent: f64,
+    /// (bid - ask) / (bid + ask) volume within 5% of mid: +1 all bids, -1 all asks
+    pub imbalance: f64,
+    /// Price levels added, removed or resized per minute since the previous
+    /// snapshot; `None` for the first snapshot of a pair
+    pub cancel_replace_per_minute: Option<f64>,
+    /// Large orders (see `LARGE_ORDER_MULTIPLE`) new since the previous snapshot
+    pub large_orders_appeared: u32,
+    /// Large orders in the previous snapshot that are gone from this one
+    pub large_orders_pulled: u32,
     pub fetched_at: i64,
 }
 
+// ─── Spoofing signals ───────────────────────────────────────────────────────
+
+/// Half-width of the band around mid price that imbalance is measured over, in percent
+const IMBALANCE_BAND_PCT: f64 = 5.0;
+
+/// A level at least this many times the book's median level amount is a large order
+const LARGE_ORDER_MULTIPLE: f64 = 5.0;
+
+/// Limits past which a pair's liquidity metrics raise an alert
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalThresholds {
+    /// Absolute imbalance
+    pub imbalance: f64,
+    pub cancel_replace_per_minute: f64,
+    pub large_orders_pulled: u32,
+}
+
+impl Default for SignalThresholds {
+    fn default() -> Self {
+        Self { imbalance: 0.8, cancel_replace_per_minute: 20.0, large_orders_pulled: 1 }
+    }
+}
+
+/// An alertable order book condition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LiquidityAlert {
+    /// Resting volume is heavily one-sided
+    Imbalance { imbalance: f64 },
+    /// Orders are being cancelled and replaced unusually often
+    CancelReplace { per_minute: f64 },
+    /// Large orders vanished between snapshots, a classic spoofing pattern
+    LargeOrdersPulled { count: u32 },
+}
+
+impl LiquidityMetrics {
+    /// Conditions in these metrics past `thresholds`
+    pub fn alerts(&self, thresholds: &SignalThresholds) -> Vec<LiquidityAlert> {
+        let mut alerts = Vec::new();
+        if self.imbalance.abs() >= thresholds.imbalance {
+            alerts.push(LiquidityAlert::Imbalance { imbalance: self.imbalance });
+        }
+        if let Some(per_minute) = self.cancel_replace_per_minute {
+            if per_minute >= thresholds.cancel_replace_per_minute {
+                alerts.push(LiquidityAlert::CancelReplace { per_minute });
+            }
+        }
+        if self.large_orders_pulled >= thresholds.large_orders_pulled.max(1) {
+            alerts.push(LiquidityAlert::LargeOrdersPulled { count: self.large_orders_pulled });
+        }
+        alerts
+    }
+}
+
+/// Amount below which a level of `order_book` is not a large order
+fn large_order_threshold(order_book: &OrderBook) -> f64 {
+    let mut amounts: Vec<f64> = order_book.bids.iter().chain(&order_book.asks).map(|l| l.amount).collect();
+    if amounts.is_empty() {
+        return f64::INFINITY;
+    }
+    amounts.sort_by(|a, b| a.total_cmp(b));
+    amounts[amounts.len() / 2] * LARGE_ORDER_MULTIPLE
+}
+
+/// Amount resting at each price of one side of a book
+fn levels_by_price(levels: &[PriceLevel]) -> HashMap<u64, f64> {
+    levels.iter().map(|l| (l.price.to_bits(), l.amount)).collect()
+}
+
+/// Order book changes between two snapshots of a pair
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct BookChanges {
+    /// Levels added, removed or resized
+    levels: u32,
+    large_appeared: u32,
+    large_pulled: u32,
+}
+
+fn book_changes(previous: &OrderBook, current: &OrderBook) -> BookChanges {
+    let (prev_large, cur_large) = (large_order_threshold(previous), large_order_threshold(current));
+    let mut changes = BookChanges::default();
+    for (prev_side, cur_side) in [(&previous.bids, &current.bids), (&previous.asks, &current.asks)] {
+        let (prev, cur) = (levels_by_price(prev_side), levels_by_price(cur_side));
+        for (price, amount) in &cur {
+            match prev.get(price) {
+                Some(prev_amount) if prev_amount == amount => {}
+                _ => changes.levels += 1,
+            }
+            if *amount >= cur_large && prev.get(price).is_none_or(|a| *a < prev_large) {
+                changes.large_appeared += 1;
+            }
+        }
+        for (price, amount) in &prev {
+            if !cur.contains_key(price) {
+                changes.levels += 1;
+            }
+            if *amount >= prev_large && cur.get(price).is_none_or(|a| *a < cur_large) {
+                changes.large_pulled += 1;
+            }
+        }
+    }
+    changes
+}
+
 // ─── Horizon API Response Types ─────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -135,6 +248,23 @@ impl CacheManager {
         None
     }
 
+    /// Last snapshot of `key` and how long ago it was taken, even if expired
+    async fn previous(&self, key: &str) -> Option<(OrderBook, Duration)> {
+        let map = self.entries.read().await;
+        map.get(key).map(|entry| (entry.order_book.clone(), entry.cached_at.elapsed()))
+    }
+
+    /// Pairs whose latest metrics raise an alert under `thresholds`
+    async fn alerts(&self, thresholds: &SignalThresholds) -> Vec<(String, LiquidityAlert)> {
+        let map = self.entries.read().await;
+        let mut alerts: Vec<(String, LiquidityAlert)> = map
+            .iter()
+            .flat_map(|(key, entry)| entry.metrics.alerts(thresholds).into_iter().map(move |a| (key.clone(), a)))
+            .collect();
+        alerts.sort_by(|a, b| a.0.cmp(&b.0));
+        alerts
+    }
+
     async fn set(&self, key: String, metrics: LiquidityMetrics, order_book: OrderBook) {
         let mut map = self.entries.write().await;
         map.insert(key, CacheEntry { metrics, order_book, cached_at: Instant::now() });
@@ -147,6 +277,7 @@ pub struct DexAggregator {
     http: Client,
     horizon_url: String,
     cache: Arc<CacheManager>,
+    thresholds: SignalThresholds,
 }
 
 impl DexAggregator {
@@ -158,6 +289,7 @@ impl DexAggregator {
                 .expect("Failed to build HTTP client"),
             horizon_url: horizon_url.into(),
             cache: Arc::new(CacheManager::new(300)), // 5 min TTL
+            thresholds: SignalThresholds::default(),
         })
     }
 
@@ -209,6 +341,13 @@ impl DexAggregator {
 
     /// Calculate liquidity metrics from an order book.
     pub fn calculate_metrics(order_book: &OrderBook) -> Option<LiquidityMetrics> {
+        Self::calculate_metrics_since(order_book, None)
+    }
+
+    /// Calculate liquidity metrics from an order book, with the cancel/replace
+    /// and large-order signals measured against `previous`, the pair's last
+    /// snapshot and how long before this one it was taken.
+    pub fn calculate_metrics_since(order_book: &OrderBook, previous: Option<(&OrderBook, Duration)>) -> Option<LiquidityMetrics> {
         if order_book.bids.is_empty() && order_book.asks.is_empty() {
             return None;
         }
@@ -229,6 +368,13 @@ impl DexAggregator {
 
         let depth_at_1_percent  = Self::depth_at_impact(order_book, mid_price, 1.0);
         let depth_at_5_percent  = Self::depth_at_impact(order_book, mid_price, 5.0);
+        let imbalance = Self::imbalance(order_book, mid_price);
+
+        let changes = previous.map(|(prev, _)| book_changes(prev, order_book)).unwrap_or_default();
+        let cancel_replace_per_minute = previous.and_then(|(_, elapsed)| {
+            let minutes = elapsed.as_secs_f64() / 60.0;
+            (minutes > 0.0).then(|| changes.levels as f64 / minutes)
+        });
 
         Some(LiquidityMetrics {
             total_bid_volume,
@@ -240,10 +386,23 @@ impl DexAggregator {
             mid_price,
             depth_at_1_percent,
             depth_at_5_percent,
+            imbalance,
+            cancel_replace_per_minute,
+            large_orders_appeared: changes.large_appeared,
+            large_orders_pulled: changes.large_pulled,
             fetched_at: chrono::Utc::now().timestamp(),
         })
     }
 
+    /// Bid against ask volume within `IMBALANCE_BAND_PCT` of mid price.
+    fn imbalance(order_book: &OrderBook, mid_price: f64) -> f64 {
+        if mid_price == 0.0 { return 0.0; }
+        let band = mid_price * IMBALANCE_BAND_PCT / 100.0;
+        let bids: f64 = order_book.bids.iter().filter(|l| l.price >= mid_price - band).map(|l| l.amount).sum();
+        let asks: f64 = order_book.asks.iter().filter(|l| l.price <= mid_price + band).map(|l| l.amount).sum();
+        if bids + asks == 0.0 { 0.0 } else { (bids - asks) / (bids + asks) }
+    }
+
     /// Sum ask volumes within `pct` price impact from mid price.
     fn depth_at_impact(order_book: &OrderBook, mid_price: f64, pct: f64) -> f64 {
         if mid_price == 0.0 { return 0.0; }
@@ -263,7 +422,8 @@ impl DexAggregator {
         }
 
         let order_book = self.get_order_book(base, counter, 200).await?;
-        let metrics = Self::calculate_metrics(&order_book)
+        let previous = self.cache.previous(&key).await;
+        let metrics = Self::calculate_metrics_since(&order_book, previous.as_ref().map(|(ob, age)| (ob, *age)))
             .unwrap_or_else(|| LiquidityMetrics {
                 total_bid_volume: 0.0,
                 total_ask_volume: 0.0,
@@ -274,6 +434,10 @@ impl DexAggregator {
                 mid_price: 0.0,
                 depth_at_1_percent: 0.0,
                 depth_at_5_percent: 0.0,
+                imbalance: 0.0,
+                cancel_replace_per_minute: None,
+                large_orders_appeared: 0,
+                large_orders_pulled: 0,
                 fetched_at: chrono::Utc::now().timestamp(),
             });
 
@@ -281,6 +445,11 @@ impl DexAggregator {
         Ok(metrics)
     }
 
+    /// Pairs whose latest metrics are past the alert thresholds, by pair key.
+    pub async fn alerts(&self) -> Vec<(String, LiquidityAlert)> {
+        self.cache.alerts(&self.thresholds).await
+    }
+
     /// Background job that refreshes top corridors every 5 minutes.
     pub fn spawn_background_refresh(self: Arc<Self>, corridors: Vec<(Asset, Asset)>) {
         tokio::spawn(async move {
@@ -292,7 +461,11 @@ impl DexAggregator {
                     match self.get_order_book(base, counter, 200).await {
                         Ok(ob) => {
                             let key = base.pair_key(counter);
-                            if let Some(metrics) = Self::calculate_metrics(&ob) {
+                            let previous = self.cache.previous(&key).await;
+                            if let Some(metrics) = Self::calculate_metrics_since(&ob, previous.as_ref().map(|(p, age)| (p, *age))) {
+                                for alert in metrics.alerts(&self.thresholds) {
+                                    warn!("Order book alert for {key}: {alert:?}");
+                                }
                                 self.cache.set(key, metrics, ob).await;
                             }
                         }
@@ -379,4 +552,55 @@ mod tests {
         let counter = Asset::native();
         assert_eq!(base.pair_key(&counter), "USDC/XLM");
     }
+
+    #[test]
+    fn test_imbalance_within_band() {
+        let m = DexAggregator::calculate_metrics(&sample_order_book()).unwrap();
+        // Within 5% of mid: bids 3500, asks 1200 (the 1.06 ask is outside)
+        assert!((m.imbalance - 2300.0 / 4700.0).abs() < 1e-9);
+        assert!(m.cancel_replace_per_minute.is_none());
+    }
+
+    #[test]
+    fn test_cancel_replace_since_previous_snapshot() {
+        let prev = sample_order_book();
+        let mut ob = sample_order_book();
+        ob.bids[0].amount = 450.0;                            // resized
+        ob.asks.remove(2);                                    // cancelled
+        ob.asks.push(PriceLevel { price: 1.03, amount: 700.0 }); // placed
+
+        let m = DexAggregator::calculate_metrics_since(&ob, Some((&prev, Duration::from_secs(30)))).unwrap();
+        // 3 changed levels in half a minute
+        assert!((m.cancel_replace_per_minute.unwrap() - 6.0).abs() < 1e-9);
+        assert_eq!(m.large_orders_appeared, 0);
+        assert_eq!(m.large_orders_pulled, 0);
+    }
+
+    #[test]
+    fn test_large_order_appears_then_pulled() {
+        let base = sample_order_book();
+        let mut walled = sample_order_book();
+        walled.bids.push(PriceLevel { price: 0.97, amount: 50_000.0 });
+
+        let m = DexAggregator::calculate_metrics_since(&walled, Some((&base, Duration::from_secs(60)))).unwrap();
+        assert_eq!(m.large_orders_appeared, 1);
+        assert_eq!(m.large_orders_pulled, 0);
+
+        let m = DexAggregator::calculate_metrics_since(&base, Some((&walled, Duration::from_secs(60)))).unwrap();
+        assert_eq!(m.large_orders_appeared, 0);
+        assert_eq!(m.large_orders_pulled, 1);
+        assert_eq!(
+            m.alerts(&SignalThresholds::default()),
+            vec![LiquidityAlert::LargeOrdersPulled { count: 1 }]
+        );
+    }
+
+    #[test]
+    fn test_alerts_respect_thresholds() {
+        let m = DexAggregator::calculate_metrics(&sample_order_book()).unwrap();
+        assert!(m.alerts(&SignalThresholds::default()).is_empty());
+
+        let strict = SignalThresholds { imbalance: 0.4, ..SignalThresholds::default() };
+        assert!(matches!(m.alerts(&strict).as_slice(), [LiquidityAlert::Imbalance { .. }]));
+    }
 }
@@ -3,7 +3,7 @@
 // Add the following methods to your existing StellarRpcClient implementation.
 // Also re-export the DexAggregator so callers can reach it through this module.
 
-use crate::services::dex_aggregator::{Asset, DexAggregator, LiquidityMetrics, OrderBook};
+use crate::services::dex_aggregator::{Asset, DexAggregator, LiquidityAlert, LiquidityMetrics, OrderBook};
 use anyhow::Result;
 use std::sync::Arc;
 
@@ -28,6 +28,11 @@ impl StellarRpcClient {
     ) -> Result<LiquidityMetrics> {
         self.dex_aggregator.get_liquidity(base, counter).await
     }
+
+    /// Pairs whose order books currently look imbalanced or spoofed.
+    pub async fn get_liquidity_alerts(&self) -> Vec<(String, LiquidityAlert)> {
+        self.dex_aggregator.alerts().await
+    }
 }
 
 // ─── Wire-up ─────────────────────────────────────────────────────────────────
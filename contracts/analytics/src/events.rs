@@ -0,0 +1,76 @@
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+// ============================================================================
+// Event Topics - Short symbols for efficient on-chain storage
+// ============================================================================
+
+/// Topic for snapshot submission events
+pub const SNAPSHOT_SUBMITTED: Symbol = symbol_short!("SNAP_SUB");
+
+/// Topic for snapshot lifecycle events (for filtering)
+pub const SNAPSHOT_LIFECYCLE: Symbol = symbol_short!("SNAP_LFE");
+
+/// Topic for admin change events
+pub const ADMIN_TRANSFERRED: Symbol = symbol_short!("ADM_XFER");
+
+/// Topic for submitter added events
+pub const SUBMITTER_ADDED: Symbol = symbol_short!("SUBM_ADD");
+
+/// Topic for submitter removed events
+pub const SUBMITTER_REMOVED: Symbol = symbol_short!("SUBM_REM");
+
+// ============================================================================
+// Event Structures
+// ============================================================================
+
+/// Event emitted when a snapshot is submitted.
+///
+/// Shares its topics and shape with the stellar_insights contract's event, so
+/// indexers can attribute each epoch to the key that published it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotSubmitted {
+    /// 32-byte hash of the analytics snapshot
+    pub hash: BytesN<32>,
+    /// Epoch identifier for this snapshot
+    pub epoch: u64,
+    /// Ledger timestamp when the snapshot was recorded
+    pub timestamp: u64,
+    /// Address that submitted the snapshot
+    pub submitter: Address,
+}
+
+impl SnapshotSubmitted {
+    /// Publish under (SNAPSHOT_SUBMITTED, SNAPSHOT_LIFECYCLE)
+    pub fn publish(env: &Env, hash: BytesN<32>, epoch: u64, timestamp: u64, submitter: Address) {
+        let event = SnapshotSubmitted {
+            hash,
+            epoch,
+            timestamp,
+            submitter,
+        };
+        env.events()
+            .publish((SNAPSHOT_SUBMITTED, SNAPSHOT_LIFECYCLE), event);
+    }
+}
+
+/// Publish an admin change under (ADMIN_TRANSFERRED,) with data
+/// `(previous_admin, new_admin)`, the same shape as the snapshot contract's
+/// `transfer_admin` event.
+pub fn publish_admin_transferred(env: &Env, previous_admin: Address, new_admin: Address) {
+    env.events()
+        .publish((ADMIN_TRANSFERRED,), (previous_admin, new_admin));
+}
+
+/// Publish a submitter grant under (SUBMITTER_ADDED,) with data
+/// `(admin, submitter)`
+pub fn publish_submitter_added(env: &Env, admin: Address, submitter: Address) {
+    env.events().publish((SUBMITTER_ADDED,), (admin, submitter));
+}
+
+/// Publish a submitter revocation under (SUBMITTER_REMOVED,) with data
+/// `(admin, submitter)`
+pub fn publish_submitter_removed(env: &Env, admin: Address, submitter: Address) {
+    env.events()
+        .publish((SUBMITTER_REMOVED,), (admin, submitter));
+}
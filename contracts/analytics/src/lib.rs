@@ -1,5 +1,31 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal, Map, Val, Vec,
+};
+
+pub mod events;
+
+use events::SnapshotSubmitted;
+
+/// Maximum number of snapshots returned by a single range query
+pub const MAX_SNAPSHOTS_PER_QUERY: u32 = 50;
+
+/// Maximum number of submitters besides the admin
+pub const MAX_SUBMITTERS: u32 = 20;
+
+/// Storage layout written by this version of the contract. Version 1 kept
+/// the whole history in one `Snapshots` map; version 2 gives each epoch its
+/// own entries.
+pub const STORAGE_VERSION: u32 = 2;
+
+/// Ledgers a snapshot entry is kept alive for when written (about 30 days)
+pub const SNAPSHOT_TTL_LEDGERS: u32 = 518_400;
+
+/// Remaining TTL below which a snapshot entry's TTL is extended (about 5 days)
+pub const SNAPSHOT_TTL_THRESHOLD: u32 = 86_400;
+
+/// Newest epochs whose entries have their TTL extended when read
+pub const AUTO_EXTEND_RECENT_EPOCHS: u32 = 10;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -7,14 +33,27 @@ pub struct SnapshotMetadata {
     pub epoch: u64,
     pub timestamp: u64,
     pub hash: BytesN<32>,
+    /// Address that submitted the snapshot
+    pub submitter: Address,
     // Extendable for future fields
 }
 
+/// Layout of `SnapshotMetadata` in version 1 storage, before the submitter
+/// was recorded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct SnapshotMetadataV1 {
+    epoch: u64,
+    timestamp: u64,
+    hash: BytesN<32>,
+}
+
 #[contracttype]
 pub enum DataKey {
-    /// Authorized submitter address (only this address can submit snapshots)
+    /// Admin address (submits snapshots and manages the other submitters)
     Admin,
-    /// Map of epoch -> snapshot metadata (persistent storage for full history)
+    /// Legacy (version 1) map of epoch -> snapshot metadata, moved to
+    /// `Snapshot` entries by `migrate_v1_to_v2` (persistent storage)
     Snapshots,
     /// Latest epoch number (instance storage for quick access)
     LatestEpoch,
@@ -22,6 +61,47 @@ pub enum DataKey {
     Paused,
     /// Governance contract address (only it can call set_admin_by_governance / set_paused_by_governance)
     Governance,
+    /// Legacy (version 1) map of epoch -> hash of the snapshot's SLA section,
+    /// moved to `SlaHash` entries by `migrate_v1_to_v2` (persistent storage)
+    SlaHashes,
+    /// Most snapshots kept in the history; older ones are archived
+    /// (instance storage, absent = unlimited)
+    MaxHistoryEntries,
+    /// A snapshot evicted from the history (persistent storage)
+    Archived(u64),
+    /// SLA hash of an archived snapshot, if it had one (persistent storage)
+    ArchivedSla(u64),
+    /// Oldest archived epoch, where pruning starts (instance storage)
+    ArchiveOldest,
+    /// Newest archived epoch, which the next eviction links from (instance storage)
+    ArchiveNewest,
+    /// Addresses besides the admin allowed to submit snapshots (instance storage)
+    Submitters,
+    /// Metadata of a snapshot in the history (persistent storage)
+    Snapshot(u64),
+    /// Hash of the SLA section of a snapshot in the history (persistent storage)
+    SlaHash(u64),
+    /// Epoch at a position of the history index; positions from
+    /// `HistoryStart` up to `HistoryEnd` hold the history's epochs in
+    /// ascending order (persistent storage)
+    HistoryEpoch(u32),
+    /// Position of the oldest epoch in the history (instance storage)
+    HistoryStart,
+    /// Position after the newest epoch in the history (instance storage)
+    HistoryEnd,
+    /// Storage layout version (instance storage, absent = 1)
+    StorageVersion,
+}
+
+/// A snapshot evicted from the history into its own storage entry.
+/// Archived entries form a list from `ArchiveOldest` to `ArchiveNewest`, so
+/// pruning can walk them without an index.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedSnapshot {
+    pub snapshot: SnapshotMetadata,
+    /// Next archived epoch, 0 for the newest
+    pub next_epoch: u64,
 }
 
 #[contract]
@@ -55,26 +135,66 @@ impl AnalyticsContract {
         // Initialize contract as not paused
         storage.set(&DataKey::Paused, &false);
 
-        // Initialize empty snapshots map
-        let persistent_storage = env.storage().persistent();
-        let empty_snapshots = Map::<u64, SnapshotMetadata>::new(&env);
-        persistent_storage.set(&DataKey::Snapshots, &empty_snapshots);
+        // Fresh deployments start on the current storage layout
+        storage.set(&DataKey::StorageVersion, &STORAGE_VERSION);
+    }
+
+    /// Initialize like `initialize`, keeping at most `max_history_entries`
+    /// snapshots in the history.
+    ///
+    /// Once the history is full, each submission moves the oldest snapshot
+    /// into the archive. Archived snapshots are still returned by
+    /// `get_snapshot` and `get_sla_hash` while their entries live, but are
+    /// left out of the history, range and page queries, and their entries are
+    /// not kept alive by contract activity, so storage rent for the history
+    /// stays bounded.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `admin` - Address authorized to submit snapshots
+    /// * `max_history_entries` - Snapshots kept in the history
+    ///
+    /// # Panics
+    /// * If contract is already initialized (admin already set)
+    /// * If `max_history_entries` is 0
+    pub fn initialize_with_retention(env: Env, admin: Address, max_history_entries: u32) {
+        if max_history_entries == 0 {
+            panic!("Invalid retention: max_history_entries must be greater than 0");
+        }
+
+        Self::initialize(env.clone(), admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxHistoryEntries, &max_history_entries);
+    }
+
+    /// Get the most snapshots kept in the history
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    ///
+    /// # Returns
+    /// * The limit, or None if history is unlimited
+    pub fn get_max_history_entries(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::MaxHistoryEntries)
     }
 
     /// Submit a new snapshot for a specific epoch.
-    /// Stores the snapshot in the historical map and updates latest epoch.
+    /// Stores the snapshot in the history, records the submitter,
+    /// updates latest epoch and emits a `SnapshotSubmitted` event.
     /// Epochs must be submitted in strictly increasing order (monotonicity).
     ///
     /// # Arguments
     /// * `env` - Contract environment
     /// * `epoch` - Epoch identifier (must be positive and strictly greater than latest)
     /// * `hash` - 32-byte hash of the analytics snapshot
-    /// * `caller` - Address attempting to submit (must be the authorized admin)
+    /// * `caller` - Address attempting to submit (must be the admin or a registered submitter)
     ///
     /// # Panics
     /// * If contract is paused for emergency maintenance
     /// * If admin is not set (contract not initialized)
-    /// * If caller is not the authorized admin
+    /// * If caller is neither the admin nor a registered submitter
+    /// * If storage has not been migrated with `migrate_v1_to_v2`
     /// * If epoch is 0 (invalid)
     /// * If epoch <= latest (monotonicity violated: out-of-order or duplicate)
     ///
@@ -94,16 +214,17 @@ impl AnalyticsContract {
         // Require authentication from the caller
         caller.require_auth();
 
-        // Verify caller is the authorized admin
+        // Verify caller is the admin or a registered submitter
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
             .expect("Contract not initialized: admin not set");
 
-        if caller != admin {
-            panic!("Unauthorized: only the admin can submit snapshots");
+        if caller != admin && !Self::get_submitters(&env).contains(&caller) {
+            panic!("Unauthorized: only the admin or a registered submitter can submit snapshots");
         }
+        Self::require_migrated(&env);
 
         if epoch == 0 {
             panic!("Invalid epoch: must be greater than 0");
@@ -130,26 +251,71 @@ impl AnalyticsContract {
         let metadata = SnapshotMetadata {
             epoch,
             timestamp,
-            hash,
+            hash: hash.clone(),
+            submitter: caller.clone(),
         };
 
-        let mut snapshots: Map<u64, SnapshotMetadata> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Snapshots)
-            .unwrap_or_else(|| Map::new(&env));
-
-        snapshots.set(epoch, metadata);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Snapshots, &snapshots);
+        Self::set_entry(&env, &DataKey::Snapshot(epoch), &metadata);
+        Self::push_epoch(&env, epoch);
+        Self::archive_overflow(&env);
         env.storage().instance().set(&DataKey::LatestEpoch, &epoch);
 
+        SnapshotSubmitted::publish(&env, hash, epoch, timestamp, caller);
+
         timestamp
     }
 
+    /// Submit a snapshot together with the hash of its SLA section.
+    /// Behaves like `submit_snapshot` and additionally records `sla_hash`, so
+    /// anchors can prove their SLA figures were anchored without publishing
+    /// the whole snapshot.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch identifier (must be positive and strictly greater than latest)
+    /// * `hash` - 32-byte hash of the analytics snapshot
+    /// * `sla_hash` - 32-byte hash of the snapshot's SLA summaries
+    /// * `caller` - Address attempting to submit (must be the authorized admin)
+    ///
+    /// # Panics
+    /// * Same conditions as `submit_snapshot`
+    ///
+    /// # Returns
+    /// * Ledger timestamp when snapshot was recorded
+    pub fn submit_snapshot_with_sla(
+        env: Env,
+        epoch: u64,
+        hash: BytesN<32>,
+        sla_hash: BytesN<32>,
+        caller: Address,
+    ) -> u64 {
+        let timestamp = Self::submit_snapshot(env.clone(), epoch, hash, caller);
+        Self::set_entry(&env, &DataKey::SlaHash(epoch), &sla_hash);
+
+        timestamp
+    }
+
+    /// Get the SLA section hash recorded for a specific epoch
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch to retrieve
+    ///
+    /// # Returns
+    /// * The SLA hash, or None if the epoch was submitted without one
+    pub fn get_sla_hash(env: Env, epoch: u64) -> Option<BytesN<32>> {
+        Self::extend_if_recent(&env, epoch);
+        let persistent = env.storage().persistent();
+        persistent
+            .get(&DataKey::SlaHash(epoch))
+            .or_else(|| persistent.get(&DataKey::ArchivedSla(epoch)))
+    }
+
     /// Get snapshot metadata for a specific epoch
     ///
+    /// Reading one of the `AUTO_EXTEND_RECENT_EPOCHS` newest epochs extends
+    /// its entries' TTL once they fall below `SNAPSHOT_TTL_THRESHOLD`.
+    ///
     /// # Arguments
     /// * `env` - Contract environment
     /// * `epoch` - Epoch to retrieve
@@ -157,13 +323,134 @@ impl AnalyticsContract {
     /// # Returns
     /// * Snapshot metadata for the epoch, or None if not found
     pub fn get_snapshot(env: Env, epoch: u64) -> Option<SnapshotMetadata> {
-        let snapshots: Map<u64, SnapshotMetadata> = env
-            .storage()
+        Self::extend_if_recent(&env, epoch);
+        env.storage()
             .persistent()
-            .get(&DataKey::Snapshots)
-            .unwrap_or_else(|| Map::new(&env));
+            .get(&DataKey::Snapshot(epoch))
+            .or_else(|| Self::get_archived(&env, epoch).map(|archived| archived.snapshot))
+    }
 
-        snapshots.get(epoch)
+    /// Extend the TTL of an epoch's entries, archived or not, to at least
+    /// `ledgers` ledgers. Anyone may pay to keep a snapshot alive.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch to keep alive
+    /// * `ledgers` - Ledgers the entries should live for from now, at most the
+    ///   network's maximum entry TTL
+    ///
+    /// # Panics
+    /// * If `ledgers` is 0
+    /// * If no snapshot exists for the epoch
+    pub fn extend_snapshot_ttl(env: Env, epoch: u64, ledgers: u32) {
+        if ledgers == 0 {
+            panic!("Invalid ledgers: must be greater than 0");
+        }
+
+        let persistent = env.storage().persistent();
+        let (snapshot_key, sla_key) = if persistent.has(&DataKey::Snapshot(epoch)) {
+            (DataKey::Snapshot(epoch), DataKey::SlaHash(epoch))
+        } else if persistent.has(&DataKey::Archived(epoch)) {
+            (DataKey::Archived(epoch), DataKey::ArchivedSla(epoch))
+        } else {
+            panic!("Snapshot not found for epoch {}", epoch);
+        };
+
+        persistent.extend_ttl(&snapshot_key, ledgers, ledgers);
+        if persistent.has(&sla_key) {
+            persistent.extend_ttl(&sla_key, ledgers, ledgers);
+        }
+        if let Some(key) = Self::index_key(&env, epoch) {
+            persistent.extend_ttl(&key, ledgers, ledgers);
+        }
+    }
+
+    /// Check a published dataset against the hash committed for `epoch`
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch the dataset claims to be
+    /// * `payload` - The dataset exactly as hashed when submitted (for the
+    ///   backend, its canonical JSON)
+    ///
+    /// # Returns
+    /// * True if the SHA-256 of `payload` equals the stored hash, false if it
+    ///   differs or no snapshot exists for the epoch
+    pub fn verify_snapshot(env: Env, epoch: u64, payload: Bytes) -> bool {
+        match Self::get_snapshot(env.clone(), epoch) {
+            Some(snapshot) => env.crypto().sha256(&payload).to_bytes() == snapshot.hash,
+            None => false,
+        }
+    }
+
+    /// Delete every snapshot, archived or not, with an epoch below
+    /// `before_epoch`, along with its SLA hash
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `before_epoch` - First epoch to keep
+    /// * `caller` - Address attempting to prune (must be the authorized admin)
+    ///
+    /// # Panics
+    /// * If admin is not set (contract not initialized)
+    /// * If caller is not the authorized admin
+    /// * If storage has not been migrated with `migrate_v1_to_v2`
+    /// * If `before_epoch` is past the latest epoch (the latest snapshot is always kept)
+    ///
+    /// # Returns
+    /// * Number of snapshots deleted
+    pub fn prune_history(env: Env, before_epoch: u64, caller: Address) -> u32 {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized: admin not set");
+
+        if caller != admin {
+            panic!("Unauthorized: only the admin can prune history");
+        }
+        Self::require_migrated(&env);
+
+        let latest = Self::get_latest_epoch(env.clone());
+        if before_epoch > latest {
+            panic!(
+                "Cannot prune the latest snapshot: before_epoch {} is past latest {}",
+                before_epoch, latest
+            );
+        }
+
+        let mut pruned = 0u32;
+
+        // Archived snapshots are all older than those in the history
+        let instance = env.storage().instance();
+        let persistent = env.storage().persistent();
+        let mut next: u64 = instance.get(&DataKey::ArchiveOldest).unwrap_or(0);
+        while next != 0 && next < before_epoch {
+            let archived: ArchivedSnapshot = persistent
+                .get(&DataKey::Archived(next))
+                .expect("Archived snapshot missing");
+            persistent.remove(&DataKey::Archived(next));
+            persistent.remove(&DataKey::ArchivedSla(next));
+            pruned += 1;
+            next = archived.next_epoch;
+        }
+        if next == 0 {
+            instance.remove(&DataKey::ArchiveOldest);
+            instance.remove(&DataKey::ArchiveNewest);
+        } else {
+            instance.set(&DataKey::ArchiveOldest, &next);
+        }
+
+        while Self::history_len(&env) > 0 && Self::epoch_at(&env, 0) < before_epoch {
+            let epoch = Self::pop_oldest_epoch(&env);
+            persistent.remove(&DataKey::Snapshot(epoch));
+            persistent.remove(&DataKey::SlaHash(epoch));
+            pruned += 1;
+        }
+
+        pruned
     }
 
     /// Get the latest snapshot metadata
@@ -189,16 +476,139 @@ impl AnalyticsContract {
 
     /// Get the complete snapshot history as a Map
     ///
+    /// Reads one entry per epoch; prefer the page, range and recent queries
+    /// for long histories.
+    ///
     /// # Arguments
     /// * `env` - Contract environment
     ///
     /// # Returns
-    /// * Map of all snapshots keyed by epoch
+    /// * Map of all snapshots in the history (archived ones excluded) keyed by epoch
     pub fn get_snapshot_history(env: Env) -> Map<u64, SnapshotMetadata> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Snapshots)
-            .unwrap_or_else(|| Map::new(&env))
+        let mut history = Map::new(&env);
+        for index in 0..Self::history_len(&env) {
+            let epoch = Self::epoch_at(&env, index);
+            history.set(epoch, Self::history_entry(&env, epoch));
+        }
+        history
+    }
+
+    /// Get snapshots recorded within a ledger timestamp range (inclusive)
+    ///
+    /// Lets light clients fetch recent epochs by wall-clock time without knowing
+    /// epoch numbers. Results are in ascending epoch order and capped at
+    /// `MAX_SNAPSHOTS_PER_QUERY`; to page, call again with `ts_start` set just
+    /// past the last returned timestamp.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `ts_start` - Earliest ledger timestamp to include
+    /// * `ts_end` - Latest ledger timestamp to include
+    ///
+    /// # Returns
+    /// * Snapshots whose timestamp falls in the range (empty if `ts_start > ts_end`)
+    pub fn get_snapshots_between(
+        env: Env,
+        ts_start: u64,
+        ts_end: u64,
+    ) -> soroban_sdk::Vec<SnapshotMetadata> {
+        let mut result = soroban_sdk::Vec::new(&env);
+        if ts_start > ts_end {
+            return result;
+        }
+
+        // Epochs are submitted in order, so timestamps never decrease; binary
+        // search for the first snapshot in range
+        let first = Self::partition_point(&env, |epoch| {
+            Self::history_entry(&env, epoch).timestamp < ts_start
+        });
+
+        for index in first..Self::history_len(&env) {
+            let snapshot = Self::history_entry(&env, Self::epoch_at(&env, index));
+            if snapshot.timestamp > ts_end || result.len() >= MAX_SNAPSHOTS_PER_QUERY {
+                break;
+            }
+            result.push_back(snapshot);
+        }
+
+        result
+    }
+
+    /// Get up to `limit` snapshots starting at `start_epoch` (inclusive)
+    ///
+    /// Results are in ascending epoch order and capped at
+    /// `MAX_SNAPSHOTS_PER_QUERY`. To page through the full history, start at 0
+    /// and call again with `start_epoch` set to the last returned epoch + 1
+    /// until fewer than `limit` snapshots come back.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `start_epoch` - First epoch to include
+    /// * `limit` - Maximum number of snapshots to return
+    ///
+    /// # Returns
+    /// * Snapshots with epoch >= `start_epoch` (empty if `limit` is 0)
+    pub fn get_snapshot_page(
+        env: Env,
+        start_epoch: u64,
+        limit: u32,
+    ) -> soroban_sdk::Vec<SnapshotMetadata> {
+        let mut result = soroban_sdk::Vec::new(&env);
+        let limit = limit.min(MAX_SNAPSHOTS_PER_QUERY);
+        if limit == 0 {
+            return result;
+        }
+
+        let first = Self::partition_point(&env, |epoch| epoch < start_epoch);
+        for index in first..Self::history_len(&env) {
+            let epoch = Self::epoch_at(&env, index);
+            result.push_back(Self::history_entry(&env, epoch));
+            if result.len() >= limit {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Get the `n` most recent snapshots in one call
+    ///
+    /// Results are newest first and capped at `MAX_SNAPSHOTS_PER_QUERY`.
+    /// Archived snapshots are not included; fetch those with `get_snapshot`.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `n` - Maximum number of snapshots to return
+    ///
+    /// # Returns
+    /// * Up to `n` snapshots in descending epoch order (empty if `n` is 0)
+    pub fn get_recent_snapshots(env: Env, n: u32) -> soroban_sdk::Vec<SnapshotMetadata> {
+        let mut result = soroban_sdk::Vec::new(&env);
+        let n = n.min(MAX_SNAPSHOTS_PER_QUERY);
+        if n == 0 {
+            return result;
+        }
+
+        for index in (0..Self::history_len(&env)).rev() {
+            let epoch = Self::epoch_at(&env, index);
+            result.push_back(Self::history_entry(&env, epoch));
+            if result.len() >= n {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Get the number of stored snapshots
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    ///
+    /// # Returns
+    /// * Number of epochs with snapshots
+    pub fn get_snapshot_count(env: Env) -> u32 {
+        Self::history_len(&env)
     }
 
     /// Get the latest epoch number
@@ -217,20 +627,112 @@ impl AnalyticsContract {
 
     /// Get all epochs that have snapshots (for iteration purposes)
     ///
+    /// Reads one index entry per epoch; prefer `get_snapshot_page` for long
+    /// histories.
+    ///
     /// # Arguments
     /// * `env` - Contract environment
     ///
     /// # Returns
     /// * Vector of all epochs with stored snapshots
     pub fn get_all_epochs(env: Env) -> soroban_sdk::Vec<u64> {
-        let snapshots = Self::get_snapshot_history(env.clone());
         let mut epochs = soroban_sdk::Vec::new(&env);
+        for index in 0..Self::history_len(&env) {
+            epochs.push_back(Self::epoch_at(&env, index));
+        }
+        epochs
+    }
+
+    /// Get the storage layout version
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    ///
+    /// # Returns
+    /// * 1 for deployments that have not run `migrate_v1_to_v2` yet,
+    ///   otherwise `STORAGE_VERSION`
+    pub fn get_storage_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StorageVersion)
+            .unwrap_or(1)
+    }
+
+    /// Move up to `limit` snapshots, oldest first, from the version 1
+    /// `Snapshots` and `SlaHashes` maps into their own persistent entries,
+    /// extending each entry's TTL
+    ///
+    /// Deployments upgraded from version 1 call this until it returns 0;
+    /// the storage version then becomes 2. Submissions and pruning are
+    /// refused until then, and reads only see epochs already moved. Archived
+    /// snapshots already have their own entries and are left as they are.
+    /// Version 1 did not record submitters, so migrated snapshots name the
+    /// admin as theirs.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Address running the migration (must be the admin)
+    /// * `limit` - Most snapshots to move in this call
+    ///
+    /// # Panics
+    /// * If contract is not initialized (admin not set)
+    /// * If caller is not the admin
+    /// * If storage is already on version 2
+    /// * If `limit` is 0
+    ///
+    /// # Returns
+    /// * Number of snapshots still to move
+    pub fn migrate_v1_to_v2(env: Env, caller: Address, limit: u32) -> u32 {
+        let admin = Self::require_admin(&env, &caller, "migrate storage");
+        if Self::get_storage_version(env.clone()) >= STORAGE_VERSION {
+            panic!("Storage already migrated to version {}", STORAGE_VERSION);
+        }
+        if limit == 0 {
+            panic!("Invalid limit: must be greater than 0");
+        }
+
+        let persistent = env.storage().persistent();
+        let mut legacy: Map<u64, SnapshotMetadataV1> = persistent
+            .get(&DataKey::Snapshots)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut legacy_sla: Map<u64, BytesN<32>> = persistent
+            .get(&DataKey::SlaHashes)
+            .unwrap_or_else(|| Map::new(&env));
 
-        for (epoch, _) in snapshots.iter() {
-            epochs.push_back(epoch);
+        let mut moved = 0u32;
+        while moved < limit {
+            let Some((epoch, legacy_snapshot)) = legacy.iter().next() else {
+                break;
+            };
+            let snapshot = SnapshotMetadata {
+                epoch: legacy_snapshot.epoch,
+                timestamp: legacy_snapshot.timestamp,
+                hash: legacy_snapshot.hash,
+                submitter: admin.clone(),
+            };
+            Self::set_entry(&env, &DataKey::Snapshot(epoch), &snapshot);
+            if let Some(sla_hash) = legacy_sla.get(epoch) {
+                Self::set_entry(&env, &DataKey::SlaHash(epoch), &sla_hash);
+                legacy_sla.remove(epoch);
+            }
+            legacy.remove(epoch);
+            Self::push_epoch(&env, epoch);
+            moved += 1;
         }
 
-        epochs
+        let remaining = legacy.len();
+        if remaining == 0 {
+            persistent.remove(&DataKey::Snapshots);
+            persistent.remove(&DataKey::SlaHashes);
+            env.storage()
+                .instance()
+                .set(&DataKey::StorageVersion, &STORAGE_VERSION);
+        } else {
+            persistent.set(&DataKey::Snapshots, &legacy);
+            persistent.set(&DataKey::SlaHashes, &legacy_sla);
+        }
+
+        remaining
     }
 
     /// Get the current authorized admin address
@@ -272,6 +774,69 @@ impl AnalyticsContract {
 
         // Update admin address
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        events::publish_admin_transferred(&env, admin, new_admin);
+    }
+
+    /// Allow an address to submit snapshots alongside the admin
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Address granting the role (must be admin)
+    /// * `submitter` - Address to allow
+    ///
+    /// # Panics
+    /// * If contract is not initialized (admin not set)
+    /// * If caller is not the admin
+    /// * If the address is already a submitter
+    /// * If `MAX_SUBMITTERS` submitters are already registered
+    pub fn add_submitter(env: Env, caller: Address, submitter: Address) {
+        let admin = Self::require_admin(&env, &caller, "manage submitters");
+
+        let mut submitters = Self::get_submitters(&env);
+        if submitters.contains(&submitter) {
+            panic!("Submitter already registered");
+        }
+        if submitters.len() >= MAX_SUBMITTERS {
+            panic!("Too many submitters: at most {} allowed", MAX_SUBMITTERS);
+        }
+        submitters.push_back(submitter.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Submitters, &submitters);
+
+        events::publish_submitter_added(&env, admin, submitter);
+    }
+
+    /// Revoke an address's permission to submit snapshots
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Address revoking the role (must be admin)
+    /// * `submitter` - Address to revoke
+    ///
+    /// # Panics
+    /// * If contract is not initialized (admin not set)
+    /// * If caller is not the admin
+    /// * If the address is not a submitter
+    pub fn remove_submitter(env: Env, caller: Address, submitter: Address) {
+        let admin = Self::require_admin(&env, &caller, "manage submitters");
+
+        let mut submitters = Self::get_submitters(&env);
+        let index = submitters
+            .first_index_of(&submitter)
+            .unwrap_or_else(|| panic!("Submitter not registered"));
+        submitters.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::Submitters, &submitters);
+
+        events::publish_submitter_removed(&env, admin, submitter);
+    }
+
+    /// Get the addresses besides the admin allowed to submit snapshots,
+    /// in the order they were added
+    pub fn list_submitters(env: Env) -> Vec<Address> {
+        Self::get_submitters(&env)
     }
 
     /// Emergency pause the contract
@@ -366,7 +931,13 @@ impl AnalyticsContract {
             panic!("Unauthorized: only the governance contract can set admin");
         }
 
+        let previous_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized: admin not set");
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        events::publish_admin_transferred(&env, previous_admin, new_admin);
     }
 
     /// Set the paused state. Only the governance contract may call this (after a passed proposal).
@@ -399,5 +970,190 @@ impl AnalyticsContract {
     }
 }
 
+impl AnalyticsContract {
+    /// Authenticate `caller` as the admin, panicking with "only the admin can
+    /// {action}" otherwise. Returns the admin.
+    fn require_admin(env: &Env, caller: &Address, action: &str) -> Address {
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized: admin not set");
+        if *caller != admin {
+            panic!("Unauthorized: only the admin can {}", action);
+        }
+        admin
+    }
+
+    fn get_submitters(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Submitters)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn get_archived(env: &Env, epoch: u64) -> Option<ArchivedSnapshot> {
+        env.storage().persistent().get(&DataKey::Archived(epoch))
+    }
+
+    /// Panic unless storage is on the current layout
+    fn require_migrated(env: &Env) {
+        if Self::get_storage_version(env.clone()) < STORAGE_VERSION {
+            panic!("Storage migration required: call migrate_v1_to_v2");
+        }
+    }
+
+    /// Positions of the oldest epoch in the history and just past the newest
+    fn history_bounds(env: &Env) -> (u32, u32) {
+        let instance = env.storage().instance();
+        (
+            instance.get(&DataKey::HistoryStart).unwrap_or(0),
+            instance.get(&DataKey::HistoryEnd).unwrap_or(0),
+        )
+    }
+
+    /// Number of epochs in the history
+    fn history_len(env: &Env) -> u32 {
+        let (start, end) = Self::history_bounds(env);
+        end - start
+    }
+
+    /// The `index`th oldest epoch in the history
+    fn epoch_at(env: &Env, index: u32) -> u64 {
+        let (start, _) = Self::history_bounds(env);
+        env.storage()
+            .persistent()
+            .get(&DataKey::HistoryEpoch(start + index))
+            .expect("History index entry missing")
+    }
+
+    /// Number of oldest epochs in the history for which `before` holds;
+    /// `before` must hold for a prefix of the history and not after it
+    fn partition_point(env: &Env, before: impl Fn(u64) -> bool) -> u32 {
+        let (mut low, mut high) = (0, Self::history_len(env));
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if before(Self::epoch_at(env, mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Index entry holding `epoch`, if it is in the history
+    fn index_key(env: &Env, epoch: u64) -> Option<DataKey> {
+        let index = Self::partition_point(env, |e| e < epoch);
+        if index < Self::history_len(env) && Self::epoch_at(env, index) == epoch {
+            let (start, _) = Self::history_bounds(env);
+            Some(DataKey::HistoryEpoch(start + index))
+        } else {
+            None
+        }
+    }
+
+    /// Append `epoch`, which must be newer than every epoch in the history
+    fn push_epoch(env: &Env, epoch: u64) {
+        let (_, end) = Self::history_bounds(env);
+        Self::set_entry(env, &DataKey::HistoryEpoch(end), &epoch);
+        env.storage()
+            .instance()
+            .set(&DataKey::HistoryEnd, &(end + 1));
+    }
+
+    /// Remove the oldest epoch from a non-empty history and return it
+    fn pop_oldest_epoch(env: &Env) -> u64 {
+        let epoch = Self::epoch_at(env, 0);
+        let (start, _) = Self::history_bounds(env);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::HistoryEpoch(start));
+        env.storage()
+            .instance()
+            .set(&DataKey::HistoryStart, &(start + 1));
+        epoch
+    }
+
+    /// Write a persistent entry and keep it alive for `SNAPSHOT_TTL_LEDGERS`
+    fn set_entry<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+        let persistent = env.storage().persistent();
+        persistent.set(key, value);
+        persistent.extend_ttl(key, SNAPSHOT_TTL_THRESHOLD, SNAPSHOT_TTL_LEDGERS);
+    }
+
+    /// Extend the TTL of `epoch`'s entries if it is one of the
+    /// `AUTO_EXTEND_RECENT_EPOCHS` newest in the history
+    fn extend_if_recent(env: &Env, epoch: u64) {
+        let len = Self::history_len(env);
+        if len == 0 {
+            return;
+        }
+        let oldest_recent = Self::epoch_at(env, len.saturating_sub(AUTO_EXTEND_RECENT_EPOCHS));
+        if epoch < oldest_recent {
+            return;
+        }
+
+        let persistent = env.storage().persistent();
+        let keys = [
+            Some(DataKey::Snapshot(epoch)),
+            Some(DataKey::SlaHash(epoch)),
+            Self::index_key(env, epoch),
+        ];
+        for key in keys.into_iter().flatten() {
+            if persistent.has(&key) {
+                persistent.extend_ttl(&key, SNAPSHOT_TTL_THRESHOLD, SNAPSHOT_TTL_LEDGERS);
+            }
+        }
+    }
+
+    /// Metadata of an epoch listed in the history
+    fn history_entry(env: &Env, epoch: u64) -> SnapshotMetadata {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Snapshot(epoch))
+            .expect("Snapshot missing")
+    }
+
+    /// Move the oldest snapshots out of the history until it fits the
+    /// configured `MaxHistoryEntries`
+    fn archive_overflow(env: &Env) {
+        let instance = env.storage().instance();
+        let Some(max_entries) = instance.get::<_, u32>(&DataKey::MaxHistoryEntries) else {
+            return;
+        };
+
+        let persistent = env.storage().persistent();
+        while Self::history_len(env) > max_entries {
+            let epoch = Self::pop_oldest_epoch(env);
+            let snapshot = Self::history_entry(env, epoch);
+            persistent.remove(&DataKey::Snapshot(epoch));
+
+            // Link the previous newest archived snapshot to this one
+            match instance.get::<_, u64>(&DataKey::ArchiveNewest) {
+                Some(newest) => {
+                    let mut previous: ArchivedSnapshot = persistent
+                        .get(&DataKey::Archived(newest))
+                        .expect("Archived snapshot missing");
+                    previous.next_epoch = epoch;
+                    persistent.set(&DataKey::Archived(newest), &previous);
+                }
+                None => instance.set(&DataKey::ArchiveOldest, &epoch),
+            }
+            let archived = ArchivedSnapshot {
+                snapshot,
+                next_epoch: 0,
+            };
+            persistent.set(&DataKey::Archived(epoch), &archived);
+            if let Some(sla_hash) = persistent.get::<_, BytesN<32>>(&DataKey::SlaHash(epoch)) {
+                persistent.set(&DataKey::ArchivedSla(epoch), &sla_hash);
+                persistent.remove(&DataKey::SlaHash(epoch));
+            }
+            instance.set(&DataKey::ArchiveNewest, &epoch);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
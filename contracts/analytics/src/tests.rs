@@ -1,7 +1,7 @@
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, BytesN, Env,
+    testutils::{storage::Persistent as _, Address as _, Events, Ledger},
+    Address, Bytes, BytesN, Env, IntoVal, Map,
 };
 
 fn create_test_hash(env: &Env, value: u8) -> BytesN<32> {
@@ -71,6 +71,41 @@ fn test_submit_single_snapshot() {
     assert_eq!(latest.epoch, epoch);
     assert_eq!(latest.hash, hash);
     assert_eq!(latest.timestamp, timestamp);
+    assert_eq!(latest.submitter, admin);
+}
+
+#[test]
+fn test_submit_snapshot_emits_submitter_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    env.ledger().set_timestamp(500);
+    let hash = create_test_hash(&env, 7);
+    client.submit_snapshot(&3, &hash, &admin);
+
+    let expected = events::SnapshotSubmitted {
+        hash,
+        epoch: 3,
+        timestamp: 500,
+        submitter: admin,
+    };
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                contract_id,
+                (events::SNAPSHOT_SUBMITTED, events::SNAPSHOT_LIFECYCLE).into_val(&env),
+                expected.into_val(&env),
+            ),
+        ]
+    );
 }
 
 #[test]
@@ -281,6 +316,604 @@ fn test_bounded_storage_growth_simulation() {
     assert_eq!(client.get_all_epochs().len(), num_epochs as u32);
 }
 
+#[test]
+fn test_submit_snapshot_with_sla_records_sla_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    env.ledger().set_timestamp(500);
+    let hash = create_test_hash(&env, 1);
+    let sla_hash = create_test_hash(&env, 2);
+
+    let timestamp = client.submit_snapshot_with_sla(&1, &hash, &sla_hash, &admin);
+    assert_eq!(timestamp, 500);
+    assert_eq!(client.get_snapshot(&1).unwrap().hash, hash);
+    assert_eq!(client.get_sla_hash(&1), Some(sla_hash));
+
+    // Epochs submitted without SLA figures have no SLA hash
+    client.submit_snapshot(&2, &create_test_hash(&env, 3), &admin);
+    assert_eq!(client.get_sla_hash(&2), None);
+    assert_eq!(client.get_latest_epoch(), 2);
+}
+
+#[test]
+#[should_panic(expected = "already exists")]
+fn test_submit_snapshot_with_sla_keeps_monotonicity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    client.submit_snapshot_with_sla(
+        &1,
+        &create_test_hash(&env, 1),
+        &create_test_hash(&env, 2),
+        &admin,
+    );
+}
+
+#[test]
+fn test_verify_snapshot_checks_payload_against_stored_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    let payload = Bytes::from_slice(&env, br#"{"epoch":1,"corridors":[]}"#);
+    let hash: BytesN<32> = env.crypto().sha256(&payload).into();
+    client.submit_snapshot(&1, &hash, &admin);
+
+    assert!(client.verify_snapshot(&1, &payload));
+
+    let tampered = Bytes::from_slice(&env, br#"{"epoch":1,"corridors":[1]}"#);
+    assert!(!client.verify_snapshot(&1, &tampered));
+    // The payload must match the epoch it claims to be
+    assert!(!client.verify_snapshot(&2, &payload));
+}
+
+#[test]
+fn test_get_snapshots_between_timestamps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    for epoch in 1..=5u64 {
+        env.ledger().set_timestamp(epoch * 100);
+        client.submit_snapshot(&epoch, &create_test_hash(&env, epoch as u8), &admin);
+    }
+
+    let range = client.get_snapshots_between(&200, &400);
+    assert_eq!(range.len(), 3);
+    assert_eq!(range.get(0).unwrap().epoch, 2);
+    assert_eq!(range.get(2).unwrap().epoch, 4);
+
+    assert_eq!(client.get_snapshots_between(&150, &199).len(), 0);
+    assert_eq!(client.get_snapshots_between(&500, &100).len(), 0);
+    assert_eq!(client.get_snapshots_between(&0, &u64::MAX).len(), 5);
+}
+
+#[test]
+fn test_get_snapshots_between_is_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    let num_epochs = MAX_SNAPSHOTS_PER_QUERY as u64 + 10;
+    for epoch in 1..=num_epochs {
+        env.ledger().set_timestamp(epoch * 10);
+        client.submit_snapshot(&epoch, &create_test_hash(&env, (epoch % 255) as u8), &admin);
+    }
+
+    let first_page = client.get_snapshots_between(&0, &u64::MAX);
+    assert_eq!(first_page.len(), MAX_SNAPSHOTS_PER_QUERY);
+
+    let last = first_page.get(MAX_SNAPSHOTS_PER_QUERY - 1).unwrap();
+    let next_page = client.get_snapshots_between(&(last.timestamp + 1), &u64::MAX);
+    assert_eq!(next_page.len(), 10);
+    assert_eq!(next_page.get(0).unwrap().epoch, last.epoch + 1);
+}
+
+#[test]
+fn test_get_snapshot_page() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(client.get_snapshot_count(), 0);
+    assert_eq!(client.get_snapshot_page(&0, &10).len(), 0);
+
+    for epoch in [2u64, 4, 6, 8, 10] {
+        client.submit_snapshot(&epoch, &create_test_hash(&env, epoch as u8), &admin);
+    }
+    assert_eq!(client.get_snapshot_count(), 5);
+
+    let first = client.get_snapshot_page(&0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().epoch, 2);
+    assert_eq!(first.get(1).unwrap().epoch, 4);
+
+    // Resume after the last returned epoch; gaps between epochs are skipped
+    let second = client.get_snapshot_page(&5, &2);
+    assert_eq!(second.get(0).unwrap().epoch, 6);
+    assert_eq!(second.get(1).unwrap().epoch, 8);
+
+    let last = client.get_snapshot_page(&9, &2);
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.get(0).unwrap().epoch, 10);
+
+    assert_eq!(client.get_snapshot_page(&11, &2).len(), 0);
+    assert_eq!(client.get_snapshot_page(&0, &0).len(), 0);
+}
+
+#[test]
+fn test_get_snapshot_page_is_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    let num_epochs = MAX_SNAPSHOTS_PER_QUERY as u64 + 5;
+    for epoch in 1..=num_epochs {
+        client.submit_snapshot(&epoch, &create_test_hash(&env, (epoch % 255) as u8), &admin);
+    }
+
+    assert_eq!(client.get_snapshot_count(), num_epochs as u32);
+    assert_eq!(
+        client.get_snapshot_page(&0, &u32::MAX).len(),
+        MAX_SNAPSHOTS_PER_QUERY
+    );
+}
+
+#[test]
+fn test_get_recent_snapshots() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(client.get_recent_snapshots(&5).len(), 0);
+
+    for epoch in [2u64, 4, 6, 8, 10] {
+        client.submit_snapshot(&epoch, &create_test_hash(&env, epoch as u8), &admin);
+    }
+
+    // Newest first
+    let recent = client.get_recent_snapshots(&3);
+    assert_eq!(recent.len(), 3);
+    assert_eq!(recent.get(0).unwrap().epoch, 10);
+    assert_eq!(recent.get(1).unwrap().epoch, 8);
+    assert_eq!(recent.get(2).unwrap().epoch, 6);
+    assert_eq!(recent.get(0), client.get_latest_snapshot());
+
+    assert_eq!(client.get_recent_snapshots(&10).len(), 5);
+    assert_eq!(client.get_recent_snapshots(&0).len(), 0);
+
+    for epoch in 11..=MAX_SNAPSHOTS_PER_QUERY as u64 + 10 {
+        client.submit_snapshot(&epoch, &create_test_hash(&env, (epoch % 255) as u8), &admin);
+    }
+    assert_eq!(
+        client.get_recent_snapshots(&u32::MAX).len(),
+        MAX_SNAPSHOTS_PER_QUERY
+    );
+}
+
+#[test]
+fn test_retention_archives_oldest_snapshots() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize_with_retention(&admin, &3);
+    assert_eq!(client.get_max_history_entries(), Some(3));
+
+    for epoch in 1..=5u64 {
+        let hash = create_test_hash(&env, epoch as u8);
+        let sla_hash = create_test_hash(&env, 100 + epoch as u8);
+        client.submit_snapshot_with_sla(&epoch, &hash, &sla_hash, &admin);
+    }
+
+    // Only the newest entries stay in the history
+    assert_eq!(client.get_snapshot_count(), 3);
+    assert_eq!(client.get_all_epochs(), soroban_sdk::vec![&env, 3, 4, 5]);
+
+    // Archived snapshots are still readable by epoch
+    for epoch in 1..=5u64 {
+        let snapshot = client.get_snapshot(&epoch).unwrap();
+        assert_eq!(snapshot.hash, create_test_hash(&env, epoch as u8));
+        assert_eq!(
+            client.get_sla_hash(&epoch),
+            Some(create_test_hash(&env, 100 + epoch as u8))
+        );
+    }
+}
+
+#[test]
+fn test_unlimited_history_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(client.get_max_history_entries(), None);
+}
+
+#[test]
+#[should_panic(expected = "Invalid retention")]
+fn test_retention_must_be_positive() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize_with_retention(&admin, &0);
+}
+
+#[test]
+fn test_prune_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize_with_retention(&admin, &3);
+    for epoch in 1..=6u64 {
+        let hash = create_test_hash(&env, epoch as u8);
+        let sla_hash = create_test_hash(&env, 100 + epoch as u8);
+        client.submit_snapshot_with_sla(&epoch, &hash, &sla_hash, &admin);
+    }
+
+    // Epochs 1-3 are archived, 4-6 in the history
+    assert_eq!(client.prune_history(&2, &admin), 1);
+    assert_eq!(client.get_snapshot(&1), None);
+    assert_eq!(client.get_sla_hash(&1), None);
+    assert!(client.get_snapshot(&2).is_some());
+
+    // Crosses from the archive into the history
+    assert_eq!(client.prune_history(&5, &admin), 3);
+    for epoch in 1..5u64 {
+        assert_eq!(client.get_snapshot(&epoch), None);
+        assert_eq!(client.get_sla_hash(&epoch), None);
+    }
+    assert_eq!(client.get_all_epochs(), soroban_sdk::vec![&env, 5, 6]);
+
+    // Nothing left to prune
+    assert_eq!(client.prune_history(&5, &admin), 0);
+
+    // Archiving continues after the archive was emptied
+    for epoch in 7..=8u64 {
+        client.submit_snapshot(&epoch, &create_test_hash(&env, epoch as u8), &admin);
+    }
+    assert_eq!(client.get_all_epochs(), soroban_sdk::vec![&env, 6, 7, 8]);
+    assert!(client.get_snapshot(&5).is_some());
+    assert_eq!(client.prune_history(&7, &admin), 2);
+    assert_eq!(client.get_snapshot(&5), None);
+    assert_eq!(client.get_all_epochs(), soroban_sdk::vec![&env, 7, 8]);
+}
+
+#[test]
+#[should_panic(expected = "Cannot prune the latest snapshot")]
+fn test_prune_keeps_latest_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    client.prune_history(&2, &admin);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_prune_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    client.prune_history(&1, &other);
+}
+
+// ============================================================================
+// Storage Rent Tests
+// ============================================================================
+
+/// Keep the contract instance alive while tests move far ahead in ledgers
+fn extend_instance(env: &Env, contract_id: &Address) {
+    env.as_contract(contract_id, || {
+        env.storage().instance().extend_ttl(1_000_000, 1_000_000);
+    });
+}
+
+fn snapshot_ttl(env: &Env, contract_id: &Address, key: DataKey) -> u32 {
+    env.as_contract(contract_id, || env.storage().persistent().get_ttl(&key))
+}
+
+#[test]
+fn test_extend_snapshot_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize_with_retention(&admin, &1);
+    for epoch in 1..=2u64 {
+        let hash = create_test_hash(&env, epoch as u8);
+        let sla_hash = create_test_hash(&env, 100 + epoch as u8);
+        client.submit_snapshot_with_sla(&epoch, &hash, &sla_hash, &admin);
+    }
+
+    client.extend_snapshot_ttl(&2, &1_000_000);
+    assert_eq!(
+        snapshot_ttl(&env, &contract_id, DataKey::Snapshot(2)),
+        1_000_000
+    );
+    assert_eq!(
+        snapshot_ttl(&env, &contract_id, DataKey::SlaHash(2)),
+        1_000_000
+    );
+    // Epoch 2 is the second ever added to the history index
+    assert_eq!(
+        snapshot_ttl(&env, &contract_id, DataKey::HistoryEpoch(1)),
+        1_000_000
+    );
+
+    // Archived snapshots can be kept alive too
+    client.extend_snapshot_ttl(&1, &1_000_000);
+    assert_eq!(
+        snapshot_ttl(&env, &contract_id, DataKey::Archived(1)),
+        1_000_000
+    );
+    assert_eq!(
+        snapshot_ttl(&env, &contract_id, DataKey::ArchivedSla(1)),
+        1_000_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Snapshot not found")]
+fn test_extend_snapshot_ttl_requires_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.extend_snapshot_ttl(&1, &1_000);
+}
+
+#[test]
+fn test_reading_recent_epochs_extends_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    let epochs = AUTO_EXTEND_RECENT_EPOCHS as u64 + 1;
+    for epoch in 1..=epochs {
+        client.submit_snapshot(&epoch, &create_test_hash(&env, epoch as u8), &admin);
+    }
+
+    // Move past the extension threshold
+    extend_instance(&env, &contract_id);
+    let elapsed = SNAPSHOT_TTL_LEDGERS - SNAPSHOT_TTL_THRESHOLD + 1;
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += elapsed);
+    let remaining = SNAPSHOT_TTL_THRESHOLD - 1;
+
+    assert!(client.get_snapshot(&epochs).is_some());
+    assert_eq!(
+        snapshot_ttl(&env, &contract_id, DataKey::Snapshot(epochs)),
+        SNAPSHOT_TTL_LEDGERS
+    );
+
+    // Epoch 1 is not among the newest epochs
+    assert!(client.get_snapshot(&1).is_some());
+    assert_eq!(
+        snapshot_ttl(&env, &contract_id, DataKey::Snapshot(1)),
+        remaining
+    );
+}
+
+// ============================================================================
+// Storage Migration Tests
+// ============================================================================
+
+/// Lay out storage the way a version 1 deployment left it
+fn install_v1_history(env: &Env, contract_id: &Address, admin: &Address, epochs: u64) {
+    env.as_contract(contract_id, || {
+        let mut snapshots = Map::<u64, SnapshotMetadataV1>::new(env);
+        let mut sla_hashes = Map::<u64, BytesN<32>>::new(env);
+        for epoch in 1..=epochs {
+            snapshots.set(
+                epoch,
+                SnapshotMetadataV1 {
+                    epoch,
+                    timestamp: 1_000 * epoch,
+                    hash: create_test_hash(env, epoch as u8),
+                },
+            );
+            if epoch % 2 == 0 {
+                sla_hashes.set(epoch, create_test_hash(env, 100 + epoch as u8));
+            }
+        }
+
+        let instance = env.storage().instance();
+        instance.set(&DataKey::Admin, admin);
+        instance.set(&DataKey::LatestEpoch, &epochs);
+        instance.set(&DataKey::Paused, &false);
+        let persistent = env.storage().persistent();
+        persistent.set(&DataKey::Snapshots, &snapshots);
+        persistent.set(&DataKey::SlaHashes, &sla_hashes);
+    });
+}
+
+#[test]
+fn test_new_deployments_use_current_storage_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+}
+
+#[test]
+fn test_migrate_v1_to_v2_in_batches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    install_v1_history(&env, &contract_id, &admin, 5);
+    assert_eq!(client.get_storage_version(), 1);
+
+    assert_eq!(client.migrate_v1_to_v2(&admin, &2), 3);
+    assert_eq!(client.get_storage_version(), 1);
+    assert_eq!(client.get_all_epochs(), soroban_sdk::vec![&env, 1, 2]);
+
+    assert_eq!(client.migrate_v1_to_v2(&admin, &10), 0);
+    assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+    assert_eq!(client.get_snapshot_count(), 5);
+    for epoch in 1..=5u64 {
+        let snapshot = client.get_snapshot(&epoch).unwrap();
+        assert_eq!(snapshot.timestamp, 1_000 * epoch);
+        assert_eq!(snapshot.hash, create_test_hash(&env, epoch as u8));
+        assert_eq!(snapshot.submitter, admin);
+        let sla_hash = (epoch % 2 == 0).then(|| create_test_hash(&env, 100 + epoch as u8));
+        assert_eq!(client.get_sla_hash(&epoch), sla_hash);
+    }
+    assert_eq!(client.get_snapshots_between(&2_000, &3_000).len(), 2);
+
+    // Legacy maps are gone and each entry is kept alive
+    env.as_contract(&contract_id, || {
+        let persistent = env.storage().persistent();
+        assert!(!persistent.has(&DataKey::Snapshots));
+        assert!(!persistent.has(&DataKey::SlaHashes));
+        assert_eq!(
+            persistent.get_ttl(&DataKey::Snapshot(1)),
+            SNAPSHOT_TTL_LEDGERS
+        );
+        // Each epoch has its own index entry
+        assert_eq!(persistent.get(&DataKey::HistoryEpoch(4)), Some(5u64));
+    });
+
+    // Submissions continue from the migrated history
+    client.submit_snapshot(&6, &create_test_hash(&env, 6), &admin);
+    assert_eq!(
+        client.get_all_epochs(),
+        soroban_sdk::vec![&env, 1, 2, 3, 4, 5, 6]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Storage migration required")]
+fn test_submit_requires_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    install_v1_history(&env, &contract_id, &admin, 2);
+    client.submit_snapshot(&3, &create_test_hash(&env, 3), &admin);
+}
+
+#[test]
+#[should_panic(expected = "Storage already migrated")]
+fn test_migrate_rejects_current_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.migrate_v1_to_v2(&admin, &10);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_migrate_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    install_v1_history(&env, &contract_id, &admin, 2);
+    client.migrate_v1_to_v2(&other, &10);
+}
+
 // ============================================================================
 // Access Control Tests - Tests for Issue #41
 // ============================================================================
@@ -377,6 +1010,41 @@ fn test_set_admin_by_authorized_admin() {
     assert_eq!(client.get_latest_epoch(), epoch);
 }
 
+#[test]
+fn test_admin_changes_emit_transfer_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_admin(&admin, &new_admin);
+    client.set_governance(&new_admin, &governance);
+    client.set_admin_by_governance(&governance, &admin);
+
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                contract_id.clone(),
+                (events::ADMIN_TRANSFERRED,).into_val(&env),
+                (admin.clone(), new_admin.clone()).into_val(&env),
+            ),
+            (
+                contract_id,
+                (events::ADMIN_TRANSFERRED,).into_val(&env),
+                (new_admin, admin).into_val(&env),
+            ),
+        ]
+    );
+}
+
 #[test]
 #[should_panic(expected = "Unauthorized")]
 fn test_set_admin_by_unauthorized_user_fails() {
@@ -418,3 +1086,117 @@ fn test_old_admin_cannot_submit_after_transfer() {
     let hash = create_test_hash(&env, 1);
     client.submit_snapshot(&epoch, &hash, &admin);
 }
+
+#[test]
+fn test_registered_submitters_can_submit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle_a = Address::generate(&env);
+    let oracle_b = Address::generate(&env);
+    client.initialize(&admin);
+    assert_eq!(client.list_submitters(), soroban_sdk::vec![&env]);
+
+    client.add_submitter(&admin, &oracle_a);
+    client.add_submitter(&admin, &oracle_b);
+    assert_eq!(
+        client.list_submitters(),
+        soroban_sdk::vec![&env, oracle_a.clone(), oracle_b.clone()]
+    );
+
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &oracle_a);
+    client.submit_snapshot(&2, &create_test_hash(&env, 2), &oracle_b);
+    client.submit_snapshot(&3, &create_test_hash(&env, 3), &admin);
+    assert_eq!(client.get_snapshot(&1).unwrap().submitter, oracle_a);
+    assert_eq!(client.get_snapshot(&2).unwrap().submitter, oracle_b);
+
+    client.remove_submitter(&admin, &oracle_a);
+    assert_eq!(client.list_submitters(), soroban_sdk::vec![&env, oracle_b]);
+}
+
+#[test]
+fn test_submitter_changes_emit_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_submitter(&admin, &oracle);
+    client.remove_submitter(&admin, &oracle);
+
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                contract_id.clone(),
+                (events::SUBMITTER_ADDED,).into_val(&env),
+                (admin.clone(), oracle.clone()).into_val(&env),
+            ),
+            (
+                contract_id,
+                (events::SUBMITTER_REMOVED,).into_val(&env),
+                (admin, oracle).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_removed_submitter_cannot_submit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_submitter(&admin, &oracle);
+    client.remove_submitter(&admin, &oracle);
+
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &oracle);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: only the admin can manage submitters")]
+fn test_submitters_cannot_manage_submitters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_submitter(&admin, &oracle);
+
+    client.add_submitter(&oracle, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Submitter already registered")]
+fn test_add_submitter_rejects_duplicates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_submitter(&admin, &oracle);
+    client.add_submitter(&admin, &oracle);
+}
@@ -0,0 +1,181 @@
+//! Runs the backend's contract bindings against the contract itself, so a
+//! renamed function, reordered argument or changed return type fails here
+//! rather than on-chain.
+
+use analytics::AnalyticsContract;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    xdr::{ScAddress, ScVal},
+    Address, Bytes, Env, Symbol, TryFromVal, Val, Vec,
+};
+use stellar_insights_contract_bindings::{
+    analytics::AnalyticsContract as Bindings, Call, FromScVal,
+};
+
+/// Invoke `call` the way Soroban RPC would and decode its result
+fn invoke<T: FromScVal>(env: &Env, call: &Call<T>) -> T {
+    let contract =
+        Address::try_from_val(env, &ScVal::Address(call.args.contract_address.clone())).unwrap();
+    let mut args = Vec::new(env);
+    for arg in call.args.args.iter() {
+        args.push_back(Val::try_from_val(env, arg).unwrap());
+    }
+    let function = Symbol::new(env, call.function());
+    let result: Val = env.invoke_contract(&contract, &function, args);
+    call.decode(ScVal::try_from_val(env, &result).unwrap())
+        .unwrap()
+}
+
+fn setup() -> (Env, Bindings, ScAddress) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let bindings = Bindings::from_address(ScAddress::from(&contract_id));
+    let admin = ScAddress::from(&Address::generate(&env));
+    invoke(&env, &bindings.initialize(&admin));
+    (env, bindings, admin)
+}
+
+#[test]
+fn test_snapshot_calls_round_trip() {
+    let (env, contract, admin) = setup();
+    env.ledger().set_timestamp(1_000);
+
+    let timestamp = invoke(&env, &contract.submit_snapshot(1, [7u8; 32], &admin));
+    assert_eq!(timestamp, 1_000);
+
+    let snapshot = invoke(&env, &contract.get_snapshot(1)).unwrap();
+    assert_eq!(snapshot.epoch, 1);
+    assert_eq!(snapshot.timestamp, 1_000);
+    assert!(snapshot.matches(&[7u8; 32]));
+    assert_eq!(snapshot.submitter, admin);
+
+    assert_eq!(invoke(&env, &contract.get_snapshot(2)), None);
+    assert_eq!(
+        invoke(&env, &contract.get_latest_snapshot()),
+        Some(snapshot.clone())
+    );
+    assert_eq!(invoke(&env, &contract.get_latest_epoch()), 1);
+    assert_eq!(invoke(&env, &contract.get_all_epochs()), vec![1]);
+    assert_eq!(invoke(&env, &contract.get_storage_version()), 2);
+    invoke(&env, &contract.extend_snapshot_ttl(1, 100_000));
+    assert_eq!(invoke(&env, &contract.get_snapshot_history())[&1], snapshot);
+    assert_eq!(
+        invoke(&env, &contract.get_snapshots_between(0, 2_000)),
+        vec![snapshot.clone()]
+    );
+    assert_eq!(
+        invoke(&env, &contract.get_snapshot_page(0, 10)),
+        vec![snapshot.clone()]
+    );
+    assert_eq!(
+        invoke(&env, &contract.get_recent_snapshots(10)),
+        vec![snapshot]
+    );
+    assert_eq!(invoke(&env, &contract.get_snapshot_count()), 1);
+}
+
+#[test]
+fn test_sla_calls_round_trip() {
+    let (env, contract, admin) = setup();
+    env.ledger().set_timestamp(2_000);
+
+    let timestamp = invoke(
+        &env,
+        &contract.submit_snapshot_with_sla(1, [7u8; 32], [9u8; 32], &admin),
+    );
+    assert_eq!(timestamp, 2_000);
+    assert!(invoke(&env, &contract.get_snapshot(1))
+        .unwrap()
+        .matches(&[7u8; 32]));
+    assert_eq!(invoke(&env, &contract.get_sla_hash(1)), Some([9u8; 32]));
+    assert_eq!(invoke(&env, &contract.get_sla_hash(2)), None);
+}
+
+#[test]
+fn test_verify_snapshot_round_trips() {
+    let (env, contract, admin) = setup();
+    let payload = br#"{"epoch":1}"#;
+    let hash = env
+        .crypto()
+        .sha256(&Bytes::from_slice(&env, payload))
+        .to_array();
+
+    invoke(&env, &contract.submit_snapshot(1, hash, &admin));
+    assert!(invoke(&env, &contract.verify_snapshot(1, payload)));
+    assert!(!invoke(&env, &contract.verify_snapshot(1, b"{}")));
+}
+
+#[test]
+fn test_admin_calls_round_trip() {
+    let (env, contract, admin) = setup();
+    assert_eq!(invoke(&env, &contract.get_admin()), Some(admin.clone()));
+
+    invoke(&env, &contract.pause(&admin));
+    assert!(invoke(&env, &contract.is_paused()));
+    invoke(&env, &contract.unpause(&admin));
+    assert!(!invoke(&env, &contract.is_paused()));
+
+    assert_eq!(invoke(&env, &contract.get_governance()), None);
+    let governance = ScAddress::from(&Address::generate(&env));
+    invoke(&env, &contract.set_governance(&admin, &governance));
+    invoke(&env, &contract.set_paused_by_governance(&governance, true));
+    assert!(invoke(&env, &contract.is_paused()));
+
+    let new_admin = ScAddress::from(&Address::generate(&env));
+    invoke(
+        &env,
+        &contract.set_admin_by_governance(&governance, &new_admin),
+    );
+    invoke(&env, &contract.set_admin(&new_admin, &admin));
+    assert_eq!(invoke(&env, &contract.get_admin()), Some(admin));
+}
+
+#[test]
+fn test_retention_calls_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let contract = Bindings::from_address(ScAddress::from(&contract_id));
+    let admin = ScAddress::from(&Address::generate(&env));
+    invoke(&env, &contract.initialize_with_retention(&admin, 2));
+    assert_eq!(invoke(&env, &contract.get_max_history_entries()), Some(2));
+
+    for epoch in 1..=3 {
+        invoke(
+            &env,
+            &contract.submit_snapshot(epoch, [epoch as u8; 32], &admin),
+        );
+    }
+    assert_eq!(invoke(&env, &contract.get_all_epochs()), vec![2, 3]);
+    assert!(invoke(&env, &contract.get_snapshot(1)).is_some());
+
+    assert_eq!(invoke(&env, &contract.prune_history(3, &admin)), 2);
+    assert_eq!(invoke(&env, &contract.get_snapshot(1)), None);
+    assert_eq!(invoke(&env, &contract.get_all_epochs()), vec![3]);
+}
+
+#[test]
+fn test_submitter_calls_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let contract = Bindings::from_address(ScAddress::from(&contract_id));
+    let admin = ScAddress::from(&Address::generate(&env));
+    let oracle = ScAddress::from(&Address::generate(&env));
+    invoke(&env, &contract.initialize(&admin));
+
+    invoke(&env, &contract.add_submitter(&admin, &oracle));
+    assert_eq!(
+        invoke(&env, &contract.list_submitters()),
+        vec![oracle.clone()]
+    );
+    invoke(&env, &contract.submit_snapshot(1, [1; 32], &oracle));
+    assert_eq!(
+        invoke(&env, &contract.get_snapshot(1)).unwrap().submitter,
+        oracle
+    );
+
+    invoke(&env, &contract.remove_submitter(&admin, &oracle));
+    assert!(invoke(&env, &contract.list_submitters()).is_empty());
+}
@@ -19,15 +19,24 @@ export class Sep24Error extends Error {
   }
 }
 
+/**
+ * The anchor's SEP-10 token is sent in `X-Anchor-Authorization`, never in the
+ * URL; `Authorization` carries the user's platform session.
+ */
 async function fetchSep24<T>(
   endpoint: string,
-  options: RequestInit = {}
+  options: RequestInit = {},
+  anchorToken?: string
 ): Promise<T> {
   const url = endpoint.startsWith("http") ? endpoint : `${API_BASE}${endpoint}`;
+  const accessToken =
+    typeof window !== "undefined" ? localStorage.getItem("access_token") : null;
   const res = await fetch(url, {
     ...options,
     headers: {
       "Content-Type": "application/json",
+      ...(accessToken && { Authorization: `Bearer ${accessToken}` }),
+      ...(anchorToken && { "X-Anchor-Authorization": `Bearer ${anchorToken}` }),
       ...(options.headers as Record<string, string>),
     },
   });
@@ -138,10 +147,15 @@ export interface DepositInteractiveParams {
 export async function startDepositInteractive(
   params: DepositInteractiveParams
 ): Promise<Sep24InteractiveResponse> {
-  return fetchSep24<Sep24InteractiveResponse>("/api/sep24/deposit/interactive", {
-    method: "POST",
-    body: JSON.stringify(params),
-  });
+  const { jwt, ...body } = params;
+  return fetchSep24<Sep24InteractiveResponse>(
+    "/api/sep24/deposit/interactive",
+    {
+      method: "POST",
+      body: JSON.stringify(body),
+    },
+    jwt
+  );
 }
 
 export interface WithdrawInteractiveParams {
@@ -163,12 +177,14 @@ export interface WithdrawInteractiveParams {
 export async function startWithdrawInteractive(
   params: WithdrawInteractiveParams
 ): Promise<Sep24InteractiveResponse> {
+  const { jwt, ...body } = params;
   return fetchSep24<Sep24InteractiveResponse>(
     "/api/sep24/withdraw/interactive",
     {
       method: "POST",
-      body: JSON.stringify(params),
-    }
+      body: JSON.stringify(body),
+    },
+    jwt
   );
 }
 
@@ -189,13 +205,14 @@ export async function getSep24Transactions(
 ): Promise<Sep24TransactionsResponse> {
   const search = new URLSearchParams();
   search.set("transfer_server", params.transfer_server);
-  if (params.jwt) search.set("jwt", params.jwt);
   if (params.asset_code) search.set("asset_code", params.asset_code);
   if (params.kind) search.set("kind", params.kind);
   if (params.limit != null) search.set("limit", String(params.limit));
   if (params.cursor) search.set("cursor", params.cursor);
   return fetchSep24<Sep24TransactionsResponse>(
-    `/api/sep24/transactions?${search}`
+    `/api/sep24/transactions?${search}`,
+    {},
+    params.jwt
   );
 }
 
@@ -211,8 +228,9 @@ export async function getSep24Transaction(
     transfer_server: transferServer,
     id,
   });
-  if (jwt) params.set("jwt", jwt);
   return fetchSep24<{ transaction: Sep24Transaction }>(
-    `/api/sep24/transaction?${params}`
+    `/api/sep24/transaction?${params}`,
+    {},
+    jwt
   );
 }
@@ -19,15 +19,24 @@ export class Sep31Error extends Error {
   }
 }
 
+/**
+ * The anchor's SEP-10 token is sent in `X-Anchor-Authorization`, never in the
+ * URL; `Authorization` carries the user's platform session.
+ */
 async function fetchSep31<T>(
   endpoint: string,
-  options: RequestInit = {}
+  options: RequestInit = {},
+  anchorToken?: string
 ): Promise<T> {
   const url = endpoint.startsWith("http") ? endpoint : `${API_BASE}${endpoint}`;
+  const accessToken =
+    typeof window !== "undefined" ? localStorage.getItem("access_token") : null;
   const res = await fetch(url, {
     ...options,
     headers: {
       "Content-Type": "application/json",
+      ...(accessToken && { Authorization: `Bearer ${accessToken}` }),
+      ...(anchorToken && { "X-Anchor-Authorization": `Bearer ${anchorToken}` }),
       ...(options.headers as Record<string, string>),
     },
   });
@@ -143,14 +152,17 @@ export async function getSep31Quote(
   params: Sep31QuoteParams
 ): Promise<Sep31QuoteResponse> {
   const { transfer_server, jwt, ...payload } = params;
-  return fetchSep31<Sep31QuoteResponse>("/api/sep31/quote", {
-    method: "POST",
-    body: JSON.stringify({
-      transfer_server,
-      jwt,
-      payload: payload as Record<string, unknown>,
-    }),
-  });
+  return fetchSep31<Sep31QuoteResponse>(
+    "/api/sep31/quote",
+    {
+      method: "POST",
+      body: JSON.stringify({
+        transfer_server,
+        payload: payload as Record<string, unknown>,
+      }),
+    },
+    jwt
+  );
 }
 
 export async function createSep31Payment(
@@ -163,10 +175,10 @@ export async function createSep31Payment(
       method: "POST",
       body: JSON.stringify({
         transfer_server,
-        jwt,
         payload: payload as Record<string, unknown>,
       }),
-    }
+    },
+    jwt
   );
 }
 
@@ -183,12 +195,13 @@ export async function getSep31Transactions(
 ): Promise<Sep31TransactionsResponse> {
   const search = new URLSearchParams();
   search.set("transfer_server", params.transfer_server);
-  if (params.jwt) search.set("jwt", params.jwt);
   if (params.status) search.set("status", params.status);
   if (params.limit != null) search.set("limit", String(params.limit));
   if (params.cursor) search.set("cursor", params.cursor);
   return fetchSep31<Sep31TransactionsResponse>(
-    `/api/sep31/transactions?${search}`
+    `/api/sep31/transactions?${search}`,
+    {},
+    params.jwt
   );
 }
 
@@ -198,9 +211,10 @@ export async function getSep31Transaction(
   jwt?: string
 ): Promise<{ transaction: Sep31Transaction }> {
   const params = new URLSearchParams({ transfer_server: transferServer });
-  if (jwt) params.set("jwt", jwt);
   return fetchSep31<{ transaction: Sep31Transaction }>(
-    `/api/sep31/transactions/${encodeURIComponent(id)}?${params}`
+    `/api/sep31/transactions/${encodeURIComponent(id)}?${params}`,
+    {},
+    jwt
   );
 }
 
@@ -213,6 +227,5 @@ export async function getSep31Customer(
     transfer_server: transferServer,
     id: customerId,
   });
-  if (jwt) params.set("jwt", jwt);
-  return fetchSep31(`/api/sep31/customer?${params}`);
+  return fetchSep31(`/api/sep31/customer?${params}`, {}, jwt);
 }